@@ -21,6 +21,7 @@ fn main() {
         samples: vec![1; 96],
         sample_rate: 48_000,
         channels: 2,
+        metadata: Default::default(),
     };
 
     let iterations = 10_000;
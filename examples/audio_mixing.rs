@@ -25,12 +25,16 @@ fn main() -> anyhow::Result<()> {
                 source: "producer:sine_left".to_string(),
                 gain: 0.6,
                 enabled: Some(true),
+                mute: None,
+                solo: None,
             },
             MixerInputConfig {
                 name: "tone_right".to_string(),
                 source: "producer:sine_right".to_string(),
                 gain: 0.4,
                 enabled: Some(true),
+                mute: None,
+                solo: None,
             },
         ],
         output_sample_rate: Some(48_000),
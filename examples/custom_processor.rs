@@ -46,6 +46,7 @@ impl Processor for ScaleProcessor {
             processing_rate_hz: 0.0,
             latency_ms: 0.0,
             errors: 0,
+            metrics: None,
         }
     }
 
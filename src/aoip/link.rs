@@ -0,0 +1,761 @@
+// src/aoip/link.rs
+//
+// `airlift_link`: Node-zu-Node-Transport über TCP. Der Sender (Consumer)
+// schickt nach einem kurzen Hello kodierte Frames mit ihrem Original-`utc_ns`,
+// der Empfänger (Producer) übernimmt den Zeitstempel unverändert – so bleibt
+// die Zeitachse über mehrere Nodes hinweg gemeinsam.
+//
+// Ab Version 2 wird der Codec ausgehandelt: Das Hello trägt die Codecs, die
+// der Sender in diesem Build kodieren kann, in der Reihenfolge seiner Policy
+// (LAN: verlustfrei zuerst, WAN: Opus zuerst) und seine Bitrate-Obergrenze.
+// Der Empfänger wählt den ersten Codec, den er dekodieren kann und den seine
+// Policy zulässt, und antwortet mit Codec und Bitrate oder einer Ablehnung.
+// Sender der Version 1 werden weiter angenommen und senden PCM.
+//
+// Hello:   "ALNK" | Version (u8) | Stream-Name (u16 Länge + UTF-8)
+//          ab Version 2: | Netz (u8, 0 = LAN, 1 = WAN) | Bitrate (u32, 0 = offen)
+//          | Anzahl (u8) | Codec-IDs (u8 …)
+// Antwort: "ALNK" | Status (u8, 0 = angenommen) | Codec (u8) | Bitrate (u32)
+//          | Grund (u16 Länge + UTF-8, leer wenn angenommen)
+// Frame:   Länge (u32, ab hier) | utc_ns (u64) | Codec (u8) | Container (u8)
+//          | Sample-Rate (u32) | Kanäle (u8) | Payload
+// Alle Zahlen Big Endian; PCM-Payload ist s16le wie im PCM-Codec, Opus läuft
+// als fortlaufender Ogg-Strom über die Frames.
+use std::collections::HashMap;
+use std::io::{self, Read, Write};
+use std::net::IpAddr;
+
+use anyhow::{anyhow, bail};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::codecs::{bitrate_range, CodecInfo, CodecKind, ContainerKind, EncodedFrame};
+use crate::config::ConfigValues;
+use crate::ring::{EncodedFramePacket, PcmFrame};
+
+pub const LINK_MAGIC: &[u8; 4] = b"ALNK";
+pub const LINK_VERSION: u8 = 2;
+/// Sender dieser Version kennen keine Aushandlung und senden PCM.
+pub const LINK_VERSION_LEGACY: u8 = 1;
+/// Opus-Bitrate, wenn keine Seite eine Obergrenze setzt
+pub const DEFAULT_LINK_OPUS_BITRATE: u32 = 128_000;
+pub const DEFAULT_LINK_PORT: u16 = 7700;
+/// Schutz gegen kaputte Längenfelder (1 s Stereo @ 192 kHz s16 passt)
+pub const MAX_LINK_PAYLOAD: usize = 1 << 20;
+const FRAME_HEADER_LEN: usize = 8 + 1 + 1 + 4 + 1;
+
+fn codec_id(kind: &CodecKind) -> u8 {
+    match kind {
+        CodecKind::Pcm => 0,
+        CodecKind::OpusOgg => 1,
+        CodecKind::OpusWebRtc => 2,
+        CodecKind::Mp3 => 3,
+        CodecKind::Vorbis => 4,
+        CodecKind::AacLc => 5,
+        CodecKind::Flac => 6,
+    }
+}
+
+fn codec_from_id(id: u8) -> anyhow::Result<CodecKind> {
+    Ok(match id {
+        0 => CodecKind::Pcm,
+        1 => CodecKind::OpusOgg,
+        2 => CodecKind::OpusWebRtc,
+        3 => CodecKind::Mp3,
+        4 => CodecKind::Vorbis,
+        5 => CodecKind::AacLc,
+        6 => CodecKind::Flac,
+        other => bail!("unknown codec id {}", other),
+    })
+}
+
+fn container_id(container: &ContainerKind) -> u8 {
+    match container {
+        ContainerKind::Raw => 0,
+        ContainerKind::Ogg => 1,
+        ContainerKind::Mpeg => 2,
+        ContainerKind::Rtp => 3,
+    }
+}
+
+fn container_from_id(id: u8) -> anyhow::Result<ContainerKind> {
+    Ok(match id {
+        0 => ContainerKind::Raw,
+        1 => ContainerKind::Ogg,
+        2 => ContainerKind::Mpeg,
+        3 => ContainerKind::Rtp,
+        other => bail!("unknown container id {}", other),
+    })
+}
+
+/// Codecs, die `airlift_link` übertragen kann.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkCodec {
+    Pcm,
+    Flac,
+    Opus,
+}
+
+impl LinkCodec {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "pcm" => Some(Self::Pcm),
+            "flac" => Some(Self::Flac),
+            "opus" | "opusogg" => Some(Self::Opus),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pcm => "pcm",
+            Self::Flac => "flac",
+            Self::Opus => "opus",
+        }
+    }
+
+    pub fn is_lossless(self) -> bool {
+        !matches!(self, Self::Opus)
+    }
+
+    fn kind(self) -> CodecKind {
+        match self {
+            Self::Pcm => CodecKind::Pcm,
+            Self::Flac => CodecKind::Flac,
+            Self::Opus => CodecKind::OpusOgg,
+        }
+    }
+
+    fn from_wire(id: u8) -> anyhow::Result<Self> {
+        match codec_from_id(id)? {
+            CodecKind::Pcm => Ok(Self::Pcm),
+            CodecKind::Flac => Ok(Self::Flac),
+            CodecKind::OpusOgg => Ok(Self::Opus),
+            other => bail!("codec {:?} is not used on airlift_link", other),
+        }
+    }
+}
+
+/// Codecs, die dieser Build auf dem Link kodieren und dekodieren kann.
+pub fn local_codecs() -> Vec<LinkCodec> {
+    #[allow(unused_mut)]
+    let mut codecs = vec![LinkCodec::Pcm];
+    #[cfg(feature = "opus")]
+    codecs.push(LinkCodec::Opus);
+    codecs
+}
+
+fn codec_names(codecs: &[LinkCodec]) -> String {
+    if codecs.is_empty() {
+        return "none".to_string();
+    }
+    codecs
+        .iter()
+        .map(|codec| codec.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Netz zwischen den Nodes (`config.network`), bestimmt die Codec-Reihenfolge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkNetwork {
+    /// Anhand der Adresse der Gegenstelle
+    Auto,
+    Lan,
+    Wan,
+}
+
+impl LinkNetwork {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "auto" => Some(Self::Auto),
+            "lan" => Some(Self::Lan),
+            "wan" => Some(Self::Wan),
+            _ => None,
+        }
+    }
+
+    /// `Auto` wird zu LAN bei privaten, Loopback- und Link-Local-Adressen,
+    /// sonst zu WAN.
+    pub fn resolve(self, peer: Option<IpAddr>) -> Self {
+        match (self, peer) {
+            (Self::Auto, Some(ip)) if is_local(ip) => Self::Lan,
+            (Self::Auto, _) => Self::Wan,
+            (explicit, _) => explicit,
+        }
+    }
+
+    /// Codecs in bevorzugter Reihenfolge
+    pub fn preference(self) -> [LinkCodec; 3] {
+        match self {
+            Self::Wan => [LinkCodec::Opus, LinkCodec::Flac, LinkCodec::Pcm],
+            Self::Auto | Self::Lan => [LinkCodec::Flac, LinkCodec::Pcm, LinkCodec::Opus],
+        }
+    }
+}
+
+fn is_local(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => v4.is_private() || v4.is_loopback() || v4.is_link_local(),
+        IpAddr::V6(v6) => {
+            let first = v6.segments()[0];
+            v6.is_loopback()
+                // fc00::/7 (ULA) und fe80::/10
+                || first & 0xfe00 == 0xfc00
+                || first & 0xffc0 == 0xfe80
+                || v6.to_ipv4_mapped().is_some_and(|v4| is_local(IpAddr::V4(v4)))
+        }
+    }
+}
+
+/// Angebot des Senders im Hello
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinkOffer {
+    /// Aufgelöst, nie `Auto`
+    pub network: LinkNetwork,
+    pub bitrate: Option<u32>,
+    /// In Reihenfolge der Sender-Policy
+    pub codecs: Vec<LinkCodec>,
+}
+
+impl LinkOffer {
+    /// Was ein Sender der Version 1 implizit anbietet
+    pub fn legacy() -> Self {
+        Self {
+            network: LinkNetwork::Lan,
+            bitrate: None,
+            codecs: vec![LinkCodec::Pcm],
+        }
+    }
+}
+
+/// Ergebnis der Aushandlung
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LinkChoice {
+    pub codec: LinkCodec,
+    /// Nur bei verlustbehafteten Codecs
+    pub bitrate: Option<u32>,
+}
+
+impl std::fmt::Display for LinkChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.bitrate {
+            Some(bitrate) => write!(f, "{} @ {} kbit/s", self.codec.as_str(), bitrate / 1000),
+            None => f.write_str(self.codec.as_str()),
+        }
+    }
+}
+
+/// Codec-Policy eines Link-Endes: `network`, `codecs` (zugelassen) und
+/// `bitrate` (Obergrenze für Opus) aus der Modul-Config.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinkPolicy {
+    pub network: LinkNetwork,
+    /// Leer = alle Codecs des Builds
+    pub codecs: Vec<LinkCodec>,
+    pub bitrate: Option<u32>,
+}
+
+impl Default for LinkPolicy {
+    fn default() -> Self {
+        Self {
+            network: LinkNetwork::Auto,
+            codecs: Vec::new(),
+            bitrate: None,
+        }
+    }
+}
+
+/// Von `LinkPolicy` gelesene `config`-Schlüssel (Strict-Modus, siehe `config::options`)
+pub const POLICY_CONFIG_KEYS: &[&str] = &["network", "codecs", "bitrate"];
+
+impl LinkPolicy {
+    pub fn from_config(
+        module_kind: &str,
+        name: &str,
+        config: &HashMap<String, Value>,
+    ) -> anyhow::Result<Self> {
+        let values = ConfigValues::new(module_kind, name, config);
+        let network = match config.get("network") {
+            None => LinkNetwork::Auto,
+            Some(value) => value.as_str().and_then(LinkNetwork::parse).ok_or_else(|| {
+                anyhow!(
+                    "{} '{}': config.network must be \"auto\", \"lan\" or \"wan\", got {}",
+                    module_kind,
+                    name,
+                    value
+                )
+            })?,
+        };
+        let codecs = match config.get("codecs") {
+            None => Vec::new(),
+            Some(Value::Array(names)) => names
+                .iter()
+                .map(|value| {
+                    value.as_str().and_then(LinkCodec::parse).ok_or_else(|| {
+                        anyhow!(
+                            "{} '{}': config.codecs: unknown codec {} (pcm, flac, opus)",
+                            module_kind,
+                            name,
+                            value
+                        )
+                    })
+                })
+                .collect::<anyhow::Result<_>>()?,
+            Some(other) => bail!(
+                "{} '{}': config.codecs must be a list, got {}",
+                module_kind,
+                name,
+                other
+            ),
+        };
+        let bitrate = values.bitrate("bitrate")?;
+        if let (Some(bitrate), Some((min, max))) = (bitrate, bitrate_range(&CodecKind::OpusOgg)) {
+            values.check_range("bitrate", bitrate, min, max)?;
+        }
+
+        let policy = Self {
+            network,
+            codecs,
+            bitrate,
+        };
+        if policy.usable().is_empty() {
+            bail!(
+                "{} '{}': none of config.codecs is available in this build ({})",
+                module_kind,
+                name,
+                codec_names(&local_codecs())
+            );
+        }
+        Ok(policy)
+    }
+
+    /// Zugelassene Codecs, die dieser Build beherrscht
+    fn usable(&self) -> Vec<LinkCodec> {
+        local_codecs()
+            .into_iter()
+            .filter(|codec| self.codecs.is_empty() || self.codecs.contains(codec))
+            .collect()
+    }
+
+    /// Angebot des Senders an die Gegenstelle `peer`.
+    pub fn offer(&self, peer: Option<IpAddr>) -> LinkOffer {
+        let network = self.network.resolve(peer);
+        let usable = self.usable();
+        LinkOffer {
+            network,
+            bitrate: self.bitrate,
+            codecs: network
+                .preference()
+                .into_iter()
+                .filter(|codec| usable.contains(codec))
+                .collect(),
+        }
+    }
+
+    /// Wahl des Empfängers: erster gemeinsamer Codec in der Reihenfolge des
+    /// Senders, bei fest eingestelltem `network` in der eigenen. Die Bitrate
+    /// ist die kleinere der beiden Obergrenzen.
+    pub fn negotiate(&self, offer: &LinkOffer) -> anyhow::Result<LinkChoice> {
+        let usable = self.usable();
+        let mut candidates: Vec<LinkCodec> = offer
+            .codecs
+            .iter()
+            .copied()
+            .filter(|codec| usable.contains(codec))
+            .collect();
+        if self.network != LinkNetwork::Auto {
+            let preference = self.network.preference();
+            candidates.sort_by_key(|codec| preference.iter().position(|p| p == codec));
+        }
+        let Some(codec) = candidates.first().copied() else {
+            bail!(
+                "no common codec (offered: {}, accepted: {})",
+                codec_names(&offer.codecs),
+                codec_names(&usable)
+            );
+        };
+        let bitrate = (!codec.is_lossless()).then(|| {
+            [offer.bitrate, self.bitrate]
+                .into_iter()
+                .flatten()
+                .min()
+                .unwrap_or(DEFAULT_LINK_OPUS_BITRATE)
+        });
+        Ok(LinkChoice { codec, bitrate })
+    }
+}
+
+/// Gelesenes Hello eines Senders
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinkHello {
+    pub stream: String,
+    pub version: u8,
+    pub offer: LinkOffer,
+}
+
+impl LinkHello {
+    /// Version-1-Sender warten nicht auf eine Antwort.
+    pub fn expects_answer(&self) -> bool {
+        self.version >= 2
+    }
+}
+
+pub fn write_hello(writer: &mut impl Write, stream: &str, offer: &LinkOffer) -> io::Result<()> {
+    let name = stream.as_bytes();
+    let len = u16::try_from(name.len())
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "stream name too long"))?;
+    let mut hello = Vec::with_capacity(4 + 1 + 2 + name.len() + 6 + offer.codecs.len());
+    hello.extend_from_slice(LINK_MAGIC);
+    hello.push(LINK_VERSION);
+    hello.extend_from_slice(&len.to_be_bytes());
+    hello.extend_from_slice(name);
+    hello.push(match offer.network {
+        LinkNetwork::Wan => 1,
+        LinkNetwork::Auto | LinkNetwork::Lan => 0,
+    });
+    hello.extend_from_slice(&offer.bitrate.unwrap_or(0).to_be_bytes());
+    hello.push(offer.codecs.len() as u8);
+    hello.extend(offer.codecs.iter().map(|codec| codec_id(&codec.kind())));
+    writer.write_all(&hello)?;
+    writer.flush()
+}
+
+/// Liest das Hello eines Senders (Version 1 oder 2).
+pub fn read_hello(reader: &mut impl Read) -> anyhow::Result<LinkHello> {
+    let mut head = [0u8; 7];
+    reader.read_exact(&mut head)?;
+    if &head[..4] != LINK_MAGIC {
+        bail!("not an airlift_link peer");
+    }
+    let version = head[4];
+    if !(LINK_VERSION_LEGACY..=LINK_VERSION).contains(&version) {
+        bail!(
+            "unsupported airlift_link version {} (expected {})",
+            version,
+            LINK_VERSION
+        );
+    }
+    let mut name = vec![0u8; u16::from_be_bytes([head[5], head[6]]) as usize];
+    reader.read_exact(&mut name)?;
+    let stream = String::from_utf8(name).map_err(|_| anyhow!("stream name is not UTF-8"))?;
+    if version == LINK_VERSION_LEGACY {
+        return Ok(LinkHello {
+            stream,
+            version,
+            offer: LinkOffer::legacy(),
+        });
+    }
+
+    let mut fixed = [0u8; 6];
+    reader.read_exact(&mut fixed)?;
+    let bitrate = u32::from_be_bytes(fixed[1..5].try_into().expect("4 bytes"));
+    let mut ids = vec![0u8; fixed[5] as usize];
+    reader.read_exact(&mut ids)?;
+    Ok(LinkHello {
+        stream,
+        version,
+        offer: LinkOffer {
+            network: if fixed[0] == 1 {
+                LinkNetwork::Wan
+            } else {
+                LinkNetwork::Lan
+            },
+            bitrate: (bitrate > 0).then_some(bitrate),
+            // Unbekannte Codecs neuerer Sender überspringen
+            codecs: ids
+                .into_iter()
+                .filter_map(|id| LinkCodec::from_wire(id).ok())
+                .collect(),
+        },
+    })
+}
+
+/// Antwort des Empfängers: gewählter Codec oder Ablehnungsgrund.
+pub fn write_answer(writer: &mut impl Write, answer: Result<&LinkChoice, &str>) -> io::Result<()> {
+    let (status, codec, bitrate, reason) = match answer {
+        Ok(choice) => (
+            0u8,
+            codec_id(&choice.codec.kind()),
+            choice.bitrate.unwrap_or(0),
+            "",
+        ),
+        Err(reason) => (1u8, 0, 0, reason),
+    };
+    let reason = &reason.as_bytes()[..reason.len().min(u16::MAX as usize)];
+    let mut out = Vec::with_capacity(4 + 1 + 1 + 4 + 2 + reason.len());
+    out.extend_from_slice(LINK_MAGIC);
+    out.push(status);
+    out.push(codec);
+    out.extend_from_slice(&bitrate.to_be_bytes());
+    out.extend_from_slice(&(reason.len() as u16).to_be_bytes());
+    out.extend_from_slice(reason);
+    writer.write_all(&out)?;
+    writer.flush()
+}
+
+/// Liest die Antwort; eine Ablehnung wird zum Fehler mit dem Grund.
+pub fn read_answer(reader: &mut impl Read) -> anyhow::Result<LinkChoice> {
+    let mut head = [0u8; 12];
+    reader.read_exact(&mut head)?;
+    if &head[..4] != LINK_MAGIC {
+        bail!("not an airlift_link peer");
+    }
+    let mut reason = vec![0u8; u16::from_be_bytes([head[10], head[11]]) as usize];
+    reader.read_exact(&mut reason)?;
+    if head[4] != 0 {
+        bail!("rejected by receiver: {}", String::from_utf8_lossy(&reason));
+    }
+    let codec = LinkCodec::from_wire(head[5])?;
+    let bitrate = u32::from_be_bytes(head[6..10].try_into().expect("4 bytes"));
+    Ok(LinkChoice {
+        codec,
+        bitrate: (!codec.is_lossless() && bitrate > 0).then_some(bitrate),
+    })
+}
+
+pub fn write_packet(writer: &mut impl Write, packet: &EncodedFramePacket) -> io::Result<usize> {
+    let payload = &packet.frame.payload;
+    if payload.len() > MAX_LINK_PAYLOAD {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("payload of {} bytes exceeds {}", payload.len(), MAX_LINK_PAYLOAD),
+        ));
+    }
+    let info = &packet.frame.info;
+    let len = FRAME_HEADER_LEN + payload.len();
+    let mut out = Vec::with_capacity(4 + len);
+    out.extend_from_slice(&(len as u32).to_be_bytes());
+    out.extend_from_slice(&packet.utc_ns.to_be_bytes());
+    out.push(codec_id(&info.kind));
+    out.push(container_id(&info.container));
+    out.extend_from_slice(&info.sample_rate.to_be_bytes());
+    out.push(info.channels);
+    out.extend_from_slice(payload);
+    writer.write_all(&out)?;
+    Ok(out.len())
+}
+
+/// Nächstes Paket; `Ok(None)` bei sauberem Verbindungsende.
+pub fn read_packet(reader: &mut impl Read) -> anyhow::Result<Option<EncodedFramePacket>> {
+    let mut len = [0u8; 4];
+    match reader.read_exact(&mut len) {
+        Ok(()) => {}
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e.into()),
+    }
+    let len = u32::from_be_bytes(len) as usize;
+    if !(FRAME_HEADER_LEN..=FRAME_HEADER_LEN + MAX_LINK_PAYLOAD).contains(&len) {
+        bail!("invalid frame length {}", len);
+    }
+    let mut body = vec![0u8; len];
+    reader.read_exact(&mut body)?;
+
+    let utc_ns = u64::from_be_bytes(body[0..8].try_into().expect("8 bytes"));
+    let info = CodecInfo {
+        kind: codec_from_id(body[8])?,
+        container: container_from_id(body[9])?,
+        sample_rate: u32::from_be_bytes(body[10..14].try_into().expect("4 bytes")),
+        channels: body[14],
+    };
+    body.drain(..FRAME_HEADER_LEN);
+    Ok(Some(EncodedFramePacket {
+        utc_ns,
+        frame: EncodedFrame { payload: body, info },
+    }))
+}
+
+/// PCM-Frame als kodiertes Paket (s16le), Zeitstempel bleibt erhalten.
+pub fn encode_pcm(frame: &PcmFrame) -> EncodedFramePacket {
+    let mut payload = Vec::with_capacity(frame.samples.len() * 2);
+    for sample in &frame.samples {
+        payload.extend_from_slice(&sample.to_le_bytes());
+    }
+    EncodedFramePacket {
+        utc_ns: frame.utc_ns,
+        frame: EncodedFrame {
+            payload,
+            info: CodecInfo {
+                kind: CodecKind::Pcm,
+                sample_rate: frame.sample_rate,
+                channels: frame.channels,
+                container: ContainerKind::Raw,
+            },
+        },
+    }
+}
+
+/// Gegenstück zu `encode_pcm`; andere Codecs dekodiert `LinkDecoder`.
+pub fn decode_pcm(packet: &EncodedFramePacket) -> anyhow::Result<PcmFrame> {
+    let info = &packet.frame.info;
+    if !matches!(info.kind, CodecKind::Pcm) {
+        bail!("unsupported codec {:?} on airlift_link (only PCM)", info.kind);
+    }
+    if info.channels == 0 || info.sample_rate == 0 {
+        bail!("invalid format {} Hz / {} ch", info.sample_rate, info.channels);
+    }
+    let payload = &packet.frame.payload;
+    if !payload.len().is_multiple_of(2) {
+        bail!("invalid pcm payload length {}", payload.len());
+    }
+    Ok(PcmFrame {
+        utc_ns: packet.utc_ns,
+        samples: payload
+            .chunks_exact(2)
+            .map(|b| i16::from_le_bytes([b[0], b[1]]))
+            .collect(),
+        sample_rate: info.sample_rate,
+        channels: info.channels,
+        metadata: Default::default(),
+    })
+}
+
+/// Kodiert PCM-Frames im ausgehandelten Codec.
+pub struct LinkEncoder {
+    choice: LinkChoice,
+    #[cfg(feature = "opus")]
+    opus: Option<crate::codecs::opus::OpusOggEncoder>,
+}
+
+impl LinkEncoder {
+    pub fn new(choice: LinkChoice) -> Self {
+        Self {
+            choice,
+            #[cfg(feature = "opus")]
+            opus: None,
+        }
+    }
+
+    pub fn choice(&self) -> &LinkChoice {
+        &self.choice
+    }
+
+    /// Null oder mehr Pakete, alle mit dem `utc_ns` von `frame`.
+    pub fn encode(&mut self, frame: &PcmFrame) -> anyhow::Result<Vec<EncodedFramePacket>> {
+        match self.choice.codec {
+            LinkCodec::Pcm => Ok(vec![encode_pcm(frame)]),
+            #[cfg(feature = "opus")]
+            LinkCodec::Opus => self.encode_opus(frame),
+            other => bail!(
+                "no {} encoder for airlift_link in this build",
+                other.as_str()
+            ),
+        }
+    }
+
+    #[cfg(feature = "opus")]
+    fn encode_opus(&mut self, frame: &PcmFrame) -> anyhow::Result<Vec<EncodedFramePacket>> {
+        use crate::codecs::opus::{OpusOggEncoder, OPUS_SAMPLE_RATE};
+        use crate::codecs::AudioCodec;
+
+        // Opus nur für 48 kHz Mono/Stereo, alles andere geht weiter als PCM
+        if frame.sample_rate != OPUS_SAMPLE_RATE || frame.channels > 2 {
+            return Ok(vec![encode_pcm(frame)]);
+        }
+        // Formatwechsel: neuer Ogg-Stream mit eigenen Headern
+        if self
+            .opus
+            .as_ref()
+            .is_some_and(|encoder| encoder.info().channels != frame.channels)
+        {
+            self.opus = None;
+        }
+        let encoder = match &mut self.opus {
+            Some(encoder) => encoder,
+            None => {
+                let mut encoder = OpusOggEncoder::new(frame.channels)?;
+                if let Some(bitrate) = self.choice.bitrate {
+                    encoder.set_bitrate(bitrate)?;
+                }
+                self.opus.insert(encoder)
+            }
+        };
+        Ok(encoder
+            .encode(&frame.samples)?
+            .into_iter()
+            .map(|encoded| EncodedFramePacket {
+                utc_ns: frame.utc_ns,
+                frame: encoded,
+            })
+            .collect())
+    }
+}
+
+/// Dekodiert empfangene Pakete; der Codec steht in jedem Paket, ein Sender
+/// darf also zwischendurch auf PCM ausweichen.
+#[derive(Default)]
+pub struct LinkDecoder {
+    #[cfg(feature = "opus")]
+    opus: Option<LinkOpusDecoder>,
+}
+
+impl LinkDecoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `Ok(None)`, solange ein Paket nur Header oder Pre-Skip enthält.
+    pub fn decode(&mut self, packet: &EncodedFramePacket) -> anyhow::Result<Option<PcmFrame>> {
+        match &packet.frame.info.kind {
+            CodecKind::Pcm => decode_pcm(packet).map(Some),
+            #[cfg(feature = "opus")]
+            CodecKind::OpusOgg => self
+                .opus
+                .get_or_insert_with(LinkOpusDecoder::default)
+                .decode(packet),
+            other => bail!("unsupported codec {:?} on airlift_link", other),
+        }
+    }
+}
+
+#[cfg(feature = "opus")]
+#[derive(Default)]
+struct LinkOpusDecoder {
+    demuxer: crate::codecs::ogg_opus::OggOpusDemuxer,
+    decoder: Option<(u8, crate::decoders::opus::OpusDecoder)>,
+    /// Noch zu verwerfende Samples pro Kanal (Pre-Skip des Encoders)
+    skip: usize,
+}
+
+#[cfg(feature = "opus")]
+impl LinkOpusDecoder {
+    fn decode(&mut self, packet: &EncodedFramePacket) -> anyhow::Result<Option<PcmFrame>> {
+        use crate::decoders::opus::{OpusDecoder, OPUS_SAMPLE_RATE};
+        use crate::decoders::AudioDecoder;
+
+        let packets = self.demuxer.push(&packet.frame.payload)?;
+        let Some(channels) = self.demuxer.channels() else {
+            return Ok(None);
+        };
+        if self
+            .decoder
+            .as_ref()
+            .is_none_or(|(current, _)| *current != channels)
+        {
+            self.decoder = Some((channels, OpusDecoder::new(channels)?));
+            self.skip = self.demuxer.pre_skip() as usize;
+        }
+        let (_, decoder) = self.decoder.as_mut().expect("decoder created above");
+        let mut samples = Vec::new();
+        for opus in packets {
+            if let Some(frame) = decoder.decode(&opus)? {
+                samples.extend(frame.samples);
+            }
+        }
+        let skip = (self.skip * channels as usize).min(samples.len());
+        samples.drain(..skip);
+        self.skip -= skip / channels as usize;
+        if samples.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(PcmFrame {
+            utc_ns: packet.utc_ns,
+            samples,
+            sample_rate: OPUS_SAMPLE_RATE,
+            channels,
+            metadata: Default::default(),
+        }))
+    }
+}
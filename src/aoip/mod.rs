@@ -0,0 +1,12 @@
+// src/aoip/mod.rs
+//
+// AoIP-Hilfen: SDP-Erzeugung/-Parsing, SAP-Announcements/-Discovery und das
+// Node-zu-Node-Protokoll `airlift_link` sowie der MPEG-TS-Demuxer für
+// Audio-Zuspielungen per UDP.
+pub mod link;
+pub mod mpegts;
+pub mod sap;
+pub mod sdp;
+
+pub use sap::{sap_service, AoipSource, SapService};
+pub use sdp::{parse_sdp, Aes67Session, SdpSummary};
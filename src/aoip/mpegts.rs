@@ -0,0 +1,340 @@
+// src/aoip/mpegts.rs
+//
+// MPEG-TS-Demuxer für Audio: liest PAT und PMT, wählt die erste Audio-PID
+// (MPEG-1/2 Audio oder AAC in ADTS) und setzt deren PES-Pakete samt PTS
+// zusammen. UDP-Datagramme enthalten meist 7 TS-Pakete, optional hinter einem
+// RTP-Header (RFC 2250) – `ts_packets` entpackt beides.
+use serde::Serialize;
+
+pub const TS_PACKET_LEN: usize = 188;
+const TS_SYNC: u8 = 0x47;
+const PAT_PID: u16 = 0x0000;
+/// PTS/DTS laufen mit 90 kHz und 33 Bit
+pub const PTS_HZ: u64 = 90_000;
+const PTS_MASK: u64 = (1 << 33) - 1;
+/// Weicht die PTS-Zeit weiter von der Wanduhr ab, wird neu verankert
+const PTS_RESYNC_NS: u64 = 1_000_000_000;
+/// Schutz gegen PES ohne Längenangabe und ohne nachfolgenden Start
+const MAX_PES_LEN: usize = 256 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TsAudioCodec {
+    /// MPEG-1/2 Audio Layer I–III (Stream-Typ 0x03/0x04)
+    MpegAudio,
+    /// AAC mit ADTS-Headern (Stream-Typ 0x0F)
+    AacAdts,
+}
+
+impl TsAudioCodec {
+    pub fn from_stream_type(stream_type: u8) -> Option<Self> {
+        match stream_type {
+            0x03 | 0x04 => Some(Self::MpegAudio),
+            0x0f => Some(Self::AacAdts),
+            _ => None,
+        }
+    }
+}
+
+/// Ein vollständiges PES-Paket der gewählten Audio-PID.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PesPacket {
+    pub pid: u16,
+    pub codec: TsAudioCodec,
+    /// 90-kHz-Zeitstempel, falls im PES-Header vorhanden
+    pub pts: Option<u64>,
+    pub payload: Vec<u8>,
+}
+
+/// Liefert die TS-Pakete eines UDP-Datagramms (roh oder in RTP verpackt).
+pub fn ts_packets(datagram: &[u8]) -> impl Iterator<Item = &[u8]> {
+    let start = if datagram.first() == Some(&TS_SYNC) {
+        0
+    } else {
+        rtp_header_len(datagram)
+            .filter(|&len| datagram.get(len) == Some(&TS_SYNC))
+            .unwrap_or(datagram.len())
+    };
+    datagram[start..].chunks_exact(TS_PACKET_LEN)
+}
+
+fn rtp_header_len(datagram: &[u8]) -> Option<usize> {
+    if datagram.len() < 12 || datagram[0] >> 6 != 2 {
+        return None;
+    }
+    let mut len = 12 + 4 * (datagram[0] & 0x0f) as usize;
+    if datagram[0] & 0x10 != 0 {
+        let ext = datagram.get(len + 2..len + 4)?;
+        len += 4 + 4 * u16::from_be_bytes([ext[0], ext[1]]) as usize;
+    }
+    Some(len)
+}
+
+/// CRC-32/MPEG-2 über eine PSI-Section inklusive CRC ergibt 0.
+fn crc32_mpeg2(data: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+    for &byte in data {
+        crc ^= (byte as u32) << 24;
+        for _ in 0..8 {
+            crc = if crc & 0x8000_0000 != 0 {
+                (crc << 1) ^ 0x04c1_1db7
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+/// PSI-Section (Pointer-Field bereits im Payload) mit geprüfter CRC; liefert
+/// Table-ID und die Bytes nach dem Section-Length-Feld ohne CRC.
+fn psi_section(payload: &[u8]) -> Option<(u8, &[u8])> {
+    let pointer = *payload.first()? as usize;
+    let section = payload.get(1 + pointer..)?;
+    if section.len() < 3 {
+        return None;
+    }
+    let length = (((section[1] & 0x0f) as usize) << 8) | section[2] as usize;
+    let section = section.get(..3 + length)?;
+    if length < 9 || crc32_mpeg2(section) != 0 {
+        return None;
+    }
+    Some((section[0], &section[3..section.len() - 4]))
+}
+
+fn parse_pts(bytes: &[u8]) -> u64 {
+    (((bytes[0] >> 1) & 0x07) as u64) << 30
+        | (bytes[1] as u64) << 22
+        | ((bytes[2] >> 1) as u64) << 15
+        | (bytes[3] as u64) << 7
+        | (bytes[4] >> 1) as u64
+}
+
+/// Zerlegt ein PES-Paket in PTS und Elementarstrom-Daten.
+fn parse_pes(data: &[u8]) -> Option<(Option<u64>, &[u8])> {
+    if data.len() < 9 || data[..3] != [0, 0, 1] {
+        return None;
+    }
+    let header_len = data[8] as usize;
+    let start = 9 + header_len;
+    let declared = u16::from_be_bytes([data[4], data[5]]) as usize;
+    let end = if declared == 0 {
+        data.len()
+    } else {
+        (6 + declared).min(data.len())
+    };
+    if start > end {
+        return None;
+    }
+    let pts = (data[7] & 0x80 != 0 && header_len >= 5).then(|| parse_pts(&data[9..14]));
+    Some((pts, &data[start..end]))
+}
+
+#[derive(Debug, Default)]
+pub struct TsDemuxer {
+    /// Feste Audio-PID statt der ersten aus der PMT
+    forced_pid: Option<u16>,
+    pmt_pid: Option<u16>,
+    audio: Option<(u16, TsAudioCodec)>,
+    pes: Vec<u8>,
+    /// PES-Anfang gesehen; bis dahin werden Fortsetzungspakete verworfen
+    in_pes: bool,
+    continuity: Option<u8>,
+    errors: u64,
+}
+
+impl TsDemuxer {
+    pub fn new(forced_pid: Option<u16>) -> Self {
+        Self {
+            forced_pid,
+            ..Self::default()
+        }
+    }
+
+    /// Gewählte Audio-PID und Codec, sobald die PMT gelesen ist.
+    pub fn audio_stream(&self) -> Option<(u16, TsAudioCodec)> {
+        self.audio
+    }
+
+    /// Verworfene Pakete: falsches Sync-Byte, Transportfehler, Continuity-Lücken
+    pub fn errors(&self) -> u64 {
+        self.errors
+    }
+
+    /// Verarbeitet ein 188-Byte-Paket und liefert ggf. ein fertiges PES-Paket.
+    pub fn push(&mut self, packet: &[u8]) -> Option<PesPacket> {
+        if packet.len() != TS_PACKET_LEN || packet[0] != TS_SYNC || packet[1] & 0x80 != 0 {
+            self.errors += 1;
+            return None;
+        }
+        let unit_start = packet[1] & 0x40 != 0;
+        let pid = u16::from_be_bytes([packet[1] & 0x1f, packet[2]]);
+        let adaptation = (packet[3] >> 4) & 0x03;
+        let continuity = packet[3] & 0x0f;
+
+        let mut offset = 4;
+        if adaptation & 0x02 != 0 {
+            offset += 1 + packet[4] as usize;
+        }
+        if adaptation & 0x01 == 0 || offset >= TS_PACKET_LEN {
+            return None;
+        }
+        let payload = &packet[offset..];
+
+        if pid == PAT_PID {
+            if unit_start {
+                self.parse_pat(payload);
+            }
+            return None;
+        }
+        if Some(pid) == self.pmt_pid {
+            if unit_start {
+                self.parse_pmt(payload);
+            }
+            return None;
+        }
+        let (audio_pid, codec) = self.audio?;
+        if pid != audio_pid {
+            return None;
+        }
+
+        if let Some(previous) = self.continuity {
+            if continuity == previous {
+                // Duplikat laut ISO 13818-1
+                return None;
+            }
+            if continuity != (previous + 1) & 0x0f {
+                self.errors += 1;
+                self.pes.clear();
+                self.in_pes = false;
+            }
+        }
+        self.continuity = Some(continuity);
+
+        let mut done = None;
+        if unit_start {
+            done = self.finish(audio_pid, codec);
+            self.in_pes = true;
+        }
+        if !self.in_pes {
+            return done;
+        }
+        self.pes.extend_from_slice(payload);
+        if self.pes.len() > MAX_PES_LEN {
+            self.errors += 1;
+            self.pes.clear();
+            self.in_pes = false;
+        } else if done.is_none() && self.pes_complete() {
+            // Länge bekannt: nicht auf das nächste PES warten
+            done = self.finish(audio_pid, codec);
+            self.in_pes = false;
+        }
+        done
+    }
+
+    fn pes_complete(&self) -> bool {
+        self.pes.len() >= 6 && {
+            let declared = u16::from_be_bytes([self.pes[4], self.pes[5]]) as usize;
+            declared != 0 && self.pes.len() >= 6 + declared
+        }
+    }
+
+    fn finish(&mut self, pid: u16, codec: TsAudioCodec) -> Option<PesPacket> {
+        if self.pes.is_empty() {
+            return None;
+        }
+        let data = std::mem::take(&mut self.pes);
+        match parse_pes(&data) {
+            Some((pts, payload)) if !payload.is_empty() => Some(PesPacket {
+                pid,
+                codec,
+                pts,
+                payload: payload.to_vec(),
+            }),
+            Some(_) => None,
+            None => {
+                self.errors += 1;
+                None
+            }
+        }
+    }
+
+    fn parse_pat(&mut self, payload: &[u8]) {
+        let Some((0x00, body)) = psi_section(payload) else {
+            return;
+        };
+        // Nach Transport-Stream-ID, Version und Section-Nummern: 4-Byte-Einträge
+        let pmt_pid = body
+            .get(5..)
+            .unwrap_or_default()
+            .chunks_exact(4)
+            .find(|entry| u16::from_be_bytes([entry[0], entry[1]]) != 0)
+            .map(|entry| u16::from_be_bytes([entry[2] & 0x1f, entry[3]]));
+        if pmt_pid.is_some() && pmt_pid != self.pmt_pid {
+            self.pmt_pid = pmt_pid;
+            self.select_audio(None);
+        }
+    }
+
+    fn parse_pmt(&mut self, payload: &[u8]) {
+        let Some((0x02, body)) = psi_section(payload) else {
+            return;
+        };
+        if body.len() < 9 {
+            return;
+        }
+        let program_info = (((body[7] & 0x0f) as usize) << 8) | body[8] as usize;
+        let mut streams = body.get(9 + program_info..).unwrap_or_default();
+        let mut selected = None;
+        while streams.len() >= 5 {
+            let stream_type = streams[0];
+            let pid = u16::from_be_bytes([streams[1] & 0x1f, streams[2]]);
+            let es_info = (((streams[3] & 0x0f) as usize) << 8) | streams[4] as usize;
+            if let Some(codec) = TsAudioCodec::from_stream_type(stream_type) {
+                if self.forced_pid.unwrap_or(pid) == pid {
+                    selected = Some((pid, codec));
+                    break;
+                }
+            }
+            streams = streams.get(5 + es_info..).unwrap_or_default();
+        }
+        if selected != self.audio {
+            self.select_audio(selected);
+        }
+    }
+
+    fn select_audio(&mut self, audio: Option<(u16, TsAudioCodec)>) {
+        if let Some((pid, codec)) = audio {
+            log::info!("MPEG-TS: audio PID {} ({:?})", pid, codec);
+        }
+        self.audio = audio;
+        self.pes.clear();
+        self.in_pes = false;
+        self.continuity = None;
+    }
+}
+
+/// Ordnet PTS-Werte der Wanduhr zu: der erste PTS wird auf die Empfangszeit
+/// verankert, danach folgen die Zeitstempel dem Abstand der PTS. Bei Sprüngen
+/// (neuer Encoder, PTS-Umbruch rückwärts, Drift > 1 s) wird neu verankert.
+#[derive(Debug, Default)]
+pub struct PtsClock {
+    anchor: Option<(u64, u64)>,
+}
+
+impl PtsClock {
+    pub fn utc_ns(&mut self, pts: Option<u64>, now_ns: u64) -> u64 {
+        let Some(pts) = pts else {
+            return now_ns;
+        };
+        if let Some((anchor_pts, anchor_ns)) = self.anchor {
+            let elapsed = pts.wrapping_sub(anchor_pts) & PTS_MASK;
+            let utc_ns = anchor_ns + elapsed * 1_000_000_000 / PTS_HZ;
+            if utc_ns.abs_diff(now_ns) <= PTS_RESYNC_NS {
+                return utc_ns;
+            }
+        }
+        self.anchor = Some((pts, now_ns));
+        now_ns
+    }
+}
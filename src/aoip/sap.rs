@@ -0,0 +1,316 @@
+// src/aoip/sap.rs
+//
+// SAP (RFC 2974): kündigt aktive AES67-Outputs alle 30 s mit ihrem SDP an und
+// sammelt fremde Announcements für `/api/devices/aoip`. Der Dienst ist
+// prozessweit (ein Announcer-, ein Listener-Thread), Outputs melden sich beim
+// Start an und beim Stop (mit Deletion-Paket) wieder ab.
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddrV4, UdpSocket};
+use std::sync::{Mutex, Once, OnceLock};
+use std::time::Duration;
+
+use anyhow::{bail, Result};
+use serde::Serialize;
+
+use crate::aoip::sdp::{parse_sdp, Aes67Session, SdpSummary};
+use crate::core::lock::lock_mutex;
+use crate::core::timestamp::utc_ns_now;
+
+/// AES67 nutzt die administrativ begrenzte SAP-Gruppe.
+pub const SAP_ADDRESS: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(239, 255, 255, 255), 9875);
+const ANNOUNCE_INTERVAL: Duration = Duration::from_secs(30);
+/// Quellen ohne erneutes Announcement gelten nach 10 Intervallen als weg.
+const SOURCE_TIMEOUT_MS: u64 = 10 * 30_000;
+const SAP_TTL: u32 = 32;
+const SDP_MIME: &str = "application/sdp";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SapPacket {
+    pub deletion: bool,
+    pub msg_id_hash: u16,
+    pub origin: IpAddr,
+    pub payload_type: String,
+    pub payload: String,
+}
+
+pub fn encode_sap(deletion: bool, msg_id_hash: u16, origin: Ipv4Addr, sdp: &str) -> Vec<u8> {
+    let mut packet = Vec::with_capacity(8 + SDP_MIME.len() + 1 + sdp.len());
+    // V=1, A=0 (IPv4), R=0, T=Announcement/Deletion, E=0, C=0
+    packet.push(0x20 | if deletion { 0x04 } else { 0x00 });
+    packet.push(0); // keine Authentifizierung
+    packet.extend_from_slice(&msg_id_hash.to_be_bytes());
+    packet.extend_from_slice(&origin.octets());
+    packet.extend_from_slice(SDP_MIME.as_bytes());
+    packet.push(0);
+    packet.extend_from_slice(sdp.as_bytes());
+    packet
+}
+
+pub fn parse_sap(buf: &[u8]) -> Result<SapPacket> {
+    if buf.len() < 4 {
+        bail!("SAP packet too short");
+    }
+    let flags = buf[0];
+    if flags >> 5 != 1 {
+        bail!("unsupported SAP version {}", flags >> 5);
+    }
+    if flags & 0x02 != 0 {
+        bail!("encrypted SAP payload");
+    }
+    if flags & 0x01 != 0 {
+        bail!("compressed SAP payload");
+    }
+
+    let ipv6 = flags & 0x10 != 0;
+    let auth_len = buf[1] as usize * 4;
+    let msg_id_hash = u16::from_be_bytes([buf[2], buf[3]]);
+
+    let (origin, mut offset) = if ipv6 {
+        let octets: [u8; 16] = buf
+            .get(4..20)
+            .ok_or_else(|| anyhow::anyhow!("SAP packet too short"))?
+            .try_into()?;
+        (IpAddr::V6(Ipv6Addr::from(octets)), 20)
+    } else {
+        let octets: [u8; 4] = buf
+            .get(4..8)
+            .ok_or_else(|| anyhow::anyhow!("SAP packet too short"))?
+            .try_into()?;
+        (IpAddr::V4(Ipv4Addr::from(octets)), 8)
+    };
+    offset += auth_len;
+
+    let body = buf
+        .get(offset..)
+        .ok_or_else(|| anyhow::anyhow!("SAP packet too short"))?;
+    let body = String::from_utf8_lossy(body);
+
+    // Payload-Type ist optional; fehlt er, beginnt das SDP direkt mit "v=0"
+    let (payload_type, payload) = if body.starts_with("v=0") {
+        (SDP_MIME.to_string(), body.to_string())
+    } else {
+        match body.split_once('\0') {
+            Some((mime, payload)) => (mime.to_string(), payload.to_string()),
+            None => bail!("SAP payload type not terminated"),
+        }
+    };
+
+    Ok(SapPacket {
+        deletion: flags & 0x04 != 0,
+        msg_id_hash,
+        origin,
+        payload_type,
+        payload,
+    })
+}
+
+/// Über SAP gefundene Quelle.
+#[derive(Debug, Clone, Serialize)]
+pub struct AoipSource {
+    pub id: String,
+    pub sap_origin: String,
+    #[serde(flatten)]
+    pub stream: SdpSummary,
+    pub last_seen_ms: u64,
+    pub sdp: String,
+}
+
+struct Announcement {
+    session: Aes67Session,
+    msg_id_hash: u16,
+}
+
+pub struct SapService {
+    announcements: Mutex<HashMap<String, Announcement>>,
+    sources: Mutex<HashMap<String, AoipSource>>,
+    socket: OnceLock<Option<UdpSocket>>,
+    announcer: Once,
+    listener: Once,
+}
+
+static SAP_SERVICE: OnceLock<SapService> = OnceLock::new();
+
+pub fn sap_service() -> &'static SapService {
+    SAP_SERVICE.get_or_init(SapService::new)
+}
+
+impl SapService {
+    fn new() -> Self {
+        Self {
+            announcements: Mutex::new(HashMap::new()),
+            sources: Mutex::new(HashMap::new()),
+            socket: OnceLock::new(),
+            announcer: Once::new(),
+            listener: Once::new(),
+        }
+    }
+
+    /// Meldet einen Output an (bzw. aktualisiert ihn) und sendet sofort.
+    pub fn announce(&'static self, key: &str, session: Aes67Session) {
+        let msg_id_hash = (session.session_id as u16) ^ (session.session_id >> 16) as u16;
+        let packet = encode_sap(false, msg_id_hash, session.origin, &session.to_sdp());
+        lock_mutex(&self.announcements, "sap.announce").insert(
+            key.to_string(),
+            Announcement {
+                session,
+                msg_id_hash,
+            },
+        );
+        self.send(&packet);
+
+        self.announcer.call_once(|| {
+            std::thread::spawn(move || loop {
+                std::thread::sleep(ANNOUNCE_INTERVAL);
+                self.announce_all();
+            });
+        });
+    }
+
+    /// Meldet einen Output ab; Empfänger entfernen ihn sofort.
+    pub fn withdraw(&self, key: &str) {
+        let removed = lock_mutex(&self.announcements, "sap.withdraw").remove(key);
+        if let Some(announcement) = removed {
+            let sdp = announcement.session.to_sdp();
+            self.send(&encode_sap(
+                true,
+                announcement.msg_id_hash,
+                announcement.session.origin,
+                &sdp,
+            ));
+        }
+    }
+
+    pub fn announced(&self) -> Vec<String> {
+        lock_mutex(&self.announcements, "sap.announced")
+            .keys()
+            .cloned()
+            .collect()
+    }
+
+    fn announce_all(&self) {
+        let packets: Vec<Vec<u8>> = lock_mutex(&self.announcements, "sap.announce_all")
+            .values()
+            .map(|a| encode_sap(false, a.msg_id_hash, a.session.origin, &a.session.to_sdp()))
+            .collect();
+        for packet in packets {
+            self.send(&packet);
+        }
+    }
+
+    fn send(&self, packet: &[u8]) {
+        let socket = self.socket.get_or_init(|| {
+            let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))
+                .and_then(|socket| socket.set_multicast_ttl_v4(SAP_TTL).map(|_| socket));
+            match socket {
+                Ok(socket) => Some(socket),
+                Err(e) => {
+                    log::warn!("SAP: cannot create announce socket: {}", e);
+                    None
+                }
+            }
+        });
+        if let Some(socket) = socket {
+            if let Err(e) = socket.send_to(packet, SAP_ADDRESS) {
+                log::debug!("SAP: send failed: {}", e);
+            }
+        }
+    }
+
+    /// Startet (einmalig) den Listener auf 239.255.255.255:9875.
+    pub fn start_discovery(&'static self) {
+        self.listener.call_once(|| {
+            let socket = match UdpSocket::bind((Ipv4Addr::UNSPECIFIED, SAP_ADDRESS.port())) {
+                Ok(socket) => socket,
+                Err(e) => {
+                    log::warn!("SAP: discovery disabled, cannot bind port {}: {}", SAP_ADDRESS.port(), e);
+                    return;
+                }
+            };
+            if let Err(e) = socket.join_multicast_v4(SAP_ADDRESS.ip(), &Ipv4Addr::UNSPECIFIED) {
+                log::warn!("SAP: discovery disabled, cannot join {}: {}", SAP_ADDRESS.ip(), e);
+                return;
+            }
+            log::info!("SAP: listening for AoIP announcements on {}", SAP_ADDRESS);
+
+            std::thread::spawn(move || {
+                let mut buf = vec![0u8; 4096];
+                loop {
+                    match socket.recv_from(&mut buf) {
+                        Ok((len, _)) => self.handle_packet(&buf[..len], utc_ns_now() / 1_000_000),
+                        Err(e) => {
+                            log::warn!("SAP: receive error: {}", e);
+                            std::thread::sleep(Duration::from_secs(1));
+                        }
+                    }
+                }
+            });
+        });
+    }
+
+    pub fn handle_packet(&self, buf: &[u8], now_ms: u64) {
+        let packet = match parse_sap(buf) {
+            Ok(packet) => packet,
+            Err(e) => {
+                log::debug!("SAP: ignoring packet: {}", e);
+                return;
+            }
+        };
+        if packet.payload_type != SDP_MIME {
+            return;
+        }
+
+        let id = format!("{}#{}", packet.origin, packet.msg_id_hash);
+        let mut sources = lock_mutex(&self.sources, "sap.handle_packet");
+        if packet.deletion {
+            sources.remove(&id);
+            return;
+        }
+        sources.insert(
+            id.clone(),
+            AoipSource {
+                id,
+                sap_origin: packet.origin.to_string(),
+                stream: parse_sdp(&packet.payload),
+                last_seen_ms: now_ms,
+                sdp: packet.payload,
+            },
+        );
+    }
+
+    /// Aktuell bekannte Quellen, abgelaufene werden entfernt.
+    pub fn sources(&self) -> Vec<AoipSource> {
+        self.sources_at(utc_ns_now() / 1_000_000)
+    }
+
+    pub fn sources_at(&self, now_ms: u64) -> Vec<AoipSource> {
+        let mut sources = lock_mutex(&self.sources, "sap.sources");
+        sources.retain(|_, source| now_ms.saturating_sub(source.last_seen_ms) < SOURCE_TIMEOUT_MS);
+        let mut list: Vec<AoipSource> = sources.values().cloned().collect();
+        list.sort_by(|a, b| a.stream.name.cmp(&b.stream.name).then(a.id.cmp(&b.id)));
+        list
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sap_round_trip_and_deletion() {
+        let sdp = "v=0\r\ns=studio\r\nc=IN IP4 239.69.1.1/32\r\nm=audio 5004 RTP/AVP 98\r\na=rtpmap:98 L24/48000/8\r\n";
+        let origin = Ipv4Addr::new(10, 0, 0, 5);
+        let service = SapService::new();
+
+        service.handle_packet(&encode_sap(false, 7, origin, sdp), 1_000);
+        let sources = service.sources_at(2_000);
+        assert_eq!(sources.len(), 1);
+        assert_eq!(sources[0].stream.name.as_deref(), Some("studio"));
+        assert_eq!(sources[0].stream.channels, Some(8));
+
+        service.handle_packet(&encode_sap(true, 7, origin, sdp), 3_000);
+        assert!(service.sources_at(3_000).is_empty());
+
+        service.handle_packet(&encode_sap(false, 7, origin, sdp), 1_000);
+        assert!(service.sources_at(1_000 + SOURCE_TIMEOUT_MS).is_empty());
+    }
+}
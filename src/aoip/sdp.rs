@@ -0,0 +1,153 @@
+// src/aoip/sdp.rs
+//
+// SDP für AES67-Streams erzeugen und (tolerant) aus SAP-Announcements lesen.
+use std::net::Ipv4Addr;
+use std::time::Duration;
+
+use serde::Serialize;
+
+/// Beschreibung eines gesendeten AES67-Streams.
+#[derive(Debug, Clone)]
+pub struct Aes67Session {
+    pub name: String,
+    pub session_id: u64,
+    pub origin: Ipv4Addr,
+    pub destination: Ipv4Addr,
+    pub port: u16,
+    pub ttl: u32,
+    pub payload_type: u8,
+    pub sample_rate: u32,
+    pub channels: u8,
+    pub packet_time: Duration,
+}
+
+impl Aes67Session {
+    pub fn to_sdp(&self) -> String {
+        let ptime_ms = self.packet_time.as_secs_f64() * 1000.0;
+        let mut sdp = String::new();
+        sdp.push_str("v=0\r\n");
+        sdp.push_str(&format!(
+            "o=- {} {} IN IP4 {}\r\n",
+            self.session_id, self.session_id, self.origin
+        ));
+        sdp.push_str(&format!("s={}\r\n", self.name));
+        if self.destination.is_multicast() {
+            sdp.push_str(&format!("c=IN IP4 {}/{}\r\n", self.destination, self.ttl));
+        } else {
+            sdp.push_str(&format!("c=IN IP4 {}\r\n", self.destination));
+        }
+        sdp.push_str("t=0 0\r\n");
+        sdp.push_str("a=clock-domain:PTPv2 0\r\n");
+        sdp.push_str(&format!(
+            "m=audio {} RTP/AVP {}\r\n",
+            self.port, self.payload_type
+        ));
+        sdp.push_str(&format!("i={} channels\r\n", self.channels));
+        sdp.push_str(&format!(
+            "a=rtpmap:{} L24/{}/{}\r\n",
+            self.payload_type, self.sample_rate, self.channels
+        ));
+        sdp.push_str("a=recvonly\r\n");
+        sdp.push_str(&format!("a=ptime:{}\r\n", ptime_ms));
+        sdp.push_str("a=ts-refclk:ptp=IEEE1588-2008:traceable\r\n");
+        sdp.push_str("a=mediaclk:direct=0\r\n");
+        sdp
+    }
+}
+
+/// Aus einem fremden SDP gelesene Stream-Daten.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SdpSummary {
+    pub name: Option<String>,
+    pub origin: Option<String>,
+    pub address: Option<String>,
+    pub port: Option<u16>,
+    pub encoding: Option<String>,
+    pub sample_rate: Option<u32>,
+    pub channels: Option<u8>,
+    pub ptime_ms: Option<f64>,
+}
+
+pub fn parse_sdp(sdp: &str) -> SdpSummary {
+    let mut summary = SdpSummary::default();
+    let mut audio_payload: Option<String> = None;
+
+    for line in sdp.lines() {
+        let line = line.trim_end_matches('\r');
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        match key {
+            "s" => summary.name = Some(value.to_string()),
+            // o=<user> <id> <version> IN IP4 <addr>
+            "o" => summary.origin = value.split_whitespace().nth(5).map(str::to_string),
+            // c=IN IP4 <addr>[/ttl]; die erste Angabe gewinnt
+            "c" if summary.address.is_none() => {
+                summary.address = value
+                    .split_whitespace()
+                    .nth(2)
+                    .map(|addr| addr.split('/').next().unwrap_or(addr).to_string());
+            }
+            "m" if audio_payload.is_none() => {
+                let mut parts = value.split_whitespace();
+                if parts.next() == Some("audio") {
+                    summary.port = parts.next().and_then(|p| p.parse().ok());
+                    audio_payload = parts.nth(1).map(str::to_string);
+                }
+            }
+            "a" => {
+                if let Some(rtpmap) = value.strip_prefix("rtpmap:") {
+                    let Some((payload, format)) = rtpmap.split_once(' ') else {
+                        continue;
+                    };
+                    if audio_payload.as_deref() != Some(payload) || summary.encoding.is_some() {
+                        continue;
+                    }
+                    let mut format = format.split('/');
+                    summary.encoding = format.next().map(str::to_string);
+                    summary.sample_rate = format.next().and_then(|r| r.parse().ok());
+                    summary.channels = Some(format.next().and_then(|c| c.parse().ok()).unwrap_or(1));
+                } else if let Some(ptime) = value.strip_prefix("ptime:") {
+                    summary.ptime_ms = ptime.trim().parse().ok();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_sdp_round_trips() {
+        let session = Aes67Session {
+            name: "airlift program".to_string(),
+            session_id: 42,
+            origin: Ipv4Addr::new(192, 168, 1, 10),
+            destination: Ipv4Addr::new(239, 69, 1, 10),
+            port: 5004,
+            ttl: 32,
+            payload_type: 97,
+            sample_rate: 48_000,
+            channels: 2,
+            packet_time: Duration::from_micros(250),
+        };
+
+        let sdp = session.to_sdp();
+        assert!(sdp.contains("a=ptime:0.25\r\n"));
+
+        let summary = parse_sdp(&sdp);
+        assert_eq!(summary.name.as_deref(), Some("airlift program"));
+        assert_eq!(summary.origin.as_deref(), Some("192.168.1.10"));
+        assert_eq!(summary.address.as_deref(), Some("239.69.1.10"));
+        assert_eq!(summary.port, Some(5004));
+        assert_eq!(summary.encoding.as_deref(), Some("L24"));
+        assert_eq!(summary.sample_rate, Some(48_000));
+        assert_eq!(summary.channels, Some(2));
+        assert_eq!(summary.ptime_ms, Some(0.25));
+    }
+}
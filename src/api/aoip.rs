@@ -0,0 +1,26 @@
+use serde::Serialize;
+use tiny_http::{Header, Request, Response, StatusCode};
+
+use crate::aoip::{sap_service, AoipSource};
+
+#[derive(Serialize)]
+pub struct AoipDevicesResponse {
+    /// Per SAP gefundene Streams im Netz
+    pub sources: Vec<AoipSource>,
+    /// Eigene, per SAP angekündigte Outputs
+    pub announced: Vec<String>,
+}
+
+pub fn handle_aoip_devices_request(req: Request) {
+    let service = sap_service();
+    let response = AoipDevicesResponse {
+        sources: service.sources(),
+        announced: service.announced(),
+    };
+    let body = serde_json::to_string(&response).unwrap_or_else(|_| "{}".to_string());
+    let _ = req.respond(
+        Response::from_string(body)
+            .with_status_code(StatusCode(200))
+            .with_header(Header::from_bytes("Content-Type", "application/json").unwrap()),
+    );
+}
@@ -0,0 +1,115 @@
+// src/api/auth.rs
+//
+// Zugriffsregeln pro Listener (`monitoring.binds`): optionaler Bearer-Token
+// und Nur-Lese-Modus. `/health` bleibt für Load-Balancer offen, WHIP prüft
+// seinen eigenen Token.
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use tiny_http::{Method, Request, StatusCode};
+
+use crate::config::BindConfig;
+
+fn header(req: &Request, name: &'static str) -> Option<String> {
+    req.headers()
+        .iter()
+        .find(|h| h.field.equiv(name))
+        .map(|h| h.value.as_str().to_string())
+}
+
+fn query_token(query: &str) -> Option<&str> {
+    query
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("token="))
+}
+
+/// `Err(status)` = Anfrage ablehnen (401 ohne/falscher Token, 403 bei
+/// schreibendem Zugriff auf einen Nur-Lese-Listener).
+pub fn authorize(req: &Request, bind: &BindConfig, path: &str, query: &str) -> Result<(), StatusCode> {
+    if path == "/health" || path.starts_with("/whip/") || path.starts_with("/whep/") {
+        return Ok(());
+    }
+
+    if let Some(expected) = &bind.token {
+        let bearer = header(req, "Authorization");
+        let presented = bearer
+            .as_deref()
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .map(str::trim)
+            .or_else(|| query_token(query));
+        if presented != Some(expected.as_str()) {
+            return Err(StatusCode(401));
+        }
+    }
+
+    if !method_allowed(bind, req.method()) {
+        return Err(StatusCode(403));
+    }
+    Ok(())
+}
+
+/// Gleiche Regel wie in `authorize`, damit `/api/me` nichts verspricht, was
+/// der Listener dann mit 403 ablehnt.
+fn method_allowed(bind: &BindConfig, method: &Method) -> bool {
+    !bind.read_only || matches!(method, Method::Get | Method::Head)
+}
+
+/// Steueraktionen, die die Web-UI ein- oder ausblendet: (Name, Endpunkte).
+const CAPABILITIES: &[(&str, &[(Method, &str)])] = &[
+    ("start_stop", &[(Method::Post, "/api/control")]),
+    (
+        "recorder",
+        &[(Method::Post, "/api/recorder/start"), (Method::Post, "/api/recorder/stop/{id}")],
+    ),
+    (
+        "config_write",
+        &[(Method::Post, "/api/config"), (Method::Get, "/ws/control config.patch")],
+    ),
+    (
+        "clip_export",
+        &[(Method::Get, "/api/recordings"), (Method::Get, "/api/recordings/{id}/waveform")],
+    ),
+    ("debug_capture", &[(Method::Post, "/api/debug/capture")]),
+    ("probe", &[(Method::Post, "/api/probe")]),
+    ("stream_metadata", &[(Method::Post, "/api/metadata")]),
+];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Capability {
+    pub allowed: bool,
+    /// "METHOD /pfad" je betroffenem Endpunkt
+    pub endpoints: Vec<String>,
+}
+
+/// Erlaubte Aktionen für Anfragen, die `authorize` auf diesem Listener
+/// bereits durchgelassen hat.
+pub fn capabilities(bind: &BindConfig) -> BTreeMap<&'static str, Capability> {
+    CAPABILITIES
+        .iter()
+        .map(|(name, endpoints)| {
+            // Config-Patches über `/ws/control` sind schreibend, obwohl der
+            // Upgrade per GET kommt
+            let allowed = if *name == "config_write" {
+                !bind.read_only
+            } else {
+                endpoints.iter().all(|(method, _)| method_allowed(bind, method))
+            };
+            let endpoints = endpoints
+                .iter()
+                .map(|(method, path)| format!("{} {}", method, path))
+                .collect();
+            (*name, Capability { allowed, endpoints })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn query_token_is_found_among_parameters() {
+        assert_eq!(query_token("from=1&token=abc"), Some("abc"));
+        assert_eq!(query_token("tokens=abc"), None);
+    }
+}
@@ -0,0 +1,45 @@
+// src/api/compare.rs
+//
+// `/api/compare/<flow>`: Beschreibung des Vorher/Nachher-Vergleichs eines
+// Flows für die UI (Abgriffe, Stream-Pfad, laufende Sessions). Der Stream
+// selbst läuft über `/ws/compare/<flow>` (siehe `ws.rs`).
+use std::sync::{Arc, Mutex};
+
+use tiny_http::{Header, Request, Response, StatusCode};
+
+use crate::core::compare::{compare_sessions, MAX_COMPARE_SESSIONS};
+use crate::core::lock::lock_mutex;
+use crate::core::AirliftNode;
+
+fn respond(req: Request, status: u16, body: serde_json::Value) {
+    let response = Response::from_string(body.to_string())
+        .with_status_code(StatusCode(status))
+        .with_header(Header::from_bytes("Content-Type", "application/json").unwrap());
+    let _ = req.respond(response);
+}
+
+pub fn handle_compare_request(req: Request, node: Arc<Mutex<AirliftNode>>, flow_name: &str) {
+    let body = {
+        let node = lock_mutex(&node, "api.compare");
+        let Some(flow) = node.flows().iter().find(|flow| flow.name == flow_name) else {
+            drop(node);
+            respond(req, 404, serde_json::json!({ "error": "unknown flow" }));
+            return;
+        };
+        let status = flow.status();
+        serde_json::json!({
+            "flow": flow.name,
+            "running": status.running,
+            "taps": {
+                "pre": format!("flow:{}:merge", flow.name),
+                "post": format!("flow:{}:output", flow.name),
+            },
+            "stream": format!("/ws/compare/{}", flow.name),
+            "processors": flow.processor_names(),
+            "bypass": flow.bypass_active(),
+            "sessions": compare_sessions(&flow.name),
+            "max_sessions": MAX_COMPARE_SESSIONS,
+        })
+    };
+    respond(req, 200, body);
+}
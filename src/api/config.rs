@@ -2,63 +2,160 @@ use std::io::Read;
 use std::sync::{Arc, Mutex};
 
 use log::error;
+use serde::Deserialize;
 use serde_json::json;
 use tiny_http::{Header, Method, Request, Response, StatusCode};
 
-use crate::config::{Config, ConfigPatch};
+use crate::app::configurator;
+use crate::config::{config_revisions, Config, ConfigPatch, ConfigRevisions, ObjectConflict};
+use crate::core::lock::lock_mutex;
+use crate::core::{AirliftNode, EventPriority, EventType};
 
-pub fn handle_config_request(mut req: Request, config: Arc<Mutex<Config>>) {
+/// Warum ein Patch nicht übernommen wurde.
+#[derive(Debug)]
+pub enum PatchRejection {
+    /// Seit `base_revision` hat jemand dieselben Objekte geändert
+    Conflict {
+        revision: u64,
+        conflicts: Vec<ObjectConflict>,
+    },
+    Invalid(String),
+    Internal(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct PatchApplied {
+    pub revision: u64,
+    pub objects: Vec<String>,
+    pub config: Config,
+}
+
+/// Prüft `base_revision` (falls gesetzt), wendet den Patch an und vergibt eine
+/// neue Revision. Ohne `base_revision` gewinnt der letzte Schreiber.
+pub fn apply_revisioned_patch(
+    config: &Mutex<Config>,
+    revisions: &ConfigRevisions,
+    body: &str,
+    base_revision: Option<u64>,
+) -> Result<PatchApplied, PatchRejection> {
+    let mut guard = config
+        .lock()
+        .map_err(|_| PatchRejection::Internal("config lock poisoned".to_string()))?;
 
-let response = if req.method() != &Method::Post {
-    Response::from_string("")
-        .with_status_code(StatusCode(405))
-} else {
+    // config_mode bestimmt die Prüfung unbekannter Felder
+    let patch = ConfigPatch::from_json(body, guard.config_mode)
+        .map_err(|err| PatchRejection::Invalid(format!("invalid patch payload: {}", err)))?;
+    let objects = patch.touched_objects();
+
+    if let Some(base) = base_revision {
+        let current = revisions.current();
+        if base > current {
+            return Err(PatchRejection::Invalid(format!(
+                "unknown base_revision {} (current {})",
+                base, current
+            )));
+        }
+        let conflicts = revisions.conflicts(base, &objects);
+        if !conflicts.is_empty() {
+            return Err(PatchRejection::Conflict {
+                revision: current,
+                conflicts,
+            });
+        }
+    }
+
+    guard
+        .apply_patch(&patch)
+        .map_err(|err| PatchRejection::Invalid(err.to_string()))?;
+    let revision = revisions.commit(&objects);
+    Ok(PatchApplied {
+        revision,
+        objects,
+        config: guard.clone(),
+    })
+}
+
+fn publish_config_patched(node: &AirliftNode, applied: &PatchApplied, source: &str) {
+    node.publish_event(
+        EventType::ConfigChanged,
+        EventPriority::Info,
+        json!({
+            "action": "config_patched",
+            "revision": applied.revision,
+            "objects": &applied.objects,
+            "source": source,
+            "timestamp": crate::core::timestamp::utc_ns_now(),
+        }),
+    );
+}
+
+fn query_base_revision(url: &str) -> Result<Option<u64>, String> {
+    let query = url.split_once('?').map(|(_, query)| query).unwrap_or("");
+    match query
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("base_revision="))
+    {
+        Some(value) => value
+            .parse()
+            .map(Some)
+            .map_err(|_| format!("invalid base_revision '{}'", value)),
+        None => Ok(None),
+    }
+}
 
+fn json_response(status: u16, payload: serde_json::Value) -> Response<std::io::Cursor<Vec<u8>>> {
+    Response::from_string(payload.to_string())
+        .with_status_code(StatusCode(status))
+        .with_header(Header::from_bytes("Content-Type", "application/json").unwrap())
+}
+
+pub fn handle_config_request(
+    mut req: Request,
+    config: Arc<Mutex<Config>>,
+    node: Arc<Mutex<AirliftNode>>,
+) {
+    let response = if req.method() != &Method::Post {
+        Response::from_string("").with_status_code(StatusCode(405))
+    } else {
         // Body lesen
         let mut body = String::new();
         if let Err(err) = req.as_reader().read_to_string(&mut body) {
             error!("[config] failed to read request body: {}", err);
-            Response::from_string("invalid request body")
-                .with_status_code(StatusCode(400))
+            Response::from_string("invalid request body").with_status_code(StatusCode(400))
         } else {
-            // JSON parsen
-            match serde_json::from_str::<ConfigPatch>(&body) {
-                Ok(patch) => {
-                    // Config locken
-                    match config.lock() {
-                        Ok(mut guard) => {
-                            // Patch anwenden
-                            match guard.apply_patch(&patch) {
-                                Ok(_) => {
-                                    let payload = json!({
-                                        "status": "ok",
-                                        "config": &*guard,
-                                    });
-                                    Response::from_string(payload.to_string())
-                                        .with_status_code(StatusCode(200))
-                                        .with_header(
-                                            Header::from_bytes(
-                                                "Content-Type",
-                                                "application/json",
-                                            )
-                                            .unwrap(),
-                                        )
-                                }
-                                Err(err) => {
-                                    error!("[config] failed to apply patch: {}", err);
-                                    Response::from_string(err.to_string())
-                                        .with_status_code(StatusCode(400))
-                                }
-                            }
-                        }
-                        Err(_) => Response::from_string("config lock poisoned")
-                            .with_status_code(StatusCode(500)),
+            match query_base_revision(req.url()).map_err(PatchRejection::Invalid).and_then(
+                |base| apply_revisioned_patch(&config, config_revisions(), &body, base),
+            ) {
+                Ok(applied) => {
+                    if let Ok(node) = node.lock() {
+                        publish_config_patched(&node, &applied, "api");
                     }
+                    json_response(
+                        200,
+                        json!({
+                            "status": "ok",
+                            "revision": applied.revision,
+                            "config": applied.config,
+                        }),
+                    )
+                }
+                Err(PatchRejection::Conflict {
+                    revision,
+                    conflicts,
+                }) => json_response(
+                    409,
+                    json!({
+                        "status": "conflict",
+                        "revision": revision,
+                        "conflicts": conflicts,
+                    }),
+                ),
+                Err(PatchRejection::Invalid(message)) => {
+                    error!("[config] failed to apply patch: {}", message);
+                    Response::from_string(message).with_status_code(StatusCode(400))
                 }
-                Err(err) => {
-                    error!("[config] invalid JSON payload: {}", err);
-                    Response::from_string("invalid JSON payload")
-                        .with_status_code(StatusCode(400))
+                Err(PatchRejection::Internal(message)) => {
+                    Response::from_string(message).with_status_code(StatusCode(500))
                 }
             }
         }
@@ -66,3 +163,111 @@ let response = if req.method() != &Method::Post {
 
     let _ = req.respond(response);
 }
+
+#[derive(Deserialize)]
+#[serde(tag = "type")]
+enum ConfigMessage {
+    #[serde(rename = "config.get")]
+    Get { id: Option<serde_json::Value> },
+    #[serde(rename = "config.patch")]
+    Patch {
+        id: Option<serde_json::Value>,
+        base_revision: Option<u64>,
+        patch: serde_json::Value,
+        /// Danach wie `reload` auf den laufenden Node anwenden
+        #[serde(default)]
+        apply: bool,
+    },
+}
+
+/// Eine Nachricht des WebSocket-Kontrollkanals (`/ws/control`) verarbeiten;
+/// liefert die Antwort. Patches brauchen dort immer `base_revision`.
+pub fn handle_config_message(
+    text: &str,
+    config: &Mutex<Config>,
+    node: &Mutex<AirliftNode>,
+    revisions: &ConfigRevisions,
+    read_only: bool,
+) -> serde_json::Value {
+    let message = match serde_json::from_str::<ConfigMessage>(text) {
+        Ok(message) => message,
+        Err(err) => {
+            return json!({
+                "type": "error",
+                "id": null,
+                "message": format!("invalid message: {}", err),
+            })
+        }
+    };
+
+    match message {
+        ConfigMessage::Get { id } => {
+            // Config-Sperre zuerst: Revision und Inhalt passen zusammen
+            let guard = lock_mutex(config, "api.config.ws_get");
+            let (revision, objects) = revisions.snapshot();
+            json!({
+                "type": "config.state",
+                "id": id,
+                "revision": revision,
+                "objects": objects,
+                "config": &*guard,
+            })
+        }
+        ConfigMessage::Patch { id, .. } if read_only => json!({
+            "type": "error",
+            "id": id,
+            "message": "listener is read-only",
+        }),
+        ConfigMessage::Patch {
+            id,
+            base_revision: None,
+            ..
+        } => json!({
+            "type": "error",
+            "id": id,
+            "message": "base_revision is required",
+        }),
+        ConfigMessage::Patch {
+            id,
+            base_revision: Some(base),
+            patch,
+            apply,
+        } => match apply_revisioned_patch(config, revisions, &patch.to_string(), Some(base)) {
+            Ok(applied) => {
+                let mut node = lock_mutex(node, "api.config.ws_patch");
+                let apply_error = if apply {
+                    configurator::apply_config(&mut node, &applied.config)
+                        .err()
+                        .map(|err| format!("failed to apply configuration: {}", err))
+                } else {
+                    None
+                };
+                publish_config_patched(&node, &applied, "ws");
+                json!({
+                    "type": "config.applied",
+                    "id": id,
+                    "revision": applied.revision,
+                    "objects": applied.objects,
+                    "applied": apply && apply_error.is_none(),
+                    "apply_error": apply_error,
+                    "config": applied.config,
+                })
+            }
+            Err(PatchRejection::Conflict {
+                revision,
+                conflicts,
+            }) => json!({
+                "type": "config.conflict",
+                "id": id,
+                "base_revision": base,
+                "revision": revision,
+                "conflicts": conflicts,
+            }),
+            Err(PatchRejection::Invalid(message) | PatchRejection::Internal(message)) => json!({
+                "type": "error",
+                "id": id,
+                "message": message,
+            }),
+        },
+    }
+}
@@ -5,8 +5,12 @@ use std::sync::{Arc, Mutex};
 use tiny_http::{Header, Method, Request, Response, StatusCode};
 
 use crate::app::configurator;
-use crate::config::Config;
-use crate::core::AirliftNode;
+use crate::config::{config_revisions, Config};
+use crate::core::safe_mode;
+use crate::core::{
+    utc_ns_now, AirliftNode, AudioError, AutomationLane, AutomationShape, CorrelationScope,
+    SpliceMode,
+};
 
 #[derive(Deserialize)]
 pub struct ControlRequest {
@@ -20,6 +24,8 @@ pub struct ControlRequest {
 pub struct ControlResponse {
     pub ok: bool,
     pub message: String,
+    /// Korrelations-ID aller Events, die diese Aktion ausgelöst hat
+    pub correlation_id: String,
 }
 
 struct ControlOutcome {
@@ -50,6 +56,7 @@ let response = if req.method() != &Method::Post {
                 Ok(payload) => {
                     match node.lock() {
                         Ok(mut guard) => {
+                            let correlation = CorrelationScope::begin("control");
                             let outcome = dispatch_control(
                                 &mut guard,
                                 &config,
@@ -61,6 +68,7 @@ let response = if req.method() != &Method::Post {
                             let body = serde_json::to_string(&ControlResponse {
                                 ok: outcome.ok,
                                 message: outcome.message,
+                                correlation_id: correlation.id().to_string(),
                             })
                             .unwrap_or_else(|_| {
                                 "{\"ok\":false,\"message\":\"serialization_error\"}".to_string()
@@ -145,13 +153,32 @@ fn dispatch_control(
             }
         }
 
+        "reload" | "config.reload" | "node.reload" if safe_mode::is_active() => ControlOutcome {
+            status: StatusCode(409),
+            ok: false,
+            message: "node is in safe mode; fix the configuration and use safe_mode.exit".to_string(),
+        },
         "reload" | "config.reload" | "node.reload" => apply_config_from_state(node, config),
 
+        "safe_mode.exit" => dispatch_safe_mode_exit(node, config),
+
         "config.import" => apply_config_from_toml(node, config, parameters),
 
         "flow.start" => dispatch_flow_action(node, target, FlowAction::Start),
         "flow.stop" => dispatch_flow_action(node, target, FlowAction::Stop),
         "flow.restart" => dispatch_flow_action(node, target, FlowAction::Restart),
+        "producer.activate" => dispatch_activate_standby(node, target),
+        "producer.pause" => dispatch_producer_pause(node, target, true),
+        "producer.resume" => dispatch_producer_pause(node, target, false),
+        "flow.on_air" => dispatch_on_air(node, target),
+        "flow.off_air" => dispatch_off_air(node, target, parameters),
+        "bypass" => dispatch_bypass(node, target, parameters),
+        "processor.configure" => dispatch_processor_configure(node, target, parameters),
+        "consumer.configure" => dispatch_consumer_configure(node, target, parameters),
+        "mixer.input" => dispatch_mixer_input(node, target, parameters),
+        "encoded.mode" => dispatch_encoded_mode(node, target, parameters),
+        "automation.schedule" => dispatch_automation_schedule(node, target, parameters),
+        "automation.cancel" => dispatch_automation_cancel(node, target, parameters),
 
         _ => ControlOutcome {
             status: StatusCode(400),
@@ -205,6 +232,572 @@ fn dispatch_flow_action(
     }
 }
 
+fn dispatch_activate_standby(node: &mut AirliftNode, target: Option<String>) -> ControlOutcome {
+    let producer_name = match target {
+        Some(name) => name,
+        None => {
+            return ControlOutcome {
+                status: StatusCode(400),
+                ok: false,
+                message: "missing target".to_string(),
+            }
+        }
+    };
+
+    match node.activate_standby_producer(&producer_name) {
+        Ok(()) => ControlOutcome {
+            status: StatusCode(200),
+            ok: true,
+            message: format!("producer '{}' activated", producer_name),
+        },
+        Err(err @ AudioError::ProducerNotFound { .. }) => ControlOutcome {
+            status: StatusCode(404),
+            ok: false,
+            message: err.to_string(),
+        },
+        Err(err) => ControlOutcome {
+            status: StatusCode(500),
+            ok: false,
+            message: format!("producer switch failed: {}", err),
+        },
+    }
+}
+
+fn dispatch_producer_pause(
+    node: &mut AirliftNode,
+    target: Option<String>,
+    pause: bool,
+) -> ControlOutcome {
+    let producer_name = match target {
+        Some(name) => name,
+        None => {
+            return ControlOutcome {
+                status: StatusCode(400),
+                ok: false,
+                message: "missing target".to_string(),
+            }
+        }
+    };
+
+    let result = if pause {
+        node.pause_producer_by_name(&producer_name).map(|_| "paused")
+    } else {
+        node.resume_producer_by_name(&producer_name).map(|_| "resumed")
+    };
+    match result {
+        Ok(state) => ControlOutcome {
+            status: StatusCode(200),
+            ok: true,
+            message: format!("producer '{}' {}", producer_name, state),
+        },
+        Err(err @ AudioError::ProducerNotFound { .. }) => ControlOutcome {
+            status: StatusCode(404),
+            ok: false,
+            message: err.to_string(),
+        },
+        // Nicht unterstützt oder nicht gestartet
+        Err(err) => ControlOutcome {
+            status: StatusCode(409),
+            ok: false,
+            message: err.to_string(),
+        },
+    }
+}
+
+fn dispatch_on_air(node: &mut AirliftNode, target: Option<String>) -> ControlOutcome {
+    let flow_name = match target {
+        Some(name) => name,
+        None => {
+            return ControlOutcome {
+                status: StatusCode(400),
+                ok: false,
+                message: "missing target".to_string(),
+            }
+        }
+    };
+
+    let result = node.flow_mut(&flow_name).and_then(|flow| flow.go_on_air());
+    match result {
+        Ok(()) => ControlOutcome {
+            status: StatusCode(200),
+            ok: true,
+            message: format!("flow '{}' on air", flow_name),
+        },
+        Err(err @ AudioError::InterlockActive { .. }) => ControlOutcome {
+            status: StatusCode(409),
+            ok: false,
+            message: err.to_string(),
+        },
+        Err(err) => ControlOutcome {
+            status: StatusCode(404),
+            ok: false,
+            message: err.to_string(),
+        },
+    }
+}
+
+/// Bypass ein/aus: mit `target` für einen Flow, ohne für den ganzen Node.
+/// `parameters.enabled` (Default `true`).
+fn dispatch_bypass(
+    node: &mut AirliftNode,
+    target: Option<String>,
+    parameters: Option<serde_json::Value>,
+) -> ControlOutcome {
+    let enabled = match parameters.as_ref().and_then(|params| params.get("enabled")) {
+        None => true,
+        Some(serde_json::Value::Bool(enabled)) => *enabled,
+        Some(_) => {
+            return ControlOutcome {
+                status: StatusCode(400),
+                ok: false,
+                message: "parameters.enabled must be a boolean".to_string(),
+            }
+        }
+    };
+    let state = if enabled { "enabled" } else { "disabled" };
+
+    match target {
+        None => {
+            node.set_bypass(enabled);
+            ControlOutcome {
+                status: StatusCode(200),
+                ok: true,
+                message: format!("global bypass {}", state),
+            }
+        }
+        Some(flow_name) => match node.flow_mut(&flow_name) {
+            Ok(flow) => {
+                flow.set_bypass(enabled);
+                ControlOutcome {
+                    status: StatusCode(200),
+                    ok: true,
+                    message: format!("bypass {} for flow '{}'", state, flow_name),
+                }
+            }
+            Err(err) => ControlOutcome {
+                status: StatusCode(404),
+                ok: false,
+                message: err.to_string(),
+            },
+        },
+    }
+}
+
+/// Laufzeit-Update eines Processors: `target` = Flow,
+/// `parameters = { "processor": "...", "config": { ... } }`.
+fn dispatch_processor_configure(
+    node: &mut AirliftNode,
+    target: Option<String>,
+    parameters: Option<serde_json::Value>,
+) -> ControlOutcome {
+    let flow_name = match target {
+        Some(name) => name,
+        None => {
+            return ControlOutcome {
+                status: StatusCode(400),
+                ok: false,
+                message: "missing target".to_string(),
+            }
+        }
+    };
+
+    let params = parameters.unwrap_or_default();
+    let (Some(processor_name), Some(processor_config)) = (
+        params.get("processor").and_then(|v| v.as_str()),
+        params.get("config").cloned(),
+    ) else {
+        return ControlOutcome {
+            status: StatusCode(400),
+            ok: false,
+            message: "parameters.processor and parameters.config are required".to_string(),
+        };
+    };
+
+    let flow = match node.flow_mut(&flow_name) {
+        Ok(flow) => flow,
+        Err(err) => {
+            return ControlOutcome {
+                status: StatusCode(404),
+                ok: false,
+                message: err.to_string(),
+            }
+        }
+    };
+
+    match flow.update_processor_config(processor_name, processor_config) {
+        Ok(()) => ControlOutcome {
+            status: StatusCode(200),
+            ok: true,
+            message: format!("processor '{}' updated", processor_name),
+        },
+        Err(err) => ControlOutcome {
+            status: StatusCode(400),
+            ok: false,
+            message: err.to_string(),
+        },
+    }
+}
+
+/// Gain, Mute oder Solo eines Mixer-Inputs: `target` = Flow,
+/// `parameters = { "processor", "input", "gain"? | "gain_db"?, "mute"?, "solo"? }`.
+fn dispatch_mixer_input(
+    node: &mut AirliftNode,
+    target: Option<String>,
+    parameters: Option<serde_json::Value>,
+) -> ControlOutcome {
+    let flow_name = match target {
+        Some(name) => name,
+        None => {
+            return ControlOutcome {
+                status: StatusCode(400),
+                ok: false,
+                message: "missing target".to_string(),
+            }
+        }
+    };
+
+    let Some(serde_json::Value::Object(mut params)) = parameters else {
+        return ControlOutcome {
+            status: StatusCode(400),
+            ok: false,
+            message: "parameters.processor and parameters.input are required".to_string(),
+        };
+    };
+    let (Some(serde_json::Value::String(processor_name)), Some(input)) =
+        (params.remove("processor"), params.get("input").cloned())
+    else {
+        return ControlOutcome {
+            status: StatusCode(400),
+            ok: false,
+            message: "parameters.processor and parameters.input are required".to_string(),
+        };
+    };
+
+    let flow = match node.flow_mut(&flow_name) {
+        Ok(flow) => flow,
+        Err(err) => {
+            return ControlOutcome {
+                status: StatusCode(404),
+                ok: false,
+                message: err.to_string(),
+            }
+        }
+    };
+
+    match flow.update_processor_config(&processor_name, serde_json::Value::Object(params)) {
+        Ok(()) => ControlOutcome {
+            status: StatusCode(200),
+            ok: true,
+            message: format!("mixer input {} updated", input),
+        },
+        Err(err) => ControlOutcome {
+            status: StatusCode(400),
+            ok: false,
+            message: err.to_string(),
+        },
+    }
+}
+
+fn dispatch_consumer_configure(
+    node: &mut AirliftNode,
+    target: Option<String>,
+    parameters: Option<serde_json::Value>,
+) -> ControlOutcome {
+    let flow_name = match target {
+        Some(name) => name,
+        None => {
+            return ControlOutcome {
+                status: StatusCode(400),
+                ok: false,
+                message: "missing target".to_string(),
+            }
+        }
+    };
+
+    let params = parameters.unwrap_or_default();
+    let (Some(consumer_name), Some(consumer_config)) = (
+        params.get("consumer").and_then(|v| v.as_str()),
+        params.get("config").cloned(),
+    ) else {
+        return ControlOutcome {
+            status: StatusCode(400),
+            ok: false,
+            message: "parameters.consumer and parameters.config are required".to_string(),
+        };
+    };
+
+    let flow = match node.flow_mut(&flow_name) {
+        Ok(flow) => flow,
+        Err(err) => {
+            return ControlOutcome {
+                status: StatusCode(404),
+                ok: false,
+                message: err.to_string(),
+            }
+        }
+    };
+
+    match flow.update_consumer_config(consumer_name, consumer_config) {
+        Ok(()) => ControlOutcome {
+            status: StatusCode(200),
+            ok: true,
+            message: format!("consumer '{}' updated", consumer_name),
+        },
+        Err(err) => ControlOutcome {
+            status: StatusCode(400),
+            ok: false,
+            message: err.to_string(),
+        },
+    }
+}
+
+/// Parameter-Verlauf einplanen: `target` = Flow, `parameters = { "processor",
+/// "parameter", "to", "from"?, "shape"?, "duration_ms"?, "at_ms"? | "delay_ms"?,
+/// "rate_hz"? }`. `from` ist Pflicht außer bei `step`; ohne Zeitangabe startet
+/// der Verlauf sofort.
+fn dispatch_automation_schedule(
+    node: &mut AirliftNode,
+    target: Option<String>,
+    parameters: Option<serde_json::Value>,
+) -> ControlOutcome {
+    let Some(flow_name) = target else {
+        return ControlOutcome {
+            status: StatusCode(400),
+            ok: false,
+            message: "missing target".to_string(),
+        };
+    };
+
+    let params = parameters.unwrap_or_default();
+    let lane = match automation_lane_from_params(&params, utc_ns_now()) {
+        Ok(lane) => lane,
+        Err(message) => {
+            return ControlOutcome {
+                status: StatusCode(400),
+                ok: false,
+                message,
+            }
+        }
+    };
+
+    let flow = match node.flow_mut(&flow_name) {
+        Ok(flow) => flow,
+        Err(err) => {
+            return ControlOutcome {
+                status: StatusCode(404),
+                ok: false,
+                message: err.to_string(),
+            }
+        }
+    };
+
+    match flow.schedule_automation(lane) {
+        Ok(id) => ControlOutcome {
+            status: StatusCode(200),
+            ok: true,
+            message: format!("automation {} scheduled", id),
+        },
+        Err(err) => ControlOutcome {
+            status: StatusCode(400),
+            ok: false,
+            message: err.to_string(),
+        },
+    }
+}
+
+fn automation_lane_from_params(
+    params: &serde_json::Value,
+    now_ns: u64,
+) -> Result<AutomationLane, String> {
+    let text = |key: &str| params.get(key).and_then(|v| v.as_str());
+    let number = |key: &str| params.get(key).and_then(|v| v.as_f64());
+
+    let (Some(processor), Some(parameter), Some(to)) =
+        (text("processor"), text("parameter"), number("to"))
+    else {
+        return Err("parameters.processor, parameters.parameter and parameters.to are required"
+            .to_string());
+    };
+    let shape_name = text("shape").unwrap_or("linear");
+    let shape = AutomationShape::parse(shape_name, number("rate_hz"))
+        .ok_or_else(|| format!("invalid shape '{}' (lfo needs rate_hz 0..=100)", shape_name))?;
+    let from = match (number("from"), shape) {
+        (Some(from), _) => from,
+        (None, AutomationShape::Step) => to,
+        (None, _) => return Err("parameters.from is required for this shape".to_string()),
+    };
+
+    let ms = |key: &str| params.get(key).and_then(|v| v.as_u64());
+    let start_ns = match (ms("at_ms"), ms("delay_ms")) {
+        (Some(at_ms), _) => at_ms.saturating_mul(1_000_000),
+        (None, Some(delay_ms)) => now_ns.saturating_add(delay_ms.saturating_mul(1_000_000)),
+        (None, None) => now_ns,
+    };
+
+    Ok(AutomationLane {
+        id: 0,
+        processor: processor.to_string(),
+        parameter: parameter.to_string(),
+        shape,
+        from,
+        to,
+        start_ns,
+        duration_ns: ms("duration_ms").unwrap_or(0).saturating_mul(1_000_000),
+    })
+}
+
+/// Verlauf abbrechen: `target` = Flow, `parameters = { "id" }`.
+/// Der zuletzt gesetzte Wert bleibt stehen.
+fn dispatch_automation_cancel(
+    node: &mut AirliftNode,
+    target: Option<String>,
+    parameters: Option<serde_json::Value>,
+) -> ControlOutcome {
+    let Some(flow_name) = target else {
+        return ControlOutcome {
+            status: StatusCode(400),
+            ok: false,
+            message: "missing target".to_string(),
+        };
+    };
+    let Some(id) = parameters
+        .as_ref()
+        .and_then(|p| p.get("id"))
+        .and_then(|v| v.as_u64())
+    else {
+        return ControlOutcome {
+            status: StatusCode(400),
+            ok: false,
+            message: "parameters.id is required".to_string(),
+        };
+    };
+
+    match node.flow_mut(&flow_name) {
+        Ok(flow) if flow.automation().cancel(id) => ControlOutcome {
+            status: StatusCode(200),
+            ok: true,
+            message: format!("automation {} cancelled", id),
+        },
+        Ok(_) => ControlOutcome {
+            status: StatusCode(404),
+            ok: false,
+            message: format!("automation {} not found", id),
+        },
+        Err(err) => ControlOutcome {
+            status: StatusCode(404),
+            ok: false,
+            message: err.to_string(),
+        },
+    }
+}
+
+/// Splicing eines Encoded-Flows: `target` = Flow,
+/// `parameters = { "mode": "passthrough" | "processed" }`.
+fn dispatch_encoded_mode(
+    node: &mut AirliftNode,
+    target: Option<String>,
+    parameters: Option<serde_json::Value>,
+) -> ControlOutcome {
+    let flow_name = match target {
+        Some(name) => name,
+        None => {
+            return ControlOutcome {
+                status: StatusCode(400),
+                ok: false,
+                message: "missing target".to_string(),
+            }
+        }
+    };
+
+    let Some(mode) = parameters
+        .as_ref()
+        .and_then(|params| params.get("mode"))
+        .and_then(|v| v.as_str())
+        .and_then(SpliceMode::parse)
+    else {
+        return ControlOutcome {
+            status: StatusCode(400),
+            ok: false,
+            message: "parameters.mode must be \"passthrough\" or \"processed\"".to_string(),
+        };
+    };
+
+    match node.encoded_flow_mut(&flow_name) {
+        Ok(flow) => {
+            flow.set_mode(mode);
+            ControlOutcome {
+                status: StatusCode(200),
+                ok: true,
+                message: format!(
+                    "splice to {} requested for flow '{}'",
+                    mode.as_str(),
+                    flow_name
+                ),
+            }
+        }
+        Err(err) => ControlOutcome {
+            status: StatusCode(404),
+            ok: false,
+            message: err.to_string(),
+        },
+    }
+}
+
+/// Off-Air in zwei Schritten: ohne Token wird ein Token ausgegeben,
+/// mit `parameters.token` wird der Wechsel bestätigt.
+fn dispatch_off_air(
+    node: &mut AirliftNode,
+    target: Option<String>,
+    parameters: Option<serde_json::Value>,
+) -> ControlOutcome {
+    let flow_name = match target {
+        Some(name) => name,
+        None => {
+            return ControlOutcome {
+                status: StatusCode(400),
+                ok: false,
+                message: "missing target".to_string(),
+            }
+        }
+    };
+
+    let flow = match node.flow_mut(&flow_name) {
+        Ok(flow) => flow,
+        Err(err) => {
+            return ControlOutcome {
+                status: StatusCode(404),
+                ok: false,
+                message: err.to_string(),
+            }
+        }
+    };
+
+    let token = parameters
+        .as_ref()
+        .and_then(|params| params.get("token"))
+        .and_then(|token| token.as_str());
+
+    match token {
+        None => ControlOutcome {
+            status: StatusCode(202),
+            ok: true,
+            message: flow.request_off_air(),
+        },
+        Some(token) => match flow.confirm_off_air(token) {
+            Ok(()) => ControlOutcome {
+                status: StatusCode(200),
+                ok: true,
+                message: format!("flow '{}' off air", flow_name),
+            },
+            Err(err) => ControlOutcome {
+                status: StatusCode(409),
+                ok: false,
+                message: err.to_string(),
+            },
+        },
+    }
+}
+
 fn apply_config_from_state(
     node: &mut AirliftNode,
     config: &Arc<Mutex<Config>>,
@@ -234,6 +827,28 @@ fn apply_config_from_state(
     }
 }
 
+/// Baut die Audio-Komponenten aus der aktuellen Konfiguration auf; erst wenn
+/// das klappt, wird der Crash-Zähler zurückgesetzt.
+fn dispatch_safe_mode_exit(node: &mut AirliftNode, config: &Arc<Mutex<Config>>) -> ControlOutcome {
+    if !safe_mode::is_active() {
+        return ControlOutcome {
+            status: StatusCode(409),
+            ok: false,
+            message: "node is not in safe mode".to_string(),
+        };
+    }
+    let outcome = apply_config_from_state(node, config);
+    if outcome.ok {
+        safe_mode::leave();
+        log::info!("[safe-mode] left safe mode, configuration applied");
+        return ControlOutcome {
+            message: "safe mode left, configuration applied".to_string(),
+            ..outcome
+        };
+    }
+    outcome
+}
+
 fn apply_config_from_toml(
     node: &mut AirliftNode,
     config: &Arc<Mutex<Config>>,
@@ -250,7 +865,7 @@ fn apply_config_from_toml(
         }
     };
 
-    let parsed: Config = match toml::from_str(&toml_payload) {
+    let parsed = match Config::from_toml(&toml_payload) {
         Ok(config) => config,
         Err(err) => {
             return ControlOutcome {
@@ -261,7 +876,16 @@ fn apply_config_from_toml(
         }
     };
 
-    if let Err(err) = configurator::apply_config(node, &parsed) {
+    // Im Safe Mode nur prüfen und übernehmen; angewendet wird mit safe_mode.exit
+    let in_safe_mode = safe_mode::is_active();
+    let result = if in_safe_mode {
+        parsed
+            .validate()
+            .and_then(|_| configurator::validate_config_capabilities(&parsed))
+    } else {
+        configurator::apply_config(node, &parsed)
+    };
+    if let Err(err) = result {
         return ControlOutcome {
             status: StatusCode(422),
             ok: false,
@@ -272,6 +896,8 @@ fn apply_config_from_toml(
     match config.lock() {
         Ok(mut guard) => {
             *guard = parsed;
+            // Offene Patches gegen die alte Config kollidieren
+            config_revisions().replace_all();
         }
         Err(_) => {
             return ControlOutcome {
@@ -285,7 +911,11 @@ fn apply_config_from_toml(
     ControlOutcome {
         status: StatusCode(200),
         ok: true,
-        message: "configuration imported".to_string(),
+        message: if in_safe_mode {
+            "configuration imported (safe mode: not applied until safe_mode.exit)".to_string()
+        } else {
+            "configuration imported".to_string()
+        },
     }
 }
 
@@ -0,0 +1,90 @@
+// src/api/debug.rs
+//
+// `/api/debug/capture`: zeitlich begrenzter Debug-Capture eines Flows
+// (siehe `core::debug_capture`). Die Zieldatei wählt der Node selbst.
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::Deserialize;
+use tiny_http::{Header, Method, Request, Response, StatusCode};
+
+use crate::config::units::parse_duration;
+use crate::core::debug_capture::{debug_capture, DebugCaptureRequest};
+use crate::core::AirliftNode;
+
+const DEFAULT_DURATION: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Deserialize)]
+struct CaptureRequest {
+    flow: String,
+    /// z. B. "10m"; Standard 5 Minuten
+    duration: Option<String>,
+    #[serde(default = "default_verbose")]
+    verbose: bool,
+}
+
+fn default_verbose() -> bool {
+    true
+}
+
+fn respond(req: Request, status: u16, body: serde_json::Value) {
+    let response = Response::from_string(body.to_string())
+        .with_status_code(StatusCode(status))
+        .with_header(Header::from_bytes("Content-Type", "application/json").unwrap());
+    let _ = req.respond(response);
+}
+
+/// GET = Status, POST = starten, DELETE = vorzeitig beenden.
+pub fn handle_capture_request(mut req: Request, node: Arc<Mutex<AirliftNode>>) {
+    match req.method() {
+        Method::Get => {
+            let status = debug_capture().status();
+            respond(req, 200, serde_json::json!({ "capture": status }));
+        }
+        Method::Delete => match debug_capture().stop() {
+            Some(status) => respond(req, 200, serde_json::json!({ "capture": status })),
+            None => respond(req, 404, serde_json::json!({ "error": "no debug capture running" })),
+        },
+        Method::Post => {
+            let mut raw = String::new();
+            if let Err(err) = req.as_reader().read_to_string(&mut raw) {
+                respond(req, 400, serde_json::json!({ "error": err.to_string() }));
+                return;
+            }
+            let (status, body) = start_capture(&raw, &node);
+            respond(req, status, body);
+        }
+        _ => respond(req, 405, serde_json::json!({ "error": "method not allowed" })),
+    }
+}
+
+fn start_capture(raw: &str, node: &Arc<Mutex<AirliftNode>>) -> (u16, serde_json::Value) {
+    let request = match serde_json::from_str::<CaptureRequest>(raw) {
+        Ok(request) => request,
+        Err(err) => return (400, serde_json::json!({ "error": err.to_string() })),
+    };
+    let duration = match request.duration.as_deref().map(parse_duration).transpose() {
+        Ok(duration) => duration.unwrap_or(DEFAULT_DURATION),
+        Err(err) => return (400, serde_json::json!({ "error": err.to_string() })),
+    };
+    if debug_capture().status().is_some_and(|status| status.active) {
+        return (409, serde_json::json!({ "error": "a debug capture is already running" }));
+    }
+
+    let node = match node.lock() {
+        Ok(node) => node,
+        Err(_) => return (500, serde_json::json!({ "error": "node lock poisoned" })),
+    };
+    match debug_capture().start(
+        &node,
+        DebugCaptureRequest {
+            flow: request.flow,
+            duration,
+            path: None,
+            verbose: request.verbose,
+        },
+    ) {
+        Ok(status) => (200, serde_json::json!({ "capture": status })),
+        Err(err) => (400, serde_json::json!({ "error": format!("{:#}", err) })),
+    }
+}
@@ -0,0 +1,94 @@
+// src/api/events.rs
+//
+// Event-Log: alle Events ab `Info` außer den dichten Pegel-/Messwert-Events
+// landen im Storage-Backend; `GET /api/events` liest sie zurück.
+use std::sync::{Arc, Mutex};
+
+use tiny_http::{Request, StatusCode};
+
+use crate::api::peaks::{query_value, respond_json, respond_storage_error};
+use crate::core::lock::lock_mutex;
+use crate::core::{AirliftNode, Event, EventHandler, EventPriority, EventType};
+use crate::storage::StorageBackend;
+
+pub const DEFAULT_EVENT_LIMIT: usize = 200;
+const MAX_EVENT_LIMIT: usize = 10_000;
+
+pub struct EventHistoryHandler {
+    name: String,
+    storage: Arc<dyn StorageBackend>,
+}
+
+impl EventHistoryHandler {
+    pub fn new(name: impl Into<String>, storage: Arc<dyn StorageBackend>) -> Self {
+        Self {
+            name: name.into(),
+            storage,
+        }
+    }
+}
+
+/// Kommen mehrmals pro Sekunde und Flow; dafür gibt es die Peak-Historie.
+fn is_measurement(event_type: &EventType) -> bool {
+    matches!(
+        event_type,
+        EventType::AudioPeak | EventType::AudioLevelStats | EventType::AnalyzerReading
+    )
+}
+
+impl EventHandler for EventHistoryHandler {
+    fn handle_event(&self, event: &Event) -> anyhow::Result<()> {
+        if is_measurement(&event.event_type) {
+            return Ok(());
+        }
+        self.storage.push_event(event)
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn priority_filter(&self) -> Option<EventPriority> {
+        Some(EventPriority::Info)
+    }
+}
+
+pub fn register_event_history(node: Arc<Mutex<AirliftNode>>, storage: Arc<dyn StorageBackend>) {
+    let handler = Arc::new(EventHistoryHandler::new("api_event_history", storage));
+    let event_bus = {
+        let node = lock_mutex(&node, "api.event_history.node");
+        node.event_bus()
+    };
+    let bus = lock_mutex(&event_bus, "api.event_history.register_handler");
+    if let Err(error) = bus.register_handler(handler) {
+        log::error!("Failed to register event history handler: {}", error);
+    }
+}
+
+/// `GET /api/events?from=<ms>&to=<ms>&limit=<n>` – die jüngsten Events im
+/// Bereich, älteste zuerst. Ohne `from`/`to` gilt alles bis jetzt.
+pub fn handle_events_request(request: Request, storage: &dyn StorageBackend, query: &str) {
+    let parse = |key: &str| query_value(query, key).map(|value| value.parse::<u64>());
+    let (from, to, limit) = match (parse("from"), parse("to"), parse("limit")) {
+        (Some(Err(_)), _, _) | (_, Some(Err(_)), _) | (_, _, Some(Err(_))) => {
+            return respond_json(
+                request,
+                StatusCode(400),
+                serde_json::json!({ "error": "from, to and limit must be integers" }),
+            );
+        }
+        (from, to, limit) => (
+            from.and_then(Result::ok).unwrap_or(0),
+            to.and_then(Result::ok).unwrap_or(u64::MAX),
+            limit.and_then(Result::ok).map(|limit| limit as usize).unwrap_or(DEFAULT_EVENT_LIMIT),
+        ),
+    };
+    if from > to || limit == 0 || limit > MAX_EVENT_LIMIT {
+        let error = format!("need from <= to and 1 <= limit <= {}", MAX_EVENT_LIMIT);
+        return respond_json(request, StatusCode(400), serde_json::json!({ "error": error }));
+    }
+    match storage.events(from, to, limit) {
+        Ok(events) => respond_json(request, StatusCode(200), serde_json::json!({ "events": events })),
+        Err(e) => respond_storage_error(request, e),
+    }
+}
@@ -0,0 +1,184 @@
+// src/api/listeners.rs
+//
+// Binden der HTTP-Listener (API, Monitoring, Audio-HTTP): belegte Ports werden
+// mit Backoff erneut versucht, Port 0 wählt einen freien Port. Die tatsächlich
+// gebundenen Adressen landen in einer globalen Liste (Status, Log).
+use std::fmt;
+use std::io;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use serde::Serialize;
+use tiny_http::Server;
+
+use crate::core::lock::lock_mutex;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BindRetry {
+    /// Versuche insgesamt (mindestens 1)
+    pub attempts: u32,
+    /// Wartezeit vor dem zweiten Versuch, verdoppelt sich danach
+    pub initial_backoff: Duration,
+}
+
+impl Default for BindRetry {
+    fn default() -> Self {
+        Self {
+            attempts: 5,
+            initial_backoff: Duration::from_millis(250),
+        }
+    }
+}
+
+impl BindRetry {
+    /// Genau ein Versuch (Tests, Kommandozeilen-Tools)
+    pub fn once() -> Self {
+        Self {
+            attempts: 1,
+            initial_backoff: Duration::ZERO,
+        }
+    }
+}
+
+/// Ein gebundener Listener, wie er in `/api/status` erscheint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ListenerInfo {
+    /// z. B. "api", "monitoring", "audio"
+    pub component: String,
+    /// Adresse aus der Config (Port evtl. 0)
+    pub configured: String,
+    /// Tatsächlich gebundene Adresse
+    pub address: String,
+    pub port: u16,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BindFailure {
+    pub component: String,
+    pub address: String,
+    pub attempts: u32,
+    pub error: String,
+}
+
+/// Sammelfehler beim Start: listet alle Listener, die nicht binden konnten.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BindReport {
+    pub failures: Vec<BindFailure>,
+}
+
+impl fmt::Display for BindReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} listener(s) failed to bind:", self.failures.len())?;
+        for failure in &self.failures {
+            write!(
+                f,
+                "\n  - {} on {}: {} (after {} attempt(s))",
+                failure.component, failure.address, failure.error, failure.attempts
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for BindReport {}
+
+static LISTENERS: OnceLock<Mutex<Vec<ListenerInfo>>> = OnceLock::new();
+
+fn registry() -> &'static Mutex<Vec<ListenerInfo>> {
+    LISTENERS.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+/// Alle bisher gebundenen Listener des Prozesses.
+pub fn listeners() -> Vec<ListenerInfo> {
+    lock_mutex(registry(), "listeners.list").clone()
+}
+
+fn is_addr_in_use(error: &(dyn std::error::Error + Send + Sync + 'static)) -> bool {
+    error
+        .downcast_ref::<io::Error>()
+        .is_some_and(|error| error.kind() == io::ErrorKind::AddrInUse)
+}
+
+/// Bindet `address`; nur bei belegtem Port wird mit Backoff erneut versucht,
+/// andere Fehler (ungültige Adresse, fehlende Rechte) scheitern sofort.
+pub fn bind_http(component: &str, address: &str, retry: BindRetry) -> Result<Server, BindFailure> {
+    let attempts = retry.attempts.max(1);
+    let mut backoff = retry.initial_backoff;
+    let mut attempt = 1;
+    let error = loop {
+        match Server::http(address) {
+            Ok(server) => {
+                register(component, address, &server);
+                return Ok(server);
+            }
+            Err(error) if attempt < attempts && is_addr_in_use(error.as_ref()) => {
+                log::warn!(
+                    "[{}] {} in use, retrying in {} ms ({}/{})",
+                    component,
+                    address,
+                    backoff.as_millis(),
+                    attempt,
+                    attempts
+                );
+                std::thread::sleep(backoff);
+                backoff *= 2;
+                attempt += 1;
+            }
+            Err(error) => break error,
+        }
+    };
+    Err(BindFailure {
+        component: component.to_string(),
+        address: address.to_string(),
+        attempts: attempt,
+        error: error.to_string(),
+    })
+}
+
+/// Bindet alle `(component, address)`-Paare. Schlägt einer fehl, werden die
+/// übrigen trotzdem versucht und alle Fehler gemeinsam gemeldet; bereits
+/// gebundene Server werden dann wieder geschlossen.
+pub fn bind_all(targets: &[(String, String)], retry: BindRetry) -> Result<Vec<Server>, BindReport> {
+    let mut servers = Vec::new();
+    let mut failures = Vec::new();
+    for (component, address) in targets {
+        match bind_http(component, address, retry) {
+            Ok(server) => servers.push(server),
+            Err(failure) => failures.push(failure),
+        }
+    }
+    if failures.is_empty() {
+        return Ok(servers);
+    }
+    for server in &servers {
+        unregister(server);
+    }
+    Err(BindReport { failures })
+}
+
+fn bound_addr(server: &Server) -> Option<std::net::SocketAddr> {
+    server.server_addr().to_ip()
+}
+
+fn register(component: &str, configured: &str, server: &Server) {
+    let Some(addr) = bound_addr(server) else {
+        return;
+    };
+    if configured.ends_with(":0") {
+        log::info!("[{}] {} bound to ephemeral port {}", component, configured, addr.port());
+    }
+    lock_mutex(registry(), "listeners.register").push(ListenerInfo {
+        component: component.to_string(),
+        configured: configured.to_string(),
+        address: addr.to_string(),
+        port: addr.port(),
+    });
+}
+
+fn unregister(server: &Server) {
+    let Some(addr) = bound_addr(server) else {
+        return;
+    };
+    let addr = addr.to_string();
+    lock_mutex(registry(), "listeners.unregister").retain(|listener| listener.address != addr);
+}
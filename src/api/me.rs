@@ -0,0 +1,40 @@
+// src/api/me.rs
+//
+// `/api/me`: was darf der anfragende Client auf diesem Listener? Die Web-UI
+// blendet damit Steuerelemente aus, statt erst nach dem Klick ein 403 zu
+// bekommen. Die Rechte hängen am Listener (Token + `read_only`), siehe `auth`.
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use tiny_http::{Header, Request, Response, StatusCode};
+
+use crate::api::auth::{capabilities, Capability};
+use crate::config::BindConfig;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MeResponse {
+    /// Konfigurierte Adresse des Listeners, über den die Anfrage kam
+    pub listener: String,
+    /// true, wenn der Listener einen Token verlangt (und dieser gepasst hat)
+    pub authenticated: bool,
+    pub read_only: bool,
+    pub capabilities: BTreeMap<&'static str, Capability>,
+}
+
+pub fn describe(bind: &BindConfig) -> MeResponse {
+    MeResponse {
+        listener: bind.address.clone(),
+        authenticated: bind.token.is_some(),
+        read_only: bind.read_only,
+        capabilities: capabilities(bind),
+    }
+}
+
+pub fn handle_me_request(req: Request, bind: &BindConfig) {
+    let body = serde_json::to_string(&describe(bind)).unwrap_or_else(|_| "{}".to_string());
+    let response = Response::from_string(body)
+        .with_status_code(StatusCode(200))
+        .with_header(Header::from_bytes("Content-Type", "application/json").unwrap())
+        .with_header(Header::from_bytes("Cache-Control", "no-store").unwrap());
+    let _ = req.respond(response);
+}
@@ -0,0 +1,149 @@
+// src/api/memory.rs
+//
+// `/api/memory`: geschätzter Speicher je Subsystem (Ring-Buffer, Timeshift,
+// Peak-Historie, Event-Queues) plus RSS des Prozesses. Gedacht zum
+// Dimensionieren kleiner ARM-Boards – die Schätzungen zählen Nutzdaten, nicht
+// Allokator-Overhead; die Differenz zum RSS steht in `unaccounted_bytes`.
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use tiny_http::{Header, Request, Response, StatusCode};
+
+use crate::audio::timeshift;
+use crate::core::buffer_sizing::{buffer_report, BufferFootprint};
+use crate::core::lock::lock_mutex;
+use crate::core::{AirliftNode, Event};
+use crate::storage::StorageBackend;
+
+/// Angenommene Heap-Größe eines Events (Payload, Strings) zusätzlich zur Struct
+const EVENT_HEAP_ESTIMATE_BYTES: usize = 256;
+/// Angenommene Größe einer serialisierten Nachricht in Handler-Queues
+const HANDLER_MESSAGE_ESTIMATE_BYTES: usize = 128;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SubsystemMemory {
+    pub name: String,
+    pub estimated_bytes: u64,
+    /// Anzahl der gezählten Einträge (Buffer, Sessions, Punkte, Events)
+    pub items: usize,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MemoryReport {
+    /// Resident Set Size aus `/proc/self/status`, außerhalb von Linux `null`
+    pub process_rss_bytes: Option<u64>,
+    pub estimated_total_bytes: u64,
+    /// RSS minus Schätzung: Code, Bibliotheken, Allokator, Thread-Stacks
+    pub unaccounted_bytes: Option<u64>,
+    pub subsystems: Vec<SubsystemMemory>,
+    /// Ring-Buffer nach Speicherbedarf absteigend
+    pub ring_buffers: Vec<BufferFootprint>,
+    pub timestamp_ms: u64,
+}
+
+/// RSS des eigenen Prozesses in Bytes (Linux).
+pub fn process_rss_bytes() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    parse_vm_rss(&status)
+}
+
+/// Liest `VmRSS:  12345 kB` aus dem Inhalt von `/proc/<pid>/status`.
+pub fn parse_vm_rss(status: &str) -> Option<u64> {
+    let line = status.lines().find(|line| line.starts_with("VmRSS:"))?;
+    let mut fields = line["VmRSS:".len()..].split_whitespace();
+    let value: u64 = fields.next()?.parse().ok()?;
+    match fields.next() {
+        Some("kB") | None => Some(value * 1024),
+        Some(_) => None,
+    }
+}
+
+pub fn memory_report(node: &AirliftNode, storage: &dyn StorageBackend) -> MemoryReport {
+    let peaks = storage.memory_usage();
+    let buffers = buffer_report(node);
+    let mut ring_buffers = buffers.buffers;
+    ring_buffers.sort_by_key(|buffer| std::cmp::Reverse(buffer.memory_bytes));
+
+    let sessions = timeshift::active_sessions();
+
+    let (queued_events, handler_backlog) = {
+        let bus = node.event_bus();
+        let bus = lock_mutex(&bus, "api.memory.event_bus");
+        (bus.queued_events(), bus.handler_backlog())
+    };
+    let event_bytes = queued_events
+        * (std::mem::size_of::<Event>() + EVENT_HEAP_ESTIMATE_BYTES)
+        + handler_backlog * HANDLER_MESSAGE_ESTIMATE_BYTES;
+
+    let subsystems = vec![
+        SubsystemMemory {
+            name: "ring_buffers".to_string(),
+            estimated_bytes: buffers.total_memory_bytes,
+            items: ring_buffers.len(),
+            detail: "slots x (slot overhead + frame samples x 2 bytes)".to_string(),
+        },
+        SubsystemMemory {
+            name: "timeshift".to_string(),
+            estimated_bytes: (sessions * timeshift::SESSION_MEMORY_BYTES) as u64,
+            items: sessions,
+            detail: format!(
+                "{} active sessions x {} bytes read buffers",
+                sessions,
+                timeshift::SESSION_MEMORY_BYTES
+            ),
+        },
+        SubsystemMemory {
+            name: "peak_history".to_string(),
+            estimated_bytes: peaks.bytes as u64,
+            items: peaks.peak_points,
+            detail: match storage.kind() {
+                "memory" => "peak points in memory incl. flow names".to_string(),
+                other => format!("kept in the {} backend", other),
+            },
+        },
+        SubsystemMemory {
+            name: "event_queues".to_string(),
+            estimated_bytes: event_bytes as u64,
+            items: queued_events + handler_backlog,
+            detail: format!(
+                "{} events on the bus, {} messages in handler queues",
+                queued_events, handler_backlog
+            ),
+        },
+    ];
+
+    let estimated_total_bytes = subsystems.iter().map(|s| s.estimated_bytes).sum();
+    let process_rss_bytes = process_rss_bytes();
+
+    MemoryReport {
+        process_rss_bytes,
+        estimated_total_bytes,
+        unaccounted_bytes: process_rss_bytes.map(|rss| rss.saturating_sub(estimated_total_bytes)),
+        subsystems,
+        ring_buffers,
+        timestamp_ms: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64,
+    }
+}
+
+pub fn handle_memory_request(
+    req: Request,
+    node: Arc<Mutex<AirliftNode>>,
+    storage: &dyn StorageBackend,
+) {
+    let response = match node.lock() {
+        Ok(guard) => {
+            let report = memory_report(&guard, storage);
+            let body = serde_json::to_string(&report).unwrap_or_else(|_| "{}".to_string());
+            Response::from_string(body)
+                .with_status_code(StatusCode(200))
+                .with_header(Header::from_bytes("Content-Type", "application/json").unwrap())
+        }
+        Err(_) => Response::from_string("node lock poisoned").with_status_code(StatusCode(500)),
+    };
+    let _ = req.respond(response);
+}
@@ -0,0 +1,90 @@
+// src/api/metadata.rs
+//
+// `/api/metadata`: Titel/Interpret für laufende Streams (siehe
+// `core::stream_metadata`). Ohne `consumer` gilt der Eintrag für alle Ausgänge.
+use std::sync::{Arc, Mutex};
+
+use serde::Deserialize;
+use tiny_http::{Header, Method, Request, Response, StatusCode};
+
+use crate::core::stream_metadata::{self, StreamMetadata};
+use crate::core::AirliftNode;
+
+#[derive(Deserialize)]
+struct MetadataRequest {
+    consumer: Option<String>,
+    #[serde(flatten)]
+    metadata: StreamMetadata,
+}
+
+fn respond(req: Request, status: u16, body: serde_json::Value) {
+    let response = Response::from_string(body.to_string())
+        .with_status_code(StatusCode(status))
+        .with_header(Header::from_bytes("Content-Type", "application/json").unwrap());
+    let _ = req.respond(response);
+}
+
+/// GET = aktuelle Einträge, POST = setzen, DELETE (`?consumer=`) = entfernen.
+pub fn handle_metadata_request(mut req: Request, node: Arc<Mutex<AirliftNode>>, query: &str) {
+    match req.method() {
+        Method::Get => {
+            let board = stream_metadata::snapshot();
+            respond(req, 200, serde_json::json!({ "metadata": board }));
+        }
+        Method::Delete => {
+            let consumer = query
+                .split('&')
+                .find_map(|pair| pair.strip_prefix("consumer="))
+                .filter(|consumer| !consumer.is_empty());
+            stream_metadata::clear(consumer);
+            respond(
+                req,
+                200,
+                serde_json::json!({ "metadata": stream_metadata::snapshot() }),
+            );
+        }
+        Method::Post => {
+            let mut raw = String::new();
+            if let Err(err) = req.as_reader().read_to_string(&mut raw) {
+                respond(req, 400, serde_json::json!({ "error": err.to_string() }));
+                return;
+            }
+            let (status, body) = set_metadata(&raw, &node);
+            respond(req, status, body);
+        }
+        _ => respond(
+            req,
+            405,
+            serde_json::json!({ "error": "method not allowed" }),
+        ),
+    }
+}
+
+fn set_metadata(raw: &str, node: &Arc<Mutex<AirliftNode>>) -> (u16, serde_json::Value) {
+    let request = match serde_json::from_str::<MetadataRequest>(raw) {
+        Ok(request) => request,
+        Err(err) => return (400, serde_json::json!({ "error": err.to_string() })),
+    };
+    if let Some(consumer) = &request.consumer {
+        let known = match node.lock() {
+            Ok(node) => node
+                .flows()
+                .iter()
+                .any(|flow| flow.consumer_names().contains(consumer)),
+            Err(_) => return (500, serde_json::json!({ "error": "node lock poisoned" })),
+        };
+        if !known {
+            return (
+                404,
+                serde_json::json!({ "error": format!("unknown consumer '{}'", consumer) }),
+            );
+        }
+    }
+    match stream_metadata::set(request.consumer.as_deref(), request.metadata) {
+        Ok(entry) => (
+            200,
+            serde_json::json!({ "consumer": request.consumer, "metadata": entry }),
+        ),
+        Err(err) => (400, serde_json::json!({ "error": format!("{:#}", err) })),
+    }
+}
@@ -3,16 +3,32 @@ use std::thread;
 
 use tiny_http::{Method, Response, Server, StatusCode};
 
-use crate::config::Config;
+use crate::config::{BindConfig, Config};
 use crate::core::AirliftNode;
 use crate::monitoring;
+use crate::storage::StorageBackend;
 
+pub mod aoip;
+pub mod auth;
 pub mod catalog;
+pub mod compare;
 pub mod config;
 pub mod control;
+pub mod debug;
+pub mod events;
+pub mod listeners;
+pub mod me;
+pub mod memory;
+pub mod metadata;
 pub mod peaks;
+pub mod probe;
 pub mod recorder;
+pub mod recordings;
 pub mod status;
+#[cfg(feature = "whep")]
+pub mod whep;
+#[cfg(feature = "whip")]
+pub mod whip;
 pub mod ws;
 
 pub fn start_api_server(
@@ -20,86 +36,232 @@ pub fn start_api_server(
     config: Arc<Mutex<Config>>,
     node: Arc<Mutex<AirliftNode>>,
 ) -> anyhow::Result<()> {
-    let server = Server::http(bind).map_err(|e| anyhow::anyhow!(e))?;
-    log::info!("[api] server on {}", bind);
+    start_api_servers(&[BindConfig::open(bind)], config, node).map(|_| ())
+}
+
+/// Startet einen Listener pro `bind`; alle teilen Node, Config und Storage-Backend.
+/// Liefert die tatsächlich gebundenen Adressen (relevant bei Port 0).
+pub fn start_api_servers(
+    binds: &[BindConfig],
+    config: Arc<Mutex<Config>>,
+    node: Arc<Mutex<AirliftNode>>,
+) -> anyhow::Result<Vec<listeners::ListenerInfo>> {
+    start_api_servers_with_retry(binds, config, node, listeners::BindRetry::default())
+}
 
-    let peak_history = peaks::register_peak_history(node.clone());
+pub fn start_api_servers_with_retry(
+    binds: &[BindConfig],
+    config: Arc<Mutex<Config>>,
+    node: Arc<Mutex<AirliftNode>>,
+    retry: listeners::BindRetry,
+) -> anyhow::Result<Vec<listeners::ListenerInfo>> {
+    // Erst alle binden, damit ein Fehler nicht halb gestartete Listener hinterlässt;
+    // der Fehler nennt alle Adressen, die nicht gebunden werden konnten.
+    let targets: Vec<(String, String)> = binds
+        .iter()
+        .map(|bind| ("api".to_string(), bind.address.clone()))
+        .collect();
+    let servers = listeners::bind_all(&targets, retry)?;
 
-    thread::spawn(move || {
-        for mut req in server.incoming_requests() {
-            let url = req.url().to_string();
-            let (path, query) = url.split_once('?').unwrap_or((&url, ""));
+    let storage = peaks::register_peak_history(node.clone());
+    events::register_event_history(node.clone(), storage.clone());
+    crate::aoip::sap_service().start_discovery();
+    crate::audio::archive::archive_registry()
+        .start_verification_job(crate::audio::archive::DEFAULT_VERIFY_INTERVAL);
 
-            if req.method() == &Method::Get && path.starts_with("/ws/recorder/") {
-                let producer_id = path
-                    .trim_start_matches("/ws/recorder/")
+    let mut bound = Vec::new();
+    for (bind, server) in binds.iter().cloned().zip(servers) {
+        let address = server
+            .server_addr()
+            .to_ip()
+            .map(|addr| addr.to_string())
+            .unwrap_or_else(|| bind.address.clone());
+        log::info!(
+            "[api] server on {}{}{}",
+            address,
+            if bind.token.is_some() { " (token)" } else { "" },
+            if bind.read_only { " (read-only)" } else { "" }
+        );
+        if let Some(addr) = server.server_addr().to_ip() {
+            bound.push(listeners::ListenerInfo {
+                component: "api".to_string(),
+                configured: bind.address.clone(),
+                address,
+                port: addr.port(),
+            });
+        }
+        let config = config.clone();
+        let node = node.clone();
+        let storage = storage.clone();
+        thread::spawn(move || serve(server, bind, config, node, storage));
+    }
+
+    Ok(bound)
+}
+
+fn serve(
+    server: Server,
+    bind: BindConfig,
+    config: Arc<Mutex<Config>>,
+    node: Arc<Mutex<AirliftNode>>,
+    storage: Arc<dyn StorageBackend>,
+) {
+    for mut req in server.incoming_requests() {
+        let url = req.url().to_string();
+        let (path, query) = url.split_once('?').unwrap_or((&url, ""));
+
+        if let Err(status) = auth::authorize(&req, &bind, path, query) {
+            let _ = req.respond(Response::empty(status));
+            continue;
+        }
+
+        if req.method() == &Method::Get && path.starts_with("/ws/recorder/") {
+            let producer_id = path
+                .trim_start_matches("/ws/recorder/")
+                .to_string();
+            ws::handle_recorder_ws_request(req, node.clone(), producer_id);
+            continue;
+        }
+
+        if req.method() == &Method::Get && path == "/ws/control" {
+            ws::handle_control_ws_request(req, config.clone(), node.clone(), bind.read_only);
+            continue;
+        }
+
+        if req.method() == &Method::Get && path == "/ws" {
+            ws::handle_ws_request(req, node.clone());
+            continue;
+        }
+
+        if req.method() == &Method::Get && path.starts_with("/ws/compare/") {
+            let flow_name = path.trim_start_matches("/ws/compare/").to_string();
+            ws::handle_compare_ws_request(req, node.clone(), flow_name);
+            continue;
+        }
+
+        if req.method() == &Method::Get && path.starts_with("/ws/echo/") {
+            let session_id = path.trim_start_matches("/ws/echo/").to_string();
+            ws::handle_echo_ws_request(req, node.clone(), session_id);
+            continue;
+        }
+
+        #[cfg(feature = "whip")]
+        if path.starts_with("/whip/") {
+            // Antwort erst nach ICE-Gathering, nicht im Accept-Loop blockieren
+            let path = path.to_string();
+            thread::spawn(move || whip::handle_whip_request(req, &path));
+            continue;
+        }
+
+        #[cfg(feature = "whep")]
+        if path.starts_with("/whep/") {
+            let path = path.to_string();
+            thread::spawn(move || whep::handle_whep_request(req, &path));
+            continue;
+        }
+
+        match (req.method(), path) {
+            (&Method::Get, "/health") => {
+                monitoring::handle_health_request(req, node.clone());
+                continue;
+            }
+            (&Method::Get, "/metrics") => {
+                monitoring::handle_metrics_request(req, node.clone());
+                continue;
+            }
+            (&Method::Post, "/api/config") => {
+                config::handle_config_request(req, config.clone(), node.clone());
+                continue;
+            }
+            (&Method::Get, "/api/status") => {
+                status::handle_status_request(req, config.clone(), node.clone());
+                continue;
+            }
+            (&Method::Get, "/api/memory") => {
+                memory::handle_memory_request(req, node.clone(), storage.as_ref());
+                continue;
+            }
+            (&Method::Get, "/api/peaks") => {
+                peaks::handle_peaks_request(req, storage.as_ref());
+                continue;
+            }
+            (&Method::Get, "/api/history") => {
+                peaks::handle_history_request(
+                    req,
+                    storage.as_ref(),
+                    if query.is_empty() { None } else { Some(query) },
+                );
+                continue;
+            }
+            (&Method::Get, "/api/events") => {
+                events::handle_events_request(req, storage.as_ref(), query);
+                continue;
+            }
+            (&Method::Post, "/api/control") => {
+                control::handle_control_request(req, config.clone(), node.clone());
+                continue;
+            }
+            (&Method::Post, "/api/probe") => {
+                // Eigener Thread: die Probe darf bis zum Timeout blockieren
+                thread::spawn(move || probe::handle_probe_request(req));
+                continue;
+            }
+            (&Method::Post, "/api/recorder/start") => {
+                recorder::handle_recorder_start(req, node.clone());
+                continue;
+            }
+            (&Method::Post, _) if path.starts_with("/api/recorder/stop/") => {
+                recorder::handle_recorder_stop(req, node.clone());
+                continue;
+            }
+            (&Method::Get | &Method::Post, "/api/recordings/verify") => {
+                // Hashen großer Archive dauert, nicht im Accept-Loop
+                thread::spawn(move || recordings::handle_verify_request(req));
+                continue;
+            }
+            (&Method::Get, "/api/recordings") => {
+                recordings::handle_list_request(req, storage.as_ref());
+                continue;
+            }
+            (&Method::Get, _) if path.starts_with("/api/recordings/") && path.ends_with("/waveform") => {
+                // Fehlende Kacheln werden erst berechnet
+                let id = path
+                    .trim_start_matches("/api/recordings/")
+                    .trim_end_matches("/waveform")
                     .to_string();
-                ws::handle_recorder_ws_request(req, node.clone(), producer_id);
-                continue;
-            }
-
-            if req.method() == &Method::Get && path == "/ws" {
-                ws::handle_ws_request(req, node.clone());
-                continue;
-            }
-
-            if req.method() == &Method::Get && path.starts_with("/ws/echo/") {
-                let session_id = path.trim_start_matches("/ws/echo/").to_string();
-                ws::handle_echo_ws_request(req, node.clone(), session_id);
-                continue;
-            }
-
-            match (req.method(), path) {
-                (&Method::Get, "/health") => {
-                    monitoring::handle_health_request(req, node.clone());
-                    continue;
-                }
-                (&Method::Get, "/metrics") => {
-                    monitoring::handle_metrics_request(req, node.clone());
-                    continue;
-                }
-                (&Method::Post, "/api/config") => {
-                    config::handle_config_request(req, config.clone());
-                    continue;
-                }
-                (&Method::Get, "/api/status") => {
-                    status::handle_status_request(req, node.clone());
-                    continue;
-                }
-                (&Method::Get, "/api/peaks") => {
-                    peaks::handle_peaks_request(req, peak_history.clone());
-                    continue;
-                }
-                (&Method::Get, "/api/history") => {
-                    peaks::handle_history_request(
-                        req,
-                        peak_history.clone(),
-                        if query.is_empty() { None } else { Some(query) },
-                    );
-                    continue;
-                }
-                (&Method::Post, "/api/control") => {
-                    control::handle_control_request(req, config.clone(), node.clone());
-                    continue;
-                }
-                (&Method::Post, "/api/recorder/start") => {
-                    recorder::handle_recorder_start(req, node.clone());
-                    continue;
-                }
-                (&Method::Post, _) if path.starts_with("/api/recorder/stop/") => {
-                    recorder::handle_recorder_stop(req, node.clone());
-                    continue;
-                }
-                (&Method::Get, "/api/catalog") => {
-                    catalog::handle_catalog_request(req, node.clone());
-                    continue;
-                }
-                _ => {
-                    let _ = req.respond(Response::empty(StatusCode(404)));
-                }
+                let query = query.to_string();
+                let storage = storage.clone();
+                thread::spawn(move || recordings::handle_waveform_request(req, storage.as_ref(), &id, &query));
+                continue;
+            }
+            (&Method::Get, "/api/devices/aoip") => {
+                aoip::handle_aoip_devices_request(req);
+                continue;
+            }
+            (&Method::Get, _) if path.starts_with("/api/compare/") => {
+                let flow_name = path.trim_start_matches("/api/compare/").to_string();
+                compare::handle_compare_request(req, node.clone(), &flow_name);
+                continue;
+            }
+            (_, "/api/metadata") => {
+                metadata::handle_metadata_request(req, node.clone(), query);
+                continue;
+            }
+            (_, "/api/debug/capture") => {
+                debug::handle_capture_request(req, node.clone());
+                continue;
+            }
+            (&Method::Get, "/api/me") => {
+                me::handle_me_request(req, &bind);
+                continue;
+            }
+            (&Method::Get, "/api/catalog") => {
+                catalog::handle_catalog_request(req, node.clone());
+                continue;
+            }
+            _ => {
+                let _ = req.respond(Response::empty(StatusCode(404)));
             }
         }
-    });
-
-    Ok(())
+    }
 }
@@ -1,83 +1,35 @@
-use std::collections::VecDeque;
 use std::sync::{Arc, Mutex};
 
 use serde::Serialize;
 use tiny_http::{Header, Request, Response, StatusCode};
 
 use crate::core::lock::lock_mutex;
-use crate::core::{AirliftNode, EventHandler, EventPriority, EventType};
-
-const PEAK_HISTORY_RETENTION_MS: u64 = 24 * 60 * 60 * 1000;
-
-#[derive(Debug, Clone, Serialize)]
-pub struct PeakPoint {
-    pub ts: u64,
-    pub peak_l: f32,
-    pub peak_r: f32,
-    pub silence: bool,
-    pub flow: String,
-}
-
-#[derive(Debug)]
-pub struct PeakHistory {
-    points: VecDeque<PeakPoint>,
-}
-
-impl PeakHistory {
-    pub fn new() -> Self {
-        Self {
-            points: VecDeque::new(),
-        }
-    }
-
-    pub fn push(&mut self, point: PeakPoint) {
-        self.points.push_back(point);
-        self.trim_to_retention();
-    }
-
-    pub fn range(&self, from: u64, to: u64, flow: Option<&str>) -> Vec<PeakPoint> {
-        self.points
-            .iter()
-            .filter(|point| point.ts >= from && point.ts <= to)
-            .filter(|point| flow.map(|filter| point.flow == filter).unwrap_or(true))
-            .cloned()
-            .collect()
-    }
-
-    pub fn buffer_range(&self, flow: Option<&str>) -> Option<(u64, u64)> {
-        let mut iter = self
-            .points
-            .iter()
-            .filter(|point| flow.map(|filter| point.flow == filter).unwrap_or(true));
-        let start = iter.next()?.ts;
-        let end = iter.last().map(|point| point.ts).unwrap_or(start);
-        Some((start, end))
-    }
-
-    fn trim_to_retention(&mut self) {
-        if let Some(latest) = self.points.back().map(|point| point.ts) {
-            let min_ts = latest.saturating_sub(PEAK_HISTORY_RETENTION_MS);
-            while let Some(front) = self.points.front() {
-                if front.ts < min_ts {
-                    self.points.pop_front();
-                } else {
-                    break;
-                }
-            }
-        }
-    }
-}
-
+use crate::core::{AirliftNode, EventHandler, EventPriority, EventType, PeakTier};
+use crate::storage::StorageBackend;
+
+pub use crate::storage::{PeakBucket, PeakHistory, PeakPoint};
+
+/// Ab so vielen Rohpunkten wird ohne `bucket_ms` automatisch aggregiert.
+pub const DEFAULT_MAX_POINTS: usize = 2000;
+const MAX_POINTS_LIMIT: usize = 100_000;
+/// Bucket-Größen für die automatische Aggregation; feste Stufen, damit
+/// benachbarte Abfragen dieselben Bucket-Grenzen bekommen.
+const BUCKET_STEPS_MS: [u64; 15] = [
+    100, 250, 500, 1_000, 2_000, 5_000, 10_000, 30_000, 60_000, 120_000, 300_000, 600_000,
+    1_800_000, 3_600_000, 7_200_000,
+];
+
+/// Schreibt die 1-s-Statistik jedes Flows ins Storage-Backend.
 pub struct PeakHistoryHandler {
     name: String,
-    history: Arc<Mutex<PeakHistory>>,
+    storage: Arc<dyn StorageBackend>,
 }
 
 impl PeakHistoryHandler {
-    pub fn new(name: impl Into<String>, history: Arc<Mutex<PeakHistory>>) -> Self {
+    pub fn new(name: impl Into<String>, storage: Arc<dyn StorageBackend>) -> Self {
         Self {
             name: name.into(),
-            history,
+            storage,
         }
     }
 }
@@ -85,6 +37,10 @@ impl PeakHistoryHandler {
 impl EventHandler for PeakHistoryHandler {
     fn handle_event(&self, event: &crate::core::Event) -> anyhow::Result<()> {
         let payload = &event.payload;
+        // Nur die 1-s-Statistik, Meter und Aggregate wären zu dicht bzw. zu grob
+        if payload.get("rate").and_then(|value| value.as_str()) != Some(PeakTier::Stats.as_str()) {
+            return Ok(());
+        }
         let timestamp = payload.get("timestamp").and_then(normalize_timestamp_ms);
         let peaks = payload.get("peaks").and_then(|value| value.as_array());
         let flow = payload
@@ -103,16 +59,13 @@ impl EventHandler for PeakHistoryHandler {
             .and_then(|value| value.as_bool())
             .unwrap_or(false);
 
-        let mut history = lock_mutex(&self.history, "api.peak_history.push");
-        history.push(PeakPoint {
+        self.storage.push_peak(PeakPoint {
             ts: timestamp,
             peak_l,
             peak_r,
             silence,
             flow: flow.to_string(),
-        });
-
-        Ok(())
+        })
     }
 
     fn name(&self) -> &str {
@@ -124,15 +77,17 @@ impl EventHandler for PeakHistoryHandler {
     }
 
     fn event_type_filter(&self) -> Option<Vec<EventType>> {
-        Some(vec![EventType::AudioPeak])
+        Some(vec![EventType::AudioLevelStats])
     }
 }
 
-pub fn register_peak_history(node: Arc<Mutex<AirliftNode>>) -> Arc<Mutex<PeakHistory>> {
-    let history = Arc::new(Mutex::new(PeakHistory::new()));
+/// Hängt die Peak-Historie an den Event-Bus; liefert das Backend, aus dem
+/// die API-Handler lesen.
+pub fn register_peak_history(node: Arc<Mutex<AirliftNode>>) -> Arc<dyn StorageBackend> {
+    let storage = crate::storage::backend();
     let handler = Arc::new(PeakHistoryHandler::new(
         "api_peak_history",
-        history.clone(),
+        storage.clone(),
     ));
     let event_bus = {
         let node = lock_mutex(&node, "api.peak_history.node");
@@ -144,10 +99,10 @@ pub fn register_peak_history(node: Arc<Mutex<AirliftNode>>) -> Arc<Mutex<PeakHis
         log::error!("Failed to register peak history handler: {}", error);
     }
 
-    history
+    storage
 }
 
-pub fn handle_peaks_request(request: Request, history: Arc<Mutex<PeakHistory>>) {
+pub fn handle_peaks_request(request: Request, storage: &dyn StorageBackend) {
     #[derive(Serialize)]
     struct PeaksResponse {
         ok: bool,
@@ -157,9 +112,9 @@ pub fn handle_peaks_request(request: Request, history: Arc<Mutex<PeakHistory>>)
 
     let flow = request.url().split('?').nth(1).and_then(|query| query_value(query, "flow"));
 
-    let range = {
-        let history = lock_mutex(&history, "api.peak_history.range");
-        history.buffer_range(flow)
+    let range = match storage.peak_range(flow) {
+        Ok(range) => range,
+        Err(e) => return respond_storage_error(request, e),
     };
 
     let response = PeaksResponse {
@@ -171,25 +126,66 @@ pub fn handle_peaks_request(request: Request, history: Arc<Mutex<PeakHistory>>)
     respond_json(request, StatusCode(200), response);
 }
 
-pub fn handle_history_request(
-    request: Request,
-    history: Arc<Mutex<PeakHistory>>,
-    query: Option<&str>,
-) {
-    let Some((from, to, flow)) = parse_history_query(query) else {
+/// Kleinste Stufe aus `BUCKET_STEPS_MS`, mit der `from..=to` in höchstens
+/// `max_points` Buckets passt.
+pub fn auto_bucket_ms(from: u64, to: u64, max_points: usize) -> u64 {
+    let span = to.saturating_sub(from).max(1);
+    let wanted = span.div_ceil(max_points.max(1) as u64);
+    BUCKET_STEPS_MS
+        .iter()
+        .copied()
+        .find(|step| *step >= wanted)
+        .unwrap_or_else(|| wanted.div_ceil(3_600_000) * 3_600_000)
+}
+
+pub fn handle_history_request(request: Request, storage: &dyn StorageBackend, query: Option<&str>) {
+    let Some(params) = parse_history_query(query) else {
         let _ = request.respond(Response::empty(StatusCode(400)));
         return;
     };
-
-    let points = {
-        let history = lock_mutex(&history, "api.peak_history.query");
-        history.range(from, to, flow)
+    let HistoryQuery {
+        from,
+        to,
+        flow,
+        bucket_ms,
+        max_points,
+    } = params;
+
+    // Ohne `bucket_ms` nur aggregieren, wenn der Bereich zu viele Rohpunkte hat
+    let bucket_ms = match bucket_ms {
+        Some(bucket_ms) => bucket_ms,
+        None => match storage.peak_count(from, to, flow) {
+            Ok(count) if count > max_points => auto_bucket_ms(from, to, max_points),
+            Ok(_) => 0,
+            Err(e) => return respond_storage_error(request, e),
+        },
     };
+    let result = if bucket_ms == 0 {
+        storage
+            .peaks(from, to, flow)
+            .map(|points| serde_json::to_value(points).unwrap_or_default())
+    } else {
+        storage
+            .aggregate_peaks(from, to, flow, bucket_ms)
+            .map(|buckets| serde_json::to_value(buckets).unwrap_or_default())
+    };
+    match result {
+        Ok(body) => respond_json(request, StatusCode(200), body),
+        Err(e) => respond_storage_error(request, e),
+    }
+}
 
-    respond_json(request, StatusCode(200), points);
+/// Backend nicht erreichbar oder Abfrage fehlgeschlagen.
+pub fn respond_storage_error(request: Request, error: anyhow::Error) {
+    log::warn!("[storage] query failed: {:#}", error);
+    respond_json(
+        request,
+        StatusCode(503),
+        serde_json::json!({ "error": format!("storage: {:#}", error) }),
+    );
 }
 
-fn respond_json<T: Serialize>(request: Request, status: StatusCode, payload: T) {
+pub(crate) fn respond_json<T: Serialize>(request: Request, status: StatusCode, payload: T) {
     let body = serde_json::to_string(&payload).unwrap_or_else(|_| "{}".to_string());
     let response = Response::from_string(body).with_status_code(status).with_header(
         Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..]).unwrap(),
@@ -197,7 +193,16 @@ fn respond_json<T: Serialize>(request: Request, status: StatusCode, payload: T)
     let _ = request.respond(response);
 }
 
-fn parse_history_query(query: Option<&str>) -> Option<(u64, u64, Option<&str>)> {
+struct HistoryQuery<'a> {
+    from: u64,
+    to: u64,
+    flow: Option<&'a str>,
+    /// `0` erzwingt Rohpunkte
+    bucket_ms: Option<u64>,
+    max_points: usize,
+}
+
+fn parse_history_query(query: Option<&str>) -> Option<HistoryQuery<'_>> {
     let query = query?;
     let from = query_value(query, "from")?.parse::<u64>().ok()?;
     let to = query_value(query, "to")?.parse::<u64>().ok()?;
@@ -205,10 +210,28 @@ fn parse_history_query(query: Option<&str>) -> Option<(u64, u64, Option<&str>)>
         return None;
     }
     let flow = query_value(query, "flow");
-    Some((from, to, flow))
+    let bucket_ms = match query_value(query, "bucket_ms") {
+        Some(value) => Some(value.parse::<u64>().ok()?),
+        None => None,
+    };
+    let max_points = match query_value(query, "max_points") {
+        Some(value) => value.parse::<usize>().ok().filter(|n| (1..=MAX_POINTS_LIMIT).contains(n))?,
+        None => DEFAULT_MAX_POINTS,
+    };
+    // Schutz vor Millionen Mini-Buckets
+    if bucket_ms.is_some_and(|ms| ms > 0 && (to - from) / ms > MAX_POINTS_LIMIT as u64) {
+        return None;
+    }
+    Some(HistoryQuery {
+        from,
+        to,
+        flow,
+        bucket_ms,
+        max_points,
+    })
 }
 
-fn query_value<'a>(query: &'a str, key: &str) -> Option<&'a str> {
+pub(crate) fn query_value<'a>(query: &'a str, key: &str) -> Option<&'a str> {
     query.split('&').find_map(|pair| {
         let mut iter = pair.splitn(2, '=');
         let name = iter.next()?;
@@ -0,0 +1,63 @@
+use std::path::Path;
+use std::time::Duration;
+
+use serde::Deserialize;
+use tiny_http::{Header, Method, Request, Response, StatusCode};
+
+use crate::decoders::probe::{probe_path, probe_url};
+
+const DEFAULT_TIMEOUT_MS: u64 = 5_000;
+const MAX_TIMEOUT_MS: u64 = 30_000;
+
+#[derive(Deserialize)]
+struct ProbeRequest {
+    url: Option<String>,
+    path: Option<String>,
+    timeout_ms: Option<u64>,
+}
+
+/// `POST /api/probe` – prüft eine Quelle, ohne einen Producer anzulegen.
+pub fn handle_probe_request(mut req: Request) {
+    let (status, body) = if req.method() != &Method::Post {
+        (405, serde_json::json!({ "error": "method not allowed" }))
+    } else {
+        let mut raw = String::new();
+        match req.as_reader().read_to_string(&mut raw) {
+            Err(err) => (400, serde_json::json!({ "error": err.to_string() })),
+            Ok(_) => match serde_json::from_str::<ProbeRequest>(&raw) {
+                Err(err) => (400, serde_json::json!({ "error": err.to_string() })),
+                Ok(probe) => run_probe(probe),
+            },
+        }
+    };
+
+    let response = Response::from_string(body.to_string())
+        .with_status_code(StatusCode(status))
+        .with_header(Header::from_bytes("Content-Type", "application/json").unwrap());
+    let _ = req.respond(response);
+}
+
+fn run_probe(probe: ProbeRequest) -> (u16, serde_json::Value) {
+    let timeout =
+        Duration::from_millis(probe.timeout_ms.unwrap_or(DEFAULT_TIMEOUT_MS).min(MAX_TIMEOUT_MS));
+
+    let result = match (probe.url, probe.path) {
+        (Some(url), None) => probe_url(&url, timeout).map_err(|e| (502, e)),
+        (None, Some(path)) => probe_path(Path::new(&path)).map_err(|e| (404, e)),
+        _ => {
+            return (
+                400,
+                serde_json::json!({ "error": "exactly one of 'url' or 'path' is required" }),
+            )
+        }
+    };
+
+    match result {
+        Ok(result) if result.format.is_none() => (
+            422,
+            serde_json::json!({ "error": "unknown audio format", "result": result }),
+        ),
+        Ok(result) => (200, serde_json::to_value(result).unwrap_or_default()),
+        Err((status, err)) => (status, serde_json::json!({ "error": format!("{:#}", err) })),
+    }
+}
@@ -0,0 +1,82 @@
+use tiny_http::{Header, Method, Request, Response, StatusCode};
+
+use crate::audio::archive::archive_registry;
+use crate::audio::waveform;
+use crate::storage::StorageBackend;
+
+/// `GET /api/recordings/verify` – letzter Prüfbericht (prüft beim ersten Aufruf).
+/// `POST /api/recordings/verify` – prüft sofort alle Archive.
+pub fn handle_verify_request(req: Request) {
+    let registry = archive_registry();
+    let report = match (req.method(), registry.last_report()) {
+        (&Method::Get, Some(report)) => report,
+        _ => registry.verify_all(),
+    };
+
+    let body = serde_json::json!({
+        "clean": report.is_clean(),
+        "archives": registry.dirs(),
+        "report": report,
+    });
+    let _ = req.respond(
+        Response::from_string(body.to_string())
+            .with_status_code(StatusCode(200))
+            .with_header(Header::from_bytes("Content-Type", "application/json").unwrap()),
+    );
+}
+
+fn respond_json(req: Request, status: u16, body: serde_json::Value) {
+    let _ = req.respond(
+        Response::from_string(body.to_string())
+            .with_status_code(StatusCode(status))
+            .with_header(Header::from_bytes("Content-Type", "application/json").unwrap()),
+    );
+}
+
+/// `GET /api/recordings` – alle Aufnahmen aus dem Index des Storage-Backends.
+pub fn handle_list_request(req: Request, storage: &dyn StorageBackend) {
+    match storage.recordings() {
+        Ok(recordings) => respond_json(req, 200, serde_json::json!({ "recordings": recordings })),
+        Err(e) => respond_json(req, 503, serde_json::json!({ "error": format!("storage: {:#}", e) })),
+    }
+}
+
+/// `GET /api/recordings/<id>/waveform?zoom=<0..10>&format=dat|json`
+pub fn handle_waveform_request(req: Request, storage: &dyn StorageBackend, id: &str, query: &str) {
+    let mut zoom = 0;
+    let mut json = false;
+    for (key, value) in query.split('&').filter_map(|pair| pair.split_once('=')) {
+        match key {
+            "zoom" => match value.parse::<u32>() {
+                Ok(value) if value <= waveform::MAX_ZOOM => zoom = value,
+                _ => {
+                    let error = format!("zoom must be between 0 and {}", waveform::MAX_ZOOM);
+                    return respond_json(req, 400, serde_json::json!({ "error": error }));
+                }
+            },
+            "format" => json = value == "json",
+            _ => {}
+        }
+    }
+
+    let recording = match storage.find_recording(id) {
+        Ok(Some(recording)) => recording,
+        Ok(None) => return respond_json(req, 404, serde_json::json!({ "error": "unknown recording" })),
+        Err(e) => return respond_json(req, 503, serde_json::json!({ "error": format!("storage: {:#}", e) })),
+    };
+    let tile = match waveform::load_tile(std::path::Path::new(&recording.path), zoom) {
+        Ok(tile) => tile,
+        Err(e) => return respond_json(req, 500, serde_json::json!({ "error": format!("{:#}", e) })),
+    };
+
+    if json {
+        return respond_json(req, 200, tile.to_json());
+    }
+    let _ = req.respond(
+        Response::from_data(tile.to_dat())
+            .with_status_code(StatusCode(200))
+            .with_header(Header::from_bytes("Content-Type", "application/octet-stream").unwrap())
+            // Fertige Aufnahmen ändern sich nicht
+            .with_header(Header::from_bytes("Cache-Control", "max-age=86400").unwrap()),
+    );
+}
@@ -4,18 +4,49 @@ use std::time::{SystemTime, UNIX_EPOCH};
 
 use tiny_http::{Header, Method, Request, Response, StatusCode};
 
-use crate::core::AirliftNode;
+use crate::api::listeners::{self, ListenerInfo};
+use crate::config::Config;
+use crate::core::buffer_sizing::{buffer_report, BufferReport};
+use crate::core::host_telemetry::HostSnapshot;
+use crate::core::safe_mode::safe_mode_status;
+use crate::core::scheduler::{scheduler, ScheduleStatus};
+use crate::core::{
+    AirliftNode, AnalyzerReadings, AutomationLane, ConnectionState, ConsumerTargetStatus, DrainStatus, EncodedFlowStatus, ErrorInfo, FailoverStatus,
+    FlowLevels, OnAirInterlock, OnAirState, ProcessingLoad, SafeModeStatus, WatchdogEntryStatus,
+};
+use crate::decoders::DecoderStats;
 
 #[derive(Serialize)]
 pub struct StatusResponse {
     pub running: bool,
     pub uptime_seconds: u64,
+    /// Globaler Bypass aktiv
+    pub bypass: bool,
     pub producers: Vec<ProducerInfo>,
+    pub standby_producers: Vec<StandbyProducerInfo>,
+    /// Failover-Gruppen mit aktivem Producer und Zustand der Mitglieder
+    pub failover: Vec<FailoverStatus>,
     pub flows: Vec<FlowInfo>,
+    /// Passthrough-Flows (kodierte Frames, kein Decode)
+    pub encoded_flows: Vec<EncodedFlowStatus>,
+    /// Nach einem Reload auslaufende Ausgänge mit verbliebenen Zuhörern
+    pub draining: Vec<DrainStatus>,
+    /// Load, CPU, Temperaturen und Platten des Rechners (`[telemetry]`)
+    pub host: Option<HostSnapshot>,
     pub ringbuffer: RingBufferInfo,
+    /// Buffer-Größen mit Worst-Case-Latenz und Speicherbedarf
+    pub buffers: BufferReport,
     pub modules: Vec<ModuleInfo>,
     pub inactive_modules: Vec<InactiveModule>,
     pub configuration_issues: Vec<ConfigurationIssue>,
+    /// Gebundene HTTP-Listener mit tatsächlichem Port (auch bei `port = 0`)
+    pub listeners: Vec<ListenerInfo>,
+    /// Zeitpläne mit nächster/letzter Ausführung
+    pub schedules: Vec<ScheduleStatus>,
+    /// Ausgefallene Producer mit Fehlerklasse und Neustart-Plan
+    pub watchdog: Vec<WatchdogEntryStatus>,
+    /// Start nach Crash-Schleife ohne Audio-Komponenten, mit letztem Grund
+    pub safe_mode: SafeModeStatus,
     pub timestamp_ms: u64,
 }
 
@@ -23,18 +54,72 @@ pub struct StatusResponse {
 pub struct ProducerInfo {
     pub name: String,
     pub running: bool,
+    /// Per `producer.pause` angehalten (läuft weiter, liefert keine Frames)
+    pub paused: bool,
     pub connected: bool,
     pub samples_processed: u64,
     pub errors: u64,
+    /// Letzter Fehler mit Kategorie und Retry-Flag
+    pub last_error: Option<ErrorInfo>,
+    /// Decoder-Zähler paketbasierter Quellen (z. B. Opus über WHIP)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decoder: Option<DecoderStats>,
+    /// Registry-Slot (`producer:<slot>`), unterscheidet sich nach einem
+    /// Standby-Wechsel vom Namen
+    pub slot: String,
+    /// Config-Pfad, z. B. `producers.mic`
+    pub config_path: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct StandbyProducerInfo {
+    pub name: String,
+    pub slot: String,
+    pub config_path: Option<String>,
 }
 
 #[derive(Serialize)]
 pub struct FlowInfo {
     pub name: String,
     pub running: bool,
+    pub config_path: Option<String>,
+    pub processors: Vec<FlowModuleInfo>,
+    pub consumers: Vec<FlowModuleInfo>,
     pub input_buffer_levels: Vec<usize>,
     pub processor_buffer_levels: Vec<usize>,
     pub output_buffer_level: usize,
+    pub on_air: OnAirState,
+    pub interlocks: Vec<OnAirInterlock>,
+    /// Wirksamer Bypass (Flow-Schalter oder globaler Bypass)
+    pub bypass: bool,
+    pub automation: Vec<AutomationLane>,
+    /// Echtzeitfaktor der Processor-Kette und Überlastzustand
+    pub load: ProcessingLoad,
+    /// Letzte Pegel-Fenster je Zeitauflösung
+    pub levels: FlowLevels,
+    /// Letzter Messwert je Analyzer-Abgriff (`config.analyzers`)
+    #[serde(skip_serializing_if = "AnalyzerReadings::is_empty")]
+    pub analyzers: AnalyzerReadings,
+}
+
+/// Processor/Consumer innerhalb eines Flows,
+/// `config_path` zeigt auf den Eintrag in der Flow-Definition
+/// (z. B. `flows.program.processors[2]`).
+#[derive(Serialize)]
+pub struct FlowModuleInfo {
+    pub name: String,
+    pub running: bool,
+    pub errors: u64,
+    pub config_path: Option<String>,
+    /// Nur Consumer mit Reconnect (z. B. Icecast)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connection: Option<ConnectionState>,
+    /// Nur `fanout`: Zustand je Ziel
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub targets: Vec<ConsumerTargetStatus>,
+    /// Nur Processors mit Messwerten (z. B. `phase_meter`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metrics: Option<serde_json::Value>,
 }
 
 #[derive(Serialize)]
@@ -90,15 +175,30 @@ pub struct ConfigurationIssue {
     pub message: String,
 }
 
-pub fn handle_status_request(mut req: Request, node: Arc<Mutex<AirliftNode>>) {
+pub fn handle_status_request(
+    mut req: Request,
+    config: Arc<Mutex<Config>>,
+    node: Arc<Mutex<AirliftNode>>,
+) {
     if req.method() != &Method::Get {
         let _ = req.respond(Response::empty(StatusCode(405)));
         return;
     }
 
+    // Config-Snapshot vor dem Node-Lock (Control lockt Node -> Config)
+    let config = match config.lock() {
+        Ok(guard) => guard.clone(),
+        Err(_) => {
+            let _ = req.respond(
+                Response::from_string("config lock poisoned").with_status_code(StatusCode(500)),
+            );
+            return;
+        }
+    };
+
     let response = match node.lock() {
         Ok(guard) => {
-            let status = build_status(&guard);
+            let status = build_status(&guard, &config);
             let body = serde_json::to_string(&status).unwrap_or_else(|_| "{}".to_string());
             Response::from_string(body)
                 .with_status_code(StatusCode(200))
@@ -110,35 +210,102 @@ pub fn handle_status_request(mut req: Request, node: Arc<Mutex<AirliftNode>>) {
     let _ = req.respond(response);
 }
 
-fn build_status(node: &AirliftNode) -> StatusResponse {
+pub(crate) fn build_status(node: &AirliftNode, config: &Config) -> StatusResponse {
     let node_status = node.status();
 
     let producers = node
         .producers()
         .iter()
-        .map(|producer| {
+        .zip(node.producer_slots())
+        .map(|(producer, slot)| {
             let status = producer.status();
             ProducerInfo {
                 name: producer.name().to_string(),
                 running: status.running,
+                paused: status.paused,
                 connected: status.connected,
                 samples_processed: status.samples_processed,
                 errors: status.errors,
+                last_error: node.producer_error(producer.name()),
+                decoder: status.decoder,
+                slot: slot.clone(),
+                config_path: config
+                    .producers
+                    .contains_key(producer.name())
+                    .then(|| format!("producers.{}", producer.name())),
             }
         })
         .collect::<Vec<_>>();
 
+    let standby_producers = node
+        .standby_producers()
+        .into_iter()
+        .map(|(name, slot)| StandbyProducerInfo {
+            config_path: config
+                .producers
+                .contains_key(&name)
+                .then(|| format!("producers.{}", name)),
+            name,
+            slot,
+        })
+        .collect::<Vec<_>>();
+
     let flows = node
         .flows()
         .iter()
         .map(|flow| {
             let status = flow.status();
+            let flow_cfg = config.flows.get(&flow.name);
+            let processors = flow
+                .processor_names()
+                .into_iter()
+                .zip(status.processor_status.iter())
+                .map(|(name, processor)| FlowModuleInfo {
+                    config_path: flow_cfg
+                        .and_then(|cfg| cfg.processors.iter().position(|p| *p == name))
+                        .map(|index| format!("flows.{}.processors[{}]", flow.name, index)),
+                    name,
+                    running: processor.running,
+                    errors: processor.errors,
+                    connection: None,
+                    targets: Vec::new(),
+                    metrics: processor.metrics.clone(),
+                })
+                .collect();
+            let consumers = flow
+                .consumer_names()
+                .into_iter()
+                .zip(status.consumer_status.iter())
+                .zip(flow.consumer_targets())
+                .map(|((name, consumer), targets)| FlowModuleInfo {
+                    config_path: flow_cfg
+                        .and_then(|cfg| cfg.outputs.iter().position(|o| *o == name))
+                        .map(|index| format!("flows.{}.outputs[{}]", flow.name, index)),
+                    name,
+                    running: consumer.running,
+                    errors: consumer.errors,
+                    connection: consumer.connection.clone(),
+                    targets,
+                    metrics: None,
+                })
+                .collect();
+
             FlowInfo {
                 name: flow.name.clone(),
                 running: status.running,
+                config_path: flow_cfg.map(|_| format!("flows.{}", flow.name)),
+                processors,
+                consumers,
                 input_buffer_levels: status.input_buffer_levels,
                 processor_buffer_levels: status.processor_buffer_levels,
                 output_buffer_level: status.output_buffer_level,
+                on_air: status.on_air,
+                interlocks: status.interlocks,
+                bypass: status.bypass,
+                automation: status.automation,
+                load: status.load,
+                levels: status.levels,
+                analyzers: status.analyzers,
             }
         })
         .collect::<Vec<_>>();
@@ -149,8 +316,8 @@ fn build_status(node: &AirliftNode) -> StatusResponse {
     for name in registry.list() {
         if let Some(buffer) = registry.get(&name) {
             let stats = buffer.stats();
-            ringbuffer_fill += stats.current_frames as u64;
-            ringbuffer_capacity += stats.capacity as u64;
+            ringbuffer_fill += stats.current_frames.get() as u64;
+            ringbuffer_capacity += stats.capacity.get() as u64;
         }
     }
 
@@ -162,15 +329,30 @@ fn build_status(node: &AirliftNode) -> StatusResponse {
     StatusResponse {
         running: node_status.running,
         uptime_seconds: node_status.uptime_seconds,
+        bypass: node.is_bypassed(),
         producers,
+        standby_producers,
+        failover: node.failover_status(),
         flows,
+        encoded_flows: node
+            .encoded_flows()
+            .iter()
+            .map(|flow| flow.status())
+            .collect(),
+        draining: node.draining_consumers(),
+        host: crate::core::host_telemetry::latest(),
         ringbuffer: RingBufferInfo {
             fill: ringbuffer_fill,
             capacity: ringbuffer_capacity,
         },
+        buffers: buffer_report(node),
         modules: Vec::new(),
         inactive_modules: Vec::new(),
         configuration_issues: Vec::new(),
+        listeners: listeners::listeners(),
+        schedules: scheduler().status(),
+        watchdog: node.watchdog_status(),
+        safe_mode: safe_mode_status(),
         timestamp_ms,
     }
 }
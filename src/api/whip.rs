@@ -0,0 +1,79 @@
+use std::io::Read;
+
+use tiny_http::{Header, Method, Request, Response, StatusCode};
+
+use crate::producers::whip::whip_endpoint;
+
+fn header(req: &Request, name: &'static str) -> Option<String> {
+    req.headers()
+        .iter()
+        .find(|h| h.field.equiv(name))
+        .map(|h| h.value.as_str().to_string())
+}
+
+fn respond_error(req: Request, status: u16, message: &str) {
+    let _ = req.respond(
+        Response::from_string(message.to_string())
+            .with_status_code(StatusCode(status))
+            .with_header(Header::from_bytes("Content-Type", "text/plain").unwrap()),
+    );
+}
+
+/// `POST /whip/<producer>` – SDP-Offer rein, SDP-Answer raus (201 + Location).
+/// `DELETE /whip/<producer>/<session>` – beendet die Session.
+pub fn handle_whip_request(mut req: Request, path: &str) {
+    let mut parts = path.trim_start_matches("/whip/").splitn(2, '/');
+    let producer = parts.next().unwrap_or_default().to_string();
+    let session = parts.next().map(str::to_string);
+
+    let Some(endpoint) = whip_endpoint(&producer) else {
+        respond_error(req, 404, "unknown WHIP endpoint");
+        return;
+    };
+    let authorization = header(&req, "Authorization");
+
+    match (req.method().clone(), session) {
+        (Method::Post, None) => {
+            let content_type = header(&req, "Content-Type").unwrap_or_default();
+            if !content_type.starts_with("application/sdp") {
+                respond_error(req, 415, "expected application/sdp");
+                return;
+            }
+            let mut offer = String::new();
+            if let Err(e) = req.as_reader().read_to_string(&mut offer) {
+                respond_error(req, 400, &e.to_string());
+                return;
+            }
+
+            match endpoint.offer(offer, authorization.as_deref()) {
+                Ok((session_id, answer)) => {
+                    let location = format!("/whip/{}/{}", producer, session_id);
+                    let _ = req.respond(
+                        Response::from_string(answer)
+                            .with_status_code(StatusCode(201))
+                            .with_header(
+                                Header::from_bytes("Content-Type", "application/sdp").unwrap(),
+                            )
+                            .with_header(Header::from_bytes("Location", location).unwrap()),
+                    );
+                }
+                Err(e) => {
+                    log::warn!("[api] WHIP offer for '{}' rejected: {}", producer, e);
+                    respond_error(req, e.status(), &e.to_string());
+                }
+            }
+        }
+        (Method::Delete, Some(session_id)) => {
+            match endpoint.delete(&session_id, authorization.as_deref()) {
+                Ok(true) => {
+                    let _ = req.respond(Response::empty(StatusCode(200)));
+                }
+                Ok(false) => respond_error(req, 404, "unknown WHIP session"),
+                Err(e) => respond_error(req, e.status(), &e.to_string()),
+            }
+        }
+        // Trickle-ICE/ICE-Restart (PATCH) wird nicht unterstützt
+        (Method::Patch, Some(_)) => respond_error(req, 405, "trickle ICE not supported"),
+        _ => respond_error(req, 405, "method not allowed"),
+    }
+}
@@ -2,11 +2,15 @@ use std::io::{Read, Write};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::Duration;
 
 use crossbeam_channel::{unbounded, Receiver, Sender};
 use tiny_http::{Header, ReadWrite, Request, Response, StatusCode};
 
+use crate::api::config::handle_config_message;
 use crate::api::recorder::{register_echo_client, unregister_echo_client};
+use crate::config::{config_revisions, Config};
+use crate::core::compare::CompareSession;
 use crate::core::lock::lock_mutex;
 use crate::core::{timestamp, AirliftNode, Event, EventHandler, EventPriority, EventType, PcmFrame};
 use crate::producers::ws::WsHandle;
@@ -14,6 +18,8 @@ use crate::producers::ws::WsHandle;
 const WEBSOCKET_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
 static WS_HANDLER_COUNTER: AtomicU64 = AtomicU64::new(1);
 const RECORDER_SAMPLE_RATE: u32 = 48_000;
+const COMPARE_POLL_INTERVAL: Duration = Duration::from_millis(20);
+const COMPARE_LEVELS_INTERVAL: Duration = Duration::from_secs(1);
 
 pub fn handle_ws_request(request: Request, node: Arc<Mutex<AirliftNode>>) {
     thread::spawn(move || {
@@ -74,6 +80,64 @@ pub fn handle_ws_request(request: Request, node: Arc<Mutex<AirliftNode>>) {
     });
 }
 
+/// Kontrollkanal für Config-Änderungen mit Revisionen; Anfrage/Antwort als
+/// JSON-Textframes (siehe `api::config::handle_config_message`).
+pub fn handle_control_ws_request(
+    request: Request,
+    config: Arc<Mutex<Config>>,
+    node: Arc<Mutex<AirliftNode>>,
+    read_only: bool,
+) {
+    thread::spawn(move || {
+        if !is_websocket_request(&request) {
+            let _ = request.respond(Response::empty(StatusCode(400)));
+            return;
+        }
+
+        let Some(key) = websocket_key(&request) else {
+            let _ = request.respond(Response::empty(StatusCode(400)));
+            return;
+        };
+
+        let accept = websocket_accept_key(&key);
+        let response = Response::empty(StatusCode(101))
+            .with_header(make_header("Upgrade", "websocket"))
+            .with_header(make_header("Connection", "Upgrade"))
+            .with_header(make_header("Sec-WebSocket-Accept", &accept));
+
+        let mut stream = request.upgrade("websocket", response);
+        if let Err(error) = serve_control_messages(&mut stream, &config, &node, read_only) {
+            log::info!("Control websocket closed: {}", error);
+        }
+    });
+}
+
+fn serve_control_messages(
+    stream: &mut dyn ReadWrite,
+    config: &Mutex<Config>,
+    node: &Mutex<AirliftNode>,
+    read_only: bool,
+) -> std::io::Result<()> {
+    loop {
+        let frame = read_ws_frame(stream)?;
+        match frame.opcode {
+            0x1 if frame.fin => {
+                let text = String::from_utf8_lossy(&frame.payload);
+                let reply =
+                    handle_config_message(&text, config, node, config_revisions(), read_only);
+                write_text_frame(stream, reply.to_string().as_bytes())?;
+            }
+            0x1 => {
+                log::warn!("Control websocket received fragmented frame");
+                return Ok(());
+            }
+            0x8 => return Ok(()),
+            0x9 => write_ws_frame(stream, 0xA, &frame.payload)?,
+            _ => log::debug!("Control websocket ignoring opcode {}", frame.opcode),
+        }
+    }
+}
+
 pub fn handle_recorder_ws_request(
     request: Request,
     _node: Arc<Mutex<AirliftNode>>,
@@ -203,6 +267,81 @@ fn stream_echo_frames(
     Ok(())
 }
 
+/// Vorher/Nachher-Stream eines Flows: Binär-Frames mit zeitgleichen,
+/// lautheitsangeglichenen Paaren (`ComparePair::encode`), dazu etwa einmal
+/// pro Sekunde die Pegel als JSON-Textframe.
+pub fn handle_compare_ws_request(
+    request: Request,
+    node: Arc<Mutex<AirliftNode>>,
+    flow_name: String,
+) {
+    thread::spawn(move || {
+        if !is_websocket_request(&request) {
+            let _ = request.respond(Response::empty(StatusCode(400)));
+            return;
+        }
+
+        let session = {
+            let node = lock_mutex(&node, "ws.compare");
+            node.flows()
+                .iter()
+                .find(|flow| flow.name == flow_name)
+                .map(CompareSession::open)
+        };
+        let mut session = match session {
+            Some(Ok(session)) => session,
+            Some(Err(error)) => {
+                log::warn!("Compare websocket '{}' rejected: {}", flow_name, error);
+                let _ = request.respond(Response::empty(StatusCode(503)));
+                return;
+            }
+            None => {
+                let _ = request.respond(Response::empty(StatusCode(404)));
+                return;
+            }
+        };
+
+        let key = match websocket_key(&request) {
+            Some(key) => key,
+            None => {
+                let _ = request.respond(Response::empty(StatusCode(400)));
+                return;
+            }
+        };
+
+        let accept = websocket_accept_key(&key);
+        let response = Response::empty(StatusCode(101))
+            .with_header(make_header("Upgrade", "websocket"))
+            .with_header(make_header("Connection", "Upgrade"))
+            .with_header(make_header("Sec-WebSocket-Accept", &accept));
+
+        let mut stream = request.upgrade("websocket", response);
+        log::info!("Compare websocket connected for flow '{}'", flow_name);
+
+        if let Err(error) = stream_compare_pairs(&mut stream, &mut session) {
+            log::info!("Compare websocket '{}' closed: {}", flow_name, error);
+        }
+    });
+}
+
+fn stream_compare_pairs(
+    stream: &mut dyn ReadWrite,
+    session: &mut CompareSession,
+) -> std::io::Result<()> {
+    let mut last_levels = std::time::Instant::now();
+    loop {
+        for pair in session.poll() {
+            write_ws_frame(stream, 0x2, &pair.encode())?;
+        }
+        if last_levels.elapsed() >= COMPARE_LEVELS_INTERVAL {
+            last_levels = std::time::Instant::now();
+            let levels = serde_json::to_vec(&session.levels()).unwrap_or_default();
+            write_text_frame(stream, &levels)?;
+        }
+        thread::sleep(COMPARE_POLL_INTERVAL);
+    }
+}
+
 fn is_websocket_request(request: &Request) -> bool {
     request
         .headers()
@@ -315,6 +454,7 @@ fn read_recorder_frames(
                     samples,
                     sample_rate: RECORDER_SAMPLE_RATE,
                     channels,
+                    metadata: Default::default(),
                 };
 
                 if let Err(error) = handle.push_frame(frame) {
@@ -495,6 +635,10 @@ impl EventHandler for WsEventHandler {
     fn event_type_filter(&self) -> Option<Vec<EventType>> {
         Some(vec![EventType::AudioPeak])
     }
+
+    fn queued(&self) -> usize {
+        self.sender.len()
+    }
 }
 
 /* ===================== SHA1 ===================== */
@@ -633,7 +777,7 @@ impl Sha1 {
     }
 }
 
-fn base64_encode(data: &[u8]) -> String {
+pub(crate) fn base64_encode(data: &[u8]) -> String {
     const TABLE: &[u8; 64] =
         b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
     let mut out = String::with_capacity(((data.len() + 2) / 3) * 4);
@@ -4,19 +4,46 @@ use anyhow::{bail, Context};
 use serde_json::Value;
 
 use crate::app::init::build_plugin_registry;
-use crate::codecs::supported_codecs;
-use crate::config::Config;
+use crate::codecs::{bitrate_range, supported_codecs};
+use crate::config::{Config, ConfigValues, ConsumerConfig};
+use crate::consumers::backup::BackupConfig;
+use crate::consumers::mirror::MirrorConfig;
+use crate::consumers::{
+    Aes67Consumer, BackupConsumer, FanoutConsumer, IcecastConsumer, LinkConsumer, MirrorConsumer,
+};
 use crate::core::consumer::file_writer::FileConsumer;
-use crate::core::{AirliftNode, Flow};
+use crate::core::buffer_sizing::flow_buffer_sizing;
+use crate::core::{
+    AirliftNode, AnalyzerTapConfig, BufferSizing, Consumer, CorrelationScope, FailoverSettings,
+    Flow, PeakRates, ProcessingBudget, Producer, WatermarkConfig,
+};
 use crate::producers;
 
+/// Gelesene `config`-Schlüssel des `sine`-Producers
+pub const SINE_CONFIG_KEYS: &[&str] = &["frequency"];
+
+/// Schlüssel, die `validate_codec_config` und `validate_value_constraints`
+/// bei jedem Modul prüfen; der Strict-Modus lässt sie überall zu.
+pub const VALIDATED_CONFIG_KEYS: &[&str] = &["buffer", "codec", "codec_id", "bitrate", "gain_db"];
+
 pub fn apply_config(node: &mut AirliftNode, config: &Config) -> anyhow::Result<()> {
+    let _correlation = CorrelationScope::begin("config");
     config
         .validate()
         .context("config validation failed before apply")?;
     validate_config_capabilities(config)?;
 
     let was_running = node.is_running();
+    // Ausgänge mit Zuhörern laufen über den Neuaufbau hinweg weiter
+    let drain_grace = match &config.reload {
+        Some(reload) => reload.drain_grace()?,
+        None => crate::core::drain::DEFAULT_DRAIN_GRACE,
+    };
+    let draining = if was_running && !drain_grace.is_zero() {
+        node.begin_draining(drain_grace)
+    } else {
+        0
+    };
     if was_running {
         node.stop()
             .map_err(|e| anyhow::anyhow!("failed to stop node: {}", e))?;
@@ -26,31 +53,26 @@ pub fn apply_config(node: &mut AirliftNode, config: &Config) -> anyhow::Result<(
 
     let plugin_registry = build_plugin_registry();
 
+    // Standby-Producer erst nach allen aktiven anlegen (Slot muss existieren)
+    let mut standby_producers = Vec::new();
+
     for (name, producer_cfg) in &config.producers {
         if !producer_cfg.enabled {
             continue;
         }
 
-        match producer_cfg.producer_type.as_str() {
-            "file" => {
-                let producer = producers::file::FileProducer::new(name, producer_cfg);
-                node.add_producer(Box::new(producer))
-                    .context("failed to add file producer")?;
-            }
+        let producer: Box<dyn Producer> = match producer_cfg.producer_type.as_str() {
+            "file" => Box::new(producers::file::FileProducer::new(name, producer_cfg)),
             #[cfg(feature = "alsa")]
-            "alsa_input" => {
-                let producer = producers::alsa::AlsaProducer::new(name, producer_cfg)
-                    .context("failed to create ALSA input producer")?;
-                node.add_producer(Box::new(producer))
-                    .context("failed to add ALSA input producer")?;
-            }
+            "alsa_input" => Box::new(
+                producers::alsa::AlsaProducer::new(name, producer_cfg)
+                    .context("failed to create ALSA input producer")?,
+            ),
             #[cfg(feature = "alsa")]
-            "alsa_output" => {
-                let producer = producers::alsa::AlsaOutputCapture::new(name, producer_cfg)
-                    .context("failed to create ALSA output capture producer")?;
-                node.add_producer(Box::new(producer))
-                    .context("failed to add ALSA output capture producer")?;
-            }
+            "alsa_output" => Box::new(
+                producers::alsa::AlsaOutputCapture::new(name, producer_cfg)
+                    .context("failed to create ALSA output capture producer")?,
+            ),
             #[cfg(not(feature = "alsa"))]
             "alsa_input" | "alsa_output" => {
                 bail!(
@@ -59,6 +81,66 @@ pub fn apply_config(node: &mut AirliftNode, config: &Config) -> anyhow::Result<(
                     producer_cfg.producer_type
                 );
             }
+            #[cfg(feature = "cpal")]
+            "cpal" => Box::new(
+                producers::cpal::CpalProducer::new(name, producer_cfg)
+                    .context("failed to create cpal input producer")?,
+            ),
+            #[cfg(not(feature = "cpal"))]
+            "cpal" => {
+                bail!(
+                    "producer '{}' uses type 'cpal' but the 'cpal' feature is disabled",
+                    name
+                );
+            }
+            #[cfg(feature = "srt")]
+            "srt" => Box::new(
+                producers::srt::SrtProducer::new(name, producer_cfg)
+                    .context("failed to create SRT producer")?,
+            ),
+            #[cfg(not(feature = "srt"))]
+            "srt" => {
+                bail!(
+                    "producer '{}' uses type 'srt' but SRT support is disabled",
+                    name
+                );
+            }
+            #[cfg(feature = "whip")]
+            "whip" => Box::new(
+                producers::whip::WhipProducer::new(name, producer_cfg)
+                    .context("failed to create WHIP producer")?,
+            ),
+            #[cfg(not(feature = "whip"))]
+            "whip" => {
+                bail!(
+                    "producer '{}' uses type 'whip' but WHIP support is disabled",
+                    name
+                );
+            }
+            "generator" => Box::new(
+                producers::generator::GeneratorProducer::new(name, producer_cfg)
+                    .context("failed to create generator producer")?,
+            ),
+            "pipe" => Box::new(
+                producers::pipe::PipeProducer::new(name, producer_cfg)
+                    .context("failed to create pipe producer")?,
+            ),
+            "airlift_link" => Box::new(
+                producers::link::LinkProducer::new(name, producer_cfg)
+                    .context("failed to create airlift_link producer")?,
+            ),
+            #[cfg(feature = "symphonia")]
+            "mpegts" => Box::new(
+                producers::mpegts::MpegTsProducer::new(name, producer_cfg)
+                    .context("failed to create MPEG-TS producer")?,
+            ),
+            #[cfg(not(feature = "symphonia"))]
+            "mpegts" => {
+                bail!(
+                    "producer '{}' uses type 'mpegts' but the 'symphonia' feature is disabled",
+                    name
+                );
+            }
             "sine" => {
                 let freq: f32 = producer_cfg
                     .config
@@ -67,20 +149,107 @@ pub fn apply_config(node: &mut AirliftNode, config: &Config) -> anyhow::Result<(
                     .map(|f| f as f32)
                     .unwrap_or(440.0);
                 let rate = producer_cfg.sample_rate.unwrap_or(48000);
-                let producer = producers::sine::SineProducer::new(name, freq, rate);
-                node.add_producer(Box::new(producer))
-                    .context("failed to add sine producer")?;
+                Box::new(producers::sine::SineProducer::new(name, freq, rate))
             }
             other => bail!("producer '{}' uses unsupported type '{}'", name, other),
+        };
+
+        match producer_cfg.standby_for() {
+            Some(slot) => standby_producers.push((slot.to_string(), producer)),
+            None => node
+                .add_producer_with_buffer(
+                    producer,
+                    BufferSizing::from_config("producer", name, &producer_cfg.config)?
+                        .unwrap_or_default(),
+                )
+                .with_context(|| format!("failed to add producer '{}'", name))?,
+        }
+
+        // Watermarks hängen am Slot-Buffer, Standbys erben sie vom aktiven Producer
+        if let (None, Some(value)) = (producer_cfg.standby_for(), producer_cfg.config.get("watermark")) {
+            let watermark = WatermarkConfig::from_config("producer", name, value)?;
+            node.set_buffer_watermark(&format!("producer:{}", name), Some(watermark))?;
         }
     }
 
+    for (slot, producer) in standby_producers {
+        let name = producer.name().to_string();
+        node.add_standby_producer(&slot, producer)
+            .with_context(|| format!("failed to add standby producer '{}'", name))?;
+    }
+
+    // Bei Reload auch auf den Standard zurücksetzen, wenn `[startup]` entfernt wurde
+    let readiness_timeout = match &config.startup {
+        Some(startup) => startup.readiness_timeout()?,
+        None => None,
+    };
+    node.set_readiness_timeout(
+        readiness_timeout.unwrap_or(crate::core::readiness::DEFAULT_READINESS_TIMEOUT),
+    );
+    node.set_startup_workers(
+        config
+            .startup
+            .as_ref()
+            .and_then(|startup| startup.workers)
+            .unwrap_or_else(crate::core::parallel::default_workers),
+    );
+
+    node.set_event_dedup_window(match &config.events {
+        Some(events) => events.dedup_window()?,
+        None => crate::core::event_dedup::DEFAULT_DEDUP_WINDOW,
+    });
+
+    node.set_default_analyzer_taps(config.default_analyzer_taps()?);
+    node.set_output_meter(config.output_meter());
+    crate::core::labels::install(config.labels()?);
+
+    crate::core::scheduler::scheduler()
+        .set_entries(crate::core::scheduler::ScheduleEntry::from_configs(config)?);
+
+    for (group_name, group_cfg) in &config.failover {
+        let settings = FailoverSettings::from_config(group_name, group_cfg)?;
+        node.add_failover_group(group_name, &group_cfg.producers, settings)
+            .with_context(|| format!("failed to add failover group '{}'", group_name))?;
+    }
+
     for (flow_name, flow_cfg) in &config.flows {
         if !flow_cfg.enabled {
             continue;
         }
 
-        let mut flow = Flow::new(flow_name);
+        let (internal, output) = flow_buffer_sizing(config, flow_name)?;
+        let mut flow = Flow::with_buffer_sizing(flow_name, internal, output);
+        flow.set_on_air_gpio(flow_cfg.config.get("on_air_gpio").and_then(|v| v.as_str()));
+        flow.set_bypass(flow_cfg.config.get("bypass").and_then(|v| v.as_bool()).unwrap_or(false));
+        if let Some(value) = flow_cfg.config.get("watermark") {
+            flow.set_output_watermark(Some(WatermarkConfig::from_config("flow", flow_name, value)?));
+        }
+        if let Some(value) = flow_cfg.config.get("overload") {
+            flow.set_processing_budget(Some(ProcessingBudget::from_config(
+                flow_name,
+                value,
+                &flow_cfg.processors,
+            )?));
+        }
+        if let Some(value) = flow_cfg.config.get("peak_rates") {
+            flow.set_peak_rates(PeakRates::from_config(flow_name, value)?);
+        }
+        let values = ConfigValues::new("flow", flow_name, &flow_cfg.config);
+        if let Some(batch) = values.f64("batch")? {
+            let max = crate::core::processor::MAX_BATCH_SIZE as f64;
+            flow.set_batch_size(values.check_range("batch", batch.floor(), 1.0, max)? as usize);
+        }
+        flow.set_default_analyzers(
+            flow_cfg.config.get("default_analyzers").and_then(|v| v.as_bool()).unwrap_or(true),
+        );
+        if let Some(value) = flow_cfg.config.get("analyzers") {
+            flow.set_analyzer_taps(AnalyzerTapConfig::from_config(
+                flow_name,
+                value,
+                &flow_cfg.inputs,
+                &flow_cfg.processors,
+            )?);
+        }
 
         for processor_name in &flow_cfg.processors {
             let processor_cfg = config.processors.get(processor_name).with_context(|| {
@@ -94,7 +263,7 @@ pub fn apply_config(node: &mut AirliftNode, config: &Config) -> anyhow::Result<(
                 continue;
             }
 
-            let processor = plugin_registry
+            let mut processor = plugin_registry
                 .create_processor(processor_name, processor_cfg)
                 .with_context(|| {
                     format!(
@@ -102,6 +271,7 @@ pub fn apply_config(node: &mut AirliftNode, config: &Config) -> anyhow::Result<(
                         processor_name, processor_cfg.processor_type
                     )
                 })?;
+            processor.attach_buffer_registry(node.buffer_registry());
             flow.add_processor(processor);
         }
 
@@ -129,24 +299,9 @@ pub fn apply_config(node: &mut AirliftNode, config: &Config) -> anyhow::Result<(
                 continue;
             }
 
-            match consumer_cfg.consumer_type.as_str() {
-                "file" => {
-                    let path = consumer_cfg.path.as_ref().with_context(|| {
-                        format!(
-                            "consumer '{}' in flow '{}' missing output path",
-                            output_name, flow_name
-                        )
-                    })?;
-                    let consumer = Box::new(FileConsumer::new(output_name, path));
-                    node.add_consumer_to_flow(flow_index, consumer)
-                        .context("failed to add consumer to flow")?;
-                }
-                other => bail!(
-                    "consumer '{}' uses unsupported type '{}'",
-                    output_name,
-                    other
-                ),
-            }
+            let consumer = create_consumer(config, flow_name, output_name, consumer_cfg)?;
+            node.add_consumer_to_flow(flow_index, consumer)
+                .context("failed to add consumer to flow")?;
         }
     }
 
@@ -162,6 +317,8 @@ pub fn apply_config(node: &mut AirliftNode, config: &Config) -> anyhow::Result<(
         for input_name in &flow_cfg.inputs {
             let buffer_name = if config.producers.contains_key(input_name) {
                 format!("producer:{}", input_name)
+            } else if config.failover.contains_key(input_name) {
+                format!("failover:{}", input_name)
             } else {
                 input_name.to_string()
             };
@@ -184,10 +341,189 @@ pub fn apply_config(node: &mut AirliftNode, config: &Config) -> anyhow::Result<(
         node.start()
             .map_err(|e| anyhow::anyhow!("failed to start node: {}", e))?;
     }
+    if draining > 0 {
+        node.connect_draining();
+    }
 
     Ok(())
 }
 
+/// Baut einen Consumer aus der Config; `fanout` baut seine Ziele mit.
+pub fn create_consumer(
+    config: &Config,
+    flow_name: &str,
+    name: &str,
+    consumer_cfg: &ConsumerConfig,
+) -> anyhow::Result<Box<dyn Consumer>> {
+    let consumer: Box<dyn Consumer> = match consumer_cfg.consumer_type.as_str() {
+        "file" => {
+            let path = consumer_cfg.path.as_ref().with_context(|| {
+                format!(
+                    "consumer '{}' in flow '{}' missing output path",
+                    name, flow_name
+                )
+            })?;
+            Box::new(FileConsumer::from_config(
+                name,
+                path,
+                &consumer_cfg.config,
+                config.flow_timezone(flow_name)?,
+            )?)
+        }
+        "aes67" => Box::new(
+            Aes67Consumer::new(name, consumer_cfg).context("failed to create AES67 consumer")?,
+        ),
+        "icecast" => Box::new(
+            IcecastConsumer::new(name, consumer_cfg)
+                .context("failed to create Icecast consumer")?,
+        ),
+        "airlift_link" => Box::new(
+            LinkConsumer::new(name, consumer_cfg)
+                .context("failed to create airlift_link consumer")?,
+        ),
+        "rtmp_out" => Box::new(
+            crate::consumers::RtmpOutputConsumer::new(name, consumer_cfg)
+                .context("failed to create RTMP output consumer")?,
+        ),
+        "udp_out" => Box::new(
+            crate::consumers::UdpOutputConsumer::new(name, consumer_cfg)
+                .context("failed to create UDP output consumer")?,
+        ),
+        "pipe" => Box::new(
+            crate::consumers::PipeConsumer::new(name, consumer_cfg)
+                .context("failed to create pipe consumer")?,
+        ),
+        "debug_dump" => Box::new(
+            crate::consumers::DebugDumpConsumer::new(name, consumer_cfg)
+                .context("failed to create debug dump consumer")?,
+        ),
+        "zmq_pub" => Box::new(
+            crate::consumers::ZmqPubConsumer::new(name, flow_name, consumer_cfg)
+                .context("failed to create ZeroMQ PUB consumer")?,
+        ),
+        #[cfg(feature = "srt")]
+        "srt_out" => Box::new(
+            crate::consumers::SrtOutputConsumer::new(name, consumer_cfg)
+                .context("failed to create SRT output consumer")?,
+        ),
+        #[cfg(not(feature = "srt"))]
+        "srt_out" => bail!(
+            "consumer '{}' uses type 'srt_out' but SRT support is disabled",
+            name
+        ),
+        #[cfg(feature = "whep")]
+        "whep" => Box::new(
+            crate::consumers::WhepConsumer::new(name, consumer_cfg)
+                .context("failed to create WHEP consumer")?,
+        ),
+        #[cfg(not(feature = "whep"))]
+        "whep" => bail!(
+            "consumer '{}' uses type 'whep' but WHEP support is disabled",
+            name
+        ),
+        "fanout" => {
+            let mut fanout =
+                FanoutConsumer::new(name, consumer_cfg).context("failed to create fanout")?;
+            for target in fanout.config().targets.clone() {
+                let target_cfg = config.consumers.get(&target).with_context(|| {
+                    format!("fanout '{}' references missing consumer '{}'", name, target)
+                })?;
+                if !target_cfg.enabled {
+                    continue;
+                }
+                if target_cfg.consumer_type == "fanout" {
+                    bail!("fanout '{}' must not target fanout '{}'", name, target);
+                }
+                fanout.add_target(
+                    create_consumer(config, flow_name, &target, target_cfg)
+                        .with_context(|| format!("fanout '{}' target '{}'", name, target))?,
+                );
+            }
+            Box::new(fanout)
+        }
+        other => bail!("consumer '{}' uses unsupported type '{}'", name, other),
+    };
+    let consumer = with_backup(config, flow_name, name, consumer_cfg, consumer)?;
+    if !consumer_cfg.config.contains_key("mirror") {
+        return Ok(consumer);
+    }
+
+    // Spiegelziel: eigener Consumer aus `[consumers]`, sendet parallel
+    let mirror_config = MirrorConfig::from_config(name, consumer_cfg)?;
+    let mirror_cfg = config.consumers.get(&mirror_config.mirror).with_context(|| {
+        format!(
+            "consumer '{}' references missing mirror '{}'",
+            name, mirror_config.mirror
+        )
+    })?;
+    if !mirror_cfg.enabled {
+        log::warn!(
+            "consumer '{}': mirror '{}' is disabled, running without mirror",
+            name,
+            mirror_config.mirror
+        );
+        return Ok(consumer);
+    }
+    if mirror_cfg.config.contains_key("mirror") {
+        bail!(
+            "consumer '{}': mirror '{}' must not have a mirror itself",
+            name,
+            mirror_config.mirror
+        );
+    }
+    let mirror = create_consumer(config, flow_name, &mirror_config.mirror, mirror_cfg)
+        .with_context(|| format!("consumer '{}' mirror '{}'", name, mirror_config.mirror))?;
+    Ok(Box::new(MirrorConsumer::with_config(
+        consumer,
+        mirror,
+        mirror_config,
+    )))
+}
+
+/// Hängt das Ersatzziel aus `config.backup` an, falls vorhanden.
+fn with_backup(
+    config: &Config,
+    flow_name: &str,
+    name: &str,
+    consumer_cfg: &ConsumerConfig,
+    consumer: Box<dyn Consumer>,
+) -> anyhow::Result<Box<dyn Consumer>> {
+    if !consumer_cfg.config.contains_key("backup") {
+        return Ok(consumer);
+    }
+
+    // Ersatzziel: eigener Consumer aus `[consumers]`, läuft nur bei Ausfall
+    let backup_config = BackupConfig::from_config(name, consumer_cfg)?;
+    let backup_cfg = config.consumers.get(&backup_config.backup).with_context(|| {
+        format!(
+            "consumer '{}' references missing backup '{}'",
+            name, backup_config.backup
+        )
+    })?;
+    if !backup_cfg.enabled {
+        log::warn!(
+            "consumer '{}': backup '{}' is disabled, running without backup",
+            name,
+            backup_config.backup
+        );
+        return Ok(consumer);
+    }
+    if backup_cfg.config.contains_key("backup") {
+        bail!(
+            "consumer '{}': backup '{}' must not have a backup itself",
+            name,
+            backup_config.backup
+        );
+    }
+    let backup = create_consumer(config, flow_name, &backup_config.backup, backup_cfg)
+        .with_context(|| format!("consumer '{}' backup '{}'", name, backup_config.backup))?;
+    Ok(Box::new(BackupConsumer::with_config(
+        consumer,
+        backup,
+        backup_config,
+    )))
+}
+
 pub fn validate_config_capabilities(config: &Config) -> anyhow::Result<()> {
     let producer_types = supported_producer_types();
     let processor_types = supported_processor_types();
@@ -202,6 +538,7 @@ pub fn validate_config_capabilities(config: &Config) -> anyhow::Result<()> {
             );
         }
         validate_codec_config(&producer_cfg.config, "producer", name)?;
+        validate_value_constraints(&producer_cfg.config, "producer", name)?;
     }
 
     for (name, processor_cfg) in &config.processors {
@@ -213,6 +550,7 @@ pub fn validate_config_capabilities(config: &Config) -> anyhow::Result<()> {
             );
         }
         validate_codec_config(&processor_cfg.config, "processor", name)?;
+        validate_value_constraints(&processor_cfg.config, "processor", name)?;
     }
 
     for (name, consumer_cfg) in &config.consumers {
@@ -224,40 +562,93 @@ pub fn validate_config_capabilities(config: &Config) -> anyhow::Result<()> {
             );
         }
         validate_codec_config(&consumer_cfg.config, "consumer", name)?;
+        validate_value_constraints(&consumer_cfg.config, "consumer", name)?;
+    }
+
+    for (name, flow_cfg) in &config.flows {
+        BufferSizing::from_config("flow", name, &flow_cfg.config)?;
     }
 
     Ok(())
 }
 
-#[cfg(feature = "alsa")]
-const SUPPORTED_PRODUCER_TYPES: [&str; 4] = ["file", "alsa_input", "alsa_output", "sine"];
-#[cfg(not(feature = "alsa"))]
-const SUPPORTED_PRODUCER_TYPES: [&str; 2] = ["file", "sine"];
-const SUPPORTED_PROCESSOR_TYPES: [&str; 3] = ["passthrough", "gain", "mixer"];
-const SUPPORTED_CONSUMER_TYPES: [&str; 1] = ["file"];
+const SUPPORTED_PRODUCER_TYPES: &[&str] = &[
+    "file",
+    #[cfg(feature = "alsa")]
+    "alsa_input",
+    #[cfg(feature = "alsa")]
+    "alsa_output",
+    #[cfg(feature = "cpal")]
+    "cpal",
+    "sine",
+    "generator",
+    "pipe",
+    "airlift_link",
+    #[cfg(feature = "symphonia")]
+    "mpegts",
+    #[cfg(feature = "srt")]
+    "srt",
+    #[cfg(feature = "whip")]
+    "whip",
+];
+const SUPPORTED_PROCESSOR_TYPES: &[&str] = &[
+    "passthrough",
+    "gain",
+    "mixer",
+    "ident",
+    "silence_detector",
+    "resampler",
+    "channel_mapper",
+    "switcher",
+    "hum_filter",
+    "agc",
+    "phase_meter",
+    "silence_fallback",
+    "fingerprint",
+    "pipe",
+    "level_meter",
+    #[cfg(feature = "wasm")]
+    "wasm",
+];
+const SUPPORTED_CONSUMER_TYPES: &[&str] = &[
+    "file",
+    "aes67",
+    "icecast",
+    "airlift_link",
+    "rtmp_out",
+    "udp_out",
+    "pipe",
+    "zmq_pub",
+    "debug_dump",
+    "fanout",
+    #[cfg(feature = "srt")]
+    "srt_out",
+    #[cfg(feature = "whep")]
+    "whep",
+];
 
 pub(crate) fn supported_producer_type_list() -> &'static [&'static str] {
-    &SUPPORTED_PRODUCER_TYPES
+    SUPPORTED_PRODUCER_TYPES
 }
 
 pub(crate) fn supported_processor_type_list() -> &'static [&'static str] {
-    &SUPPORTED_PROCESSOR_TYPES
+    SUPPORTED_PROCESSOR_TYPES
 }
 
 pub(crate) fn supported_consumer_type_list() -> &'static [&'static str] {
-    &SUPPORTED_CONSUMER_TYPES
+    SUPPORTED_CONSUMER_TYPES
 }
 
 fn supported_producer_types() -> HashSet<&'static str> {
-    SUPPORTED_PRODUCER_TYPES.into_iter().collect()
+    SUPPORTED_PRODUCER_TYPES.iter().copied().collect()
 }
 
 fn supported_processor_types() -> HashSet<&'static str> {
-    SUPPORTED_PROCESSOR_TYPES.into_iter().collect()
+    SUPPORTED_PROCESSOR_TYPES.iter().copied().collect()
 }
 
 fn supported_consumer_types() -> HashSet<&'static str> {
-    SUPPORTED_CONSUMER_TYPES.into_iter().collect()
+    SUPPORTED_CONSUMER_TYPES.iter().copied().collect()
 }
 
 fn validate_codec_config(
@@ -292,6 +683,40 @@ fn validate_codec_config(
     Ok(())
 }
 
+/// Einheiten und Wertebereiche gängiger Schlüssel prüfen, bevor sie tief
+/// in Encodern oder Processors auffallen.
+fn validate_value_constraints(
+    config: &HashMap<String, Value>,
+    module_kind: &str,
+    module_name: &str,
+) -> anyhow::Result<()> {
+    let values = ConfigValues::new(module_kind, module_name, config);
+    BufferSizing::from_config(module_kind, module_name, config)?;
+
+    if let Some(bitrate) = values.bitrate("bitrate")? {
+        let codec = config
+            .get("codec")
+            .or_else(|| config.get("codec_id"))
+            .and_then(|v| v.as_str())
+            .map(|id| id.to_lowercase());
+        let range = codec.and_then(|id| {
+            supported_codecs()
+                .into_iter()
+                .find(|info| format!("{:?}", info.kind).to_lowercase() == id)
+                .and_then(|info| bitrate_range(&info.kind))
+        });
+        if let Some((min, max)) = range {
+            values.check_range("bitrate", bitrate, min, max)?;
+        }
+    }
+
+    if let Some(gain_db) = values.db("gain_db")? {
+        values.check_range("gain_db", gain_db, -60.0, 24.0)?;
+    }
+
+    Ok(())
+}
+
 fn supported_codec_ids() -> HashSet<String> {
     supported_codecs()
         .into_iter()
@@ -0,0 +1,282 @@
+// src/app/dry_run.rs
+//
+// Offline-Probelauf der Processor-Kette eines Flows: dekodiert eine Datei,
+// schickt sie in 20-ms-Frames so schnell wie möglich durch frisch angelegte
+// Processors (gleiche Konfiguration wie im Live-Betrieb) und schreibt das
+// Ergebnis als 16-Bit-WAV. Der Bericht vergleicht Eingang und Ausgang:
+// integrierte Lautheit, höchste Short-Term-Lautheit, True-Peak und ein
+// Histogramm der Pegelabsenkung je 100-ms-Block. Der laufende Node bleibt
+// unberührt.
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{anyhow, Context};
+use serde::Serialize;
+
+use crate::app::init::PluginRegistry;
+use crate::audio::loudness::{IntegratedLoudness, SILENCE_LUFS};
+use crate::audio::true_peak::TruePeakMeter;
+use crate::config::Config;
+use crate::core::processor::Processor;
+use crate::core::ringbuffer::AudioRingBuffer;
+use crate::producers::file::open_audio_file;
+use crate::ring::PcmFrame;
+
+const FRAME_MS: u64 = 20;
+/// Puffer zwischen zwei Processors; reicht für Processors, die je Aufruf
+/// mehrere Frames ausgeben
+const LINK_CAPACITY: usize = 64;
+/// Klassenbreite des Absenkungs-Histogramms
+const REDUCTION_BIN_DB: f32 = 1.0;
+/// Die letzte Klasse sammelt alles ab hier
+const MAX_REDUCTION_DB: f32 = 24.0;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct LevelReport {
+    /// BS.1770 mit absolutem und relativem Gate
+    pub integrated_lufs: Option<f32>,
+    pub max_short_term_lufs: Option<f32>,
+    pub true_peak_dbtp: Option<f32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReductionBin {
+    pub from_db: f32,
+    /// `None` für die offene letzte Klasse
+    pub to_db: Option<f32>,
+    pub blocks: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct GainReductionReport {
+    /// Blöcke, deren Eingang über −70 LUFS liegt
+    pub blocks: u64,
+    pub mean_db: Option<f32>,
+    pub max_db: Option<f32>,
+    pub histogram: Vec<ReductionBin>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DryRunReport {
+    pub flow: String,
+    pub input: String,
+    pub output: String,
+    pub processors: Vec<String>,
+    pub sample_rate: u32,
+    pub channels: u8,
+    pub input_seconds: f64,
+    pub output_seconds: f64,
+    pub before: LevelReport,
+    pub after: LevelReport,
+    pub gain_reduction: GainReductionReport,
+}
+
+/// Lautheit und True-Peak eines Abgriffs.
+#[derive(Default)]
+struct LevelTap {
+    loudness: IntegratedLoudness,
+    true_peak: TruePeakMeter,
+    samples: u64,
+    sample_rate: u32,
+}
+
+impl LevelTap {
+    fn push(&mut self, frame: &PcmFrame) {
+        self.loudness.push(frame);
+        self.true_peak.push(frame);
+        if frame.channels > 0 {
+            self.samples += (frame.samples.len() / frame.channels as usize) as u64;
+        }
+        self.sample_rate = frame.sample_rate;
+    }
+
+    fn seconds(&self) -> f64 {
+        if self.sample_rate == 0 {
+            return 0.0;
+        }
+        self.samples as f64 / self.sample_rate as f64
+    }
+
+    fn report(&self) -> LevelReport {
+        LevelReport {
+            integrated_lufs: self.loudness.integrated(),
+            max_short_term_lufs: self.loudness.max_short_term(),
+            true_peak_dbtp: self.true_peak.dbtp(),
+        }
+    }
+}
+
+/// Absenkung je Block (Eingang − Ausgang, Anhebungen zählen als 0 dB);
+/// Blöcke werden in Reihenfolge einander zugeordnet.
+pub fn gain_reduction(before: &IntegratedLoudness, after: &IntegratedLoudness) -> GainReductionReport {
+    let bins = (MAX_REDUCTION_DB / REDUCTION_BIN_DB) as usize + 1;
+    let mut histogram: Vec<ReductionBin> = (0..bins)
+        .map(|index| ReductionBin {
+            from_db: index as f32 * REDUCTION_BIN_DB,
+            to_db: (index + 1 < bins).then(|| (index + 1) as f32 * REDUCTION_BIN_DB),
+            blocks: 0,
+        })
+        .collect();
+
+    let mut report = GainReductionReport::default();
+    let mut sum = 0.0;
+    for (pre, post) in before.block_loudness().zip(after.block_loudness()) {
+        if pre <= SILENCE_LUFS {
+            continue;
+        }
+        let reduction = (pre - post).max(0.0);
+        let bin = ((reduction / REDUCTION_BIN_DB) as usize).min(bins - 1);
+        histogram[bin].blocks += 1;
+        report.blocks += 1;
+        sum += reduction as f64;
+        report.max_db = Some(report.max_db.map_or(reduction, |max: f32| max.max(reduction)));
+    }
+    if report.blocks > 0 {
+        report.mean_db = Some((sum / report.blocks as f64) as f32);
+    }
+    report.histogram = histogram;
+    report
+}
+
+/// Legt die aktiven Processors des Flows so an, wie der Node es tut.
+pub fn build_chain(
+    config: &Config,
+    flow_name: &str,
+    registry: &PluginRegistry,
+) -> anyhow::Result<Vec<Box<dyn Processor>>> {
+    let flow_cfg = config
+        .flows
+        .get(flow_name)
+        .ok_or_else(|| anyhow!("flow '{}' not found", flow_name))?;
+    let mut chain = Vec::new();
+    for proc_name in &flow_cfg.processors {
+        let proc_cfg = config.processors.get(proc_name).ok_or_else(|| {
+            anyhow!("flow '{}': processor '{}' not configured", flow_name, proc_name)
+        })?;
+        if !proc_cfg.enabled {
+            continue;
+        }
+        chain.push(
+            registry
+                .create_processor(proc_name, proc_cfg)
+                .with_context(|| format!("flow '{}': processor '{}'", flow_name, proc_name))?,
+        );
+    }
+    Ok(chain)
+}
+
+/// `<input>.dryrun.wav` neben der Eingangsdatei.
+pub fn default_output_path(input: &Path) -> PathBuf {
+    let stem = input
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("input");
+    input.with_file_name(format!("{}.dryrun.wav", stem))
+}
+
+/// Lässt `input` durch `chain` laufen und schreibt das Ergebnis nach `output`.
+pub fn run_chain(
+    flow_name: &str,
+    mut chain: Vec<Box<dyn Processor>>,
+    input: &Path,
+    output: &Path,
+) -> anyhow::Result<DryRunReport> {
+    let mut reader = open_audio_file(input)?;
+    let info = reader.info().clone();
+    let channels = info.channels.max(1) as usize;
+    let frame_len = (info.sample_rate as u64 * FRAME_MS / 1000).max(1) as usize * channels;
+
+    let links: Vec<Arc<AudioRingBuffer>> = (0..=chain.len())
+        .map(|_| Arc::new(AudioRingBuffer::new(LINK_CAPACITY)))
+        .collect();
+    let mut before = LevelTap::default();
+    let mut after = LevelTap::default();
+    let mut writer: Option<hound::WavWriter<_>> = None;
+    let mut pending: Vec<i16> = Vec::new();
+    let mut utc_ns = 0u64;
+    let mut finished = false;
+
+    while !finished {
+        match reader.read_block()? {
+            Some(block) => pending.extend_from_slice(&block),
+            None => finished = true,
+        }
+        while pending.len() >= frame_len || (finished && !pending.is_empty()) {
+            let take = frame_len.min(pending.len());
+            let frame = PcmFrame {
+                utc_ns,
+                samples: pending.drain(..take).collect(),
+                sample_rate: info.sample_rate,
+                channels: info.channels,
+                metadata: Default::default(),
+            };
+            utc_ns += (take / channels) as u64 * 1_000_000_000 / info.sample_rate.max(1) as u64;
+            before.push(&frame);
+            links[0].push(frame);
+
+            for (index, processor) in chain.iter_mut().enumerate() {
+                processor
+                    .process(&links[index], &links[index + 1])
+                    .with_context(|| format!("processor '{}' failed", processor.name()))?;
+            }
+
+            while let Some(frame) = links[chain.len()].pop() {
+                after.push(&frame);
+                if writer.is_none() {
+                    let spec = hound::WavSpec {
+                        channels: frame.channels as u16,
+                        sample_rate: frame.sample_rate,
+                        bits_per_sample: 16,
+                        sample_format: hound::SampleFormat::Int,
+                    };
+                    writer = Some(
+                        hound::WavWriter::create(output, spec)
+                            .with_context(|| format!("cannot create {}", output.display()))?,
+                    );
+                }
+                if let Some(writer) = writer.as_mut() {
+                    for sample in &frame.samples {
+                        writer.write_sample(*sample)?;
+                    }
+                }
+            }
+        }
+    }
+    match writer {
+        Some(writer) => writer.finalize()?,
+        None => log::warn!("[dry-run] flow '{}' produced no output", flow_name),
+    }
+
+    Ok(DryRunReport {
+        flow: flow_name.to_string(),
+        input: input.display().to_string(),
+        output: output.display().to_string(),
+        processors: chain.iter().map(|p| p.name().to_string()).collect(),
+        sample_rate: info.sample_rate,
+        channels: info.channels,
+        input_seconds: before.seconds(),
+        output_seconds: after.seconds(),
+        before: before.report(),
+        after: after.report(),
+        gain_reduction: gain_reduction(&before.loudness, &after.loudness),
+    })
+}
+
+/// Probelauf der konfigurierten Kette von `flow_name`.
+pub fn dry_run(
+    config: &Config,
+    flow_name: &str,
+    registry: &PluginRegistry,
+    input: &Path,
+    output: &Path,
+) -> anyhow::Result<DryRunReport> {
+    let chain = build_chain(config, flow_name, registry)?;
+    log::info!(
+        "[dry-run] flow '{}': {} processors, {} -> {}",
+        flow_name,
+        chain.len(),
+        input.display(),
+        output.display()
+    );
+    run_chain(flow_name, chain, input, output)
+}
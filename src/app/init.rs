@@ -4,6 +4,9 @@ use crate::config;
 use crate::core::processor::Processor;
 use crate::processors;
 
+/// Gelesene `config`-Schlüssel des `gain`-Processors
+pub const GAIN_CONFIG_KEYS: &[&str] = &["gain", "gain_db"];
+
 type ProcessorFactory =
     Box<dyn Fn(&str, &config::ProcessorConfig) -> anyhow::Result<Box<dyn Processor>> + Send + Sync>;
 
@@ -37,16 +40,108 @@ impl PluginRegistry {
         });
 
         self.register_processor("gain", |name, cfg| {
-            let gain = cfg
-                .config
-                .get("gain")
-                .and_then(|v| v.as_f64())
-                .unwrap_or(1.0) as f32;
+            let values = config::ConfigValues::new("processor", name, &cfg.config);
+            let gain = match values.db("gain_db")? {
+                Some(db) => config::units::db_to_linear(db),
+                None => values.f64("gain")?.unwrap_or(1.0) as f32,
+            };
             Ok(Box::new(crate::core::processor::basic::Gain::new(
                 name, gain,
             )))
         });
 
+        self.register_processor("ident", |name, cfg| {
+            Ok(Box::new(processors::IdentInjector::from_config(
+                name,
+                &cfg.config,
+            )?))
+        });
+
+        self.register_processor("silence_detector", |name, cfg| {
+            Ok(Box::new(processors::SilenceDetector::from_config(
+                name,
+                &cfg.config,
+            )?))
+        });
+
+        self.register_processor("resampler", |name, cfg| {
+            Ok(Box::new(processors::ResamplerProcessor::from_config(
+                name,
+                &cfg.config,
+            )?))
+        });
+
+        self.register_processor("channel_mapper", |name, cfg| {
+            Ok(Box::new(processors::ChannelMapper::from_config(
+                name,
+                &cfg.config,
+            )?))
+        });
+
+        self.register_processor("switcher", |name, cfg| {
+            Ok(Box::new(processors::Switcher::from_config(
+                name,
+                &cfg.config,
+            )?))
+        });
+
+        self.register_processor("hum_filter", |name, cfg| {
+            Ok(Box::new(processors::HumFilter::from_config(
+                name,
+                &cfg.config,
+            )?))
+        });
+
+        self.register_processor("agc", |name, cfg| {
+            Ok(Box::new(processors::Agc::from_config(
+                name,
+                &cfg.config,
+            )?))
+        });
+
+        self.register_processor("phase_meter", |name, cfg| {
+            Ok(Box::new(processors::PhaseMeter::from_config(
+                name,
+                &cfg.config,
+            )?))
+        });
+
+        self.register_processor("silence_fallback", |name, cfg| {
+            Ok(Box::new(processors::SilenceFallback::from_config(
+                name,
+                &cfg.config,
+            )?))
+        });
+
+        self.register_processor("fingerprint", |name, cfg| {
+            Ok(Box::new(processors::FingerprintProcessor::from_config(
+                name,
+                &cfg.config,
+            )?))
+        });
+
+        self.register_processor("pipe", |name, cfg| {
+            Ok(Box::new(processors::PipeProcessor::from_config(
+                name,
+                &cfg.config,
+            )?))
+        });
+
+        self.register_processor("level_meter", |name, cfg| {
+            Ok(Box::new(processors::LevelMeter::from_config(
+                name,
+                &cfg.config,
+            )?))
+        });
+
+        #[cfg(feature = "wasm")]
+        self.register_processor("wasm", |name, cfg| {
+            Ok(Box::new(processors::WasmProcessor::from_config(
+                name,
+                &cfg.config,
+            )?))
+        });
+
 self.register_processor("mixer", |name, cfg| {
     let mut mixer = processors::Mixer::new(name);
 
@@ -1,2 +1,4 @@
 pub mod configurator;
+pub mod dry_run;
 pub mod init;
+pub mod runtime;
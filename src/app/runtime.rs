@@ -0,0 +1,459 @@
+// src/app/runtime.rs
+//
+// Einbettung als Bibliothek: `NodeRuntime` baut einen Node aus einer
+// `Config`, startet API, Scheduler, Regeln und MQTT und fährt alles in fester
+// Reihenfolge wieder herunter. Das Binary nutzt denselben Weg.
+//
+// Threads und Besitz:
+// - `build` legt nur an (Producer, Flows, Consumer), es läuft noch nichts.
+// - `start` startet die Threads des `AirliftNode` (Producer, Flows,
+//   Consumer, EventBus), die der Node besitzt und in `stop` joint, dazu den
+//   globalen Scheduler, Regel- und MQTT-Thread (Besitz der Runtime) und einen
+//   Supervisor-Thread für Watchdog und Safe-Mode-Stabilität.
+// - Die HTTP-Listener (`api`) laufen bis zum Prozessende; tiny_http lässt
+//   sich nicht sauber anhalten. Wer mehrere Runtimes nacheinander startet,
+//   schaltet sie mit `api(false)` ab.
+// - `wait` blockiert den aufrufenden Thread bis `ShutdownHandle::request`;
+//   Shutdown-Hooks laufen auf dem Thread, der `wait`/`shutdown` aufruft.
+// - HTTP-Client, Storage, State-Store und Telemetrie sind prozessweit
+//   (`install_process_globals`); pro Prozess richtet sie nur eine Runtime ein.
+//
+// Reihenfolge beim Herunterfahren:
+// 1. Hooks `ShutdownPhase::BeforeStop` (Audio läuft noch)
+// 2. Supervisor, Regeln, MQTT und Scheduler anhalten
+// 3. Draining-Ausgänge schließen, Node stoppen (Consumer finalisieren Dateien)
+// 4. Hooks `ShutdownPhase::AfterStop`
+// Innerhalb einer Phase in Registrierungsreihenfolge; ein fehlschlagender Hook
+// hält die übrigen nicht auf.
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Result};
+
+use crate::api::{self, listeners::ListenerInfo};
+use crate::app::configurator;
+use crate::config::{self, Config};
+use crate::core::lock::lock_mutex;
+use crate::core::scheduler::{scheduler, ScheduleEntry};
+use crate::core::{self, safe_mode, AirliftNode};
+use crate::producers::wait::StopWait;
+use crate::storage;
+
+/// Takt des Supervisors (Watchdog, Stabilitäts-Markierung)
+const SUPERVISOR_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Wann ein Shutdown-Hook läuft.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShutdownPhase {
+    /// Audio läuft noch, z. B. letzte Ansage, Status an ein Leitsystem
+    BeforeStop,
+    /// Node gestoppt, Aufnahmen abgeschlossen, z. B. Upload, Aufräumen
+    AfterStop,
+}
+
+type ShutdownHook = Box<dyn FnOnce() -> Result<()> + Send>;
+
+struct RegisteredHook {
+    phase: ShutdownPhase,
+    name: String,
+    hook: ShutdownHook,
+}
+
+/// Fordert das Herunterfahren an, z. B. aus einem Signal-Handler. Klonbar
+/// und von jedem Thread aus nutzbar.
+#[derive(Clone)]
+pub struct ShutdownHandle {
+    requested: Arc<AtomicBool>,
+    wait: Arc<StopWait>,
+}
+
+impl ShutdownHandle {
+    fn new() -> Self {
+        Self {
+            requested: Arc::new(AtomicBool::new(false)),
+            wait: Arc::new(StopWait::new()),
+        }
+    }
+
+    pub fn request(&self) {
+        self.requested.store(true, Ordering::SeqCst);
+        self.wait.notify_all();
+    }
+
+    pub fn is_requested(&self) -> bool {
+        self.requested.load(Ordering::SeqCst)
+    }
+
+    fn wait_timeout(&self, timeout: Duration) {
+        if !self.is_requested() {
+            self.wait.wait_timeout(timeout);
+        }
+    }
+}
+
+pub struct NodeRuntimeBuilder {
+    config: Config,
+    api: bool,
+    process_globals: bool,
+    stable_after: Option<Duration>,
+    hooks: Vec<RegisteredHook>,
+}
+
+impl NodeRuntimeBuilder {
+    /// HTTP-API und Monitoring laut `[monitoring]` starten (Standard an).
+    pub fn api(mut self, enabled: bool) -> Self {
+        self.api = enabled;
+        self
+    }
+
+    /// HTTP-Client, Storage, State-Store und Telemetrie aus der Config
+    /// einrichten (Standard an). Aus, wenn der Einbettende sie selbst setzt.
+    pub fn process_globals(mut self, enabled: bool) -> Self {
+        self.process_globals = enabled;
+        self
+    }
+
+    /// Nach dieser Laufzeit gilt der Start als stabil (Safe-Mode-Zähler).
+    pub fn stable_after(mut self, duration: Duration) -> Self {
+        self.stable_after = Some(duration);
+        self
+    }
+
+    pub fn on_shutdown<F>(mut self, phase: ShutdownPhase, name: &str, hook: F) -> Self
+    where
+        F: FnOnce() -> Result<()> + Send + 'static,
+    {
+        self.hooks.push(RegisteredHook {
+            phase,
+            name: name.to_string(),
+            hook: Box::new(hook),
+        });
+        self
+    }
+
+    /// Prüft die Config und baut Producer, Flows und Consumer auf; gestartet
+    /// wird erst mit `NodeRuntime::start`.
+    pub fn build(self) -> Result<NodeRuntime> {
+        if self.process_globals {
+            install_process_globals(&self.config);
+        }
+        let mut node = AirliftNode::new();
+        configurator::apply_config(&mut node, &self.config)?;
+        Ok(NodeRuntime {
+            node: node.into_shared(),
+            config: Arc::new(Mutex::new(self.config)),
+            api: self.api,
+            stable_after: self.stable_after,
+            hooks: self.hooks,
+            shutdown: ShutdownHandle::new(),
+            listeners: Vec::new(),
+            services: None,
+            finished: false,
+        })
+    }
+}
+
+/// Alles, was `start` neben dem Node startet.
+struct Services {
+    supervisor: Option<JoinHandle<()>>,
+    supervisor_stop: Arc<AtomicBool>,
+    #[cfg(feature = "lua")]
+    rules: Option<crate::rules::RulesEngine>,
+    mqtt: Option<crate::mqtt::MqttPublisher>,
+}
+
+pub struct NodeRuntime {
+    node: Arc<Mutex<AirliftNode>>,
+    config: Arc<Mutex<Config>>,
+    api: bool,
+    stable_after: Option<Duration>,
+    hooks: Vec<RegisteredHook>,
+    shutdown: ShutdownHandle,
+    listeners: Vec<ListenerInfo>,
+    services: Option<Services>,
+    finished: bool,
+}
+
+impl NodeRuntime {
+    pub fn builder(config: Config) -> NodeRuntimeBuilder {
+        NodeRuntimeBuilder {
+            config,
+            api: true,
+            process_globals: true,
+            stable_after: None,
+            hooks: Vec::new(),
+        }
+    }
+
+    /// Geteilter Node, z. B. für eigene Steuerung oder Statusabfragen.
+    pub fn node(&self) -> Arc<Mutex<AirliftNode>> {
+        self.node.clone()
+    }
+
+    /// Aktive Config; Reloads über die API ersetzen sie.
+    pub fn config(&self) -> Arc<Mutex<Config>> {
+        self.config.clone()
+    }
+
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        self.shutdown.clone()
+    }
+
+    /// Tatsächlich gebundene API-Adressen (relevant bei Port 0).
+    pub fn listeners(&self) -> &[ListenerInfo] {
+        &self.listeners
+    }
+
+    pub fn is_started(&self) -> bool {
+        self.services.is_some()
+    }
+
+    /// Hook nach dem Bauen registrieren; gleiche Reihenfolge wie im Builder.
+    pub fn on_shutdown<F>(&mut self, phase: ShutdownPhase, name: &str, hook: F)
+    where
+        F: FnOnce() -> Result<()> + Send + 'static,
+    {
+        self.hooks.push(RegisteredHook {
+            phase,
+            name: name.to_string(),
+            hook: Box::new(hook),
+        });
+    }
+
+    /// Startet API, Node, Scheduler, Regeln, MQTT und Supervisor. Kehrt
+    /// sofort zurück.
+    pub fn start(&mut self) -> Result<()> {
+        if self.services.is_some() || self.finished {
+            bail!("node runtime was already started");
+        }
+        let snapshot = lock_mutex(&self.config, "runtime.start").clone();
+
+        if self.api {
+            self.listeners = api::start_api_servers(
+                &snapshot.monitoring.effective_binds(),
+                self.config.clone(),
+                self.node.clone(),
+            )?;
+        }
+        lock_mutex(&self.node, "runtime.start")
+            .start()
+            .map_err(|e| anyhow::anyhow!("failed to start node: {}", e))?;
+        scheduler().start(self.node.clone(), ScheduleEntry::from_configs(&snapshot)?);
+
+        #[cfg(feature = "lua")]
+        let rules = match &snapshot.rules {
+            Some(rules) => Some(crate::rules::RulesEngine::start(
+                rules,
+                self.node.clone(),
+                self.config.clone(),
+            )?),
+            None => None,
+        };
+        #[cfg(not(feature = "lua"))]
+        if snapshot.rules.is_some() {
+            log::warn!(
+                "[rules] configured but Lua support is disabled; rebuild with --features lua"
+            );
+        }
+
+        let mqtt = match &snapshot.mqtt {
+            Some(mqtt) => {
+                let options = crate::mqtt::MqttOptions::from_config(mqtt, &snapshot.node_name)?;
+                log::info!(
+                    "MQTT: publishing to {}:{} under '{}'",
+                    options.host,
+                    options.port,
+                    options.prefix
+                );
+                Some(crate::mqtt::MqttPublisher::start(
+                    options,
+                    self.node.clone(),
+                )?)
+            }
+            None => None,
+        };
+
+        let supervisor_stop = Arc::new(AtomicBool::new(false));
+        let supervisor = {
+            let node = self.node.clone();
+            let stop = supervisor_stop.clone();
+            let wait = self.shutdown.clone();
+            let stable_after = self.stable_after;
+            std::thread::Builder::new()
+                .name("airlift-supervisor".to_string())
+                .spawn(move || {
+                    let started = Instant::now();
+                    while !stop.load(Ordering::SeqCst) {
+                        wait.wait_timeout(SUPERVISOR_INTERVAL);
+                        if let Ok(mut node) = node.lock() {
+                            node.run_watchdog();
+                        }
+                        if stable_after.is_some_and(|after| started.elapsed() >= after) {
+                            safe_mode::mark_stable();
+                        }
+                    }
+                })?
+        };
+
+        self.services = Some(Services {
+            supervisor: Some(supervisor),
+            supervisor_stop,
+            #[cfg(feature = "lua")]
+            rules,
+            mqtt,
+        });
+        Ok(())
+    }
+
+    /// Blockiert bis `ShutdownHandle::request` und fährt dann herunter.
+    pub fn wait(mut self) -> Result<()> {
+        while !self.shutdown.is_requested() {
+            self.shutdown.wait_timeout(SUPERVISOR_INTERVAL);
+        }
+        self.finish()
+    }
+
+    /// Sofort herunterfahren (ohne auf eine Anforderung zu warten).
+    pub fn shutdown(mut self) -> Result<()> {
+        self.shutdown.request();
+        self.finish()
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        if self.finished {
+            return Ok(());
+        }
+        self.finished = true;
+        let mut first_error = None;
+
+        self.run_hooks(ShutdownPhase::BeforeStop, &mut first_error);
+
+        if let Some(mut services) = self.services.take() {
+            services.supervisor_stop.store(true, Ordering::SeqCst);
+            self.shutdown.request();
+            if let Some(supervisor) = services.supervisor.take() {
+                let _ = supervisor.join();
+            }
+            // Sauberes Beenden zählt nicht als Crash
+            safe_mode::mark_stable();
+            #[cfg(feature = "lua")]
+            drop(services.rules.take());
+            drop(services.mqtt.take());
+            scheduler().stop();
+
+            let mut node = lock_mutex(&self.node, "runtime.shutdown");
+            node.stop_draining();
+            if let Err(e) = node.stop() {
+                first_error.get_or_insert(anyhow::anyhow!("failed to stop node: {}", e));
+            }
+            drop(node);
+            log::info!("Node stopped");
+        }
+
+        self.run_hooks(ShutdownPhase::AfterStop, &mut first_error);
+        match first_error {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    fn run_hooks(&mut self, phase: ShutdownPhase, first_error: &mut Option<anyhow::Error>) {
+        let (due, rest): (Vec<_>, Vec<_>) = std::mem::take(&mut self.hooks)
+            .into_iter()
+            .partition(|hook| hook.phase == phase);
+        self.hooks = rest;
+        for hook in due {
+            if let Err(e) = (hook.hook)() {
+                log::warn!("[runtime] shutdown hook '{}' failed: {:#}", hook.name, e);
+                first_error
+                    .get_or_insert(e.context(format!("shutdown hook '{}' failed", hook.name)));
+            }
+        }
+    }
+}
+
+impl Drop for NodeRuntime {
+    /// Eine gestartete Runtime fährt beim Verwerfen geordnet herunter.
+    fn drop(&mut self) {
+        if self.services.is_some() {
+            if let Err(e) = self.finish() {
+                log::warn!("[runtime] shutdown on drop: {:#}", e);
+            }
+        }
+    }
+}
+
+/// Prozessweite Einstellungen aus der Config: State-Store (`[startup]`),
+/// HTTP-Client, Storage-Backend und Rechner-Telemetrie. Fehler führen zu
+/// Standardwerten bzw. zum Betrieb ohne das jeweilige Feature.
+pub fn install_process_globals(config: &Config) {
+    install_state_store(&config.startup.clone().unwrap_or_default());
+    install_http_client(config.http_client.as_ref());
+    install_storage(config.storage.as_ref());
+    install_host_telemetry(config.telemetry.as_ref());
+}
+
+/// Zustand über Neustarts (`startup.state_dir`); ohne Verzeichnis oder bei
+/// Fehlern läuft der Node ohne Persistenz.
+fn install_state_store(startup: &config::StartupConfig) {
+    let Some(dir) = startup.state_dir.as_deref() else {
+        return;
+    };
+    let store = startup
+        .state_max_age()
+        .and_then(|max_age| Ok((max_age, startup.state_interval()?)))
+        .and_then(|(max_age, interval)| core::StateStore::open(dir, max_age, interval));
+    match store {
+        Ok(store) => {
+            log::info!("Runtime state persisted in {}", dir);
+            core::state_store::install(Some(store));
+        }
+        Err(e) => log::warn!("[state] persistence disabled: {:#}", e),
+    }
+}
+
+/// Einstellungen für ausgehende HTTP-Anfragen (`[http_client]`), ohne
+/// eigenen Proxy mit `http_proxy` aus der Umgebung.
+fn install_http_client(config: Option<&config::HttpClientConfig>) {
+    let settings = match config.map(|config| config.settings()).transpose() {
+        Ok(settings) => settings.unwrap_or_default(),
+        Err(e) => {
+            log::warn!("[http] {:#}, using defaults", e);
+            Default::default()
+        }
+    };
+    core::http_client::install(settings.with_env_proxy());
+}
+
+/// Backend für Historie und Aufnahme-Index (`[storage]`); lässt es sich nicht
+/// öffnen, läuft der Node mit dem Speicher-Backend weiter.
+fn install_storage(config: Option<&config::StorageConfig>) {
+    let Some(config) = config else {
+        return;
+    };
+    match storage::open(config) {
+        Ok(backend) => {
+            log::info!("[storage] using {} backend", backend.kind());
+            storage::install(backend);
+        }
+        Err(e) => log::warn!("[storage] {:#}, keeping history in memory", e),
+    }
+}
+
+/// Abtastung des Rechners für /metrics (`[telemetry]`); läuft auch ohne
+/// Abschnitt mit Standardwerten, `enabled = false` schaltet sie ab.
+fn install_host_telemetry(config: Option<&config::TelemetryConfig>) {
+    if config.is_some_and(|config| !config.enabled()) {
+        return;
+    }
+    let settings = match config.map(|config| config.settings()).transpose() {
+        Ok(settings) => settings.unwrap_or_default(),
+        Err(e) => {
+            log::warn!("[telemetry] {:#}, using defaults", e);
+            Default::default()
+        }
+    };
+    core::host_telemetry::install(settings);
+}
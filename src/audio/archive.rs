@@ -0,0 +1,420 @@
+// src/audio/archive.rs
+//
+// Mitschnitt-Archiv: pro Tag und Verzeichnis ein Manifest
+// (`manifest-YYYY-MM-DD.json`) mit Datei, Dauer, Größe und SHA-256. Ein
+// Hintergrund-Job prüft regelmäßig alle bekannten Archive und hält den letzten
+// Bericht für `/api/recordings/verify` bereit (Sendemitschnitt-Pflicht).
+use std::collections::BTreeSet;
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, Once, OnceLock};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::core::lock::lock_mutex;
+use crate::core::timestamp::{civil_from_days, utc_ns_now};
+use crate::core::timezone::TimeZone;
+
+const MANIFEST_PREFIX: &str = "manifest-";
+const MANIFEST_SUFFIX: &str = ".json";
+pub const DEFAULT_VERIFY_INTERVAL: Duration = Duration::from_secs(6 * 3600);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// Dateiname relativ zum Verzeichnis des Manifests
+    pub file: String,
+    pub duration_ms: u64,
+    pub bytes: u64,
+    pub sha256: String,
+    pub finished_at_ms: u64,
+    /// `finished_at_ms` als Ortszeit mit Offset, z. B. `2026-10-25T02:30:00+01:00`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finished_at_local: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DayManifest {
+    pub date: String,
+    pub entries: Vec<ManifestEntry>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileState {
+    Ok,
+    Missing,
+    Corrupted,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FileCheck {
+    pub path: String,
+    pub manifest: String,
+    pub state: FileState,
+    pub expected_sha256: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actual_sha256: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct VerifyReport {
+    pub started_at_ms: u64,
+    pub finished_at_ms: u64,
+    pub manifests: usize,
+    pub checked: usize,
+    pub ok: usize,
+    /// Nur Auffälligkeiten (missing/corrupted), intakte Dateien werden gezählt
+    pub problems: Vec<FileCheck>,
+    pub errors: Vec<String>,
+}
+
+/// Aufnahme aus einem Manifest, adressierbar über ihre `id`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordingInfo {
+    pub id: String,
+    pub path: String,
+    #[serde(flatten)]
+    pub entry: ManifestEntry,
+}
+
+/// Stabile ID einer fertigen Aufnahme: die ersten 16 Hex-Stellen ihres SHA-256.
+pub fn recording_id(entry: &ManifestEntry) -> String {
+    entry.sha256.chars().take(16).collect()
+}
+
+pub fn recording_info(dir: &Path, entry: ManifestEntry) -> RecordingInfo {
+    RecordingInfo {
+        id: recording_id(&entry),
+        path: dir.join(&entry.file).display().to_string(),
+        entry,
+    }
+}
+
+impl VerifyReport {
+    pub fn is_clean(&self) -> bool {
+        self.problems.is_empty() && self.errors.is_empty()
+    }
+}
+
+/// SHA-256 einer Datei als Hex-String.
+pub fn sha256_file(path: &Path) -> Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect())
+}
+
+/// UTC-Datum (YYYY-MM-DD) zu Millisekunden seit Epoch.
+pub fn utc_date(ms: u64) -> String {
+    let (year, month, day) = civil_from_days((ms / 86_400_000) as i64);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+pub fn manifest_path(dir: &Path, date: &str) -> PathBuf {
+    dir.join(format!("{}{}{}", MANIFEST_PREFIX, date, MANIFEST_SUFFIX))
+}
+
+pub fn load_manifest(path: &Path) -> Result<DayManifest> {
+    let raw = fs::read_to_string(path)
+        .with_context(|| format!("cannot read manifest {}", path.display()))?;
+    serde_json::from_str(&raw).with_context(|| format!("invalid manifest {}", path.display()))
+}
+
+/// Hasht eine fertige Aufnahme und trägt sie ins Tagesmanifest ihres
+/// Verzeichnisses ein. Das Manifest bleibt nach UTC-Datum benannt, der
+/// Eintrag trägt zusätzlich die Ortszeit in `timezone`.
+pub fn record_file(path: &Path, duration_ms: u64, timezone: &TimeZone) -> Result<ManifestEntry> {
+    record_file_in(path, duration_ms, utc_ns_now() / 1_000_000, timezone)
+}
+
+pub fn record_file_at(path: &Path, duration_ms: u64, now_ms: u64) -> Result<ManifestEntry> {
+    record_file_in(path, duration_ms, now_ms, &TimeZone::utc())
+}
+
+pub fn record_file_in(
+    path: &Path,
+    duration_ms: u64,
+    now_ms: u64,
+    timezone: &TimeZone,
+) -> Result<ManifestEntry> {
+    let dir = archive_dir(path);
+    let file = path
+        .file_name()
+        .with_context(|| format!("not a file: {}", path.display()))?
+        .to_string_lossy()
+        .to_string();
+    let entry = ManifestEntry {
+        file,
+        duration_ms,
+        bytes: fs::metadata(path)?.len(),
+        sha256: sha256_file(path)?,
+        finished_at_ms: now_ms,
+        finished_at_local: Some(timezone.format_local(now_ms)),
+        timezone: Some(timezone.name().to_string()),
+    };
+
+    // Mehrere FileConsumer können ins selbe Verzeichnis schreiben
+    let _guard = lock_mutex(&archive_registry().manifest_lock, "archive.record_file");
+    let date = utc_date(now_ms);
+    let manifest_file = manifest_path(&dir, &date);
+    let mut manifest = if manifest_file.exists() {
+        load_manifest(&manifest_file)?
+    } else {
+        DayManifest {
+            date,
+            entries: Vec::new(),
+        }
+    };
+    // Gleicher Dateiname (Neustart mit identischem Pfad) ersetzt den Eintrag
+    manifest.entries.retain(|e| e.file != entry.file);
+    manifest.entries.push(entry.clone());
+
+    let tmp = manifest_file.with_extension("json.tmp");
+    let mut out = File::create(&tmp)?;
+    out.write_all(serde_json::to_string_pretty(&manifest)?.as_bytes())?;
+    out.sync_all()?;
+    fs::rename(&tmp, &manifest_file)?;
+
+    register_archive_dir(&dir);
+    // Das Manifest bleibt maßgeblich für die Prüfung; der Index ist Komfort
+    if let Err(e) = crate::storage::backend().index_recording(&recording_info(&dir, entry.clone())) {
+        log::warn!("[storage] recording {} not indexed: {:#}", path.display(), e);
+    }
+    Ok(entry)
+}
+
+/// Entfernt eine (gelöschte) Aufnahme aus den Manifesten ihres Verzeichnisses;
+/// leere Manifeste verschwinden mit. Liefert die Zahl entfernter Einträge.
+pub fn forget_file(path: &Path) -> Result<usize> {
+    let dir = archive_dir(path);
+    let file = path
+        .file_name()
+        .with_context(|| format!("not a file: {}", path.display()))?
+        .to_string_lossy()
+        .to_string();
+    if let Err(e) = crate::storage::backend().forget_recording(&dir.join(&file)) {
+        log::warn!("[storage] recording {} not removed from index: {:#}", path.display(), e);
+    }
+    let Ok(read_dir) = fs::read_dir(&dir) else {
+        return Ok(0);
+    };
+
+    let _guard = lock_mutex(&archive_registry().manifest_lock, "archive.forget_file");
+    let mut removed = 0;
+    for manifest_file in manifest_files(read_dir) {
+        let mut manifest = load_manifest(&manifest_file)?;
+        let before = manifest.entries.len();
+        manifest.entries.retain(|e| e.file != file);
+        if manifest.entries.len() == before {
+            continue;
+        }
+        removed += before - manifest.entries.len();
+        if manifest.entries.is_empty() {
+            fs::remove_file(&manifest_file)?;
+            continue;
+        }
+        let tmp = manifest_file.with_extension("json.tmp");
+        let mut out = File::create(&tmp)?;
+        out.write_all(serde_json::to_string_pretty(&manifest)?.as_bytes())?;
+        out.sync_all()?;
+        fs::rename(&tmp, &manifest_file)?;
+    }
+    Ok(removed)
+}
+
+fn manifest_files(read_dir: fs::ReadDir) -> Vec<PathBuf> {
+    let mut manifests: Vec<PathBuf> = read_dir
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with(MANIFEST_PREFIX) && n.ends_with(MANIFEST_SUFFIX))
+        })
+        .collect();
+    manifests.sort();
+    manifests
+}
+
+/// Alle Aufnahmen aus den Manifesten eines Verzeichnisses, älteste zuerst.
+pub fn list_recordings(dir: &Path) -> Vec<RecordingInfo> {
+    let Ok(read_dir) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    manifest_files(read_dir)
+        .iter()
+        .filter_map(|manifest| load_manifest(manifest).ok())
+        .flat_map(|manifest| manifest.entries)
+        .map(|entry| recording_info(dir, entry))
+        .collect()
+}
+
+/// Prüft alle Manifeste eines Verzeichnisses.
+pub fn verify_dir(dir: &Path) -> VerifyReport {
+    let mut report = VerifyReport {
+        started_at_ms: utc_ns_now() / 1_000_000,
+        ..Default::default()
+    };
+    verify_into(dir, &mut report);
+    report.finished_at_ms = utc_ns_now() / 1_000_000;
+    report
+}
+
+fn verify_into(dir: &Path, report: &mut VerifyReport) {
+    let read_dir = match fs::read_dir(dir) {
+        Ok(read_dir) => read_dir,
+        Err(e) => {
+            report
+                .errors
+                .push(format!("cannot read archive dir {}: {}", dir.display(), e));
+            return;
+        }
+    };
+
+    for manifest_file in manifest_files(read_dir) {
+        let manifest = match load_manifest(&manifest_file) {
+            Ok(manifest) => manifest,
+            Err(e) => {
+                report.errors.push(format!("{:#}", e));
+                continue;
+            }
+        };
+        report.manifests += 1;
+
+        for entry in manifest.entries {
+            let path = dir.join(&entry.file);
+            report.checked += 1;
+            let (state, actual) = if !path.exists() {
+                (FileState::Missing, None)
+            } else {
+                match sha256_file(&path) {
+                    Ok(actual) if actual == entry.sha256 => (FileState::Ok, Some(actual)),
+                    Ok(actual) => (FileState::Corrupted, Some(actual)),
+                    // Unlesbar zählt wie beschädigt
+                    Err(_) => (FileState::Corrupted, None),
+                }
+            };
+            if state == FileState::Ok {
+                report.ok += 1;
+                continue;
+            }
+            report.problems.push(FileCheck {
+                path: path.display().to_string(),
+                manifest: manifest_file.display().to_string(),
+                state,
+                expected_sha256: entry.sha256,
+                actual_sha256: actual,
+            });
+        }
+    }
+}
+
+pub struct ArchiveRegistry {
+    dirs: Mutex<BTreeSet<PathBuf>>,
+    last_report: Mutex<Option<VerifyReport>>,
+    manifest_lock: Mutex<()>,
+    verifier: Once,
+}
+
+static ARCHIVE_REGISTRY: OnceLock<ArchiveRegistry> = OnceLock::new();
+
+pub fn archive_registry() -> &'static ArchiveRegistry {
+    ARCHIVE_REGISTRY.get_or_init(|| ArchiveRegistry {
+        dirs: Mutex::new(BTreeSet::new()),
+        last_report: Mutex::new(None),
+        manifest_lock: Mutex::new(()),
+        verifier: Once::new(),
+    })
+}
+
+/// Verzeichnis, in dem das Manifest einer Aufnahme liegt.
+pub fn archive_dir(path: &Path) -> PathBuf {
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => PathBuf::from("."),
+    }
+}
+
+pub fn register_archive_dir(dir: &Path) {
+    lock_mutex(&archive_registry().dirs, "archive.register").insert(dir.to_path_buf());
+}
+
+impl ArchiveRegistry {
+    pub fn dirs(&self) -> Vec<PathBuf> {
+        lock_mutex(&self.dirs, "archive.dirs").iter().cloned().collect()
+    }
+
+    /// Prüft alle bekannten Archive und merkt sich den Bericht.
+    pub fn verify_all(&self) -> VerifyReport {
+        let mut report = VerifyReport {
+            started_at_ms: utc_ns_now() / 1_000_000,
+            ..Default::default()
+        };
+        for dir in self.dirs() {
+            verify_into(&dir, &mut report);
+        }
+        report.finished_at_ms = utc_ns_now() / 1_000_000;
+
+        if !report.is_clean() {
+            log::warn!(
+                "Archive verification: {} missing/corrupted, {} errors ({} files checked)",
+                report.problems.len(),
+                report.errors.len(),
+                report.checked
+            );
+        }
+        *lock_mutex(&self.last_report, "archive.verify_all") = Some(report.clone());
+        report
+    }
+
+    pub fn recordings(&self) -> Vec<RecordingInfo> {
+        self.dirs().iter().flat_map(|dir| list_recordings(dir)).collect()
+    }
+
+    pub fn find_recording(&self, id: &str) -> Option<RecordingInfo> {
+        self.recordings().into_iter().find(|recording| recording.id == id)
+    }
+
+    pub fn last_report(&self) -> Option<VerifyReport> {
+        lock_mutex(&self.last_report, "archive.last_report").clone()
+    }
+
+    /// Startet (einmalig) den periodischen Prüf-Job.
+    pub fn start_verification_job(&'static self, interval: Duration) {
+        self.verifier.call_once(|| {
+            log::info!("Archive verification every {:?}", interval);
+            std::thread::spawn(move || loop {
+                std::thread::sleep(interval);
+                self.verify_all();
+            });
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn utc_date_handles_leap_years() {
+        assert_eq!(utc_date(0), "1970-01-01");
+        assert_eq!(utc_date(951_782_400_000), "2000-02-29");
+        assert_eq!(utc_date(1_709_251_199_999), "2024-02-29");
+    }
+}
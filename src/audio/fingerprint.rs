@@ -0,0 +1,178 @@
+// src/audio/fingerprint.rs
+//
+// Audio-Fingerprint nach dem Chromaprint-Prinzip (nicht bitkompatibel): die
+// Mono-Summe läuft durch eine Halbton-Filterbank über fünf Oktaven ab 55 Hz,
+// je 100 ms (`HOP_MS`) werden die Bandenergien auf 12 Chroma-Klassen
+// gefaltet und normiert. Aus dem Vergleich benachbarter Klassen und der
+// Veränderung zum vorigen Block entsteht ein 32-Bit-Hash je Block. Die
+// Vergleiche sind pegelunabhängig; derselbe Beitrag liefert auch nach Gain-
+// oder Codec-Änderungen nahezu dieselben Hashes, `similarity` misst das.
+use crate::audio::loudness::Biquad;
+use crate::ring::PcmFrame;
+
+/// Blocklänge je Hash
+pub const HOP_MS: u64 = 100;
+/// Kennung des Verfahrens in Ausgaben, bei Änderungen hochzählen
+pub const FINGERPRINT_ALGORITHM: &str = "airlift-chroma-1";
+
+const LOWEST_NOTE_HZ: f64 = 55.0;
+const OCTAVES: usize = 5;
+/// Güte für einen Halbton Bandbreite
+const SEMITONE_Q: f64 = 17.3;
+/// Blöcke unter ca. -80 dBFS gelten als Stille (Hash 0)
+const SILENCE_MEAN_SQUARE: f64 = 1e-8;
+
+fn bandpass(center_hz: f64, sample_rate: u32) -> Biquad {
+    let w0 = 2.0 * std::f64::consts::PI * center_hz / sample_rate as f64;
+    let alpha = w0.sin() / (2.0 * SEMITONE_Q);
+    let a0 = 1.0 + alpha;
+    Biquad {
+        b: [alpha / a0, 0.0, -alpha / a0],
+        a: [-2.0 * w0.cos() / a0, (1.0 - alpha) / a0],
+        z: [0.0; 2],
+    }
+}
+
+/// Anteil übereinstimmender Bits zweier Hash-Folgen (1.0 = identisch,
+/// um 0.5 = unkorreliert). Verglichen wird über die kürzere Länge.
+pub fn similarity(a: &[u32], b: &[u32]) -> f64 {
+    let len = a.len().min(b.len());
+    if len == 0 {
+        return 0.0;
+    }
+    let differing: u32 = a.iter().zip(b).map(|(a, b)| (a ^ b).count_ones()).sum();
+    1.0 - differing as f64 / (32 * len) as f64
+}
+
+/// Erzeugt je abgeschlossenem Block einen Hash. Ein Wechsel der Samplerate
+/// setzt Filter und angefangenen Block zurück.
+pub struct Fingerprinter {
+    sample_rate: u32,
+    filters: Vec<Biquad>,
+    energy: Vec<f64>,
+    sum_sq: f64,
+    frames: u64,
+    previous: Option<([f64; 12], f64)>,
+}
+
+impl Default for Fingerprinter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Fingerprinter {
+    pub fn new() -> Self {
+        Self {
+            sample_rate: 0,
+            filters: Vec::new(),
+            energy: Vec::new(),
+            sum_sq: 0.0,
+            frames: 0,
+            previous: None,
+        }
+    }
+
+    /// Verwirft Filterzustand und angefangenen Block, z. B. nach Lücken.
+    pub fn reset(&mut self) {
+        let sample_rate = self.sample_rate;
+        self.sample_rate = 0;
+        if sample_rate > 0 {
+            self.prepare(sample_rate);
+        }
+    }
+
+    fn prepare(&mut self, sample_rate: u32) {
+        self.sample_rate = sample_rate;
+        self.filters = (0..OCTAVES * 12)
+            .map(|note| LOWEST_NOTE_HZ * 2f64.powf(note as f64 / 12.0))
+            .filter(|hz| *hz < sample_rate as f64 * 0.45)
+            .map(|hz| bandpass(hz, sample_rate))
+            .collect();
+        self.energy = vec![0.0; self.filters.len()];
+        self.sum_sq = 0.0;
+        self.frames = 0;
+        self.previous = None;
+    }
+
+    /// Verarbeitet einen Frame; liefert `(utc_ns am Blockende, Hash)` für
+    /// jeden darin abgeschlossenen Block.
+    pub fn push(&mut self, frame: &PcmFrame) -> Vec<(u64, u32)> {
+        let channels = frame.channels as usize;
+        let mut hashes = Vec::new();
+        if channels == 0 || frame.sample_rate == 0 {
+            return hashes;
+        }
+        if frame.sample_rate != self.sample_rate {
+            self.prepare(frame.sample_rate);
+        }
+        let hop_frames = frame.sample_rate as u64 * HOP_MS / 1000;
+        for (n, chunk) in frame.samples.chunks_exact(channels).enumerate() {
+            let mono = chunk.iter().map(|s| *s as f64).sum::<f64>() / (channels as f64 * 32768.0);
+            self.sum_sq += mono * mono;
+            for (filter, energy) in self.filters.iter_mut().zip(self.energy.iter_mut()) {
+                let y = filter.process(mono);
+                *energy += y * y;
+            }
+            self.frames += 1;
+            if self.frames >= hop_frames {
+                let end = frame.utc_ns + (n as u64 + 1) * 1_000_000_000 / frame.sample_rate as u64;
+                hashes.push((end, self.close_block()));
+            }
+        }
+        hashes
+    }
+
+    fn close_block(&mut self) -> u32 {
+        let mean_square = self.sum_sq / self.frames.max(1) as f64;
+        let mut chroma = [0.0; 12];
+        for (note, energy) in self.energy.iter_mut().enumerate() {
+            chroma[note % 12] += *energy;
+            *energy = 0.0;
+        }
+        self.sum_sq = 0.0;
+        self.frames = 0;
+
+        if mean_square < SILENCE_MEAN_SQUARE {
+            self.previous = None;
+            return 0;
+        }
+        let norm = chroma.iter().map(|c| c * c).sum::<f64>().sqrt();
+        if norm > 0.0 {
+            chroma.iter_mut().for_each(|c| *c /= norm);
+        }
+        let (previous, previous_energy) = self.previous.unwrap_or((chroma, mean_square));
+
+        let mut hash = 0u32;
+        for i in 0..12 {
+            // Bits 0–11: Verlauf über die Tonklassen
+            if chroma[i] > chroma[(i + 1) % 12] {
+                hash |= 1 << i;
+            }
+            // Bits 12–23: Veränderung zum vorigen Block
+            if chroma[i] > previous[i] {
+                hash |= 1 << (12 + i);
+            }
+        }
+        // Bits 24–29: Tritonus-Paare
+        for i in 0..6 {
+            if chroma[i] > chroma[i + 6] {
+                hash |= 1 << (24 + i);
+            }
+        }
+        // Bit 30: Lautheitsverlauf, Bit 31: stärkste Tonklasse gewechselt
+        if mean_square > previous_energy {
+            hash |= 1 << 30;
+        }
+        let strongest = |values: &[f64; 12]| {
+            (0..12)
+                .max_by(|a, b| values[*a].total_cmp(&values[*b]))
+                .unwrap_or(0)
+        };
+        if strongest(&chroma) != strongest(&previous) {
+            hash |= 1 << 31;
+        }
+        self.previous = Some((chroma, mean_square));
+        hash
+    }
+}
@@ -10,8 +10,9 @@ use std::thread;
 
 use anyhow::anyhow;
 use log::{error, info, warn};
-use tiny_http::{Header, Method, Response, Server, StatusCode};
+use tiny_http::{Header, Method, Response, StatusCode};
 
+use crate::api::listeners::{self, BindReport, BindRetry};
 use crate::audio::{EncodedFrameSource, EncodedRead};
 use crate::codecs::{supported_codecs, CodecInfo, ContainerKind, EncodedFrame};
 use crate::core::error::{AudioError, AudioResult};
@@ -109,8 +110,12 @@ where
     F: Fn() -> R + Send + Sync + 'static,
     R: EncodedFrameSource + Send + 'static,
 {
-    let server = Server::http(bind)
-        .map_err(|e| AudioError::with_context("bind audio http server", anyhow!(e)))?;
+    let server = listeners::bind_http("audio", bind, BindRetry::default()).map_err(|failure| {
+        AudioError::with_context(
+            "bind audio http server",
+            anyhow!(BindReport { failures: vec![failure] }),
+        )
+    })?;
 
     let codec_id = require_codec_id(codec_id.as_deref())?;
 
@@ -0,0 +1,248 @@
+// src/audio/loudness.rs
+//
+// Lautheit nach ITU-R BS.1770 (K-Filter, Kanalgewichte 1.0 für L/R):
+// Short-Term-Lautheit über ein gleitendes 3-s-Fenster aus 100-ms-Blöcken.
+// Kein Gating, für Vergleiche und Anzeigen, nicht für normgerechte Messungen.
+// `IntegratedLoudness` mittelt 400-ms-Fenster (Schritt 100 ms) mit absolutem
+// und relativem Gate über eine ganze Datei (Offline-Auswertung) und behält
+// die Blockwerte für Auswertungen je Block.
+use std::collections::VecDeque;
+
+use crate::ring::PcmFrame;
+
+pub const BLOCK_MS: u64 = 100;
+pub const SHORT_TERM_BLOCKS: usize = 30;
+/// Darunter gilt das Signal als Stille (−70 LUFS, absolutes Gate in BS.1770).
+pub const SILENCE_LUFS: f32 = -70.0;
+/// Gating-Fenster für integrierte Lautheit: 4 × 100 ms, 75 % Überlappung
+const GATE_BLOCKS: usize = 4;
+/// Relatives Gate unterhalb der absolut gegateten Lautheit
+const RELATIVE_GATE_LU: f64 = 10.0;
+
+/// Biquad in Transposed Direct Form II (a0 normiert auf 1).
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Biquad {
+    pub(crate) b: [f64; 3],
+    pub(crate) a: [f64; 2],
+    pub(crate) z: [f64; 2],
+}
+
+impl Biquad {
+    pub(crate) fn process(&mut self, x: f64) -> f64 {
+        let y = self.b[0] * x + self.z[0];
+        self.z[0] = self.b[1] * x - self.a[0] * y + self.z[1];
+        self.z[1] = self.b[2] * x - self.a[1] * y;
+        y
+    }
+}
+
+/// Zweistufiger K-Filter (High-Shelf + Hochpass), Koeffizienten für beliebige
+/// Sampleraten wie in libebur128 hergeleitet.
+#[derive(Debug, Clone, Copy)]
+struct KWeighting {
+    shelf: Biquad,
+    highpass: Biquad,
+}
+
+impl KWeighting {
+    fn new(sample_rate: u32) -> Self {
+        let fs = sample_rate as f64;
+
+        let f0 = 1_681.974_450_955_533;
+        let gain_db = 3.999_843_853_973_347;
+        let q = 0.707_175_236_955_419_6;
+        let k = (std::f64::consts::PI * f0 / fs).tan();
+        let vh = 10f64.powf(gain_db / 20.0);
+        let vb = vh.powf(0.499_666_774_154_541_6);
+        let a0 = 1.0 + k / q + k * k;
+        let shelf = Biquad {
+            b: [
+                (vh + vb * k / q + k * k) / a0,
+                2.0 * (k * k - vh) / a0,
+                (vh - vb * k / q + k * k) / a0,
+            ],
+            a: [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+            z: [0.0; 2],
+        };
+
+        let f0 = 38.135_470_876_024_44;
+        let q = 0.500_327_037_323_877_3;
+        let k = (std::f64::consts::PI * f0 / fs).tan();
+        let a0 = 1.0 + k / q + k * k;
+        let highpass = Biquad {
+            b: [1.0, -2.0, 1.0],
+            a: [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+            z: [0.0; 2],
+        };
+
+        Self { shelf, highpass }
+    }
+
+    fn process(&mut self, x: f64) -> f64 {
+        self.highpass.process(self.shelf.process(x))
+    }
+}
+
+/// Short-Term-Lautheit eines Signals; Frames werden in Blöcke zerlegt.
+/// Samplerate- oder Kanalwechsel setzen die Messung zurück.
+pub struct LoudnessMeter {
+    sample_rate: u32,
+    channels: u8,
+    filters: Vec<KWeighting>,
+    block_len: usize,
+    block_sum: f64,
+    block_filled: usize,
+    /// Mittleres Quadrat (über Kanäle summiert) je abgeschlossenem Block
+    blocks: VecDeque<f64>,
+}
+
+impl Default for LoudnessMeter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LoudnessMeter {
+    pub fn new() -> Self {
+        Self {
+            sample_rate: 0,
+            channels: 0,
+            filters: Vec::new(),
+            block_len: 0,
+            block_sum: 0.0,
+            block_filled: 0,
+            blocks: VecDeque::with_capacity(SHORT_TERM_BLOCKS),
+        }
+    }
+
+    fn reset(&mut self, sample_rate: u32, channels: u8) {
+        self.sample_rate = sample_rate;
+        self.channels = channels;
+        self.filters = vec![KWeighting::new(sample_rate); channels as usize];
+        self.block_len = (sample_rate as u64 * BLOCK_MS / 1000).max(1) as usize;
+        self.block_sum = 0.0;
+        self.block_filled = 0;
+        self.blocks.clear();
+    }
+
+    /// Nimmt ein Frame auf; liefert die Anzahl neu abgeschlossener Blöcke.
+    pub fn push(&mut self, frame: &PcmFrame) -> usize {
+        if frame.channels == 0 || frame.sample_rate == 0 {
+            return 0;
+        }
+        if frame.sample_rate != self.sample_rate || frame.channels != self.channels {
+            self.reset(frame.sample_rate, frame.channels);
+        }
+
+        let mut completed = 0;
+        for chunk in frame.samples.chunks_exact(self.channels as usize) {
+            for (sample, filter) in chunk.iter().zip(self.filters.iter_mut()) {
+                let y = filter.process(*sample as f64 / 32768.0);
+                self.block_sum += y * y;
+            }
+            self.block_filled += 1;
+            if self.block_filled == self.block_len {
+                if self.blocks.len() == SHORT_TERM_BLOCKS {
+                    self.blocks.pop_front();
+                }
+                self.blocks
+                    .push_back(self.block_sum / self.block_len as f64);
+                self.block_sum = 0.0;
+                self.block_filled = 0;
+                completed += 1;
+            }
+        }
+        completed
+    }
+
+    /// Short-Term-Lautheit in LUFS; `None`, solange noch kein Block voll ist.
+    pub fn short_term(&self) -> Option<f32> {
+        if self.blocks.is_empty() {
+            return None;
+        }
+        let mean = self.blocks.iter().sum::<f64>() / self.blocks.len() as f64;
+        if mean <= 0.0 {
+            return Some(f32::NEG_INFINITY);
+        }
+        Some((-0.691 + 10.0 * mean.log10()) as f32)
+    }
+
+    /// Lautheit des zuletzt abgeschlossenen 100-ms-Blocks (Momentary-ähnlich).
+    pub fn last_block(&self) -> Option<f32> {
+        let mean = *self.blocks.back()?;
+        if mean <= 0.0 {
+            return Some(f32::NEG_INFINITY);
+        }
+        Some((-0.691 + 10.0 * mean.log10()) as f32)
+    }
+}
+
+fn power_to_lufs(power: f64) -> f64 {
+    -0.691 + 10.0 * power.log10()
+}
+
+/// Integrierte Lautheit (BS.1770 Gating) plus Short-Term-Verlauf.
+#[derive(Default)]
+pub struct IntegratedLoudness {
+    meter: LoudnessMeter,
+    /// Mittleres Quadrat je abgeschlossenem 100-ms-Block, ganze Laufzeit
+    blocks: Vec<f64>,
+    max_short_term: Option<f32>,
+}
+
+impl IntegratedLoudness {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, frame: &PcmFrame) {
+        let completed = self.meter.push(frame);
+        let recent = &self.meter.blocks;
+        self.blocks
+            .extend(recent.range(recent.len() - completed.min(recent.len())..));
+        if completed > 0 {
+            if let Some(short_term) = self.meter.short_term().filter(|v| v.is_finite()) {
+                self.max_short_term = Some(match self.max_short_term {
+                    Some(max) => max.max(short_term),
+                    None => short_term,
+                });
+            }
+        }
+    }
+
+    /// Integrierte Lautheit in LUFS; `None` ohne Fenster oberhalb von −70 LUFS.
+    pub fn integrated(&self) -> Option<f32> {
+        let windows: Vec<f64> = self
+            .blocks
+            .windows(GATE_BLOCKS)
+            .map(|window| window.iter().sum::<f64>() / GATE_BLOCKS as f64)
+            .collect();
+        let gated_mean = |threshold: f64| {
+            let above: Vec<f64> = windows
+                .iter()
+                .copied()
+                .filter(|power| *power > 0.0 && power_to_lufs(*power) > threshold)
+                .collect();
+            (!above.is_empty()).then(|| above.iter().sum::<f64>() / above.len() as f64)
+        };
+        let absolute = gated_mean(SILENCE_LUFS as f64)?;
+        let relative = gated_mean(power_to_lufs(absolute) - RELATIVE_GATE_LU)?;
+        Some(power_to_lufs(relative) as f32)
+    }
+
+    /// Höchste Short-Term-Lautheit bisher.
+    pub fn max_short_term(&self) -> Option<f32> {
+        self.max_short_term
+    }
+
+    /// Lautheit je 100-ms-Block in LUFS (−∞ bei digitaler Stille).
+    pub fn block_loudness(&self) -> impl Iterator<Item = f32> + '_ {
+        self.blocks.iter().map(|power| {
+            if *power > 0.0 {
+                power_to_lufs(*power) as f32
+            } else {
+                f32::NEG_INFINITY
+            }
+        })
+    }
+}
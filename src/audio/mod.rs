@@ -4,10 +4,19 @@ use std::sync::Arc;
 
 use crate::ring::{EncodedRingRead, EncodedSource};
 
+pub mod archive;
+pub mod fingerprint;
 pub mod http;
 pub mod live;
+pub mod loudness;
+pub mod ogg;
 pub mod path;
+pub mod resample;
+pub mod spectrum;
 pub mod timeshift;
+pub mod true_peak;
+pub mod wav;
+pub mod waveform;
 
 pub use path::sanitize_audio_path;
 
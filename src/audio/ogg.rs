@@ -0,0 +1,446 @@
+// src/audio/ogg.rs
+//
+// Ogg-Seiten lesen/schreiben und Chaining für Live-Streams: `OggChainer`
+// sitzt hinter einem Opus- oder Vorbis-Encoder und beendet bei neuen
+// Metadaten den laufenden logischen Stream (EOS) und beginnt an der nächsten
+// Seitengrenze einen neuen (BOS, neue Serial) mit denselben Codec-Headern und
+// aktualisiertem Kommentar-Header. Player wie VLC, ffmpeg oder Browser zeigen
+// den neuen Titel, ohne dass die Icecast-Verbindung neu aufgebaut wird.
+use anyhow::{bail, Result};
+
+const CAPTURE_PATTERN: &[u8; 4] = b"OggS";
+const HEADER_LEN: usize = 27;
+const FLAG_CONTINUED: u8 = 0x01;
+const FLAG_BOS: u8 = 0x02;
+const FLAG_EOS: u8 = 0x04;
+/// Granule-Position „kein Paket endet auf dieser Seite“
+pub const NO_GRANULE: u64 = u64::MAX;
+/// Obergrenze für gepufferte, noch unvollständige Seiten
+const MAX_PENDING: usize = 1 << 20;
+
+/// CRC-32 nach Ogg-Spezifikation (Polynom 0x04c11db7, ohne Reflexion).
+pub fn crc32(data: &[u8]) -> u32 {
+    static TABLE: std::sync::OnceLock<[u32; 256]> = std::sync::OnceLock::new();
+    let table = TABLE.get_or_init(|| {
+        let mut table = [0u32; 256];
+        for (index, entry) in table.iter_mut().enumerate() {
+            let mut crc = (index as u32) << 24;
+            for _ in 0..8 {
+                crc = if crc & 0x8000_0000 != 0 {
+                    (crc << 1) ^ 0x04c1_1db7
+                } else {
+                    crc << 1
+                };
+            }
+            *entry = crc;
+        }
+        table
+    });
+    data.iter().fold(0u32, |crc, byte| {
+        (crc << 8) ^ table[(((crc >> 24) as u8) ^ byte) as usize]
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OggPage {
+    pub header_type: u8,
+    pub granule: u64,
+    pub serial: u32,
+    pub sequence: u32,
+    /// Lacing-Werte (je höchstens 255)
+    pub segments: Vec<u8>,
+    pub body: Vec<u8>,
+}
+
+impl OggPage {
+    /// Eine vollständige Seite am Anfang von `data` samt Länge; `Ok(None)`,
+    /// wenn noch Bytes fehlen.
+    pub fn parse(data: &[u8]) -> Result<Option<(OggPage, usize)>> {
+        if data.len() < HEADER_LEN {
+            return Ok(None);
+        }
+        if &data[..4] != CAPTURE_PATTERN || data[4] != 0 {
+            bail!("not an Ogg page");
+        }
+        let segment_count = data[26] as usize;
+        let Some(segments) = data.get(HEADER_LEN..HEADER_LEN + segment_count) else {
+            return Ok(None);
+        };
+        let body_len: usize = segments.iter().map(|lace| *lace as usize).sum();
+        let total = HEADER_LEN + segment_count + body_len;
+        let Some(page) = data.get(..total) else {
+            return Ok(None);
+        };
+        let mut unchecked = page.to_vec();
+        unchecked[22..26].fill(0);
+        if crc32(&unchecked) != u32::from_le_bytes(page[22..26].try_into()?) {
+            bail!("Ogg page CRC mismatch");
+        }
+        Ok(Some((
+            OggPage {
+                header_type: page[5],
+                granule: u64::from_le_bytes(page[6..14].try_into()?),
+                serial: u32::from_le_bytes(page[14..18].try_into()?),
+                sequence: u32::from_le_bytes(page[18..22].try_into()?),
+                segments: segments.to_vec(),
+                body: page[HEADER_LEN + segment_count..].to_vec(),
+            },
+            total,
+        )))
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(HEADER_LEN + self.segments.len() + self.body.len());
+        out.extend_from_slice(CAPTURE_PATTERN);
+        out.push(0);
+        out.push(self.header_type);
+        out.extend_from_slice(&self.granule.to_le_bytes());
+        out.extend_from_slice(&self.serial.to_le_bytes());
+        out.extend_from_slice(&self.sequence.to_le_bytes());
+        out.extend_from_slice(&[0; 4]);
+        out.push(self.segments.len() as u8);
+        out.extend_from_slice(&self.segments);
+        out.extend_from_slice(&self.body);
+        let crc = crc32(&out);
+        out[22..26].copy_from_slice(&crc.to_le_bytes());
+        out
+    }
+
+    pub fn is_continued(&self) -> bool {
+        self.header_type & FLAG_CONTINUED != 0
+    }
+
+    pub fn is_bos(&self) -> bool {
+        self.header_type & FLAG_BOS != 0
+    }
+
+    pub fn is_eos(&self) -> bool {
+        self.header_type & FLAG_EOS != 0
+    }
+
+    /// Paketteile der Seite mit „Paket endet hier“-Markierung.
+    pub fn packet_parts(&self) -> Vec<(&[u8], bool)> {
+        let mut parts = Vec::new();
+        let mut start = 0;
+        let mut len = 0;
+        for lace in &self.segments {
+            len += *lace as usize;
+            if *lace < 255 {
+                parts.push((&self.body[start..start + len], true));
+                start += len;
+                len = 0;
+            }
+        }
+        if len > 0 || start < self.body.len() {
+            parts.push((&self.body[start..start + len], false));
+        }
+        parts
+    }
+}
+
+/// Verteilt ganze Pakete auf Seiten (höchstens 255 Segmente je Seite);
+/// Header-Seiten tragen Granule 0.
+pub fn paginate(packets: &[Vec<u8>], serial: u32, sequence: &mut u32, bos: bool) -> Vec<OggPage> {
+    let mut pages = Vec::new();
+    let mut header_type = if bos { FLAG_BOS } else { 0 };
+    let mut segments = Vec::new();
+    let mut body = Vec::new();
+    let mut finish = |segments: &mut Vec<u8>, body: &mut Vec<u8>, header_type: u8| {
+        let ends_packet = segments.iter().any(|lace| *lace < 255);
+        pages.push(OggPage {
+            header_type,
+            granule: if ends_packet { 0 } else { NO_GRANULE },
+            serial,
+            sequence: *sequence,
+            segments: std::mem::take(segments),
+            body: std::mem::take(body),
+        });
+        *sequence = sequence.wrapping_add(1);
+    };
+    for packet in packets {
+        let mut rest = packet.as_slice();
+        loop {
+            if segments.len() == 255 {
+                let continued = segments.last() == Some(&255);
+                finish(&mut segments, &mut body, header_type);
+                header_type = if continued { FLAG_CONTINUED } else { 0 };
+            }
+            let take = rest.len().min(255);
+            segments.push(take as u8);
+            body.extend_from_slice(&rest[..take]);
+            rest = &rest[take..];
+            if take < 255 {
+                break;
+            }
+        }
+    }
+    if !segments.is_empty() {
+        finish(&mut segments, &mut body, header_type);
+    }
+    pages
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OggCodec {
+    Opus,
+    Vorbis,
+}
+
+impl OggCodec {
+    fn detect(id_header: &[u8]) -> Option<Self> {
+        if id_header.starts_with(b"OpusHead") {
+            Some(Self::Opus)
+        } else if id_header.starts_with(b"\x01vorbis") {
+            Some(Self::Vorbis)
+        } else {
+            None
+        }
+    }
+
+    /// ID-, Kommentar- (und bei Vorbis Setup-) Header
+    fn header_count(self) -> usize {
+        match self {
+            Self::Opus => 2,
+            Self::Vorbis => 3,
+        }
+    }
+
+    fn comment_magic(self) -> &'static [u8] {
+        match self {
+            Self::Opus => b"OpusTags",
+            Self::Vorbis => b"\x03vorbis",
+        }
+    }
+
+    /// Kommentar-Header mit dem Vendor-String des Encoders.
+    fn comment_header(self, vendor: &[u8], comments: &[String]) -> Vec<u8> {
+        let mut packet = self.comment_magic().to_vec();
+        packet.extend_from_slice(&(vendor.len() as u32).to_le_bytes());
+        packet.extend_from_slice(vendor);
+        packet.extend_from_slice(&(comments.len() as u32).to_le_bytes());
+        for comment in comments {
+            packet.extend_from_slice(&(comment.len() as u32).to_le_bytes());
+            packet.extend_from_slice(comment.as_bytes());
+        }
+        if self == Self::Vorbis {
+            // Framing-Bit
+            packet.push(1);
+        }
+        packet
+    }
+
+    fn vendor(self, comment_header: &[u8]) -> Vec<u8> {
+        let magic = self.comment_magic().len();
+        comment_header
+            .get(magic..magic + 4)
+            .and_then(|len| len.try_into().ok())
+            .map(|len| u32::from_le_bytes(len) as usize)
+            .and_then(|len| comment_header.get(magic + 4..magic + 4 + len))
+            .unwrap_or(b"airlift")
+            .to_vec()
+    }
+}
+
+enum Phase {
+    /// Header-Pakete des ersten Streams werden gesammelt
+    Headers {
+        packets: Vec<Vec<u8>>,
+        partial: Vec<u8>,
+    },
+    Streaming,
+}
+
+/// Schreibt den Ogg-Strom eines Encoders um: Serial, Sequenznummern und
+/// Granule-Positionen stammen vom Chainer, damit neue Links nahtlos anschließen.
+pub struct OggChainer {
+    pending: Vec<u8>,
+    phase: Phase,
+    codec: Option<OggCodec>,
+    /// ID-Header (bei Opus im Link ohne Pre-Skip) und Setup-Header (Vorbis)
+    id_header: Vec<u8>,
+    setup_header: Option<Vec<u8>>,
+    vendor: Vec<u8>,
+    serial: u32,
+    sequence: u32,
+    /// Granule des Encoders beim Beginn des aktuellen Links
+    granule_base: u64,
+    last_granule: u64,
+    comments: Option<Vec<String>>,
+    pending_comments: Option<Vec<String>>,
+    links: u64,
+}
+
+impl Default for OggChainer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OggChainer {
+    pub fn new() -> Self {
+        Self {
+            pending: Vec::new(),
+            phase: Phase::Headers {
+                packets: Vec::new(),
+                partial: Vec::new(),
+            },
+            codec: None,
+            id_header: Vec::new(),
+            setup_header: None,
+            vendor: Vec::new(),
+            serial: 0,
+            sequence: 0,
+            granule_base: 0,
+            last_granule: 0,
+            comments: None,
+            pending_comments: None,
+            links: 0,
+        }
+    }
+
+    /// Neue Kommentare (`TITLE=...`); vor dem ersten Audio ersetzen sie den
+    /// Kommentar-Header des Encoders, danach beginnt ein neuer Link.
+    pub fn set_comments(&mut self, comments: Vec<String>) {
+        self.pending_comments = Some(comments);
+    }
+
+    pub fn codec(&self) -> Option<OggCodec> {
+        self.codec
+    }
+
+    /// Kommentare des aktuellen Links, falls vom Chainer gesetzt
+    pub fn comments(&self) -> Option<&[String]> {
+        self.comments.as_deref()
+    }
+
+    /// Anzahl begonnener Links nach dem ersten
+    pub fn links(&self) -> u64 {
+        self.links
+    }
+
+    /// Bytes des Encoders hinein, umgeschriebener Ogg-Strom heraus.
+    pub fn push(&mut self, data: &[u8]) -> Result<Vec<u8>> {
+        self.pending.extend_from_slice(data);
+        let mut out = Vec::new();
+        let mut consumed = 0;
+        while let Some((page, len)) = OggPage::parse(&self.pending[consumed..])? {
+            consumed += len;
+            self.page(page, &mut out)?;
+        }
+        self.pending.drain(..consumed);
+        if self.pending.len() > MAX_PENDING {
+            bail!("Ogg page exceeds {} bytes", MAX_PENDING);
+        }
+        Ok(out)
+    }
+
+    fn page(&mut self, page: OggPage, out: &mut Vec<u8>) -> Result<()> {
+        if let Phase::Headers { packets, partial } = &mut self.phase {
+            if page.is_bos() {
+                self.serial = page.serial;
+            }
+            for (part, complete) in page.packet_parts() {
+                partial.extend_from_slice(part);
+                if complete {
+                    packets.push(std::mem::take(partial));
+                }
+            }
+            if self.codec.is_none() {
+                let Some(id) = packets.first() else {
+                    return Ok(());
+                };
+                self.codec = Some(OggCodec::detect(id).ok_or_else(|| {
+                    anyhow::anyhow!("Ogg chaining supports Opus and Vorbis only")
+                })?);
+            }
+            let codec = self.codec.unwrap_or(OggCodec::Opus);
+            if packets.len() < codec.header_count() {
+                return Ok(());
+            }
+            let mut packets = std::mem::take(packets);
+            self.phase = Phase::Streaming;
+            self.id_header = packets.remove(0);
+            self.vendor = codec.vendor(&packets[0]);
+            self.setup_header = packets.get(1).cloned();
+            let comment_header = match self.pending_comments.take() {
+                Some(comments) => {
+                    let header = codec.comment_header(&self.vendor, &comments);
+                    self.comments = Some(comments);
+                    header
+                }
+                None => packets.remove(0),
+            };
+            self.write_headers(comment_header, out);
+            if codec == OggCodec::Opus {
+                // Folgende Links starten mitten im Signal: kein Pre-Skip
+                if let Some(pre_skip) = self.id_header.get_mut(10..12) {
+                    pre_skip.fill(0);
+                }
+            }
+            return Ok(());
+        }
+
+        if self.pending_comments.is_some() && !page.is_continued() && !page.is_eos() {
+            self.chain(out);
+        }
+        let granule = if page.granule == NO_GRANULE {
+            NO_GRANULE
+        } else {
+            self.last_granule = page.granule;
+            page.granule.saturating_sub(self.granule_base)
+        };
+        let rewritten = OggPage {
+            header_type: page.header_type & !FLAG_BOS,
+            granule,
+            serial: self.serial,
+            sequence: self.sequence,
+            ..page
+        };
+        self.sequence = self.sequence.wrapping_add(1);
+        out.extend_from_slice(&rewritten.to_bytes());
+        Ok(())
+    }
+
+    fn write_headers(&mut self, comment_header: Vec<u8>, out: &mut Vec<u8>) {
+        self.sequence = 0;
+        for page in paginate(
+            std::slice::from_ref(&self.id_header),
+            self.serial,
+            &mut self.sequence,
+            true,
+        ) {
+            out.extend_from_slice(&page.to_bytes());
+        }
+        let mut rest = vec![comment_header];
+        rest.extend(self.setup_header.clone());
+        for page in paginate(&rest, self.serial, &mut self.sequence, false) {
+            out.extend_from_slice(&page.to_bytes());
+        }
+    }
+
+    /// Laufenden Link beenden und einen neuen mit den neuen Kommentaren beginnen.
+    fn chain(&mut self, out: &mut Vec<u8>) {
+        let Some(codec) = self.codec else {
+            return;
+        };
+        let Some(comments) = self.pending_comments.take() else {
+            return;
+        };
+        let eos = OggPage {
+            header_type: FLAG_EOS,
+            granule: self.last_granule.saturating_sub(self.granule_base),
+            serial: self.serial,
+            sequence: self.sequence,
+            segments: Vec::new(),
+            body: Vec::new(),
+        };
+        out.extend_from_slice(&eos.to_bytes());
+
+        self.serial = self.serial.wrapping_add(1);
+        self.granule_base = self.last_granule;
+        self.links += 1;
+        let header = codec.comment_header(&self.vendor, &comments);
+        self.comments = Some(comments);
+        self.write_headers(header, out);
+    }
+}
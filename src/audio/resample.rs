@@ -0,0 +1,232 @@
+// src/audio/resample.rs
+//
+// Abtastratenwandlung mit polyphasem, Kaiser-gefenstertem Sinc-Filter. Das
+// Verhältnis wird auf ganze Zahlen gekürzt (44,1 → 48 kHz = 160/147), die
+// Position läuft exakt in Bruchteilen eines Input-Samples und driftet nicht.
+// Die Filtertabelle hat eine feste Zahl Phasen, Zwischenwerte werden linear
+// interpoliert; damit geht jedes Verhältnis. Bei Abwärtswandlung liegt die
+// Grenzfrequenz unter der neuen Nyquist-Frequenz (Anti-Aliasing).
+//
+// Eingang in beliebig großen Stücken; das Ausgangs-Sample `n` liegt genau
+// auf Input-Position `n * in / out`, die Verzögerung beträgt `latency_frames`
+// Input-Samples.
+
+/// Phasen der Filtertabelle (Zwischenwerte linear interpoliert)
+const PHASES: usize = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResampleQuality {
+    /// 8 Nulldurchgänge je Seite, wenig Rechenzeit
+    Low,
+    Medium,
+    /// 32 Nulldurchgänge je Seite, > 90 dB Sperrdämpfung
+    High,
+}
+
+impl ResampleQuality {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "low" => Some(Self::Low),
+            "medium" => Some(Self::Medium),
+            "high" => Some(Self::High),
+            _ => None,
+        }
+    }
+
+    /// Nulldurchgänge je Seite, Anteil der Nyquist-Frequenz, Kaiser-Beta
+    fn parameters(self) -> (usize, f64, f64) {
+        match self {
+            Self::Low => (8, 0.85, 6.0),
+            Self::Medium => (16, 0.92, 8.0),
+            Self::High => (32, 0.95, 10.0),
+        }
+    }
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Modifizierte Bessel-Funktion erster Art, Ordnung 0 (Reihenentwicklung)
+fn bessel_i0(x: f64) -> f64 {
+    let mut sum = 1.0;
+    let mut term = 1.0;
+    let half = x / 2.0;
+    for k in 1..64 {
+        term *= (half / k as f64) * (half / k as f64);
+        sum += term;
+        if term < sum * 1e-12 {
+            break;
+        }
+    }
+    sum
+}
+
+pub struct Resampler {
+    input_rate: u32,
+    output_rate: u32,
+    channels: usize,
+    /// Gekürztes Verhältnis: `up` Ausgangs- auf `down` Input-Samples
+    up: u64,
+    down: u64,
+    /// Filterbreite je Seite in Input-Samples
+    half: usize,
+    /// `PHASES + 1` Zeilen zu je `2 * half` Koeffizienten
+    table: Vec<f32>,
+    /// Input je Kanal ab dem ältesten noch benötigten Sample
+    history: Vec<Vec<f32>>,
+    /// Index in `history` des Samples vor der aktuellen Ausgabeposition
+    center: usize,
+    /// Abstand der Ausgabeposition zu `center` in 1/`up` Input-Samples
+    frac: u64,
+    coefficients: Vec<f32>,
+}
+
+impl Resampler {
+    pub fn new(input_rate: u32, output_rate: u32, channels: u8, quality: ResampleQuality) -> Self {
+        let divisor = gcd(input_rate.max(1) as u64, output_rate.max(1) as u64);
+        let up = output_rate.max(1) as u64 / divisor;
+        let down = input_rate.max(1) as u64 / divisor;
+
+        let (zero_crossings, rolloff, beta) = quality.parameters();
+        // Grenzfrequenz relativ zur Input-Nyquist-Frequenz
+        let cutoff = (up as f64 / down as f64).min(1.0) * rolloff;
+        let half = (zero_crossings as f64 / cutoff).ceil() as usize;
+        let taps = 2 * half;
+        let window_norm = bessel_i0(beta);
+
+        let mut table = vec![0.0f32; (PHASES + 1) * taps];
+        for phase in 0..=PHASES {
+            let offset = phase as f64 / PHASES as f64;
+            let row = &mut table[phase * taps..(phase + 1) * taps];
+            let mut sum = 0.0;
+            let mut values = vec![0.0f64; taps];
+            for (k, value) in values.iter_mut().enumerate() {
+                // Abstand des Input-Samples zur Ausgabeposition
+                let x = k as f64 - (half - 1) as f64 - offset;
+                let arg = std::f64::consts::PI * cutoff * x;
+                let sinc = if x.abs() < 1e-12 {
+                    1.0
+                } else {
+                    arg.sin() / arg
+                };
+                let ratio = x / half as f64;
+                let window = if ratio.abs() >= 1.0 {
+                    0.0
+                } else {
+                    bessel_i0(beta * (1.0 - ratio * ratio).sqrt()) / window_norm
+                };
+                *value = sinc * window;
+                sum += *value;
+            }
+            // Gleichanteil je Phase auf 1 normieren
+            for (tap, value) in row.iter_mut().zip(values) {
+                *tap = (value / sum) as f32;
+            }
+        }
+
+        let channels = channels.max(1) as usize;
+        Self {
+            input_rate,
+            output_rate,
+            channels,
+            up,
+            down,
+            half,
+            table,
+            // Vorlauf aus Stille, damit das erste Sample mittig im Filter liegt
+            history: vec![vec![0.0; half - 1]; channels],
+            center: half - 1,
+            frac: 0,
+            coefficients: vec![0.0; taps],
+        }
+    }
+
+    pub fn input_rate(&self) -> u32 {
+        self.input_rate
+    }
+
+    pub fn output_rate(&self) -> u32 {
+        self.output_rate
+    }
+
+    pub fn channels(&self) -> u8 {
+        self.channels as u8
+    }
+
+    /// Input-Samples, die noch im Filter stecken, bevor sie im Ausgang landen
+    pub fn latency_frames(&self) -> usize {
+        self.half
+    }
+
+    /// Input-Samples je Ausgangs-Sample (`in / out`)
+    pub fn step(&self) -> f64 {
+        self.down as f64 / self.up as f64
+    }
+
+    /// Nimmt interleavte Samples an und liefert alle Ausgangs-Samples, die
+    /// dadurch berechenbar wurden (interleaved, gleiche Kanalzahl).
+    pub fn process(&mut self, input: &[i16]) -> Vec<i16> {
+        for chunk in input.chunks_exact(self.channels) {
+            for (history, sample) in self.history.iter_mut().zip(chunk) {
+                history.push(*sample as f32);
+            }
+        }
+
+        let taps = 2 * self.half;
+        let available = self.history[0].len();
+        let estimate = ((available.saturating_sub(self.center + self.half)) as u64 * self.up
+            / self.down
+            + 1) as usize;
+        let mut output = Vec::with_capacity(estimate * self.channels);
+
+        while self.center + self.half < available {
+            let scaled = self.frac * PHASES as u64;
+            let phase = (scaled / self.up) as usize;
+            let weight = (scaled % self.up) as f32 / self.up as f32;
+            let lower = &self.table[phase * taps..(phase + 1) * taps];
+            let upper = &self.table[(phase + 1) * taps..(phase + 2) * taps];
+            for ((coefficient, a), b) in self.coefficients.iter_mut().zip(lower).zip(upper) {
+                *coefficient = a + (b - a) * weight;
+            }
+
+            let start = self.center + 1 - self.half;
+            for history in &self.history {
+                let value: f32 = history[start..start + taps]
+                    .iter()
+                    .zip(&self.coefficients)
+                    .map(|(sample, coefficient)| sample * coefficient)
+                    .sum();
+                output.push(value.round().clamp(-32768.0, 32767.0) as i16);
+            }
+
+            self.frac += self.down;
+            self.center += (self.frac / self.up) as usize;
+            self.frac %= self.up;
+        }
+
+        // Nicht mehr benötigte Samples verwerfen
+        let consumed = (self.center + 1).saturating_sub(self.half).min(available);
+        if consumed > 0 {
+            for history in &mut self.history {
+                history.drain(..consumed);
+            }
+            self.center -= consumed;
+        }
+        output
+    }
+
+    /// Verwirft den Filterinhalt (z. B. nach einer Lücke im Input).
+    pub fn reset(&mut self) {
+        for history in &mut self.history {
+            history.clear();
+            history.resize(self.half - 1, 0.0);
+        }
+        self.center = self.half - 1;
+        self.frac = 0;
+    }
+}
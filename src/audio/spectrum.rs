@@ -0,0 +1,112 @@
+// src/audio/spectrum.rs
+//
+// Oktavband-Spektrum (31.5 Hz … 16 kHz) für Analyzer-Abgriffe: Bandpässe
+// konstanter Güte auf der Mono-Summe, Pegel je Band als RMS in dBFS
+// (Vollaussteuerungs-Sinus = 0 dB). Für Anzeigen, kein Messgerät nach IEC 61260.
+use crate::audio::loudness::Biquad;
+use crate::ring::PcmFrame;
+
+/// Mittenfrequenzen der Oktavbänder
+pub const OCTAVE_BANDS_HZ: [f32; 10] = [
+    31.5, 63.0, 125.0, 250.0, 500.0, 1_000.0, 2_000.0, 4_000.0, 8_000.0, 16_000.0,
+];
+/// Untergrenze der Anzeige; Stille liefert diesen Wert statt −∞.
+pub const SPECTRUM_FLOOR_DB: f32 = -120.0;
+/// Güte für eine Oktave Bandbreite: √2 / (2 − 1)
+const OCTAVE_Q: f64 = std::f64::consts::SQRT_2;
+
+/// Bandpass mit 0 dB in der Mitte (RBJ Audio EQ Cookbook).
+fn bandpass(center_hz: f64, sample_rate: u32) -> Biquad {
+    let w0 = 2.0 * std::f64::consts::PI * center_hz / sample_rate as f64;
+    let alpha = w0.sin() / (2.0 * OCTAVE_Q);
+    let a0 = 1.0 + alpha;
+    Biquad {
+        b: [alpha / a0, 0.0, -alpha / a0],
+        a: [-2.0 * w0.cos() / a0, (1.0 - alpha) / a0],
+        z: [0.0; 2],
+    }
+}
+
+/// Sammelt Bandenergien, bis `take` ein Fenster abschließt. Ein Wechsel
+/// der Samplerate setzt Filter und angefangenes Fenster zurück.
+pub struct SpectrumAnalyzer {
+    sample_rate: u32,
+    /// `None` für Bänder oberhalb von ~0.45 × Samplerate
+    filters: Vec<Option<Biquad>>,
+    sum_sq: Vec<f64>,
+    frames: u64,
+}
+
+impl Default for SpectrumAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SpectrumAnalyzer {
+    pub fn new() -> Self {
+        Self {
+            sample_rate: 0,
+            filters: Vec::new(),
+            sum_sq: vec![0.0; OCTAVE_BANDS_HZ.len()],
+            frames: 0,
+        }
+    }
+
+    fn reset(&mut self, sample_rate: u32) {
+        let nyquist = sample_rate as f64 / 2.0;
+        self.sample_rate = sample_rate;
+        self.filters = OCTAVE_BANDS_HZ
+            .iter()
+            .map(|hz| ((*hz as f64) < nyquist * 0.9).then(|| bandpass(*hz as f64, sample_rate)))
+            .collect();
+        self.sum_sq = vec![0.0; OCTAVE_BANDS_HZ.len()];
+        self.frames = 0;
+    }
+
+    pub fn push(&mut self, frame: &PcmFrame) {
+        let channels = frame.channels as usize;
+        if channels == 0 || frame.sample_rate == 0 {
+            return;
+        }
+        if frame.sample_rate != self.sample_rate {
+            self.reset(frame.sample_rate);
+        }
+        for chunk in frame.samples.chunks_exact(channels) {
+            let mono = chunk.iter().map(|s| *s as f64).sum::<f64>() / (channels as f64 * 32768.0);
+            for (filter, sum) in self.filters.iter_mut().zip(self.sum_sq.iter_mut()) {
+                if let Some(filter) = filter {
+                    let y = filter.process(mono);
+                    *sum += y * y;
+                }
+            }
+            self.frames += 1;
+        }
+    }
+
+    /// Sample-Frames im angefangenen Fenster.
+    pub fn frames(&self) -> u64 {
+        self.frames
+    }
+
+    /// Schließt das Fenster: Pegel je Band in dBFS, `None` für Bänder, die
+    /// bei der aktuellen Samplerate nicht messbar sind.
+    pub fn take(&mut self) -> Vec<Option<f32>> {
+        let frames = self.frames.max(1) as f64;
+        let levels = self
+            .filters
+            .iter()
+            .zip(&self.sum_sq)
+            .map(|(filter, sum)| {
+                filter.map(|_| {
+                    // +3 dB: Sinus-RMS auf Spitzenwert bezogen
+                    let db = 10.0 * (2.0 * sum / frames).log10();
+                    (db as f32).max(SPECTRUM_FLOOR_DB)
+                })
+            })
+            .collect();
+        self.sum_sq.iter_mut().for_each(|sum| *sum = 0.0);
+        self.frames = 0;
+        levels
+    }
+}
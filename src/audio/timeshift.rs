@@ -1,4 +1,5 @@
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::thread::sleep;
 use std::time::Duration;
 
@@ -8,6 +9,32 @@ use log::{debug, info, warn};
 /// Audio-Parameter (müssen zu Recorder passen)
 const SAMPLE_RATE: u32 = 48_000;
 const CHANNELS: u16 = 2;
+/// Samples pro Lese-Block
+const READ_BLOCK_SAMPLES: usize = 4096;
+/// Speicher einer Session: Sample-Block, PCM-Bytes und hound-Lesepuffer (8 KiB)
+pub const SESSION_MEMORY_BYTES: usize = READ_BLOCK_SAMPLES * 2 + READ_BLOCK_SAMPLES * 2 + 8 * 1024;
+
+static ACTIVE_SESSIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// Laufende Timeshift-Streams (für `/api/memory`).
+pub fn active_sessions() -> usize {
+    ACTIVE_SESSIONS.load(Ordering::Relaxed)
+}
+
+struct SessionGuard;
+
+impl SessionGuard {
+    fn enter() -> Self {
+        ACTIVE_SESSIONS.fetch_add(1, Ordering::Relaxed);
+        Self
+    }
+}
+
+impl Drop for SessionGuard {
+    fn drop(&mut self) {
+        ACTIVE_SESSIONS.fetch_sub(1, Ordering::Relaxed);
+    }
+}
 
 /// Blockierender Timeshift-Reader mit hound.
 /// Ruft `on_pcm(bytes)` für jedes gelesene PCM-Chunk auf.
@@ -17,6 +44,7 @@ pub fn stream_timeshift(
     mut on_pcm: impl FnMut(&[u8]) -> anyhow::Result<()>,
 ) -> anyhow::Result<()> {
     info!("[timeshift] starting at ts={} ms", start_ts_ms);
+    let _session = SessionGuard::enter();
 
     let mut cur_ts_ms = start_ts_ms;
     let mut retry_count = 0;
@@ -104,7 +132,7 @@ pub fn stream_timeshift(
         // Streaming-Loop für diese Stunde
         let mut bytes_read_total = 0;
         let mut chunk_count = 0;
-        let mut samples_buf = vec![0i16; 4096]; // 2KB Samples = 4KB Bytes
+        let mut samples_buf = vec![0i16; READ_BLOCK_SAMPLES]; // 4096 Samples = 8KB Bytes
 
         loop {
             // Samples mit hound lesen - KORRIGIERT für hound 3.5.1
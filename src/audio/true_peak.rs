@@ -0,0 +1,98 @@
+// src/audio/true_peak.rs
+//
+// True-Peak nach dem Verfahren aus ITU-R BS.1770 Anhang 2: 4-fache
+// Überabtastung mit einem polyphasen Interpolationsfilter (12 Taps je Phase,
+// Hann-gefensterter Sinc), Maximum des Betrags über alle Kanäle. Der Filter
+// ist selbst berechnet, nicht die Koeffizienten der Norm.
+use crate::ring::PcmFrame;
+
+const OVERSAMPLE: usize = 4;
+const TAPS: usize = 12;
+
+/// Koeffizienten je Phase, auf Gleichanteil 1 normiert. Phase 0 trifft das
+/// Original-Sample (Verzögerung `TAPS / 2`).
+fn interpolation_filter() -> [[f64; TAPS]; OVERSAMPLE] {
+    let half_span = TAPS as f64 / 2.0 + 0.5;
+    let mut phases = [[0.0; TAPS]; OVERSAMPLE];
+    for (phase, taps) in phases.iter_mut().enumerate() {
+        for (k, tap) in taps.iter_mut().enumerate() {
+            let x = k as f64 - (TAPS / 2) as f64 + phase as f64 / OVERSAMPLE as f64;
+            let sinc = if x == 0.0 {
+                1.0
+            } else {
+                (std::f64::consts::PI * x).sin() / (std::f64::consts::PI * x)
+            };
+            let window = 0.5 * (1.0 + (std::f64::consts::PI * x / half_span).cos());
+            *tap = sinc * window;
+        }
+        let sum: f64 = taps.iter().sum();
+        taps.iter_mut().for_each(|tap| *tap /= sum);
+    }
+    phases
+}
+
+/// Höchster Inter-Sample-Pegel seit dem Anlegen; ein Kanalwechsel setzt die
+/// Filterhistorie zurück, nicht den Spitzenwert.
+pub struct TruePeakMeter {
+    filter: [[f64; TAPS]; OVERSAMPLE],
+    channels: usize,
+    /// Letzte `TAPS` Samples je Kanal, Ringpuffer ab `pos`
+    history: Vec<[f64; TAPS]>,
+    pos: usize,
+    peak: f64,
+}
+
+impl Default for TruePeakMeter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TruePeakMeter {
+    pub fn new() -> Self {
+        Self {
+            filter: interpolation_filter(),
+            channels: 0,
+            history: Vec::new(),
+            pos: 0,
+            peak: 0.0,
+        }
+    }
+
+    pub fn push(&mut self, frame: &PcmFrame) {
+        let channels = frame.channels as usize;
+        if channels == 0 {
+            return;
+        }
+        if channels != self.channels {
+            self.channels = channels;
+            self.history = vec![[0.0; TAPS]; channels];
+            self.pos = 0;
+        }
+        for chunk in frame.samples.chunks_exact(channels) {
+            self.pos = (self.pos + 1) % TAPS;
+            for (sample, history) in chunk.iter().zip(self.history.iter_mut()) {
+                history[self.pos] = *sample as f64 / 32768.0;
+                for taps in &self.filter {
+                    // taps[k] gehört zum Sample k Schritte vor dem neuesten
+                    let y: f64 = taps
+                        .iter()
+                        .enumerate()
+                        .map(|(k, tap)| tap * history[(self.pos + TAPS - k) % TAPS])
+                        .sum();
+                    self.peak = self.peak.max(y.abs());
+                }
+            }
+        }
+    }
+
+    /// Linearer Spitzenwert (1.0 = Vollaussteuerung).
+    pub fn peak(&self) -> f64 {
+        self.peak
+    }
+
+    /// Spitzenwert in dBTP; `None` bei digitaler Stille.
+    pub fn dbtp(&self) -> Option<f32> {
+        (self.peak > 0.0).then(|| (20.0 * self.peak.log10()) as f32)
+    }
+}
@@ -0,0 +1,208 @@
+// src/audio/wav.rs
+//
+// WAV-Header für den Recorder: RIFF/WAVE mit einem `JUNK`-Platzhalter direkt
+// hinter dem Kopf, optional `bext` (Broadcast Wave, EBU Tech 3285), dann
+// `fmt ` und `data`. Übersteigt die Datei beim Abschluss 4 GiB, wird aus
+// `RIFF` ein `RF64` und aus dem Platzhalter der `ds64`-Chunk mit den echten
+// 64-Bit-Größen (EBU Tech 3306) – ohne die Audiodaten zu verschieben.
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom, Write};
+
+use anyhow::{bail, Result};
+
+use crate::core::timezone::TimeZone;
+
+/// Nutzdaten von `ds64` ohne Tabelle: RIFF-, data- und Sample-Größe (je u64)
+/// plus Tabellenlänge (u32).
+const DS64_LEN: u32 = 28;
+/// Feste Länge des `bext`-Chunks (Version 1) ohne Coding History
+const BEXT_FIXED_LEN: usize = 602;
+/// Offset von `JUNK`/`ds64` in der Datei
+const DS64_OFFSET: u64 = 12;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WavFormat {
+    pub sample_rate: u32,
+    pub channels: u16,
+    pub bits_per_sample: u16,
+}
+
+impl WavFormat {
+    pub fn block_align(&self) -> u64 {
+        u64::from(self.channels) * u64::from(self.bits_per_sample) / 8
+    }
+}
+
+/// Felder für den `bext`-Chunk aus der Consumer-Config.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BwfOptions {
+    pub description: String,
+    pub originator: String,
+}
+
+/// Inhalt eines `bext`-Chunks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bext {
+    pub description: String,
+    pub originator: String,
+    pub originator_reference: String,
+    /// `yyyy-mm-dd` (Ortszeit)
+    pub origination_date: String,
+    /// `hh:mm:ss` (Ortszeit)
+    pub origination_time: String,
+    /// Samples seit lokaler Mitternacht
+    pub time_reference: u64,
+    pub coding_history: String,
+}
+
+impl BwfOptions {
+    /// `bext` für eine Aufnahme ab `started_at_ms` (UTC), Datum und Uhrzeit
+    /// in der Zeitzone des Flows.
+    pub fn bext(&self, started_at_ms: u64, timezone: &TimeZone, format: &WavFormat) -> Bext {
+        let local_s = timezone.to_local((started_at_ms / 1000) as i64);
+        let since_midnight_ms = local_s.rem_euclid(86_400) as u64 * 1000 + started_at_ms % 1000;
+        Bext {
+            description: self.description.clone(),
+            originator: self.originator.clone(),
+            originator_reference: String::new(),
+            origination_date: timezone.format_template("%Y-%m-%d", started_at_ms),
+            origination_time: timezone.format_template("%H:%M:%S", started_at_ms),
+            time_reference: since_midnight_ms * u64::from(format.sample_rate) / 1000,
+            coding_history: format!(
+                "A=PCM,F={},W={},M={},T=airlift-node\r\n",
+                format.sample_rate,
+                format.bits_per_sample,
+                if format.channels == 1 { "mono" } else { "stereo" }
+            ),
+        }
+    }
+}
+
+fn push_fixed(out: &mut Vec<u8>, text: &str, len: usize) {
+    let bytes = text.as_bytes();
+    let used = bytes.len().min(len);
+    out.extend_from_slice(&bytes[..used]);
+    out.resize(out.len() + len - used, 0);
+}
+
+fn push_chunk(out: &mut Vec<u8>, id: &[u8; 4], body: &[u8]) {
+    out.extend_from_slice(id);
+    out.extend_from_slice(&(body.len() as u32).to_le_bytes());
+    out.extend_from_slice(body);
+    if body.len() % 2 == 1 {
+        out.push(0);
+    }
+}
+
+impl Bext {
+    fn body(&self) -> Vec<u8> {
+        let mut body = Vec::with_capacity(BEXT_FIXED_LEN + self.coding_history.len());
+        push_fixed(&mut body, &self.description, 256);
+        push_fixed(&mut body, &self.originator, 32);
+        push_fixed(&mut body, &self.originator_reference, 32);
+        push_fixed(&mut body, &self.origination_date, 10);
+        push_fixed(&mut body, &self.origination_time, 8);
+        body.extend_from_slice(&(self.time_reference as u32).to_le_bytes());
+        body.extend_from_slice(&((self.time_reference >> 32) as u32).to_le_bytes());
+        // Version 1, UMID und Reserved bleiben leer
+        body.extend_from_slice(&1u16.to_le_bytes());
+        body.resize(BEXT_FIXED_LEN, 0);
+        body.extend_from_slice(self.coding_history.as_bytes());
+        body
+    }
+}
+
+/// Kompletter Header mit Platzhalter-Größen; die Audiodaten folgen direkt
+/// dahinter (`header.len()` ist der Daten-Offset).
+pub fn header(format: &WavFormat, bext: Option<&Bext>) -> Vec<u8> {
+    let mut out = Vec::with_capacity(128 + bext.map_or(0, |_| BEXT_FIXED_LEN + 64));
+    out.extend_from_slice(b"RIFF");
+    out.extend_from_slice(&0u32.to_le_bytes());
+    out.extend_from_slice(b"WAVE");
+    push_chunk(&mut out, b"JUNK", &[0u8; DS64_LEN as usize]);
+    if let Some(bext) = bext {
+        push_chunk(&mut out, b"bext", &bext.body());
+    }
+
+    let mut fmt = Vec::with_capacity(16);
+    fmt.extend_from_slice(&1u16.to_le_bytes());
+    fmt.extend_from_slice(&format.channels.to_le_bytes());
+    fmt.extend_from_slice(&format.sample_rate.to_le_bytes());
+    let byte_rate = format.sample_rate as u64 * format.block_align();
+    fmt.extend_from_slice(&(byte_rate as u32).to_le_bytes());
+    fmt.extend_from_slice(&(format.block_align() as u16).to_le_bytes());
+    fmt.extend_from_slice(&format.bits_per_sample.to_le_bytes());
+    push_chunk(&mut out, b"fmt ", &fmt);
+
+    out.extend_from_slice(b"data");
+    out.extend_from_slice(&0u32.to_le_bytes());
+    out
+}
+
+/// Offset der Audiodaten: Chunks ab Byte 12 bis `data` durchgehen. Kommt
+/// auch mit fremden oder älteren Headern ohne Platzhalter zurecht.
+pub fn data_offset(file: &mut File) -> Result<u64> {
+    let len = file.metadata()?.len();
+    let mut head = [0u8; 12];
+    file.seek(SeekFrom::Start(0))?;
+    file.read_exact(&mut head)?;
+    if !(&head[..4] == b"RIFF" || &head[..4] == b"RF64") || &head[8..12] != b"WAVE" {
+        bail!("not a WAV file");
+    }
+    let mut pos = 12u64;
+    while pos + 8 <= len {
+        let mut chunk = [0u8; 8];
+        file.seek(SeekFrom::Start(pos))?;
+        file.read_exact(&mut chunk)?;
+        if &chunk[..4] == b"data" {
+            return Ok(pos + 8);
+        }
+        let size = u64::from(u32::from_le_bytes([chunk[4], chunk[5], chunk[6], chunk[7]]));
+        pos += 8 + size + size % 2;
+    }
+    bail!("no data chunk")
+}
+
+/// Größen eintragen. Passt die Datei nicht mehr in 32 Bit und gibt es den
+/// Platzhalter, wird sie zu RF64; liefert `true` in diesem Fall.
+pub fn finalize(file: &mut File, data_offset: u64, data_bytes: u64, format: &WavFormat) -> Result<bool> {
+    let riff_size = data_offset - 8 + data_bytes;
+    if riff_size <= u64::from(u32::MAX) {
+        file.seek(SeekFrom::Start(4))?;
+        file.write_all(&(riff_size as u32).to_le_bytes())?;
+        file.seek(SeekFrom::Start(data_offset - 4))?;
+        file.write_all(&(data_bytes as u32).to_le_bytes())?;
+        return Ok(false);
+    }
+
+    let mut placeholder = [0u8; 8];
+    file.seek(SeekFrom::Start(DS64_OFFSET))?;
+    file.read_exact(&mut placeholder)?;
+    let reserved = u32::from_le_bytes([placeholder[4], placeholder[5], placeholder[6], placeholder[7]]);
+    if !(&placeholder[..4] == b"JUNK" || &placeholder[..4] == b"ds64") || reserved < DS64_LEN {
+        // Alter Header ohne Platzhalter: bei 4 GiB abschneiden
+        let data = u64::from(u32::MAX) - (data_offset - 8);
+        let data = data - data % format.block_align().max(1);
+        log::warn!("WAV file exceeds 4 GiB without room for ds64, truncating to {} bytes", data);
+        file.set_len(data_offset + data)?;
+        return finalize(file, data_offset, data, format);
+    }
+
+    let mut ds64 = Vec::with_capacity(8 + reserved as usize);
+    ds64.extend_from_slice(b"ds64");
+    ds64.extend_from_slice(&reserved.to_le_bytes());
+    ds64.extend_from_slice(&riff_size.to_le_bytes());
+    ds64.extend_from_slice(&data_bytes.to_le_bytes());
+    ds64.extend_from_slice(&(data_bytes / format.block_align().max(1)).to_le_bytes());
+    ds64.extend_from_slice(&0u32.to_le_bytes());
+    ds64.resize(8 + reserved as usize, 0);
+
+    file.seek(SeekFrom::Start(0))?;
+    file.write_all(b"RF64")?;
+    file.write_all(&u32::MAX.to_le_bytes())?;
+    file.seek(SeekFrom::Start(DS64_OFFSET))?;
+    file.write_all(&ds64)?;
+    file.seek(SeekFrom::Start(data_offset - 4))?;
+    file.write_all(&u32::MAX.to_le_bytes())?;
+    Ok(true)
+}
@@ -0,0 +1,204 @@
+// src/audio/waveform.rs
+//
+// Vorberechnete Wellenformen für archivierte Aufnahmen, damit der Web-Player
+// auch stundenlange Dateien sofort zeichnen kann. Pro Zoomstufe eine Datei im
+// audiowaveform-Format (.dat v1, 8 Bit, mono) unter `<dir>/.waveforms/`;
+// Stufe 0 hat 256 Frames pro Pixel, jede weitere die doppelte Anzahl.
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+
+use crate::producers::file::open_audio_file;
+
+pub const BASE_SAMPLES_PER_PIXEL: u32 = 256;
+pub const MAX_ZOOM: u32 = 10;
+const WAVEFORM_DIR: &str = ".waveforms";
+const DAT_VERSION: i32 = 1;
+/// audiowaveform-Flag: 8-Bit-Werte
+const FLAG_8BIT: u32 = 1;
+const DAT_HEADER_LEN: usize = 20;
+
+/// Min/Max-Paare einer Zoomstufe.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Waveform {
+    pub sample_rate: u32,
+    pub samples_per_pixel: u32,
+    pub data: Vec<(i8, i8)>,
+}
+
+/// JSON-Variante wie `audiowaveform --output-format json`.
+#[derive(Serialize)]
+struct WaveformJson {
+    version: u32,
+    channels: u32,
+    sample_rate: u32,
+    samples_per_pixel: u32,
+    bits: u32,
+    length: usize,
+    data: Vec<i8>,
+}
+
+impl Waveform {
+    /// Fasst jeweils `factor` Pixel zusammen.
+    pub fn downsample(&self, factor: u32) -> Self {
+        let data = self
+            .data
+            .chunks(factor.max(1) as usize)
+            .map(|chunk| {
+                chunk.iter().fold((i8::MAX, i8::MIN), |(min, max), (lo, hi)| {
+                    (min.min(*lo), max.max(*hi))
+                })
+            })
+            .collect();
+        Self {
+            sample_rate: self.sample_rate,
+            samples_per_pixel: self.samples_per_pixel * factor.max(1),
+            data,
+        }
+    }
+
+    pub fn to_dat(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(DAT_HEADER_LEN + self.data.len() * 2);
+        out.extend_from_slice(&DAT_VERSION.to_le_bytes());
+        out.extend_from_slice(&FLAG_8BIT.to_le_bytes());
+        out.extend_from_slice(&(self.sample_rate as i32).to_le_bytes());
+        out.extend_from_slice(&(self.samples_per_pixel as i32).to_le_bytes());
+        out.extend_from_slice(&(self.data.len() as u32).to_le_bytes());
+        for (min, max) in &self.data {
+            out.push(*min as u8);
+            out.push(*max as u8);
+        }
+        out
+    }
+
+    pub fn from_dat(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < DAT_HEADER_LEN {
+            bail!("waveform file too short");
+        }
+        let word = |offset: usize| {
+            u32::from_le_bytes([bytes[offset], bytes[offset + 1], bytes[offset + 2], bytes[offset + 3]])
+        };
+        if word(0) as i32 != DAT_VERSION || word(4) & FLAG_8BIT == 0 {
+            bail!("unsupported waveform file (version {}, flags {})", word(0), word(4));
+        }
+        let length = word(16) as usize;
+        let data = &bytes[DAT_HEADER_LEN..];
+        if data.len() != length * 2 {
+            bail!("waveform file truncated ({} of {} points)", data.len() / 2, length);
+        }
+        Ok(Self {
+            sample_rate: word(8),
+            samples_per_pixel: word(12),
+            data: data.chunks_exact(2).map(|pair| (pair[0] as i8, pair[1] as i8)).collect(),
+        })
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        let json = WaveformJson {
+            version: 2,
+            channels: 1,
+            sample_rate: self.sample_rate,
+            samples_per_pixel: self.samples_per_pixel,
+            bits: 8,
+            length: self.data.len(),
+            data: self.data.iter().flat_map(|(min, max)| [*min, *max]).collect(),
+        };
+        serde_json::to_value(json).unwrap_or_default()
+    }
+}
+
+/// Liest die Aufnahme einmal und liefert Stufe 0 (Kanäle zusammengefasst).
+pub fn compute_waveform(path: &Path) -> Result<Waveform> {
+    let mut reader = open_audio_file(path)?;
+    let info = reader.info().clone();
+    let channels = info.channels.max(1) as usize;
+    let per_pixel = BASE_SAMPLES_PER_PIXEL as usize * channels;
+
+    let mut data = Vec::new();
+    let (mut min, mut max, mut filled) = (i16::MAX, i16::MIN, 0usize);
+    while let Some(block) = reader.read_block()? {
+        for sample in block {
+            min = min.min(sample);
+            max = max.max(sample);
+            filled += 1;
+            if filled == per_pixel {
+                data.push(((min >> 8) as i8, (max >> 8) as i8));
+                (min, max, filled) = (i16::MAX, i16::MIN, 0);
+            }
+        }
+    }
+    if filled > 0 {
+        data.push(((min >> 8) as i8, (max >> 8) as i8));
+    }
+
+    Ok(Waveform {
+        sample_rate: info.sample_rate,
+        samples_per_pixel: BASE_SAMPLES_PER_PIXEL,
+        data,
+    })
+}
+
+pub fn tile_path(recording: &Path, zoom: u32) -> PathBuf {
+    let file = recording
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    crate::audio::archive::archive_dir(recording)
+        .join(WAVEFORM_DIR)
+        .join(format!("{}.z{}.dat", file, zoom))
+}
+
+/// Berechnet alle Zoomstufen und schreibt sie neben die Aufnahme.
+pub fn generate_tiles(recording: &Path) -> Result<Waveform> {
+    let base = compute_waveform(recording)?;
+    let dir = crate::audio::archive::archive_dir(recording).join(WAVEFORM_DIR);
+    fs::create_dir_all(&dir).with_context(|| format!("create {}", dir.display()))?;
+
+    let mut level = base.clone();
+    for zoom in 0..=MAX_ZOOM {
+        if zoom > 0 {
+            level = level.downsample(2);
+        }
+        let path = tile_path(recording, zoom);
+        let tmp = path.with_extension("dat.tmp");
+        fs::write(&tmp, level.to_dat()).with_context(|| format!("write {}", tmp.display()))?;
+        fs::rename(&tmp, &path)?;
+    }
+    log::info!(
+        "Waveform tiles for {} written ({} points at zoom 0)",
+        recording.display(),
+        base.data.len()
+    );
+    Ok(base)
+}
+
+/// Zoomstufe laden; fehlende Kacheln (ältere Aufnahmen) werden erzeugt.
+pub fn load_tile(recording: &Path, zoom: u32) -> Result<Waveform> {
+    if zoom > MAX_ZOOM {
+        bail!("zoom must be between 0 and {}", MAX_ZOOM);
+    }
+    let path = tile_path(recording, zoom);
+    let tile_is_fresh = match (fs::metadata(&path), fs::metadata(recording)) {
+        (Ok(tile), Ok(audio)) => match (tile.modified(), audio.modified()) {
+            (Ok(tile), Ok(audio)) => tile >= audio,
+            _ => true,
+        },
+        (Err(_), _) => false,
+        (Ok(_), Err(e)) => return Err(e).with_context(|| format!("{}", recording.display())),
+    };
+    if !tile_is_fresh {
+        return Ok(generate_tiles(recording)?.downsample(1 << zoom));
+    }
+    Waveform::from_dat(&fs::read(&path)?)
+}
+
+/// Erzeugt die Kacheln im Hintergrund, z. B. direkt nach einer Aufnahme.
+pub fn spawn_tile_generation(recording: PathBuf) {
+    std::thread::spawn(move || {
+        if let Err(e) = generate_tiles(&recording) {
+            log::warn!("Waveform tiles for {} failed: {:#}", recording.display(), e);
+        }
+    });
+}
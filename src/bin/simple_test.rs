@@ -6,13 +6,14 @@ fn main() {
 
     // Jetzt mit airlift_node::
     let buffer = airlift_node::core::ringbuffer::AudioRingBuffer::new(5);
-    println!("✓ Buffer created: capacity = {}", buffer.stats().capacity);
+    println!("✓ Buffer created: capacity = {}", buffer.stats().capacity.get());
 
     let frame = airlift_node::core::ringbuffer::PcmFrame {
         utc_ns: 123456789,
         samples: vec![1, 2, 3],
         sample_rate: 48000,
         channels: 1,
+        metadata: Default::default(),
     };
 
     buffer.push(frame);
@@ -0,0 +1,901 @@
+// src/codecs/flac.rs
+//
+// FLAC-Encoder (16 Bit, verlustfrei) ohne externe Bibliothek, gedacht für
+// Archiv-Aufnahmen. Die Kompressionsstufen 0–8 folgen grob libFLAC:
+// Blockgröße, Stereo-Dekorrelation (Links/Seite, Mitte/Seite), maximale
+// LPC-Ordnung und Rice-Partitionen wachsen mit der Stufe. Jeder Block wird als
+// Constant, Verbatim, Fixed (Ordnung 0–4) oder LPC kodiert, je nachdem, was
+// am kleinsten ist. Der Stream beginnt mit `fLaC`, STREAMINFO und
+// VORBIS_COMMENT; Gesamtlänge, Frame-Größen und MD5 stehen erst nach
+// `flush` fest und kommen über `final_header` an den Dateianfang.
+use std::collections::HashMap;
+
+use anyhow::{bail, Result};
+use md5::{Digest, Md5};
+
+use crate::codecs::{
+    AudioCodec, CodecInfo, CodecKind, ContainerKind, EncodedFrame, PCM_SAMPLE_RATE,
+};
+use crate::config::ConfigValues;
+
+/// Höchste Kanalzahl eines FLAC-Streams
+pub const MAX_FLAC_CHANNELS: u8 = 8;
+pub const FLAC_BITS_PER_SAMPLE: u32 = 16;
+/// Stufe ohne Angabe, wie bei libFLAC
+pub const DEFAULT_COMPRESSION_LEVEL: u8 = 5;
+
+const BLOCK_STREAMINFO: u8 = 0;
+const BLOCK_VORBIS_COMMENT: u8 = 4;
+/// `fLaC` plus STREAMINFO-Block, der Teil, den `final_header` ersetzt
+const STREAMINFO_END: usize = 4 + 4 + 34;
+/// Rice-Parameter 15 ist die Escape-Kennung
+const MAX_RICE_PARAM: u32 = 14;
+
+/// Einstellungen für den Codec `flac`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FlacEncoderOptions {
+    /// 0 (schnell) … 8 (kleinste Dateien)
+    pub compression_level: u8,
+}
+
+impl Default for FlacEncoderOptions {
+    fn default() -> Self {
+        Self {
+            compression_level: DEFAULT_COMPRESSION_LEVEL,
+        }
+    }
+}
+
+impl FlacEncoderOptions {
+    /// Liest `{ compression_level = 8 }` aus `config.flac` eines Moduls.
+    pub fn from_config(
+        module_kind: &str,
+        module_name: &str,
+        value: Option<&serde_json::Value>,
+    ) -> Result<Self> {
+        let map: HashMap<String, serde_json::Value> = match value {
+            None => return Ok(Self::default()),
+            Some(serde_json::Value::Object(map)) => map.clone().into_iter().collect(),
+            Some(other) => bail!(
+                "{} '{}': config.flac must be a table, got {}",
+                module_kind,
+                module_name,
+                other
+            ),
+        };
+        let values = ConfigValues::new(module_kind, module_name, &map);
+        let compression_level = match values.f64("compression_level")? {
+            Some(level) if level.fract() != 0.0 => bail!(
+                "{} '{}': config.flac.compression_level must be a whole number, got {}",
+                module_kind,
+                module_name,
+                level
+            ),
+            Some(level) => values.check_range("compression_level", level, 0.0, 8.0)? as u8,
+            None => DEFAULT_COMPRESSION_LEVEL,
+        };
+        Ok(Self { compression_level })
+    }
+
+    /// Samples pro Kanal und Frame
+    pub fn block_size(&self) -> usize {
+        self.level().block_size
+    }
+
+    fn level(&self) -> Level {
+        let (block_size, stereo, max_lpc_order, max_partition_order) = match self.compression_level
+        {
+            0 => (1152, false, 0, 3),
+            1 | 2 => (1152, true, 0, 3),
+            3 => (4096, false, 6, 4),
+            4 => (4096, true, 8, 4),
+            5 => (4096, true, 8, 5),
+            6 => (4096, true, 8, 6),
+            _ => (4096, true, 12, 6),
+        };
+        Level {
+            block_size,
+            stereo,
+            max_lpc_order,
+            max_partition_order,
+            exhaustive: self.compression_level >= 8,
+        }
+    }
+}
+
+/// Aus der Kompressionsstufe abgeleitete Encoder-Parameter
+#[derive(Debug, Clone, Copy)]
+struct Level {
+    block_size: usize,
+    /// Links/Seite, Seite/Rechts und Mitte/Seite ausprobieren
+    stereo: bool,
+    /// 0 = nur Fixed-Prädiktoren
+    max_lpc_order: usize,
+    max_partition_order: u32,
+    /// Jede LPC-Ordnung kodieren statt sie zu schätzen
+    exhaustive: bool,
+}
+
+pub struct FlacEncoder {
+    info: CodecInfo,
+    options: FlacEncoderOptions,
+    level: Level,
+    /// Interleaved, weniger als ein Block
+    pending: Vec<i16>,
+    frame_number: u64,
+    /// Samples pro Kanal
+    total_samples: u64,
+    md5: Md5,
+    min_frame: u32,
+    max_frame: u32,
+    header_sent: bool,
+    finished: Option<[u8; 16]>,
+}
+
+impl FlacEncoder {
+    pub fn new(channels: u8) -> Result<Self> {
+        Self::with_options(channels, &FlacEncoderOptions::default())
+    }
+
+    pub fn with_options(channels: u8, options: &FlacEncoderOptions) -> Result<Self> {
+        if channels == 0 || channels > MAX_FLAC_CHANNELS {
+            bail!(
+                "FLAC supports 1 to {} channels, got {}",
+                MAX_FLAC_CHANNELS,
+                channels
+            );
+        }
+        if options.compression_level > 8 {
+            bail!(
+                "FLAC compression level must be 0..=8, got {}",
+                options.compression_level
+            );
+        }
+        let level = options.level();
+        Ok(Self {
+            info: CodecInfo {
+                kind: CodecKind::Flac,
+                sample_rate: PCM_SAMPLE_RATE,
+                channels,
+                container: ContainerKind::Raw,
+            },
+            options: *options,
+            level,
+            pending: Vec::with_capacity(level.block_size * channels as usize),
+            frame_number: 0,
+            total_samples: 0,
+            md5: Md5::new(),
+            min_frame: 0,
+            max_frame: 0,
+            header_sent: false,
+            finished: None,
+        })
+    }
+
+    pub fn options(&self) -> &FlacEncoderOptions {
+        &self.options
+    }
+
+    /// Bisher kodierte Samples pro Kanal
+    pub fn total_samples(&self) -> u64 {
+        self.total_samples
+    }
+
+    fn frame(&self, payload: Vec<u8>) -> EncodedFrame {
+        EncodedFrame {
+            payload,
+            info: self.info.clone(),
+        }
+    }
+
+    /// `fLaC`, STREAMINFO mit dem aktuellen Stand und ein VORBIS_COMMENT
+    /// nur mit Vendor-String.
+    fn stream_header(&self, md5: [u8; 16]) -> Vec<u8> {
+        let mut header = b"fLaC".to_vec();
+        header.extend_from_slice(&metadata_block_header(BLOCK_STREAMINFO, false, 34));
+        header.extend_from_slice(&(self.level.block_size as u16).to_be_bytes());
+        header.extend_from_slice(&(self.level.block_size as u16).to_be_bytes());
+        header.extend_from_slice(&self.min_frame.to_be_bytes()[1..]);
+        header.extend_from_slice(&self.max_frame.to_be_bytes()[1..]);
+        let packed = (self.info.sample_rate as u64) << 44
+            | ((self.info.channels as u64 - 1) << 41)
+            | ((FLAC_BITS_PER_SAMPLE as u64 - 1) << 36)
+            | (self.total_samples & 0xF_FFFF_FFFF);
+        header.extend_from_slice(&packed.to_be_bytes());
+        header.extend_from_slice(&md5);
+
+        let vendor = concat!("airlift ", env!("CARGO_PKG_VERSION"));
+        let comment_len = 4 + vendor.len() + 4;
+        header.extend_from_slice(&metadata_block_header(
+            BLOCK_VORBIS_COMMENT,
+            true,
+            comment_len,
+        ));
+        header.extend_from_slice(&(vendor.len() as u32).to_le_bytes());
+        header.extend_from_slice(vendor.as_bytes());
+        header.extend_from_slice(&0u32.to_le_bytes());
+        header
+    }
+
+    fn take_header(&mut self) -> Option<EncodedFrame> {
+        if self.header_sent {
+            return None;
+        }
+        self.header_sent = true;
+        Some(self.frame(self.stream_header([0; 16])))
+    }
+
+    /// Kodiert die ersten `samples` Samples pro Kanal aus `pending`.
+    fn encode_block(&mut self, samples: usize) -> EncodedFrame {
+        let channels = self.info.channels as usize;
+        let block: Vec<Vec<i32>> = (0..channels)
+            .map(|channel| {
+                self.pending[..samples * channels]
+                    .iter()
+                    .skip(channel)
+                    .step_by(channels)
+                    .map(|s| *s as i32)
+                    .collect()
+            })
+            .collect();
+        self.pending.drain(..samples * channels);
+
+        let mut writer = BitWriter::default();
+        let (assignment, subframes) = if channels == 2 && self.level.stereo {
+            let (left, right) = (&block[0], &block[1]);
+            let mid: Vec<i32> = left.iter().zip(right).map(|(l, r)| (l + r) >> 1).collect();
+            let side: Vec<i32> = left.iter().zip(right).map(|(l, r)| l - r).collect();
+            let bps = FLAC_BITS_PER_SAMPLE;
+            let [l, r, m, s] = [
+                encode_subframe(left, bps, &self.level),
+                encode_subframe(right, bps, &self.level),
+                encode_subframe(&mid, bps, &self.level),
+                encode_subframe(&side, bps + 1, &self.level),
+            ];
+            // Unabhängig, Links/Seite, Seite/Rechts, Mitte/Seite
+            [
+                (0b0001, [l.clone(), r.clone()]),
+                (0b1000, [l, s.clone()]),
+                (0b1001, [s.clone(), r]),
+                (0b1010, [m, s]),
+            ]
+            .into_iter()
+            .min_by_key(|(_, pair)| pair[0].len_bits() + pair[1].len_bits())
+            .map(|(code, pair)| (code, pair.to_vec()))
+            .expect("four stereo modes")
+        } else {
+            let subframes = block
+                .iter()
+                .map(|samples| encode_subframe(samples, FLAC_BITS_PER_SAMPLE, &self.level))
+                .collect();
+            (channels as u32 - 1, subframes)
+        };
+
+        // Frame-Header (feste Blockgröße, Frame-Nummer als UTF-8-Zahl)
+        let (size_code, size_extra) = block_size_code(samples);
+        writer.write(0xFFF8, 16);
+        writer.write(size_code as u64, 4);
+        writer.write(sample_rate_code(self.info.sample_rate) as u64, 4);
+        writer.write(assignment as u64, 4);
+        // 16 Bit, reserviertes Bit
+        writer.write(0b1000, 4);
+        write_utf8(&mut writer, self.frame_number);
+        match size_extra {
+            Some(8) => writer.write(samples as u64 - 1, 8),
+            Some(_) => writer.write(samples as u64 - 1, 16),
+            None => {}
+        }
+        let crc = crc8(writer.bytes());
+        writer.write(crc as u64, 8);
+        for subframe in &subframes {
+            writer.append(subframe);
+        }
+        let mut payload = writer.into_bytes();
+        let crc = crc16(&payload);
+        payload.extend_from_slice(&crc.to_be_bytes());
+
+        let size = payload.len() as u32;
+        self.min_frame = if self.frame_number == 0 {
+            size
+        } else {
+            self.min_frame.min(size)
+        };
+        self.max_frame = self.max_frame.max(size);
+        self.frame_number += 1;
+        self.total_samples += samples as u64;
+        self.frame(payload)
+    }
+}
+
+impl AudioCodec for FlacEncoder {
+    fn info(&self) -> &CodecInfo {
+        &self.info
+    }
+
+    fn encode(&mut self, pcm: &[i16]) -> Result<Vec<EncodedFrame>> {
+        let channels = self.info.channels as usize;
+        if !pcm.len().is_multiple_of(channels) {
+            bail!(
+                "FLAC encoder expected whole sample frames of {} channels, got {} samples",
+                channels,
+                pcm.len()
+            );
+        }
+        if self.finished.is_some() {
+            bail!("FLAC encoder already flushed");
+        }
+        let mut bytes = Vec::with_capacity(pcm.len() * 2);
+        for sample in pcm {
+            bytes.extend_from_slice(&sample.to_le_bytes());
+        }
+        self.md5.update(&bytes);
+
+        let mut frames: Vec<EncodedFrame> = self.take_header().into_iter().collect();
+        self.pending.extend_from_slice(pcm);
+        let block = self.level.block_size;
+        while self.pending.len() >= block * channels {
+            frames.push(self.encode_block(block));
+        }
+        Ok(frames)
+    }
+
+    /// Letzter, kürzerer Block; danach nimmt der Encoder nichts mehr an.
+    fn flush(&mut self) -> Result<Vec<EncodedFrame>> {
+        if self.finished.is_some() {
+            return Ok(Vec::new());
+        }
+        let mut frames: Vec<EncodedFrame> = self.take_header().into_iter().collect();
+        let remaining = self.pending.len() / self.info.channels as usize;
+        if remaining > 0 {
+            frames.push(self.encode_block(remaining));
+        }
+        self.finished = Some(self.md5.finalize_reset().into());
+        Ok(frames)
+    }
+
+    /// STREAMINFO mit Gesamtlänge, Frame-Größen und MD5.
+    fn final_header(&self) -> Option<Vec<u8>> {
+        let md5 = self.finished?;
+        let mut header = self.stream_header(md5);
+        header.truncate(STREAMINFO_END);
+        Some(header)
+    }
+}
+
+fn metadata_block_header(kind: u8, last: bool, len: usize) -> [u8; 4] {
+    let len = (len as u32).to_be_bytes();
+    [kind | if last { 0x80 } else { 0 }, len[1], len[2], len[3]]
+}
+
+/// Blockgrößen-Code im Frame-Header; `Some(bits)`, wenn die Größe danach
+/// ausgeschrieben wird.
+fn block_size_code(samples: usize) -> (u32, Option<u32>) {
+    match samples {
+        192 => (1, None),
+        576 => (2, None),
+        1152 => (3, None),
+        2304 => (4, None),
+        4608 => (5, None),
+        256 => (8, None),
+        512 => (9, None),
+        1024 => (10, None),
+        2048 => (11, None),
+        4096 => (12, None),
+        8192 => (13, None),
+        16384 => (14, None),
+        32768 => (15, None),
+        n if n <= 256 => (6, Some(8)),
+        _ => (7, Some(16)),
+    }
+}
+
+/// 0 = Rate steht nur in STREAMINFO
+fn sample_rate_code(rate: u32) -> u32 {
+    match rate {
+        88_200 => 1,
+        176_400 => 2,
+        192_000 => 3,
+        8_000 => 4,
+        16_000 => 5,
+        22_050 => 6,
+        24_000 => 7,
+        32_000 => 8,
+        44_100 => 9,
+        48_000 => 10,
+        96_000 => 11,
+        _ => 0,
+    }
+}
+
+/// Frame-Nummer in der erweiterten UTF-8-Kodierung von FLAC.
+fn write_utf8(writer: &mut BitWriter, value: u64) {
+    if value < 0x80 {
+        writer.write(value, 8);
+        return;
+    }
+    let bytes = match value {
+        0..=0x7FF => 2,
+        0x800..=0xFFFF => 3,
+        0x1_0000..=0x1F_FFFF => 4,
+        0x20_0000..=0x3FF_FFFF => 5,
+        0x400_0000..=0x7FFF_FFFF => 6,
+        _ => 7,
+    };
+    let prefix = (0xFF00u64 >> bytes) & 0xFF;
+    writer.write(prefix | (value >> (6 * (bytes - 1))), 8);
+    for index in (0..bytes - 1).rev() {
+        writer.write(0x80 | ((value >> (6 * index)) & 0x3F), 8);
+    }
+}
+
+/// Kleinster Subframe für einen Kanal mit `bps` Bit pro Sample.
+fn encode_subframe(samples: &[i32], bps: u32, level: &Level) -> BitWriter {
+    let mut writer = BitWriter::default();
+    if samples.iter().all(|s| *s == samples[0]) {
+        subframe_header(&mut writer, 0);
+        writer.write_signed(samples[0] as i64, bps);
+        return writer;
+    }
+
+    let verbatim_bits = 8 + (samples.len() as u64) * bps as u64;
+    let mut best: Option<(u64, Predictor, Vec<i32>, RicePlan)> = None;
+    let mut consider = |predictor: Predictor, residual: Vec<i32>| {
+        let plan = RicePlan::new(
+            &residual,
+            samples.len(),
+            predictor.order(),
+            level.max_partition_order,
+        );
+        let bits = 8 + predictor.header_bits(bps) + plan.bits;
+        if best
+            .as_ref()
+            .is_none_or(|(best_bits, ..)| bits < *best_bits)
+        {
+            best = Some((bits, predictor, residual, plan));
+        }
+    };
+
+    for order in 0..=4.min(samples.len() - 1) {
+        consider(Predictor::Fixed(order), fixed_residual(samples, order));
+    }
+    if level.max_lpc_order > 0 {
+        for (coefficients, precision, shift) in lpc_candidates(samples, bps, level) {
+            let residual = lpc_residual(samples, &coefficients, shift);
+            consider(
+                Predictor::Lpc {
+                    coefficients,
+                    precision,
+                    shift,
+                },
+                residual,
+            );
+        }
+    }
+
+    match best {
+        Some((bits, predictor, residual, plan)) if bits < verbatim_bits => {
+            let order = predictor.order();
+            match &predictor {
+                Predictor::Fixed(order) => subframe_header(&mut writer, 0b001000 | *order as u32),
+                Predictor::Lpc { .. } => {
+                    subframe_header(&mut writer, 0b100000 | (order as u32 - 1))
+                }
+            }
+            for sample in &samples[..order] {
+                writer.write_signed(*sample as i64, bps);
+            }
+            if let Predictor::Lpc {
+                coefficients,
+                precision,
+                shift,
+            } = &predictor
+            {
+                writer.write(*precision as u64 - 1, 4);
+                writer.write_signed(*shift as i64, 5);
+                for coefficient in coefficients {
+                    writer.write_signed(*coefficient as i64, *precision);
+                }
+            }
+            plan.write(&mut writer, &residual, samples.len(), order);
+        }
+        _ => {
+            subframe_header(&mut writer, 1);
+            for sample in samples {
+                writer.write_signed(*sample as i64, bps);
+            }
+        }
+    }
+    writer
+}
+
+/// Füllbit, Typ, keine "wasted bits"
+fn subframe_header(writer: &mut BitWriter, kind: u32) {
+    writer.write(kind as u64, 8 - 1);
+    writer.write(0, 1);
+}
+
+enum Predictor {
+    Fixed(usize),
+    Lpc {
+        coefficients: Vec<i32>,
+        precision: u32,
+        shift: u32,
+    },
+}
+
+impl Predictor {
+    fn order(&self) -> usize {
+        match self {
+            Predictor::Fixed(order) => *order,
+            Predictor::Lpc { coefficients, .. } => coefficients.len(),
+        }
+    }
+
+    /// Warm-up-Samples und Koeffizienten, ohne Subframe-Header
+    fn header_bits(&self, bps: u32) -> u64 {
+        let warmup = self.order() as u64 * bps as u64;
+        match self {
+            Predictor::Fixed(_) => warmup,
+            Predictor::Lpc {
+                coefficients,
+                precision,
+                ..
+            } => warmup + 4 + 5 + coefficients.len() as u64 * *precision as u64,
+        }
+    }
+}
+
+/// Restfehler der festen Polynom-Prädiktoren (Ordnung 0–4).
+fn fixed_residual(samples: &[i32], order: usize) -> Vec<i32> {
+    samples[order..]
+        .iter()
+        .enumerate()
+        .map(|(index, sample)| {
+            let i = index + order;
+            let x = |back: usize| samples[i - back] as i64;
+            let prediction = match order {
+                0 => 0,
+                1 => x(1),
+                2 => 2 * x(1) - x(2),
+                3 => 3 * x(1) - 3 * x(2) + x(3),
+                _ => 4 * x(1) - 6 * x(2) + 4 * x(3) - x(4),
+            };
+            (*sample as i64 - prediction) as i32
+        })
+        .collect()
+}
+
+fn lpc_residual(samples: &[i32], coefficients: &[i32], shift: u32) -> Vec<i32> {
+    let order = coefficients.len();
+    (order..samples.len())
+        .map(|i| {
+            let prediction: i64 = coefficients
+                .iter()
+                .enumerate()
+                .map(|(j, c)| *c as i64 * samples[i - 1 - j] as i64)
+                .sum();
+            (samples[i] as i64 - (prediction >> shift)) as i32
+        })
+        .collect()
+}
+
+/// Quantisierte LPC-Koeffizienten (Tukey-Fenster, Levinson-Durbin). Ohne
+/// `exhaustive` nur die Ordnung mit der kleinsten geschätzten Größe.
+fn lpc_candidates(samples: &[i32], bps: u32, level: &Level) -> Vec<(Vec<i32>, u32, u32)> {
+    let n = samples.len();
+    let max_order = level.max_lpc_order.min(n.saturating_sub(1)).min(32);
+    if max_order == 0 {
+        return Vec::new();
+    }
+    let windowed: Vec<f64> = samples
+        .iter()
+        .enumerate()
+        .map(|(i, s)| *s as f64 * tukey(i, n, 0.5))
+        .collect();
+    let autocorrelation: Vec<f64> = (0..=max_order)
+        .map(|lag| (lag..n).map(|i| windowed[i] * windowed[i - lag]).sum())
+        .collect();
+    if autocorrelation[0] <= 0.0 {
+        return Vec::new();
+    }
+
+    // Levinson-Durbin: Koeffizienten und Restenergie je Ordnung
+    let mut orders: Vec<(Vec<f64>, f64)> = Vec::with_capacity(max_order);
+    let mut lpc: Vec<f64> = Vec::new();
+    let mut error = autocorrelation[0];
+    for order in 0..max_order {
+        let mut reflection = -autocorrelation[order + 1];
+        for (j, coefficient) in lpc.iter().enumerate() {
+            reflection -= coefficient * autocorrelation[order - j];
+        }
+        reflection /= error;
+        let previous = lpc.clone();
+        for j in 0..order {
+            lpc[j] += reflection * previous[order - 1 - j];
+        }
+        lpc.push(reflection);
+        error *= 1.0 - reflection * reflection;
+        // Prädiktor `x[i] ≈ Σ c_j x[i-1-j]`
+        orders.push((lpc.iter().map(|c| -c).collect(), error.max(0.0)));
+        if error <= 0.0 {
+            break;
+        }
+    }
+
+    let precision = qlp_precision(n);
+    let quantized = |coefficients: &[f64]| quantize(coefficients, precision);
+    if level.exhaustive {
+        return orders
+            .iter()
+            .filter_map(|(coefficients, _)| quantized(coefficients))
+            .map(|(q, shift)| (q, precision, shift))
+            .collect();
+    }
+    // Geschätzte Bits: Restfehler pro Sample plus Kopf
+    let estimate = |order: usize, error: f64| {
+        let per_sample = (0.5 * (error / n as f64).max(1.0).log2()).max(0.0);
+        (n - order) as f64 * per_sample + (order as u32 * (precision + bps)) as f64
+    };
+    orders
+        .iter()
+        .enumerate()
+        .min_by(|(a, (_, ea)), (b, (_, eb))| estimate(a + 1, *ea).total_cmp(&estimate(b + 1, *eb)))
+        .and_then(|(_, (coefficients, _))| quantized(coefficients))
+        .map(|(q, shift)| vec![(q, precision, shift)])
+        .unwrap_or_default()
+}
+
+fn tukey(i: usize, n: usize, p: f64) -> f64 {
+    let taper = (p * (n - 1) as f64 / 2.0).floor() as usize;
+    if taper == 0 {
+        return 1.0;
+    }
+    let edge = |k: usize| 0.5 - 0.5 * (std::f64::consts::PI * k as f64 / taper as f64).cos();
+    if i < taper {
+        edge(i)
+    } else if i >= n - taper {
+        edge(n - 1 - i)
+    } else {
+        1.0
+    }
+}
+
+/// Koeffizienten-Genauigkeit in Bit, wie libFLAC nach Blockgröße.
+fn qlp_precision(block_size: usize) -> u32 {
+    match block_size {
+        0..=192 => 7,
+        193..=384 => 8,
+        385..=576 => 9,
+        577..=1152 => 10,
+        1153..=2304 => 11,
+        2305..=4608 => 12,
+        _ => 13,
+    }
+}
+
+/// Rundet mit Fehlerrückführung auf `precision` Bit; `None`, wenn der
+/// nötige Shift negativ würde.
+fn quantize(coefficients: &[f64], precision: u32) -> Option<(Vec<i32>, u32)> {
+    let max = coefficients.iter().fold(0.0f64, |max, c| max.max(c.abs()));
+    if max <= 0.0 || !max.is_finite() {
+        return None;
+    }
+    let shift = precision as i32 - 1 - (max.log2().floor() as i32 + 1);
+    if shift < 0 {
+        return None;
+    }
+    let shift = shift.min(15) as u32;
+    let limit = (1i64 << (precision - 1)) - 1;
+    let scale = (1u64 << shift) as f64;
+    let mut carry = 0.0;
+    let quantized = coefficients
+        .iter()
+        .map(|c| {
+            carry += c * scale;
+            let q = (carry.round() as i64).clamp(-limit - 1, limit);
+            carry -= q as f64;
+            q as i32
+        })
+        .collect();
+    Some((quantized, shift))
+}
+
+/// Partitionierung und Rice-Parameter für einen Restfehler-Block.
+struct RicePlan {
+    partition_order: u32,
+    params: Vec<u32>,
+    /// Geschätzte Größe inklusive Methoden- und Partitionskopf
+    bits: u64,
+}
+
+impl RicePlan {
+    fn new(residual: &[i32], block_size: usize, predictor_order: usize, max_order: u32) -> Self {
+        let mut prefix = Vec::with_capacity(residual.len() + 1);
+        prefix.push(0u64);
+        for value in residual {
+            prefix.push(prefix.last().unwrap() + zigzag(*value));
+        }
+        let mut best: Option<RicePlan> = None;
+        for order in 0..=max_order {
+            let partitions = 1usize << order;
+            if !block_size.is_multiple_of(partitions) || block_size / partitions <= predictor_order
+            {
+                break;
+            }
+            let length = block_size / partitions;
+            let mut bits = 2 + 4;
+            let mut params = Vec::with_capacity(partitions);
+            for partition in 0..partitions {
+                // Die erste Partition enthält keine Warm-up-Samples
+                let start = (partition * length).saturating_sub(predictor_order);
+                let end = (partition + 1) * length - predictor_order;
+                let (param, cost) = rice_param(prefix[end] - prefix[start], (end - start) as u64);
+                params.push(param);
+                bits += 4 + cost;
+            }
+            if best.as_ref().is_none_or(|best| bits < best.bits) {
+                best = Some(RicePlan {
+                    partition_order: order,
+                    params,
+                    bits,
+                });
+            }
+        }
+        best.unwrap_or_else(|| {
+            let (param, cost) = rice_param(prefix[residual.len()], residual.len() as u64);
+            RicePlan {
+                partition_order: 0,
+                params: vec![param],
+                bits: 2 + 4 + 4 + cost,
+            }
+        })
+    }
+
+    fn write(
+        &self,
+        writer: &mut BitWriter,
+        residual: &[i32],
+        block_size: usize,
+        predictor_order: usize,
+    ) {
+        // Methode 0: 4-Bit-Rice-Parameter
+        writer.write(0, 2);
+        writer.write(self.partition_order as u64, 4);
+        let length = block_size >> self.partition_order;
+        let mut offset = 0;
+        for (partition, param) in self.params.iter().enumerate() {
+            let count = if partition == 0 {
+                length - predictor_order
+            } else {
+                length
+            };
+            writer.write(*param as u64, 4);
+            for value in &residual[offset..offset + count] {
+                let value = zigzag(*value);
+                writer.write_unary(value >> param);
+                writer.write(value, *param);
+            }
+            offset += count;
+        }
+    }
+}
+
+fn zigzag(value: i32) -> u64 {
+    let value = value as i64;
+    if value >= 0 {
+        (value as u64) << 1
+    } else {
+        ((-value as u64) << 1) - 1
+    }
+}
+
+/// Bester Parameter für `count` Werte mit Summe `sum`, samt geschätzter Bits.
+fn rice_param(sum: u64, count: u64) -> (u32, u64) {
+    (0..=MAX_RICE_PARAM)
+        .map(|param| (param, count * (param as u64 + 1) + (sum >> param)))
+        .min_by_key(|(_, bits)| *bits)
+        .expect("rice parameters")
+}
+
+/// MSB-first-Bitstrom
+#[derive(Default, Clone)]
+pub(super) struct BitWriter {
+    bytes: Vec<u8>,
+    accumulator: u64,
+    bits: u32,
+}
+
+impl BitWriter {
+    /// Die unteren `count` Bit von `value`, höchstens 32.
+    pub(super) fn write(&mut self, value: u64, count: u32) {
+        if count == 0 {
+            return;
+        }
+        self.accumulator = (self.accumulator << count) | (value & ((1u64 << count) - 1));
+        self.bits += count;
+        while self.bits >= 8 {
+            self.bits -= 8;
+            self.bytes.push((self.accumulator >> self.bits) as u8);
+        }
+        self.accumulator &= (1u64 << self.bits) - 1;
+    }
+
+    fn write_signed(&mut self, value: i64, count: u32) {
+        self.write(value as u64, count);
+    }
+
+    /// `zeros` Nullen und eine abschließende Eins
+    fn write_unary(&mut self, mut zeros: u64) {
+        while zeros >= 32 {
+            self.write(0, 32);
+            zeros -= 32;
+        }
+        self.write(1, zeros as u32 + 1);
+    }
+
+    fn append(&mut self, other: &BitWriter) {
+        for byte in &other.bytes {
+            self.write(*byte as u64, 8);
+        }
+        self.write(other.accumulator, other.bits);
+    }
+
+    fn len_bits(&self) -> u64 {
+        self.bytes.len() as u64 * 8 + self.bits as u64
+    }
+
+    /// Nur die vollständigen Bytes
+    fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Mit Nullen auf ganze Bytes aufgefüllt
+    pub(super) fn into_bytes(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.write(0, 8 - self.bits);
+        }
+        self.bytes
+    }
+}
+
+const fn crc_table(polynomial: u16, width: u32) -> [u16; 256] {
+    let mut table = [0u16; 256];
+    let top = 1u16 << (width - 1);
+    let mask = if width == 16 {
+        0xFFFF
+    } else {
+        (1u16 << width) - 1
+    };
+    let mut byte = 0;
+    while byte < 256 {
+        let mut crc = (byte as u16) << (width - 8);
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & top != 0 {
+                (crc << 1) ^ polynomial
+            } else {
+                crc << 1
+            };
+            bit += 1;
+        }
+        table[byte] = crc & mask;
+        byte += 1;
+    }
+    table
+}
+
+const CRC8_TABLE: [u16; 256] = crc_table(0x07, 8);
+const CRC16_TABLE: [u16; 256] = crc_table(0x8005, 16);
+
+/// CRC-8 des Frame-Headers (Polynom x^8 + x^2 + x + 1)
+fn crc8(data: &[u8]) -> u8 {
+    data.iter()
+        .fold(0u8, |crc, byte| CRC8_TABLE[(crc ^ byte) as usize] as u8)
+}
+
+/// CRC-16 über den ganzen Frame (Polynom x^16 + x^15 + x^2 + 1)
+fn crc16(data: &[u8]) -> u16 {
+    data.iter().fold(0u16, |crc, byte| {
+        (crc << 8) ^ CRC16_TABLE[((crc >> 8) as u8 ^ byte) as usize]
+    })
+}
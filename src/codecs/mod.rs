@@ -1,16 +1,186 @@
+pub mod flac;
+#[cfg(feature = "mp3")]
+pub mod mp3;
+pub mod ogg_opus;
+#[cfg(feature = "opus")]
+pub mod opus;
 pub mod pcm;
 
+use std::collections::HashMap;
+use std::time::Duration;
+
+use anyhow::bail;
+
+use crate::config::ConfigValues;
 pub use crate::types::{CodecInfo, CodecKind, ContainerKind, EncodedFrame};
+pub use flac::FlacEncoderOptions;
 
 pub const PCM_SAMPLE_RATE: u32 = 48_000;
 pub const PCM_CHANNELS: u8 = 2;
 pub const PCM_FRAME_MS: u32 = 100;
 pub const PCM_SAMPLES_PER_CH: usize = (PCM_SAMPLE_RATE as usize / 1000) * PCM_FRAME_MS as usize;
 pub const PCM_I16_SAMPLES: usize = PCM_SAMPLES_PER_CH * PCM_CHANNELS as usize;
+/// Bitrate des Codecs `mp3` ohne `bitrate` in der Config
+pub const MP3_DEFAULT_BITRATE: u32 = 128_000;
 
 pub trait AudioCodec: Send + Sync {
     fn info(&self) -> &CodecInfo;
     fn encode(&mut self, pcm: &[i16]) -> anyhow::Result<Vec<EncodedFrame>>;
+    /// Gepufferte Reste am Stream-Ende (z. B. letzte Ogg-Seite mit EOS).
+    fn flush(&mut self) -> anyhow::Result<Vec<EncodedFrame>> {
+        Ok(Vec::new())
+    }
+    /// Nach `flush`: endgültige Fassung der ersten Stream-Bytes (z. B.
+    /// FLAC-STREAMINFO mit Länge und MD5), die Dateischreiber über den
+    /// Dateianfang legen. Nie länger als der zuerst ausgegebene Header.
+    fn final_header(&self) -> Option<Vec<u8>> {
+        None
+    }
+}
+
+/// Zulässiger Bitraten-Bereich (bit/s) für verlustbehaftete Codecs.
+pub fn bitrate_range(kind: &CodecKind) -> Option<(u32, u32)> {
+    match kind {
+        CodecKind::OpusOgg | CodecKind::OpusWebRtc => Some((6_000, 510_000)),
+        CodecKind::Mp3 => Some((32_000, 320_000)),
+        CodecKind::Vorbis => Some((45_000, 500_000)),
+        CodecKind::AacLc => Some((8_000, 320_000)),
+        CodecKind::Pcm | CodecKind::Flac => None,
+    }
+}
+
+/// Bitraten-Steuerung des Opus-Encoders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OpusBitrateMode {
+    /// Variable Bitrate (libopus-Standard)
+    Vbr,
+    /// VBR mit begrenzter Schwankung, Pakete bleiben nahe an der Bitrate
+    ConstrainedVbr,
+    /// Konstante Paketgröße
+    Cbr,
+}
+
+impl OpusBitrateMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OpusBitrateMode::Vbr => "vbr",
+            OpusBitrateMode::ConstrainedVbr => "cvbr",
+            OpusBitrateMode::Cbr => "cbr",
+        }
+    }
+}
+
+/// Zulässige Opus-Paketdauern (RFC 6716)
+pub const OPUS_FRAME_SIZES_US: [u64; 6] = [2_500, 5_000, 10_000, 20_000, 40_000, 60_000];
+
+/// Einstellungen für `opusogg` und `opuswebrtc`; `None` lässt den
+/// libopus-Standard stehen.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpusEncoderOptions {
+    /// Gesamtbitrate über alle Streams (bit/s)
+    pub bitrate: Option<u32>,
+    /// 0 (schnell) … 10 (beste Qualität)
+    pub complexity: Option<u8>,
+    pub bitrate_mode: Option<OpusBitrateMode>,
+    /// Paketdauer, eine aus `OPUS_FRAME_SIZES_US`
+    pub frame_size: Duration,
+}
+
+impl Default for OpusEncoderOptions {
+    fn default() -> Self {
+        Self {
+            bitrate: None,
+            complexity: None,
+            bitrate_mode: None,
+            frame_size: Duration::from_millis(20),
+        }
+    }
+}
+
+impl OpusEncoderOptions {
+    /// Liest `{ bitrate = "96k", complexity = 10, bitrate_mode = "cbr",
+    /// frame_size = "20ms" }` aus `config.opus` eines Moduls.
+    pub fn from_config(
+        module_kind: &str,
+        module_name: &str,
+        value: Option<&serde_json::Value>,
+    ) -> anyhow::Result<Self> {
+        let defaults = Self::default();
+        let map: HashMap<String, serde_json::Value> = match value {
+            None => return Ok(defaults),
+            Some(serde_json::Value::Object(map)) => map.clone().into_iter().collect(),
+            Some(other) => bail!(
+                "{} '{}': config.opus must be a table, got {}",
+                module_kind,
+                module_name,
+                other
+            ),
+        };
+        let values = ConfigValues::new(module_kind, module_name, &map);
+
+        let bitrate = match values.bitrate("bitrate")? {
+            Some(bitrate) => {
+                let (min, max) = bitrate_range(&CodecKind::OpusOgg).unwrap_or((1, u32::MAX));
+                Some(values.check_range("bitrate", bitrate, min, max)?)
+            }
+            None => None,
+        };
+        let complexity = match values.f64("complexity")? {
+            Some(complexity) => {
+                Some(values.check_range("complexity", complexity, 0.0, 10.0)? as u8)
+            }
+            None => None,
+        };
+        let bitrate_mode = match map.get("bitrate_mode") {
+            None => None,
+            Some(serde_json::Value::String(mode)) => {
+                Some(match mode.trim().to_ascii_lowercase().as_str() {
+                    "vbr" => OpusBitrateMode::Vbr,
+                    "cvbr" => OpusBitrateMode::ConstrainedVbr,
+                    "cbr" => OpusBitrateMode::Cbr,
+                    other => bail!(
+                        "{} '{}': config.opus.bitrate_mode '{}' unknown (vbr, cvbr or cbr)",
+                        module_kind,
+                        module_name,
+                        other
+                    ),
+                })
+            }
+            Some(other) => bail!(
+                "{} '{}': config.opus.bitrate_mode must be a string, got {}",
+                module_kind,
+                module_name,
+                other
+            ),
+        };
+        let frame_size = match values.duration("frame_size")? {
+            Some(frame_size) => {
+                // Auf µs runden, "2.5ms" landet sonst knapp daneben
+                let us = (frame_size.as_nanos() as f64 / 1000.0).round() as u64;
+                if !OPUS_FRAME_SIZES_US.contains(&us) {
+                    bail!(
+                        "{} '{}': config.opus.frame_size must be 2.5, 5, 10, 20, 40 or 60 ms",
+                        module_kind,
+                        module_name
+                    );
+                }
+                Duration::from_micros(us)
+            }
+            None => defaults.frame_size,
+        };
+
+        Ok(Self {
+            bitrate,
+            complexity,
+            bitrate_mode,
+            frame_size,
+        })
+    }
+
+    /// Samples pro Kanal und Paket bei 48 kHz.
+    pub fn frame_samples(&self) -> usize {
+        (self.frame_size.as_micros() as usize * 48) / 1000
+    }
 }
 
 pub fn supported_codecs() -> Vec<CodecInfo> {
@@ -63,3 +233,56 @@ pub fn supported_codecs() -> Vec<CodecInfo> {
 
     codecs
 }
+
+/// Encoder für eine Codec-ID wie in `supported_codecs` (`pcm`, `opusogg`, ...).
+pub fn create_encoder(codec_id: &str) -> anyhow::Result<Box<dyn AudioCodec>> {
+    create_encoder_for_channels(codec_id, PCM_CHANNELS)
+}
+
+/// Wie `create_encoder`, aber für Flows mit `channels` Kanälen; `opusogg`
+/// kodiert mehr als zwei Kanäle als Multistream (Mapping-Familie 1).
+pub fn create_encoder_for_channels(
+    codec_id: &str,
+    channels: u8,
+) -> anyhow::Result<Box<dyn AudioCodec>> {
+    create_encoder_with_options(codec_id, channels, &OpusEncoderOptions::default())
+}
+
+/// Wie `create_encoder_for_channels`, mit Opus-Einstellungen; andere
+/// Codecs ignorieren sie.
+#[cfg_attr(not(feature = "opus"), allow(unused_variables))]
+pub fn create_encoder_with_options(
+    codec_id: &str,
+    channels: u8,
+    opus: &OpusEncoderOptions,
+) -> anyhow::Result<Box<dyn AudioCodec>> {
+    let codec_id = codec_id.to_ascii_lowercase();
+    match codec_id.as_str() {
+        "pcm" if channels != PCM_CHANNELS => {
+            anyhow::bail!("PCM codec supports {} channels, got {}", PCM_CHANNELS, channels)
+        }
+        "pcm" => Ok(Box::new(pcm::PcmCodec::new())),
+        "flac" => Ok(Box::new(flac::FlacEncoder::new(channels)?)),
+        "mp3" => create_mp3_encoder(channels, MP3_DEFAULT_BITRATE),
+        #[cfg(feature = "opus")]
+        "opusogg" => Ok(Box::new(opus::OpusOggEncoder::with_options(channels, opus)?)),
+        #[cfg(feature = "opus")]
+        "opuswebrtc" => Ok(Box::new(opus::OpusRtpEncoder::with_options(channels, opus)?)),
+        _ if supported_codecs()
+            .iter()
+            .any(|info| format!("{:?}", info.kind).to_lowercase() == codec_id) =>
+        {
+            anyhow::bail!("no encoder for codec '{}' in this build", codec_id)
+        }
+        _ => anyhow::bail!("unknown codec '{}'", codec_id),
+    }
+}
+
+/// MP3-Encoder mit fester Bitrate (bit/s); braucht das Feature `mp3`.
+#[cfg_attr(not(feature = "mp3"), allow(unused_variables))]
+pub fn create_mp3_encoder(channels: u8, bitrate: u32) -> anyhow::Result<Box<dyn AudioCodec>> {
+    #[cfg(feature = "mp3")]
+    return Ok(Box::new(mp3::Mp3Encoder::new(channels, bitrate)?));
+    #[cfg(not(feature = "mp3"))]
+    anyhow::bail!("no encoder for codec 'mp3' in this build")
+}
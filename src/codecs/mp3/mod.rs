@@ -0,0 +1,577 @@
+// src/codecs/mp3/mod.rs
+//
+// MP3-Encoder (MPEG-1 Layer III, 48 kHz, Mono/Stereo, konstante Bitrate)
+// ohne externe Bibliothek. Polyphasen-Filterbank, MDCT mit langen Blöcken und
+// Alias-Reduktion folgen ISO/IEC 11172-3 Anhang C. Ein psychoakustisches
+// Modell gibt es nicht: Skalenfaktoren bleiben 0, pro Granule und Kanal wird
+// der kleinste globale Gain gesucht, dessen Huffman-Bits in den Anteil am
+// Frame passen. Ohne Bit-Reservoir ist jeder Frame für sich dekodierbar.
+mod tables;
+
+use std::f32::consts::PI;
+use std::sync::OnceLock;
+
+use anyhow::{bail, Result};
+
+use crate::codecs::flac::BitWriter;
+use crate::codecs::{
+    AudioCodec, CodecInfo, CodecKind, ContainerKind, EncodedFrame, PCM_SAMPLE_RATE,
+};
+use tables::{
+    HuffmanTable, BIG_VALUE_TABLES, QUAD_CODES_A, QUAD_LENS_A, SFB_LONG_48K, SYNTHESIS_WINDOW,
+};
+
+/// Samples pro Kanal und Frame (zwei Granules)
+pub const MP3_FRAME_SAMPLES: usize = 1152;
+/// Layer-III-Bitraten (bit/s); der Header-Index ist die Position plus 1
+pub const MP3_BITRATES: [u32; 14] = [
+    32_000, 40_000, 48_000, 56_000, 64_000, 80_000, 96_000, 112_000, 128_000, 160_000, 192_000,
+    224_000, 256_000, 320_000,
+];
+
+const GRANULE: usize = 576;
+const SUBBANDS: usize = 32;
+const SLOTS: usize = GRANULE / SUBBANDS;
+/// 15 plus 13 Linbits
+const MAX_QUANT: u32 = 8206;
+/// `part2_3_length` hat 12 Bit
+const MAX_GRANULE_BITS: usize = 4095;
+/// Rundung beim Quantisieren wie im Referenz-Encoder
+const QUANT_ROUNDING: f32 = 0.4054;
+/// Alias-Reduktion, c_i aus Tabelle B.9
+const ALIAS_COEFFICIENTS: [f32; 8] = [
+    -0.6, -0.535, -0.33, -0.185, -0.095, -0.041, -0.0142, -0.0037,
+];
+/// Bänder in region0 und region1 abhängig von der Zahl der Bänder mit
+/// Big-Values (wie bei shine/LAME)
+const REGION_SPLIT: [(usize, usize); 23] = [
+    (0, 0),
+    (0, 0),
+    (0, 0),
+    (0, 0),
+    (0, 0),
+    (0, 1),
+    (1, 1),
+    (1, 1),
+    (1, 2),
+    (2, 2),
+    (2, 3),
+    (2, 3),
+    (3, 4),
+    (3, 4),
+    (3, 4),
+    (4, 5),
+    (4, 5),
+    (4, 6),
+    (5, 6),
+    (5, 6),
+    (5, 7),
+    (6, 7),
+    (6, 7),
+];
+
+/// Vorberechnete Filterbank-Koeffizienten, für alle Encoder gleich.
+struct Transforms {
+    /// Analysefenster C = D/32
+    window: [f32; 512],
+    /// cos((2k + 1)(i - 16)π/64)
+    analysis: [[f32; 64]; SUBBANDS],
+    /// Sinusfenster mal MDCT-Kern cos(π/72 (2i + 19)(2k + 1)), durch 9
+    /// geteilt, weil die IMDCT des Decoders nicht normiert ist
+    mdct: [[f32; 36]; SLOTS],
+    /// (cs, ca) der Alias-Reduktion
+    alias: [(f32, f32); 8],
+}
+
+fn transforms() -> &'static Transforms {
+    static TRANSFORMS: OnceLock<Transforms> = OnceLock::new();
+    TRANSFORMS.get_or_init(|| {
+        let mut window = [0.0; 512];
+        for (c, d) in window.iter_mut().zip(SYNTHESIS_WINDOW) {
+            *c = d / 32.0;
+        }
+        let mut analysis = [[0.0; 64]; SUBBANDS];
+        for (k, row) in analysis.iter_mut().enumerate() {
+            for (i, value) in row.iter_mut().enumerate() {
+                *value = ((2 * k + 1) as f32 * (i as f32 - 16.0) * PI / 64.0).cos();
+            }
+        }
+        let mut mdct = [[0.0; 36]; SLOTS];
+        for (k, row) in mdct.iter_mut().enumerate() {
+            for (i, value) in row.iter_mut().enumerate() {
+                let window = (PI / 36.0 * (i as f32 + 0.5)).sin();
+                *value =
+                    window * (PI / 72.0 * (2 * i + 19) as f32 * (2 * k + 1) as f32).cos() / 9.0;
+            }
+        }
+        let alias = ALIAS_COEFFICIENTS.map(|c| {
+            let norm = (1.0 + c * c).sqrt();
+            (1.0 / norm, c / norm)
+        });
+        Transforms {
+            window,
+            analysis,
+            mdct,
+            alias,
+        }
+    })
+}
+
+/// Filterbank-Zustand eines Kanals
+struct ChannelState {
+    /// Die letzten 512 Samples, das neueste vorn
+    history: [f32; 512],
+    /// Subband-Samples der vorigen Granule (MDCT-Überlappung)
+    previous: [[f32; SLOTS]; SUBBANDS],
+}
+
+impl ChannelState {
+    fn new() -> Self {
+        Self {
+            history: [0.0; 512],
+            previous: [[0.0; SLOTS]; SUBBANDS],
+        }
+    }
+
+    /// 576 Samples eines Kanals in 576 Spektrallinien.
+    fn analyze(&mut self, samples: impl Iterator<Item = i16>) -> [f32; GRANULE] {
+        let transforms = transforms();
+        let mut samples = samples.map(|sample| sample as f32 / 32768.0);
+        let mut subbands = [[0.0f32; SLOTS]; SUBBANDS];
+        for slot in 0..SLOTS {
+            self.history.copy_within(0..480, 32);
+            for i in (0..32).rev() {
+                self.history[i] = samples.next().unwrap_or(0.0);
+            }
+            let mut folded = [0.0f32; 64];
+            for (i, value) in folded.iter_mut().enumerate() {
+                *value = (0..8)
+                    .map(|j| transforms.window[i + 64 * j] * self.history[i + 64 * j])
+                    .sum();
+            }
+            for (band, (row, out)) in transforms.analysis.iter().zip(&mut subbands).enumerate() {
+                let value: f32 = row.iter().zip(&folded).map(|(m, y)| m * y).sum();
+                // Frequenzumkehr der ungeraden Subbänder
+                out[slot] = if band % 2 == 1 && slot % 2 == 1 {
+                    -value
+                } else {
+                    value
+                };
+            }
+        }
+
+        let mut lines = [0.0f32; GRANULE];
+        for (band, current) in subbands.iter().enumerate() {
+            let mut input = [0.0f32; 36];
+            input[..SLOTS].copy_from_slice(&self.previous[band]);
+            input[SLOTS..].copy_from_slice(current);
+            for (k, row) in transforms.mdct.iter().enumerate() {
+                lines[band * SLOTS + k] = row.iter().zip(&input).map(|(m, x)| m * x).sum();
+            }
+            self.previous[band] = *current;
+        }
+        for band in 1..SUBBANDS {
+            for (i, (cs, ca)) in transforms.alias.iter().enumerate() {
+                let upper = lines[band * SLOTS - 1 - i];
+                let lower = lines[band * SLOTS + i];
+                lines[band * SLOTS - 1 - i] = upper * cs + lower * ca;
+                lines[band * SLOTS + i] = lower * cs - upper * ca;
+            }
+        }
+        lines
+    }
+}
+
+/// Quantisierte Granule eines Kanals mit ihrer Seiteninformation.
+struct Granule {
+    values: [u32; GRANULE],
+    negative: [bool; GRANULE],
+    global_gain: u8,
+    layout: Layout,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Layout {
+    big_values: usize,
+    /// Ende der Count1-Region, danach nur Nullen
+    count1_end: usize,
+    region0_count: usize,
+    region1_count: usize,
+    table_select: [usize; 3],
+    count1_table_b: bool,
+    bits: usize,
+}
+
+impl Layout {
+    /// Grenzen von region1 und region2 innerhalb der Big-Values
+    fn region_bounds(&self) -> [usize; 3] {
+        let end = self.big_values * 2;
+        [
+            SFB_LONG_48K[self.region0_count + 1].min(end),
+            SFB_LONG_48K[self.region0_count + self.region1_count + 2].min(end),
+            end,
+        ]
+    }
+}
+
+impl Granule {
+    /// Kleinster globaler Gain, dessen Huffman-Bits in `budget` passen.
+    fn quantize(lines: &[f32; GRANULE], budget: usize) -> Self {
+        let mut granule = Self {
+            values: [0; GRANULE],
+            negative: [false; GRANULE],
+            global_gain: 0,
+            layout: Layout::default(),
+        };
+        let mut magnitudes = [0.0f32; GRANULE];
+        for (i, line) in lines.iter().enumerate() {
+            magnitudes[i] = line.abs().powf(0.75);
+            granule.negative[i] = *line < 0.0;
+        }
+
+        let (mut low, mut high) = (0u8, u8::MAX);
+        let mut values = [0u32; GRANULE];
+        while low < high {
+            let gain = low + (high - low) / 2;
+            match quantize_values(&magnitudes, gain, &mut values).then(|| layout(&values)) {
+                Some(layout) if layout.bits <= budget => high = gain,
+                _ => low = gain + 1,
+            }
+        }
+        granule.global_gain = low;
+        quantize_values(&magnitudes, low, &mut granule.values);
+        granule.layout = layout(&granule.values);
+        granule
+    }
+
+    fn write(&self, writer: &mut BitWriter) {
+        let layout = &self.layout;
+        let mut start = 0;
+        for (region, end) in layout.region_bounds().into_iter().enumerate() {
+            let table = &BIG_VALUE_TABLES[layout.table_select[region]];
+            for i in (start..end).step_by(2) {
+                if table.wrap > 0 {
+                    write_pair(writer, table, self.pair(i), self.pair(i + 1));
+                }
+            }
+            start = end;
+        }
+        for i in (start..layout.count1_end).step_by(4) {
+            let quad = &self.values[i..i + 4];
+            let index = quad_index(quad);
+            if layout.count1_table_b {
+                writer.write(15 - index as u64, 4);
+            } else {
+                writer.write(QUAD_CODES_A[index] as u64, QUAD_LENS_A[index] as u32);
+            }
+            for (value, negative) in quad.iter().zip(&self.negative[i..i + 4]) {
+                if *value != 0 {
+                    writer.write(*negative as u64, 1);
+                }
+            }
+        }
+    }
+
+    fn pair(&self, i: usize) -> (u32, bool) {
+        (self.values[i], self.negative[i])
+    }
+}
+
+/// `false`, wenn ein Wert über den größten kodierbaren hinausgeht.
+fn quantize_values(magnitudes: &[f32; GRANULE], gain: u8, values: &mut [u32; GRANULE]) -> bool {
+    let scale = 2f32.powf(-0.1875 * (gain as f32 - 210.0));
+    for (value, magnitude) in values.iter_mut().zip(magnitudes) {
+        let quantized = magnitude * scale + QUANT_ROUNDING;
+        if quantized > MAX_QUANT as f32 {
+            return false;
+        }
+        *value = quantized as u32;
+    }
+    true
+}
+
+/// Aufteilung in Big-Values, Count1 und Nullen samt Tabellenwahl.
+fn layout(values: &[u32; GRANULE]) -> Layout {
+    let mut count1_end = GRANULE;
+    while count1_end >= 2 && values[count1_end - 1] == 0 && values[count1_end - 2] == 0 {
+        count1_end -= 2;
+    }
+    let mut big_end = count1_end;
+    while big_end >= 4 && values[big_end - 4..big_end].iter().all(|value| *value <= 1) {
+        big_end -= 4;
+    }
+
+    let bands = SFB_LONG_48K
+        .iter()
+        .take_while(|bound| **bound < big_end)
+        .count();
+    let (region0_count, region1_count) = REGION_SPLIT[bands.min(REGION_SPLIT.len() - 1)];
+    let mut layout = Layout {
+        big_values: big_end / 2,
+        count1_end,
+        region0_count,
+        region1_count,
+        ..Layout::default()
+    };
+
+    let mut start = 0;
+    for (region, end) in layout.region_bounds().into_iter().enumerate() {
+        let (table, bits) = choose_table(&values[start..end]);
+        layout.table_select[region] = table;
+        layout.bits += bits;
+        start = end;
+    }
+
+    let (mut bits_a, mut bits_b) = (0, 0);
+    for quad in values[big_end..count1_end].chunks(4) {
+        let index = quad_index(quad);
+        let signs = quad.iter().filter(|value| **value != 0).count();
+        bits_a += QUAD_LENS_A[index] as usize + signs;
+        bits_b += 4 + signs;
+    }
+    layout.count1_table_b = bits_b < bits_a;
+    layout.bits += bits_a.min(bits_b);
+    layout
+}
+
+/// `v << 3 | w << 2 | x << 1 | y` eines Count1-Quadrupels
+fn quad_index(quad: &[u32]) -> usize {
+    quad.iter()
+        .fold(0, |index, value| index << 1 | *value as usize)
+}
+
+/// Günstigste Big-Values-Tabelle für eine Region: `(table_select, bits)`.
+fn choose_table(values: &[u32]) -> (usize, usize) {
+    let max = values.iter().copied().max().unwrap_or(0);
+    if max == 0 {
+        return (0, 0);
+    }
+    let fits = |table: &HuffmanTable| {
+        if table.linbits > 0 {
+            max < 15 + (1 << table.linbits)
+        } else {
+            (max as usize) < table.wrap
+        }
+    };
+    let mut candidates: Vec<usize> = (1..16)
+        .filter(|table| BIG_VALUE_TABLES[*table].wrap > 0 && fits(&BIG_VALUE_TABLES[*table]))
+        .collect();
+    // Je Linbits-Familie nur die kleinste passende
+    candidates.extend((16..24).find(|table| fits(&BIG_VALUE_TABLES[*table])));
+    candidates.extend((24..32).find(|table| fits(&BIG_VALUE_TABLES[*table])));
+    candidates
+        .into_iter()
+        .map(|table| {
+            let bits = values
+                .chunks(2)
+                .map(|pair| pair_bits(&BIG_VALUE_TABLES[table], pair[0], pair[1]))
+                .sum();
+            (table, bits)
+        })
+        .min_by_key(|(_, bits)| *bits)
+        .expect("table 24 + 13 linbits fits every value")
+}
+
+fn escape(table: &HuffmanTable, value: u32) -> usize {
+    if table.linbits > 0 {
+        value.min(15) as usize
+    } else {
+        value as usize
+    }
+}
+
+fn pair_bits(table: &HuffmanTable, x: u32, y: u32) -> usize {
+    let mut bits = table.lens[escape(table, x) * table.wrap + escape(table, y)] as usize;
+    for value in [x, y] {
+        if value > 0 {
+            bits += 1;
+        }
+        if table.linbits > 0 && value >= 15 {
+            bits += table.linbits as usize;
+        }
+    }
+    bits
+}
+
+fn write_pair(writer: &mut BitWriter, table: &HuffmanTable, x: (u32, bool), y: (u32, bool)) {
+    let index = escape(table, x.0) * table.wrap + escape(table, y.0);
+    writer.write(table.codes[index] as u64, table.lens[index] as u32);
+    for (value, negative) in [x, y] {
+        if value == 0 {
+            continue;
+        }
+        if table.linbits > 0 && value >= 15 {
+            writer.write((value - 15) as u64, table.linbits);
+        }
+        writer.write(negative as u64, 1);
+    }
+}
+
+pub struct Mp3Encoder {
+    info: CodecInfo,
+    bitrate_index: u32,
+    frame_bytes: usize,
+    states: Vec<ChannelState>,
+    /// Interleaved, weniger als ein Frame
+    pending: Vec<i16>,
+    frames: u64,
+    finished: bool,
+}
+
+impl Mp3Encoder {
+    pub fn new(channels: u8, bitrate: u32) -> Result<Self> {
+        if !(1..=2).contains(&channels) {
+            bail!("MP3 supports 1 or 2 channels, got {}", channels);
+        }
+        let Some(position) = MP3_BITRATES.iter().position(|rate| *rate == bitrate) else {
+            bail!(
+                "MP3 bitrate must be one of {} kbit/s, got {}",
+                MP3_BITRATES
+                    .iter()
+                    .map(|rate| (rate / 1000).to_string())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                bitrate
+            );
+        };
+        Ok(Self {
+            info: CodecInfo {
+                kind: CodecKind::Mp3,
+                sample_rate: PCM_SAMPLE_RATE,
+                channels,
+                container: ContainerKind::Mpeg,
+            },
+            bitrate_index: position as u32 + 1,
+            frame_bytes: 144 * bitrate as usize / PCM_SAMPLE_RATE as usize,
+            states: (0..channels).map(|_| ChannelState::new()).collect(),
+            pending: Vec::with_capacity(MP3_FRAME_SAMPLES * channels as usize),
+            frames: 0,
+            finished: false,
+        })
+    }
+
+    pub fn bitrate(&self) -> u32 {
+        MP3_BITRATES[self.bitrate_index as usize - 1]
+    }
+
+    /// Bisher ausgegebene Frames
+    pub fn frames(&self) -> u64 {
+        self.frames
+    }
+
+    /// Kodiert die ersten 1152 Samples pro Kanal aus `pending`.
+    fn encode_frame(&mut self) -> EncodedFrame {
+        let channels = self.states.len();
+        let side_bytes = if channels == 1 { 17 } else { 32 };
+        let mut remaining = (self.frame_bytes - 4 - side_bytes) * 8;
+        let mut granules = Vec::with_capacity(2 * channels);
+        for granule in 0..2 {
+            for (channel, state) in self.states.iter_mut().enumerate() {
+                let offset = granule * GRANULE * channels + channel;
+                let samples = self.pending[offset..]
+                    .iter()
+                    .step_by(channels)
+                    .take(GRANULE)
+                    .copied();
+                let lines = state.analyze(samples);
+                let budget = (remaining / (2 * channels - granules.len())).min(MAX_GRANULE_BITS);
+                let quantized = Granule::quantize(&lines, budget);
+                remaining -= quantized.layout.bits;
+                granules.push(quantized);
+            }
+        }
+        self.pending.drain(..MP3_FRAME_SAMPLES * channels);
+
+        let mut writer = BitWriter::default();
+        // Header: MPEG-1, Layer III, ohne CRC, 48 kHz, ohne Padding
+        writer.write(0x7FF, 11);
+        writer.write(0b11, 2);
+        writer.write(0b01, 2);
+        writer.write(1, 1);
+        writer.write(self.bitrate_index as u64, 4);
+        writer.write(0b01, 2);
+        writer.write(0, 2);
+        // Stereo bzw. Mono, ohne Mode-Extension, Copyright, Original, Emphasis
+        writer.write(if channels == 1 { 0b11 } else { 0b00 }, 2);
+        writer.write(0, 6);
+
+        // Seiteninformation: main_data_begin 0, private Bits, scfsi
+        writer.write(0, 9);
+        writer.write(0, if channels == 1 { 5 } else { 3 });
+        writer.write(0, 4 * channels as u32);
+        for granule in &granules {
+            let layout = &granule.layout;
+            writer.write(layout.bits as u64, 12);
+            writer.write(layout.big_values as u64, 9);
+            writer.write(granule.global_gain as u64, 8);
+            // scalefac_compress 0, keine Blockumschaltung
+            writer.write(0, 4);
+            writer.write(0, 1);
+            for table in layout.table_select {
+                writer.write(table as u64, 5);
+            }
+            writer.write(layout.region0_count as u64, 4);
+            writer.write(layout.region1_count as u64, 3);
+            // preflag, scalefac_scale
+            writer.write(0, 2);
+            writer.write(layout.count1_table_b as u64, 1);
+        }
+        for granule in &granules {
+            granule.write(&mut writer);
+        }
+
+        let mut payload = writer.into_bytes();
+        payload.resize(self.frame_bytes, 0);
+        self.frames += 1;
+        EncodedFrame {
+            payload,
+            info: self.info.clone(),
+        }
+    }
+}
+
+impl AudioCodec for Mp3Encoder {
+    fn info(&self) -> &CodecInfo {
+        &self.info
+    }
+
+    fn encode(&mut self, pcm: &[i16]) -> Result<Vec<EncodedFrame>> {
+        let channels = self.states.len();
+        if !pcm.len().is_multiple_of(channels) {
+            bail!(
+                "MP3 encoder expected whole sample frames of {} channels, got {} samples",
+                channels,
+                pcm.len()
+            );
+        }
+        if self.finished {
+            bail!("MP3 encoder already flushed");
+        }
+        self.pending.extend_from_slice(pcm);
+        let mut frames = Vec::new();
+        while self.pending.len() >= MP3_FRAME_SAMPLES * channels {
+            frames.push(self.encode_frame());
+        }
+        Ok(frames)
+    }
+
+    /// Rest mit Stille aufgefüllt plus ein stiller Frame, damit die
+    /// Verzögerung der Filterbank mit ausgegeben wird.
+    fn flush(&mut self) -> Result<Vec<EncodedFrame>> {
+        if self.finished {
+            return Ok(Vec::new());
+        }
+        self.finished = true;
+        if self.frames == 0 && self.pending.is_empty() {
+            return Ok(Vec::new());
+        }
+        let frame_len = MP3_FRAME_SAMPLES * self.states.len();
+        let padded = self.pending.len().div_ceil(frame_len) * frame_len + frame_len;
+        self.pending.resize(padded, 0);
+        let mut frames = Vec::new();
+        while !self.pending.is_empty() {
+            frames.push(self.encode_frame());
+        }
+        Ok(frames)
+    }
+}
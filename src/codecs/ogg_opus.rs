@@ -0,0 +1,281 @@
+// src/codecs/ogg_opus.rs
+//
+// Ogg-Kapselung für Opus (RFC 7845) inklusive Multistream: Bei mehr als zwei
+// Kanälen wird nach Channel-Mapping-Familie 1 (Vorbis-Kanalreihenfolge,
+// 1–8 Kanäle) auf mehrere Opus-Streams verteilt, gekoppelte Paare zuerst. Der
+// OpusHead trägt Stream-Anzahl und Mapping-Tabelle, damit Decoder wie ffmpeg,
+// VLC oder Browser das Surround-Signal aus einem einzigen Ogg-Stream
+// zurückgewinnen. `OggOpusDemuxer` geht den Weg zurück zu einzelnen Paketen.
+use std::time::Duration;
+
+use anyhow::{bail, Result};
+
+use crate::audio::ogg::{paginate, OggPage};
+
+/// Höchste Kanalzahl der Mapping-Familie 1
+pub const MAX_SURROUND_CHANNELS: u8 = 8;
+/// Granule-Positionen zählen bei Opus immer in 48 kHz.
+pub const OPUS_GRANULE_RATE: u32 = 48_000;
+
+const FLAG_EOS: u8 = 0x04;
+
+/// Aufteilung der Eingangskanäle auf Opus-Streams.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChannelMapping {
+    pub family: u8,
+    pub channels: u8,
+    pub streams: u8,
+    /// Gekoppelte (Stereo-)Streams, belegen die ersten Stream-Indizes
+    pub coupled: u8,
+    /// Pro Ausgangskanal der dekodierte Kanal (`2 * coupled` + Mono-Streams)
+    pub mapping: Vec<u8>,
+}
+
+impl ChannelMapping {
+    /// Familie 0 für Mono/Stereo, Familie 1 für 3–8 Kanäle in
+    /// Vorbis-Reihenfolge (L, C, R, ... LFE zuletzt).
+    pub fn for_channels(channels: u8) -> Result<Self> {
+        let (family, streams, coupled, mapping): (u8, u8, u8, &[u8]) = match channels {
+            1 => (0, 1, 0, &[0]),
+            2 => (0, 1, 1, &[0, 1]),
+            // L, C, R
+            3 => (1, 2, 1, &[0, 2, 1]),
+            // FL, FR, RL, RR
+            4 => (1, 2, 2, &[0, 1, 2, 3]),
+            // FL, C, FR, RL, RR
+            5 => (1, 3, 2, &[0, 4, 1, 2, 3]),
+            // 5.1: FL, C, FR, RL, RR, LFE
+            6 => (1, 4, 2, &[0, 4, 1, 2, 3, 5]),
+            // 6.1: FL, C, FR, SL, SR, RC, LFE
+            7 => (1, 4, 3, &[0, 4, 1, 2, 3, 5, 6]),
+            // 7.1: FL, C, FR, SL, SR, RL, RR, LFE
+            8 => (1, 5, 3, &[0, 6, 1, 2, 3, 4, 5, 7]),
+            _ => bail!(
+                "Opus supports 1 to {} channels, got {}",
+                MAX_SURROUND_CHANNELS,
+                channels
+            ),
+        };
+        Ok(Self {
+            family,
+            channels,
+            streams,
+            coupled,
+            mapping: mapping.to_vec(),
+        })
+    }
+
+    pub fn is_multistream(&self) -> bool {
+        self.streams > 1
+    }
+}
+
+/// ID-Header (`OpusHead`, Version 1). Die Mapping-Tabelle steht nur bei
+/// Familie ≠ 0 im Header.
+pub fn opus_head(mapping: &ChannelMapping, pre_skip: u16, input_rate: u32) -> Vec<u8> {
+    let mut packet = b"OpusHead".to_vec();
+    packet.push(1);
+    packet.push(mapping.channels);
+    packet.extend_from_slice(&pre_skip.to_le_bytes());
+    packet.extend_from_slice(&input_rate.to_le_bytes());
+    // Output-Gain 0 dB
+    packet.extend_from_slice(&0i16.to_le_bytes());
+    packet.push(mapping.family);
+    if mapping.family != 0 {
+        packet.push(mapping.streams);
+        packet.push(mapping.coupled);
+        packet.extend_from_slice(&mapping.mapping);
+    }
+    packet
+}
+
+/// Kommentar-Header ohne Tags, nur mit Vendor-String.
+pub fn opus_tags(vendor: &str) -> Vec<u8> {
+    let mut packet = b"OpusTags".to_vec();
+    packet.extend_from_slice(&(vendor.len() as u32).to_le_bytes());
+    packet.extend_from_slice(vendor.as_bytes());
+    packet.extend_from_slice(&0u32.to_le_bytes());
+    packet
+}
+
+/// Dauer eines Opus-Pakets aus dem TOC-Byte (RFC 6716, Abschnitt 3.1).
+/// Bei Multistream-Paketen genügt der erste Stream, alle sind gleich lang.
+pub fn opus_packet_duration(packet: &[u8]) -> Option<Duration> {
+    let toc = *packet.first()?;
+    let config = toc >> 3;
+    // Frame-Dauer in 1/10 ms
+    let frame = match config {
+        0..=11 => [100, 200, 400, 600][(config % 4) as usize],
+        12..=15 => [100, 200][(config % 2) as usize],
+        _ => [25, 50, 100, 200][(config % 4) as usize],
+    };
+    let frames = match toc & 0x03 {
+        0 => 1,
+        1 | 2 => 2,
+        _ => (*packet.get(1)? & 0x3F) as u64,
+    };
+    // Mehr als 120 ms je Paket sind ungültig
+    let duration = frame * frames;
+    (frames > 0 && duration <= 1_200).then(|| Duration::from_micros(duration * 100))
+}
+
+/// `opus_packet_duration` in 48-kHz-Samples, z. B. als RTP-Timestamp-Schritt.
+pub fn opus_packet_samples(packet: &[u8]) -> Option<u32> {
+    opus_packet_duration(packet)
+        .map(|duration| (duration.as_micros() as u64 * OPUS_GRANULE_RATE as u64 / 1_000_000) as u32)
+}
+
+/// Verpackt Opus-Pakete eines logischen Streams in Ogg-Seiten. Die
+/// Granule-Position jeder Seite ist die Sampleposition (48 kHz, inklusive
+/// Pre-Skip) am Ende des letzten dort abgeschlossenen Pakets.
+pub struct OggOpusMuxer {
+    serial: u32,
+    sequence: u32,
+    granule: u64,
+    headers: Option<[Vec<u8>; 2]>,
+}
+
+impl OggOpusMuxer {
+    pub fn new(serial: u32, mapping: &ChannelMapping, pre_skip: u16, input_rate: u32) -> Self {
+        Self {
+            serial,
+            sequence: 0,
+            granule: pre_skip as u64,
+            headers: Some([
+                opus_head(mapping, pre_skip, input_rate),
+                opus_tags(concat!("airlift ", env!("CARGO_PKG_VERSION"))),
+            ]),
+        }
+    }
+
+    /// Header-Seiten vor dem ersten Audiopaket: OpusHead allein auf der
+    /// BOS-Seite, OpusTags auf eigener Seite.
+    fn take_headers(&mut self) -> Vec<OggPage> {
+        let Some([head, tags]) = self.headers.take() else {
+            return Vec::new();
+        };
+        let mut pages = paginate(&[head], self.serial, &mut self.sequence, true);
+        pages.extend(paginate(&[tags], self.serial, &mut self.sequence, false));
+        pages
+    }
+
+    /// Seiten für `packets` mit je `samples` Samples pro Kanal (48 kHz).
+    pub fn pages(&mut self, packets: &[Vec<u8>], samples: u64) -> Vec<OggPage> {
+        let mut pages = self.take_headers();
+        if packets.is_empty() {
+            return pages;
+        }
+        for mut page in paginate(packets, self.serial, &mut self.sequence, false) {
+            let finished = page.segments.iter().filter(|lace| **lace < 255).count() as u64;
+            if finished > 0 {
+                self.granule += finished * samples;
+                page.granule = self.granule;
+            }
+            pages.push(page);
+        }
+        pages
+    }
+
+    /// Letzte Seite mit EOS; `packets` darf leer sein.
+    pub fn finish(&mut self, packets: &[Vec<u8>], samples: u64) -> Vec<OggPage> {
+        let mut pages = self.pages(packets, samples);
+        match pages.last_mut() {
+            Some(last) if !packets.is_empty() => last.header_type |= FLAG_EOS,
+            _ => pages.push(OggPage {
+                header_type: FLAG_EOS,
+                granule: self.granule,
+                serial: self.serial,
+                sequence: self.next_sequence(),
+                segments: Vec::new(),
+                body: Vec::new(),
+            }),
+        }
+        pages
+    }
+
+    fn next_sequence(&mut self) -> u32 {
+        let sequence = self.sequence;
+        self.sequence = self.sequence.wrapping_add(1);
+        sequence
+    }
+
+    /// Aktuelle Granule-Position (48-kHz-Samples inklusive Pre-Skip)
+    pub fn granule(&self) -> u64 {
+        self.granule
+    }
+}
+
+/// Gegenstück zu `OggOpusMuxer`: setzt Opus-Pakete aus Ogg-Seiten wieder
+/// zusammen. Die beiden Header-Pakete werden ausgewertet und nicht
+/// weitergereicht.
+#[derive(Default)]
+pub struct OggOpusDemuxer {
+    pending: Vec<u8>,
+    partial: Vec<u8>,
+    headers_seen: usize,
+    channels: Option<u8>,
+    pre_skip: u16,
+}
+
+impl OggOpusDemuxer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Kanalzahl laut OpusHead, sobald er gelesen ist.
+    pub fn channels(&self) -> Option<u8> {
+        self.channels
+    }
+
+    /// Samples pro Kanal, die ein Decoder am Anfang verwerfen soll.
+    pub fn pre_skip(&self) -> u16 {
+        self.pre_skip
+    }
+
+    /// Nimmt beliebig zerteilte Ogg-Bytes an und liefert die darin
+    /// abgeschlossenen Audiopakete.
+    pub fn push(&mut self, data: &[u8]) -> Result<Vec<Vec<u8>>> {
+        self.pending.extend_from_slice(data);
+        let mut packets = Vec::new();
+        let mut consumed = 0;
+        while let Some((page, len)) = OggPage::parse(&self.pending[consumed..])? {
+            consumed += len;
+            // Neuer logischer Stream (z. B. nach Formatwechsel beim Sender)
+            if page.is_bos() {
+                self.headers_seen = 0;
+                self.partial.clear();
+            }
+            for (part, complete) in page.packet_parts() {
+                self.partial.extend_from_slice(part);
+                if complete {
+                    let packet = std::mem::take(&mut self.partial);
+                    if let Some(packet) = self.header_or_audio(packet)? {
+                        packets.push(packet);
+                    }
+                }
+            }
+        }
+        self.pending.drain(..consumed);
+        Ok(packets)
+    }
+
+    fn header_or_audio(&mut self, packet: Vec<u8>) -> Result<Option<Vec<u8>>> {
+        match self.headers_seen {
+            0 => {
+                if !packet.starts_with(b"OpusHead") || packet.len() < 19 {
+                    bail!("Ogg stream does not start with an OpusHead");
+                }
+                self.channels = Some(packet[9]);
+                self.pre_skip = u16::from_le_bytes([packet[10], packet[11]]);
+            }
+            1 => {
+                if !packet.starts_with(b"OpusTags") {
+                    bail!("OpusHead is not followed by OpusTags");
+                }
+            }
+            _ => return Ok(Some(packet)),
+        }
+        self.headers_seen += 1;
+        Ok(None)
+    }
+}
@@ -0,0 +1,358 @@
+// src/codecs/opus.rs
+//
+// Opus-Encoder (libopus) in zwei Verpackungen: `OpusOggEncoder` (`opusogg`)
+// schreibt Ogg-Seiten, `OpusRtpEncoder` (`opuswebrtc`) liefert jedes Paket
+// einzeln als RTP-Payload (RFC 7587). Beide laufen über die
+// Multistream-API: Mono/Stereo ergeben einen einzelnen Stream (Familie 0),
+// 3–8 Kanäle werden in Ogg nach Mapping-Familie 1 verteilt (siehe
+// `ogg_opus`), so dass Surround-Zuspielungen als ein Opus-Stream laufen;
+// RTP bleibt bei Mono/Stereo. Bitrate,
+// Komplexität, VBR/CBR und Paketdauer kommen aus `OpusEncoderOptions`,
+// Eingang immer 48 kHz interleaved.
+use std::ffi::CStr;
+use std::os::raw::c_int;
+
+use anyhow::{bail, Result};
+use audiopus_sys as ffi;
+
+use crate::audio::ogg::OggPage;
+use crate::codecs::ogg_opus::{ChannelMapping, OggOpusMuxer};
+use crate::codecs::{
+    AudioCodec, CodecInfo, CodecKind, ContainerKind, EncodedFrame, OpusBitrateMode,
+    OpusEncoderOptions,
+};
+use crate::core::timestamp::utc_ns_now;
+
+pub const OPUS_SAMPLE_RATE: u32 = 48_000;
+/// Obergrenze für ein 20-ms-Paket: 1275 Bytes pro Stream (RFC 6716) plus
+/// Self-Delimiting-Overhead.
+const MAX_PACKET_PER_STREAM: usize = 1_280;
+
+// Aus opus_defines.h
+const OPUS_APPLICATION_AUDIO: c_int = 2049;
+const OPUS_SET_BITRATE_REQUEST: c_int = 4002;
+const OPUS_SET_VBR_REQUEST: c_int = 4006;
+const OPUS_SET_COMPLEXITY_REQUEST: c_int = 4010;
+const OPUS_SET_VBR_CONSTRAINT_REQUEST: c_int = 4020;
+const OPUS_GET_LOOKAHEAD_REQUEST: c_int = 4027;
+
+/// Besitzt den libopus-Multistream-Encoder-State.
+struct RawEncoder(*mut ffi::OpusMSEncoder);
+
+// Der State ist nicht an einen Thread gebunden, Zugriff nur über `&mut`.
+unsafe impl Send for RawEncoder {}
+unsafe impl Sync for RawEncoder {}
+
+impl Drop for RawEncoder {
+    fn drop(&mut self) {
+        unsafe { ffi::opus_multistream_encoder_destroy(self.0) }
+    }
+}
+
+fn opus_error(code: c_int) -> anyhow::Error {
+    let message = unsafe { CStr::from_ptr(ffi::opus_strerror(code)) };
+    anyhow::anyhow!("libopus: {}", message.to_string_lossy())
+}
+
+/// Gemeinsamer Teil beider Encoder: libopus-State, Optionen und die noch
+/// nicht zu einem Paket gewordenen Samples.
+struct PacketEncoder {
+    encoder: RawEncoder,
+    mapping: ChannelMapping,
+    options: OpusEncoderOptions,
+    /// Samples pro Kanal und Paket
+    frame_samples: usize,
+    lookahead: u16,
+    /// Noch nicht zu einem Paket gewordene Samples (interleaved)
+    pending: Vec<i16>,
+    packet: Vec<u8>,
+}
+
+impl PacketEncoder {
+    fn new(channels: u8, options: &OpusEncoderOptions) -> Result<Self> {
+        let mapping = ChannelMapping::for_channels(channels)?;
+
+        let mut streams: c_int = 0;
+        let mut coupled: c_int = 0;
+        let mut table = vec![0u8; channels as usize];
+        let mut error: c_int = 0;
+        let raw = unsafe {
+            ffi::opus_multistream_surround_encoder_create(
+                OPUS_SAMPLE_RATE as i32,
+                channels as c_int,
+                mapping.family as c_int,
+                &mut streams,
+                &mut coupled,
+                table.as_mut_ptr(),
+                OPUS_APPLICATION_AUDIO,
+                &mut error,
+            )
+        };
+        if raw.is_null() || error != 0 {
+            return Err(opus_error(error));
+        }
+        let encoder = RawEncoder(raw);
+        // Der OpusHead muss exakt die Aufteilung von libopus beschreiben
+        if (streams as u8, coupled as u8, table.as_slice())
+            != (mapping.streams, mapping.coupled, mapping.mapping.as_slice())
+        {
+            bail!(
+                "libopus chose an unexpected layout for {} channels ({} streams, {} coupled, {:?})",
+                channels,
+                streams,
+                coupled,
+                table
+            );
+        }
+
+        let mut lookahead: i32 = 0;
+        let result = unsafe {
+            ffi::opus_multistream_encoder_ctl(
+                encoder.0,
+                OPUS_GET_LOOKAHEAD_REQUEST,
+                &mut lookahead as *mut i32,
+            )
+        };
+        if result != 0 {
+            return Err(opus_error(result));
+        }
+
+        let frame_samples = options.frame_samples();
+        // 40/60-ms-Pakete bestehen aus mehreren 20-ms-Frames
+        let packet_len = MAX_PACKET_PER_STREAM * streams as usize * frame_samples.div_ceil(960);
+        let mut packet_encoder = Self {
+            encoder,
+            mapping,
+            options: options.clone(),
+            frame_samples,
+            lookahead: lookahead.clamp(0, u16::MAX as i32) as u16,
+            pending: Vec::with_capacity(frame_samples * channels as usize * 2),
+            packet: vec![0; packet_len],
+        };
+        packet_encoder.apply_options()?;
+        Ok(packet_encoder)
+    }
+
+    fn ctl(&mut self, request: c_int, value: i32) -> Result<()> {
+        let result = unsafe { ffi::opus_multistream_encoder_ctl(self.encoder.0, request, value) };
+        if result != 0 {
+            return Err(opus_error(result));
+        }
+        Ok(())
+    }
+
+    fn apply_options(&mut self) -> Result<()> {
+        if let Some(bitrate) = self.options.bitrate {
+            self.ctl(OPUS_SET_BITRATE_REQUEST, bitrate as i32)?;
+        }
+        if let Some(complexity) = self.options.complexity {
+            self.ctl(OPUS_SET_COMPLEXITY_REQUEST, complexity as i32)?;
+        }
+        if let Some(mode) = self.options.bitrate_mode {
+            let (vbr, constrained) = match mode {
+                OpusBitrateMode::Vbr => (1, 0),
+                OpusBitrateMode::ConstrainedVbr => (1, 1),
+                OpusBitrateMode::Cbr => (0, 0),
+            };
+            self.ctl(OPUS_SET_VBR_REQUEST, vbr)?;
+            self.ctl(OPUS_SET_VBR_CONSTRAINT_REQUEST, constrained)?;
+        }
+        Ok(())
+    }
+
+    fn set_bitrate(&mut self, bitrate: u32) -> Result<()> {
+        self.ctl(OPUS_SET_BITRATE_REQUEST, bitrate as i32)?;
+        self.options.bitrate = Some(bitrate);
+        Ok(())
+    }
+
+    fn push(&mut self, pcm: &[i16]) -> Result<()> {
+        if !pcm.len().is_multiple_of(self.mapping.channels as usize) {
+            bail!(
+                "Opus encoder expected interleaved {}-channel samples, got {}",
+                self.mapping.channels,
+                pcm.len()
+            );
+        }
+        self.pending.extend_from_slice(pcm);
+        Ok(())
+    }
+
+    /// Rest mit Stille zum letzten Paket auffüllen.
+    fn pad(&mut self) {
+        let block = self.frame_samples * self.mapping.channels as usize;
+        if !self.pending.is_empty() {
+            let padded = self.pending.len().div_ceil(block) * block;
+            self.pending.resize(padded, 0);
+        }
+    }
+
+    /// Kodiert alle vollständigen Blöcke aus `pending`.
+    fn encode_packets(&mut self) -> Result<Vec<Vec<u8>>> {
+        let block = self.frame_samples * self.mapping.channels as usize;
+        let mut packets = Vec::new();
+        let mut offset = 0;
+        while self.pending.len() - offset >= block {
+            let len = unsafe {
+                ffi::opus_multistream_encode(
+                    self.encoder.0,
+                    self.pending[offset..].as_ptr(),
+                    self.frame_samples as c_int,
+                    self.packet.as_mut_ptr(),
+                    self.packet.len() as i32,
+                )
+            };
+            if len < 0 {
+                return Err(opus_error(len));
+            }
+            packets.push(self.packet[..len as usize].to_vec());
+            offset += block;
+        }
+        self.pending.drain(..offset);
+        Ok(packets)
+    }
+}
+
+pub struct OpusOggEncoder {
+    inner: PacketEncoder,
+    info: CodecInfo,
+    muxer: OggOpusMuxer,
+}
+
+impl OpusOggEncoder {
+    pub fn new(channels: u8) -> Result<Self> {
+        Self::with_options(channels, &OpusEncoderOptions::default())
+    }
+
+    pub fn with_options(channels: u8, options: &OpusEncoderOptions) -> Result<Self> {
+        let inner = PacketEncoder::new(channels, options)?;
+        Ok(Self {
+            info: CodecInfo {
+                kind: CodecKind::OpusOgg,
+                sample_rate: OPUS_SAMPLE_RATE,
+                channels,
+                container: ContainerKind::Ogg,
+            },
+            // Serial muss nur innerhalb eines physischen Streams eindeutig sein
+            muxer: OggOpusMuxer::new(
+                utc_ns_now() as u32,
+                &inner.mapping,
+                inner.lookahead,
+                OPUS_SAMPLE_RATE,
+            ),
+            inner,
+        })
+    }
+
+    pub fn mapping(&self) -> &ChannelMapping {
+        &self.inner.mapping
+    }
+
+    pub fn options(&self) -> &OpusEncoderOptions {
+        &self.inner.options
+    }
+
+    /// Gesamtbitrate über alle Streams (bit/s).
+    pub fn set_bitrate(&mut self, bitrate: u32) -> Result<()> {
+        self.inner.set_bitrate(bitrate)
+    }
+
+    fn frame(&self, pages: Vec<OggPage>) -> Vec<EncodedFrame> {
+        if pages.is_empty() {
+            return Vec::new();
+        }
+        vec![EncodedFrame {
+            payload: pages.iter().flat_map(|page| page.to_bytes()).collect(),
+            info: self.info.clone(),
+        }]
+    }
+}
+
+impl AudioCodec for OpusOggEncoder {
+    fn info(&self) -> &CodecInfo {
+        &self.info
+    }
+
+    fn encode(&mut self, pcm: &[i16]) -> Result<Vec<EncodedFrame>> {
+        self.inner.push(pcm)?;
+        let packets = self.inner.encode_packets()?;
+        let pages = self.muxer.pages(&packets, self.inner.frame_samples as u64);
+        Ok(self.frame(pages))
+    }
+
+    fn flush(&mut self) -> Result<Vec<EncodedFrame>> {
+        self.inner.pad();
+        let packets = self.inner.encode_packets()?;
+        let pages = self.muxer.finish(&packets, self.inner.frame_samples as u64);
+        Ok(self.frame(pages))
+    }
+}
+
+/// Rohe Opus-Pakete, je Paket ein `EncodedFrame` – fertig für einen
+/// RTP-Payload (RFC 7587) oder einen WebRTC-Track. Nur Mono/Stereo.
+pub struct OpusRtpEncoder {
+    inner: PacketEncoder,
+    info: CodecInfo,
+}
+
+impl OpusRtpEncoder {
+    pub fn new(channels: u8) -> Result<Self> {
+        Self::with_options(channels, &OpusEncoderOptions::default())
+    }
+
+    pub fn with_options(channels: u8, options: &OpusEncoderOptions) -> Result<Self> {
+        if !(1..=2).contains(&channels) {
+            bail!("Opus over RTP supports 1 or 2 channels, got {}", channels);
+        }
+        Ok(Self {
+            inner: PacketEncoder::new(channels, options)?,
+            info: CodecInfo {
+                kind: CodecKind::OpusWebRtc,
+                sample_rate: OPUS_SAMPLE_RATE,
+                channels,
+                container: ContainerKind::Rtp,
+            },
+        })
+    }
+
+    pub fn options(&self) -> &OpusEncoderOptions {
+        &self.inner.options
+    }
+
+    /// RTP-Timestamp-Schritt pro Paket (48-kHz-Takt).
+    pub fn packet_samples(&self) -> u32 {
+        self.inner.frame_samples as u32
+    }
+
+    pub fn set_bitrate(&mut self, bitrate: u32) -> Result<()> {
+        self.inner.set_bitrate(bitrate)
+    }
+
+    fn frames(&self, packets: Vec<Vec<u8>>) -> Vec<EncodedFrame> {
+        packets
+            .into_iter()
+            .map(|payload| EncodedFrame {
+                payload,
+                info: self.info.clone(),
+            })
+            .collect()
+    }
+}
+
+impl AudioCodec for OpusRtpEncoder {
+    fn info(&self) -> &CodecInfo {
+        &self.info
+    }
+
+    fn encode(&mut self, pcm: &[i16]) -> Result<Vec<EncodedFrame>> {
+        self.inner.push(pcm)?;
+        let packets = self.inner.encode_packets()?;
+        Ok(self.frames(packets))
+    }
+
+    fn flush(&mut self) -> Result<Vec<EncodedFrame>> {
+        self.inner.pad();
+        let packets = self.inner.encode_packets()?;
+        Ok(self.frames(packets))
+    }
+}
@@ -116,6 +116,7 @@ impl AudioDecoder for PcmPassthroughDecoder {
             samples: pcm,
             sample_rate: PCM_SAMPLE_RATE,
             channels: PCM_CHANNELS,
+            metadata: Default::default(),
         }))
     }
 }
@@ -1,8 +1,17 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 
-use anyhow::{bail, Context};
+use anyhow::{anyhow, bail, Context};
+
+use crate::core::timezone::TimeZone;
+
+pub mod options;
+pub mod revision;
+pub mod units;
+
+pub use revision::{config_revisions, ConfigRevisions, ObjectConflict};
+pub use units::ConfigValues;
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ProducerConfig {
@@ -51,28 +60,633 @@ pub struct FlowConfig {
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct MonitoringConfig {
+    /// 0 = vom System gewählter Port (steht nach dem Start im Status/Log)
     pub http_port: u16,
+    /// Zusätzliche/abweichende Listener; leer = `0.0.0.0:<http_port>` ohne Auth
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub binds: Vec<BindConfig>,
+}
+
+/// `[[monitoring.binds]]`: Listener-Adresse mit eigener Zugriffsregel.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct BindConfig {
+    /// z. B. "127.0.0.1:8087" oder "10.20.0.5:8087"
+    pub address: String,
+    /// Bearer-Token (`Authorization: Bearer …` oder `?token=`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    /// Nur GET/HEAD (Dashboards, Monitoring)
+    #[serde(default)]
+    pub read_only: bool,
+}
+
+impl BindConfig {
+    pub fn open(address: &str) -> Self {
+        Self {
+            address: address.to_string(),
+            token: None,
+            read_only: false,
+        }
+    }
+}
+
+impl MonitoringConfig {
+    /// Wirksame Listener: `binds` oder der klassische Port auf allen Interfaces.
+    pub fn effective_binds(&self) -> Vec<BindConfig> {
+        if self.binds.is_empty() {
+            vec![BindConfig::open(&format!("0.0.0.0:{}", self.http_port))]
+        } else {
+            self.binds.clone()
+        }
+    }
+}
+
+/// `strict` lehnt unbekannte Felder ab, `compat` ignoriert sie mit Warnung.
+/// Fehlt der Schlüssel, gilt `compat` (bestehende Configs); neue Configs
+/// (`Config::default()`) werden mit `strict` gespeichert.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigMode {
+    Strict,
+    #[default]
+    Compat,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Config {
+    #[serde(default)]
+    pub config_mode: ConfigMode,
     pub node_name: String,
+    /// Zeitzone für Zeitpläne und Aufnahmen: IANA-Name ("Europe/Berlin"),
+    /// POSIX-TZ oder Offset ("+01:00"); Standard UTC
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
     pub producers: HashMap<String, ProducerConfig>,
     pub processors: HashMap<String, ProcessorConfig>,
     pub consumers: HashMap<String, ConsumerConfig>,
     pub flows: HashMap<String, FlowConfig>,
     #[serde(default)]
     pub monitoring: MonitoringConfig,
+    /// Lua-Regeln (Cargo-Feature `lua`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rules: Option<RulesConfig>,
+    /// Failover-Gruppen, als Flow-Input wie ein Producer referenzierbar
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub failover: HashMap<String, FailoverConfig>,
+    /// Startverhalten (Readiness-Gate der Consumer)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub startup: Option<StartupConfig>,
+    /// Zeitgesteuertes Aktivieren/Deaktivieren von Producern und Flows
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub schedules: HashMap<String, ScheduleConfig>,
+    /// Ausgehende HTTP-Anfragen (Probe, Webhooks)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_client: Option<HttpClientConfig>,
+    /// Ablage für Peak-Historie, Events und Aufnahme-Index
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage: Option<StorageConfig>,
+    /// Standard-Analyzer, die jeder Flow automatisch bekommt
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub analyzers: Option<AnalyzerDefaultsConfig>,
+    /// Audio-Events und Health-Snapshots an einen MQTT-Broker
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mqtt: Option<MqttConfig>,
+    /// Zusammenfassen wiederholter Fehler-Events
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub events: Option<EventsConfig>,
+    /// Verhalten beim Anwenden einer geänderten Config
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reload: Option<ReloadConfig>,
+    /// Rechner-Telemetrie (Load, CPU, Temperatur, Platten) in /metrics
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub telemetry: Option<TelemetryConfig>,
+}
+
+/// `[analyzers]`: Analyzer am Output jedes Flows, ohne sie pro Flow
+/// eintragen zu müssen. Fehlt der Abschnitt, gelten Peak und Stille.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct AnalyzerDefaultsConfig {
+    /// `false` schaltet die Standard-Analyzer ab
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    /// z. B. ["peak", "silence", "lufs"]; Standard Peak und Stille
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kinds: Option<Vec<String>>,
+    /// Messtakt, z. B. "100ms"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interval: Option<String>,
+    /// `true` hängt einen `level_meter` ans Ende jedes Flows, dessen
+    /// Output-Pegel als `<flow>:output` in `/api/peaks` erscheint
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_meter: Option<bool>,
+}
+
+impl AnalyzerDefaultsConfig {
+    pub fn taps(&self) -> anyhow::Result<Vec<crate::core::AnalyzerTapConfig>> {
+        use crate::core::analyzer_taps::{DEFAULT_ANALYZER_INTERVAL, DEFAULT_ANALYZER_KINDS};
+        use crate::core::{AnalyzerKind, AnalyzerTapConfig};
+
+        if self.enabled == Some(false) {
+            return Ok(Vec::new());
+        }
+        let kinds = match &self.kinds {
+            Some(kinds) => kinds
+                .iter()
+                .map(|kind| {
+                    AnalyzerKind::parse(kind).ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "analyzers.kinds: unknown kind '{}' (peak, lufs, spectrum or silence)",
+                            kind
+                        )
+                    })
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?,
+            None => DEFAULT_ANALYZER_KINDS.to_vec(),
+        };
+        let interval = match self.interval.as_deref() {
+            Some(text) => units::parse_duration(text)
+                .map_err(|e| anyhow::anyhow!("analyzers.interval invalid: {}", e))?,
+            None => DEFAULT_ANALYZER_INTERVAL,
+        };
+        AnalyzerTapConfig::defaults(&kinds, interval)
+    }
+}
+
+/// `[http_client]`: Timeouts, Wiederholungen, User-Agent und Proxy für
+/// ausgehende HTTP-Anfragen.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct HttpClientConfig {
+    /// z. B. "5s"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connect_timeout: Option<String>,
+    /// z. B. "10s"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub read_timeout: Option<String>,
+    /// Zusätzliche Versuche nach dem ersten
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retries: Option<u32>,
+    /// Wartezeit vor dem ersten Wiederholen, danach verdoppelt, z. B. "500ms"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_backoff: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+    /// `http://[user:pass@]host:port`; fehlt = `http_proxy` aus der Umgebung
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+    /// Hosts oder Domain-Suffixe (".lan") ohne Proxy
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub no_proxy: Vec<String>,
+}
+
+impl HttpClientConfig {
+    pub fn settings(&self) -> anyhow::Result<crate::core::http_client::HttpSettings> {
+        use crate::core::http_client::{HttpProxy, HttpSettings};
+        let defaults = HttpSettings::default();
+        let duration = |key: &str, value: &Option<String>, default: std::time::Duration| {
+            match value.as_deref() {
+                Some(text) => units::parse_duration(text)
+                    .map_err(|e| anyhow::anyhow!("http_client.{} invalid: {}", key, e)),
+                None => Ok(default),
+            }
+        };
+        let settings = HttpSettings {
+            connect_timeout: duration("connect_timeout", &self.connect_timeout, defaults.connect_timeout)?,
+            read_timeout: duration("read_timeout", &self.read_timeout, defaults.read_timeout)?,
+            retries: self.retries.unwrap_or(defaults.retries),
+            retry_backoff: duration("retry_backoff", &self.retry_backoff, defaults.retry_backoff)?,
+            user_agent: self.user_agent.clone().unwrap_or(defaults.user_agent),
+            proxy: self
+                .proxy
+                .as_deref()
+                .map(|proxy| HttpProxy::parse(proxy).context("http_client.proxy invalid"))
+                .transpose()?,
+            no_proxy: self.no_proxy.clone(),
+        };
+        for (key, value) in [
+            ("connect_timeout", settings.connect_timeout),
+            ("read_timeout", settings.read_timeout),
+        ] {
+            if value < std::time::Duration::from_millis(100) || value > std::time::Duration::from_secs(300) {
+                bail!("http_client.{} must be between 100ms and 300s", key);
+            }
+        }
+        if settings.retries > 10 {
+            bail!("http_client.retries must be at most 10");
+        }
+        if settings.retry_backoff > std::time::Duration::from_secs(60) {
+            bail!("http_client.retry_backoff must be at most 60s");
+        }
+        if settings.user_agent.trim().is_empty() || settings.user_agent.contains(['\r', '\n']) {
+            bail!("http_client.user_agent must be a single non-empty line");
+        }
+        Ok(settings)
+    }
+}
+
+/// `[storage]`: Backend für Historie und Aufnahme-Metadaten (`memory`,
+/// `sqlite`, `influx`).
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct StorageConfig {
+    /// Standard "memory"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backend: Option<String>,
+    /// Datenbankdatei (sqlite)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// Aufbewahrung von Peaks und Events (memory, sqlite), z. B. "7d"; Standard 24 h
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retention: Option<String>,
+    /// z. B. "http://influx:8086" (influx)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub database: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+}
+
+impl StorageConfig {
+    pub fn backend(&self) -> &str {
+        self.backend.as_deref().unwrap_or("memory")
+    }
+
+    pub fn retention(&self) -> anyhow::Result<std::time::Duration> {
+        match self.retention.as_deref() {
+            Some(text) => {
+                let retention = units::parse_duration(text)
+                    .map_err(|e| anyhow::anyhow!("storage.retention invalid: {}", e))?;
+                if retention < std::time::Duration::from_secs(60) {
+                    bail!("storage.retention must be at least 1m");
+                }
+                Ok(retention)
+            }
+            None => Ok(crate::storage::DEFAULT_RETENTION),
+        }
+    }
+
+    pub fn validate(&self) -> anyhow::Result<()> {
+        self.retention()?;
+        match self.backend() {
+            "memory" => {}
+            "sqlite" => {
+                if !cfg!(feature = "sqlite") {
+                    bail!("storage.backend 'sqlite' needs the 'sqlite' feature");
+                }
+                if self.path.as_deref().is_none_or(|path| path.trim().is_empty()) {
+                    bail!("storage.path is required for the sqlite backend");
+                }
+            }
+            "influx" => {
+                crate::storage::influx::InfluxOptions::from_config(self)?;
+            }
+            other => bail!("storage.backend '{}' unknown (memory, sqlite, influx)", other),
+        }
+        Ok(())
+    }
+}
+
+/// `[schedules.<name>]`: Cron-Zeitplan für einen Producer, Flow oder eine
+/// ausgelöste Aufnahme (`consumer`), in der Zeitzone des Flows bzw. des Nodes.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ScheduleConfig {
+    /// z. B. "0 18 * * mon-fri" oder "@daily"
+    pub cron: String,
+    /// "enable" oder "disable"
+    pub action: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub producer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flow: Option<String>,
+    /// FileConsumer mit `config.pre_roll`: enable startet, disable beendet die Aufnahme
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub consumer: Option<String>,
+    #[serde(default = "default_schedule_enabled")]
+    pub enabled: bool,
+}
+
+fn default_schedule_enabled() -> bool {
+    true
+}
+
+/// `[startup]`: Consumer starten erst, wenn die Inputs ihres Flows Audio liefern.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct StartupConfig {
+    /// Maximale Wartezeit, z. B. "3s"; "0s" = nicht warten
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub readiness_timeout: Option<String>,
+    /// Producer/Flows, die gleichzeitig gestartet/gestoppt werden; 1 = nacheinander
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workers: Option<usize>,
+    /// Instabile Starts in Folge bis zum Safe Mode; 0 = nie
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_crashes: Option<u32>,
+    /// Laufzeit, ab der ein Start als stabil gilt, z. B. "60s"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stable_after: Option<String>,
+    /// Pfad der Crash-Zähler-Datei
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crash_file: Option<String>,
+    /// Verzeichnis für Laufzeitzustand über Neustarts; fehlt = keine Persistenz
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_dir: Option<String>,
+    /// Ältere Zustände werden verworfen, z. B. "5m"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_max_age: Option<String>,
+    /// Abstand der Snapshots, z. B. "5s"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_interval: Option<String>,
+}
+
+impl StartupConfig {
+    pub fn readiness_timeout(&self) -> anyhow::Result<Option<std::time::Duration>> {
+        self.readiness_timeout
+            .as_deref()
+            .map(|text| {
+                units::parse_duration(text)
+                    .map_err(|e| anyhow::anyhow!("startup.readiness_timeout invalid: {}", e))
+            })
+            .transpose()
+    }
+
+    pub fn stable_after(&self) -> anyhow::Result<std::time::Duration> {
+        match self.stable_after.as_deref() {
+            Some(text) => units::parse_duration(text)
+                .map_err(|e| anyhow::anyhow!("startup.stable_after invalid: {}", e)),
+            None => Ok(crate::core::safe_mode::DEFAULT_STABLE_AFTER),
+        }
+    }
+
+    pub fn state_max_age(&self) -> anyhow::Result<std::time::Duration> {
+        match self.state_max_age.as_deref() {
+            Some(text) => units::parse_duration(text)
+                .map_err(|e| anyhow::anyhow!("startup.state_max_age invalid: {}", e)),
+            None => Ok(crate::core::state_store::DEFAULT_MAX_AGE),
+        }
+    }
+
+    pub fn state_interval(&self) -> anyhow::Result<std::time::Duration> {
+        let interval = match self.state_interval.as_deref() {
+            Some(text) => units::parse_duration(text)
+                .map_err(|e| anyhow::anyhow!("startup.state_interval invalid: {}", e))?,
+            None => crate::core::state_store::DEFAULT_INTERVAL,
+        };
+        if interval < std::time::Duration::from_millis(100) {
+            bail!("startup.state_interval must be at least 100ms");
+        }
+        Ok(interval)
+    }
+}
+
+/// `[failover.<name>]`: priorisierte Producer-Liste (erster = primär).
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FailoverConfig {
+    pub producers: Vec<String>,
+    /// Stille/Ausfall bis zur Umschaltung, z. B. "5s"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<String>,
+    /// Stabile Zeit vor dem Zurückschalten, z. B. "30s"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recover_after: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub silence_threshold_db: Option<f32>,
+}
+
+/// `[rules]`: Lua-Skripte für Event-/Timer-Automation.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RulesConfig {
+    pub scripts: Vec<String>,
+    /// Maximale Laufzeit eines Handler-Aufrufs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_runtime_ms: Option<u64>,
+    /// Erlaubte URL-Präfixe für `airlift.webhook` (leer = keine Webhooks)
+    #[serde(default)]
+    pub webhook_allow: Vec<String>,
+}
+
+/// `[events]`: Event-Bus des Nodes.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct EventsConfig {
+    /// Wiederholte Warnungen/Fehler einer Komponente werden so lange nur
+    /// gezählt und dann zusammengefasst, z. B. "30s" (Standard); "0s" = aus
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dedup_window: Option<String>,
+}
+
+impl EventsConfig {
+    pub fn dedup_window(&self) -> anyhow::Result<std::time::Duration> {
+        match self.dedup_window.as_deref() {
+            Some(text) => units::parse_duration(text)
+                .map_err(|e| anyhow::anyhow!("events.dedup_window invalid: {}", e)),
+            None => Ok(crate::core::event_dedup::DEFAULT_DEDUP_WINDOW),
+        }
+    }
+}
+
+/// `[reload]`: Anwenden einer geänderten Config zur Laufzeit.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct ReloadConfig {
+    /// So lange bedienen Ausgänge der alten Config ihre verbundenen Zuhörer
+    /// weiter, z. B. "30s" (Standard); "0s" = sofort trennen
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub drain_grace: Option<String>,
+}
+
+impl ReloadConfig {
+    pub fn drain_grace(&self) -> anyhow::Result<std::time::Duration> {
+        match self.drain_grace.as_deref() {
+            Some(text) => units::parse_duration(text)
+                .map_err(|e| anyhow::anyhow!("reload.drain_grace invalid: {}", e)),
+            None => Ok(crate::core::drain::DEFAULT_DRAIN_GRACE),
+        }
+    }
+}
+
+/// `[telemetry]`: Abtastung des Rechners für /metrics und den Status.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct TelemetryConfig {
+    /// `false` schaltet die Abtastung ab
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    /// z. B. "5s" (Standard)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interval: Option<String>,
+    /// Weitere Pfade, deren Dateisystem überwacht wird; Aufnahme-Verzeichnisse
+    /// sind immer dabei
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub paths: Vec<String>,
+    /// SMART-Health per `smartctl` (Standard aus)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub smart: Option<bool>,
+    /// z. B. "10m" (Standard)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub smart_interval: Option<String>,
+}
+
+impl TelemetryConfig {
+    pub fn enabled(&self) -> bool {
+        self.enabled.unwrap_or(true)
+    }
+
+    pub fn settings(&self) -> anyhow::Result<crate::core::host_telemetry::TelemetrySettings> {
+        use crate::core::host_telemetry::TelemetrySettings;
+        let defaults = TelemetrySettings::default();
+        let duration = |key: &str, value: &Option<String>, default: std::time::Duration| {
+            match value.as_deref() {
+                Some(text) => units::parse_duration(text)
+                    .map_err(|e| anyhow::anyhow!("telemetry.{} invalid: {}", key, e)),
+                None => Ok(default),
+            }
+        };
+        let settings = TelemetrySettings {
+            interval: duration("interval", &self.interval, defaults.interval)?,
+            paths: self.paths.iter().map(std::path::PathBuf::from).collect(),
+            smart: self.smart.unwrap_or(defaults.smart),
+            smart_interval: duration("smart_interval", &self.smart_interval, defaults.smart_interval)?,
+        };
+        if settings.interval < std::time::Duration::from_secs(1) || settings.interval > std::time::Duration::from_secs(3600) {
+            bail!("telemetry.interval must be between 1s and 1h");
+        }
+        if settings.smart_interval < std::time::Duration::from_secs(60) {
+            bail!("telemetry.smart_interval must be at least 60s");
+        }
+        if self.paths.iter().any(|path| path.trim().is_empty()) {
+            bail!("telemetry.paths must not contain empty entries");
+        }
+        Ok(settings)
+    }
+}
+
+/// `[mqtt]`: Stille, Pegel, Lautheit und Health an einen MQTT-Broker
+/// (siehe `crate::mqtt`).
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct MqttConfig {
+    /// `mqtt://host:1883`
+    pub broker: String,
+    /// Standard "airlift-<node_name>"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    /// Standard "airlift/<node_name>"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub topic_prefix: Option<String>,
+    /// 0, 1 oder 2; Standard 0
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub qos: Option<u8>,
+    /// "silence", "loudness", "peak" und/oder Event-Typen wie "ProducerFailover";
+    /// leer = die ersten drei
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<String>,
+    /// z. B. "10s" (Standard), "0s" schaltet die Snapshots ab
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub health_interval: Option<String>,
+    /// Standard "30s"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keepalive: Option<String>,
 }
 
 impl Config {
     pub fn load(path: &str) -> anyhow::Result<Self> {
         let content = fs::read_to_string(path)?;
-        let config: Self = toml::from_str(&content)?;
+        let config = Self::from_toml(&content).with_context(|| format!("failed to parse '{}'", path))?;
         config.validate().context("config validation failed")?;
         Ok(config)
     }
 
+    /// Parst TOML und prüft auf unbekannte Felder (je nach `config_mode`).
+    pub fn from_toml(content: &str) -> anyhow::Result<Self> {
+        let mut unknown = Vec::new();
+        let config: Self = serde_ignored::deserialize(toml::Deserializer::new(content), |path| {
+            unknown.push(path.to_string())
+        })?;
+        unknown.extend(config.unknown_component_options());
+        report_unknown_fields(unknown, config.config_mode)?;
+        Ok(config)
+    }
+
+    /// `config`-Schlüssel, die der Modultyp nicht kennt (`consumers.<name>.config.<key>`,
+    /// siehe `options::known_options`).
+    fn unknown_component_options(&self) -> Vec<String> {
+        let mut modules: Vec<(&str, &str, &str, &HashMap<String, serde_json::Value>)> = Vec::new();
+        for (name, cfg) in &self.producers {
+            modules.push(("producer", name, &cfg.producer_type, &cfg.config));
+        }
+        for (name, cfg) in &self.processors {
+            modules.push(("processor", name, &cfg.processor_type, &cfg.config));
+        }
+        for (name, cfg) in &self.consumers {
+            modules.push(("consumer", name, &cfg.consumer_type, &cfg.config));
+        }
+        for (name, cfg) in &self.flows {
+            modules.push(("flow", name, "", &cfg.config));
+        }
+
+        let mut unknown = Vec::new();
+        for (kind, name, module_type, config) in modules {
+            let Some(known) = options::known_options(kind, module_type) else {
+                continue;
+            };
+            for key in config.keys().filter(|key| !known.contains(&key.as_str())) {
+                unknown.push(format!("{}s.{}.config.{}", kind, name, key));
+            }
+        }
+        unknown
+    }
+
+    /// Zeitzone des Nodes (`timezone`), Standard UTC.
+    pub fn timezone(&self) -> anyhow::Result<TimeZone> {
+        match &self.timezone {
+            Some(spec) => TimeZone::parse(spec).context("timezone invalid"),
+            None => Ok(TimeZone::utc()),
+        }
+    }
+
+    /// Standard-Analyzer aus `[analyzers]` (ohne Abschnitt: Peak und Stille).
+    pub fn default_analyzer_taps(&self) -> anyhow::Result<Vec<crate::core::AnalyzerTapConfig>> {
+        self.analyzers.clone().unwrap_or_default().taps()
+    }
+
+    /// `[analyzers] output_meter`, Standard aus.
+    pub fn output_meter(&self) -> bool {
+        self.analyzers
+            .as_ref()
+            .and_then(|analyzers| analyzers.output_meter)
+            .unwrap_or(false)
+    }
+
+    /// `config.labels` aller Flows und Producer.
+    pub fn labels(&self) -> anyhow::Result<crate::core::labels::LabelSet> {
+        use crate::core::labels::parse_labels;
+        let mut labels = crate::core::labels::LabelSet::default();
+        for (name, flow) in &self.flows {
+            labels.flows.insert(name.clone(), parse_labels("flow", name, &flow.config)?);
+        }
+        for (name, producer) in &self.producers {
+            labels
+                .producers
+                .insert(name.clone(), parse_labels("producer", name, &producer.config)?);
+        }
+        Ok(labels)
+    }
+
+    /// `flows.<name>.config.timezone`, sonst die Zeitzone des Nodes.
+    pub fn flow_timezone(&self, flow: &str) -> anyhow::Result<TimeZone> {
+        let spec = self
+            .flows
+            .get(flow)
+            .and_then(|cfg| cfg.config.get("timezone"));
+        match spec {
+            Some(serde_json::Value::String(spec)) => TimeZone::parse(spec)
+                .with_context(|| format!("flow '{}': config.timezone invalid", flow)),
+            Some(other) => bail!("flow '{}': config.timezone must be a string, got {}", flow, other),
+            None => self.timezone(),
+        }
+    }
+
     pub fn save(&self, path: &str) -> anyhow::Result<()> {
         let content = toml::to_string_pretty(self)?;
         fs::write(path, content)?;
@@ -96,11 +710,135 @@ impl Config {
             consumer.validate(name)?;
         }
 
+        for (name, producer) in &self.producers {
+            if let Some(slot) = producer.standby_for() {
+                match self.producers.get(slot) {
+                    None => bail!("producer '{}' is standby for missing producer '{}'", name, slot),
+                    Some(primary) if primary.standby_for().is_some() => bail!(
+                        "producer '{}' is standby for '{}', which is itself a standby producer",
+                        name,
+                        slot
+                    ),
+                    Some(_) => {}
+                }
+            }
+        }
+
+        for (name, group) in &self.failover {
+            if self.producers.contains_key(name) {
+                bail!("failover group '{}' has the same name as a producer", name);
+            }
+            if group.producers.len() < 2 {
+                bail!("failover group '{}' needs at least two producers", name);
+            }
+            for (index, member) in group.producers.iter().enumerate() {
+                match self.producers.get(member) {
+                    None => bail!("failover group '{}' references missing producer '{}'", name, member),
+                    Some(producer) if producer.standby_for().is_some() => bail!(
+                        "failover group '{}' references standby producer '{}'",
+                        name,
+                        member
+                    ),
+                    Some(_) => {}
+                }
+                if group.producers[..index].contains(member) {
+                    bail!("failover group '{}' lists producer '{}' twice", name, member);
+                }
+            }
+            for (key, value) in [("timeout", &group.timeout), ("recover_after", &group.recover_after)] {
+                if let Some(text) = value {
+                    units::parse_duration(text)
+                        .with_context(|| format!("failover '{}': {} invalid", name, key))?;
+                }
+            }
+        }
+
+        if let Some(http_client) = &self.http_client {
+            http_client.settings()?;
+        }
+        if let Some(storage) = &self.storage {
+            storage.validate()?;
+        }
+        if let Some(mqtt) = &self.mqtt {
+            crate::mqtt::MqttOptions::from_config(mqtt, &self.node_name)?;
+        }
+        if let Some(events) = &self.events {
+            events.dedup_window()?;
+        }
+        if let Some(reload) = &self.reload {
+            reload.drain_grace()?;
+        }
+        if let Some(telemetry) = &self.telemetry {
+            telemetry.settings()?;
+        }
+        self.default_analyzer_taps()?;
+        self.labels()?;
+
+        if let Some(startup) = &self.startup {
+            startup.readiness_timeout()?;
+            startup.stable_after()?;
+            startup.state_max_age()?;
+            startup.state_interval()?;
+            if let Some(workers) = startup.workers {
+                if !(1..=crate::core::parallel::MAX_STARTUP_WORKERS).contains(&workers) {
+                    bail!(
+                        "startup.workers = {} out of range (1..={})",
+                        workers,
+                        crate::core::parallel::MAX_STARTUP_WORKERS
+                    );
+                }
+            }
+        }
+
+        self.timezone()?;
+        for name in self.flows.keys() {
+            self.flow_timezone(name)?;
+        }
+
+        for (name, schedule) in &self.schedules {
+            let entry = crate::core::scheduler::ScheduleEntry::from_config(name, schedule)?;
+            match &entry.target {
+                crate::core::scheduler::ScheduleTarget::Producer(producer) => {
+                    if !self.producers.contains_key(producer) {
+                        bail!("schedule '{}' references missing producer '{}'", name, producer);
+                    }
+                }
+                crate::core::scheduler::ScheduleTarget::Flow(flow) => {
+                    if !self.flows.contains_key(flow) {
+                        bail!("schedule '{}' references missing flow '{}'", name, flow);
+                    }
+                }
+                crate::core::scheduler::ScheduleTarget::Consumer(consumer) => {
+                    let Some(consumer_cfg) = self.consumers.get(consumer) else {
+                        bail!("schedule '{}' references missing consumer '{}'", name, consumer);
+                    };
+                    if !consumer_cfg.config.contains_key("pre_roll") {
+                        bail!(
+                            "schedule '{}': consumer '{}' records continuously (no config.pre_roll)",
+                            name,
+                            consumer
+                        );
+                    }
+                }
+            }
+        }
+
         for (name, flow) in &self.flows {
             flow.validate(name)?;
             for input in &flow.inputs {
-                if !self.producers.contains_key(input) {
+                if self.failover.contains_key(input) {
+                    continue;
+                }
+                let Some(producer) = self.producers.get(input) else {
                     bail!("flow '{}' references missing producer '{}'", name, input);
+                };
+                if let Some(slot) = producer.standby_for() {
+                    bail!(
+                        "flow '{}' references standby producer '{}'; use '{}' instead",
+                        name,
+                        input,
+                        slot
+                    );
                 }
             }
             for processor in &flow.processors {
@@ -119,8 +857,147 @@ impl Config {
             }
         }
 
-        if self.monitoring.http_port == 0 {
-            bail!("monitoring.http_port must be > 0");
+        // Fanout-Ziele lesen nur über ihren Fanout, nie zusätzlich direkt
+        let mut fanout_targets = HashSet::new();
+        for (name, consumer) in &self.consumers {
+            if consumer.consumer_type != "fanout" {
+                continue;
+            }
+            let targets = consumer.config.get("targets").and_then(|v| v.as_array());
+            for target in targets.into_iter().flatten().filter_map(|v| v.as_str()) {
+                match self.consumers.get(target) {
+                    None => bail!("fanout '{}' references missing consumer '{}'", name, target),
+                    Some(child) if child.consumer_type == "fanout" => {
+                        bail!("fanout '{}' must not target fanout '{}'", name, target)
+                    }
+                    Some(_) => {}
+                }
+                if !fanout_targets.insert(target) {
+                    bail!(
+                        "consumer '{}' is the target of more than one fanout",
+                        target
+                    );
+                }
+                if let Some((flow, _)) = self
+                    .flows
+                    .iter()
+                    .find(|(_, flow)| flow.outputs.iter().any(|o| o == target))
+                {
+                    bail!(
+                        "consumer '{}' is a target of fanout '{}' and must not be an output of flow '{}'",
+                        target,
+                        name,
+                        flow
+                    );
+                }
+            }
+        }
+
+        // Ersatzziele laufen nur über ihren primären Consumer
+        let mut backups = HashSet::new();
+        for (name, consumer) in &self.consumers {
+            let Some(backup) = consumer.config.get("backup") else {
+                continue;
+            };
+            let backup = backup
+                .as_str()
+                .ok_or_else(|| anyhow!("consumer '{}': config.backup must name a consumer", name))?;
+            match self.consumers.get(backup) {
+                None => bail!("consumer '{}' references missing backup '{}'", name, backup),
+                Some(child) if child.config.contains_key("backup") => {
+                    bail!("consumer '{}': backup '{}' must not have a backup itself", name, backup)
+                }
+                Some(_) => {}
+            }
+            if !backups.insert(backup) {
+                bail!("consumer '{}' is the backup of more than one consumer", backup);
+            }
+            if fanout_targets.contains(backup) {
+                bail!("consumer '{}' is a fanout target and must not be a backup", backup);
+            }
+            if let Some((flow, _)) = self
+                .flows
+                .iter()
+                .find(|(_, flow)| flow.outputs.iter().any(|o| o == backup))
+            {
+                bail!(
+                    "consumer '{}' is the backup of '{}' and must not be an output of flow '{}'",
+                    backup,
+                    name,
+                    flow
+                );
+            }
+        }
+
+        // Spiegelziele ebenso, und nur für einen Consumer
+        let mut mirrors = HashSet::new();
+        for (name, consumer) in &self.consumers {
+            let Some(mirror) = consumer.config.get("mirror") else {
+                continue;
+            };
+            let mirror = mirror
+                .as_str()
+                .ok_or_else(|| anyhow!("consumer '{}': config.mirror must name a consumer", name))?;
+            match self.consumers.get(mirror) {
+                None => bail!("consumer '{}' references missing mirror '{}'", name, mirror),
+                Some(child) if child.config.contains_key("mirror") => {
+                    bail!("consumer '{}': mirror '{}' must not have a mirror itself", name, mirror)
+                }
+                Some(_) => {}
+            }
+            if !mirrors.insert(mirror) {
+                bail!("consumer '{}' is the mirror of more than one consumer", mirror);
+            }
+            if fanout_targets.contains(mirror) || backups.contains(mirror) {
+                bail!(
+                    "consumer '{}' is a fanout target or backup and must not be a mirror",
+                    mirror
+                );
+            }
+            if let Some((flow, _)) = self
+                .flows
+                .iter()
+                .find(|(_, flow)| flow.outputs.iter().any(|o| o == mirror))
+            {
+                bail!(
+                    "consumer '{}' is the mirror of '{}' and must not be an output of flow '{}'",
+                    mirror,
+                    name,
+                    flow
+                );
+            }
+        }
+
+        for (index, bind) in self.monitoring.binds.iter().enumerate() {
+            let port = bind
+                .address
+                .rsplit_once(':')
+                .and_then(|(host, port)| (!host.is_empty()).then_some(port));
+            if port.and_then(|port| port.parse::<u16>().ok()).is_none() {
+                bail!(
+                    "monitoring.binds[{}].address '{}' must be <host>:<port>",
+                    index,
+                    bind.address
+                );
+            }
+            if self.monitoring.binds[..index]
+                .iter()
+                .any(|other| other.address == bind.address)
+            {
+                bail!("monitoring.binds lists '{}' twice", bind.address);
+            }
+            if bind.token.as_deref().is_some_and(|token| token.trim().is_empty()) {
+                bail!("monitoring.binds[{}].token must not be empty", index);
+            }
+        }
+
+        if let Some(rules) = &self.rules {
+            if rules.scripts.is_empty() {
+                bail!("rules.scripts must not be empty");
+            }
+            if rules.max_runtime_ms == Some(0) {
+                bail!("rules.max_runtime_ms must be > 0");
+            }
         }
 
         Ok(())
@@ -130,20 +1007,62 @@ impl Config {
         let mut next = self.clone();
         patch.apply_to(&mut next)?;
         next.validate()?;
+        // Bestehende Schlüssel wurden schon beim Laden gemeldet
+        let existing = self.unknown_component_options();
+        let added = next
+            .unknown_component_options()
+            .into_iter()
+            .filter(|field| !existing.contains(field))
+            .collect();
+        report_unknown_fields(added, next.config_mode)?;
         *self = next;
         Ok(())
     }
 }
 
+/// Meldet unbekannte Felder: `strict` lehnt ab, `compat` warnt je Feld.
+fn report_unknown_fields(mut unknown: Vec<String>, mode: ConfigMode) -> anyhow::Result<()> {
+    if unknown.is_empty() {
+        return Ok(());
+    }
+    unknown.sort();
+
+    match mode {
+        ConfigMode::Strict => bail!(
+            "unknown config field(s): {} (set config_mode = \"compat\" to ignore)",
+            unknown.join(", ")
+        ),
+        ConfigMode::Compat => {
+            for field in &unknown {
+                log::warn!("Ignoring unknown config field '{}'", field);
+            }
+            Ok(())
+        }
+    }
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
+            config_mode: ConfigMode::Strict,
             node_name: "airlift-node".to_string(),
+            timezone: None,
             producers: HashMap::new(),
             processors: HashMap::new(),
             consumers: HashMap::new(),
             flows: HashMap::new(),
             monitoring: MonitoringConfig::default(),
+            rules: None,
+            failover: HashMap::new(),
+            startup: None,
+            schedules: HashMap::new(),
+            http_client: None,
+            storage: None,
+            analyzers: None,
+            mqtt: None,
+            events: None,
+            reload: None,
+            telemetry: None,
         }
     }
 }
@@ -164,6 +1083,12 @@ impl Default for ProducerConfig {
 }
 
 impl ProducerConfig {
+    /// Warm-Standby: Producer wird nicht gestartet, sondern kann per Control
+    /// anstelle des Producers `standby_for` aktiviert werden.
+    pub fn standby_for(&self) -> Option<&str> {
+        self.config.get("standby_for").and_then(|v| v.as_str())
+    }
+
     fn validate(&self, name: &str) -> anyhow::Result<()> {
         if name.trim().is_empty() {
             bail!("producer name must not be empty");
@@ -255,12 +1180,16 @@ impl FlowConfig {
 
 impl Default for MonitoringConfig {
     fn default() -> Self {
-        Self { http_port: 8087 }
+        Self {
+            http_port: 8087,
+            binds: Vec::new(),
+        }
     }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
 pub struct ConfigPatch {
+    pub config_mode: Option<ConfigMode>,
     pub node_name: Option<String>,
     pub producers: Option<HashMap<String, ProducerConfigPatch>>,
     pub processors: Option<HashMap<String, ProcessorConfigPatch>>,
@@ -270,7 +1199,50 @@ pub struct ConfigPatch {
 }
 
 impl ConfigPatch {
+    /// Parst einen JSON-Patch; unbekannte Felder je nach `mode` ablehnen oder warnen.
+    pub fn from_json(body: &str, mode: ConfigMode) -> anyhow::Result<Self> {
+        let mut unknown = Vec::new();
+        let mut deserializer = serde_json::Deserializer::from_str(body);
+        let patch: Self = serde_ignored::deserialize(&mut deserializer, |path| {
+            unknown.push(path.to_string())
+        })?;
+        deserializer.end()?;
+        report_unknown_fields(unknown, mode)?;
+        Ok(patch)
+    }
+
+    /// Vom Patch berührte Objekte (`producers.<name>`, `flows.<name>`, `node_name`, ...),
+    /// sortiert; Grundlage der Konfliktprüfung in `ConfigRevisions`.
+    pub fn touched_objects(&self) -> Vec<String> {
+        let mut objects = Vec::new();
+        if self.config_mode.is_some() {
+            objects.push("config_mode".to_string());
+        }
+        if self.node_name.is_some() {
+            objects.push("node_name".to_string());
+        }
+        let sections = [
+            ("producers", self.producers.as_ref().map(|m| m.keys().collect::<Vec<_>>())),
+            ("processors", self.processors.as_ref().map(|m| m.keys().collect())),
+            ("consumers", self.consumers.as_ref().map(|m| m.keys().collect())),
+            ("flows", self.flows.as_ref().map(|m| m.keys().collect())),
+        ];
+        for (section, names) in sections {
+            for name in names.unwrap_or_default() {
+                objects.push(format!("{}.{}", section, name));
+            }
+        }
+        if self.monitoring.is_some() {
+            objects.push("monitoring".to_string());
+        }
+        objects.sort();
+        objects
+    }
+
     fn apply_to(&self, config: &mut Config) -> anyhow::Result<()> {
+        if let Some(mode) = self.config_mode {
+            config.config_mode = mode;
+        }
         if let Some(ref node_name) = self.node_name {
             if node_name.trim().is_empty() {
                 bail!("node_name must not be empty");
@@ -364,9 +1336,7 @@ pub struct MonitoringConfigPatch {
 impl MonitoringConfigPatch {
     fn apply_to(&self, target: &mut MonitoringConfig) -> anyhow::Result<()> {
         if let Some(port) = self.http_port {
-            if port == 0 {
-                bail!("monitoring.http_port must be > 0");
-            }
+            // 0 = freien Port wählen lassen (siehe `listeners` im Status)
             target.http_port = port;
         }
         Ok(())
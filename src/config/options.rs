@@ -0,0 +1,126 @@
+// src/config/options.rs
+//
+// Bekannte Schlüssel der freien `config`-Tabellen je Komponententyp. Die
+// Strukturfelder meldet serde_ignored beim Einlesen (`Config::from_toml`), die
+// `config`-Maps werden erst beim Anlegen der Module gelesen. Jedes Modul
+// führt seine Schlüssel als `CONFIG_KEYS` neben dem Code, der sie liest;
+// hier werden sie nur je Typ zusammengesetzt. Verschachtelte Tabellen
+// (`opus`, `inputs`, `buffer`, ...) prüfen die Module beim Einlesen.
+
+use crate::aoip::link::POLICY_CONFIG_KEYS;
+use crate::app::configurator::{SINE_CONFIG_KEYS, VALIDATED_CONFIG_KEYS};
+use crate::app::init::GAIN_CONFIG_KEYS;
+use crate::consumers;
+use crate::core::consumer::file_writer;
+use crate::core::{file_rotation, pre_roll};
+use crate::{processors, producers};
+
+/// Gilt für jeden Producer (Watermark, Labels, Warm-Standby).
+const PRODUCER_COMMON: &[&str] = &["watermark", "labels", "standby_for"];
+
+/// `flows.<name>.config`
+const FLOW_OPTIONS: &[&str] = &[
+    "buffer",
+    "labels",
+    "timezone",
+    "on_air_gpio",
+    "bypass",
+    "watermark",
+    "overload",
+    "peak_rates",
+    "batch",
+    "default_analyzers",
+    "analyzers",
+];
+
+fn producer_options(producer_type: &str) -> Option<Vec<&'static str>> {
+    let options: Vec<&'static str> = match producer_type {
+        "file" | "cpal" | "alsa_output" => Vec::new(),
+        #[cfg(feature = "alsa")]
+        "alsa_input" => producers::alsa::producer::CONFIG_KEYS.to_vec(),
+        "sine" => SINE_CONFIG_KEYS.to_vec(),
+        "generator" => producers::generator::CONFIG_KEYS.to_vec(),
+        "pipe" => producers::pipe::CONFIG_KEYS.to_vec(),
+        "airlift_link" => [producers::link::CONFIG_KEYS, POLICY_CONFIG_KEYS].concat(),
+        #[cfg(feature = "symphonia")]
+        "mpegts" => producers::mpegts::CONFIG_KEYS.to_vec(),
+        #[cfg(feature = "srt")]
+        "srt" => producers::srt::CONFIG_KEYS.to_vec(),
+        #[cfg(feature = "whip")]
+        "whip" => producers::whip::CONFIG_KEYS.to_vec(),
+        _ => return None,
+    };
+    Some([VALIDATED_CONFIG_KEYS, PRODUCER_COMMON, &options].concat())
+}
+
+fn processor_options(processor_type: &str) -> Option<Vec<&'static str>> {
+    let options: &[&str] = match processor_type {
+        "passthrough" => &[],
+        "gain" => GAIN_CONFIG_KEYS,
+        "mixer" => processors::mixer::CONFIG_KEYS,
+        "ident" => processors::ident::CONFIG_KEYS,
+        "silence_detector" => processors::silence_detector::CONFIG_KEYS,
+        "resampler" => processors::resampler::CONFIG_KEYS,
+        "channel_mapper" => processors::channel_mapper::CONFIG_KEYS,
+        "switcher" => processors::switcher::CONFIG_KEYS,
+        "hum_filter" => processors::hum_filter::CONFIG_KEYS,
+        "agc" => processors::agc::CONFIG_KEYS,
+        "phase_meter" => processors::phase_meter::CONFIG_KEYS,
+        "silence_fallback" => processors::silence_fallback::CONFIG_KEYS,
+        "fingerprint" => processors::fingerprint::CONFIG_KEYS,
+        "pipe" => processors::pipe::CONFIG_KEYS,
+        "level_meter" => processors::level_meter::CONFIG_KEYS,
+        #[cfg(feature = "wasm")]
+        "wasm" => processors::wasm::CONFIG_KEYS,
+        _ => return None,
+    };
+    Some([VALIDATED_CONFIG_KEYS, options].concat())
+}
+
+fn consumer_options(consumer_type: &str) -> Option<Vec<&'static str>> {
+    let options: Vec<&'static str> = match consumer_type {
+        "file" => [
+            file_writer::CONFIG_KEYS,
+            pre_roll::CONFIG_KEYS,
+            file_rotation::CONFIG_KEYS,
+        ]
+        .concat(),
+        "aes67" => consumers::aes67::CONFIG_KEYS.to_vec(),
+        "icecast" => [consumers::icecast::CONFIG_KEYS, consumers::tls::CONFIG_KEYS].concat(),
+        "airlift_link" => [consumers::link::CONFIG_KEYS, POLICY_CONFIG_KEYS].concat(),
+        "rtmp_out" => [consumers::rtmp::CONFIG_KEYS, consumers::tls::CONFIG_KEYS].concat(),
+        "udp_out" => consumers::udp::CONFIG_KEYS.to_vec(),
+        "pipe" => consumers::pipe::CONFIG_KEYS.to_vec(),
+        "debug_dump" => consumers::debug_dump::CONFIG_KEYS.to_vec(),
+        "zmq_pub" => consumers::zmq::CONFIG_KEYS.to_vec(),
+        "fanout" => consumers::fanout::CONFIG_KEYS.to_vec(),
+        #[cfg(feature = "srt")]
+        "srt_out" => consumers::srt::CONFIG_KEYS.to_vec(),
+        #[cfg(feature = "whep")]
+        "whep" => consumers::whep::CONFIG_KEYS.to_vec(),
+        _ => return None,
+    };
+    // Ersatzziel und Spiegel hängt der Configurator an jeden Consumer
+    Some(
+        [
+            VALIDATED_CONFIG_KEYS,
+            consumers::backup::CONFIG_KEYS,
+            consumers::mirror::CONFIG_KEYS,
+            &options,
+        ]
+        .concat(),
+    )
+}
+
+/// Zulässige `config`-Schlüssel eines Moduls; `None` bei unbekanntem oder
+/// im Build deaktiviertem Typ (den meldet `validate_config_capabilities`)
+/// oder `kind` ohne Liste.
+pub fn known_options(kind: &str, module_type: &str) -> Option<Vec<&'static str>> {
+    match kind {
+        "producer" => producer_options(module_type),
+        "processor" => processor_options(module_type),
+        "consumer" => consumer_options(module_type),
+        "flow" => Some(FLOW_OPTIONS.to_vec()),
+        _ => None,
+    }
+}
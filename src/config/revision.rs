@@ -0,0 +1,113 @@
+// src/config/revision.rs
+//
+// Revisionen für Config-Änderungen (optimistische Nebenläufigkeit): jede
+// übernommene Änderung erhöht die globale Revision und stempelt die berührten
+// Objekte (`producers.mic`, `flows.main`, `node_name`, ...). Ein Patch gegen
+// eine ältere Revision kollidiert nur, wenn seitdem eines *seiner* Objekte
+// geändert wurde – Änderungen an anderen Objekten gehen durch.
+//
+// Aufrufer halten beim Prüfen und Übernehmen die Config-Sperre, damit Prüfung,
+// `apply_patch` und `commit` zusammen atomar sind.
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Mutex, OnceLock};
+
+use serde::Serialize;
+
+use crate::core::lock::lock_mutex;
+
+#[derive(Debug, Default)]
+struct RevisionState {
+    revision: u64,
+    objects: HashMap<String, u64>,
+    /// Komplett ersetzte Config (Import): gilt für alle Objekte ohne eigenen Stempel
+    floor: u64,
+}
+
+impl RevisionState {
+    fn object_revision(&self, object: &str) -> u64 {
+        self.objects
+            .get(object)
+            .copied()
+            .unwrap_or(0)
+            .max(self.floor)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ObjectConflict {
+    pub object: String,
+    /// Revision, in der das Objekt zuletzt geändert wurde
+    pub revision: u64,
+}
+
+#[derive(Debug, Default)]
+pub struct ConfigRevisions {
+    state: Mutex<RevisionState>,
+}
+
+impl ConfigRevisions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn current(&self) -> u64 {
+        lock_mutex(&self.state, "config.revisions.current").revision
+    }
+
+    pub fn object_revision(&self, object: &str) -> u64 {
+        lock_mutex(&self.state, "config.revisions.object").object_revision(object)
+    }
+
+    /// Objekte, die nach `base_revision` geändert wurden.
+    pub fn conflicts(&self, base_revision: u64, objects: &[String]) -> Vec<ObjectConflict> {
+        let state = lock_mutex(&self.state, "config.revisions.conflicts");
+        objects
+            .iter()
+            .filter_map(|object| {
+                let revision = state.object_revision(object);
+                (revision > base_revision).then(|| ObjectConflict {
+                    object: object.clone(),
+                    revision,
+                })
+            })
+            .collect()
+    }
+
+    /// Übernommene Änderung: neue Revision, berührte Objekte gestempelt.
+    pub fn commit(&self, objects: &[String]) -> u64 {
+        let mut state = lock_mutex(&self.state, "config.revisions.commit");
+        state.revision += 1;
+        let revision = state.revision;
+        for object in objects {
+            state.objects.insert(object.clone(), revision);
+        }
+        revision
+    }
+
+    /// Ganze Config ersetzt: jeder ältere Patch kollidiert.
+    pub fn replace_all(&self) -> u64 {
+        let mut state = lock_mutex(&self.state, "config.revisions.replace_all");
+        state.revision += 1;
+        state.floor = state.revision;
+        state.objects.clear();
+        state.revision
+    }
+
+    /// Aktuelle Revision und alle einzeln gestempelten Objekte.
+    pub fn snapshot(&self) -> (u64, BTreeMap<String, u64>) {
+        let state = lock_mutex(&self.state, "config.revisions.snapshot");
+        let objects = state
+            .objects
+            .iter()
+            .map(|(object, revision)| (object.clone(), *revision))
+            .collect();
+        (state.revision, objects)
+    }
+}
+
+static CONFIG_REVISIONS: OnceLock<ConfigRevisions> = OnceLock::new();
+
+/// Revisionen der laufenden Config (API und WebSocket teilen sie).
+pub fn config_revisions() -> &'static ConfigRevisions {
+    CONFIG_REVISIONS.get_or_init(ConfigRevisions::new)
+}
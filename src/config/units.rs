@@ -0,0 +1,218 @@
+// src/config/units.rs
+//
+// Einheiten-Parsing für Config-Werte: Dauern ("200ms", "2s"), Größen
+// ("64KiB"), Pegel ("-6dB") und Bitraten ("128k"), jeweils mit
+// Bereichsprüfung und verständlichen Fehlermeldungen.
+use std::collections::HashMap;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail};
+use serde_json::Value;
+
+fn split_number(input: &str) -> anyhow::Result<(f64, String)> {
+    let trimmed = input.trim();
+    let split = trimmed
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-' || c == '+'))
+        .unwrap_or(trimmed.len());
+    let (number, unit) = trimmed.split_at(split);
+    let value: f64 = number
+        .parse()
+        .map_err(|_| anyhow!("'{}' does not start with a number", input))?;
+    Ok((value, unit.trim().to_string()))
+}
+
+/// "200ms", "2s", "1.5s", "5m", "1h", "30d", "250us"
+pub fn parse_duration(input: &str) -> anyhow::Result<Duration> {
+    let (value, unit) = split_number(input)?;
+    if value < 0.0 {
+        bail!("duration '{}' must not be negative", input);
+    }
+    let seconds = match unit.to_ascii_lowercase().as_str() {
+        "us" | "µs" => value / 1_000_000.0,
+        "ms" => value / 1000.0,
+        "s" | "sec" => value,
+        "m" | "min" => value * 60.0,
+        "h" => value * 3600.0,
+        "d" => value * 86_400.0,
+        "" => bail!("duration '{}' needs a unit (e.g. \"200ms\", \"2s\")", input),
+        other => bail!("unknown duration unit '{}' in '{}' (use us, ms, s, m, h, d)", other, input),
+    };
+    Duration::try_from_secs_f64(seconds).map_err(|_| anyhow!("duration '{}' is out of range", input))
+}
+
+/// "4096", "64KiB", "1.5MB", "2GiB" (KB/MB/GB dezimal, KiB/MiB/GiB binär)
+pub fn parse_size(input: &str) -> anyhow::Result<u64> {
+    let (value, unit) = split_number(input)?;
+    if value < 0.0 {
+        bail!("size '{}' must not be negative", input);
+    }
+    let factor: f64 = match unit.as_str() {
+        "" | "B" => 1.0,
+        "KB" | "kB" | "k" => 1e3,
+        "MB" | "M" => 1e6,
+        "GB" | "G" => 1e9,
+        "KiB" => 1024.0,
+        "MiB" => 1024.0 * 1024.0,
+        "GiB" => 1024.0 * 1024.0 * 1024.0,
+        other => bail!("unknown size unit '{}' in '{}' (use B, KB, KiB, MB, MiB, GB, GiB)", other, input),
+    };
+    Ok((value * factor).round() as u64)
+}
+
+/// "-6dB", "+3 dB", "-3.5" (ohne Einheit = dB)
+pub fn parse_db(input: &str) -> anyhow::Result<f32> {
+    let (value, unit) = split_number(input)?;
+    match unit.to_ascii_lowercase().as_str() {
+        "" | "db" | "dbfs" => Ok(value as f32),
+        other => bail!("unknown level unit '{}' in '{}' (use dB)", other, input),
+    }
+}
+
+/// "128k", "128kbps", "96000"
+pub fn parse_bitrate(input: &str) -> anyhow::Result<u32> {
+    let (value, unit) = split_number(input)?;
+    if value <= 0.0 {
+        bail!("bitrate '{}' must be > 0", input);
+    }
+    let factor = match unit.to_ascii_lowercase().as_str() {
+        "" | "bps" => 1.0,
+        "k" | "kbps" | "kbit" => 1000.0,
+        "m" | "mbps" | "mbit" => 1_000_000.0,
+        other => bail!("unknown bitrate unit '{}' in '{}' (use k or kbps)", other, input),
+    };
+    Ok((value * factor).round() as u32)
+}
+
+pub fn db_to_linear(db: f32) -> f32 {
+    10f32.powf(db / 20.0)
+}
+
+/// Zugriff auf die freie `config`-Tabelle eines Moduls mit Einheiten-Parsing.
+/// Fehlermeldungen enthalten Modul und Schlüssel, z. B.
+/// `consumer 'stream': config.bitrate = 600000 out of range (6000..=510000)`.
+pub struct ConfigValues<'a> {
+    owner: String,
+    values: &'a HashMap<String, Value>,
+}
+
+impl<'a> ConfigValues<'a> {
+    pub fn new(module_kind: &str, module_name: &str, values: &'a HashMap<String, Value>) -> Self {
+        Self {
+            owner: format!("{} '{}'", module_kind, module_name),
+            values,
+        }
+    }
+
+    fn parse<T>(
+        &self,
+        key: &str,
+        from_number: impl Fn(f64) -> anyhow::Result<T>,
+        from_str: impl Fn(&str) -> anyhow::Result<T>,
+    ) -> anyhow::Result<Option<T>> {
+        let Some(value) = self.values.get(key) else {
+            return Ok(None);
+        };
+        let parsed = match value {
+            Value::Number(number) => number
+                .as_f64()
+                .ok_or_else(|| anyhow!("not a finite number"))
+                .and_then(&from_number),
+            Value::String(text) => from_str(text),
+            other => Err(anyhow!("unexpected value {}", other)),
+        };
+        parsed
+            .map(Some)
+            .map_err(|e| anyhow!("{}: config.{} invalid: {}", self.owner, key, e))
+    }
+
+    /// Zahlen ohne Einheit gelten als Millisekunden.
+    pub fn duration(&self, key: &str) -> anyhow::Result<Option<Duration>> {
+        self.parse(
+            key,
+            |ms| {
+                if ms < 0.0 {
+                    bail!("duration must not be negative");
+                }
+                Duration::try_from_secs_f64(ms / 1000.0)
+                    .map_err(|_| anyhow!("duration {} ms is out of range", ms))
+            },
+            parse_duration,
+        )
+    }
+
+    pub fn size(&self, key: &str) -> anyhow::Result<Option<u64>> {
+        self.parse(
+            key,
+            |bytes| {
+                if bytes < 0.0 {
+                    bail!("size must not be negative");
+                }
+                Ok(bytes as u64)
+            },
+            parse_size,
+        )
+    }
+
+    pub fn db(&self, key: &str) -> anyhow::Result<Option<f32>> {
+        self.parse(key, |db| Ok(db as f32), parse_db)
+    }
+
+    pub fn bitrate(&self, key: &str) -> anyhow::Result<Option<u32>> {
+        self.parse(
+            key,
+            |bps| {
+                if bps <= 0.0 {
+                    bail!("bitrate must be > 0");
+                }
+                Ok(bps as u32)
+            },
+            parse_bitrate,
+        )
+    }
+
+    pub fn f64(&self, key: &str) -> anyhow::Result<Option<f64>> {
+        self.parse(key, Ok, |text| {
+            text.trim()
+                .parse::<f64>()
+                .map_err(|_| anyhow!("'{}' is not a number", text))
+        })
+    }
+
+    /// Prüft einen bereits geparsten Wert gegen einen Bereich.
+    pub fn check_range<T: PartialOrd + std::fmt::Display>(
+        &self,
+        key: &str,
+        value: T,
+        min: T,
+        max: T,
+    ) -> anyhow::Result<T> {
+        if value < min || value > max {
+            bail!(
+                "{}: config.{} = {} out of range ({}..={})",
+                self.owner,
+                key,
+                value,
+                min,
+                max
+            );
+        }
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_units() {
+        assert_eq!(parse_duration("200ms").unwrap(), Duration::from_millis(200));
+        assert_eq!(parse_duration("2s").unwrap(), Duration::from_secs(2));
+        assert_eq!(parse_size("64KiB").unwrap(), 65_536);
+        assert_eq!(parse_size("1.5MB").unwrap(), 1_500_000);
+        assert_eq!(parse_db("-6dB").unwrap(), -6.0);
+        assert_eq!(parse_bitrate("128k").unwrap(), 128_000);
+        assert!(parse_duration("200").is_err());
+        assert!(parse_size("12 parsecs").is_err());
+    }
+}
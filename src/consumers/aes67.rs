@@ -0,0 +1,513 @@
+// src/consumers/aes67.rs
+//
+// AES67-/Livewire+-Sender: verpackt PCM aus dem Flow als RTP L24 (48 kHz,
+// Packet Time 1 ms oder 250 µs) und sendet per Multicast-UDP. Die RTP-
+// Timestamps werden aus `PcmFrame::utc_ns` + TAI-Offset abgeleitet, also an
+// die (PTP-disziplinierte) Systemzeit gekoppelt.
+use crate::impl_connectable_consumer;
+use std::net::{Ipv4Addr, SocketAddrV4, UdpSocket};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+
+use crate::aoip::sap::sap_service;
+use crate::aoip::sdp::Aes67Session;
+use crate::config::{ConfigValues, ConsumerConfig};
+use crate::core::idle::IdleBackoff;
+use crate::core::{AudioRingBuffer, Consumer, ConsumerStatus, PcmFrame};
+
+pub const AES67_SAMPLE_RATE: u32 = 48_000;
+const LIVEWIRE_PORT: u16 = 5004;
+const DEFAULT_PAYLOAD_TYPE: u8 = 97;
+const DEFAULT_TTL: u32 = 32;
+/// TAI - UTC (Stand 2017, seitdem unverändert)
+const DEFAULT_TAI_OFFSET: Duration = Duration::from_secs(37);
+/// Ab dieser Abweichung zwischen RTP-Takt und Frame-Zeit wird neu synchronisiert.
+const RESYNC_THRESHOLD_SAMPLES: i64 = 480;
+/// Liegt der Sender so weit hinter dem Zeitplan, wird der Takt neu gesetzt.
+const MAX_SEND_LAG: Duration = Duration::from_millis(20);
+const RTP_HEADER_LEN: usize = 12;
+
+#[derive(Debug, Clone)]
+pub struct Aes67Config {
+    pub destination: SocketAddrV4,
+    /// Lokale Adresse, an die der Socket gebunden wird
+    pub interface: Option<Ipv4Addr>,
+    pub packet_time: Duration,
+    pub payload_type: u8,
+    pub ttl: u32,
+    pub tai_offset: Duration,
+    /// Kanalzahl laut SDP; Frames mit anderer Kanalzahl werden verworfen
+    pub channels: u8,
+    /// Per SAP ankündigen (Standard: an)
+    pub sap: bool,
+    /// Sessionname im SDP (`s=`)
+    pub session_name: Option<String>,
+}
+
+/// Gelesene `config`-Schlüssel (Strict-Modus, siehe `config::options`)
+pub const CONFIG_KEYS: &[&str] = &[
+    "address",
+    "interface",
+    "channels",
+    "livewire_channel",
+    "packet_time",
+    "payload_type",
+    "sap",
+    "session_name",
+    "tai_offset",
+    "ttl",
+];
+
+impl Aes67Config {
+    /// Erwartet `address` ("239.69.1.10:5004") oder `livewire_channel`;
+    /// optional `packet_time` ("1ms"/"250us"), `payload_type`, `ttl`,
+    /// `interface`, `tai_offset`, `channels`, `sap` und `session_name`.
+    pub fn from_config(name: &str, config: &ConsumerConfig) -> Result<Self> {
+        let values = ConfigValues::new("consumer", name, &config.config);
+
+        let destination = match (
+            config.config.get("address").and_then(|v| v.as_str()),
+            config.config.get("livewire_channel").and_then(|v| v.as_u64()),
+        ) {
+            (Some(_), Some(_)) => bail!(
+                "consumer '{}': config.address and config.livewire_channel are exclusive",
+                name
+            ),
+            (Some(address), None) => address
+                .parse::<SocketAddrV4>()
+                .with_context(|| format!("consumer '{}': invalid address '{}'", name, address))?,
+            (None, Some(channel)) => {
+                let channel = values.check_range("livewire_channel", channel, 1, 32_767)?;
+                livewire_multicast(channel as u16)
+            }
+            (None, None) => bail!(
+                "consumer '{}': config.address or config.livewire_channel is required",
+                name
+            ),
+        };
+        if !destination.ip().is_multicast() {
+            log::warn!(
+                "consumer '{}': {} is not a multicast address, sending unicast",
+                name,
+                destination
+            );
+        }
+
+        let packet_time = values
+            .duration("packet_time")?
+            .unwrap_or(Duration::from_millis(1));
+        if packet_time != Duration::from_millis(1) && packet_time != Duration::from_micros(250) {
+            bail!(
+                "consumer '{}': config.packet_time must be \"1ms\" or \"250us\"",
+                name
+            );
+        }
+
+        let payload_type = match values.f64("payload_type")? {
+            Some(pt) => values.check_range("payload_type", pt as u8, 96, 127)?,
+            None => DEFAULT_PAYLOAD_TYPE,
+        };
+        let ttl = match values.f64("ttl")? {
+            Some(ttl) => values.check_range("ttl", ttl as u32, 1, 255)?,
+            None => DEFAULT_TTL,
+        };
+        let interface = config
+            .config
+            .get("interface")
+            .and_then(|v| v.as_str())
+            .map(|ip| {
+                ip.parse::<Ipv4Addr>()
+                    .with_context(|| format!("consumer '{}': invalid interface '{}'", name, ip))
+            })
+            .transpose()?;
+
+        Ok(Self {
+            destination,
+            interface,
+            packet_time,
+            payload_type,
+            ttl,
+            tai_offset: values.duration("tai_offset")?.unwrap_or(DEFAULT_TAI_OFFSET),
+            channels: match values.f64("channels")? {
+                Some(channels) => values.check_range("channels", channels as u8, 1, 64)?,
+                None => 2,
+            },
+            sap: config.config.get("sap").and_then(|v| v.as_bool()).unwrap_or(true),
+            session_name: config
+                .config
+                .get("session_name")
+                .and_then(|v| v.as_str())
+                .map(str::to_string),
+        })
+    }
+
+    pub fn samples_per_packet(&self) -> usize {
+        (AES67_SAMPLE_RATE as u128 * self.packet_time.as_nanos() / 1_000_000_000) as usize
+    }
+
+    /// Lokale Absenderadresse: `interface` oder die Route zum Ziel.
+    pub fn origin(&self) -> Ipv4Addr {
+        if let Some(interface) = self.interface {
+            return interface;
+        }
+        UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))
+            .and_then(|socket| {
+                socket.connect(self.destination)?;
+                socket.local_addr()
+            })
+            .ok()
+            .and_then(|addr| match addr.ip() {
+                std::net::IpAddr::V4(ip) => Some(ip),
+                std::net::IpAddr::V6(_) => None,
+            })
+            .unwrap_or(Ipv4Addr::UNSPECIFIED)
+    }
+
+    pub fn session(&self, name: &str, session_id: u64) -> Aes67Session {
+        Aes67Session {
+            name: self.session_name.clone().unwrap_or_else(|| name.to_string()),
+            session_id,
+            origin: self.origin(),
+            destination: *self.destination.ip(),
+            port: self.destination.port(),
+            ttl: self.ttl,
+            payload_type: self.payload_type,
+            sample_rate: AES67_SAMPLE_RATE,
+            channels: self.channels,
+            packet_time: self.packet_time,
+        }
+    }
+}
+
+/// Livewire-Kanal → Multicast-Gruppe `239.192.<hi>.<lo>`, Port 5004.
+pub fn livewire_multicast(channel: u16) -> SocketAddrV4 {
+    let [hi, lo] = channel.to_be_bytes();
+    SocketAddrV4::new(Ipv4Addr::new(239, 192, hi, lo), LIVEWIRE_PORT)
+}
+
+/// RTP-Media-Clock (48 kHz) zum PTP-Zeitpunkt (TAI) `utc_ns + tai_offset`.
+pub fn media_clock(utc_ns: u64, tai_offset: Duration) -> u32 {
+    let tai_ns = utc_ns as u128 + tai_offset.as_nanos();
+    (tai_ns * AES67_SAMPLE_RATE as u128 / 1_000_000_000) as u32
+}
+
+/// Zerlegt PCM-Frames in RTP-L24-Pakete fester Länge.
+pub struct RtpPacketizer {
+    payload_type: u8,
+    ssrc: u32,
+    samples_per_packet: usize,
+    tai_offset: Duration,
+    sequence: u16,
+    channels: u8,
+    pending: Vec<i16>,
+    /// RTP-Timestamp des ersten Samples in `pending`
+    next_timestamp: Option<u32>,
+    resyncs: u64,
+}
+
+impl RtpPacketizer {
+    pub fn new(config: &Aes67Config, ssrc: u32) -> Self {
+        Self {
+            payload_type: config.payload_type,
+            ssrc,
+            samples_per_packet: config.samples_per_packet(),
+            tai_offset: config.tai_offset,
+            sequence: 0,
+            channels: config.channels,
+            pending: Vec::new(),
+            next_timestamp: None,
+            resyncs: 0,
+        }
+    }
+
+    pub fn resyncs(&self) -> u64 {
+        self.resyncs
+    }
+
+    pub fn push(&mut self, frame: &PcmFrame) -> Result<Vec<Vec<u8>>> {
+        if frame.sample_rate != AES67_SAMPLE_RATE {
+            bail!(
+                "AES67 requires {} Hz, frame has {} Hz",
+                AES67_SAMPLE_RATE,
+                frame.sample_rate
+            );
+        }
+        if frame.channels != self.channels {
+            bail!(
+                "stream is announced with {} channel(s), frame has {}",
+                self.channels,
+                frame.channels
+            );
+        }
+        let channels = self.channels.max(1);
+
+        // Erwarteter Takt am Frame-Anfang vs. Frame-Zeitstempel
+        let frame_clock = media_clock(frame.utc_ns, self.tai_offset);
+        let queued = (self.pending.len() / channels as usize) as u32;
+        match self.next_timestamp {
+            None => self.next_timestamp = Some(frame_clock),
+            Some(ts) => {
+                let drift = frame_clock.wrapping_sub(ts.wrapping_add(queued)) as i32 as i64;
+                if drift.abs() > RESYNC_THRESHOLD_SAMPLES {
+                    self.resyncs += 1;
+                    self.pending.clear();
+                    self.next_timestamp = Some(frame_clock);
+                }
+            }
+        }
+
+        self.pending.extend_from_slice(&frame.samples);
+
+        let chunk = self.samples_per_packet * channels as usize;
+        let mut packets = Vec::new();
+        while self.pending.len() >= chunk {
+            let timestamp = self.next_timestamp.unwrap_or(frame_clock);
+            packets.push(self.packet(timestamp, &self.pending[..chunk]));
+            self.pending.drain(..chunk);
+            self.sequence = self.sequence.wrapping_add(1);
+            self.next_timestamp = Some(timestamp.wrapping_add(self.samples_per_packet as u32));
+        }
+        Ok(packets)
+    }
+
+    fn packet(&self, timestamp: u32, samples: &[i16]) -> Vec<u8> {
+        let mut packet = Vec::with_capacity(RTP_HEADER_LEN + samples.len() * 3);
+        packet.push(0x80); // V=2, kein Padding/Extension/CSRC
+        packet.push(self.payload_type & 0x7F);
+        packet.extend_from_slice(&self.sequence.to_be_bytes());
+        packet.extend_from_slice(&timestamp.to_be_bytes());
+        packet.extend_from_slice(&self.ssrc.to_be_bytes());
+        for sample in samples {
+            // L24 big-endian, unteres Byte leer
+            let [hi, lo] = sample.to_be_bytes();
+            packet.extend_from_slice(&[hi, lo, 0]);
+        }
+        packet
+    }
+}
+
+pub struct Aes67Consumer {
+    name: String,
+    config: Aes67Config,
+    running: Arc<AtomicBool>,
+    connected: Arc<AtomicBool>,
+    input_buffer: Option<Arc<AudioRingBuffer>>,
+    reader_id: String,
+    thread_handle: Option<std::thread::JoinHandle<()>>,
+    frames_processed: Arc<AtomicU64>,
+    bytes_written: Arc<AtomicU64>,
+    errors: Arc<AtomicU64>,
+}
+
+impl Aes67Consumer {
+    pub fn new(name: &str, config: &ConsumerConfig) -> Result<Self> {
+        Ok(Self::with_config(name, Aes67Config::from_config(name, config)?))
+    }
+
+    pub fn with_config(name: &str, config: Aes67Config) -> Self {
+        Self {
+            name: name.to_string(),
+            config,
+            running: Arc::new(AtomicBool::new(false)),
+            connected: Arc::new(AtomicBool::new(false)),
+            input_buffer: None,
+            reader_id: format!("consumer:{}", name),
+            thread_handle: None,
+            frames_processed: Arc::new(AtomicU64::new(0)),
+            bytes_written: Arc::new(AtomicU64::new(0)),
+            errors: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    pub fn config(&self) -> &Aes67Config {
+        &self.config
+    }
+
+    /// SSRC aus dem Namen, damit sie über Neustarts stabil bleibt.
+    fn ssrc(&self) -> u32 {
+        self.name
+            .bytes()
+            .fold(0x811C_9DC5u32, |hash, b| (hash ^ b as u32).wrapping_mul(0x0100_0193))
+    }
+}
+
+impl Consumer for Aes67Consumer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn start(&mut self) -> Result<()> {
+        if self.running.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+
+        let buffer = self
+            .input_buffer
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("Aes67Consumer '{}' missing input buffer", self.name))?;
+
+        let bind = SocketAddrV4::new(self.config.interface.unwrap_or(Ipv4Addr::UNSPECIFIED), 0);
+        let socket = UdpSocket::bind(bind)
+            .with_context(|| format!("Aes67Consumer '{}': bind {} failed", self.name, bind))?;
+        socket.set_multicast_ttl_v4(self.config.ttl)?;
+        socket.set_multicast_loop_v4(false)?;
+
+        log::info!(
+            "Aes67Consumer '{}': sending to {} (ptime {} us, PT {})",
+            self.name,
+            self.config.destination,
+            self.config.packet_time.as_micros(),
+            self.config.payload_type
+        );
+
+        if self.config.sap {
+            let session = self.config.session(&self.name, self.ssrc() as u64);
+            sap_service().announce(&self.reader_id, session);
+        }
+
+        self.running.store(true, Ordering::SeqCst);
+        self.connected.store(true, Ordering::SeqCst);
+
+        let running = self.running.clone();
+        let connected = self.connected.clone();
+        let reader_id = self.reader_id.clone();
+        let frames_processed = self.frames_processed.clone();
+        let bytes_written = self.bytes_written.clone();
+        let errors = self.errors.clone();
+        let name = self.name.clone();
+        let destination = self.config.destination;
+        let packet_time = self.config.packet_time;
+        let mut packetizer = RtpPacketizer::new(&self.config, self.ssrc());
+
+        let handle = std::thread::spawn(move || {
+            let mut next_send = Instant::now();
+            let mut idle = IdleBackoff::new(Duration::from_millis(1));
+            while running.load(Ordering::Relaxed) {
+                let Some(frame) = buffer.pop_for_reader(&reader_id) else {
+                    idle.sleep_for(&buffer);
+                    continue;
+                };
+                idle.reset();
+
+                let packets = match packetizer.push(&frame) {
+                    Ok(packets) => packets,
+                    Err(e) => {
+                        if errors.fetch_add(1, Ordering::Relaxed) == 0 {
+                            log::error!("Aes67Consumer '{}': {}", name, e);
+                        }
+                        continue;
+                    }
+                };
+
+                for packet in packets {
+                    // Pakete im Abstand der Packet Time senden statt im Burst
+                    let now = Instant::now();
+                    if now < next_send {
+                        std::thread::sleep(next_send - now);
+                    } else if now - next_send > MAX_SEND_LAG {
+                        next_send = now;
+                    }
+                    next_send += packet_time;
+
+                    match socket.send_to(&packet, destination) {
+                        Ok(sent) => {
+                            bytes_written.fetch_add(sent as u64, Ordering::Relaxed);
+                        }
+                        Err(e) => {
+                            errors.fetch_add(1, Ordering::Relaxed);
+                            log::warn!("Aes67Consumer '{}': send error: {}", name, e);
+                        }
+                    }
+                }
+                frames_processed.fetch_add(1, Ordering::Relaxed);
+            }
+            connected.store(false, Ordering::SeqCst);
+            if packetizer.resyncs() > 0 {
+                log::info!(
+                    "Aes67Consumer '{}': {} timestamp resync(s)",
+                    name,
+                    packetizer.resyncs()
+                );
+            }
+        });
+
+        self.thread_handle = Some(handle);
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        self.running.store(false, Ordering::SeqCst);
+        if let Some(handle) = self.thread_handle.take() {
+            if handle.join().is_err() {
+                self.errors.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        if self.config.sap {
+            sap_service().withdraw(&self.reader_id);
+        }
+        Ok(())
+    }
+
+    fn status(&self) -> ConsumerStatus {
+        ConsumerStatus {
+            running: self.running.load(Ordering::Relaxed),
+            connected: self.connected.load(Ordering::Relaxed),
+            frames_processed: self.frames_processed.load(Ordering::Relaxed),
+            bytes_written: self.bytes_written.load(Ordering::Relaxed),
+            errors: self.errors.load(Ordering::Relaxed),
+            connection: None,
+        }
+    }
+
+    fn attach_input_buffer(&mut self, buffer: Arc<AudioRingBuffer>) {
+        self.input_buffer = Some(buffer);
+    }
+}
+
+impl_connectable_consumer!(Aes67Consumer);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> Aes67Config {
+        Aes67Config {
+            destination: livewire_multicast(1),
+            interface: None,
+            packet_time: Duration::from_micros(250),
+            payload_type: 97,
+            ttl: 32,
+            tai_offset: Duration::ZERO,
+            channels: 2,
+            sap: false,
+            session_name: None,
+        }
+    }
+
+    #[test]
+    fn packetizes_l24_with_continuous_timestamps() {
+        let mut packetizer = RtpPacketizer::new(&config(), 7);
+        let frame = PcmFrame {
+            utc_ns: 1_000_000_000,
+            samples: vec![0x1234; 30 * 2],
+            sample_rate: 48_000,
+            channels: 2,
+            metadata: Default::default(),
+        };
+
+        let packets = packetizer.push(&frame).unwrap();
+        assert_eq!(packets.len(), 2); // 2 × 12 Samples, 6 bleiben liegen
+        assert_eq!(packets[0].len(), RTP_HEADER_LEN + 12 * 2 * 3);
+        assert_eq!(&packets[0][RTP_HEADER_LEN..RTP_HEADER_LEN + 3], &[0x12, 0x34, 0x00]);
+
+        let ts = |p: &Vec<u8>| u32::from_be_bytes(p[4..8].try_into().unwrap());
+        assert_eq!(ts(&packets[0]), 48_000);
+        assert_eq!(ts(&packets[1]), 48_012);
+        assert_eq!(u16::from_be_bytes([packets[1][2], packets[1][3]]), 1);
+
+        assert_eq!(livewire_multicast(300).ip(), &Ipv4Addr::new(239, 192, 1, 44));
+    }
+}
@@ -0,0 +1,447 @@
+// src/consumers/backup.rs
+//
+// Ersatzziel für einen Consumer (`config.backup`): scheitert das primäre Ziel,
+// z. B. der erste Icecast-Server, `failover_after`-mal in Folge, übernimmt der
+// dort genannte Consumer. Das primäre Ziel verbindet im Hintergrund weiter;
+// ist es `failback_after` lang wieder verbunden, wird das Ersatzziel gestoppt
+// (bis dahin senden beide, es entsteht keine Lücke). Jeder Wechsel erzeugt
+// ein `ConsumerFailover`-Event.
+use crate::impl_connectable_consumer;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail, Result};
+use serde::Serialize;
+
+use crate::config::{ConfigValues, ConsumerConfig};
+use crate::core::lock::lock_mutex;
+use crate::core::timestamp::utc_ns_now;
+use crate::core::{
+    AudioRingBuffer, Consumer, ConsumerStatus, ConsumerTargetStatus, EventEmitter, EventPriority,
+    EventType,
+};
+use crate::producers::wait::StopWait;
+
+const DEFAULT_FAILOVER_AFTER: u32 = 3;
+const DEFAULT_FAILBACK_AFTER: Duration = Duration::from_secs(30);
+/// Wartezeit vor dem nächsten Startversuch eines gestoppten Ziels
+const RESTART_DELAY: Duration = Duration::from_secs(2);
+/// Prüfintervall der Überwachung
+const SUPERVISE_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BackupConfig {
+    /// Name des Ersatz-Consumers aus `[consumers]`
+    pub backup: String,
+    /// Fehlversuche des primären Ziels bis zur Umschaltung
+    pub failover_after: u32,
+    /// So lange muss das primäre Ziel wieder verbunden sein
+    pub failback_after: Duration,
+}
+
+/// Gelesene `config`-Schlüssel (Strict-Modus, siehe `config::options`)
+pub const CONFIG_KEYS: &[&str] = &["backup", "failover_after", "failback_after"];
+
+impl BackupConfig {
+    /// Liest `backup`, `failover_after` (Standard 3) und `failback_after`
+    /// (Standard 30 s) aus der Konfiguration des primären Consumers.
+    pub fn from_config(name: &str, config: &ConsumerConfig) -> Result<Self> {
+        let values = ConfigValues::new("consumer", name, &config.config);
+        let backup = config
+            .config
+            .get("backup")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|backup| !backup.is_empty())
+            .ok_or_else(|| anyhow!("consumer '{}': config.backup must name a consumer", name))?;
+        if backup == name {
+            bail!("consumer '{}': config.backup must not name itself", name);
+        }
+
+        let failover_after = match values.f64("failover_after")? {
+            Some(count) if count.fract() != 0.0 => {
+                bail!("consumer '{}': config.failover_after must be a whole number", name)
+            }
+            Some(count) => values.check_range("failover_after", count, 1.0, 100.0)? as u32,
+            None => DEFAULT_FAILOVER_AFTER,
+        };
+        let failback_after = values
+            .duration("failback_after")?
+            .unwrap_or(DEFAULT_FAILBACK_AFTER);
+        values.check_range("failback_after", failback_after.as_millis() as u64, 0, 3_600_000)?;
+
+        Ok(Self {
+            backup: backup.to_string(),
+            failover_after,
+            failback_after,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupRole {
+    Primary,
+    Backup,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupSwitchReason {
+    /// Primäres Ziel `failover_after`-mal gescheitert
+    Failed,
+    /// Primäres Ziel wieder stabil verbunden
+    Recovered,
+}
+
+struct Slot {
+    consumer: Box<dyn Consumer>,
+    restarts: u32,
+    start_errors: u64,
+    retry_at: Option<Instant>,
+    last_error: Option<String>,
+}
+
+impl Slot {
+    fn new(consumer: Box<dyn Consumer>) -> Self {
+        Self {
+            consumer,
+            restarts: 0,
+            start_errors: 0,
+            retry_at: None,
+            last_error: None,
+        }
+    }
+
+    fn try_start(&mut self, owner: &str, now: Instant) -> bool {
+        // Ausgefallene Ziele zuerst sauber beenden (Thread einsammeln)
+        let _ = self.consumer.stop();
+        match self.consumer.start() {
+            Ok(()) => {
+                self.retry_at = None;
+                true
+            }
+            Err(e) => {
+                log::warn!(
+                    "Consumer '{}': '{}' failed to start: {:#}",
+                    owner,
+                    self.consumer.name(),
+                    e
+                );
+                self.start_errors += 1;
+                self.last_error = Some(format!("{:#}", e));
+                self.retry_at = Some(now + RESTART_DELAY);
+                false
+            }
+        }
+    }
+
+    /// Startet ein gestopptes Ziel neu, sobald der Backoff abgelaufen ist.
+    /// Liefert `true`, wenn ein fälliger Neustart nötig war.
+    fn restart_if_stopped(&mut self, owner: &str, now: Instant) -> bool {
+        if self.consumer.status().running || self.retry_at.is_some_and(|at| now < at) {
+            return false;
+        }
+        if self.retry_at.is_none() {
+            self.last_error = Some("target stopped".to_string());
+        }
+        if self.try_start(owner, now) {
+            self.restarts += 1;
+        }
+        true
+    }
+
+    /// Erster Start des Ersatzziels bei der Umschaltung
+    fn start_if_stopped(&mut self, owner: &str, now: Instant) {
+        self.retry_at = None;
+        if !self.consumer.status().running {
+            self.try_start(owner, now);
+        }
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        self.retry_at = None;
+        self.consumer.stop()
+    }
+
+    fn status(&self, now: Instant, active: bool) -> ConsumerTargetStatus {
+        let status = self.consumer.status();
+        ConsumerTargetStatus {
+            name: self.consumer.name().to_string(),
+            running: status.running,
+            connected: status.connected,
+            frames_processed: status.frames_processed,
+            bytes_written: status.bytes_written,
+            errors: status.errors + self.start_errors,
+            restarts: self.restarts,
+            retry_in_ms: self
+                .retry_at
+                .map(|at| at.saturating_duration_since(now).as_millis() as u64),
+            last_error: self.last_error.clone(),
+            connection: status.connection,
+            active: Some(active),
+            mirror: None,
+        }
+    }
+}
+
+struct BackupState {
+    primary: Slot,
+    backup: Slot,
+    active: BackupRole,
+    /// Eigene Zählung (Ausfälle, gescheiterte Starts) seit der letzten Verbindung
+    failures: u32,
+    healthy_since: Option<Instant>,
+}
+
+impl BackupState {
+    /// Fehlversuche des primären Ziels: eigene Zählung oder die Reconnect-
+    /// Zählung des Consumers, je nachdem, was höher ist.
+    fn primary_failures(&self, status: &ConsumerStatus) -> u32 {
+        let reported = status
+            .connection
+            .as_ref()
+            .map(|connection| connection.failed_attempts)
+            .unwrap_or(0);
+        self.failures.max(reported)
+    }
+
+    fn supervise(
+        &mut self,
+        owner: &str,
+        config: &BackupConfig,
+        now: Instant,
+    ) -> Option<(BackupSwitchReason, u32)> {
+        if self.primary.restart_if_stopped(owner, now) {
+            self.failures += 1;
+        }
+        let status = self.primary.consumer.status();
+        let healthy = status.running && status.connected;
+        if healthy && self.active == BackupRole::Primary {
+            self.failures = 0;
+        }
+        let failures = self.primary_failures(&status);
+
+        match self.active {
+            BackupRole::Primary => {
+                if healthy || failures < config.failover_after {
+                    return None;
+                }
+                self.active = BackupRole::Backup;
+                self.healthy_since = None;
+                self.backup.start_if_stopped(owner, now);
+                Some((BackupSwitchReason::Failed, failures))
+            }
+            BackupRole::Backup => {
+                self.backup.restart_if_stopped(owner, now);
+                if !healthy {
+                    self.healthy_since = None;
+                    return None;
+                }
+                let since = *self.healthy_since.get_or_insert(now);
+                if now.duration_since(since) < config.failback_after {
+                    return None;
+                }
+                if let Err(e) = self.backup.stop() {
+                    log::warn!("Consumer '{}': backup failed to stop: {:#}", owner, e);
+                }
+                self.active = BackupRole::Primary;
+                self.failures = 0;
+                self.healthy_since = None;
+                Some((BackupSwitchReason::Recovered, failures))
+            }
+        }
+    }
+
+    fn names(&self) -> (String, String) {
+        (
+            self.primary.consumer.name().to_string(),
+            self.backup.consumer.name().to_string(),
+        )
+    }
+}
+
+pub struct BackupConsumer {
+    name: String,
+    config: BackupConfig,
+    state: Arc<Mutex<BackupState>>,
+    emitter: Option<EventEmitter>,
+    running: Arc<AtomicBool>,
+    wait: Arc<StopWait>,
+    thread_handle: Option<std::thread::JoinHandle<()>>,
+}
+
+impl BackupConsumer {
+    /// Der Wrapper trägt den Namen des primären Consumers.
+    pub fn with_config(
+        primary: Box<dyn Consumer>,
+        backup: Box<dyn Consumer>,
+        config: BackupConfig,
+    ) -> Self {
+        Self {
+            name: primary.name().to_string(),
+            config,
+            state: Arc::new(Mutex::new(BackupState {
+                primary: Slot::new(primary),
+                backup: Slot::new(backup),
+                active: BackupRole::Primary,
+                failures: 0,
+                healthy_since: None,
+            })),
+            emitter: None,
+            running: Arc::new(AtomicBool::new(false)),
+            wait: Arc::new(StopWait::new()),
+            thread_handle: None,
+        }
+    }
+
+    pub fn config(&self) -> &BackupConfig {
+        &self.config
+    }
+
+    pub fn active(&self) -> BackupRole {
+        lock_mutex(&self.state, "backup.active").active
+    }
+}
+
+fn publish_switch(
+    emitter: Option<&EventEmitter>,
+    consumer: &str,
+    (from, to): (&str, &str),
+    reason: BackupSwitchReason,
+    failures: u32,
+) {
+    let priority = match reason {
+        BackupSwitchReason::Failed => EventPriority::Warning,
+        BackupSwitchReason::Recovered => EventPriority::Info,
+    };
+    log::warn!(
+        "Consumer '{}': switching '{}' -> '{}' ({:?}, {} failures)",
+        consumer,
+        from,
+        to,
+        reason,
+        failures
+    );
+    if let Some(emitter) = emitter {
+        emitter.emit(
+            EventType::ConsumerFailover,
+            priority,
+            serde_json::json!({
+                "consumer": consumer,
+                "from": from,
+                "to": to,
+                "reason": reason,
+                "failures": failures,
+                "timestamp": utc_ns_now(),
+            }),
+        );
+    }
+}
+
+impl Consumer for BackupConsumer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn start(&mut self) -> Result<()> {
+        if self.running.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+
+        // Scheitert schon der erste Start, zählt das als Fehlversuch
+        let now = Instant::now();
+        let mut state = lock_mutex(&self.state, "backup.start");
+        state.active = BackupRole::Primary;
+        state.healthy_since = None;
+        state.failures = 0;
+        if !state.primary.try_start(&self.name, now) {
+            state.failures = 1;
+        }
+        drop(state);
+
+        self.running.store(true, Ordering::SeqCst);
+        let running = self.running.clone();
+        let state = self.state.clone();
+        let wait = self.wait.clone();
+        let name = self.name.clone();
+        let config = self.config.clone();
+        let emitter = self.emitter.clone();
+
+        self.thread_handle = Some(std::thread::spawn(move || {
+            while running.load(Ordering::Relaxed) {
+                wait.wait_timeout(SUPERVISE_INTERVAL);
+                if !running.load(Ordering::Relaxed) {
+                    break;
+                }
+                let mut state = lock_mutex(&state, "backup.supervise");
+                if let Some((reason, failures)) = state.supervise(&name, &config, Instant::now()) {
+                    let (primary, backup) = state.names();
+                    drop(state);
+                    let route = match reason {
+                        BackupSwitchReason::Failed => (primary.as_str(), backup.as_str()),
+                        BackupSwitchReason::Recovered => (backup.as_str(), primary.as_str()),
+                    };
+                    publish_switch(emitter.as_ref(), &name, route, reason, failures);
+                }
+            }
+        }));
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        self.running.store(false, Ordering::SeqCst);
+        self.wait.notify_all();
+        if let Some(handle) = self.thread_handle.take() {
+            let _ = handle.join();
+        }
+
+        let mut state = lock_mutex(&self.state, "backup.stop");
+        let backup = state.backup.stop();
+        let primary = state.primary.stop();
+        primary.and(backup)
+    }
+
+    fn status(&self) -> ConsumerStatus {
+        let state = lock_mutex(&self.state, "backup.status");
+        let primary = state.primary.consumer.status();
+        let backup = state.backup.consumer.status();
+        let active = match state.active {
+            BackupRole::Primary => &primary,
+            BackupRole::Backup => &backup,
+        };
+        ConsumerStatus {
+            running: self.running.load(Ordering::Relaxed),
+            connected: active.connected,
+            frames_processed: primary.frames_processed + backup.frames_processed,
+            bytes_written: primary.bytes_written + backup.bytes_written,
+            errors: primary.errors
+                + backup.errors
+                + state.primary.start_errors
+                + state.backup.start_errors,
+            connection: active.connection.clone(),
+        }
+    }
+
+    fn attach_input_buffer(&mut self, buffer: Arc<AudioRingBuffer>) {
+        let mut state = lock_mutex(&self.state, "backup.attach");
+        state.primary.consumer.attach_input_buffer(buffer.clone());
+        state.backup.consumer.attach_input_buffer(buffer);
+    }
+
+    fn attach_event_emitter(&mut self, emitter: EventEmitter) {
+        self.emitter = Some(emitter);
+    }
+
+    fn targets(&self) -> Vec<ConsumerTargetStatus> {
+        let now = Instant::now();
+        let state = lock_mutex(&self.state, "backup.targets");
+        vec![
+            state.primary.status(now, state.active == BackupRole::Primary),
+            state.backup.status(now, state.active == BackupRole::Backup),
+        ]
+    }
+}
+
+impl_connectable_consumer!(BackupConsumer);
@@ -0,0 +1,424 @@
+// src/consumers/debug_dump.rs
+//
+// Diagnose-Ausgang (`debug_dump`): loggt je Frame Zeitstempel, Abstand zum
+// Vorgänger, Größe, Peak, RMS und Begleitdaten (`PcmFrame::metadata`);
+// optional die ersten `hex_bytes` Bytes des kodierten Frames.
+// Zeitstempel-Sprünge (Abstand weicht mehr als `JITTER_NS` von der Dauer des
+// Vorgängers ab) werden als GAP/OVERLAP markiert und gezählt. Über `consumer.configure` lässt sich die Ausgabe zur Laufzeit
+// ein- und ausschalten, ohne den Flow neu zu starten.
+use crate::impl_connectable_consumer;
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context, Result};
+
+use crate::codecs::create_encoder;
+use crate::config::{ConfigValues, ConsumerConfig};
+use crate::core::idle::IdleBackoff;
+use crate::core::lock::lock_mutex;
+use crate::core::{AudioRingBuffer, Consumer, ConsumerStatus};
+use crate::producers::wait::StopWait;
+use crate::types::{Frames, Samples};
+use crate::{FrameMetadata, PcmFrame};
+
+const IDLE_WAIT: Duration = Duration::from_millis(5);
+/// Erlaubte Abweichung zwischen Zeitstempel-Abstand und Frame-Dauer
+pub const JITTER_NS: i64 = 1_000_000;
+const MAX_EVERY: u64 = 100_000;
+const MAX_HEX_BYTES: u64 = 4096;
+/// Unterhalb davon gilt ein Frame als digital still
+const SILENCE_DBFS: f32 = -120.0;
+
+/// Zur Laufzeit änderbare Einstellungen.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DebugDumpConfig {
+    pub enabled: bool,
+    /// Nur jeden n-ten Frame loggen (gemessen wird jeder)
+    pub every: u64,
+    /// 0 = kein Hex-Dump
+    pub hex_bytes: usize,
+    /// Codec-ID für den Hex-Dump
+    pub codec: String,
+}
+
+impl Default for DebugDumpConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            every: 1,
+            hex_bytes: 0,
+            codec: "pcm".to_string(),
+        }
+    }
+}
+
+/// Gelesene `config`-Schlüssel (Strict-Modus, siehe `config::options`)
+pub const CONFIG_KEYS: &[&str] = &["enabled", "codec", "every", "hex_bytes"];
+
+impl DebugDumpConfig {
+    /// Optional `enabled`, `every`, `hex_bytes` und `codec` (Standard `pcm`).
+    pub fn from_config(name: &str, config: &ConsumerConfig) -> Result<Self> {
+        Self::default().updated(name, &serde_json::to_value(&config.config)?)
+    }
+
+    /// Übernimmt die in `update` gesetzten Schlüssel, der Rest bleibt.
+    pub fn updated(&self, name: &str, update: &serde_json::Value) -> Result<Self> {
+        let map = match update {
+            serde_json::Value::Object(map) => map.clone().into_iter().collect(),
+            other => bail!("consumer '{}': config must be a table, got {}", name, other),
+        };
+        let values = ConfigValues::new("consumer", name, &map);
+        let mut next = self.clone();
+
+        if let Some(enabled) = map.get("enabled") {
+            next.enabled = enabled
+                .as_bool()
+                .ok_or_else(|| anyhow!("consumer '{}': config.enabled must be a boolean", name))?;
+        }
+        if let Some(every) = values.size("every")? {
+            next.every = values.check_range("every", every, 1, MAX_EVERY)?;
+        }
+        if let Some(hex_bytes) = values.size("hex_bytes")? {
+            next.hex_bytes = values.check_range("hex_bytes", hex_bytes, 0, MAX_HEX_BYTES)? as usize;
+        }
+        if let Some(codec) = map.get("codec") {
+            let codec = codec
+                .as_str()
+                .ok_or_else(|| anyhow!("consumer '{}': config.codec must be a string", name))?
+                .trim()
+                .to_ascii_lowercase();
+            create_encoder(&codec).with_context(|| format!("consumer '{}'", name))?;
+            next.codec = codec;
+        }
+        Ok(next)
+    }
+}
+
+/// Messwerte eines Frames.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameStats {
+    pub seq: u64,
+    pub utc_ns: u64,
+    /// Abstand zum vorherigen Frame (`None` beim ersten)
+    pub delta_ns: Option<i64>,
+    /// Dauer laut Sample-Anzahl und Rate
+    pub duration_ns: u64,
+    pub samples: Samples,
+    pub frames: Frames,
+    pub sample_rate: u32,
+    pub channels: u8,
+    pub peak_dbfs: f32,
+    pub rms_dbfs: f32,
+    pub clipped: usize,
+    pub metadata: FrameMetadata,
+}
+
+impl FrameStats {
+    pub fn measure(seq: u64, frame: &PcmFrame, previous: Option<&FrameStats>) -> Self {
+        let samples = Samples::of(frame);
+        let frames = samples.to_frames(frame.channels);
+        let mut peak = 0i32;
+        let mut sum = 0f64;
+        let mut clipped = 0;
+        for &sample in &frame.samples {
+            let magnitude = (sample as i32).abs();
+            peak = peak.max(magnitude);
+            sum += (sample as f64) * (sample as f64);
+            if magnitude >= i16::MAX as i32 {
+                clipped += 1;
+            }
+        }
+        let rms = if samples.is_zero() {
+            0.0
+        } else {
+            (sum / samples.get() as f64).sqrt()
+        };
+        Self {
+            seq,
+            utc_ns: frame.utc_ns,
+            delta_ns: previous.map(|prev| frame.utc_ns as i64 - prev.utc_ns as i64),
+            duration_ns: (frames.to_millis(frame.sample_rate).get() * 1_000_000.0) as u64,
+            samples,
+            frames,
+            sample_rate: frame.sample_rate,
+            channels: frame.channels,
+            peak_dbfs: dbfs(peak as f64),
+            rms_dbfs: dbfs(rms),
+            clipped,
+            metadata: frame.metadata.clone(),
+        }
+    }
+
+    /// Abstand minus Dauer des Vorgängers; positiv = Lücke, negativ = Überlappung
+    pub fn jitter_ns(&self, previous: &FrameStats) -> Option<i64> {
+        self.delta_ns
+            .map(|delta| delta - previous.duration_ns as i64)
+    }
+
+    pub fn line(&self, previous: Option<&FrameStats>) -> String {
+        let mut line = format!(
+            "#{} utc={} delta={} size={}/{} {}Hz/{}ch peak={:.1}dBFS rms={:.1}dBFS",
+            self.seq,
+            self.utc_ns,
+            self.delta_ns
+                .map(|delta| format!("{:.3}ms", delta as f64 / 1e6))
+                .unwrap_or_else(|| "-".to_string()),
+            self.samples,
+            self.frames,
+            self.sample_rate,
+            self.channels,
+            self.peak_dbfs,
+            self.rms_dbfs
+        );
+        if self.clipped > 0 {
+            let _ = write!(line, " clipped={}", self.clipped);
+        }
+        if !self.metadata.is_empty() {
+            let _ = write!(
+                line,
+                " meta={}",
+                serde_json::to_string(&self.metadata).unwrap_or_default()
+            );
+        }
+        match previous.and_then(|previous| self.jitter_ns(previous)) {
+            Some(jitter) if jitter > JITTER_NS => {
+                let _ = write!(line, " GAP +{:.3}ms", jitter as f64 / 1e6);
+            }
+            Some(jitter) if jitter < -JITTER_NS => {
+                let _ = write!(line, " OVERLAP {:.3}ms", jitter as f64 / 1e6);
+            }
+            _ => {}
+        }
+        line
+    }
+}
+
+fn dbfs(value: f64) -> f32 {
+    if value <= 0.0 {
+        return SILENCE_DBFS;
+    }
+    ((20.0 * (value / i16::MAX as f64).log10()) as f32).max(SILENCE_DBFS)
+}
+
+/// `00 1f a3 …` mit höchstens `limit` Bytes, Rest als `(+n bytes)`.
+pub fn hex_dump(bytes: &[u8], limit: usize) -> String {
+    let mut out = String::with_capacity(limit.min(bytes.len()) * 3 + 16);
+    for (index, byte) in bytes.iter().take(limit).enumerate() {
+        if index > 0 {
+            out.push(' ');
+        }
+        let _ = write!(out, "{:02x}", byte);
+    }
+    if bytes.len() > limit {
+        let _ = write!(out, " … (+{} bytes)", bytes.len() - limit);
+    }
+    out
+}
+
+pub struct DebugDumpConsumer {
+    name: String,
+    config: Arc<Mutex<DebugDumpConfig>>,
+    running: Arc<AtomicBool>,
+    input_buffer: Option<Arc<AudioRingBuffer>>,
+    reader_id: String,
+    thread_handle: Option<thread::JoinHandle<()>>,
+    wait: Arc<StopWait>,
+    last_stats: Arc<Mutex<Option<FrameStats>>>,
+    gaps: Arc<AtomicU64>,
+    frames_processed: Arc<AtomicU64>,
+    bytes_written: Arc<AtomicU64>,
+    errors: Arc<AtomicU64>,
+}
+
+impl DebugDumpConsumer {
+    pub fn new(name: &str, config: &ConsumerConfig) -> Result<Self> {
+        Ok(Self::with_config(
+            name,
+            DebugDumpConfig::from_config(name, config)?,
+        ))
+    }
+
+    pub fn with_config(name: &str, config: DebugDumpConfig) -> Self {
+        Self {
+            name: name.to_string(),
+            config: Arc::new(Mutex::new(config)),
+            running: Arc::new(AtomicBool::new(false)),
+            input_buffer: None,
+            reader_id: format!("consumer:{}", name),
+            thread_handle: None,
+            wait: Arc::new(StopWait::new()),
+            last_stats: Arc::new(Mutex::new(None)),
+            gaps: Arc::new(AtomicU64::new(0)),
+            frames_processed: Arc::new(AtomicU64::new(0)),
+            bytes_written: Arc::new(AtomicU64::new(0)),
+            errors: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    pub fn config(&self) -> DebugDumpConfig {
+        lock_mutex(&self.config, "debug_dump.config").clone()
+    }
+
+    /// Messwerte des zuletzt gelesenen Frames.
+    pub fn last_stats(&self) -> Option<FrameStats> {
+        lock_mutex(&self.last_stats, "debug_dump.last_stats").clone()
+    }
+
+    /// Zeitstempel-Sprünge seit dem Start.
+    pub fn gaps(&self) -> u64 {
+        self.gaps.load(Ordering::Relaxed)
+    }
+}
+
+impl Consumer for DebugDumpConsumer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn start(&mut self) -> Result<()> {
+        if self.running.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        let buffer = self
+            .input_buffer
+            .clone()
+            .ok_or_else(|| anyhow!("DebugDumpConsumer '{}' missing input buffer", self.name))?;
+
+        self.running.store(true, Ordering::SeqCst);
+        let running = self.running.clone();
+        let config = self.config.clone();
+        let last_stats = self.last_stats.clone();
+        let gaps = self.gaps.clone();
+        let frames_processed = self.frames_processed.clone();
+        let bytes_written = self.bytes_written.clone();
+        let errors = self.errors.clone();
+        let reader_id = self.reader_id.clone();
+        let name = self.name.clone();
+        let wait = self.wait.clone();
+
+        self.thread_handle = Some(thread::spawn(move || {
+            let mut idle = IdleBackoff::new(IDLE_WAIT);
+            let mut previous: Option<FrameStats> = None;
+            let mut encoder: Option<(String, Box<dyn crate::encoders::AudioCodec>)> = None;
+            let mut seq = 0u64;
+
+            while running.load(Ordering::Relaxed) {
+                let Some(frame) = buffer.pop_for_reader(&reader_id) else {
+                    idle.wait_for(&buffer, &wait);
+                    continue;
+                };
+                idle.reset();
+                seq += 1;
+                let settings = lock_mutex(&config, "debug_dump.settings").clone();
+                let stats = FrameStats::measure(seq, &frame, previous.as_ref());
+                let jump = previous
+                    .as_ref()
+                    .and_then(|previous| stats.jitter_ns(previous))
+                    .is_some_and(|jitter| jitter.abs() > JITTER_NS);
+                if jump {
+                    gaps.fetch_add(1, Ordering::Relaxed);
+                }
+
+                // Sprünge immer, sonst nur jeden `every`-ten Frame
+                if settings.enabled && (jump || seq.is_multiple_of(settings.every)) {
+                    let line = stats.line(previous.as_ref());
+                    if jump {
+                        log::warn!("DebugDump '{}': {}", name, line);
+                    } else {
+                        log::info!("DebugDump '{}': {}", name, line);
+                    }
+
+                    if settings.hex_bytes > 0 {
+                        if encoder.as_ref().map(|(codec, _)| codec) != Some(&settings.codec) {
+                            encoder = match create_encoder(&settings.codec) {
+                                Ok(created) => Some((settings.codec.clone(), created)),
+                                Err(e) => {
+                                    errors.fetch_add(1, Ordering::Relaxed);
+                                    log::warn!("DebugDump '{}': encoder: {}", name, e);
+                                    None
+                                }
+                            };
+                        }
+                        if let Some((codec, encoder)) = encoder.as_mut() {
+                            match encoder.encode(&frame.samples) {
+                                Ok(packets) => {
+                                    for packet in packets {
+                                        bytes_written.fetch_add(
+                                            packet.payload.len() as u64,
+                                            Ordering::Relaxed,
+                                        );
+                                        log::info!(
+                                            "DebugDump '{}': #{} {} {} bytes: {}",
+                                            name,
+                                            seq,
+                                            codec,
+                                            packet.payload.len(),
+                                            hex_dump(&packet.payload, settings.hex_bytes)
+                                        );
+                                    }
+                                }
+                                Err(e) => {
+                                    errors.fetch_add(1, Ordering::Relaxed);
+                                    log::warn!("DebugDump '{}': encode error: {}", name, e);
+                                }
+                            }
+                        }
+                    }
+                }
+
+                *lock_mutex(&last_stats, "debug_dump.store") = Some(stats.clone());
+                previous = Some(stats);
+                frames_processed.fetch_add(1, Ordering::Relaxed);
+            }
+            running.store(false, Ordering::SeqCst);
+        }));
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        self.running.store(false, Ordering::SeqCst);
+        self.wait.notify_all();
+        if let Some(handle) = self.thread_handle.take() {
+            if handle.join().is_err() {
+                self.errors.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        Ok(())
+    }
+
+    fn status(&self) -> ConsumerStatus {
+        let running = self.running.load(Ordering::Relaxed);
+        ConsumerStatus {
+            running,
+            connected: running,
+            frames_processed: self.frames_processed.load(Ordering::Relaxed),
+            bytes_written: self.bytes_written.load(Ordering::Relaxed),
+            errors: self.errors.load(Ordering::Relaxed),
+            connection: None,
+        }
+    }
+
+    fn attach_input_buffer(&mut self, buffer: Arc<AudioRingBuffer>) {
+        self.input_buffer = Some(buffer);
+    }
+
+    fn update_config(&mut self, config: serde_json::Value) -> Result<()> {
+        let mut current = lock_mutex(&self.config, "debug_dump.update_config");
+        let next = current.updated(&self.name, &config)?;
+        log::info!(
+            "DebugDump '{}': enabled={} every={} hex_bytes={} codec={}",
+            self.name,
+            next.enabled,
+            next.every,
+            next.hex_bytes,
+            next.codec
+        );
+        *current = next;
+        Ok(())
+    }
+}
+
+impl_connectable_consumer!(DebugDumpConsumer);
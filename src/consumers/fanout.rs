@@ -0,0 +1,351 @@
+// src/consumers/fanout.rs
+//
+// Fanout-Ausgang: treibt mehrere Consumer (z. B. zwei Icecast-Server und eine
+// Datei) aus demselben Flow-Ausgang. Jedes Ziel liest mit eigenem Reader, ein
+// langsames Ziel verliert nur eigene Frames und hält die anderen nicht auf.
+// Ziele, die nicht starten oder ausfallen, werden einzeln mit exponentiellem
+// Backoff neu gestartet; Fehler und Neustarts zählen pro Ziel.
+use crate::impl_connectable_consumer;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail, Result};
+
+use crate::config::{ConfigValues, ConsumerConfig};
+use crate::core::lock::lock_mutex;
+use crate::core::{AudioRingBuffer, Consumer, ConsumerStatus, ConsumerTargetStatus};
+use crate::producers::wait::StopWait;
+
+const DEFAULT_RESTART: Duration = Duration::from_secs(1);
+const DEFAULT_MAX_RESTART: Duration = Duration::from_secs(30);
+/// Prüfintervall der Überwachung
+const SUPERVISE_INTERVAL: Duration = Duration::from_millis(100);
+/// Nach dieser Laufzeit gilt ein Ziel wieder als stabil (Backoff zurücksetzen)
+const STABLE_AFTER: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FanoutConfig {
+    /// Namen der Ziel-Consumer aus `[consumers]`
+    pub targets: Vec<String>,
+    /// Erster Neustart-Backoff; verdoppelt sich bis `max_restart`
+    pub restart: Duration,
+    pub max_restart: Duration,
+}
+
+/// Gelesene `config`-Schlüssel (Strict-Modus, siehe `config::options`)
+pub const CONFIG_KEYS: &[&str] = &["targets", "restart", "max_restart"];
+
+impl FanoutConfig {
+    pub fn from_config(name: &str, config: &ConsumerConfig) -> Result<Self> {
+        let values = ConfigValues::new("consumer", name, &config.config);
+        let entries = config
+            .config
+            .get("targets")
+            .and_then(|v| v.as_array())
+            .ok_or_else(|| anyhow!("consumer '{}': fanout needs config.targets = [..]", name))?;
+
+        let mut targets = Vec::with_capacity(entries.len());
+        let mut seen = HashSet::new();
+        for entry in entries {
+            let target = entry
+                .as_str()
+                .map(str::trim)
+                .filter(|target| !target.is_empty())
+                .ok_or_else(|| {
+                    anyhow!(
+                        "consumer '{}': config.targets must list consumer names",
+                        name
+                    )
+                })?;
+            if target == name {
+                bail!("consumer '{}': fanout must not target itself", name);
+            }
+            if !seen.insert(target) {
+                bail!("consumer '{}': target '{}' listed twice", name, target);
+            }
+            targets.push(target.to_string());
+        }
+        if targets.is_empty() {
+            bail!("consumer '{}': config.targets must not be empty", name);
+        }
+
+        let restart = values.duration("restart")?.unwrap_or(DEFAULT_RESTART);
+        values.check_range("restart", restart.as_millis() as u64, 100, 60_000)?;
+        let max_restart = values
+            .duration("max_restart")?
+            .unwrap_or(DEFAULT_MAX_RESTART)
+            .max(restart);
+        values.check_range("max_restart", max_restart.as_millis() as u64, 100, 600_000)?;
+
+        Ok(Self {
+            targets,
+            restart,
+            max_restart,
+        })
+    }
+}
+
+struct Target {
+    consumer: Box<dyn Consumer>,
+    restarts: u32,
+    start_errors: u64,
+    backoff: Duration,
+    retry_at: Option<Instant>,
+    running_since: Option<Instant>,
+    last_error: Option<String>,
+}
+
+impl Target {
+    fn new(consumer: Box<dyn Consumer>, backoff: Duration) -> Self {
+        Self {
+            consumer,
+            restarts: 0,
+            start_errors: 0,
+            backoff,
+            retry_at: None,
+            running_since: None,
+            last_error: None,
+        }
+    }
+
+    fn try_start(&mut self, fanout: &str, config: &FanoutConfig, now: Instant) -> bool {
+        // Ausgefallene Ziele zuerst sauber beenden (Thread einsammeln)
+        let _ = self.consumer.stop();
+        match self.consumer.start() {
+            Ok(()) => {
+                self.retry_at = None;
+                self.running_since = Some(now);
+                true
+            }
+            Err(e) => {
+                log::warn!(
+                    "FanoutConsumer '{}': target '{}' failed to start: {:#}",
+                    fanout,
+                    self.consumer.name(),
+                    e
+                );
+                self.start_errors += 1;
+                self.last_error = Some(format!("{:#}", e));
+                self.schedule_retry(config, now);
+                false
+            }
+        }
+    }
+
+    fn schedule_retry(&mut self, config: &FanoutConfig, now: Instant) {
+        self.running_since = None;
+        self.retry_at = Some(now + self.backoff);
+        self.backoff = (self.backoff * 2).min(config.max_restart);
+    }
+
+    fn supervise(&mut self, fanout: &str, config: &FanoutConfig, now: Instant) {
+        if self.consumer.status().running {
+            if self
+                .running_since
+                .is_some_and(|since| now.duration_since(since) >= STABLE_AFTER)
+            {
+                self.backoff = config.restart;
+            }
+            return;
+        }
+        match self.retry_at {
+            None => {
+                log::warn!(
+                    "FanoutConsumer '{}': target '{}' stopped, restarting in {:?}",
+                    fanout,
+                    self.consumer.name(),
+                    self.backoff
+                );
+                self.last_error = Some("target stopped".to_string());
+                self.schedule_retry(config, now);
+            }
+            Some(retry_at) if now >= retry_at => {
+                if self.try_start(fanout, config, now) {
+                    self.restarts += 1;
+                    log::info!(
+                        "FanoutConsumer '{}': target '{}' restarted",
+                        fanout,
+                        self.consumer.name()
+                    );
+                }
+            }
+            Some(_) => {}
+        }
+    }
+
+    fn status(&self, now: Instant) -> ConsumerTargetStatus {
+        let status = self.consumer.status();
+        ConsumerTargetStatus {
+            name: self.consumer.name().to_string(),
+            running: status.running,
+            connected: status.connected,
+            frames_processed: status.frames_processed,
+            bytes_written: status.bytes_written,
+            errors: status.errors + self.start_errors,
+            restarts: self.restarts,
+            retry_in_ms: self
+                .retry_at
+                .map(|at| at.saturating_duration_since(now).as_millis() as u64),
+            last_error: self.last_error.clone(),
+            connection: status.connection,
+            active: None,
+            mirror: None,
+        }
+    }
+}
+
+pub struct FanoutConsumer {
+    name: String,
+    config: FanoutConfig,
+    targets: Arc<Mutex<Vec<Target>>>,
+    input_buffer: Option<Arc<AudioRingBuffer>>,
+    running: Arc<AtomicBool>,
+    wait: Arc<StopWait>,
+    thread_handle: Option<std::thread::JoinHandle<()>>,
+}
+
+impl FanoutConsumer {
+    /// Ziele werden danach mit `add_target` angehängt (siehe Configurator).
+    pub fn new(name: &str, config: &ConsumerConfig) -> Result<Self> {
+        Ok(Self::with_config(
+            name,
+            FanoutConfig::from_config(name, config)?,
+        ))
+    }
+
+    pub fn with_config(name: &str, config: FanoutConfig) -> Self {
+        Self {
+            name: name.to_string(),
+            config,
+            targets: Arc::new(Mutex::new(Vec::new())),
+            input_buffer: None,
+            running: Arc::new(AtomicBool::new(false)),
+            wait: Arc::new(StopWait::new()),
+            thread_handle: None,
+        }
+    }
+
+    pub fn config(&self) -> &FanoutConfig {
+        &self.config
+    }
+
+    /// Hängt ein Ziel an; es liest mit eigenem Reader aus demselben Buffer.
+    pub fn add_target(&mut self, mut consumer: Box<dyn Consumer>) {
+        if let Some(buffer) = &self.input_buffer {
+            consumer.attach_input_buffer(buffer.clone());
+        }
+        lock_mutex(&self.targets, "fanout.add_target")
+            .push(Target::new(consumer, self.config.restart));
+    }
+}
+
+impl Consumer for FanoutConsumer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn start(&mut self) -> Result<()> {
+        if self.running.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        if self.input_buffer.is_none() {
+            bail!("FanoutConsumer '{}' missing input buffer", self.name);
+        }
+
+        // Ein Ziel, das nicht startet, blockiert die anderen nicht
+        let now = Instant::now();
+        let mut targets = lock_mutex(&self.targets, "fanout.start");
+        if targets.is_empty() {
+            bail!("FanoutConsumer '{}' has no targets", self.name);
+        }
+        for target in targets.iter_mut() {
+            target.backoff = self.config.restart;
+            target.try_start(&self.name, &self.config, now);
+        }
+        drop(targets);
+
+        self.running.store(true, Ordering::SeqCst);
+        let running = self.running.clone();
+        let targets = self.targets.clone();
+        let wait = self.wait.clone();
+        let name = self.name.clone();
+        let config = self.config.clone();
+
+        self.thread_handle = Some(std::thread::spawn(move || {
+            while running.load(Ordering::Relaxed) {
+                wait.wait_timeout(SUPERVISE_INTERVAL);
+                if !running.load(Ordering::Relaxed) {
+                    break;
+                }
+                let now = Instant::now();
+                for target in lock_mutex(&targets, "fanout.supervise").iter_mut() {
+                    target.supervise(&name, &config, now);
+                }
+            }
+        }));
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        self.running.store(false, Ordering::SeqCst);
+        self.wait.notify_all();
+        if let Some(handle) = self.thread_handle.take() {
+            let _ = handle.join();
+        }
+
+        let mut first_error = None;
+        for target in lock_mutex(&self.targets, "fanout.stop").iter_mut() {
+            target.retry_at = None;
+            target.running_since = None;
+            if let Err(e) = target.consumer.stop() {
+                log::warn!(
+                    "FanoutConsumer '{}': target '{}' failed to stop: {:#}",
+                    self.name,
+                    target.consumer.name(),
+                    e
+                );
+                first_error.get_or_insert(e);
+            }
+        }
+        first_error.map_or(Ok(()), Err)
+    }
+
+    fn status(&self) -> ConsumerStatus {
+        let now = Instant::now();
+        let targets: Vec<_> = lock_mutex(&self.targets, "fanout.status")
+            .iter()
+            .map(|target| target.status(now))
+            .collect();
+        ConsumerStatus {
+            running: self.running.load(Ordering::Relaxed),
+            connected: targets.iter().any(|target| target.connected),
+            frames_processed: targets
+                .iter()
+                .map(|target| target.frames_processed)
+                .max()
+                .unwrap_or(0),
+            bytes_written: targets.iter().map(|target| target.bytes_written).sum(),
+            errors: targets.iter().map(|target| target.errors).sum(),
+            connection: None,
+        }
+    }
+
+    fn attach_input_buffer(&mut self, buffer: Arc<AudioRingBuffer>) {
+        for target in lock_mutex(&self.targets, "fanout.attach").iter_mut() {
+            target.consumer.attach_input_buffer(buffer.clone());
+        }
+        self.input_buffer = Some(buffer);
+    }
+
+    fn targets(&self) -> Vec<ConsumerTargetStatus> {
+        let now = Instant::now();
+        lock_mutex(&self.targets, "fanout.targets")
+            .iter()
+            .map(|target| target.status(now))
+            .collect()
+    }
+}
+
+impl_connectable_consumer!(FanoutConsumer);
@@ -0,0 +1,635 @@
+// src/consumers/icecast.rs
+//
+// Icecast-Ausgang (Port des alten `icecast_out`): kodiert die Flow-Frames mit
+// dem konfigurierten Codec und schickt sie per SOURCE-Request an einen
+// Icecast-Mount. Verbindungsfehler und abgelehnte Logins führen zu Reconnects
+// mit exponentiellem Backoff; der Zustand steht in `ConsumerStatus::connection`.
+// PCM wird als WAV-Stream (Header mit offener Länge) gesendet. Mit
+// `url = "https://..."` (oder `tls = true`) läuft die Verbindung über TLS.
+// Bei Ogg-Codecs setzt `/api/metadata` den Titel per Ogg-Chaining um.
+use crate::impl_connectable_consumer;
+use std::io::{BufWriter, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context, Result};
+
+use crate::api::ws::base64_encode;
+use crate::audio::ogg::OggChainer;
+use crate::codecs::{create_encoder, CodecInfo, CodecKind, ContainerKind};
+use crate::config::{ConfigValues, ConsumerConfig};
+use crate::consumers::tls::{self, ConsumerStream, TlsOptions};
+use crate::core::http_client;
+use crate::core::idle::IdleBackoff;
+use crate::core::lock::lock_mutex;
+use crate::core::stream_metadata;
+use crate::core::timestamp::utc_ns_now;
+use crate::core::{
+    AudioRingBuffer, ConnectionPhase, ConnectionState, Consumer, ConsumerStatus, EventEmitter,
+    EventPriority, EventType,
+};
+use crate::producers::wait::StopWait;
+
+const DEFAULT_PORT: u16 = 8000;
+const DEFAULT_TLS_PORT: u16 = 443;
+const DEFAULT_RECONNECT: Duration = Duration::from_secs(1);
+const DEFAULT_MAX_RECONNECT: Duration = Duration::from_secs(30);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(5);
+const WRITE_TIMEOUT: Duration = Duration::from_secs(2);
+/// Kürzestes Warten auf Frames, wächst im Leerlauf (`IdleBackoff`)
+const POLL_INTERVAL: Duration = Duration::from_millis(2);
+/// Obergrenze für die Antwort-Header des Servers
+const MAX_RESPONSE_BYTES: usize = 8 * 1024;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IcecastConfig {
+    pub host: String,
+    pub port: u16,
+    /// Mit führendem `/`, z. B. `/live.ogg`
+    pub mount: String,
+    pub user: String,
+    pub password: String,
+    /// Codec-ID wie in `supported_codecs` (`pcm`, `opusogg`, ...)
+    pub codec: String,
+    pub stream_name: Option<String>,
+    pub description: Option<String>,
+    pub genre: Option<String>,
+    pub public: bool,
+    /// Erster Backoff; verdoppelt sich bis `max_reconnect`
+    pub reconnect: Duration,
+    pub max_reconnect: Duration,
+    /// `Some` = Verbindung über TLS
+    pub tls: Option<TlsOptions>,
+}
+
+/// `http(s)://host[:port]/mount` → (TLS?, Host, Port, Mount)
+pub fn parse_url(url: &str) -> Result<(bool, String, Option<u16>, String)> {
+    let (tls, rest) = if let Some(rest) = url.strip_prefix("https://") {
+        (true, rest)
+    } else if let Some(rest) = url.strip_prefix("http://") {
+        (false, rest)
+    } else {
+        bail!("'{}' must start with http:// or https://", url);
+    };
+    let (authority, mount) = match rest.find('/') {
+        Some(index) => (&rest[..index], &rest[index..]),
+        None => (rest, ""),
+    };
+    if mount.len() < 2 {
+        bail!("'{}' has no mount", url);
+    }
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((host, port)) => {
+            let port = port
+                .parse::<u16>()
+                .ok()
+                .filter(|port| *port > 0)
+                .ok_or_else(|| anyhow!("'{}' has an invalid port", url))?;
+            (host, Some(port))
+        }
+        None => (authority, None),
+    };
+    if host.is_empty() {
+        bail!("'{}' has no host", url);
+    }
+    Ok((tls, host.to_string(), port, mount.to_string()))
+}
+
+/// Gelesene `config`-Schlüssel (Strict-Modus, siehe `config::options`)
+pub const CONFIG_KEYS: &[&str] = &[
+    "host",
+    "port",
+    "mount",
+    "user",
+    "password",
+    "name",
+    "description",
+    "genre",
+    "public",
+    "codec",
+    "codec_id",
+    "reconnect",
+    "max_reconnect",
+    "tls",
+];
+
+impl IcecastConfig {
+    pub fn from_config(name: &str, config: &ConsumerConfig) -> Result<Self> {
+        let values = ConfigValues::new("consumer", name, &config.config);
+        let text = |key: &str| -> Option<String> {
+            config
+                .config
+                .get(key)
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+
+        let from_url = config
+            .url
+            .as_deref()
+            .map(|url| parse_url(url.trim()).map_err(|e| anyhow!("consumer '{}': url {}", name, e)))
+            .transpose()?;
+        if from_url.is_some() && (text("host").is_some() || text("mount").is_some()) {
+            bail!("consumer '{}': use either url or config.host/config.mount", name);
+        }
+        let use_tls = match config.config.get("tls") {
+            None => from_url.as_ref().is_some_and(|(tls, ..)| *tls),
+            Some(value) => value
+                .as_bool()
+                .ok_or_else(|| anyhow!("consumer '{}': config.tls must be true or false", name))?,
+        };
+        let tls_options = TlsOptions::from_config(name, &config.config)?;
+        let tls = if use_tls {
+            tls::ensure_supported(name)?;
+            Some(tls_options)
+        } else if tls_options.is_configured() {
+            bail!("consumer '{}': tls_* options need an https:// url or tls = true", name);
+        } else {
+            None
+        };
+
+        let host = match &from_url {
+            Some((_, host, ..)) => host.clone(),
+            None => text("host")
+                .ok_or_else(|| anyhow!("consumer '{}': icecast needs config.host", name))?,
+        };
+        let default_port = if tls.is_some() { DEFAULT_TLS_PORT } else { DEFAULT_PORT };
+        let port = match config.config.get("port") {
+            None => from_url.as_ref().and_then(|(_, _, port, _)| *port).unwrap_or(default_port),
+            Some(value) => value
+                .as_u64()
+                .and_then(|port| u16::try_from(port).ok())
+                .filter(|port| *port > 0)
+                .ok_or_else(|| anyhow!("consumer '{}': config.port must be 1-65535", name))?,
+        };
+        let mount = match &from_url {
+            Some((.., mount)) => mount.clone(),
+            None => text("mount")
+                .ok_or_else(|| anyhow!("consumer '{}': icecast needs config.mount", name))?,
+        };
+        let mount = if mount.starts_with('/') {
+            mount
+        } else {
+            format!("/{}", mount)
+        };
+        if mount.chars().any(|c| c.is_whitespace() || c.is_control()) {
+            bail!("consumer '{}': config.mount must not contain whitespace", name);
+        }
+        let password = text("password")
+            .ok_or_else(|| anyhow!("consumer '{}': icecast needs config.password", name))?;
+
+        let codec = text("codec")
+            .or_else(|| text("codec_id"))
+            .unwrap_or_else(|| "pcm".to_string())
+            .to_ascii_lowercase();
+        let info = create_encoder(&codec)
+            .with_context(|| format!("consumer '{}'", name))?
+            .info()
+            .clone();
+        if content_type(&info).is_none() {
+            bail!(
+                "consumer '{}': codec '{}' ({:?} container) cannot be streamed to Icecast",
+                name,
+                codec,
+                info.container
+            );
+        }
+
+        let reconnect = values.duration("reconnect")?.unwrap_or(DEFAULT_RECONNECT);
+        values.check_range("reconnect", reconnect.as_millis() as u64, 100, 60_000)?;
+        let max_reconnect = values
+            .duration("max_reconnect")?
+            .unwrap_or(DEFAULT_MAX_RECONNECT)
+            .max(reconnect);
+        values.check_range("max_reconnect", max_reconnect.as_millis() as u64, 100, 600_000)?;
+
+        Ok(Self {
+            host,
+            port,
+            mount,
+            user: text("user").unwrap_or_else(|| "source".to_string()),
+            password,
+            codec,
+            stream_name: text("name"),
+            description: text("description"),
+            genre: text("genre"),
+            public: config
+                .config
+                .get("public")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+            reconnect,
+            max_reconnect,
+            tls,
+        })
+    }
+
+    pub fn endpoint(&self) -> String {
+        let scheme = if self.tls.is_some() { "https://" } else { "" };
+        format!("{}{}:{}{}", scheme, self.host, self.port, self.mount)
+    }
+}
+
+/// MIME-Typ für den Mount; `None` = nicht über Icecast sendbar (RTP).
+pub fn content_type(info: &CodecInfo) -> Option<&'static str> {
+    match (&info.container, &info.kind) {
+        (ContainerKind::Ogg, _) => Some("application/ogg"),
+        (ContainerKind::Mpeg, _) => Some("audio/mpeg"),
+        (ContainerKind::Raw, CodecKind::Pcm) => Some("audio/wav"),
+        (ContainerKind::Raw, CodecKind::Flac) => Some("audio/flac"),
+        (ContainerKind::Raw, CodecKind::AacLc) => Some("audio/aac"),
+        _ => None,
+    }
+}
+
+fn header_value(value: &str) -> String {
+    value.replace(['\r', '\n'], " ")
+}
+
+/// SOURCE-Request inkl. Basic-Auth und `Ice-*`-Headern.
+pub fn source_request(config: &IcecastConfig, info: &CodecInfo) -> String {
+    let auth = base64_encode(format!("{}:{}", config.user, config.password).as_bytes());
+    let mut request = format!(
+        "SOURCE {} HTTP/1.0\r\n\
+         Host: {}:{}\r\n\
+         Authorization: Basic {}\r\n\
+         User-Agent: {}\r\n\
+         Content-Type: {}\r\n\
+         Ice-Public: {}\r\n\
+         Ice-Audio-Info: samplerate={};channels={}\r\n",
+        config.mount,
+        config.host,
+        config.port,
+        auth,
+        http_client::settings().user_agent,
+        content_type(info).unwrap_or("application/octet-stream"),
+        u8::from(config.public),
+        info.sample_rate,
+        info.channels
+    );
+    for (header, value) in [
+        ("Ice-Name", &config.stream_name),
+        ("Ice-Description", &config.description),
+        ("Ice-Genre", &config.genre),
+    ] {
+        if let Some(value) = value {
+            request.push_str(&format!("{}: {}\r\n", header, header_value(value)));
+        }
+    }
+    request.push_str("\r\n");
+    request
+}
+
+/// Statuszeile der Server-Antwort prüfen (`HTTP/1.0 200 OK`).
+pub fn check_response(response: &str) -> Result<()> {
+    let status_line = response.lines().next().unwrap_or("");
+    let code = status_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|code| code.parse::<u16>().ok())
+        .ok_or_else(|| anyhow!("invalid response '{}'", status_line.trim()))?;
+    match code {
+        200..=299 => Ok(()),
+        401 => bail!("authentication failed (401)"),
+        403 => bail!("mount refused, in use or limit reached (403)"),
+        _ => bail!("server answered '{}'", status_line.trim()),
+    }
+}
+
+/// WAV-Header ohne bekannte Länge für PCM-Streams.
+fn streaming_wav_header(info: &CodecInfo) -> Vec<u8> {
+    let channels = info.channels as u16;
+    let block_align = channels * 2;
+    let byte_rate = info.sample_rate * block_align as u32;
+    let mut header = Vec::with_capacity(44);
+    header.extend_from_slice(b"RIFF");
+    header.extend_from_slice(&u32::MAX.to_le_bytes());
+    header.extend_from_slice(b"WAVEfmt ");
+    header.extend_from_slice(&16u32.to_le_bytes());
+    header.extend_from_slice(&1u16.to_le_bytes());
+    header.extend_from_slice(&channels.to_le_bytes());
+    header.extend_from_slice(&info.sample_rate.to_le_bytes());
+    header.extend_from_slice(&byte_rate.to_le_bytes());
+    header.extend_from_slice(&block_align.to_le_bytes());
+    header.extend_from_slice(&16u16.to_le_bytes());
+    header.extend_from_slice(b"data");
+    header.extend_from_slice(&u32::MAX.to_le_bytes());
+    header
+}
+
+fn connect(config: &IcecastConfig, info: &CodecInfo) -> Result<ConsumerStream> {
+    let addr = (config.host.as_str(), config.port)
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| anyhow!("'{}' did not resolve", config.host))?;
+    let tcp = TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT)?;
+    tcp.set_nodelay(true)?;
+    tcp.set_write_timeout(Some(WRITE_TIMEOUT))?;
+    // Gilt auch für den TLS-Handshake und die Antwort-Header
+    tcp.set_read_timeout(Some(RESPONSE_TIMEOUT))?;
+    let mut stream = match &config.tls {
+        Some(options) => tls::wrap(tcp, &config.host, options)?,
+        None => ConsumerStream::Plain(tcp),
+    };
+    stream.write_all(source_request(config, info).as_bytes())?;
+
+    // Antwort-Header bis zur Leerzeile lesen
+    let mut response = Vec::new();
+    let mut byte = [0u8; 1];
+    while !response.ends_with(b"\r\n\r\n") && !response.ends_with(b"\n\n") {
+        if response.len() >= MAX_RESPONSE_BYTES {
+            bail!("response header too large");
+        }
+        match stream.read(&mut byte)? {
+            0 => break,
+            _ => response.push(byte[0]),
+        }
+    }
+    check_response(&String::from_utf8_lossy(&response))?;
+    Ok(stream)
+}
+
+pub struct IcecastConsumer {
+    name: String,
+    config: IcecastConfig,
+    running: Arc<AtomicBool>,
+    connected: Arc<AtomicBool>,
+    state: Arc<Mutex<ConnectionState>>,
+    input_buffer: Option<Arc<AudioRingBuffer>>,
+    reader_id: String,
+    wait: Arc<StopWait>,
+    thread_handle: Option<std::thread::JoinHandle<()>>,
+    frames_processed: Arc<AtomicU64>,
+    bytes_written: Arc<AtomicU64>,
+    errors: Arc<AtomicU64>,
+    emitter: Option<EventEmitter>,
+}
+
+/// Verbindungsfehler als `Error`-Event; der Bus fasst Wiederholungen einer
+/// flatternden Verbindung zusammen (`core::event_dedup`).
+fn report_failure(emitter: &EventEmitter, endpoint: &str, error_type: &str, message: &str) {
+    emitter.emit(
+        EventType::Error,
+        EventPriority::Warning,
+        serde_json::json!({
+            "error_type": error_type,
+            "endpoint": endpoint,
+            "message": message,
+            "timestamp": utc_ns_now(),
+        }),
+    );
+}
+
+impl IcecastConsumer {
+    pub fn new(name: &str, config: &ConsumerConfig) -> Result<Self> {
+        Ok(Self::with_config(name, IcecastConfig::from_config(name, config)?))
+    }
+
+    pub fn with_config(name: &str, config: IcecastConfig) -> Self {
+        let state = ConnectionState {
+            phase: ConnectionPhase::Stopped,
+            endpoint: config.endpoint(),
+            failed_attempts: 0,
+            retry_in_ms: None,
+            last_error: None,
+        };
+        Self {
+            name: name.to_string(),
+            config,
+            running: Arc::new(AtomicBool::new(false)),
+            connected: Arc::new(AtomicBool::new(false)),
+            state: Arc::new(Mutex::new(state)),
+            input_buffer: None,
+            reader_id: format!("consumer:{}", name),
+            wait: Arc::new(StopWait::new()),
+            thread_handle: None,
+            frames_processed: Arc::new(AtomicU64::new(0)),
+            bytes_written: Arc::new(AtomicU64::new(0)),
+            errors: Arc::new(AtomicU64::new(0)),
+            emitter: None,
+        }
+    }
+
+    pub fn config(&self) -> &IcecastConfig {
+        &self.config
+    }
+}
+
+impl Consumer for IcecastConsumer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn start(&mut self) -> Result<()> {
+        if self.running.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        let buffer = self
+            .input_buffer
+            .clone()
+            .ok_or_else(|| anyhow!("IcecastConsumer '{}' missing input buffer", self.name))?;
+
+        self.running.store(true, Ordering::SeqCst);
+        let running = self.running.clone();
+        let connected = self.connected.clone();
+        let state = self.state.clone();
+        let wait = self.wait.clone();
+        let reader_id = self.reader_id.clone();
+        let frames_processed = self.frames_processed.clone();
+        let bytes_written = self.bytes_written.clone();
+        let errors = self.errors.clone();
+        let name = self.name.clone();
+        let config = self.config.clone();
+        let emitter = self.emitter.clone();
+
+        log::info!(
+            "IcecastConsumer '{}': streaming {} to {}",
+            name,
+            config.codec,
+            config.endpoint()
+        );
+
+        self.thread_handle = Some(std::thread::spawn(move || {
+            let mut backoff = config.reconnect;
+            while running.load(Ordering::Relaxed) {
+                {
+                    let mut state = lock_mutex(&state, "icecast.state");
+                    state.phase = ConnectionPhase::Connecting;
+                    state.retry_in_ms = None;
+                }
+
+                // Neuer Encoder pro Verbindung: Ogg-Streams brauchen frische Header
+                let session = create_encoder(&config.codec).and_then(|encoder| {
+                    let stream = connect(&config, encoder.info())?;
+                    Ok((encoder, stream))
+                });
+                let (mut encoder, stream) = match session {
+                    Ok(session) => session,
+                    Err(e) => {
+                        errors.fetch_add(1, Ordering::Relaxed);
+                        let mut state = lock_mutex(&state, "icecast.state");
+                        if let Some(emitter) = &emitter {
+                            let message = format!("connect failed: {:#}", e);
+                            report_failure(emitter, &state.endpoint, "connect_failed", &message);
+                        } else if state.failed_attempts == 0 {
+                            log::warn!(
+                                "IcecastConsumer '{}': connect to {} failed: {:#}",
+                                name,
+                                state.endpoint,
+                                e
+                            );
+                        }
+                        state.phase = ConnectionPhase::Backoff;
+                        state.failed_attempts = state.failed_attempts.saturating_add(1);
+                        state.retry_in_ms = Some(backoff.as_millis() as u64);
+                        state.last_error = Some(format!("{:#}", e));
+                        drop(state);
+                        wait.wait_timeout(backoff);
+                        backoff = (backoff * 2).min(config.max_reconnect);
+                        continue;
+                    }
+                };
+
+                log::info!("IcecastConsumer '{}': connected to {}", name, config.endpoint());
+                backoff = config.reconnect;
+                {
+                    let mut state = lock_mutex(&state, "icecast.state");
+                    state.phase = ConnectionPhase::Connected;
+                    state.failed_attempts = 0;
+                    state.retry_in_ms = None;
+                }
+                connected.store(true, Ordering::SeqCst);
+                // Live weitersenden, kein Aufholen alter Frames
+                buffer.skip_to_latest(&reader_id);
+
+                let mut writer = BufWriter::new(stream);
+                let mut failure = None;
+                if matches!(encoder.info().kind, CodecKind::Pcm) {
+                    if let Err(e) = writer.write_all(&streaming_wav_header(encoder.info())) {
+                        failure = Some(anyhow!("send failed: {}", e));
+                    }
+                }
+
+                // Ogg: Metadaten-Updates als neuer Link im laufenden Stream
+                let mut chainer = matches!(encoder.info().container, ContainerKind::Ogg)
+                    .then(OggChainer::new);
+                let mut metadata_seen = 0;
+                let mut metadata_applied = 0;
+
+                let mut idle = IdleBackoff::new(POLL_INTERVAL);
+                while failure.is_none() && running.load(Ordering::Relaxed) {
+                    if let Some(chainer) = chainer.as_mut() {
+                        if stream_metadata::version() != metadata_seen {
+                            metadata_seen = stream_metadata::version();
+                            if let Some(entry) = stream_metadata::current(&name)
+                                .filter(|entry| entry.version != metadata_applied)
+                            {
+                                metadata_applied = entry.version;
+                                chainer.set_comments(entry.metadata.comments());
+                                log::debug!("IcecastConsumer '{}': metadata update {}", name, entry.version);
+                            }
+                        }
+                    }
+                    let Some(frame) = buffer.pop_for_reader(&reader_id) else {
+                        if let Err(e) = writer.flush() {
+                            failure = Some(anyhow!("send failed: {}", e));
+                            break;
+                        }
+                        idle.wait_for(&buffer, &wait);
+                        continue;
+                    };
+                    idle.reset();
+                    let encoded = match encoder.encode(&frame.samples) {
+                        Ok(encoded) => encoded,
+                        Err(e) => {
+                            errors.fetch_add(1, Ordering::Relaxed);
+                            log::debug!("IcecastConsumer '{}': encode error: {}", name, e);
+                            continue;
+                        }
+                    };
+                    for packet in encoded {
+                        let payload = match chainer.as_mut() {
+                            Some(chainer) => match chainer.push(&packet.payload) {
+                                Ok(payload) => payload,
+                                Err(e) => {
+                                    failure = Some(anyhow!("Ogg chaining failed: {}", e));
+                                    break;
+                                }
+                            },
+                            None => packet.payload,
+                        };
+                        if let Err(e) = writer.write_all(&payload) {
+                            failure = Some(anyhow!("send failed: {}", e));
+                            break;
+                        }
+                        bytes_written.fetch_add(payload.len() as u64, Ordering::Relaxed);
+                    }
+                    frames_processed.fetch_add(1, Ordering::Relaxed);
+                }
+
+                connected.store(false, Ordering::SeqCst);
+                if let Some(e) = failure {
+                    match &emitter {
+                        Some(emitter) => report_failure(
+                            emitter,
+                            &config.endpoint(),
+                            "connection_lost",
+                            &e.to_string(),
+                        ),
+                        None => log::warn!("IcecastConsumer '{}': {}", name, e),
+                    }
+                    errors.fetch_add(1, Ordering::Relaxed);
+                    let mut state = lock_mutex(&state, "icecast.state");
+                    state.phase = ConnectionPhase::Backoff;
+                    state.failed_attempts = 1;
+                    state.retry_in_ms = Some(backoff.as_millis() as u64);
+                    state.last_error = Some(e.to_string());
+                    drop(state);
+                    wait.wait_timeout(backoff);
+                }
+            }
+
+            let mut state = lock_mutex(&state, "icecast.state");
+            state.phase = ConnectionPhase::Stopped;
+            state.retry_in_ms = None;
+        }));
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        self.running.store(false, Ordering::SeqCst);
+        self.wait.notify_all();
+        if let Some(handle) = self.thread_handle.take() {
+            if handle.join().is_err() {
+                self.errors.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        Ok(())
+    }
+
+    fn status(&self) -> ConsumerStatus {
+        ConsumerStatus {
+            running: self.running.load(Ordering::Relaxed),
+            connected: self.connected.load(Ordering::Relaxed),
+            frames_processed: self.frames_processed.load(Ordering::Relaxed),
+            bytes_written: self.bytes_written.load(Ordering::Relaxed),
+            errors: self.errors.load(Ordering::Relaxed),
+            connection: Some(lock_mutex(&self.state, "icecast.status").clone()),
+        }
+    }
+
+    fn attach_input_buffer(&mut self, buffer: Arc<AudioRingBuffer>) {
+        self.input_buffer = Some(buffer);
+    }
+
+    fn attach_event_emitter(&mut self, emitter: EventEmitter) {
+        self.emitter = Some(emitter);
+    }
+}
+
+impl_connectable_consumer!(IcecastConsumer);
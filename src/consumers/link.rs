@@ -0,0 +1,301 @@
+// src/consumers/link.rs
+//
+// Sendeseite von `airlift_link`: liest PCM aus dem Flow und schickt die
+// Frames mit ihrem `utc_ns` per TCP an einen `airlift_link`-Producer auf
+// einem anderen Node. Der Codec wird bei jeder Verbindung mit dem Empfänger
+// ausgehandelt (siehe `aoip::link`). Bei Verbindungsverlust wird neu
+// verbunden und ab dem aktuellen Stand weitergesendet (kein Aufholen alter
+// Frames).
+use crate::impl_connectable_consumer;
+use std::io::BufWriter;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Result};
+
+use crate::aoip::link::{
+    read_answer, write_hello, write_packet, LinkChoice, LinkEncoder, LinkPolicy, DEFAULT_LINK_PORT,
+};
+use crate::config::{ConfigValues, ConsumerConfig};
+use crate::core::idle::IdleBackoff;
+use crate::core::lock::lock_mutex;
+use crate::core::{AudioRingBuffer, Consumer, ConsumerStatus};
+use crate::producers::wait::StopWait;
+
+const DEFAULT_RECONNECT: Duration = Duration::from_secs(1);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+const WRITE_TIMEOUT: Duration = Duration::from_secs(2);
+/// Wartezeit auf die Codec-Antwort des Empfängers
+const ANSWER_TIMEOUT: Duration = Duration::from_secs(3);
+/// Kürzestes Warten auf Frames, wächst im Leerlauf (`IdleBackoff`)
+const POLL_INTERVAL: Duration = Duration::from_millis(2);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinkConsumerConfig {
+    /// Ziel "host:port"; ohne Port 7700
+    pub address: String,
+    /// Stream-Name im Hello, der Empfänger kann darauf prüfen
+    pub stream: String,
+    pub reconnect: Duration,
+    /// Codec-Policy (`network`, `codecs`, `bitrate`)
+    pub policy: LinkPolicy,
+}
+
+/// Gelesene `config`-Schlüssel (Strict-Modus, siehe `config::options`)
+pub const CONFIG_KEYS: &[&str] = &["address", "stream", "transport", "reconnect"];
+
+impl LinkConsumerConfig {
+    pub fn from_config(name: &str, config: &ConsumerConfig) -> Result<Self> {
+        let values = ConfigValues::new("consumer", name, &config.config);
+        match config.config.get("transport").and_then(|v| v.as_str()) {
+            None | Some("tcp") => {}
+            Some("quic") => bail!("consumer '{}': QUIC transport is not available in this build, use tcp", name),
+            Some(other) => bail!("consumer '{}': unknown transport '{}' (tcp)", name, other),
+        }
+        let address = config
+            .url
+            .as_deref()
+            .or_else(|| config.config.get("address").and_then(|v| v.as_str()))
+            .map(str::trim)
+            .filter(|address| !address.is_empty())
+            .ok_or_else(|| anyhow!("consumer '{}': airlift_link needs config.address (host:port)", name))?;
+        let address = if address.rsplit_once(':').is_some_and(|(_, port)| port.parse::<u16>().is_ok()) {
+            address.to_string()
+        } else {
+            format!("{}:{}", address, DEFAULT_LINK_PORT)
+        };
+        let reconnect = values.duration("reconnect")?.unwrap_or(DEFAULT_RECONNECT);
+        values.check_range("reconnect", reconnect.as_millis() as u64, 100, 60_000)?;
+
+        Ok(Self {
+            address,
+            stream: config
+                .config
+                .get("stream")
+                .and_then(|v| v.as_str())
+                .unwrap_or(name)
+                .to_string(),
+            reconnect,
+            policy: LinkPolicy::from_config("consumer", name, &config.config)?,
+        })
+    }
+}
+
+pub struct LinkConsumer {
+    name: String,
+    config: LinkConsumerConfig,
+    running: Arc<AtomicBool>,
+    connected: Arc<AtomicBool>,
+    negotiated: Arc<Mutex<Option<LinkChoice>>>,
+    input_buffer: Option<Arc<AudioRingBuffer>>,
+    reader_id: String,
+    wait: Arc<StopWait>,
+    thread_handle: Option<std::thread::JoinHandle<()>>,
+    frames_processed: Arc<AtomicU64>,
+    bytes_written: Arc<AtomicU64>,
+    errors: Arc<AtomicU64>,
+}
+
+impl LinkConsumer {
+    pub fn new(name: &str, config: &ConsumerConfig) -> Result<Self> {
+        Ok(Self::with_config(name, LinkConsumerConfig::from_config(name, config)?))
+    }
+
+    pub fn with_config(name: &str, config: LinkConsumerConfig) -> Self {
+        Self {
+            name: name.to_string(),
+            config,
+            running: Arc::new(AtomicBool::new(false)),
+            connected: Arc::new(AtomicBool::new(false)),
+            negotiated: Arc::new(Mutex::new(None)),
+            input_buffer: None,
+            reader_id: format!("consumer:{}", name),
+            wait: Arc::new(StopWait::new()),
+            thread_handle: None,
+            frames_processed: Arc::new(AtomicU64::new(0)),
+            bytes_written: Arc::new(AtomicU64::new(0)),
+            errors: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    pub fn config(&self) -> &LinkConsumerConfig {
+        &self.config
+    }
+
+    /// Mit dem Empfänger ausgehandelter Codec der laufenden Verbindung
+    pub fn negotiated(&self) -> Option<LinkChoice> {
+        lock_mutex(&self.negotiated, "link_consumer.negotiated").clone()
+    }
+}
+
+/// Hello mit dem Codec-Angebot senden und die Wahl des Empfängers lesen.
+fn negotiate(stream: &TcpStream, writer: &mut BufWriter<TcpStream>, config: &LinkConsumerConfig) -> Result<LinkChoice> {
+    let offer = config.policy.offer(stream.peer_addr().ok().map(|addr| addr.ip()));
+    write_hello(writer, &config.stream, &offer)?;
+    let mut reader = stream.try_clone()?;
+    reader.set_read_timeout(Some(ANSWER_TIMEOUT))?;
+    let choice = read_answer(&mut reader)?;
+    if !offer.codecs.contains(&choice.codec) {
+        bail!("receiver chose {}, which was not offered", choice.codec.as_str());
+    }
+    Ok(choice)
+}
+
+fn connect(config: &LinkConsumerConfig) -> Result<TcpStream> {
+    let addr = config
+        .address
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| anyhow!("'{}' did not resolve", config.address))?;
+    let stream = TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT)?;
+    stream.set_nodelay(true)?;
+    stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+    Ok(stream)
+}
+
+impl Consumer for LinkConsumer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn start(&mut self) -> Result<()> {
+        if self.running.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        let buffer = self
+            .input_buffer
+            .clone()
+            .ok_or_else(|| anyhow!("LinkConsumer '{}' missing input buffer", self.name))?;
+
+        self.running.store(true, Ordering::SeqCst);
+        let running = self.running.clone();
+        let connected = self.connected.clone();
+        let wait = self.wait.clone();
+        let reader_id = self.reader_id.clone();
+        let frames_processed = self.frames_processed.clone();
+        let bytes_written = self.bytes_written.clone();
+        let errors = self.errors.clone();
+        let name = self.name.clone();
+        let config = self.config.clone();
+        let negotiated = self.negotiated.clone();
+
+        log::info!(
+            "LinkConsumer '{}': sending stream '{}' to {}",
+            name,
+            config.stream,
+            config.address
+        );
+
+        self.thread_handle = Some(std::thread::spawn(move || {
+            while running.load(Ordering::Relaxed) {
+                let stream = match connect(&config) {
+                    Ok(stream) => stream,
+                    Err(e) => {
+                        if errors.fetch_add(1, Ordering::Relaxed) == 0 {
+                            log::warn!("LinkConsumer '{}': connect to {} failed: {}", name, config.address, e);
+                        }
+                        wait.wait_timeout(config.reconnect);
+                        continue;
+                    }
+                };
+                let mut writer = match stream.try_clone() {
+                    Ok(clone) => BufWriter::new(clone),
+                    Err(e) => {
+                        log::warn!("LinkConsumer '{}': {}", name, e);
+                        errors.fetch_add(1, Ordering::Relaxed);
+                        wait.wait_timeout(config.reconnect);
+                        continue;
+                    }
+                };
+                let choice = match negotiate(&stream, &mut writer, &config) {
+                    Ok(choice) => choice,
+                    Err(e) => {
+                        log::warn!("LinkConsumer '{}': negotiation with {} failed: {:#}", name, config.address, e);
+                        errors.fetch_add(1, Ordering::Relaxed);
+                        wait.wait_timeout(config.reconnect);
+                        continue;
+                    }
+                };
+                log::info!("LinkConsumer '{}': connected to {} ({})", name, config.address, choice);
+                *lock_mutex(&negotiated, "link_consumer.connected") = Some(choice.clone());
+                let mut encoder = LinkEncoder::new(choice);
+                connected.store(true, Ordering::SeqCst);
+                // Nach (Wieder-)Verbindung live weitersenden
+                buffer.skip_to_latest(&reader_id);
+
+                let mut idle = IdleBackoff::new(POLL_INTERVAL);
+                while running.load(Ordering::Relaxed) {
+                    let Some(frame) = buffer.pop_for_reader(&reader_id) else {
+                        if let Err(e) = std::io::Write::flush(&mut writer) {
+                            log::warn!("LinkConsumer '{}': send failed: {}", name, e);
+                            errors.fetch_add(1, Ordering::Relaxed);
+                            break;
+                        }
+                        idle.wait_for(&buffer, &wait);
+                        continue;
+                    };
+                    idle.reset();
+                    let packets = match encoder.encode(&frame) {
+                        Ok(packets) => packets,
+                        Err(e) => {
+                            if errors.fetch_add(1, Ordering::Relaxed) == 0 {
+                                log::warn!("LinkConsumer '{}': encode failed: {:#}", name, e);
+                            }
+                            continue;
+                        }
+                    };
+                    let sent = packets
+                        .iter()
+                        .try_fold(0, |sent, packet| Ok::<_, std::io::Error>(sent + write_packet(&mut writer, packet)?));
+                    match sent {
+                        Ok(sent) => {
+                            bytes_written.fetch_add(sent as u64, Ordering::Relaxed);
+                            frames_processed.fetch_add(1, Ordering::Relaxed);
+                        }
+                        Err(e) => {
+                            log::warn!("LinkConsumer '{}': send failed: {}", name, e);
+                            errors.fetch_add(1, Ordering::Relaxed);
+                            break;
+                        }
+                    }
+                }
+                connected.store(false, Ordering::SeqCst);
+                *lock_mutex(&negotiated, "link_consumer.disconnected") = None;
+                if running.load(Ordering::Relaxed) {
+                    wait.wait_timeout(config.reconnect);
+                }
+            }
+        }));
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        self.running.store(false, Ordering::SeqCst);
+        self.wait.notify_all();
+        if let Some(handle) = self.thread_handle.take() {
+            if handle.join().is_err() {
+                self.errors.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        Ok(())
+    }
+
+    fn status(&self) -> ConsumerStatus {
+        ConsumerStatus {
+            running: self.running.load(Ordering::Relaxed),
+            connected: self.connected.load(Ordering::Relaxed),
+            frames_processed: self.frames_processed.load(Ordering::Relaxed),
+            bytes_written: self.bytes_written.load(Ordering::Relaxed),
+            errors: self.errors.load(Ordering::Relaxed),
+            connection: None,
+        }
+    }
+
+    fn attach_input_buffer(&mut self, buffer: Arc<AudioRingBuffer>) {
+        self.input_buffer = Some(buffer);
+    }
+}
+
+impl_connectable_consumer!(LinkConsumer);
@@ -0,0 +1,307 @@
+// src/consumers/mirror.rs
+//
+// Spiegelziel für einen Consumer (`config.mirror`): der dort genannte Consumer,
+// z. B. ein Staging-Icecast, bekommt denselben Flow-Ausgang mit eigenem Reader
+// und sendet parallel zum eigentlichen Ziel. So lässt sich neue Infrastruktur
+// vor der Umstellung mit echtem Programm prüfen. Das Spiegelziel ist vom
+// primären Ziel entkoppelt: Ausfälle und Neustarts (exponentieller Backoff)
+// betreffen nur den Spiegel, Status und Fehler des Consumers bleiben die des
+// primären Ziels.
+use crate::impl_connectable_consumer;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail, Result};
+
+use crate::config::ConsumerConfig;
+use crate::core::lock::lock_mutex;
+use crate::core::{AudioRingBuffer, Consumer, ConsumerStatus, ConsumerTargetStatus, EventEmitter};
+use crate::producers::wait::StopWait;
+
+/// Erster Neustart-Backoff des Spiegels; verdoppelt sich bis `MAX_RESTART`
+const RESTART: Duration = Duration::from_secs(1);
+const MAX_RESTART: Duration = Duration::from_secs(30);
+/// Nach dieser Laufzeit gilt der Spiegel wieder als stabil (Backoff zurücksetzen)
+const STABLE_AFTER: Duration = Duration::from_secs(10);
+/// Prüfintervall der Überwachung
+const SUPERVISE_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MirrorConfig {
+    /// Name des Spiegel-Consumers aus `[consumers]`
+    pub mirror: String,
+}
+
+/// Gelesene `config`-Schlüssel (Strict-Modus, siehe `config::options`)
+pub const CONFIG_KEYS: &[&str] = &["mirror"];
+
+impl MirrorConfig {
+    pub fn from_config(name: &str, config: &ConsumerConfig) -> Result<Self> {
+        let mirror = config
+            .config
+            .get("mirror")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|mirror| !mirror.is_empty())
+            .ok_or_else(|| anyhow!("consumer '{}': config.mirror must name a consumer", name))?;
+        if mirror == name {
+            bail!("consumer '{}': config.mirror must not name itself", name);
+        }
+        Ok(Self {
+            mirror: mirror.to_string(),
+        })
+    }
+}
+
+struct MirrorSlot {
+    consumer: Box<dyn Consumer>,
+    restarts: u32,
+    start_errors: u64,
+    backoff: Duration,
+    retry_at: Option<Instant>,
+    running_since: Option<Instant>,
+    last_error: Option<String>,
+}
+
+impl MirrorSlot {
+    fn new(consumer: Box<dyn Consumer>) -> Self {
+        Self {
+            consumer,
+            restarts: 0,
+            start_errors: 0,
+            backoff: RESTART,
+            retry_at: None,
+            running_since: None,
+            last_error: None,
+        }
+    }
+
+    fn try_start(&mut self, owner: &str, now: Instant) -> bool {
+        // Ausgefallenen Spiegel zuerst sauber beenden (Thread einsammeln)
+        let _ = self.consumer.stop();
+        match self.consumer.start() {
+            Ok(()) => {
+                self.retry_at = None;
+                self.running_since = Some(now);
+                true
+            }
+            Err(e) => {
+                log::warn!(
+                    "Consumer '{}': mirror '{}' failed to start: {:#}",
+                    owner,
+                    self.consumer.name(),
+                    e
+                );
+                self.start_errors += 1;
+                self.last_error = Some(format!("{:#}", e));
+                self.schedule_retry(now);
+                false
+            }
+        }
+    }
+
+    fn schedule_retry(&mut self, now: Instant) {
+        self.running_since = None;
+        self.retry_at = Some(now + self.backoff);
+        self.backoff = (self.backoff * 2).min(MAX_RESTART);
+    }
+
+    fn supervise(&mut self, owner: &str, now: Instant) {
+        if self.consumer.status().running {
+            if self
+                .running_since
+                .is_some_and(|since| now.duration_since(since) >= STABLE_AFTER)
+            {
+                self.backoff = RESTART;
+            }
+            return;
+        }
+        match self.retry_at {
+            None => {
+                log::warn!(
+                    "Consumer '{}': mirror '{}' stopped, restarting in {:?}",
+                    owner,
+                    self.consumer.name(),
+                    self.backoff
+                );
+                self.last_error = Some("target stopped".to_string());
+                self.schedule_retry(now);
+            }
+            Some(retry_at) if now >= retry_at => {
+                if self.try_start(owner, now) {
+                    self.restarts += 1;
+                }
+            }
+            Some(_) => {}
+        }
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        self.retry_at = None;
+        self.running_since = None;
+        self.consumer.stop()
+    }
+
+    fn status(&self, now: Instant) -> ConsumerTargetStatus {
+        let status = self.consumer.status();
+        ConsumerTargetStatus {
+            name: self.consumer.name().to_string(),
+            running: status.running,
+            connected: status.connected,
+            frames_processed: status.frames_processed,
+            bytes_written: status.bytes_written,
+            errors: status.errors + self.start_errors,
+            restarts: self.restarts,
+            retry_in_ms: self
+                .retry_at
+                .map(|at| at.saturating_duration_since(now).as_millis() as u64),
+            last_error: self.last_error.clone(),
+            connection: status.connection,
+            active: None,
+            mirror: Some(true),
+        }
+    }
+}
+
+pub struct MirrorConsumer {
+    name: String,
+    config: MirrorConfig,
+    primary: Box<dyn Consumer>,
+    mirror: Arc<Mutex<MirrorSlot>>,
+    running: Arc<AtomicBool>,
+    wait: Arc<StopWait>,
+    thread_handle: Option<std::thread::JoinHandle<()>>,
+}
+
+impl MirrorConsumer {
+    /// Der Wrapper trägt den Namen des primären Consumers.
+    pub fn with_config(
+        primary: Box<dyn Consumer>,
+        mirror: Box<dyn Consumer>,
+        config: MirrorConfig,
+    ) -> Self {
+        Self {
+            name: primary.name().to_string(),
+            config,
+            primary,
+            mirror: Arc::new(Mutex::new(MirrorSlot::new(mirror))),
+            running: Arc::new(AtomicBool::new(false)),
+            wait: Arc::new(StopWait::new()),
+            thread_handle: None,
+        }
+    }
+
+    pub fn config(&self) -> &MirrorConfig {
+        &self.config
+    }
+}
+
+impl Consumer for MirrorConsumer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn start(&mut self) -> Result<()> {
+        if self.running.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        // Das primäre Ziel verhält sich wie ohne Spiegel
+        self.primary.start()?;
+
+        // Ein Spiegel, der nicht startet, wird nur neu versucht
+        let mut mirror = lock_mutex(&self.mirror, "mirror.start");
+        mirror.backoff = RESTART;
+        mirror.try_start(&self.name, Instant::now());
+        drop(mirror);
+
+        self.running.store(true, Ordering::SeqCst);
+        let running = self.running.clone();
+        let mirror = self.mirror.clone();
+        let wait = self.wait.clone();
+        let name = self.name.clone();
+
+        self.thread_handle = Some(std::thread::spawn(move || {
+            while running.load(Ordering::Relaxed) {
+                wait.wait_timeout(SUPERVISE_INTERVAL);
+                if !running.load(Ordering::Relaxed) {
+                    break;
+                }
+                lock_mutex(&mirror, "mirror.supervise").supervise(&name, Instant::now());
+            }
+        }));
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        self.running.store(false, Ordering::SeqCst);
+        self.wait.notify_all();
+        if let Some(handle) = self.thread_handle.take() {
+            let _ = handle.join();
+        }
+
+        let mut mirror = lock_mutex(&self.mirror, "mirror.stop");
+        if let Err(e) = mirror.stop() {
+            log::warn!(
+                "Consumer '{}': mirror '{}' failed to stop: {:#}",
+                self.name,
+                mirror.consumer.name(),
+                e
+            );
+        }
+        drop(mirror);
+        self.primary.stop()
+    }
+
+    fn status(&self) -> ConsumerStatus {
+        self.primary.status()
+    }
+
+    fn attach_input_buffer(&mut self, buffer: Arc<AudioRingBuffer>) {
+        lock_mutex(&self.mirror, "mirror.attach")
+            .consumer
+            .attach_input_buffer(buffer.clone());
+        self.primary.attach_input_buffer(buffer);
+    }
+
+    fn attach_event_emitter(&mut self, emitter: EventEmitter) {
+        let mut mirror = lock_mutex(&self.mirror, "mirror.attach_event_emitter");
+        let instance = mirror.consumer.name().to_string();
+        mirror
+            .consumer
+            .attach_event_emitter(emitter.for_instance(&instance));
+        drop(mirror);
+        self.primary.attach_event_emitter(emitter);
+    }
+
+    fn targets(&self) -> Vec<ConsumerTargetStatus> {
+        let now = Instant::now();
+        let status = self.primary.status();
+        // Ersatzziele o. Ä. des primären Ziels bleiben sichtbar
+        let mut targets = self.primary.targets();
+        if targets.is_empty() {
+            targets.push(ConsumerTargetStatus {
+                name: self.name.clone(),
+                running: status.running,
+                connected: status.connected,
+                frames_processed: status.frames_processed,
+                bytes_written: status.bytes_written,
+                errors: status.errors,
+                restarts: 0,
+                retry_in_ms: None,
+                last_error: None,
+                connection: status.connection,
+                active: None,
+                mirror: Some(false),
+            });
+        }
+        targets.push(lock_mutex(&self.mirror, "mirror.targets").status(now));
+        targets
+    }
+
+    fn update_config(&mut self, config: serde_json::Value) -> Result<()> {
+        self.primary.update_config(config)
+    }
+}
+
+impl_connectable_consumer!(MirrorConsumer);
@@ -1,3 +1,34 @@
+pub mod aes67;
+pub mod backup;
+pub mod debug_dump;
+pub mod fanout;
+pub mod icecast;
+pub mod link;
+pub mod mirror;
+pub mod pipe;
+pub mod rtmp;
+#[cfg(feature = "srt")]
+pub mod srt;
+pub mod tls;
+pub mod udp;
+#[cfg(feature = "whep")]
+pub mod whep;
 pub mod ws;
+pub mod zmq;
 
+pub use aes67::Aes67Consumer;
+pub use backup::BackupConsumer;
+pub use debug_dump::DebugDumpConsumer;
+pub use fanout::FanoutConsumer;
+pub use icecast::IcecastConsumer;
+pub use link::LinkConsumer;
+pub use mirror::MirrorConsumer;
+pub use pipe::PipeConsumer;
+pub use rtmp::RtmpOutputConsumer;
+#[cfg(feature = "srt")]
+pub use srt::SrtOutputConsumer;
+pub use udp::UdpOutputConsumer;
+#[cfg(feature = "whep")]
+pub use whep::WhepConsumer;
 pub use ws::WsConsumer;
+pub use zmq::ZmqPubConsumer;
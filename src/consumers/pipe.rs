@@ -0,0 +1,308 @@
+// src/consumers/pipe.rs
+//
+// Gegenstück zum `pipe`-Producer: schreibt den Flow als Roh-PCM oder
+// kodiert (Codec-Registry) nach stdout oder in eine Named Pipe, damit
+// ffmpeg, sox oder eigene Tools ohne Netzwerk oder Platte mitlesen können.
+// Verschwindet der Leser einer FIFO, wird sie neu geöffnet; stdout endet mit
+// dem ersten Schreibfehler.
+use crate::impl_connectable_consumer;
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context, Result};
+
+use crate::audio::sanitize_audio_path;
+use crate::codecs::create_encoder;
+use crate::config::ConsumerConfig;
+use crate::core::idle::IdleBackoff;
+use crate::core::{AudioRingBuffer, Consumer, ConsumerStatus};
+use crate::producers::pipe::RawFormat;
+use crate::producers::wait::StopWait;
+
+/// Wartezeit, bevor eine FIFO nach Schreibfehler neu geöffnet wird
+const REOPEN_DELAY: Duration = Duration::from_millis(500);
+const IDLE_WAIT: Duration = Duration::from_millis(2);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PipeTarget {
+    Stdout,
+    /// FIFO oder Datei; wird nach einem Schreibfehler neu geöffnet, wenn
+    /// `reopen` gesetzt ist
+    Path(PathBuf),
+}
+
+/// Was in die Pipe geht: Roh-PCM im gewünschten Sampleformat oder die
+/// Payloads eines Encoders.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PipeOutput {
+    Raw(RawFormat),
+    Encoded(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PipeOutputConfig {
+    pub target: PipeTarget,
+    pub output: PipeOutput,
+    pub reopen: bool,
+}
+
+/// Gelesene `config`-Schlüssel (Strict-Modus, siehe `config::options`)
+pub const CONFIG_KEYS: &[&str] = &["format", "codec", "reopen"];
+
+impl PipeOutputConfig {
+    /// `path` wie beim Producer (`-` oder leer = stdout); optional `format`
+    /// (Roh-PCM, Standard `s16le`) oder `codec`, dazu `reopen`.
+    pub fn from_config(name: &str, config: &ConsumerConfig) -> Result<Self> {
+        let target = match config.path.as_deref().map(str::trim) {
+            None | Some("") | Some("-") => PipeTarget::Stdout,
+            Some(path) => PipeTarget::Path(
+                sanitize_audio_path(path).with_context(|| format!("consumer '{}'", name))?,
+            ),
+        };
+        let text = |key: &str| -> Result<Option<String>> {
+            match config.config.get(key) {
+                None => Ok(None),
+                Some(value) => value
+                    .as_str()
+                    .map(|text| Some(text.trim().to_ascii_lowercase()))
+                    .ok_or_else(|| anyhow!("consumer '{}': config.{} must be a string", name, key)),
+            }
+        };
+
+        let output = match (text("format")?, text("codec")?) {
+            (Some(_), Some(_)) => bail!(
+                "consumer '{}': config.format (raw PCM) and config.codec are exclusive",
+                name
+            ),
+            (None, Some(codec)) => {
+                create_encoder(&codec).with_context(|| format!("consumer '{}'", name))?;
+                PipeOutput::Encoded(codec)
+            }
+            (format, None) => PipeOutput::Raw(
+                RawFormat::parse(format.as_deref().unwrap_or("s16le"))
+                    .map_err(|e| anyhow!("consumer '{}': {}", name, e))?,
+            ),
+        };
+
+        let is_path = matches!(target, PipeTarget::Path(_));
+        let reopen = match config.config.get("reopen") {
+            None => is_path,
+            Some(value) => value
+                .as_bool()
+                .ok_or_else(|| anyhow!("consumer '{}': config.reopen must be a boolean", name))?,
+        };
+        Ok(Self {
+            reopen: reopen && is_path,
+            target,
+            output,
+        })
+    }
+}
+
+pub struct PipeConsumer {
+    name: String,
+    config: PipeOutputConfig,
+    /// Pro Start ein eigenes Flag: ein blockierendes Öffnen der FIFO kann
+    /// nicht abgebrochen werden, der alte Thread beendet sich danach selbst.
+    running: Arc<AtomicBool>,
+    connected: Arc<AtomicBool>,
+    input_buffer: Option<Arc<AudioRingBuffer>>,
+    reader_id: String,
+    thread_handle: Option<thread::JoinHandle<()>>,
+    wait: Arc<StopWait>,
+    frames_processed: Arc<AtomicU64>,
+    bytes_written: Arc<AtomicU64>,
+    errors: Arc<AtomicU64>,
+}
+
+impl PipeConsumer {
+    pub fn new(name: &str, config: &ConsumerConfig) -> Result<Self> {
+        Ok(Self::with_config(name, PipeOutputConfig::from_config(name, config)?))
+    }
+
+    pub fn with_config(name: &str, config: PipeOutputConfig) -> Self {
+        Self {
+            name: name.to_string(),
+            config,
+            running: Arc::new(AtomicBool::new(false)),
+            connected: Arc::new(AtomicBool::new(false)),
+            input_buffer: None,
+            reader_id: format!("consumer:{}", name),
+            thread_handle: None,
+            wait: Arc::new(StopWait::new()),
+            frames_processed: Arc::new(AtomicU64::new(0)),
+            bytes_written: Arc::new(AtomicU64::new(0)),
+            errors: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    pub fn config(&self) -> &PipeOutputConfig {
+        &self.config
+    }
+
+    fn open(target: &PipeTarget) -> io::Result<Box<dyn Write + Send>> {
+        match target {
+            PipeTarget::Stdout => Ok(Box::new(io::stdout())),
+            // Öffnen einer FIFO blockiert, bis ein Leser da ist
+            PipeTarget::Path(path) => Ok(Box::new(
+                OpenOptions::new().write(true).create(true).truncate(true).open(path)?,
+            )),
+        }
+    }
+}
+
+impl Consumer for PipeConsumer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn start(&mut self) -> Result<()> {
+        if self.running.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        let buffer = self
+            .input_buffer
+            .clone()
+            .ok_or_else(|| anyhow!("PipeConsumer '{}' missing input buffer", self.name))?;
+
+        self.running = Arc::new(AtomicBool::new(true));
+        let running = self.running.clone();
+        let connected = self.connected.clone();
+        let frames_processed = self.frames_processed.clone();
+        let bytes_written = self.bytes_written.clone();
+        let errors = self.errors.clone();
+        let reader_id = self.reader_id.clone();
+        let config = self.config.clone();
+        let name = self.name.clone();
+        let wait = self.wait.clone();
+
+        log::info!(
+            "PipeConsumer '{}': writing {:?} to {:?}",
+            name,
+            config.output,
+            config.target
+        );
+
+        self.thread_handle = Some(thread::spawn(move || {
+            'outer: while running.load(Ordering::Relaxed) {
+                let mut writer = match Self::open(&config.target) {
+                    Ok(writer) => writer,
+                    Err(e) => {
+                        log::error!("PipeConsumer '{}': open failed: {}", name, e);
+                        errors.fetch_add(1, Ordering::Relaxed);
+                        if !config.reopen {
+                            break;
+                        }
+                        thread::sleep(REOPEN_DELAY);
+                        continue;
+                    }
+                };
+                // Neuer Encoder pro Öffnen, damit der Leser sauber einsteigt
+                let mut encoder = match &config.output {
+                    PipeOutput::Encoded(codec) => match create_encoder(codec) {
+                        Ok(encoder) => Some(encoder),
+                        Err(e) => {
+                            log::error!("PipeConsumer '{}': {}", name, e);
+                            errors.fetch_add(1, Ordering::Relaxed);
+                            break;
+                        }
+                    },
+                    PipeOutput::Raw(_) => None,
+                };
+                connected.store(true, Ordering::Relaxed);
+                // Nur live weiterschreiben, kein Rückstau aus der Wartezeit
+                buffer.skip_to_latest(&reader_id);
+
+                let mut idle = IdleBackoff::new(IDLE_WAIT);
+                while running.load(Ordering::Relaxed) {
+                    let Some(frame) = buffer.pop_for_reader(&reader_id) else {
+                        idle.wait_for(&buffer, &wait);
+                        continue;
+                    };
+                    idle.reset();
+                    let chunks = match (&config.output, encoder.as_mut()) {
+                        (PipeOutput::Raw(format), _) => vec![format.encode(&frame.samples)],
+                        (PipeOutput::Encoded(_), Some(encoder)) => match encoder.encode(&frame.samples) {
+                            Ok(encoded) => encoded.into_iter().map(|packet| packet.payload).collect(),
+                            Err(e) => {
+                                errors.fetch_add(1, Ordering::Relaxed);
+                                log::debug!("PipeConsumer '{}': encode error: {}", name, e);
+                                continue;
+                            }
+                        },
+                        (PipeOutput::Encoded(_), None) => Vec::new(),
+                    };
+                    let written = chunks
+                        .iter()
+                        .try_for_each(|chunk| writer.write_all(chunk))
+                        .and_then(|_| writer.flush());
+                    if let Err(e) = written {
+                        // Leser weg (EPIPE): FIFO neu öffnen, stdout aufgeben
+                        connected.store(false, Ordering::Relaxed);
+                        errors.fetch_add(1, Ordering::Relaxed);
+                        if !config.reopen {
+                            log::warn!("PipeConsumer '{}': write failed: {}", name, e);
+                            break 'outer;
+                        }
+                        log::info!("PipeConsumer '{}': reader closed ({}), reopening", name, e);
+                        thread::sleep(REOPEN_DELAY);
+                        continue 'outer;
+                    }
+                    let len: usize = chunks.iter().map(Vec::len).sum();
+                    bytes_written.fetch_add(len as u64, Ordering::Relaxed);
+                    frames_processed.fetch_add(1, Ordering::Relaxed);
+                }
+
+                if let Some(encoder) = encoder.as_mut() {
+                    if let Ok(rest) = encoder.flush() {
+                        for packet in rest {
+                            let _ = writer.write_all(&packet.payload);
+                        }
+                        let _ = writer.flush();
+                    }
+                }
+            }
+            connected.store(false, Ordering::Relaxed);
+            running.store(false, Ordering::SeqCst);
+        }));
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        self.running.store(false, Ordering::SeqCst);
+        self.wait.notify_all();
+        if let Some(handle) = self.thread_handle.take() {
+            // Nur joinen, wenn der Thread nicht beim Öffnen der FIFO hängt
+            if handle.is_finished() || self.connected.load(Ordering::Relaxed) {
+                if handle.join().is_err() {
+                    self.errors.fetch_add(1, Ordering::Relaxed);
+                }
+            } else {
+                log::debug!("PipeConsumer '{}': writer still waiting for a reader, detaching", self.name);
+            }
+        }
+        Ok(())
+    }
+
+    fn status(&self) -> ConsumerStatus {
+        ConsumerStatus {
+            running: self.running.load(Ordering::Relaxed),
+            connected: self.connected.load(Ordering::Relaxed),
+            frames_processed: self.frames_processed.load(Ordering::Relaxed),
+            bytes_written: self.bytes_written.load(Ordering::Relaxed),
+            errors: self.errors.load(Ordering::Relaxed),
+            connection: None,
+        }
+    }
+
+    fn attach_input_buffer(&mut self, buffer: Arc<AudioRingBuffer>) {
+        self.input_buffer = Some(buffer);
+    }
+}
+
+impl_connectable_consumer!(PipeConsumer);
@@ -0,0 +1,1244 @@
+// src/consumers/rtmp.rs
+//
+// RTMP-Ausgang (`rtmp_out`): kodiert die Flow-Frames als AAC oder MP3, packt
+// sie in FLV-Audio-Tags und publiziert sie per RTMP an einen Ingest (YouTube,
+// Twitch, nginx-rtmp, ...). Handshake, Chunking und AMF0-Kommandos sind hier
+// selbst implementiert, nur das Nötigste für einen Publisher. Abbrüche führen
+// wie beim Icecast-Ausgang zu Reconnects mit Backoff, der Zustand steht in
+// `ConsumerStatus::connection`. `rtmps://` läuft über `consumers::tls`.
+use crate::impl_connectable_consumer;
+use std::collections::HashMap;
+use std::io::{ErrorKind, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, bail, Context, Result};
+
+use crate::codecs::{create_encoder, CodecInfo, CodecKind};
+use crate::config::{ConfigValues, ConsumerConfig};
+use crate::consumers::tls::{self, ConsumerStream, TlsOptions};
+use crate::core::idle::IdleBackoff;
+use crate::core::lock::lock_mutex;
+use crate::core::{AudioRingBuffer, ConnectionPhase, ConnectionState, Consumer, ConsumerStatus};
+use crate::producers::wait::StopWait;
+
+pub const DEFAULT_PORT: u16 = 1935;
+const RTMPS_PORT: u16 = 443;
+const DEFAULT_RECONNECT: Duration = Duration::from_secs(1);
+const DEFAULT_MAX_RECONNECT: Duration = Duration::from_secs(30);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(10);
+const WRITE_TIMEOUT: Duration = Duration::from_secs(2);
+/// Kürzestes Warten auf Frames, wächst im Leerlauf (`IdleBackoff`)
+const POLL_INTERVAL: Duration = Duration::from_millis(2);
+/// Lesetimeout während des Sendens: nur anstehende Server-Nachrichten abholen
+const POLL_TIMEOUT: Duration = Duration::from_millis(1);
+const HANDSHAKE_SIZE: usize = 1536;
+/// Eigene Chunk-Größe (Standard im Protokoll: 128)
+pub const CHUNK_SIZE: usize = 4096;
+/// Größere Nachrichten vom Server gelten als Protokollfehler
+const MAX_MESSAGE_LEN: usize = 1024 * 1024;
+
+const CSID_CONTROL: u32 = 2;
+const CSID_COMMAND: u32 = 3;
+const CSID_AUDIO: u32 = 4;
+
+pub const MSG_SET_CHUNK_SIZE: u8 = 1;
+pub const MSG_ACK: u8 = 3;
+pub const MSG_USER_CONTROL: u8 = 4;
+pub const MSG_WINDOW_ACK_SIZE: u8 = 5;
+pub const MSG_AUDIO: u8 = 8;
+pub const MSG_DATA_AMF0: u8 = 18;
+pub const MSG_COMMAND_AMF0: u8 = 20;
+
+const FLV_SOUND_AAC: u8 = 10;
+const FLV_SOUND_MP3: u8 = 2;
+pub(crate) const AAC_FREQUENCIES: [u32; 13] = [
+    96_000, 88_200, 64_000, 48_000, 44_100, 32_000, 24_000, 22_050, 16_000, 12_000, 11_025, 8_000,
+    7_350,
+];
+
+/// Zerlegte `rtmp://` bzw. `rtmps://`-URL.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RtmpUrl {
+    pub tls: bool,
+    pub host: String,
+    pub port: u16,
+    /// Application, z. B. `live2` bei YouTube
+    pub app: String,
+}
+
+impl RtmpUrl {
+    /// `tcUrl` für das `connect`-Kommando (ohne Stream-Key).
+    pub fn tc_url(&self) -> String {
+        let scheme = if self.tls { "rtmps" } else { "rtmp" };
+        format!("{}://{}:{}/{}", scheme, self.host, self.port, self.app)
+    }
+}
+
+/// Zerlegt die URL; ohne separaten Stream-Key gilt das letzte Pfadsegment
+/// als Key (`rtmp://live.twitch.tv/app/<key>`).
+pub fn parse_rtmp_url(url: &str, has_key: bool) -> Result<(RtmpUrl, Option<String>)> {
+    let (tls, rest) = if let Some(rest) = url.strip_prefix("rtmps://") {
+        (true, rest)
+    } else if let Some(rest) = url.strip_prefix("rtmp://") {
+        (false, rest)
+    } else {
+        bail!("'{}' is not an rtmp:// or rtmps:// URL", url);
+    };
+    let (authority, path) = rest.split_once('/').unwrap_or((rest, ""));
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((host, port)) => (
+            host,
+            port.parse::<u16>()
+                .ok()
+                .filter(|port| *port > 0)
+                .ok_or_else(|| anyhow!("invalid port in '{}'", url))?,
+        ),
+        None => (authority, if tls { RTMPS_PORT } else { DEFAULT_PORT }),
+    };
+    if host.is_empty() {
+        bail!("missing host in '{}'", url);
+    }
+
+    let path = path.trim_matches('/');
+    let (app, key) = match path.rsplit_once('/') {
+        Some((app, key)) if !has_key => (app, Some(key.to_string())),
+        _ => (path, None),
+    };
+    if app.is_empty() {
+        bail!("missing application in '{}'", url);
+    }
+    Ok((
+        RtmpUrl {
+            tls,
+            host: host.to_string(),
+            port,
+            app: app.to_string(),
+        },
+        key,
+    ))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RtmpOutputConfig {
+    pub url: RtmpUrl,
+    pub stream_key: String,
+    /// Codec-ID wie in `supported_codecs`, nur AAC oder MP3
+    pub codec: String,
+    pub reconnect: Duration,
+    pub max_reconnect: Duration,
+    /// Bei `rtmps://` gesetzt
+    pub tls: Option<TlsOptions>,
+}
+
+/// Gelesene `config`-Schlüssel (Strict-Modus, siehe `config::options`)
+pub const CONFIG_KEYS: &[&str] = &[
+    "url",
+    "stream_key",
+    "codec",
+    "codec_id",
+    "reconnect",
+    "max_reconnect",
+];
+
+impl RtmpOutputConfig {
+    /// Erwartet `url` (oder `url` am Consumer) und `stream_key`, sofern der
+    /// Key nicht in der URL steht; optional `codec` (Standard `aaclc`).
+    pub fn from_config(name: &str, config: &ConsumerConfig) -> Result<Self> {
+        let values = ConfigValues::new("consumer", name, &config.config);
+        let text = |key: &str| -> Option<String> {
+            config
+                .config
+                .get(key)
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+
+        let url = text("url")
+            .or_else(|| config.url.clone())
+            .ok_or_else(|| anyhow!("consumer '{}': rtmp_out needs config.url", name))?;
+        let stream_key = text("stream_key");
+        let (url, key_from_url) = parse_rtmp_url(&url, stream_key.is_some())
+            .with_context(|| format!("consumer '{}': config.url", name))?;
+        let tls_options = TlsOptions::from_config(name, &config.config)?;
+        let tls = if url.tls {
+            tls::ensure_supported(name)?;
+            Some(tls_options)
+        } else if tls_options.is_configured() {
+            bail!("consumer '{}': tls_* options need an rtmps:// url", name);
+        } else {
+            None
+        };
+        let stream_key = stream_key
+            .or(key_from_url)
+            .ok_or_else(|| anyhow!("consumer '{}': rtmp_out needs config.stream_key", name))?;
+        if stream_key
+            .chars()
+            .any(|c| c.is_whitespace() || c.is_control())
+        {
+            bail!(
+                "consumer '{}': config.stream_key must not contain whitespace",
+                name
+            );
+        }
+
+        let codec = text("codec")
+            .or_else(|| text("codec_id"))
+            .unwrap_or_else(|| "aaclc".to_string())
+            .to_ascii_lowercase();
+        let info = create_encoder(&codec)
+            .with_context(|| format!("consumer '{}'", name))?
+            .info()
+            .clone();
+        flv_sound_format(&info).ok_or_else(|| {
+            anyhow!(
+                "consumer '{}': codec '{}' cannot be carried in FLV (use aaclc or mp3)",
+                name,
+                codec
+            )
+        })?;
+
+        let reconnect = values.duration("reconnect")?.unwrap_or(DEFAULT_RECONNECT);
+        values.check_range("reconnect", reconnect.as_millis() as u64, 100, 60_000)?;
+        let max_reconnect = values
+            .duration("max_reconnect")?
+            .unwrap_or(DEFAULT_MAX_RECONNECT)
+            .max(reconnect);
+        values.check_range(
+            "max_reconnect",
+            max_reconnect.as_millis() as u64,
+            100,
+            600_000,
+        )?;
+
+        Ok(Self {
+            url,
+            stream_key,
+            codec,
+            reconnect,
+            max_reconnect,
+            tls,
+        })
+    }
+
+    /// Ziel ohne Stream-Key (landet in Status und Logs).
+    pub fn endpoint(&self) -> String {
+        self.url.tc_url()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// FLV
+
+fn flv_sound_format(info: &CodecInfo) -> Option<u8> {
+    match info.kind {
+        CodecKind::AacLc => Some(FLV_SOUND_AAC),
+        CodecKind::Mp3 => Some(FLV_SOUND_MP3),
+        _ => None,
+    }
+}
+
+/// Erstes Byte eines FLV-Audio-Tags. AAC verlangt immer 44 kHz/Stereo, die
+/// echten Werte stehen in der AudioSpecificConfig.
+pub fn flv_audio_header(info: &CodecInfo) -> Option<u8> {
+    let format = flv_sound_format(info)?;
+    if format == FLV_SOUND_AAC {
+        return Some(0xAF);
+    }
+    let rate = match info.sample_rate {
+        0..=8_000 => 0,
+        8_001..=11_025 => 1,
+        11_026..=22_050 => 2,
+        _ => 3,
+    };
+    let stereo = u8::from(info.channels > 1);
+    Some(format << 4 | rate << 2 | 1 << 1 | stereo)
+}
+
+/// AudioSpecificConfig (AAC-LC) für den Sequence-Header.
+pub fn aac_audio_specific_config(sample_rate: u32, channels: u8) -> Result<[u8; 2]> {
+    let index = AAC_FREQUENCIES
+        .iter()
+        .position(|rate| *rate == sample_rate)
+        .ok_or_else(|| anyhow!("AAC does not support {} Hz", sample_rate))? as u8;
+    if !(1..=7).contains(&channels) {
+        bail!("AAC does not support {} channels", channels);
+    }
+    // objectType 2 (LC, 5 Bit), Frequenzindex (4 Bit), Kanäle (4 Bit)
+    Ok([2 << 3 | index >> 1, (index & 1) << 7 | channels << 3])
+}
+
+/// Zerlegt ADTS-Daten in rohe AAC-Frames (FLV will keine ADTS-Header);
+/// ohne Sync-Wort gilt die Payload als ein roher Frame.
+pub fn split_adts(payload: &[u8]) -> Vec<&[u8]> {
+    let is_adts = |data: &[u8]| data.len() >= 7 && data[0] == 0xFF && data[1] & 0xF6 == 0xF0;
+    if !is_adts(payload) {
+        return vec![payload];
+    }
+    let mut frames = Vec::new();
+    let mut rest = payload;
+    while is_adts(rest) {
+        let header_len = if rest[1] & 0x01 == 0 { 9 } else { 7 };
+        let frame_len =
+            ((rest[3] as usize & 0x03) << 11) | (rest[4] as usize) << 3 | (rest[5] as usize) >> 5;
+        if frame_len < header_len || frame_len > rest.len() {
+            break;
+        }
+        frames.push(&rest[header_len..frame_len]);
+        rest = &rest[frame_len..];
+    }
+    frames
+}
+
+// ---------------------------------------------------------------------------
+// AMF0
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Amf0 {
+    Number(f64),
+    Bool(bool),
+    String(String),
+    Object(Vec<(String, Amf0)>),
+    EcmaArray(Vec<(String, Amf0)>),
+    Array(Vec<Amf0>),
+    Null,
+}
+
+impl Amf0 {
+    pub fn encode(&self, out: &mut Vec<u8>) {
+        match self {
+            Amf0::Number(value) => {
+                out.push(0x00);
+                out.extend_from_slice(&value.to_be_bytes());
+            }
+            Amf0::Bool(value) => {
+                out.push(0x01);
+                out.push(u8::from(*value));
+            }
+            Amf0::String(value) if value.len() > u16::MAX as usize => {
+                out.push(0x0C);
+                out.extend_from_slice(&(value.len() as u32).to_be_bytes());
+                out.extend_from_slice(value.as_bytes());
+            }
+            Amf0::String(value) => {
+                out.push(0x02);
+                encode_amf_key(value, out);
+            }
+            Amf0::Object(fields) => {
+                out.push(0x03);
+                encode_amf_fields(fields, out);
+            }
+            Amf0::EcmaArray(fields) => {
+                out.push(0x08);
+                out.extend_from_slice(&(fields.len() as u32).to_be_bytes());
+                encode_amf_fields(fields, out);
+            }
+            Amf0::Array(values) => {
+                out.push(0x0A);
+                out.extend_from_slice(&(values.len() as u32).to_be_bytes());
+                for value in values {
+                    value.encode(out);
+                }
+            }
+            Amf0::Null => out.push(0x05),
+        }
+    }
+
+    /// Liest alle Werte einer Kommando-Nachricht.
+    pub fn decode_all(mut data: &[u8]) -> Result<Vec<Amf0>> {
+        let mut values = Vec::new();
+        while !data.is_empty() {
+            values.push(Self::decode(&mut data)?);
+        }
+        Ok(values)
+    }
+
+    pub fn decode(data: &mut &[u8]) -> Result<Amf0> {
+        let marker = take(data, 1)?[0];
+        Ok(match marker {
+            0x00 => Amf0::Number(f64::from_be_bytes(take(data, 8)?.try_into()?)),
+            0x01 => Amf0::Bool(take(data, 1)?[0] != 0),
+            0x02 => Amf0::String(decode_amf_key(data)?),
+            0x03 => Amf0::Object(decode_amf_fields(data)?),
+            0x05 | 0x06 => Amf0::Null,
+            0x08 => {
+                take(data, 4)?;
+                Amf0::EcmaArray(decode_amf_fields(data)?)
+            }
+            0x0A => {
+                let count = u32::from_be_bytes(take(data, 4)?.try_into()?) as usize;
+                let mut values = Vec::with_capacity(count.min(64));
+                for _ in 0..count {
+                    values.push(Self::decode(data)?);
+                }
+                Amf0::Array(values)
+            }
+            0x0C => {
+                let len = u32::from_be_bytes(take(data, 4)?.try_into()?) as usize;
+                Amf0::String(String::from_utf8_lossy(take(data, len)?).into_owned())
+            }
+            other => bail!("unsupported AMF0 type 0x{:02x}", other),
+        })
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Amf0::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Amf0::Number(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Feld eines Objekts bzw. ECMA-Arrays.
+    pub fn get(&self, key: &str) -> Option<&Amf0> {
+        match self {
+            Amf0::Object(fields) | Amf0::EcmaArray(fields) => fields
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+}
+
+fn take<'a>(data: &mut &'a [u8], len: usize) -> Result<&'a [u8]> {
+    if data.len() < len {
+        bail!("truncated AMF0 data");
+    }
+    let (head, tail) = data.split_at(len);
+    *data = tail;
+    Ok(head)
+}
+
+fn encode_amf_key(key: &str, out: &mut Vec<u8>) {
+    let bytes = &key.as_bytes()[..key.len().min(u16::MAX as usize)];
+    out.extend_from_slice(&(bytes.len() as u16).to_be_bytes());
+    out.extend_from_slice(bytes);
+}
+
+fn encode_amf_fields(fields: &[(String, Amf0)], out: &mut Vec<u8>) {
+    for (key, value) in fields {
+        encode_amf_key(key, out);
+        value.encode(out);
+    }
+    out.extend_from_slice(&[0x00, 0x00, 0x09]);
+}
+
+fn decode_amf_key(data: &mut &[u8]) -> Result<String> {
+    let len = u16::from_be_bytes(take(data, 2)?.try_into()?) as usize;
+    Ok(String::from_utf8_lossy(take(data, len)?).into_owned())
+}
+
+fn decode_amf_fields(data: &mut &[u8]) -> Result<Vec<(String, Amf0)>> {
+    let mut fields = Vec::new();
+    loop {
+        let key = decode_amf_key(data)?;
+        if key.is_empty() && data.first() == Some(&0x09) {
+            take(data, 1)?;
+            return Ok(fields);
+        }
+        fields.push((key, Amf0::decode(data)?));
+    }
+}
+
+/// Kommando-Nachricht: Name, Transaktions-ID, Argumente.
+pub fn encode_command(name: &str, transaction: f64, args: &[Amf0]) -> Vec<u8> {
+    let mut out = Vec::new();
+    Amf0::String(name.to_string()).encode(&mut out);
+    Amf0::Number(transaction).encode(&mut out);
+    for arg in args {
+        arg.encode(&mut out);
+    }
+    out
+}
+
+// ---------------------------------------------------------------------------
+// Chunking
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RtmpMessage {
+    pub csid: u32,
+    pub timestamp: u32,
+    pub type_id: u8,
+    pub stream_id: u32,
+    pub payload: Vec<u8>,
+}
+
+/// Zerlegt eine Nachricht in Chunks (Typ 0, danach Typ-3-Fortsetzungen).
+pub fn write_message(out: &mut Vec<u8>, chunk_size: usize, message: &RtmpMessage) {
+    let extended = message.timestamp >= 0xFF_FFFF;
+    let basic = |fmt: u8, out: &mut Vec<u8>| {
+        // csid < 64 reicht für die eigenen Chunk-Streams
+        out.push(fmt << 6 | (message.csid as u8 & 0x3F));
+        if extended {
+            out.extend_from_slice(&message.timestamp.to_be_bytes());
+        }
+    };
+
+    out.push((message.csid as u8) & 0x3F);
+    out.extend_from_slice(&message.timestamp.min(0xFF_FFFF).to_be_bytes()[1..]);
+    out.extend_from_slice(&(message.payload.len() as u32).to_be_bytes()[1..]);
+    out.push(message.type_id);
+    out.extend_from_slice(&message.stream_id.to_le_bytes());
+    if extended {
+        out.extend_from_slice(&message.timestamp.to_be_bytes());
+    }
+
+    let mut chunks = message.payload.chunks(chunk_size.max(1));
+    if let Some(first) = chunks.next() {
+        out.extend_from_slice(first);
+    }
+    for chunk in chunks {
+        basic(3, out);
+        out.extend_from_slice(chunk);
+    }
+}
+
+#[derive(Debug, Default)]
+struct ChunkStream {
+    timestamp: u32,
+    delta: u32,
+    length: usize,
+    type_id: u8,
+    stream_id: u32,
+    extended: bool,
+    payload: Vec<u8>,
+}
+
+static EMPTY_STREAM: ChunkStream = ChunkStream {
+    timestamp: 0,
+    delta: 0,
+    length: 0,
+    type_id: 0,
+    stream_id: 0,
+    extended: false,
+    payload: Vec::new(),
+};
+
+/// Setzt eingehende Chunks wieder zu Nachrichten zusammen.
+pub struct ChunkReader {
+    buffer: Vec<u8>,
+    chunk_size: usize,
+    streams: HashMap<u32, ChunkStream>,
+}
+
+impl Default for ChunkReader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChunkReader {
+    pub fn new() -> Self {
+        Self {
+            buffer: Vec::new(),
+            chunk_size: 128,
+            streams: HashMap::new(),
+        }
+    }
+
+    pub fn set_chunk_size(&mut self, chunk_size: usize) {
+        self.chunk_size = chunk_size.max(1);
+    }
+
+    pub fn feed(&mut self, data: &[u8]) {
+        self.buffer.extend_from_slice(data);
+    }
+
+    /// Nächste vollständige Nachricht; `Ok(None)` = es fehlen noch Bytes.
+    pub fn next_message(&mut self) -> Result<Option<RtmpMessage>> {
+        loop {
+            let Some((consumed, csid, complete)) = self.parse_chunk()? else {
+                return Ok(None);
+            };
+            self.buffer.drain(..consumed);
+            if !complete {
+                continue;
+            }
+            let Some(stream) = self.streams.get_mut(&csid) else {
+                continue;
+            };
+            let message = RtmpMessage {
+                csid,
+                timestamp: stream.timestamp,
+                type_id: stream.type_id,
+                stream_id: stream.stream_id,
+                payload: std::mem::take(&mut stream.payload),
+            };
+            if message.type_id == MSG_SET_CHUNK_SIZE && message.payload.len() >= 4 {
+                let size = u32::from_be_bytes(message.payload[..4].try_into()?) & 0x7FFF_FFFF;
+                self.set_chunk_size(size as usize);
+            }
+            return Ok(Some(message));
+        }
+    }
+
+    /// Liest einen Chunk und übernimmt ihn in den Chunk-Stream, aber nur wenn
+    /// er vollständig im Puffer liegt; liefert verbrauchte Bytes, Chunk-Stream
+    /// und ob die Nachricht komplett ist.
+    fn parse_chunk(&mut self) -> Result<Option<(usize, u32, bool)>> {
+        let data = &self.buffer;
+        let Some(&first) = data.first() else {
+            return Ok(None);
+        };
+        let fmt = first >> 6;
+        let (csid, mut pos) = match first & 0x3F {
+            0 if data.len() >= 2 => (64 + data[1] as u32, 2),
+            1 if data.len() >= 3 => (64 + data[1] as u32 + data[2] as u32 * 256, 3),
+            0 | 1 => return Ok(None),
+            csid => (csid as u32, 1),
+        };
+        let header_len = [11, 7, 3, 0][fmt as usize];
+        if data.len() < pos + header_len {
+            return Ok(None);
+        }
+        let u24 = |at: usize| u32::from_be_bytes([0, data[at], data[at + 1], data[at + 2]]);
+
+        let Some(previous) =
+            self.streams
+                .get(&csid)
+                .or(if fmt == 0 { Some(&EMPTY_STREAM) } else { None })
+        else {
+            bail!("chunk stream {} continues without a header", csid);
+        };
+        // Nur den Header kopieren, die Payload wird erst beim Übernehmen ergänzt
+        let mut stream = ChunkStream {
+            payload: Vec::new(),
+            ..*previous
+        };
+        let partial = previous.payload.len();
+        let starting = partial == 0;
+        let mut time_field = None;
+        if fmt <= 2 {
+            time_field = Some(u24(pos));
+        }
+        if fmt <= 1 {
+            stream.length = u24(pos + 3) as usize;
+            stream.type_id = data[pos + 6];
+        }
+        if fmt == 0 {
+            stream.stream_id = u32::from_le_bytes(data[pos + 7..pos + 11].try_into()?);
+        }
+        pos += header_len;
+
+        let extended = match time_field {
+            Some(value) => value == 0xFF_FFFF,
+            None => stream.extended,
+        };
+        let time = if extended {
+            if data.len() < pos + 4 {
+                return Ok(None);
+            }
+            let value = u32::from_be_bytes(data[pos..pos + 4].try_into()?);
+            pos += 4;
+            value
+        } else {
+            time_field.unwrap_or(stream.delta)
+        };
+        if time_field.is_some() {
+            stream.extended = extended;
+        }
+
+        if stream.length > MAX_MESSAGE_LEN {
+            bail!("message of {} bytes exceeds limit", stream.length);
+        }
+        let remaining = stream.length.saturating_sub(partial);
+        let len = remaining.min(self.chunk_size);
+        if data.len() < pos + len {
+            return Ok(None);
+        }
+        if starting {
+            if fmt == 0 {
+                stream.timestamp = time;
+                stream.delta = 0;
+            } else {
+                stream.delta = time;
+                stream.timestamp = stream.timestamp.wrapping_add(time);
+            }
+        }
+        let complete = partial + len >= stream.length;
+        let chunk = &data[pos..pos + len];
+        let entry = self.streams.entry(csid).or_default();
+        stream.payload = std::mem::take(&mut entry.payload);
+        stream.payload.extend_from_slice(chunk);
+        *entry = stream;
+        Ok(Some((pos + len, csid, complete)))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Session
+
+/// Bytes für C1 bzw. Handshake-Zufall, ohne eigene RNG-Abhängigkeit.
+fn handshake_noise(len: usize) -> Vec<u8> {
+    let mut state = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|time| time.as_nanos() as u64)
+        .unwrap_or(0x9E37_79B9_7F4A_7C15)
+        | 1;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as u8
+        })
+        .collect()
+}
+
+/// Verbundene, publizierende RTMP-Sitzung.
+pub struct RtmpSession {
+    stream: ConsumerStream,
+    reader: ChunkReader,
+    stream_id: u32,
+    /// Vom Server gewünschtes Acknowledgement-Fenster
+    window: u32,
+    received: u64,
+    acked: u64,
+    sound_header: u8,
+    aac: bool,
+}
+
+impl RtmpSession {
+    /// Handshake, `connect`, `createStream`, `publish`; danach Metadaten und
+    /// bei AAC der Sequence-Header.
+    pub fn connect(config: &RtmpOutputConfig, info: &CodecInfo) -> Result<Self> {
+        let sound_header = flv_audio_header(info)
+            .ok_or_else(|| anyhow!("codec {:?} cannot be carried in FLV", info.kind))?;
+        let addr = (config.url.host.as_str(), config.url.port)
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| anyhow!("'{}' did not resolve", config.url.host))?;
+        let tcp = TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT)?;
+        tcp.set_nodelay(true)?;
+        tcp.set_write_timeout(Some(WRITE_TIMEOUT))?;
+        tcp.set_read_timeout(Some(RESPONSE_TIMEOUT))?;
+        let mut stream = match &config.tls {
+            Some(options) => tls::wrap(tcp, &config.url.host, options)?,
+            None => ConsumerStream::Plain(tcp),
+        };
+        handshake(&mut stream)?;
+
+        let mut session = Self {
+            stream,
+            reader: ChunkReader::new(),
+            stream_id: 0,
+            window: 0,
+            received: 0,
+            acked: 0,
+            sound_header,
+            aac: matches!(info.kind, CodecKind::AacLc),
+        };
+        session.send(
+            CSID_CONTROL,
+            0,
+            MSG_SET_CHUNK_SIZE,
+            0,
+            &(CHUNK_SIZE as u32).to_be_bytes(),
+        )?;
+
+        let connect = encode_command(
+            "connect",
+            1.0,
+            &[Amf0::Object(vec![
+                ("app".into(), Amf0::String(config.url.app.clone())),
+                ("type".into(), Amf0::String("nonprivate".into())),
+                (
+                    "flashVer".into(),
+                    Amf0::String(format!(
+                        "FMLE/3.0 (compatible; airlift-node/{})",
+                        env!("CARGO_PKG_VERSION")
+                    )),
+                ),
+                ("tcUrl".into(), Amf0::String(config.url.tc_url())),
+            ])],
+        );
+        session.send(CSID_COMMAND, 0, MSG_COMMAND_AMF0, 0, &connect)?;
+        session.wait_for_result(1.0, "connect")?;
+
+        let key = Amf0::String(config.stream_key.clone());
+        for (transaction, name) in [(2.0, "releaseStream"), (3.0, "FCPublish")] {
+            let command = encode_command(name, transaction, &[Amf0::Null, key.clone()]);
+            session.send(CSID_COMMAND, 0, MSG_COMMAND_AMF0, 0, &command)?;
+        }
+        let command = encode_command("createStream", 4.0, &[Amf0::Null]);
+        session.send(CSID_COMMAND, 0, MSG_COMMAND_AMF0, 0, &command)?;
+        let result = session.wait_for_result(4.0, "createStream")?;
+        session.stream_id = result
+            .get(3)
+            .and_then(Amf0::as_f64)
+            .ok_or_else(|| anyhow!("createStream returned no stream id"))?
+            as u32;
+
+        let publish = encode_command(
+            "publish",
+            5.0,
+            &[Amf0::Null, key, Amf0::String("live".into())],
+        );
+        session.send(CSID_AUDIO, 0, MSG_COMMAND_AMF0, session.stream_id, &publish)?;
+        session.wait_for_publish()?;
+
+        let mut metadata = Vec::new();
+        for value in [
+            Amf0::String("@setDataFrame".into()),
+            Amf0::String("onMetaData".into()),
+            Amf0::EcmaArray(vec![
+                (
+                    "audiocodecid".into(),
+                    Amf0::Number((sound_header >> 4) as f64),
+                ),
+                (
+                    "audiosamplerate".into(),
+                    Amf0::Number(info.sample_rate as f64),
+                ),
+                ("audiosamplesize".into(), Amf0::Number(16.0)),
+                ("audiochannels".into(), Amf0::Number(info.channels as f64)),
+                ("stereo".into(), Amf0::Bool(info.channels > 1)),
+                (
+                    "encoder".into(),
+                    Amf0::String(format!("airlift-node/{}", env!("CARGO_PKG_VERSION"))),
+                ),
+            ]),
+        ] {
+            value.encode(&mut metadata);
+        }
+        session.send(CSID_AUDIO, 0, MSG_DATA_AMF0, session.stream_id, &metadata)?;
+
+        if session.aac {
+            let config = aac_audio_specific_config(info.sample_rate, info.channels)?;
+            session.send(
+                CSID_AUDIO,
+                0,
+                MSG_AUDIO,
+                session.stream_id,
+                &[sound_header, 0, config[0], config[1]],
+            )?;
+        }
+        session.stream.set_read_timeout(Some(POLL_TIMEOUT))?;
+        Ok(session)
+    }
+
+    /// Schickt einen kodierten Frame als FLV-Audio-Tag.
+    pub fn send_audio(&mut self, timestamp_ms: u32, frame: &[u8]) -> Result<usize> {
+        let mut body = Vec::with_capacity(frame.len() + 2);
+        body.push(self.sound_header);
+        if self.aac {
+            body.push(1);
+        }
+        body.extend_from_slice(frame);
+        self.send(CSID_AUDIO, timestamp_ms, MSG_AUDIO, self.stream_id, &body)?;
+        Ok(body.len())
+    }
+
+    /// Holt anstehende Server-Nachrichten ab (Pings, Fehler), ohne zu warten.
+    pub fn poll(&mut self) -> Result<()> {
+        while let Some(message) = self.read_message(false)? {
+            if message.type_id == MSG_COMMAND_AMF0 {
+                let values = Amf0::decode_all(&message.payload).unwrap_or_default();
+                if let Some(status) = values.get(3) {
+                    check_status(status)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn send(
+        &mut self,
+        csid: u32,
+        timestamp: u32,
+        type_id: u8,
+        stream_id: u32,
+        payload: &[u8],
+    ) -> Result<()> {
+        let mut out = Vec::with_capacity(payload.len() + 32);
+        write_message(
+            &mut out,
+            CHUNK_SIZE,
+            &RtmpMessage {
+                csid,
+                timestamp,
+                type_id,
+                stream_id,
+                payload: payload.to_vec(),
+            },
+        );
+        self.stream.write_all(&out).context("send failed")?;
+        Ok(())
+    }
+
+    /// Nächste Nachricht, Protokollsteuerung wird hier erledigt. Mit
+    /// `block = false` liefert ein Lese-Timeout `None`.
+    fn read_message(&mut self, block: bool) -> Result<Option<RtmpMessage>> {
+        loop {
+            if let Some(message) = self.reader.next_message()? {
+                match message.type_id {
+                    MSG_WINDOW_ACK_SIZE if message.payload.len() >= 4 => {
+                        self.window = u32::from_be_bytes(message.payload[..4].try_into()?);
+                    }
+                    // Ping-Request (6) mit Ping-Response (7) beantworten
+                    MSG_USER_CONTROL if message.payload.starts_with(&[0, 6]) => {
+                        let mut response = vec![0, 7];
+                        response.extend_from_slice(&message.payload[2..]);
+                        self.send(CSID_CONTROL, 0, MSG_USER_CONTROL, 0, &response)?;
+                    }
+                    _ => return Ok(Some(message)),
+                }
+                continue;
+            }
+
+            let mut chunk = [0u8; 4096];
+            let read = match self.stream.read(&mut chunk) {
+                Ok(0) => bail!("server closed the connection"),
+                Ok(read) => read,
+                Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {
+                    if block {
+                        bail!("timed out waiting for server response");
+                    }
+                    return Ok(None);
+                }
+                Err(e) => return Err(e).context("receive failed"),
+            };
+            self.reader.feed(&chunk[..read]);
+            self.received += read as u64;
+            if self.window > 0 && self.received - self.acked >= self.window as u64 {
+                self.acked = self.received;
+                let sequence = (self.received as u32).to_be_bytes();
+                self.send(CSID_CONTROL, 0, MSG_ACK, 0, &sequence)?;
+            }
+        }
+    }
+
+    /// Wartet auf `_result`/`_error` zur Transaktion; andere Kommandos
+    /// (`onBWDone`, ...) werden übergangen.
+    fn wait_for_result(&mut self, transaction: f64, command: &str) -> Result<Vec<Amf0>> {
+        loop {
+            let Some(message) = self.read_message(true)? else {
+                continue;
+            };
+            if message.type_id != MSG_COMMAND_AMF0 {
+                continue;
+            }
+            let values = Amf0::decode_all(&message.payload)?;
+            if values.get(1).and_then(Amf0::as_f64) != Some(transaction) {
+                continue;
+            }
+            match values.first().and_then(Amf0::as_str) {
+                Some("_result") => return Ok(values),
+                Some("_error") => {
+                    let detail = values
+                        .get(3)
+                        .and_then(|info| info.get("description").or_else(|| info.get("code")))
+                        .and_then(Amf0::as_str)
+                        .unwrap_or("no details");
+                    bail!("{} rejected: {}", command, detail);
+                }
+                _ => continue,
+            }
+        }
+    }
+
+    fn wait_for_publish(&mut self) -> Result<()> {
+        loop {
+            let Some(message) = self.read_message(true)? else {
+                continue;
+            };
+            if message.type_id != MSG_COMMAND_AMF0 {
+                continue;
+            }
+            let values = Amf0::decode_all(&message.payload)?;
+            if values.first().and_then(Amf0::as_str) != Some("onStatus") {
+                continue;
+            }
+            let Some(status) = values.get(3) else {
+                continue;
+            };
+            check_status(status)?;
+            if status.get("code").and_then(Amf0::as_str) == Some("NetStream.Publish.Start") {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// `onStatus` mit `level = "error"` (z. B. `NetStream.Publish.BadName` bei
+/// falschem Stream-Key) wird zum Fehler.
+fn check_status(status: &Amf0) -> Result<()> {
+    if status.get("level").and_then(Amf0::as_str) != Some("error") {
+        return Ok(());
+    }
+    let code = status
+        .get("code")
+        .and_then(Amf0::as_str)
+        .unwrap_or("unknown");
+    match status.get("description").and_then(Amf0::as_str) {
+        Some(description) => bail!("{}: {}", code, description),
+        None => bail!("{}", code),
+    }
+}
+
+fn handshake(stream: &mut ConsumerStream) -> Result<()> {
+    let mut c0c1 = Vec::with_capacity(1 + HANDSHAKE_SIZE);
+    c0c1.push(3);
+    c0c1.extend_from_slice(&[0; 8]);
+    c0c1.extend_from_slice(&handshake_noise(HANDSHAKE_SIZE - 8));
+    stream.write_all(&c0c1).context("handshake failed")?;
+
+    let mut s0s1 = vec![0u8; 1 + HANDSHAKE_SIZE];
+    stream.read_exact(&mut s0s1).context("handshake failed")?;
+    if s0s1[0] != 3 {
+        bail!("server speaks RTMP version {}, expected 3", s0s1[0]);
+    }
+    // C2 = Echo von S1
+    stream.write_all(&s0s1[1..]).context("handshake failed")?;
+    let mut s2 = vec![0u8; HANDSHAKE_SIZE];
+    stream.read_exact(&mut s2).context("handshake failed")?;
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Consumer
+
+pub struct RtmpOutputConsumer {
+    name: String,
+    config: RtmpOutputConfig,
+    running: Arc<AtomicBool>,
+    connected: Arc<AtomicBool>,
+    state: Arc<Mutex<ConnectionState>>,
+    input_buffer: Option<Arc<AudioRingBuffer>>,
+    reader_id: String,
+    wait: Arc<StopWait>,
+    thread_handle: Option<std::thread::JoinHandle<()>>,
+    frames_processed: Arc<AtomicU64>,
+    bytes_written: Arc<AtomicU64>,
+    errors: Arc<AtomicU64>,
+}
+
+impl RtmpOutputConsumer {
+    pub fn new(name: &str, config: &ConsumerConfig) -> Result<Self> {
+        Ok(Self::with_config(
+            name,
+            RtmpOutputConfig::from_config(name, config)?,
+        ))
+    }
+
+    pub fn with_config(name: &str, config: RtmpOutputConfig) -> Self {
+        let state = ConnectionState {
+            phase: ConnectionPhase::Stopped,
+            endpoint: config.endpoint(),
+            failed_attempts: 0,
+            retry_in_ms: None,
+            last_error: None,
+        };
+        Self {
+            name: name.to_string(),
+            config,
+            running: Arc::new(AtomicBool::new(false)),
+            connected: Arc::new(AtomicBool::new(false)),
+            state: Arc::new(Mutex::new(state)),
+            input_buffer: None,
+            reader_id: format!("consumer:{}", name),
+            wait: Arc::new(StopWait::new()),
+            thread_handle: None,
+            frames_processed: Arc::new(AtomicU64::new(0)),
+            bytes_written: Arc::new(AtomicU64::new(0)),
+            errors: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    pub fn config(&self) -> &RtmpOutputConfig {
+        &self.config
+    }
+}
+
+impl Consumer for RtmpOutputConsumer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn start(&mut self) -> Result<()> {
+        if self.running.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        let buffer = self
+            .input_buffer
+            .clone()
+            .ok_or_else(|| anyhow!("RtmpOutputConsumer '{}' missing input buffer", self.name))?;
+
+        self.running.store(true, Ordering::SeqCst);
+        let running = self.running.clone();
+        let connected = self.connected.clone();
+        let state = self.state.clone();
+        let wait = self.wait.clone();
+        let reader_id = self.reader_id.clone();
+        let frames_processed = self.frames_processed.clone();
+        let bytes_written = self.bytes_written.clone();
+        let errors = self.errors.clone();
+        let name = self.name.clone();
+        let config = self.config.clone();
+
+        log::info!(
+            "RtmpOutputConsumer '{}': publishing {} to {}",
+            name,
+            config.codec,
+            config.endpoint()
+        );
+
+        self.thread_handle = Some(std::thread::spawn(move || {
+            let mut backoff = config.reconnect;
+            while running.load(Ordering::Relaxed) {
+                {
+                    let mut state = lock_mutex(&state, "rtmp_out.state");
+                    state.phase = ConnectionPhase::Connecting;
+                    state.retry_in_ms = None;
+                }
+
+                // Neuer Encoder pro Verbindung, Zeitstempel beginnen bei 0
+                let session = create_encoder(&config.codec).and_then(|encoder| {
+                    let session = RtmpSession::connect(&config, encoder.info())?;
+                    Ok((encoder, session))
+                });
+                let (mut encoder, mut session) = match session {
+                    Ok(session) => session,
+                    Err(e) => {
+                        errors.fetch_add(1, Ordering::Relaxed);
+                        let mut state = lock_mutex(&state, "rtmp_out.state");
+                        if state.failed_attempts == 0 {
+                            log::warn!(
+                                "RtmpOutputConsumer '{}': publish to {} failed: {:#}",
+                                name,
+                                state.endpoint,
+                                e
+                            );
+                        }
+                        state.phase = ConnectionPhase::Backoff;
+                        state.failed_attempts = state.failed_attempts.saturating_add(1);
+                        state.retry_in_ms = Some(backoff.as_millis() as u64);
+                        state.last_error = Some(format!("{:#}", e));
+                        drop(state);
+                        wait.wait_timeout(backoff);
+                        backoff = (backoff * 2).min(config.max_reconnect);
+                        continue;
+                    }
+                };
+
+                log::info!(
+                    "RtmpOutputConsumer '{}': publishing to {}",
+                    name,
+                    config.endpoint()
+                );
+                backoff = config.reconnect;
+                {
+                    let mut state = lock_mutex(&state, "rtmp_out.state");
+                    state.phase = ConnectionPhase::Connected;
+                    state.failed_attempts = 0;
+                    state.retry_in_ms = None;
+                }
+                connected.store(true, Ordering::SeqCst);
+                // Live weitersenden, kein Aufholen alter Frames
+                buffer.skip_to_latest(&reader_id);
+
+                let sample_rate = encoder.info().sample_rate.max(1) as u64;
+                let samples_per_frame = match encoder.info().kind {
+                    CodecKind::Mp3 => 1152,
+                    _ => 1024,
+                };
+                let mut samples_sent = 0u64;
+                let mut failure = None;
+                let mut idle = IdleBackoff::new(POLL_INTERVAL);
+                while failure.is_none() && running.load(Ordering::Relaxed) {
+                    let Some(frame) = buffer.pop_for_reader(&reader_id) else {
+                        if let Err(e) = session.poll() {
+                            failure = Some(e);
+                            break;
+                        }
+                        idle.wait_for(&buffer, &wait);
+                        continue;
+                    };
+                    idle.reset();
+                    let encoded = match encoder.encode(&frame.samples) {
+                        Ok(encoded) => encoded,
+                        Err(e) => {
+                            errors.fetch_add(1, Ordering::Relaxed);
+                            log::debug!("RtmpOutputConsumer '{}': encode error: {}", name, e);
+                            continue;
+                        }
+                    };
+                    'packets: for packet in encoded {
+                        for raw in split_adts(&packet.payload) {
+                            let timestamp = (samples_sent * 1000 / sample_rate) as u32;
+                            match session.send_audio(timestamp, raw) {
+                                Ok(len) => {
+                                    bytes_written.fetch_add(len as u64, Ordering::Relaxed);
+                                }
+                                Err(e) => {
+                                    failure = Some(e);
+                                    break 'packets;
+                                }
+                            }
+                            samples_sent += samples_per_frame;
+                        }
+                    }
+                    frames_processed.fetch_add(1, Ordering::Relaxed);
+                }
+
+                connected.store(false, Ordering::SeqCst);
+                if let Some(e) = failure {
+                    log::warn!("RtmpOutputConsumer '{}': {:#}", name, e);
+                    errors.fetch_add(1, Ordering::Relaxed);
+                    let mut state = lock_mutex(&state, "rtmp_out.state");
+                    state.phase = ConnectionPhase::Backoff;
+                    state.failed_attempts = 1;
+                    state.retry_in_ms = Some(backoff.as_millis() as u64);
+                    state.last_error = Some(format!("{:#}", e));
+                    drop(state);
+                    wait.wait_timeout(backoff);
+                }
+            }
+
+            let mut state = lock_mutex(&state, "rtmp_out.state");
+            state.phase = ConnectionPhase::Stopped;
+            state.retry_in_ms = None;
+        }));
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        self.running.store(false, Ordering::SeqCst);
+        self.wait.notify_all();
+        if let Some(handle) = self.thread_handle.take() {
+            if handle.join().is_err() {
+                self.errors.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        Ok(())
+    }
+
+    fn status(&self) -> ConsumerStatus {
+        ConsumerStatus {
+            running: self.running.load(Ordering::Relaxed),
+            connected: self.connected.load(Ordering::Relaxed),
+            frames_processed: self.frames_processed.load(Ordering::Relaxed),
+            bytes_written: self.bytes_written.load(Ordering::Relaxed),
+            errors: self.errors.load(Ordering::Relaxed),
+            connection: Some(lock_mutex(&self.state, "rtmp_out.status").clone()),
+        }
+    }
+
+    fn attach_input_buffer(&mut self, buffer: Arc<AudioRingBuffer>) {
+        self.input_buffer = Some(buffer);
+    }
+}
+
+impl_connectable_consumer!(RtmpOutputConsumer);
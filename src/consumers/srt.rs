@@ -0,0 +1,498 @@
+// src/consumers/srt.rs
+//
+// SRT-Ausgang (`srt_out`): kodiert die Flow-Frames und verschickt sie als
+// RFMA-Pakete (Gegenstück zum `srt`-Producer) im Caller- oder Listener-Modus.
+// Optional AES-verschlüsselt (`passphrase`) und mit Stream-ID. Abbrüche führen
+// wie beim Icecast-Ausgang zu Reconnects mit Backoff, der Zustand steht in
+// `ConsumerStatus::connection`.
+use crate::impl_connectable_consumer;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail, Context, Result};
+use bytes::Bytes;
+use futures_util::{SinkExt, StreamExt};
+use srt_tokio::{SrtListener, SrtSocket};
+
+use crate::codecs::create_encoder;
+use crate::config::{ConfigValues, ConsumerConfig};
+use crate::core::idle::IdleBackoff;
+use crate::core::lock::lock_mutex;
+use crate::core::{
+    AudioError, AudioRingBuffer, ConnectionPhase, ConnectionState, Consumer, ConsumerStatus,
+};
+use crate::producers::srt::SrtMode;
+
+const DEFAULT_LATENCY: Duration = Duration::from_millis(120);
+const DEFAULT_RECONNECT: Duration = Duration::from_secs(1);
+const DEFAULT_MAX_RECONNECT: Duration = Duration::from_secs(30);
+const DEFAULT_KEY_LENGTH: u16 = 16;
+/// Wie oft Accept- und Sendeschleife das Stop-Flag prüfen.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+const IDLE_WAIT: Duration = Duration::from_millis(2);
+
+const RFMA_MAGIC: &[u8; 4] = b"RFMA";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SrtOutputConfig {
+    pub mode: SrtMode,
+    /// Caller: Gegenstelle, Listener: lokale Bind-Adresse
+    pub address: SocketAddr,
+    pub latency: Duration,
+    pub streamid: Option<String>,
+    /// SRT-Passphrase (10-79 Zeichen), aktiviert die Verschlüsselung
+    pub passphrase: Option<String>,
+    /// AES-Schlüssellänge in Bytes (16, 24, 32)
+    pub key_length: u16,
+    /// Codec-ID wie in `supported_codecs`
+    pub codec: String,
+    pub reconnect: Duration,
+    pub max_reconnect: Duration,
+}
+
+/// Gelesene `config`-Schlüssel (Strict-Modus, siehe `config::options`)
+pub const CONFIG_KEYS: &[&str] = &[
+    "mode",
+    "address",
+    "streamid",
+    "passphrase",
+    "key_length",
+    "latency_ms",
+    "codec",
+    "codec_id",
+    "reconnect",
+    "max_reconnect",
+];
+
+impl SrtOutputConfig {
+    /// Erwartet `address`; optional `mode` ("caller"/"listener", Standard
+    /// caller), `latency_ms`, `streamid`, `passphrase`, `key_length`, `codec`.
+    pub fn from_config(name: &str, config: &ConsumerConfig) -> Result<Self> {
+        let values = ConfigValues::new("consumer", name, &config.config);
+        let text = |key: &str| -> Option<String> {
+            config
+                .config
+                .get(key)
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+
+        let mode = match text("mode").as_deref() {
+            None | Some("caller") => SrtMode::Caller,
+            Some("listener") => SrtMode::Listener,
+            Some(other) => bail!(
+                "consumer '{}': config.mode must be \"caller\" or \"listener\", got \"{}\"",
+                name,
+                other
+            ),
+        };
+
+        let address = text("address")
+            .or_else(|| config.url.clone())
+            .ok_or_else(|| anyhow!("consumer '{}': srt_out needs config.address", name))?;
+        let address: SocketAddr = address
+            .trim_start_matches("srt://")
+            .parse()
+            .with_context(|| format!("consumer '{}': invalid address '{}'", name, address))?;
+
+        let latency = values.duration("latency_ms")?.unwrap_or(DEFAULT_LATENCY);
+        values.check_range("latency_ms", latency.as_millis() as u64, 20, 8_000)?;
+
+        let streamid = text("streamid");
+        if streamid.as_ref().is_some_and(|id| id.len() > 512) {
+            bail!("consumer '{}': config.streamid is limited to 512 bytes", name);
+        }
+
+        let passphrase = text("passphrase");
+        if let Some(passphrase) = &passphrase {
+            if !(10..=79).contains(&passphrase.len()) {
+                bail!("consumer '{}': config.passphrase must be 10-79 characters", name);
+            }
+        }
+        let key_length = match config.config.get("key_length") {
+            None => DEFAULT_KEY_LENGTH,
+            Some(value) => value
+                .as_u64()
+                .filter(|len| matches!(len, 16 | 24 | 32))
+                .map(|len| len as u16)
+                .ok_or_else(|| {
+                    anyhow!("consumer '{}': config.key_length must be 16, 24 or 32", name)
+                })?,
+        };
+        // Sonst liefe die Verbindung unbemerkt unverschlüsselt
+        if passphrase.is_none() && config.config.contains_key("key_length") {
+            bail!("consumer '{}': config.key_length needs config.passphrase", name);
+        }
+
+        let codec = text("codec")
+            .or_else(|| text("codec_id"))
+            .unwrap_or_else(|| "pcm".to_string())
+            .to_ascii_lowercase();
+        create_encoder(&codec).with_context(|| format!("consumer '{}'", name))?;
+
+        let reconnect = values.duration("reconnect")?.unwrap_or(DEFAULT_RECONNECT);
+        values.check_range("reconnect", reconnect.as_millis() as u64, 100, 60_000)?;
+        let max_reconnect = values
+            .duration("max_reconnect")?
+            .unwrap_or(DEFAULT_MAX_RECONNECT)
+            .max(reconnect);
+        values.check_range("max_reconnect", max_reconnect.as_millis() as u64, 100, 600_000)?;
+
+        Ok(Self {
+            mode,
+            address,
+            latency,
+            streamid,
+            passphrase,
+            key_length,
+            codec,
+            reconnect,
+            max_reconnect,
+        })
+    }
+
+    pub fn endpoint(&self) -> String {
+        let mode = match self.mode {
+            SrtMode::Caller => "caller",
+            SrtMode::Listener => "listener",
+        };
+        match &self.streamid {
+            Some(streamid) => format!("srt://{} ({}, streamid {})", self.address, mode, streamid),
+            None => format!("srt://{} ({})", self.address, mode),
+        }
+    }
+}
+
+/// RFMA-Paket (Magic, seq, utc_ns, Payload-Länge, Payload), siehe
+/// `producers::srt::parse_rfma`.
+pub fn encode_rfma(seq: u64, utc_ns: u64, payload: &[u8]) -> Vec<u8> {
+    let mut packet = Vec::with_capacity(24 + payload.len());
+    packet.extend_from_slice(RFMA_MAGIC);
+    packet.extend_from_slice(&seq.to_be_bytes());
+    packet.extend_from_slice(&utc_ns.to_be_bytes());
+    packet.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+    packet.extend_from_slice(payload);
+    packet
+}
+
+struct Shared {
+    running: AtomicBool,
+    connected: AtomicBool,
+    state: Mutex<ConnectionState>,
+    frames_processed: AtomicU64,
+    bytes_written: AtomicU64,
+    errors: AtomicU64,
+}
+
+impl Shared {
+    fn set_phase(&self, phase: ConnectionPhase) {
+        let mut state = lock_mutex(&self.state, "srt_out.state");
+        state.phase = phase;
+        state.retry_in_ms = None;
+    }
+}
+
+pub struct SrtOutputConsumer {
+    name: String,
+    config: SrtOutputConfig,
+    shared: Arc<Shared>,
+    input_buffer: Option<Arc<AudioRingBuffer>>,
+    reader_id: String,
+    thread_handle: Option<std::thread::JoinHandle<()>>,
+}
+
+impl SrtOutputConsumer {
+    pub fn new(name: &str, config: &ConsumerConfig) -> Result<Self> {
+        Ok(Self::with_config(name, SrtOutputConfig::from_config(name, config)?))
+    }
+
+    pub fn with_config(name: &str, config: SrtOutputConfig) -> Self {
+        let state = ConnectionState {
+            phase: ConnectionPhase::Stopped,
+            endpoint: config.endpoint(),
+            failed_attempts: 0,
+            retry_in_ms: None,
+            last_error: None,
+        };
+        Self {
+            name: name.to_string(),
+            config,
+            shared: Arc::new(Shared {
+                running: AtomicBool::new(false),
+                connected: AtomicBool::new(false),
+                state: Mutex::new(state),
+                frames_processed: AtomicU64::new(0),
+                bytes_written: AtomicU64::new(0),
+                errors: AtomicU64::new(0),
+            }),
+            input_buffer: None,
+            reader_id: format!("consumer:{}", name),
+            thread_handle: None,
+        }
+    }
+
+    pub fn config(&self) -> &SrtOutputConfig {
+        &self.config
+    }
+}
+
+impl Consumer for SrtOutputConsumer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn start(&mut self) -> Result<()> {
+        if self.shared.running.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        let buffer = self
+            .input_buffer
+            .clone()
+            .ok_or_else(|| anyhow!("SrtOutputConsumer '{}' missing input buffer", self.name))?;
+
+        log::info!(
+            "SrtOutputConsumer '{}': streaming {} to {} (latency {} ms{})",
+            self.name,
+            self.config.codec,
+            self.config.endpoint(),
+            self.config.latency.as_millis(),
+            if self.config.passphrase.is_some() { ", encrypted" } else { "" }
+        );
+
+        self.shared.running.store(true, Ordering::SeqCst);
+        let name = self.name.clone();
+        let config = self.config.clone();
+        let shared = self.shared.clone();
+        let reader_id = self.reader_id.clone();
+
+        self.thread_handle = Some(std::thread::spawn(move || {
+            let runtime = match tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+            {
+                Ok(runtime) => runtime,
+                Err(e) => {
+                    log::error!("SrtOutputConsumer '{}': failed to create runtime: {}", name, e);
+                    shared.errors.fetch_add(1, Ordering::Relaxed);
+                    shared.running.store(false, Ordering::SeqCst);
+                    shared.set_phase(ConnectionPhase::Stopped);
+                    return;
+                }
+            };
+
+            runtime.block_on(run(&name, &config, &shared, &buffer, &reader_id));
+            shared.connected.store(false, Ordering::SeqCst);
+            shared.set_phase(ConnectionPhase::Stopped);
+            log::info!("SrtOutputConsumer '{}': stopped", name);
+        }));
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        self.shared.running.store(false, Ordering::SeqCst);
+        if let Some(handle) = self.thread_handle.take() {
+            if handle.join().is_err() {
+                self.shared.errors.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        Ok(())
+    }
+
+    fn status(&self) -> ConsumerStatus {
+        ConsumerStatus {
+            running: self.shared.running.load(Ordering::Relaxed),
+            connected: self.shared.connected.load(Ordering::Relaxed),
+            frames_processed: self.shared.frames_processed.load(Ordering::Relaxed),
+            bytes_written: self.shared.bytes_written.load(Ordering::Relaxed),
+            errors: self.shared.errors.load(Ordering::Relaxed),
+            connection: Some(lock_mutex(&self.shared.state, "srt_out.status").clone()),
+        }
+    }
+
+    fn attach_input_buffer(&mut self, buffer: Arc<AudioRingBuffer>) {
+        self.input_buffer = Some(buffer);
+    }
+}
+
+async fn run(
+    name: &str,
+    config: &SrtOutputConfig,
+    shared: &Shared,
+    buffer: &AudioRingBuffer,
+    reader_id: &str,
+) {
+    let mut backoff = config.reconnect;
+    while shared.running.load(Ordering::Relaxed) {
+        shared.set_phase(ConnectionPhase::Connecting);
+        let result = match config.mode {
+            SrtMode::Caller => call(config).await,
+            SrtMode::Listener => accept(config, shared).await,
+        };
+        let socket = match result {
+            Ok(Some(socket)) => socket,
+            Ok(None) => continue,
+            Err(e) => {
+                shared.errors.fetch_add(1, Ordering::Relaxed);
+                let mut state = lock_mutex(&shared.state, "srt_out.state");
+                if state.failed_attempts == 0 {
+                    log::warn!(
+                        "SrtOutputConsumer '{}': connect to {} failed: {:#}",
+                        name,
+                        state.endpoint,
+                        e
+                    );
+                }
+                state.phase = ConnectionPhase::Backoff;
+                state.failed_attempts = state.failed_attempts.saturating_add(1);
+                state.retry_in_ms = Some(backoff.as_millis() as u64);
+                state.last_error = Some(format!("{:#}", e));
+                drop(state);
+                sleep_unless_stopped(shared, backoff).await;
+                backoff = (backoff * 2).min(config.max_reconnect);
+                continue;
+            }
+        };
+
+        log::info!("SrtOutputConsumer '{}': connected to {}", name, config.endpoint());
+        backoff = config.reconnect;
+        {
+            let mut state = lock_mutex(&shared.state, "srt_out.state");
+            state.phase = ConnectionPhase::Connected;
+            state.failed_attempts = 0;
+            state.retry_in_ms = None;
+        }
+        shared.connected.store(true, Ordering::SeqCst);
+        // Live weitersenden, kein Aufholen alter Frames
+        buffer.skip_to_latest(reader_id);
+
+        let result = send(name, config, shared, buffer, reader_id, socket).await;
+        shared.connected.store(false, Ordering::SeqCst);
+        if let Err(e) = result {
+            log::warn!("SrtOutputConsumer '{}': {:#}", name, e);
+            shared.errors.fetch_add(1, Ordering::Relaxed);
+            let mut state = lock_mutex(&shared.state, "srt_out.state");
+            state.phase = ConnectionPhase::Backoff;
+            state.failed_attempts = 1;
+            state.retry_in_ms = Some(backoff.as_millis() as u64);
+            state.last_error = Some(format!("{:#}", e));
+            drop(state);
+            sleep_unless_stopped(shared, backoff).await;
+        }
+    }
+}
+
+async fn sleep_unless_stopped(shared: &Shared, duration: Duration) {
+    let deadline = tokio::time::Instant::now() + duration;
+    while shared.running.load(Ordering::Relaxed) && tokio::time::Instant::now() < deadline {
+        tokio::time::sleep(POLL_INTERVAL.min(duration)).await;
+    }
+}
+
+async fn call(config: &SrtOutputConfig) -> Result<Option<SrtSocket>> {
+    let mut builder = SrtSocket::builder().latency(config.latency);
+    if let Some(passphrase) = &config.passphrase {
+        builder = builder.encryption(config.key_length, passphrase.as_str());
+    }
+    let socket = builder
+        .call(config.address, config.streamid.as_deref())
+        .await
+        .map_err(|e| AudioError::network(format!("call {}: {}", config.address, e)))?;
+    Ok(Some(socket))
+}
+
+/// Wartet auf einen Empfänger; mit `streamid` werden andere Stream-IDs
+/// abgewiesen. `Ok(None)` = gestoppt, bevor sich jemand verbunden hat.
+async fn accept(config: &SrtOutputConfig, shared: &Shared) -> Result<Option<SrtSocket>> {
+    let mut builder = SrtListener::builder().latency(config.latency);
+    if let Some(passphrase) = &config.passphrase {
+        builder = builder.encryption(config.key_length, passphrase.as_str());
+    }
+    let (_listener, mut incoming) = builder
+        .bind(config.address)
+        .await
+        .map_err(|e| AudioError::network(format!("bind {}: {}", config.address, e)))?;
+    let mut requests = incoming.incoming();
+
+    while shared.running.load(Ordering::Relaxed) {
+        let request = match tokio::time::timeout(POLL_INTERVAL, requests.next()).await {
+            Ok(Some(request)) => request,
+            Ok(None) => return Ok(None),
+            Err(_) => continue,
+        };
+
+        let requested = request.stream_id().map(|id| id.to_string());
+        if let Some(expected) = &config.streamid {
+            if requested.as_deref() != Some(expected.as_str()) {
+                log::warn!(
+                    "SRT output {}: rejecting stream id {:?} from {}",
+                    config.address,
+                    requested,
+                    request.remote()
+                );
+                let _ = request
+                    .reject(srt_tokio::options::RejectReason::Server(
+                        srt_tokio::options::ServerRejectReason::BadRequest,
+                    ))
+                    .await;
+                continue;
+            }
+        }
+        let socket = request
+            .accept(None)
+            .await
+            .map_err(|e| AudioError::network(format!("accept: {}", e)))?;
+        return Ok(Some(socket));
+    }
+    Ok(None)
+}
+
+async fn send(
+    name: &str,
+    config: &SrtOutputConfig,
+    shared: &Shared,
+    buffer: &AudioRingBuffer,
+    reader_id: &str,
+    mut socket: SrtSocket,
+) -> Result<()> {
+    // Neuer Encoder pro Verbindung, damit der Empfänger sauber einsteigt
+    let mut encoder = create_encoder(&config.codec)?;
+    let mut seq = 0u64;
+    // Im async-Kontext ohne Wecken durch den Buffer, nur mit wachsendem Intervall
+    let mut idle = IdleBackoff::new(IDLE_WAIT);
+
+    while shared.running.load(Ordering::Relaxed) {
+        let Some(frame) = buffer.pop_for_reader(reader_id) else {
+            tokio::time::sleep(idle.next_wait()).await;
+            continue;
+        };
+        idle.reset();
+        let encoded = match encoder.encode(&frame.samples) {
+            Ok(encoded) => encoded,
+            Err(e) => {
+                shared.errors.fetch_add(1, Ordering::Relaxed);
+                log::debug!("SrtOutputConsumer '{}': encode error: {}", name, e);
+                continue;
+            }
+        };
+        for packet in encoded {
+            let message = encode_rfma(seq, frame.utc_ns, &packet.payload);
+            seq = seq.wrapping_add(1);
+            let len = message.len() as u64;
+            socket
+                .send((Instant::now(), Bytes::from(message)))
+                .await
+                .map_err(|e| AudioError::network(format!("send failed: {}", e)))?;
+            shared.bytes_written.fetch_add(len, Ordering::Relaxed);
+        }
+        shared.frames_processed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    let _ = socket.close().await;
+    Ok(())
+}
+
+impl_connectable_consumer!(SrtOutputConsumer);
@@ -0,0 +1,297 @@
+// src/consumers/tls.rs
+//
+// TLS für ausgehende Consumer-Verbindungen (z. B. Icecast über https://).
+// Vertrauensbasis sind die eingebauten Mozilla-Wurzeln oder eine eigene CA
+// (`tls_ca_file`). Mit `tls_pin` zählt nur noch der SHA-256-Fingerabdruck des
+// Server-Zertifikats: passt er, wird auch ein selbstsigniertes Zertifikat
+// akzeptiert; passt er nicht, scheitert die Verbindung trotz gültiger Kette.
+// Der eigentliche Handshake braucht das Cargo-Feature `tls` (rustls).
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::path::PathBuf;
+
+use anyhow::{anyhow, bail, Result};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TlsOptions {
+    /// Name für SNI und Zertifikatsprüfung; Standard ist der Host
+    pub server_name: Option<String>,
+    /// Eigene CA (PEM) statt der eingebauten Wurzeln
+    pub ca_file: Option<PathBuf>,
+    /// Erlaubte SHA-256-Fingerabdrücke des Server-Zertifikats
+    pub pins: Vec<[u8; 32]>,
+}
+
+/// Gelesene `config`-Schlüssel (Strict-Modus, siehe `config::options`)
+pub const CONFIG_KEYS: &[&str] = &["tls_pin", "tls_ca_file", "tls_server_name"];
+
+impl TlsOptions {
+    /// `tls_server_name`, `tls_ca_file` und `tls_pin` (String oder Liste)
+    /// aus der `config`-Tabelle eines Consumers.
+    pub fn from_config(consumer: &str, config: &HashMap<String, Value>) -> Result<Self> {
+        let text = |key: &str| -> Result<Option<String>> {
+            match config.get(key) {
+                None => Ok(None),
+                Some(Value::String(value)) if !value.trim().is_empty() => {
+                    Ok(Some(value.trim().to_string()))
+                }
+                Some(_) => bail!("consumer '{}': config.{} must be a non-empty string", consumer, key),
+            }
+        };
+        let pins = match config.get("tls_pin") {
+            None => Vec::new(),
+            Some(Value::String(pin)) => vec![pin.as_str()],
+            Some(Value::Array(pins)) => pins
+                .iter()
+                .map(|pin| {
+                    pin.as_str().ok_or_else(|| {
+                        anyhow!("consumer '{}': config.tls_pin entries must be strings", consumer)
+                    })
+                })
+                .collect::<Result<_>>()?,
+            Some(_) => bail!("consumer '{}': config.tls_pin must be a string or a list", consumer),
+        }
+        .into_iter()
+        .map(|pin| {
+            parse_pin(pin).map_err(|e| anyhow!("consumer '{}': config.tls_pin: {}", consumer, e))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+        let ca_file = text("tls_ca_file")?.map(PathBuf::from);
+        if let Some(path) = &ca_file {
+            if !path.is_file() {
+                bail!("consumer '{}': config.tls_ca_file {} not found", consumer, path.display());
+            }
+        }
+        Ok(Self {
+            server_name: text("tls_server_name")?,
+            ca_file,
+            pins,
+        })
+    }
+
+    /// Sind TLS-Optionen gesetzt? (Ohne TLS-Verbindung sind sie ein Fehler.)
+    pub fn is_configured(&self) -> bool {
+        self.server_name.is_some() || self.ca_file.is_some() || !self.pins.is_empty()
+    }
+}
+
+/// Fingerabdruck als Hex, mit oder ohne `:` (wie `openssl x509 -fingerprint -sha256`).
+pub fn parse_pin(text: &str) -> Result<[u8; 32]> {
+    let hex: String = text.chars().filter(|c| *c != ':').collect();
+    if hex.len() != 64 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        bail!("'{}' is not a SHA-256 fingerprint (64 hex digits)", text);
+    }
+    let mut pin = [0u8; 32];
+    for (index, byte) in pin.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[index * 2..index * 2 + 2], 16)?;
+    }
+    Ok(pin)
+}
+
+/// SHA-256 eines DER-kodierten Zertifikats.
+pub fn certificate_fingerprint(der: &[u8]) -> [u8; 32] {
+    Sha256::digest(der).into()
+}
+
+pub fn format_fingerprint(fingerprint: &[u8; 32]) -> String {
+    fingerprint
+        .iter()
+        .map(|byte| format!("{:02X}", byte))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// Verbindung eines Consumers, mit oder ohne TLS.
+pub enum ConsumerStream {
+    Plain(TcpStream),
+    #[cfg(feature = "tls")]
+    Tls(Box<rustls::StreamOwned<rustls::ClientConnection, TcpStream>>),
+}
+
+impl ConsumerStream {
+    pub fn is_tls(&self) -> bool {
+        !matches!(self, ConsumerStream::Plain(_))
+    }
+
+    /// Darunterliegende TCP-Verbindung, z. B. für Timeouts.
+    pub fn tcp(&self) -> &TcpStream {
+        match self {
+            ConsumerStream::Plain(stream) => stream,
+            #[cfg(feature = "tls")]
+            ConsumerStream::Tls(stream) => stream.get_ref(),
+        }
+    }
+
+    pub fn set_read_timeout(&self, timeout: Option<std::time::Duration>) -> std::io::Result<()> {
+        self.tcp().set_read_timeout(timeout)
+    }
+}
+
+impl Read for ConsumerStream {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self {
+            ConsumerStream::Plain(stream) => stream.read(buf),
+            #[cfg(feature = "tls")]
+            ConsumerStream::Tls(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for ConsumerStream {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            ConsumerStream::Plain(stream) => stream.write(buf),
+            #[cfg(feature = "tls")]
+            ConsumerStream::Tls(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            ConsumerStream::Plain(stream) => stream.flush(),
+            #[cfg(feature = "tls")]
+            ConsumerStream::Tls(stream) => stream.flush(),
+        }
+    }
+}
+
+/// Ohne Feature `tls` lassen sich TLS-Ziele nicht konfigurieren.
+pub fn ensure_supported(consumer: &str) -> Result<()> {
+    if cfg!(feature = "tls") {
+        Ok(())
+    } else {
+        bail!(
+            "consumer '{}': TLS requested, but this build lacks the 'tls' feature",
+            consumer
+        )
+    }
+}
+
+/// TLS-Handshake über eine bestehende TCP-Verbindung (Timeouts gelten weiter).
+#[cfg(feature = "tls")]
+pub fn wrap(stream: TcpStream, host: &str, options: &TlsOptions) -> Result<ConsumerStream> {
+    use rustls::pki_types::ServerName;
+    use std::sync::Arc;
+
+    let server_name = ServerName::try_from(options.server_name.as_deref().unwrap_or(host).to_string())
+        .map_err(|e| anyhow!("invalid TLS server name: {}", e))?;
+    let config = client_config(options)?;
+    let mut connection = rustls::ClientConnection::new(Arc::new(config), server_name)?;
+    let mut stream = stream;
+    while connection.is_handshaking() {
+        connection
+            .complete_io(&mut stream)
+            .map_err(|e| anyhow!("TLS handshake failed: {}", e))?;
+    }
+    Ok(ConsumerStream::Tls(Box::new(rustls::StreamOwned::new(connection, stream))))
+}
+
+#[cfg(not(feature = "tls"))]
+pub fn wrap(_stream: TcpStream, _host: &str, _options: &TlsOptions) -> Result<ConsumerStream> {
+    bail!("TLS is not available in this build (feature 'tls')")
+}
+
+#[cfg(feature = "tls")]
+fn client_config(options: &TlsOptions) -> Result<rustls::ClientConfig> {
+    use rustls::pki_types::pem::PemObject;
+    use rustls::pki_types::CertificateDer;
+    use std::sync::Arc;
+
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let builder = rustls::ClientConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()?;
+    if !options.pins.is_empty() {
+        return Ok(builder
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(pinning::PinnedVerifier {
+                pins: options.pins.clone(),
+                provider,
+            }))
+            .with_no_client_auth());
+    }
+
+    let mut roots = rustls::RootCertStore::empty();
+    match &options.ca_file {
+        Some(path) => {
+            for cert in CertificateDer::pem_file_iter(path)
+                .map_err(|e| anyhow!("cannot read {}: {}", path.display(), e))?
+            {
+                let cert = cert.map_err(|e| anyhow!("invalid PEM in {}: {}", path.display(), e))?;
+                roots.add(cert)?;
+            }
+            if roots.is_empty() {
+                bail!("{} contains no certificates", path.display());
+            }
+        }
+        None => roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned()),
+    }
+    Ok(builder.with_root_certificates(roots).with_no_client_auth())
+}
+
+#[cfg(feature = "tls")]
+mod pinning {
+    use std::sync::Arc;
+
+    use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+    use rustls::crypto::{verify_tls12_signature, verify_tls13_signature, CryptoProvider};
+    use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+    use rustls::{DigitallySignedStruct, Error, SignatureScheme};
+
+    use super::{certificate_fingerprint, format_fingerprint};
+
+    /// Akzeptiert genau die Zertifikate mit gepinntem Fingerabdruck.
+    #[derive(Debug)]
+    pub struct PinnedVerifier {
+        pub pins: Vec<[u8; 32]>,
+        pub provider: Arc<CryptoProvider>,
+    }
+
+    impl ServerCertVerifier for PinnedVerifier {
+        fn verify_server_cert(
+            &self,
+            end_entity: &CertificateDer<'_>,
+            _intermediates: &[CertificateDer<'_>],
+            _server_name: &ServerName<'_>,
+            _ocsp_response: &[u8],
+            _now: UnixTime,
+        ) -> Result<ServerCertVerified, Error> {
+            let fingerprint = certificate_fingerprint(end_entity.as_ref());
+            if self.pins.contains(&fingerprint) {
+                Ok(ServerCertVerified::assertion())
+            } else {
+                Err(Error::General(format!(
+                    "server certificate {} matches no tls_pin",
+                    format_fingerprint(&fingerprint)
+                )))
+            }
+        }
+
+        fn verify_tls12_signature(
+            &self,
+            message: &[u8],
+            cert: &CertificateDer<'_>,
+            dss: &DigitallySignedStruct,
+        ) -> Result<HandshakeSignatureValid, Error> {
+            verify_tls12_signature(message, cert, dss, &self.provider.signature_verification_algorithms)
+        }
+
+        fn verify_tls13_signature(
+            &self,
+            message: &[u8],
+            cert: &CertificateDer<'_>,
+            dss: &DigitallySignedStruct,
+        ) -> Result<HandshakeSignatureValid, Error> {
+            verify_tls13_signature(message, cert, dss, &self.provider.signature_verification_algorithms)
+        }
+
+        fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+            self.provider
+                .signature_verification_algorithms
+                .supported_schemes()
+        }
+    }
+}
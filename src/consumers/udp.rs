@@ -0,0 +1,470 @@
+// src/consumers/udp.rs
+//
+// UDP-Ausgang (`udp_out`, Nachfolger von `io/udp_out.rs`): kodiert die
+// Flow-Frames über die Codec-Registry und verschickt sie per UDP an `target`
+// (Unicast oder Multicast), zerlegt in Datagramme von höchstens `packet_size`
+// Bytes. AAC geht mit ADTS-Headern raus, damit Empfänger ohne Out-of-Band-
+// Config einsteigen können; optional kommt vor jedes Datagramm ein RTP-Header.
+use crate::impl_connectable_consumer;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs, UdpSocket};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context, Result};
+
+use crate::codecs::ogg_opus::opus_packet_samples;
+use crate::codecs::{
+    create_encoder_with_options, CodecInfo, CodecKind, ContainerKind, OpusEncoderOptions,
+    PCM_CHANNELS,
+};
+use crate::config::{ConfigValues, ConsumerConfig};
+use crate::consumers::rtmp::AAC_FREQUENCIES;
+use crate::core::idle::IdleBackoff;
+use crate::core::{AudioRingBuffer, Consumer, ConsumerStatus};
+use crate::producers::wait::StopWait;
+
+/// MTU-freundlich und ein Vielfaches der MPEG-TS-Paketgröße (7 × 188)
+pub const DEFAULT_PACKET_SIZE: usize = 1316;
+/// Größte UDP-Payload über IPv4
+const MAX_PACKET_SIZE: usize = 65_507;
+const MIN_PACKET_SIZE: usize = 64;
+const DEFAULT_PAYLOAD_TYPE: u8 = 96;
+const DEFAULT_TTL: u32 = 32;
+const RTP_HEADER_LEN: usize = 12;
+const ADTS_HEADER_LEN: usize = 7;
+const IDLE_WAIT: Duration = Duration::from_millis(2);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RtpOptions {
+    pub payload_type: u8,
+    /// Ohne Angabe aus dem Consumer-Namen abgeleitet
+    pub ssrc: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UdpOutputConfig {
+    pub target: SocketAddr,
+    /// Maximale Datagrammgröße inklusive RTP-Header
+    pub packet_size: usize,
+    /// Codec-ID wie in `supported_codecs`
+    pub codec: String,
+    /// Encoder-Einstellungen für `opuswebrtc` (`config.opus`)
+    pub opus: OpusEncoderOptions,
+    /// AAC-Frames mit ADTS-Header versehen (Standard bei `aaclc`)
+    pub adts: bool,
+    pub rtp: Option<RtpOptions>,
+    /// Multicast-TTL bzw. Hop-Limit
+    pub ttl: u32,
+}
+
+/// Gelesene `config`-Schlüssel (Strict-Modus, siehe `config::options`)
+pub const CONFIG_KEYS: &[&str] = &[
+    "address",
+    "target",
+    "codec",
+    "codec_id",
+    "opus",
+    "packet_size",
+    "payload_type",
+    "ssrc",
+    "ttl",
+    "rtp",
+    "adts",
+];
+
+impl UdpOutputConfig {
+    /// Erwartet `target` ("host:port", auch Multicast); optional
+    /// `packet_size`, `codec` (Standard `pcm`), `opus`, `adts`, `rtp`,
+    /// `payload_type`, `ssrc` und `ttl`.
+    pub fn from_config(name: &str, config: &ConsumerConfig) -> Result<Self> {
+        let values = ConfigValues::new("consumer", name, &config.config);
+        let text = |key: &str| -> Option<String> {
+            config
+                .config
+                .get(key)
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+        let flag = |key: &str| -> Result<Option<bool>> {
+            match config.config.get(key) {
+                None => Ok(None),
+                Some(value) => value
+                    .as_bool()
+                    .map(Some)
+                    .ok_or_else(|| anyhow!("consumer '{}': config.{} must be true or false", name, key)),
+            }
+        };
+
+        let target = text("target")
+            .or_else(|| text("address"))
+            .or_else(|| config.url.clone())
+            .ok_or_else(|| anyhow!("consumer '{}': udp_out needs config.target", name))?;
+        let target = target
+            .trim_start_matches("udp://")
+            .to_socket_addrs()
+            .ok()
+            .and_then(|mut addrs| addrs.next())
+            .ok_or_else(|| anyhow!("consumer '{}': invalid target '{}'", name, target))?;
+        if target.port() == 0 {
+            bail!("consumer '{}': config.target needs a port", name);
+        }
+
+        let packet_size = match values.size("packet_size")? {
+            Some(size) => values.check_range("packet_size", size, MIN_PACKET_SIZE as u64, MAX_PACKET_SIZE as u64)?
+                as usize,
+            None => DEFAULT_PACKET_SIZE,
+        };
+
+        let codec = text("codec")
+            .or_else(|| text("codec_id"))
+            .unwrap_or_else(|| "pcm".to_string())
+            .to_ascii_lowercase();
+        let opus = OpusEncoderOptions::from_config("consumer", name, config.config.get("opus"))?;
+        let info = create_encoder_with_options(&codec, PCM_CHANNELS, &opus)
+            .with_context(|| format!("consumer '{}'", name))?
+            .info()
+            .clone();
+        // Ogg-Seiten lassen sich nach Paketverlust nicht neu synchronisieren
+        if matches!(info.container, ContainerKind::Ogg) {
+            bail!(
+                "consumer '{}': codec '{}' uses Ogg framing, which cannot be sent over UDP",
+                name,
+                codec
+            );
+        }
+
+        let is_aac = matches!(info.kind, CodecKind::AacLc);
+        let adts = flag("adts")?.unwrap_or(is_aac);
+        if adts && !is_aac {
+            bail!("consumer '{}': config.adts only applies to codec aaclc", name);
+        }
+
+        let rtp = if flag("rtp")?.unwrap_or(false) {
+            let payload_type = match values.f64("payload_type")? {
+                Some(pt) => values.check_range("payload_type", pt, 0.0, 127.0)? as u8,
+                None => DEFAULT_PAYLOAD_TYPE,
+            };
+            let ssrc = match values.f64("ssrc")? {
+                Some(ssrc) => Some(values.check_range("ssrc", ssrc, 0.0, u32::MAX as f64)? as u32),
+                None => None,
+            };
+            Some(RtpOptions { payload_type, ssrc })
+        } else {
+            for key in ["payload_type", "ssrc"] {
+                if config.config.contains_key(key) {
+                    bail!("consumer '{}': config.{} needs rtp = true", name, key);
+                }
+            }
+            None
+        };
+
+        let ttl = match values.f64("ttl")? {
+            Some(ttl) => values.check_range("ttl", ttl, 1.0, 255.0)? as u32,
+            None => DEFAULT_TTL,
+        };
+
+        Ok(Self {
+            target,
+            packet_size,
+            codec,
+            opus,
+            adts,
+            rtp,
+            ttl,
+        })
+    }
+
+    /// Platz für Nutzdaten pro Datagramm.
+    pub fn payload_size(&self) -> usize {
+        self.packet_size - if self.rtp.is_some() { RTP_HEADER_LEN } else { 0 }
+    }
+}
+
+/// ADTS-Header (MPEG-4, ohne CRC) für einen rohen AAC-LC-Frame.
+pub fn adts_header(frame_len: usize, sample_rate: u32, channels: u8) -> Result<[u8; ADTS_HEADER_LEN]> {
+    let index = AAC_FREQUENCIES
+        .iter()
+        .position(|rate| *rate == sample_rate)
+        .ok_or_else(|| anyhow!("AAC does not support {} Hz", sample_rate))? as u8;
+    if !(1..=7).contains(&channels) {
+        bail!("AAC does not support {} channels", channels);
+    }
+    let len = frame_len + ADTS_HEADER_LEN;
+    if len > 0x1FFF {
+        bail!("AAC frame of {} bytes does not fit into ADTS", frame_len);
+    }
+    Ok([
+        0xFF,
+        0xF1, // MPEG-4, Layer 0, kein CRC
+        1 << 6 | index << 2 | (channels >> 2) & 0x01, // Profil LC (objectType 2 - 1)
+        (channels & 0x03) << 6 | (len >> 11) as u8 & 0x03,
+        (len >> 3) as u8,
+        ((len & 0x07) as u8) << 5 | 0x1F,
+        0xFC, // Buffer-Fullness 0x7FF (variabel), ein Raw-Block
+    ])
+}
+
+/// Setzt vor jeden rohen AAC-Frame einen ADTS-Header; Payloads, die schon
+/// ADTS sind, bleiben unverändert.
+pub fn to_adts(payload: &[u8], info: &CodecInfo) -> Result<Vec<u8>> {
+    if payload.len() >= ADTS_HEADER_LEN && payload[0] == 0xFF && payload[1] & 0xF6 == 0xF0 {
+        return Ok(payload.to_vec());
+    }
+    let mut framed = Vec::with_capacity(ADTS_HEADER_LEN + payload.len());
+    framed.extend_from_slice(&adts_header(payload.len(), info.sample_rate, info.channels)?);
+    framed.extend_from_slice(payload);
+    Ok(framed)
+}
+
+/// Zerlegt kodierte Payloads in Datagramme und setzt bei Bedarf RTP-Header.
+/// Alle Teile einer Payload tragen denselben Timestamp, das letzte das
+/// Marker-Bit.
+pub struct UdpPacketizer {
+    payload_size: usize,
+    rtp: Option<(u8, u32)>,
+    sequence: u16,
+}
+
+impl UdpPacketizer {
+    pub fn new(config: &UdpOutputConfig, default_ssrc: u32) -> Self {
+        Self {
+            payload_size: config.payload_size(),
+            rtp: config
+                .rtp
+                .as_ref()
+                .map(|rtp| (rtp.payload_type, rtp.ssrc.unwrap_or(default_ssrc))),
+            sequence: 0,
+        }
+    }
+
+    pub fn packetize(&mut self, payload: &[u8], timestamp: u32) -> Vec<Vec<u8>> {
+        let Some((payload_type, ssrc)) = self.rtp else {
+            return payload.chunks(self.payload_size).map(<[u8]>::to_vec).collect();
+        };
+        let chunks = payload.chunks(self.payload_size).count();
+        payload
+            .chunks(self.payload_size)
+            .enumerate()
+            .map(|(index, chunk)| {
+                let marker = if index + 1 == chunks { 0x80 } else { 0 };
+                let mut packet = Vec::with_capacity(RTP_HEADER_LEN + chunk.len());
+                packet.push(0x80); // V=2, kein Padding/Extension/CSRC
+                packet.push(marker | payload_type & 0x7F);
+                packet.extend_from_slice(&self.sequence.to_be_bytes());
+                packet.extend_from_slice(&timestamp.to_be_bytes());
+                packet.extend_from_slice(&ssrc.to_be_bytes());
+                packet.extend_from_slice(chunk);
+                self.sequence = self.sequence.wrapping_add(1);
+                packet
+            })
+            .collect()
+    }
+}
+
+pub struct UdpOutputConsumer {
+    name: String,
+    config: UdpOutputConfig,
+    running: Arc<AtomicBool>,
+    connected: Arc<AtomicBool>,
+    input_buffer: Option<Arc<AudioRingBuffer>>,
+    reader_id: String,
+    thread_handle: Option<std::thread::JoinHandle<()>>,
+    wait: Arc<StopWait>,
+    frames_processed: Arc<AtomicU64>,
+    bytes_written: Arc<AtomicU64>,
+    errors: Arc<AtomicU64>,
+}
+
+impl UdpOutputConsumer {
+    pub fn new(name: &str, config: &ConsumerConfig) -> Result<Self> {
+        Ok(Self::with_config(name, UdpOutputConfig::from_config(name, config)?))
+    }
+
+    pub fn with_config(name: &str, config: UdpOutputConfig) -> Self {
+        Self {
+            name: name.to_string(),
+            config,
+            running: Arc::new(AtomicBool::new(false)),
+            connected: Arc::new(AtomicBool::new(false)),
+            input_buffer: None,
+            reader_id: format!("consumer:{}", name),
+            thread_handle: None,
+            wait: Arc::new(StopWait::new()),
+            frames_processed: Arc::new(AtomicU64::new(0)),
+            bytes_written: Arc::new(AtomicU64::new(0)),
+            errors: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    pub fn config(&self) -> &UdpOutputConfig {
+        &self.config
+    }
+
+    /// SSRC aus dem Namen, damit sie über Neustarts stabil bleibt.
+    fn ssrc(&self) -> u32 {
+        self.name
+            .bytes()
+            .fold(0x811C_9DC5u32, |hash, b| (hash ^ b as u32).wrapping_mul(0x0100_0193))
+    }
+
+    fn bind(&self) -> Result<UdpSocket> {
+        let target = self.config.target;
+        let bind: SocketAddr = match target.ip() {
+            IpAddr::V4(_) => (Ipv4Addr::UNSPECIFIED, 0).into(),
+            IpAddr::V6(_) => (Ipv6Addr::UNSPECIFIED, 0).into(),
+        };
+        let socket = UdpSocket::bind(bind)
+            .with_context(|| format!("UdpOutputConsumer '{}': bind {} failed", self.name, bind))?;
+        if target.ip().is_multicast() {
+            match target.ip() {
+                IpAddr::V4(_) => {
+                    socket.set_multicast_ttl_v4(self.config.ttl)?;
+                    socket.set_multicast_loop_v4(false)?;
+                }
+                IpAddr::V6(_) => socket.set_multicast_loop_v6(false)?,
+            }
+        }
+        socket
+            .connect(target)
+            .with_context(|| format!("UdpOutputConsumer '{}': connect {} failed", self.name, target))?;
+        Ok(socket)
+    }
+}
+
+impl Consumer for UdpOutputConsumer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn start(&mut self) -> Result<()> {
+        if self.running.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        let buffer = self
+            .input_buffer
+            .clone()
+            .ok_or_else(|| anyhow!("UdpOutputConsumer '{}' missing input buffer", self.name))?;
+        let socket = self.bind()?;
+        let mut encoder =
+            create_encoder_with_options(&self.config.codec, PCM_CHANNELS, &self.config.opus)?;
+        // Opus über RTP: jedes Paket bekommt seinen eigenen Timestamp
+        let per_packet = matches!(encoder.info().kind, CodecKind::OpusWebRtc);
+        let mut packetizer = UdpPacketizer::new(&self.config, self.ssrc());
+
+        log::info!(
+            "UdpOutputConsumer '{}': sending {} to {} ({} byte packets{}{})",
+            self.name,
+            self.config.codec,
+            self.config.target,
+            self.config.packet_size,
+            if self.config.adts { ", ADTS" } else { "" },
+            if self.config.rtp.is_some() { ", RTP" } else { "" }
+        );
+
+        self.running.store(true, Ordering::SeqCst);
+        self.connected.store(true, Ordering::SeqCst);
+
+        let running = self.running.clone();
+        let connected = self.connected.clone();
+        let reader_id = self.reader_id.clone();
+        let frames_processed = self.frames_processed.clone();
+        let bytes_written = self.bytes_written.clone();
+        let errors = self.errors.clone();
+        let name = self.name.clone();
+        let adts = self.config.adts;
+        let wait = self.wait.clone();
+
+        self.thread_handle = Some(std::thread::spawn(move || {
+            // RTP-Takt = Samplerate der Frames, fortlaufend ab einem
+            // SSRC-abhängigen Startwert
+            let mut timestamp = packetizer.rtp.map(|(_, ssrc)| ssrc.rotate_left(16)).unwrap_or(0);
+            let mut idle = IdleBackoff::new(IDLE_WAIT);
+            while running.load(Ordering::Relaxed) {
+                let Some(frame) = buffer.pop_for_reader(&reader_id) else {
+                    idle.wait_for(&buffer, &wait);
+                    continue;
+                };
+                idle.reset();
+                let frame_samples = frame.samples.len() / frame.channels.max(1) as usize;
+                let encoded = match encoder.encode(&frame.samples) {
+                    Ok(encoded) => encoded,
+                    Err(e) => {
+                        errors.fetch_add(1, Ordering::Relaxed);
+                        log::debug!("UdpOutputConsumer '{}': encode error: {}", name, e);
+                        continue;
+                    }
+                };
+                for packet in encoded {
+                    let payload = if adts {
+                        match to_adts(&packet.payload, &packet.info) {
+                            Ok(payload) => payload,
+                            Err(e) => {
+                                if errors.fetch_add(1, Ordering::Relaxed) == 0 {
+                                    log::error!("UdpOutputConsumer '{}': {}", name, e);
+                                }
+                                continue;
+                            }
+                        }
+                    } else {
+                        packet.payload
+                    };
+                    let packet_timestamp = timestamp;
+                    if per_packet {
+                        timestamp = timestamp.wrapping_add(opus_packet_samples(&payload).unwrap_or(0));
+                    }
+                    for datagram in packetizer.packetize(&payload, packet_timestamp) {
+                        match socket.send(&datagram) {
+                            Ok(sent) => {
+                                bytes_written.fetch_add(sent as u64, Ordering::Relaxed);
+                            }
+                            // Ohne Empfänger meldet Unicast ICMP-Fehler; kein Grund abzubrechen
+                            Err(e) => {
+                                if errors.fetch_add(1, Ordering::Relaxed) == 0 {
+                                    log::warn!("UdpOutputConsumer '{}': send error: {}", name, e);
+                                }
+                            }
+                        }
+                    }
+                }
+                if !per_packet {
+                    timestamp = timestamp.wrapping_add(frame_samples as u32);
+                }
+                frames_processed.fetch_add(1, Ordering::Relaxed);
+            }
+            connected.store(false, Ordering::SeqCst);
+            log::info!("UdpOutputConsumer '{}': stopped", name);
+        }));
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        self.running.store(false, Ordering::SeqCst);
+        self.wait.notify_all();
+        if let Some(handle) = self.thread_handle.take() {
+            if handle.join().is_err() {
+                self.errors.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        Ok(())
+    }
+
+    fn status(&self) -> ConsumerStatus {
+        ConsumerStatus {
+            running: self.running.load(Ordering::Relaxed),
+            connected: self.connected.load(Ordering::Relaxed),
+            frames_processed: self.frames_processed.load(Ordering::Relaxed),
+            bytes_written: self.bytes_written.load(Ordering::Relaxed),
+            errors: self.errors.load(Ordering::Relaxed),
+            connection: None,
+        }
+    }
+
+    fn attach_input_buffer(&mut self, buffer: Arc<AudioRingBuffer>) {
+        self.input_buffer = Some(buffer);
+    }
+}
+
+impl_connectable_consumer!(UdpOutputConsumer);
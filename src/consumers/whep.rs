@@ -0,0 +1,623 @@
+// src/consumers/whep.rs
+//
+// WHEP-Playout (WebRTC-HTTP Egress Protocol): Browser abonnieren den Ton eines
+// Flows per `POST /whep/<consumer>` mit einem SDP-Offer und erhalten die
+// Antwort (ohne Trickle-ICE, Kandidaten stecken im Answer). Der Consumer
+// kodiert die Flow-Frames einmal nach Opus; ein gemeinsamer Track verteilt die
+// Pakete als RTP an alle Sessions. Ohne Session wird nicht kodiert.
+use crate::impl_connectable_consumer;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, Weak};
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context, Result};
+use bytes::Bytes;
+use webrtc::api::interceptor_registry::register_default_interceptors;
+use webrtc::api::media_engine::{MediaEngine, MIME_TYPE_OPUS};
+use webrtc::api::{APIBuilder, API};
+use webrtc::ice_transport::ice_server::RTCIceServer;
+use webrtc::interceptor::registry::Registry;
+use webrtc::media::Sample;
+use webrtc::peer_connection::configuration::RTCConfiguration;
+use webrtc::peer_connection::peer_connection_state::RTCPeerConnectionState;
+use webrtc::peer_connection::sdp::session_description::RTCSessionDescription;
+use webrtc::peer_connection::RTCPeerConnection;
+use webrtc::rtp_transceiver::rtp_codec::{
+    RTCRtpCodecCapability, RTCRtpCodecParameters, RTPCodecType,
+};
+use webrtc::track::track_local::track_local_static_sample::TrackLocalStaticSample;
+use webrtc::track::track_local::TrackLocal;
+
+pub use crate::codecs::ogg_opus::opus_packet_duration;
+use crate::codecs::{
+    create_encoder_with_options, AudioCodec, CodecKind, OpusEncoderOptions, PCM_CHANNELS,
+};
+use crate::config::{ConfigValues, ConsumerConfig};
+use crate::core::idle::IdleBackoff;
+use crate::core::lock::lock_mutex;
+use crate::core::timestamp::utc_ns_now;
+use crate::core::{AudioRingBuffer, Consumer, ConsumerStatus};
+use crate::producers::wait::StopWait;
+
+const OPUS_PAYLOAD_TYPE: u8 = 111;
+const OPUS_FMTP: &str = "minptime=10;useinbandfec=1;stereo=1";
+const ICE_GATHER_TIMEOUT: Duration = Duration::from_secs(5);
+const DEFAULT_MAX_SESSIONS: u64 = 10;
+/// Ohne Session: so oft wird geprüft, ob jemand zuhört.
+const IDLE_WAIT: Duration = Duration::from_millis(20);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WhepConfig {
+    /// Erwarteter Bearer-Token (`Authorization: Bearer <token>`)
+    pub token: Option<String>,
+    pub ice_servers: Vec<String>,
+    pub max_sessions: usize,
+    /// Codec-ID wie in `supported_codecs`, nur `opuswebrtc`
+    pub codec: String,
+    /// Encoder-Einstellungen (`config.opus`)
+    pub opus: OpusEncoderOptions,
+}
+
+/// Gelesene `config`-Schlüssel (Strict-Modus, siehe `config::options`)
+pub const CONFIG_KEYS: &[&str] = &["codec", "opus", "ice_servers", "max_sessions", "token"];
+
+impl WhepConfig {
+    /// Optional `token`, `ice_servers` (Liste von URLs), `max_sessions`
+    /// (Standard 10), `codec` (Standard `opuswebrtc`) und `opus`.
+    pub fn from_config(name: &str, config: &ConsumerConfig) -> Result<Self> {
+        let values = ConfigValues::new("consumer", name, &config.config);
+
+        let ice_servers = match config.config.get("ice_servers") {
+            None => Vec::new(),
+            Some(value) => value
+                .as_array()
+                .with_context(|| format!("consumer '{}': config.ice_servers must be a list", name))?
+                .iter()
+                .map(|v| {
+                    v.as_str().map(str::to_string).with_context(|| {
+                        format!("consumer '{}': config.ice_servers must contain URLs", name)
+                    })
+                })
+                .collect::<Result<_>>()?,
+        };
+
+        let max_sessions = config
+            .config
+            .get("max_sessions")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(DEFAULT_MAX_SESSIONS);
+        values.check_range("max_sessions", max_sessions, 1, 100)?;
+
+        let codec = config
+            .config
+            .get("codec")
+            .and_then(|v| v.as_str())
+            .unwrap_or("opuswebrtc")
+            .to_ascii_lowercase();
+        if codec != "opuswebrtc" {
+            bail!(
+                "consumer '{}': whep needs codec 'opuswebrtc', got '{}'",
+                name,
+                codec
+            );
+        }
+        let opus = OpusEncoderOptions::from_config("consumer", name, config.config.get("opus"))?;
+        let info = create_encoder_with_options(&codec, PCM_CHANNELS, &opus)
+            .with_context(|| format!("consumer '{}'", name))?
+            .info()
+            .clone();
+        if !matches!(info.kind, CodecKind::OpusWebRtc) || info.sample_rate != 48_000 {
+            bail!(
+                "consumer '{}': encoder for '{}' does not produce 48 kHz Opus packets",
+                name,
+                codec
+            );
+        }
+
+        Ok(Self {
+            token: config
+                .config
+                .get("token")
+                .and_then(|v| v.as_str())
+                .map(str::to_string),
+            ice_servers,
+            max_sessions: max_sessions as usize,
+            codec,
+            opus,
+        })
+    }
+}
+
+/// Fehler beim Anlegen einer Session, mit passendem HTTP-Status.
+#[derive(Debug)]
+pub enum WhepError {
+    Unauthorized,
+    TooManySessions,
+    BadOffer(anyhow::Error),
+    Internal(anyhow::Error),
+}
+
+impl WhepError {
+    pub fn status(&self) -> u16 {
+        match self {
+            WhepError::Unauthorized => 401,
+            WhepError::TooManySessions => 503,
+            WhepError::BadOffer(_) => 400,
+            WhepError::Internal(_) => 500,
+        }
+    }
+}
+
+impl std::fmt::Display for WhepError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WhepError::Unauthorized => write!(f, "invalid or missing bearer token"),
+            WhepError::TooManySessions => write!(f, "maximum number of WHEP sessions reached"),
+            WhepError::BadOffer(e) => write!(f, "invalid SDP offer: {:#}", e),
+            WhepError::Internal(e) => write!(f, "{:#}", e),
+        }
+    }
+}
+
+/// Laufender Endpunkt eines WHEP-Consumers; lebt von `start` bis `stop`.
+pub struct WhepEndpoint {
+    name: String,
+    config: WhepConfig,
+    handle: tokio::runtime::Handle,
+    api: API,
+    running: Arc<AtomicBool>,
+    /// Gemeinsamer Track aller Sessions, paketiert Opus nach RTP
+    track: Arc<TrackLocalStaticSample>,
+    sessions: Mutex<HashMap<String, Arc<RTCPeerConnection>>>,
+    next_session: AtomicU64,
+}
+
+impl WhepEndpoint {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn session_count(&self) -> usize {
+        lock_mutex(&self.sessions, "whep.session_count").len()
+    }
+
+    fn authorize(&self, authorization: Option<&str>) -> Result<(), WhepError> {
+        match &self.config.token {
+            None => Ok(()),
+            Some(token) => match authorization.and_then(|h| h.strip_prefix("Bearer ")) {
+                Some(given) if given.trim() == token => Ok(()),
+                _ => Err(WhepError::Unauthorized),
+            },
+        }
+    }
+
+    /// Beantwortet ein SDP-Offer; liefert Session-ID und SDP-Answer.
+    pub fn offer(
+        self: &Arc<Self>,
+        sdp: String,
+        authorization: Option<&str>,
+    ) -> Result<(String, String), WhepError> {
+        self.authorize(authorization)?;
+        if !self.running.load(Ordering::Relaxed) {
+            return Err(WhepError::Internal(anyhow!(
+                "consumer '{}' is stopped",
+                self.name
+            )));
+        }
+        if self.session_count() >= self.config.max_sessions {
+            return Err(WhepError::TooManySessions);
+        }
+
+        let session_id = format!(
+            "{:x}-{}",
+            utc_ns_now() / 1_000_000,
+            self.next_session.fetch_add(1, Ordering::Relaxed)
+        );
+        let endpoint = self.clone();
+        let id = session_id.clone();
+        let answer = self
+            .handle
+            .block_on(async move { endpoint.negotiate(id, sdp).await })?;
+        log::info!(
+            "WhepConsumer '{}': session {} created",
+            self.name,
+            session_id
+        );
+        Ok((session_id, answer))
+    }
+
+    async fn negotiate(
+        self: Arc<Self>,
+        session_id: String,
+        sdp: String,
+    ) -> Result<String, WhepError> {
+        let offer = RTCSessionDescription::offer(sdp).map_err(|e| WhepError::BadOffer(e.into()))?;
+
+        let config = RTCConfiguration {
+            ice_servers: if self.config.ice_servers.is_empty() {
+                Vec::new()
+            } else {
+                vec![RTCIceServer {
+                    urls: self.config.ice_servers.clone(),
+                    ..Default::default()
+                }]
+            },
+            ..Default::default()
+        };
+        let pc = Arc::new(
+            self.api
+                .new_peer_connection(config)
+                .await
+                .map_err(|e| WhepError::Internal(e.into()))?,
+        );
+
+        let weak: Weak<WhepEndpoint> = Arc::downgrade(&self);
+        let id = session_id.clone();
+        pc.on_peer_connection_state_change(Box::new(move |state| {
+            let weak = weak.clone();
+            let id = id.clone();
+            Box::pin(async move {
+                let Some(endpoint) = weak.upgrade() else {
+                    return;
+                };
+                log::info!("WhepConsumer '{}': session {} {}", endpoint.name, id, state);
+                if matches!(
+                    state,
+                    RTCPeerConnectionState::Failed | RTCPeerConnectionState::Closed
+                ) {
+                    // Nicht im Callback der Verbindung selbst schließen
+                    tokio::spawn(async move {
+                        endpoint.remove_session(&id).await;
+                    });
+                }
+            })
+        }));
+
+        let result = async {
+            pc.set_remote_description(offer)
+                .await
+                .map_err(|e| WhepError::BadOffer(e.into()))?;
+            // Übernimmt den recvonly-Audio-Transceiver aus dem Offer
+            let sender = pc
+                .add_track(self.track.clone() as Arc<dyn TrackLocal + Send + Sync>)
+                .await
+                .map_err(|e| WhepError::Internal(e.into()))?;
+            // RTCP (Receiver Reports, NACK) lesen, sonst laufen die Interceptors leer
+            tokio::spawn(async move {
+                let mut buf = vec![0u8; 1500];
+                while sender.read(&mut buf).await.is_ok() {}
+            });
+            let answer = pc
+                .create_answer(None)
+                .await
+                .map_err(|e| WhepError::BadOffer(e.into()))?;
+            let mut gathered = pc.gathering_complete_promise().await;
+            pc.set_local_description(answer)
+                .await
+                .map_err(|e| WhepError::Internal(e.into()))?;
+            // Kein Trickle-ICE: Answer erst mit allen Kandidaten zurückgeben
+            let _ = tokio::time::timeout(ICE_GATHER_TIMEOUT, gathered.recv()).await;
+            pc.local_description()
+                .await
+                .map(|desc| desc.sdp)
+                .ok_or_else(|| WhepError::Internal(anyhow!("no local description")))
+        }
+        .await;
+
+        match result {
+            Ok(answer) => {
+                lock_mutex(&self.sessions, "whep.negotiate").insert(session_id, pc);
+                Ok(answer)
+            }
+            Err(e) => {
+                let _ = pc.close().await;
+                Err(e)
+            }
+        }
+    }
+
+    async fn remove_session(&self, session_id: &str) -> bool {
+        let pc = lock_mutex(&self.sessions, "whep.remove_session").remove(session_id);
+        match pc {
+            Some(pc) => {
+                let _ = pc.close().await;
+                log::info!(
+                    "WhepConsumer '{}': session {} closed",
+                    self.name,
+                    session_id
+                );
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Beendet eine Session (`DELETE` auf die Resource-URL).
+    pub fn delete(&self, session_id: &str, authorization: Option<&str>) -> Result<bool, WhepError> {
+        self.authorize(authorization)?;
+        Ok(self.handle.block_on(self.remove_session(session_id)))
+    }
+
+    /// Ein Opus-Paket an alle Sessions; liefert die Nutzdatenlänge.
+    fn send(&self, packet: Vec<u8>) -> Result<usize> {
+        let duration = opus_packet_duration(&packet)
+            .ok_or_else(|| anyhow!("not an Opus packet ({} bytes)", packet.len()))?;
+        let len = packet.len();
+        let sample = Sample {
+            data: Bytes::from(packet),
+            duration,
+            ..Default::default()
+        };
+        self.handle
+            .block_on(self.track.write_sample(&sample))
+            .map_err(|e| anyhow!("RTP write failed: {}", e))?;
+        Ok(len)
+    }
+
+    fn close_all(&self) {
+        let sessions: Vec<String> = lock_mutex(&self.sessions, "whep.close_all")
+            .keys()
+            .cloned()
+            .collect();
+        for id in sessions {
+            self.handle.block_on(self.remove_session(&id));
+        }
+    }
+}
+
+static WHEP_ENDPOINTS: OnceLock<Mutex<HashMap<String, Arc<WhepEndpoint>>>> = OnceLock::new();
+
+fn whep_endpoints() -> &'static Mutex<HashMap<String, Arc<WhepEndpoint>>> {
+    WHEP_ENDPOINTS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Endpunkt eines laufenden WHEP-Consumers.
+pub fn whep_endpoint(name: &str) -> Option<Arc<WhepEndpoint>> {
+    lock_mutex(whep_endpoints(), "whep.lookup")
+        .get(name)
+        .cloned()
+}
+
+fn opus_capability() -> RTCRtpCodecCapability {
+    RTCRtpCodecCapability {
+        mime_type: MIME_TYPE_OPUS.to_owned(),
+        clock_rate: 48_000,
+        channels: 2,
+        sdp_fmtp_line: OPUS_FMTP.to_owned(),
+        rtcp_feedback: vec![],
+    }
+}
+
+fn build_api() -> Result<API> {
+    let mut media = MediaEngine::default();
+    media.register_codec(
+        RTCRtpCodecParameters {
+            capability: opus_capability(),
+            payload_type: OPUS_PAYLOAD_TYPE,
+            ..Default::default()
+        },
+        RTPCodecType::Audio,
+    )?;
+    let registry = register_default_interceptors(Registry::new(), &mut media)?;
+    Ok(APIBuilder::new()
+        .with_media_engine(media)
+        .with_interceptor_registry(registry)
+        .build())
+}
+
+pub struct WhepConsumer {
+    name: String,
+    config: WhepConfig,
+    running: Arc<AtomicBool>,
+    input_buffer: Option<Arc<AudioRingBuffer>>,
+    reader_id: String,
+    wait: Arc<StopWait>,
+    thread_handle: Option<std::thread::JoinHandle<()>>,
+    runtime: Option<tokio::runtime::Runtime>,
+    endpoint: Option<Arc<WhepEndpoint>>,
+    frames_processed: Arc<AtomicU64>,
+    bytes_written: Arc<AtomicU64>,
+    errors: Arc<AtomicU64>,
+}
+
+impl WhepConsumer {
+    pub fn new(name: &str, config: &ConsumerConfig) -> Result<Self> {
+        Ok(Self::with_config(
+            name,
+            WhepConfig::from_config(name, config)?,
+        ))
+    }
+
+    pub fn with_config(name: &str, config: WhepConfig) -> Self {
+        Self {
+            name: name.to_string(),
+            config,
+            running: Arc::new(AtomicBool::new(false)),
+            input_buffer: None,
+            reader_id: format!("consumer:{}", name),
+            wait: Arc::new(StopWait::new()),
+            thread_handle: None,
+            runtime: None,
+            endpoint: None,
+            frames_processed: Arc::new(AtomicU64::new(0)),
+            bytes_written: Arc::new(AtomicU64::new(0)),
+            errors: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    pub fn config(&self) -> &WhepConfig {
+        &self.config
+    }
+}
+
+impl Consumer for WhepConsumer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn start(&mut self) -> Result<()> {
+        if self.running.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        let buffer = self
+            .input_buffer
+            .clone()
+            .ok_or_else(|| anyhow!("WhepConsumer '{}' missing input buffer", self.name))?;
+
+        let mut endpoints = lock_mutex(whep_endpoints(), "whep.start");
+        if endpoints.contains_key(&self.name) {
+            bail!("WHEP endpoint '{}' is already registered", self.name);
+        }
+
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(2)
+            .thread_name(format!("whep-{}", self.name))
+            .enable_all()
+            .build()
+            .context("failed to create WHEP runtime")?;
+
+        let endpoint = Arc::new(WhepEndpoint {
+            name: self.name.clone(),
+            config: self.config.clone(),
+            handle: runtime.handle().clone(),
+            api: build_api()?,
+            running: self.running.clone(),
+            track: Arc::new(TrackLocalStaticSample::new(
+                opus_capability(),
+                "audio".to_owned(),
+                format!("airlift-{}", self.name),
+            )),
+            sessions: Mutex::new(HashMap::new()),
+            next_session: AtomicU64::new(0),
+        });
+
+        self.running.store(true, Ordering::SeqCst);
+        endpoints.insert(self.name.clone(), endpoint.clone());
+        drop(endpoints);
+
+        let running = self.running.clone();
+        let wait = self.wait.clone();
+        let reader_id = self.reader_id.clone();
+        let frames_processed = self.frames_processed.clone();
+        let bytes_written = self.bytes_written.clone();
+        let errors = self.errors.clone();
+        let name = self.name.clone();
+        let codec = self.config.codec.clone();
+        let opus = self.config.opus.clone();
+        let thread_endpoint = endpoint.clone();
+
+        self.thread_handle = Some(std::thread::spawn(move || {
+            let endpoint = thread_endpoint;
+            let mut encoder: Option<Box<dyn AudioCodec>> = None;
+            let mut unheard = IdleBackoff::new(IDLE_WAIT);
+            let mut idle = IdleBackoff::new(Duration::from_millis(2));
+            while running.load(Ordering::Relaxed) {
+                if endpoint.session_count() == 0 {
+                    // Niemand hört zu: nicht kodieren, beim nächsten Abonnenten
+                    // mit frischem Encoder live einsteigen
+                    encoder = None;
+                    buffer.skip_to_latest(&reader_id);
+                    unheard.wait(&wait);
+                    continue;
+                }
+                unheard.reset();
+                if encoder.is_none() {
+                    match create_encoder_with_options(&codec, PCM_CHANNELS, &opus) {
+                        Ok(created) => encoder = Some(created),
+                        Err(e) => {
+                            errors.fetch_add(1, Ordering::Relaxed);
+                            log::warn!("WhepConsumer '{}': {:#}", name, e);
+                            wait.wait_timeout(Duration::from_secs(1));
+                            continue;
+                        }
+                    }
+                }
+                let Some(frame) = buffer.pop_for_reader(&reader_id) else {
+                    idle.wait_for(&buffer, &wait);
+                    continue;
+                };
+                idle.reset();
+                let Some(active) = encoder.as_mut() else {
+                    continue;
+                };
+                let encoded = match active.encode(&frame.samples) {
+                    Ok(encoded) => encoded,
+                    Err(e) => {
+                        errors.fetch_add(1, Ordering::Relaxed);
+                        log::debug!("WhepConsumer '{}': encode error: {}", name, e);
+                        continue;
+                    }
+                };
+                for packet in encoded {
+                    match endpoint.send(packet.payload) {
+                        Ok(len) => {
+                            bytes_written.fetch_add(len as u64, Ordering::Relaxed);
+                        }
+                        Err(e) => {
+                            errors.fetch_add(1, Ordering::Relaxed);
+                            log::debug!("WhepConsumer '{}': {:#}", name, e);
+                        }
+                    }
+                }
+                frames_processed.fetch_add(1, Ordering::Relaxed);
+            }
+        }));
+
+        self.endpoint = Some(endpoint);
+        self.runtime = Some(runtime);
+
+        log::info!(
+            "WhepConsumer '{}': serving offers on /whep/{}",
+            self.name,
+            self.name
+        );
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        self.running.store(false, Ordering::SeqCst);
+        self.wait.notify_all();
+        lock_mutex(whep_endpoints(), "whep.stop").remove(&self.name);
+
+        if let Some(handle) = self.thread_handle.take() {
+            if handle.join().is_err() {
+                self.errors.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        if let Some(endpoint) = self.endpoint.take() {
+            endpoint.close_all();
+        }
+        // Runtime nie aus einem ihrer eigenen Tasks heraus droppen
+        if let Some(runtime) = self.runtime.take() {
+            runtime.shutdown_timeout(Duration::from_secs(1));
+        }
+        Ok(())
+    }
+
+    fn status(&self) -> ConsumerStatus {
+        ConsumerStatus {
+            running: self.running.load(Ordering::Relaxed),
+            connected: self
+                .endpoint
+                .as_ref()
+                .is_some_and(|endpoint| endpoint.session_count() > 0),
+            frames_processed: self.frames_processed.load(Ordering::Relaxed),
+            bytes_written: self.bytes_written.load(Ordering::Relaxed),
+            errors: self.errors.load(Ordering::Relaxed),
+            connection: None,
+        }
+    }
+
+    fn attach_input_buffer(&mut self, buffer: Arc<AudioRingBuffer>) {
+        self.input_buffer = Some(buffer);
+    }
+}
+
+impl Drop for WhepConsumer {
+    fn drop(&mut self) {
+        let _ = self.stop();
+    }
+}
+
+impl_connectable_consumer!(WhepConsumer);
@@ -87,7 +87,7 @@ impl Consumer for WsConsumer {
                             "WsConsumer '{}' stats: available={}, buffer_frames={}, dropped={}, processed={}, errors={}, backlog={}",
                             name,
                             available,
-                            stats.current_frames,
+                            stats.current_frames.get(),
                             stats.dropped_frames,
                             frames_processed.load(Ordering::Relaxed),
                             errors.load(Ordering::Relaxed),
@@ -99,8 +99,8 @@ impl Consumer for WsConsumer {
                     if echo_mode {
                         let stats = buffer.stats();
                         let available = buffer.available_for_reader(&reader_id);
-                        if stats.capacity > 0
-                            && available as f32 > (stats.capacity as f32 * 0.75)
+                        if !stats.capacity.is_zero()
+                            && available as f32 > (stats.capacity.get() as f32 * 0.75)
                         {
                             buffer.skip_to_latest(&reader_id);
                             errors.fetch_add(1, Ordering::Relaxed);
@@ -197,6 +197,7 @@ impl Consumer for WsConsumer {
             frames_processed: self.frames_processed.load(Ordering::Relaxed),
             bytes_written: self.bytes_written.load(Ordering::Relaxed),
             errors: self.errors.load(Ordering::Relaxed),
+            connection: None,
         }
     }
 
@@ -0,0 +1,672 @@
+// src/consumers/zmq.rs
+//
+// ZeroMQ-Ausgang (`zmq_pub`): ein PUB-Socket, an den sich beliebige SUB-Sockets
+// (libzmq, pyzmq, ...) verbinden. Jede Nachricht besteht aus drei Teilen:
+// Topic (Standard: Flow-Name), JSON-Header mit Zeitstempel und Format,
+// kodierte Payload aus der Codec-Registry. ZMTP 3.0 mit NULL-Mechanismus ist
+// hier selbst implementiert, nur das Nötigste für einen Publisher; Abos kommen
+// als Nachricht (3.0) oder als SUBSCRIBE/CANCEL-Kommando (3.1). Wie bei ZeroMQ
+// bekommt jeder Subscriber eine Queue mit `hwm` Nachrichten, ist sie voll,
+// werden neue Nachrichten für ihn verworfen. Beim Draining (Config-Änderung,
+// siehe `core::drain`) wird der Port freigegeben, verbundene Subscriber
+// bekommen weiter Nachrichten.
+use crate::impl_connectable_consumer;
+use std::io::{self, ErrorKind, Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context, Result};
+use crossbeam_channel::{bounded, Receiver, RecvTimeoutError, Sender, TrySendError};
+
+use crate::codecs::{create_encoder_with_options, OpusEncoderOptions, PCM_CHANNELS};
+use crate::config::{ConfigValues, ConsumerConfig};
+use crate::core::idle::IdleBackoff;
+use crate::core::lock::lock_mutex;
+use crate::core::{AudioRingBuffer, Consumer, ConsumerStatus};
+use crate::producers::wait::StopWait;
+
+pub const DEFAULT_HWM: usize = 100;
+const MAX_HWM: u64 = 100_000;
+pub const DEFAULT_MAX_SUBSCRIBERS: usize = 32;
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(2);
+const WRITE_TIMEOUT: Duration = Duration::from_secs(2);
+/// Lesetimeout der Sessions: nur anstehende Abos abholen
+const READ_POLL: Duration = Duration::from_millis(1);
+/// So lange wartet eine Session auf die nächste Nachricht
+const SEND_POLL: Duration = Duration::from_millis(20);
+const IDLE_WAIT: Duration = Duration::from_millis(2);
+/// Größter Frame, den ein Subscriber schicken darf (Abos, Kommandos)
+const MAX_INCOMING_FRAME: usize = 64 * 1024;
+const MAX_TOPIC_LEN: usize = 255;
+
+const FLAG_MORE: u8 = 0x01;
+const FLAG_LONG: u8 = 0x02;
+const FLAG_COMMAND: u8 = 0x04;
+const GREETING_LEN: usize = 64;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZmqPubConfig {
+    pub bind: SocketAddr,
+    pub topic: String,
+    /// Codec-ID wie in `supported_codecs`
+    pub codec: String,
+    /// Encoder-Einstellungen für Opus (`config.opus`)
+    pub opus: OpusEncoderOptions,
+    /// Nachrichten je Subscriber, bevor verworfen wird
+    pub hwm: usize,
+    pub max_subscribers: usize,
+}
+
+/// Gelesene `config`-Schlüssel (Strict-Modus, siehe `config::options`)
+pub const CONFIG_KEYS: &[&str] = &["bind", "topic", "codec", "opus", "hwm", "max_subscribers"];
+
+impl ZmqPubConfig {
+    /// Erwartet `url` bzw. `config.bind` (`tcp://*:5556`, `tcp://127.0.0.1:5556`);
+    /// optional `topic` (Standard: Flow-Name), `codec` (Standard `pcm`),
+    /// `opus`, `hwm` und `max_subscribers`.
+    pub fn from_config(name: &str, flow_name: &str, config: &ConsumerConfig) -> Result<Self> {
+        let values = ConfigValues::new("consumer", name, &config.config);
+        let text = |key: &str| -> Option<String> {
+            config
+                .config
+                .get(key)
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+
+        let endpoint = text("bind")
+            .or_else(|| config.url.clone())
+            .ok_or_else(|| anyhow!("consumer '{}': zmq_pub needs config.bind", name))?;
+        let bind = parse_endpoint(&endpoint).with_context(|| format!("consumer '{}'", name))?;
+
+        let topic = text("topic").unwrap_or_else(|| flow_name.to_string());
+        if topic.len() > MAX_TOPIC_LEN {
+            bail!(
+                "consumer '{}': config.topic must not exceed {} bytes",
+                name,
+                MAX_TOPIC_LEN
+            );
+        }
+
+        let codec = text("codec")
+            .unwrap_or_else(|| "pcm".to_string())
+            .to_ascii_lowercase();
+        let opus = OpusEncoderOptions::from_config("consumer", name, config.config.get("opus"))?;
+        create_encoder_with_options(&codec, PCM_CHANNELS, &opus)
+            .with_context(|| format!("consumer '{}'", name))?;
+
+        let hwm = match values.size("hwm")? {
+            Some(hwm) => values.check_range("hwm", hwm, 1, MAX_HWM)? as usize,
+            None => DEFAULT_HWM,
+        };
+        let max_subscribers = match values.size("max_subscribers")? {
+            Some(max) => values.check_range("max_subscribers", max, 1, 1024)? as usize,
+            None => DEFAULT_MAX_SUBSCRIBERS,
+        };
+
+        Ok(Self {
+            bind,
+            topic,
+            codec,
+            opus,
+            hwm,
+            max_subscribers,
+        })
+    }
+}
+
+/// `tcp://host:port`; `*` steht wie bei ZeroMQ für alle Interfaces.
+pub fn parse_endpoint(endpoint: &str) -> Result<SocketAddr> {
+    let rest = endpoint
+        .trim()
+        .strip_prefix("tcp://")
+        .ok_or_else(|| anyhow!("endpoint '{}' must start with tcp://", endpoint))?;
+    let rest = match rest.strip_prefix("*:") {
+        Some(port) => format!("0.0.0.0:{}", port),
+        None => rest.to_string(),
+    };
+    rest.to_socket_addrs()
+        .ok()
+        .and_then(|mut addrs| addrs.next())
+        .ok_or_else(|| anyhow!("invalid endpoint '{}'", endpoint))
+}
+
+/// Hängt einen ZMTP-Frame an `out` an.
+pub fn write_frame(out: &mut Vec<u8>, flags: u8, body: &[u8]) {
+    if body.len() > 255 {
+        out.push(flags | FLAG_LONG);
+        out.extend_from_slice(&(body.len() as u64).to_be_bytes());
+    } else {
+        out.push(flags);
+        out.push(body.len() as u8);
+    }
+    out.extend_from_slice(body);
+}
+
+/// Liest einen ZMTP-Frame vom Anfang von `data`: `(flags, body, Länge)`.
+pub fn parse_frame(data: &[u8], max_body: usize) -> Result<Option<(u8, Vec<u8>, usize)>> {
+    let Some(&flags) = data.first() else {
+        return Ok(None);
+    };
+    let (len, header) = if flags & FLAG_LONG != 0 {
+        let Some(bytes) = data.get(1..9) else {
+            return Ok(None);
+        };
+        (u64::from_be_bytes(bytes.try_into()?) as usize, 9)
+    } else {
+        let Some(&len) = data.get(1) else {
+            return Ok(None);
+        };
+        (len as usize, 2)
+    };
+    if len > max_body {
+        bail!("frame of {} bytes exceeds limit of {}", len, max_body);
+    }
+    match data.get(header..header + len) {
+        Some(body) => Ok(Some((flags, body.to_vec(), header + len))),
+        None => Ok(None),
+    }
+}
+
+/// Greeting für ZMTP 3.0 mit NULL-Mechanismus.
+pub fn greeting(as_server: bool) -> [u8; GREETING_LEN] {
+    let mut greeting = [0u8; GREETING_LEN];
+    greeting[0] = 0xff;
+    greeting[9] = 0x7f;
+    greeting[10] = 3;
+    greeting[11] = 0;
+    greeting[12..16].copy_from_slice(b"NULL");
+    greeting[32] = as_server as u8;
+    greeting
+}
+
+/// Kommando-Body: Name mit Längenbyte, dann die Daten.
+pub fn command(name: &str, data: &[u8]) -> Vec<u8> {
+    let mut body = Vec::with_capacity(1 + name.len() + data.len());
+    body.push(name.len() as u8);
+    body.extend_from_slice(name.as_bytes());
+    body.extend_from_slice(data);
+    body
+}
+
+/// READY-Kommando mit `Socket-Type`.
+pub fn ready(socket_type: &str) -> Vec<u8> {
+    let mut properties = Vec::new();
+    properties.push(b"Socket-Type".len() as u8);
+    properties.extend_from_slice(b"Socket-Type");
+    properties.extend_from_slice(&(socket_type.len() as u32).to_be_bytes());
+    properties.extend_from_slice(socket_type.as_bytes());
+    let mut out = Vec::new();
+    write_frame(&mut out, FLAG_COMMAND, &command("READY", &properties));
+    out
+}
+
+fn split_command(body: &[u8]) -> Result<(&[u8], &[u8])> {
+    let len = *body.first().ok_or_else(|| anyhow!("empty command"))? as usize;
+    let name = body
+        .get(1..1 + len)
+        .ok_or_else(|| anyhow!("truncated command name"))?;
+    Ok((name, &body[1 + len..]))
+}
+
+fn socket_type(mut properties: &[u8]) -> Result<Option<String>> {
+    while !properties.is_empty() {
+        let name_len = properties[0] as usize;
+        let name = properties
+            .get(1..1 + name_len)
+            .ok_or_else(|| anyhow!("truncated READY property"))?;
+        let value_len = properties
+            .get(1 + name_len..5 + name_len)
+            .ok_or_else(|| anyhow!("truncated READY property"))?;
+        let value_len = u32::from_be_bytes(value_len.try_into()?) as usize;
+        let start = 5 + name_len;
+        let value = properties
+            .get(start..start + value_len)
+            .ok_or_else(|| anyhow!("truncated READY property"))?;
+        if name.eq_ignore_ascii_case(b"Socket-Type") {
+            return Ok(Some(String::from_utf8_lossy(value).into_owned()));
+        }
+        properties = &properties[start + value_len..];
+    }
+    Ok(None)
+}
+
+/// Baut eine komplette Nachricht (Topic, Header, Payload) als Bytes.
+pub fn message(topic: &str, header: &serde_json::Value, payload: &[u8]) -> Vec<u8> {
+    let header = header.to_string();
+    let mut out = Vec::with_capacity(topic.len() + header.len() + payload.len() + 24);
+    write_frame(&mut out, FLAG_MORE, topic.as_bytes());
+    write_frame(&mut out, FLAG_MORE, header.as_bytes());
+    write_frame(&mut out, 0, payload);
+    out
+}
+
+struct Session {
+    name: String,
+    topic: String,
+    running: Arc<AtomicBool>,
+    subscribers: Arc<AtomicU64>,
+    bytes_written: Arc<AtomicU64>,
+    messages: Receiver<Arc<Vec<u8>>>,
+}
+
+impl Session {
+    fn run(&self, mut stream: TcpStream) -> Result<()> {
+        stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+        stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+        let _ = stream.set_nodelay(true);
+
+        stream.write_all(&greeting(true))?;
+        let mut peer = [0u8; GREETING_LEN];
+        stream.read_exact(&mut peer).context("greeting")?;
+        if peer[0] != 0xff || peer[9] != 0x7f {
+            bail!("peer does not speak ZMTP");
+        }
+        if peer[10] < 3 {
+            bail!("peer speaks ZMTP {}.x, need 3.0 or later", peer[10]);
+        }
+        if &peer[12..16] != b"NULL" || peer[16..32].iter().any(|&b| b != 0) {
+            bail!("peer requires a security mechanism other than NULL");
+        }
+        stream.write_all(&ready("PUB"))?;
+
+        let mut pending = Vec::new();
+        let (flags, body) = self.read_frame(&mut stream, &mut pending)?;
+        if flags & FLAG_COMMAND == 0 {
+            bail!("expected READY command");
+        }
+        let (name, properties) = split_command(&body)?;
+        if name != b"READY" {
+            bail!("expected READY, got {}", String::from_utf8_lossy(name));
+        }
+        match socket_type(properties)?.as_deref() {
+            Some("SUB") | Some("XSUB") => {}
+            other => bail!("peer socket type {:?} cannot subscribe to PUB", other),
+        }
+
+        self.subscribers.fetch_add(1, Ordering::SeqCst);
+        let result = self.serve(&mut stream, pending);
+        self.subscribers.fetch_sub(1, Ordering::SeqCst);
+        result
+    }
+
+    fn read_frame(&self, stream: &mut TcpStream, pending: &mut Vec<u8>) -> Result<(u8, Vec<u8>)> {
+        let mut chunk = [0u8; 4096];
+        loop {
+            if let Some((flags, body, len)) = parse_frame(pending, MAX_INCOMING_FRAME)? {
+                pending.drain(..len);
+                return Ok((flags, body));
+            }
+            let read = stream.read(&mut chunk)?;
+            if read == 0 {
+                bail!("connection closed during handshake");
+            }
+            pending.extend_from_slice(&chunk[..read]);
+        }
+    }
+
+    fn serve(&self, stream: &mut TcpStream, mut pending: Vec<u8>) -> Result<()> {
+        stream.set_read_timeout(Some(READ_POLL))?;
+        let mut subscriptions: Vec<Vec<u8>> = Vec::new();
+        let mut subscribed = false;
+        let mut chunk = [0u8; 4096];
+        while self.running.load(Ordering::Relaxed) {
+            match stream.read(&mut chunk) {
+                Ok(0) => return Ok(()),
+                Ok(read) => pending.extend_from_slice(&chunk[..read]),
+                Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+                Err(e) => return Err(e.into()),
+            }
+            while let Some((flags, body, len)) = parse_frame(&pending, MAX_INCOMING_FRAME)? {
+                pending.drain(..len);
+                if let Some(reply) = self.handle_incoming(flags, &body, &mut subscriptions)? {
+                    stream.write_all(&reply)?;
+                }
+                subscribed = subscriptions
+                    .iter()
+                    .any(|prefix| self.topic.as_bytes().starts_with(prefix));
+            }
+
+            match self.messages.recv_timeout(SEND_POLL) {
+                Ok(message) => {
+                    if subscribed {
+                        stream.write_all(&message)?;
+                        self.bytes_written
+                            .fetch_add(message.len() as u64, Ordering::Relaxed);
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => return Ok(()),
+            }
+        }
+        Ok(())
+    }
+
+    /// Abos (3.0: Nachricht mit 0x01/0x00, 3.1: SUBSCRIBE/CANCEL) und PING.
+    fn handle_incoming(
+        &self,
+        flags: u8,
+        body: &[u8],
+        subscriptions: &mut Vec<Vec<u8>>,
+    ) -> Result<Option<Vec<u8>>> {
+        let (subscribe, prefix) = if flags & FLAG_COMMAND != 0 {
+            let (name, data) = split_command(body)?;
+            match name {
+                b"SUBSCRIBE" => (true, data),
+                b"CANCEL" => (false, data),
+                b"PING" => {
+                    // PONG mit dem Kontext des PING (nach 2 Byte TTL)
+                    let context = data.get(2..).unwrap_or_default();
+                    let mut reply = Vec::new();
+                    write_frame(&mut reply, FLAG_COMMAND, &command("PONG", context));
+                    return Ok(Some(reply));
+                }
+                _ => return Ok(None),
+            }
+        } else {
+            match body.split_first() {
+                Some((1, prefix)) => (true, prefix),
+                Some((0, prefix)) => (false, prefix),
+                _ => return Ok(None),
+            }
+        };
+        if subscribe {
+            log::debug!(
+                "ZmqPubConsumer '{}': subscription '{}'",
+                self.name,
+                String::from_utf8_lossy(prefix)
+            );
+            subscriptions.push(prefix.to_vec());
+        } else if let Some(index) = subscriptions.iter().position(|s| s == prefix) {
+            subscriptions.remove(index);
+        }
+        Ok(None)
+    }
+}
+
+struct Subscriber {
+    sender: Sender<Arc<Vec<u8>>>,
+    stream: TcpStream,
+    handle: thread::JoinHandle<()>,
+}
+
+pub struct ZmqPubConsumer {
+    name: String,
+    config: ZmqPubConfig,
+    running: Arc<AtomicBool>,
+    input_buffer: Option<Arc<AudioRingBuffer>>,
+    reader_id: String,
+    thread_handle: Option<thread::JoinHandle<()>>,
+    wait: Arc<StopWait>,
+    local_addr: Arc<Mutex<Option<SocketAddr>>>,
+    /// `None` nach `stop` bzw. ab `begin_drain`
+    listener: Arc<Mutex<Option<TcpListener>>>,
+    subscribers: Arc<AtomicU64>,
+    frames_processed: Arc<AtomicU64>,
+    bytes_written: Arc<AtomicU64>,
+    dropped: Arc<AtomicU64>,
+    errors: Arc<AtomicU64>,
+}
+
+impl ZmqPubConsumer {
+    pub fn new(name: &str, flow_name: &str, config: &ConsumerConfig) -> Result<Self> {
+        Ok(Self::with_config(
+            name,
+            ZmqPubConfig::from_config(name, flow_name, config)?,
+        ))
+    }
+
+    pub fn with_config(name: &str, config: ZmqPubConfig) -> Self {
+        Self {
+            name: name.to_string(),
+            config,
+            running: Arc::new(AtomicBool::new(false)),
+            input_buffer: None,
+            reader_id: format!("consumer:{}", name),
+            thread_handle: None,
+            wait: Arc::new(StopWait::new()),
+            local_addr: Arc::new(Mutex::new(None)),
+            listener: Arc::new(Mutex::new(None)),
+            subscribers: Arc::new(AtomicU64::new(0)),
+            frames_processed: Arc::new(AtomicU64::new(0)),
+            bytes_written: Arc::new(AtomicU64::new(0)),
+            dropped: Arc::new(AtomicU64::new(0)),
+            errors: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    pub fn config(&self) -> &ZmqPubConfig {
+        &self.config
+    }
+
+    /// Tatsächliche Adresse (bei Port 0 vom System gewählt).
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        *lock_mutex(&self.local_addr, "zmq_pub.local_addr")
+    }
+
+    /// Subscriber mit abgeschlossenem Handshake.
+    pub fn subscribers(&self) -> u64 {
+        self.subscribers.load(Ordering::SeqCst)
+    }
+
+    /// Wegen voller Queue (`hwm`) verworfene Nachrichten.
+    pub fn dropped_messages(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+fn accept_pending(
+    listener: &TcpListener,
+    subscribers: &mut Vec<Subscriber>,
+    session: impl Fn(Receiver<Arc<Vec<u8>>>) -> Session,
+    config: &ZmqPubConfig,
+    name: &str,
+) -> io::Result<()> {
+    loop {
+        let (stream, addr) = match listener.accept() {
+            Ok(accepted) => accepted,
+            Err(e) if e.kind() == ErrorKind::WouldBlock => return Ok(()),
+            Err(e) => return Err(e),
+        };
+        subscribers.retain(|subscriber| !subscriber.handle.is_finished());
+        if subscribers.len() >= config.max_subscribers {
+            log::warn!(
+                "ZmqPubConsumer '{}': rejecting {} (max_subscribers {} reached)",
+                name,
+                addr,
+                config.max_subscribers
+            );
+            continue;
+        }
+        stream.set_nonblocking(false)?;
+        let control = stream.try_clone()?;
+        let (sender, receiver) = bounded(config.hwm);
+        let session = session(receiver);
+        let name = name.to_string();
+        let handle = thread::spawn(move || {
+            log::info!(
+                "ZmqPubConsumer '{}': subscriber connected from {}",
+                name,
+                addr
+            );
+            match session.run(stream) {
+                Ok(()) => log::info!("ZmqPubConsumer '{}': subscriber {} left", name, addr),
+                Err(e) => log::warn!("ZmqPubConsumer '{}': subscriber {}: {:#}", name, addr, e),
+            }
+        });
+        subscribers.push(Subscriber {
+            sender,
+            stream: control,
+            handle,
+        });
+    }
+}
+
+impl Consumer for ZmqPubConsumer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn start(&mut self) -> Result<()> {
+        if self.running.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        let buffer = self
+            .input_buffer
+            .clone()
+            .ok_or_else(|| anyhow!("ZmqPubConsumer '{}' missing input buffer", self.name))?;
+        let mut encoder =
+            create_encoder_with_options(&self.config.codec, PCM_CHANNELS, &self.config.opus)?;
+        let listener = TcpListener::bind(self.config.bind).with_context(|| {
+            format!(
+                "ZmqPubConsumer '{}': bind {} failed",
+                self.name, self.config.bind
+            )
+        })?;
+        listener.set_nonblocking(true)?;
+        let local = listener.local_addr()?;
+        *lock_mutex(&self.local_addr, "zmq_pub.start") = Some(local);
+        *lock_mutex(&self.listener, "zmq_pub.start") = Some(listener);
+        log::info!(
+            "ZmqPubConsumer '{}': publishing topic '{}' on tcp://{}",
+            self.name,
+            self.config.topic,
+            local
+        );
+
+        self.running.store(true, Ordering::SeqCst);
+        let running = self.running.clone();
+        let subscriber_count = self.subscribers.clone();
+        let frames_processed = self.frames_processed.clone();
+        let bytes_written = self.bytes_written.clone();
+        let dropped = self.dropped.clone();
+        let errors = self.errors.clone();
+        let reader_id = self.reader_id.clone();
+        let config = self.config.clone();
+        let name = self.name.clone();
+        let wait = self.wait.clone();
+        let listener = self.listener.clone();
+
+        self.thread_handle = Some(thread::spawn(move || {
+            let session = |messages| Session {
+                name: name.clone(),
+                topic: config.topic.clone(),
+                running: running.clone(),
+                subscribers: subscriber_count.clone(),
+                bytes_written: bytes_written.clone(),
+                messages,
+            };
+            let mut subscribers: Vec<Subscriber> = Vec::new();
+            let mut sequence = 0u64;
+            let mut idle = IdleBackoff::new(IDLE_WAIT);
+            // Nur live senden, kein Rückstau aus der Zeit vor dem Start
+            buffer.skip_to_latest(&reader_id);
+
+            while running.load(Ordering::Relaxed) {
+                if let Some(listener) = lock_mutex(&listener, "zmq_pub.accept").as_ref() {
+                    if let Err(e) =
+                        accept_pending(listener, &mut subscribers, session, &config, &name)
+                    {
+                        log::warn!("ZmqPubConsumer '{}': accept failed: {}", name, e);
+                        errors.fetch_add(1, Ordering::Relaxed);
+                    }
+                }
+                let Some(frame) = buffer.pop_for_reader(&reader_id) else {
+                    idle.wait_for(&buffer, &wait);
+                    continue;
+                };
+                idle.reset();
+                let encoded = match encoder.encode(&frame.samples) {
+                    Ok(encoded) => encoded,
+                    Err(e) => {
+                        errors.fetch_add(1, Ordering::Relaxed);
+                        log::debug!("ZmqPubConsumer '{}': encode error: {}", name, e);
+                        continue;
+                    }
+                };
+                for packet in encoded {
+                    sequence += 1;
+                    let header = serde_json::json!({
+                        "seq": sequence,
+                        "utc_ns": frame.utc_ns,
+                        "sample_rate": packet.info.sample_rate,
+                        "channels": packet.info.channels,
+                        "codec": config.codec,
+                    });
+                    let message = Arc::new(message(&config.topic, &header, &packet.payload));
+                    subscribers.retain(|subscriber| {
+                        match subscriber.sender.try_send(message.clone()) {
+                            Ok(()) => true,
+                            Err(TrySendError::Full(_)) => {
+                                dropped.fetch_add(1, Ordering::Relaxed);
+                                true
+                            }
+                            Err(TrySendError::Disconnected(_)) => false,
+                        }
+                    });
+                }
+                frames_processed.fetch_add(1, Ordering::Relaxed);
+            }
+
+            for subscriber in subscribers {
+                drop(subscriber.sender);
+                let _ = subscriber.stream.shutdown(Shutdown::Both);
+                let _ = subscriber.handle.join();
+            }
+            running.store(false, Ordering::SeqCst);
+        }));
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        self.running.store(false, Ordering::SeqCst);
+        self.wait.notify_all();
+        if let Some(handle) = self.thread_handle.take() {
+            if handle.join().is_err() {
+                self.errors.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        *lock_mutex(&self.local_addr, "zmq_pub.stop") = None;
+        *lock_mutex(&self.listener, "zmq_pub.stop") = None;
+        Ok(())
+    }
+
+    fn status(&self) -> ConsumerStatus {
+        ConsumerStatus {
+            running: self.running.load(Ordering::Relaxed),
+            connected: self.subscribers.load(Ordering::Relaxed) > 0,
+            frames_processed: self.frames_processed.load(Ordering::Relaxed),
+            bytes_written: self.bytes_written.load(Ordering::Relaxed),
+            errors: self.errors.load(Ordering::Relaxed),
+            connection: None,
+        }
+    }
+
+    fn attach_input_buffer(&mut self, buffer: Arc<AudioRingBuffer>) {
+        self.input_buffer = Some(buffer);
+    }
+
+    fn listeners(&self) -> usize {
+        self.subscribers.load(Ordering::SeqCst) as usize
+    }
+
+    fn begin_drain(&mut self) -> bool {
+        if lock_mutex(&self.listener, "zmq_pub.begin_drain").take().is_none() {
+            return false;
+        }
+        *lock_mutex(&self.local_addr, "zmq_pub.begin_drain") = None;
+        log::info!(
+            "ZmqPubConsumer '{}': draining, {} freed for the new configuration",
+            self.name,
+            self.config.bind
+        );
+        true
+    }
+}
+
+impl_connectable_consumer!(ZmqPubConsumer);
@@ -0,0 +1,480 @@
+// src/core/analyzer_taps.rs
+//
+// Analyzer-Abgriffe an frei wählbaren Stellen eines Flows: Peak, Lautheit
+// (LUFS) oder Oktavspektrum hängen sich per eigenem Reader an einen Input
+// (z. B. den Producer-Buffer), den Merge-Buffer vor der Processor-Kette, den
+// Buffer hinter einem bestimmten Processor oder den Output. Gemessen wird in
+// einem eigenen Thread je Flow, der Processing-Thread bleibt unberührt.
+// Ohne eigene Angabe bekommt jeder Flow die Standard-Analyzer (`[analyzers]`,
+// Peak und Stille am Output), die mit dem Flow entstehen und verschwinden.
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::{anyhow, bail};
+use serde::Serialize;
+
+use crate::audio::loudness::{LoudnessMeter, BLOCK_MS};
+use crate::audio::spectrum::{SpectrumAnalyzer, OCTAVE_BANDS_HZ};
+use crate::config::ConfigValues;
+use crate::core::event_bus::EventEmitter;
+use crate::core::idle::IdleBackoff;
+use crate::core::events::{EventPriority, EventType};
+use crate::core::lock::lock_mutex;
+use crate::core::peak_rates::{MultiRatePeaks, PeakRates, PeakTier};
+use crate::core::ringbuffer::AudioRingBuffer;
+use crate::ring::PcmFrame;
+
+pub const DEFAULT_ANALYZER_INTERVAL: Duration = Duration::from_millis(100);
+/// Was jeder Flow ohne `[analyzers]` automatisch am Output misst
+pub const DEFAULT_ANALYZER_KINDS: [AnalyzerKind; 2] = [AnalyzerKind::Peak, AnalyzerKind::Silence];
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Stelle im Flow, an der ein Analyzer mitliest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TapPoint {
+    /// `input:<name>` – ein Input wie in `flows.<flow>.inputs` (Producer,
+    /// Failover-Gruppe, Registry-Buffer), vor dem Merge
+    Input(String),
+    /// `merge` – alle Inputs zusammen, vor der Processor-Kette
+    Merge,
+    /// `processor:<name>` – hinter diesem Processor
+    Processor(String),
+    /// `output` – was die Consumer bekommen
+    Output,
+}
+
+impl TapPoint {
+    pub fn parse(text: &str) -> anyhow::Result<Self> {
+        let text = text.trim();
+        let point = match text.split_once(':') {
+            None if text == "merge" => TapPoint::Merge,
+            None if text == "output" => TapPoint::Output,
+            Some(("input", name)) if !name.is_empty() => TapPoint::Input(name.to_string()),
+            Some(("processor", name)) if !name.is_empty() => TapPoint::Processor(name.to_string()),
+            _ => bail!(
+                "unknown tap point '{}' (expected input:<name>, merge, processor:<name> or output)",
+                text
+            ),
+        };
+        Ok(point)
+    }
+}
+
+impl fmt::Display for TapPoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TapPoint::Input(name) => write!(f, "input:{}", name),
+            TapPoint::Merge => write!(f, "merge"),
+            TapPoint::Processor(name) => write!(f, "processor:{}", name),
+            TapPoint::Output => write!(f, "output"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnalyzerKind {
+    Peak,
+    Lufs,
+    Spectrum,
+    Silence,
+}
+
+impl AnalyzerKind {
+    pub fn parse(text: &str) -> Option<Self> {
+        match text.trim().to_ascii_lowercase().as_str() {
+            "peak" => Some(AnalyzerKind::Peak),
+            "lufs" | "loudness" => Some(AnalyzerKind::Lufs),
+            "spectrum" => Some(AnalyzerKind::Spectrum),
+            "silence" => Some(AnalyzerKind::Silence),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AnalyzerKind::Peak => "peak",
+            AnalyzerKind::Lufs => "lufs",
+            AnalyzerKind::Spectrum => "spectrum",
+            AnalyzerKind::Silence => "silence",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnalyzerTapConfig {
+    pub name: String,
+    pub point: TapPoint,
+    pub kind: AnalyzerKind,
+    /// Fensterlänge = Abstand der Messwerte
+    pub interval: Duration,
+}
+
+impl AnalyzerTapConfig {
+    /// Liest `flows.<flow>.config.analyzers`: Tabelle Tap-Name →
+    /// `{ point = "processor:eq", kind = "lufs", interval = "200ms" }`.
+    /// Inputs und Processors müssen im Flow vorkommen.
+    pub fn from_config(
+        flow_name: &str,
+        value: &serde_json::Value,
+        inputs: &[String],
+        processors: &[String],
+    ) -> anyhow::Result<Vec<Self>> {
+        let serde_json::Value::Object(taps) = value else {
+            bail!(
+                "flow '{}': config.analyzers must be a table, got {}",
+                flow_name,
+                value
+            );
+        };
+
+        let mut configs = Vec::new();
+        for (name, tap) in taps {
+            let owner = format!("{}.{}", flow_name, name);
+            let serde_json::Value::Object(map) = tap else {
+                bail!("analyzer '{}': must be a table, got {}", owner, tap);
+            };
+            let map: HashMap<String, serde_json::Value> = map.clone().into_iter().collect();
+            let values = ConfigValues::new("analyzer", &owner, &map);
+
+            let point = map
+                .get("point")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| anyhow!("analyzer '{}': config.point missing", owner))?;
+            let point =
+                TapPoint::parse(point).map_err(|e| anyhow!("analyzer '{}': {}", owner, e))?;
+            match &point {
+                TapPoint::Input(input) if !inputs.contains(input) => bail!(
+                    "analyzer '{}': '{}' is not an input of flow '{}'",
+                    owner,
+                    input,
+                    flow_name
+                ),
+                TapPoint::Processor(processor) if !processors.contains(processor) => bail!(
+                    "analyzer '{}': processor '{}' is not part of flow '{}'",
+                    owner,
+                    processor,
+                    flow_name
+                ),
+                _ => {}
+            }
+
+            let kind = map.get("kind").and_then(|v| v.as_str()).unwrap_or("peak");
+            let kind = AnalyzerKind::parse(kind).ok_or_else(|| {
+                anyhow!(
+                    "analyzer '{}': unknown kind '{}' (peak, lufs, spectrum or silence)",
+                    owner,
+                    kind
+                )
+            })?;
+
+            let interval = values
+                .duration("interval")?
+                .unwrap_or(DEFAULT_ANALYZER_INTERVAL);
+            check_interval(&owner, kind, interval)?;
+
+            configs.push(Self {
+                name: name.clone(),
+                point,
+                kind,
+                interval,
+            });
+        }
+        Ok(configs)
+    }
+
+    /// Standard-Analyzer am Output, benannt nach ihrer Art (`peak`,
+    /// `silence`, ...).
+    pub fn defaults(kinds: &[AnalyzerKind], interval: Duration) -> anyhow::Result<Vec<Self>> {
+        let mut configs: Vec<Self> = Vec::new();
+        for kind in kinds {
+            check_interval(&format!("analyzers.{}", kind.as_str()), *kind, interval)?;
+            if configs.iter().any(|config| config.kind == *kind) {
+                bail!("analyzers.kinds lists '{}' twice", kind.as_str());
+            }
+            configs.push(Self {
+                name: kind.as_str().to_string(),
+                point: TapPoint::Output,
+                kind: *kind,
+                interval,
+            });
+        }
+        Ok(configs)
+    }
+}
+
+fn check_interval(owner: &str, kind: AnalyzerKind, interval: Duration) -> anyhow::Result<()> {
+    let ms = interval.as_millis() as u64;
+    if !(10..=10_000).contains(&ms) {
+        bail!(
+            "analyzer '{}': config.interval = {} out of range (10..=10000)",
+            owner,
+            ms
+        );
+    }
+    if kind == AnalyzerKind::Lufs && !ms.is_multiple_of(BLOCK_MS) {
+        bail!(
+            "analyzer '{}': lufs interval must be a multiple of {} ms",
+            owner,
+            BLOCK_MS
+        );
+    }
+    Ok(())
+}
+
+/// Messwerte eines Analyzers (Pegel linear, 1.0 = Vollaussteuerung).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AnalyzerValues {
+    Peak {
+        peaks: [f32; 2],
+        rms: [f32; 2],
+        clipped: u64,
+    },
+    Lufs {
+        /// Short-Term (3 s)
+        short_term: Option<f32>,
+        /// Letzter 100-ms-Block
+        block: Option<f32>,
+    },
+    Spectrum {
+        bands_hz: Vec<f32>,
+        /// dBFS je Band, `None` oberhalb der halben Samplerate
+        levels_db: Vec<Option<f32>>,
+    },
+    Silence {
+        /// Höchster Peak beider Kanäle im Fenster
+        peak: f32,
+        silent: bool,
+        /// Dauer der aktuellen Stille, 0 bei Audio
+        silent_ms: u64,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AnalyzerReading {
+    pub point: String,
+    /// Audio-Zeitstempel am Fensterende
+    pub utc_ns: u64,
+    pub window_ms: u64,
+    #[serde(flatten)]
+    pub values: AnalyzerValues,
+}
+
+enum AnalyzerState {
+    /// Gleiche Messung wie der Flow-Meter, nur die schnellste Stufe
+    Peak(MultiRatePeaks),
+    Lufs {
+        meter: LoudnessMeter,
+        blocks: u64,
+    },
+    Spectrum(SpectrumAnalyzer),
+    Silence {
+        peaks: MultiRatePeaks,
+        /// Audio-Zeitstempel, an dem die aktuelle Stille begann
+        silent_since: Option<u64>,
+    },
+}
+
+/// Ein Analyzer ohne Seiteneffekte; der Flow füttert ihn aus dem Abgriff.
+pub struct AnalyzerTap {
+    config: AnalyzerTapConfig,
+    state: AnalyzerState,
+}
+
+impl AnalyzerTap {
+    pub fn new(config: AnalyzerTapConfig) -> Self {
+        let state = match config.kind {
+            AnalyzerKind::Peak => AnalyzerState::Peak(MultiRatePeaks::new(PeakRates {
+                meter: config.interval,
+                stats: config.interval,
+                aggregate: config.interval,
+            })),
+            AnalyzerKind::Lufs => AnalyzerState::Lufs {
+                meter: LoudnessMeter::new(),
+                blocks: 0,
+            },
+            AnalyzerKind::Spectrum => AnalyzerState::Spectrum(SpectrumAnalyzer::new()),
+            AnalyzerKind::Silence => AnalyzerState::Silence {
+                peaks: MultiRatePeaks::new(PeakRates {
+                    meter: config.interval,
+                    stats: config.interval,
+                    aggregate: config.interval,
+                }),
+                silent_since: None,
+            },
+        };
+        Self { config, state }
+    }
+
+    pub fn config(&self) -> &AnalyzerTapConfig {
+        &self.config
+    }
+
+    /// Liefert die Messwerte aller Fenster, die mit diesem Frame enden.
+    /// Peak-Fenster sind sample-genau, LUFS und Spektrum enden an Frame-Grenzen.
+    pub fn push(&mut self, frame: &PcmFrame) -> Vec<AnalyzerReading> {
+        if frame.channels == 0 || frame.sample_rate == 0 {
+            return Vec::new();
+        }
+        let window_ms = self.config.interval.as_millis() as u64;
+        let frame_end = frame.utc_ns
+            + (frame.samples.len() / frame.channels as usize) as u64 * 1_000_000_000
+                / frame.sample_rate as u64;
+        let reading = |utc_ns, values| AnalyzerReading {
+            point: self.config.point.to_string(),
+            utc_ns,
+            window_ms,
+            values,
+        };
+
+        match &mut self.state {
+            AnalyzerState::Peak(peaks) => peaks
+                .push(frame)
+                .into_iter()
+                .filter(|window| window.tier == PeakTier::Meter)
+                .map(|window| {
+                    reading(
+                        window.utc_ns,
+                        AnalyzerValues::Peak {
+                            peaks: window.peaks,
+                            rms: window.rms,
+                            clipped: window.clipped,
+                        },
+                    )
+                })
+                .collect(),
+            AnalyzerState::Lufs { meter, blocks } => {
+                *blocks += meter.push(frame) as u64;
+                if *blocks < window_ms / BLOCK_MS {
+                    return Vec::new();
+                }
+                *blocks = 0;
+                vec![reading(
+                    frame_end,
+                    AnalyzerValues::Lufs {
+                        short_term: meter.short_term(),
+                        block: meter.last_block(),
+                    },
+                )]
+            }
+            AnalyzerState::Spectrum(spectrum) => {
+                spectrum.push(frame);
+                let window_frames = window_ms * frame.sample_rate as u64 / 1_000;
+                if spectrum.frames() < window_frames.max(1) {
+                    return Vec::new();
+                }
+                vec![reading(
+                    frame_end,
+                    AnalyzerValues::Spectrum {
+                        bands_hz: OCTAVE_BANDS_HZ.to_vec(),
+                        levels_db: spectrum.take(),
+                    },
+                )]
+            }
+            AnalyzerState::Silence {
+                peaks,
+                silent_since,
+            } => peaks
+                .push(frame)
+                .into_iter()
+                .filter(|window| window.tier == PeakTier::Meter)
+                .map(|window| {
+                    let peak = window.peaks[0].max(window.peaks[1]);
+                    let silent = window.silence;
+                    let window_start = window.utc_ns.saturating_sub(window_ms * 1_000_000);
+                    *silent_since = if silent {
+                        Some(silent_since.unwrap_or(window_start))
+                    } else {
+                        None
+                    };
+                    let silent_ms = silent_since
+                        .map(|since| window.utc_ns.saturating_sub(since) / 1_000_000)
+                        .unwrap_or(0);
+                    reading(
+                        window.utc_ns,
+                        AnalyzerValues::Silence {
+                            peak,
+                            silent,
+                            silent_ms,
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Letzter Messwert je Tap-Name.
+pub type AnalyzerReadings = BTreeMap<String, AnalyzerReading>;
+
+/// Mess-Thread eines Flows: liest alle Abgriffe mit eigenen Readern.
+pub(crate) struct AnalyzerWorker {
+    pub(crate) flow: String,
+    pub(crate) taps: Vec<(AnalyzerTap, Arc<AudioRingBuffer>)>,
+    pub(crate) readings: Arc<Mutex<AnalyzerReadings>>,
+    pub(crate) emitter: Option<EventEmitter>,
+    pub(crate) running: Arc<AtomicBool>,
+}
+
+impl AnalyzerWorker {
+    fn reader_id(&self, tap: &AnalyzerTap) -> String {
+        format!("analyzer:{}:{}", self.flow, tap.config.name)
+    }
+
+    pub(crate) fn run(mut self) {
+        lock_mutex(&self.readings, "analyzer_taps.reset").clear();
+        // Erst ab jetzt messen, keine alten Frames aus dem Buffer
+        for (tap, buffer) in &self.taps {
+            let reader_id = self.reader_id(tap);
+            while buffer.pop_for_reader(&reader_id).is_some() {}
+        }
+
+        let buffers: Vec<Arc<AudioRingBuffer>> =
+            self.taps.iter().map(|(_, buffer)| buffer.clone()).collect();
+        let inputs: Vec<&AudioRingBuffer> = buffers.iter().map(|buffer| buffer.as_ref()).collect();
+        let mut idle = IdleBackoff::new(POLL_INTERVAL);
+        while self.running.load(Ordering::Relaxed) {
+            let mut closed = Vec::new();
+            let mut frames = 0;
+            for index in 0..self.taps.len() {
+                let reader_id = self.reader_id(&self.taps[index].0);
+                let (tap, buffer) = &mut self.taps[index];
+                while let Some(frame) = buffer.pop_for_reader(&reader_id) {
+                    frames += 1;
+                    for reading in tap.push(&frame) {
+                        closed.push((tap.config.name.clone(), reading));
+                    }
+                }
+            }
+            self.publish(closed);
+            if frames > 0 {
+                idle.reset();
+            }
+            idle.sleep_for_any(&inputs);
+        }
+
+        for (tap, buffer) in &self.taps {
+            buffer.remove_reader(&self.reader_id(tap));
+        }
+    }
+
+    fn publish(&self, closed: Vec<(String, AnalyzerReading)>) {
+        if closed.is_empty() {
+            return;
+        }
+        if let Some(emitter) = &self.emitter {
+            for (tap, reading) in &closed {
+                let mut payload = serde_json::to_value(reading).unwrap_or_default();
+                payload["tap"] = serde_json::json!(tap);
+                payload["flow"] = serde_json::json!(self.flow);
+                emitter.emit(EventType::AnalyzerReading, EventPriority::Debug, payload);
+            }
+        }
+        let mut readings = lock_mutex(&self.readings, "analyzer_taps.publish");
+        readings.extend(closed);
+    }
+}
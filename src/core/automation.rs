@@ -0,0 +1,253 @@
+// src/core/automation.rs
+//
+// Parameter-Automation pro Flow: geplante Verläufe (Rampen, Sprünge, LFO) für
+// Processor-Parameter. Der Flow-Thread wertet die Kurven zum Zeitstempel jedes
+// Frames aus und setzt den Wert vor dessen Verarbeitung (`set_parameter`),
+// Zeitbasis ist daher die Frame-Zeit (`utc_ns`), nicht die Wanduhr.
+use std::collections::HashSet;
+use std::f64::consts::PI;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+
+use crate::core::error::{AudioError, AudioResult};
+use crate::core::lock::lock_mutex;
+use crate::core::processor::{process_batched, Processor};
+use crate::core::ringbuffer::AudioRingBuffer;
+use crate::core::timestamp::utc_ns_now;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum AutomationShape {
+    /// Sofort auf `to`
+    Step,
+    Linear,
+    /// Cosinus-S-Kurve (weicher Ein-/Auslauf)
+    Smooth,
+    /// Schwingt zwischen `from` und `to`, am Ende zurück auf `from`
+    Lfo { rate_hz: f64 },
+}
+
+impl AutomationShape {
+    pub fn parse(name: &str, rate_hz: Option<f64>) -> Option<Self> {
+        match name {
+            "step" => Some(Self::Step),
+            "linear" => Some(Self::Linear),
+            "smooth" => Some(Self::Smooth),
+            "lfo" => rate_hz
+                .filter(|rate| *rate > 0.0 && *rate <= 100.0)
+                .map(|rate_hz| Self::Lfo { rate_hz }),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AutomationLane {
+    pub id: u64,
+    pub processor: String,
+    pub parameter: String,
+    pub shape: AutomationShape,
+    pub from: f64,
+    pub to: f64,
+    pub start_ns: u64,
+    pub duration_ns: u64,
+}
+
+impl AutomationLane {
+    pub fn end_ns(&self) -> u64 {
+        self.start_ns.saturating_add(self.duration_ns)
+    }
+
+    /// Wert zum Zeitpunkt `t_ns`; `None` vor dem Start.
+    pub fn value_at(&self, t_ns: u64) -> Option<f64> {
+        if t_ns < self.start_ns {
+            return None;
+        }
+        if t_ns >= self.end_ns() {
+            return Some(match self.shape {
+                AutomationShape::Lfo { .. } => self.from,
+                _ => self.to,
+            });
+        }
+
+        let elapsed = (t_ns - self.start_ns) as f64;
+        let progress = elapsed / self.duration_ns as f64;
+        let span = self.to - self.from;
+        Some(match self.shape {
+            AutomationShape::Step => self.to,
+            AutomationShape::Linear => self.from + span * progress,
+            AutomationShape::Smooth => self.from + span * (1.0 - (PI * progress).cos()) / 2.0,
+            AutomationShape::Lfo { rate_hz } => {
+                let phase = 2.0 * PI * rate_hz * elapsed / 1e9;
+                self.from + span * (1.0 - phase.cos()) / 2.0
+            }
+        })
+    }
+}
+
+/// Geplante Verläufe eines Flows, geteilt zwischen API und Flow-Thread.
+#[derive(Clone, Default)]
+pub struct FlowAutomation {
+    lanes: Arc<Mutex<Vec<AutomationLane>>>,
+    next_id: Arc<AtomicU64>,
+}
+
+impl FlowAutomation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Plant einen Verlauf ein; `id` wird vergeben.
+    pub fn schedule(&self, mut lane: AutomationLane) -> AudioResult<u64> {
+        if lane.processor.is_empty() || lane.parameter.is_empty() {
+            return Err(AudioError::message("automation needs processor and parameter"));
+        }
+        if !lane.from.is_finite() || !lane.to.is_finite() {
+            return Err(AudioError::message("automation values must be finite"));
+        }
+        if lane.duration_ns == 0 && lane.shape != AutomationShape::Step {
+            return Err(AudioError::message("automation duration must be > 0"));
+        }
+        lane.id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        let id = lane.id;
+        lock_mutex(&self.lanes, "automation.schedule").push(lane);
+        Ok(id)
+    }
+
+    pub fn cancel(&self, id: u64) -> bool {
+        let mut lanes = lock_mutex(&self.lanes, "automation.cancel");
+        let before = lanes.len();
+        lanes.retain(|lane| lane.id != id);
+        lanes.len() != before
+    }
+
+    pub fn lanes(&self) -> Vec<AutomationLane> {
+        lock_mutex(&self.lanes, "automation.lanes").clone()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        lock_mutex(&self.lanes, "automation.is_empty").is_empty()
+    }
+
+    /// Momentaufnahme für einen Durchlauf des Flow-Threads.
+    pub(crate) fn snapshot(&self) -> AutomationPass {
+        AutomationPass {
+            lanes: self.lanes(),
+            finished: HashSet::new(),
+        }
+    }
+
+    /// Entfernt Verläufe, deren Endwert gesetzt wurde.
+    pub(crate) fn retire(&self, pass: &AutomationPass) {
+        if pass.finished.is_empty() {
+            return;
+        }
+        lock_mutex(&self.lanes, "automation.retire").retain(|lane| !pass.finished.contains(&lane.id));
+    }
+}
+
+pub(crate) struct AutomationPass {
+    lanes: Vec<AutomationLane>,
+    finished: HashSet<u64>,
+}
+
+impl AutomationPass {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.lanes.is_empty()
+    }
+
+    pub(crate) fn targets(&self, processor: &str) -> bool {
+        self.lanes.iter().any(|lane| lane.processor == processor)
+    }
+
+    /// Setzt alle Parameter des Processors auf ihren Wert zu `t_ns`.
+    /// Bei mehreren Verläufen auf denselben Parameter gewinnt der zuletzt geplante.
+    pub(crate) fn apply(&mut self, processor: &mut dyn Processor, t_ns: u64) {
+        let name = processor.name().to_string();
+        for lane in self.lanes.iter().filter(|lane| lane.processor == name) {
+            if self.finished.contains(&lane.id) {
+                continue;
+            }
+            let Some(value) = lane.value_at(t_ns) else {
+                continue;
+            };
+            if let Err(e) = processor.set_parameter(&lane.parameter, value) {
+                log::warn!(
+                    "Automation {}: cannot set '{}.{}': {}",
+                    lane.id,
+                    lane.processor,
+                    lane.parameter,
+                    e
+                );
+                self.finished.insert(lane.id);
+                continue;
+            }
+            if t_ns >= lane.end_ns() {
+                self.finished.insert(lane.id);
+            }
+        }
+    }
+}
+
+/// Ruft `process_batched` auf; ist der Processor automatisiert, wird der Input Frame
+/// für Frame über `staging` geschickt und vor jedem Frame der Wert zu dessen
+/// Zeitstempel gesetzt. Der abschließende Aufruf mit leerem Input bedient
+/// Processors, die selbst aus der Registry lesen (Mixer).
+pub(crate) fn process_automated(
+    processor: &mut dyn Processor,
+    input: &AudioRingBuffer,
+    output: &AudioRingBuffer,
+    staging: &AudioRingBuffer,
+    pass: &mut AutomationPass,
+    max_batch: usize,
+) -> anyhow::Result<()> {
+    if pass.is_empty() || !pass.targets(processor.name()) {
+        return process_batched(processor, input, output, max_batch);
+    }
+
+    while let Some(frame) = input.pop() {
+        pass.apply(processor, frame.utc_ns);
+        staging.push(frame);
+        processor.process(staging, output)?;
+    }
+    pass.apply(processor, utc_ns_now());
+    processor.process(input, output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lane(shape: AutomationShape) -> AutomationLane {
+        AutomationLane {
+            id: 0,
+            processor: "gain".to_string(),
+            parameter: "gain_db".to_string(),
+            shape,
+            from: 0.0,
+            to: -6.0,
+            start_ns: 1_000,
+            duration_ns: 10_000,
+        }
+    }
+
+    #[test]
+    fn curves_interpolate_between_from_and_to() {
+        let linear = lane(AutomationShape::Linear);
+        assert_eq!(linear.value_at(999), None);
+        assert_eq!(linear.value_at(1_000), Some(0.0));
+        assert_eq!(linear.value_at(6_000), Some(-3.0));
+        assert_eq!(linear.value_at(20_000), Some(-6.0));
+
+        let smooth = lane(AutomationShape::Smooth);
+        assert!((smooth.value_at(6_000).unwrap() + 3.0).abs() < 1e-9);
+        assert!(smooth.value_at(2_000).unwrap() > linear.value_at(2_000).unwrap());
+
+        let lfo = lane(AutomationShape::Lfo { rate_hz: 1e5 });
+        // Halbe Periode (5 µs) erreicht `to`, am Ende zurück auf `from`
+        assert!((lfo.value_at(6_000).unwrap() + 6.0).abs() < 1e-9);
+        assert_eq!(lfo.value_at(11_000), Some(0.0));
+    }
+}
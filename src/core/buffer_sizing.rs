@@ -0,0 +1,254 @@
+// src/core/buffer_sizing.rs
+//
+// Ringbuffer-Größen aus der Config (`buffer = { slots, prealloc }` pro
+// Producer, Flow und Consumer) und die Umrechnung in Worst-Case-Latenz und
+// Speicherbedarf für Startlog und `/api/status`.
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::bail;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::config::{Config, ConfigValues};
+use crate::core::ringbuffer::AudioRingBuffer;
+use crate::core::AirliftNode;
+use crate::types::{Millis, Samples, Slots};
+
+pub const DEFAULT_BUFFER_SLOTS: Slots = Slots(1000);
+pub const MAX_BUFFER_SLOTS: Slots = Slots(100_000);
+/// 10 s Stereo @ 192 kHz
+pub const MAX_PREALLOC_SAMPLES: Samples = Samples(3_840_000);
+
+/// Annahme, solange noch kein Frame im Buffer lag: 20 ms @ 48 kHz Stereo
+const NOMINAL_SAMPLE_RATE: u32 = 48_000;
+const NOMINAL_CHANNELS: u8 = 2;
+const NOMINAL_FRAME_SAMPLES: Samples = Samples(1920);
+
+/// Slots und reservierte Samples pro Slot eines Ringbuffers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct BufferSizing {
+    pub slots: Slots,
+    /// 0 = keine Vorbelegung, Frames werden übernommen
+    pub prealloc_samples: Samples,
+}
+
+impl Default for BufferSizing {
+    fn default() -> Self {
+        Self {
+            slots: DEFAULT_BUFFER_SLOTS,
+            prealloc_samples: Samples::ZERO,
+        }
+    }
+}
+
+impl BufferSizing {
+    /// Liest `config.buffer`: Zahl (= Slots) oder `{ slots = 2000, prealloc = 1920 }`.
+    pub fn from_config(
+        module_kind: &str,
+        module_name: &str,
+        config: &HashMap<String, Value>,
+    ) -> anyhow::Result<Option<Self>> {
+        let map: HashMap<String, Value> = match config.get("buffer") {
+            None => return Ok(None),
+            Some(Value::Number(slots)) => HashMap::from([("slots".to_string(), Value::Number(slots.clone()))]),
+            Some(Value::Object(map)) => map.clone().into_iter().collect(),
+            Some(other) => bail!(
+                "{} '{}': config.buffer must be a number or a table, got {}",
+                module_kind,
+                module_name,
+                other
+            ),
+        };
+        let values = ConfigValues::new(module_kind, module_name, &map);
+        let defaults = Self::default();
+
+        let slots = values.f64("slots")?.map(|v| v as usize).unwrap_or(defaults.slots.get());
+        let prealloc_samples = values
+            .f64("prealloc")?
+            .map(|v| v as usize)
+            .unwrap_or(defaults.prealloc_samples.get());
+        Ok(Some(Self {
+            slots: Slots(values.check_range("slots", slots, 1, MAX_BUFFER_SLOTS.get())?),
+            prealloc_samples: Samples(values.check_range(
+                "prealloc",
+                prealloc_samples,
+                0,
+                MAX_PREALLOC_SAMPLES.get(),
+            )?),
+        }))
+    }
+
+    /// Größe eines bestehenden Buffers
+    pub fn of(buffer: &AudioRingBuffer) -> Self {
+        Self {
+            slots: buffer.slots(),
+            prealloc_samples: buffer.prealloc_samples(),
+        }
+    }
+
+    /// Der jeweils größere Wert beider Angaben
+    pub fn max(self, other: Self) -> Self {
+        Self {
+            slots: self.slots.max(other.slots),
+            prealloc_samples: self.prealloc_samples.max(other.prealloc_samples),
+        }
+    }
+
+    pub fn build(&self) -> Arc<AudioRingBuffer> {
+        Arc::new(AudioRingBuffer::with_prealloc(self.slots, self.prealloc_samples))
+    }
+}
+
+/// Größen eines Flows: interne Buffer (Merge, Processor-Kette) und Output.
+/// Der Output muss so tief sein, wie der langsamste Consumer zurückliegen darf.
+pub fn flow_buffer_sizing(config: &Config, flow_name: &str) -> anyhow::Result<(BufferSizing, BufferSizing)> {
+    let Some(flow_cfg) = config.flows.get(flow_name) else {
+        return Ok((BufferSizing::default(), BufferSizing::default()));
+    };
+    let internal = BufferSizing::from_config("flow", flow_name, &flow_cfg.config)?.unwrap_or_default();
+    let mut output = internal;
+    for consumer_name in &flow_cfg.outputs {
+        if let Some(consumer_cfg) = config.consumers.get(consumer_name).filter(|cfg| cfg.enabled) {
+            if let Some(sizing) = BufferSizing::from_config("consumer", consumer_name, &consumer_cfg.config)? {
+                output = output.max(sizing);
+            }
+        }
+    }
+    Ok((internal, output))
+}
+
+/// Latenz und Speicher eines Buffers.
+#[derive(Debug, Clone, Serialize)]
+pub struct BufferFootprint {
+    pub name: String,
+    pub slots: Slots,
+    pub prealloc_samples: Samples,
+    /// Frame-Größe der Berechnung (gemessen oder angenommen)
+    pub frame_samples: Samples,
+    pub sample_rate: u32,
+    pub channels: u8,
+    /// `false`: noch kein Frame, Annahme 20 ms @ 48 kHz Stereo
+    pub measured: bool,
+    /// Voller Buffer bis zum langsamsten Reader
+    pub worst_case_latency_ms: Millis,
+    pub memory_bytes: u64,
+}
+
+impl BufferFootprint {
+    pub fn of(name: &str, buffer: &AudioRingBuffer) -> Self {
+        let prealloc_samples = buffer.prealloc_samples();
+        let (frame_samples, sample_rate, channels, measured) = match buffer.last_frame_shape() {
+            Some((samples, rate, channels)) if rate > 0 && channels > 0 => (samples, rate, channels, true),
+            _ => (
+                if prealloc_samples.is_zero() { NOMINAL_FRAME_SAMPLES } else { prealloc_samples },
+                NOMINAL_SAMPLE_RATE,
+                NOMINAL_CHANNELS,
+                false,
+            ),
+        };
+        let slots = buffer.slots();
+        let frame_ms = frame_samples.to_frames(channels).to_millis(sample_rate);
+        let sample_bytes = frame_samples.max(prealloc_samples).bytes();
+        Self {
+            name: name.to_string(),
+            slots,
+            prealloc_samples,
+            frame_samples,
+            sample_rate,
+            channels,
+            measured,
+            worst_case_latency_ms: slots.span(frame_ms),
+            memory_bytes: slots.get() as u64 * (AudioRingBuffer::SLOT_OVERHEAD_BYTES as u64 + sample_bytes),
+        }
+    }
+}
+
+/// Worst-Case-Latenz eines Flows: langsamster Input plus Merge, Processor-Kette und Output.
+#[derive(Debug, Clone, Serialize)]
+pub struct FlowLatency {
+    pub flow: String,
+    pub worst_case_latency_ms: Millis,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BufferReport {
+    pub total_memory_bytes: u64,
+    pub buffers: Vec<BufferFootprint>,
+    pub flows: Vec<FlowLatency>,
+}
+
+pub fn buffer_report(node: &AirliftNode) -> BufferReport {
+    let registry = node.buffer_registry();
+    let mut names = registry.list();
+    names.sort();
+    let registered: Vec<(String, Arc<AudioRingBuffer>)> = names
+        .into_iter()
+        .filter_map(|name| registry.get(&name).map(|buffer| (name, buffer)))
+        .collect();
+
+    let mut buffers: Vec<BufferFootprint> = registered
+        .iter()
+        .map(|(name, buffer)| BufferFootprint::of(name, buffer))
+        .collect();
+    let mut flows = Vec::new();
+
+    for flow in node.flows() {
+        let input_ms = flow
+            .input_buffers
+            .iter()
+            .filter_map(|input| {
+                registered
+                    .iter()
+                    .position(|(_, buffer)| Arc::ptr_eq(buffer, input))
+                    .map(|index| buffers[index].worst_case_latency_ms)
+            })
+            .fold(Millis::ZERO, Millis::max);
+
+        let mut path_ms = Millis::ZERO;
+        for (name, buffer, on_path) in flow.named_buffers() {
+            let footprint = BufferFootprint::of(&name, &buffer);
+            if on_path {
+                path_ms += footprint.worst_case_latency_ms;
+            }
+            buffers.push(footprint);
+        }
+        flows.push(FlowLatency {
+            flow: flow.name.clone(),
+            worst_case_latency_ms: input_ms + path_ms,
+        });
+    }
+
+    BufferReport {
+        total_memory_bytes: buffers.iter().map(|buffer| buffer.memory_bytes).sum(),
+        buffers,
+        flows,
+    }
+}
+
+/// Startlog: eine Zeile pro Buffer und Flow plus Gesamtspeicher.
+pub fn log_buffer_report(report: &BufferReport) {
+    for buffer in &report.buffers {
+        log::debug!(
+            "Buffer '{}': {}, prealloc {}, worst case {}, {:.1} MiB{}",
+            buffer.name,
+            buffer.slots,
+            buffer.prealloc_samples,
+            buffer.worst_case_latency_ms,
+            buffer.memory_bytes as f64 / (1024.0 * 1024.0),
+            if buffer.measured { "" } else { " (assuming 20 ms frames)" }
+        );
+    }
+    for flow in &report.flows {
+        log::info!(
+            "Flow '{}': worst-case buffer latency {}",
+            flow.flow,
+            flow.worst_case_latency_ms
+        );
+    }
+    log::info!(
+        "Ring buffers: {} buffers, {:.1} MiB worst case",
+        report.buffers.len(),
+        report.total_memory_bytes as f64 / (1024.0 * 1024.0)
+    );
+}
@@ -0,0 +1,264 @@
+// src/core/compare.rs
+//
+// Vorher/Nachher-Vergleich eines Flows: liest den Merge-Buffer (vor der
+// Processor-Kette) und den Output-Buffer (danach) über eigene Reader, ordnet
+// die Frames über ihren Zeitstempel einander zu und gleicht die Lautheit an.
+// Die lautere Seite wird abgesenkt, damit der Vergleich nicht einfach
+// "lauter klingt besser" misst. Der Flow selbst bleibt unberührt.
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use anyhow::bail;
+use serde::Serialize;
+
+use crate::audio::loudness::{LoudnessMeter, SILENCE_LUFS};
+use crate::core::lock::lock_mutex;
+use crate::core::node::Flow;
+use crate::core::ringbuffer::AudioRingBuffer;
+use crate::ring::PcmFrame;
+
+/// Gleichzeitige Vergleichs-Sessions (alle Flows zusammen).
+pub const MAX_COMPARE_SESSIONS: usize = 4;
+/// Länge des Binär-Headers eines Paars, siehe `ComparePair::encode`.
+pub const PAIR_HEADER_LEN: usize = 20;
+/// Ohne passende Zeitstempel werden Frames nach so vielen Fehlversuchen
+/// in Reihenfolge zugeordnet (Processor stempelt neu).
+const MAX_UNMATCHED: u64 = 250;
+const MAX_PENDING: usize = 250;
+/// Stärkste Absenkung beim Angleichen
+const MAX_MATCH_DB: f32 = 24.0;
+/// Nachführung der Angleichung je 100-ms-Block
+const GAIN_SLEW_DB: f32 = 0.5;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct CompareLevels {
+    pub id: u64,
+    pub flow: String,
+    /// Short-Term-Lautheit ohne Angleichung
+    pub pre_lufs: Option<f32>,
+    pub post_lufs: Option<f32>,
+    /// Aktuell angewendete Absenkung (≤ 0)
+    pub pre_gain_db: f32,
+    pub post_gain_db: f32,
+    pub pairs: u64,
+    /// Frames ohne Gegenstück (z. B. nach Überläufen)
+    pub dropped: u64,
+    /// Zuordnung über Zeitstempel; `false` = nach Reihenfolge
+    pub aligned: bool,
+}
+
+/// Zeitgleiche Frames beider Abgriffe, bereits lautheitsangeglichen.
+#[derive(Debug, Clone)]
+pub struct ComparePair {
+    pub utc_ns: u64,
+    pub pre: PcmFrame,
+    pub post: PcmFrame,
+}
+
+impl ComparePair {
+    /// Binärformat für `/ws/compare/<flow>` (Little Endian):
+    /// `utc_ns: u64`, `sample_rate: u32`, `pre_channels: u8`,
+    /// `post_channels: u8`, 2 Byte reserviert, `pre_samples: u32`, danach
+    /// die Pre-Samples (i16) und bis zum Ende die Post-Samples (i16).
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(
+            PAIR_HEADER_LEN + 2 * (self.pre.samples.len() + self.post.samples.len()),
+        );
+        out.extend_from_slice(&self.utc_ns.to_le_bytes());
+        out.extend_from_slice(&self.pre.sample_rate.to_le_bytes());
+        out.push(self.pre.channels);
+        out.push(self.post.channels);
+        out.extend_from_slice(&[0, 0]);
+        out.extend_from_slice(&(self.pre.samples.len() as u32).to_le_bytes());
+        for sample in self.pre.samples.iter().chain(self.post.samples.iter()) {
+            out.extend_from_slice(&sample.to_le_bytes());
+        }
+        out
+    }
+}
+
+struct Registry {
+    next_id: u64,
+    sessions: HashMap<u64, Arc<Mutex<CompareLevels>>>,
+}
+
+static COMPARE_SESSIONS: OnceLock<Mutex<Registry>> = OnceLock::new();
+
+fn registry() -> &'static Mutex<Registry> {
+    COMPARE_SESSIONS.get_or_init(|| {
+        Mutex::new(Registry {
+            next_id: 1,
+            sessions: HashMap::new(),
+        })
+    })
+}
+
+/// Pegel der laufenden Sessions eines Flows.
+pub fn compare_sessions(flow: &str) -> Vec<CompareLevels> {
+    let registry = lock_mutex(registry(), "compare.sessions");
+    let mut sessions: Vec<CompareLevels> = registry
+        .sessions
+        .values()
+        .map(|levels| lock_mutex(levels, "compare.session_levels").clone())
+        .filter(|levels| levels.flow == flow)
+        .collect();
+    sessions.sort_by_key(|levels| levels.id);
+    sessions
+}
+
+/// Nähert `current` um höchstens `GAIN_SLEW_DB` je Block an `target` an.
+fn slew(current: f32, target: f32, blocks: usize) -> f32 {
+    let step = GAIN_SLEW_DB * blocks as f32;
+    current + (target - current).clamp(-step, step)
+}
+
+fn apply_gain(mut frame: PcmFrame, gain_db: f32) -> PcmFrame {
+    if gain_db != 0.0 {
+        let factor = 10f32.powf(gain_db / 20.0);
+        for sample in frame.samples.iter_mut() {
+            *sample = (*sample as f32 * factor)
+                .round()
+                .clamp(i16::MIN as f32, i16::MAX as f32) as i16;
+        }
+    }
+    frame
+}
+
+pub struct CompareSession {
+    id: u64,
+    reader_id: String,
+    pre: Arc<AudioRingBuffer>,
+    post: Arc<AudioRingBuffer>,
+    pending_pre: VecDeque<PcmFrame>,
+    pending_post: VecDeque<PcmFrame>,
+    unmatched: u64,
+    pre_meter: LoudnessMeter,
+    post_meter: LoudnessMeter,
+    levels: Arc<Mutex<CompareLevels>>,
+}
+
+impl CompareSession {
+    /// Hängt sich an Merge- und Output-Buffer des Flows; ab jetzt neue Frames.
+    pub fn open(flow: &Flow) -> anyhow::Result<Self> {
+        let mut registry = lock_mutex(registry(), "compare.open");
+        if registry.sessions.len() >= MAX_COMPARE_SESSIONS {
+            bail!(
+                "maximum number of compare sessions ({}) reached",
+                MAX_COMPARE_SESSIONS
+            );
+        }
+        let id = registry.next_id;
+        registry.next_id += 1;
+        let levels = Arc::new(Mutex::new(CompareLevels {
+            id,
+            flow: flow.name.clone(),
+            aligned: true,
+            ..CompareLevels::default()
+        }));
+        registry.sessions.insert(id, levels.clone());
+        drop(registry);
+
+        let session = Self {
+            id,
+            reader_id: format!("compare:{}", id),
+            pre: flow.input_merge_buffer.clone(),
+            post: flow.output_buffer.clone(),
+            pending_pre: VecDeque::new(),
+            pending_post: VecDeque::new(),
+            unmatched: 0,
+            pre_meter: LoudnessMeter::new(),
+            post_meter: LoudnessMeter::new(),
+            levels,
+        };
+        while session.pre.pop_for_reader(&session.reader_id).is_some() {}
+        while session.post.pop_for_reader(&session.reader_id).is_some() {}
+        Ok(session)
+    }
+
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn levels(&self) -> CompareLevels {
+        lock_mutex(&self.levels, "compare.levels").clone()
+    }
+
+    /// Liest neue Frames beider Abgriffe und liefert alle zuordenbaren Paare.
+    pub fn poll(&mut self) -> Vec<ComparePair> {
+        while let Some(frame) = self.pre.pop_for_reader(&self.reader_id) {
+            self.pending_pre.push_back(frame);
+        }
+        while let Some(frame) = self.post.pop_for_reader(&self.reader_id) {
+            self.pending_post.push_back(frame);
+        }
+        let mut pairs = Vec::new();
+        let mut levels = lock_mutex(&self.levels, "compare.poll");
+
+        for pending in [&mut self.pending_pre, &mut self.pending_post] {
+            while pending.len() > MAX_PENDING {
+                pending.pop_front();
+                levels.dropped += 1;
+            }
+        }
+
+        while let (Some(pre), Some(post)) = (self.pending_pre.front(), self.pending_post.front()) {
+            let matched = pre.utc_ns == post.utc_ns;
+            if levels.aligned && !matched {
+                // Ältere Seite hat kein Gegenstück mehr
+                if pre.utc_ns < post.utc_ns {
+                    self.pending_pre.pop_front();
+                } else {
+                    self.pending_post.pop_front();
+                }
+                levels.dropped += 1;
+                self.unmatched += 1;
+                if self.unmatched > MAX_UNMATCHED {
+                    levels.aligned = false;
+                }
+                continue;
+            }
+            if matched {
+                self.unmatched = 0;
+            }
+
+            let (Some(pre), Some(post)) =
+                (self.pending_pre.pop_front(), self.pending_post.pop_front())
+            else {
+                break;
+            };
+            let blocks = self.pre_meter.push(&pre).max(self.post_meter.push(&post));
+            if blocks > 0 {
+                levels.pre_lufs = self.pre_meter.short_term();
+                levels.post_lufs = self.post_meter.short_term();
+                // Bei Stille die letzte Angleichung halten
+                if let (Some(pre_lufs), Some(post_lufs)) = (levels.pre_lufs, levels.post_lufs) {
+                    if pre_lufs > SILENCE_LUFS && post_lufs > SILENCE_LUFS {
+                        let difference = post_lufs - pre_lufs;
+                        let pre_target = difference.clamp(-MAX_MATCH_DB, 0.0);
+                        let post_target = (-difference).clamp(-MAX_MATCH_DB, 0.0);
+                        levels.pre_gain_db = slew(levels.pre_gain_db, pre_target, blocks);
+                        levels.post_gain_db = slew(levels.post_gain_db, post_target, blocks);
+                    }
+                }
+            }
+
+            levels.pairs += 1;
+            pairs.push(ComparePair {
+                utc_ns: pre.utc_ns,
+                pre: apply_gain(pre, levels.pre_gain_db),
+                post: apply_gain(post, levels.post_gain_db),
+            });
+        }
+        pairs
+    }
+}
+
+impl Drop for CompareSession {
+    fn drop(&mut self) {
+        self.pre.remove_reader(&self.reader_id);
+        self.post.remove_reader(&self.reader_id);
+        lock_mutex(registry(), "compare.close")
+            .sessions
+            .remove(&self.id);
+    }
+}
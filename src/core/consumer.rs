@@ -2,8 +2,11 @@ use crate::core::consumer::encoded_output::EncodedOutputConsumer;
 use crate::core::consumer::file_writer::FileConsumer;
 use crate::impl_connectable_consumer;
 use crate::audio::sanitize_audio_path;
+use crate::core::event_bus::EventEmitter;
+use crate::core::idle::IdleBackoff;
 use crate::core::ringbuffer::AudioRingBuffer;
 use anyhow::Result;
+use serde::Serialize;
 use std::io::{self, Seek};
 use std::sync::Arc;
 
@@ -14,6 +17,29 @@ pub trait Consumer: Send + Sync {
     fn status(&self) -> ConsumerStatus;
     fn attach_input_buffer(&mut self, buffer: Arc<AudioRingBuffer>);
     fn attach_encoder(&mut self, _encoder: Box<dyn crate::encoders::AudioCodec>) {}
+    /// Wird vom Flow aufgerufen, sobald ein EventBus verfügbar ist.
+    fn attach_event_emitter(&mut self, _emitter: EventEmitter) {}
+    /// Zustand der Unter-Ziele (`fanout`, Ersatzziel per `config.backup`,
+    /// Spiegel per `config.mirror`); sonst leer
+    fn targets(&self) -> Vec<ConsumerTargetStatus> {
+        Vec::new()
+    }
+    /// Laufzeit-Änderung über `consumer.configure` (`POST /api/control`).
+    fn update_config(&mut self, _config: serde_json::Value) -> Result<()> {
+        anyhow::bail!("consumer '{}' does not support runtime configuration", self.name())
+    }
+    /// Verbundene Zuhörer bei Ausgängen, die selbst Verbindungen annehmen
+    /// (z. B. `zmq_pub`); sonst 0.
+    fn listeners(&self) -> usize {
+        0
+    }
+    /// Draining bei Config-Änderungen (`core::drain`): keine neuen
+    /// Verbindungen mehr annehmen und Port bzw. Route freigeben, bestehende
+    /// weiter bedienen. `false` = nicht unterstützt, der Consumer wird wie
+    /// bisher gestoppt.
+    fn begin_drain(&mut self) -> bool {
+        false
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -23,25 +49,207 @@ pub struct ConsumerStatus {
     pub frames_processed: u64,
     pub bytes_written: u64,
     pub errors: u64,
+    /// Verbindungszustand von Netzwerk-Ausgängen mit Reconnect; sonst `None`
+    pub connection: Option<ConnectionState>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectionPhase {
+    Connecting,
+    Connected,
+    /// Wartet nach einem Fehler auf den nächsten Versuch
+    Backoff,
+    Stopped,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConnectionState {
+    pub phase: ConnectionPhase,
+    /// Ziel, z. B. `icecast.example.org:8000/live.ogg`
+    pub endpoint: String,
+    /// Fehlgeschlagene Versuche seit der letzten stabilen Verbindung
+    pub failed_attempts: u32,
+    pub retry_in_ms: Option<u64>,
+    pub last_error: Option<String>,
+}
+
+/// Ein Ziel hinter einem `fanout`-Consumer, Ersatzziel- oder Spiegel-Paar mit
+/// eigenem Reader und Neustart-Zustand.
+#[derive(Debug, Clone, Serialize)]
+pub struct ConsumerTargetStatus {
+    pub name: String,
+    pub running: bool,
+    pub connected: bool,
+    pub frames_processed: u64,
+    pub bytes_written: u64,
+    /// Fehler des Ziels plus fehlgeschlagene Neustarts
+    pub errors: u64,
+    /// Neustarts nach Ausfall seit dem Start des Fanouts
+    pub restarts: u32,
+    pub retry_in_ms: Option<u64>,
+    pub last_error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connection: Option<ConnectionState>,
+    /// Nur bei `config.backup`: ob dieses Ziel gerade sendet
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active: Option<bool>,
+    /// Nur bei `config.mirror`: ob dieses Ziel der Spiegel ist
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mirror: Option<bool>,
 }
 
 pub mod file_writer {
     use super::*;
-    use std::fs::File;
-    use std::io::{self, BufWriter, Seek, Write};
+    use crate::audio::wav::{self, BwfOptions, WavFormat};
+    use crate::audio::{archive, waveform};
+    use crate::codecs::flac::FlacEncoder;
+    use crate::codecs::{
+        create_encoder, create_mp3_encoder, AudioCodec, FlacEncoderOptions, MP3_DEFAULT_BITRATE,
+    };
+    use crate::config::ConfigValues;
+    use crate::core::file_rotation::{
+        FileRotation, FinishedSegment, Retention, SegmentHook, PART_SUFFIX,
+    };
+    use crate::core::pre_roll::{PreRoll, PreRollBuffer, RecordTrigger, RecordTriggerHandler};
+    use crate::core::state_store::{self, StateStore};
+    use crate::core::timestamp::utc_ns_now;
+    use crate::core::timezone::TimeZone;
+    use serde::Deserialize;
+    use std::fs::{File, OpenOptions};
+    use std::io::{BufWriter, Seek, Write};
+    use std::path::{Path, PathBuf};
     use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 
+    /// Header-Format: 48 kHz, Stereo, 16 Bit
+    const SAMPLE_RATE: u64 = 48_000;
+    const CHANNELS: u64 = 2;
+    const WAV_FORMAT: WavFormat = WavFormat {
+        sample_rate: SAMPLE_RATE as u32,
+        channels: CHANNELS as u16,
+        bits_per_sample: 16,
+    };
+
+    /// Dateiformat einer Aufnahme (`config.format`, sonst aus der Endung).
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum FileFormat {
+        Wav,
+        Flac,
+        OggOpus,
+        Mp3,
+    }
+
+    impl FileFormat {
+        pub fn parse(text: &str) -> Option<Self> {
+            match text.trim().to_ascii_lowercase().as_str() {
+                "wav" => Some(Self::Wav),
+                "flac" => Some(Self::Flac),
+                "ogg" | "opus" | "oggopus" => Some(Self::OggOpus),
+                "mp3" => Some(Self::Mp3),
+                _ => None,
+            }
+        }
+
+        /// Format zur Dateiendung des Pfads, `None` bei unbekannter Endung.
+        pub fn from_path(path: &str) -> Option<Self> {
+            Path::new(path)
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .and_then(Self::parse)
+        }
+
+        /// Codec-ID in der Registry; WAV schreibt PCM direkt.
+        pub fn codec_id(&self) -> Option<&'static str> {
+            match self {
+                Self::Wav => None,
+                Self::Flac => Some("flac"),
+                Self::OggOpus => Some("opusogg"),
+                Self::Mp3 => Some("mp3"),
+            }
+        }
+
+        pub fn as_str(&self) -> &'static str {
+            match self {
+                Self::Wav => "wav",
+                Self::Flac => "flac",
+                Self::OggOpus => "ogg",
+                Self::Mp3 => "mp3",
+            }
+        }
+    }
+
+    /// Liefert pro Segment einen frischen Encoder (jede Datei braucht ihre
+    /// eigenen Stream-Header).
+    pub type EncoderFactory = Arc<dyn Fn() -> Result<Box<dyn AudioCodec>> + Send + Sync>;
+
     pub struct FileConsumer {
         name: String,
         running: Arc<AtomicBool>,
         input_buffer: Option<Arc<AudioRingBuffer>>,
         reader_id: String,
         output_path: String,
+        format: FileFormat,
+        encoder_factory: Option<EncoderFactory>,
+        bwf: Option<BwfOptions>,
+        timezone: TimeZone,
+        rotation: Option<FileRotation>,
+        hooks: Vec<Arc<dyn SegmentHook>>,
+        pre_roll: Option<PreRoll>,
+        trigger: Arc<RecordTrigger>,
+        /// Ausgelöste Aufnahme läuft (ohne `pre_roll` immer während des Laufs)
+        recording: Arc<AtomicBool>,
+        emitter: Option<EventEmitter>,
         thread_handle: Option<std::thread::JoinHandle<()>>,
         frames_processed: Arc<AtomicU64>,
         bytes_written: Arc<AtomicU64>,
+        errors: Arc<AtomicU64>,
+    }
+
+    /// Platzhalter im Pfad für den Startzeitpunkt (Ortszeit und UTC)
+    pub const TIME_PLACEHOLDER: &str = "{time}";
+
+    /// Gespeicherte Position im offenen Segment; nach einem Absturz wird die
+    /// `.part`-Datei damit beim nächsten Start abgeschlossen.
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct RecorderState {
+        pub path: PathBuf,
+        pub part_path: PathBuf,
+        pub started_at_ms: u64,
+        /// Bis hierhin geflushte Bytes (PCM bei WAV, sonst kodiert)
+        pub data_bytes: u64,
+    }
+
+    fn state_key(name: &str) -> String {
+        format!("recorder-{}", name)
+    }
+
+    /// Segment im Schreiben: `<pfad>.part`, erst beim Abschluss umbenannt.
+    struct OpenSegment {
+        path: PathBuf,
+        part_path: PathBuf,
+        writer: BufWriter<File>,
+        /// `None` bei WAV
+        encoder: Option<Box<dyn AudioCodec>>,
+        /// Beginn der PCM-Daten hinter dem WAV-Header
+        data_offset: u64,
+        started_at_ms: u64,
+        deadline_ms: Option<u64>,
+        /// Geschriebene PCM-Samples (alle Kanäle), Grundlage der Dauer
+        samples: u64,
+        /// Dateigröße inklusive Header
+        bytes: u64,
     }
 
+    /// Gelesene `config`-Schlüssel (Strict-Modus, siehe `config::options`)
+    pub const CONFIG_KEYS: &[&str] = &[
+        "format",
+        "bwf",
+        "bwf_description",
+        "bwf_originator",
+        "flac",
+        "record",
+    ];
+
     impl FileConsumer {
         pub fn new(name: &str, output_path: &str) -> Self {
             Self {
@@ -50,51 +258,418 @@ pub mod file_writer {
                 input_buffer: None,
                 reader_id: format!("consumer:{}", name),
                 output_path: output_path.to_string(),
+                format: FileFormat::Wav,
+                encoder_factory: None,
+                bwf: None,
+                timezone: TimeZone::utc(),
+                rotation: None,
+                hooks: Vec::new(),
+                pre_roll: None,
+                trigger: Arc::new(RecordTrigger::default()),
+                recording: Arc::new(AtomicBool::new(false)),
+                emitter: None,
                 thread_handle: None,
                 frames_processed: Arc::new(AtomicU64::new(0)),
                 bytes_written: Arc::new(AtomicU64::new(0)),
+                errors: Arc::new(AtomicU64::new(0)),
             }
         }
 
-        fn write_wav_header(
-            writer: &mut BufWriter<File>,
-            sample_rate: u32,
-            channels: u16,
-            bits_per_sample: u16,
-        ) -> Result<()> {
-            writer.write_all(b"RIFF")?;
-            writer.write_all(&0u32.to_le_bytes())?;
-            writer.write_all(b"WAVE")?;
+        /// Wie `new`, plus Format (`format`), Rotation (`rotate_every`,
+        /// `rotate_size`), Aufbewahrung (`retention_files`, `retention_age`)
+        /// und Vorlauf (`pre_roll`, `record_on`, `record_for`) aus `config`.
+        pub fn from_config(
+            name: &str,
+            output_path: &str,
+            config: &std::collections::HashMap<String, serde_json::Value>,
+            timezone: TimeZone,
+        ) -> Result<Self> {
+            let by_extension = FileFormat::from_path(output_path);
+            let format = match config.get("format") {
+                None => by_extension.unwrap_or(FileFormat::Wav),
+                Some(serde_json::Value::String(text)) => FileFormat::parse(text).ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "consumer '{}': config.format '{}' is not one of wav, flac, ogg, mp3",
+                        name,
+                        text
+                    )
+                })?,
+                Some(_) => anyhow::bail!("consumer '{}': config.format must be a string", name),
+            };
+            if by_extension.is_some_and(|by_extension| by_extension != format) {
+                anyhow::bail!(
+                    "consumer '{}': config.format '{}' does not match the extension of {}",
+                    name,
+                    format.as_str(),
+                    output_path
+                );
+            }
 
-            writer.write_all(b"fmt ")?;
-            writer.write_all(&16u32.to_le_bytes())?;
-            writer.write_all(&1u16.to_le_bytes())?;
-            writer.write_all(&channels.to_le_bytes())?;
-            writer.write_all(&sample_rate.to_le_bytes())?;
+            let bwf = match config.get("bwf") {
+                None | Some(serde_json::Value::Bool(false)) => None,
+                Some(serde_json::Value::Bool(true)) => {
+                    let text = |key: &str| match config.get(key) {
+                        None => Ok(None),
+                        Some(serde_json::Value::String(value)) => Ok(Some(value.clone())),
+                        Some(_) => Err(anyhow::anyhow!("consumer '{}': config.{} must be a string", name, key)),
+                    };
+                    Some(BwfOptions {
+                        description: text("bwf_description")?.unwrap_or_else(|| name.to_string()),
+                        originator: text("bwf_originator")?.unwrap_or_else(|| "airlift-node".to_string()),
+                    })
+                }
+                Some(_) => anyhow::bail!("consumer '{}': config.bwf must be true or false", name),
+            };
+            if bwf.is_some() && format != FileFormat::Wav {
+                anyhow::bail!("consumer '{}': config.bwf needs format 'wav'", name);
+            }
+            let flac = FlacEncoderOptions::from_config("consumer", name, config.get("flac"))?;
+            if config.contains_key("flac") && format != FileFormat::Flac {
+                anyhow::bail!("consumer '{}': config.flac needs format 'flac'", name);
+            }
+            let bitrate = ConfigValues::new("consumer", name, config).bitrate("bitrate")?;
+            if bitrate.is_some() && format != FileFormat::Mp3 {
+                anyhow::bail!("consumer '{}': config.bitrate needs format 'mp3'", name);
+            }
+            let mut consumer = Self::new(name, output_path);
+            if format == FileFormat::Flac {
+                let factory: EncoderFactory = Arc::new(move || {
+                    Ok(Box::new(FlacEncoder::with_options(CHANNELS as u8, &flac)?) as Box<dyn AudioCodec>)
+                });
+                consumer = consumer.with_encoder(format, factory);
+            } else if format == FileFormat::Mp3 {
+                let bitrate = bitrate.unwrap_or(MP3_DEFAULT_BITRATE);
+                create_mp3_encoder(CHANNELS as u8, bitrate)
+                    .map_err(|e| anyhow::anyhow!("consumer '{}': config.format 'mp3': {}", name, e))?;
+                consumer = consumer
+                    .with_encoder(format, Arc::new(move || create_mp3_encoder(CHANNELS as u8, bitrate)));
+            } else if let Some(codec) = format.codec_id() {
+                // Früh scheitern, wenn der Build keinen Encoder dafür hat
+                create_encoder(codec)
+                    .map_err(|e| anyhow::anyhow!("consumer '{}': config.format '{}': {}", name, format.as_str(), e))?;
+                consumer = consumer.with_encoder(format, Arc::new(move || create_encoder(codec)));
+            }
+            let mut consumer = consumer
+                .with_bwf(bwf)
+                .with_timezone(timezone)
+                .with_rotation(FileRotation::from_config(name, output_path, config)?)
+                .with_pre_roll(PreRoll::from_config(name, config)?);
+            if let Some(retention) = Retention::from_config(name, output_path, config)? {
+                consumer.add_segment_hook(Arc::new(retention));
+            }
+            Ok(consumer)
+        }
 
-            let byte_rate = sample_rate as u32 * channels as u32 * bits_per_sample as u32 / 8;
-            writer.write_all(&byte_rate.to_le_bytes())?;
+        /// Kodiert jedes Segment mit einem eigenen Encoder aus `factory`
+        /// statt PCM in WAV zu schreiben.
+        pub fn with_encoder(mut self, format: FileFormat, factory: EncoderFactory) -> Self {
+            self.format = format;
+            self.encoder_factory = Some(factory);
+            self
+        }
 
-            let block_align = channels as u16 * bits_per_sample as u16 / 8;
-            writer.write_all(&block_align.to_le_bytes())?;
-            writer.write_all(&bits_per_sample.to_le_bytes())?;
+        pub fn format(&self) -> FileFormat {
+            self.format
+        }
 
-            writer.write_all(b"data")?;
-            writer.write_all(&0u32.to_le_bytes())?;
+        /// Broadcast Wave: `bext`-Chunk mit Beschreibung, Urheber und
+        /// Startzeit (Ortszeit, Samples seit Mitternacht) in jeder WAV-Datei.
+        pub fn with_bwf(mut self, bwf: Option<BwfOptions>) -> Self {
+            self.bwf = bwf;
+            self
+        }
 
-            Ok(())
+        /// Zeitzone für Zeitcodes im Pfad und die Ortszeit im Archiv-Manifest.
+        pub fn with_timezone(mut self, timezone: TimeZone) -> Self {
+            self.timezone = timezone;
+            self
         }
 
-        fn update_wav_header(file: &mut File, data_size: u32) -> Result<()> {
-            let file_size = data_size + 36;
-            file.seek(std::io::SeekFrom::Start(4))?;
-            file.write_all(&file_size.to_le_bytes())?;
+        /// Neue Datei nach Laufzeit und/oder Größe; der Pfad braucht dann
+        /// einen Zeitcode (`%Y%m%d-%H%M` oder `{time}`).
+        pub fn with_rotation(mut self, rotation: Option<FileRotation>) -> Self {
+            self.rotation = rotation;
+            self
+        }
 
-            file.seek(std::io::SeekFrom::Start(40))?;
-            file.write_all(&data_size.to_le_bytes())?;
+        /// Aufnahme erst auf Auslöser, mit den letzten `pre_roll.duration`
+        /// davor am Dateianfang.
+        pub fn with_pre_roll(mut self, pre_roll: Option<PreRoll>) -> Self {
+            self.pre_roll = pre_roll;
+            self
+        }
 
+        /// Startet bzw. beendet eine ausgelöste Aufnahme; nur mit `pre_roll`.
+        pub fn set_recording(&self, on: bool) -> Result<()> {
+            if self.pre_roll.is_none() {
+                anyhow::bail!("consumer '{}' records continuously (no config.pre_roll)", self.name);
+            }
+            log::info!(
+                "FileConsumer '{}': recording {}",
+                self.name,
+                if on { "started" } else { "stopped" }
+            );
+            self.trigger.set_manual(on);
             Ok(())
         }
+
+        /// Schreibt gerade in eine Datei.
+        pub fn recording(&self) -> bool {
+            self.recording.load(Ordering::Relaxed)
+        }
+
+        /// Wird nach jedem fertigen Segment aufgerufen (auch ohne Rotation
+        /// beim Stoppen).
+        pub fn add_segment_hook(&mut self, hook: Arc<dyn SegmentHook>) {
+            self.hooks.push(hook);
+        }
+
+        /// Pfad mit ersetzten Zeitcodes: `{time}` wird z. B. zu
+        /// `20261025T023000+0100_20261025T013000Z`, `%Y/%m/%d/%H%M` zu
+        /// `2026/10/25/0230` (Ortszeit).
+        pub fn resolve_path(&self, utc_ms: u64) -> String {
+            let path = self
+                .output_path
+                .replace(TIME_PLACEHOLDER, &self.timezone.file_stamp(utc_ms));
+            self.timezone.format_template(&path, utc_ms)
+        }
+
+        fn open_segment(
+            path: PathBuf,
+            started_at_ms: u64,
+            rotation: Option<&FileRotation>,
+            timezone: &TimeZone,
+            encoder_factory: Option<&EncoderFactory>,
+            bwf: Option<&BwfOptions>,
+        ) -> Result<OpenSegment> {
+            let encoder = encoder_factory.map(|factory| factory()).transpose()?;
+            if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+                std::fs::create_dir_all(dir)?;
+            }
+            let mut part_path = path.clone().into_os_string();
+            part_path.push(PART_SUFFIX);
+            let part_path = PathBuf::from(part_path);
+            let mut writer = BufWriter::new(File::create(&part_path)?);
+            let bytes = if encoder.is_none() {
+                let bext = bwf.map(|bwf| bwf.bext(started_at_ms, timezone, &WAV_FORMAT));
+                let header = wav::header(&WAV_FORMAT, bext.as_ref());
+                writer.write_all(&header)?;
+                header.len() as u64
+            } else {
+                0
+            };
+            Ok(OpenSegment {
+                path,
+                part_path,
+                writer,
+                encoder,
+                data_offset: bytes,
+                started_at_ms,
+                deadline_ms: rotation.and_then(|r| r.deadline_ms(started_at_ms, timezone)),
+                samples: 0,
+                bytes,
+            })
+        }
+
+        /// PCM direkt (WAV) oder über den Encoder; liefert die geschriebenen Bytes.
+        fn write_frame(segment: &mut OpenSegment, samples: &[i16]) -> Result<u64> {
+            let written = match segment.encoder.as_mut() {
+                None => {
+                    let mut data = Vec::with_capacity(samples.len() * 2);
+                    for sample in samples {
+                        data.extend_from_slice(&sample.to_le_bytes());
+                    }
+                    segment.writer.write_all(&data)?;
+                    data.len() as u64
+                }
+                Some(encoder) => {
+                    let mut written = 0;
+                    for encoded in encoder.encode(samples)? {
+                        segment.writer.write_all(&encoded.payload)?;
+                        written += encoded.payload.len() as u64;
+                    }
+                    written
+                }
+            };
+            segment.samples += samples.len() as u64;
+            segment.bytes += written;
+            Ok(written)
+        }
+
+        /// Header schreiben (bzw. Encoder leeren), syncen und `.part` atomar
+        /// umbenennen.
+        fn finish_segment(mut segment: OpenSegment) -> Result<FinishedSegment> {
+            if let Some(mut encoder) = segment.encoder.take() {
+                for encoded in encoder.flush()? {
+                    segment.writer.write_all(&encoded.payload)?;
+                    segment.bytes += encoded.payload.len() as u64;
+                }
+                let mut file = segment.writer.into_inner().map_err(|e| e.into_error())?;
+                // z. B. FLAC: Länge und MD5 stehen erst jetzt fest
+                if let Some(header) = encoder.final_header() {
+                    file.seek(std::io::SeekFrom::Start(0))?;
+                    file.write_all(&header)?;
+                }
+                file.sync_all()?;
+                drop(file);
+                std::fs::rename(&segment.part_path, &segment.path)?;
+                return Ok(FinishedSegment {
+                    consumer: String::new(),
+                    bytes: segment.bytes,
+                    duration_ms: segment.samples * 1000 / (SAMPLE_RATE * CHANNELS),
+                    started_at_ms: segment.started_at_ms,
+                    path: segment.path,
+                });
+            }
+            let mut file = segment.writer.into_inner().map_err(|e| e.into_error())?;
+            let data_size = segment.samples * 2;
+            if wav::finalize(&mut file, segment.data_offset, data_size, &WAV_FORMAT)? {
+                log::info!("{} exceeds 4 GiB, written as RF64", segment.path.display());
+            }
+            file.sync_all()?;
+            drop(file);
+            std::fs::rename(&segment.part_path, &segment.path)?;
+            Ok(FinishedSegment {
+                consumer: String::new(),
+                bytes: segment.data_offset + data_size,
+                duration_ms: segment.samples * 1000 / (SAMPLE_RATE * CHANNELS),
+                started_at_ms: segment.started_at_ms,
+                path: segment.path,
+            })
+        }
+
+        /// Schließt eine nach Absturz liegengebliebene `.part`-Datei ab:
+        /// Header aus der Dateilänge (auf ganze Sample-Frames gekürzt),
+        /// umbenennen, ins Archiv eintragen. Ohne gespeicherten Zustand
+        /// passiert nichts.
+        pub fn recover_interrupted(&self, store: &StateStore) -> Option<PathBuf> {
+            let key = state_key(&self.name);
+            let stored = store.load_json::<RecorderState>(&key)?;
+            let state = stored.value;
+            let recovered = Self::finish_part(&state, stored.saved_at_ms);
+            store.remove(&key);
+            match recovered {
+                Ok(segment) => {
+                    let path = segment.path.clone();
+                    log::warn!(
+                        "FileConsumer '{}' recovered interrupted recording {} ({} ms)",
+                        self.name,
+                        path.display(),
+                        segment.duration_ms
+                    );
+                    Self::publish_segment(
+                        FinishedSegment {
+                            consumer: self.name.clone(),
+                            ..segment
+                        },
+                        &self.timezone,
+                        &self.hooks,
+                    );
+                    Some(path)
+                }
+                Err(e) => {
+                    log::error!(
+                        "FileConsumer '{}': cannot recover {}: {:#}",
+                        self.name,
+                        state.part_path.display(),
+                        e
+                    );
+                    None
+                }
+            }
+        }
+
+        fn finish_part(state: &RecorderState, saved_at_ms: u64) -> Result<FinishedSegment> {
+            if state.path.exists() {
+                anyhow::bail!("{} already exists", state.path.display());
+            }
+            let mut file = OpenOptions::new().read(true).write(true).open(&state.part_path)?;
+            let len = file.metadata()?.len();
+            let Ok(data_offset) = wav::data_offset(&mut file) else {
+                // Kodierte Formate ohne Längenfeld: Datei bleibt wie sie ist,
+                // die Dauer reicht bis zum letzten gespeicherten Stand.
+                file.sync_all()?;
+                drop(file);
+                std::fs::rename(&state.part_path, &state.path)?;
+                return Ok(FinishedSegment {
+                    consumer: String::new(),
+                    path: state.path.clone(),
+                    started_at_ms: state.started_at_ms,
+                    duration_ms: saved_at_ms.saturating_sub(state.started_at_ms),
+                    bytes: len,
+                });
+            };
+            let block = WAV_FORMAT.block_align();
+            let available = len.saturating_sub(data_offset);
+            if available < state.data_bytes {
+                log::warn!(
+                    "{} is shorter than the last saved position ({} < {} bytes)",
+                    state.part_path.display(),
+                    available,
+                    state.data_bytes
+                );
+            }
+            let data = available - available % block;
+            file.set_len(data_offset + data)?;
+            wav::finalize(&mut file, data_offset, data, &WAV_FORMAT)?;
+            file.sync_all()?;
+            drop(file);
+            std::fs::rename(&state.part_path, &state.path)?;
+            Ok(FinishedSegment {
+                consumer: String::new(),
+                path: state.path.clone(),
+                started_at_ms: state.started_at_ms,
+                duration_ms: data / 2 * 1000 / (SAMPLE_RATE * CHANNELS),
+                bytes: data_offset + data,
+            })
+        }
+
+        fn save_state(store: &StateStore, name: &str, segment: &OpenSegment) {
+            let state = RecorderState {
+                path: segment.path.clone(),
+                part_path: segment.part_path.clone(),
+                started_at_ms: segment.started_at_ms,
+                data_bytes: match segment.encoder {
+                    None => segment.samples * 2,
+                    Some(_) => segment.bytes,
+                },
+            };
+            if let Err(e) = store.save_json(&state_key(name), &state) {
+                log::warn!("[state] recorder '{}': {:#}", name, e);
+            }
+        }
+
+        fn segment_path(output_path: &str, timezone: &TimeZone, utc_ms: u64) -> Result<PathBuf> {
+            let resolved = output_path.replace(TIME_PLACEHOLDER, &timezone.file_stamp(utc_ms));
+            sanitize_audio_path(&timezone.format_template(&resolved, utc_ms))
+        }
+
+        /// Archiv-Eintrag, Waveform und Hooks für ein fertiges Segment.
+        fn publish_segment(segment: FinishedSegment, timezone: &TimeZone, hooks: &[Arc<dyn SegmentHook>]) {
+            match archive::record_file(&segment.path, segment.duration_ms, timezone) {
+                Ok(_) => waveform::spawn_tile_generation(segment.path.clone()),
+                Err(e) => log::error!(
+                    "Failed to add {} to archive manifest: {:#}",
+                    segment.path.display(),
+                    e
+                ),
+            }
+            if hooks.is_empty() {
+                return;
+            }
+            let hooks = hooks.to_vec();
+            std::thread::spawn(move || {
+                for hook in hooks {
+                    if let Err(e) = hook.segment_finished(&segment) {
+                        log::error!(
+                            "Segment hook for {} failed: {:#}",
+                            segment.path.display(),
+                            e
+                        );
+                    }
+                }
+            });
+        }
     }
 
     impl Consumer for FileConsumer {
@@ -107,78 +682,234 @@ pub mod file_writer {
                 return Ok(());
             }
 
-            let output_path = sanitize_audio_path(&self.output_path)?;
+            let store = state_store::installed();
+            if let Some(store) = &store {
+                self.recover_interrupted(store);
+            }
+
+            let first_path = Self::segment_path(&self.output_path, &self.timezone, utc_ns_now() / 1_000_000)?;
             log::info!(
                 "FileConsumer '{}' starting to write to {}",
                 self.name,
-                output_path.display()
+                first_path.display()
             );
+            archive::register_archive_dir(&archive::archive_dir(&first_path));
+            if let Some(pre_roll) = self.pre_roll.as_ref().filter(|p| !p.record_on.is_empty()) {
+                match &self.emitter {
+                    Some(emitter) => {
+                        let handler = RecordTriggerHandler::new(&self.name, pre_roll, self.trigger.clone());
+                        if let Err(e) = emitter.subscribe(Arc::new(handler)) {
+                            log::warn!("FileConsumer '{}': cannot watch events: {:#}", self.name, e);
+                        }
+                    }
+                    None => log::warn!(
+                        "FileConsumer '{}': no event bus, config.record_on is ignored",
+                        self.name
+                    ),
+                }
+            }
             self.running.store(true, Ordering::SeqCst);
 
+            let name = self.name.clone();
             let running = self.running.clone();
             let input_buffer = self.input_buffer.clone();
-            let output_path = output_path.clone();
+            let output_path = self.output_path.clone();
             let frames_processed = self.frames_processed.clone();
             let bytes_written = self.bytes_written.clone();
+            let errors = self.errors.clone();
             let reader_id = self.reader_id.clone();
+            let timezone = self.timezone.clone();
+            let rotation = self.rotation.clone();
+            let hooks = self.hooks.clone();
+            let encoder_factory = self.encoder_factory.clone();
+            let bwf = self.bwf.clone();
+            let pre_roll = self.pre_roll.clone();
+            let trigger = self.trigger.clone();
+            let recording = self.recording.clone();
 
-            let handle = std::thread::spawn(move || match File::create(&output_path) {
-                Ok(file) => {
-                    let mut writer = BufWriter::new(file);
+            let handle = std::thread::spawn(move || {
+                let close = |segment: OpenSegment| match Self::finish_segment(segment) {
+                    Ok(finished) => {
+                        if let Some(store) = &store {
+                            store.remove(&state_key(&name));
+                        }
+                        log::info!("FileConsumer '{}' finished {}", name, finished.path.display());
+                        Self::publish_segment(
+                            FinishedSegment {
+                                consumer: name.clone(),
+                                ..finished
+                            },
+                            &timezone,
+                            &hooks,
+                        );
+                    }
+                    Err(e) => {
+                        errors.fetch_add(1, Ordering::Relaxed);
+                        log::error!("FileConsumer '{}': failed to finish segment: {}", name, e);
+                    }
+                };
 
-                    if let Err(e) = Self::write_wav_header(&mut writer, 48000, 2, 16) {
-                        log::error!("Failed to write WAV header: {}", e);
-                        return;
+                let mut last_saved = std::time::Instant::now();
+                // Mit Vorlauf wird die erste Datei erst beim Auslösen geöffnet
+                let mut held = pre_roll.as_ref().map(|pre_roll| PreRollBuffer::new(pre_roll.duration));
+                let mut segment = if held.is_some() {
+                    None
+                } else {
+                    match Self::open_segment(
+                        first_path.clone(),
+                        utc_ns_now() / 1_000_000,
+                        rotation.as_ref(),
+                        &timezone,
+                        encoder_factory.as_ref(),
+                        bwf.as_ref(),
+                    ) {
+                        Ok(segment) => {
+                            if let Some(store) = &store {
+                                Self::save_state(store, &name, &segment);
+                            }
+                            recording.store(true, Ordering::SeqCst);
+                            Some(segment)
+                        }
+                        Err(e) => {
+                            errors.fetch_add(1, Ordering::Relaxed);
+                            log::error!("Failed to create file {}: {}", first_path.display(), e);
+                            return;
+                        }
                     }
+                };
 
-                    let mut total_samples: u32 = 0;
+                let mut idle = IdleBackoff::new(std::time::Duration::from_millis(10));
+                while running.load(Ordering::Relaxed) {
+                    let Some(buffer) = &input_buffer else {
+                        std::thread::sleep(std::time::Duration::from_millis(100));
+                        continue;
+                    };
+                    let Some(frame) = buffer.pop_for_reader(&reader_id) else {
+                        idle.sleep_for(buffer);
+                        continue;
+                    };
+                    idle.reset();
 
-                    while running.load(Ordering::Relaxed) {
-                        if let Some(buffer) = &input_buffer {
-                            if let Some(frame) = buffer.pop_for_reader(&reader_id) {
-                                for sample in &frame.samples {
-                                    if let Err(e) = writer.write_all(&sample.to_le_bytes()) {
-                                        log::error!("Write error: {}", e);
-                                        break;
-                                    }
-                                    bytes_written.fetch_add(2, Ordering::Relaxed);
+                    let now_ms = if frame.utc_ns > 0 {
+                        frame.utc_ns / 1_000_000
+                    } else {
+                        utc_ns_now() / 1_000_000
+                    };
+                    if let Some(held) = held.as_mut() {
+                        if !trigger.active(std::time::Instant::now()) {
+                            if let Some(done) = segment.take() {
+                                close(done);
+                                recording.store(false, Ordering::SeqCst);
+                            }
+                            held.push(frame);
+                            continue;
+                        }
+                    }
+                    if let (Some(rotation), Some(current)) = (&rotation, &segment) {
+                        // Bei WAV ist die Größe nach diesem Frame exakt bekannt
+                        let pending = if current.encoder.is_none() {
+                            frame.samples.len() as u64 * 2
+                        } else {
+                            0
+                        };
+                        let bytes = current.bytes + pending;
+                        if rotation.due(current.deadline_ms, now_ms, bytes) {
+                            if let Some(done) = segment.take() {
+                                close(done);
+                            }
+                        }
+                    }
+                    if segment.is_none() {
+                        // Ausgelöste Datei beginnt mit dem ältesten Vorlauf-Frame
+                        let started_ms = held
+                            .as_ref()
+                            .and_then(|held| held.first_utc_ns())
+                            .filter(|utc_ns| *utc_ns > 0)
+                            .map_or(now_ms, |utc_ns| utc_ns / 1_000_000);
+                        let opened = Self::segment_path(&output_path, &timezone, started_ms).and_then(|path| {
+                            archive::register_archive_dir(&archive::archive_dir(&path));
+                            Self::open_segment(
+                                path,
+                                started_ms,
+                                rotation.as_ref(),
+                                &timezone,
+                                encoder_factory.as_ref(),
+                                bwf.as_ref(),
+                            )
+                        });
+                        match opened {
+                            Ok(opened) => {
+                                if let Some(store) = &store {
+                                    Self::save_state(store, &name, &opened);
                                 }
-
-                                total_samples += frame.samples.len() as u32;
-                                frames_processed.fetch_add(1, Ordering::Relaxed);
-
-                                if frames_processed.load(Ordering::Relaxed) % 10 == 0 {
-                                    if let Err(e) = writer.flush() {
-                                        log::error!("Flush error: {}", e);
-                                    }
+                                segment = Some(opened);
+                            }
+                            Err(e) => {
+                                // Frame verwerfen, beim nächsten erneut versuchen
+                                errors.fetch_add(1, Ordering::Relaxed);
+                                log::error!("FileConsumer '{}': cannot open next segment: {}", name, e);
+                                continue;
+                            }
+                        }
+                    }
+                    let Some(current) = segment.as_mut() else {
+                        continue;
+                    };
+                    if let Some(held) = held.as_mut() {
+                        if !recording.swap(true, Ordering::SeqCst) {
+                            log::info!(
+                                "FileConsumer '{}' recording to {} with {} ms pre-roll",
+                                name,
+                                current.path.display(),
+                                held.held().as_millis()
+                            );
+                        }
+                        for earlier in held.take() {
+                            match Self::write_frame(current, &earlier.samples) {
+                                Ok(written) => {
+                                    bytes_written.fetch_add(written, Ordering::Relaxed);
+                                }
+                                Err(e) => {
+                                    errors.fetch_add(1, Ordering::Relaxed);
+                                    log::error!("Write error: {}", e);
                                 }
-                            } else {
-                                std::thread::sleep(std::time::Duration::from_millis(10));
                             }
-                        } else {
-                            std::thread::sleep(std::time::Duration::from_millis(100));
+                            frames_processed.fetch_add(1, Ordering::Relaxed);
                         }
                     }
 
-                    if let Ok(mut file) = writer.into_inner() {
-                        let data_size = total_samples * 2;
-                        if let Err(e) = Self::update_wav_header(&mut file, data_size) {
-                            log::error!("Failed to update WAV header: {}", e);
+                    match Self::write_frame(current, &frame.samples) {
+                        Ok(written) => {
+                            bytes_written.fetch_add(written, Ordering::Relaxed);
                         }
-                        if let Err(e) = file.sync_all() {
-                            log::error!("Failed to sync file: {}", e);
+                        Err(e) => {
+                            errors.fetch_add(1, Ordering::Relaxed);
+                            log::error!("Write error: {}", e);
                         }
                     }
+                    frames_processed.fetch_add(1, Ordering::Relaxed);
 
-                    log::info!(
-                        "FileConsumer stopped. Wrote {} frames to {}",
-                        frames_processed.load(Ordering::Relaxed),
-                        output_path.display()
-                    );
+                    if frames_processed.load(Ordering::Relaxed) % 10 == 0 {
+                        if let Err(e) = current.writer.flush() {
+                            log::error!("Flush error: {}", e);
+                        } else if let Some(store) = &store {
+                            if last_saved.elapsed() >= store.interval() {
+                                last_saved = std::time::Instant::now();
+                                Self::save_state(store, &name, current);
+                            }
+                        }
+                    }
                 }
-                Err(e) => {
-                    log::error!("Failed to create file {}: {}", output_path.display(), e);
+
+                if let Some(done) = segment.take() {
+                    close(done);
                 }
+                recording.store(false, Ordering::SeqCst);
+
+                log::info!(
+                    "FileConsumer stopped. Wrote {} frames",
+                    frames_processed.load(Ordering::Relaxed)
+                );
             });
 
             self.thread_handle = Some(handle);
@@ -188,6 +919,11 @@ pub mod file_writer {
         fn stop(&mut self) -> Result<()> {
             log::info!("FileConsumer '{}' stopping...", self.name);
             self.running.store(false, Ordering::SeqCst);
+            if let (Some(emitter), Some(pre_roll)) = (&self.emitter, &self.pre_roll) {
+                if !pre_roll.record_on.is_empty() {
+                    let _ = emitter.unsubscribe(&format!("record_trigger:{}", self.name));
+                }
+            }
 
             if let Some(handle) = self.thread_handle.take() {
                 if let Err(e) = handle.join() {
@@ -204,7 +940,8 @@ pub mod file_writer {
                 connected: self.input_buffer.is_some(),
                 frames_processed: self.frames_processed.load(Ordering::Relaxed),
                 bytes_written: self.bytes_written.load(Ordering::Relaxed),
-                errors: 0,
+                errors: self.errors.load(Ordering::Relaxed),
+                connection: None,
             }
         }
 
@@ -212,6 +949,18 @@ pub mod file_writer {
             self.input_buffer = Some(buffer);
             log::info!("FileConsumer '{}' attached to buffer", self.name);
         }
+
+        fn attach_event_emitter(&mut self, emitter: EventEmitter) {
+            self.emitter = Some(emitter);
+        }
+
+        /// `{"record": true|false}` steuert eine ausgelöste Aufnahme.
+        fn update_config(&mut self, config: serde_json::Value) -> Result<()> {
+            match config.get("record") {
+                Some(serde_json::Value::Bool(on)) => self.set_recording(*on),
+                _ => anyhow::bail!("consumer '{}': expected {{\"record\": true|false}}", self.name),
+            }
+        }
     }
 }
 
@@ -274,9 +1023,11 @@ pub mod encoded_output {
             let name = self.name.clone();
 
             let handle = std::thread::spawn(move || {
+                let mut idle = IdleBackoff::new(std::time::Duration::from_millis(10));
                 while running.load(Ordering::Relaxed) {
                     if let Some(buffer) = &input_buffer {
                         if let Some(frame) = buffer.pop_for_reader(&reader_id) {
+                            idle.reset();
                             match encoder.encode(&frame.samples) {
                                 Ok(encoded_frames) => {
                                     for encoded in encoded_frames {
@@ -305,7 +1056,7 @@ pub mod encoded_output {
                                 }
                             }
                         } else {
-                            std::thread::sleep(std::time::Duration::from_millis(10));
+                            idle.sleep_for(buffer);
                         }
                     } else {
                         std::thread::sleep(std::time::Duration::from_millis(100));
@@ -336,6 +1087,7 @@ pub mod encoded_output {
                 frames_processed: self.frames_processed.load(Ordering::Relaxed),
                 bytes_written: self.bytes_written.load(Ordering::Relaxed),
                 errors: 0,
+                connection: None,
             }
         }
 
@@ -0,0 +1,91 @@
+// src/core/correlation.rs
+//
+// Korrelations-IDs für zusammengehörige Events (z. B. alle Events eines
+// Config-Apply). Die ID gilt pro Thread, solange ein `CorrelationScope` lebt;
+// `Event::new` übernimmt sie automatisch. Threads, die für eine Aktion
+// arbeiten (`parallel::for_each_bounded`, Failover), setzen sie per
+// `CorrelationScope::with_id` neu.
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::core::timestamp::utc_ns_now;
+
+thread_local! {
+    static CURRENT: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+static COUNTER: AtomicU64 = AtomicU64::new(1);
+
+pub fn new_correlation_id(prefix: &str) -> String {
+    format!(
+        "{}-{:x}-{}",
+        prefix,
+        utc_ns_now() / 1_000_000,
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    )
+}
+
+/// Aktuelle Korrelations-ID des Threads, falls ein Scope aktiv ist.
+pub fn current_correlation_id() -> Option<String> {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+/// RAII-Guard: setzt die Korrelations-ID für die Lebensdauer des Scopes.
+///
+/// Ist bereits ein Scope aktiv, wird dessen ID weiterverwendet, damit
+/// verschachtelte Aktionen (z. B. Config-Apply innerhalb einer Control-Aktion)
+/// zu einer Gruppe gehören.
+pub struct CorrelationScope {
+    id: String,
+    previous: Option<String>,
+}
+
+impl CorrelationScope {
+    pub fn begin(prefix: &str) -> Self {
+        let previous = current_correlation_id();
+        let id = previous
+            .clone()
+            .unwrap_or_else(|| new_correlation_id(prefix));
+        Self::enter(id, previous)
+    }
+
+    /// Setzt eine vorgegebene ID (z. B. aus einem Request-Header).
+    pub fn with_id(id: &str) -> Self {
+        let previous = current_correlation_id();
+        Self::enter(id.to_string(), previous)
+    }
+
+    fn enter(id: String, previous: Option<String>) -> Self {
+        CURRENT.with(|current| *current.borrow_mut() = Some(id.clone()));
+        Self { id, previous }
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+impl Drop for CorrelationScope {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        CURRENT.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nested_scopes_share_id() {
+        assert!(current_correlation_id().is_none());
+        {
+            let outer = CorrelationScope::begin("control");
+            let inner = CorrelationScope::begin("config");
+            assert_eq!(outer.id(), inner.id());
+            drop(inner);
+            assert_eq!(current_correlation_id().as_deref(), Some(outer.id()));
+        }
+        assert!(current_correlation_id().is_none());
+    }
+}
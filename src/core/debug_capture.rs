@@ -0,0 +1,329 @@
+// src/core/debug_capture.rs
+//
+// Zeitlich begrenzter Debug-Capture für den Produktivbetrieb: für N Minuten
+// wird das Logging ausführlicher, jedes Frame eines Flows (Inputs und Output)
+// als Trace-Zeile und die Ringbuffer-Statistik im Sekundentakt in eine
+// JSON-Lines-Datei geschrieben. Danach wird alles automatisch zurückgesetzt.
+// Die Frames werden über eigene Reader gelesen, der Flow selbst bleibt unberührt.
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail, Context};
+use serde::Serialize;
+
+use crate::core::lock::lock_mutex;
+use crate::core::logging::set_verbose_logging;
+use crate::core::ringbuffer::{AudioRingBuffer, RingBufferStats};
+use crate::core::timestamp::utc_ns_now;
+use crate::core::{AirliftNode, BufferRegistry};
+
+pub const MAX_CAPTURE_DURATION: Duration = Duration::from_secs(60 * 60);
+const DEFAULT_CAPTURE_DIR: &str = "./data/debug";
+const READER_ID: &str = "debug-capture";
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+const STATS_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone)]
+pub struct DebugCaptureRequest {
+    pub flow: String,
+    pub duration: Duration,
+    /// Zieldatei; Standard `./data/debug/<flow>-<zeit>.jsonl`
+    pub path: Option<PathBuf>,
+    /// Logging für die Dauer auf Debug anheben
+    pub verbose: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DebugCaptureStatus {
+    pub active: bool,
+    pub flow: String,
+    pub path: String,
+    pub started_ms: u64,
+    pub ends_ms: u64,
+    pub verbose: bool,
+    pub frames_traced: u64,
+    pub stats_samples: u64,
+}
+
+#[derive(Serialize)]
+struct FrameTrace<'a> {
+    kind: &'static str,
+    buffer: &'a str,
+    utc_ns: u64,
+    samples: usize,
+    sample_rate: u32,
+    channels: u8,
+    peak: f32,
+    /// Alter des Frames beim Auslesen
+    age_ms: f64,
+}
+
+#[derive(Serialize)]
+struct BufferSample {
+    name: String,
+    capacity: usize,
+    frames: usize,
+    dropped: u64,
+    latest_ns: Option<u64>,
+}
+
+impl BufferSample {
+    fn new(name: &str, stats: RingBufferStats) -> Self {
+        Self {
+            name: name.to_string(),
+            capacity: stats.capacity.get(),
+            frames: stats.current_frames.get(),
+            dropped: stats.dropped_frames,
+            latest_ns: stats.latest_timestamp,
+        }
+    }
+}
+
+struct ActiveCapture {
+    status: Arc<Mutex<DebugCaptureStatus>>,
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+/// Ein Capture gleichzeitig, prozessweit.
+pub struct DebugCaptureService {
+    active: Mutex<Option<ActiveCapture>>,
+    last: Mutex<Option<DebugCaptureStatus>>,
+}
+
+static DEBUG_CAPTURE: OnceLock<DebugCaptureService> = OnceLock::new();
+
+pub fn debug_capture() -> &'static DebugCaptureService {
+    DEBUG_CAPTURE.get_or_init(|| DebugCaptureService {
+        active: Mutex::new(None),
+        last: Mutex::new(None),
+    })
+}
+
+impl DebugCaptureService {
+    pub fn start(&self, node: &AirliftNode, request: DebugCaptureRequest) -> anyhow::Result<DebugCaptureStatus> {
+        if request.duration.is_zero() || request.duration > MAX_CAPTURE_DURATION {
+            bail!(
+                "capture duration must be between 1s and {} min",
+                MAX_CAPTURE_DURATION.as_secs() / 60
+            );
+        }
+        let mut active = lock_mutex(&self.active, "debug_capture.start");
+        if let Some(mut previous) = active.take() {
+            let status = lock_mutex(&previous.status, "debug_capture.previous").clone();
+            if status.active {
+                *active = Some(previous);
+                bail!("a debug capture for flow '{}' is already running", status.flow);
+            }
+            previous.join();
+            *lock_mutex(&self.last, "debug_capture.last") = Some(status);
+        }
+
+        let flow = node
+            .flows()
+            .iter()
+            .find(|flow| flow.name == request.flow)
+            .ok_or_else(|| anyhow!("flow '{}' not found", request.flow))?;
+        let mut buffers: Vec<(String, Arc<AudioRingBuffer>)> = flow
+            .input_buffers
+            .iter()
+            .enumerate()
+            .map(|(index, buffer)| (format!("flow:{}:input[{}]", flow.name, index), buffer.clone()))
+            .collect();
+        buffers.push((format!("flow:{}:output", flow.name), flow.output_buffer.clone()));
+        // Nur neue Frames tracen
+        for (_, buffer) in &buffers {
+            while buffer.pop_for_reader(READER_ID).is_some() {}
+        }
+
+        let path = match request.path.clone() {
+            Some(path) => path,
+            None => PathBuf::from(DEFAULT_CAPTURE_DIR)
+                .join(format!("{}-{}.jsonl", request.flow, utc_ns_now() / 1_000_000)),
+        };
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("create capture dir {}", dir.display()))?;
+        }
+        let file = File::create(&path).with_context(|| format!("create {}", path.display()))?;
+
+        let started_ms = utc_ns_now() / 1_000_000;
+        let status = Arc::new(Mutex::new(DebugCaptureStatus {
+            active: true,
+            flow: request.flow.clone(),
+            path: path.display().to_string(),
+            started_ms,
+            ends_ms: started_ms + request.duration.as_millis() as u64,
+            verbose: request.verbose,
+            frames_traced: 0,
+            stats_samples: 0,
+        }));
+        let stop = Arc::new(AtomicBool::new(false));
+        let was_verbose = request.verbose && set_verbose_logging(true);
+
+        log::warn!(
+            "Debug capture for flow '{}' started ({} s, {}) -> {}",
+            request.flow,
+            request.duration.as_secs(),
+            if request.verbose { "verbose logging" } else { "normal logging" },
+            path.display()
+        );
+
+        let worker = CaptureWorker {
+            writer: BufWriter::new(file),
+            buffers,
+            registry: node.buffer_registry(),
+            deadline: Instant::now() + request.duration,
+            stop: stop.clone(),
+            status: status.clone(),
+            frames: 0,
+        };
+        let restore_verbose = request.verbose;
+        let handle = std::thread::spawn(move || {
+            worker.run();
+            // Nur zurücksetzen, was der Capture selbst eingeschaltet hat
+            if restore_verbose && !was_verbose {
+                set_verbose_logging(false);
+            }
+        });
+
+        let snapshot = lock_mutex(&status, "debug_capture.snapshot").clone();
+        *active = Some(ActiveCapture {
+            status,
+            stop,
+            handle: Some(handle),
+        });
+        Ok(snapshot)
+    }
+
+    /// Beendet einen laufenden Capture vorzeitig.
+    pub fn stop(&self) -> Option<DebugCaptureStatus> {
+        let mut capture = lock_mutex(&self.active, "debug_capture.stop").take()?;
+        capture.stop.store(true, Ordering::SeqCst);
+        capture.join();
+        let status = lock_mutex(&capture.status, "debug_capture.stop_status").clone();
+        *lock_mutex(&self.last, "debug_capture.last") = Some(status.clone());
+        Some(status)
+    }
+
+    /// Laufender oder zuletzt beendeter Capture.
+    pub fn status(&self) -> Option<DebugCaptureStatus> {
+        if let Some(capture) = lock_mutex(&self.active, "debug_capture.status").as_ref() {
+            return Some(lock_mutex(&capture.status, "debug_capture.status_inner").clone());
+        }
+        lock_mutex(&self.last, "debug_capture.last").clone()
+    }
+}
+
+impl ActiveCapture {
+    fn join(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+struct CaptureWorker {
+    writer: BufWriter<File>,
+    buffers: Vec<(String, Arc<AudioRingBuffer>)>,
+    registry: Arc<BufferRegistry>,
+    deadline: Instant,
+    stop: Arc<AtomicBool>,
+    status: Arc<Mutex<DebugCaptureStatus>>,
+    frames: u64,
+}
+
+impl CaptureWorker {
+    fn run(mut self) {
+        let mut next_stats = Instant::now();
+        let mut stats_samples = 0;
+
+        while !self.stop.load(Ordering::Relaxed) && Instant::now() < self.deadline {
+            if let Err(e) = self.trace_frames() {
+                log::error!("Debug capture: write failed: {}", e);
+                break;
+            }
+            if Instant::now() >= next_stats {
+                if let Err(e) = self.write_stats() {
+                    log::error!("Debug capture: write failed: {}", e);
+                    break;
+                }
+                stats_samples += 1;
+                next_stats += STATS_INTERVAL;
+            }
+            {
+                let mut status = lock_mutex(&self.status, "debug_capture.progress");
+                status.frames_traced = self.frames;
+                status.stats_samples = stats_samples;
+            }
+            std::thread::sleep(POLL_INTERVAL);
+        }
+
+        let _ = self.writer.flush();
+        for (_, buffer) in &self.buffers {
+            buffer.remove_reader(READER_ID);
+        }
+        let mut status = lock_mutex(&self.status, "debug_capture.finish");
+        status.active = false;
+        status.ends_ms = utc_ns_now() / 1_000_000;
+        log::warn!(
+            "Debug capture for flow '{}' finished ({} frames, {} stat samples) -> {}",
+            status.flow,
+            status.frames_traced,
+            status.stats_samples,
+            status.path
+        );
+    }
+
+    fn trace_frames(&mut self) -> anyhow::Result<()> {
+        for (name, buffer) in &self.buffers {
+            while let Some(frame) = buffer.pop_for_reader(READER_ID) {
+                let peak = frame
+                    .samples
+                    .iter()
+                    .map(|sample| (*sample as f32).abs() / 32768.0)
+                    .fold(0.0, f32::max);
+                let kind = if name.ends_with(":output") { "output" } else { "input" };
+                let trace = FrameTrace {
+                    kind,
+                    buffer: name,
+                    utc_ns: frame.utc_ns,
+                    samples: frame.samples.len(),
+                    sample_rate: frame.sample_rate,
+                    channels: frame.channels,
+                    peak,
+                    age_ms: utc_ns_now().saturating_sub(frame.utc_ns) as f64 / 1e6,
+                };
+                serde_json::to_writer(&mut self.writer, &serde_json::json!({ "frame": trace }))?;
+                self.writer.write_all(b"\n")?;
+                self.frames += 1;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_stats(&mut self) -> anyhow::Result<()> {
+        let mut buffers: Vec<BufferSample> = self
+            .buffers
+            .iter()
+            .map(|(name, buffer)| BufferSample::new(name, buffer.stats()))
+            .collect();
+        for name in self.registry.list() {
+            if let Some(buffer) = self.registry.get(&name) {
+                buffers.push(BufferSample::new(&name, buffer.stats()));
+            }
+        }
+        serde_json::to_writer(
+            &mut self.writer,
+            &serde_json::json!({ "ring_stats": { "timestamp_ms": utc_ns_now() / 1_000_000, "buffers": buffers } }),
+        )?;
+        self.writer.write_all(b"\n")?;
+        self.writer.flush()?;
+        Ok(())
+    }
+}
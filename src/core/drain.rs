@@ -0,0 +1,209 @@
+// src/core/drain.rs
+//
+// Draining bei Config-Änderungen: Ausgänge, an denen gerade Zuhörer hängen
+// (`Consumer::listeners`), werden beim Neuaufbau nicht gestoppt, sondern aus
+// dem alten Flow gelöst. Sie nehmen keine neuen Verbindungen mehr an
+// (`Consumer::begin_drain` gibt Port bzw. Route für den neuen Consumer frei)
+// und bedienen die bestehenden weiter, bis diese gehen oder die Frist
+// abläuft. Ton bekommen sie über einen Weiterleiter aus dem Ausgang des
+// neuen Flows gleichen Namens in ihren alten Buffer; Encoder, Codec und
+// Verbindungen bleiben die alten.
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::core::idle::IdleBackoff;
+use crate::core::lock::lock_mutex;
+use crate::core::{AudioRingBuffer, Consumer};
+use crate::producers::wait::StopWait;
+
+/// Standard-Frist (`[reload] drain_grace`)
+pub const DEFAULT_DRAIN_GRACE: Duration = Duration::from_secs(30);
+/// Wartezeit ohne Frames bzw. ohne Quelle
+const IDLE_WAIT: Duration = Duration::from_millis(20);
+
+/// Aus dem alten Flow gelöster Consumer samt seinem Eingangs-Buffer
+pub struct DrainCandidate {
+    pub flow: String,
+    pub consumer: Box<dyn Consumer>,
+    pub buffer: Arc<AudioRingBuffer>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DrainStatus {
+    pub consumer: String,
+    pub flow: String,
+    pub listeners: usize,
+    pub remaining_ms: u64,
+    /// `false`, solange (oder wenn) es keinen neuen Flow gleichen Namens gibt
+    pub forwarding: bool,
+}
+
+struct Drain {
+    status: Arc<Mutex<DrainStatus>>,
+    source: Arc<Mutex<Option<Arc<AudioRingBuffer>>>>,
+    running: Arc<AtomicBool>,
+    wait: Arc<StopWait>,
+    handle: Option<std::thread::JoinHandle<()>>,
+}
+
+impl Drain {
+    fn stop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+        self.wait.notify_all();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct DrainPool {
+    drains: Vec<Drain>,
+}
+
+impl DrainPool {
+    /// Bedient `candidate` weiter, bis keine Zuhörer mehr da sind oder
+    /// `grace` abgelaufen ist; danach wird der Consumer gestoppt.
+    pub fn drain(&mut self, candidate: DrainCandidate, grace: Duration) {
+        let DrainCandidate {
+            flow,
+            mut consumer,
+            buffer,
+        } = candidate;
+        let status = Arc::new(Mutex::new(DrainStatus {
+            consumer: consumer.name().to_string(),
+            flow: flow.clone(),
+            listeners: consumer.listeners(),
+            remaining_ms: grace.as_millis() as u64,
+            forwarding: false,
+        }));
+        let source: Arc<Mutex<Option<Arc<AudioRingBuffer>>>> = Arc::new(Mutex::new(None));
+        let running = Arc::new(AtomicBool::new(true));
+        let wait = Arc::new(StopWait::new());
+
+        log::info!(
+            "Draining consumer '{}' of flow '{}' ({} listeners, up to {:?})",
+            consumer.name(),
+            flow,
+            consumer.listeners(),
+            grace
+        );
+
+        let handle = {
+            let status = status.clone();
+            let source = source.clone();
+            let running = running.clone();
+            let wait = wait.clone();
+            std::thread::spawn(move || {
+                let deadline = Instant::now() + grace;
+                let reader_id = format!("drain:{}", consumer.name());
+                let mut idle = IdleBackoff::new(IDLE_WAIT);
+                while running.load(Ordering::Relaxed) {
+                    let now = Instant::now();
+                    let listeners = consumer.listeners();
+                    {
+                        let mut status = lock_mutex(&status, "drain.status");
+                        status.listeners = listeners;
+                        status.remaining_ms =
+                            deadline.saturating_duration_since(now).as_millis() as u64;
+                    }
+                    if listeners == 0 || now >= deadline {
+                        break;
+                    }
+
+                    let current = lock_mutex(&source, "drain.source").clone();
+                    let Some(current) = current else {
+                        idle.wait(&wait);
+                        continue;
+                    };
+                    let mut forwarded = false;
+                    while let Some(frame) = current.pop_for_reader(&reader_id) {
+                        buffer.push(frame);
+                        forwarded = true;
+                    }
+                    if forwarded {
+                        idle.reset();
+                    } else {
+                        idle.wait_for(&current, &wait);
+                    }
+                }
+
+                let name = consumer.name().to_string();
+                if let Err(e) = consumer.stop() {
+                    log::warn!("Draining consumer '{}' failed to stop: {:#}", name, e);
+                }
+                if let Some(current) = lock_mutex(&source, "drain.finish").take() {
+                    current.remove_reader(&reader_id);
+                }
+                let status = lock_mutex(&status, "drain.finish");
+                log::info!(
+                    "Drained consumer '{}' ({} listeners left)",
+                    name,
+                    status.listeners
+                );
+            })
+        };
+
+        self.drains.push(Drain {
+            status,
+            source,
+            running,
+            wait,
+            handle: Some(handle),
+        });
+    }
+
+    /// Verbindet wartende Drains mit dem neuen Ausgang ihres Flows.
+    pub fn connect(&mut self, outputs: &[(String, Arc<AudioRingBuffer>)]) {
+        for drain in &self.drains {
+            let mut status = lock_mutex(&drain.status, "drain.connect");
+            let Some((_, output)) = outputs.iter().find(|(flow, _)| *flow == status.flow) else {
+                continue;
+            };
+            let mut source = lock_mutex(&drain.source, "drain.connect");
+            if source.is_none() {
+                // Live weiterleiten, kein Rückstau aus dem Neustart
+                output.skip_to_latest(&format!("drain:{}", status.consumer));
+                *source = Some(output.clone());
+                status.forwarding = true;
+            }
+            drop(source);
+            drain.wait.notify_all();
+        }
+    }
+
+    /// Laufende Drains; abgeschlossene werden dabei eingesammelt.
+    pub fn status(&mut self) -> Vec<DrainStatus> {
+        self.drains.retain_mut(|drain| {
+            let finished = drain
+                .handle
+                .as_ref()
+                .is_none_or(|handle| handle.is_finished());
+            if finished {
+                drain.stop();
+            }
+            !finished
+        });
+        self.drains
+            .iter()
+            .map(|drain| lock_mutex(&drain.status, "drain.list").clone())
+            .collect()
+    }
+
+    /// Beendet alle Drains sofort (Shutdown).
+    pub fn stop_all(&mut self) {
+        for drain in &mut self.drains {
+            drain.stop();
+        }
+        self.drains.clear();
+    }
+}
+
+impl Drop for DrainPool {
+    fn drop(&mut self) {
+        self.stop_all();
+    }
+}
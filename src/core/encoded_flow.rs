@@ -0,0 +1,731 @@
+// src/core/encoded_flow.rs
+//
+// Passthrough-Flows für bereits kodierte Frames: ein Encoded-Producer
+// (z. B. SRT/TS-Input) schreibt in einen EncodedRing, jeder Output liest mit
+// eigenem Reader und bekommt die Frames bit-genau weitergereicht – ohne
+// Decode/Re-Encode. Optional zählt ein Inspector Frames, Bytes und Lücken.
+//
+// Splicing: neben dem Passthrough-Input gibt es einen "processed"-Input (z. B.
+// ein PCM-Flow mit EncodedOutputConsumer für lokale Breakouts). Umgeschaltet
+// wird nur an Frame-Grenzen und nur zwischen kompatiblen Codecs, damit der
+// Bitstream für Hörer gültig bleibt.
+//
+// Mit installiertem StateStore überlebt der Ring (samt Cursor jedes Outputs,
+// Splice-Modus und letztem Codec) einen Neustart.
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::codecs::{CodecInfo, CodecKind, ContainerKind, EncodedFrame};
+use crate::core::error::{AudioError, AudioResult};
+use crate::core::lock::lock_mutex;
+use crate::core::logging::{ComponentLogger, LogContext};
+use crate::core::state_store::{self, StateStore};
+use crate::core::ProducerStatus;
+use crate::ring::{
+    EncodedFramePacket, EncodedRing, EncodedRingRead, EncodedRingReader, EncodedSink,
+};
+
+const DEFAULT_RING_CAPACITY: usize = 256;
+
+/// Quelle, die gerade in den Ring des Flows schreibt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpliceMode {
+    Passthrough,
+    Processed,
+}
+
+impl SpliceMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SpliceMode::Passthrough => "passthrough",
+            SpliceMode::Processed => "processed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "passthrough" => Some(SpliceMode::Passthrough),
+            "processed" => Some(SpliceMode::Processed),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SpliceStatus {
+    pub mode: SpliceMode,
+    /// Angeforderter Modus; weicht ab, solange der Wechsel aussteht
+    pub requested_mode: SpliceMode,
+    pub splices: u64,
+    /// Wechsel, die wegen inkompatiblem Codec oder Ogg-Container verweigert
+    /// wurden
+    pub rejected: u64,
+}
+
+struct SpliceState {
+    active: SpliceMode,
+    requested: SpliceMode,
+    last_utc_ns: Option<u64>,
+    last_info: Option<CodecInfo>,
+    splices: u64,
+    rejected: u64,
+    reject_warned: bool,
+}
+
+/// Entscheidet pro Frame, welche Quelle in den Ring schreibt.
+struct Splicer {
+    flow: String,
+    ring: EncodedRing,
+    state: Mutex<SpliceState>,
+}
+
+impl Splicer {
+    fn offer(&self, source: SpliceMode, packet: EncodedFramePacket) {
+        // Lock bleibt bis nach dem Push gehalten, damit kein Frame der alten
+        // Quelle hinter dem ersten Frame der neuen Quelle landet.
+        let mut state = lock_mutex(&self.state, "encoded_flow.splice");
+        if source != state.active {
+            if source != state.requested {
+                return;
+            }
+            if let Some(info) = state.last_info.clone() {
+                if let Some(reason) = splice_refusal(&info, &packet.frame.info) {
+                    state.rejected += 1;
+                    if !state.reject_warned {
+                        state.reject_warned = true;
+                        self.warn(&format!("Splice to {} refused: {}", source.as_str(), reason));
+                    }
+                    return;
+                }
+            }
+            // Frames, die zeitlich vor dem zuletzt gesendeten liegen, überspringen
+            if state.last_utc_ns.is_some_and(|last| packet.utc_ns <= last) {
+                return;
+            }
+            state.active = source;
+            state.splices += 1;
+            self.info(&format!("Spliced to {} at utc_ns={}", source.as_str(), packet.utc_ns));
+        }
+
+        state.last_utc_ns = Some(packet.utc_ns);
+        state.last_info = Some(packet.frame.info.clone());
+        self.ring.writer_push(packet.utc_ns, packet.frame);
+    }
+}
+
+impl ComponentLogger for Splicer {
+    fn log_context(&self) -> LogContext {
+        LogContext::new("EncodedSplicer", &self.flow)
+    }
+}
+
+fn codec_compatible(a: &CodecInfo, b: &CodecInfo) -> bool {
+    std::mem::discriminant(&a.kind) == std::mem::discriminant(&b.kind)
+        && a.sample_rate == b.sample_rate
+        && a.channels == b.channels
+        && a.container == b.container
+}
+
+/// Grund, warum von `current` nicht auf `next` gewechselt werden darf.
+/// Ogg scheidet immer aus: Die andere Quelle hat eine eigene Serial und
+/// liefert mitten im Stream keine BOS-/Header-Seiten, der Ring enthielte
+/// danach keinen gültigen Ogg-Bitstream mehr.
+fn splice_refusal(current: &CodecInfo, next: &CodecInfo) -> Option<String> {
+    if current.container == ContainerKind::Ogg || next.container == ContainerKind::Ogg {
+        return Some("Ogg bitstreams cannot be spliced".to_string());
+    }
+    if !codec_compatible(current, next) {
+        return Some(format!(
+            "codec {:?}/{} Hz/{} ch does not match {:?}/{} Hz/{} ch",
+            next.kind,
+            next.sample_rate,
+            next.channels,
+            current.kind,
+            current.sample_rate,
+            current.channels
+        ));
+    }
+    None
+}
+
+/// Sink für eine Quelle des Flows (Passthrough oder Processed).
+struct SpliceInput {
+    splicer: Arc<Splicer>,
+    source: SpliceMode,
+}
+
+impl EncodedSink for SpliceInput {
+    fn push(&self, frame: EncodedFramePacket) -> Result<()> {
+        self.splicer.offer(self.source, frame);
+        Ok(())
+    }
+}
+
+/// Producer, der bereits kodierte Frames liefert.
+pub trait EncodedProducer: Send + Sync {
+    fn name(&self) -> &str;
+    fn start(&mut self) -> Result<()>;
+    fn stop(&mut self) -> Result<()>;
+    fn status(&self) -> ProducerStatus;
+    fn attach_encoded_sink(&mut self, sink: Arc<dyn EncodedSink>);
+}
+
+/// Laufzeitwerte eines Outputs bzw. des Inspectors.
+#[derive(Debug, Default)]
+struct StreamCounters {
+    frames: AtomicU64,
+    bytes: AtomicU64,
+    gaps: AtomicU64,
+    errors: AtomicU64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EncodedOutputStatus {
+    pub name: String,
+    pub frames: u64,
+    pub bytes: u64,
+    pub gaps: u64,
+    pub errors: u64,
+}
+
+/// Ergebnis der (optionalen) Stream-Inspektion.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct StreamInspection {
+    pub frames: u64,
+    pub bytes: u64,
+    pub gaps: u64,
+    pub codec: Option<CodecInfo>,
+    /// Mittlere Bitrate seit Start (bit/s)
+    pub bitrate_bps: u64,
+    pub codec_changes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EncodedFlowStatus {
+    pub name: String,
+    pub running: bool,
+    pub producer: Option<String>,
+    pub outputs: Vec<EncodedOutputStatus>,
+    pub inspection: Option<StreamInspection>,
+    pub splice: SpliceStatus,
+}
+
+struct EncodedOutput {
+    name: String,
+    sink: Arc<dyn EncodedSink>,
+    counters: Arc<StreamCounters>,
+    /// Zuletzt weitergereichte Sequenznummer
+    cursor: Arc<AtomicU64>,
+}
+
+/// Gespeicherter Zustand neben dem Ring-Snapshot.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EncodedFlowState {
+    pub requested_mode: Option<SpliceMode>,
+    pub last_utc_ns: Option<u64>,
+    pub last_info: Option<CodecInfo>,
+    /// Output-Name → letzte weitergereichte Sequenznummer
+    #[serde(default)]
+    pub cursors: HashMap<String, u64>,
+}
+
+/// Was der Persistenz-Thread zum Speichern braucht.
+#[derive(Clone)]
+struct FlowPersistence {
+    flow: String,
+    ring: EncodedRing,
+    splicer: Arc<Splicer>,
+    cursors: Vec<(String, Arc<AtomicU64>)>,
+}
+
+impl FlowPersistence {
+    fn ring_key(&self) -> String {
+        format!("encoded-{}", self.flow)
+    }
+
+    fn meta_key(&self) -> String {
+        format!("encoded-{}-meta", self.flow)
+    }
+
+    fn save(&self, store: &StateStore) {
+        let slots = self.ring.snapshot();
+        if slots.is_empty() {
+            return;
+        }
+        let state = {
+            let splice = lock_mutex(&self.splicer.state, "encoded_flow.persist");
+            EncodedFlowState {
+                requested_mode: Some(splice.requested),
+                last_utc_ns: splice.last_utc_ns,
+                last_info: splice.last_info.clone(),
+                cursors: self
+                    .cursors
+                    .iter()
+                    .map(|(name, cursor)| (name.clone(), cursor.load(Ordering::Relaxed)))
+                    .filter(|(_, seq)| *seq > 0)
+                    .collect(),
+            }
+        };
+        let result = store
+            .save_bytes(&self.ring_key(), &state_store::encode_slots(&slots))
+            .and_then(|_| store.save_json(&self.meta_key(), &state));
+        if let Err(e) = result {
+            log::warn!("[state] encoded flow '{}': {:#}", self.flow, e);
+        }
+    }
+
+    /// Ring und Splice-Zustand wiederherstellen; liefert die Output-Cursor.
+    fn restore(&self, store: &StateStore) -> HashMap<String, u64> {
+        let Some(meta) = store.load_fresh_json::<EncodedFlowState>(&self.meta_key()) else {
+            return HashMap::new();
+        };
+        let Some(ring) = store.load_fresh_bytes(&self.ring_key()) else {
+            return HashMap::new();
+        };
+        let slots = match state_store::decode_slots(&ring.value) {
+            Ok(slots) => slots,
+            Err(e) => {
+                log::warn!("[state] encoded flow '{}': {:#}", self.flow, e);
+                return HashMap::new();
+            }
+        };
+        let restored = self.ring.restore(&slots);
+        if restored == 0 {
+            return HashMap::new();
+        }
+        let mut splice = lock_mutex(&self.splicer.state, "encoded_flow.restore");
+        if let Some(mode) = meta.value.requested_mode {
+            splice.requested = mode;
+        }
+        splice.last_utc_ns = meta.value.last_utc_ns;
+        splice.last_info = meta.value.last_info.clone();
+        drop(splice);
+        log::info!(
+            "[state] encoded flow '{}': restored {} frame(s) saved {} s ago",
+            self.flow,
+            restored,
+            meta.age.as_secs()
+        );
+        meta.value.cursors
+    }
+}
+
+pub struct EncodedFlow {
+    pub name: String,
+    ring: EncodedRing,
+    splicer: Arc<Splicer>,
+    producer: Option<Box<dyn EncodedProducer>>,
+    outputs: Vec<EncodedOutput>,
+    inspection: Option<Arc<Mutex<StreamInspection>>>,
+    running: bool,
+    stop: Arc<AtomicBool>,
+    threads: Vec<JoinHandle<()>>,
+}
+
+impl EncodedFlow {
+    pub fn new(name: &str) -> Self {
+        Self::with_capacity(name, DEFAULT_RING_CAPACITY)
+    }
+
+    pub fn with_capacity(name: &str, capacity: usize) -> Self {
+        let placeholder = EncodedFrame {
+            payload: Vec::new(),
+            info: CodecInfo {
+                kind: CodecKind::Pcm,
+                sample_rate: 0,
+                channels: 0,
+                container: ContainerKind::Raw,
+            },
+        };
+        let ring = EncodedRing::new(capacity.max(1), placeholder);
+        let splicer = Arc::new(Splicer {
+            flow: name.to_string(),
+            ring: ring.clone(),
+            state: Mutex::new(SpliceState {
+                active: SpliceMode::Passthrough,
+                requested: SpliceMode::Passthrough,
+                last_utc_ns: None,
+                last_info: None,
+                splices: 0,
+                rejected: 0,
+                reject_warned: false,
+            }),
+        });
+        Self {
+            name: name.to_string(),
+            ring,
+            splicer,
+            producer: None,
+            outputs: Vec::new(),
+            inspection: None,
+            running: false,
+            stop: Arc::new(AtomicBool::new(false)),
+            threads: Vec::new(),
+        }
+    }
+
+    /// Ring des Flows, z. B. für Watermarks oder eigene Reader.
+    pub fn ring(&self) -> &EncodedRing {
+        &self.ring
+    }
+
+    pub fn set_producer(&mut self, mut producer: Box<dyn EncodedProducer>) {
+        producer.attach_encoded_sink(Arc::new(SpliceInput {
+            splicer: self.splicer.clone(),
+            source: SpliceMode::Passthrough,
+        }));
+        self.info(&format!("Encoded producer '{}' attached", producer.name()));
+        self.producer = Some(producer);
+    }
+
+    /// Sink für die verarbeitete Quelle, z. B. als Ziel eines
+    /// `EncodedOutputConsumer` in einem PCM-Flow.
+    pub fn processed_input(&self) -> Arc<dyn EncodedSink> {
+        Arc::new(SpliceInput {
+            splicer: self.splicer.clone(),
+            source: SpliceMode::Processed,
+        })
+    }
+
+    /// Fordert einen Wechsel an; er greift am nächsten passenden Frame der
+    /// Zielquelle.
+    pub fn set_mode(&self, mode: SpliceMode) {
+        let mut state = lock_mutex(&self.splicer.state, "encoded_flow.set_mode");
+        if state.requested == mode {
+            return;
+        }
+        state.requested = mode;
+        state.reject_warned = false;
+        drop(state);
+        self.info(&format!("Splice to {} requested", mode.as_str()));
+    }
+
+    /// Aktuell aktive Quelle.
+    pub fn mode(&self) -> SpliceMode {
+        lock_mutex(&self.splicer.state, "encoded_flow.mode").active
+    }
+
+    pub fn add_output(&mut self, name: &str, sink: Arc<dyn EncodedSink>) {
+        self.outputs.push(EncodedOutput {
+            name: name.to_string(),
+            sink,
+            counters: Arc::new(StreamCounters::default()),
+            cursor: Arc::new(AtomicU64::new(0)),
+        });
+        self.info(&format!("Encoded output '{}' added", name));
+    }
+
+    /// Aktiviert die Stream-Inspektion (Codec, Bitrate, Lücken).
+    pub fn enable_inspection(&mut self) {
+        if self.inspection.is_none() {
+            self.inspection = Some(Arc::new(Mutex::new(StreamInspection::default())));
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    fn persistence(&self) -> FlowPersistence {
+        FlowPersistence {
+            flow: self.name.clone(),
+            ring: self.ring.clone(),
+            splicer: self.splicer.clone(),
+            cursors: self
+                .outputs
+                .iter()
+                .map(|output| (output.name.clone(), output.cursor.clone()))
+                .collect(),
+        }
+    }
+
+    pub fn start(&mut self) -> AudioResult<()> {
+        if self.running {
+            return Ok(());
+        }
+        self.running = true;
+        self.stop.store(false, Ordering::SeqCst);
+
+        let store = state_store::installed();
+        let cursors = store
+            .as_deref()
+            .map(|store| self.persistence().restore(store))
+            .unwrap_or_default();
+
+        for output in &self.outputs {
+            let reader = match cursors.get(&output.name) {
+                Some(seq) => self.ring.subscribe_after(*seq),
+                None => self.ring.subscribe(),
+            };
+            let stop = self.stop.clone();
+            let sink = output.sink.clone();
+            let counters = output.counters.clone();
+            let cursor = output.cursor.clone();
+            let name = format!("{}:{}", self.name, output.name);
+            self.threads.push(std::thread::spawn(move || {
+                forward_loop(reader, stop, sink, counters, cursor, &name)
+            }));
+        }
+
+        if let Some(store) = store {
+            let persistence = self.persistence();
+            let stop = self.stop.clone();
+            self.threads.push(std::thread::spawn(move || {
+                persist_loop(persistence, store, stop)
+            }));
+        }
+
+        if let Some(inspection) = &self.inspection {
+            let reader = self.ring.subscribe();
+            let stop = self.stop.clone();
+            let inspection = inspection.clone();
+            self.threads.push(std::thread::spawn(move || {
+                inspect_loop(reader, stop, inspection)
+            }));
+        }
+
+        if let Some(producer) = self.producer.as_mut() {
+            if let Err(e) = producer.start() {
+                let producer_name = producer.name().to_string();
+                self.stop()?;
+                return Err(AudioError::with_context(
+                    format!("start encoded producer '{}'", producer_name),
+                    e,
+                ));
+            }
+        }
+
+        self.info(&format!(
+            "Encoded passthrough started ({} output(s))",
+            self.outputs.len()
+        ));
+        Ok(())
+    }
+
+    pub fn stop(&mut self) -> AudioResult<()> {
+        if !self.running {
+            return Ok(());
+        }
+        self.running = false;
+        self.stop.store(true, Ordering::SeqCst);
+
+        let mut producer_error = None;
+        if let Some(producer) = self.producer.as_mut() {
+            if let Err(e) = producer.stop() {
+                producer_error = Some((producer.name().to_string(), e));
+            }
+        }
+
+        // Wartende Reader aufwecken; das Stop-Flag wird ohne Ring-Lock gesetzt,
+        // daher so lange notifizieren, bis der Thread wirklich beendet ist.
+        let notifier = self.ring.subscribe().notifier();
+        for handle in std::mem::take(&mut self.threads) {
+            while !handle.is_finished() {
+                notifier.notify_all();
+                std::thread::sleep(Duration::from_millis(5));
+            }
+            if handle.join().is_err() {
+                self.error("Encoded forward thread panicked");
+            }
+        }
+
+        if let Some(store) = state_store::installed() {
+            self.persistence().save(&store);
+        }
+
+        match producer_error {
+            Some((name, e)) => Err(AudioError::with_context(
+                format!("stop encoded producer '{}'", name),
+                e,
+            )),
+            None => {
+                self.info("Encoded passthrough stopped");
+                Ok(())
+            }
+        }
+    }
+
+    pub fn status(&self) -> EncodedFlowStatus {
+        EncodedFlowStatus {
+            name: self.name.clone(),
+            running: self.is_running(),
+            producer: self.producer.as_ref().map(|p| p.name().to_string()),
+            outputs: self
+                .outputs
+                .iter()
+                .map(|output| EncodedOutputStatus {
+                    name: output.name.clone(),
+                    frames: output.counters.frames.load(Ordering::Relaxed),
+                    bytes: output.counters.bytes.load(Ordering::Relaxed),
+                    gaps: output.counters.gaps.load(Ordering::Relaxed),
+                    errors: output.counters.errors.load(Ordering::Relaxed),
+                })
+                .collect(),
+            inspection: self
+                .inspection
+                .as_ref()
+                .map(|inspection| lock_mutex(inspection, "encoded_flow.status").clone()),
+            splice: {
+                let state = lock_mutex(&self.splicer.state, "encoded_flow.status");
+                SpliceStatus {
+                    mode: state.active,
+                    requested_mode: state.requested,
+                    splices: state.splices,
+                    rejected: state.rejected,
+                }
+            },
+        }
+    }
+}
+
+fn forward_loop(
+    mut reader: EncodedRingReader,
+    stop: Arc<AtomicBool>,
+    sink: Arc<dyn EncodedSink>,
+    counters: Arc<StreamCounters>,
+    cursor: Arc<AtomicU64>,
+    name: &str,
+) {
+    cursor.store(reader.last_seq(), Ordering::Relaxed);
+    while let Some(read) = reader.wait_for_read_or_stop(&stop) {
+        cursor.store(reader.last_seq(), Ordering::Relaxed);
+        match read {
+            EncodedRingRead::Frame { frame, utc_ns } => {
+                let bytes = frame.payload.len() as u64;
+                match sink.push(EncodedFramePacket { utc_ns, frame }) {
+                    Ok(()) => {
+                        counters.frames.fetch_add(1, Ordering::Relaxed);
+                        counters.bytes.fetch_add(bytes, Ordering::Relaxed);
+                    }
+                    Err(e) => {
+                        counters.errors.fetch_add(1, Ordering::Relaxed);
+                        log::warn!("Encoded output '{}': push error: {}", name, e);
+                    }
+                }
+            }
+            EncodedRingRead::Gap { missed } => {
+                counters.gaps.fetch_add(missed, Ordering::Relaxed);
+            }
+            EncodedRingRead::Empty => {}
+        }
+    }
+}
+
+/// Speichert alle `interval` einen Snapshot, damit auch ein Absturz das
+/// Fenster nicht verliert; der letzte folgt in `stop()`.
+fn persist_loop(persistence: FlowPersistence, store: Arc<StateStore>, stop: Arc<AtomicBool>) {
+    let step = Duration::from_millis(50);
+    let mut waited = Duration::ZERO;
+    while !stop.load(Ordering::Relaxed) {
+        std::thread::sleep(step);
+        waited += step;
+        if waited >= store.interval() {
+            waited = Duration::ZERO;
+            persistence.save(&store);
+        }
+    }
+}
+
+fn inspect_loop(
+    mut reader: EncodedRingReader,
+    stop: Arc<AtomicBool>,
+    inspection: Arc<Mutex<StreamInspection>>,
+) {
+    let started = Instant::now();
+    while let Some(read) = reader.wait_for_read_or_stop(&stop) {
+        let mut inspection = lock_mutex(&inspection, "encoded_flow.inspect");
+        match read {
+            EncodedRingRead::Frame { frame, .. } => {
+                inspection.frames += 1;
+                inspection.bytes += frame.payload.len() as u64;
+                let changed = inspection
+                    .codec
+                    .as_ref()
+                    .is_some_and(|codec| !codec_compatible(codec, &frame.info));
+                if changed {
+                    inspection.codec_changes += 1;
+                }
+                inspection.codec = Some(frame.info);
+                let secs = started.elapsed().as_secs_f64();
+                if secs > 0.0 {
+                    inspection.bitrate_bps = (inspection.bytes as f64 * 8.0 / secs) as u64;
+                }
+            }
+            EncodedRingRead::Gap { missed } => inspection.gaps += missed,
+            EncodedRingRead::Empty => {}
+        }
+    }
+}
+
+impl ComponentLogger for EncodedFlow {
+    fn log_context(&self) -> LogContext {
+        LogContext::new("EncodedFlow", &self.name)
+    }
+}
+
+impl Drop for EncodedFlow {
+    fn drop(&mut self) {
+        let _ = self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn packet(utc_ns: u64, kind: CodecKind, tag: u8) -> EncodedFramePacket {
+        EncodedFramePacket {
+            utc_ns,
+            frame: EncodedFrame {
+                payload: vec![tag],
+                info: CodecInfo {
+                    kind,
+                    sample_rate: 48_000,
+                    channels: 2,
+                    container: ContainerKind::Raw,
+                },
+            },
+        }
+    }
+
+    #[test]
+    fn splice_switches_at_frame_boundary_with_compatible_codec() {
+        let flow = EncodedFlow::new("relay");
+        let sink = Arc::new(SpliceInput {
+            splicer: flow.splicer.clone(),
+            source: SpliceMode::Passthrough,
+        });
+        let processed = flow.processed_input();
+        let mut reader = flow.ring().subscribe();
+
+        sink.push(packet(10, CodecKind::AacLc, 1)).unwrap();
+        processed.push(packet(15, CodecKind::AacLc, 2)).unwrap();
+
+        flow.set_mode(SpliceMode::Processed);
+        // Inkompatibler Codec und überlappender Frame werden nicht übernommen
+        processed.push(packet(20, CodecKind::Mp3, 3)).unwrap();
+        processed.push(packet(5, CodecKind::AacLc, 4)).unwrap();
+        assert_eq!(flow.mode(), SpliceMode::Passthrough);
+        sink.push(packet(20, CodecKind::AacLc, 5)).unwrap();
+        processed.push(packet(30, CodecKind::AacLc, 6)).unwrap();
+        sink.push(packet(30, CodecKind::AacLc, 7)).unwrap();
+        assert_eq!(flow.mode(), SpliceMode::Processed);
+
+        let mut tags = Vec::new();
+        while let EncodedRingRead::Frame { frame, .. } = reader.poll() {
+            tags.push(frame.payload[0]);
+        }
+        assert_eq!(tags, vec![1, 5, 6]);
+
+        let status = flow.status().splice;
+        assert_eq!(status.splices, 1);
+        assert_eq!(status.rejected, 1);
+    }
+}
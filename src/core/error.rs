@@ -1,8 +1,56 @@
 use std::error::Error as StdError;
+use std::sync::Mutex;
+
+use serde::Serialize;
 use thiserror::Error;
 
 pub type AudioResult<T> = Result<T, AudioError>;
 
+/// Fehlerklasse, einheitlich für Status, Events und Watchdog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCategory {
+    /// Falsche oder fehlende Konfiguration – ohne Änderung zwecklos
+    Config,
+    /// Audio-Hardware, FIFO, Gerätedatei
+    Device,
+    /// Verbindung, Socket, Gegenstelle
+    Network,
+    /// Ungültige oder nicht dekodierbare Daten
+    Codec,
+    Internal,
+}
+
+impl ErrorCategory {
+    /// Standard für das Retry-Flag: Netzwerk und Geräte erholen sich oft.
+    pub fn retryable_by_default(self) -> bool {
+        matches!(self, ErrorCategory::Device | ErrorCategory::Network)
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorCategory::Config => "config",
+            ErrorCategory::Device => "device",
+            ErrorCategory::Network => "network",
+            ErrorCategory::Codec => "codec",
+            ErrorCategory::Internal => "internal",
+        }
+    }
+
+    fn of_io(kind: std::io::ErrorKind) -> Self {
+        use std::io::ErrorKind::*;
+        match kind {
+            ConnectionRefused | ConnectionReset | ConnectionAborted | NotConnected
+            | AddrInUse | AddrNotAvailable | BrokenPipe | TimedOut | UnexpectedEof => {
+                ErrorCategory::Network
+            }
+            NotFound | PermissionDenied => ErrorCategory::Config,
+            InvalidData => ErrorCategory::Codec,
+            _ => ErrorCategory::Internal,
+        }
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum AudioError {
     #[error("{message}")]
@@ -17,9 +65,24 @@ pub enum AudioError {
     ProducerNotFound { name: String },
     #[error("flow '{name}' not found")]
     FlowNotFound { name: String },
+    #[error("flow '{flow}' interlock active: {reason}")]
+    InterlockActive { flow: String, reason: String },
+    #[error("config error: {message}")]
+    Config { message: String },
+    #[error("device error: {message}")]
+    Device { message: String, retryable: bool },
+    #[error("network error: {message}")]
+    Network { message: String, retryable: bool },
+    #[error("codec error: {message}")]
+    Codec { message: String },
+    #[error("internal error: {message}")]
+    Internal { message: String },
+    /// Kategorie und Retry-Flag werden beim Einpacken aus der Quelle übernommen
     #[error("{context}: {source}")]
     Context {
         context: String,
+        category: ErrorCategory,
+        retryable: bool,
         #[source]
         source: Box<dyn StdError + Send + Sync>,
     },
@@ -32,15 +95,168 @@ impl AudioError {
         }
     }
 
-pub fn with_context(
-    context: impl Into<String>,
-    source: impl Into<anyhow::Error>,
-) -> Self {
-    AudioError::Context {
-        context: context.into(),
-        source: source.into().into(),
+    pub fn config(message: impl Into<String>) -> Self {
+        Self::Config {
+            message: message.into(),
+        }
+    }
+
+    pub fn device(message: impl Into<String>) -> Self {
+        Self::Device {
+            message: message.into(),
+            retryable: true,
+        }
+    }
+
+    pub fn network(message: impl Into<String>) -> Self {
+        Self::Network {
+            message: message.into(),
+            retryable: true,
+        }
+    }
+
+    pub fn codec(message: impl Into<String>) -> Self {
+        Self::Codec {
+            message: message.into(),
+        }
+    }
+
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::Internal {
+            message: message.into(),
+        }
     }
+
+    /// Überschreibt das Retry-Flag (nur Device/Network/Context tragen eins).
+    pub fn with_retryable(mut self, value: bool) -> Self {
+        match &mut self {
+            Self::Device { retryable, .. }
+            | Self::Network { retryable, .. }
+            | Self::Context { retryable, .. } => *retryable = value,
+            _ => {}
+        }
+        self
+    }
+
+    pub fn with_context(context: impl Into<String>, source: impl Into<anyhow::Error>) -> Self {
+        let source = source.into();
+        let (category, retryable) = classify(&source);
+        // AudioError direkt einpacken, damit `source()` darauf zeigt
+        let source: Box<dyn StdError + Send + Sync> = match source.downcast::<AudioError>() {
+            Ok(error) => Box::new(error),
+            Err(other) => other.into(),
+        };
+        AudioError::Context {
+            context: context.into(),
+            category,
+            retryable,
+            source,
+        }
+    }
+
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            Self::BufferNotFound { .. }
+            | Self::InvalidFlowIndex { .. }
+            | Self::InvalidProducerIndex { .. }
+            | Self::ProducerNotFound { .. }
+            | Self::FlowNotFound { .. }
+            | Self::Config { .. } => ErrorCategory::Config,
+            Self::Device { .. } => ErrorCategory::Device,
+            Self::Network { .. } => ErrorCategory::Network,
+            Self::Codec { .. } => ErrorCategory::Codec,
+            Self::Message { .. } | Self::InterlockActive { .. } | Self::Internal { .. } => {
+                ErrorCategory::Internal
+            }
+            Self::Context { category, .. } => *category,
+        }
+    }
+
+    /// Ob ein erneuter Versuch ohne Eingriff Aussicht auf Erfolg hat.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Device { retryable, .. }
+            | Self::Network { retryable, .. }
+            | Self::Context { retryable, .. } => *retryable,
+            // Löst sich auf, sobald die Bedingung (Stille, Consumer) weg ist
+            Self::InterlockActive { .. } => true,
+            _ => false,
+        }
+    }
+}
+
+/// Kategorie und Retry-Flag eines anyhow-Fehlers: erster `AudioError` bzw.
+/// `io::Error` in der Kette, sonst intern und nicht wiederholbar.
+pub fn classify(error: &anyhow::Error) -> (ErrorCategory, bool) {
+    for cause in error.chain() {
+        if let Some(audio) = cause.downcast_ref::<AudioError>() {
+            return (audio.category(), audio.is_retryable());
+        }
+        if let Some(io) = cause.downcast_ref::<std::io::Error>() {
+            let category = ErrorCategory::of_io(io.kind());
+            return (category, category.retryable_by_default());
+        }
+    }
+    (ErrorCategory::Internal, false)
+}
+
+/// Serialisierbare Momentaufnahme eines Fehlers für Status und Events.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ErrorInfo {
+    pub category: ErrorCategory,
+    pub retryable: bool,
+    pub message: String,
+    pub timestamp_ms: u64,
 }
+
+impl ErrorInfo {
+    pub fn from_anyhow(error: &anyhow::Error) -> Self {
+        let (category, retryable) = classify(error);
+        Self::new(category, retryable, format!("{:#}", error))
+    }
+
+    pub fn from_audio(error: &AudioError) -> Self {
+        Self::new(error.category(), error.is_retryable(), error.to_string())
+    }
+
+    fn new(category: ErrorCategory, retryable: bool, message: String) -> Self {
+        Self {
+            category,
+            retryable,
+            message,
+            timestamp_ms: crate::core::timestamp::utc_ns_now() / 1_000_000,
+        }
+    }
+}
+
+/// Letzter Laufzeitfehler eines Moduls, geteilt mit dessen Worker-Thread.
+#[derive(Debug, Default)]
+pub struct LastError(Mutex<Option<ErrorInfo>>);
+
+impl LastError {
+    pub fn record(&self, error: &anyhow::Error) {
+        self.set(ErrorInfo::from_anyhow(error));
+    }
+
+    pub fn record_audio(&self, error: &AudioError) {
+        self.set(ErrorInfo::from_audio(error));
+    }
+
+    pub fn get(&self) -> Option<ErrorInfo> {
+        self.0.lock().ok().and_then(|guard| guard.clone())
+    }
+
+    pub fn clear(&self) {
+        if let Ok(mut guard) = self.0.lock() {
+            *guard = None;
+        }
+    }
+
+    fn set(&self, info: ErrorInfo) {
+        if let Ok(mut guard) = self.0.lock() {
+            *guard = Some(info);
+        }
+    }
 }
 
 #[derive(Debug, Error)]
@@ -1,5 +1,6 @@
 // src/core/event_bus.rs
 
+use super::event_dedup::{DedupStats, EventDeduplicator};
 use super::events::{Event, EventPriority, EventType};
 use super::lock::{lock_mutex, lock_rwlock_read, lock_rwlock_write};
 use super::logging::{ComponentLogger, LogContext};
@@ -10,8 +11,12 @@ use crossbeam_channel::{select, unbounded, Receiver, Sender};
 use std::collections::HashMap;
 use std::sync::{
     atomic::{AtomicBool, AtomicU64, Ordering},
-    Arc, RwLock,
+    Arc, Mutex, RwLock,
 };
+use std::time::{Duration, Instant};
+
+/// Wie oft der Processing-Thread abgelaufene Dedup-Fenster zusammenfasst
+const DEDUP_FLUSH_INTERVAL: Duration = Duration::from_secs(1);
 
 /// Event-Handler Trait
 pub trait EventHandler: Send + Sync {
@@ -25,6 +30,11 @@ pub trait EventHandler: Send + Sync {
     fn event_type_filter(&self) -> Option<Vec<EventType>> {
         None
     }
+
+    /// Noch nicht abgearbeitete Einträge in einer eigenen Queue des Handlers
+    fn queued(&self) -> usize {
+        0
+    }
 }
 
 /// Event-Bus
@@ -39,6 +49,9 @@ pub struct EventBus {
 
     handlers: Arc<RwLock<Vec<Arc<dyn EventHandler>>>>,
 
+    /// Fasst wiederholte Warnungen/Fehler je Komponente zusammen
+    dedup: Arc<Mutex<EventDeduplicator>>,
+
     running: Arc<AtomicBool>,
     event_count: Arc<AtomicU64>,
 
@@ -57,6 +70,7 @@ impl EventBus {
             stop_tx,
             stop_rx,
             handlers: Arc::new(RwLock::new(Vec::new())),
+            dedup: Arc::new(Mutex::new(EventDeduplicator::default())),
             running: Arc::new(AtomicBool::new(false)),
             event_count: Arc::new(AtomicU64::new(0)),
             thread_handle: None,
@@ -72,9 +86,11 @@ impl EventBus {
             return Ok(());
         }
 
+        let event_tx = self.event_tx.clone();
         let event_rx = self.event_rx.clone();
         let stop_rx = self.stop_rx.clone();
         let handlers = self.handlers.clone();
+        let dedup = self.dedup.clone();
         let running = self.running.clone();
         let event_count = self.event_count.clone();
         let name = self.name.clone();
@@ -82,9 +98,10 @@ impl EventBus {
         let handle = std::thread::spawn(move || {
             processing_loop(
                 name,
-                event_rx,
+                (event_tx, event_rx),
                 stop_rx,
                 handlers,
+                dedup,
                 running,
                 event_count,
             );
@@ -113,30 +130,33 @@ impl EventBus {
         Ok(())
     }
 
-    /// Event publizieren
+    /// Event publizieren; Wiederholungen einer Warnung/eines Fehlers im
+    /// Dedup-Fenster werden nur gezählt (siehe `core::event_dedup`).
     pub fn publish(&self, event: Event) -> Result<()> {
         self.event_count.fetch_add(1, Ordering::Relaxed);
 
-        // lokales Logging nach Priorität
-        match event.priority {
-            EventPriority::Critical | EventPriority::Error => {
-                self.error(&event.format_message());
-            }
-            EventPriority::Warning => {
-                self.warn(&event.format_message());
-            }
-            EventPriority::Info => {
-                self.info(&event.format_message());
-            }
-            EventPriority::Debug => {
-                self.debug(&event.format_message());
-            }
+        let events = lock_mutex(&self.dedup, "event_bus.publish").filter(event, Instant::now());
+        for event in events {
+            log_event(self, &event);
+            self.event_tx.send(event)?;
         }
-
-        self.event_tx.send(event)?;
         Ok(())
     }
 
+    /// Fenster für das Zusammenfassen wiederholter Events (`[events]`);
+    /// `Duration::ZERO` schaltet es ab.
+    pub fn set_dedup_window(&self, window: Duration) {
+        lock_mutex(&self.dedup, "event_bus.set_dedup_window").set_window(window);
+    }
+
+    pub fn dedup_window(&self) -> Duration {
+        lock_mutex(&self.dedup, "event_bus.dedup_window").window()
+    }
+
+    pub fn dedup_stats(&self) -> DedupStats {
+        lock_mutex(&self.dedup, "event_bus.dedup_stats").stats()
+    }
+
     /// Handler registrieren
     pub fn register_handler(&self, handler: Arc<dyn EventHandler>) -> Result<()> {
         let mut handlers = lock_rwlock_write(&self.handlers, "event_bus.register_handler");
@@ -176,6 +196,17 @@ impl EventBus {
     pub fn event_count(&self) -> u64 {
         self.event_count.load(Ordering::Relaxed)
     }
+
+    /// Publizierte, noch nicht verteilte Events
+    pub fn queued_events(&self) -> usize {
+        self.event_rx.len()
+    }
+
+    /// Summe der Handler-eigenen Queues (z. B. WebSocket-Clients)
+    pub fn handler_backlog(&self) -> usize {
+        let handlers = lock_rwlock_read(&self.handlers, "event_bus.handler_backlog");
+        handlers.iter().map(|handler| handler.queued()).sum()
+    }
 }
 
 /// ==========================
@@ -184,21 +215,36 @@ impl EventBus {
 
 fn processing_loop(
     name: String,
-    event_rx: Receiver<Event>,
+    (event_tx, event_rx): (Sender<Event>, Receiver<Event>),
     stop_rx: Receiver<()>,
     handlers: Arc<RwLock<Vec<Arc<dyn EventHandler>>>>,
+    dedup: Arc<Mutex<EventDeduplicator>>,
     running: Arc<AtomicBool>,
     event_count: Arc<AtomicU64>,
 ) {
     let logger = EventBusLogger { name };
+    let mut last_flush = Instant::now();
 
     logger.info("EventBus processing loop started");
 
     while running.load(Ordering::Relaxed) {
+        // Zusammenfassungen abgelaufener Dedup-Fenster nachreichen
+        if last_flush.elapsed() >= DEDUP_FLUSH_INTERVAL {
+            last_flush = Instant::now();
+            let summaries = lock_mutex(&dedup, "event_bus.dedup_flush").flush(last_flush);
+            for summary in summaries {
+                log_event(&logger, &summary);
+                let _ = event_tx.send(summary);
+            }
+        }
+
         select! {
             recv(stop_rx) -> _ => {
                 break;
             }
+            default(DEDUP_FLUSH_INTERVAL) => {
+                continue;
+            }
             recv(event_rx) -> msg => {
                 let event = match msg {
                     Ok(e) => e,
@@ -226,10 +272,7 @@ if let Some(min) = min_priority {
 let allowed = EventHandler::event_type_filter(&**handler);
 
 if let Some(allowed) = allowed {
-    let matches = allowed.iter().any(|t| {
-        std::mem::discriminant(t)
-            == std::mem::discriminant(&event.event_type)
-    });
+    let matches = allowed.iter().any(|t| t.matches(&event.event_type));
     if !matches {
         continue;
     }
@@ -256,6 +299,103 @@ if let Some(allowed) = allowed {
     logger.info("EventBus processing loop stopped");
 }
 
+/// Lokales Logging nach Priorität
+fn log_event(logger: &impl ComponentLogger, event: &Event) {
+    match event.priority {
+        EventPriority::Critical | EventPriority::Error => {
+            logger.error(&event.format_message());
+        }
+        EventPriority::Warning => {
+            logger.warn(&event.format_message());
+        }
+        EventPriority::Info => {
+            logger.info(&event.format_message());
+        }
+        EventPriority::Debug => {
+            logger.debug(&event.format_message());
+        }
+    }
+}
+
+/// ==========================
+/// Emitter
+/// ==========================
+
+/// Handle zum Veröffentlichen von Events auf dem Node-Bus,
+/// wird u. a. an Processors übergeben.
+#[derive(Clone)]
+pub struct EventEmitter {
+    event_bus: Arc<Mutex<EventBus>>,
+    source: String,
+    source_instance: String,
+    context: Option<serde_json::Value>,
+}
+
+impl EventEmitter {
+    pub fn new(event_bus: Arc<Mutex<EventBus>>, source: &str, source_instance: &str) -> Self {
+        Self {
+            event_bus,
+            source: source.to_string(),
+            source_instance: source_instance.to_string(),
+            context: None,
+        }
+    }
+
+    pub fn with_context(mut self, context: serde_json::Value) -> Self {
+        self.context = Some(context);
+        self
+    }
+
+    /// Kontext der Quelle, bei Processors z. B. `{"flow": "main"}`
+    pub fn context(&self) -> Option<&serde_json::Value> {
+        self.context.as_ref()
+    }
+
+    /// Gleiche Quelle, andere Instanz, z. B. für Unter-Ziele eines Consumers
+    pub fn for_instance(&self, source_instance: &str) -> Self {
+        Self {
+            source_instance: source_instance.to_string(),
+            ..self.clone()
+        }
+    }
+
+    /// Handler am Bus dieses Emitters anmelden, z. B. für Event-Auslöser
+    pub fn subscribe(&self, handler: Arc<dyn EventHandler>) -> Result<()> {
+        lock_mutex(&self.event_bus, "event_emitter.subscribe").register_handler(handler)
+    }
+
+    pub fn unsubscribe(&self, handler_name: &str) -> Result<()> {
+        lock_mutex(&self.event_bus, "event_emitter.unsubscribe").unregister_handler(handler_name)
+    }
+
+    pub fn emit(&self, event_type: EventType, priority: EventPriority, payload: serde_json::Value) {
+        let mut event = Event::new(
+            event_type,
+            priority,
+            &self.source,
+            &self.source_instance,
+            payload,
+        );
+        if let Some(context) = &self.context {
+            event = event.with_context(context.clone());
+        }
+
+        let bus = lock_mutex(&self.event_bus, "event_emitter.emit");
+        if let Err(e) = bus.publish(event) {
+            log::warn!(
+                "Failed to publish event from {} '{}': {}",
+                self.source,
+                self.source_instance,
+                e
+            );
+        }
+    }
+
+    pub fn emit_custom(&self, name: &str, priority: EventPriority, payload: serde_json::Value) {
+        self.emit(EventType::custom(name), priority, payload);
+    }
+}
+
 /// ==========================
 /// Logging
 /// ==========================
@@ -324,8 +464,9 @@ impl EventHandler for EventAuditHandler {
 
         if self.log_enabled {
             log::info!(
-                "[event_id={}][{:?}] {}",
+                "[event_id={}][corr={}][{:?}] {}",
                 event.id,
+                event.correlation_id.as_deref().unwrap_or("-"),
                 event.event_type,
                 event.payload
             );
@@ -0,0 +1,193 @@
+// src/core/event_dedup.rs
+//
+// Zusammenfassen wiederholter Fehler-Events. Eine flatternde Verbindung
+// (Icecast, SRT, …) meldet sonst alle paar Sekunden denselben Fehler und
+// Log wie Audit-Trail laufen voll. Pro (Quelle, Instanz, Fehlerart) geht
+// das erste Event durch, Wiederholungen im Fenster werden nur gezählt und
+// nach Ablauf als ein Event "repeated N times in last M s" nachgereicht.
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use super::events::{Event, EventPriority};
+
+/// Standard-Fenster (`[events] dedup_window`)
+pub const DEFAULT_DEDUP_WINDOW: Duration = Duration::from_secs(30);
+
+/// Ab dieser Priorität wird zusammengefasst, Info/Debug laufen immer durch
+pub const DEDUP_MIN_PRIORITY: EventPriority = EventPriority::Warning;
+
+/// Komponente und Fehlerart eines Events
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DedupKey {
+    pub source: String,
+    pub source_instance: String,
+    /// Event-Typ, bei Fehlern ergänzt um `error_type` bzw. `category`
+    /// aus dem Payload, z. B. "Error/connect_failed"
+    pub kind: String,
+}
+
+impl DedupKey {
+    /// `None` für Events unter `DEDUP_MIN_PRIORITY`
+    pub fn of(event: &Event) -> Option<Self> {
+        if event.priority < DEDUP_MIN_PRIORITY {
+            return None;
+        }
+        let detail = ["error_type", "category"]
+            .iter()
+            .find_map(|key| event.payload.get(*key).and_then(|value| value.as_str()));
+        let kind = match detail {
+            Some(detail) => format!("{}/{}", event.event_type_str(), detail),
+            None => event.event_type_str().to_string(),
+        };
+        Some(Self {
+            source: event.source.clone(),
+            source_instance: event.source_instance.clone(),
+            kind,
+        })
+    }
+}
+
+struct Window {
+    opened: Instant,
+    suppressed: u64,
+    /// Letztes unterdrücktes Event, Vorlage für die Zusammenfassung
+    last: Option<Event>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DedupStats {
+    /// Offene Fenster
+    pub tracked: usize,
+    /// Seit dem Start unterdrückte Events
+    pub suppressed: u64,
+    /// Nachgereichte Zusammenfassungen
+    pub summaries: u64,
+}
+
+pub struct EventDeduplicator {
+    window: Duration,
+    windows: HashMap<DedupKey, Window>,
+    suppressed: u64,
+    summaries: u64,
+}
+
+impl Default for EventDeduplicator {
+    fn default() -> Self {
+        Self::new(DEFAULT_DEDUP_WINDOW)
+    }
+}
+
+impl EventDeduplicator {
+    /// `Duration::ZERO` schaltet das Zusammenfassen ab
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            windows: HashMap::new(),
+            suppressed: 0,
+            summaries: 0,
+        }
+    }
+
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// Gilt auch für schon offene Fenster.
+    pub fn set_window(&mut self, window: Duration) {
+        self.window = window;
+    }
+
+    /// Zu veröffentlichende Events: leer, wenn `event` eine Wiederholung im
+    /// offenen Fenster ist; sonst `event`, ggf. nach der Zusammenfassung des
+    /// abgelaufenen Fensters.
+    pub fn filter(&mut self, event: Event, now: Instant) -> Vec<Event> {
+        let Some(key) = DedupKey::of(&event) else {
+            return vec![event];
+        };
+
+        let mut out = Vec::new();
+        if let Some(window) = self.windows.get_mut(&key) {
+            if now.duration_since(window.opened) < self.window {
+                window.suppressed += 1;
+                window.last = Some(event);
+                self.suppressed += 1;
+                return out;
+            }
+            if let Some(window) = self.windows.remove(&key) {
+                out.extend(self.summarize(window, now));
+            }
+        }
+
+        if !self.window.is_zero() {
+            self.windows.insert(
+                key,
+                Window {
+                    opened: now,
+                    suppressed: 0,
+                    last: None,
+                },
+            );
+        }
+        out.push(event);
+        out
+    }
+
+    /// Schließt abgelaufene Fenster; liefert die fälligen Zusammenfassungen.
+    pub fn flush(&mut self, now: Instant) -> Vec<Event> {
+        let expired: Vec<DedupKey> = self
+            .windows
+            .iter()
+            .filter(|(_, window)| now.duration_since(window.opened) >= self.window)
+            .map(|(key, _)| key.clone())
+            .collect();
+
+        let mut out = Vec::new();
+        for key in expired {
+            if let Some(window) = self.windows.remove(&key) {
+                out.extend(self.summarize(window, now));
+            }
+        }
+        out
+    }
+
+    pub fn stats(&self) -> DedupStats {
+        DedupStats {
+            tracked: self.windows.len(),
+            suppressed: self.suppressed,
+            summaries: self.summaries,
+        }
+    }
+
+    fn summarize(&mut self, window: Window, now: Instant) -> Option<Event> {
+        let last = window.last?;
+        self.summaries += 1;
+        let seconds = now.duration_since(window.opened).as_secs().max(1);
+
+        let mut payload = match last.payload {
+            serde_json::Value::Object(map) => map,
+            other => {
+                let mut map = serde_json::Map::new();
+                map.insert("payload".to_string(), other);
+                map
+            }
+        };
+        payload.insert("repeated".into(), window.suppressed.into());
+        payload.insert("window_s".into(), seconds.into());
+        payload.insert(
+            "summary".into(),
+            format!("repeated {} times in last {} s", window.suppressed, seconds).into(),
+        );
+
+        let mut summary = Event::new(
+            last.event_type,
+            last.priority,
+            &last.source,
+            &last.source_instance,
+            serde_json::Value::Object(payload),
+        );
+        summary.context = last.context;
+        summary.correlation_id = last.correlation_id;
+        summary.labels = last.labels;
+        Some(summary)
+    }
+}
@@ -1,4 +1,6 @@
 // src/core/events.rs
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 /// Event-Typen im System
@@ -8,10 +10,68 @@ pub enum EventType {
     BufferOverflow,
     ConfigChanged,
     AudioPeak,
+    OnAirChanged,
+    FlowStateChanged,
+    BufferWatermark,
+    /// Umschaltung innerhalb einer Failover-Gruppe
+    ProducerFailover,
+    /// Zeitplan ausgeführt (`[schedules.*]`)
+    ScheduleFired,
+    /// Watchdog: neu gestarteter Producer läuft wieder stabil
+    ProducerRecovered,
+    /// Processor-Kette eines Flows über bzw. wieder unter ihrem Zeitbudget
+    ProcessingOverload,
+    /// Zusammengefasste Pegel eines Flows (1-s-Statistik, 10-s-Aggregat)
+    AudioLevelStats,
+    /// Messwert eines Analyzer-Abgriffs (`config.analyzers`)
+    AnalyzerReading,
+    /// Consumer wechselt auf sein Ersatzziel (`config.backup`) oder zurück
+    ConsumerFailover,
+    /// Domänenspezifische Events, z. B. aus Processors ("agc_gain_reduction_high")
+    Custom(String),
     #[cfg(feature = "debug-events")]
     Debug(DebugEventType),
 }
 
+impl EventType {
+    pub fn custom(name: &str) -> Self {
+        EventType::Custom(name.to_string())
+    }
+
+    /// Typ zu einem Namen wie `Event::event_type_str`, z. B. "BufferWatermark";
+    /// unbekannte Namen werden zu `Custom`.
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "Error" => EventType::Error,
+            "BufferOverflow" => EventType::BufferOverflow,
+            "ConfigChanged" => EventType::ConfigChanged,
+            "AudioPeak" => EventType::AudioPeak,
+            "OnAirChanged" => EventType::OnAirChanged,
+            "FlowStateChanged" => EventType::FlowStateChanged,
+            "BufferWatermark" => EventType::BufferWatermark,
+            "ProducerFailover" => EventType::ProducerFailover,
+            "ScheduleFired" => EventType::ScheduleFired,
+            "ProducerRecovered" => EventType::ProducerRecovered,
+            "ProcessingOverload" => EventType::ProcessingOverload,
+            "AudioLevelStats" => EventType::AudioLevelStats,
+            "AnalyzerReading" => EventType::AnalyzerReading,
+            "ConsumerFailover" => EventType::ConsumerFailover,
+            other => EventType::custom(other),
+        }
+    }
+
+    /// Filter-Vergleich: gleicher Typ, bei `Custom` zusätzlich gleicher Name
+    /// (`Custom("*")` passt auf alle Custom-Events).
+    pub fn matches(&self, other: &EventType) -> bool {
+        match (self, other) {
+            (EventType::Custom(filter), EventType::Custom(name)) => {
+                filter == "*" || filter == name
+            }
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+}
+
 #[cfg(feature = "debug-events")]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum DebugEventType {
@@ -66,6 +126,10 @@ pub struct Event {
 
     /// optionale Korrelation (z. B. Request-ID)
     pub correlation_id: Option<String>,
+
+    /// `config.labels` der Quelle (Flow, Producer), z. B. `site`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub labels: BTreeMap<String, String>,
 }
 
 impl Event {
@@ -88,7 +152,8 @@ impl Event {
             source_instance: source_instance.to_string(),
             payload,
             context: None,
-            correlation_id: None,
+            correlation_id: crate::core::correlation::current_correlation_id(),
+            labels: crate::core::labels::source_labels(source, source_instance),
         }
     }
 
@@ -134,12 +199,24 @@ impl Event {
         }
     }
 
-    fn event_type_str(&self) -> &str {
+    /// Name des Event-Typs, bei `Custom` der frei gewählte Name.
+    pub fn event_type_str(&self) -> &str {
         match &self.event_type {
             EventType::Error => "Error",
             EventType::BufferOverflow => "BufferOverflow",
             EventType::ConfigChanged => "ConfigChanged",
             EventType::AudioPeak => "AudioPeak",
+            EventType::OnAirChanged => "OnAirChanged",
+            EventType::FlowStateChanged => "FlowStateChanged",
+            EventType::BufferWatermark => "BufferWatermark",
+            EventType::ProducerFailover => "ProducerFailover",
+            EventType::ScheduleFired => "ScheduleFired",
+            EventType::ProducerRecovered => "ProducerRecovered",
+            EventType::ProcessingOverload => "ProcessingOverload",
+            EventType::AudioLevelStats => "AudioLevelStats",
+            EventType::AnalyzerReading => "AnalyzerReading",
+            EventType::ConsumerFailover => "ConsumerFailover",
+            EventType::Custom(name) => name,
             #[cfg(feature = "debug-events")]
             EventType::Debug(d) => d.event_type_str(),
         }
@@ -0,0 +1,400 @@
+// src/core/failover.rs
+//
+// Failover-Gruppen: ein Flow-Input (`failover:<gruppe>`) wird aus einer
+// priorisierten Liste von Producern gespeist. Liefert der aktive Producer
+// länger als `timeout` nur Stille oder gar keine Frames, wird auf den
+// höchstpriorisierten gesunden umgeschaltet; ist ein höher priorisierter wieder
+// `recover_after` lang gesund, wird zurückgeschaltet. Jede Umschaltung
+// erzeugt ein `ProducerFailover`-Event; alle Events einer Ausfall-Sequenz
+// (Umschalten bis zur Rückkehr zum primären Producer) tragen dieselbe
+// Korrelations-ID.
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::config::{units, FailoverConfig};
+use crate::core::correlation::CorrelationScope;
+use crate::core::event_bus::EventEmitter;
+use crate::core::events::{EventPriority, EventType};
+use crate::core::lock::lock_mutex;
+use crate::core::ringbuffer::AudioRingBuffer;
+use crate::core::timestamp::utc_ns_now;
+use crate::ring::PcmFrame;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FailoverSettings {
+    /// Stille bzw. fehlende Frames, ab der ein Producer als ausgefallen gilt
+    pub timeout: Duration,
+    /// Wie lange ein höher priorisierter Producer gesund sein muss,
+    /// bevor zurückgeschaltet wird
+    pub recover_after: Duration,
+    /// Linearer Spitzenpegel, unter dem ein Frame als Stille zählt
+    pub silence_threshold: f32,
+}
+
+impl Default for FailoverSettings {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(5),
+            recover_after: Duration::from_secs(10),
+            silence_threshold: 0.001,
+        }
+    }
+}
+
+impl FailoverSettings {
+    pub fn from_config(group: &str, config: &FailoverConfig) -> anyhow::Result<Self> {
+        let defaults = Self::default();
+        let duration = |key: &str, value: &Option<String>, default: Duration| match value {
+            Some(text) => units::parse_duration(text)
+                .map_err(|e| anyhow::anyhow!("failover '{}': {} invalid: {}", group, key, e)),
+            None => Ok(default),
+        };
+        let timeout = duration("timeout", &config.timeout, defaults.timeout)?;
+        if timeout.is_zero() {
+            anyhow::bail!("failover '{}': timeout must be > 0", group);
+        }
+        Ok(Self {
+            timeout,
+            recover_after: duration("recover_after", &config.recover_after, defaults.recover_after)?,
+            silence_threshold: config
+                .silence_threshold_db
+                .map(units::db_to_linear)
+                .unwrap_or(defaults.silence_threshold),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailoverReason {
+    /// Frames kommen, aber nur Stille
+    Silence,
+    /// Keine Frames mehr
+    Disconnected,
+    /// Höher priorisierter Producer ist wieder gesund
+    Recovered,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FailoverSwitch {
+    pub group: String,
+    pub from: String,
+    pub to: String,
+    pub reason: FailoverReason,
+}
+
+#[derive(Debug, Clone, Default)]
+struct MemberHealth {
+    last_frame_ns: Option<u64>,
+    last_audio_ns: Option<u64>,
+    healthy_since_ns: Option<u64>,
+}
+
+/// Umschaltlogik einer Gruppe, ohne Threads und Buffer (Zeit wird übergeben).
+#[derive(Debug, Clone)]
+pub struct FailoverSelector {
+    group: String,
+    members: Vec<String>,
+    settings: FailoverSettings,
+    health: Vec<MemberHealth>,
+    active: usize,
+    started_ns: u64,
+    switches: u64,
+    last_switch_ns: Option<u64>,
+}
+
+impl FailoverSelector {
+    pub fn new(group: &str, members: Vec<String>, settings: FailoverSettings, now_ns: u64) -> Self {
+        Self {
+            group: group.to_string(),
+            health: vec![MemberHealth::default(); members.len()],
+            members,
+            settings,
+            active: 0,
+            started_ns: now_ns,
+            switches: 0,
+            last_switch_ns: None,
+        }
+    }
+
+    pub fn active(&self) -> &str {
+        &self.members[self.active]
+    }
+
+    pub fn active_index(&self) -> usize {
+        self.active
+    }
+
+    /// Verbucht einen Frame von Mitglied `member`.
+    pub fn record(&mut self, member: usize, frame: &PcmFrame, now_ns: u64) {
+        let Some(health) = self.health.get_mut(member) else {
+            return;
+        };
+        health.last_frame_ns = Some(now_ns);
+        let threshold = self.settings.silence_threshold;
+        if frame
+            .samples
+            .iter()
+            .any(|sample| (*sample as f32).abs() / 32768.0 >= threshold)
+        {
+            health.last_audio_ns = Some(now_ns);
+        }
+    }
+
+    /// Gesund = hörbares Audio innerhalb von `timeout`. Direkt nach dem Start
+    /// gilt jedes Mitglied bis zum Ablauf von `timeout` als gesund.
+    pub fn is_healthy(&self, member: usize, now_ns: u64) -> bool {
+        let last = self.health[member].last_audio_ns.unwrap_or(self.started_ns);
+        now_ns.saturating_sub(last) < self.settings.timeout.as_nanos() as u64
+    }
+
+    fn is_receiving(&self, member: usize, now_ns: u64) -> bool {
+        let last = self.health[member].last_frame_ns.unwrap_or(self.started_ns);
+        now_ns.saturating_sub(last) < self.settings.timeout.as_nanos() as u64
+    }
+
+    /// Prüft den Zustand aller Mitglieder und schaltet ggf. um.
+    pub fn evaluate(&mut self, now_ns: u64) -> Option<FailoverSwitch> {
+        for member in 0..self.members.len() {
+            let healthy = self.is_healthy(member, now_ns);
+            let since = &mut self.health[member].healthy_since_ns;
+            if healthy {
+                since.get_or_insert(now_ns);
+            } else {
+                *since = None;
+            }
+        }
+
+        let target = if !self.is_healthy(self.active, now_ns) {
+            let reason = if self.is_receiving(self.active, now_ns) {
+                FailoverReason::Silence
+            } else {
+                FailoverReason::Disconnected
+            };
+            (0..self.members.len())
+                .find(|&member| member != self.active && self.is_healthy(member, now_ns))
+                .map(|member| (member, reason))
+        } else {
+            let recover_ns = self.settings.recover_after.as_nanos() as u64;
+            (0..self.active)
+                .find(|&member| {
+                    self.health[member]
+                        .healthy_since_ns
+                        .is_some_and(|since| now_ns.saturating_sub(since) >= recover_ns)
+                })
+                .map(|member| (member, FailoverReason::Recovered))
+        };
+
+        let (to, reason) = target?;
+        let switch = FailoverSwitch {
+            group: self.group.clone(),
+            from: self.members[self.active].clone(),
+            to: self.members[to].clone(),
+            reason,
+        };
+        self.active = to;
+        self.switches += 1;
+        self.last_switch_ns = Some(now_ns);
+        Some(switch)
+    }
+
+    pub fn status(&self, now_ns: u64) -> FailoverStatus {
+        FailoverStatus {
+            name: self.group.clone(),
+            active: self.active().to_string(),
+            switches: self.switches,
+            last_switch_ms: self.last_switch_ns.map(|ns| ns / 1_000_000),
+            members: self
+                .members
+                .iter()
+                .enumerate()
+                .map(|(index, name)| FailoverMemberStatus {
+                    name: name.clone(),
+                    healthy: self.is_healthy(index, now_ns),
+                    receiving: self.is_receiving(index, now_ns),
+                    last_audio_ms: self.health[index].last_audio_ns.map(|ns| ns / 1_000_000),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FailoverMemberStatus {
+    pub name: String,
+    pub healthy: bool,
+    /// Frames innerhalb von `timeout` (unabhängig vom Pegel)
+    pub receiving: bool,
+    pub last_audio_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FailoverStatus {
+    pub name: String,
+    pub active: String,
+    pub switches: u64,
+    pub last_switch_ms: Option<u64>,
+    pub members: Vec<FailoverMemberStatus>,
+}
+
+/// Gruppe mit Thread: liest alle Mitglieds-Buffer und reicht die Frames des
+/// aktiven Producers an den Gruppen-Buffer weiter.
+pub struct FailoverGroup {
+    name: String,
+    members: Vec<(String, Arc<AudioRingBuffer>)>,
+    output: Arc<AudioRingBuffer>,
+    settings: FailoverSettings,
+    selector: Arc<Mutex<FailoverSelector>>,
+    emitter: Option<EventEmitter>,
+    running: Arc<AtomicBool>,
+    thread_handle: Option<JoinHandle<()>>,
+}
+
+impl FailoverGroup {
+    pub fn new(
+        name: &str,
+        members: Vec<(String, Arc<AudioRingBuffer>)>,
+        output: Arc<AudioRingBuffer>,
+        settings: FailoverSettings,
+    ) -> Self {
+        let names = members.iter().map(|(member, _)| member.clone()).collect();
+        Self {
+            name: name.to_string(),
+            members,
+            output,
+            settings,
+            selector: Arc::new(Mutex::new(FailoverSelector::new(
+                name,
+                names,
+                settings,
+                utc_ns_now(),
+            ))),
+            emitter: None,
+            running: Arc::new(AtomicBool::new(false)),
+            thread_handle: None,
+        }
+    }
+
+    pub fn with_emitter(mut self, emitter: EventEmitter) -> Self {
+        self.emitter = Some(emitter);
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn members(&self) -> Vec<String> {
+        self.members.iter().map(|(name, _)| name.clone()).collect()
+    }
+
+    pub fn status(&self) -> FailoverStatus {
+        lock_mutex(&self.selector, "failover.status").status(utc_ns_now())
+    }
+
+    pub fn start(&mut self) {
+        if self.running.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        // Frischer Start: wieder beim primären Producer beginnen
+        *lock_mutex(&self.selector, "failover.start") = FailoverSelector::new(
+            &self.name,
+            self.members(),
+            self.settings,
+            utc_ns_now(),
+        );
+
+        let members = self.members.clone();
+        let output = self.output.clone();
+        let selector = self.selector.clone();
+        let emitter = self.emitter.clone();
+        let running = self.running.clone();
+        let reader_id = format!("failover:{}", self.name);
+        let primary = self.members.first().map(|(name, _)| name.clone());
+
+        self.thread_handle = Some(std::thread::spawn(move || {
+            // Offen vom ersten Umschalten bis zur Rückkehr zum primären Producer
+            let mut sequence: Option<CorrelationScope> = None;
+            while running.load(Ordering::Relaxed) {
+                let now = utc_ns_now();
+                let mut selector = lock_mutex(&selector, "failover.loop");
+                let active = selector.active_index();
+                for (index, (_, buffer)) in members.iter().enumerate() {
+                    while let Some(frame) = buffer.pop_for_reader(&reader_id) {
+                        selector.record(index, &frame, now);
+                        if index == active {
+                            output.push(frame);
+                        }
+                    }
+                }
+                let switch = selector.evaluate(now);
+                drop(selector);
+
+                if let Some(switch) = switch {
+                    if sequence.is_none() {
+                        sequence = Some(CorrelationScope::begin("failover"));
+                    }
+                    publish_switch(emitter.as_ref(), &switch);
+                    if primary.as_deref() == Some(switch.to.as_str()) {
+                        sequence = None;
+                    }
+                }
+                std::thread::sleep(POLL_INTERVAL);
+            }
+        }));
+        log::info!(
+            "Failover group '{}' started ({})",
+            self.name,
+            self.members().join(" > ")
+        );
+    }
+
+    pub fn stop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+        if let Some(handle) = self.thread_handle.take() {
+            if handle.join().is_err() {
+                log::error!("Failover group '{}': thread panicked", self.name);
+            }
+        }
+    }
+}
+
+impl Drop for FailoverGroup {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+fn publish_switch(emitter: Option<&EventEmitter>, switch: &FailoverSwitch) {
+    let priority = match switch.reason {
+        FailoverReason::Recovered => EventPriority::Info,
+        FailoverReason::Silence | FailoverReason::Disconnected => EventPriority::Warning,
+    };
+    log::warn!(
+        "Failover '{}': switching '{}' -> '{}' ({:?})",
+        switch.group,
+        switch.from,
+        switch.to,
+        switch.reason
+    );
+    if let Some(emitter) = emitter {
+        emitter.emit(
+            EventType::ProducerFailover,
+            priority,
+            serde_json::json!({
+                "group": switch.group,
+                "from": switch.from,
+                "to": switch.to,
+                "reason": switch.reason,
+                "timestamp": utc_ns_now(),
+            }),
+        );
+    }
+}
@@ -0,0 +1,337 @@
+// src/core/file_rotation.rs
+//
+// Segmentierte Aufnahmen für den FileConsumer: neue Datei nach Laufzeit
+// (an der Uhr der Zeitzone ausgerichtet, z. B. jede volle Stunde) oder nach
+// Größe. Dateinamen kommen aus einer strftime-Vorlage wie
+// `/archive/%Y/%m/%d/%H%M.wav`. Fertige Segmente gehen an `SegmentHook`s;
+// `Retention` löscht damit alte Segmente, die zur Vorlage passen.
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use anyhow::bail;
+use serde_json::Value;
+
+use crate::audio::archive;
+use crate::config::ConfigValues;
+use crate::core::timezone::TimeZone;
+
+/// Kürzeste Segmentlänge
+pub const MIN_ROTATE_EVERY: Duration = Duration::from_secs(10);
+/// Kleinste Segmentgröße
+pub const MIN_ROTATE_SIZE: u64 = 1024 * 1024;
+/// Endung, unter der ein Segment geschrieben wird, bis es fertig ist
+pub const PART_SUFFIX: &str = ".part";
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Wann ein Segment endet; ohne beides schreibt der Consumer eine Datei.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FileRotation {
+    pub every: Option<Duration>,
+    pub max_bytes: Option<u64>,
+}
+
+/// Aufbewahrung alter Segmente; ohne beides wird nichts gelöscht.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Retention {
+    template: String,
+    pub max_files: Option<usize>,
+    pub max_age: Option<Duration>,
+}
+
+/// Ein abgeschlossenes, umbenanntes Segment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FinishedSegment {
+    pub consumer: String,
+    pub path: PathBuf,
+    pub started_at_ms: u64,
+    pub duration_ms: u64,
+    pub bytes: u64,
+}
+
+/// Wird nach jedem fertigen Segment aufgerufen (eigener Thread, nicht im
+/// Schreib-Thread), z. B. für Aufbewahrung oder Upload.
+pub trait SegmentHook: Send + Sync {
+    fn segment_finished(&self, segment: &FinishedSegment) -> anyhow::Result<()>;
+}
+
+/// Enthält die Vorlage einen Zeitcode, der sich von Segment zu Segment ändert?
+pub fn has_time_code(template: &str) -> bool {
+    template.contains(super::consumer::file_writer::TIME_PLACEHOLDER)
+        || ["%Y", "%y", "%m", "%d", "%H", "%M", "%S", "%j", "%s"]
+            .iter()
+            .any(|code| template.contains(code))
+}
+
+/// Gelesene `config`-Schlüssel (Strict-Modus, siehe `config::options`)
+pub const CONFIG_KEYS: &[&str] = &[
+    "rotate_every",
+    "rotate_size",
+    "retention_files",
+    "retention_age",
+];
+
+impl FileRotation {
+    /// `config.rotate_every` ("1h") und `config.rotate_size` ("500MB").
+    pub fn from_config(
+        consumer: &str,
+        template: &str,
+        config: &HashMap<String, Value>,
+    ) -> anyhow::Result<Option<Self>> {
+        let values = ConfigValues::new("consumer", consumer, config);
+        let rotation = Self {
+            every: values.duration("rotate_every")?,
+            max_bytes: values.size("rotate_size")?,
+        };
+        if rotation.every.is_none() && rotation.max_bytes.is_none() {
+            return Ok(None);
+        }
+        if let Some(every) = rotation.every {
+            values.check_range(
+                "rotate_every",
+                every.as_secs(),
+                MIN_ROTATE_EVERY.as_secs(),
+                SECONDS_PER_DAY as u64,
+            )?;
+        }
+        if let Some(bytes) = rotation.max_bytes {
+            // WAV-Header zählen Bytes in 32 Bit
+            values.check_range("rotate_size", bytes, MIN_ROTATE_SIZE, u32::MAX as u64)?;
+        }
+        if !has_time_code(template) {
+            bail!(
+                "consumer '{}': rotation needs a time code in the path (e.g. %Y%m%d-%H%M or {{time}})",
+                consumer
+            );
+        }
+        Ok(Some(rotation))
+    }
+
+    /// Ende des Segments, das bei `started_ms` beginnt: nächste Grenze der
+    /// Ortszeit, die ein Vielfaches von `every` ist (stündlich = volle
+    /// Stunde, täglich = Mitternacht).
+    pub fn deadline_ms(&self, started_ms: u64, timezone: &TimeZone) -> Option<u64> {
+        let period = self.every?.as_secs().max(1) as i64;
+        if period == SECONDS_PER_DAY {
+            return Some(timezone.next_local_midnight(started_ms));
+        }
+        if period == 3600 {
+            return Some(timezone.next_top_of_hour(started_ms));
+        }
+        let utc_s = (started_ms / 1000) as i64;
+        let offset = i64::from(timezone.offset_at(utc_s));
+        let local = utc_s + offset;
+        let boundary = local - local.rem_euclid(period) + period;
+        Some(((boundary - offset) * 1000) as u64)
+    }
+
+    pub fn due(&self, deadline_ms: Option<u64>, now_ms: u64, bytes: u64) -> bool {
+        deadline_ms.is_some_and(|deadline| now_ms >= deadline)
+            || self.max_bytes.is_some_and(|max| bytes >= max)
+    }
+}
+
+impl Retention {
+    /// `config.retention_files` (Anzahl) und `config.retention_age` ("30d").
+    pub fn from_config(
+        consumer: &str,
+        template: &str,
+        config: &HashMap<String, Value>,
+    ) -> anyhow::Result<Option<Self>> {
+        let values = ConfigValues::new("consumer", consumer, config);
+        let max_files = values.f64("retention_files")?;
+        if let Some(files) = max_files {
+            values.check_range("retention_files", files, 1.0, 1_000_000.0)?;
+        }
+        let retention = Self {
+            template: template.to_string(),
+            max_files: max_files.map(|files| files as usize),
+            max_age: values.duration("retention_age")?,
+        };
+        if retention.max_files.is_none() && retention.max_age.is_none() {
+            return Ok(None);
+        }
+        if !has_time_code(template) {
+            bail!(
+                "consumer '{}': retention needs a time code in the path to tell segments apart",
+                consumer
+            );
+        }
+        Ok(Some(retention))
+    }
+
+    pub fn new(template: &str, max_files: Option<usize>, max_age: Option<Duration>) -> Self {
+        Self {
+            template: template.to_string(),
+            max_files,
+            max_age,
+        }
+    }
+
+    /// Alle fertigen Segmente der Vorlage, älteste zuerst (nach Änderungszeit).
+    pub fn segments(&self) -> Vec<(PathBuf, SystemTime)> {
+        let (root, pattern) = split_template(&self.template);
+        let mut found = Vec::new();
+        collect_matching(&root, &pattern, &mut found);
+        let mut segments: Vec<(PathBuf, SystemTime)> = found
+            .into_iter()
+            .filter_map(|path| {
+                let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+                Some((path, modified))
+            })
+            .collect();
+        segments.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+        segments
+    }
+
+    /// Löscht überzählige und zu alte Segmente samt Manifest-Einträgen und
+    /// leer gewordenen Verzeichnissen. Liefert die gelöschten Pfade.
+    pub fn apply(&self, now: SystemTime) -> Vec<PathBuf> {
+        let segments = self.segments();
+        let excess = self
+            .max_files
+            .map_or(0, |max| segments.len().saturating_sub(max));
+        let (root, _) = split_template(&self.template);
+        let mut removed = Vec::new();
+        for (index, (path, modified)) in segments.iter().enumerate() {
+            let expired = self.max_age.is_some_and(|max_age| {
+                now.duration_since(*modified).unwrap_or_default() > max_age
+            });
+            if index >= excess && !expired {
+                continue;
+            }
+            if let Err(e) = fs::remove_file(path) {
+                log::warn!("[retention] cannot remove {}: {}", path.display(), e);
+                continue;
+            }
+            if let Err(e) = archive::forget_file(path) {
+                log::warn!("[retention] manifest update for {} failed: {:#}", path.display(), e);
+            }
+            remove_empty_dirs(path, &root);
+            removed.push(path.clone());
+        }
+        removed
+    }
+}
+
+impl SegmentHook for Retention {
+    fn segment_finished(&self, segment: &FinishedSegment) -> anyhow::Result<()> {
+        for path in self.apply(SystemTime::now()) {
+            log::info!("[retention] '{}' removed {}", segment.consumer, path.display());
+        }
+        Ok(())
+    }
+}
+
+/// Fester Teil der Vorlage (bis zum ersten Zeitcode) und die Komponenten danach.
+fn split_template(template: &str) -> (PathBuf, Vec<String>) {
+    let mut root = PathBuf::new();
+    let mut pattern = Vec::new();
+    for component in Path::new(template).components() {
+        let text = component.as_os_str().to_string_lossy().to_string();
+        let variable = matches!(component, Component::Normal(_))
+            && (text.contains('%') || text.contains('{'));
+        if pattern.is_empty() && !variable {
+            root.push(component.as_os_str());
+        } else {
+            pattern.push(text);
+        }
+    }
+    if root.as_os_str().is_empty() {
+        root.push(".");
+    }
+    // Ohne Zeitcode ist der letzte Teil der Dateiname
+    if pattern.is_empty() {
+        if let Some(file) = root.file_name().map(|f| f.to_string_lossy().to_string()) {
+            pattern.push(file);
+            root.pop();
+        }
+    }
+    (root, pattern)
+}
+
+fn collect_matching(dir: &Path, pattern: &[String], found: &mut Vec<PathBuf>) {
+    let Some((first, rest)) = pattern.split_first() else {
+        return;
+    };
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !component_matches(first, &name) {
+            continue;
+        }
+        let path = entry.path();
+        match (rest.is_empty(), path.is_dir()) {
+            (true, false) => found.push(path),
+            (false, true) => collect_matching(&path, rest, found),
+            _ => {}
+        }
+    }
+}
+
+/// Vergleicht einen Namen mit einer Vorlagen-Komponente: Zeitcodes stehen für
+/// Ziffern fester Breite, `{time}` für beliebigen Text.
+pub fn component_matches(pattern: &str, name: &str) -> bool {
+    fn matches(pattern: &[char], name: &[char]) -> bool {
+        let Some((&first, rest)) = pattern.split_first() else {
+            return name.is_empty();
+        };
+        if first == '{' {
+            let placeholder: Vec<char> = "{time}".chars().collect();
+            if pattern.starts_with(&placeholder) {
+                let rest = &pattern[placeholder.len()..];
+                return (0..=name.len()).any(|skip| matches(rest, &name[skip..]));
+            }
+        }
+        if first == '%' {
+            if let Some((&code, rest)) = rest.split_first() {
+                let digits = |width: usize| {
+                    name.len() >= width
+                        && name[..width].iter().all(|c| c.is_ascii_digit())
+                        && matches(rest, &name[width..])
+                };
+                return match code {
+                    'Y' => digits(4),
+                    'y' | 'm' | 'd' | 'H' | 'M' | 'S' => digits(2),
+                    'j' => digits(3),
+                    'z' => {
+                        name.len() >= 5
+                            && matches!(name[0], '+' | '-')
+                            && name[1..5].iter().all(|c| c.is_ascii_digit())
+                            && matches(rest, &name[5..])
+                    }
+                    's' => (1..=name.len()).any(|width| {
+                        name[..width].iter().all(|c| c.is_ascii_digit()) && matches(rest, &name[width..])
+                    }),
+                    '%' => name.first() == Some(&'%') && matches(rest, &name[1..]),
+                    other => {
+                        name.len() >= 2
+                            && name[0] == '%'
+                            && name[1] == other
+                            && matches(rest, &name[2..])
+                    }
+                };
+            }
+        }
+        name.first() == Some(&first) && matches(rest, &name[1..])
+    }
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    matches(&pattern, &name)
+}
+
+fn remove_empty_dirs(file: &Path, root: &Path) {
+    let mut dir = file.parent();
+    while let Some(current) = dir {
+        if current == root || !current.starts_with(root) {
+            break;
+        }
+        if fs::remove_dir(current).is_err() {
+            break;
+        }
+        dir = current.parent();
+    }
+}
@@ -0,0 +1,474 @@
+// src/core/host_telemetry.rs
+//
+// Zustand des Rechners neben der Audio-Pipeline (`[telemetry]`): Load,
+// CPU-Auslastung pro Kern, Temperaturen aus hwmon und Belegung der
+// Dateisysteme, auf denen aufgenommen wird, optional SMART-Health per
+// `smartctl`. Ein Hintergrund-Thread tastet im Takt `interval` ab; /metrics
+// und der Status lesen nur den letzten Stand, ein Scrape kostet also keine
+// Plattenzugriffe. Quellen sind /proc und /sys (Linux), anderswo bleiben die
+// Felder leer.
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::audio::archive;
+use crate::core::lock::lock_mutex;
+use crate::core::timestamp::utc_ns_now;
+use crate::producers::wait::StopWait;
+
+pub const DEFAULT_TELEMETRY_INTERVAL: Duration = Duration::from_secs(5);
+/// SMART seltener abfragen; `smartctl` kostet Zeit und weckt schlafende Platten
+pub const DEFAULT_SMART_INTERVAL: Duration = Duration::from_secs(600);
+const HWMON_ROOT: &str = "/sys/class/hwmon";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TelemetrySettings {
+    pub interval: Duration,
+    /// Zusätzlich überwachte Pfade; Aufnahme-Verzeichnisse kommen automatisch
+    /// aus dem Archiv-Register dazu
+    pub paths: Vec<PathBuf>,
+    /// SMART-Health der Aufnahme-Platten (braucht `smartctl` und Rechte)
+    pub smart: bool,
+    pub smart_interval: Duration,
+}
+
+impl Default for TelemetrySettings {
+    fn default() -> Self {
+        Self {
+            interval: DEFAULT_TELEMETRY_INTERVAL,
+            paths: Vec::new(),
+            smart: false,
+            smart_interval: DEFAULT_SMART_INTERVAL,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct HostSnapshot {
+    pub sampled_at_ms: u64,
+    /// 1, 5 und 15 Minuten
+    pub load_average: Option<[f64; 3]>,
+    /// Auslastung aller Kerne seit der vorigen Abtastung (0..1)
+    pub cpu_usage_ratio: Option<f64>,
+    pub cores: Vec<CoreUsage>,
+    pub temperatures: Vec<Temperature>,
+    pub disks: Vec<DiskStatus>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CoreUsage {
+    pub core: usize,
+    pub usage_ratio: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Temperature {
+    /// hwmon-Name, z. B. `coretemp`, `nvme`, `cpu_thermal`
+    pub chip: String,
+    /// `tempN_label` oder `tempN`
+    pub sensor: String,
+    pub celsius: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DiskStatus {
+    pub mount_point: String,
+    pub device: String,
+    /// Überwachte Pfade auf diesem Dateisystem
+    pub paths: Vec<String>,
+    pub total_bytes: u64,
+    pub available_bytes: u64,
+    pub used_ratio: f64,
+    pub smart: Option<SmartHealth>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SmartHealth {
+    pub passed: bool,
+    pub temperature_celsius: Option<f64>,
+    pub power_on_hours: Option<u64>,
+    /// ATA-Attribut 5 bzw. NVMe `media_errors`
+    pub reallocated_sectors: Option<u64>,
+    pub checked_at_ms: u64,
+}
+
+/// `/proc/loadavg`: "0.52 0.58 0.59 1/467 12345"
+pub fn parse_loadavg(text: &str) -> Option<[f64; 3]> {
+    let mut fields = text.split_whitespace().map(|field| field.parse().ok());
+    Some([fields.next()??, fields.next()??, fields.next()??])
+}
+
+/// Jiffies einer `cpu`-Zeile aus `/proc/stat`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CpuTimes {
+    pub busy: u64,
+    pub total: u64,
+}
+
+impl CpuTimes {
+    /// Anteil Arbeit zwischen `earlier` und `self`
+    pub fn usage_since(&self, earlier: &CpuTimes) -> Option<f64> {
+        let total = self.total.checked_sub(earlier.total)?;
+        let busy = self.busy.checked_sub(earlier.busy)?;
+        (total > 0).then(|| (busy as f64 / total as f64).clamp(0.0, 1.0))
+    }
+}
+
+/// `cpu`-Zeilen aus `/proc/stat`: Summe (`None`) und Kerne (`Some(n)`).
+/// idle und iowait zählen als frei.
+pub fn parse_proc_stat(text: &str) -> Vec<(Option<usize>, CpuTimes)> {
+    text.lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let name = fields.next()?.strip_prefix("cpu")?;
+            let core = if name.is_empty() {
+                None
+            } else {
+                Some(name.parse().ok()?)
+            };
+            // user nice system idle iowait irq softirq steal (guest zählt in user)
+            let values: Vec<u64> = fields.take(8).filter_map(|v| v.parse().ok()).collect();
+            if values.len() < 4 {
+                return None;
+            }
+            let total: u64 = values.iter().sum();
+            let idle = values[3] + values.get(4).copied().unwrap_or(0);
+            Some((
+                core,
+                CpuTimes {
+                    busy: total - idle,
+                    total,
+                },
+            ))
+        })
+        .collect()
+}
+
+/// Temperaturen aller hwmon-Chips unter `root` (`/sys/class/hwmon`).
+pub fn read_hwmon(root: &Path) -> Vec<Temperature> {
+    let Ok(entries) = std::fs::read_dir(root) else {
+        return Vec::new();
+    };
+    let mut chips: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .collect();
+    chips.sort();
+    let mut temperatures = Vec::new();
+    for chip_dir in chips {
+        let chip = std::fs::read_to_string(chip_dir.join("name"))
+            .map(|name| name.trim().to_string())
+            .unwrap_or_else(|_| {
+                chip_dir
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .to_string()
+            });
+        let Ok(files) = std::fs::read_dir(&chip_dir) else {
+            continue;
+        };
+        let mut inputs: Vec<String> = files
+            .filter_map(|file| file.ok())
+            .map(|file| file.file_name().to_string_lossy().to_string())
+            .filter(|name| name.starts_with("temp") && name.ends_with("_input"))
+            .collect();
+        inputs.sort();
+        for input in inputs {
+            let Some(millidegrees) = std::fs::read_to_string(chip_dir.join(&input))
+                .ok()
+                .and_then(|value| value.trim().parse::<i64>().ok())
+            else {
+                continue;
+            };
+            let base = input.trim_end_matches("_input");
+            let sensor = std::fs::read_to_string(chip_dir.join(format!("{}_label", base)))
+                .map(|label| label.trim().to_string())
+                .unwrap_or_else(|_| base.to_string());
+            temperatures.push(Temperature {
+                chip: chip.clone(),
+                sensor,
+                celsius: millidegrees as f64 / 1000.0,
+            });
+        }
+    }
+    temperatures
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mount {
+    pub mount_point: PathBuf,
+    pub device: String,
+}
+
+/// `/proc/self/mountinfo`: Feld 5 ist der Mountpoint, das Feld nach " - "
+/// plus eins die Quelle. Leerzeichen stehen dort als `\040`.
+pub fn parse_mountinfo(text: &str) -> Vec<Mount> {
+    text.lines()
+        .filter_map(|line| {
+            let (left, right) = line.split_once(" - ")?;
+            let mount_point = left.split_whitespace().nth(4)?;
+            let device = right.split_whitespace().nth(1)?;
+            Some(Mount {
+                mount_point: PathBuf::from(mount_point.replace("\\040", " ")),
+                device: device.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Dateisystem mit dem längsten passenden Mountpoint
+pub fn mount_for<'a>(path: &Path, mounts: &'a [Mount]) -> Option<&'a Mount> {
+    mounts
+        .iter()
+        .filter(|mount| path.starts_with(&mount.mount_point))
+        .max_by_key(|mount| mount.mount_point.components().count())
+}
+
+/// Ausgabe von `smartctl --json -H -A <device>`
+pub fn parse_smartctl(json: &str, checked_at_ms: u64) -> Option<SmartHealth> {
+    let value: serde_json::Value = serde_json::from_str(json).ok()?;
+    let passed = value.pointer("/smart_status/passed")?.as_bool()?;
+    let reallocated = value
+        .pointer("/ata_smart_attributes/table")
+        .and_then(|table| table.as_array())
+        .and_then(|table| {
+            table
+                .iter()
+                .find(|attribute| attribute.get("id").and_then(|id| id.as_u64()) == Some(5))
+        })
+        .and_then(|attribute| attribute.pointer("/raw/value"))
+        .or_else(|| value.pointer("/nvme_smart_health_information_log/media_errors"))
+        .and_then(|raw| raw.as_u64());
+    Some(SmartHealth {
+        passed,
+        temperature_celsius: value
+            .pointer("/temperature/current")
+            .and_then(|t| t.as_f64()),
+        power_on_hours: value
+            .pointer("/power_on_time/hours")
+            .and_then(|h| h.as_u64()),
+        reallocated_sectors: reallocated,
+        checked_at_ms,
+    })
+}
+
+fn query_smart(device: &str, now_ms: u64) -> Option<SmartHealth> {
+    let output = Command::new("smartctl")
+        .args(["--json", "-H", "-A", device])
+        .output()
+        .ok()?;
+    // Exit-Code ist eine Bitmaske, auch bei gültigem JSON oft ≠ 0
+    parse_smartctl(&String::from_utf8_lossy(&output.stdout), now_ms)
+}
+
+fn filesystem_size(path: &Path) -> Option<(u64, u64)> {
+    let stats = nix::sys::statvfs::statvfs(path).ok()?;
+    let fragment = stats.fragment_size() as u64;
+    Some((
+        stats.blocks() as u64 * fragment,
+        stats.blocks_available() as u64 * fragment,
+    ))
+}
+
+/// Nächster existierender Vorfahre (Aufnahme-Verzeichnisse entstehen erst
+/// mit der ersten Datei).
+fn existing_ancestor(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .find(|ancestor| !ancestor.as_os_str().is_empty() && ancestor.exists())
+        .and_then(|ancestor| ancestor.canonicalize().ok())
+}
+
+/// Hält die vorige CPU-Abtastung und den SMART-Cache.
+pub struct HostSampler {
+    settings: TelemetrySettings,
+    previous: Vec<(Option<usize>, CpuTimes)>,
+    smart: HashMap<String, SmartHealth>,
+    last_smart: Option<Instant>,
+}
+
+impl HostSampler {
+    pub fn new(settings: TelemetrySettings) -> Self {
+        Self {
+            settings,
+            previous: Vec::new(),
+            smart: HashMap::new(),
+            last_smart: None,
+        }
+    }
+
+    pub fn sample(&mut self) -> HostSnapshot {
+        let now_ms = utc_ns_now() / 1_000_000;
+        let mut snapshot = HostSnapshot {
+            sampled_at_ms: now_ms,
+            load_average: std::fs::read_to_string("/proc/loadavg")
+                .ok()
+                .and_then(|text| parse_loadavg(&text)),
+            temperatures: read_hwmon(Path::new(HWMON_ROOT)),
+            ..Default::default()
+        };
+
+        let current = std::fs::read_to_string("/proc/stat")
+            .map(|text| parse_proc_stat(&text))
+            .unwrap_or_default();
+        for (core, times) in &current {
+            let Some(usage) = self
+                .previous
+                .iter()
+                .find(|(previous_core, _)| previous_core == core)
+                .and_then(|(_, previous)| times.usage_since(previous))
+            else {
+                continue;
+            };
+            match core {
+                None => snapshot.cpu_usage_ratio = Some(usage),
+                Some(core) => snapshot.cores.push(CoreUsage {
+                    core: *core,
+                    usage_ratio: usage,
+                }),
+            }
+        }
+        self.previous = current;
+
+        snapshot.disks = self.disks(now_ms);
+        snapshot
+    }
+
+    fn disks(&mut self, now_ms: u64) -> Vec<DiskStatus> {
+        let mounts = std::fs::read_to_string("/proc/self/mountinfo")
+            .map(|text| parse_mountinfo(&text))
+            .unwrap_or_default();
+        let mut paths = self.settings.paths.clone();
+        paths.extend(archive::archive_registry().dirs());
+
+        let mut disks: Vec<DiskStatus> = Vec::new();
+        for path in paths {
+            let Some(existing) = existing_ancestor(&path) else {
+                continue;
+            };
+            let (mount_point, device) = match mount_for(&existing, &mounts) {
+                Some(mount) => (mount.mount_point.clone(), mount.device.clone()),
+                None => (existing.clone(), String::new()),
+            };
+            let label = path.display().to_string();
+            if let Some(disk) = disks
+                .iter_mut()
+                .find(|disk| Path::new(&disk.mount_point) == mount_point)
+            {
+                if !disk.paths.contains(&label) {
+                    disk.paths.push(label);
+                }
+                continue;
+            }
+            let Some((total_bytes, available_bytes)) = filesystem_size(&existing) else {
+                continue;
+            };
+            disks.push(DiskStatus {
+                mount_point: mount_point.display().to_string(),
+                device,
+                paths: vec![label],
+                total_bytes,
+                available_bytes,
+                used_ratio: if total_bytes > 0 {
+                    1.0 - available_bytes as f64 / total_bytes as f64
+                } else {
+                    0.0
+                },
+                smart: None,
+            });
+        }
+
+        if self.settings.smart {
+            let due = self
+                .last_smart
+                .is_none_or(|last| last.elapsed() >= self.settings.smart_interval);
+            if due {
+                self.last_smart = Some(Instant::now());
+                for disk in &disks {
+                    if !disk.device.starts_with("/dev/") {
+                        continue;
+                    }
+                    match query_smart(&disk.device, now_ms) {
+                        Some(health) => {
+                            self.smart.insert(disk.device.clone(), health);
+                        }
+                        None => log::debug!("[telemetry] no SMART data for {}", disk.device),
+                    }
+                }
+            }
+            for disk in &mut disks {
+                disk.smart = self.smart.get(&disk.device).cloned();
+            }
+        }
+        disks
+    }
+}
+
+struct Telemetry {
+    latest: Arc<Mutex<Option<HostSnapshot>>>,
+    running: Arc<std::sync::atomic::AtomicBool>,
+    wait: Arc<StopWait>,
+    handle: Option<std::thread::JoinHandle<()>>,
+}
+
+static TELEMETRY: OnceLock<Mutex<Option<Telemetry>>> = OnceLock::new();
+
+fn telemetry_slot() -> &'static Mutex<Option<Telemetry>> {
+    TELEMETRY.get_or_init(|| Mutex::new(None))
+}
+
+/// Startet (oder ersetzt) den Abtast-Thread des Prozesses.
+pub fn install(settings: TelemetrySettings) {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    uninstall();
+    let latest = Arc::new(Mutex::new(None));
+    let running = Arc::new(AtomicBool::new(true));
+    let wait = Arc::new(StopWait::new());
+    let handle = {
+        let latest = latest.clone();
+        let running = running.clone();
+        let wait = wait.clone();
+        let interval = settings.interval;
+        std::thread::spawn(move || {
+            let mut sampler = HostSampler::new(settings);
+            while running.load(Ordering::Relaxed) {
+                let snapshot = sampler.sample();
+                *lock_mutex(&latest, "telemetry.sample") = Some(snapshot);
+                wait.wait_timeout(interval);
+            }
+        })
+    };
+    *lock_mutex(telemetry_slot(), "telemetry.install") = Some(Telemetry {
+        latest,
+        running,
+        wait,
+        handle: Some(handle),
+    });
+}
+
+/// Beendet den Abtast-Thread; `latest` liefert danach `None`.
+pub fn uninstall() {
+    let previous = lock_mutex(telemetry_slot(), "telemetry.uninstall").take();
+    if let Some(mut telemetry) = previous {
+        telemetry
+            .running
+            .store(false, std::sync::atomic::Ordering::SeqCst);
+        telemetry.wait.notify_all();
+        if let Some(handle) = telemetry.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Letzte Abtastung; `None` ohne `[telemetry]`-Thread oder vor der ersten.
+pub fn latest() -> Option<HostSnapshot> {
+    let slot = lock_mutex(telemetry_slot(), "telemetry.latest");
+    let telemetry = slot.as_ref()?;
+    let latest = lock_mutex(&telemetry.latest, "telemetry.latest");
+    latest.clone()
+}
@@ -0,0 +1,482 @@
+// src/core/http_client.rs
+//
+// Gemeinsamer HTTP/1.0-Client für ausgehende Anfragen (Format-Probe,
+// Webhooks, …): einheitliche Connect-/Read-Timeouts, Wiederholungen mit
+// Backoff, User-Agent und optionaler HTTP-Proxy. Pro Ziel (`host:port`)
+// werden Anfragen, Fehler und Wiederholungen gezählt und unter `/metrics`
+// exportiert.
+//
+// Wiederholt wird nur, was gefahrlos geht: ein fehlgeschlagener
+// Verbindungsaufbau immer, bei GET zusätzlich Lesefehler vor der Antwort und
+// 502/503/504.
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::Serialize;
+
+use crate::api::ws::base64_encode;
+use crate::core::lock::lock_mutex;
+
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+pub const DEFAULT_READ_TIMEOUT: Duration = Duration::from_secs(10);
+pub const DEFAULT_RETRIES: u32 = 2;
+pub const DEFAULT_RETRY_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_HEADER_BYTES: usize = 16 * 1024;
+
+pub fn default_user_agent() -> String {
+    format!("airlift-node/{}", env!("CARGO_PKG_VERSION"))
+}
+
+/// HTTP-Proxy (`http://[user:pass@]host[:port]`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpProxy {
+    pub host: String,
+    pub port: u16,
+    /// Fertiger Wert für `Proxy-Authorization`
+    pub authorization: Option<String>,
+}
+
+impl HttpProxy {
+    pub fn parse(url: &str) -> Result<Self> {
+        let rest = url.strip_prefix("http://").unwrap_or(url);
+        let rest = rest.trim_end_matches('/');
+        let (credentials, authority) = match rest.rsplit_once('@') {
+            Some((credentials, authority)) => (Some(credentials), authority),
+            None => (None, rest),
+        };
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => (
+                host,
+                port.parse::<u16>()
+                    .map_err(|_| anyhow!("invalid proxy port in '{}'", url))?,
+            ),
+            None => (authority, 8080),
+        };
+        if host.is_empty() || host.contains('/') {
+            bail!("invalid proxy '{}'", url);
+        }
+        Ok(Self {
+            host: host.to_string(),
+            port,
+            authorization: credentials
+                .map(|credentials| format!("Basic {}", base64_encode(credentials.as_bytes()))),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpSettings {
+    pub connect_timeout: Duration,
+    pub read_timeout: Duration,
+    /// Zusätzliche Versuche nach dem ersten
+    pub retries: u32,
+    /// Wartezeit vor dem ersten Wiederholen, danach verdoppelt
+    pub retry_backoff: Duration,
+    pub user_agent: String,
+    pub proxy: Option<HttpProxy>,
+    /// Hosts (oder Domain-Suffixe wie `.lan`) ohne Proxy
+    pub no_proxy: Vec<String>,
+}
+
+impl Default for HttpSettings {
+    fn default() -> Self {
+        Self {
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            read_timeout: DEFAULT_READ_TIMEOUT,
+            retries: DEFAULT_RETRIES,
+            retry_backoff: DEFAULT_RETRY_BACKOFF,
+            user_agent: default_user_agent(),
+            proxy: None,
+            no_proxy: Vec::new(),
+        }
+    }
+}
+
+impl HttpSettings {
+    /// Proxy aus `http_proxy`/`HTTP_PROXY` und `no_proxy`/`NO_PROXY`, falls
+    /// keiner konfiguriert ist.
+    pub fn with_env_proxy(mut self) -> Self {
+        if self.proxy.is_some() {
+            return self;
+        }
+        let env = |names: [&str; 2]| names.iter().find_map(|name| std::env::var(name).ok());
+        if let Some(url) = env(["http_proxy", "HTTP_PROXY"]).filter(|url| !url.is_empty()) {
+            match HttpProxy::parse(&url) {
+                Ok(proxy) => self.proxy = Some(proxy),
+                Err(e) => log::warn!("[http] ignoring http_proxy: {:#}", e),
+            }
+        }
+        if let Some(list) = env(["no_proxy", "NO_PROXY"]) {
+            self.no_proxy.extend(
+                list.split(',')
+                    .map(|entry| entry.trim().to_string())
+                    .filter(|entry| !entry.is_empty()),
+            );
+        }
+        self
+    }
+
+    fn proxy_for(&self, host: &str) -> Option<&HttpProxy> {
+        let bypass = self.no_proxy.iter().any(|entry| {
+            entry == "*"
+                || entry.eq_ignore_ascii_case(host)
+                || (entry.starts_with('.') && host.to_ascii_lowercase().ends_with(&entry.to_ascii_lowercase()))
+        });
+        if bypass {
+            None
+        } else {
+            self.proxy.as_ref()
+        }
+    }
+}
+
+static SETTINGS: OnceLock<Mutex<HttpSettings>> = OnceLock::new();
+
+fn settings_slot() -> &'static Mutex<HttpSettings> {
+    SETTINGS.get_or_init(|| Mutex::new(HttpSettings::default()))
+}
+
+/// Einstellungen des Prozesses (aus `[http_client]`).
+pub fn install(settings: HttpSettings) {
+    *lock_mutex(settings_slot(), "http_client.install") = settings;
+}
+
+pub fn settings() -> HttpSettings {
+    lock_mutex(settings_slot(), "http_client.settings").clone()
+}
+
+/// Zähler pro Ziel für `/metrics`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct DestinationStats {
+    /// `host:port`
+    pub destination: String,
+    /// Versuche inklusive Wiederholungen
+    pub requests: u64,
+    /// Verbindungs-/Lesefehler und Antworten ab 400
+    pub errors: u64,
+    pub retries: u64,
+    pub last_status: Option<u16>,
+    pub last_error: Option<String>,
+    pub last_duration_ms: Option<u64>,
+}
+
+static STATS: OnceLock<Mutex<BTreeMap<String, DestinationStats>>> = OnceLock::new();
+
+fn stats_slot() -> &'static Mutex<BTreeMap<String, DestinationStats>> {
+    STATS.get_or_init(|| Mutex::new(BTreeMap::new()))
+}
+
+fn record(destination: &str, update: impl FnOnce(&mut DestinationStats)) {
+    let mut stats = lock_mutex(stats_slot(), "http_client.record");
+    let entry = stats
+        .entry(destination.to_string())
+        .or_insert_with(|| DestinationStats {
+            destination: destination.to_string(),
+            ..Default::default()
+        });
+    update(entry);
+}
+
+pub fn destination_stats() -> Vec<DestinationStats> {
+    lock_mutex(stats_slot(), "http_client.destination_stats")
+        .values()
+        .cloned()
+        .collect()
+}
+
+/// `http://host[:port]/path` (auch `icy://`) in Host, Port und Pfad.
+pub fn parse_http_url(url: &str) -> Result<(String, u16, String)> {
+    let rest = match url.split_once("://") {
+        Some(("http", rest)) | Some(("icy", rest)) => rest,
+        Some((scheme, _)) => bail!("unsupported URL scheme '{}' (use http)", scheme),
+        None => bail!("invalid URL '{}'", url),
+    };
+    let (authority, path) = match rest.find('/') {
+        Some(idx) => (&rest[..idx], &rest[idx..]),
+        None => (rest, "/"),
+    };
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((host, port)) => (
+            host,
+            port.parse::<u16>()
+                .map_err(|_| anyhow!("invalid port in '{}'", url))?,
+        ),
+        None => (authority, 80),
+    };
+    if host.is_empty() {
+        bail!("missing host in '{}'", url);
+    }
+    Ok((host.to_string(), port, path.to_string()))
+}
+
+#[derive(Debug, Clone)]
+pub struct HttpRequest {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+    /// Ersetzt Connect- und Read-Timeout der Einstellungen
+    pub timeout: Option<Duration>,
+}
+
+impl HttpRequest {
+    pub fn get(url: &str) -> Self {
+        Self {
+            method: "GET".to_string(),
+            url: url.to_string(),
+            headers: Vec::new(),
+            body: Vec::new(),
+            timeout: None,
+        }
+    }
+
+    pub fn post(url: &str, content_type: &str, body: Vec<u8>) -> Self {
+        Self {
+            method: "POST".to_string(),
+            body,
+            ..Self::get(url)
+        }
+        .header("Content-Type", content_type)
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    fn idempotent(&self) -> bool {
+        matches!(self.method.as_str(), "GET" | "HEAD")
+    }
+}
+
+/// Antwort mit gelesenem Kopf; der Body wird über `Read` gestreamt.
+pub struct HttpResponse {
+    pub status: u16,
+    pub status_line: String,
+    /// Header-Namen klein geschrieben
+    pub headers: Vec<(String, String)>,
+    buffered: Vec<u8>,
+    stream: TcpStream,
+}
+
+impl HttpResponse {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Fehler bei Status ab 400.
+    pub fn error_for_status(self) -> Result<Self> {
+        if self.status >= 400 {
+            bail!("http status {}", self.status_line);
+        }
+        Ok(self)
+    }
+}
+
+impl Read for HttpResponse {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if !self.buffered.is_empty() {
+            let n = buf.len().min(self.buffered.len());
+            buf[..n].copy_from_slice(&self.buffered[..n]);
+            self.buffered.drain(..n);
+            return Ok(n);
+        }
+        self.stream.read(buf)
+    }
+}
+
+/// Fehler eines Versuchs; `sent` = Anfrage ging schon raus.
+struct AttemptError {
+    error: anyhow::Error,
+    sent: bool,
+}
+
+pub struct HttpClient {
+    settings: HttpSettings,
+}
+
+impl HttpClient {
+    pub fn new(settings: HttpSettings) -> Self {
+        Self { settings }
+    }
+
+    pub fn settings(&self) -> &HttpSettings {
+        &self.settings
+    }
+
+    pub fn send(&self, request: &HttpRequest) -> Result<HttpResponse> {
+        let (host, port, _) = parse_http_url(&request.url)?;
+        let destination = format!("{}:{}", host, port);
+        let mut backoff = self.settings.retry_backoff;
+        let mut attempt = 0;
+        loop {
+            let started = Instant::now();
+            let result = self.attempt(request, &host, port);
+            let elapsed = started.elapsed().as_millis() as u64;
+            let retryable = match &result {
+                Ok(response) => {
+                    request.idempotent() && matches!(response.status, 502..=504)
+                }
+                Err(e) => !e.sent || request.idempotent(),
+            };
+            record(&destination, |stats| {
+                stats.requests += 1;
+                stats.last_duration_ms = Some(elapsed);
+                match &result {
+                    Ok(response) => {
+                        stats.last_status = Some(response.status);
+                        if response.status >= 400 {
+                            stats.errors += 1;
+                            stats.last_error = Some(response.status_line.clone());
+                        }
+                    }
+                    Err(e) => {
+                        stats.errors += 1;
+                        stats.last_status = None;
+                        stats.last_error = Some(format!("{:#}", e.error));
+                    }
+                }
+            });
+
+            if !retryable || attempt >= self.settings.retries {
+                return result.map_err(|e| e.error);
+            }
+            attempt += 1;
+            record(&destination, |stats| stats.retries += 1);
+            match &result {
+                Ok(response) => log::debug!(
+                    "[http] {} answered {}, retry {}/{} in {:?}",
+                    destination,
+                    response.status,
+                    attempt,
+                    self.settings.retries,
+                    backoff
+                ),
+                Err(e) => log::debug!(
+                    "[http] {}: {:#}, retry {}/{} in {:?}",
+                    destination,
+                    e.error,
+                    attempt,
+                    self.settings.retries,
+                    backoff
+                ),
+            }
+            std::thread::sleep(backoff);
+            backoff = backoff.saturating_mul(2);
+        }
+    }
+
+    fn attempt(&self, request: &HttpRequest, host: &str, port: u16) -> Result<HttpResponse, AttemptError> {
+        let not_sent = |error: anyhow::Error| AttemptError { error, sent: false };
+        let sent = |error: anyhow::Error| AttemptError { error, sent: true };
+        let connect_timeout = request.timeout.unwrap_or(self.settings.connect_timeout);
+        let read_timeout = request.timeout.unwrap_or(self.settings.read_timeout);
+        let proxy = self.settings.proxy_for(host);
+
+        let (connect_host, connect_port) = match proxy {
+            Some(proxy) => (proxy.host.as_str(), proxy.port),
+            None => (host, port),
+        };
+        let addr = (connect_host, connect_port)
+            .to_socket_addrs()
+            .with_context(|| format!("failed to resolve {}", connect_host))
+            .map_err(not_sent)?
+            .next()
+            .ok_or_else(|| not_sent(anyhow!("no address for {}", connect_host)))?;
+        let mut stream = TcpStream::connect_timeout(&addr, connect_timeout)
+            .with_context(|| format!("failed to connect to {}", addr))
+            .map_err(not_sent)?;
+        stream
+            .set_read_timeout(Some(read_timeout))
+            .and_then(|_| stream.set_write_timeout(Some(read_timeout)))
+            .map_err(|e| not_sent(e.into()))?;
+
+        let (_, _, path) = parse_http_url(&request.url).map_err(not_sent)?;
+        // Über einen Proxy steht die absolute URL in der Request-Zeile
+        let target = match proxy {
+            Some(_) if port == 80 => format!("http://{}{}", host, path),
+            Some(_) => format!("http://{}:{}{}", host, port, path),
+            None => path,
+        };
+        let host_header = if port == 80 {
+            host.to_string()
+        } else {
+            format!("{}:{}", host, port)
+        };
+        let mut head = format!(
+            "{} {} HTTP/1.0\r\nHost: {}\r\nUser-Agent: {}\r\n",
+            request.method, target, host_header, self.settings.user_agent
+        );
+        if let Some(authorization) = proxy.and_then(|proxy| proxy.authorization.as_ref()) {
+            head.push_str(&format!("Proxy-Authorization: {}\r\n", authorization));
+        }
+        for (name, value) in &request.headers {
+            head.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        if !request.body.is_empty() || request.method == "POST" {
+            head.push_str(&format!("Content-Length: {}\r\n", request.body.len()));
+        }
+        head.push_str("Connection: close\r\n\r\n");
+        stream
+            .write_all(head.as_bytes())
+            .and_then(|_| stream.write_all(&request.body))
+            .context("failed to send request")
+            .map_err(sent)?;
+
+        let mut data = Vec::new();
+        let mut chunk = [0u8; 8192];
+        let header_end = loop {
+            if let Some(pos) = data.windows(4).position(|window| window == b"\r\n\r\n") {
+                break pos;
+            }
+            if data.len() > MAX_HEADER_BYTES {
+                return Err(sent(anyhow!("response header too large")));
+            }
+            let n = stream
+                .read(&mut chunk)
+                .context("failed to read response header")
+                .map_err(sent)?;
+            if n == 0 {
+                return Err(sent(anyhow!("connection closed before response header")));
+            }
+            data.extend_from_slice(&chunk[..n]);
+        };
+
+        let header = String::from_utf8_lossy(&data[..header_end]).to_string();
+        let mut lines = header.lines();
+        let status_line = lines.next().unwrap_or_default().to_string();
+        let status: u16 = status_line
+            .split_whitespace()
+            .nth(1)
+            .and_then(|code| code.parse().ok())
+            .ok_or_else(|| sent(anyhow!("invalid status line '{}'", status_line)))?;
+        let headers = lines
+            .filter_map(|line| line.split_once(':'))
+            .map(|(key, value)| (key.trim().to_ascii_lowercase(), value.trim().to_string()))
+            .collect();
+        Ok(HttpResponse {
+            status,
+            status_line,
+            headers,
+            buffered: data.split_off(header_end + 4),
+            stream,
+        })
+    }
+}
+
+/// Anfrage mit den installierten Einstellungen.
+pub fn send(request: &HttpRequest) -> Result<HttpResponse> {
+    HttpClient::new(settings()).send(request)
+}
@@ -0,0 +1,165 @@
+// src/core/idle.rs
+//
+// Adaptives Warten für Schleifen, die auf Audio pollen. Solange Frames
+// kommen, bleibt die Wartezeit kurz; bleibt der Buffer länger leer, wächst
+// sie bis `MAX_IDLE` und spart auf Edge-Geräten mit Akku CPU-Weckungen.
+// Wer lange wartet, lässt sich vom Buffer beim nächsten Push wecken, damit
+// der erste Frame nach einer Pause nicht auf das lange Intervall wartet.
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant};
+
+use crate::core::lock::lock_mutex;
+use crate::core::ringbuffer::AudioRingBuffer;
+use crate::producers::wait::StopWait;
+
+/// Obergrenze der Wartezeit bei anhaltendem Leerlauf
+pub const MAX_IDLE: Duration = Duration::from_millis(250);
+/// So lange bleibt es nach dem letzten Frame beim kurzen Intervall
+/// (mehrere Frame-Abstände, damit normale Lücken nicht bremsen)
+pub const IDLE_GRACE: Duration = Duration::from_millis(200);
+
+#[derive(Debug, Clone)]
+pub struct IdleBackoff {
+    min: Duration,
+    max: Duration,
+    grace: Duration,
+    current: Duration,
+    idle_since: Option<Instant>,
+    /// Für `sleep_for`, beim ersten Gebrauch angelegt
+    waker: Option<Arc<StopWait>>,
+}
+
+impl IdleBackoff {
+    /// Kurzes Intervall `min`, danach Verdopplung bis `MAX_IDLE`.
+    pub fn new(min: Duration) -> Self {
+        Self {
+            min,
+            max: MAX_IDLE.max(min),
+            grace: IDLE_GRACE,
+            current: min,
+            idle_since: None,
+            waker: None,
+        }
+    }
+
+    pub fn with_max(mut self, max: Duration) -> Self {
+        self.max = max.max(self.min);
+        self
+    }
+
+    pub fn with_grace(mut self, grace: Duration) -> Self {
+        self.grace = grace;
+        self
+    }
+
+    /// Nichts zu tun: liefert die nächste Wartezeit.
+    pub fn next_wait(&mut self) -> Duration {
+        let since = *self.idle_since.get_or_insert_with(Instant::now);
+        if since.elapsed() < self.grace {
+            return self.min;
+        }
+        let wait = self.current;
+        self.current = (self.current * 2).min(self.max);
+        wait
+    }
+
+    /// Arbeit erledigt: zurück zum kurzen Intervall.
+    pub fn reset(&mut self) {
+        self.current = self.min;
+        self.idle_since = None;
+    }
+
+    /// Wartet auf den nächsten Frame in `buffer`, höchstens die nächste
+    /// Wartezeit; `wait.notify_all()` (Stop) beendet das Warten ebenfalls.
+    /// Registriert wird nur bei langen Wartezeiten, damit Pausen zwischen
+    /// Frames nichts extra kosten.
+    pub fn wait_for(&mut self, buffer: &AudioRingBuffer, wait: &Arc<StopWait>) {
+        self.wait_on(&[buffer], wait);
+    }
+
+    /// Wartet die nächste Wartezeit auf `wait` (ohne Buffer, z. B. solange
+    /// niemand zuhört).
+    pub fn wait(&mut self, wait: &StopWait) {
+        wait.wait_timeout(self.next_wait());
+    }
+
+    /// Wie `wait_for` für Schleifen ohne eigenes `StopWait`.
+    pub fn sleep_for(&mut self, buffer: &AudioRingBuffer) {
+        self.sleep_for_any(&[buffer]);
+    }
+
+    /// Wie `sleep_for`, geweckt vom ersten Push in einen der Buffer (Flows
+    /// mit mehreren Eingängen).
+    pub fn sleep_for_any(&mut self, buffers: &[&AudioRingBuffer]) {
+        let waker = self.waker.get_or_insert_with(|| Arc::new(StopWait::new())).clone();
+        self.wait_on(buffers, &waker);
+    }
+
+    fn wait_on(&mut self, buffers: &[&AudioRingBuffer], wait: &Arc<StopWait>) {
+        let timeout = self.next_wait();
+        if timeout <= self.min {
+            wait.wait_timeout(timeout);
+            return;
+        }
+        for buffer in buffers {
+            buffer.add_waker(wait);
+        }
+        wait.wait_timeout(timeout);
+        for buffer in buffers {
+            buffer.remove_waker(wait);
+        }
+    }
+
+    /// Ohne Weck-Möglichkeit schlafen (Schleifen ohne Buffer).
+    pub fn sleep(&mut self) {
+        std::thread::sleep(self.next_wait());
+    }
+
+    pub fn is_backed_off(&self) -> bool {
+        self.current > self.min
+    }
+}
+
+/// Warter eines Buffers; `notify` nach jedem Push, nur mit Registrierungen
+/// kostet es mehr als ein atomares Lesen.
+#[derive(Debug, Default)]
+pub struct PushWakers {
+    wakers: Mutex<Vec<Weak<StopWait>>>,
+    active: AtomicBool,
+}
+
+impl PushWakers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&self, waker: &Arc<StopWait>) {
+        let mut wakers = lock_mutex(&self.wakers, "idle.wakers.add");
+        if !wakers.iter().any(|known| known.as_ptr() == Arc::as_ptr(waker)) {
+            wakers.push(Arc::downgrade(waker));
+        }
+        self.active.store(true, Ordering::Release);
+    }
+
+    pub fn remove(&self, waker: &Arc<StopWait>) {
+        let mut wakers = lock_mutex(&self.wakers, "idle.wakers.remove");
+        wakers.retain(|known| known.as_ptr() != Arc::as_ptr(waker) && known.strong_count() > 0);
+        self.active.store(!wakers.is_empty(), Ordering::Release);
+    }
+
+    pub fn notify(&self) {
+        if !self.active.load(Ordering::Acquire) {
+            return;
+        }
+        let mut wakers = lock_mutex(&self.wakers, "idle.wakers.notify");
+        wakers.retain(|known| match known.upgrade() {
+            Some(waker) => {
+                waker.notify_all();
+                true
+            }
+            None => false,
+        });
+        self.active.store(!wakers.is_empty(), Ordering::Release);
+    }
+}
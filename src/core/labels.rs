@@ -0,0 +1,167 @@
+// src/core/labels.rs
+//
+// Frei wählbare Labels an Flows und Producern (`config.labels`, z. B.
+// `{ site = "studio-b", transmitter = "north" }`). Sie erscheinen als
+// zusätzliche Prometheus-Labels in `/metrics`, unter `labels` in Events der
+// jeweiligen Quelle und als Influx-Tags, damit Fleet-Dashboards nach Standort
+// filtern können, ohne Namenskonventionen zu parsen.
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Mutex, OnceLock};
+
+use anyhow::{anyhow, bail, Result};
+use serde_json::Value;
+
+use crate::core::lock::lock_mutex;
+
+pub type Labels = BTreeMap<String, String>;
+
+/// Von Airlift selbst vergebene Label- bzw. Tag-Namen
+pub const RESERVED_LABELS: &[&str] = &[
+    "flow",
+    "producer",
+    "consumer",
+    "processor",
+    "buffer",
+    "channel",
+    "destination",
+    "source",
+    "priority",
+    "le",
+    "quantile",
+];
+const MAX_LABELS: usize = 16;
+const MAX_VALUE_LEN: usize = 128;
+
+/// `config.labels` eines Moduls; Werte dürfen Strings, Zahlen oder Bools sein.
+pub fn parse_labels(
+    module_kind: &str,
+    module_name: &str,
+    config: &HashMap<String, Value>,
+) -> Result<Labels> {
+    let Some(value) = config.get("labels") else {
+        return Ok(Labels::new());
+    };
+    let entries = value.as_object().ok_or_else(|| {
+        anyhow!(
+            "{} '{}': config.labels must be a table of key = \"value\"",
+            module_kind,
+            module_name
+        )
+    })?;
+    if entries.len() > MAX_LABELS {
+        bail!(
+            "{} '{}': config.labels allows at most {} entries",
+            module_kind,
+            module_name,
+            MAX_LABELS
+        );
+    }
+
+    let mut labels = Labels::new();
+    for (key, value) in entries {
+        if !is_label_name(key) {
+            bail!(
+                "{} '{}': label '{}' must match [a-zA-Z_][a-zA-Z0-9_]* and not start with '__'",
+                module_kind,
+                module_name,
+                key
+            );
+        }
+        if RESERVED_LABELS.contains(&key.as_str()) {
+            bail!(
+                "{} '{}': label '{}' is reserved",
+                module_kind,
+                module_name,
+                key
+            );
+        }
+        let value = match value {
+            Value::String(text) => text.trim().to_string(),
+            Value::Number(number) => number.to_string(),
+            Value::Bool(flag) => flag.to_string(),
+            other => bail!(
+                "{} '{}': label '{}' must be a string, got {}",
+                module_kind,
+                module_name,
+                key,
+                other
+            ),
+        };
+        if value.is_empty() || value.len() > MAX_VALUE_LEN {
+            bail!(
+                "{} '{}': label '{}' must have 1..={} characters",
+                module_kind,
+                module_name,
+                key,
+                MAX_VALUE_LEN
+            );
+        }
+        labels.insert(key.clone(), value);
+    }
+    Ok(labels)
+}
+
+fn is_label_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !name.starts_with("__")
+}
+
+/// Labels aller Flows und Producer einer Konfiguration.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LabelSet {
+    pub flows: HashMap<String, Labels>,
+    pub producers: HashMap<String, Labels>,
+}
+
+static LABELS: OnceLock<Mutex<LabelSet>> = OnceLock::new();
+
+fn labels_slot() -> &'static Mutex<LabelSet> {
+    LABELS.get_or_init(|| Mutex::new(LabelSet::default()))
+}
+
+/// Ersetzt die Labels des Prozesses (beim Aufbau des Nodes aus der Config).
+pub fn install(labels: LabelSet) {
+    *lock_mutex(labels_slot(), "labels.install") = labels;
+}
+
+pub fn flow_labels(flow: &str) -> Labels {
+    lock_mutex(labels_slot(), "labels.flow")
+        .flows
+        .get(flow)
+        .cloned()
+        .unwrap_or_default()
+}
+
+pub fn producer_labels(producer: &str) -> Labels {
+    lock_mutex(labels_slot(), "labels.producer")
+        .producers
+        .get(producer)
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Buffer erben die Labels ihres Producers (`producer:<name>`) bzw. Flows
+/// (`flow:<name>:output`).
+pub fn buffer_labels(buffer: &str) -> Labels {
+    if let Some(producer) = buffer.strip_prefix("producer:") {
+        return producer_labels(producer);
+    }
+    match buffer.strip_prefix("flow:").and_then(|rest| rest.rsplit_once(':')) {
+        Some((flow, _)) => flow_labels(flow),
+        None => Labels::new(),
+    }
+}
+
+/// Labels einer Event-Quelle (`flow`, `producer`, `buffer`); sonst leer.
+pub fn source_labels(source: &str, instance: &str) -> Labels {
+    match source {
+        "flow" => flow_labels(instance),
+        "producer" => producer_labels(instance),
+        "buffer" => buffer_labels(instance),
+        _ => Labels::new(),
+    }
+}
@@ -1,7 +1,10 @@
 // src/core/logging.rs - Vereinfachte Version
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::OnceLock;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use log::LevelFilter;
+
 // Globale Sequenznummer für Korrelation
 static LOG_SEQUENCE: AtomicU64 = AtomicU64::new(0);
 
@@ -83,13 +86,77 @@ pub trait ComponentLogger {
 
         let buffer_info = format!(
             "buffer[addr={:?}] frames={}/{} dropped={}",
-            buffer as *const _, stats.current_frames, stats.capacity, stats.dropped_frames
+            buffer as *const _, stats.current_frames.get(), stats.capacity.get(), stats.dropped_frames
         );
 
         log::debug!("{}", ctx.format("TRACE", &buffer_info));
     }
 }
 
+// Zur Laufzeit umschaltbare Ausführlichkeit (z. B. für Debug-Captures):
+// zwei fertig konfigurierte Logger, der verbose wird nur bei Bedarf benutzt.
+static VERBOSE: AtomicBool = AtomicBool::new(false);
+static LEVELS: OnceLock<(LevelFilter, LevelFilter)> = OnceLock::new();
+
+pub struct SwitchableLogger {
+    normal: env_logger::Logger,
+    verbose: env_logger::Logger,
+}
+
+impl SwitchableLogger {
+    fn active(&self) -> &env_logger::Logger {
+        if VERBOSE.load(Ordering::Relaxed) {
+            &self.verbose
+        } else {
+            &self.normal
+        }
+    }
+}
+
+impl log::Log for SwitchableLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        log::Log::enabled(self.active(), metadata)
+    }
+
+    fn log(&self, record: &log::Record) {
+        log::Log::log(self.active(), record);
+    }
+
+    fn flush(&self) {
+        log::Log::flush(&self.normal);
+        log::Log::flush(&self.verbose);
+    }
+}
+
+/// Installiert den globalen Logger; `verbose` gilt nur, solange
+/// `set_verbose_logging(true)` aktiv ist.
+pub fn init_switchable_logger(
+    normal: env_logger::Logger,
+    verbose: env_logger::Logger,
+) -> Result<(), log::SetLoggerError> {
+    let _ = LEVELS.set((normal.filter(), verbose.filter()));
+    log::set_boxed_logger(Box::new(SwitchableLogger { normal, verbose }))?;
+    log::set_max_level(normal_level());
+    Ok(())
+}
+
+fn normal_level() -> LevelFilter {
+    LEVELS.get().map(|(normal, _)| *normal).unwrap_or(LevelFilter::Info)
+}
+
+/// Schaltet den verbose Logger ein/aus; liefert den vorherigen Zustand.
+pub fn set_verbose_logging(enabled: bool) -> bool {
+    let previous = VERBOSE.swap(enabled, Ordering::SeqCst);
+    if let Some((normal, verbose)) = LEVELS.get() {
+        log::set_max_level(if enabled { (*verbose).max(*normal) } else { *normal });
+    }
+    previous
+}
+
+pub fn verbose_logging() -> bool {
+    VERBOSE.load(Ordering::Relaxed)
+}
+
 // Utils
 pub fn utc_ns_now() -> u64 {
     SystemTime::now()
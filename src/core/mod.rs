@@ -1,38 +1,90 @@
+pub mod analyzer_taps;
+pub mod automation;
+pub mod buffer_sizing;
 pub mod buffer_registry;
+pub mod compare;
 pub mod connectable;
 pub mod consumer;
+pub mod correlation;
+pub mod debug_capture;
 pub mod device_scanner;
+pub mod drain;
+pub mod encoded_flow;
 pub mod error;
 pub mod event_bus;
+pub mod event_dedup;
 pub mod events;
+pub mod failover;
+pub mod file_rotation;
 pub mod graph;
 pub mod graph_api;
+pub mod host_telemetry;
+pub mod http_client;
+pub mod idle;
+pub mod labels;
 pub mod lock;
 pub mod node;
+pub mod on_air;
+pub mod parallel;
+pub mod peak_rates;
 pub mod plugin;
+pub mod pre_roll;
 pub mod processor;
+pub mod processing_load;
+pub mod readiness;
 #[cfg(feature = "lockfree")]
 #[path = "ringbuffer_lockfree.rs"]
 pub mod ringbuffer;
 #[cfg(not(feature = "lockfree"))]
 pub mod ringbuffer;
+pub mod safe_mode;
+pub mod scheduler;
+pub mod state_store;
+pub mod stream_metadata;
 pub mod timestamp;
+pub mod timezone;
+pub mod watchdog;
+pub mod watermark;
 
+pub use analyzer_taps::{
+    AnalyzerKind, AnalyzerReading, AnalyzerReadings, AnalyzerTapConfig, AnalyzerValues, TapPoint,
+};
+pub use automation::{AutomationLane, AutomationShape, FlowAutomation};
 pub use buffer_registry::BufferRegistry;
-pub use consumer::{Consumer, ConsumerStatus};
-pub use error::{AudioError, AudioResult, ConfigError};
+pub use buffer_sizing::BufferSizing;
+pub use consumer::{
+    ConnectionPhase, ConnectionState, Consumer, ConsumerStatus, ConsumerTargetStatus,
+};
+pub use correlation::{current_correlation_id, CorrelationScope};
+pub use drain::{DrainCandidate, DrainPool, DrainStatus};
+pub use encoded_flow::{EncodedFlow, EncodedFlowStatus, EncodedProducer, SpliceMode};
+pub use error::{
+    classify, AudioError, AudioResult, ConfigError, ErrorCategory, ErrorInfo, LastError,
+};
 pub use event_bus::{
-    EventAuditHandler, EventBus, EventHandler, EventHandlerStats,
+    EventAuditHandler, EventBus, EventEmitter, EventHandler, EventHandlerStats,
 };
+pub use event_dedup::{DedupKey, DedupStats, EventDeduplicator};
 #[cfg(feature = "debug-events")]
 pub use events::DebugEventType;
 pub use events::{Event, EventBuilder, EventPriority, EventType};
+pub use failover::{FailoverGroup, FailoverSelector, FailoverSettings, FailoverStatus};
+pub use file_rotation::{FileRotation, FinishedSegment, Retention, SegmentHook};
 pub use graph::{AudioGraph, GraphNode, GraphSnapshot, NodeClass};
 pub use graph_api::{ConnectionRequest, DisconnectStrategy, GraphApi, NodeRequest};
 pub use node::{AirliftNode, Flow};
+pub use on_air::{OnAirController, OnAirGpio, OnAirInterlock, OnAirState};
+pub use peak_rates::{FlowLevels, LevelWindow, PeakRates, PeakTier};
+pub use processing_load::{LoadMonitor, OverloadAction, ProcessingBudget, ProcessingLoad};
 pub use plugin::{AudioPlugin, PluginFactory, PluginInfo, ProcessorPluginAdapter};
+pub use pre_roll::{PreRoll, PreRollBuffer, RecordTrigger, RecordTriggerHandler};
 pub use ringbuffer::*;
+pub use safe_mode::{CrashCounter, CrashRecord, SafeModeStatus};
+pub use state_store::StateStore;
 pub use timestamp::*;
+pub use timezone::TimeZone;
+pub use watchdog::{Watchdog, WatchdogAction, WatchdogEntryStatus, WatchdogSettings};
+pub use watermark::{WatermarkConfig, WatermarkCrossing, WatermarkLevel, WatermarkMonitor};
 
 pub trait Producer: Send + Sync {
     fn name(&self) -> &str;
@@ -41,15 +93,32 @@ pub trait Producer: Send + Sync {
     fn status(&self) -> ProducerStatus;
     fn attach_ring_buffer(&mut self, buffer: std::sync::Arc<AudioRingBuffer>);
     fn attach_decoder(&mut self, _decoder: Box<dyn crate::decoders::AudioDecoder>) {}
+    /// Hält an der aktuellen Position an, ohne den Producer zu stoppen.
+    /// Live-Quellen können nicht pausieren (Standard: Fehler).
+    fn pause(&mut self) -> anyhow::Result<()> {
+        anyhow::bail!("producer '{}' does not support pause", self.name())
+    }
+    /// Setzt nach `pause()` an derselben Position fort.
+    fn resume(&mut self) -> anyhow::Result<()> {
+        anyhow::bail!("producer '{}' does not support pause", self.name())
+    }
+    /// Letzter Laufzeitfehler (Kategorie, Retry-Flag) für Status und Watchdog.
+    fn last_error(&self) -> Option<ErrorInfo> {
+        None
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct ProducerStatus {
     pub running: bool,
+    /// Läuft, liefert aber wegen `pause()` keine Frames
+    pub paused: bool,
     pub connected: bool,
     pub samples_processed: u64,
     pub errors: u64,
     pub buffer_stats: Option<RingBufferStats>,
+    /// Decoder-Zähler (PLC, FEC) bei paketbasierten Quellen
+    pub decoder: Option<crate::decoders::DecoderStats>,
 }
 
 pub mod logging;
@@ -1,18 +1,36 @@
-use crate::core::error::{AudioError, AudioResult};
+use crate::core::error::{AudioError, AudioResult, ErrorInfo};
 #[cfg(feature = "debug-events")]
 use crate::core::DebugEventType;
-use crate::core::{Event, EventAuditHandler, EventBus, EventPriority, EventType};
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{Arc, Mutex};
-use std::time::Instant;
-
-use super::consumer::{Consumer, ConsumerStatus};
+use crate::core::{Event, EventAuditHandler, EventBus, EventEmitter, EventPriority, EventType};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant};
+
+use super::analyzer_taps::{
+    AnalyzerReadings, AnalyzerTap, AnalyzerTapConfig, AnalyzerWorker, TapPoint,
+};
+use super::automation::{process_automated, AutomationLane, FlowAutomation};
+use super::buffer_sizing::{self, BufferSizing};
+use super::consumer::{Consumer, ConsumerStatus, ConsumerTargetStatus};
+use super::drain::{DrainCandidate, DrainPool, DrainStatus};
+use super::encoded_flow::EncodedFlow;
+use super::failover::{FailoverGroup, FailoverSettings, FailoverStatus};
+use super::idle::IdleBackoff;
 use super::lock::lock_mutex;
-use super::processor::{Processor, ProcessorStatus};
+use super::on_air::{OnAirController, OnAirGpio, OnAirInterlock, OnAirState};
+use super::processor::{Processor, ProcessorStatus, DEFAULT_BATCH_SIZE, MAX_BATCH_SIZE};
+use super::parallel;
+use super::peak_rates::{FlowLevels, PeakRates, PeakTap};
+use super::processing_load::{
+    frame_duration, LoadMonitor, LoadTransition, ProcessingBudget, ProcessingLoad,
+};
+use super::readiness;
 use super::ringbuffer::AudioRingBuffer;
+use super::watchdog::{Watchdog, WatchdogAction, WatchdogEntryStatus, WatchdogSettings};
+use super::watermark::{WatermarkConfig, WatermarkMonitor};
 use super::BufferRegistry;
 use crate::core::logging::ComponentLogger;
-use crate::ring::PcmFrame;
+use crate::types::{Samples, Slots};
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum PipelineMode {
@@ -31,6 +49,28 @@ struct ProcessorLink {
     buffer: Option<Arc<AudioRingBuffer>>,
 }
 
+/// Processor eines Flows. Jeder hat einen eigenen Lock, den der Verarbeitungs-Thread
+/// nur für den Batch dieses Processors hält – ein Config-Update wartet so höchstens
+/// auf einen Batch eines Processors, nie auf einen ganzen Ketten-Durchlauf.
+#[derive(Clone)]
+struct FlowProcessor {
+    name: String,
+    processor: Arc<Mutex<Box<dyn Processor>>>,
+}
+
+impl FlowProcessor {
+    fn new(processor: Box<dyn Processor>) -> Self {
+        Self {
+            name: processor.name().to_string(),
+            processor: Arc::new(Mutex::new(processor)),
+        }
+    }
+
+    fn lock(&self, context: &str) -> std::sync::MutexGuard<'_, Box<dyn Processor>> {
+        lock_mutex(&self.processor, context)
+    }
+}
+
 #[cfg(feature = "simplified-pipeline")]
 const DEFAULT_PIPELINE_MODE: PipelineMode = PipelineMode::Simplified;
 
@@ -40,122 +80,112 @@ const DEFAULT_PIPELINE_MODE: PipelineMode = PipelineMode::Legacy;
 pub struct Flow {
     pub name: String,
     pub input_buffers: Vec<Arc<AudioRingBuffer>>,
+    /// Registry-Namen der Inputs (für `input:<name>`-Abgriffe)
+    input_names: Vec<(String, Arc<AudioRingBuffer>)>,
     pub input_merge_buffer: Arc<AudioRingBuffer>,
     pub processor_buffers: Vec<Arc<AudioRingBuffer>>,
     pub output_buffer: Arc<AudioRingBuffer>,
-    processors: Vec<Box<dyn Processor>>,
+    processors: Vec<FlowProcessor>,
     consumers: Vec<Box<dyn Consumer>>,
     pipeline_mode: PipelineMode,
     processor_links: Vec<ProcessorLink>,
     scratch_buffers: [Arc<AudioRingBuffer>; 2],
+    /// Größe für Merge-/Processor-Buffer
+    buffer_sizing: BufferSizing,
     running: Arc<AtomicBool>,
+    /// Eindeutig je `start_processing`; ein verzögerter Consumer-Start
+    /// erkennt daran, dass der Flow inzwischen neu gestartet wurde.
+    run_id: u64,
+    silence: Arc<AtomicBool>,
+    bypass: BypassSwitch,
+    on_air: OnAirController,
+    output_watermark: Option<WatermarkConfig>,
+    automation: FlowAutomation,
+    /// Zeitbudget der Processor-Kette (`config.overload`)
+    processing_budget: Option<ProcessingBudget>,
+    /// Höchstens so viele Frames je Buffer-Zugriff (`config.batch`)
+    batch_size: usize,
+    load: Arc<Mutex<ProcessingLoad>>,
+    /// Fensterlängen der Pegelmessung (`config.peak_rates`)
+    peak_rates: PeakRates,
+    levels: Arc<Mutex<FlowLevels>>,
+    /// Analyzer-Abgriffe (`config.analyzers`)
+    analyzer_taps: Vec<AnalyzerTapConfig>,
+    /// Standard-Analyzer des Nodes übernehmen (`config.default_analyzers`)
+    default_analyzers: bool,
+    analyzer_readings: Arc<Mutex<AnalyzerReadings>>,
     event_bus: Option<Arc<Mutex<EventBus>>>,
     thread_handle: Option<std::thread::JoinHandle<()>>,
+    analyzer_handle: Option<std::thread::JoinHandle<()>>,
 }
 
-const PEAK_EMIT_INTERVAL_NS: u64 = 100_000_000;
-const SILENCE_THRESHOLD: f32 = 0.001;
-
-struct PeakAccumulator {
-    peaks: [f32; 2],
-    has_samples: bool,
-    last_emit_ns: u64,
+/// Bypass ("Transparent-Modus"): Flow-eigener Schalter plus der globale des Nodes.
+#[derive(Clone, Default)]
+struct BypassSwitch {
+    flow: Arc<AtomicBool>,
+    node: Arc<AtomicBool>,
 }
 
-impl PeakAccumulator {
-    fn new() -> Self {
-        Self {
-            peaks: [0.0, 0.0],
-            has_samples: false,
-            last_emit_ns: 0,
-        }
-    }
-
-    fn update_from_frame(&mut self, frame: &PcmFrame) {
-        let channels = frame.channels as usize;
-        if channels == 0 {
-            return;
-        }
-
-        for (index, sample) in frame.samples.iter().enumerate() {
-            let channel = index % channels;
-            if channel > 1 {
-                continue;
-            }
-
-            let value = (*sample as f32).abs() / 32768.0;
-            if value > self.peaks[channel] {
-                self.peaks[channel] = value;
-            }
-        }
-
-        if channels == 1 {
-            self.peaks[1] = self.peaks[0];
-        }
-
-        self.has_samples = true;
+impl BypassSwitch {
+    fn active(&self) -> bool {
+        self.flow.load(Ordering::Relaxed) || self.node.load(Ordering::Relaxed)
     }
 
-    fn emit_if_ready(&mut self, event_bus: &Arc<Mutex<EventBus>>, flow_name: &str) {
-        if !self.has_samples {
-            return;
-        }
-
-        let now = crate::core::timestamp::utc_ns_now();
-        if self.last_emit_ns != 0 && now.saturating_sub(self.last_emit_ns) < PEAK_EMIT_INTERVAL_NS {
+    /// Beim Umschalten den jeweils anderen Leser auf den aktuellen Stand bringen,
+    /// damit keine veralteten Frames aus dem Merge-Buffer nachgereicht werden.
+    /// `active` ist der wirksame Zustand inkl. Überlast-Passthrough.
+    fn sync_readers(active: bool, was_active: &mut bool, merge_buffer: &AudioRingBuffer, bypass_reader_id: &str) {
+        if active == *was_active {
             return;
         }
-
-        let silence = self.peaks.iter().all(|peak| *peak < SILENCE_THRESHOLD);
-        let payload = serde_json::json!({
-            "timestamp": now,
-            "peaks": [self.peaks[0], self.peaks[1]],
-            "silence": silence,
-            "flow": flow_name,
-        });
-
-        let event = Event::new(
-            EventType::AudioPeak,
-            EventPriority::Debug,
-            "flow",
-            flow_name,
-            payload,
-        );
-
-        let bus = lock_mutex(event_bus, "flow.peak_event");
-        if let Err(error) = bus.publish(event) {
-            log::error!(
-                "Failed to publish audio peak event for flow '{}': {}",
-                flow_name,
-                error
-            );
-        }
-
-        self.peaks = [0.0, 0.0];
-        self.has_samples = false;
-        self.last_emit_ns = now;
+        let stale_reader = if active { bypass_reader_id } else { "default" };
+        while merge_buffer.pop_for_reader(stale_reader).is_some() {}
+        *was_active = active;
     }
 }
 
+/// Quelle für `Flow::run_id`, über alle Flows und Config-Reloads eindeutig
+static NEXT_FLOW_RUN: AtomicU64 = AtomicU64::new(1);
+
 impl Flow {
     pub fn new(name: &str) -> Self {
+        Self::with_buffer_sizing(name, BufferSizing::default(), BufferSizing::default())
+    }
+
+    /// Flow mit eigenen Buffer-Größen: `internal` für Merge- und
+    /// Processor-Buffer, `output` für den Buffer, aus dem die Consumer lesen.
+    pub fn with_buffer_sizing(name: &str, internal: BufferSizing, output: BufferSizing) -> Self {
         let flow = Self {
             name: name.to_string(),
             input_buffers: Vec::new(),
-            input_merge_buffer: Arc::new(AudioRingBuffer::new(1000)),
+            input_names: Vec::new(),
+            input_merge_buffer: internal.build(),
             processor_buffers: Vec::new(),
-            output_buffer: Arc::new(AudioRingBuffer::new(1000)),
+            output_buffer: output.build(),
             processors: Vec::new(),
             consumers: Vec::new(),
             pipeline_mode: DEFAULT_PIPELINE_MODE,
             processor_links: Vec::new(),
-            scratch_buffers: [
-                Arc::new(AudioRingBuffer::new(1000)),
-                Arc::new(AudioRingBuffer::new(1000)),
-            ],
+            scratch_buffers: [internal.build(), internal.build()],
+            buffer_sizing: internal,
             running: Arc::new(AtomicBool::new(false)),
+            run_id: 0,
+            silence: Arc::new(AtomicBool::new(true)),
+            bypass: BypassSwitch::default(),
+            on_air: OnAirController::new(),
+            output_watermark: None,
+            automation: FlowAutomation::new(),
+            processing_budget: None,
+            batch_size: DEFAULT_BATCH_SIZE,
+            load: Arc::new(Mutex::new(ProcessingLoad::default())),
+            peak_rates: PeakRates::default(),
+            levels: Arc::new(Mutex::new(FlowLevels::default())),
+            analyzer_taps: Vec::new(),
+            default_analyzers: true,
+            analyzer_readings: Arc::new(Mutex::new(AnalyzerReadings::new())),
             event_bus: None,
             thread_handle: None,
+            analyzer_handle: None,
         };
 
         flow.info(&format!("Flow '{}' created", name));
@@ -197,7 +227,7 @@ impl Flow {
             let buffer = link
                 .buffer
                 .clone()
-                .unwrap_or_else(|| Arc::new(AudioRingBuffer::new(1000)));
+                .unwrap_or_else(|| self.buffer_sizing.build());
             link.buffer = Some(buffer.clone());
             self.processor_buffers.push(buffer);
         }
@@ -227,7 +257,8 @@ impl Flow {
             .ok_or_else(|| AudioError::BufferNotFound {
                 name: buffer_name.to_string(),
             })?;
-        self.add_input_buffer(buffer);
+        self.add_input_buffer(buffer.clone());
+        self.input_names.push((buffer_name.to_string(), buffer));
         self.info(&format!(
             "Connected input buffer from registry '{}'",
             buffer_name
@@ -249,6 +280,8 @@ impl Flow {
         let before = self.input_buffers.len();
         self.input_buffers
             .retain(|candidate| !Arc::ptr_eq(candidate, &buffer));
+        self.input_names
+            .retain(|(_, candidate)| !Arc::ptr_eq(candidate, &buffer));
         if self.input_buffers.len() == before {
             return Err(AudioError::message(format!(
                 "buffer '{}' is not connected to flow '{}'",
@@ -273,14 +306,14 @@ impl Flow {
 
     pub fn add_processor_with_buffering(
         &mut self,
-        processor: Box<dyn Processor>,
+        mut processor: Box<dyn Processor>,
         buffering: ProcessorBuffering,
     ) {
         let processor_name = processor.name().to_string();
 
         match self.pipeline_mode {
             PipelineMode::Legacy => {
-                let buffer = Arc::new(AudioRingBuffer::new(1000));
+                let buffer = self.buffer_sizing.build();
                 self.processor_buffers.push(buffer.clone());
                 self.processor_links.push(ProcessorLink {
                     buffer: Some(buffer),
@@ -289,7 +322,7 @@ impl Flow {
             PipelineMode::Simplified => {
                 let buffer = match buffering {
                     ProcessorBuffering::Enabled => {
-                        let buffer = Arc::new(AudioRingBuffer::new(1000));
+                        let buffer = self.buffer_sizing.build();
                         self.processor_buffers.push(buffer.clone());
                         Some(buffer)
                     }
@@ -299,7 +332,10 @@ impl Flow {
             }
         }
 
-        self.processors.push(processor);
+        if let Some(event_bus) = &self.event_bus {
+            processor.attach_event_emitter(self.processor_emitter(event_bus, &processor_name));
+        }
+        self.processors.push(FlowProcessor::new(processor));
 
         // Logging nach mutable borrow
         self.info(&format!("Added processor '{}'", processor_name));
@@ -310,6 +346,9 @@ impl Flow {
         let output_buffer = self.output_buffer.clone();
         let output_buffer_addr = Arc::as_ptr(&output_buffer);
         consumer.attach_input_buffer(output_buffer);
+        if let Some(event_bus) = &self.event_bus {
+            consumer.attach_event_emitter(EventEmitter::new(event_bus.clone(), "consumer", &consumer_name));
+        }
 
         self.consumers.push(consumer);
 
@@ -321,14 +360,261 @@ impl Flow {
     }
 
     pub fn attach_event_bus(&mut self, event_bus: Arc<Mutex<EventBus>>) {
+        for entry in &self.processors {
+            let emitter = self.processor_emitter(&event_bus, &entry.name);
+            entry.lock("flow.attach_event_bus").attach_event_emitter(emitter);
+        }
+        for consumer in self.consumers.iter_mut() {
+            let name = consumer.name().to_string();
+            consumer.attach_event_emitter(EventEmitter::new(event_bus.clone(), "consumer", &name));
+        }
         self.event_bus = Some(event_bus);
+        self.install_output_watermark();
+    }
+
+    /// Watermarks auf dem Output-Buffer: meldet Consumer, die zurückfallen.
+    pub fn set_output_watermark(&mut self, config: Option<WatermarkConfig>) {
+        self.output_watermark = config;
+        self.install_output_watermark();
+    }
+
+    pub fn output_watermark(&self) -> Option<WatermarkConfig> {
+        self.output_watermark
+    }
+
+    /// Zeitbudget und Überlastverhalten der Processor-Kette, wirkt ab dem
+    /// nächsten Start. Gemessen wird auch ohne Budget.
+    pub fn set_processing_budget(&mut self, budget: Option<ProcessingBudget>) {
+        self.processing_budget = budget;
+    }
+
+    pub fn processing_budget(&self) -> Option<&ProcessingBudget> {
+        self.processing_budget.as_ref()
+    }
+
+    /// Frames je Buffer-Zugriff im Processing-Thread (1..=256), wirkt ab dem
+    /// nächsten Start. Größere Stapel sparen Locks bei kleinen Frames.
+    pub fn set_batch_size(&mut self, batch_size: usize) {
+        self.batch_size = batch_size.clamp(1, MAX_BATCH_SIZE);
+    }
+
+    pub fn batch_size(&self) -> usize {
+        self.batch_size
+    }
+
+    /// Echtzeitfaktor und Überlastzustand des Processing-Threads.
+    pub fn processing_load(&self) -> ProcessingLoad {
+        lock_mutex(&self.load, "flow.processing_load").clone()
+    }
+
+    /// Fensterlängen für Meter, Statistik und Aggregate, wirkt ab dem
+    /// nächsten Start.
+    pub fn set_peak_rates(&mut self, rates: PeakRates) {
+        self.peak_rates = rates;
+    }
+
+    pub fn peak_rates(&self) -> PeakRates {
+        self.peak_rates
+    }
+
+    /// Zuletzt geschlossene Pegel-Fenster je Stufe.
+    pub fn levels(&self) -> FlowLevels {
+        lock_mutex(&self.levels, "flow.levels").clone()
+    }
+
+    /// Analyzer-Abgriffe, wirken ab dem nächsten Start.
+    pub fn set_analyzer_taps(&mut self, taps: Vec<AnalyzerTapConfig>) {
+        self.analyzer_taps = taps;
+    }
+
+    pub fn analyzer_taps(&self) -> &[AnalyzerTapConfig] {
+        &self.analyzer_taps
+    }
+
+    /// `false`: `AirliftNode::add_flow` hängt keine Standard-Analyzer an.
+    pub fn set_default_analyzers(&mut self, enabled: bool) {
+        self.default_analyzers = enabled;
+    }
+
+    /// Ergänzt Standard-Analyzer; gleichnamige eigene Abgriffe haben Vorrang.
+    pub fn add_default_analyzer_taps(&mut self, defaults: &[AnalyzerTapConfig]) {
+        if !self.default_analyzers {
+            return;
+        }
+        for tap in defaults {
+            if !self.analyzer_taps.iter().any(|own| own.name == tap.name) {
+                self.analyzer_taps.push(tap.clone());
+            }
+        }
+    }
+
+    /// Hängt einen `level_meter` ans Ende der Processor-Kette, der den
+    /// Output-Pegel als Reihe `<flow>:output` in die Peak-History schreibt.
+    /// Entfällt mit `default_analyzers = false` oder wenn der Flow schon
+    /// einen gleichnamigen Processor hat.
+    pub fn add_output_meter(&mut self) {
+        use crate::processors::level_meter::{LevelMeter, OUTPUT_METER_NAME};
+
+        if !self.default_analyzers
+            || self.processor_names().iter().any(|name| name == OUTPUT_METER_NAME)
+        {
+            return;
+        }
+        let meter = LevelMeter::output(&self.name, self.peak_rates);
+        self.add_processor_unbuffered(Box::new(meter));
+    }
+
+    /// Letzter Messwert je Analyzer-Abgriff.
+    pub fn analyzer_readings(&self) -> AnalyzerReadings {
+        lock_mutex(&self.analyzer_readings, "flow.analyzer_readings").clone()
+    }
+
+    /// Buffer hinter einer Stelle im Flow; `None`, wenn es die Stelle nicht
+    /// (mehr) gibt oder der Processor ungepuffert läuft.
+    fn tap_buffer(&self, point: &TapPoint) -> Option<Arc<AudioRingBuffer>> {
+        match point {
+            TapPoint::Input(name) => self
+                .input_names
+                .iter()
+                .find(|(registry_name, _)| {
+                    // `producer:mic` und `failover:main` heißen im Flow `mic` bzw. `main`
+                    let input = registry_name.split_once(':').map(|(_, input)| input);
+                    registry_name == name || input == Some(name.as_str())
+                })
+                .map(|(_, buffer)| buffer.clone()),
+            TapPoint::Merge => Some(self.input_merge_buffer.clone()),
+            TapPoint::Output => Some(self.output_buffer.clone()),
+            TapPoint::Processor(name) => {
+                let names = self.processor_names();
+                let index = names.iter().position(|candidate| candidate == name)?;
+                if index + 1 == names.len() {
+                    return Some(self.output_buffer.clone());
+                }
+                match self.pipeline_mode {
+                    PipelineMode::Legacy => self.processor_buffers.get(index).cloned(),
+                    PipelineMode::Simplified => {
+                        self.processor_links.get(index).and_then(|link| link.buffer.clone())
+                    }
+                }
+            }
+        }
+    }
+
+    fn start_analyzers(&mut self) {
+        let mut taps = Vec::new();
+        for config in &self.analyzer_taps {
+            match self.tap_buffer(&config.point) {
+                Some(buffer) => taps.push((AnalyzerTap::new(config.clone()), buffer)),
+                None => self.warn(&format!(
+                    "Analyzer '{}': tap point '{}' not available, skipped",
+                    config.name, config.point
+                )),
+            }
+        }
+        if taps.is_empty() {
+            return;
+        }
+        let worker = AnalyzerWorker {
+            flow: self.name.clone(),
+            taps,
+            readings: self.analyzer_readings.clone(),
+            emitter: self
+                .event_bus
+                .as_ref()
+                .map(|event_bus| EventEmitter::new(event_bus.clone(), "flow", &self.name)),
+            running: self.running.clone(),
+        };
+        self.analyzer_handle = Some(std::thread::spawn(move || worker.run()));
+    }
+
+    fn install_output_watermark(&self) {
+        let monitor = self.output_watermark.map(|config| {
+            let buffer_name = format!("flow:{}:output", self.name);
+            let monitor = WatermarkMonitor::new(&buffer_name, config);
+            let monitor = match &self.event_bus {
+                Some(event_bus) => monitor.with_emitter(
+                    EventEmitter::new(event_bus.clone(), "buffer", &buffer_name)
+                        .with_context(serde_json::json!({ "flow": self.name })),
+                ),
+                None => monitor,
+            };
+            Arc::new(monitor)
+        });
+        self.output_buffer.set_watermark(monitor);
+    }
+
+    fn processor_emitter(&self, event_bus: &Arc<Mutex<EventBus>>, processor_name: &str) -> EventEmitter {
+        EventEmitter::new(event_bus.clone(), "processor", processor_name)
+            .with_context(serde_json::json!({ "flow": self.name }))
     }
 
     pub fn processor_names(&self) -> Vec<String> {
-        self.processors
+        self.processors.iter().map(|entry| entry.name.clone()).collect()
+    }
+
+    /// Interne Buffer mit Registry-Namen (`flow:<name>:merge|processor[i]|scratch[i]|output`);
+    /// `true`, wenn der Buffer auf dem Signalweg liegt und zur Latenz zählt.
+    pub fn named_buffers(&self) -> Vec<(String, Arc<AudioRingBuffer>, bool)> {
+        let mut buffers = vec![(
+            format!("flow:{}:merge", self.name),
+            self.input_merge_buffer.clone(),
+            true,
+        )];
+        let processor_buffers: Vec<Arc<AudioRingBuffer>> = match self.pipeline_mode {
+            PipelineMode::Legacy => self.processor_buffers.clone(),
+            PipelineMode::Simplified => self
+                .processor_links
+                .iter()
+                .filter_map(|link| link.buffer.clone())
+                .collect(),
+        };
+        for (index, buffer) in processor_buffers.into_iter().enumerate() {
+            buffers.push((format!("flow:{}:processor[{}]", self.name, index), buffer, true));
+        }
+        for (index, buffer) in self.scratch_buffers.iter().enumerate() {
+            buffers.push((format!("flow:{}:scratch[{}]", self.name, index), buffer.clone(), false));
+        }
+        buffers.push((
+            format!("flow:{}:output", self.name),
+            self.output_buffer.clone(),
+            true,
+        ));
+        buffers
+    }
+
+    /// Reicht ein (Teil-)Config-Update zur Laufzeit an einen Processor weiter.
+    pub fn update_processor_config(
+        &self,
+        processor_name: &str,
+        config: serde_json::Value,
+    ) -> AudioResult<()> {
+        let entry = self
+            .processors
             .iter()
-            .map(|processor| processor.name().to_string())
-            .collect()
+            .find(|entry| entry.name == processor_name)
+            .ok_or_else(|| {
+                AudioError::message(format!(
+                    "processor '{}' not found in flow '{}'",
+                    processor_name, self.name
+                ))
+            })?;
+        entry
+            .lock("flow.update_processor_config")
+            .update_config(config)
+            .map_err(|e| AudioError::with_context(format!("processor '{}'", processor_name), e))
+    }
+
+    /// Plant einen Parameter-Verlauf für einen Processor dieses Flows ein.
+    pub fn schedule_automation(&self, lane: AutomationLane) -> AudioResult<u64> {
+        if !self.processor_names().contains(&lane.processor) {
+            return Err(AudioError::message(format!(
+                "processor '{}' not found in flow '{}'",
+                lane.processor, self.name
+            )));
+        }
+        let id = self.automation.schedule(lane)?;
+        self.debug(&format!("Automation {} scheduled", id));
+        Ok(id)
     }
 
     pub fn consumer_names(&self) -> Vec<String> {
@@ -338,15 +624,76 @@ impl Flow {
             .collect()
     }
 
+    pub fn update_consumer_config(
+        &mut self,
+        consumer_name: &str,
+        config: serde_json::Value,
+    ) -> AudioResult<()> {
+        let flow_name = &self.name;
+        let consumer = self
+            .consumers
+            .iter_mut()
+            .find(|consumer| consumer.name() == consumer_name)
+            .ok_or_else(|| {
+                AudioError::message(format!(
+                    "consumer '{}' not found in flow '{}'",
+                    consumer_name, flow_name
+                ))
+            })?;
+        consumer
+            .update_config(config)
+            .map_err(|e| AudioError::with_context(format!("consumer '{}'", consumer_name), e))
+    }
+
+    /// Löst Consumer mit verbundenen Zuhörern, die Draining unterstützen, aus
+    /// dem Flow; sie laufen weiter und werden beim Stoppen nicht angefasst.
+    pub fn take_draining_consumers(&mut self) -> Vec<DrainCandidate> {
+        let mut candidates = Vec::new();
+        let mut index = 0;
+        while index < self.consumers.len() {
+            let consumer = &mut self.consumers[index];
+            if consumer.listeners() > 0 && consumer.begin_drain() {
+                candidates.push(DrainCandidate {
+                    flow: self.name.clone(),
+                    consumer: self.consumers.remove(index),
+                    buffer: self.output_buffer.clone(),
+                });
+            } else {
+                index += 1;
+            }
+        }
+        candidates
+    }
+
+    /// Ziele je Consumer in `consumer_names`-Reihenfolge (leer außer bei Fanout).
+    pub fn consumer_targets(&self) -> Vec<Vec<ConsumerTargetStatus>> {
+        self.consumers.iter().map(|consumer| consumer.targets()).collect()
+    }
+
     pub fn start(&mut self) -> AudioResult<()> {
+        if self.start_processing() {
+            self.start_consumers();
+        }
+        Ok(())
+    }
+
+    /// Alle Inputs haben schon Audio geliefert (Flows ohne Input gelten als bereit).
+    pub fn inputs_ready(&self) -> bool {
+        self.input_buffers.iter().all(|buffer| buffer.has_received())
+    }
+
+    /// Erste Startphase: nur der Processing-Thread. `false`, wenn der Flow
+    /// bereits lief.
+    pub(crate) fn start_processing(&mut self) -> bool {
         self.info("Starting flow...");
 
         if self.running.load(Ordering::Relaxed) {
             self.warn("Flow already running");
-            return Ok(());
+            return false;
         }
 
         self.running.store(true, Ordering::SeqCst);
+        self.run_id = NEXT_FLOW_RUN.fetch_add(1, Ordering::Relaxed);
 
         // Starte Processing-Thread
         let running = self.running.clone();
@@ -359,15 +706,24 @@ impl Flow {
         let scratch_buffers = self.scratch_buffers.clone();
         let flow_name = self.name.clone();
         let flow_reader_id = format!("flow:{}:input", self.name);
-        let event_bus = self.event_bus.clone();
+        let peaks = PeakTap::new(&self.name, self.peak_rates, self.silence.clone(), self.levels.clone());
+        let peaks = match &self.event_bus {
+            Some(event_bus) => peaks.with_emitter(EventEmitter::new(event_bus.clone(), "flow", &self.name)),
+            None => peaks,
+        };
+        let bypass = self.bypass.clone();
+        let automation = self.automation.clone();
+        let load = LoadMonitor::new(&self.name, self.processing_budget.clone(), self.load.clone());
+        let load = match &self.event_bus {
+            Some(event_bus) => load.with_emitter(EventEmitter::new(event_bus.clone(), "flow", &self.name)),
+            None => load,
+        };
 
-        // Prozessoren für Thread vorbereiten
-        let mut thread_processors: Vec<Box<dyn Processor>> = Vec::new();
-        for processor in &self.processors {
-            thread_processors.push(Box::new(super::processor::basic::PassThrough::new(
-                processor.name(),
-            )));
-        }
+        let batch_size = self.batch_size;
+
+        // Der Thread arbeitet auf der echten Kette (nicht auf Platzhaltern), damit
+        // Config-Updates und eigene Events die laufenden Processoren erreichen
+        let thread_processors = self.processors.clone();
 
         let handle = std::thread::spawn(move || match pipeline_mode {
             PipelineMode::Legacy => {
@@ -378,7 +734,11 @@ impl Flow {
                     processor_buffers,
                     output_buffer,
                     thread_processors,
-                    event_bus,
+                    peaks,
+                    bypass,
+                    automation,
+                    load,
+                    batch_size,
                     &flow_name,
                     &flow_reader_id,
                 );
@@ -392,7 +752,11 @@ impl Flow {
                     scratch_buffers,
                     processor_links,
                     thread_processors,
-                    event_bus,
+                    peaks,
+                    bypass,
+                    automation,
+                    load,
+                    batch_size,
                     &flow_name,
                     &flow_reader_id,
                 );
@@ -400,7 +764,12 @@ impl Flow {
         });
 
         self.thread_handle = Some(handle);
+        self.start_analyzers();
+        true
+    }
 
+    /// Zweite Startphase: Consumer, nachdem der Node die Readiness abgewartet hat.
+    pub(crate) fn start_consumers(&mut self) {
         // Consumer starten - Namen vorher sammeln
         let consumer_names: Vec<String> = self
             .consumers
@@ -432,6 +801,8 @@ impl Flow {
             ));
         }
 
+        self.publish_state_changed();
+
         if start_errors.is_empty() {
             self.info("Flow started successfully");
         } else {
@@ -440,8 +811,6 @@ impl Flow {
                 start_errors.len()
             ));
         }
-
-        Ok(())
     }
 
     fn processing_loop_legacy(
@@ -450,8 +819,12 @@ impl Flow {
         input_merge_buffer: Arc<AudioRingBuffer>,
         processor_buffers: Vec<Arc<AudioRingBuffer>>,
         output_buffer: Arc<AudioRingBuffer>,
-        mut processors: Vec<Box<dyn Processor>>,
-        event_bus: Option<Arc<Mutex<EventBus>>>,
+        processors: Vec<FlowProcessor>,
+        mut peaks: PeakTap,
+        bypass: BypassSwitch,
+        automation: FlowAutomation,
+        mut load: LoadMonitor,
+        batch_size: usize,
         flow_name: &str,
         flow_reader_id: &str,
     ) {
@@ -468,9 +841,13 @@ impl Flow {
             Arc::as_ptr(&output_buffer)
         ));
 
-        let mut peak_accumulator = PeakAccumulator::new();
         let mut iteration = 0;
+        // Ohne Audio wird seltener gepollt; ein Push in einen Eingang weckt sofort
+        let mut idle = IdleBackoff::new(Duration::from_millis(10));
+        let inputs: Vec<&AudioRingBuffer> = input_buffers.iter().map(|buffer| buffer.as_ref()).collect();
         let output_reader_id = format!("{}:output", flow_reader_id);
+        let mut was_bypassed = false;
+        let automation_staging = AudioRingBuffer::new(8);
         while running.load(Ordering::Relaxed) {
             iteration += 1;
 
@@ -479,20 +856,32 @@ impl Flow {
                 continue;
             }
 
+            let passthrough = bypass.active() || load.passthrough();
+            BypassSwitch::sync_readers(passthrough, &mut was_bypassed, &input_merge_buffer, &output_reader_id);
+
             // Sammle Frames von allen Input-Buffern
             let mut frames_collected = 0;
+            let mut audio_collected = Duration::ZERO;
             for buffer in &input_buffers {
-                while let Some(frame) = buffer.pop_for_reader(flow_reader_id) {
-                    peak_accumulator.update_from_frame(&frame);
-                    input_merge_buffer.push(frame);
-                    frames_collected += 1;
+                loop {
+                    let frames = buffer.pop_batch_for_reader(flow_reader_id, batch_size);
+                    if frames.is_empty() {
+                        break;
+                    }
+                    for frame in &frames {
+                        peaks.push(frame);
+                        audio_collected += frame_duration(frame);
+                    }
+                    frames_collected += frames.len();
+                    input_merge_buffer.push_batch(frames);
                 }
             }
-
-            if let Some(ref event_bus) = event_bus {
-                peak_accumulator.emit_if_ready(event_bus, flow_name);
+            if frames_collected > 0 {
+                idle.reset();
             }
 
+            peaks.publish();
+
             // Log alle 100 Iterationen
             if iteration % 100 == 0 {
                 let total_frames: usize = input_buffers.iter().map(|b| b.len()).sum();
@@ -513,12 +902,13 @@ impl Flow {
 
             // Einfache Pipeline-Verarbeitung
             let proc_len = processors.len();
-            if proc_len == 0 {
-                while let Some(frame) = input_merge_buffer.pop_for_reader(&output_reader_id) {
-                    output_buffer.push(frame);
-                }
+            let chain_started = Instant::now();
+            if proc_len == 0 || was_bypassed {
+                forward_reader(&input_merge_buffer, &output_reader_id, &output_buffer, batch_size);
+                Self::record_load(&mut load, Duration::ZERO, audio_collected, &flow_logger);
             } else {
-                for (i, processor) in processors.iter_mut().enumerate() {
+                let mut automation_pass = automation.snapshot();
+                for (i, entry) in processors.iter().enumerate() {
                     let input = if i == 0 {
                         &input_merge_buffer
                     } else {
@@ -531,17 +921,26 @@ impl Flow {
                         &output_buffer
                     };
 
-                    if let Err(e) = processor.process(input, output) {
-                        flow_logger.error(&format!(
-                            "Processor '{}' error: {}",
-                            processor.name(),
-                            e
-                        ));
+                    if load.skips(&entry.name) {
+                        forward_frames(input, output, batch_size);
+                        continue;
+                    }
+
+                    if let Err(e) = process_automated(
+                        entry.lock("flow.processing_loop").as_mut(),
+                        input,
+                        output,
+                        &automation_staging,
+                        &mut automation_pass,
+                        batch_size,
+                    ) {
+                        flow_logger.error(&format!("Processor '{}' error: {}", entry.name, e));
                     }
                 }
+                automation.retire(&automation_pass);
+                Self::record_load(&mut load, chain_started.elapsed(), audio_collected, &flow_logger);
             }
-
-            std::thread::sleep(std::time::Duration::from_millis(10));
+            idle.sleep_for_any(&inputs);
         }
 
         flow_logger.info("Processing thread stopped");
@@ -554,8 +953,12 @@ impl Flow {
         output_buffer: Arc<AudioRingBuffer>,
         scratch_buffers: [Arc<AudioRingBuffer>; 2],
         processor_links: Vec<ProcessorLink>,
-        mut processors: Vec<Box<dyn Processor>>,
-        event_bus: Option<Arc<Mutex<EventBus>>>,
+        processors: Vec<FlowProcessor>,
+        mut peaks: PeakTap,
+        bypass: BypassSwitch,
+        automation: FlowAutomation,
+        mut load: LoadMonitor,
+        batch_size: usize,
         flow_name: &str,
         flow_reader_id: &str,
     ) {
@@ -567,9 +970,13 @@ impl Flow {
             input_buffers.len()
         ));
 
-        let mut peak_accumulator = PeakAccumulator::new();
         let mut iteration = 0;
+        // Ohne Audio wird seltener gepollt; ein Push in einen Eingang weckt sofort
+        let mut idle = IdleBackoff::new(Duration::from_millis(10));
+        let inputs: Vec<&AudioRingBuffer> = input_buffers.iter().map(|buffer| buffer.as_ref()).collect();
         let output_reader_id = format!("{}:output", flow_reader_id);
+        let mut was_bypassed = false;
+        let automation_staging = AudioRingBuffer::new(8);
         while running.load(Ordering::Relaxed) {
             iteration += 1;
 
@@ -578,19 +985,31 @@ impl Flow {
                 continue;
             }
 
+            let passthrough = bypass.active() || load.passthrough();
+            BypassSwitch::sync_readers(passthrough, &mut was_bypassed, &input_merge_buffer, &output_reader_id);
+
             let mut frames_collected = 0;
+            let mut audio_collected = Duration::ZERO;
             for buffer in &input_buffers {
-                while let Some(frame) = buffer.pop_for_reader(flow_reader_id) {
-                    peak_accumulator.update_from_frame(&frame);
-                    input_merge_buffer.push(frame);
-                    frames_collected += 1;
+                loop {
+                    let frames = buffer.pop_batch_for_reader(flow_reader_id, batch_size);
+                    if frames.is_empty() {
+                        break;
+                    }
+                    for frame in &frames {
+                        peaks.push(frame);
+                        audio_collected += frame_duration(frame);
+                    }
+                    frames_collected += frames.len();
+                    input_merge_buffer.push_batch(frames);
                 }
             }
-
-            if let Some(ref event_bus) = event_bus {
-                peak_accumulator.emit_if_ready(event_bus, flow_name);
+            if frames_collected > 0 {
+                idle.reset();
             }
 
+            peaks.publish();
+
             if iteration % 100 == 0 {
                 let total_frames: usize = input_buffers.iter().map(|b| b.len()).sum();
                 let total_available: usize = input_buffers
@@ -612,15 +1031,16 @@ impl Flow {
             let mut scratch_index = 0;
 
             let proc_len = processors.len();
-            if proc_len == 0 {
-                while let Some(frame) = input_merge_buffer.pop_for_reader(&output_reader_id) {
-                    output_buffer.push(frame);
-                }
-                std::thread::sleep(std::time::Duration::from_millis(10));
+            if proc_len == 0 || was_bypassed {
+                forward_reader(&input_merge_buffer, &output_reader_id, &output_buffer, batch_size);
+                Self::record_load(&mut load, Duration::ZERO, audio_collected, &flow_logger);
+                idle.sleep_for_any(&inputs);
                 continue;
             }
 
-            for (i, processor) in processors.iter_mut().enumerate() {
+            let chain_started = Instant::now();
+            let mut automation_pass = automation.snapshot();
+            for (i, entry) in processors.iter().enumerate() {
                 let is_last = i + 1 == proc_len;
                 let link_buffer = processor_links.get(i).and_then(|link| link.buffer.clone());
 
@@ -634,19 +1054,52 @@ impl Flow {
                     buffer
                 };
 
-                if let Err(e) = processor.process(&current_input, &output) {
-                    flow_logger.error(&format!("Processor '{}' error: {}", processor.name(), e));
+                if load.skips(&entry.name) {
+                    forward_frames(&current_input, &output, batch_size);
+                    current_input = output;
+                    continue;
+                }
+
+                if let Err(e) = process_automated(
+                    entry.lock("flow.processing_loop").as_mut(),
+                    &current_input,
+                    &output,
+                    &automation_staging,
+                    &mut automation_pass,
+                    batch_size,
+                ) {
+                    flow_logger.error(&format!("Processor '{}' error: {}", entry.name, e));
                 }
 
                 current_input = output;
             }
-
-            std::thread::sleep(std::time::Duration::from_millis(10));
+            automation.retire(&automation_pass);
+            Self::record_load(&mut load, chain_started.elapsed(), audio_collected, &flow_logger);
+            idle.sleep_for_any(&inputs);
         }
 
         flow_logger.info("Processing thread stopped (simplified)");
     }
 
+    fn record_load(load: &mut LoadMonitor, processing: Duration, audio: Duration, flow_logger: &FlowLogger) {
+        let Some(transition) = load.record(processing, audio) else {
+            return;
+        };
+        let current = load.load();
+        match transition {
+            LoadTransition::Overloaded => flow_logger.warn(&format!(
+                "Processing overloaded: realtime factor {:.2} (limit {:.2}), action {}",
+                current.realtime_factor,
+                current.limit.unwrap_or_default(),
+                current.action.map(|action| action.as_str()).unwrap_or("alert")
+            )),
+            LoadTransition::Recovered => flow_logger.info(&format!(
+                "Processing recovered: realtime factor {:.2}",
+                current.realtime_factor
+            )),
+        }
+    }
+
     pub fn stop(&mut self) -> AudioResult<()> {
         self.info("Stopping flow...");
         self.running.store(false, Ordering::SeqCst);
@@ -687,6 +1140,13 @@ impl Flow {
                 self.error(&format!("Failed to join flow thread: {:?}", e));
             }
         }
+        if let Some(handle) = self.analyzer_handle.take() {
+            if let Err(e) = handle.join() {
+                self.error(&format!("Failed to join analyzer thread: {:?}", e));
+            }
+        }
+        self.silence.store(true, Ordering::Relaxed);
+        self.publish_state_changed();
 
         if stop_errors.is_empty() {
             self.info("Flow stopped successfully");
@@ -698,8 +1158,11 @@ impl Flow {
     }
 
     pub fn status(&self) -> FlowStatus {
-        let processor_status: Vec<ProcessorStatus> =
-            self.processors.iter().map(|p| p.status()).collect();
+        let processor_status: Vec<ProcessorStatus> = self
+            .processors
+            .iter()
+            .map(|entry| entry.lock("flow.status").status())
+            .collect();
 
         let consumer_status: Vec<ConsumerStatus> =
             self.consumers.iter().map(|c| c.status()).collect();
@@ -723,78 +1186,320 @@ impl Flow {
             input_buffer_levels,
             processor_buffer_levels,
             output_buffer_level: self.output_buffer.len(),
+            on_air: self.on_air.state(),
+            interlocks: self.on_air_interlocks(),
+            bypass: self.bypass.active(),
+            automation: self.automation.lanes(),
+            load: self.processing_load(),
+            levels: self.levels(),
+            analyzers: self.analyzer_readings(),
         }
     }
-}
-
-// Helper struct für Thread-Logging
-struct FlowLogger {
-    name: String,
-}
 
-impl crate::core::logging::ComponentLogger for FlowLogger {
-    fn log_context(&self) -> crate::core::logging::LogContext {
-        crate::core::logging::LogContext::new("Flow", &self.name)
+    /// Geplante Parameter-Verläufe (Automation) dieses Flows.
+    pub fn automation(&self) -> &FlowAutomation {
+        &self.automation
     }
-}
 
-// Implementierung des ComponentLogger Traits für Flow
-impl crate::core::logging::ComponentLogger for Flow {
-    fn log_context(&self) -> crate::core::logging::LogContext {
-        crate::core::logging::LogContext::new("Flow", &self.name)
+    /// Schaltet den Bypass des Flows: Input geht direkt an die Consumer.
+    pub fn set_bypass(&mut self, enabled: bool) {
+        if self.bypass.flow.swap(enabled, Ordering::SeqCst) == enabled {
+            return;
+        }
+        self.info(&format!("Bypass {}", if enabled { "enabled" } else { "disabled" }));
+        if let Some(event_bus) = &self.event_bus {
+            let event = Event::new(
+                EventType::ConfigChanged,
+                EventPriority::Info,
+                "flow",
+                &self.name,
+                serde_json::json!({
+                    "action": "bypass_changed",
+                    "flow": self.name,
+                    "enabled": enabled,
+                    "timestamp": crate::core::timestamp::utc_ns_now(),
+                }),
+            );
+            let bus = lock_mutex(event_bus, "flow.bypass_event");
+            if let Err(error) = bus.publish(event) {
+                self.error(&format!("Failed to publish bypass event: {}", error));
+            }
+        }
     }
-}
-
-#[derive(Debug)]
-pub struct FlowStatus {
-    pub running: bool,
-    pub processor_status: Vec<ProcessorStatus>,
-    pub consumer_status: Vec<ConsumerStatus>,
-    pub input_buffer_levels: Vec<usize>,
-    pub processor_buffer_levels: Vec<usize>,
-    pub output_buffer_level: usize,
-}
-
-pub struct AirliftNode {
-    running: Arc<AtomicBool>,
-    start_time: Instant,
-    producers: Vec<Box<dyn super::Producer>>,
-    producer_buffers: Vec<Arc<AudioRingBuffer>>,
-    pub flows: Vec<Flow>,
-    buffer_registry: Arc<BufferRegistry>,
-    event_bus: Arc<Mutex<EventBus>>,
-}
 
-impl AirliftNode {
-    pub fn new() -> Self {
-        // EventBus erstellen und Standard-Handler registrieren
-        let mut event_bus = EventBus::new("airlift_node");
+    /// Flow-eigener Schalter (ohne globalen Bypass).
+    pub fn is_bypassed(&self) -> bool {
+        self.bypass.flow.load(Ordering::Relaxed)
+    }
 
-        // Standard-Handler registrieren
-        let audit_handler = Arc::new(EventAuditHandler::new(
-            "node_event_audit",
-            EventPriority::Info,
-        ));
-        let _ = event_bus.register_handler(audit_handler);
+    /// Wirksamer Zustand inkl. globalem Bypass des Nodes.
+    pub fn bypass_active(&self) -> bool {
+        self.bypass.active()
+    }
 
-        // EventBus starten
-        event_bus.start().expect("Failed to start EventBus");
+    fn attach_node_bypass(&mut self, node_bypass: Arc<AtomicBool>) {
+        self.bypass.node = node_bypass;
+    }
 
-        let node = Self {
-            running: Arc::new(AtomicBool::new(false)),
-            start_time: Instant::now(),
-            producers: Vec::new(),
-            producer_buffers: Vec::new(),
-            flows: Vec::new(),
-            buffer_registry: Arc::new(BufferRegistry::new()),
-            event_bus: Arc::new(Mutex::new(event_bus)),
-        };
+    pub fn on_air_state(&self) -> OnAirState {
+        self.on_air.state()
+    }
 
-        node.info("AirliftNode created with buffer registry");
-        node
+    /// Optionaler GPIO-Ausgang (z.B. `/sys/class/gpio/gpio17/value`) für die On-Air-Lampe.
+    pub fn set_on_air_gpio(&mut self, path: Option<&str>) {
+        self.on_air.set_gpio(path.map(OnAirGpio::new));
     }
 
-    pub fn publish_event(
+    /// Aktuell aktive Interlocks, die ein On-Air verhindern würden.
+    pub fn on_air_interlocks(&self) -> Vec<OnAirInterlock> {
+        let mut interlocks = Vec::new();
+        if self.silence.load(Ordering::Relaxed) {
+            interlocks.push(OnAirInterlock::Silence);
+        }
+        for consumer in &self.consumers {
+            if !consumer.status().running {
+                interlocks.push(OnAirInterlock::EncoderUnhealthy {
+                    consumer: consumer.name().to_string(),
+                });
+            }
+        }
+        interlocks
+    }
+
+    pub fn go_on_air(&mut self) -> AudioResult<()> {
+        let interlocks = self.on_air_interlocks();
+        let changed = self
+            .on_air
+            .go_on_air(&interlocks)
+            .map_err(|reason| AudioError::InterlockActive {
+                flow: self.name.clone(),
+                reason,
+            })?;
+
+        if changed {
+            self.info("Flow is ON AIR");
+            self.publish_on_air_changed(OnAirState::OffAir);
+        }
+        Ok(())
+    }
+
+    /// Fordert ein Bestätigungs-Token für den Wechsel auf Off-Air an.
+    pub fn request_off_air(&mut self) -> String {
+        self.on_air.request_off_air()
+    }
+
+    pub fn confirm_off_air(&mut self, token: &str) -> AudioResult<()> {
+        let changed = self
+            .on_air
+            .confirm_off_air(token)
+            .map_err(|reason| AudioError::InterlockActive {
+                flow: self.name.clone(),
+                reason,
+            })?;
+
+        if changed {
+            self.info("Flow is OFF AIR");
+            self.publish_on_air_changed(OnAirState::OnAir);
+        }
+        Ok(())
+    }
+
+    fn publish_state_changed(&self) {
+        let Some(event_bus) = &self.event_bus else {
+            return;
+        };
+
+        let running = self.running.load(Ordering::Relaxed);
+        let event = Event::new(
+            EventType::FlowStateChanged,
+            EventPriority::Info,
+            "flow",
+            &self.name,
+            serde_json::json!({
+                "flow": self.name,
+                "running": running,
+                "timestamp": crate::core::timestamp::utc_ns_now(),
+            }),
+        );
+
+        let bus = lock_mutex(event_bus, "flow.state_event");
+        if let Err(error) = bus.publish(event) {
+            self.error(&format!("Failed to publish flow state event: {}", error));
+        }
+    }
+
+    fn publish_on_air_changed(&self, previous: OnAirState) {
+        let Some(event_bus) = &self.event_bus else {
+            return;
+        };
+
+        let event = Event::new(
+            EventType::OnAirChanged,
+            EventPriority::Info,
+            "flow",
+            &self.name,
+            serde_json::json!({
+                "flow": self.name,
+                "state": self.on_air.state().as_str(),
+                "previous": previous.as_str(),
+                "timestamp": self.on_air.changed_ns(),
+            }),
+        );
+
+        let bus = lock_mutex(event_bus, "flow.on_air_event");
+        if let Err(error) = bus.publish(event) {
+            self.error(&format!("Failed to publish on-air event: {}", error));
+        }
+    }
+}
+
+/// Frames eines ausgelassenen Processors unverändert weiterreichen.
+fn forward_frames(input: &AudioRingBuffer, output: &AudioRingBuffer, batch_size: usize) {
+    forward_reader(input, "default", output, batch_size);
+}
+
+fn forward_reader(input: &AudioRingBuffer, reader_id: &str, output: &AudioRingBuffer, batch_size: usize) {
+    loop {
+        let frames = input.pop_batch_for_reader(reader_id, batch_size);
+        if frames.is_empty() {
+            return;
+        }
+        output.push_batch(frames);
+    }
+}
+
+// Helper struct für Thread-Logging
+struct FlowLogger {
+    name: String,
+}
+
+impl crate::core::logging::ComponentLogger for FlowLogger {
+    fn log_context(&self) -> crate::core::logging::LogContext {
+        crate::core::logging::LogContext::new("Flow", &self.name)
+    }
+}
+
+// Implementierung des ComponentLogger Traits für Flow
+impl crate::core::logging::ComponentLogger for Flow {
+    fn log_context(&self) -> crate::core::logging::LogContext {
+        crate::core::logging::LogContext::new("Flow", &self.name)
+    }
+}
+
+#[derive(Debug)]
+pub struct FlowStatus {
+    pub running: bool,
+    pub processor_status: Vec<ProcessorStatus>,
+    pub consumer_status: Vec<ConsumerStatus>,
+    pub input_buffer_levels: Vec<usize>,
+    pub processor_buffer_levels: Vec<usize>,
+    pub output_buffer_level: usize,
+    pub on_air: OnAirState,
+    pub interlocks: Vec<OnAirInterlock>,
+    /// Processing wird übersprungen (Flow- oder Node-Bypass)
+    pub bypass: bool,
+    /// Aktive und geplante Automation-Verläufe
+    pub automation: Vec<AutomationLane>,
+    /// Echtzeitfaktor und Überlastzustand der Processor-Kette
+    pub load: ProcessingLoad,
+    /// Zuletzt geschlossene Pegel-Fenster (Meter, Statistik, Aggregat)
+    pub levels: FlowLevels,
+    /// Letzter Messwert je Analyzer-Abgriff
+    pub analyzers: AnalyzerReadings,
+}
+
+struct StandbyProducer {
+    slot: String,
+    producer: Box<dyn super::Producer>,
+}
+
+pub struct AirliftNode {
+    running: Arc<AtomicBool>,
+    start_time: Instant,
+    producers: Vec<Box<dyn super::Producer>>,
+    producer_buffers: Vec<Arc<AudioRingBuffer>>,
+    /// Slot (= Registry-Name `producer:<slot>`) je aktivem Producer
+    producer_slots: Vec<String>,
+    standby_producers: Vec<StandbyProducer>,
+    /// Failover-Gruppen, Ausgang jeweils `failover:<name>` in der Registry
+    failover_groups: Vec<FailoverGroup>,
+    pub flows: Vec<Flow>,
+    /// Passthrough-Flows für kodierte Frames (ohne Decode/Re-Encode)
+    encoded_flows: Vec<EncodedFlow>,
+    buffer_registry: Arc<BufferRegistry>,
+    event_bus: Arc<Mutex<EventBus>>,
+    /// Globaler Bypass, wird mit allen Flows geteilt
+    bypass: Arc<AtomicBool>,
+    /// Wie lange `start` auf Audio in den Flow-Inputs wartet, bevor die
+    /// Consumer trotzdem starten
+    readiness_timeout: Duration,
+    /// Wie viele Producer/Flows `start`/`stop` gleichzeitig bearbeiten
+    startup_workers: usize,
+    /// Neustarts ausgefallener Producer (siehe `run_watchdog`)
+    watchdog: Watchdog,
+    /// Bekommt jeder neue Flow in `add_flow` (`[analyzers]`)
+    default_analyzer_taps: Vec<AnalyzerTapConfig>,
+    /// `level_meter` am Ende jedes neuen Flows (`[analyzers] output_meter`)
+    output_meter: bool,
+    /// Consumer aus der vorigen Config, die noch Zuhörer bedienen
+    drains: Mutex<DrainPool>,
+    /// Der geteilte Node (`into_shared`); Hintergrund-Threads wie das
+    /// Readiness-Gate zur Laufzeit sperren ihn nur kurz.
+    shared: Option<Weak<Mutex<AirliftNode>>>,
+}
+
+impl AirliftNode {
+    pub fn new() -> Self {
+        // EventBus erstellen und Standard-Handler registrieren
+        let mut event_bus = EventBus::new("airlift_node");
+
+        // Standard-Handler registrieren
+        let audit_handler = Arc::new(EventAuditHandler::new(
+            "node_event_audit",
+            EventPriority::Info,
+        ));
+        let _ = event_bus.register_handler(audit_handler);
+
+        // EventBus starten
+        event_bus.start().expect("Failed to start EventBus");
+
+        let node = Self {
+            running: Arc::new(AtomicBool::new(false)),
+            start_time: Instant::now(),
+            producers: Vec::new(),
+            producer_buffers: Vec::new(),
+            producer_slots: Vec::new(),
+            standby_producers: Vec::new(),
+            failover_groups: Vec::new(),
+            bypass: Arc::new(AtomicBool::new(false)),
+            flows: Vec::new(),
+            encoded_flows: Vec::new(),
+            buffer_registry: Arc::new(BufferRegistry::new()),
+            event_bus: Arc::new(Mutex::new(event_bus)),
+            readiness_timeout: readiness::DEFAULT_READINESS_TIMEOUT,
+            startup_workers: parallel::default_workers(),
+            watchdog: Watchdog::default(),
+            default_analyzer_taps: Vec::new(),
+            output_meter: false,
+            drains: Mutex::new(DrainPool::default()),
+            shared: None,
+        };
+
+        node.info("AirliftNode created with buffer registry");
+        node
+    }
+
+    /// Legt den Node hinter den Mutex, den API, Zeitplan und Regeln teilen,
+    /// und merkt sich den Handle für Arbeit, die nicht unter der Sperre
+    /// warten darf (Readiness-Gate bei `start_flow_by_name`).
+    pub fn into_shared(mut self) -> Arc<Mutex<AirliftNode>> {
+        Arc::new_cyclic(|shared| {
+            self.shared = Some(shared.clone());
+            Mutex::new(self)
+        })
+    }
+
+    pub fn publish_event(
         &self,
         event_type: EventType,
         priority: EventPriority,
@@ -818,8 +1523,17 @@ impl AirliftNode {
     }
 
     pub fn add_producer(&mut self, producer: Box<dyn super::Producer>) -> AudioResult<()> {
+        self.add_producer_with_buffer(producer, BufferSizing::default())
+    }
+
+    /// Wie `add_producer`, mit eigener Größe für den Slot-Buffer.
+    pub fn add_producer_with_buffer(
+        &mut self,
+        producer: Box<dyn super::Producer>,
+        sizing: BufferSizing,
+    ) -> AudioResult<()> {
         let producer_name = producer.name().to_string();
-        let buffer = Arc::new(AudioRingBuffer::new(1000));
+        let buffer = sizing.build();
 
         let mut producer = producer;
         producer.attach_ring_buffer(buffer.clone());
@@ -850,6 +1564,7 @@ impl AirliftNode {
         }
 
         self.producer_buffers.push(buffer);
+        self.producer_slots.push(producer_name.clone());
         self.producers.push(producer);
 
         self.publish_event(
@@ -870,8 +1585,197 @@ impl AirliftNode {
         Ok(())
     }
 
+    /// Registriert einen Warm-Standby-Producer für `slot`. Er schreibt in
+    /// denselben Buffer (`producer:<slot>`), wird aber erst per
+    /// `activate_standby_producer` gestartet.
+    pub fn add_standby_producer(
+        &mut self,
+        slot: &str,
+        mut producer: Box<dyn super::Producer>,
+    ) -> AudioResult<()> {
+        let index = self.slot_index(slot)?;
+        producer.attach_ring_buffer(self.producer_buffers[index].clone());
+
+        self.info(&format!(
+            "Added standby producer '{}' for slot '{}'",
+            producer.name(),
+            slot
+        ));
+        self.standby_producers.push(StandbyProducer {
+            slot: slot.to_string(),
+            producer,
+        });
+        Ok(())
+    }
+
+    /// Watermarks für einen registrierten Buffer (z. B. `producer:mic`) setzen
+    /// oder mit `None` entfernen.
+    pub fn set_buffer_watermark(
+        &self,
+        buffer_name: &str,
+        config: Option<WatermarkConfig>,
+    ) -> AudioResult<()> {
+        let buffer = self
+            .buffer_registry
+            .get(buffer_name)
+            .ok_or_else(|| AudioError::BufferNotFound {
+                name: buffer_name.to_string(),
+            })?;
+        let monitor = config.map(|config| {
+            Arc::new(
+                WatermarkMonitor::new(buffer_name, config).with_emitter(EventEmitter::new(
+                    self.event_bus.clone(),
+                    "buffer",
+                    buffer_name,
+                )),
+            )
+        });
+        buffer.set_watermark(monitor);
+        Ok(())
+    }
+
+    /// Stoppt den aktiven Producer des Slots und startet den Standby-Producer
+    /// am selben Buffer. Der bisherige Producer wird selbst zum Standby.
+    pub fn activate_standby_producer(&mut self, standby_name: &str) -> AudioResult<()> {
+        let standby_index = self
+            .standby_producers
+            .iter()
+            .position(|standby| standby.producer.name() == standby_name)
+            .ok_or_else(|| AudioError::ProducerNotFound {
+                name: standby_name.to_string(),
+            })?;
+        let slot = self.standby_producers[standby_index].slot.clone();
+        let index = self.slot_index(&slot)?;
+        let previous_name = self.producers[index].name().to_string();
+        self.watchdog.forget(&previous_name);
+
+        if self.running.load(Ordering::Relaxed) {
+            if let Err(e) = self.producers[index].stop() {
+                self.warn(&format!("Failed to stop producer '{}': {}", previous_name, e));
+            }
+            if let Err(e) = self.standby_producers[standby_index].producer.start() {
+                // Zurück auf den bisherigen Producer
+                if let Err(restart_error) = self.producers[index].start() {
+                    self.error(&format!(
+                        "Failed to restart producer '{}' after failed switch: {}",
+                        previous_name, restart_error
+                    ));
+                }
+                return Err(AudioError::with_context(
+                    format!("start standby producer '{}'", standby_name),
+                    e,
+                ));
+            }
+        }
+
+        let standby = self.standby_producers.remove(standby_index);
+        let previous = std::mem::replace(&mut self.producers[index], standby.producer);
+        self.standby_producers.push(StandbyProducer {
+            slot: slot.clone(),
+            producer: previous,
+        });
+
+        self.publish_event(
+            EventType::ConfigChanged,
+            EventPriority::Info,
+            serde_json::json!({
+                "action": "producer_switched",
+                "slot": slot,
+                "from": previous_name,
+                "to": standby_name,
+                "buffer_name": format!("producer:{}", slot),
+                "timestamp": crate::core::timestamp::utc_ns_now(),
+            }),
+        );
+
+        self.info(&format!(
+            "Switched slot '{}' from '{}' to '{}'",
+            slot, previous_name, standby_name
+        ));
+        Ok(())
+    }
+
+    /// Slot je aktivem Producer (gleiche Reihenfolge wie `producers()`)
+    pub fn producer_slots(&self) -> &[String] {
+        &self.producer_slots
+    }
+
+    /// Legt eine Failover-Gruppe über die Slots `members` (Priorität in
+    /// Reihenfolge) an und registriert ihren Ausgang als `failover:<name>`.
+    pub fn add_failover_group(
+        &mut self,
+        name: &str,
+        members: &[String],
+        settings: FailoverSettings,
+    ) -> AudioResult<()> {
+        if members.is_empty() {
+            return Err(AudioError::message(format!(
+                "failover group '{}' has no producers",
+                name
+            )));
+        }
+        let buffers = members
+            .iter()
+            .map(|member| {
+                let index = self.slot_index(member)?;
+                Ok((member.clone(), self.producer_buffers[index].clone()))
+            })
+            .collect::<AudioResult<Vec<_>>>()?;
+
+        // Gruppen-Buffer so groß wie der größte Mitglieds-Buffer
+        let sizing = buffers
+            .iter()
+            .map(|(_, buffer)| BufferSizing::of(buffer))
+            .fold(BufferSizing { slots: Slots(1), prealloc_samples: Samples::ZERO }, BufferSizing::max);
+        let output = sizing.build();
+        let buffer_name = format!("failover:{}", name);
+        self.buffer_registry
+            .register(&buffer_name, output.clone())
+            .map_err(|e| AudioError::with_context(format!("register buffer '{}'", buffer_name), e))?;
+
+        let mut group = FailoverGroup::new(name, buffers, output, settings)
+            .with_emitter(EventEmitter::new(self.event_bus.clone(), "failover", name));
+        if self.running.load(Ordering::Relaxed) {
+            group.start();
+        }
+        self.info(&format!(
+            "Added failover group '{}' ({}) as '{}'",
+            name,
+            members.join(" > "),
+            buffer_name
+        ));
+        self.failover_groups.push(group);
+        Ok(())
+    }
+
+    pub fn failover_status(&self) -> Vec<FailoverStatus> {
+        self.failover_groups.iter().map(|group| group.status()).collect()
+    }
+
+    /// Standby-Producer als (Name, Slot)
+    pub fn standby_producers(&self) -> Vec<(String, String)> {
+        self.standby_producers
+            .iter()
+            .map(|standby| (standby.producer.name().to_string(), standby.slot.clone()))
+            .collect()
+    }
+
+    fn slot_index(&self, slot: &str) -> AudioResult<usize> {
+        self.producer_slots
+            .iter()
+            .position(|candidate| candidate == slot)
+            .ok_or_else(|| AudioError::ProducerNotFound {
+                name: slot.to_string(),
+            })
+    }
+
     pub fn add_flow(&mut self, mut flow: Flow) {
+        flow.add_default_analyzer_taps(&self.default_analyzer_taps);
+        if self.output_meter {
+            flow.add_output_meter();
+        }
         flow.attach_event_bus(self.event_bus.clone());
+        flow.attach_node_bypass(self.bypass.clone());
         let flow_name = flow.name.clone();
         self.flows.push(flow);
 
@@ -879,6 +1783,68 @@ impl AirliftNode {
         self.info(&format!("Added flow: '{}'", flow_name));
     }
 
+    /// Obergrenze für das Readiness-Gate in `start` (`Duration::ZERO` = nicht warten).
+    pub fn set_readiness_timeout(&mut self, timeout: Duration) {
+        self.readiness_timeout = timeout;
+    }
+
+    pub fn readiness_timeout(&self) -> Duration {
+        self.readiness_timeout
+    }
+
+    /// Fenster, in dem wiederholte Warnungen/Fehler einer Komponente nur
+    /// gezählt werden (`[events] dedup_window`, `Duration::ZERO` = aus).
+    pub fn set_event_dedup_window(&self, window: Duration) {
+        lock_mutex(&self.event_bus, "airlift_node.set_event_dedup_window").set_dedup_window(window);
+    }
+
+    /// Parallelität beim Starten/Stoppen; `1` = nacheinander wie früher.
+    pub fn set_startup_workers(&mut self, workers: usize) {
+        self.startup_workers = workers.clamp(1, parallel::MAX_STARTUP_WORKERS);
+    }
+
+    pub fn startup_workers(&self) -> usize {
+        self.startup_workers
+    }
+
+    /// Standard-Analyzer für alle danach hinzugefügten Flows, auch solche,
+    /// die per API entstehen; leer = keine.
+    pub fn set_default_analyzer_taps(&mut self, taps: Vec<AnalyzerTapConfig>) {
+        self.default_analyzer_taps = taps;
+    }
+
+    pub fn default_analyzer_taps(&self) -> &[AnalyzerTapConfig] {
+        &self.default_analyzer_taps
+    }
+
+    /// Output-Meter für alle danach hinzugefügten Flows.
+    pub fn set_output_meter(&mut self, enabled: bool) {
+        self.output_meter = enabled;
+    }
+
+    pub fn output_meter(&self) -> bool {
+        self.output_meter
+    }
+
+    pub fn add_encoded_flow(&mut self, flow: EncodedFlow) {
+        let flow_name = flow.name.clone();
+        self.encoded_flows.push(flow);
+        self.info(&format!("Added encoded flow: '{}'", flow_name));
+    }
+
+    pub fn encoded_flows(&self) -> &[EncodedFlow] {
+        &self.encoded_flows
+    }
+
+    pub fn encoded_flow_mut(&mut self, flow_name: &str) -> AudioResult<&mut EncodedFlow> {
+        self.encoded_flows
+            .iter_mut()
+            .find(|flow| flow.name == flow_name)
+            .ok_or_else(|| AudioError::FlowNotFound {
+                name: flow_name.to_string(),
+            })
+    }
+
     pub fn add_consumer_to_flow(
         &mut self,
         flow_index: usize,
@@ -895,17 +1861,182 @@ impl AirliftNode {
         Ok(())
     }
 
+    /// Globaler Bypass: alle Flows leiten ihren Input unverarbeitet weiter.
+    pub fn set_bypass(&mut self, enabled: bool) {
+        if self.bypass.swap(enabled, Ordering::SeqCst) == enabled {
+            return;
+        }
+        self.info(&format!(
+            "Global bypass {}",
+            if enabled { "enabled" } else { "disabled" }
+        ));
+        self.publish_event(
+            EventType::ConfigChanged,
+            EventPriority::Info,
+            serde_json::json!({
+                "action": "bypass_changed",
+                "flow": null,
+                "enabled": enabled,
+                "timestamp": crate::core::timestamp::utc_ns_now(),
+            }),
+        );
+    }
+
+    pub fn is_bypassed(&self) -> bool {
+        self.bypass.load(Ordering::Relaxed)
+    }
+
     pub fn flow_index_by_name(&self, flow_name: &str) -> Option<usize> {
         self.flows.iter().position(|flow| flow.name == flow_name)
     }
 
+    /// Startet einen Flow zur Laufzeit (API, Zeitplan, Regeln) mit demselben
+    /// Readiness-Gate wie beim Node-Start. Am geteilten Node (`into_shared`)
+    /// kehrt der Aufruf sofort zurück; das Warten läuft in einem eigenen
+    /// Thread, der den Node nur zum Starten der Consumer sperrt.
     pub fn start_flow_by_name(&mut self, flow_name: &str) -> AudioResult<()> {
-        let flow = self
+        let index = self
+            .flow_index_by_name(flow_name)
+            .ok_or_else(|| AudioError::message(format!("flow '{}' not found", flow_name)))?;
+        if !self.flows[index].start_processing() {
+            return Ok(());
+        }
+        let flow = &mut self.flows[index];
+        if flow.inputs_ready() {
+            flow.start_consumers();
+            return Ok(());
+        }
+        match self.shared.clone() {
+            Some(shared) => self.start_consumers_in_background(shared, index),
+            None => self.start_consumers_when_ready(vec![index]),
+        }
+        Ok(())
+    }
+
+    fn start_consumers_in_background(&self, shared: Weak<Mutex<AirliftNode>>, index: usize) {
+        let flow = &self.flows[index];
+        let flow_name = flow.name.clone();
+        let run_id = flow.run_id;
+        let inputs = flow.input_buffers.clone();
+        let timeout = self.readiness_timeout;
+        let correlation = super::current_correlation_id();
+        std::thread::spawn(move || {
+            let _correlation = correlation.as_deref().map(super::CorrelationScope::with_id);
+            let not_ready = readiness::wait_until_ready(timeout, || {
+                if inputs.iter().all(|buffer| buffer.has_received()) {
+                    Vec::new()
+                } else {
+                    vec![flow_name.clone()]
+                }
+            });
+            let Some(shared) = shared.upgrade() else {
+                return;
+            };
+            let mut node = lock_mutex(&shared, "node.flow_readiness");
+            node.finish_flow_start(&flow_name, run_id, !not_ready.is_empty());
+        });
+    }
+
+    /// Zweite Hälfte von `start_consumers_in_background`: nur, wenn derselbe
+    /// Lauf des Flows noch aktiv ist (kein Stop/Restart dazwischen).
+    fn finish_flow_start(&mut self, flow_name: &str, run_id: u64, timed_out: bool) {
+        let timeout = self.readiness_timeout;
+        let Some(flow) = self.flows.iter_mut().find(|flow| flow.name == flow_name) else {
+            return;
+        };
+        if flow.run_id != run_id || !flow.running.load(Ordering::Relaxed) {
+            return;
+        }
+        if timed_out {
+            flow.warn(&format!(
+                "Inputs not producing after {} ms, starting consumers anyway",
+                timeout.as_millis()
+            ));
+        }
+        flow.start_consumers();
+    }
+
+    /// Startreihenfolge der Flows in Stufen (Indizes aufsteigend): Ist ein
+    /// Input der Output eines anderen Flows, startet der Flow eine Stufe
+    /// später. Flows in einem Zyklus kommen gemeinsam in die letzte Stufe.
+    fn flow_start_levels(&self) -> Vec<Vec<usize>> {
+        let upstream: Vec<Vec<usize>> = self
+            .flows
+            .iter()
+            .enumerate()
+            .map(|(index, flow)| {
+                self.flows
+                    .iter()
+                    .enumerate()
+                    .filter(|(other, source)| {
+                        *other != index
+                            && flow
+                                .input_buffers
+                                .iter()
+                                .any(|input| Arc::ptr_eq(input, &source.output_buffer))
+                    })
+                    .map(|(other, _)| other)
+                    .collect()
+            })
+            .collect();
+
+        let mut placed = vec![false; self.flows.len()];
+        let mut remaining: Vec<usize> = (0..self.flows.len()).collect();
+        let mut levels = Vec::new();
+        while !remaining.is_empty() {
+            let (ready, blocked): (Vec<usize>, Vec<usize>) = remaining
+                .iter()
+                .partition(|index| upstream[**index].iter().all(|source| placed[*source]));
+            if ready.is_empty() {
+                let names: Vec<&str> = blocked.iter().map(|index| self.flows[*index].name.as_str()).collect();
+                self.warn(&format!("Flows {} feed each other, starting them together", names.join(", ")));
+                levels.push(blocked);
+                break;
+            }
+            for index in &ready {
+                placed[*index] = true;
+            }
+            levels.push(ready);
+            remaining = blocked;
+        }
+        levels
+    }
+
+    /// Startet die Consumer jedes Flows in `pending`, sobald dessen eigene
+    /// Inputs Audio liefern; nach `readiness_timeout` die übrigen mit Warnung.
+    fn start_consumers_when_ready(&mut self, mut pending: Vec<usize>) {
+        let workers = self.startup_workers;
+        let flows = &mut self.flows;
+        let not_ready = readiness::wait_until_ready(self.readiness_timeout, || {
+            let (ready, waiting): (Vec<usize>, Vec<usize>) =
+                pending.iter().partition(|index| flows[**index].inputs_ready());
+            let mut ready_flows: Vec<&mut Flow> = flows
+                .iter_mut()
+                .enumerate()
+                .filter(|(index, _)| ready.contains(index))
+                .map(|(_, flow)| flow)
+                .collect();
+            parallel::for_each_bounded(&mut ready_flows, workers, |flow| flow.start_consumers());
+            pending = waiting;
+            pending.iter().map(|index| flows[*index].name.clone()).collect()
+        });
+        if not_ready.is_empty() {
+            return;
+        }
+
+        self.warn(&format!(
+            "Inputs of flow(s) {} not producing after {} ms, starting consumers anyway",
+            not_ready.join(", "),
+            self.readiness_timeout.as_millis()
+        ));
+        let mut late_flows: Vec<&mut Flow> = self
             .flows
             .iter_mut()
-            .find(|flow| flow.name == flow_name)
-            .ok_or_else(|| AudioError::message(format!("flow '{}' not found", flow_name)))?;
-        flow.start()
+            .enumerate()
+            .filter(|(index, _)| pending.contains(index))
+            .map(|(_, flow)| flow)
+            .collect();
+        parallel::for_each_bounded(&mut late_flows, workers, |flow| flow.start_consumers());
     }
 
     pub fn stop_flow_by_name(&mut self, flow_name: &str) -> AudioResult<()> {
@@ -917,17 +2048,248 @@ impl AirliftNode {
         flow.stop()
     }
 
+    /// Startet bzw. beendet die ausgelöste Aufnahme eines FileConsumers mit
+    /// `config.pre_roll` (`{"record": ...}`), z. B. per Zeitplan.
+    pub fn set_consumer_recording(&mut self, consumer_name: &str, on: bool) -> AudioResult<()> {
+        let flow = self
+            .flows
+            .iter_mut()
+            .find(|flow| flow.consumer_names().iter().any(|name| name == consumer_name))
+            .ok_or_else(|| AudioError::message(format!("consumer '{}' not found", consumer_name)))?;
+        flow.update_consumer_config(consumer_name, serde_json::json!({ "record": on }))
+    }
+
+    /// Startet einen Producer (Name oder Slot) zur Laufzeit, z. B. per Zeitplan.
+    pub fn start_producer_by_name(&mut self, producer_name: &str) -> AudioResult<()> {
+        let producer = self.producer_mut(producer_name)?;
+        let name = producer.name().to_string();
+        let result = producer
+            .start()
+            .map_err(|e| AudioError::with_context(format!("producer '{}'", producer_name), e));
+        // Manueller Start ersetzt geplante Neustarts
+        self.watchdog.forget(&name);
+        result
+    }
+
+    /// Stoppt einen Producer; der Watchdog startet ihn nicht wieder.
+    pub fn stop_producer_by_name(&mut self, producer_name: &str) -> AudioResult<()> {
+        let producer = self.producer_mut(producer_name)?;
+        let name = producer.name().to_string();
+        let result = producer
+            .stop()
+            .map_err(|e| AudioError::with_context(format!("producer '{}'", producer_name), e));
+        self.watchdog.hold(&name);
+        result
+    }
+
+    /// Hält einen Producer an der aktuellen Position an (nur abspielende
+    /// Quellen wie Dateien); der Thread und der Buffer bleiben bestehen.
+    pub fn pause_producer_by_name(&mut self, producer_name: &str) -> AudioResult<()> {
+        let producer = self.producer_mut(producer_name)?;
+        producer
+            .pause()
+            .map_err(|e| AudioError::with_context(format!("producer '{}'", producer_name), e))
+    }
+
+    pub fn resume_producer_by_name(&mut self, producer_name: &str) -> AudioResult<()> {
+        let producer = self.producer_mut(producer_name)?;
+        producer
+            .resume()
+            .map_err(|e| AudioError::with_context(format!("producer '{}'", producer_name), e))
+    }
+
+    pub fn set_watchdog_settings(&mut self, settings: WatchdogSettings) {
+        self.watchdog.set_settings(settings);
+    }
+
+    /// Vom Watchdog erfasste Producer mit Fehler und Neustart-Plan.
+    pub fn watchdog_status(&self) -> Vec<WatchdogEntryStatus> {
+        self.watchdog.status(Instant::now())
+    }
+
+    /// Letzter Fehler eines Producers: Start-Fehler aus dem Watchdog, sonst
+    /// der vom Producer gemeldete Laufzeitfehler.
+    pub fn producer_error(&self, producer_name: &str) -> Option<ErrorInfo> {
+        self.watchdog.error_for(producer_name).cloned().or_else(|| {
+            self.producers
+                .iter()
+                .find(|producer| producer.name() == producer_name)
+                .and_then(|producer| producer.last_error())
+        })
+    }
+
+    /// Prüft auf ausgefallene Producer und startet die mit wiederholbarem
+    /// Fehler nach Backoff neu. Wird periodisch aufgerufen (siehe `main`).
+    pub fn run_watchdog(&mut self) {
+        self.run_watchdog_at(Instant::now());
+    }
+
+    pub fn run_watchdog_at(&mut self, now: Instant) {
+        if !self.running.load(Ordering::Relaxed) {
+            return;
+        }
+
+        // Neue Ausfälle: Producer steht und meldet einen Fehler. Reguläres
+        // Ende (z. B. Dateiende) hat keinen Fehler und bleibt unberührt.
+        let failures: Vec<(String, ErrorInfo)> = self
+            .producers
+            .iter()
+            .filter(|producer| {
+                !self.watchdog.is_failed(producer.name())
+                    && !self.watchdog.is_held(producer.name())
+            })
+            .filter(|producer| !producer.status().running)
+            .filter_map(|producer| {
+                producer
+                    .last_error()
+                    .map(|error| (producer.name().to_string(), error))
+            })
+            .collect();
+        for (name, error) in failures {
+            self.report_producer_failure(&name, error, now);
+        }
+
+        for name in self.watchdog.take_due(now) {
+            let Some(producer) = self.producers.iter_mut().find(|p| p.name() == name) else {
+                self.watchdog.forget(&name);
+                continue;
+            };
+            let _ = producer.stop();
+            match producer.start() {
+                Ok(()) => {
+                    self.watchdog.restarted(&name, now);
+                    self.info(&format!("Watchdog restarted producer '{}'", name));
+                }
+                Err(e) => self.report_producer_failure(&name, ErrorInfo::from_anyhow(&e), now),
+            }
+        }
+
+        for (name, attempts) in self.watchdog.take_recovered(now) {
+            self.info(&format!(
+                "Producer '{}' recovered after {} restart(s)",
+                name, attempts
+            ));
+            EventEmitter::new(self.event_bus.clone(), "producer", &name).emit(
+                EventType::ProducerRecovered,
+                EventPriority::Info,
+                serde_json::json!({
+                    "producer": name,
+                    "attempts": attempts,
+                    "timestamp": crate::core::timestamp::utc_ns_now(),
+                }),
+            );
+        }
+    }
+
+    fn report_producer_failure(&mut self, producer_name: &str, error: ErrorInfo, now: Instant) {
+        let action = self.watchdog.report_failure(producer_name, error.clone(), now);
+        let (priority, retry_in_ms) = match action {
+            WatchdogAction::RetryIn(delay) => {
+                self.warn(&format!(
+                    "Producer '{}' failed ({}): {}; restarting in {} ms",
+                    producer_name,
+                    error.category.as_str(),
+                    error.message,
+                    delay.as_millis()
+                ));
+                (EventPriority::Warning, Some(delay.as_millis() as u64))
+            }
+            WatchdogAction::GiveUp => {
+                self.error(&format!(
+                    "Producer '{}' failed ({}, not retryable): {}",
+                    producer_name,
+                    error.category.as_str(),
+                    error.message
+                ));
+                (EventPriority::Error, None)
+            }
+        };
+        EventEmitter::new(self.event_bus.clone(), "producer", producer_name).emit(
+            EventType::Error,
+            priority,
+            serde_json::json!({
+                "error_type": error.category,
+                "category": error.category,
+                "retryable": error.retryable,
+                "message": error.message,
+                "action": if retry_in_ms.is_some() { "restart" } else { "give_up" },
+                "retry_in_ms": retry_in_ms,
+                "timestamp": crate::core::timestamp::utc_ns_now(),
+            }),
+        );
+    }
+
+    fn producer_mut(&mut self, producer_name: &str) -> AudioResult<&mut Box<dyn super::Producer>> {
+        self.producers
+            .iter_mut()
+            .zip(self.producer_slots.iter())
+            .find(|(p, slot)| p.name() == producer_name || *slot == producer_name)
+            .map(|(producer, _)| producer)
+            .ok_or_else(|| AudioError::ProducerNotFound {
+                name: producer_name.to_string(),
+            })
+    }
+
     pub fn restart_flow_by_name(&mut self, flow_name: &str) -> AudioResult<()> {
         self.stop_flow_by_name(flow_name)?;
         self.start_flow_by_name(flow_name)
     }
 
+    pub fn flow_mut(&mut self, flow_name: &str) -> AudioResult<&mut Flow> {
+        self.flows
+            .iter_mut()
+            .find(|flow| flow.name == flow_name)
+            .ok_or_else(|| AudioError::FlowNotFound {
+                name: flow_name.to_string(),
+            })
+    }
+
+    /// Vor dem Neuaufbau: Consumer mit Zuhörern aus allen Flows lösen und
+    /// höchstens `grace` lang weiterlaufen lassen (`core::drain`). Liefert die
+    /// Anzahl; `connect_draining` verbindet sie danach mit den neuen Flows.
+    pub fn begin_draining(&mut self, grace: Duration) -> usize {
+        let candidates: Vec<DrainCandidate> = self
+            .flows
+            .iter_mut()
+            .flat_map(|flow| flow.take_draining_consumers())
+            .collect();
+        let count = candidates.len();
+        let mut drains = lock_mutex(&self.drains, "airlift_node.begin_draining");
+        for candidate in candidates {
+            drains.drain(candidate, grace);
+        }
+        count
+    }
+
+    /// Speist Drains aus dem Ausgang des neuen Flows gleichen Namens.
+    pub fn connect_draining(&mut self) {
+        let outputs: Vec<(String, Arc<AudioRingBuffer>)> = self
+            .flows
+            .iter()
+            .map(|flow| (flow.name.clone(), flow.output_buffer.clone()))
+            .collect();
+        lock_mutex(&self.drains, "airlift_node.connect_draining").connect(&outputs);
+    }
+
+    pub fn draining_consumers(&self) -> Vec<DrainStatus> {
+        lock_mutex(&self.drains, "airlift_node.draining_consumers").status()
+    }
+
+    /// Beendet alle Drains sofort, z. B. beim Herunterfahren.
+    pub fn stop_draining(&self) {
+        lock_mutex(&self.drains, "airlift_node.stop_draining").stop_all();
+    }
+
     pub fn reset_modules(&mut self) {
         self.running.store(false, Ordering::SeqCst);
         self.start_time = Instant::now();
         self.producers.clear();
         self.producer_buffers.clear();
+        self.producer_slots.clear();
+        self.standby_producers.clear();
+        self.failover_groups.clear();
         self.flows.clear();
+        self.encoded_flows.clear();
         self.buffer_registry = Arc::new(BufferRegistry::new());
     }
 
@@ -1060,9 +2422,10 @@ impl AirliftNode {
     pub fn add_processor_to_flow(
         &mut self,
         flow_index: usize,
-        processor: Box<dyn Processor>,
+        mut processor: Box<dyn Processor>,
     ) -> AudioResult<()> {
         if flow_index < self.flows.len() {
+            processor.attach_buffer_registry(self.buffer_registry());
             self.flows[flow_index].add_processor(processor);
             Ok(())
         } else {
@@ -1075,6 +2438,7 @@ impl AirliftNode {
 
     pub fn start(&mut self) -> AudioResult<()> {
         self.info("Node starting...");
+        buffer_sizing::log_buffer_report(&buffer_sizing::buffer_report(self));
 
         {
             let mut event_bus = lock_mutex(&self.event_bus, "airlift_node.start_event_bus");
@@ -1101,20 +2465,22 @@ impl AirliftNode {
 
         self.running.store(true, Ordering::SeqCst);
 
-        // Producer starten - Namen vorher sammeln
+        // Producer parallel starten (Verbindungsaufbau dauert), Ergebnisse in
+        // Config-Reihenfolge - Namen vorher sammeln
+        let workers = self.startup_workers;
         let producer_names: Vec<String> = self
             .producers
             .iter()
             .map(|p| p.name().to_string())
             .collect();
-        let mut start_errors = Vec::new();
-
-        for (i, producer) in self.producers.iter_mut().enumerate() {
-            let producer_name = &producer_names[i];
-            if let Err(e) = producer.start() {
-                start_errors.push((producer_name.clone(), e));
-            }
-        }
+        let results = parallel::for_each_bounded(&mut self.producers, workers, |producer| {
+            producer.start()
+        });
+        let start_errors: Vec<(String, anyhow::Error)> = producer_names
+            .iter()
+            .zip(results)
+            .filter_map(|(name, result)| result.err().map(|e| (name.clone(), e)))
+            .collect();
 
         // Jetzt loggen (nach mutable borrow)
         for (producer_name, error) in &start_errors {
@@ -1123,6 +2489,10 @@ impl AirliftNode {
                 producer_name, error
             ));
         }
+        let now = Instant::now();
+        for (producer_name, error) in &start_errors {
+            self.report_producer_failure(producer_name, ErrorInfo::from_anyhow(error), now);
+        }
 
         let successful_starts = producer_names.len() - start_errors.len();
         if successful_starts > 0 {
@@ -1132,14 +2502,35 @@ impl AirliftNode {
             ));
         }
 
-        // Flows starten - Namen vorher sammeln
+        for group in self.failover_groups.iter_mut() {
+            group.start();
+        }
+
+        // Flows in zwei Phasen: erst Processing (Upstream-Flows zuerst), die
+        // Consumer eines Flows erst, wenn seine Inputs Audio liefern
+        // (Encoder/Verbindungen sonst mit leeren Buffern)
         let flow_names: Vec<String> = self.flows.iter().map(|f| f.name.clone()).collect();
-        let mut flow_start_errors = Vec::new();
+        let mut flow_start_errors: Vec<(String, AudioError)> = Vec::new();
+
+        let mut started = Vec::new();
+        for level in self.flow_start_levels() {
+            let mut level_flows: Vec<&mut Flow> = self
+                .flows
+                .iter_mut()
+                .enumerate()
+                .filter(|(index, _)| level.contains(index))
+                .map(|(_, flow)| flow)
+                .collect();
+            let results =
+                parallel::for_each_bounded(&mut level_flows, workers, |flow| flow.start_processing());
+            started.extend(level.into_iter().zip(results).filter_map(|(index, ok)| ok.then_some(index)));
+        }
+        self.start_consumers_when_ready(started);
 
-        for (i, flow) in self.flows.iter_mut().enumerate() {
-            let flow_name = &flow_names[i];
-            if let Err(e) = flow.start() {
-                flow_start_errors.push((flow_name.clone(), e));
+        let results = parallel::for_each_bounded(&mut self.encoded_flows, workers, |flow| flow.start());
+        for (flow, result) in self.encoded_flows.iter().zip(results) {
+            if let Err(e) = result {
+                flow_start_errors.push((flow.name.clone(), e));
             }
         }
 
@@ -1148,7 +2539,8 @@ impl AirliftNode {
             self.warn(&format!("Failed to start flow '{}': {}", flow_name, error));
         }
 
-        let successful_flows = flow_names.len() - flow_start_errors.len();
+        let successful_flows =
+            flow_names.len() + self.encoded_flows.len() - flow_start_errors.len();
         if successful_flows > 0 {
             self.info(&format!(
                 "{} flow(s) started successfully",
@@ -1180,14 +2572,23 @@ impl AirliftNode {
         );
 
         self.running.store(false, Ordering::SeqCst);
+        self.watchdog.clear();
 
-        // Flows stoppen - Namen vorher sammeln
+        // Flows parallel stoppen - Namen vorher sammeln
+        let workers = self.startup_workers;
         let flow_names: Vec<String> = self.flows.iter().map(|f| f.name.clone()).collect();
         let mut flow_stop_errors = Vec::new();
 
-        for (i, flow) in self.flows.iter_mut().enumerate() {
-            let flow_name = &flow_names[i];
-            if let Err(e) = flow.stop() {
+        let results = parallel::for_each_bounded(&mut self.encoded_flows, workers, |flow| flow.stop());
+        for (flow, result) in self.encoded_flows.iter().zip(results) {
+            if let Err(e) = result {
+                flow_stop_errors.push((flow.name.clone(), e));
+            }
+        }
+
+        let results = parallel::for_each_bounded(&mut self.flows, workers, |flow| flow.stop());
+        for (flow_name, result) in flow_names.iter().zip(results) {
+            if let Err(e) = result {
                 flow_stop_errors.push((flow_name.clone(), e));
             }
         }
@@ -1197,7 +2598,8 @@ impl AirliftNode {
             self.warn(&format!("Error stopping flow '{}': {}", flow_name, error));
         }
 
-        let successful_flows = flow_names.len() - flow_stop_errors.len();
+        let successful_flows =
+            flow_names.len() + self.encoded_flows.len() - flow_stop_errors.len();
         if successful_flows > 0 {
             self.info(&format!(
                 "{} flow(s) stopped successfully",
@@ -1205,20 +2607,24 @@ impl AirliftNode {
             ));
         }
 
+        for group in self.failover_groups.iter_mut() {
+            group.stop();
+        }
+
         // Producer stoppen - Namen vorher sammeln
         let producer_names: Vec<String> = self
             .producers
             .iter()
             .map(|p| p.name().to_string())
             .collect();
-        let mut producer_stop_errors = Vec::new();
-
-        for (i, producer) in self.producers.iter_mut().enumerate() {
-            let producer_name = &producer_names[i];
-            if let Err(e) = producer.stop() {
-                producer_stop_errors.push((producer_name.clone(), e));
-            }
-        }
+        let results = parallel::for_each_bounded(&mut self.producers, workers, |producer| {
+            producer.stop()
+        });
+        let producer_stop_errors: Vec<(String, anyhow::Error)> = producer_names
+            .iter()
+            .zip(results)
+            .filter_map(|(name, result)| result.err().map(|e| (name.clone(), e)))
+            .collect();
 
         // Loggen
         for (producer_name, error) in &producer_stop_errors {
@@ -1292,10 +2698,11 @@ impl AirliftNode {
 
     /// Entfernt einen Producer und den zugehörigen Buffer aus der Registry
     pub fn remove_producer(&mut self, producer_name: &str) -> AudioResult<()> {
-        // Finde den Index des Producers
+        // Finde den Index des Producers (Name oder Slot)
         let index = self.producers
             .iter()
-            .position(|p| p.name() == producer_name)
+            .zip(self.producer_slots.iter())
+            .position(|(p, slot)| p.name() == producer_name || slot == producer_name)
             .ok_or_else(|| AudioError::ProducerNotFound {
                 name: producer_name.to_string(),
             })?;
@@ -1308,12 +2715,15 @@ impl AirliftNode {
             }
         }
 
-        // Entferne Producer und seinen Buffer
+        // Entferne Producer, seinen Buffer und zugehörige Standby-Producer
+        self.watchdog.forget(self.producers[index].name());
         self.producers.remove(index);
         self.producer_buffers.remove(index);
+        let slot = self.producer_slots.remove(index);
+        self.standby_producers.retain(|standby| standby.slot != slot);
 
         // Entferne Buffer aus der Registry
-        let buffer_name = format!("producer:{}", producer_name);
+        let buffer_name = format!("producer:{}", slot);
         if let Err(e) = self.buffer_registry.remove(&buffer_name) {
             self.warn(&format!("Failed to remove buffer '{}' from registry: {}", buffer_name, e));
         }
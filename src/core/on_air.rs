@@ -0,0 +1,176 @@
+// src/core/on_air.rs
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::core::timestamp::utc_ns_now;
+
+/// Wie lange ein Off-Air-Token gültig bleibt.
+const OFF_AIR_TOKEN_TTL: Duration = Duration::from_secs(30);
+
+static TOKEN_COUNTER: AtomicU64 = AtomicU64::new(1);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OnAirState {
+    OffAir,
+    OnAir,
+}
+
+impl OnAirState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OnAirState::OffAir => "off_air",
+            OnAirState::OnAir => "on_air",
+        }
+    }
+}
+
+/// Gründe, die einen Wechsel auf On-Air verhindern.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum OnAirInterlock {
+    Silence,
+    EncoderUnhealthy { consumer: String },
+}
+
+impl std::fmt::Display for OnAirInterlock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OnAirInterlock::Silence => write!(f, "silence detected"),
+            OnAirInterlock::EncoderUnhealthy { consumer } => {
+                write!(f, "consumer '{}' unhealthy", consumer)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct PendingOffAir {
+    token: String,
+    expires_ns: u64,
+}
+
+/// Optionaler GPIO-Ausgang (sysfs `value`-Datei), der den On-Air-Zustand spiegelt.
+#[derive(Debug, Clone)]
+pub struct OnAirGpio {
+    path: PathBuf,
+}
+
+impl OnAirGpio {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn write(&self, state: OnAirState) -> std::io::Result<()> {
+        let value = match state {
+            OnAirState::OnAir => "1\n",
+            OnAirState::OffAir => "0\n",
+        };
+        fs::write(&self.path, value)
+    }
+}
+
+/// Zustandsmaschine für On-Air/Off-Air eines Flows.
+///
+/// On-Air nur ohne aktive Interlocks; Off-Air erfordert ein Bestätigungs-Token,
+/// das zuvor über `request_off_air` angefordert wurde.
+#[derive(Debug)]
+pub struct OnAirController {
+    state: OnAirState,
+    pending_off_air: Option<PendingOffAir>,
+    gpio: Option<OnAirGpio>,
+    changed_ns: u64,
+}
+
+impl OnAirController {
+    pub fn new() -> Self {
+        Self {
+            state: OnAirState::OffAir,
+            pending_off_air: None,
+            gpio: None,
+            changed_ns: 0,
+        }
+    }
+
+    pub fn state(&self) -> OnAirState {
+        self.state
+    }
+
+    pub fn changed_ns(&self) -> u64 {
+        self.changed_ns
+    }
+
+    pub fn set_gpio(&mut self, gpio: Option<OnAirGpio>) {
+        self.gpio = gpio;
+        self.sync_gpio();
+    }
+
+    /// Wechselt auf On-Air, sofern keine Interlocks aktiv sind.
+    pub fn go_on_air(&mut self, interlocks: &[OnAirInterlock]) -> Result<bool, String> {
+        if !interlocks.is_empty() {
+            let reasons: Vec<String> = interlocks.iter().map(|i| i.to_string()).collect();
+            return Err(reasons.join(", "));
+        }
+        Ok(self.transition(OnAirState::OnAir))
+    }
+
+    /// Erzeugt ein Token, mit dem der Wechsel auf Off-Air bestätigt werden muss.
+    pub fn request_off_air(&mut self) -> String {
+        let token = format!(
+            "{:x}{:04x}",
+            utc_ns_now(),
+            TOKEN_COUNTER.fetch_add(1, Ordering::Relaxed) & 0xffff
+        );
+        self.pending_off_air = Some(PendingOffAir {
+            token: token.clone(),
+            expires_ns: utc_ns_now() + OFF_AIR_TOKEN_TTL.as_nanos() as u64,
+        });
+        token
+    }
+
+    pub fn confirm_off_air(&mut self, token: &str) -> Result<bool, String> {
+        let pending = self
+            .pending_off_air
+            .take()
+            .ok_or_else(|| "no off-air request pending".to_string())?;
+
+        if utc_ns_now() > pending.expires_ns {
+            return Err("off-air token expired".to_string());
+        }
+        if pending.token != token {
+            self.pending_off_air = Some(pending);
+            return Err("invalid off-air token".to_string());
+        }
+
+        Ok(self.transition(OnAirState::OffAir))
+    }
+
+    fn transition(&mut self, next: OnAirState) -> bool {
+        if self.state == next {
+            return false;
+        }
+        self.state = next;
+        self.changed_ns = utc_ns_now();
+        self.pending_off_air = None;
+        self.sync_gpio();
+        true
+    }
+
+    fn sync_gpio(&self) {
+        if let Some(gpio) = &self.gpio {
+            if let Err(e) = gpio.write(self.state) {
+                log::warn!("Failed to write on-air GPIO {:?}: {}", gpio.path, e);
+            }
+        }
+    }
+}
+
+impl Default for OnAirController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,64 @@
+// src/core/parallel.rs
+//
+// Paralleles Starten/Stoppen unabhängiger Komponenten (Producer, Flows) mit
+// begrenzter Worker-Zahl. Ergebnisse kommen in Eingabereihenfolge zurück, damit
+// die zusammenfassenden Logs unabhängig vom Thread-Timing gleich bleiben.
+use std::sync::Mutex;
+
+use crate::core::correlation::{current_correlation_id, CorrelationScope};
+use crate::core::lock::lock_mutex;
+
+/// Obergrenze für `startup.workers`
+pub const MAX_STARTUP_WORKERS: usize = 64;
+
+/// Standard: Anzahl CPUs, mindestens 2, höchstens 8 – Starts warten meist
+/// auf Netzwerk/Geräte, nicht auf CPU.
+pub fn default_workers() -> usize {
+    std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(2)
+        .clamp(2, 8)
+}
+
+/// Ruft `f` für jedes Element mit höchstens `workers` Threads gleichzeitig auf.
+/// Bei `workers <= 1` oder einem Element läuft alles im aufrufenden Thread.
+/// Die Worker übernehmen die Korrelations-ID des Aufrufers.
+pub fn for_each_bounded<T, R, F>(items: &mut [T], workers: usize, f: F) -> Vec<R>
+where
+    T: Send,
+    R: Send,
+    F: Fn(&mut T) -> R + Sync,
+{
+    let workers = workers.clamp(1, MAX_STARTUP_WORKERS).min(items.len());
+    if workers <= 1 {
+        return items.iter_mut().map(f).collect();
+    }
+
+    let len = items.len();
+    let queue = Mutex::new(items.iter_mut().enumerate());
+    let results: Mutex<Vec<Option<R>>> = Mutex::new((0..len).map(|_| None).collect());
+    let correlation = current_correlation_id();
+
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| {
+                let _correlation = correlation.as_deref().map(CorrelationScope::with_id);
+                loop {
+                    let next = lock_mutex(&queue, "parallel.queue").next();
+                    let Some((index, item)) = next else {
+                        break;
+                    };
+                    let result = f(item);
+                    lock_mutex(&results, "parallel.results")[index] = Some(result);
+                }
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .into_iter()
+        .map(|result| result.expect("every item is processed exactly once"))
+        .collect()
+}
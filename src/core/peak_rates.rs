@@ -0,0 +1,370 @@
+// src/core/peak_rates.rs
+//
+// Pegelmessung eines Flows in mehreren Zeitauflösungen aus einem einzigen
+// Abgriff: schnelle Meter-Fenster (Standard 50 ms) für die UI, daraus
+// zusammengefasste Statistik-Fenster (1 s) für die Peak-History und
+// Aggregate (10 s) für Archiv/Metriken. Die langsamen Stufen rechnen nur
+// die geschlossenen Fenster der schnelleren Stufe zusammen, es gibt also
+// keinen zusätzlichen Reader am Ringbuffer und keinen zweiten Sample-Durchlauf.
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::config::ConfigValues;
+use crate::core::event_bus::EventEmitter;
+use crate::core::events::{EventPriority, EventType};
+use crate::core::lock::lock_mutex;
+use crate::ring::PcmFrame;
+
+/// Darunter (linear, 1.0 = Vollaussteuerung) gilt ein Fenster als still.
+pub const SILENCE_THRESHOLD: f32 = 0.001;
+
+/// Zeitauflösung eines Pegel-Fensters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PeakTier {
+    /// Schnelle Anzeige (`AudioPeak`)
+    Meter,
+    /// Peak-History (`AudioLevelStats`, `rate = "stats"`)
+    Stats,
+    /// Archiv/Metriken (`AudioLevelStats`, `rate = "aggregate"`)
+    Aggregate,
+}
+
+impl PeakTier {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PeakTier::Meter => "meter",
+            PeakTier::Stats => "stats",
+            PeakTier::Aggregate => "aggregate",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PeakRates {
+    pub meter: Duration,
+    pub stats: Duration,
+    pub aggregate: Duration,
+}
+
+impl Default for PeakRates {
+    fn default() -> Self {
+        Self {
+            meter: Duration::from_millis(50),
+            stats: Duration::from_secs(1),
+            aggregate: Duration::from_secs(10),
+        }
+    }
+}
+
+impl PeakRates {
+    /// Liest `{ meter = "50ms", stats = "1s", aggregate = "10s" }` aus
+    /// `flows.<name>.config.peak_rates`. Jede Stufe muss ein Vielfaches der
+    /// schnelleren sein, weil sie aus deren Fenstern zusammengesetzt wird.
+    pub fn from_config(flow_name: &str, value: &serde_json::Value) -> anyhow::Result<Self> {
+        Self::parse("flow", flow_name, value)
+    }
+
+    /// Wie `from_config`, für andere Module mit `config.peak_rates`
+    /// (z. B. der `level_meter`-Processor).
+    pub fn parse(kind: &str, name: &str, value: &serde_json::Value) -> anyhow::Result<Self> {
+        let map: HashMap<String, serde_json::Value> = match value {
+            serde_json::Value::Object(map) => map.clone().into_iter().collect(),
+            serde_json::Value::Bool(true) => HashMap::new(),
+            other => anyhow::bail!(
+                "{} '{}': config.peak_rates must be a table, got {}",
+                kind,
+                name,
+                other
+            ),
+        };
+        let values = ConfigValues::new(kind, name, &map);
+        let defaults = Self::default();
+
+        let meter = values.duration("meter")?.unwrap_or(defaults.meter);
+        values.check_range("meter", meter.as_millis() as u64, 10, 1_000)?;
+        let stats = values.duration("stats")?.unwrap_or(defaults.stats);
+        values.check_range(
+            "stats",
+            stats.as_millis() as u64,
+            meter.as_millis() as u64,
+            60_000,
+        )?;
+        let aggregate = values.duration("aggregate")?.unwrap_or(defaults.aggregate);
+        values.check_range(
+            "aggregate",
+            aggregate.as_millis() as u64,
+            stats.as_millis() as u64,
+            3_600_000,
+        )?;
+
+        for (key, slow, fast) in [("stats", stats, meter), ("aggregate", aggregate, stats)] {
+            if slow.as_millis() % fast.as_millis() != 0 {
+                anyhow::bail!(
+                    "{} '{}': config.peak_rates.{} ({} ms) must be a multiple of {} ms",
+                    kind,
+                    name,
+                    key,
+                    slow.as_millis(),
+                    fast.as_millis()
+                );
+            }
+        }
+
+        Ok(Self {
+            meter,
+            stats,
+            aggregate,
+        })
+    }
+
+    pub fn window(&self, tier: PeakTier) -> Duration {
+        match tier {
+            PeakTier::Meter => self.meter,
+            PeakTier::Stats => self.stats,
+            PeakTier::Aggregate => self.aggregate,
+        }
+    }
+}
+
+/// Ein geschlossenes Messfenster (Werte linear, 1.0 = Vollaussteuerung).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LevelWindow {
+    pub tier: PeakTier,
+    pub window_ms: u64,
+    /// Audio-Zeitstempel am Fensterende
+    pub utc_ns: u64,
+    pub peaks: [f32; 2],
+    pub rms: [f32; 2],
+    /// Samples auf Vollaussteuerung
+    pub clipped: u64,
+    pub silence: bool,
+    /// Sample-Frames im Fenster
+    pub frames: u64,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Accumulator {
+    peaks: [f32; 2],
+    sum_sq: [f64; 2],
+    clipped: u64,
+    frames: u64,
+}
+
+impl Accumulator {
+    fn merge(&mut self, other: &Accumulator) {
+        for channel in 0..2 {
+            self.peaks[channel] = self.peaks[channel].max(other.peaks[channel]);
+            self.sum_sq[channel] += other.sum_sq[channel];
+        }
+        self.clipped += other.clipped;
+        self.frames += other.frames;
+    }
+
+    fn close(&mut self, tier: PeakTier, window: Duration, utc_ns: u64) -> LevelWindow {
+        let frames = self.frames.max(1) as f64;
+        let level = LevelWindow {
+            tier,
+            window_ms: window.as_millis() as u64,
+            utc_ns,
+            peaks: self.peaks,
+            rms: self.sum_sq.map(|sum| (sum / frames).sqrt() as f32),
+            clipped: self.clipped,
+            silence: self.peaks.iter().all(|peak| *peak < SILENCE_THRESHOLD),
+            frames: self.frames,
+        };
+        *self = Accumulator::default();
+        level
+    }
+}
+
+/// Fenster-Berechnung ohne Seiteneffekte; `PeakTap` hängt sie an den Flow.
+pub struct MultiRatePeaks {
+    rates: PeakRates,
+    sample_rate: u32,
+    /// Fenstergröße je Stufe in Sample-Frames
+    window_frames: [u64; 3],
+    tiers: [Accumulator; 3],
+}
+
+impl MultiRatePeaks {
+    pub fn new(rates: PeakRates) -> Self {
+        Self {
+            rates,
+            sample_rate: 0,
+            window_frames: [0; 3],
+            tiers: [Accumulator::default(); 3],
+        }
+    }
+
+    pub fn rates(&self) -> PeakRates {
+        self.rates
+    }
+
+    fn reset(&mut self, sample_rate: u32) {
+        let frames = |window: Duration| {
+            (window.as_nanos() as u64 * sample_rate as u64 / 1_000_000_000).max(1)
+        };
+        self.sample_rate = sample_rate;
+        self.window_frames = [
+            frames(self.rates.meter),
+            frames(self.rates.stats),
+            frames(self.rates.aggregate),
+        ];
+        self.tiers = [Accumulator::default(); 3];
+    }
+
+    /// Liefert alle Fenster, die mit diesem Frame voll werden, in
+    /// zeitlicher Reihenfolge (eine langsame Stufe direkt nach dem
+    /// Meter-Fenster, das sie abschließt).
+    pub fn push(&mut self, frame: &PcmFrame) -> Vec<LevelWindow> {
+        let channels = frame.channels as usize;
+        if channels == 0 || frame.sample_rate == 0 {
+            return Vec::new();
+        }
+        if frame.sample_rate != self.sample_rate {
+            // Angefangene Fenster wären bei anderer Rate falsch lang
+            self.reset(frame.sample_rate);
+        }
+
+        let mut closed = Vec::new();
+        for (offset, sample_frame) in frame.samples.chunks_exact(channels).enumerate() {
+            let meter = &mut self.tiers[0];
+            for channel in 0..2 {
+                let sample = sample_frame[channel.min(channels - 1)];
+                let value = (sample as f32).abs() / 32768.0;
+                meter.peaks[channel] = meter.peaks[channel].max(value);
+                meter.sum_sq[channel] += (value as f64) * (value as f64);
+            }
+            meter.clipped += sample_frame[..channels.min(2)]
+                .iter()
+                .filter(|sample| **sample == i16::MAX || **sample == i16::MIN)
+                .count() as u64;
+            meter.frames += 1;
+
+            if meter.frames < self.window_frames[0] {
+                continue;
+            }
+            let utc_ns =
+                frame.utc_ns + (offset as u64 + 1) * 1_000_000_000 / frame.sample_rate as u64;
+            let mut fast = self.tiers[0];
+            closed.push(self.tiers[0].close(PeakTier::Meter, self.rates.meter, utc_ns));
+            for (index, tier) in [(1, PeakTier::Stats), (2, PeakTier::Aggregate)] {
+                self.tiers[index].merge(&fast);
+                if self.tiers[index].frames < self.window_frames[index] {
+                    break;
+                }
+                fast = self.tiers[index];
+                closed.push(self.tiers[index].close(tier, self.rates.window(tier), utc_ns));
+            }
+        }
+        closed
+    }
+}
+
+/// Zuletzt geschlossene Fenster je Stufe, für Status und `/metrics`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct FlowLevels {
+    pub meter: Option<LevelWindow>,
+    pub stats: Option<LevelWindow>,
+    pub aggregate: Option<LevelWindow>,
+}
+
+/// Läuft im Processing-Thread eines Flows: misst die eingelesenen Frames,
+/// pflegt Stille-Flag und `FlowLevels` und veröffentlicht die Fenster.
+pub struct PeakTap {
+    flow: String,
+    peaks: MultiRatePeaks,
+    silence: Arc<AtomicBool>,
+    levels: Arc<Mutex<FlowLevels>>,
+    emitter: Option<EventEmitter>,
+    pending: Vec<LevelWindow>,
+}
+
+impl PeakTap {
+    pub fn new(
+        flow: &str,
+        rates: PeakRates,
+        silence: Arc<AtomicBool>,
+        levels: Arc<Mutex<FlowLevels>>,
+    ) -> Self {
+        *lock_mutex(&levels, "peak_tap.reset") = FlowLevels::default();
+        Self {
+            flow: flow.to_string(),
+            peaks: MultiRatePeaks::new(rates),
+            silence,
+            levels,
+            emitter: None,
+            pending: Vec::new(),
+        }
+    }
+
+    pub fn with_emitter(mut self, emitter: EventEmitter) -> Self {
+        self.emitter = Some(emitter);
+        self
+    }
+
+    pub fn push(&mut self, frame: &PcmFrame) {
+        // Signal beendet die Stille sofort, nicht erst mit dem nächsten
+        // Meter-Fenster; in die Stille geht es weiterhin fensterweise.
+        if frame
+            .samples
+            .iter()
+            .any(|sample| (*sample as f32).abs() / 32768.0 >= SILENCE_THRESHOLD)
+        {
+            self.silence.store(false, Ordering::Relaxed);
+        }
+        let closed = self.peaks.push(frame);
+        if closed.is_empty() {
+            return;
+        }
+        let mut levels = lock_mutex(&self.levels, "peak_tap.push");
+        for window in &closed {
+            let slot = match window.tier {
+                PeakTier::Meter => {
+                    self.silence.store(window.silence, Ordering::Relaxed);
+                    &mut levels.meter
+                }
+                PeakTier::Stats => &mut levels.stats,
+                PeakTier::Aggregate => &mut levels.aggregate,
+            };
+            *slot = Some(window.clone());
+        }
+        drop(levels);
+        if self.emitter.is_some() {
+            self.pending.extend(closed);
+        }
+    }
+
+    /// Veröffentlicht die seit dem letzten Aufruf geschlossenen Fenster:
+    /// Meter als `AudioPeak`, Stats/Aggregate als `AudioLevelStats`.
+    pub fn publish(&mut self) {
+        let Some(emitter) = &self.emitter else {
+            return;
+        };
+        for window in self.pending.drain(..) {
+            let mut payload = serde_json::json!({
+                "timestamp": window.utc_ns,
+                "window_ms": window.window_ms,
+                "peaks": window.peaks,
+                "rms": window.rms,
+                "clipped": window.clipped,
+                "silence": window.silence,
+                "flow": self.flow,
+            });
+            let event_type = match window.tier {
+                PeakTier::Meter => EventType::AudioPeak,
+                tier => {
+                    payload["rate"] = serde_json::json!(tier.as_str());
+                    payload["frames"] = serde_json::json!(window.frames);
+                    EventType::AudioLevelStats
+                }
+            };
+            emitter.emit(event_type, EventPriority::Debug, payload);
+        }
+    }
+}
@@ -0,0 +1,236 @@
+// src/core/pre_roll.rs
+//
+// Ausgelöste Aufnahmen mit Vorlauf für den FileConsumer (`config.pre_roll`):
+// Der Consumer läuft scharf, schreibt aber erst, wenn die Aufnahme ausgelöst
+// wird – von Hand (`consumer.configure` mit `record`), per Zeitplan oder durch
+// ein Event (`record_on`). Die Datei beginnt dann mit den letzten `pre_roll`
+// Sekunden vor dem Auslöser, damit der Anlass eines Vorfalls mit drauf ist.
+// Der Vorlauf liegt als PCM im Speicher; kodiert wird erst beim Schreiben,
+// weil jede Datei ihre eigenen Stream-Header braucht.
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::bail;
+use serde_json::Value;
+
+use crate::config::ConfigValues;
+use crate::core::lock::lock_mutex;
+use crate::core::{Event, EventHandler, EventType, PcmFrame};
+
+/// Längster Vorlauf (PCM, 48 kHz Stereo: ca. 11,5 MB pro Minute)
+pub const MAX_PRE_ROLL: Duration = Duration::from_secs(600);
+/// Aufnahmedauer nach einem Event, wenn `record_for` fehlt
+pub const DEFAULT_RECORD_FOR: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone)]
+pub struct PreRoll {
+    /// Vorlauf vor dem Auslöser (`pre_roll`)
+    pub duration: Duration,
+    /// Events, die eine Aufnahme auslösen (`record_on`)
+    pub record_on: Vec<EventType>,
+    /// Aufnahmedauer nach dem letzten auslösenden Event (`record_for`)
+    pub record_for: Duration,
+}
+
+/// Gelesene `config`-Schlüssel (Strict-Modus, siehe `config::options`)
+pub const CONFIG_KEYS: &[&str] = &["pre_roll", "record_on", "record_for"];
+
+impl PreRoll {
+    /// `config.pre_roll` ("10s"), `config.record_on` (Event-Typ oder Liste)
+    /// und `config.record_for`; ohne `pre_roll` nimmt der Consumer durchgehend auf.
+    pub fn from_config(
+        consumer: &str,
+        config: &HashMap<String, Value>,
+    ) -> anyhow::Result<Option<Self>> {
+        let values = ConfigValues::new("consumer", consumer, config);
+        let record_on = match config.get("record_on") {
+            None => Vec::new(),
+            Some(Value::String(name)) => vec![name.clone()],
+            Some(Value::Array(names)) => names
+                .iter()
+                .map(|name| {
+                    name.as_str().map(str::to_string).ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "consumer '{}': config.record_on must list event types",
+                            consumer
+                        )
+                    })
+                })
+                .collect::<anyhow::Result<_>>()?,
+            Some(_) => bail!(
+                "consumer '{}': config.record_on must list event types",
+                consumer
+            ),
+        };
+        let record_for = values.duration("record_for")?;
+
+        let Some(duration) = values.duration("pre_roll")? else {
+            if !record_on.is_empty() || record_for.is_some() {
+                bail!(
+                    "consumer '{}': config.record_on needs config.pre_roll",
+                    consumer
+                );
+            }
+            return Ok(None);
+        };
+        values.check_range("pre_roll", duration.as_secs(), 0, MAX_PRE_ROLL.as_secs())?;
+        if record_on.iter().any(|name| name.trim().is_empty()) {
+            bail!(
+                "consumer '{}': config.record_on must not contain empty names",
+                consumer
+            );
+        }
+        let record_for = record_for.unwrap_or(DEFAULT_RECORD_FOR);
+        if record_for.is_zero() {
+            bail!("consumer '{}': config.record_for must be > 0", consumer);
+        }
+        Ok(Some(Self {
+            duration,
+            record_on: record_on
+                .iter()
+                .map(|name| EventType::from_name(name.trim()))
+                .collect(),
+            record_for,
+        }))
+    }
+}
+
+/// Rollender Vorlauf: die jüngsten Frames, zusammen mindestens `limit` lang.
+pub struct PreRollBuffer {
+    limit_ns: u64,
+    frames: VecDeque<PcmFrame>,
+    held_ns: u64,
+}
+
+fn frame_ns(frame: &PcmFrame) -> u64 {
+    let channels = frame.channels.max(1) as u64;
+    let rate = frame.sample_rate.max(1) as u64;
+    frame.samples.len() as u64 / channels * 1_000_000_000 / rate
+}
+
+impl PreRollBuffer {
+    pub fn new(limit: Duration) -> Self {
+        Self {
+            limit_ns: limit.as_nanos() as u64,
+            frames: VecDeque::new(),
+            held_ns: 0,
+        }
+    }
+
+    pub fn push(&mut self, frame: PcmFrame) {
+        if self.limit_ns == 0 {
+            return;
+        }
+        self.held_ns += frame_ns(&frame);
+        self.frames.push_back(frame);
+        // Ältesten Frame nur verwerfen, wenn der Rest die Länge noch abdeckt
+        while let Some(oldest) = self.frames.front() {
+            let oldest_ns = frame_ns(oldest);
+            if self.held_ns - oldest_ns < self.limit_ns {
+                break;
+            }
+            self.held_ns -= oldest_ns;
+            self.frames.pop_front();
+        }
+    }
+
+    /// Gehaltene Audiodauer
+    pub fn held(&self) -> Duration {
+        Duration::from_nanos(self.held_ns)
+    }
+
+    /// Zeitstempel des ältesten Frames (0 = unbekannt)
+    pub fn first_utc_ns(&self) -> Option<u64> {
+        self.frames.front().map(|frame| frame.utc_ns)
+    }
+
+    /// Gibt alle Frames in Reihenfolge heraus und leert den Vorlauf.
+    pub fn take(&mut self) -> Vec<PcmFrame> {
+        self.held_ns = 0;
+        self.frames.drain(..).collect()
+    }
+}
+
+#[derive(Debug, Default)]
+struct TriggerState {
+    manual: bool,
+    hold_until: Option<Instant>,
+}
+
+/// Auslöser einer Aufnahme, geteilt zwischen Schreib-Thread, Steuerung und
+/// Event-Handler.
+#[derive(Debug, Default)]
+pub struct RecordTrigger {
+    state: Mutex<TriggerState>,
+}
+
+impl RecordTrigger {
+    /// Von Hand bzw. per Zeitplan starten; `false` beendet auch eine per
+    /// Event ausgelöste Aufnahme.
+    pub fn set_manual(&self, on: bool) {
+        let mut state = lock_mutex(&self.state, "pre_roll.set_manual");
+        state.manual = on;
+        if !on {
+            state.hold_until = None;
+        }
+    }
+
+    /// Aufnahme mindestens bis `until`; weitere Events verlängern.
+    pub fn hold(&self, until: Instant) {
+        let mut state = lock_mutex(&self.state, "pre_roll.hold");
+        if state.hold_until.is_none_or(|current| current < until) {
+            state.hold_until = Some(until);
+        }
+    }
+
+    pub fn active(&self, now: Instant) -> bool {
+        let state = lock_mutex(&self.state, "pre_roll.active");
+        state.manual || state.hold_until.is_some_and(|until| now < until)
+    }
+}
+
+/// Löst bei passenden Events eine Aufnahme für `record_for` aus.
+pub struct RecordTriggerHandler {
+    name: String,
+    consumer: String,
+    record_on: Vec<EventType>,
+    record_for: Duration,
+    trigger: Arc<RecordTrigger>,
+}
+
+impl RecordTriggerHandler {
+    pub fn new(consumer: &str, pre_roll: &PreRoll, trigger: Arc<RecordTrigger>) -> Self {
+        Self {
+            name: format!("record_trigger:{}", consumer),
+            consumer: consumer.to_string(),
+            record_on: pre_roll.record_on.clone(),
+            record_for: pre_roll.record_for,
+            trigger,
+        }
+    }
+}
+
+impl EventHandler for RecordTriggerHandler {
+    fn handle_event(&self, event: &Event) -> anyhow::Result<()> {
+        let now = Instant::now();
+        if !self.trigger.active(now) {
+            log::info!(
+                "FileConsumer '{}': recording triggered by {} from {}",
+                self.consumer,
+                event.event_type_str(),
+                event.source_instance
+            );
+        }
+        self.trigger.hold(now + self.record_for);
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn event_type_filter(&self) -> Option<Vec<EventType>> {
+        Some(self.record_on.clone())
+    }
+}
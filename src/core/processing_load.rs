@@ -0,0 +1,322 @@
+// src/core/processing_load.rs
+//
+// Rechenzeit der Processor-Kette eines Flows: Echtzeitfaktor (Rechenzeit /
+// verarbeitete Audiodauer) je Messfenster und Überlastverhalten, damit ein zu
+// langsamer Flow nicht unbemerkt immer weiter zurückfällt.
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::config::ConfigValues;
+use crate::core::event_bus::EventEmitter;
+use crate::core::events::{EventPriority, EventType};
+use crate::core::lock::lock_mutex;
+use crate::core::timestamp::utc_ns_now;
+use crate::ring::PcmFrame;
+
+/// Audiodauer, über die ein Echtzeitfaktor gemittelt wird.
+pub const LOAD_WINDOW: Duration = Duration::from_millis(500);
+
+/// Was der Flow bei Überlast tut.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverloadAction {
+    /// Nur Event und Status, die Kette läuft unverändert weiter
+    Alert,
+    /// Processors aus `optional` werden übersprungen (Frames unverändert weiter)
+    SkipOptional,
+    /// Ganze Kette umgehen, wie beim Bypass
+    Passthrough,
+}
+
+impl OverloadAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OverloadAction::Alert => "alert",
+            OverloadAction::SkipOptional => "skip_optional",
+            OverloadAction::Passthrough => "passthrough",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessingBudget {
+    /// Erlaubter Echtzeitfaktor, 0.8 = 80 % der Audiodauer
+    pub limit: f32,
+    /// Darunter gilt der Flow wieder als erholt
+    pub recover: f32,
+    /// So lange muss `limit` über- bzw. `recover` unterschritten sein
+    pub hold: Duration,
+    pub action: OverloadAction,
+    /// Processors, die `skip_optional` auslassen darf
+    pub optional: Vec<String>,
+}
+
+impl Default for ProcessingBudget {
+    fn default() -> Self {
+        Self {
+            limit: 0.8,
+            recover: 0.5,
+            hold: Duration::from_secs(2),
+            action: OverloadAction::Alert,
+            optional: Vec::new(),
+        }
+    }
+}
+
+impl ProcessingBudget {
+    /// Liest `{ limit = 0.8, recover = 0.5, hold = "2s", action = "alert",
+    /// optional = ["meter"] }` aus `flows.<name>.config.overload`.
+    /// `processors` sind die Processors des Flows (für `optional`).
+    pub fn from_config(
+        flow_name: &str,
+        value: &serde_json::Value,
+        processors: &[String],
+    ) -> anyhow::Result<Self> {
+        let map: HashMap<String, serde_json::Value> = match value {
+            serde_json::Value::Object(map) => map.clone().into_iter().collect(),
+            serde_json::Value::Bool(true) => HashMap::new(),
+            other => anyhow::bail!(
+                "flow '{}': config.overload must be a table, got {}",
+                flow_name,
+                other
+            ),
+        };
+        let values = ConfigValues::new("flow", flow_name, &map);
+        let defaults = Self::default();
+
+        let limit = values.f64("limit")?.map(|v| v as f32).unwrap_or(defaults.limit);
+        values.check_range("limit", limit, 0.01, 1.0)?;
+        let recover = values
+            .f64("recover")?
+            .map(|v| v as f32)
+            .unwrap_or(defaults.recover.min(limit));
+        values.check_range("recover", recover, 0.0, limit)?;
+
+        let action = match map.get("action").and_then(|v| v.as_str()) {
+            None | Some("alert") => OverloadAction::Alert,
+            Some("skip_optional") => OverloadAction::SkipOptional,
+            Some("passthrough") => OverloadAction::Passthrough,
+            Some(other) => anyhow::bail!(
+                "flow '{}': config.overload.action must be \"alert\", \"skip_optional\" or \"passthrough\", got \"{}\"",
+                flow_name,
+                other
+            ),
+        };
+
+        let optional: Vec<String> = match map.get("optional") {
+            None => Vec::new(),
+            Some(serde_json::Value::Array(items)) => items
+                .iter()
+                .map(|item| {
+                    item.as_str().map(str::to_string).ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "flow '{}': config.overload.optional must list processor names",
+                            flow_name
+                        )
+                    })
+                })
+                .collect::<anyhow::Result<_>>()?,
+            Some(other) => anyhow::bail!(
+                "flow '{}': config.overload.optional must be a list, got {}",
+                flow_name,
+                other
+            ),
+        };
+        if let Some(unknown) = optional.iter().find(|name| !processors.contains(name)) {
+            anyhow::bail!(
+                "flow '{}': config.overload.optional names unknown processor '{}'",
+                flow_name,
+                unknown
+            );
+        }
+        if action == OverloadAction::SkipOptional && optional.is_empty() {
+            anyhow::bail!(
+                "flow '{}': config.overload.action = \"skip_optional\" needs config.overload.optional",
+                flow_name
+            );
+        }
+
+        Ok(Self {
+            limit,
+            recover,
+            hold: values.duration("hold")?.unwrap_or(defaults.hold),
+            action,
+            optional,
+        })
+    }
+}
+
+/// Messwerte für `FlowStatus`/`GET /api/status`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ProcessingLoad {
+    /// Rechenzeit / Audiodauer im letzten Messfenster (1.0 = gerade noch Echtzeit)
+    pub realtime_factor: f32,
+    /// Höchster Fenster-Wert seit dem Start
+    pub peak_realtime_factor: f32,
+    /// Dauer der Processor-Kette im letzten Durchlauf
+    pub last_iteration_us: u64,
+    pub max_iteration_us: u64,
+    /// Konfiguriertes Limit, `None` ohne `config.overload`
+    pub limit: Option<f32>,
+    pub overloaded: bool,
+    /// Aktives Überlastverhalten, solange `overloaded`
+    pub action: Option<OverloadAction>,
+    /// Wie oft der Flow in Überlast ging
+    pub overloads: u64,
+}
+
+/// Übergang in oder aus der Überlast.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadTransition {
+    Overloaded,
+    Recovered,
+}
+
+/// Läuft im Processing-Thread eines Flows; `load()` ist mit dem Flow geteilt.
+pub struct LoadMonitor {
+    flow: String,
+    budget: Option<ProcessingBudget>,
+    load: Arc<Mutex<ProcessingLoad>>,
+    emitter: Option<EventEmitter>,
+    window_processing_ns: u64,
+    window_audio_ns: u64,
+    above_since_ns: Option<u64>,
+    below_since_ns: Option<u64>,
+    degraded: Option<OverloadAction>,
+}
+
+impl LoadMonitor {
+    pub fn new(flow: &str, budget: Option<ProcessingBudget>, load: Arc<Mutex<ProcessingLoad>>) -> Self {
+        *lock_mutex(&load, "load_monitor.reset") = ProcessingLoad {
+            limit: budget.as_ref().map(|budget| budget.limit),
+            ..ProcessingLoad::default()
+        };
+        Self {
+            flow: flow.to_string(),
+            budget,
+            load,
+            emitter: None,
+            window_processing_ns: 0,
+            window_audio_ns: 0,
+            above_since_ns: None,
+            below_since_ns: None,
+            degraded: None,
+        }
+    }
+
+    pub fn with_emitter(mut self, emitter: EventEmitter) -> Self {
+        self.emitter = Some(emitter);
+        self
+    }
+
+    pub fn load(&self) -> ProcessingLoad {
+        lock_mutex(&self.load, "load_monitor.load").clone()
+    }
+
+    /// Ganze Kette umgehen (nur bei `action = "passthrough"` in Überlast).
+    pub fn passthrough(&self) -> bool {
+        self.degraded == Some(OverloadAction::Passthrough)
+    }
+
+    /// Processor wird in diesem Durchlauf ausgelassen.
+    pub fn skips(&self, processor: &str) -> bool {
+        self.degraded == Some(OverloadAction::SkipOptional)
+            && self
+                .budget
+                .as_ref()
+                .is_some_and(|budget| budget.optional.iter().any(|name| name == processor))
+    }
+
+    /// Ein Durchlauf der Kette: Rechenzeit und Dauer der eingelesenen Frames.
+    pub fn record(&mut self, processing: Duration, audio: Duration) -> Option<LoadTransition> {
+        self.record_at(utc_ns_now(), processing, audio)
+    }
+
+    pub fn record_at(&mut self, now_ns: u64, processing: Duration, audio: Duration) -> Option<LoadTransition> {
+        let processing_ns = processing.as_nanos() as u64;
+        self.window_processing_ns += processing_ns;
+        self.window_audio_ns += audio.as_nanos() as u64;
+
+        let mut load = lock_mutex(&self.load, "load_monitor.record");
+        load.last_iteration_us = processing_ns / 1_000;
+        load.max_iteration_us = load.max_iteration_us.max(load.last_iteration_us);
+        if self.window_audio_ns < LOAD_WINDOW.as_nanos() as u64 {
+            return None;
+        }
+
+        let factor = self.window_processing_ns as f32 / self.window_audio_ns as f32;
+        self.window_processing_ns = 0;
+        self.window_audio_ns = 0;
+        load.realtime_factor = factor;
+        load.peak_realtime_factor = load.peak_realtime_factor.max(factor);
+
+        let budget = self.budget.as_ref()?;
+        let hold_ns = budget.hold.as_nanos() as u64;
+        let transition = if factor > budget.limit {
+            self.below_since_ns = None;
+            let since = *self.above_since_ns.get_or_insert(now_ns);
+            (self.degraded.is_none() && now_ns.saturating_sub(since) >= hold_ns).then(|| {
+                self.degraded = Some(budget.action);
+                load.overloads += 1;
+                LoadTransition::Overloaded
+            })
+        } else {
+            self.above_since_ns = None;
+            if self.degraded.is_some() && factor <= budget.recover {
+                // Im Passthrough sinkt der Faktor sofort, nach `hold` wird
+                // die volle Kette wieder probiert.
+                let since = *self.below_since_ns.get_or_insert(now_ns);
+                (now_ns.saturating_sub(since) >= hold_ns).then(|| {
+                    self.degraded = None;
+                    LoadTransition::Recovered
+                })
+            } else {
+                self.below_since_ns = None;
+                None
+            }
+        };
+        load.overloaded = self.degraded.is_some();
+        load.action = self.degraded;
+        drop(load);
+
+        if let Some(transition) = transition {
+            self.publish(transition, factor);
+        }
+        transition
+    }
+
+    fn publish(&self, transition: LoadTransition, factor: f32) {
+        let (Some(emitter), Some(budget)) = (&self.emitter, &self.budget) else {
+            return;
+        };
+        let (priority, state) = match transition {
+            LoadTransition::Overloaded => (EventPriority::Warning, "overloaded"),
+            LoadTransition::Recovered => (EventPriority::Info, "recovered"),
+        };
+        emitter.emit(
+            EventType::ProcessingOverload,
+            priority,
+            serde_json::json!({
+                "flow": self.flow,
+                "state": state,
+                "action": budget.action,
+                "realtime_factor": factor,
+                "limit": budget.limit,
+                "recover": budget.recover,
+                "timestamp": utc_ns_now(),
+            }),
+        );
+    }
+}
+
+/// Audiodauer eines Frames (0 bei fehlender Rate oder Kanalzahl).
+pub fn frame_duration(frame: &PcmFrame) -> Duration {
+    if frame.sample_rate == 0 || frame.channels == 0 {
+        return Duration::ZERO;
+    }
+    let per_channel = frame.samples.len() as u64 / frame.channels as u64;
+    Duration::from_nanos(per_channel * 1_000_000_000 / frame.sample_rate as u64)
+}
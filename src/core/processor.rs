@@ -1,7 +1,16 @@
 use crate::core::processor::basic::{PassThrough, Gain};
 use crate::impl_connectable_processor;
-use crate::core::ringbuffer::AudioRingBuffer;
+use crate::core::event_bus::EventEmitter;
+use crate::core::events::EventPriority;
+use crate::core::ringbuffer::{AudioRingBuffer, PcmFrame};
+use crate::core::BufferRegistry;
 use anyhow::Result;
+use std::sync::Arc;
+
+/// Standard für `config.batch` eines Flows: so viele Frames holt der Flow
+/// höchstens auf einmal aus einem Buffer.
+pub const DEFAULT_BATCH_SIZE: usize = 8;
+pub const MAX_BATCH_SIZE: usize = 256;
 
 pub trait Processor: Send + Sync {
     fn name(&self) -> &str;
@@ -12,10 +21,48 @@ pub trait Processor: Send + Sync {
         output_buffer: &AudioRingBuffer,
     ) -> Result<()>;
 
+    /// `true`, wenn `process_batch` implementiert ist; der Flow reicht dann
+    /// mehrere Frames auf einmal durch statt `process` aufzurufen.
+    fn supports_batch(&self) -> bool {
+        false
+    }
+
+    /// Verarbeitet einen Stapel Frames an Ort und Stelle (Frames dürfen
+    /// entfernt oder ergänzt werden). Nur aufgerufen, wenn `supports_batch`.
+    fn process_batch(&mut self, _frames: &mut Vec<PcmFrame>) -> Result<()> {
+        anyhow::bail!("Processor '{}' does not support batch processing", self.name())
+    }
+
     fn status(&self) -> ProcessorStatus;
 
     fn update_config(&mut self, config: serde_json::Value) -> Result<()>;
 
+    /// Setzt einen einzelnen numerischen Parameter (Automation, pro Frame).
+    /// Standard: `update_config({name: value})`; Processors mit häufig
+    /// automatisierten Parametern überschreiben das ohne Logging.
+    fn set_parameter(&mut self, name: &str, value: f64) -> Result<()> {
+        self.update_config(serde_json::json!({ name: value }))
+    }
+
+    /// Wird vom Flow aufgerufen, sobald ein EventBus verfügbar ist.
+    /// Processors, die Events senden wollen, speichern den Emitter.
+    fn attach_event_emitter(&mut self, _emitter: EventEmitter) {}
+
+    fn event_emitter(&self) -> Option<&EventEmitter> {
+        None
+    }
+
+    /// Wird beim Aufbau des Flows aufgerufen. Processors, die selbst aus
+    /// Registry-Buffern lesen (Mixer, Switcher), speichern die Registry.
+    fn attach_buffer_registry(&mut self, _registry: Arc<BufferRegistry>) {}
+
+    /// Veröffentlicht ein `EventType::Custom(name)` über den Node-EventBus.
+    fn emit_event(&self, name: &str, priority: EventPriority, payload: serde_json::Value) {
+        if let Some(emitter) = self.event_emitter() {
+            emitter.emit_custom(name, priority, payload);
+        }
+    }
+
     fn as_any(&self) -> &dyn std::any::Any
     where
         Self: Sized + 'static,
@@ -31,12 +78,36 @@ pub trait Processor: Send + Sync {
     }
 }
 
+/// Ein Durchlauf eines Processors: mit `supports_batch` stapelweise zu je
+/// höchstens `max_batch` Frames (ein Lock pro Stapel statt pro Frame),
+/// sonst über `process`.
+pub fn process_batched(
+    processor: &mut dyn Processor,
+    input: &AudioRingBuffer,
+    output: &AudioRingBuffer,
+    max_batch: usize,
+) -> Result<()> {
+    if max_batch <= 1 || !processor.supports_batch() {
+        return processor.process(input, output);
+    }
+    loop {
+        let mut frames = input.pop_batch(max_batch);
+        if frames.is_empty() {
+            return Ok(());
+        }
+        processor.process_batch(&mut frames)?;
+        output.push_batch(frames);
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ProcessorStatus {
     pub running: bool,
     pub processing_rate_hz: f32,
     pub latency_ms: f32,
     pub errors: u64,
+    /// Messwerte von Analyse-Processors (z. B. Phasenmeter); sonst `None`
+    pub metrics: Option<serde_json::Value>,
 }
 
 // Basis-Processors (können hier bleiben oder in processors/ verschoben werden)
@@ -86,12 +157,21 @@ pub mod basic {
             Ok(())
         }
 
+        fn supports_batch(&self) -> bool {
+            true
+        }
+
+        fn process_batch(&mut self, _frames: &mut Vec<PcmFrame>) -> Result<()> {
+            Ok(())
+        }
+
         fn status(&self) -> ProcessorStatus {
             ProcessorStatus {
                 running: true,
                 processing_rate_hz: 0.0,
                 latency_ms: 0.0,
                 errors: 0,
+                metrics: None,
             }
         }
 
@@ -120,6 +200,12 @@ pub mod basic {
                 gain,
             }
         }
+
+        fn apply(&self, frame: &mut PcmFrame) {
+            for sample in frame.samples.iter_mut() {
+                *sample = (*sample as f32 * self.gain).clamp(-32768.0, 32767.0) as i16;
+            }
+        }
     }
 
     impl Processor for Gain {
@@ -133,31 +219,58 @@ pub mod basic {
             output_buffer: &AudioRingBuffer,
         ) -> Result<()> {
             while let Some(mut frame) = input_buffer.pop() {
-                for sample in frame.samples.iter_mut() {
-                    *sample = (*sample as f32 * self.gain).clamp(-32768.0, 32767.0) as i16;
-                }
+                self.apply(&mut frame);
                 output_buffer.push(frame);
             }
             Ok(())
         }
 
+        fn supports_batch(&self) -> bool {
+            true
+        }
+
+        fn process_batch(&mut self, frames: &mut Vec<PcmFrame>) -> Result<()> {
+            for frame in frames.iter_mut() {
+                self.apply(frame);
+            }
+            Ok(())
+        }
+
         fn status(&self) -> ProcessorStatus {
             ProcessorStatus {
                 running: true,
                 processing_rate_hz: 0.0,
                 latency_ms: 0.0,
                 errors: 0,
+                metrics: None,
             }
         }
 
         fn update_config(&mut self, config: serde_json::Value) -> Result<()> {
-            if let Some(gain) = config.get("gain").and_then(|v| v.as_f64()) {
+            let gain_db = match config.get("gain_db") {
+                Some(serde_json::Value::String(text)) => Some(crate::config::units::parse_db(text)?),
+                Some(value) => value.as_f64().map(|db| db as f32),
+                None => None,
+            };
+            if let Some(db) = gain_db {
+                self.gain = crate::config::units::db_to_linear(db);
+                log::info!("Processor '{}' gain updated to {} dB", self.name, db);
+            } else if let Some(gain) = config.get("gain").and_then(|v| v.as_f64()) {
                 self.gain = gain as f32;
                 log::info!("Processor '{}' gain updated to {}", self.name, self.gain);
             }
             Ok(())
         }
 
+        fn set_parameter(&mut self, name: &str, value: f64) -> Result<()> {
+            match name {
+                "gain_db" => self.gain = crate::config::units::db_to_linear(value as f32),
+                "gain" => self.gain = value.max(0.0) as f32,
+                _ => anyhow::bail!("Processor '{}' has no parameter '{}'", self.name, name),
+            }
+            Ok(())
+        }
+
         fn as_any(&self) -> &dyn std::any::Any {
             self
         }
@@ -55,6 +55,7 @@ impl ProducerBase {
                 samples: pcm_samples,
                 sample_rate: self.sample_rate,
                 channels: self.channels,
+                metadata: Default::default(),
             };
             let sample_len = frame.samples.len() as u64;
             buffer.push(frame).map_err(|e| {
@@ -0,0 +1,26 @@
+// src/core/readiness.rs
+//
+// Readiness-Gate beim Start: Consumer (Encoder, Verbindungen zu externen
+// Servern) werden erst gestartet, wenn die Upstream-Buffer ihres Flows Audio
+// liefern. Läuft das Timeout ab, starten sie trotzdem (mit Warnung), damit ein
+// toter Producer nicht den ganzen Node blockiert.
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_READINESS_TIMEOUT: Duration = Duration::from_secs(3);
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Pollt `pending` bis die Liste leer ist oder `timeout` abläuft; liefert die
+/// Namen, die bis dahin nicht bereit waren.
+pub fn wait_until_ready<F>(timeout: Duration, mut pending: F) -> Vec<String>
+where
+    F: FnMut() -> Vec<String>,
+{
+    let deadline = Instant::now() + timeout;
+    loop {
+        let waiting = pending();
+        if waiting.is_empty() || Instant::now() >= deadline {
+            return waiting;
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
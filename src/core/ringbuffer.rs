@@ -4,10 +4,14 @@ use std::sync::{Arc, Mutex, MutexGuard};
 use std::time::Duration;
 use std::fmt::Debug;
 
+use crate::core::idle::PushWakers;
 use crate::core::lock::lock_mutex_with_timeout;
 use crate::core::logging::ComponentLogger;
+use crate::core::watermark::{ReaderLag, WatermarkMonitor};
+use crate::producers::wait::StopWait;
 pub use crate::ring::PcmFrame;
 use crate::ring::PcmSink;
+use crate::types::{Samples, Slots};
 
 #[derive(Debug)]
 struct RingSlot {
@@ -24,6 +28,14 @@ pub struct AudioRingBuffer {
     read_positions: Mutex<HashMap<String, u64>>,
     dropped_frames: AtomicU64,
     high_water_warned: AtomicBool,
+    /// Pro Slot reservierte Samples (0 = Frames werden übernommen)
+    prealloc_samples: Samples,
+    /// Form des zuletzt geschriebenen Frames: Samples bzw. `rate << 8 | channels`
+    last_frame_samples: AtomicU64,
+    last_frame_format: AtomicU64,
+    watermark: Mutex<Option<Arc<WatermarkMonitor>>>,
+    /// Werden nach jedem Push geweckt (adaptives Warten der Leser)
+    wakers: PushWakers,
 }
 
 const BUFFER_LOCK_TIMEOUT: Duration = Duration::from_millis(5);
@@ -32,12 +44,29 @@ const HIGH_WATER_RESET_THRESHOLD: f32 = 0.5;
 const DROP_LOG_INTERVAL: u64 = 1_000;
 
 impl AudioRingBuffer {
+    /// Verwaltungsaufwand pro Slot ohne Sample-Daten
+    pub const SLOT_OVERHEAD_BYTES: usize = std::mem::size_of::<RingSlot>();
+
     pub fn new(capacity: usize) -> Self {
+        Self::with_prealloc(Slots(capacity), Samples::ZERO)
+    }
+
+    /// Ring mit `prealloc_samples` reservierten Samples je Slot; passende
+    /// Frames werden hineinkopiert statt ihre Allokation zu übernehmen.
+    pub fn with_prealloc(slots: Slots, prealloc_samples: Samples) -> Self {
+        let capacity = slots.get().max(1);
         let mut slots = Vec::with_capacity(capacity);
         for _ in 0..capacity {
+            let frame = (!prealloc_samples.is_zero()).then(|| PcmFrame {
+                utc_ns: 0,
+                samples: Vec::with_capacity(prealloc_samples.get()),
+                sample_rate: 0,
+                channels: 0,
+                metadata: Default::default(),
+            });
             slots.push(RingSlot {
                 seq: AtomicU64::new(0),
-                frame: Mutex::new(None),
+                frame: Mutex::new(frame),
             });
         }
 
@@ -49,12 +78,34 @@ impl AudioRingBuffer {
             read_positions: Mutex::new(HashMap::new()),
             dropped_frames: AtomicU64::new(0),
             high_water_warned: AtomicBool::new(false),
+            prealloc_samples,
+            last_frame_samples: AtomicU64::new(0),
+            last_frame_format: AtomicU64::new(0),
+            watermark: Mutex::new(None),
+            wakers: PushWakers::new(),
         }
     }
 
     /// Push a frame into the ring.
     /// Returns the current number of frames in the buffer.
     pub fn push(&self, frame: PcmFrame) -> u64 {
+        match self.store(frame) {
+            Some(seq) => self.pushed(seq),
+            None => self.len() as u64,
+        }
+    }
+
+    /// Mehrere Frames am Stück; Füllstand, Watermarks und wartende Leser
+    /// werden nur einmal nach dem letzten Frame bedient.
+    pub fn push_batch(&self, frames: impl IntoIterator<Item = PcmFrame>) -> u64 {
+        match frames.into_iter().filter_map(|frame| self.store(frame)).last() {
+            Some(seq) => self.pushed(seq),
+            None => self.len() as u64,
+        }
+    }
+
+    /// Schreibt den Frame in seinen Slot; `None`, wenn er verworfen wurde.
+    fn store(&self, frame: PcmFrame) -> Option<u64> {
         let seq = self.next_seq.fetch_add(1, Ordering::Relaxed);
 
         // Logging: Nur alle 50 Frames oder wenn interessant
@@ -74,11 +125,17 @@ impl AudioRingBuffer {
         if let Some(mut guard) =
             lock_mutex_with_timeout(&slot.frame, "ringbuffer.push.slot", BUFFER_LOCK_TIMEOUT)
         {
-            *guard = Some(frame);
+            self.last_frame_samples
+                .store(frame.samples.len() as u64, Ordering::Relaxed);
+            self.last_frame_format.store(
+                (frame.sample_rate as u64) << 8 | frame.channels as u64,
+                Ordering::Relaxed,
+            );
+            store_frame(self.prealloc_samples, &mut guard, frame);
         } else {
             self.dropped_frames.fetch_add(1, Ordering::Relaxed);
             self.warn("Dropping frame: slot lock timeout");
-            return self.len() as u64;
+            return None;
         }
 
         slot.seq.store(seq, Ordering::Release);
@@ -93,7 +150,10 @@ impl AudioRingBuffer {
                 ));
             }
         }
+        Some(seq)
+    }
 
+    fn pushed(&self, seq: u64) -> u64 {
         let new_len = self.len() as u64;
 
         let utilization = new_len as f32 / self.capacity as f32;
@@ -108,13 +168,122 @@ impl AudioRingBuffer {
             self.high_water_warned.store(false, Ordering::Relaxed);
         }
 
+        self.check_watermark(seq);
+        self.wakers.notify();
+
         new_len
     }
 
+    /// `waker` wird nach jedem Push geweckt, bis `remove_waker` oder bis der
+    /// letzte `Arc` fällt.
+    pub fn add_waker(&self, waker: &Arc<StopWait>) {
+        self.wakers.add(waker);
+    }
+
+    pub fn remove_waker(&self, waker: &Arc<StopWait>) {
+        self.wakers.remove(waker);
+    }
+
+    /// High/Low-Watermarks pro Reader aktivieren (`None` deaktiviert).
+    pub fn set_watermark(&self, monitor: Option<Arc<WatermarkMonitor>>) {
+        if let Some(mut guard) =
+            lock_mutex_with_timeout(&self.watermark, "ringbuffer.set_watermark", BUFFER_LOCK_TIMEOUT)
+        {
+            *guard = monitor;
+        }
+    }
+
+    pub fn watermark(&self) -> Option<Arc<WatermarkMonitor>> {
+        lock_mutex_with_timeout(&self.watermark, "ringbuffer.watermark", BUFFER_LOCK_TIMEOUT)
+            .and_then(|guard| guard.clone())
+    }
+
+    fn check_watermark(&self, head: u64) {
+        let Some(monitor) = self.watermark() else {
+            return;
+        };
+        let oldest = self.oldest_seq(head);
+        let lags = match lock_mutex_with_timeout(
+            &self.read_positions,
+            "ringbuffer.watermark.read_positions",
+            BUFFER_LOCK_TIMEOUT,
+        ) {
+            Some(read_positions) => read_positions
+                .iter()
+                .map(|(reader, pos)| ReaderLag {
+                    reader: reader.clone(),
+                    position: *pos,
+                    lag: (head + 1).saturating_sub((*pos).max(oldest)) as usize,
+                })
+                .collect::<Vec<_>>(),
+            None => return,
+        };
+        monitor.observe(self.capacity, lags);
+    }
+
     pub fn pop(&self) -> Option<PcmFrame> {
         self.pop_for_reader("default")
     }
 
+    pub fn pop_batch(&self, max: usize) -> Vec<PcmFrame> {
+        self.pop_batch_for_reader("default", max)
+    }
+
+    /// Bis zu `max` Frames für `reader_id`; die Leseposition wird dabei nur
+    /// einmal gesperrt statt zweimal pro Frame.
+    pub fn pop_batch_for_reader(&self, reader_id: &str, max: usize) -> Vec<PcmFrame> {
+        let head = self.head_seq.load(Ordering::Acquire);
+        if head == 0 || max == 0 {
+            return Vec::new();
+        }
+
+        let oldest = self.oldest_seq(head);
+        let Some(mut read_positions) = lock_mutex_with_timeout(
+            &self.read_positions,
+            "ringbuffer.pop_batch.read_positions",
+            BUFFER_LOCK_TIMEOUT,
+        ) else {
+            self.warn("Batch pop aborted: read_positions lock timeout");
+            return Vec::new();
+        };
+        let position = read_positions
+            .entry(reader_id.to_string())
+            .or_insert(oldest);
+        if *position < oldest {
+            *position = oldest;
+        }
+
+        let mut frames = Vec::with_capacity(max.min((head + 1).saturating_sub(*position) as usize));
+        while frames.len() < max && *position <= head {
+            let target_seq = *position;
+            let slot = &self.slots[(target_seq as usize) % self.capacity];
+            let slot_seq = slot.seq.load(Ordering::Acquire);
+            if slot_seq != target_seq {
+                // Während des Lesens überholt: wie `pop_for_reader` beim
+                // ältesten noch vorhandenen Frame weitermachen
+                self.dropped_frames.fetch_add(1, Ordering::Relaxed);
+                *position = self.oldest_seq(self.head_seq.load(Ordering::Acquire));
+                self.warn(&format!(
+                    "Sequence mismatch for reader '{}': expected {}, got {}",
+                    reader_id, target_seq, slot_seq
+                ));
+                break;
+            }
+            let Some(guard) =
+                lock_mutex_with_timeout(&slot.frame, "ringbuffer.pop_batch.slot", BUFFER_LOCK_TIMEOUT)
+            else {
+                self.warn("Batch pop stopped: slot lock timeout");
+                break;
+            };
+            let Some(frame) = guard.as_ref().cloned() else {
+                break;
+            };
+            frames.push(frame);
+            *position = target_seq + 1;
+        }
+        frames
+    }
+
     /// Leser-spezifisches Pop, mit Reader-ID (Multi-Reader).
     pub fn pop_for_reader(&self, reader_id: &str) -> Option<PcmFrame> {
         let head = self.head_seq.load(Ordering::Acquire);
@@ -228,7 +397,10 @@ Some(guard) => {
             if let Some(mut guard) =
                 lock_mutex_with_timeout(&slot.frame, "ringbuffer.clear.slot", BUFFER_LOCK_TIMEOUT)
             {
-                *guard = None;
+                // Reservierter Speicher bleibt, Slot ist über `seq = 0` ungültig
+                if self.prealloc_samples.is_zero() {
+                    *guard = None;
+                }
             } else {
                 self.warn("Clear aborted: slot lock timeout");
                 return;
@@ -264,6 +436,11 @@ if let Some(mut read_positions) = lock_mutex_with_timeout(
         self.len() == 0
     }
 
+    /// Mindestens ein Frame seit dem Anlegen bzw. `clear()` geschrieben
+    pub fn has_received(&self) -> bool {
+        self.head_seq.load(Ordering::Acquire) > 0
+    }
+
     /// Anzahl der für einen bestimmten Reader verfügbaren Frames
     pub fn available_for_reader(&self, reader_id: &str) -> usize {
         let head = self.head_seq.load(Ordering::Acquire);
@@ -299,6 +476,19 @@ let read_positions: MutexGuard<'_, HashMap<String, u64>> =
         self.available_for_reader("default")
     }
 
+    /// Meldet einen Reader ab (zählt danach nicht mehr für Watermarks).
+    pub fn remove_reader(&self, reader_id: &str) {
+        if let Some(mut read_positions) = lock_mutex_with_timeout(
+            &self.read_positions,
+            "ringbuffer.remove_reader.read_positions",
+            BUFFER_LOCK_TIMEOUT,
+        ) {
+            read_positions.remove(reader_id);
+        } else {
+            self.warn("remove_reader aborted: read_positions lock timeout");
+        }
+    }
+
     pub fn skip_to_latest(&self, reader_id: &str) {
         let head = self.head_seq.load(Ordering::Acquire);
         if head == 0 {
@@ -316,12 +506,34 @@ let read_positions: MutexGuard<'_, HashMap<String, u64>> =
         }
     }
 
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn slots(&self) -> Slots {
+        Slots(self.capacity)
+    }
+
+    pub fn prealloc_samples(&self) -> Samples {
+        self.prealloc_samples
+    }
+
+    /// (Samples, Sample-Rate, Kanäle) des zuletzt geschriebenen Frames
+    pub fn last_frame_shape(&self) -> Option<(Samples, u32, u8)> {
+        let samples = self.last_frame_samples.load(Ordering::Relaxed);
+        let format = self.last_frame_format.load(Ordering::Relaxed);
+        if samples == 0 || format == 0 {
+            return None;
+        }
+        Some((Samples(samples as usize), (format >> 8) as u32, (format & 0xff) as u8))
+    }
+
     pub fn stats(&self) -> RingBufferStats {
         let head = self.head_seq.load(Ordering::Acquire);
         if head == 0 {
             return RingBufferStats {
-                capacity: self.capacity,
-                current_frames: 0,
+                capacity: self.slots(),
+                current_frames: Slots::ZERO,
                 dropped_frames: self.dropped_frames.load(Ordering::Relaxed),
                 latest_timestamp: None,
                 oldest_timestamp: None,
@@ -333,8 +545,8 @@ let read_positions: MutexGuard<'_, HashMap<String, u64>> =
         let oldest_timestamp = self.slot_timestamp(oldest);
 
         RingBufferStats {
-            capacity: self.capacity,
-            current_frames: self.len(),
+            capacity: self.slots(),
+            current_frames: Slots(self.len()),
             dropped_frames: self.dropped_frames.load(Ordering::Relaxed),
             latest_timestamp,
             oldest_timestamp,
@@ -392,6 +604,23 @@ let read_positions: MutexGuard<'_, HashMap<String, u64>> =
     }
 }
 
+/// Kopiert in vorhandenen Slot-Speicher, wenn er reserviert wurde und reicht.
+fn store_frame(prealloc_samples: Samples, target: &mut Option<PcmFrame>, frame: PcmFrame) {
+    match target {
+        Some(existing)
+            if !prealloc_samples.is_zero() && existing.samples.capacity() >= frame.samples.len() =>
+        {
+            existing.samples.clear();
+            existing.samples.extend_from_slice(&frame.samples);
+            existing.utc_ns = frame.utc_ns;
+            existing.sample_rate = frame.sample_rate;
+            existing.channels = frame.channels;
+            existing.metadata = frame.metadata;
+        }
+        _ => *target = Some(frame),
+    }
+}
+
 impl PcmSink for AudioRingBuffer {
     fn push(&self, frame: PcmFrame) -> anyhow::Result<()> {
         self.push(frame);
@@ -420,8 +649,9 @@ impl Iterator for RingBufferIter {
 
 #[derive(Debug, Clone)]
 pub struct RingBufferStats {
-    pub capacity: usize,
-    pub current_frames: usize,
+    pub capacity: Slots,
+    /// Belegte Slots, je einer pro `PcmFrame`
+    pub current_frames: Slots,
     pub dropped_frames: u64,
     pub latest_timestamp: Option<u64>,
     pub oldest_timestamp: Option<u64>,
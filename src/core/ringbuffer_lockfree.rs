@@ -5,10 +5,14 @@ use std::sync::{Arc, RwLock};
 use std::time::Duration;
 use std::fmt::Debug;
 
+use crate::core::idle::PushWakers;
 use crate::core::lock::{lock_rwlock_read_with_timeout, lock_rwlock_write_with_timeout};
 use crate::core::logging::ComponentLogger;
+use crate::core::watermark::{ReaderLag, WatermarkMonitor};
+use crate::producers::wait::StopWait;
 pub use crate::ring::PcmFrame;
 use crate::ring::PcmSink;
+use crate::types::{Samples, Slots};
 
 #[derive(Debug)]
 struct RingSlot {
@@ -73,6 +77,35 @@ impl ReaderRegistry {
         None
     }
 
+    /// Reader-Rückstände für Watermarks; Reader sind hier nur per Hash bekannt.
+    fn lags(&self, head: u64, oldest: u64) -> Vec<ReaderLag> {
+        self.slots
+            .iter()
+            .filter_map(|slot| {
+                let hash = slot.id_hash.load(Ordering::Acquire);
+                let position = slot.position.load(Ordering::Acquire);
+                if hash == 0 || position == 0 {
+                    return None;
+                }
+                Some(ReaderLag {
+                    reader: format!("reader#{:016x}", hash),
+                    position,
+                    lag: (head + 1).saturating_sub(position.max(oldest)) as usize,
+                })
+            })
+            .collect()
+    }
+
+    /// Slot bleibt belegt (Hash-Kette), nur die Position wird zurückgesetzt.
+    fn release(&self, reader_id: &str) {
+        let hash = hash_reader_id(reader_id);
+        for slot in &self.slots {
+            if slot.id_hash.load(Ordering::Acquire) == hash {
+                slot.position.store(0, Ordering::Release);
+            }
+        }
+    }
+
     fn clear_positions(&self) {
         for slot in &self.slots {
             slot.position.store(0, Ordering::Release);
@@ -99,15 +132,40 @@ pub struct AudioRingBuffer {
     readers: ReaderRegistry,
     dropped_frames: AtomicU64,
     high_water_warned: AtomicBool,
+    /// Pro Slot reservierte Samples (0 = Frames werden übernommen)
+    prealloc_samples: Samples,
+    /// Form des zuletzt geschriebenen Frames: Samples bzw. `rate << 8 | channels`
+    last_frame_samples: AtomicU64,
+    last_frame_format: AtomicU64,
+    watermark: RwLock<Option<Arc<WatermarkMonitor>>>,
+    /// Werden nach jedem Push geweckt (adaptives Warten der Leser)
+    wakers: PushWakers,
 }
 
 impl AudioRingBuffer {
+    /// Verwaltungsaufwand pro Slot ohne Sample-Daten
+    pub const SLOT_OVERHEAD_BYTES: usize = std::mem::size_of::<RingSlot>();
+
     pub fn new(capacity: usize) -> Self {
+        Self::with_prealloc(Slots(capacity), Samples::ZERO)
+    }
+
+    /// Ring mit `prealloc_samples` reservierten Samples je Slot; passende
+    /// Frames werden hineinkopiert statt ihre Allokation zu übernehmen.
+    pub fn with_prealloc(slots: Slots, prealloc_samples: Samples) -> Self {
+        let capacity = slots.get().max(1);
         let mut slots = Vec::with_capacity(capacity);
         for _ in 0..capacity {
+            let frame = (!prealloc_samples.is_zero()).then(|| PcmFrame {
+                utc_ns: 0,
+                samples: Vec::with_capacity(prealloc_samples.get()),
+                sample_rate: 0,
+                channels: 0,
+                metadata: Default::default(),
+            });
             slots.push(RingSlot {
                 seq: AtomicU64::new(0),
-                frame: RwLock::new(None),
+                frame: RwLock::new(frame),
             });
         }
 
@@ -119,6 +177,11 @@ impl AudioRingBuffer {
             readers: ReaderRegistry::new(MAX_READERS),
             dropped_frames: AtomicU64::new(0),
             high_water_warned: AtomicBool::new(false),
+            prealloc_samples,
+            last_frame_samples: AtomicU64::new(0),
+            last_frame_format: AtomicU64::new(0),
+            watermark: RwLock::new(None),
+            wakers: PushWakers::new(),
         }
     }
 
@@ -145,7 +208,13 @@ impl AudioRingBuffer {
             "ringbuffer_lockfree.push.slot",
             BUFFER_LOCK_TIMEOUT,
         ) {
-            *guard = Some(frame);
+            self.last_frame_samples
+                .store(frame.samples.len() as u64, Ordering::Relaxed);
+            self.last_frame_format.store(
+                (frame.sample_rate as u64) << 8 | frame.channels as u64,
+                Ordering::Relaxed,
+            );
+            store_frame(self.prealloc_samples, &mut guard, frame);
             slot.seq.store(seq, Ordering::Release);
         } else {
             self.dropped_frames.fetch_add(1, Ordering::Relaxed);
@@ -177,13 +246,69 @@ impl AudioRingBuffer {
             self.high_water_warned.store(false, Ordering::Relaxed);
         }
 
+        if let Some(monitor) = self.watermark() {
+            monitor.observe(self.capacity, self.readers.lags(seq, self.oldest_seq(seq)));
+        }
+        self.wakers.notify();
+
         new_len
     }
 
+    /// `waker` wird nach jedem Push geweckt, bis `remove_waker` oder bis der
+    /// letzte `Arc` fällt.
+    pub fn add_waker(&self, waker: &Arc<StopWait>) {
+        self.wakers.add(waker);
+    }
+
+    pub fn remove_waker(&self, waker: &Arc<StopWait>) {
+        self.wakers.remove(waker);
+    }
+
+    /// High/Low-Watermarks pro Reader aktivieren (`None` deaktiviert).
+    pub fn set_watermark(&self, monitor: Option<Arc<WatermarkMonitor>>) {
+        if let Some(mut guard) = lock_rwlock_write_with_timeout(
+            &self.watermark,
+            "ringbuffer_lockfree.set_watermark",
+            BUFFER_LOCK_TIMEOUT,
+        ) {
+            *guard = monitor;
+        }
+    }
+
+    pub fn watermark(&self) -> Option<Arc<WatermarkMonitor>> {
+        lock_rwlock_read_with_timeout(
+            &self.watermark,
+            "ringbuffer_lockfree.watermark",
+            BUFFER_LOCK_TIMEOUT,
+        )
+        .and_then(|guard| guard.clone())
+    }
+
     pub fn pop(&self) -> Option<PcmFrame> {
         self.pop_for_reader("default")
     }
 
+    pub fn pop_batch(&self, max: usize) -> Vec<PcmFrame> {
+        self.pop_batch_for_reader("default", max)
+    }
+
+    /// Bis zu `max` Frames für `reader_id`; Lesepositionen sind hier atomar,
+    /// daher Frame für Frame.
+    pub fn pop_batch_for_reader(&self, reader_id: &str, max: usize) -> Vec<PcmFrame> {
+        std::iter::from_fn(|| self.pop_for_reader(reader_id))
+            .take(max)
+            .collect()
+    }
+
+    /// Mehrere Frames am Stück (Gegenstück zu `AudioRingBuffer::push_batch`).
+    pub fn push_batch(&self, frames: impl IntoIterator<Item = PcmFrame>) -> u64 {
+        let mut len = self.len() as u64;
+        for frame in frames {
+            len = self.push(frame);
+        }
+        len
+    }
+
     /// Leser-spezifisches Pop, mit Reader-ID (Multi-Reader).
     pub fn pop_for_reader(&self, reader_id: &str) -> Option<PcmFrame> {
         let head = self.head_seq.load(Ordering::Acquire);
@@ -269,7 +394,10 @@ impl AudioRingBuffer {
                 "ringbuffer_lockfree.clear.slot",
                 BUFFER_LOCK_TIMEOUT,
             ) {
-                *guard = None;
+                // Reservierter Speicher bleibt, Slot ist über `seq = 0` ungültig
+                if self.prealloc_samples.is_zero() {
+                    *guard = None;
+                }
             } else {
                 self.warn("Clear aborted: slot write lock timeout");
                 return;
@@ -283,6 +411,11 @@ impl AudioRingBuffer {
         self.readers.clear_positions();
     }
 
+    /// Meldet einen Reader ab (zählt danach nicht mehr für Watermarks).
+    pub fn remove_reader(&self, reader_id: &str) {
+        self.readers.release(reader_id);
+    }
+
     pub fn len(&self) -> usize {
         let head = self.head_seq.load(Ordering::Acquire);
         if head == 0 {
@@ -296,6 +429,11 @@ impl AudioRingBuffer {
         self.len() == 0
     }
 
+    /// Mindestens ein Frame seit dem Anlegen bzw. `clear()` geschrieben
+    pub fn has_received(&self) -> bool {
+        self.head_seq.load(Ordering::Acquire) > 0
+    }
+
     /// Anzahl der für einen bestimmten Reader verfügbaren Frames
     pub fn available_for_reader(&self, reader_id: &str) -> usize {
         let head = self.head_seq.load(Ordering::Acquire);
@@ -335,12 +473,34 @@ impl AudioRingBuffer {
         self.available_for_reader("default")
     }
 
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn slots(&self) -> Slots {
+        Slots(self.capacity)
+    }
+
+    pub fn prealloc_samples(&self) -> Samples {
+        self.prealloc_samples
+    }
+
+    /// (Samples, Sample-Rate, Kanäle) des zuletzt geschriebenen Frames
+    pub fn last_frame_shape(&self) -> Option<(Samples, u32, u8)> {
+        let samples = self.last_frame_samples.load(Ordering::Relaxed);
+        let format = self.last_frame_format.load(Ordering::Relaxed);
+        if samples == 0 || format == 0 {
+            return None;
+        }
+        Some((Samples(samples as usize), (format >> 8) as u32, (format & 0xff) as u8))
+    }
+
     pub fn stats(&self) -> RingBufferStats {
         let head = self.head_seq.load(Ordering::Acquire);
         if head == 0 {
             return RingBufferStats {
-                capacity: self.capacity,
-                current_frames: 0,
+                capacity: self.slots(),
+                current_frames: Slots::ZERO,
                 dropped_frames: self.dropped_frames.load(Ordering::Relaxed),
                 latest_timestamp: None,
                 oldest_timestamp: None,
@@ -352,8 +512,8 @@ impl AudioRingBuffer {
         let oldest_timestamp = self.slot_timestamp(oldest);
 
         RingBufferStats {
-            capacity: self.capacity,
-            current_frames: self.len(),
+            capacity: self.slots(),
+            current_frames: Slots(self.len()),
             dropped_frames: self.dropped_frames.load(Ordering::Relaxed),
             latest_timestamp,
             oldest_timestamp,
@@ -417,6 +577,23 @@ impl AudioRingBuffer {
     }
 }
 
+/// Kopiert in vorhandenen Slot-Speicher, wenn er reserviert wurde und reicht.
+fn store_frame(prealloc_samples: Samples, target: &mut Option<PcmFrame>, frame: PcmFrame) {
+    match target {
+        Some(existing)
+            if !prealloc_samples.is_zero() && existing.samples.capacity() >= frame.samples.len() =>
+        {
+            existing.samples.clear();
+            existing.samples.extend_from_slice(&frame.samples);
+            existing.utc_ns = frame.utc_ns;
+            existing.sample_rate = frame.sample_rate;
+            existing.channels = frame.channels;
+            existing.metadata = frame.metadata;
+        }
+        _ => *target = Some(frame),
+    }
+}
+
 impl PcmSink for AudioRingBuffer {
     fn push(&self, frame: PcmFrame) -> anyhow::Result<()> {
         self.push(frame);
@@ -445,8 +622,9 @@ impl Iterator for RingBufferIter {
 
 #[derive(Debug, Clone)]
 pub struct RingBufferStats {
-    pub capacity: usize,
-    pub current_frames: usize,
+    pub capacity: Slots,
+    /// Belegte Slots, je einer pro `PcmFrame`
+    pub current_frames: Slots,
     pub dropped_frames: u64,
     pub latest_timestamp: Option<u64>,
     pub oldest_timestamp: Option<u64>,
@@ -0,0 +1,232 @@
+// src/core/safe_mode.rs
+//
+// Schutz vor Crash-Schleifen beim Start. Eine kleine JSON-Datei zählt Starts,
+// die weder `stable_after` überlebt haben noch sauber beendet wurden. Ab
+// `max_crashes` solcher Starts in Folge bootet der Node im Safe Mode: nur
+// API-/Monitoring-Server, keine Audio-Komponenten. `GET /api/status` zeigt das
+// samt letztem Absturzgrund (Panic oder Startfehler); `safe_mode.exit` wendet
+// die (reparierte) Konfiguration an und setzt den Zähler zurück.
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use crate::core::lock::lock_mutex;
+
+pub const DEFAULT_CRASH_FILE: &str = "airlift-crashes.json";
+pub const DEFAULT_MAX_CRASHES: u32 = 3;
+pub const DEFAULT_STABLE_AFTER: Duration = Duration::from_secs(60);
+/// Grund, wenn der letzte Start ohne erfassten Fehler endete (Kill, OOM, Segfault)
+pub const UNRECORDED_EXIT: &str = "process exited during startup without a recorded error";
+
+/// Inhalt der Crash-Datei.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CrashRecord {
+    /// Starts in Folge, die nicht stabil wurden
+    pub unstable_starts: u32,
+    pub last_reason: Option<String>,
+    pub last_crash_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SafeModeStatus {
+    pub active: bool,
+    pub unstable_starts: u32,
+    /// 0 = Safe Mode abgeschaltet
+    pub max_crashes: u32,
+    pub last_crash_reason: Option<String>,
+    pub last_crash_ms: Option<u64>,
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Safe Mode, wenn die letzten `max_crashes` Starts alle instabil waren.
+pub fn should_enter(previous: &CrashRecord, max_crashes: u32) -> bool {
+    max_crashes > 0 && previous.unstable_starts >= max_crashes
+}
+
+pub struct CrashCounter {
+    path: PathBuf,
+    record: Mutex<CrashRecord>,
+    stable: AtomicBool,
+}
+
+impl CrashCounter {
+    /// Liest die Crash-Datei; fehlt sie oder ist sie unlesbar, beginnt die
+    /// Zählung bei null.
+    pub fn open(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref().to_path_buf();
+        let record = match std::fs::read_to_string(&path) {
+            Ok(text) => serde_json::from_str(&text).unwrap_or_else(|e| {
+                log::warn!("[safe-mode] ignoring unreadable {}: {}", path.display(), e);
+                CrashRecord::default()
+            }),
+            Err(_) => CrashRecord::default(),
+        };
+        Self {
+            path,
+            record: Mutex::new(record),
+            stable: AtomicBool::new(false),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn record(&self) -> CrashRecord {
+        lock_mutex(&self.record, "safe_mode.record").clone()
+    }
+
+    fn save(&self, record: &CrashRecord) -> anyhow::Result<()> {
+        let json = serde_json::to_string_pretty(record)?;
+        let tmp = self.path.with_extension("tmp");
+        std::fs::write(&tmp, json)
+            .and_then(|_| std::fs::rename(&tmp, &self.path))
+            .with_context(|| format!("cannot write {}", self.path.display()))
+    }
+
+    /// Stand vor diesem Start; ein instabiler Vorgänger ohne erfassten
+    /// Grund bekommt `UNRECORDED_EXIT`.
+    pub fn previous(&self) -> CrashRecord {
+        let mut record = lock_mutex(&self.record, "safe_mode.previous");
+        if record.unstable_starts > 0 && record.last_reason.is_none() {
+            record.last_reason = Some(UNRECORDED_EXIT.to_string());
+        }
+        record.clone()
+    }
+
+    /// Zählt einen neuen (noch instabilen) Start.
+    pub fn begin_start(&self) -> anyhow::Result<()> {
+        let mut record = lock_mutex(&self.record, "safe_mode.begin_start");
+        if record.unstable_starts > 0 && record.last_reason.is_none() {
+            record.last_reason = Some(UNRECORDED_EXIT.to_string());
+        }
+        record.unstable_starts += 1;
+        self.stable.store(false, Ordering::SeqCst);
+        self.save(&record)
+    }
+
+    /// Hält den Grund fest, solange der laufende Start noch nicht stabil ist.
+    pub fn record_failure(&self, reason: &str) {
+        if self.stable.load(Ordering::SeqCst) {
+            return;
+        }
+        let mut record = lock_mutex(&self.record, "safe_mode.record_failure");
+        record.last_reason = Some(reason.to_string());
+        record.last_crash_ms = Some(now_ms());
+        if let Err(e) = self.save(&record) {
+            log::warn!("[safe-mode] {}", e);
+        }
+    }
+
+    /// Start gilt als gelungen (stabil gelaufen oder sauber beendet).
+    pub fn mark_stable(&self) {
+        if self.stable.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let mut record = lock_mutex(&self.record, "safe_mode.mark_stable");
+        *record = CrashRecord::default();
+        if let Err(e) = self.save(&record) {
+            log::warn!("[safe-mode] {}", e);
+        }
+    }
+
+    pub fn is_stable(&self) -> bool {
+        self.stable.load(Ordering::SeqCst)
+    }
+}
+
+struct SafeModeState {
+    counter: Option<&'static CrashCounter>,
+    status: SafeModeStatus,
+}
+
+static SAFE_MODE: OnceLock<Mutex<SafeModeState>> = OnceLock::new();
+
+fn state() -> &'static Mutex<SafeModeState> {
+    SAFE_MODE.get_or_init(|| {
+        Mutex::new(SafeModeState {
+            counter: None,
+            status: SafeModeStatus::default(),
+        })
+    })
+}
+
+/// Registriert den Zähler des Prozesses; `active` = im Safe Mode gestartet.
+pub fn install(counter: CrashCounter, max_crashes: u32, previous: &CrashRecord, active: bool) {
+    let counter: &'static CrashCounter = Box::leak(Box::new(counter));
+    let mut state = lock_mutex(state(), "safe_mode.install");
+    state.counter = Some(counter);
+    state.status = SafeModeStatus {
+        active,
+        unstable_starts: previous.unstable_starts,
+        max_crashes,
+        last_crash_reason: previous.last_reason.clone(),
+        last_crash_ms: previous.last_crash_ms,
+    };
+}
+
+fn counter() -> Option<&'static CrashCounter> {
+    lock_mutex(state(), "safe_mode.counter").counter
+}
+
+pub fn safe_mode_status() -> SafeModeStatus {
+    lock_mutex(state(), "safe_mode.status").status.clone()
+}
+
+pub fn is_active() -> bool {
+    lock_mutex(state(), "safe_mode.is_active").status.active
+}
+
+pub fn record_failure(reason: &str) {
+    if let Some(counter) = counter() {
+        counter.record_failure(reason);
+    }
+}
+
+pub fn mark_stable() {
+    if let Some(counter) = counter() {
+        counter.mark_stable();
+    }
+}
+
+/// Verlässt den Safe Mode, nachdem die Konfiguration angewendet wurde.
+pub fn leave() {
+    mark_stable();
+    let mut state = lock_mutex(state(), "safe_mode.leave");
+    state.status.active = false;
+    state.status.unstable_starts = 0;
+}
+
+/// Panics vor dem stabilen Zustand landen als Grund in der Crash-Datei;
+/// der bisherige Hook läuft danach weiter.
+pub fn install_panic_hook() {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let message = info
+            .payload()
+            .downcast_ref::<&str>()
+            .map(|text| text.to_string())
+            .or_else(|| info.payload().downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        let location = info
+            .location()
+            .map(|location| format!(" at {}:{}", location.file(), location.line()))
+            .unwrap_or_default();
+        let thread = std::thread::current()
+            .name()
+            .unwrap_or("unnamed")
+            .to_string();
+        record_failure(&format!("panic in thread '{}': {}{}", thread, message, location));
+        previous(info);
+    }));
+}
@@ -0,0 +1,605 @@
+// src/core/scheduler.rs
+//
+// Zeitgesteuertes Aktivieren/Deaktivieren von Producern und Flows, z. B. um
+// 18:00 auf den Satelliten-Feed umschalten, sowie Start/Ende ausgelöster
+// Aufnahmen (FileConsumer mit `pre_roll`). Zeitpläne kommen aus
+// `[schedules.<name>]` im Cron-Format (Minute Stunde Tag Monat Wochentag) in
+// der Zeitzone des Ziel-Flows bzw. des Nodes (`timezone`, Standard UTC).
+// Sommerzeit wie bei cron: Zeitpunkte in der übersprungenen Stunde laufen
+// direkt nach der Umstellung, die doppelte Stunde feuert nur einmal.
+// Jede Ausführung erzeugt ein `ScheduleFired`-Event und steht in `/api/status`.
+// Beim Start und nach einem Reload wird pro Ziel der zuletzt fällige Zeitplan
+// nachgeholt, damit ein Neustart um 19:00 wieder auf dem Satelliten landet.
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail};
+use serde::Serialize;
+
+use crate::config::{Config, ScheduleConfig};
+use crate::core::error::AudioResult;
+use crate::core::lock::lock_mutex;
+use crate::core::timestamp::{civil_from_days, utc_ns_now};
+use crate::core::timezone::TimeZone;
+use crate::core::{AirliftNode, EventPriority, EventType};
+use crate::producers::wait::StopWait;
+
+const MINUTES_PER_DAY: u64 = 1440;
+/// Suchfenster für nächste/letzte Ausführung (auch 29. Februar)
+const SEARCH_DAYS: u64 = 5 * 366;
+const TICK: Duration = Duration::from_millis(500);
+/// Nach längeren Aussetzern (Suspend, Uhrsprung) nur die aktuelle Minute
+const MAX_CATCH_UP_MINUTES: u64 = 5;
+
+/// Cron-Ausdruck mit fünf Feldern; `*`, Listen, Bereiche, Schritte, Namen
+/// (`mon`, `jan`) und `@hourly`/`@daily`/`@weekly`/`@monthly`/`@yearly`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u32,
+    days: u32,
+    months: u16,
+    weekdays: u8,
+    /// Tag und Wochentag eingeschränkt: einer von beiden genügt (wie cron)
+    days_restricted: bool,
+    weekdays_restricted: bool,
+}
+
+const MONTH_NAMES: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+const WEEKDAY_NAMES: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+fn parse_value(text: &str, names: &[&str], offset: u32) -> anyhow::Result<u32> {
+    if let Some(index) = names.iter().position(|name| name.eq_ignore_ascii_case(text)) {
+        return Ok(index as u32 + offset);
+    }
+    text.parse::<u32>()
+        .map_err(|_| anyhow!("'{}' is not a number", text))
+}
+
+fn parse_field(spec: &str, min: u32, max: u32, names: &[&str], offset: u32) -> anyhow::Result<u64> {
+    let mut mask = 0u64;
+    for part in spec.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step = step
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|step| *step > 0)
+                    .ok_or_else(|| anyhow!("invalid step in '{}'", part))?;
+                (range, Some(step))
+            }
+            None => (part, None),
+        };
+        let (start, end) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((a, b)) => (parse_value(a, names, offset)?, parse_value(b, names, offset)?),
+                None => {
+                    let value = parse_value(range, names, offset)?;
+                    (value, if step.is_some() { max } else { value })
+                }
+            },
+        };
+        if start < min || end > max || start > end {
+            bail!("'{}' out of range ({}-{})", part, min, max);
+        }
+        for value in (start..=end).step_by(step.unwrap_or(1) as usize) {
+            mask |= 1u64 << value;
+        }
+    }
+    Ok(mask)
+}
+
+impl CronSchedule {
+    pub fn parse(expression: &str) -> anyhow::Result<Self> {
+        let expanded = match expression.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            other => other,
+        };
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            bail!("cron '{}': expected 5 fields (minute hour day month weekday)", expression);
+        };
+        let context = |field: &str, e: anyhow::Error| anyhow!("cron '{}': {} field: {}", expression, field, e);
+
+        let mut weekdays = parse_field(weekday, 0, 7, &WEEKDAY_NAMES, 0).map_err(|e| context("weekday", e))?;
+        // 7 = Sonntag wie 0
+        if weekdays & (1 << 7) != 0 {
+            weekdays = (weekdays | 1) & 0x7f;
+        }
+        Ok(Self {
+            minutes: parse_field(minute, 0, 59, &[], 0).map_err(|e| context("minute", e))?,
+            hours: parse_field(hour, 0, 23, &[], 0).map_err(|e| context("hour", e))? as u32,
+            days: parse_field(day, 1, 31, &[], 0).map_err(|e| context("day", e))? as u32,
+            months: parse_field(month, 1, 12, &MONTH_NAMES, 1).map_err(|e| context("month", e))? as u16,
+            weekdays: weekdays as u8,
+            days_restricted: !day.starts_with('*'),
+            weekdays_restricted: !weekday.starts_with('*'),
+        })
+    }
+
+    fn day_matches(&self, day: u64) -> bool {
+        let (_, month, dom) = civil_from_days(day as i64);
+        if self.months & (1 << month) == 0 {
+            return false;
+        }
+        let dom_ok = self.days & (1 << dom) != 0;
+        // 1970-01-01 war ein Donnerstag
+        let dow_ok = self.weekdays & (1 << ((day + 4) % 7)) != 0;
+        match (self.days_restricted, self.weekdays_restricted) {
+            (true, true) => dom_ok || dow_ok,
+            (true, false) => dom_ok,
+            (false, true) => dow_ok,
+            (false, false) => true,
+        }
+    }
+
+    /// Passt auf die Minute `minute` (Minuten seit Epoch, UTC)?
+    pub fn matches_minute(&self, minute: u64) -> bool {
+        let in_day = minute % MINUTES_PER_DAY;
+        self.minutes & (1 << (in_day % 60)) != 0
+            && self.hours & (1 << (in_day / 60)) != 0
+            && self.day_matches(minute / MINUTES_PER_DAY)
+    }
+
+    /// Nächste Ausführung nach `ms` (Millisekunden seit Epoch).
+    pub fn next_after(&self, ms: u64) -> Option<u64> {
+        let mut minute = ms / 60_000 + 1;
+        let limit = minute + SEARCH_DAYS * MINUTES_PER_DAY;
+        while minute < limit {
+            let day = minute / MINUTES_PER_DAY;
+            if !self.day_matches(day) {
+                minute = (day + 1) * MINUTES_PER_DAY;
+                continue;
+            }
+            let hour = (minute % MINUTES_PER_DAY) / 60;
+            if self.hours & (1 << hour) == 0 {
+                minute = day * MINUTES_PER_DAY + (hour + 1) * 60;
+                continue;
+            }
+            if self.minutes & (1 << (minute % 60)) != 0 {
+                return Some(minute * 60_000);
+            }
+            minute += 1;
+        }
+        None
+    }
+
+    /// Letzte Ausführung bis einschließlich der Minute von `ms`.
+    pub fn previous_at_or_before(&self, ms: u64) -> Option<u64> {
+        let mut minute = ms / 60_000;
+        let limit = minute.saturating_sub(SEARCH_DAYS * MINUTES_PER_DAY);
+        while minute >= limit {
+            let day = minute / MINUTES_PER_DAY;
+            if !self.day_matches(day) {
+                minute = (day * MINUTES_PER_DAY).checked_sub(1)?;
+                continue;
+            }
+            let hour = (minute % MINUTES_PER_DAY) / 60;
+            if self.hours & (1 << hour) == 0 {
+                minute = (day * MINUTES_PER_DAY + hour * 60).checked_sub(1)?;
+                continue;
+            }
+            if self.minutes & (1 << (minute % 60)) != 0 {
+                return Some(minute * 60_000);
+            }
+            minute = minute.checked_sub(1)?;
+        }
+        None
+    }
+
+    /// Feuert der Zeitplan in der UTC-Minute `minute`? Geprüft werden alle
+    /// Ortszeit-Minuten, die seit der vorigen UTC-Minute erstmals erreicht
+    /// wurden (nach einer Vorstellung mehrere, nach einer Rückstellung keine).
+    pub fn fires_at_minute(&self, minute: u64, tz: &TimeZone) -> bool {
+        let local = local_minute(tz, minute);
+        let seen = high_water(tz, minute);
+        (seen + 1..=local).any(|candidate| self.matches_minute(candidate as u64))
+    }
+
+    /// Nächste Ausführung nach `ms` in der Zeitzone `tz` (UTC-Millisekunden).
+    pub fn next_after_in(&self, ms: u64, tz: &TimeZone) -> Option<u64> {
+        let mut minute = ms / 60_000 + 1;
+        let limit = minute + SEARCH_DAYS * MINUTES_PER_DAY;
+        while minute < limit {
+            if self.fires_at_minute(minute, tz) {
+                return Some(minute * 60_000);
+            }
+            // Bis zum nächsten Wechsel ist der Offset konstant
+            let offset = local_minute(tz, minute) - minute as i64;
+            let from_local = local_minute(tz, minute).max(high_water(tz, minute));
+            let candidate = self.next_after(from_local as u64 * 60_000)? / 60_000;
+            let candidate_utc = (candidate as i64 - offset) as u64;
+            match next_transition_minute(tz, minute) {
+                Some(change) if candidate_utc >= change => minute = change,
+                _ => return Some(candidate_utc * 60_000),
+            }
+        }
+        None
+    }
+
+    /// Letzte Ausführung bis einschließlich der Minute von `ms` in `tz`.
+    pub fn previous_at_or_before_in(&self, ms: u64, tz: &TimeZone) -> Option<u64> {
+        let mut minute = ms / 60_000;
+        let limit = minute.saturating_sub(SEARCH_DAYS * MINUTES_PER_DAY);
+        while minute >= limit {
+            let offset = local_minute(tz, minute) - minute as i64;
+            let change = last_transition_minute(tz, minute);
+            // Minuten nach dem Wechsel: eigene Ortszeit, aber nur, wenn sie
+            // nicht schon vor dem Wechsel vorkam
+            let floor = change
+                .map(|change| local_minute(tz, change).max(high_water(tz, change)))
+                .unwrap_or(i64::MIN);
+            let local = local_minute(tz, minute) as u64;
+            if let Some(candidate) = self.previous_at_or_before(local * 60_000) {
+                let candidate = (candidate / 60_000) as i64;
+                if candidate > floor {
+                    return Some((candidate - offset) as u64 * 60_000);
+                }
+            }
+            let change = change?;
+            if change < limit {
+                return None;
+            }
+            if self.fires_at_minute(change, tz) {
+                return Some(change * 60_000);
+            }
+            minute = change.checked_sub(1)?;
+        }
+        None
+    }
+}
+
+/// Ortszeit-Minute (Minuten seit Epoch) zur UTC-Minute.
+fn local_minute(tz: &TimeZone, minute: u64) -> i64 {
+    tz.to_local(minute as i64 * 60).div_euclid(60)
+}
+
+/// Höchste Ortszeit-Minute aller UTC-Minuten vor `minute`; nach einer
+/// Rückstellung liegt sie vor der aktuellen Ortszeit.
+fn high_water(tz: &TimeZone, minute: u64) -> i64 {
+    let previous = local_minute(tz, minute.saturating_sub(1));
+    match last_transition_minute(tz, minute.saturating_sub(1)) {
+        Some(change) if change > 0 => previous.max(local_minute(tz, change - 1)),
+        _ => previous,
+    }
+}
+
+/// Erste UTC-Minute mit neuem Offset, bei oder vor `minute`.
+fn last_transition_minute(tz: &TimeZone, minute: u64) -> Option<u64> {
+    tz.last_transition_at_or_before(minute as i64 * 60 + 59)
+        .map(|t| t.at.div_euclid(60).max(0) as u64)
+}
+
+/// Erste UTC-Minute mit neuem Offset nach `minute`.
+fn next_transition_minute(tz: &TimeZone, minute: u64) -> Option<u64> {
+    tz.next_transition_after(minute as i64 * 60 + 59)
+        .map(|t| t.at.div_euclid(60).max(0) as u64)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScheduleAction {
+    Enable,
+    Disable,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScheduleTarget {
+    Producer(String),
+    Flow(String),
+    Consumer(String),
+}
+
+impl std::fmt::Display for ScheduleTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Producer(name) => write!(f, "producer:{}", name),
+            Self::Flow(name) => write!(f, "flow:{}", name),
+            Self::Consumer(name) => write!(f, "consumer:{}", name),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ScheduleEntry {
+    pub name: String,
+    pub expression: String,
+    pub cron: CronSchedule,
+    pub action: ScheduleAction,
+    pub target: ScheduleTarget,
+    /// Zeitzone, in der `cron` gelesen wird
+    pub timezone: TimeZone,
+}
+
+impl ScheduleEntry {
+    pub fn from_config(name: &str, cfg: &ScheduleConfig) -> anyhow::Result<Self> {
+        let action = match cfg.action.to_ascii_lowercase().as_str() {
+            "enable" | "start" => ScheduleAction::Enable,
+            "disable" | "stop" => ScheduleAction::Disable,
+            other => bail!("schedule '{}': unknown action '{}' (enable, disable)", name, other),
+        };
+        let target = match (&cfg.producer, &cfg.flow, &cfg.consumer) {
+            (Some(producer), None, None) => ScheduleTarget::Producer(producer.clone()),
+            (None, Some(flow), None) => ScheduleTarget::Flow(flow.clone()),
+            (None, None, Some(consumer)) => ScheduleTarget::Consumer(consumer.clone()),
+            _ => bail!("schedule '{}': set exactly one of 'producer', 'flow' or 'consumer'", name),
+        };
+        Ok(Self {
+            name: name.to_string(),
+            expression: cfg.cron.clone(),
+            cron: CronSchedule::parse(&cfg.cron).map_err(|e| anyhow!("schedule '{}': {}", name, e))?,
+            action,
+            target,
+            timezone: TimeZone::utc(),
+        })
+    }
+
+    pub fn with_timezone(mut self, timezone: TimeZone) -> Self {
+        self.timezone = timezone;
+        self
+    }
+
+    /// Alle aktiven Zeitpläne der Config, nach Namen sortiert. Flow-Ziele
+    /// nutzen die Zeitzone des Flows, sonst gilt die des Nodes.
+    pub fn from_configs(config: &Config) -> anyhow::Result<Vec<Self>> {
+        let mut entries = config
+            .schedules
+            .iter()
+            .filter(|(_, cfg)| cfg.enabled)
+            .map(|(name, cfg)| {
+                let entry = Self::from_config(name, cfg)?;
+                let timezone = match &entry.target {
+                    ScheduleTarget::Flow(flow) => config.flow_timezone(flow)?,
+                    ScheduleTarget::Producer(_) | ScheduleTarget::Consumer(_) => config.timezone()?,
+                };
+                Ok(entry.with_timezone(timezone))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(entries)
+    }
+
+    pub fn next_after(&self, ms: u64) -> Option<u64> {
+        self.cron.next_after_in(ms, &self.timezone)
+    }
+
+    /// Führt die Aktion aus und veröffentlicht ein `ScheduleFired`-Event.
+    pub fn apply(&self, node: &mut AirliftNode, reason: &str) -> AudioResult<()> {
+        let result = match (&self.target, self.action) {
+            (ScheduleTarget::Producer(name), ScheduleAction::Enable) => node.start_producer_by_name(name),
+            (ScheduleTarget::Producer(name), ScheduleAction::Disable) => node.stop_producer_by_name(name),
+            (ScheduleTarget::Flow(name), ScheduleAction::Enable) => node.start_flow_by_name(name),
+            (ScheduleTarget::Flow(name), ScheduleAction::Disable) => node.stop_flow_by_name(name),
+            (ScheduleTarget::Consumer(name), action) => {
+                node.set_consumer_recording(name, action == ScheduleAction::Enable)
+            }
+        };
+        let error = result.as_ref().err().map(|e| e.to_string());
+        node.publish_event(
+            EventType::ScheduleFired,
+            if error.is_some() { EventPriority::Warning } else { EventPriority::Info },
+            serde_json::json!({
+                "schedule": self.name,
+                "action": self.action,
+                "target": self.target.to_string(),
+                "timezone": self.timezone.name(),
+                "reason": reason,
+                "ok": error.is_none(),
+                "error": error,
+                "timestamp": utc_ns_now(),
+            }),
+        );
+        result
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduleStatus {
+    pub name: String,
+    pub cron: String,
+    pub action: ScheduleAction,
+    pub target: String,
+    pub timezone: String,
+    pub next_run_ms: Option<u64>,
+    /// `next_run_ms` als Ortszeit mit Offset, z. B. `2026-10-25T02:30:00+01:00`
+    pub next_run_local: Option<String>,
+    pub last_run_ms: Option<u64>,
+    pub last_error: Option<String>,
+    pub runs: u64,
+}
+
+impl ScheduleStatus {
+    fn new(entry: &ScheduleEntry, now_ms: u64) -> Self {
+        let mut status = Self {
+            name: entry.name.clone(),
+            cron: entry.expression.clone(),
+            action: entry.action,
+            target: entry.target.to_string(),
+            timezone: entry.timezone.name().to_string(),
+            next_run_ms: None,
+            next_run_local: None,
+            last_run_ms: None,
+            last_error: None,
+            runs: 0,
+        };
+        status.set_next_run(entry, now_ms);
+        status
+    }
+
+    fn set_next_run(&mut self, entry: &ScheduleEntry, now_ms: u64) {
+        self.next_run_ms = entry.next_after(now_ms);
+        self.next_run_local = self.next_run_ms.map(|ms| entry.timezone.format_local(ms));
+    }
+}
+
+struct SchedulerState {
+    entries: Vec<ScheduleEntry>,
+    status: Vec<ScheduleStatus>,
+    /// Nach Start/Reload den Sollzustand der Ziele herstellen
+    resync: bool,
+}
+
+struct SchedulerThread {
+    stop: Arc<AtomicBool>,
+    wait: Arc<StopWait>,
+    handle: JoinHandle<()>,
+}
+
+pub struct SchedulerService {
+    state: Arc<Mutex<SchedulerState>>,
+    thread: Mutex<Option<SchedulerThread>>,
+}
+
+static SCHEDULER: OnceLock<SchedulerService> = OnceLock::new();
+
+pub fn scheduler() -> &'static SchedulerService {
+    SCHEDULER.get_or_init(|| SchedulerService {
+        state: Arc::new(Mutex::new(SchedulerState {
+            entries: Vec::new(),
+            status: Vec::new(),
+            resync: false,
+        })),
+        thread: Mutex::new(None),
+    })
+}
+
+impl SchedulerService {
+    /// Ersetzt die Zeitpläne (z. B. nach Reload). Hält dabei keinen Node-Lock,
+    /// das Nachholen übernimmt der Scheduler-Thread.
+    pub fn set_entries(&self, entries: Vec<ScheduleEntry>) {
+        let now_ms = utc_ns_now() / 1_000_000;
+        let mut state = lock_mutex(&self.state, "scheduler.set_entries");
+        state.status = entries
+            .iter()
+            .map(|entry| ScheduleStatus::new(entry, now_ms))
+            .collect();
+        state.entries = entries;
+        state.resync = true;
+    }
+
+    /// Startet den Scheduler-Thread (einmalig; weitere Aufrufe tauschen nur die Einträge).
+    pub fn start(&self, node: Arc<Mutex<AirliftNode>>, entries: Vec<ScheduleEntry>) {
+        let count = entries.len();
+        self.set_entries(entries);
+        let mut thread = lock_mutex(&self.thread, "scheduler.start");
+        if thread.is_some() {
+            return;
+        }
+        log::info!("Scheduler started with {} schedule(s)", count);
+        let stop = Arc::new(AtomicBool::new(false));
+        let wait = Arc::new(StopWait::new());
+        let state = self.state.clone();
+        let handle = {
+            let stop = stop.clone();
+            let wait = wait.clone();
+            std::thread::spawn(move || run(state, node, stop, wait))
+        };
+        *thread = Some(SchedulerThread { stop, wait, handle });
+    }
+
+    pub fn stop(&self) {
+        if let Some(thread) = lock_mutex(&self.thread, "scheduler.stop").take() {
+            thread.stop.store(true, Ordering::SeqCst);
+            thread.wait.notify_all();
+            let _ = thread.handle.join();
+        }
+    }
+
+    pub fn status(&self) -> Vec<ScheduleStatus> {
+        lock_mutex(&self.state, "scheduler.status").status.clone()
+    }
+}
+
+fn run(
+    state: Arc<Mutex<SchedulerState>>,
+    node: Arc<Mutex<AirliftNode>>,
+    stop: Arc<AtomicBool>,
+    wait: Arc<StopWait>,
+) {
+    let mut last_minute = utc_ns_now() / 60_000_000_000;
+    while !stop.load(Ordering::Relaxed) {
+        let now_ms = utc_ns_now() / 1_000_000;
+        let minute = now_ms / 60_000;
+
+        let resync = std::mem::take(&mut lock_mutex(&state, "scheduler.resync").resync);
+        if resync {
+            resync_targets(&state, &node, now_ms);
+        }
+        if minute > last_minute {
+            let first = (last_minute + 1).max(minute.saturating_sub(MAX_CATCH_UP_MINUTES - 1));
+            for due in first..=minute {
+                fire_due(&state, &node, due);
+            }
+        }
+        // Uhr zurückgestellt: nicht doppelt feuern
+        last_minute = last_minute.max(minute);
+        wait.wait_timeout(TICK);
+    }
+}
+
+/// Pro Ziel den zuletzt fälligen Zeitplan anwenden.
+fn resync_targets(state: &Arc<Mutex<SchedulerState>>, node: &Arc<Mutex<AirliftNode>>, now_ms: u64) {
+    let entries = lock_mutex(state, "scheduler.resync_entries").entries.clone();
+    let mut latest: HashMap<String, (u64, &ScheduleEntry)> = HashMap::new();
+    for entry in &entries {
+        if let Some(at) = entry.cron.previous_at_or_before_in(now_ms, &entry.timezone) {
+            let key = entry.target.to_string();
+            if !matches!(latest.get(&key), Some((best, _)) if *best >= at) {
+                latest.insert(key, (at, entry));
+            }
+        }
+    }
+    let mut due: Vec<(u64, &ScheduleEntry)> = latest.into_values().collect();
+    due.sort_by(|a, b| a.1.name.cmp(&b.1.name));
+    for (at, entry) in due {
+        execute(state, node, entry, at, "resync");
+    }
+}
+
+fn fire_due(state: &Arc<Mutex<SchedulerState>>, node: &Arc<Mutex<AirliftNode>>, minute: u64) {
+    let entries = lock_mutex(state, "scheduler.due_entries").entries.clone();
+    for entry in entries
+        .iter()
+        .filter(|entry| entry.cron.fires_at_minute(minute, &entry.timezone))
+    {
+        execute(state, node, entry, minute * 60_000, "schedule");
+    }
+}
+
+fn execute(
+    state: &Arc<Mutex<SchedulerState>>,
+    node: &Arc<Mutex<AirliftNode>>,
+    entry: &ScheduleEntry,
+    at_ms: u64,
+    reason: &str,
+) {
+    let result = entry.apply(&mut lock_mutex(node, "scheduler.apply"), reason);
+    match &result {
+        Ok(()) => log::info!(
+            "Schedule '{}' ({}): {:?} {}",
+            entry.name,
+            reason,
+            entry.action,
+            entry.target
+        ),
+        Err(e) => log::warn!("Schedule '{}' failed: {:?} {}: {}", entry.name, entry.action, entry.target, e),
+    }
+
+    let mut state = lock_mutex(state, "scheduler.record");
+    if let Some(status) = state.status.iter_mut().find(|status| status.name == entry.name) {
+        status.last_run_ms = Some(at_ms);
+        status.last_error = result.err().map(|e| e.to_string());
+        status.runs += 1;
+        status.set_next_run(entry, utc_ns_now() / 1_000_000);
+    }
+}
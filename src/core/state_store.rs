@@ -0,0 +1,303 @@
+// src/core/state_store.rs
+//
+// Laufzeitzustand über Neustarts hinweg: Encoded-Ring samt Reader-Cursorn,
+// Splice-Modus und letzter Codec eines Encoded-Flows sowie die Position des
+// Recorders in der offenen `.part`-Datei. Ein schnelles Upgrade (oder ein
+// Absturz) verliert so weder das Timeshift-Fenster noch hinterlässt es eine
+// kaputte Archivdatei.
+//
+// Jeder Schlüssel ist eine Datei `<state_dir>/<key>.state`: Magic, Version,
+// Speicherzeitpunkt, dann die Nutzdaten (JSON oder Binär). Geschrieben wird
+// über eine temporäre Datei plus `rename`, damit ein Absturz mitten im
+// Schreiben nie einen halben Zustand hinterlässt.
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{bail, Context};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::codecs::{CodecInfo, CodecKind, ContainerKind, EncodedFrame};
+use crate::core::lock::lock_mutex;
+use crate::ring::encoded_ring::EncodedSlot;
+
+/// Ältere Zustände werden verworfen (Timeshift-Fenster, Splice-Modus).
+pub const DEFAULT_MAX_AGE: Duration = Duration::from_secs(300);
+/// Abstand der periodischen Snapshots
+pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(5);
+/// Format-Version; ältere oder neuere Dateien werden ignoriert
+pub const STATE_VERSION: u32 = 1;
+const MAGIC: &[u8; 8] = b"AIRLSTAT";
+const HEADER_LEN: usize = 8 + 4 + 8;
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Gespeicherter Wert mit Alter.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stored<T> {
+    pub value: T,
+    pub saved_at_ms: u64,
+    pub age: Duration,
+}
+
+pub struct StateStore {
+    dir: PathBuf,
+    max_age: Duration,
+    interval: Duration,
+}
+
+impl StateStore {
+    pub fn open(dir: impl AsRef<Path>, max_age: Duration, interval: Duration) -> anyhow::Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("cannot create state dir {}", dir.display()))?;
+        Ok(Self {
+            dir,
+            max_age,
+            interval,
+        })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn max_age(&self) -> Duration {
+        self.max_age
+    }
+
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        let file: String = key
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+            .collect();
+        self.dir.join(format!("{}.state", file))
+    }
+
+    pub fn save_bytes(&self, key: &str, payload: &[u8]) -> anyhow::Result<()> {
+        let path = self.path(key);
+        let mut data = Vec::with_capacity(HEADER_LEN + payload.len());
+        data.extend_from_slice(MAGIC);
+        data.extend_from_slice(&STATE_VERSION.to_le_bytes());
+        data.extend_from_slice(&now_ms().to_le_bytes());
+        data.extend_from_slice(payload);
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, &data)
+            .and_then(|_| std::fs::rename(&tmp, &path))
+            .with_context(|| format!("cannot write {}", path.display()))
+    }
+
+    /// Nutzdaten unabhängig vom Alter; unlesbare Dateien gelten als fehlend.
+    pub fn load_bytes(&self, key: &str) -> Option<Stored<Vec<u8>>> {
+        let path = self.path(key);
+        let data = std::fs::read(&path).ok()?;
+        if data.len() < HEADER_LEN || &data[..8] != MAGIC {
+            log::warn!("[state] ignoring {}: not a state file", path.display());
+            return None;
+        }
+        let version = u32::from_le_bytes(data[8..12].try_into().ok()?);
+        if version != STATE_VERSION {
+            log::warn!(
+                "[state] ignoring {}: version {} (expected {})",
+                path.display(),
+                version,
+                STATE_VERSION
+            );
+            return None;
+        }
+        let saved_at_ms = u64::from_le_bytes(data[12..20].try_into().ok()?);
+        Some(Stored {
+            value: data[HEADER_LEN..].to_vec(),
+            saved_at_ms,
+            age: Duration::from_millis(now_ms().saturating_sub(saved_at_ms)),
+        })
+    }
+
+    pub fn save_json<T: Serialize>(&self, key: &str, value: &T) -> anyhow::Result<()> {
+        self.save_bytes(key, &serde_json::to_vec(value)?)
+    }
+
+    pub fn load_json<T: DeserializeOwned>(&self, key: &str) -> Option<Stored<T>> {
+        let stored = self.load_bytes(key)?;
+        match serde_json::from_slice(&stored.value) {
+            Ok(value) => Some(Stored {
+                value,
+                saved_at_ms: stored.saved_at_ms,
+                age: stored.age,
+            }),
+            Err(e) => {
+                log::warn!("[state] ignoring '{}': {}", key, e);
+                None
+            }
+        }
+    }
+
+    /// Wie `load_bytes`, aber nur jünger als `max_age`.
+    pub fn load_fresh_bytes(&self, key: &str) -> Option<Stored<Vec<u8>>> {
+        self.load_bytes(key).filter(|stored| self.is_fresh(key, stored.age))
+    }
+
+    /// Wie `load_json`, aber nur jünger als `max_age`.
+    pub fn load_fresh_json<T: DeserializeOwned>(&self, key: &str) -> Option<Stored<T>> {
+        self.load_json(key).filter(|stored| self.is_fresh(key, stored.age))
+    }
+
+    fn is_fresh(&self, key: &str, age: Duration) -> bool {
+        if age > self.max_age {
+            log::info!("[state] '{}' is {} s old, discarding", key, age.as_secs());
+            return false;
+        }
+        true
+    }
+
+    pub fn remove(&self, key: &str) {
+        let path = self.path(key);
+        if let Err(e) = std::fs::remove_file(&path) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                log::warn!("[state] cannot remove {}: {}", path.display(), e);
+            }
+        }
+    }
+}
+
+static STATE_STORE: OnceLock<Mutex<Option<Arc<StateStore>>>> = OnceLock::new();
+
+fn slot() -> &'static Mutex<Option<Arc<StateStore>>> {
+    STATE_STORE.get_or_init(|| Mutex::new(None))
+}
+
+/// Registriert den Store des Prozesses (`None` = keine Persistenz).
+pub fn install(store: Option<StateStore>) {
+    *lock_mutex(slot(), "state_store.install") = store.map(Arc::new);
+}
+
+pub fn installed() -> Option<Arc<StateStore>> {
+    lock_mutex(slot(), "state_store.installed").clone()
+}
+
+fn codec_kind_code(kind: &CodecKind) -> u8 {
+    match kind {
+        CodecKind::Pcm => 0,
+        CodecKind::OpusOgg => 1,
+        CodecKind::OpusWebRtc => 2,
+        CodecKind::Mp3 => 3,
+        CodecKind::Vorbis => 4,
+        CodecKind::AacLc => 5,
+        CodecKind::Flac => 6,
+    }
+}
+
+fn codec_kind_from(code: u8) -> anyhow::Result<CodecKind> {
+    Ok(match code {
+        0 => CodecKind::Pcm,
+        1 => CodecKind::OpusOgg,
+        2 => CodecKind::OpusWebRtc,
+        3 => CodecKind::Mp3,
+        4 => CodecKind::Vorbis,
+        5 => CodecKind::AacLc,
+        6 => CodecKind::Flac,
+        other => bail!("unknown codec code {}", other),
+    })
+}
+
+fn container_code(container: &ContainerKind) -> u8 {
+    match container {
+        ContainerKind::Raw => 0,
+        ContainerKind::Ogg => 1,
+        ContainerKind::Mpeg => 2,
+        ContainerKind::Rtp => 3,
+    }
+}
+
+fn container_from(code: u8) -> anyhow::Result<ContainerKind> {
+    Ok(match code {
+        0 => ContainerKind::Raw,
+        1 => ContainerKind::Ogg,
+        2 => ContainerKind::Mpeg,
+        3 => ContainerKind::Rtp,
+        other => bail!("unknown container code {}", other),
+    })
+}
+
+/// Binärformat der Ring-Slots: pro Slot seq, utc_ns, Codec, Payload.
+pub fn encode_slots(slots: &[EncodedSlot]) -> Vec<u8> {
+    let payload: usize = slots.iter().map(|slot| slot.frame.payload.len() + 31).sum();
+    let mut out = Vec::with_capacity(4 + payload);
+    out.extend_from_slice(&(slots.len() as u32).to_le_bytes());
+    for slot in slots {
+        let info = &slot.frame.info;
+        out.extend_from_slice(&slot.seq.to_le_bytes());
+        out.extend_from_slice(&slot.utc_ns.to_le_bytes());
+        out.push(codec_kind_code(&info.kind));
+        out.extend_from_slice(&info.sample_rate.to_le_bytes());
+        out.push(info.channels);
+        out.push(container_code(&info.container));
+        out.extend_from_slice(&(slot.frame.payload.len() as u32).to_le_bytes());
+        out.extend_from_slice(&slot.frame.payload);
+    }
+    out
+}
+
+pub fn decode_slots(data: &[u8]) -> anyhow::Result<Vec<EncodedSlot>> {
+    struct Cursor<'a> {
+        data: &'a [u8],
+        pos: usize,
+    }
+    impl<'a> Cursor<'a> {
+        fn take(&mut self, len: usize) -> anyhow::Result<&'a [u8]> {
+            if self.data.len() - self.pos < len {
+                bail!("ring snapshot truncated at byte {}", self.pos);
+            }
+            let bytes = &self.data[self.pos..self.pos + len];
+            self.pos += len;
+            Ok(bytes)
+        }
+        fn u8(&mut self) -> anyhow::Result<u8> {
+            Ok(self.take(1)?[0])
+        }
+        fn u32(&mut self) -> anyhow::Result<u32> {
+            Ok(u32::from_le_bytes(self.take(4)?.try_into()?))
+        }
+        fn u64(&mut self) -> anyhow::Result<u64> {
+            Ok(u64::from_le_bytes(self.take(8)?.try_into()?))
+        }
+    }
+
+    let mut cursor = Cursor { data, pos: 0 };
+    let count = cursor.u32()? as usize;
+    let mut slots = Vec::with_capacity(count.min(data.len() / 27));
+    for _ in 0..count {
+        let seq = cursor.u64()?;
+        let utc_ns = cursor.u64()?;
+        let kind = codec_kind_from(cursor.u8()?)?;
+        let sample_rate = cursor.u32()?;
+        let channels = cursor.u8()?;
+        let container = container_from(cursor.u8()?)?;
+        let len = cursor.u32()? as usize;
+        let payload = cursor.take(len)?.to_vec();
+        slots.push(EncodedSlot {
+            seq,
+            utc_ns,
+            frame: Arc::new(EncodedFrame {
+                payload,
+                info: CodecInfo {
+                    kind,
+                    sample_rate,
+                    channels,
+                    container,
+                },
+            }),
+        });
+    }
+    Ok(slots)
+}
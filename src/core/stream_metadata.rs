@@ -0,0 +1,187 @@
+// src/core/stream_metadata.rs
+//
+// Titel/Interpret laufender Streams, gesetzt über `/api/metadata`. Ein
+// Eintrag ohne Consumer gilt für alle Ausgänge, ein Eintrag pro Consumer nur
+// für diesen; es gilt jeweils der zuletzt gesetzte. Icecast-Ausgänge mit
+// Ogg-Codec beginnen bei jeder Änderung einen neuen Ogg-Link mit passenden
+// Kommentaren (`audio::ogg::OggChainer`), Hörer bleiben verbunden.
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::core::lock::lock_mutex;
+use crate::core::timestamp::utc_ns_now;
+
+const MAX_VALUE_LEN: usize = 1024;
+const MAX_TAGS: usize = 32;
+const MAX_KEY_LEN: usize = 64;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StreamMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub artist: Option<String>,
+    /// Weitere Vorbis-Kommentare, z. B. `ALBUM`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tags: BTreeMap<String, String>,
+}
+
+impl StreamMetadata {
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none() && self.artist.is_none() && self.tags.is_empty()
+    }
+
+    /// Leere Werte entfernen, Schlüssel in Großbuchstaben, Grenzen prüfen.
+    pub fn normalized(self) -> Result<Self> {
+        let clean = |value: Option<String>| {
+            value
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+        let title = clean(self.title);
+        let artist = clean(self.artist);
+        if self.tags.len() > MAX_TAGS {
+            bail!("metadata allows at most {} tags", MAX_TAGS);
+        }
+        let mut tags = BTreeMap::new();
+        for (key, value) in self.tags {
+            let key = key.trim().to_ascii_uppercase();
+            // Vorbis-Kommentar: druckbares ASCII ohne '='
+            if key.is_empty()
+                || key.len() > MAX_KEY_LEN
+                || !key.bytes().all(|b| (0x20..=0x7d).contains(&b) && b != b'=')
+            {
+                bail!(
+                    "metadata tag '{}' must be 1..={} printable ASCII characters without '='",
+                    key,
+                    MAX_KEY_LEN
+                );
+            }
+            if key == "TITLE" || key == "ARTIST" {
+                bail!(
+                    "metadata tag '{}' must be set as '{}'",
+                    key,
+                    key.to_ascii_lowercase()
+                );
+            }
+            if let Some(value) = clean(Some(value)) {
+                tags.insert(key, value);
+            }
+        }
+        let metadata = Self {
+            title,
+            artist,
+            tags,
+        };
+        for value in metadata.values() {
+            if value.len() > MAX_VALUE_LEN {
+                bail!("metadata values must not exceed {} bytes", MAX_VALUE_LEN);
+            }
+        }
+        if metadata.is_empty() {
+            bail!("metadata needs a title, an artist or tags");
+        }
+        Ok(metadata)
+    }
+
+    fn values(&self) -> impl Iterator<Item = &String> {
+        self.title
+            .iter()
+            .chain(self.artist.iter())
+            .chain(self.tags.values())
+    }
+
+    /// Kommentare im Vorbis-Format (`TITLE=...`), Titel und Interpret zuerst.
+    pub fn comments(&self) -> Vec<String> {
+        let mut comments = Vec::new();
+        if let Some(title) = &self.title {
+            comments.push(format!("TITLE={}", title));
+        }
+        if let Some(artist) = &self.artist {
+            comments.push(format!("ARTIST={}", artist));
+        }
+        comments.extend(
+            self.tags
+                .iter()
+                .map(|(key, value)| format!("{}={}", key, value)),
+        );
+        comments
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MetadataEntry {
+    #[serde(flatten)]
+    pub metadata: StreamMetadata,
+    /// Steigt mit jeder Änderung (über alle Einträge)
+    pub version: u64,
+    pub updated_ms: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MetadataBoard {
+    /// Für alle Ausgänge
+    pub all: Option<MetadataEntry>,
+    pub consumers: BTreeMap<String, MetadataEntry>,
+}
+
+static BOARD: OnceLock<Mutex<MetadataBoard>> = OnceLock::new();
+static VERSION: AtomicU64 = AtomicU64::new(0);
+
+fn board_slot() -> &'static Mutex<MetadataBoard> {
+    BOARD.get_or_init(|| Mutex::new(MetadataBoard::default()))
+}
+
+/// Setzt Metadaten für einen Consumer bzw. (`None`) für alle.
+pub fn set(consumer: Option<&str>, metadata: StreamMetadata) -> Result<MetadataEntry> {
+    let metadata = metadata.normalized()?;
+    let mut board = lock_mutex(board_slot(), "stream_metadata.set");
+    let entry = MetadataEntry {
+        metadata,
+        version: VERSION.load(Ordering::Acquire) + 1,
+        updated_ms: utc_ns_now() / 1_000_000,
+    };
+    match consumer {
+        Some(consumer) => {
+            board.consumers.insert(consumer.to_string(), entry.clone());
+        }
+        None => board.all = Some(entry.clone()),
+    }
+    VERSION.store(entry.version, Ordering::Release);
+    Ok(entry)
+}
+
+/// Entfernt die Metadaten eines Consumers bzw. (`None`) die für alle; laufende
+/// Streams behalten ihren letzten Titel.
+pub fn clear(consumer: Option<&str>) {
+    let mut board = lock_mutex(board_slot(), "stream_metadata.clear");
+    match consumer {
+        Some(consumer) => {
+            board.consumers.remove(consumer);
+        }
+        None => board.all = None,
+    }
+}
+
+/// Zähler aller Änderungen; billig genug für jeden Frame.
+pub fn version() -> u64 {
+    VERSION.load(Ordering::Acquire)
+}
+
+/// Gültige Metadaten für `consumer`.
+pub fn current(consumer: &str) -> Option<MetadataEntry> {
+    let board = lock_mutex(board_slot(), "stream_metadata.current");
+    [board.all.as_ref(), board.consumers.get(consumer)]
+        .into_iter()
+        .flatten()
+        .max_by_key(|entry| entry.version)
+        .cloned()
+}
+
+pub fn snapshot() -> MetadataBoard {
+    lock_mutex(board_slot(), "stream_metadata.snapshot").clone()
+}
@@ -17,3 +17,29 @@ pub fn ns_since_midnight(utc_ns: u64) -> u64 {
     let seconds_since_midnight = seconds_since_epoch % seconds_in_day;
     seconds_since_midnight * 1_000_000_000 + (utc_ns % 1_000_000_000)
 }
+
+/// Tage seit 1970-01-01 zu (Jahr, Monat, Tag), proleptisch gregorianisch
+/// (civil_from_days, H. Hinnant).
+pub fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month as u32, day as u32)
+}
+
+/// Umkehrung von `civil_from_days`: (Jahr, Monat, Tag) zu Tagen seit 1970-01-01.
+pub fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = year - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let mp = (i64::from(month) + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
@@ -0,0 +1,608 @@
+// src/core/timezone.rs
+//
+// Zeitzonen für Scheduler und Aufnahmen, ohne externe Crate: UTC, feste
+// Offsets ("+01:00"), POSIX-TZ-Regeln ("CET-1CEST,M3.5.0,M10.5.0/3") und
+// IANA-Namen ("Europe/Berlin"). Bei IANA-Namen wird aus der TZif-Datei unter
+// `$TZDIR` bzw. `/usr/share/zoneinfo` nur die POSIX-Regel am Dateiende
+// gelesen, d. h. die aktuell gültige Sommerzeitregel gilt auch rückwirkend.
+//
+// Alle Zeiten sind Sekunden bzw. Millisekunden seit Epoch (UTC); Offsets in
+// Sekunden östlich von UTC.
+use std::fmt;
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{anyhow, bail, Context, Result};
+
+use crate::core::timestamp::{civil_from_days, days_from_civil};
+
+const DEFAULT_ZONEINFO_DIR: &str = "/usr/share/zoneinfo";
+const SECONDS_PER_DAY: i64 = 86_400;
+/// Standard-Umschaltzeit der POSIX-Regeln (02:00 Ortszeit)
+const DEFAULT_RULE_TIME: i32 = 2 * 3600;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RuleDate {
+    /// `Mm.w.d`: Monat, Woche 1-5 (5 = letzte), Wochentag 0 = Sonntag
+    MonthWeekDay { month: u32, week: u32, weekday: u32 },
+    /// `Jn`: Tag 1-365, der 29. Februar zählt nie
+    Julian(u32),
+    /// `n`: Tag 0-365, inklusive 29. Februar
+    ZeroBased(u32),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct DstRule {
+    offset: i32,
+    start: RuleDate,
+    /// Sekunden nach Mitternacht, Ortszeit vor der Umschaltung (Normalzeit)
+    start_time: i32,
+    end: RuleDate,
+    /// Sekunden nach Mitternacht, Ortszeit vor der Umschaltung (Sommerzeit)
+    end_time: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Rule {
+    Fixed(i32),
+    Posix { std_offset: i32, dst: Option<DstRule> },
+}
+
+/// Offset-Wechsel: ab `at` (Sekunden, UTC) gilt `after` statt `before`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transition {
+    pub at: i64,
+    pub before: i32,
+    pub after: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimeZone {
+    name: String,
+    rule: Rule,
+}
+
+impl Default for TimeZone {
+    fn default() -> Self {
+        Self::utc()
+    }
+}
+
+impl fmt::Display for TimeZone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+impl TimeZone {
+    pub fn utc() -> Self {
+        Self {
+            name: "UTC".to_string(),
+            rule: Rule::Fixed(0),
+        }
+    }
+
+    /// `"UTC"`, `"+01:00"`/`"-0530"`, POSIX-TZ oder IANA-Name.
+    pub fn parse(spec: &str) -> Result<Self> {
+        let spec = spec.trim();
+        if spec.is_empty() {
+            bail!("time zone must not be empty");
+        }
+        if matches!(spec, "UTC" | "Z" | "GMT" | "Etc/UTC" | "Etc/GMT" | "utc") {
+            return Ok(Self::utc());
+        }
+        if spec.starts_with('+') || spec.starts_with('-') {
+            let offset = parse_fixed_offset(spec)
+                .with_context(|| format!("time zone '{}': expected ±HH:MM", spec))?;
+            return Ok(Self {
+                name: spec.to_string(),
+                rule: Rule::Fixed(offset),
+            });
+        }
+        if looks_like_posix(spec) {
+            let rule = parse_posix(spec).with_context(|| format!("time zone '{}'", spec))?;
+            return Ok(Self {
+                name: spec.to_string(),
+                rule,
+            });
+        }
+        let dir = std::env::var_os("TZDIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_ZONEINFO_DIR));
+        Self::load(spec, &dir)
+    }
+
+    /// IANA-Zone aus einem zoneinfo-Verzeichnis laden.
+    pub fn load(name: &str, zoneinfo_dir: &Path) -> Result<Self> {
+        let relative = Path::new(name);
+        if !relative
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+        {
+            bail!("time zone '{}': invalid zone name", name);
+        }
+        let path = zoneinfo_dir.join(relative);
+        let data = std::fs::read(&path)
+            .with_context(|| format!("unknown time zone '{}' ({})", name, path.display()))?;
+        let footer = tzif_footer(&data).with_context(|| format!("time zone '{}'", name))?;
+        let rule = parse_posix(&footer)
+            .with_context(|| format!("time zone '{}': rule '{}'", name, footer))?;
+        Ok(Self {
+            name: name.to_string(),
+            rule,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn is_utc(&self) -> bool {
+        self.rule == Rule::Fixed(0)
+    }
+
+    /// Offset (Sekunden östlich von UTC) zum Zeitpunkt `utc_s`.
+    pub fn offset_at(&self, utc_s: i64) -> i32 {
+        match self.rule {
+            Rule::Fixed(offset) | Rule::Posix { std_offset: offset, dst: None } => offset,
+            Rule::Posix {
+                std_offset,
+                dst: Some(dst),
+            } => {
+                let year = civil_from_days((utc_s + i64::from(std_offset)).div_euclid(SECONDS_PER_DAY)).0;
+                let (start, end) = dst_window(year, std_offset, &dst);
+                let in_dst = if start < end {
+                    start <= utc_s && utc_s < end
+                } else {
+                    // Südhalbkugel: Sommerzeit über den Jahreswechsel
+                    !(end <= utc_s && utc_s < start)
+                };
+                if in_dst {
+                    dst.offset
+                } else {
+                    std_offset
+                }
+            }
+        }
+    }
+
+    fn transitions_in(&self, year: i64) -> Option<[Transition; 2]> {
+        let Rule::Posix {
+            std_offset,
+            dst: Some(dst),
+        } = self.rule
+        else {
+            return None;
+        };
+        let (start, end) = dst_window(year, std_offset, &dst);
+        let mut transitions = [
+            Transition { at: start, before: std_offset, after: dst.offset },
+            Transition { at: end, before: dst.offset, after: std_offset },
+        ];
+        transitions.sort_by_key(|t| t.at);
+        Some(transitions)
+    }
+
+    /// Letzter Offset-Wechsel bei oder vor `utc_s`.
+    pub fn last_transition_at_or_before(&self, utc_s: i64) -> Option<Transition> {
+        let year = civil_from_days(utc_s.div_euclid(SECONDS_PER_DAY)).0;
+        (year - 1..=year + 1)
+            .filter_map(|year| self.transitions_in(year))
+            .flatten()
+            .filter(|t| t.at <= utc_s)
+            .max_by_key(|t| t.at)
+    }
+
+    /// Nächster Offset-Wechsel nach `utc_s`.
+    pub fn next_transition_after(&self, utc_s: i64) -> Option<Transition> {
+        let year = civil_from_days(utc_s.div_euclid(SECONDS_PER_DAY)).0;
+        (year - 1..=year + 1)
+            .filter_map(|year| self.transitions_in(year))
+            .flatten()
+            .filter(|t| t.at > utc_s)
+            .min_by_key(|t| t.at)
+    }
+
+    /// Ortszeit als Sekunden seit Epoch (wie eine UTC-Zeit lesbar).
+    pub fn to_local(&self, utc_s: i64) -> i64 {
+        utc_s + i64::from(self.offset_at(utc_s))
+    }
+
+    /// Nächste lokale Mitternacht nach `utc_ms`, als UTC-Millisekunden. An
+    /// Umschalttagen hat der lokale Tag 23 oder 25 Stunden.
+    pub fn next_local_midnight(&self, utc_ms: u64) -> u64 {
+        self.next_local_boundary(utc_ms, SECONDS_PER_DAY, false)
+    }
+
+    /// Nächste volle Stunde der Ortszeit nach `utc_ms` (bei Offsets wie
+    /// +05:30 nicht die volle UTC-Stunde). Die bei Rückstellung doppelte
+    /// Stunde beginnt ein zweites Mal, übersprungene Stunden entfallen.
+    pub fn next_top_of_hour(&self, utc_ms: u64) -> u64 {
+        self.next_local_boundary(utc_ms, 3600, true)
+    }
+
+    fn next_local_boundary(&self, utc_ms: u64, period: i64, repeat_on_fallback: bool) -> u64 {
+        let mut from = (utc_ms / 1000) as i64 + 1;
+        loop {
+            let transition = self.next_transition_after(from - 1);
+            // Grenze liegt auf einem Wechsel, wenn er sie überspringt oder
+            // (stündlich) genau auf einer vollen Stunde landet
+            let boundary_at = |t: &Transition| {
+                let before = t.at - 1 + i64::from(t.before);
+                let after = t.at + i64::from(t.after);
+                after.div_euclid(period) > before.div_euclid(period)
+                    || (repeat_on_fallback && after.rem_euclid(period) == 0)
+            };
+            if let Some(t) = transition.filter(|t| t.at == from) {
+                if boundary_at(&t) {
+                    return from as u64 * 1000;
+                }
+                from += 1;
+                continue;
+            }
+
+            let local = from + i64::from(self.offset_at(from));
+            let boundary = from + (period - local.rem_euclid(period)) % period;
+            match transition {
+                Some(t) if t.at <= boundary => {
+                    if boundary_at(&t) {
+                        return t.at as u64 * 1000;
+                    }
+                    from = t.at + 1;
+                }
+                _ => return boundary as u64 * 1000,
+            }
+        }
+    }
+
+    /// ISO 8601 mit Offset, z. B. `2026-10-25T02:30:00+01:00`.
+    pub fn format_local(&self, utc_ms: u64) -> String {
+        let utc_s = (utc_ms / 1000) as i64;
+        let offset = self.offset_at(utc_s);
+        let (date, time) = split_civil(utc_s + i64::from(offset));
+        format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}{}",
+            date.0,
+            date.1,
+            date.2,
+            time.0,
+            time.1,
+            time.2,
+            format_offset(offset, true)
+        )
+    }
+
+    /// Für Dateinamen: Ortszeit und UTC, z. B.
+    /// `20261025T023000+0100_20261025T013000Z`.
+    pub fn file_stamp(&self, utc_ms: u64) -> String {
+        let utc_s = (utc_ms / 1000) as i64;
+        let offset = self.offset_at(utc_s);
+        format!(
+            "{}{}_{}Z",
+            compact(utc_s + i64::from(offset)),
+            format_offset(offset, false),
+            compact(utc_s)
+        )
+    }
+
+    /// strftime-ähnliche Vorlage in Ortszeit: `%Y %y %m %d %H %M %S %j`,
+    /// `%z` (Offset, `+0100`), `%s` (Unix-Sekunden) und `%%`; andere Codes
+    /// bleiben stehen.
+    pub fn format_template(&self, template: &str, utc_ms: u64) -> String {
+        let utc_s = (utc_ms / 1000) as i64;
+        let offset = self.offset_at(utc_s);
+        let local = utc_s + i64::from(offset);
+        let (date, time) = split_civil(local);
+        let mut out = String::with_capacity(template.len() + 8);
+        let mut chars = template.chars();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                out.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('Y') => out.push_str(&format!("{:04}", date.0)),
+                Some('y') => out.push_str(&format!("{:02}", date.0.rem_euclid(100))),
+                Some('m') => out.push_str(&format!("{:02}", date.1)),
+                Some('d') => out.push_str(&format!("{:02}", date.2)),
+                Some('H') => out.push_str(&format!("{:02}", time.0)),
+                Some('M') => out.push_str(&format!("{:02}", time.1)),
+                Some('S') => out.push_str(&format!("{:02}", time.2)),
+                Some('j') => {
+                    let year_start = days_from_civil(date.0, 1, 1);
+                    out.push_str(&format!("{:03}", local.div_euclid(SECONDS_PER_DAY) - year_start + 1))
+                }
+                Some('z') => out.push_str(&format_offset(offset, false)),
+                Some('s') => out.push_str(&utc_s.to_string()),
+                Some('%') => out.push('%'),
+                Some(other) => {
+                    out.push('%');
+                    out.push(other);
+                }
+                None => out.push('%'),
+            }
+        }
+        out
+    }
+}
+
+fn split_civil(seconds: i64) -> ((i64, u32, u32), (i64, i64, i64)) {
+    let date = civil_from_days(seconds.div_euclid(SECONDS_PER_DAY));
+    let in_day = seconds.rem_euclid(SECONDS_PER_DAY);
+    (date, (in_day / 3600, in_day % 3600 / 60, in_day % 60))
+}
+
+fn compact(seconds: i64) -> String {
+    let (date, time) = split_civil(seconds);
+    format!(
+        "{:04}{:02}{:02}T{:02}{:02}{:02}",
+        date.0, date.1, date.2, time.0, time.1, time.2
+    )
+}
+
+fn format_offset(offset: i32, colon: bool) -> String {
+    let sign = if offset < 0 { '-' } else { '+' };
+    let minutes = offset.unsigned_abs() / 60;
+    if colon {
+        format!("{}{:02}:{:02}", sign, minutes / 60, minutes % 60)
+    } else {
+        format!("{}{:02}{:02}", sign, minutes / 60, minutes % 60)
+    }
+}
+
+/// `±HH:MM`, `±HHMM` oder `±HH`
+fn parse_fixed_offset(text: &str) -> Result<i32> {
+    let (sign, rest) = match text.split_at(1) {
+        ("+", rest) => (1, rest),
+        ("-", rest) => (-1, rest),
+        _ => bail!("missing sign"),
+    };
+    let digits: String = rest.chars().filter(|c| *c != ':').collect();
+    if !digits.chars().all(|c| c.is_ascii_digit()) || !matches!(digits.len(), 2 | 4) {
+        bail!("invalid offset");
+    }
+    let hours: i32 = digits[..2].parse()?;
+    let minutes: i32 = if digits.len() == 4 { digits[2..].parse()? } else { 0 };
+    if hours > 14 || minutes > 59 {
+        bail!("offset out of range");
+    }
+    Ok(sign * (hours * 3600 + minutes * 60))
+}
+
+/// Beginn und Ende der Sommerzeit im Jahr `year` (UTC-Sekunden).
+fn dst_window(year: i64, std_offset: i32, dst: &DstRule) -> (i64, i64) {
+    let start = rule_day(year, dst.start) * SECONDS_PER_DAY + i64::from(dst.start_time)
+        - i64::from(std_offset);
+    let end =
+        rule_day(year, dst.end) * SECONDS_PER_DAY + i64::from(dst.end_time) - i64::from(dst.offset);
+    (start, end)
+}
+
+fn is_leap(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if is_leap(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Tag (seit Epoch) einer Regel-Angabe im Jahr `year`.
+fn rule_day(year: i64, date: RuleDate) -> i64 {
+    match date {
+        RuleDate::MonthWeekDay { month, week, weekday } => {
+            let first = days_from_civil(year, month, 1);
+            // 1970-01-01 war ein Donnerstag
+            let first_weekday = (first + 4).rem_euclid(7) as u32;
+            let mut day = 1 + (weekday + 7 - first_weekday) % 7 + (week - 1) * 7;
+            while day > days_in_month(year, month) {
+                day -= 7;
+            }
+            days_from_civil(year, month, day)
+        }
+        RuleDate::Julian(n) => {
+            let leap_shift = u32::from(is_leap(year) && n >= 60);
+            days_from_civil(year, 1, 1) + i64::from(n - 1 + leap_shift)
+        }
+        RuleDate::ZeroBased(n) => days_from_civil(year, 1, 1) + i64::from(n),
+    }
+}
+
+/// POSIX-Regel am Ende einer TZif-Datei (ab Version 2).
+fn tzif_footer(data: &[u8]) -> Result<String> {
+    if data.len() < 44 || &data[..4] != b"TZif" {
+        bail!("not a TZif file");
+    }
+    if data[4] < b'2' {
+        bail!("TZif version 1 has no POSIX rule");
+    }
+    let count = |header: usize, index: usize| -> Result<usize> {
+        let at = header + 20 + index * 4;
+        let bytes = data
+            .get(at..at + 4)
+            .ok_or_else(|| anyhow!("truncated TZif header"))?;
+        Ok(u32::from_be_bytes(bytes.try_into()?) as usize)
+    };
+    // isutcnt, isstdcnt, leapcnt, timecnt, typecnt, charcnt
+    let block_len = |header: usize, time_size: usize| -> Result<usize> {
+        Ok(count(header, 3)? * time_size
+            + count(header, 3)?
+            + count(header, 4)? * 6
+            + count(header, 5)?
+            + count(header, 2)? * (time_size + 4)
+            + count(header, 1)?
+            + count(header, 0)?)
+    };
+    let v2_header = 44 + block_len(0, 4)?;
+    let footer_start = v2_header + 44 + block_len(v2_header, 8)?;
+    let footer = data
+        .get(footer_start..)
+        .ok_or_else(|| anyhow!("truncated TZif data"))?;
+    let footer = std::str::from_utf8(footer)?.trim_matches('\n');
+    if footer.is_empty() {
+        bail!("TZif file has no POSIX rule");
+    }
+    Ok(footer.to_string())
+}
+
+struct PosixParser<'a> {
+    text: &'a [u8],
+    pos: usize,
+}
+
+impl PosixParser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.text.get(self.pos).copied()
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        if self.peek() == Some(byte) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn name(&mut self) -> Result<()> {
+        let start = self.pos;
+        if self.eat(b'<') {
+            while self.peek().is_some_and(|b| b != b'>') {
+                self.pos += 1;
+            }
+            if !self.eat(b'>') {
+                bail!("unterminated '<' in zone name");
+            }
+            return Ok(());
+        }
+        while self.peek().is_some_and(|b| b.is_ascii_alphabetic()) {
+            self.pos += 1;
+        }
+        if self.pos - start < 3 {
+            bail!("zone abbreviation too short");
+        }
+        Ok(())
+    }
+
+    fn number(&mut self) -> Result<i32> {
+        let start = self.pos;
+        while self.peek().is_some_and(|b| b.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        if start == self.pos {
+            bail!("expected a number at position {}", start);
+        }
+        Ok(std::str::from_utf8(&self.text[start..self.pos])?.parse()?)
+    }
+
+    /// `[+-]hh[:mm[:ss]]` in Sekunden
+    fn time(&mut self) -> Result<i32> {
+        let sign = if self.eat(b'-') {
+            -1
+        } else {
+            self.eat(b'+');
+            1
+        };
+        let mut seconds = self.number()? * 3600;
+        if self.eat(b':') {
+            seconds += self.number()? * 60;
+            if self.eat(b':') {
+                seconds += self.number()?;
+            }
+        }
+        Ok(sign * seconds)
+    }
+
+    fn date(&mut self) -> Result<RuleDate> {
+        if self.eat(b'M') {
+            let month = self.number()? as u32;
+            if !self.eat(b'.') {
+                bail!("expected '.' in M rule");
+            }
+            let week = self.number()? as u32;
+            if !self.eat(b'.') {
+                bail!("expected '.' in M rule");
+            }
+            let weekday = self.number()? as u32;
+            if !(1..=12).contains(&month) || !(1..=5).contains(&week) || weekday > 6 {
+                bail!("M rule out of range");
+            }
+            return Ok(RuleDate::MonthWeekDay { month, week, weekday });
+        }
+        if self.eat(b'J') {
+            let day = self.number()? as u32;
+            if !(1..=365).contains(&day) {
+                bail!("J rule out of range");
+            }
+            return Ok(RuleDate::Julian(day));
+        }
+        let day = self.number()? as u32;
+        if day > 365 {
+            bail!("day rule out of range");
+        }
+        Ok(RuleDate::ZeroBased(day))
+    }
+
+    fn rule_part(&mut self) -> Result<(RuleDate, i32)> {
+        let date = self.date()?;
+        let time = if self.eat(b'/') {
+            self.time()?
+        } else {
+            DEFAULT_RULE_TIME
+        };
+        Ok((date, time))
+    }
+}
+
+/// POSIX-TZ, z. B. `CET-1CEST,M3.5.0,M10.5.0/3` (Offset westlich positiv).
+/// POSIX-TZ an der Form erkennen: Abkürzung (`CET`, `<+03>`) direkt gefolgt
+/// vom Offset. IANA-Namen haben stattdessen `/` oder enden nach dem Namen.
+fn looks_like_posix(spec: &str) -> bool {
+    let mut parser = PosixParser {
+        text: spec.as_bytes(),
+        pos: 0,
+    };
+    parser.name().is_ok() && matches!(parser.peek(), Some(b'0'..=b'9' | b'+' | b'-'))
+}
+
+fn parse_posix(spec: &str) -> Result<Rule> {
+    let mut parser = PosixParser {
+        text: spec.as_bytes(),
+        pos: 0,
+    };
+    parser.name()?;
+    let std_offset = -parser.time()?;
+    if parser.peek().is_none() {
+        return Ok(Rule::Posix { std_offset, dst: None });
+    }
+
+    parser.name()?;
+    let dst_offset = match parser.peek() {
+        Some(b',') | None => std_offset + 3600,
+        _ => -parser.time()?,
+    };
+    if !parser.eat(b',') {
+        bail!("'{}': missing DST rule", spec);
+    }
+    let (start, start_time) = parser.rule_part()?;
+    if !parser.eat(b',') {
+        bail!("'{}': missing DST end", spec);
+    }
+    let (end, end_time) = parser.rule_part()?;
+    if parser.peek().is_some() {
+        bail!("'{}': trailing characters", spec);
+    }
+    Ok(Rule::Posix {
+        std_offset,
+        dst: Some(DstRule {
+            offset: dst_offset,
+            start,
+            start_time,
+            end,
+            end_time,
+        }),
+    })
+}
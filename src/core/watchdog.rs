@@ -0,0 +1,209 @@
+// src/core/watchdog.rs
+//
+// Producer-Watchdog: merkt sich Producer, die beim Start oder zur Laufzeit mit
+// einem Fehler ausgefallen sind. Wiederholbare Fehler (`ErrorInfo::retryable`)
+// werden mit exponentiellem Backoff neu gestartet, alle anderen nur gemeldet.
+// Der Node ruft `AirliftNode::run_watchdog` periodisch auf.
+use std::collections::{BTreeMap, HashSet};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::core::ErrorInfo;
+
+#[derive(Debug, Clone, Copy)]
+pub struct WatchdogSettings {
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    /// So lange muss ein neu gestarteter Producer laufen, bis er als erholt
+    /// gilt und der Backoff zurückgesetzt wird
+    pub stable_after: Duration,
+}
+
+impl Default for WatchdogSettings {
+    fn default() -> Self {
+        Self {
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(30),
+            stable_after: Duration::from_secs(10),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchdogAction {
+    RetryIn(Duration),
+    /// Nicht wiederholbar: bleibt gestoppt, bis jemand eingreift
+    GiveUp,
+}
+
+#[derive(Debug)]
+struct Entry {
+    error: ErrorInfo,
+    attempts: u32,
+    next_attempt: Option<Instant>,
+    restarted_at: Option<Instant>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WatchdogEntryStatus {
+    pub producer: String,
+    pub error: ErrorInfo,
+    /// Bisherige Neustart-Versuche
+    pub attempts: u32,
+    /// Nächster Versuch; `null` nach Aufgeben oder während der Bewährung
+    pub retry_in_ms: Option<u64>,
+    /// Neu gestartet, aber noch nicht `stable_after` lang gelaufen
+    pub restarted: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct Watchdog {
+    settings: WatchdogSettings,
+    entries: BTreeMap<String, Entry>,
+    /// Per API/Zeitplan gestoppt: nicht neu starten
+    held: HashSet<String>,
+}
+
+impl Watchdog {
+    pub fn new(settings: WatchdogSettings) -> Self {
+        Self {
+            settings,
+            ..Self::default()
+        }
+    }
+
+    pub fn settings(&self) -> WatchdogSettings {
+        self.settings
+    }
+
+    pub fn set_settings(&mut self, settings: WatchdogSettings) {
+        self.settings = settings;
+    }
+
+    /// Meldet einen Ausfall; liefert, ob und wann neu gestartet wird.
+    pub fn report_failure(
+        &mut self,
+        producer: &str,
+        error: ErrorInfo,
+        now: Instant,
+    ) -> WatchdogAction {
+        let attempts = self.entries.get(producer).map_or(0, |entry| entry.attempts);
+        let action = if error.retryable && !self.held.contains(producer) {
+            WatchdogAction::RetryIn(self.backoff(attempts))
+        } else {
+            WatchdogAction::GiveUp
+        };
+        let next_attempt = match action {
+            WatchdogAction::RetryIn(delay) => Some(now + delay),
+            WatchdogAction::GiveUp => None,
+        };
+        self.entries.insert(
+            producer.to_string(),
+            Entry {
+                error,
+                attempts,
+                next_attempt,
+                restarted_at: None,
+            },
+        );
+        action
+    }
+
+    fn backoff(&self, attempts: u32) -> Duration {
+        self.settings
+            .initial_backoff
+            .saturating_mul(1 << attempts.min(16))
+            .min(self.settings.max_backoff)
+    }
+
+    /// Erfasst, aber nicht gerade in der Bewährung nach einem Neustart.
+    pub fn is_failed(&self, producer: &str) -> bool {
+        self.entries
+            .get(producer)
+            .is_some_and(|entry| entry.restarted_at.is_none())
+    }
+
+    /// Fällige Neustarts; zählt den Versuch mit.
+    pub fn take_due(&mut self, now: Instant) -> Vec<String> {
+        let mut due = Vec::new();
+        for (name, entry) in self.entries.iter_mut() {
+            if entry.next_attempt.is_some_and(|at| at <= now) {
+                entry.attempts += 1;
+                entry.next_attempt = None;
+                due.push(name.clone());
+            }
+        }
+        due
+    }
+
+    /// Neustart gelungen: ab jetzt Bewährung bis `stable_after`.
+    pub fn restarted(&mut self, producer: &str, now: Instant) {
+        if let Some(entry) = self.entries.get_mut(producer) {
+            entry.restarted_at = Some(now);
+        }
+    }
+
+    /// Producer, die seit `stable_after` wieder laufen; werden ausgetragen.
+    /// Liefert Name und Zahl der benötigten Versuche.
+    pub fn take_recovered(&mut self, now: Instant) -> Vec<(String, u32)> {
+        let stable_after = self.settings.stable_after;
+        let recovered: Vec<(String, u32)> = self
+            .entries
+            .iter()
+            .filter(|(_, entry)| {
+                entry
+                    .restarted_at
+                    .is_some_and(|at| now.saturating_duration_since(at) >= stable_after)
+            })
+            .map(|(name, entry)| (name.clone(), entry.attempts))
+            .collect();
+        for (name, _) in &recovered {
+            self.entries.remove(name);
+        }
+        recovered
+    }
+
+    /// Bewusst gestoppt: Eintrag verwerfen und keine Neustarts mehr.
+    pub fn hold(&mut self, producer: &str) {
+        self.entries.remove(producer);
+        self.held.insert(producer.to_string());
+    }
+
+    pub fn release(&mut self, producer: &str) {
+        self.held.remove(producer);
+    }
+
+    pub fn is_held(&self, producer: &str) -> bool {
+        self.held.contains(producer)
+    }
+
+    pub fn forget(&mut self, producer: &str) {
+        self.entries.remove(producer);
+        self.held.remove(producer);
+    }
+
+    /// Beim Stoppen des Nodes: laufende Neustart-Pläne verwerfen.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn error_for(&self, producer: &str) -> Option<&ErrorInfo> {
+        self.entries.get(producer).map(|entry| &entry.error)
+    }
+
+    pub fn status(&self, now: Instant) -> Vec<WatchdogEntryStatus> {
+        self.entries
+            .iter()
+            .map(|(name, entry)| WatchdogEntryStatus {
+                producer: name.clone(),
+                error: entry.error.clone(),
+                attempts: entry.attempts,
+                retry_in_ms: entry
+                    .next_attempt
+                    .map(|at| at.saturating_duration_since(now).as_millis() as u64),
+                restarted: entry.restarted_at.is_some(),
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,249 @@
+// src/core/watermark.rs
+//
+// High/Low-Watermarks für Buffer: meldet per Event, wenn ein Reader länger als
+// `hold` über `high` (Anteil der Kapazität) liegt bzw. wieder unter `low` fällt.
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::config::ConfigValues;
+use crate::core::event_bus::EventEmitter;
+use crate::core::events::{EventPriority, EventType};
+use crate::core::lock::lock_mutex;
+use crate::core::timestamp::utc_ns_now;
+
+/// Reader, die so lange nicht gelesen haben, gelten als entfernt.
+const STALE_READER_NS: u64 = 30_000_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WatermarkConfig {
+    pub high: f32,
+    pub low: f32,
+    pub hold: Duration,
+}
+
+impl Default for WatermarkConfig {
+    fn default() -> Self {
+        Self {
+            high: 0.8,
+            low: 0.5,
+            hold: Duration::from_secs(5),
+        }
+    }
+}
+
+impl WatermarkConfig {
+    /// Liest `{ high = 0.8, low = 0.5, hold = "5s" }` aus einer Modul-Config.
+    pub fn from_config(
+        module_kind: &str,
+        module_name: &str,
+        value: &serde_json::Value,
+    ) -> anyhow::Result<Self> {
+        let map: HashMap<String, serde_json::Value> = match value {
+            serde_json::Value::Object(map) => map.clone().into_iter().collect(),
+            serde_json::Value::Bool(true) => HashMap::new(),
+            other => anyhow::bail!(
+                "{} '{}': config.watermark must be a table, got {}",
+                module_kind,
+                module_name,
+                other
+            ),
+        };
+        let values = ConfigValues::new(module_kind, module_name, &map);
+        let defaults = Self::default();
+
+        let high = values.f64("high")?.map(|v| v as f32).unwrap_or(defaults.high);
+        let low = values.f64("low")?.map(|v| v as f32).unwrap_or(defaults.low);
+        values.check_range("high", high, 0.0, 1.0)?;
+        values.check_range("low", low, 0.0, high)?;
+
+        Ok(Self {
+            high,
+            low,
+            hold: values.duration("hold")?.unwrap_or(defaults.hold),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WatermarkLevel {
+    High,
+    Low,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WatermarkCrossing {
+    pub buffer: String,
+    pub reader: String,
+    pub level: WatermarkLevel,
+    pub fill: f32,
+}
+
+/// Beobachtung eines Readers: Position (für Stale-Erkennung) und Rückstand in Frames.
+#[derive(Debug, Clone)]
+pub struct ReaderLag {
+    pub reader: String,
+    pub position: u64,
+    pub lag: usize,
+}
+
+struct ReaderState {
+    position: u64,
+    advanced_ns: u64,
+    above_since_ns: Option<u64>,
+    high: bool,
+}
+
+pub struct WatermarkMonitor {
+    buffer: String,
+    config: WatermarkConfig,
+    emitter: Option<EventEmitter>,
+    readers: Mutex<HashMap<String, ReaderState>>,
+}
+
+impl std::fmt::Debug for WatermarkMonitor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WatermarkMonitor")
+            .field("buffer", &self.buffer)
+            .field("config", &self.config)
+            .finish()
+    }
+}
+
+impl WatermarkMonitor {
+    pub fn new(buffer: &str, config: WatermarkConfig) -> Self {
+        Self {
+            buffer: buffer.to_string(),
+            config,
+            emitter: None,
+            readers: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn with_emitter(mut self, emitter: EventEmitter) -> Self {
+        self.emitter = Some(emitter);
+        self
+    }
+
+    pub fn config(&self) -> WatermarkConfig {
+        self.config
+    }
+
+    /// Wertet die aktuellen Reader-Rückstände aus und veröffentlicht Übergänge.
+    pub fn observe(&self, capacity: usize, lags: Vec<ReaderLag>) -> Vec<WatermarkCrossing> {
+        self.observe_at(utc_ns_now(), capacity, lags)
+    }
+
+    pub fn observe_at(&self, now_ns: u64, capacity: usize, lags: Vec<ReaderLag>) -> Vec<WatermarkCrossing> {
+        if capacity == 0 {
+            return Vec::new();
+        }
+
+        let hold_ns = self.config.hold.as_nanos() as u64;
+        let mut crossings = Vec::new();
+        let mut readers = lock_mutex(&self.readers, "watermark.observe");
+        readers.retain(|id, _| lags.iter().any(|lag| &lag.reader == id));
+
+        for lag in lags {
+            let state = readers.entry(lag.reader.clone()).or_insert(ReaderState {
+                position: lag.position,
+                advanced_ns: now_ns,
+                above_since_ns: None,
+                high: false,
+            });
+
+            if state.position != lag.position {
+                state.position = lag.position;
+                state.advanced_ns = now_ns;
+            }
+            if now_ns.saturating_sub(state.advanced_ns) > STALE_READER_NS {
+                continue;
+            }
+
+            let fill = (lag.lag.min(capacity) as f32) / capacity as f32;
+            if fill >= self.config.high {
+                let since = *state.above_since_ns.get_or_insert(now_ns);
+                if !state.high && now_ns.saturating_sub(since) >= hold_ns {
+                    state.high = true;
+                    crossings.push(self.crossing(&lag.reader, WatermarkLevel::High, fill));
+                }
+            } else {
+                state.above_since_ns = None;
+                if state.high && fill <= self.config.low {
+                    state.high = false;
+                    crossings.push(self.crossing(&lag.reader, WatermarkLevel::Low, fill));
+                }
+            }
+        }
+        drop(readers);
+
+        for crossing in &crossings {
+            self.publish(crossing, capacity);
+        }
+        crossings
+    }
+
+    fn crossing(&self, reader: &str, level: WatermarkLevel, fill: f32) -> WatermarkCrossing {
+        WatermarkCrossing {
+            buffer: self.buffer.clone(),
+            reader: reader.to_string(),
+            level,
+            fill,
+        }
+    }
+
+    fn publish(&self, crossing: &WatermarkCrossing, capacity: usize) {
+        let Some(emitter) = &self.emitter else {
+            return;
+        };
+        let (priority, threshold) = match crossing.level {
+            WatermarkLevel::High => (EventPriority::Warning, self.config.high),
+            WatermarkLevel::Low => (EventPriority::Info, self.config.low),
+        };
+        emitter.emit(
+            EventType::BufferWatermark,
+            priority,
+            serde_json::json!({
+                "buffer": crossing.buffer,
+                "reader": crossing.reader,
+                "level": crossing.level,
+                "fill": crossing.fill,
+                "threshold": threshold,
+                "capacity": capacity,
+                "hold_ms": self.config.hold.as_millis() as u64,
+                "timestamp": utc_ns_now(),
+            }),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lag(lag: usize, position: u64) -> Vec<ReaderLag> {
+        vec![ReaderLag {
+            reader: "consumer:out".to_string(),
+            position,
+            lag,
+        }]
+    }
+
+    #[test]
+    fn high_requires_hold_and_low_uses_hysteresis() {
+        let monitor = WatermarkMonitor::new("buf", WatermarkConfig::default());
+        let s = 1_000_000_000;
+
+        assert!(monitor.observe_at(0, 100, lag(90, 1)).is_empty());
+        assert!(monitor.observe_at(4 * s, 100, lag(90, 2)).is_empty());
+        let high = monitor.observe_at(5 * s, 100, lag(90, 3));
+        assert_eq!(high[0].level, WatermarkLevel::High);
+
+        assert!(monitor.observe_at(6 * s, 100, lag(60, 4)).is_empty());
+        let low = monitor.observe_at(7 * s, 100, lag(40, 5));
+        assert_eq!(low[0].level, WatermarkLevel::Low);
+    }
+}
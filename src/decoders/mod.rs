@@ -1,5 +1,38 @@
 use crate::ring::PcmFrame;
+use serde::Serialize;
+
+pub mod mpeg_audio;
+#[cfg(feature = "opus")]
+pub mod opus;
+pub mod probe;
+
+/// Zähler eines Decoders für `ProducerStatus`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct DecoderStats {
+    pub packets: u64,
+    /// Per Packet-Loss-Concealment aufgefüllte Pakete
+    pub plc_events: u64,
+    /// Aus der In-Band-FEC des Folgepakets rekonstruierte Pakete
+    pub fec_recoveries: u64,
+    pub errors: u64,
+    /// Audio-Bandbreite des letzten Pakets (`narrowband` … `fullband`)
+    pub bandwidth: Option<String>,
+}
 
 pub trait AudioDecoder: Send {
     fn decode(&mut self, packet: &[u8]) -> anyhow::Result<Option<PcmFrame>>;
+
+    /// Ersatz für `lost` verlorene Pakete unmittelbar vor `next`, das danach
+    /// regulär dekodiert wird. Standard: ein leeres Paket je Verlust.
+    fn conceal(&mut self, lost: u16, _next: &[u8]) -> anyhow::Result<Vec<PcmFrame>> {
+        let mut frames = Vec::new();
+        for _ in 0..lost {
+            frames.extend(self.decode(&[])?);
+        }
+        Ok(frames)
+    }
+
+    fn stats(&self) -> Option<DecoderStats> {
+        None
+    }
 }
@@ -0,0 +1,315 @@
+// src/decoders/mpeg_audio.rs
+//
+// Decoder für Audio aus MPEG-TS: MPEG-1/2 Audio (Layer II/III) und AAC mit
+// ADTS-Headern. `EsFramer` zerlegt den Elementarstrom in einzelne Frames
+// (angefangene Frames bleiben bis zum nächsten PES liegen), dekodiert wird mit
+// symphonia (Cargo-Feature `symphonia`).
+use crate::aoip::mpegts::TsAudioCodec;
+
+/// Angefangene Daten ohne gültigen Header werden ab dieser Größe verworfen
+const MAX_PENDING: usize = 64 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EsCodec {
+    Mp1,
+    Mp2,
+    Mp3,
+    /// AudioSpecificConfig (ISO 14496-3) aus dem ADTS-Header
+    Aac { audio_specific_config: [u8; 2] },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EsFrameHeader {
+    pub codec: EsCodec,
+    /// Gesamtlänge inklusive Header
+    pub frame_len: usize,
+    /// Beginn der Rohdaten (bei MPEG Audio 0, der Decoder liest den Header selbst)
+    pub payload_offset: usize,
+    pub sample_rate: u32,
+    pub channels: u8,
+}
+
+const MPA_BITRATES_V1: [[u32; 15]; 3] = [
+    [0, 32, 64, 96, 128, 160, 192, 224, 256, 288, 320, 352, 384, 416, 448],
+    [0, 32, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320, 384],
+    [0, 32, 40, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320],
+];
+const MPA_BITRATES_V2: [[u32; 15]; 2] = [
+    [0, 32, 48, 56, 64, 80, 96, 112, 128, 144, 160, 176, 192, 224, 256],
+    [0, 8, 16, 24, 32, 40, 48, 56, 64, 80, 96, 112, 128, 144, 160],
+];
+const MPA_SAMPLE_RATES: [u32; 3] = [44_100, 48_000, 32_000];
+const AAC_SAMPLE_RATES: [u32; 13] = [
+    96_000, 88_200, 64_000, 48_000, 44_100, 32_000, 24_000, 22_050, 16_000, 12_000, 11_025,
+    8_000, 7_350,
+];
+
+/// Header eines MPEG-1/2/2.5-Audio-Frames; Free-Format wird nicht unterstützt.
+pub fn parse_mpa_header(data: &[u8]) -> Option<EsFrameHeader> {
+    let header = data.get(..4)?;
+    if header[0] != 0xff || header[1] & 0xe0 != 0xe0 {
+        return None;
+    }
+    // 3 = MPEG-1, 2 = MPEG-2, 0 = MPEG-2.5
+    let version = (header[1] >> 3) & 0x03;
+    let layer = match (header[1] >> 1) & 0x03 {
+        3 => 1,
+        2 => 2,
+        1 => 3,
+        _ => return None,
+    };
+    let bitrate_index = (header[2] >> 4) as usize;
+    let rate_index = ((header[2] >> 2) & 0x03) as usize;
+    if version == 1 || bitrate_index == 0 || bitrate_index == 15 || rate_index == 3 {
+        return None;
+    }
+    let padding = ((header[2] >> 1) & 0x01) as u32;
+    let sample_rate = match version {
+        3 => MPA_SAMPLE_RATES[rate_index],
+        2 => MPA_SAMPLE_RATES[rate_index] / 2,
+        _ => MPA_SAMPLE_RATES[rate_index] / 4,
+    };
+    let kbps = if version == 3 {
+        MPA_BITRATES_V1[layer - 1][bitrate_index]
+    } else {
+        MPA_BITRATES_V2[usize::from(layer != 1)][bitrate_index]
+    };
+    let bitrate = kbps * 1000;
+    let frame_len = match layer {
+        1 => (12 * bitrate / sample_rate + padding) * 4,
+        3 if version != 3 => 72 * bitrate / sample_rate + padding,
+        _ => 144 * bitrate / sample_rate + padding,
+    };
+    Some(EsFrameHeader {
+        codec: match layer {
+            1 => EsCodec::Mp1,
+            2 => EsCodec::Mp2,
+            _ => EsCodec::Mp3,
+        },
+        frame_len: frame_len as usize,
+        payload_offset: 0,
+        sample_rate,
+        channels: if header[3] >> 6 == 3 { 1 } else { 2 },
+    })
+}
+
+/// ADTS-Header (7 Byte, mit CRC 9 Byte) eines AAC-Frames.
+pub fn parse_adts_header(data: &[u8]) -> Option<EsFrameHeader> {
+    let header = data.get(..7)?;
+    // Syncword 0xFFF, Layer 00
+    if header[0] != 0xff || header[1] & 0xf6 != 0xf0 {
+        return None;
+    }
+    let header_len = if header[1] & 0x01 != 0 { 7 } else { 9 };
+    let object_type = (header[2] >> 6) + 1;
+    let rate_index = (header[2] >> 2) & 0x0f;
+    let channel_config = ((header[2] & 0x01) << 2) | (header[3] >> 6);
+    let frame_len = (((header[3] & 0x03) as usize) << 11)
+        | ((header[4] as usize) << 3)
+        | (header[5] >> 5) as usize;
+    let sample_rate = *AAC_SAMPLE_RATES.get(rate_index as usize)?;
+    if channel_config == 0 || frame_len <= header_len {
+        return None;
+    }
+    let config =
+        ((object_type as u16) << 11) | ((rate_index as u16) << 7) | ((channel_config as u16) << 3);
+    Some(EsFrameHeader {
+        codec: EsCodec::Aac {
+            audio_specific_config: config.to_be_bytes(),
+        },
+        frame_len,
+        payload_offset: header_len,
+        sample_rate,
+        channels: if channel_config == 7 { 8 } else { channel_config },
+    })
+}
+
+/// Zerlegt einen Elementarstrom in Frames, auch über PES-Grenzen hinweg.
+#[derive(Debug)]
+pub struct EsFramer {
+    codec: TsAudioCodec,
+    pending: Vec<u8>,
+}
+
+impl EsFramer {
+    pub fn new(codec: TsAudioCodec) -> Self {
+        Self {
+            codec,
+            pending: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, data: &[u8]) {
+        self.pending.extend_from_slice(data);
+    }
+
+    /// Verwirft angefangene Daten (z. B. nach einer Continuity-Lücke).
+    pub fn reset(&mut self) {
+        self.pending.clear();
+    }
+
+    fn parse(&self, data: &[u8]) -> Option<EsFrameHeader> {
+        match self.codec {
+            TsAudioCodec::MpegAudio => parse_mpa_header(data),
+            TsAudioCodec::AacAdts => parse_adts_header(data),
+        }
+    }
+
+    /// Nächster vollständiger Frame samt Header; Müll vor dem Sync wird übersprungen.
+    pub fn next_frame(&mut self) -> Option<(EsFrameHeader, Vec<u8>)> {
+        let mut start = 0;
+        while start + 1 < self.pending.len() {
+            if self.pending[start] != 0xff {
+                start += 1;
+                continue;
+            }
+            let Some(header) = self.parse(&self.pending[start..]) else {
+                if self.pending.len() - start < 9 {
+                    // Header evtl. noch unvollständig
+                    break;
+                }
+                start += 1;
+                continue;
+            };
+            if self.pending.len() - start < header.frame_len {
+                break;
+            }
+            let frame = self.pending[start..start + header.frame_len].to_vec();
+            self.pending.drain(..start + header.frame_len);
+            return Some((header, frame));
+        }
+        self.pending.drain(..start);
+        if self.pending.len() > MAX_PENDING {
+            self.pending.clear();
+        }
+        None
+    }
+}
+
+#[cfg(feature = "symphonia")]
+pub use symphonia_decoder::TsAudioDecoder;
+
+#[cfg(feature = "symphonia")]
+mod symphonia_decoder {
+    use anyhow::{anyhow, Result};
+    use symphonia::core::audio::{Channels, SampleBuffer};
+    use symphonia::core::codecs::{
+        CodecParameters, CodecType, Decoder, DecoderOptions, CODEC_TYPE_AAC, CODEC_TYPE_MP1,
+        CODEC_TYPE_MP2, CODEC_TYPE_MP3,
+    };
+    use symphonia::core::errors::Error as SymphoniaError;
+    use symphonia::core::formats::Packet;
+
+    use super::{EsCodec, EsFrameHeader, EsFramer};
+    use crate::aoip::mpegts::TsAudioCodec;
+    use crate::core::timestamp::utc_ns_now;
+    use crate::decoders::AudioDecoder;
+    use crate::ring::PcmFrame;
+
+    /// Dekodiert die Nutzdaten eines PES-Pakets; alle darin enthaltenen
+    /// Frames landen in einem `PcmFrame`.
+    pub struct TsAudioDecoder {
+        framer: EsFramer,
+        /// Decoder für Codec/Rate/Kanäle des letzten Frames
+        decoder: Option<(EsCodec, u32, u8, Box<dyn Decoder>)>,
+    }
+
+    impl TsAudioDecoder {
+        pub fn new(codec: TsAudioCodec) -> Self {
+            Self {
+                framer: EsFramer::new(codec),
+                decoder: None,
+            }
+        }
+
+        pub fn reset(&mut self) {
+            self.framer.reset();
+            if let Some((_, _, _, decoder)) = self.decoder.as_mut() {
+                decoder.reset();
+            }
+        }
+
+        fn decoder_for(&mut self, header: &EsFrameHeader) -> Result<&mut dyn Decoder> {
+            let matches = self.decoder.as_ref().is_some_and(|(codec, rate, channels, _)| {
+                (*codec, *rate, *channels) == (header.codec, header.sample_rate, header.channels)
+            });
+            if !matches {
+                let (codec_type, extra): (CodecType, Option<[u8; 2]>) = match header.codec {
+                    EsCodec::Mp1 => (CODEC_TYPE_MP1, None),
+                    EsCodec::Mp2 => (CODEC_TYPE_MP2, None),
+                    EsCodec::Mp3 => (CODEC_TYPE_MP3, None),
+                    EsCodec::Aac {
+                        audio_specific_config,
+                    } => (CODEC_TYPE_AAC, Some(audio_specific_config)),
+                };
+                let mut params = CodecParameters::new();
+                params
+                    .for_codec(codec_type)
+                    .with_sample_rate(header.sample_rate)
+                    .with_channels(if header.channels == 1 {
+                        Channels::FRONT_LEFT
+                    } else {
+                        Channels::FRONT_LEFT | Channels::FRONT_RIGHT
+                    });
+                if let Some(config) = extra {
+                    params.with_extra_data(Box::new(config));
+                }
+                let decoder = symphonia::default::get_codecs()
+                    .make(&params, &DecoderOptions::default())
+                    .map_err(|e| anyhow!("no decoder for {:?}: {}", header.codec, e))?;
+                log::info!(
+                    "MPEG-TS: decoding {:?} {} Hz / {} ch",
+                    header.codec,
+                    header.sample_rate,
+                    header.channels
+                );
+                self.decoder = Some((header.codec, header.sample_rate, header.channels, decoder));
+            }
+            let (_, _, _, decoder) = self.decoder.as_mut().expect("decoder set above");
+            Ok(decoder.as_mut())
+        }
+    }
+
+    impl AudioDecoder for TsAudioDecoder {
+        fn decode(&mut self, packet: &[u8]) -> Result<Option<PcmFrame>> {
+            self.framer.push(packet);
+            let mut samples: Vec<i16> = Vec::new();
+            let mut format: Option<(u32, u8)> = None;
+
+            while let Some((header, frame)) = self.framer.next_frame() {
+                let decoder = self.decoder_for(&header)?;
+                let data = &frame[header.payload_offset..];
+                let decoded = match decoder.decode(&Packet::new_from_slice(0, 0, 0, data)) {
+                    Ok(decoded) => decoded,
+                    // Einzelne kaputte Frames überspringen
+                    Err(SymphoniaError::DecodeError(e)) => {
+                        log::debug!("MPEG-TS: skipping undecodable frame: {}", e);
+                        continue;
+                    }
+                    Err(e) => return Err(anyhow!("decode: {}", e)),
+                };
+                if decoded.frames() == 0 {
+                    continue;
+                }
+                let spec = *decoded.spec();
+                let frame_format = (spec.rate, spec.channels.count() as u8);
+                if format.is_some_and(|format| format != frame_format) {
+                    // Formatwechsel mitten im PES: der neue Teil gewinnt
+                    samples.clear();
+                }
+                format = Some(frame_format);
+                let mut buffer = SampleBuffer::<i16>::new(decoded.capacity() as u64, spec);
+                buffer.copy_interleaved_ref(decoded);
+                samples.extend_from_slice(buffer.samples());
+            }
+
+            Ok(format.map(|(sample_rate, channels)| PcmFrame {
+                utc_ns: utc_ns_now(),
+                samples,
+                sample_rate,
+                channels,
+                metadata: Default::default(),
+            }))
+        }
+    }
+}
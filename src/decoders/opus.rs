@@ -0,0 +1,284 @@
+// src/decoders/opus.rs
+//
+// Opus-Decoder (libopus) für paketbasierte Quellen wie WebRTC/RTP: ein Paket
+// pro Aufruf, Ausgabe immer 48 kHz. Verlorene Pakete werden per In-Band-FEC
+// aus dem Folgepaket rekonstruiert oder per Packet-Loss-Concealment von
+// libopus aufgefüllt (Länge jeweils wie das letzte Paket).
+//
+// Direkt über `audiopus_sys`, weil `opus::Decoder` weder Phase-Inversion
+// noch FEC-Dekodierung mit passender Framegröße anbietet.
+use std::collections::HashMap;
+use std::ffi::CStr;
+use std::os::raw::c_int;
+
+use anyhow::{bail, Result};
+use audiopus_sys as ffi;
+
+use crate::config::ConfigValues;
+use crate::core::timestamp::utc_ns_now;
+use crate::decoders::{AudioDecoder, DecoderStats};
+use crate::ring::PcmFrame;
+
+pub const OPUS_SAMPLE_RATE: u32 = 48_000;
+/// Längstes Opus-Paket: 120 ms bei 48 kHz.
+const MAX_SAMPLES_PER_CH: usize = 5_760;
+/// Annahme für PLC/FEC, solange noch kein Paket dekodiert wurde (20 ms).
+const DEFAULT_PACKET_SAMPLES: usize = 960;
+
+// CTL-Requests aus opus_defines.h
+const OPUS_GET_BANDWIDTH_REQUEST: c_int = 4009;
+const OPUS_SET_GAIN_REQUEST: c_int = 4034;
+const OPUS_GET_LAST_PACKET_DURATION_REQUEST: c_int = 4039;
+const OPUS_SET_PHASE_INVERSION_DISABLED_REQUEST: c_int = 4046;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpusDecoderOptions {
+    /// Pegelanpassung im Decoder (libopus: Q8-dB, ±128 dB)
+    pub gain_db: f32,
+    /// Ein verlorenes Paket aus der FEC des Folgepakets rekonstruieren
+    pub fec: bool,
+    /// Phase-Inversion bei Intensity-Stereo abschalten (bessere Mono-Downmixe)
+    pub disable_phase_inversion: bool,
+}
+
+impl Default for OpusDecoderOptions {
+    fn default() -> Self {
+        Self {
+            gain_db: 0.0,
+            fec: true,
+            disable_phase_inversion: false,
+        }
+    }
+}
+
+impl OpusDecoderOptions {
+    /// Liest `{ gain = "-3dB", fec = true, phase_inversion = false }` aus
+    /// `config.opus` eines Producers.
+    pub fn from_config(producer: &str, value: Option<&serde_json::Value>) -> Result<Self> {
+        let defaults = Self::default();
+        let map: HashMap<String, serde_json::Value> = match value {
+            None => return Ok(defaults),
+            Some(serde_json::Value::Object(map)) => map.clone().into_iter().collect(),
+            Some(other) => bail!(
+                "producer '{}': config.opus must be a table, got {}",
+                producer,
+                other
+            ),
+        };
+        let values = ConfigValues::new("producer", producer, &map);
+        let flag = |key: &str, default: bool| -> Result<bool> {
+            match map.get(key) {
+                None => Ok(default),
+                Some(serde_json::Value::Bool(value)) => Ok(*value),
+                Some(other) => bail!(
+                    "producer '{}': config.opus.{} must be true or false, got {}",
+                    producer,
+                    key,
+                    other
+                ),
+            }
+        };
+
+        let gain_db = values.db("gain")?.unwrap_or(defaults.gain_db);
+        values.check_range("gain", gain_db, -128.0, 127.0)?;
+
+        Ok(Self {
+            gain_db,
+            fec: flag("fec", defaults.fec)?,
+            disable_phase_inversion: !flag("phase_inversion", true)?,
+        })
+    }
+
+    /// Wert für `OPUS_SET_GAIN` (1/256 dB).
+    pub fn gain_q8(&self) -> i32 {
+        (self.gain_db * 256.0).round().clamp(-32_768.0, 32_767.0) as i32
+    }
+}
+
+/// Besitzt den libopus-Decoder-State.
+struct RawDecoder(*mut ffi::OpusDecoder);
+
+// Der State ist nicht an einen Thread gebunden, Zugriff nur über `&mut`.
+unsafe impl Send for RawDecoder {}
+
+impl Drop for RawDecoder {
+    fn drop(&mut self) {
+        unsafe { ffi::opus_decoder_destroy(self.0) }
+    }
+}
+
+fn opus_error(code: c_int) -> anyhow::Error {
+    let message = unsafe { CStr::from_ptr(ffi::opus_strerror(code)) };
+    anyhow::anyhow!("libopus: {}", message.to_string_lossy())
+}
+
+pub struct OpusDecoder {
+    decoder: RawDecoder,
+    channels: u8,
+    options: OpusDecoderOptions,
+    buffer: Vec<i16>,
+    stats: DecoderStats,
+}
+
+impl OpusDecoder {
+    pub fn new(channels: u8) -> Result<Self> {
+        Self::with_options(channels, OpusDecoderOptions::default())
+    }
+
+    pub fn with_options(channels: u8, options: OpusDecoderOptions) -> Result<Self> {
+        if !(1..=2).contains(&channels) {
+            bail!("Opus decoder supports 1 or 2 channels, got {}", channels);
+        }
+
+        let mut error: c_int = 0;
+        let raw = unsafe {
+            ffi::opus_decoder_create(OPUS_SAMPLE_RATE as i32, channels as c_int, &mut error)
+        };
+        if raw.is_null() || error != 0 {
+            return Err(opus_error(error));
+        }
+        let decoder = RawDecoder(raw);
+
+        let gain = options.gain_q8();
+        if gain != 0 {
+            let result = unsafe { ffi::opus_decoder_ctl(decoder.0, OPUS_SET_GAIN_REQUEST, gain) };
+            if result != 0 {
+                return Err(opus_error(result));
+            }
+        }
+        if options.disable_phase_inversion {
+            let disabled: c_int = 1;
+            let result = unsafe {
+                ffi::opus_decoder_ctl(decoder.0, OPUS_SET_PHASE_INVERSION_DISABLED_REQUEST, disabled)
+            };
+            if result != 0 {
+                return Err(opus_error(result));
+            }
+        }
+
+        Ok(Self {
+            decoder,
+            channels,
+            options,
+            buffer: vec![0; MAX_SAMPLES_PER_CH * channels as usize],
+            stats: DecoderStats::default(),
+        })
+    }
+
+    pub fn options(&self) -> &OpusDecoderOptions {
+        &self.options
+    }
+
+    /// Samples pro Kanal des zuletzt dekodierten Pakets.
+    fn last_packet_samples(&self) -> usize {
+        let mut samples: i32 = 0;
+        let result = unsafe {
+            ffi::opus_decoder_ctl(
+                self.decoder.0,
+                OPUS_GET_LAST_PACKET_DURATION_REQUEST,
+                &mut samples as *mut i32,
+            )
+        };
+        match result {
+            0 if samples > 0 => (samples as usize).min(MAX_SAMPLES_PER_CH),
+            _ => DEFAULT_PACKET_SAMPLES,
+        }
+    }
+
+    fn bandwidth(&self) -> Option<String> {
+        let mut bandwidth: i32 = 0;
+        let result = unsafe {
+            ffi::opus_decoder_ctl(
+                self.decoder.0,
+                OPUS_GET_BANDWIDTH_REQUEST,
+                &mut bandwidth as *mut i32,
+            )
+        };
+        let name = match (result, bandwidth) {
+            (0, 1101) => "narrowband",
+            (0, 1102) => "mediumband",
+            (0, 1103) => "wideband",
+            (0, 1104) => "superwideband",
+            (0, 1105) => "fullband",
+            _ => return None,
+        };
+        Some(name.to_string())
+    }
+
+    /// `packet` leer = PLC. `frame_size` in Samples pro Kanal.
+    fn decode_into_frame(&mut self, packet: &[u8], frame_size: usize, fec: bool) -> Result<Option<PcmFrame>> {
+        let data = if packet.is_empty() {
+            std::ptr::null()
+        } else {
+            packet.as_ptr()
+        };
+        let per_channel = unsafe {
+            ffi::opus_decode(
+                self.decoder.0,
+                data,
+                packet.len() as i32,
+                self.buffer.as_mut_ptr(),
+                frame_size as c_int,
+                fec as c_int,
+            )
+        };
+        if per_channel < 0 {
+            self.stats.errors += 1;
+            return Err(opus_error(per_channel));
+        }
+        if per_channel == 0 {
+            return Ok(None);
+        }
+        Ok(Some(PcmFrame {
+            utc_ns: utc_ns_now(),
+            samples: self.buffer[..per_channel as usize * self.channels as usize].to_vec(),
+            sample_rate: OPUS_SAMPLE_RATE,
+            channels: self.channels,
+            metadata: Default::default(),
+        }))
+    }
+}
+
+/// CELT-only-Pakete (TOC-Config 16–31) tragen keine FEC-Daten.
+fn may_carry_fec(packet: &[u8]) -> bool {
+    packet.first().is_some_and(|toc| toc >> 3 < 16)
+}
+
+impl AudioDecoder for OpusDecoder {
+    fn decode(&mut self, packet: &[u8]) -> Result<Option<PcmFrame>> {
+        let frame_size = if packet.is_empty() {
+            self.stats.plc_events += 1;
+            self.last_packet_samples()
+        } else {
+            self.stats.packets += 1;
+            MAX_SAMPLES_PER_CH
+        };
+        let frame = self.decode_into_frame(packet, frame_size, false)?;
+        if !packet.is_empty() {
+            self.stats.bandwidth = self.bandwidth();
+        }
+        Ok(frame)
+    }
+
+    fn conceal(&mut self, lost: u16, next: &[u8]) -> Result<Vec<PcmFrame>> {
+        let mut frames = Vec::new();
+        let use_fec = self.options.fec && may_carry_fec(next);
+        let plc = if use_fec { lost.saturating_sub(1) } else { lost };
+        for _ in 0..plc {
+            frames.extend(self.decode(&[])?);
+        }
+        if use_fec && lost > 0 {
+            // Nur das direkt vorangehende Paket steckt in der FEC von `next`
+            let frame_size = self.last_packet_samples();
+            if let Some(frame) = self.decode_into_frame(next, frame_size, true)? {
+                self.stats.fec_recoveries += 1;
+                frames.push(frame);
+            }
+        }
+        Ok(frames)
+    }
+
+    fn stats(&self) -> Option<DecoderStats> {
+        Some(self.stats.clone())
+    }
+}
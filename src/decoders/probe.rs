@@ -0,0 +1,372 @@
+// src/decoders/probe.rs
+//
+// Format-Probe für Dateien und HTTP/Icecast-URLs: liest nur den Anfang der
+// Quelle und erkennt Codec, Samplerate, Kanäle und Bitrate anhand der
+// Header (WAV, Ogg/Opus, Ogg/Vorbis, FLAC, MP3, ADTS/AAC), ohne zu dekodieren.
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use crate::core::http_client::{self, HttpRequest};
+
+/// So viele Bytes reichen für alle unterstützten Header (inkl. ID3-Tags üblicher Größe).
+const PROBE_BYTES: usize = 64 * 1024;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct StreamFormat {
+    pub codec: String,
+    pub container: String,
+    pub sample_rate: Option<u32>,
+    pub channels: Option<u8>,
+    pub bits_per_sample: Option<u16>,
+    /// bit/s
+    pub bitrate: Option<u32>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProbeResult {
+    pub source: String,
+    pub format: Option<StreamFormat>,
+    pub content_type: Option<String>,
+    /// `icy-*`-Header ohne Präfix (`name`, `genre`, `br`, `metaint`, …)
+    pub icy: BTreeMap<String, String>,
+    pub stream_title: Option<String>,
+    pub bytes_read: usize,
+}
+
+pub fn probe_path(path: &Path) -> Result<ProbeResult> {
+    let mut file =
+        std::fs::File::open(path).with_context(|| format!("failed to open {:?}", path))?;
+    let mut data = Vec::with_capacity(PROBE_BYTES);
+    std::io::Read::by_ref(&mut file)
+        .take(PROBE_BYTES as u64)
+        .read_to_end(&mut data)
+        .with_context(|| format!("failed to read {:?}", path))?;
+
+    Ok(ProbeResult {
+        source: path.display().to_string(),
+        format: sniff(&data),
+        bytes_read: data.len(),
+        ..Default::default()
+    })
+}
+
+/// Verbindet sich kurz per HTTP (mit `Icy-MetaData: 1`) und wertet
+/// Antwort-Header und die ersten Bytes des Streams aus.
+pub fn probe_url(url: &str, timeout: Duration) -> Result<ProbeResult> {
+    let deadline = Instant::now() + timeout;
+    let mut response = http_client::send(
+        &HttpRequest::get(url)
+            .header("Icy-MetaData", "1")
+            .timeout(timeout),
+    )?
+    .error_for_status()?;
+
+    let mut result = ProbeResult {
+        source: url.to_string(),
+        ..Default::default()
+    };
+    for (key, value) in &response.headers {
+        if let Some(icy_key) = key.strip_prefix("icy-") {
+            result.icy.insert(icy_key.to_string(), value.clone());
+        } else if key == "content-type" {
+            result.content_type = Some(value.clone());
+        }
+    }
+
+    let mut body = Vec::with_capacity(PROBE_BYTES);
+    let mut chunk = [0u8; 8192];
+    while body.len() < PROBE_BYTES && Instant::now() < deadline {
+        match response.read(&mut chunk) {
+            Ok(0) => break,
+            Ok(n) => body.extend_from_slice(&chunk[..n]),
+            Err(e)
+                if matches!(
+                    e.kind(),
+                    std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+                ) =>
+            {
+                break
+            }
+            Err(e) => return Err(e).context("failed to read stream"),
+        }
+    }
+    result.bytes_read = body.len();
+
+    let metaint = result.icy.get("metaint").and_then(|v| v.parse::<usize>().ok());
+    if let Some(metaint) = metaint.filter(|m| *m > 0) {
+        let (audio, title) = strip_icy_metadata(&body, metaint);
+        body = audio;
+        result.stream_title = title;
+    }
+
+    result.format = sniff(&body).or_else(|| {
+        result
+            .content_type
+            .as_deref()
+            .and_then(format_from_content_type)
+    });
+    if let Some(format) = result.format.as_mut() {
+        if format.bitrate.is_none() {
+            format.bitrate = result
+                .icy
+                .get("br")
+                .and_then(|br| br.split(',').next())
+                .and_then(|br| br.trim().parse::<u32>().ok())
+                .map(|kbps| kbps * 1000);
+        }
+    }
+    Ok(result)
+}
+
+/// Entfernt die ICY-Metadatenblöcke (alle `metaint` Bytes) und liefert den
+/// ersten `StreamTitle`, falls vorhanden.
+fn strip_icy_metadata(body: &[u8], metaint: usize) -> (Vec<u8>, Option<String>) {
+    let mut audio = Vec::with_capacity(body.len());
+    let mut title = None;
+    let mut pos = 0;
+    while pos < body.len() {
+        let end = (pos + metaint).min(body.len());
+        audio.extend_from_slice(&body[pos..end]);
+        pos = end;
+        if pos >= body.len() {
+            break;
+        }
+        let meta_len = body[pos] as usize * 16;
+        let meta_end = (pos + 1 + meta_len).min(body.len());
+        if title.is_none() && meta_len > 0 {
+            let meta = String::from_utf8_lossy(&body[pos + 1..meta_end]);
+            title = meta
+                .split("StreamTitle='")
+                .nth(1)
+                .and_then(|rest| rest.split("';").next())
+                .map(|t| t.trim_end_matches('\0').to_string());
+        }
+        pos = meta_end;
+    }
+    (audio, title)
+}
+
+fn format_from_content_type(content_type: &str) -> Option<StreamFormat> {
+    let (codec, container) = match content_type.split(';').next()?.trim() {
+        "audio/mpeg" | "audio/mp3" => ("mp3", "mpeg"),
+        "audio/aac" | "audio/aacp" => ("aac", "adts"),
+        "audio/ogg" | "application/ogg" => ("unknown", "ogg"),
+        "audio/opus" => ("opus", "ogg"),
+        "audio/flac" => ("flac", "flac"),
+        "audio/wav" | "audio/x-wav" => ("pcm", "wav"),
+        _ => return None,
+    };
+    Some(StreamFormat {
+        codec: codec.to_string(),
+        container: container.to_string(),
+        ..Default::default()
+    })
+}
+
+fn u16_le(data: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(data.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn u32_le(data: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(data.get(at..at + 4)?.try_into().ok()?))
+}
+
+/// Erkennt das Format anhand der ersten Bytes.
+pub fn sniff(data: &[u8]) -> Option<StreamFormat> {
+    if data.starts_with(b"RIFF") && data.get(8..12) == Some(b"WAVE") {
+        return sniff_wav(data);
+    }
+    if data.starts_with(b"OggS") {
+        return sniff_ogg(data);
+    }
+    if data.starts_with(b"fLaC") {
+        return flac_streaminfo(data.get(8..)?, "flac");
+    }
+
+    let mut offset = 0;
+    if data.starts_with(b"ID3") && data.len() >= 10 {
+        let size = data[6..10]
+            .iter()
+            .fold(0usize, |acc, b| (acc << 7) | (*b & 0x7f) as usize);
+        let footer = if data[5] & 0x10 != 0 { 10 } else { 0 };
+        offset = 10 + size + footer;
+    }
+    // Erstes Sync-Wort suchen (Streams setzen nicht immer an Frame-Grenzen ein)
+    let limit = data.len().saturating_sub(4).min(offset + 4096);
+    (offset..limit).find_map(|pos| sniff_mpeg_frame(&data[pos..]))
+}
+
+fn sniff_wav(data: &[u8]) -> Option<StreamFormat> {
+    let mut pos = 12;
+    while pos + 8 <= data.len() {
+        let id = &data[pos..pos + 4];
+        let size = u32_le(data, pos + 4)? as usize;
+        if id == b"fmt " {
+            let fmt = pos + 8;
+            let format_tag = u16_le(data, fmt)?;
+            let codec = match format_tag {
+                1 | 0xFFFE => "pcm",
+                3 => "pcm_float",
+                _ => "unknown",
+            };
+            return Some(StreamFormat {
+                codec: codec.to_string(),
+                container: "wav".to_string(),
+                channels: Some(u16_le(data, fmt + 2)? as u8),
+                sample_rate: Some(u32_le(data, fmt + 4)?),
+                bitrate: u32_le(data, fmt + 8).map(|byte_rate| byte_rate * 8),
+                bits_per_sample: u16_le(data, fmt + 14),
+            });
+        }
+        pos += 8 + size + (size & 1);
+    }
+    None
+}
+
+fn sniff_ogg(data: &[u8]) -> Option<StreamFormat> {
+    let segments = *data.get(26)? as usize;
+    let payload = data.get(27 + segments..)?;
+
+    if payload.starts_with(b"OpusHead") {
+        return Some(StreamFormat {
+            codec: "opus".to_string(),
+            container: "ogg".to_string(),
+            // Opus dekodiert immer mit 48 kHz
+            sample_rate: Some(48_000),
+            channels: payload.get(9).copied(),
+            ..Default::default()
+        });
+    }
+    if payload.starts_with(b"\x01vorbis") {
+        let nominal = u32_le(payload, 20).filter(|br| *br > 0 && *br < i32::MAX as u32);
+        return Some(StreamFormat {
+            codec: "vorbis".to_string(),
+            container: "ogg".to_string(),
+            channels: payload.get(11).copied(),
+            sample_rate: u32_le(payload, 12),
+            bitrate: nominal,
+            ..Default::default()
+        });
+    }
+    if payload.starts_with(b"\x7fFLAC") {
+        return flac_streaminfo(payload.get(17..)?, "ogg");
+    }
+    Some(StreamFormat {
+        codec: "unknown".to_string(),
+        container: "ogg".to_string(),
+        ..Default::default()
+    })
+}
+
+fn flac_streaminfo(info: &[u8], container: &str) -> Option<StreamFormat> {
+    let b = info.get(10..14)?;
+    let sample_rate = ((b[0] as u32) << 12) | ((b[1] as u32) << 4) | ((b[2] as u32) >> 4);
+    let channels = ((b[2] >> 1) & 0x07) + 1;
+    let bits = ((((b[2] & 0x01) as u16) << 4) | ((b[3] >> 4) as u16)) + 1;
+    Some(StreamFormat {
+        codec: "flac".to_string(),
+        container: container.to_string(),
+        sample_rate: Some(sample_rate),
+        channels: Some(channels),
+        bits_per_sample: Some(bits),
+        bitrate: None,
+    })
+}
+
+const ADTS_SAMPLE_RATES: [u32; 13] = [
+    96000, 88200, 64000, 48000, 44100, 32000, 24000, 22050, 16000, 12000, 11025, 8000, 7350,
+];
+const MPEG1_L1_KBPS: [u32; 15] = [0, 32, 64, 96, 128, 160, 192, 224, 256, 288, 320, 352, 384, 416, 448];
+const MPEG1_L2_KBPS: [u32; 15] = [0, 32, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320, 384];
+const MPEG1_L3_KBPS: [u32; 15] = [0, 32, 40, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320];
+const MPEG2_L1_KBPS: [u32; 15] = [0, 32, 48, 56, 64, 80, 96, 112, 128, 144, 160, 176, 192, 224, 256];
+const MPEG2_L23_KBPS: [u32; 15] = [0, 8, 16, 24, 32, 40, 48, 56, 64, 80, 96, 112, 128, 144, 160];
+
+fn sniff_mpeg_frame(h: &[u8]) -> Option<StreamFormat> {
+    if h.len() < 4 || h[0] != 0xFF || h[1] & 0xE0 != 0xE0 {
+        return None;
+    }
+    let version = (h[1] >> 3) & 0x03;
+    let layer = (h[1] >> 1) & 0x03;
+
+    // ADTS (AAC): Layer-Bits sind 0
+    if layer == 0 && h[1] & 0xF0 == 0xF0 {
+        let sample_rate = *ADTS_SAMPLE_RATES.get(((h[2] >> 2) & 0x0F) as usize)?;
+        let channels = ((h[2] & 0x01) << 2) | (h[3] >> 6);
+        return Some(StreamFormat {
+            codec: "aac".to_string(),
+            container: "adts".to_string(),
+            sample_rate: Some(sample_rate),
+            channels: (channels > 0).then_some(channels),
+            ..Default::default()
+        });
+    }
+    if version == 1 || layer == 0 {
+        return None;
+    }
+
+    let bitrate_index = (h[2] >> 4) as usize;
+    let rate_index = ((h[2] >> 2) & 0x03) as usize;
+    if bitrate_index == 0 || bitrate_index == 15 || rate_index == 3 {
+        return None;
+    }
+    let table = match (version, layer) {
+        (3, 3) => &MPEG1_L1_KBPS,
+        (3, 2) => &MPEG1_L2_KBPS,
+        (3, 1) => &MPEG1_L3_KBPS,
+        (_, 3) => &MPEG2_L1_KBPS,
+        _ => &MPEG2_L23_KBPS,
+    };
+    let base_rate = [44_100, 48_000, 32_000][rate_index];
+    let sample_rate = match version {
+        3 => base_rate,
+        2 => base_rate / 2,
+        _ => base_rate / 4,
+    };
+    let codec = match layer {
+        3 => "mp1",
+        2 => "mp2",
+        _ => "mp3",
+    };
+    Some(StreamFormat {
+        codec: codec.to_string(),
+        container: "mpeg".to_string(),
+        sample_rate: Some(sample_rate),
+        channels: Some(if h[3] >> 6 == 3 { 1 } else { 2 }),
+        bitrate: Some(table[bitrate_index] * 1000),
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sniffs_mp3_frame_after_id3() {
+        let mut data = b"ID3\x04\x00\x00\x00\x00\x00\x02xx".to_vec();
+        data.extend_from_slice(&[0xFF, 0xFB, 0x90, 0x44, 0, 0, 0, 0]);
+        let format = sniff(&data).expect("mp3 detected");
+        assert_eq!(format.codec, "mp3");
+        assert_eq!(format.sample_rate, Some(44_100));
+        assert_eq!(format.bitrate, Some(128_000));
+        assert_eq!(format.channels, Some(2));
+    }
+
+    #[test]
+    fn strips_icy_metadata() {
+        let mut body = b"abcd".to_vec();
+        body.push(2);
+        body.extend_from_slice(b"StreamTitle='x';\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0");
+        body.truncate(5 + 32);
+        body.extend_from_slice(b"efgh");
+        let (audio, title) = strip_icy_metadata(&body, 4);
+        assert_eq!(audio, b"abcdefgh");
+        assert_eq!(title.as_deref(), Some("x"));
+    }
+}
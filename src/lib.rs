@@ -1,4 +1,5 @@
 // src/lib.rs
+pub mod aoip;
 pub mod api;
 pub mod app;
 pub mod audio;
@@ -11,11 +12,15 @@ pub mod encoders;
 pub mod processors;
 pub mod producers;
 pub mod ring;
+#[cfg(feature = "lua")]
+pub mod rules;
+pub mod storage;
 pub mod testing;
 pub mod types;
 pub mod monitoring;
+pub mod mqtt;
 
 // Re-export die wichtigsten Typen
 pub use core::timestamp::utc_ns_now;
 pub use core::{AirliftNode, AudioRingBuffer, ComponentLogger, Flow, LogContext};
-pub use types::PcmFrame;
+pub use types::{FrameMetadata, Frames, Millis, PcmFrame, Samples, Slots};
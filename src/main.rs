@@ -2,20 +2,25 @@ use airlift_node::{
     api,
     config,
     core,
-    producers,
-    consumers,
 };
 
-use airlift_node::app::init::{build_plugin_registry, PluginRegistry};
+use airlift_node::app::init::build_plugin_registry;
+use airlift_node::app::runtime::{self, NodeRuntime};
 
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 fn main() -> anyhow::Result<()> {
-    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info"))
+    // Verbose-Logger nur während eines Debug-Captures aktiv
+    let normal = env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info"))
         .format_timestamp_millis()
-        .init();
+        .build();
+    let verbose = env_logger::Builder::new()
+        .parse_filters("info,airlift_node=debug")
+        .format_timestamp_millis()
+        .build();
+    core::logging::init_switchable_logger(normal, verbose)?;
 
     log::info!("=== Airlift Node v0.3.0 ===");
 
@@ -31,6 +36,7 @@ fn main() -> anyhow::Result<()> {
                     return Ok(());
                 }
             }
+            "--dry-run" => return run_dry_run(&args[2..]),
             _ => {}
         }
     }
@@ -41,7 +47,7 @@ fn main() -> anyhow::Result<()> {
 #[cfg(feature = "alsa")]
 fn run_discovery() -> anyhow::Result<()> {
     use airlift_node::core::device_scanner::DeviceScanner;
-    let scanner = producers::alsa::AlsaDeviceScanner;
+    let scanner = airlift_node::producers::alsa::AlsaDeviceScanner;
 
     log::info!("Starting ALSA device discovery…");
     let devices = scanner.scan_devices()?;
@@ -58,7 +64,7 @@ fn run_discovery() -> anyhow::Result<()> {
 #[cfg(feature = "alsa")]
 fn test_device(device_id: &str) -> anyhow::Result<()> {
     use airlift_node::core::device_scanner::DeviceScanner;
-    let scanner = producers::alsa::AlsaDeviceScanner;
+    let scanner = airlift_node::producers::alsa::AlsaDeviceScanner;
 
     log::info!("Testing device {}", device_id);
     let result = scanner.test_device(device_id, 3000)?;
@@ -72,122 +78,101 @@ fn test_device(_device_id: &str) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// `--dry-run <flow> <input> [--output <wav>] [--report <json>] [--config <toml>]`
+fn run_dry_run(args: &[String]) -> anyhow::Result<()> {
+    use airlift_node::app::dry_run;
+    use std::path::PathBuf;
+
+    let usage = "Usage: airlift-node --dry-run <flow> <input> [--output <wav>] [--report <json>] [--config <toml>]";
+    let mut positional = Vec::new();
+    let mut output = None;
+    let mut report = None;
+    let mut config_path = "config.toml".to_string();
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--output" => output = Some(PathBuf::from(rest.next().ok_or_else(|| anyhow::anyhow!(usage))?)),
+            "--report" => report = Some(PathBuf::from(rest.next().ok_or_else(|| anyhow::anyhow!(usage))?)),
+            "--config" => config_path = rest.next().ok_or_else(|| anyhow::anyhow!(usage))?.clone(),
+            _ => positional.push(arg.clone()),
+        }
+    }
+    let [flow, input] = positional.as_slice() else {
+        anyhow::bail!(usage);
+    };
+    let input = PathBuf::from(input);
+    let output = output.unwrap_or_else(|| dry_run::default_output_path(&input));
+
+    let cfg = config::Config::load(&config_path)?;
+    let result = dry_run::dry_run(&cfg, flow, &build_plugin_registry(), &input, &output)?;
+    let json = serde_json::to_string_pretty(&result)?;
+    match report {
+        Some(path) => {
+            std::fs::write(&path, json)?;
+            log::info!("Dry-run report written to {}", path.display());
+        }
+        None => println!("{}", json),
+    }
+    Ok(())
+}
+
 fn run_normal_mode() -> anyhow::Result<()> {
+    use airlift_node::core::safe_mode;
+
     let cfg = config::Config::load("config.toml")
         .unwrap_or_else(|e| {
             log::warn!("Config error: {}, using defaults", e);
             config::Config::default()
         });
 
-    let cfg = Arc::new(Mutex::new(cfg));
-    let node = Arc::new(Mutex::new(core::AirliftNode::new()));
-
-    let snapshot = cfg.lock().unwrap().clone();
-    log::info!("Node: {}", snapshot.node_name);
-
-    let api_bind = format!("0.0.0.0:{}", snapshot.monitoring.http_port);
-    api::start_api_server(&api_bind, cfg.clone(), node.clone())?;
-
-    let plugin_registry: PluginRegistry = build_plugin_registry();
-
-    {
-        let mut node = node.lock().unwrap();
-
-        /* ---------------- Producers ---------------- */
-        for (name, p_cfg) in &snapshot.producers {
-            if !p_cfg.enabled {
-                continue;
-            }
-
-            match p_cfg.producer_type.as_str() {
-                "sine" => {
-                    let freq = p_cfg.config
-                        .get("frequency")
-                        .and_then(|v| v.as_f64())
-                        .unwrap_or(440.0) as f32;
-                    let rate = p_cfg.sample_rate.unwrap_or(48_000);
-
-                    node.add_producer(Box::new(
-                        producers::sine::SineProducer::new(name, freq, rate),
-                    ))?;
-
-                    log::info!("Added sine producer '{}' ({} Hz)", name, freq);
-                }
-                other => {
-                    log::error!("Unsupported producer type '{}'", other);
-                }
-            }
-        }
-
-        /* ---------------- Flows ---------------- */
-        for (flow_name, flow_cfg) in &snapshot.flows {
-            if !flow_cfg.enabled {
-                continue;
-            }
-
-            let mut flow = core::Flow::new(flow_name);
-
-            // Processors
-            for proc_name in &flow_cfg.processors {
-                if let Some(proc_cfg) = snapshot.processors.get(proc_name) {
-                    if proc_cfg.enabled {
-                        let p = plugin_registry.create_processor(proc_name, proc_cfg)?;
-                        flow.add_processor(p);
-                        log::info!(
-                            "Added processor '{}' to flow '{}'",
-                            proc_name, flow_name
-                        );
-                    }
-                }
-            }
-
-            // Consumers
-            for out_name in &flow_cfg.outputs {
-                if let Some(c_cfg) = snapshot.consumers.get(out_name) {
-                    if !c_cfg.enabled {
-                        continue;
-                    }
-
-                    match c_cfg.consumer_type.as_str() {
-                        "file" => {
-                            let path = c_cfg.path.as_ref().unwrap();
-                            flow.add_consumer(Box::new(
-                                core::consumer::file_writer::FileConsumer::new(out_name, path),
-                            ));
-                            log::info!(
-                                "Added FileConsumer '{}' to flow '{}' ({})",
-                                out_name, flow_name, path
-                            );
-                        }
-                        other => {
-                            log::error!("Unsupported consumer type '{}'", other);
-                        }
-                    }
-                }
-            }
-
-            node.add_flow(flow);
-            log::info!("Added flow '{}'", flow_name);
-        }
-
-        /* ---------------- Connections (BORROW-SAFE) ---------------- */
-        let flow_count = node.flows.len();
-
-        for flow_index in 0..flow_count {
-            let flow_name = node.flows[flow_index].name.clone();
-
-            if let Some(flow_cfg) = snapshot.flows.get(&flow_name) {
-                for input in &flow_cfg.inputs {
-                    let buffer_name = format!("producer:{}", input);
-                    node.connect_flow_input(flow_index, &buffer_name)?;
-                }
-            }
+    let startup = cfg.startup.clone().unwrap_or_default();
+    let max_crashes = startup.max_crashes.unwrap_or(safe_mode::DEFAULT_MAX_CRASHES);
+    let stable_after = startup.stable_after().unwrap_or_else(|e| {
+        log::warn!("{}, using default", e);
+        safe_mode::DEFAULT_STABLE_AFTER
+    });
+    let counter = core::CrashCounter::open(
+        startup
+            .crash_file
+            .as_deref()
+            .unwrap_or(safe_mode::DEFAULT_CRASH_FILE),
+    );
+    let previous = counter.previous();
+    let safe = safe_mode::should_enter(&previous, max_crashes);
+    if !safe {
+        if let Err(e) = counter.begin_start() {
+            log::warn!("[safe-mode] crash counter not updated: {}", e);
         }
+    }
+    safe_mode::install(counter, max_crashes, &previous, safe);
+    safe_mode::install_panic_hook();
+    runtime::install_process_globals(&cfg);
 
-        node.start()?;
+    if safe {
+        return run_safe_mode(cfg);
+    }
+    let result = run_audio_mode(cfg, stable_after);
+    if let Err(e) = &result {
+        safe_mode::record_failure(&format!("{:#}", e));
     }
+    result
+}
 
-    log::info!("Node started. Press Ctrl+C to stop.");
+/// Nur API/Monitoring; Audio erst nach `safe_mode.exit`.
+fn run_safe_mode(cfg: config::Config) -> anyhow::Result<()> {
+    let status = airlift_node::core::safe_mode::safe_mode_status();
+    log::error!(
+        "SAFE MODE: {} unstable starts in a row (limit {}), last reason: {}",
+        status.unstable_starts,
+        status.max_crashes,
+        status.last_crash_reason.as_deref().unwrap_or("unknown")
+    );
+    log::error!("Audio components are not started; fix the configuration and send the 'safe_mode.exit' action");
+
+    let binds = cfg.monitoring.effective_binds();
+    let cfg = Arc::new(Mutex::new(cfg));
+    let node = Arc::new(Mutex::new(core::AirliftNode::new()));
+    api::start_api_servers(&binds, cfg, node.clone())?;
 
     let shutdown = Arc::new(AtomicBool::new(false));
     let s = shutdown.clone();
@@ -198,9 +183,33 @@ fn run_normal_mode() -> anyhow::Result<()> {
 
     while !shutdown.load(Ordering::Relaxed) {
         std::thread::sleep(Duration::from_millis(500));
+        if let Ok(mut node) = node.lock() {
+            node.run_watchdog();
+        }
     }
 
+    node.lock().unwrap().stop_draining();
     node.lock().unwrap().stop()?;
     log::info!("Node stopped");
     Ok(())
 }
+
+fn run_audio_mode(cfg: config::Config, stable_after: Duration) -> anyhow::Result<()> {
+    log::info!("Node: {}", cfg.node_name);
+
+    let mut runtime = NodeRuntime::builder(cfg)
+        .process_globals(false)
+        .stable_after(stable_after)
+        .build()?;
+    runtime.start()?;
+
+    log::info!("Node started. Press Ctrl+C to stop.");
+
+    let shutdown = runtime.shutdown_handle();
+    ctrlc::set_handler(move || {
+        log::info!("Shutdown requested");
+        shutdown.request();
+    })?;
+
+    runtime.wait()
+}
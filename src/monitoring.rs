@@ -2,12 +2,17 @@ use std::fmt::Write;
 use std::sync::{Arc, Mutex};
 use std::thread;
 
-use tiny_http::{Header, Method, Request, Response, Server, StatusCode};
+use tiny_http::{Header, Method, Request, Response, StatusCode};
 
+use crate::api::listeners::{self, BindReport, BindRetry};
+use crate::core::host_telemetry::{self, DiskStatus, HostSnapshot, SmartHealth};
+use crate::core::http_client::{self, DestinationStats};
+use crate::core::labels::{self, Labels};
 use crate::core::AirliftNode;
 
 pub fn start_monitoring_server(bind: &str, node: Arc<Mutex<AirliftNode>>) -> anyhow::Result<()> {
-    let server = Server::http(bind).map_err(|e| anyhow::anyhow!(e))?;
+    let server = listeners::bind_http("monitoring", bind, BindRetry::default())
+        .map_err(|failure| anyhow::anyhow!(BindReport { failures: vec![failure] }))?;
     log::info!("[monitoring] server on {}", bind);
 
     thread::spawn(move || {
@@ -63,8 +68,9 @@ fn build_metrics(node: &AirliftNode) -> String {
         let status = producer.status();
         let _ = writeln!(
             output,
-            "airlift_frames_processed_total{{producer=\"{}\"}} {}",
+            "airlift_frames_processed_total{{producer=\"{}\"{}}} {}",
             escape_label_value(producer.name()),
+            extra_labels(&labels::producer_labels(producer.name())),
             status.samples_processed
         );
     }
@@ -94,33 +100,37 @@ fn build_metrics(node: &AirliftNode) -> String {
     for buffer_name in registry.list() {
         if let Some(buffer) = registry.get(&buffer_name) {
             let stats = buffer.stats();
-            let utilization = if stats.capacity > 0 {
-                stats.current_frames as f64 / stats.capacity as f64
+            let utilization = if !stats.capacity.is_zero() {
+                stats.current_frames.get() as f64 / stats.capacity.get() as f64
             } else {
                 0.0
             };
-            let label = escape_label_value(&buffer_name);
+            let label = format!(
+                "buffer=\"{}\"{}",
+                escape_label_value(&buffer_name),
+                extra_labels(&labels::buffer_labels(&buffer_name))
+            );
             let _ = writeln!(
                 output,
-                "airlift_buffer_utilization_ratio{{buffer=\"{}\"}} {}",
+                "airlift_buffer_utilization_ratio{{{}}} {}",
                 label, utilization
             );
             let _ = writeln!(
                 output,
-                "airlift_buffer_frames{{buffer=\"{}\"}} {}",
-                label, stats.current_frames
+                "airlift_buffer_frames{{{}}} {}",
+                label, stats.current_frames.get()
             );
             let _ = writeln!(
                 output,
-                "airlift_buffer_capacity_frames{{buffer=\"{}\"}} {}",
-                label, stats.capacity
+                "airlift_buffer_capacity_frames{{{}}} {}",
+                label, stats.capacity.get()
             );
             if let (Some(oldest), Some(latest)) = (stats.oldest_timestamp, stats.latest_timestamp) {
                 if latest >= oldest {
                     let latency = (latest - oldest) as f64 / 1_000_000_000.0;
                     let _ = writeln!(
                         output,
-                        "airlift_buffer_latency_seconds{{buffer=\"{}\"}} {}",
+                        "airlift_buffer_latency_seconds{{{}}} {}",
                         label, latency
                     );
                 }
@@ -128,9 +138,188 @@ fn build_metrics(node: &AirliftNode) -> String {
         }
     }
 
+    // Pegel aus dem langsamsten Fenster (Standard 10 s), nicht aus dem Meter
+    let _ = writeln!(
+        output,
+        "# HELP airlift_flow_peak_ratio Highest sample level of the last aggregate window (1.0 = full scale)."
+    );
+    let _ = writeln!(output, "# TYPE airlift_flow_peak_ratio gauge");
+    let _ = writeln!(
+        output,
+        "# HELP airlift_flow_rms_ratio RMS level of the last aggregate window (1.0 = full scale)."
+    );
+    let _ = writeln!(output, "# TYPE airlift_flow_rms_ratio gauge");
+    let _ = writeln!(
+        output,
+        "# HELP airlift_flow_clipped_samples Full-scale samples in the last aggregate window."
+    );
+    let _ = writeln!(output, "# TYPE airlift_flow_clipped_samples gauge");
+    for flow in node.flows() {
+        let Some(window) = flow.levels().aggregate else {
+            continue;
+        };
+        let label = format!(
+            "flow=\"{}\"{}",
+            escape_label_value(&flow.name),
+            extra_labels(&labels::flow_labels(&flow.name))
+        );
+        for (channel, name) in ["left", "right"].iter().enumerate() {
+            let _ = writeln!(
+                output,
+                "airlift_flow_peak_ratio{{{},channel=\"{}\"}} {}",
+                label, name, window.peaks[channel]
+            );
+            let _ = writeln!(
+                output,
+                "airlift_flow_rms_ratio{{{},channel=\"{}\"}} {}",
+                label, name, window.rms[channel]
+            );
+        }
+        let _ = writeln!(
+            output,
+            "airlift_flow_clipped_samples{{{}}} {}",
+            label, window.clipped
+        );
+    }
+
+    let destinations = http_client::destination_stats();
+    write_http_counter(
+        &mut output,
+        &destinations,
+        "airlift_http_requests_total",
+        "Outgoing HTTP requests per destination, including retries.",
+        |stats| stats.requests,
+    );
+    write_http_counter(
+        &mut output,
+        &destinations,
+        "airlift_http_errors_total",
+        "Failed outgoing HTTP requests (connection/read errors and status >= 400).",
+        |stats| stats.errors,
+    );
+    write_http_counter(
+        &mut output,
+        &destinations,
+        "airlift_http_retries_total",
+        "Retried outgoing HTTP requests.",
+        |stats| stats.retries,
+    );
+
+    if let Some(host) = host_telemetry::latest() {
+        write_host_metrics(&mut output, &host);
+    }
+
     output
 }
 
+/// Gauges aus der letzten Rechner-Abtastung (`[telemetry]`).
+fn write_host_metrics(output: &mut String, host: &HostSnapshot) {
+    let gauge = |output: &mut String, metric: &str, help: &str| {
+        let _ = writeln!(output, "# HELP {} {}", metric, help);
+        let _ = writeln!(output, "# TYPE {} gauge", metric);
+    };
+
+    if let Some(load) = host.load_average {
+        gauge(output, "airlift_host_load_average", "System load average.");
+        for (window, value) in ["1m", "5m", "15m"].iter().zip(load) {
+            let _ = writeln!(output, "airlift_host_load_average{{window=\"{}\"}} {}", window, value);
+        }
+    }
+
+    if host.cpu_usage_ratio.is_some() || !host.cores.is_empty() {
+        gauge(output, "airlift_host_cpu_usage_ratio", "CPU busy time since the previous sample (0..1).");
+        if let Some(usage) = host.cpu_usage_ratio {
+            let _ = writeln!(output, "airlift_host_cpu_usage_ratio{{core=\"all\"}} {:.4}", usage);
+        }
+        for core in &host.cores {
+            let _ = writeln!(output, "airlift_host_cpu_usage_ratio{{core=\"{}\"}} {:.4}", core.core, core.usage_ratio);
+        }
+    }
+
+    if !host.temperatures.is_empty() {
+        gauge(output, "airlift_host_temperature_celsius", "Hardware monitor temperature sensors.");
+        for temperature in &host.temperatures {
+            let _ = writeln!(
+                output,
+                "airlift_host_temperature_celsius{{chip=\"{}\",sensor=\"{}\"}} {}",
+                escape_label_value(&temperature.chip),
+                escape_label_value(&temperature.sensor),
+                temperature.celsius
+            );
+        }
+    }
+
+    if host.disks.is_empty() {
+        return;
+    }
+    let disk_label = |disk: &DiskStatus| {
+        format!(
+            "mount_point=\"{}\",device=\"{}\"",
+            escape_label_value(&disk.mount_point),
+            escape_label_value(&disk.device)
+        )
+    };
+    gauge(output, "airlift_host_disk_total_bytes", "Size of filesystems holding recording paths.");
+    for disk in &host.disks {
+        let _ = writeln!(output, "airlift_host_disk_total_bytes{{{}}} {}", disk_label(disk), disk.total_bytes);
+    }
+    gauge(output, "airlift_host_disk_available_bytes", "Space available to the node on filesystems holding recording paths.");
+    for disk in &host.disks {
+        let _ = writeln!(output, "airlift_host_disk_available_bytes{{{}}} {}", disk_label(disk), disk.available_bytes);
+    }
+    gauge(output, "airlift_host_disk_used_ratio", "Used share of filesystems holding recording paths (0..1).");
+    for disk in &host.disks {
+        let _ = writeln!(output, "airlift_host_disk_used_ratio{{{}}} {:.4}", disk_label(disk), disk.used_ratio);
+    }
+
+    let smart: Vec<(&DiskStatus, &SmartHealth)> = host
+        .disks
+        .iter()
+        .filter_map(|disk| disk.smart.as_ref().map(|smart| (disk, smart)))
+        .collect();
+    if smart.is_empty() {
+        return;
+    }
+    gauge(output, "airlift_host_disk_smart_passed", "SMART overall health self-assessment (1 = passed).");
+    for (disk, health) in &smart {
+        let _ = writeln!(output, "airlift_host_disk_smart_passed{{{}}} {}", disk_label(disk), u8::from(health.passed));
+    }
+    gauge(output, "airlift_host_disk_reallocated_sectors", "Reallocated sectors (ATA) or media errors (NVMe) reported by SMART.");
+    for (disk, health) in &smart {
+        if let Some(sectors) = health.reallocated_sectors {
+            let _ = writeln!(output, "airlift_host_disk_reallocated_sectors{{{}}} {}", disk_label(disk), sectors);
+        }
+    }
+}
+
+fn write_http_counter(
+    output: &mut String,
+    destinations: &[DestinationStats],
+    metric: &str,
+    help: &str,
+    value: fn(&DestinationStats) -> u64,
+) {
+    let _ = writeln!(output, "# HELP {} {}", metric, help);
+    let _ = writeln!(output, "# TYPE {} counter", metric);
+    for stats in destinations {
+        let _ = writeln!(
+            output,
+            "{}{{destination=\"{}\"}} {}",
+            metric,
+            escape_label_value(&stats.destination),
+            value(stats)
+        );
+    }
+}
+
+/// `config.labels` als zusätzliche Labels, z. B. `,site="studio-b"`.
+fn extra_labels(labels: &Labels) -> String {
+    labels
+        .iter()
+        .map(|(key, value)| format!(",{}=\"{}\"", key, escape_label_value(value)))
+        .collect()
+}
+
 fn escape_label_value(value: &str) -> String {
     value.replace('\\', "\\\\").replace('\"', "\\\"")
 }
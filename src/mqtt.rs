@@ -0,0 +1,722 @@
+// src/mqtt.rs
+//
+// MQTT-Ausgang für Haustechnik und Automationssysteme: Stille-, Pegel- und
+// Lautheits-Events sowie regelmäßige Health-Snapshots gehen als JSON an einen
+// Broker (`[mqtt]`). MQTT 3.1.1 ist hier selbst implementiert, nur das
+// Nötigste für einen Publisher (QoS 0–2, Last Will, Keepalive, kein TLS).
+// Events kommen wie bei den Regeln über eine begrenzte Queue vom Event-Bus;
+// ist der Broker nicht erreichbar, werden sie verworfen, nach dem Reconnect
+// geht es live weiter.
+//
+// Topics (`<prefix>` Standard `airlift/<node_name>`):
+//   <prefix>/status                  "online"/"offline" (retained, Last Will)
+//   <prefix>/health                  Health-Snapshot (retained)
+//   <prefix>/flows/<flow>/silence    nur beim Wechsel Stille/Audio (retained)
+//   <prefix>/flows/<flow>/loudness   Messwerte der LUFS-Analyzer
+//   <prefix>/flows/<flow>/peak       1-s-Pegelstatistik des Flows
+//   <prefix>/events/<EventType>      weitere Event-Typen aus `events`
+use std::collections::HashMap;
+use std::io::{ErrorKind, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail, Context, Result};
+use crossbeam_channel::{bounded, Receiver, RecvTimeoutError, Sender, TrySendError};
+
+use crate::config::units::parse_duration;
+use crate::config::MqttConfig;
+use crate::core::lock::lock_mutex;
+use crate::core::timestamp::utc_ns_now;
+use crate::core::{AirliftNode, Event, EventBus, EventHandler, EventType};
+
+pub const DEFAULT_PORT: u16 = 1883;
+pub const DEFAULT_EVENTS: [&str; 3] = ["silence", "loudness", "peak"];
+const DEFAULT_HEALTH_INTERVAL: Duration = Duration::from_secs(10);
+const DEFAULT_KEEPALIVE: Duration = Duration::from_secs(30);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+/// Wartezeit auf CONNACK, PUBACK, PUBREC, PUBCOMP und PINGRESP
+const ACK_TIMEOUT: Duration = Duration::from_secs(5);
+const RECONNECT_MIN: Duration = Duration::from_secs(1);
+const RECONNECT_MAX: Duration = Duration::from_secs(30);
+/// Wie oft der MQTT-Thread das Stop-Flag prüft
+const STOP_POLL: Duration = Duration::from_millis(200);
+const EVENT_QUEUE: usize = 1024;
+const HANDLER_NAME: &str = "mqtt";
+
+const CONNECT: u8 = 0x10;
+const CONNACK: u8 = 0x20;
+const PUBLISH: u8 = 0x30;
+const PUBACK: u8 = 0x40;
+const PUBREC: u8 = 0x50;
+const PUBREL: u8 = 0x62;
+const PUBCOMP: u8 = 0x70;
+const PINGREQ: u8 = 0xc0;
+const PINGRESP: u8 = 0xd0;
+const DISCONNECT: u8 = 0xe0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MqttOptions {
+    pub host: String,
+    pub port: u16,
+    pub client_id: String,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// Ohne abschließenden `/`
+    pub prefix: String,
+    pub qos: u8,
+    pub events: Vec<String>,
+    /// `None` = keine Health-Snapshots
+    pub health_interval: Option<Duration>,
+    pub keepalive: Duration,
+}
+
+impl MqttOptions {
+    pub fn from_config(config: &MqttConfig, node_name: &str) -> Result<Self> {
+        let broker = config.broker.trim();
+        if broker.starts_with("mqtts://") {
+            bail!("mqtt.broker: TLS (mqtts://) is not supported, use mqtt://");
+        }
+        let address = broker.strip_prefix("mqtt://").unwrap_or(broker);
+        let address = address.trim_end_matches('/');
+        let (host, port) = match address.rsplit_once(':') {
+            Some((host, port)) if !host.ends_with(']') || host.starts_with('[') => {
+                let port = port
+                    .parse::<u16>()
+                    .map_err(|_| anyhow!("mqtt.broker '{}' has an invalid port", broker))?;
+                (host, port)
+            }
+            _ => (address, DEFAULT_PORT),
+        };
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+        if host.is_empty() || port == 0 {
+            bail!("mqtt.broker '{}' must look like mqtt://host:1883", broker);
+        }
+
+        let client_id = config
+            .client_id
+            .clone()
+            .unwrap_or_else(|| format!("airlift-{}", node_name));
+        if client_id.trim().is_empty() || client_id.len() > 0xffff {
+            bail!("mqtt.client_id must not be empty");
+        }
+        if config.password.is_some() && config.username.is_none() {
+            bail!("mqtt.password needs mqtt.username");
+        }
+
+        let prefix = config
+            .topic_prefix
+            .clone()
+            .unwrap_or_else(|| format!("airlift/{}", topic_segment(node_name)));
+        let prefix = prefix.trim().trim_end_matches('/').to_string();
+        if prefix.is_empty() || prefix.contains(['+', '#']) {
+            bail!("mqtt.topic_prefix must be a non-empty topic without wildcards");
+        }
+
+        let qos = config.qos.unwrap_or(0);
+        if qos > 2 {
+            bail!("mqtt.qos must be 0, 1 or 2");
+        }
+
+        let events: Vec<String> = if config.events.is_empty() {
+            DEFAULT_EVENTS.iter().map(|name| name.to_string()).collect()
+        } else {
+            config
+                .events
+                .iter()
+                .map(|name| name.trim().to_string())
+                .collect()
+        };
+        for name in &events {
+            if name.is_empty() || name.contains(['/', '+', '#']) {
+                bail!("mqtt.events entry '{}' is not a valid event name", name);
+            }
+        }
+
+        let duration = |key: &str, value: &Option<String>, default: Duration| -> Result<Duration> {
+            match value {
+                Some(text) => {
+                    parse_duration(text).map_err(|e| anyhow!("mqtt.{} invalid: {}", key, e))
+                }
+                None => Ok(default),
+            }
+        };
+        let health_interval = duration(
+            "health_interval",
+            &config.health_interval,
+            DEFAULT_HEALTH_INTERVAL,
+        )?;
+        if !health_interval.is_zero() && health_interval < Duration::from_secs(1) {
+            bail!("mqtt.health_interval must be 0s or at least 1s");
+        }
+        let keepalive = duration("keepalive", &config.keepalive, DEFAULT_KEEPALIVE)?;
+        if keepalive < Duration::from_secs(5) || keepalive.as_secs() > u16::MAX as u64 {
+            bail!("mqtt.keepalive must be between 5s and 18h");
+        }
+
+        Ok(Self {
+            host: host.to_string(),
+            port,
+            client_id,
+            username: config.username.clone(),
+            password: config.password.clone(),
+            prefix,
+            qos,
+            events,
+            health_interval: (!health_interval.is_zero()).then_some(health_interval),
+            keepalive,
+        })
+    }
+
+    pub fn status_topic(&self) -> String {
+        format!("{}/status", self.prefix)
+    }
+
+    pub fn health_topic(&self) -> String {
+        format!("{}/health", self.prefix)
+    }
+
+    /// Event-Typen, die der Handler am Bus abonniert.
+    fn event_filter(&self) -> Vec<EventType> {
+        let mut filter = Vec::new();
+        for name in &self.events {
+            let event_type = match name.as_str() {
+                "silence" | "loudness" => EventType::AnalyzerReading,
+                "peak" => EventType::AudioLevelStats,
+                other => EventType::from_name(other),
+            };
+            if !filter
+                .iter()
+                .any(|known: &EventType| known.matches(&event_type))
+            {
+                filter.push(event_type);
+            }
+        }
+        filter
+    }
+}
+
+/// Flow- und Node-Namen als ein Topic-Level (ohne `/` und Wildcards).
+fn topic_segment(name: &str) -> String {
+    name.replace(['/', '+', '#'], "_")
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MqttMessage {
+    pub topic: String,
+    pub payload: Vec<u8>,
+    pub retain: bool,
+}
+
+/// Übersetzt Events in MQTT-Nachrichten; merkt sich den Stille-Zustand je
+/// Analyzer, damit nur Wechsel publiziert werden.
+pub struct MqttRouter {
+    prefix: String,
+    events: Vec<String>,
+    silent: HashMap<(String, String), bool>,
+}
+
+impl MqttRouter {
+    pub fn new(options: &MqttOptions) -> Self {
+        Self {
+            prefix: options.prefix.clone(),
+            events: options.events.clone(),
+            silent: HashMap::new(),
+        }
+    }
+
+    fn wants(&self, name: &str) -> bool {
+        self.events.iter().any(|event| event == name)
+    }
+
+    pub fn route(&mut self, event: &Event) -> Option<MqttMessage> {
+        let payload = &event.payload;
+        let flow = payload
+            .get("flow")
+            .and_then(|flow| flow.as_str())
+            .unwrap_or(&event.source_instance);
+        let flow_topic =
+            |leaf: &str| format!("{}/flows/{}/{}", self.prefix, topic_segment(flow), leaf);
+        let message = |topic: String, retain: bool| MqttMessage {
+            topic,
+            payload: payload.to_string().into_bytes(),
+            retain,
+        };
+
+        match &event.event_type {
+            EventType::AnalyzerReading => {
+                match payload.get("kind").and_then(|kind| kind.as_str()) {
+                    Some("silence") if self.wants("silence") => {
+                        let silent = payload.get("silent")?.as_bool()?;
+                        let tap = payload
+                            .get("tap")
+                            .and_then(|tap| tap.as_str())
+                            .unwrap_or_default();
+                        let key = (flow.to_string(), tap.to_string());
+                        if self.silent.insert(key, silent) == Some(silent) {
+                            return None;
+                        }
+                        return Some(message(flow_topic("silence"), true));
+                    }
+                    Some("lufs") if self.wants("loudness") => {
+                        return Some(message(flow_topic("loudness"), false));
+                    }
+                    _ => {}
+                }
+            }
+            EventType::AudioLevelStats
+                if self.wants("peak")
+                    && payload.get("rate").and_then(|rate| rate.as_str()) == Some("stats") =>
+            {
+                return Some(message(flow_topic("peak"), false));
+            }
+            _ => {}
+        }
+
+        let name = event.event_type_str();
+        if !self.wants(name) {
+            return None;
+        }
+        Some(MqttMessage {
+            topic: format!("{}/events/{}", self.prefix, topic_segment(name)),
+            payload: serde_json::to_vec(event).ok()?,
+            retain: false,
+        })
+    }
+}
+
+/// Kompakter Zustand für `<prefix>/health`.
+pub fn health_snapshot(node: &AirliftNode) -> serde_json::Value {
+    let status = node.status();
+    let producers: Vec<serde_json::Value> = node
+        .producers()
+        .iter()
+        .zip(&status.producer_status)
+        .map(|(producer, status)| {
+            serde_json::json!({
+                "name": producer.name(),
+                "running": status.running,
+                "connected": status.connected,
+                "errors": status.errors,
+            })
+        })
+        .collect();
+    let flows: Vec<serde_json::Value> = node
+        .flows()
+        .iter()
+        .zip(&status.flow_status)
+        .map(|(flow, status)| {
+            let consumers: Vec<serde_json::Value> = flow
+                .consumer_names()
+                .into_iter()
+                .zip(&status.consumer_status)
+                .map(|(name, status)| {
+                    serde_json::json!({
+                        "name": name,
+                        "running": status.running,
+                        "connected": status.connected,
+                        "errors": status.errors,
+                    })
+                })
+                .collect();
+            serde_json::json!({
+                "name": flow.name,
+                "running": status.running,
+                "on_air": status.on_air,
+                "realtime_factor": status.load.realtime_factor,
+                "overloaded": status.load.overloaded,
+                "consumers": consumers,
+            })
+        })
+        .collect();
+    serde_json::json!({
+        "running": status.running,
+        "uptime_seconds": status.uptime_seconds,
+        "utc_ms": utc_ns_now() / 1_000_000,
+        "producers": producers,
+        "flows": flows,
+    })
+}
+
+fn write_remaining_length(out: &mut Vec<u8>, mut len: usize) {
+    loop {
+        let mut byte = (len % 128) as u8;
+        len /= 128;
+        if len > 0 {
+            byte |= 0x80;
+        }
+        out.push(byte);
+        if len == 0 {
+            break;
+        }
+    }
+}
+
+fn write_string(out: &mut Vec<u8>, value: &[u8]) {
+    out.extend_from_slice(&(value.len() as u16).to_be_bytes());
+    out.extend_from_slice(value);
+}
+
+fn packet(header: u8, body: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(body.len() + 5);
+    out.push(header);
+    write_remaining_length(&mut out, body.len());
+    out.extend_from_slice(body);
+    out
+}
+
+/// CONNECT mit Clean Session und `<prefix>/status` = "offline" als Last Will.
+pub fn connect_packet(options: &MqttOptions) -> Vec<u8> {
+    let mut flags = 0x02 | 0x04 | 0x20 | (options.qos << 3);
+    if options.username.is_some() {
+        flags |= 0x80;
+    }
+    if options.password.is_some() {
+        flags |= 0x40;
+    }
+    let mut body = Vec::new();
+    write_string(&mut body, b"MQTT");
+    body.push(4);
+    body.push(flags);
+    body.extend_from_slice(&(options.keepalive.as_secs() as u16).to_be_bytes());
+    write_string(&mut body, options.client_id.as_bytes());
+    write_string(&mut body, options.status_topic().as_bytes());
+    write_string(&mut body, b"offline");
+    if let Some(username) = &options.username {
+        write_string(&mut body, username.as_bytes());
+    }
+    if let Some(password) = &options.password {
+        write_string(&mut body, password.as_bytes());
+    }
+    packet(CONNECT, &body)
+}
+
+pub fn publish_packet(topic: &str, payload: &[u8], qos: u8, retain: bool, id: u16) -> Vec<u8> {
+    let mut body = Vec::with_capacity(topic.len() + payload.len() + 4);
+    write_string(&mut body, topic.as_bytes());
+    if qos > 0 {
+        body.extend_from_slice(&id.to_be_bytes());
+    }
+    body.extend_from_slice(payload);
+    packet(PUBLISH | (qos << 1) | retain as u8, &body)
+}
+
+/// Liest ein Paket: `(erstes Byte, Rest)`.
+pub fn read_packet(reader: &mut impl Read) -> Result<(u8, Vec<u8>)> {
+    let mut header = [0u8; 1];
+    reader.read_exact(&mut header)?;
+    let mut len = 0usize;
+    for shift in 0..4 {
+        let mut byte = [0u8; 1];
+        reader.read_exact(&mut byte)?;
+        len |= ((byte[0] & 0x7f) as usize) << (7 * shift);
+        if byte[0] & 0x80 == 0 {
+            let mut body = vec![0u8; len];
+            reader.read_exact(&mut body)?;
+            return Ok((header[0], body));
+        }
+    }
+    bail!("malformed remaining length")
+}
+
+/// Eine Verbindung zum Broker; Publizieren wartet die Bestätigung der
+/// jeweiligen QoS-Stufe ab, es ist also immer nur eine Nachricht unterwegs.
+pub struct MqttClient {
+    stream: TcpStream,
+    next_id: u16,
+    keepalive: Duration,
+    last_sent: Instant,
+}
+
+impl MqttClient {
+    pub fn connect(options: &MqttOptions) -> Result<Self> {
+        let address = (options.host.as_str(), options.port)
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| anyhow!("cannot resolve {}", options.host))?;
+        let mut stream = TcpStream::connect_timeout(&address, CONNECT_TIMEOUT)
+            .with_context(|| format!("connect to {}:{}", options.host, options.port))?;
+        stream.set_read_timeout(Some(ACK_TIMEOUT))?;
+        stream.set_write_timeout(Some(ACK_TIMEOUT))?;
+        let _ = stream.set_nodelay(true);
+        stream.write_all(&connect_packet(options))?;
+
+        let (header, body) = read_packet(&mut stream).context("waiting for CONNACK")?;
+        if header != CONNACK || body.len() != 2 {
+            bail!("expected CONNACK, got packet type 0x{:02x}", header);
+        }
+        match body[1] {
+            0 => {}
+            1 => bail!("broker rejected protocol version 3.1.1"),
+            2 => bail!("broker rejected client id '{}'", options.client_id),
+            3 => bail!("broker unavailable"),
+            4 => bail!("broker rejected username or password"),
+            5 => bail!("not authorized"),
+            code => bail!("broker refused connection (code {})", code),
+        }
+        Ok(Self {
+            stream,
+            next_id: 0,
+            keepalive: options.keepalive,
+            last_sent: Instant::now(),
+        })
+    }
+
+    fn send(&mut self, data: &[u8]) -> Result<()> {
+        self.stream.write_all(data)?;
+        self.last_sent = Instant::now();
+        Ok(())
+    }
+
+    /// Wartet auf ein Paket vom Typ `header` mit Paket-ID `id`.
+    fn expect(&mut self, header: u8, id: u16) -> Result<()> {
+        loop {
+            let (received, body) = read_packet(&mut self.stream).map_err(|e| {
+                match e.downcast_ref::<std::io::Error>().map(|e| e.kind()) {
+                    Some(ErrorKind::WouldBlock | ErrorKind::TimedOut) => {
+                        anyhow!(
+                            "no acknowledgement (0x{:02x}) within {:?}",
+                            header,
+                            ACK_TIMEOUT
+                        )
+                    }
+                    _ => e,
+                }
+            })?;
+            if received & 0xf0 == header & 0xf0 && body.get(..2) == Some(&id.to_be_bytes()[..]) {
+                return Ok(());
+            }
+            // Späte PINGRESP o. Ä. überspringen
+        }
+    }
+
+    pub fn publish(&mut self, topic: &str, payload: &[u8], qos: u8, retain: bool) -> Result<()> {
+        if qos == 0 {
+            return self.send(&publish_packet(topic, payload, 0, retain, 0));
+        }
+        self.next_id = self.next_id.checked_add(1).unwrap_or(1);
+        let id = self.next_id;
+        self.send(&publish_packet(topic, payload, qos, retain, id))?;
+        if qos == 1 {
+            return self.expect(PUBACK, id);
+        }
+        self.expect(PUBREC, id)?;
+        self.send(&packet(PUBREL, &id.to_be_bytes()))?;
+        self.expect(PUBCOMP, id)
+    }
+
+    /// PINGREQ, wenn seit der halben Keepalive-Zeit nichts gesendet wurde.
+    pub fn keepalive(&mut self) -> Result<()> {
+        if self.last_sent.elapsed() < self.keepalive / 2 {
+            return Ok(());
+        }
+        self.send(&[PINGREQ, 0])?;
+        loop {
+            let (header, _) = read_packet(&mut self.stream).context("waiting for PINGRESP")?;
+            if header == PINGRESP {
+                return Ok(());
+            }
+        }
+    }
+
+    pub fn disconnect(mut self) {
+        let _ = self.send(&[DISCONNECT, 0]);
+    }
+}
+
+struct MqttEventHandler {
+    tx: Sender<Event>,
+    filter: Vec<EventType>,
+    dropped: AtomicU64,
+}
+
+impl EventHandler for MqttEventHandler {
+    fn handle_event(&self, event: &Event) -> Result<()> {
+        match self.tx.try_send(event.clone()) {
+            Ok(()) | Err(TrySendError::Disconnected(_)) => Ok(()),
+            Err(TrySendError::Full(_)) => {
+                // Broker weg oder zu langsam: verwerfen statt den Bus zu blockieren
+                if self
+                    .dropped
+                    .fetch_add(1, Ordering::Relaxed)
+                    .is_multiple_of(100)
+                {
+                    log::warn!("MQTT: event queue full, dropping events");
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn name(&self) -> &str {
+        HANDLER_NAME
+    }
+
+    fn event_type_filter(&self) -> Option<Vec<EventType>> {
+        Some(self.filter.clone())
+    }
+}
+
+struct Session<'a> {
+    options: &'a MqttOptions,
+    node: &'a Arc<Mutex<AirliftNode>>,
+    events: &'a Receiver<Event>,
+    stop: &'a AtomicBool,
+    published: &'a AtomicU64,
+}
+
+impl Session<'_> {
+    fn run(&self, client: &mut MqttClient, router: &mut MqttRouter) -> Result<()> {
+        let options = self.options;
+        client.publish(&options.status_topic(), b"online", options.qos, true)?;
+        // Was während der Verbindungspause aufgelaufen ist, ist nicht mehr aktuell
+        while self.events.try_recv().is_ok() {}
+
+        let mut next_health = Instant::now();
+        while !self.stop.load(Ordering::Relaxed) {
+            if let Some(interval) = options.health_interval {
+                if Instant::now() >= next_health {
+                    let snapshot = health_snapshot(&lock_mutex(self.node, "mqtt.health"));
+                    client.publish(
+                        &options.health_topic(),
+                        snapshot.to_string().as_bytes(),
+                        options.qos,
+                        true,
+                    )?;
+                    self.published.fetch_add(1, Ordering::Relaxed);
+                    next_health = Instant::now() + interval;
+                }
+            }
+            match self.events.recv_timeout(STOP_POLL) {
+                Ok(event) => {
+                    if let Some(message) = router.route(&event) {
+                        client.publish(
+                            &message.topic,
+                            &message.payload,
+                            options.qos,
+                            message.retain,
+                        )?;
+                        self.published.fetch_add(1, Ordering::Relaxed);
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => client.keepalive()?,
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        }
+        // Sauberes DISCONNECT unterdrückt den Last Will
+        client.publish(&options.status_topic(), b"offline", options.qos, true)
+    }
+}
+
+/// Hintergrund-Dienst für `[mqtt]`: verbindet (und verbindet neu) im eigenen
+/// Thread, der Node startet also auch ohne erreichbaren Broker.
+pub struct MqttPublisher {
+    stop: Arc<AtomicBool>,
+    connected: Arc<AtomicBool>,
+    published: Arc<AtomicU64>,
+    event_bus: Arc<Mutex<EventBus>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl MqttPublisher {
+    pub fn start(options: MqttOptions, node: Arc<Mutex<AirliftNode>>) -> Result<Self> {
+        let event_bus = lock_mutex(&node, "mqtt.event_bus").event_bus();
+        let (event_tx, event_rx) = bounded::<Event>(EVENT_QUEUE);
+        let stop = Arc::new(AtomicBool::new(false));
+        let connected = Arc::new(AtomicBool::new(false));
+        let published = Arc::new(AtomicU64::new(0));
+
+        lock_mutex(&event_bus, "mqtt.register_handler").register_handler(Arc::new(
+            MqttEventHandler {
+                tx: event_tx,
+                filter: options.event_filter(),
+                dropped: AtomicU64::new(0),
+            },
+        ))?;
+
+        let thread_stop = stop.clone();
+        let thread_connected = connected.clone();
+        let thread_published = published.clone();
+        let thread = std::thread::Builder::new()
+            .name("mqtt".to_string())
+            .spawn(move || {
+                let session = Session {
+                    options: &options,
+                    node: &node,
+                    events: &event_rx,
+                    stop: &thread_stop,
+                    published: &thread_published,
+                };
+                let mut router = MqttRouter::new(&options);
+                let mut backoff = RECONNECT_MIN;
+                while !thread_stop.load(Ordering::Relaxed) {
+                    match MqttClient::connect(&options) {
+                        Ok(mut client) => {
+                            log::info!(
+                                "MQTT: connected to {}:{} as '{}'",
+                                options.host,
+                                options.port,
+                                options.client_id
+                            );
+                            backoff = RECONNECT_MIN;
+                            thread_connected.store(true, Ordering::SeqCst);
+                            let result = session.run(&mut client, &mut router);
+                            thread_connected.store(false, Ordering::SeqCst);
+                            match result {
+                                Ok(()) => {
+                                    client.disconnect();
+                                    continue;
+                                }
+                                Err(e) => log::warn!("MQTT: connection lost: {:#}", e),
+                            }
+                        }
+                        Err(e) => log::warn!(
+                            "MQTT: connect to {}:{} failed: {:#}",
+                            options.host,
+                            options.port,
+                            e
+                        ),
+                    }
+                    let retry_at = Instant::now() + backoff;
+                    while Instant::now() < retry_at && !thread_stop.load(Ordering::Relaxed) {
+                        std::thread::sleep(STOP_POLL);
+                    }
+                    backoff = (backoff * 2).min(RECONNECT_MAX);
+                }
+            })?;
+
+        Ok(Self {
+            stop,
+            connected,
+            published,
+            event_bus,
+            thread: Some(thread),
+        })
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::SeqCst)
+    }
+
+    /// Publizierte Events und Health-Snapshots
+    pub fn published(&self) -> u64 {
+        self.published.load(Ordering::Relaxed)
+    }
+
+    pub fn stop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        let _ =
+            lock_mutex(&self.event_bus, "mqtt.unregister_handler").unregister_handler(HANDLER_NAME);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for MqttPublisher {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
@@ -0,0 +1,235 @@
+// src/processors/agc.rs
+//
+// Langsame Pegelautomatik für unbeaufsichtigte Zuspielungen, deren Pegel
+// über den Tag wandert. Gemessen wird der RMS-Pegel über ein gleitendes
+// Fenster (`window`, Standard 3 s; Vollaussteuerungs-Sinus = 0 dB), die
+// Verstärkung läuft mit höchstens `rate` dB/s auf `target` zu und bleibt
+// zwischen `min_gain` und `max_gain`. Liegt ein Frame unter `freeze_below`,
+// wird weder gemessen noch nachgeregelt: Pausen und Stille ziehen die
+// Verstärkung nicht hoch. Innerhalb eines Frames wird die Verstärkung
+// linear gerampt, damit keine Stufen hörbar sind.
+use std::collections::HashMap;
+use std::time::Duration;
+
+use anyhow::{bail, Result};
+use serde_json::Value;
+
+use crate::config::units::db_to_linear;
+use crate::config::ConfigValues;
+use crate::core::logging::{ComponentLogger, LogContext};
+use crate::core::processor::{Processor, ProcessorStatus};
+use crate::core::ringbuffer::{AudioRingBuffer, PcmFrame};
+use crate::impl_connectable_processor;
+
+const DEFAULT_TARGET_DB: f32 = -18.0;
+const DEFAULT_MAX_GAIN_DB: f32 = 12.0;
+const DEFAULT_MIN_GAIN_DB: f32 = -12.0;
+const DEFAULT_RATE_DB_PER_S: f32 = 1.0;
+const DEFAULT_FREEZE_BELOW_DB: f32 = -45.0;
+const DEFAULT_WINDOW: Duration = Duration::from_secs(3);
+
+/// RMS-Pegel relativ zum Vollaussteuerungs-Sinus
+fn level_db(mean_square: f64) -> f32 {
+    if mean_square <= 0.0 {
+        return f32::NEG_INFINITY;
+    }
+    (10.0 * (2.0 * mean_square / (32768.0 * 32768.0)).log10()) as f32
+}
+
+pub struct Agc {
+    name: String,
+    target_db: f32,
+    max_gain_db: f32,
+    min_gain_db: f32,
+    rate_db_per_s: f32,
+    freeze_below_db: f32,
+    window: Duration,
+    enabled: bool,
+    /// Gleitender Mittelwert der Quadrate (vor der Verstärkung)
+    mean_square: Option<f64>,
+    gain_db: f32,
+    frozen: bool,
+}
+
+/// Gelesene `config`-Schlüssel (Strict-Modus, siehe `config::options`)
+pub const CONFIG_KEYS: &[&str] = &[
+    "enabled",
+    "target",
+    "min_gain",
+    "max_gain",
+    "rate",
+    "window",
+    "freeze_below",
+];
+
+impl Agc {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            target_db: DEFAULT_TARGET_DB,
+            max_gain_db: DEFAULT_MAX_GAIN_DB,
+            min_gain_db: DEFAULT_MIN_GAIN_DB,
+            rate_db_per_s: DEFAULT_RATE_DB_PER_S,
+            freeze_below_db: DEFAULT_FREEZE_BELOW_DB,
+            window: DEFAULT_WINDOW,
+            enabled: true,
+            mean_square: None,
+            gain_db: 0.0,
+            frozen: false,
+        }
+    }
+
+    /// Optional `target` (Standard -18 dB), `max_gain` (+12 dB), `min_gain`
+    /// (-12 dB), `rate` (dB/s, Standard 1), `window` ("3s"),
+    /// `freeze_below` (-45 dB) und `enabled`.
+    pub fn from_config(name: &str, config: &HashMap<String, Value>) -> Result<Self> {
+        let mut agc = Self::new(name);
+        agc.apply(config)?;
+        Ok(agc)
+    }
+
+    /// Aktuelle Verstärkung in dB
+    pub fn gain_db(&self) -> f32 {
+        self.gain_db
+    }
+
+    /// `true`, solange das Signal unter `freeze_below` liegt
+    pub fn is_frozen(&self) -> bool {
+        self.frozen
+    }
+
+    fn apply(&mut self, config: &HashMap<String, Value>) -> Result<()> {
+        let values = ConfigValues::new("processor", &self.name, config);
+        if let Some(target) = values.db("target")? {
+            self.target_db = values.check_range("target", target, -60.0, 0.0)?;
+        }
+        if let Some(max_gain) = values.db("max_gain")? {
+            self.max_gain_db = values.check_range("max_gain", max_gain, 0.0, 40.0)?;
+        }
+        if let Some(min_gain) = values.db("min_gain")? {
+            self.min_gain_db = values.check_range("min_gain", min_gain, -40.0, 0.0)?;
+        }
+        if let Some(rate) = values.f64("rate")? {
+            self.rate_db_per_s = values.check_range("rate", rate, 0.01, 20.0)? as f32;
+        }
+        if let Some(freeze_below) = values.db("freeze_below")? {
+            self.freeze_below_db = values.check_range("freeze_below", freeze_below, -90.0, 0.0)?;
+        }
+        if let Some(window) = values.duration("window")? {
+            if window < Duration::from_millis(100) {
+                bail!("processor '{}': config.window must be >= 100ms", self.name);
+            }
+            self.window = window;
+        }
+        if let Some(enabled) = config.get("enabled").and_then(|v| v.as_bool()) {
+            self.enabled = enabled;
+        }
+        self.gain_db = self.gain_db.clamp(self.min_gain_db, self.max_gain_db);
+        Ok(())
+    }
+
+    fn regulate(&mut self, frame: &mut PcmFrame) {
+        if !self.enabled || frame.samples.is_empty() || frame.sample_rate == 0 {
+            return;
+        }
+        let channels = frame.channels.max(1) as usize;
+        let frames = frame.samples.len() / channels;
+        let duration = frames as f64 / frame.sample_rate as f64;
+        let mean_square = frame
+            .samples
+            .iter()
+            .map(|sample| (*sample as f64) * (*sample as f64))
+            .sum::<f64>()
+            / frame.samples.len() as f64;
+
+        let start_gain = self.gain_db;
+        self.frozen = level_db(mean_square) < self.freeze_below_db;
+        if !self.frozen {
+            let alpha = 1.0 - (-duration / self.window.as_secs_f64()).exp();
+            let average = match self.mean_square {
+                Some(average) => average + (mean_square - average) * alpha,
+                None => mean_square,
+            };
+            self.mean_square = Some(average);
+            let wanted =
+                (self.target_db - level_db(average)).clamp(self.min_gain_db, self.max_gain_db);
+            let step = self.rate_db_per_s * duration as f32;
+            self.gain_db += (wanted - self.gain_db).clamp(-step, step);
+        }
+
+        let (from, to) = (db_to_linear(start_gain), db_to_linear(self.gain_db));
+        if from == 1.0 && to == 1.0 {
+            return;
+        }
+        for (n, samples) in frame.samples.chunks_exact_mut(channels).enumerate() {
+            let gain = from + (to - from) * (n + 1) as f32 / frames as f32;
+            for sample in samples {
+                *sample = (*sample as f32 * gain).round().clamp(-32768.0, 32767.0) as i16;
+            }
+        }
+    }
+}
+
+impl Processor for Agc {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn process(
+        &mut self,
+        input_buffer: &AudioRingBuffer,
+        output_buffer: &AudioRingBuffer,
+    ) -> Result<()> {
+        while let Some(mut frame) = input_buffer.pop() {
+            self.regulate(&mut frame);
+            output_buffer.push(frame);
+        }
+        Ok(())
+    }
+
+    fn supports_batch(&self) -> bool {
+        true
+    }
+
+    fn process_batch(&mut self, frames: &mut Vec<PcmFrame>) -> Result<()> {
+        for frame in frames.iter_mut() {
+            self.regulate(frame);
+        }
+        Ok(())
+    }
+
+    fn status(&self) -> ProcessorStatus {
+        ProcessorStatus {
+            running: self.enabled,
+            processing_rate_hz: 0.0,
+            latency_ms: 0.0,
+            errors: 0,
+            metrics: None,
+        }
+    }
+
+    /// Teil-Updates: `{"target": "-16dB"}`, `{"enabled": false}`
+    fn update_config(&mut self, config: Value) -> Result<()> {
+        let Value::Object(map) = config else {
+            bail!("agc config must be an object");
+        };
+        let map: HashMap<String, Value> = map.into_iter().collect();
+        self.apply(&map)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl ComponentLogger for Agc {
+    fn log_context(&self) -> LogContext {
+        LogContext::new("Agc", &self.name)
+    }
+}
+
+impl_connectable_processor!(Agc);
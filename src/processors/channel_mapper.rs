@@ -0,0 +1,270 @@
+// src/processors/channel_mapper.rs
+//
+// Kanaloperationen für Geräte mit falschem Layout: Downmix auf Mono mit
+// wählbarem Pan-Law, Mono → N Kanäle duplizieren, L/R tauschen oder eine
+// freie Matrix (eine Zeile je Ausgangskanal, ein Faktor je Eingangskanal).
+// Alle Modi laufen auf dieselbe Matrixmultiplikation hinaus; Frames, für die
+// der Modus nicht passt (Downmix eines Mono-Frames), laufen unverändert
+// durch.
+use std::collections::HashMap;
+
+use anyhow::{bail, Result};
+use serde_json::Value;
+
+use crate::config::ConfigValues;
+use crate::core::logging::{ComponentLogger, LogContext};
+use crate::core::processor::{Processor, ProcessorStatus};
+use crate::core::ringbuffer::{AudioRingBuffer, PcmFrame};
+use crate::impl_connectable_processor;
+
+const MAX_CHANNELS: usize = 32;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChannelMapping {
+    /// Alle Kanäle auf Mono, jeder Kanal mit `gain` (linear)
+    Downmix { gain: f32 },
+    /// Mono auf `channels` identische Kanäle
+    Duplicate { channels: u8 },
+    /// Kanal 1 und 2 tauschen
+    Swap,
+    /// Zeile je Ausgangskanal, Spalte je Eingangskanal
+    Matrix(Vec<Vec<f32>>),
+}
+
+impl ChannelMapping {
+    /// Matrix für einen Frame mit `channels` Kanälen; `None` heißt
+    /// unverändert durchreichen.
+    fn matrix(&self, channels: usize) -> Option<Vec<Vec<f32>>> {
+        match self {
+            Self::Downmix { gain } if channels > 1 => Some(vec![vec![*gain; channels]]),
+            Self::Duplicate { channels: outputs } if channels == 1 => {
+                Some(vec![vec![1.0]; *outputs as usize])
+            }
+            Self::Swap if channels == 2 => Some(vec![vec![0.0, 1.0], vec![1.0, 0.0]]),
+            Self::Matrix(rows) => Some(rows.clone()),
+            _ => None,
+        }
+    }
+}
+
+pub struct ChannelMapper {
+    name: String,
+    mapping: ChannelMapping,
+    enabled: bool,
+    /// Matrix für die zuletzt gesehene Kanalzahl
+    cached: Option<(u8, Option<Vec<Vec<f32>>>)>,
+    /// Frames, deren Kanalzahl nicht zur Matrix passt
+    errors: u64,
+}
+
+/// Gelesene `config`-Schlüssel (Strict-Modus, siehe `config::options`)
+pub const CONFIG_KEYS: &[&str] = &["enabled", "mode", "matrix", "channels", "law"];
+
+impl ChannelMapper {
+    pub fn new(name: &str, mapping: ChannelMapping) -> Self {
+        Self {
+            name: name.to_string(),
+            mapping,
+            enabled: true,
+            cached: None,
+            errors: 0,
+        }
+    }
+
+    /// `mode` (`downmix` | `duplicate` | `swap` | `matrix`), dazu je nach
+    /// Modus `law` (Standard "-6dB"), `channels` (Standard 2) bzw. `matrix`;
+    /// optional `enabled`.
+    pub fn from_config(name: &str, config: &HashMap<String, Value>) -> Result<Self> {
+        let mut mapper = Self::new(name, ChannelMapping::Downmix { gain: 0.5 });
+        if !config.contains_key("mode") {
+            bail!("processor '{}': config.mode is required", name);
+        }
+        mapper.apply(config)?;
+        Ok(mapper)
+    }
+
+    pub fn mapping(&self) -> &ChannelMapping {
+        &self.mapping
+    }
+
+    fn apply(&mut self, config: &HashMap<String, Value>) -> Result<()> {
+        if let Some(mode) = config.get("mode") {
+            self.mapping = match mode.as_str() {
+                Some("downmix") => ChannelMapping::Downmix {
+                    gain: self.parse_law(config)?,
+                },
+                Some("duplicate") => {
+                    let values = ConfigValues::new("processor", &self.name, config);
+                    let channels = values
+                        .f64("channels")?
+                        .map(|channels| {
+                            values.check_range("channels", channels, 2.0, MAX_CHANNELS as f64)
+                        })
+                        .transpose()?
+                        .unwrap_or(2.0);
+                    ChannelMapping::Duplicate {
+                        channels: channels as u8,
+                    }
+                }
+                Some("swap") => ChannelMapping::Swap,
+                Some("matrix") => ChannelMapping::Matrix(self.parse_matrix(config.get("matrix"))?),
+                _ => bail!(
+                    "processor '{}': config.mode must be downmix, duplicate, swap or matrix",
+                    self.name
+                ),
+            };
+            self.cached = None;
+        }
+        if let Some(enabled) = config.get("enabled").and_then(|v| v.as_bool()) {
+            self.enabled = enabled;
+        }
+        Ok(())
+    }
+
+    /// Pan-Law als Pegel je Kanal: "-3dB" (leistungsgleich), "-6dB"
+    /// (Mittelwert, kann nicht übersteuern) oder "0dB" (Summe).
+    fn parse_law(&self, config: &HashMap<String, Value>) -> Result<f32> {
+        let law = ConfigValues::new("processor", &self.name, config).db("law")?;
+        match law.map(|db| db.round() as i32) {
+            None | Some(-6) => Ok(0.5),
+            Some(-3) => Ok(std::f32::consts::FRAC_1_SQRT_2),
+            Some(0) => Ok(1.0),
+            Some(_) => bail!(
+                "processor '{}': config.law must be 0dB, -3dB or -6dB",
+                self.name
+            ),
+        }
+    }
+
+    fn parse_matrix(&self, matrix: Option<&Value>) -> Result<Vec<Vec<f32>>> {
+        let invalid = || {
+            anyhow::anyhow!(
+                "processor '{}': config.matrix must be a list of rows with one gain per input channel",
+                self.name
+            )
+        };
+        let rows = matrix.and_then(|m| m.as_array()).ok_or_else(invalid)?;
+        let rows = rows
+            .iter()
+            .map(|row| {
+                row.as_array()
+                    .ok_or_else(invalid)?
+                    .iter()
+                    .map(|gain| gain.as_f64().map(|g| g as f32).ok_or_else(invalid))
+                    .collect::<Result<Vec<f32>>>()
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let inputs = rows.first().map(Vec::len).unwrap_or(0);
+        if rows.is_empty()
+            || rows.len() > MAX_CHANNELS
+            || inputs == 0
+            || inputs > MAX_CHANNELS
+            || rows.iter().any(|row| row.len() != inputs)
+        {
+            return Err(invalid());
+        }
+        Ok(rows)
+    }
+
+    fn map(&mut self, frame: &mut PcmFrame) {
+        if !self.enabled {
+            return;
+        }
+        let channels = frame.channels.max(1);
+        if !matches!(&self.cached, Some((cached, _)) if *cached == channels) {
+            self.cached = Some((channels, self.mapping.matrix(channels as usize)));
+        }
+        let Some((_, Some(matrix))) = &self.cached else {
+            return;
+        };
+        if matrix[0].len() != channels as usize {
+            if self.errors == 0 {
+                self.warn(&format!(
+                    "Matrix expects {} input channels, frame has {}",
+                    matrix[0].len(),
+                    channels
+                ));
+            }
+            self.errors += 1;
+            return;
+        }
+
+        let mut output = Vec::with_capacity(frame.samples.len() / channels as usize * matrix.len());
+        for input in frame.samples.chunks_exact(channels as usize) {
+            for row in matrix {
+                let value: f32 = row
+                    .iter()
+                    .zip(input)
+                    .map(|(gain, sample)| gain * *sample as f32)
+                    .sum();
+                output.push(value.round().clamp(-32768.0, 32767.0) as i16);
+            }
+        }
+        frame.samples = output;
+        frame.channels = matrix.len() as u8;
+    }
+}
+
+impl Processor for ChannelMapper {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn process(
+        &mut self,
+        input_buffer: &AudioRingBuffer,
+        output_buffer: &AudioRingBuffer,
+    ) -> Result<()> {
+        while let Some(mut frame) = input_buffer.pop() {
+            self.map(&mut frame);
+            output_buffer.push(frame);
+        }
+        Ok(())
+    }
+
+    fn supports_batch(&self) -> bool {
+        true
+    }
+
+    fn process_batch(&mut self, frames: &mut Vec<PcmFrame>) -> Result<()> {
+        for frame in frames.iter_mut() {
+            self.map(frame);
+        }
+        Ok(())
+    }
+
+    fn status(&self) -> ProcessorStatus {
+        ProcessorStatus {
+            running: self.enabled,
+            processing_rate_hz: 0.0,
+            latency_ms: 0.0,
+            errors: self.errors,
+            metrics: None,
+        }
+    }
+
+    /// Teil-Updates: `{"mode": "swap"}`, `{"enabled": false}`
+    fn update_config(&mut self, config: Value) -> Result<()> {
+        let Value::Object(map) = config else {
+            bail!("channel_mapper config must be an object");
+        };
+        let map: HashMap<String, Value> = map.into_iter().collect();
+        self.apply(&map)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl ComponentLogger for ChannelMapper {
+    fn log_context(&self) -> LogContext {
+        LogContext::new("ChannelMapper", &self.name)
+    }
+}
+
+impl_connectable_processor!(ChannelMapper);
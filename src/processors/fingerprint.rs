@@ -0,0 +1,354 @@
+// src/processors/fingerprint.rs
+//
+// Fingerprints für die Sendeverifikation: der Processor berechnet laufend
+// Hashes (`audio::fingerprint`, ein Hash je 100 ms) und gibt alle `interval`
+// das letzte `window` als JSON aus, per POST an `url` und/oder als Zeile in
+// `file` (JSON Lines). Abgleich mit der Referenz geschieht beim Empfänger.
+// Ausgeliefert wird in einem eigenen Thread, der Audio-Thread wartet nie auf
+// Netz oder Platte. Lücken im Audio (Sprung in `utc_ns`) beginnen ein neues
+// Fenster, das Signal läuft unverändert durch.
+use std::collections::{HashMap, VecDeque};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use crossbeam_channel::Sender;
+use serde_json::Value;
+
+use crate::api::ws::base64_encode;
+use crate::audio::fingerprint::{Fingerprinter, FINGERPRINT_ALGORITHM, HOP_MS};
+use crate::config::ConfigValues;
+use crate::core::event_bus::EventEmitter;
+use crate::core::http_client::{self, HttpRequest};
+use crate::core::logging::{ComponentLogger, LogContext};
+use crate::core::processor::{Processor, ProcessorStatus};
+use crate::core::ringbuffer::{AudioRingBuffer, PcmFrame};
+use crate::impl_connectable_processor;
+
+const DEFAULT_WINDOW: Duration = Duration::from_secs(10);
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(5);
+/// Größere Sprünge in `utc_ns` (vor oder zurück) gelten als Lücke
+const MAX_GAP_NS: u64 = 500_000_000;
+
+/// Ein Fenster samt Ziel, für den Auslieferungs-Thread
+struct Delivery {
+    url: Option<String>,
+    file: Option<PathBuf>,
+    payload: Value,
+}
+
+fn deliver(name: &str, delivery: Delivery, errors: &AtomicU64) {
+    if let Some(path) = &delivery.file {
+        let result = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| file.write_all(format!("{}\n", delivery.payload).as_bytes()));
+        if let Err(e) = result {
+            errors.fetch_add(1, Ordering::Relaxed);
+            log::warn!("[{}] fingerprint file {:?} failed: {}", name, path, e);
+        }
+    }
+    if let Some(url) = &delivery.url {
+        let request = HttpRequest::post(
+            url,
+            "application/json",
+            delivery.payload.to_string().into_bytes(),
+        )
+        .timeout(DELIVERY_TIMEOUT);
+        let result = http_client::send(&request).and_then(|response| response.error_for_status());
+        if let Err(e) = result {
+            errors.fetch_add(1, Ordering::Relaxed);
+            log::warn!("[{}] fingerprint POST failed: {:#}", name, e);
+        }
+    }
+}
+
+pub struct FingerprintProcessor {
+    name: String,
+    window: Duration,
+    interval: Duration,
+    url: Option<String>,
+    file: Option<PathBuf>,
+    enabled: bool,
+    fingerprinter: Fingerprinter,
+    /// `(utc_ns am Blockende, Hash)`, höchstens `window` lang
+    hashes: VecDeque<(u64, u32)>,
+    /// Blöcke seit dem letzten ausgegebenen Fenster
+    since_emit: u64,
+    /// Erwarteter Beginn des nächsten Frames
+    next_utc_ns: Option<u64>,
+    windows: u64,
+    last_end_utc_ns: Option<u64>,
+    /// Fehlgeschlagene Auslieferungen (aus dem Auslieferungs-Thread gezählt)
+    errors: Arc<AtomicU64>,
+    sender: Option<Sender<Delivery>>,
+    emitter: Option<EventEmitter>,
+}
+
+/// Gelesene `config`-Schlüssel (Strict-Modus, siehe `config::options`)
+pub const CONFIG_KEYS: &[&str] = &["enabled", "file", "url", "flow", "interval", "window"];
+
+impl FingerprintProcessor {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            window: DEFAULT_WINDOW,
+            interval: DEFAULT_WINDOW,
+            url: None,
+            file: None,
+            enabled: true,
+            fingerprinter: Fingerprinter::new(),
+            hashes: VecDeque::new(),
+            since_emit: 0,
+            next_utc_ns: None,
+            windows: 0,
+            last_end_utc_ns: None,
+            errors: Arc::new(AtomicU64::new(0)),
+            sender: None,
+            emitter: None,
+        }
+    }
+
+    /// Erwartet `url` (http://…) und/oder `file` (JSON Lines); optional
+    /// `window` ("10s"), `interval` (Standard = `window`) und `enabled`.
+    pub fn from_config(name: &str, config: &HashMap<String, Value>) -> Result<Self> {
+        let mut processor = Self::new(name);
+        processor.apply(config)?;
+        if processor.url.is_none() && processor.file.is_none() {
+            bail!(
+                "processor '{}': config.url or config.file is required",
+                name
+            );
+        }
+        Ok(processor)
+    }
+
+    /// Ausgegebene Fenster
+    pub fn windows(&self) -> u64 {
+        self.windows
+    }
+
+    fn apply(&mut self, config: &HashMap<String, Value>) -> Result<()> {
+        let values = ConfigValues::new("processor", &self.name, config);
+        let window = values.duration("window")?;
+        if let Some(window) = window {
+            let secs = values.check_range("window", window.as_secs_f64(), 1.0, 300.0)?;
+            self.window = Duration::from_secs_f64(secs);
+        }
+        match values.duration("interval")? {
+            Some(interval) => {
+                if interval < Duration::from_millis(HOP_MS) || interval > self.window {
+                    bail!(
+                        "processor '{}': config.interval must be between {}ms and the window",
+                        self.name,
+                        HOP_MS
+                    );
+                }
+                self.interval = interval;
+            }
+            // Ohne eigenes `interval` lückenlos aneinander
+            None if window.is_some() => self.interval = self.window,
+            None => {}
+        }
+        if let Some(url) = config.get("url") {
+            self.url = match url {
+                Value::Null => None,
+                Value::String(url) if url.is_empty() => None,
+                Value::String(url) => {
+                    http_client::parse_http_url(url).with_context(|| {
+                        format!("processor '{}': config.url invalid", self.name)
+                    })?;
+                    Some(url.clone())
+                }
+                _ => bail!("processor '{}': config.url must be a URL", self.name),
+            };
+        }
+        if let Some(file) = config.get("file") {
+            self.file = match file {
+                Value::Null => None,
+                Value::String(file) if file.is_empty() => None,
+                Value::String(file) => Some(PathBuf::from(file)),
+                _ => bail!("processor '{}': config.file must be a path", self.name),
+            };
+        }
+        if let Some(enabled) = config.get("enabled").and_then(|v| v.as_bool()) {
+            self.enabled = enabled;
+            if !enabled {
+                self.restart();
+            }
+        }
+        Ok(())
+    }
+
+    fn restart(&mut self) {
+        self.fingerprinter.reset();
+        self.hashes.clear();
+        self.since_emit = 0;
+        self.next_utc_ns = None;
+    }
+
+    fn analyze(&mut self, frame: &PcmFrame) {
+        if !self.enabled || frame.sample_rate == 0 {
+            return;
+        }
+        if let Some(expected) = self.next_utc_ns {
+            if frame.utc_ns.abs_diff(expected) > MAX_GAP_NS {
+                self.debug(&format!(
+                    "Gap in audio ({} ns), starting a new window",
+                    frame.utc_ns as i128 - expected as i128
+                ));
+                self.restart();
+            }
+        }
+        let frames = frame.samples.len() / frame.channels.max(1) as usize;
+        self.next_utc_ns =
+            Some(frame.utc_ns + frames as u64 * 1_000_000_000 / frame.sample_rate as u64);
+
+        let window_hops = (self.window.as_millis() as u64 / HOP_MS).max(1) as usize;
+        let interval_hops = (self.interval.as_millis() as u64 / HOP_MS).max(1);
+        for hash in self.fingerprinter.push(frame) {
+            self.hashes.push_back(hash);
+            while self.hashes.len() > window_hops {
+                self.hashes.pop_front();
+            }
+            self.since_emit += 1;
+            if self.hashes.len() == window_hops && self.since_emit >= interval_hops {
+                self.since_emit = 0;
+                self.emit_window();
+            }
+        }
+    }
+
+    fn emit_window(&mut self) {
+        let (Some(first), Some(last)) = (self.hashes.front(), self.hashes.back()) else {
+            return;
+        };
+        let start_utc_ns = first.0.saturating_sub(HOP_MS * 1_000_000);
+        let end_utc_ns = last.0;
+        let bytes: Vec<u8> = self
+            .hashes
+            .iter()
+            .flat_map(|(_, hash)| hash.to_le_bytes())
+            .collect();
+        let mut payload = serde_json::json!({
+            "processor": self.name,
+            "algorithm": FINGERPRINT_ALGORITHM,
+            "start_utc_ns": start_utc_ns,
+            "end_utc_ns": end_utc_ns,
+            "hop_ms": HOP_MS,
+            "hashes": self.hashes.len(),
+            "fingerprint": base64_encode(&bytes),
+        });
+        if let Some(flow) = self
+            .emitter
+            .as_ref()
+            .and_then(|emitter| emitter.context())
+            .and_then(|context| context.get("flow"))
+        {
+            payload["flow"] = flow.clone();
+        }
+        self.windows += 1;
+        self.last_end_utc_ns = Some(end_utc_ns);
+
+        let sender = self.sender.get_or_insert_with(|| {
+            let (sender, receiver) = crossbeam_channel::unbounded::<Delivery>();
+            let name = self.name.clone();
+            let errors = self.errors.clone();
+            std::thread::spawn(move || {
+                for delivery in receiver {
+                    deliver(&name, delivery, &errors);
+                }
+            });
+            sender
+        });
+        let delivery = Delivery {
+            url: self.url.clone(),
+            file: self.file.clone(),
+            payload,
+        };
+        if sender.send(delivery).is_err() {
+            self.errors.fetch_add(1, Ordering::Relaxed);
+            self.sender = None;
+        }
+    }
+}
+
+impl Processor for FingerprintProcessor {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn process(
+        &mut self,
+        input_buffer: &AudioRingBuffer,
+        output_buffer: &AudioRingBuffer,
+    ) -> Result<()> {
+        while let Some(frame) = input_buffer.pop() {
+            self.analyze(&frame);
+            output_buffer.push(frame);
+        }
+        Ok(())
+    }
+
+    fn supports_batch(&self) -> bool {
+        true
+    }
+
+    fn process_batch(&mut self, frames: &mut Vec<PcmFrame>) -> Result<()> {
+        for frame in frames.iter() {
+            self.analyze(frame);
+        }
+        Ok(())
+    }
+
+    fn status(&self) -> ProcessorStatus {
+        ProcessorStatus {
+            running: self.enabled,
+            processing_rate_hz: 0.0,
+            latency_ms: 0.0,
+            errors: self.errors.load(Ordering::Relaxed),
+            metrics: Some(serde_json::json!({
+                "windows": self.windows,
+                "last_end_utc_ns": self.last_end_utc_ns,
+            })),
+        }
+    }
+
+    /// Teil-Updates: `{"url": "http://…"}`, `{"file": null}`, `{"enabled": false}`
+    fn update_config(&mut self, config: Value) -> Result<()> {
+        let Value::Object(map) = config else {
+            bail!("fingerprint config must be an object");
+        };
+        let map: HashMap<String, Value> = map.into_iter().collect();
+        self.apply(&map)
+    }
+
+    fn attach_event_emitter(&mut self, emitter: EventEmitter) {
+        self.emitter = Some(emitter);
+    }
+
+    fn event_emitter(&self) -> Option<&EventEmitter> {
+        self.emitter.as_ref()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl ComponentLogger for FingerprintProcessor {
+    fn log_context(&self) -> LogContext {
+        LogContext::new("Fingerprint", &self.name)
+    }
+}
+
+impl_connectable_processor!(FingerprintProcessor);
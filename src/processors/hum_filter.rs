@@ -0,0 +1,234 @@
+// src/processors/hum_filter.rs
+//
+// Aufräumen schlecht geerdeter Analog-Zuspielungen: DC-Blocker (einpoliger
+// Hochpass, Standard 5 Hz) und optional schmale Notch-Filter auf der
+// Netzfrequenz (`mains` = 50 oder 60 Hz) samt Oberwellen. Filterzustand je
+// Kanal; ein Wechsel von Samplerate oder Kanalzahl setzt ihn zurück.
+use std::collections::HashMap;
+
+use anyhow::{bail, Result};
+use serde_json::Value;
+
+use crate::audio::loudness::Biquad;
+use crate::config::ConfigValues;
+use crate::core::logging::{ComponentLogger, LogContext};
+use crate::core::processor::{Processor, ProcessorStatus};
+use crate::core::ringbuffer::{AudioRingBuffer, PcmFrame};
+use crate::impl_connectable_processor;
+
+const DEFAULT_DC_CUTOFF_HZ: f64 = 5.0;
+const DEFAULT_HARMONICS: usize = 4;
+const DEFAULT_Q: f64 = 30.0;
+
+/// Notch bei `frequency` mit Güte `q` (RBJ Audio EQ Cookbook).
+fn notch(frequency: f64, q: f64, sample_rate: u32) -> Biquad {
+    let w0 = 2.0 * std::f64::consts::PI * frequency / sample_rate as f64;
+    let alpha = w0.sin() / (2.0 * q);
+    let a0 = 1.0 + alpha;
+    let cos = w0.cos();
+    Biquad {
+        b: [1.0 / a0, -2.0 * cos / a0, 1.0 / a0],
+        a: [-2.0 * cos / a0, (1.0 - alpha) / a0],
+        z: [0.0; 2],
+    }
+}
+
+#[derive(Clone)]
+struct ChannelState {
+    /// DC-Blocker: letzter Eingang und Ausgang
+    x1: f64,
+    y1: f64,
+    notches: Vec<Biquad>,
+}
+
+pub struct HumFilter {
+    name: String,
+    dc_block: bool,
+    dc_cutoff_hz: f64,
+    /// Netzfrequenz in Hz, `None` ohne Notch
+    mains: Option<f64>,
+    harmonics: usize,
+    q: f64,
+    enabled: bool,
+    /// Samplerate und Kanalzahl, für die `channels` aufgebaut ist
+    shape: Option<(u32, u8)>,
+    /// Rückkopplung des DC-Blockers für `shape`
+    dc_pole: f64,
+    channels: Vec<ChannelState>,
+}
+
+/// Gelesene `config`-Schlüssel (Strict-Modus, siehe `config::options`)
+pub const CONFIG_KEYS: &[&str] = &["enabled", "mains", "harmonics", "q", "dc_block", "dc_cutoff"];
+
+impl HumFilter {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            dc_block: true,
+            dc_cutoff_hz: DEFAULT_DC_CUTOFF_HZ,
+            mains: None,
+            harmonics: DEFAULT_HARMONICS,
+            q: DEFAULT_Q,
+            enabled: true,
+            shape: None,
+            dc_pole: 0.0,
+            channels: Vec::new(),
+        }
+    }
+
+    /// Optional `dc_block` (Standard an), `dc_cutoff` (Hz, Standard 5),
+    /// `mains` (50 | 60, Standard aus), `harmonics` (Standard 4), `q`
+    /// (Standard 30) und `enabled`.
+    pub fn from_config(name: &str, config: &HashMap<String, Value>) -> Result<Self> {
+        let mut filter = Self::new(name);
+        filter.apply(config)?;
+        Ok(filter)
+    }
+
+    fn apply(&mut self, config: &HashMap<String, Value>) -> Result<()> {
+        let values = ConfigValues::new("processor", &self.name, config);
+        if let Some(dc_block) = config.get("dc_block").and_then(|v| v.as_bool()) {
+            self.dc_block = dc_block;
+        }
+        if let Some(cutoff) = values.f64("dc_cutoff")? {
+            self.dc_cutoff_hz = values.check_range("dc_cutoff", cutoff, 1.0, 40.0)?;
+        }
+        match config.get("mains") {
+            None => {}
+            Some(Value::Null) | Some(Value::Bool(false)) => self.mains = None,
+            Some(value) => {
+                self.mains = match value.as_u64() {
+                    Some(0) => None,
+                    Some(hz @ (50 | 60)) => Some(hz as f64),
+                    _ => bail!("processor '{}': config.mains must be 50 or 60", self.name),
+                };
+            }
+        }
+        if let Some(harmonics) = values.f64("harmonics")? {
+            self.harmonics = values.check_range("harmonics", harmonics, 1.0, 10.0)? as usize;
+        }
+        if let Some(q) = values.f64("q")? {
+            self.q = values.check_range("q", q, 1.0, 100.0)?;
+        }
+        if let Some(enabled) = config.get("enabled").and_then(|v| v.as_bool()) {
+            self.enabled = enabled;
+        }
+        // Neu aufbauen beim nächsten Frame
+        self.shape = None;
+        Ok(())
+    }
+
+    fn prepare(&mut self, sample_rate: u32, channels: u8) {
+        if self.shape == Some((sample_rate, channels)) {
+            return;
+        }
+        self.shape = Some((sample_rate, channels));
+        self.dc_pole = (-2.0 * std::f64::consts::PI * self.dc_cutoff_hz / sample_rate as f64).exp();
+        // Oberwellen oberhalb von ~0,45 × Samplerate entfallen
+        let notches: Vec<Biquad> = self
+            .mains
+            .map(|mains| {
+                (1..=self.harmonics)
+                    .map(|k| mains * k as f64)
+                    .filter(|hz| *hz < sample_rate as f64 * 0.45)
+                    .map(|hz| notch(hz, self.q, sample_rate))
+                    .collect()
+            })
+            .unwrap_or_default();
+        self.channels = vec![
+            ChannelState {
+                x1: 0.0,
+                y1: 0.0,
+                notches,
+            };
+            channels as usize
+        ];
+    }
+
+    fn filter(&mut self, frame: &mut PcmFrame) {
+        if !self.enabled || frame.channels == 0 || frame.sample_rate == 0 {
+            return;
+        }
+        self.prepare(frame.sample_rate, frame.channels);
+        let channels = frame.channels as usize;
+        for samples in frame.samples.chunks_exact_mut(channels) {
+            for (sample, state) in samples.iter_mut().zip(&mut self.channels) {
+                let mut value = *sample as f64;
+                if self.dc_block {
+                    let y = value - state.x1 + self.dc_pole * state.y1;
+                    state.x1 = value;
+                    state.y1 = y;
+                    value = y;
+                }
+                for notch in &mut state.notches {
+                    value = notch.process(value);
+                }
+                *sample = value.round().clamp(-32768.0, 32767.0) as i16;
+            }
+        }
+    }
+}
+
+impl Processor for HumFilter {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn process(
+        &mut self,
+        input_buffer: &AudioRingBuffer,
+        output_buffer: &AudioRingBuffer,
+    ) -> Result<()> {
+        while let Some(mut frame) = input_buffer.pop() {
+            self.filter(&mut frame);
+            output_buffer.push(frame);
+        }
+        Ok(())
+    }
+
+    fn supports_batch(&self) -> bool {
+        true
+    }
+
+    fn process_batch(&mut self, frames: &mut Vec<PcmFrame>) -> Result<()> {
+        for frame in frames.iter_mut() {
+            self.filter(frame);
+        }
+        Ok(())
+    }
+
+    fn status(&self) -> ProcessorStatus {
+        ProcessorStatus {
+            running: self.enabled,
+            processing_rate_hz: 0.0,
+            latency_ms: 0.0,
+            errors: 0,
+            metrics: None,
+        }
+    }
+
+    /// Teil-Updates: `{"mains": 60}`, `{"dc_block": false}`
+    fn update_config(&mut self, config: Value) -> Result<()> {
+        let Value::Object(map) = config else {
+            bail!("hum_filter config must be an object");
+        };
+        let map: HashMap<String, Value> = map.into_iter().collect();
+        self.apply(&map)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl ComponentLogger for HumFilter {
+    fn log_context(&self) -> LogContext {
+        LogContext::new("HumFilter", &self.name)
+    }
+}
+
+impl_connectable_processor!(HumFilter);
@@ -0,0 +1,353 @@
+// src/processors/ident.rs
+//
+// Ident/Beacon-Injektion: spielt eine kurze WAV-Datei (Station-Ident,
+// Watermark-Piep) im festen Intervall in den Flow ein und duckt dabei das
+// Programm. Die Intervalle sind an der Uhr ausgerichtet (z. B. alle 15 min
+// zur vollen Viertelstunde), ausgelöst wird anhand von `PcmFrame::utc_ns`.
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use serde_json::Value;
+
+use crate::config::units::db_to_linear;
+use crate::config::ConfigValues;
+use crate::core::event_bus::EventEmitter;
+use crate::core::events::EventPriority;
+use crate::core::logging::{ComponentLogger, LogContext};
+use crate::core::processor::{Processor, ProcessorStatus};
+use crate::core::ringbuffer::{AudioRingBuffer, PcmFrame};
+use crate::impl_connectable_processor;
+
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(15 * 60);
+const DEFAULT_LEVEL_DB: f32 = -6.0;
+const DEFAULT_DUCK_DB: f32 = -12.0;
+const DEFAULT_RAMP: Duration = Duration::from_millis(200);
+
+/// Eingelesene Ident-Datei (interleaved i16).
+pub struct IdentClip {
+    pub samples: Vec<i16>,
+    pub sample_rate: u32,
+    pub channels: u16,
+}
+
+impl IdentClip {
+    pub fn load(path: &Path) -> Result<Self> {
+        let mut reader = hound::WavReader::open(path)
+            .with_context(|| format!("failed to open ident file {:?}", path))?;
+        let spec = reader.spec();
+        if spec.sample_format != hound::SampleFormat::Int || spec.bits_per_sample != 16 {
+            bail!("ident file {:?} must be 16-bit PCM WAV", path);
+        }
+        let samples = reader
+            .samples::<i16>()
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .with_context(|| format!("failed to read ident file {:?}", path))?;
+        Ok(Self {
+            samples,
+            sample_rate: spec.sample_rate,
+            channels: spec.channels,
+        })
+    }
+
+    pub(crate) fn frames(&self) -> usize {
+        self.samples.len() / self.channels.max(1) as usize
+    }
+
+    /// Sample für Programmkanal `channel`; Mono wird auf alle Kanäle verteilt.
+    pub(crate) fn sample(&self, frame: usize, channel: usize) -> f32 {
+        let channels = self.channels.max(1) as usize;
+        self.samples[frame * channels + channel % channels] as f32
+    }
+}
+
+pub struct IdentInjector {
+    name: String,
+    clip: Option<IdentClip>,
+    interval: Duration,
+    level: f32,
+    duck: f32,
+    ramp: Duration,
+    enabled: bool,
+    next_trigger_ns: Option<u64>,
+    /// Position (in Frames der Datei) während ein Ident läuft
+    position: Option<usize>,
+    duck_gain: f32,
+    plays: u64,
+    errors: u64,
+    rate_warned: bool,
+    emitter: Option<EventEmitter>,
+}
+
+/// Gelesene `config`-Schlüssel (Strict-Modus, siehe `config::options`)
+pub const CONFIG_KEYS: &[&str] = &[
+    "enabled",
+    "file",
+    "trigger",
+    "interval",
+    "level_db",
+    "duck_db",
+    "ramp",
+];
+
+impl IdentInjector {
+    pub fn new(name: &str, clip: Option<IdentClip>) -> Self {
+        Self {
+            name: name.to_string(),
+            clip,
+            interval: DEFAULT_INTERVAL,
+            level: db_to_linear(DEFAULT_LEVEL_DB),
+            duck: db_to_linear(DEFAULT_DUCK_DB),
+            ramp: DEFAULT_RAMP,
+            enabled: true,
+            next_trigger_ns: None,
+            position: None,
+            duck_gain: 1.0,
+            plays: 0,
+            errors: 0,
+            rate_warned: false,
+            emitter: None,
+        }
+    }
+
+    /// Erwartet `file`; optional `interval` ("15m"), `level_db`, `duck_db`,
+    /// `ramp` ("200ms") und `enabled`.
+    pub fn from_config(name: &str, config: &HashMap<String, Value>) -> Result<Self> {
+        let file = config
+            .get("file")
+            .and_then(|v| v.as_str())
+            .with_context(|| format!("processor '{}': config.file is required", name))?;
+        let clip = IdentClip::load(Path::new(file))?;
+
+        let mut injector = Self::new(name, Some(clip));
+        injector.apply(config)?;
+        Ok(injector)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.position.is_some()
+    }
+
+    pub fn plays(&self) -> u64 {
+        self.plays
+    }
+
+    /// Startet den Ident sofort (z. B. aus einem Scheduler oder per API).
+    pub fn trigger(&mut self) {
+        if self.clip.is_some() && self.position.is_none() {
+            self.position = Some(0);
+            self.plays += 1;
+            self.info("Ident started");
+            self.emit_event(
+                "ident_started",
+                EventPriority::Info,
+                serde_json::json!({ "plays": self.plays }),
+            );
+        }
+    }
+
+    fn apply(&mut self, config: &HashMap<String, Value>) -> Result<()> {
+        let values = ConfigValues::new("processor", &self.name, config);
+
+        if let Some(interval) = values.duration("interval")? {
+            if interval.is_zero() {
+                bail!("processor '{}': config.interval must be > 0", self.name);
+            }
+            self.interval = interval;
+            self.next_trigger_ns = None;
+        }
+        if let Some(db) = values.db("level_db")? {
+            self.level = db_to_linear(values.check_range("level_db", db, -60.0, 12.0)?);
+        }
+        if let Some(db) = values.db("duck_db")? {
+            self.duck = db_to_linear(values.check_range("duck_db", db, -60.0, 0.0)?);
+        }
+        if let Some(ramp) = values.duration("ramp")? {
+            self.ramp = ramp;
+        }
+        if let Some(file) = config.get("file").and_then(|v| v.as_str()) {
+            self.clip = Some(IdentClip::load(Path::new(file))?);
+            self.position = None;
+            self.rate_warned = false;
+        }
+        if let Some(enabled) = config.get("enabled").and_then(|v| v.as_bool()) {
+            self.enabled = enabled;
+        }
+        if config.get("trigger").and_then(|v| v.as_bool()) == Some(true) {
+            self.trigger();
+        }
+        Ok(())
+    }
+
+    fn inject(&mut self, frame: &mut PcmFrame) {
+        let interval_ns = self.interval.as_nanos() as u64;
+        let next = *self
+            .next_trigger_ns
+            .get_or_insert((frame.utc_ns / interval_ns + 1) * interval_ns);
+        if frame.utc_ns >= next {
+            self.next_trigger_ns = Some((frame.utc_ns / interval_ns + 1) * interval_ns);
+            if self.enabled {
+                self.trigger();
+            }
+        }
+
+        if self.position.is_none() && self.duck_gain >= 1.0 {
+            return;
+        }
+
+        let Some(clip) = &self.clip else {
+            return;
+        };
+        if clip.sample_rate != frame.sample_rate {
+            if !self.rate_warned {
+                self.rate_warned = true;
+                self.errors += 1;
+                self.warn(&format!(
+                    "Ident sample rate {} Hz does not match flow ({} Hz), skipping",
+                    clip.sample_rate, frame.sample_rate
+                ));
+            }
+            self.position = None;
+            self.duck_gain = 1.0;
+            return;
+        }
+
+        let channels = frame.channels.max(1) as usize;
+        let ramp_frames = (self.ramp.as_secs_f32() * frame.sample_rate as f32).max(1.0);
+        let duck_step = (1.0 - self.duck) / ramp_frames;
+        let clip_frames = clip.frames();
+
+        for (i, chunk) in frame.samples.chunks_mut(channels).enumerate() {
+            let ident_frame = self.position.map(|pos| pos + i).filter(|f| *f < clip_frames);
+            let target = if ident_frame.is_some() { self.duck } else { 1.0 };
+            if self.duck_gain > target {
+                self.duck_gain = (self.duck_gain - duck_step).max(target);
+            } else if self.duck_gain < target {
+                self.duck_gain = (self.duck_gain + duck_step).min(target);
+            }
+
+            for (channel, sample) in chunk.iter_mut().enumerate() {
+                let mut mixed = *sample as f32 * self.duck_gain;
+                if let Some(ident_frame) = ident_frame {
+                    mixed += clip.sample(ident_frame, channel) * self.level;
+                }
+                *sample = mixed.clamp(-32768.0, 32767.0) as i16;
+            }
+        }
+
+        if let Some(pos) = self.position {
+            // Marker für Consumer/Recorder: dieser Frame enthält den Ident
+            frame.metadata.insert("ident", serde_json::json!(self.name));
+            let next_pos = pos + frame.samples.len() / channels;
+            self.position = (next_pos < clip_frames).then_some(next_pos);
+            if self.position.is_none() {
+                self.info("Ident finished");
+            }
+        }
+    }
+}
+
+impl Processor for IdentInjector {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn process(&mut self, input_buffer: &AudioRingBuffer, output_buffer: &AudioRingBuffer) -> Result<()> {
+        while let Some(mut frame) = input_buffer.pop() {
+            self.inject(&mut frame);
+            output_buffer.push(frame);
+        }
+        Ok(())
+    }
+
+    fn status(&self) -> ProcessorStatus {
+        ProcessorStatus {
+            running: self.enabled,
+            processing_rate_hz: 0.0,
+            latency_ms: 0.0,
+            errors: self.errors,
+            metrics: None,
+        }
+    }
+
+    /// Teil-Updates: `{"enabled": false}`, `{"trigger": true}`, `{"level_db": -3}` …
+    fn update_config(&mut self, config: Value) -> Result<()> {
+        let Value::Object(map) = config else {
+            bail!("ident config must be an object");
+        };
+        let map: HashMap<String, Value> = map.into_iter().collect();
+        self.apply(&map)
+    }
+
+    fn attach_event_emitter(&mut self, emitter: EventEmitter) {
+        self.emitter = Some(emitter);
+    }
+
+    fn event_emitter(&self) -> Option<&EventEmitter> {
+        self.emitter.as_ref()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl ComponentLogger for IdentInjector {
+    fn log_context(&self) -> LogContext {
+        LogContext::new("IdentInjector", &self.name)
+    }
+}
+
+impl_connectable_processor!(IdentInjector);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn program(utc_ns: u64) -> PcmFrame {
+        PcmFrame {
+            utc_ns,
+            samples: vec![1000; 8],
+            sample_rate: 48_000,
+            channels: 2,
+            metadata: Default::default(),
+        }
+    }
+
+    #[test]
+    fn ident_fires_on_interval_and_ducks_program() {
+        let clip = IdentClip {
+            samples: vec![2000; 4],
+            sample_rate: 48_000,
+            channels: 1,
+        };
+        let mut injector = IdentInjector::new("ident", Some(clip));
+        let mut config = HashMap::new();
+        config.insert("interval".to_string(), serde_json::json!("1s"));
+        config.insert("level_db".to_string(), serde_json::json!(0));
+        config.insert("duck_db".to_string(), serde_json::json!("-6dB"));
+        config.insert("ramp".to_string(), serde_json::json!(0));
+        injector.apply(&config).unwrap();
+
+        let mut before = program(500_000_000);
+        injector.inject(&mut before);
+        assert_eq!(before.samples, vec![1000; 8]);
+        assert!(before.metadata.is_empty());
+
+        let mut during = program(1_000_000_000);
+        injector.inject(&mut during);
+        assert_eq!(injector.plays(), 1);
+        assert!(during.samples[0] > 2000 && during.samples[0] < 3000);
+        assert_eq!(during.metadata.get("ident"), Some(&serde_json::json!("ident")));
+        assert!(!injector.is_playing());
+    }
+}
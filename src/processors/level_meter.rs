@@ -0,0 +1,248 @@
+// src/processors/level_meter.rs
+//
+// Pegelmessung an beliebiger Stelle der Processor-Kette, mit denselben
+// Fenstern wie der Eingangs-Abgriff des Flows (`peak_rates`, Standard
+// 50 ms / 1 s / 10 s). Meter-Fenster gehen als `AudioPeak`, Statistik und
+// Aggregate als `AudioLevelStats` auf den EventBus; die Peak-History
+// speichert sie unter `series` (Standard `<flow>:<processor>`), damit sie
+// sich nicht mit dem Eingangspegel des Flows mischen. Mit
+// `[analyzers] output_meter = true` hängt der Node ihn ans Ende jedes Flows.
+// Das Signal läuft unverändert durch.
+use std::collections::HashMap;
+
+use anyhow::{bail, Result};
+use serde_json::Value;
+
+use crate::core::event_bus::EventEmitter;
+use crate::core::events::{EventPriority, EventType};
+use crate::core::logging::{ComponentLogger, LogContext};
+use crate::core::peak_rates::{FlowLevels, LevelWindow, MultiRatePeaks, PeakRates, PeakTier};
+use crate::core::processor::{Processor, ProcessorStatus};
+use crate::core::ringbuffer::{AudioRingBuffer, PcmFrame};
+use crate::impl_connectable_processor;
+
+/// Name des automatisch angehängten Output-Meters
+pub const OUTPUT_METER_NAME: &str = "output_meter";
+
+pub struct LevelMeter {
+    name: String,
+    /// Name der Messreihe in der Peak-History; `None` = `<flow>:<processor>`
+    series: Option<String>,
+    peaks: MultiRatePeaks,
+    levels: FlowLevels,
+    enabled: bool,
+    emitter: Option<EventEmitter>,
+}
+
+/// Gelesene `config`-Schlüssel (Strict-Modus, siehe `config::options`)
+pub const CONFIG_KEYS: &[&str] = &["enabled", "flow", "series", "peak_rates"];
+
+impl LevelMeter {
+    pub fn new(name: &str, rates: PeakRates) -> Self {
+        Self {
+            name: name.to_string(),
+            series: None,
+            peaks: MultiRatePeaks::new(rates),
+            levels: FlowLevels::default(),
+            enabled: true,
+            emitter: None,
+        }
+    }
+
+    /// Optional `peak_rates` ({ meter, stats, aggregate } wie beim Flow),
+    /// `series` und `enabled`.
+    pub fn from_config(name: &str, config: &HashMap<String, Value>) -> Result<Self> {
+        let mut meter = Self::new(name, PeakRates::default());
+        meter.apply(config)?;
+        Ok(meter)
+    }
+
+    /// Meter am Ende eines Flows, misst in dessen Fensterlängen und
+    /// schreibt die Reihe `<flow>:output`.
+    pub fn output(flow: &str, rates: PeakRates) -> Self {
+        let mut meter = Self::new(OUTPUT_METER_NAME, rates);
+        meter.series = Some(format!("{}:output", flow));
+        meter
+    }
+
+    /// Zuletzt geschlossene Fenster je Stufe
+    pub fn levels(&self) -> &FlowLevels {
+        &self.levels
+    }
+
+    /// Name der Messreihe, unter der die Fenster veröffentlicht werden
+    pub fn series(&self) -> String {
+        if let Some(series) = &self.series {
+            return series.clone();
+        }
+        let flow = self
+            .emitter
+            .as_ref()
+            .and_then(|emitter| emitter.context())
+            .and_then(|context| context.get("flow"))
+            .and_then(|flow| flow.as_str());
+        match flow {
+            Some(flow) => format!("{}:{}", flow, self.name),
+            None => self.name.clone(),
+        }
+    }
+
+    fn apply(&mut self, config: &HashMap<String, Value>) -> Result<()> {
+        if let Some(value) = config.get("peak_rates") {
+            let rates = PeakRates::parse("processor", &self.name, value)?;
+            if rates != self.peaks.rates() {
+                self.peaks = MultiRatePeaks::new(rates);
+                self.levels = FlowLevels::default();
+            }
+        }
+        match config.get("series") {
+            None => {}
+            Some(Value::String(series)) if !series.trim().is_empty() => {
+                self.series = Some(series.trim().to_string());
+            }
+            Some(Value::Null) => self.series = None,
+            Some(other) => bail!(
+                "processor '{}': config.series must be a non-empty string, got {}",
+                self.name,
+                other
+            ),
+        }
+        if let Some(enabled) = config.get("enabled").and_then(|v| v.as_bool()) {
+            self.enabled = enabled;
+            if !enabled {
+                // Angefangene Fenster würden sonst über die Pause gestreckt
+                self.peaks = MultiRatePeaks::new(self.peaks.rates());
+            }
+        }
+        Ok(())
+    }
+
+    fn measure(&mut self, frame: &PcmFrame) {
+        if !self.enabled {
+            return;
+        }
+        let closed = self.peaks.push(frame);
+        if closed.is_empty() {
+            return;
+        }
+        for window in &closed {
+            let slot = match window.tier {
+                PeakTier::Meter => &mut self.levels.meter,
+                PeakTier::Stats => &mut self.levels.stats,
+                PeakTier::Aggregate => &mut self.levels.aggregate,
+            };
+            *slot = Some(window.clone());
+        }
+        self.publish(closed);
+    }
+
+    /// Gleiche Payload wie der Eingangs-Abgriff des Flows, plus `processor`.
+    fn publish(&self, closed: Vec<LevelWindow>) {
+        let Some(emitter) = &self.emitter else {
+            return;
+        };
+        let series = self.series();
+        for window in closed {
+            let mut payload = serde_json::json!({
+                "timestamp": window.utc_ns,
+                "window_ms": window.window_ms,
+                "peaks": window.peaks,
+                "rms": window.rms,
+                "clipped": window.clipped,
+                "silence": window.silence,
+                "flow": series,
+                "processor": self.name,
+            });
+            let event_type = match window.tier {
+                PeakTier::Meter => EventType::AudioPeak,
+                tier => {
+                    payload["rate"] = serde_json::json!(tier.as_str());
+                    payload["frames"] = serde_json::json!(window.frames);
+                    EventType::AudioLevelStats
+                }
+            };
+            emitter.emit(event_type, EventPriority::Debug, payload);
+        }
+    }
+}
+
+impl Processor for LevelMeter {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn process(
+        &mut self,
+        input_buffer: &AudioRingBuffer,
+        output_buffer: &AudioRingBuffer,
+    ) -> Result<()> {
+        while let Some(frame) = input_buffer.pop() {
+            self.measure(&frame);
+            output_buffer.push(frame);
+        }
+        Ok(())
+    }
+
+    fn supports_batch(&self) -> bool {
+        true
+    }
+
+    fn process_batch(&mut self, frames: &mut Vec<PcmFrame>) -> Result<()> {
+        for frame in frames.iter() {
+            self.measure(frame);
+        }
+        Ok(())
+    }
+
+    fn status(&self) -> ProcessorStatus {
+        let rates = self.peaks.rates();
+        ProcessorStatus {
+            running: self.enabled,
+            processing_rate_hz: 0.0,
+            latency_ms: 0.0,
+            errors: 0,
+            metrics: Some(serde_json::json!({
+                "series": self.series(),
+                "peak_rates": {
+                    "meter_ms": rates.meter.as_millis() as u64,
+                    "stats_ms": rates.stats.as_millis() as u64,
+                    "aggregate_ms": rates.aggregate.as_millis() as u64,
+                },
+                "levels": self.levels,
+            })),
+        }
+    }
+
+    /// Teil-Updates: `{"series": "studio"}`, `{"peak_rates": {"stats": "2s"}}`
+    fn update_config(&mut self, config: Value) -> Result<()> {
+        let Value::Object(map) = config else {
+            bail!("level_meter config must be an object");
+        };
+        let map: HashMap<String, Value> = map.into_iter().collect();
+        self.apply(&map)
+    }
+
+    fn attach_event_emitter(&mut self, emitter: EventEmitter) {
+        self.emitter = Some(emitter);
+    }
+
+    fn event_emitter(&self) -> Option<&EventEmitter> {
+        self.emitter.as_ref()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl ComponentLogger for LevelMeter {
+    fn log_context(&self) -> LogContext {
+        LogContext::new("LevelMeter", &self.name)
+    }
+}
+
+impl_connectable_processor!(LevelMeter);
@@ -1,11 +1,16 @@
+use crate::audio::resample::{ResampleQuality, Resampler};
+use crate::config::ConfigValues;
 use crate::impl_connectable_processor;
 use crate::core::logging::{ComponentLogger, LogContext};
 use crate::core::processor::{Processor, ProcessorStatus};
 use crate::core::ringbuffer::{AudioRingBuffer, PcmFrame};
 use crate::core::BufferRegistry;
+use crate::types::FrameMetadata;
 use anyhow::{bail, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
+use std::time::Duration;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MixerInputConfig {
@@ -13,8 +18,19 @@ pub struct MixerInputConfig {
     pub source: String,        // Buffer-Name in der Registry (z.B. "alsa_input", "file_output")
     pub gain: f32,             // Gain für diesen Input (0.0 - 1.0 oder mehr)
     pub enabled: Option<bool>, // Optional: Input deaktivieren
+    pub mute: Option<bool>,    // Optional: stumm, liest aber weiter mit
+    pub solo: Option<bool>,    // Optional: sind Inputs solo, hört man nur diese
 }
 
+/// Gelesene `config`-Schlüssel (Strict-Modus, siehe `config::options`)
+pub const CONFIG_KEYS: &[&str] = &[
+    "inputs",
+    "output_sample_rate",
+    "output_channels",
+    "master_gain",
+    "auto_connect",
+];
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MixerConfig {
     pub inputs: Vec<MixerInputConfig>,
@@ -31,15 +47,132 @@ pub struct Mixer {
     output_sample_rate: u32,
     output_channels: u8,
     master_gain: f32,
+    master_ramp: Option<GainRamp>,
     buffer_registry: Option<Arc<BufferRegistry>>,
     connected: bool,
 }
 
 struct MixerInputBuffer {
+    name: String,
     source_name: String,
     reader_id: String,
     gain: f32,
+    mute: bool,
+    solo: bool,
     buffer: Arc<AudioRingBuffer>,
+    /// Samplerate und Kanalzahl des zuletzt gelesenen Frames
+    input_format: Option<(u32, u8)>,
+    resampler: Option<Resampler>,
+    /// Gewandelte Samples im Ausgangsformat, noch nicht gemischt
+    pending: VecDeque<i16>,
+    /// Laufende Gain-Rampe; `gain` folgt ihr Frame für Frame
+    ramp: Option<GainRamp>,
+}
+
+/// Linearer Gain-Verlauf über eine feste Zahl Sample-Frames der Ausgangsrate.
+/// Läuft in Audio-Zeit und Sample für Sample, damit nichts stuft.
+#[derive(Debug, Clone)]
+struct GainRamp {
+    from: f32,
+    to: f32,
+    /// Länge und bisheriger Fortschritt in Sample-Frames
+    length: u64,
+    position: u64,
+}
+
+impl GainRamp {
+    fn new(from: f32, to: f32, duration: Duration, sample_rate: u32) -> Self {
+        Self {
+            from,
+            to,
+            length: (duration.as_secs_f64() * sample_rate as f64).round().max(1.0) as u64,
+            position: 0,
+        }
+    }
+
+    /// Gain `offset` Sample-Frames nach der aktuellen Position
+    fn gain_at(&self, offset: u64) -> f32 {
+        let position = (self.position + offset).min(self.length);
+        self.from + (self.to - self.from) * (position as f64 / self.length as f64) as f32
+    }
+
+    fn advance(&mut self, frames: u64) {
+        self.position = (self.position + frames).min(self.length);
+    }
+
+    fn finished(&self) -> bool {
+        self.position >= self.length
+    }
+
+    fn status(&self, sample_rate: u32) -> serde_json::Value {
+        serde_json::json!({
+            "to": self.to,
+            "remaining_ms": (self.length - self.position) * 1000 / sample_rate.max(1) as u64,
+        })
+    }
+}
+
+impl MixerInputBuffer {
+    fn new(name: &str, source_name: &str, reader_id: String, buffer: Arc<AudioRingBuffer>) -> Self {
+        Self {
+            name: name.to_string(),
+            source_name: source_name.to_string(),
+            reader_id,
+            gain: 1.0,
+            mute: false,
+            solo: false,
+            buffer,
+            input_format: None,
+            resampler: None,
+            pending: VecDeque::new(),
+            ramp: None,
+        }
+    }
+
+    /// Wandelt einen Frame ins Ausgangsformat (erst Kanäle, dann Samplerate)
+    /// und hängt ihn an `pending`; `true`, wenn sich das Input-Format geändert hat.
+    fn feed(&mut self, frame: &PcmFrame, sample_rate: u32, channels: u8) -> bool {
+        let format = (frame.sample_rate, frame.channels.max(1));
+        let changed = self.input_format != Some(format);
+        if changed {
+            self.input_format = Some(format);
+            self.resampler = (format.0 != sample_rate && format.0 > 0).then(|| {
+                Resampler::new(format.0, sample_rate, channels, ResampleQuality::High)
+            });
+        }
+        let samples = convert_channels(&frame.samples, format.1, channels);
+        match &mut self.resampler {
+            Some(resampler) => self.pending.extend(resampler.process(&samples)),
+            None => self.pending.extend(samples),
+        }
+        changed
+    }
+}
+
+/// Kanalzahl anpassen: Beim Hochmischen wiederholen sich die Input-Kanäle
+/// (Mono → alle Kanäle), beim Heruntermischen werden Kanäle mit gleichem
+/// Rest gemittelt (alles → Mono, 4 → 2 als 1+3 / 2+4).
+fn convert_channels(samples: &[i16], from: u8, to: u8) -> Vec<i16> {
+    let (from, to) = (from.max(1) as usize, to.max(1) as usize);
+    if from == to {
+        return samples.to_vec();
+    }
+    let mut output = Vec::with_capacity(samples.len() / from * to);
+    for chunk in samples.chunks_exact(from) {
+        for channel in 0..to {
+            if to > from {
+                output.push(chunk[channel % from]);
+            } else {
+                let (sum, count) = chunk
+                    .iter()
+                    .skip(channel)
+                    .step_by(to)
+                    .fold((0i32, 0i32), |(sum, count), s| (sum + *s as i32, count + 1));
+                output.push((sum / count.max(1)) as i16);
+            }
+        }
+    }
+    output
 }
 
 const MAX_BATCH_FRAMES: usize = 8;
@@ -61,6 +194,7 @@ impl Mixer {
             output_sample_rate: 48000,
             output_channels: 2,
             master_gain: 1.0,
+            master_ramp: None,
             buffer_registry: None,
             connected: false,
         }
@@ -79,6 +213,7 @@ impl Mixer {
             output_sample_rate,
             output_channels,
             master_gain,
+            master_ramp: None,
             buffer_registry: None,
             connected: false,
         };
@@ -110,12 +245,16 @@ impl Mixer {
                 }
 
                 if let Some(buffer) = registry.get(&input_config.source) {
-                    self.input_buffers.push(MixerInputBuffer {
-                        source_name: input_config.source.clone(),
-                        reader_id: format!("mixer:{}:{}", self.name, input_config.source),
-                        gain: input_config.gain,
+                    let mut input = MixerInputBuffer::new(
+                        &input_config.name,
+                        &input_config.source,
+                        format!("mixer:{}:{}", self.name, input_config.source),
                         buffer,
-                    });
+                    );
+                    input.gain = input_config.gain;
+                    input.mute = input_config.mute.unwrap_or(false);
+                    input.solo = input_config.solo.unwrap_or(false);
+                    self.input_buffers.push(input);
 
                     self.info(&format!(
                         "Connected input '{}' to source '{}' (gain: {})",
@@ -159,12 +298,14 @@ impl Mixer {
         self.input_buffers
             .retain(|input| input.source_name != source_name);
 
-        self.input_buffers.push(MixerInputBuffer {
-            source_name: source_name.to_string(),
-            reader_id: format!("mixer:{}:{}", self.name, source_name),
-            gain,
+        let mut input = MixerInputBuffer::new(
+            source_name,
+            source_name,
+            format!("mixer:{}:{}", self.name, source_name),
             buffer,
-        });
+        );
+        input.gain = gain;
+        self.input_buffers.push(input);
         self.connected = true;
 
         self.info(&format!(
@@ -181,6 +322,7 @@ impl Mixer {
         self.output_sample_rate = config.output_sample_rate.unwrap_or(self.output_sample_rate);
         self.output_channels = config.output_channels.unwrap_or(self.output_channels);
         self.master_gain = config.master_gain.unwrap_or(self.master_gain);
+        self.master_ramp = None;
 
         self.info(&format!(
             "Updated mixer config with {} inputs",
@@ -203,24 +345,210 @@ impl Mixer {
         Ok(())
     }
 
-    /// Mixing-Logik
-    fn mix_batch(&self, batch_size: usize) -> Vec<PcmFrame> {
+    /// Setzt den Gain eines Inputs (Name im Mixer) ab dem nächsten Frame.
+    pub fn set_input_gain(&mut self, input: &str, gain: f32) -> Result<()> {
+        if !(0.0..=16.0).contains(&gain) {
+            bail!("gain {} out of range (0.0..=16.0)", gain);
+        }
+        self.modify_input(input, |state| state.0 = gain)?;
+        for buffer in self.input_buffers.iter_mut().filter(|b| b.name == input) {
+            buffer.ramp = None;
+        }
+        Ok(())
+    }
+
+    /// Blendet den Gain eines Inputs über `duration` linear auf `gain`. Die
+    /// Config erhält sofort den Zielwert; eine laufende Rampe wird ab ihrem
+    /// aktuellen Wert ersetzt.
+    pub fn ramp_input_gain(&mut self, input: &str, gain: f32, duration: Duration) -> Result<()> {
+        if duration.is_zero() {
+            return self.set_input_gain(input, gain);
+        }
+        if !(0.0..=16.0).contains(&gain) {
+            bail!("gain {} out of range (0.0..=16.0)", gain);
+        }
+        let mut found = false;
+        for config in self.config.inputs.iter_mut().filter(|c| c.name == input) {
+            config.gain = gain;
+            found = true;
+        }
+        let sample_rate = self.output_sample_rate;
+        for buffer in self.input_buffers.iter_mut().filter(|b| b.name == input) {
+            buffer.ramp = Some(GainRamp::new(buffer.gain, gain, duration, sample_rate));
+            found = true;
+        }
+        if !found {
+            bail!("mixer '{}' has no input '{}'", self.name, input);
+        }
+        Ok(())
+    }
+
+    /// Wie `ramp_input_gain` für den Master-Gain
+    pub fn ramp_master_gain(&mut self, gain: f32, duration: Duration) -> Result<()> {
+        if !(0.0..=16.0).contains(&gain) {
+            bail!("master_gain {} out of range (0.0..=16.0)", gain);
+        }
+        self.config.master_gain = Some(gain);
+        if duration.is_zero() {
+            self.master_gain = gain;
+            self.master_ramp = None;
+        } else {
+            self.master_ramp = Some(GainRamp::new(
+                self.master_gain,
+                gain,
+                duration,
+                self.output_sample_rate,
+            ));
+        }
+        Ok(())
+    }
+
+    /// `ramp` aus einem Teil-Update: Dauer wie "2s" oder Millisekunden
+    fn ramp_duration(&self, config: &serde_json::Value) -> Result<Option<Duration>> {
+        let map: HashMap<String, serde_json::Value> = config
+            .get("ramp")
+            .map(|ramp| ("ramp".to_string(), ramp.clone()))
+            .into_iter()
+            .collect();
+        ConfigValues::new("processor", &self.name, &map).duration("ramp")
+    }
+
+    /// Schaltet einen Input stumm; seine Frames werden weiter gelesen.
+    pub fn set_input_mute(&mut self, input: &str, mute: bool) -> Result<()> {
+        self.modify_input(input, |state| state.1 = mute)
+    }
+
+    /// Solo: Sobald ein Input solo ist, sind nur die Solo-Inputs hörbar.
+    pub fn set_input_solo(&mut self, input: &str, solo: bool) -> Result<()> {
+        self.modify_input(input, |state| state.2 = solo)
+    }
+
+    /// Ändert (Gain, Mute, Solo) im verbundenen Input und in der Config, damit
+    /// die Werte ein erneutes Verbinden überstehen.
+    fn modify_input(&mut self, input: &str, apply: impl Fn(&mut (f32, bool, bool))) -> Result<()> {
+        let mut found = false;
+        for config in self.config.inputs.iter_mut().filter(|c| c.name == input) {
+            let mut state = (
+                config.gain,
+                config.mute.unwrap_or(false),
+                config.solo.unwrap_or(false),
+            );
+            apply(&mut state);
+            (config.gain, config.mute, config.solo) = (state.0, Some(state.1), Some(state.2));
+            found = true;
+        }
+        for buffer in self.input_buffers.iter_mut().filter(|b| b.name == input) {
+            let mut state = (buffer.gain, buffer.mute, buffer.solo);
+            apply(&mut state);
+            (buffer.gain, buffer.mute, buffer.solo) = state;
+            found = true;
+        }
+        if !found {
+            bail!("mixer '{}' has no input '{}'", self.name, input);
+        }
+        Ok(())
+    }
+
+    /// Teil-Update eines Inputs: `{"input": "music", "gain_db": -6,
+    /// "mute": false, "solo": true}`; alles wird vor dem Anwenden geprüft.
+    fn update_input(&mut self, config: &serde_json::Value) -> Result<()> {
+        let Some(input) = config.get("input").and_then(|v| v.as_str()) else {
+            bail!("mixer input must be a name");
+        };
+        let number = |key: &str| match config.get(key) {
+            None => Ok(None),
+            Some(value) => value
+                .as_f64()
+                .map(Some)
+                .ok_or_else(|| anyhow::anyhow!("mixer input {} must be a number", key)),
+        };
+        let flag = |key: &str| match config.get(key) {
+            None => Ok(None),
+            Some(value) => value
+                .as_bool()
+                .map(Some)
+                .ok_or_else(|| anyhow::anyhow!("mixer input {} must be true or false", key)),
+        };
+        let gain = match (number("gain")?, number("gain_db")?) {
+            (Some(_), Some(_)) => bail!("set either gain or gain_db, not both"),
+            (Some(gain), None) => Some(gain as f32),
+            (None, Some(db)) => Some(crate::config::units::db_to_linear(db as f32)),
+            (None, None) => None,
+        };
+        let (mute, solo) = (flag("mute")?, flag("solo")?);
+        if gain.is_none() && mute.is_none() && solo.is_none() {
+            bail!("mixer input update needs gain, gain_db, mute or solo");
+        }
+        let ramp = self.ramp_duration(config)?;
+        if ramp.is_some() && gain.is_none() {
+            bail!("mixer input ramp needs gain or gain_db");
+        }
+        if gain.is_some_and(|gain| !(0.0..=16.0).contains(&gain)) {
+            bail!("gain {:?} out of range (0.0..=16.0)", gain);
+        }
+        if !self.config.inputs.iter().any(|c| c.name == input)
+            && !self.input_buffers.iter().any(|b| b.name == input)
+        {
+            bail!("mixer '{}' has no input '{}'", self.name, input);
+        }
+
+        if let Some(gain) = gain {
+            self.ramp_input_gain(input, gain, ramp.unwrap_or_default())?;
+        }
+        if let Some(mute) = mute {
+            self.set_input_mute(input, mute)?;
+        }
+        if let Some(solo) = solo {
+            self.set_input_solo(input, solo)?;
+        }
+        self.info(&format!(
+            "Input '{}' updated (gain: {:?}, ramp: {:?}, mute: {:?}, solo: {:?})",
+            input, gain, ramp, mute, solo
+        ));
+        Ok(())
+    }
+
+    /// Samples je Ausgangs-Frame (100 ms, interleaved)
+    fn target_samples(&self) -> usize {
+        (self.output_sample_rate as usize / 10) * self.output_channels as usize
+    }
+
+    /// Mixing-Logik: Jeder Input füllt seinen Vorrat im Ausgangsformat, bis
+    /// ein Ausgangs-Frame voll ist; Reste bleiben für den nächsten Frame.
+    fn mix_batch(&mut self, batch_size: usize) -> Vec<PcmFrame> {
         if !self.connected || self.input_buffers.is_empty() {
             return Vec::new();
         }
 
-        let target_samples =
-            (self.output_sample_rate as usize / 10) * self.output_channels as usize;
+        let sample_rate = self.output_sample_rate;
+        let channels = self.output_channels;
+        let target_samples = self.target_samples();
         let mut mixed_frames = Vec::with_capacity(batch_size);
+        let any_solo = self.input_buffers.iter().any(|input| input.solo);
+        let mut format_changes = Vec::new();
 
         for _ in 0..batch_size {
             let mut mixed_samples = vec![0i16; target_samples];
+            let mut metadata = FrameMetadata::default();
             let mut frames_mixed = 0;
 
-            for input in &self.input_buffers {
-                if let Some(frame) = input.buffer.pop_for_reader(&input.reader_id) {
+            for input in &mut self.input_buffers {
+                let mut popped = false;
+                while input.pending.len() < target_samples {
+                    let Some(frame) = input.buffer.pop_for_reader(&input.reader_id) else {
+                        break;
+                    };
+                    if input.feed(&frame, sample_rate, channels) {
+                        format_changes.push((input.name.clone(), frame.sample_rate, frame.channels));
+                    }
+                    popped = true;
+                    // Begleitdaten aller hörbaren Eingänge; bei gleichem Schlüssel gewinnt der erste
+                    if !input.mute && (!any_solo || input.solo) {
+                        metadata.merge(&frame.metadata);
+                    }
+                }
+                if popped || input.pending.len() >= target_samples {
                     frames_mixed += 1;
-                    self.mix_samples(&mut mixed_samples, &frame.samples, input.gain);
                 }
             }
 
@@ -228,29 +556,81 @@ impl Mixer {
                 break;
             }
 
+            let frame_length = (target_samples / channels as usize) as u64;
+            for input in &mut self.input_buffers {
+                let take = input.pending.len().min(target_samples);
+                let samples = input.pending.drain(..take);
+                // Stumme Inputs lesen weiter mit, tragen aber nichts bei
+                if !input.mute && (!any_solo || input.solo) {
+                    Self::mix_samples(
+                        &mut mixed_samples,
+                        samples,
+                        channels,
+                        input.gain,
+                        input.ramp.as_ref(),
+                    );
+                }
+                // Rampen laufen in Audio-Zeit weiter, auch stumm
+                if let Some(ramp) = &mut input.ramp {
+                    ramp.advance(frame_length);
+                    input.gain = ramp.gain_at(0);
+                    if ramp.finished() {
+                        input.ramp = None;
+                    }
+                }
+            }
+
             self.apply_master_gain(&mut mixed_samples);
 
             mixed_frames.push(PcmFrame {
                 utc_ns: crate::core::timestamp::utc_ns_now(),
                 samples: mixed_samples,
-                sample_rate: self.output_sample_rate,
-                channels: self.output_channels,
+                sample_rate,
+                channels,
+                metadata,
             });
         }
 
+        for (input, rate, input_channels) in format_changes {
+            if rate == sample_rate && input_channels == channels {
+                self.debug(&format!("Input '{}' matches the output format", input));
+            } else {
+                self.info(&format!(
+                    "Input '{}' delivers {} Hz/{} ch, converting to {} Hz/{} ch",
+                    input, rate, input_channels, sample_rate, channels
+                ));
+            }
+        }
+
         mixed_frames
     }
 
-    fn mix_samples(&self, mixed_samples: &mut [i16], input_samples: &[i16], gain: f32) {
-        let samples_to_mix = input_samples.len().min(mixed_samples.len());
-        for i in 0..samples_to_mix {
-            let sample = input_samples[i] as f32 * gain;
-            mixed_samples[i] = (mixed_samples[i] as f32 + sample).clamp(-32768.0, 32767.0) as i16;
+    fn mix_samples(
+        mixed_samples: &mut [i16],
+        input_samples: impl Iterator<Item = i16>,
+        channels: u8,
+        gain: f32,
+        ramp: Option<&GainRamp>,
+    ) {
+        for (i, (mixed, sample)) in mixed_samples.iter_mut().zip(input_samples).enumerate() {
+            let gain = ramp.map_or(gain, |ramp| ramp.gain_at((i / channels as usize) as u64));
+            *mixed = (*mixed as f32 + sample as f32 * gain).clamp(-32768.0, 32767.0) as i16;
         }
     }
 
-    fn apply_master_gain(&self, samples: &mut [i16]) {
-        if self.master_gain != 1.0 {
+    fn apply_master_gain(&mut self, samples: &mut [i16]) {
+        let channels = self.output_channels.max(1) as usize;
+        if let Some(ramp) = &mut self.master_ramp {
+            for (i, sample) in samples.iter_mut().enumerate() {
+                let gain = ramp.gain_at((i / channels) as u64);
+                *sample = (*sample as f32 * gain).clamp(-32768.0, 32767.0) as i16;
+            }
+            ramp.advance((samples.len() / channels) as u64);
+            self.master_gain = ramp.gain_at(0);
+            if ramp.finished() {
+                self.master_ramp = None;
+            }
+        } else if self.master_gain != 1.0 {
             for sample in samples.iter_mut() {
                 *sample = (*sample as f32 * self.master_gain).clamp(-32768.0, 32767.0) as i16;
             }
@@ -277,6 +657,40 @@ impl Mixer {
     pub fn get_config(&self) -> &MixerConfig {
         &self.config
     }
+
+    /// Verbundene Inputs mit Gain, Mute und Solo für den Status
+    fn metrics(&self) -> serde_json::Value {
+        let any_solo = self.input_buffers.iter().any(|input| input.solo);
+        let inputs: Vec<serde_json::Value> = self
+            .input_buffers
+            .iter()
+            .map(|input| {
+                let gain_db = (input.gain > 0.0).then(|| 20.0 * input.gain.log10());
+                serde_json::json!({
+                    "name": input.name,
+                    "source": input.source_name,
+                    "gain": input.gain,
+                    "gain_db": gain_db,
+                    "mute": input.mute,
+                    "solo": input.solo,
+                    "audible": !input.mute && (!any_solo || input.solo),
+                    "buffered": input.buffer.available_for_reader(&input.reader_id),
+                    "sample_rate": input.input_format.map(|(rate, _)| rate),
+                    "channels": input.input_format.map(|(_, channels)| channels),
+                    "resampling": input.resampler.is_some(),
+                    "ramp": input.ramp.as_ref().map(|ramp| ramp.status(self.output_sample_rate)),
+                })
+            })
+            .collect();
+        serde_json::json!({
+            "master_gain": self.master_gain,
+            "master_ramp": self
+                .master_ramp
+                .as_ref()
+                .map(|ramp| ramp.status(self.output_sample_rate)),
+            "inputs": inputs,
+        })
+    }
 }
 
 impl Processor for Mixer {
@@ -304,9 +718,14 @@ impl Processor for Mixer {
             }
         }
 
+        // Verfügbare Frames plus volle Frames im Vorrat (z. B. Reste großer Input-Frames)
+        let target_samples = self.target_samples();
         let mut max_available = 0;
         for input in &self.input_buffers {
-            max_available = max_available.max(input.buffer.available_for_reader(&input.reader_id));
+            max_available = max_available.max(
+                input.buffer.available_for_reader(&input.reader_id)
+                    + input.pending.len() / target_samples.max(1),
+            );
         }
 
         if max_available == 0 {
@@ -366,10 +785,27 @@ impl Processor for Mixer {
             processing_rate_hz: 10.0,       // 100ms frames = 10Hz
             latency_ms: avg_buffer * 100.0, // ~100ms pro Frame
             errors: 0,
+            metrics: Some(self.metrics()),
         }
     }
 
     fn update_config(&mut self, config: serde_json::Value) -> Result<()> {
+        // Teil-Update eines einzelnen Inputs (`mixer.input` über die API)
+        if config.get("input").is_some() {
+            return self.update_input(&config);
+        }
+
+        // Teil-Update nur des Master-Gains (z. B. aus Lua-Regeln), optional
+        // mit `ramp`
+        if config.get("inputs").is_none() {
+            if let Some(gain) = config.get("master_gain").and_then(|v| v.as_f64()) {
+                let ramp = self.ramp_duration(&config)?.unwrap_or_default();
+                self.ramp_master_gain(gain as f32, ramp)?;
+                self.info(&format!("Master gain set to {:.3} (ramp: {:?})", gain, ramp));
+                return Ok(());
+            }
+        }
+
         match serde_json::from_value::<MixerConfig>(config) {
             Ok(mixer_config) => {
                 self.update_config(&mixer_config) // Referenz übergeben
@@ -381,6 +817,23 @@ impl Processor for Mixer {
         }
     }
 
+    fn attach_buffer_registry(&mut self, registry: Arc<BufferRegistry>) {
+        self.set_buffer_registry(registry);
+    }
+
+    fn set_parameter(&mut self, name: &str, value: f64) -> Result<()> {
+        let gain = match name {
+            "master_gain" => value,
+            "master_gain_db" => crate::config::units::db_to_linear(value as f32) as f64,
+            _ => bail!("Mixer '{}' has no parameter '{}'", self.name, name),
+        };
+        let gain = gain.clamp(0.0, 16.0) as f32;
+        self.master_gain = gain;
+        self.master_ramp = None;
+        self.config.master_gain = Some(gain);
+        Ok(())
+    }
+
     // Typ-Casting Methoden
     fn as_any(&self) -> &dyn std::any::Any {
         self
@@ -418,6 +871,8 @@ mod tests {
                 gain: 0.8,
                 source: "mic_producer".to_string(),
                 enabled: Some(true),
+                mute: None,
+                solo: None,
             }],
             output_sample_rate: Some(44100),
             output_channels: Some(1),
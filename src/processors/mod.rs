@@ -1,2 +1,30 @@
+pub mod agc;
+pub mod channel_mapper;
+pub mod fingerprint;
+pub mod hum_filter;
+pub mod ident;
+pub mod level_meter;
 pub mod mixer;
+pub mod phase_meter;
+pub mod pipe;
+pub mod resampler;
+pub mod silence_detector;
+pub mod silence_fallback;
+pub mod switcher;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub use agc::Agc;
+pub use channel_mapper::{ChannelMapper, ChannelMapping};
+pub use fingerprint::FingerprintProcessor;
+pub use hum_filter::HumFilter;
+pub use ident::{IdentClip, IdentInjector};
+pub use level_meter::LevelMeter;
 pub use mixer::{Mixer, MixerConfig, MixerInputConfig};
+pub use phase_meter::{PhaseMeter, PhaseReading};
+pub use pipe::{PipeFailure, PipeProcessor};
+pub use resampler::ResamplerProcessor;
+pub use silence_detector::SilenceDetector;
+pub use silence_fallback::SilenceFallback;
+pub use switcher::{CrossfadeCurve, Switcher};
+#[cfg(feature = "wasm")]
+pub use wasm::WasmProcessor;
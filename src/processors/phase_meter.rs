@@ -0,0 +1,327 @@
+// src/processors/phase_meter.rs
+//
+// Stereo-Korrelation und Balance: je Fenster (`window`, Standard 300 ms)
+// Korrelationsgradmesser (+1 mono-kompatibel, 0 unkorreliert, -1 verpolt)
+// und Balance (-1 nur links, +1 nur rechts). Die Werte stehen im
+// Processor-Status (`metrics`) und gehen als `AudioPeak` mit
+// `kind = "phase"` an den Peaks-WebSocket. Bleibt die Korrelation
+// `duration` lang unter `alarm_below`, folgt `phase_problem` auf dem
+// EventBus, nach dem ersten Fenster darüber `phase_recovered`. Fenster unter
+// `min_level` (Stille) ändern den Alarmzustand nicht. Gemessen wird in
+// Audio-Zeit, das Signal läuft unverändert durch.
+use std::collections::HashMap;
+use std::time::Duration;
+
+use anyhow::{bail, Result};
+use serde_json::Value;
+
+use crate::config::units::db_to_linear;
+use crate::config::ConfigValues;
+use crate::core::event_bus::EventEmitter;
+use crate::core::events::{EventPriority, EventType};
+use crate::core::logging::{ComponentLogger, LogContext};
+use crate::core::processor::{Processor, ProcessorStatus};
+use crate::core::ringbuffer::{AudioRingBuffer, PcmFrame};
+use crate::impl_connectable_processor;
+
+const DEFAULT_WINDOW: Duration = Duration::from_millis(300);
+const DEFAULT_ALARM_BELOW: f64 = 0.0;
+const DEFAULT_DURATION: Duration = Duration::from_secs(5);
+const DEFAULT_MIN_LEVEL_DB: f32 = -50.0;
+
+/// Messwerte eines abgeschlossenen Fensters
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PhaseReading {
+    /// Audio-Zeitstempel am Fensterende
+    pub utc_ns: u64,
+    pub correlation: f64,
+    pub balance: f64,
+}
+
+#[derive(Default)]
+struct Window {
+    frames: u64,
+    sum_lr: f64,
+    sum_ll: f64,
+    sum_rr: f64,
+}
+
+pub struct PhaseMeter {
+    name: String,
+    window: Duration,
+    alarm_below: f64,
+    duration: Duration,
+    /// Mindest-RMS (linear, 1.0 = Vollaussteuerung) für die Alarmauswertung
+    min_level: f64,
+    enabled: bool,
+    current: Window,
+    latest: Option<PhaseReading>,
+    /// Beginn der aktuellen negativen Phase (Audio-Zeit)
+    negative_since: Option<u64>,
+    alarm: bool,
+    alarms: u64,
+    emitter: Option<EventEmitter>,
+}
+
+/// Gelesene `config`-Schlüssel (Strict-Modus, siehe `config::options`)
+pub const CONFIG_KEYS: &[&str] = &[
+    "enabled",
+    "flow",
+    "alarm_below",
+    "duration",
+    "window",
+    "min_level",
+];
+
+impl PhaseMeter {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            window: DEFAULT_WINDOW,
+            alarm_below: DEFAULT_ALARM_BELOW,
+            duration: DEFAULT_DURATION,
+            min_level: db_to_linear(DEFAULT_MIN_LEVEL_DB) as f64,
+            enabled: true,
+            current: Window::default(),
+            latest: None,
+            negative_since: None,
+            alarm: false,
+            alarms: 0,
+            emitter: None,
+        }
+    }
+
+    /// Optional `window` ("300ms"), `alarm_below` (Korrelation, Standard 0),
+    /// `duration` ("5s"), `min_level` (-50 dB) und `enabled`.
+    pub fn from_config(name: &str, config: &HashMap<String, Value>) -> Result<Self> {
+        let mut meter = Self::new(name);
+        meter.apply(config)?;
+        Ok(meter)
+    }
+
+    /// Letztes abgeschlossenes Fenster
+    pub fn latest(&self) -> Option<PhaseReading> {
+        self.latest
+    }
+
+    /// `true`, solange ein Phasenalarm aussteht
+    pub fn is_alarm(&self) -> bool {
+        self.alarm
+    }
+
+    pub fn alarms(&self) -> u64 {
+        self.alarms
+    }
+
+    fn apply(&mut self, config: &HashMap<String, Value>) -> Result<()> {
+        let values = ConfigValues::new("processor", &self.name, config);
+        if let Some(window) = values.duration("window")? {
+            let ms = values.check_range("window", window.as_millis() as u64, 10, 10_000)?;
+            self.window = Duration::from_millis(ms);
+        }
+        if let Some(alarm_below) = values.f64("alarm_below")? {
+            self.alarm_below = values.check_range("alarm_below", alarm_below, -1.0, 1.0)?;
+        }
+        if let Some(duration) = values.duration("duration")? {
+            if duration.is_zero() {
+                bail!("processor '{}': config.duration must be > 0", self.name);
+            }
+            self.duration = duration;
+        }
+        if let Some(min_level) = values.db("min_level")? {
+            let min_level = values.check_range("min_level", min_level, -90.0, 0.0)?;
+            self.min_level = db_to_linear(min_level) as f64;
+        }
+        if let Some(enabled) = config.get("enabled").and_then(|v| v.as_bool()) {
+            self.enabled = enabled;
+            if !enabled {
+                self.current = Window::default();
+                self.negative_since = None;
+            }
+        }
+        Ok(())
+    }
+
+    fn measure(&mut self, frame: &PcmFrame) {
+        if !self.enabled || frame.channels < 2 || frame.sample_rate == 0 {
+            return;
+        }
+        let channels = frame.channels as usize;
+        let window_frames = (self.window.as_secs_f64() * frame.sample_rate as f64).max(1.0) as u64;
+        for (n, samples) in frame.samples.chunks_exact(channels).enumerate() {
+            let (left, right) = (samples[0] as f64, samples[1] as f64);
+            self.current.sum_lr += left * right;
+            self.current.sum_ll += left * left;
+            self.current.sum_rr += right * right;
+            self.current.frames += 1;
+            if self.current.frames >= window_frames {
+                let end = frame.utc_ns + (n as u64 + 1) * 1_000_000_000 / frame.sample_rate as u64;
+                let window = std::mem::take(&mut self.current);
+                self.close_window(window, end);
+            }
+        }
+    }
+
+    fn close_window(&mut self, window: Window, end_ns: u64) {
+        let norm = (window.sum_ll * window.sum_rr).sqrt();
+        let correlation = if norm > 0.0 {
+            window.sum_lr / norm
+        } else {
+            0.0
+        };
+        let (rms_left, rms_right) = (
+            (window.sum_ll / window.frames as f64).sqrt() / 32768.0,
+            (window.sum_rr / window.frames as f64).sqrt() / 32768.0,
+        );
+        let balance = if rms_left + rms_right > 0.0 {
+            (rms_right - rms_left) / (rms_left + rms_right)
+        } else {
+            0.0
+        };
+        let reading = PhaseReading {
+            utc_ns: end_ns,
+            correlation,
+            balance,
+        };
+        self.latest = Some(reading);
+
+        if let Some(emitter) = &self.emitter {
+            let mut payload = serde_json::json!({
+                "kind": "phase",
+                "timestamp": end_ns,
+                "window_ms": self.window.as_millis() as u64,
+                "correlation": correlation,
+                "balance": balance,
+                "processor": self.name,
+            });
+            if let Some(flow) = emitter.context().and_then(|context| context.get("flow")) {
+                payload["flow"] = flow.clone();
+            }
+            emitter.emit(EventType::AudioPeak, EventPriority::Debug, payload);
+        }
+
+        // Stille sagt nichts über die Phase
+        if rms_left.max(rms_right) < self.min_level {
+            return;
+        }
+        let window_start = end_ns.saturating_sub(self.window.as_nanos() as u64);
+        if correlation < self.alarm_below {
+            let since = *self.negative_since.get_or_insert(window_start);
+            let negative_ms = end_ns.saturating_sub(since) / 1_000_000;
+            if !self.alarm && negative_ms >= self.duration.as_millis() as u64 {
+                self.alarm = true;
+                self.alarms += 1;
+                self.warn(&format!(
+                    "Correlation {:.2} below {:.2} for {} ms",
+                    correlation, self.alarm_below, negative_ms
+                ));
+                self.emit_event(
+                    "phase_problem",
+                    EventPriority::Warning,
+                    serde_json::json!({
+                        "processor": self.name,
+                        "correlation": correlation,
+                        "alarm_below": self.alarm_below,
+                        "negative_since_utc_ns": since,
+                        "negative_ms": negative_ms,
+                    }),
+                );
+            }
+        } else {
+            let since = self.negative_since.take();
+            if self.alarm {
+                self.alarm = false;
+                self.info(&format!("Correlation recovered ({:.2})", correlation));
+                self.emit_event(
+                    "phase_recovered",
+                    EventPriority::Info,
+                    serde_json::json!({
+                        "processor": self.name,
+                        "correlation": correlation,
+                        "alarm_below": self.alarm_below,
+                        "negative_since_utc_ns": since,
+                        "negative_ms": since
+                            .map(|since| window_start.saturating_sub(since) / 1_000_000),
+                    }),
+                );
+            }
+        }
+    }
+}
+
+impl Processor for PhaseMeter {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn process(
+        &mut self,
+        input_buffer: &AudioRingBuffer,
+        output_buffer: &AudioRingBuffer,
+    ) -> Result<()> {
+        while let Some(frame) = input_buffer.pop() {
+            self.measure(&frame);
+            output_buffer.push(frame);
+        }
+        Ok(())
+    }
+
+    fn supports_batch(&self) -> bool {
+        true
+    }
+
+    fn process_batch(&mut self, frames: &mut Vec<PcmFrame>) -> Result<()> {
+        for frame in frames.iter() {
+            self.measure(frame);
+        }
+        Ok(())
+    }
+
+    fn status(&self) -> ProcessorStatus {
+        ProcessorStatus {
+            running: self.enabled,
+            processing_rate_hz: 0.0,
+            latency_ms: 0.0,
+            errors: 0,
+            metrics: Some(serde_json::json!({
+                "correlation": self.latest.map(|reading| reading.correlation),
+                "balance": self.latest.map(|reading| reading.balance),
+                "utc_ns": self.latest.map(|reading| reading.utc_ns),
+                "phase_alarm": self.alarm,
+            })),
+        }
+    }
+
+    /// Teil-Updates: `{"alarm_below": -0.3}`, `{"duration": "10s"}`
+    fn update_config(&mut self, config: Value) -> Result<()> {
+        let Value::Object(map) = config else {
+            bail!("phase_meter config must be an object");
+        };
+        let map: HashMap<String, Value> = map.into_iter().collect();
+        self.apply(&map)
+    }
+
+    fn attach_event_emitter(&mut self, emitter: EventEmitter) {
+        self.emitter = Some(emitter);
+    }
+
+    fn event_emitter(&self) -> Option<&EventEmitter> {
+        self.emitter.as_ref()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl ComponentLogger for PhaseMeter {
+    fn log_context(&self) -> LogContext {
+        LogContext::new("PhaseMeter", &self.name)
+    }
+}
+
+impl_connectable_processor!(PhaseMeter);
@@ -0,0 +1,489 @@
+// src/processors/pipe.rs
+//
+// Externer Prozess als Processor: startet `command` mit `args` und schickt
+// die Frames als rohes PCM (s16le, interleaved, Format des Flows) über
+// stdin, das Ergebnis kommt über stdout zurück. So lassen sich beliebige
+// DSP-Werkzeuge wie `sox` oder proprietäre Processors einhängen.
+// `{rate}` und `{channels}` in `args` werden ersetzt. Schreiben, Lesen und
+// stderr laufen in eigenen Threads, der Audio-Thread blockiert nie. Endet
+// der Prozess oder liefert er `timeout` lang nichts, wird er nach
+// `restart_delay` neu gestartet; bis dahin gehen die Frames je nach
+// `on_failure` unverändert (`passthrough`) oder stumm (`silence`) weiter.
+use std::collections::{HashMap, VecDeque};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+use crossbeam_channel::{Receiver, Sender};
+use serde_json::Value;
+
+use crate::config::ConfigValues;
+use crate::core::event_bus::EventEmitter;
+use crate::core::events::EventPriority;
+use crate::core::logging::{ComponentLogger, LogContext};
+use crate::core::processor::{Processor, ProcessorStatus};
+use crate::core::ringbuffer::{AudioRingBuffer, PcmFrame};
+use crate::impl_connectable_processor;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(2);
+const DEFAULT_RESTART_DELAY: Duration = Duration::from_secs(1);
+
+/// Verhalten, solange der Prozess nicht läuft
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PipeFailure {
+    Passthrough,
+    Silence,
+}
+
+/// Laufender Prozess samt I/O-Threads
+struct PipeChild {
+    child: Child,
+    stdin: Sender<Vec<u8>>,
+    stdout: Receiver<Vec<u8>>,
+    /// Von den I/O-Threads gesetzt, wenn eine Seite geschlossen wurde
+    closed: Arc<AtomicBool>,
+    format: (u32, u8),
+}
+
+impl PipeChild {
+    fn spawn(name: &str, command: &str, args: &[String], format: (u32, u8)) -> Result<Self> {
+        let args: Vec<String> = args
+            .iter()
+            .map(|arg| {
+                arg.replace("{rate}", &format.0.to_string())
+                    .replace("{channels}", &format.1.to_string())
+            })
+            .collect();
+        let mut child = Command::new(command)
+            .args(&args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .with_context(|| format!("failed to start '{}'", command))?;
+        let closed = Arc::new(AtomicBool::new(false));
+
+        let (stdin, input) = crossbeam_channel::unbounded::<Vec<u8>>();
+        let mut child_stdin = child.stdin.take().context("child stdin missing")?;
+        let writer_closed = closed.clone();
+        std::thread::spawn(move || {
+            for chunk in input {
+                if child_stdin.write_all(&chunk).is_err() {
+                    break;
+                }
+            }
+            writer_closed.store(true, Ordering::Relaxed);
+        });
+
+        let (output, stdout) = crossbeam_channel::unbounded::<Vec<u8>>();
+        let mut child_stdout = child.stdout.take().context("child stdout missing")?;
+        let reader_closed = closed.clone();
+        std::thread::spawn(move || {
+            let mut buffer = vec![0u8; 16 * 1024];
+            loop {
+                match child_stdout.read(&mut buffer) {
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => {
+                        if output.send(buffer[..n].to_vec()).is_err() {
+                            break;
+                        }
+                    }
+                }
+            }
+            reader_closed.store(true, Ordering::Relaxed);
+        });
+
+        if let Some(stderr) = child.stderr.take() {
+            let name = name.to_string();
+            std::thread::spawn(move || {
+                for line in BufReader::new(stderr).lines().map_while(|line| line.ok()) {
+                    log::debug!("[{}] stderr: {}", name, line);
+                }
+            });
+        }
+
+        Ok(Self {
+            child,
+            stdin,
+            stdout,
+            closed,
+            format,
+        })
+    }
+
+    fn stop(mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+pub struct PipeProcessor {
+    name: String,
+    command: String,
+    args: Vec<String>,
+    timeout: Duration,
+    restart_delay: Duration,
+    on_failure: PipeFailure,
+    enabled: bool,
+    child: Option<PipeChild>,
+    /// Frames beim Prozess, mit Zeitpunkt des Absendens (Watchdog)
+    pending: VecDeque<(PcmFrame, Instant)>,
+    /// Bereits gelesene, noch keinem Frame zugeordnete Bytes
+    output: Vec<u8>,
+    retry_at: Option<Instant>,
+    starts: u64,
+    restarts: u64,
+    errors: u64,
+    emitter: Option<EventEmitter>,
+}
+
+/// Gelesene `config`-Schlüssel (Strict-Modus, siehe `config::options`)
+pub const CONFIG_KEYS: &[&str] = &[
+    "enabled",
+    "command",
+    "args",
+    "timeout",
+    "restart_delay",
+    "on_failure",
+];
+
+impl PipeProcessor {
+    pub fn new(name: &str, command: &str, args: Vec<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            command: command.to_string(),
+            args,
+            timeout: DEFAULT_TIMEOUT,
+            restart_delay: DEFAULT_RESTART_DELAY,
+            on_failure: PipeFailure::Passthrough,
+            enabled: true,
+            child: None,
+            pending: VecDeque::new(),
+            output: Vec::new(),
+            retry_at: None,
+            starts: 0,
+            restarts: 0,
+            errors: 0,
+            emitter: None,
+        }
+    }
+
+    /// Erwartet `command`; optional `args` (Liste, `{rate}`/`{channels}`
+    /// werden ersetzt), `timeout` ("2s"), `restart_delay` ("1s"),
+    /// `on_failure` (`passthrough` | `silence`) und `enabled`.
+    pub fn from_config(name: &str, config: &HashMap<String, Value>) -> Result<Self> {
+        let command = config
+            .get("command")
+            .and_then(|v| v.as_str())
+            .filter(|command| !command.is_empty())
+            .with_context(|| format!("processor '{}': config.command is required", name))?;
+        let mut processor = Self::new(name, command, Vec::new());
+        processor.apply(config)?;
+        Ok(processor)
+    }
+
+    /// `true`, solange der externe Prozess läuft
+    pub fn is_running(&self) -> bool {
+        self.child.is_some()
+    }
+
+    pub fn restarts(&self) -> u64 {
+        self.restarts
+    }
+
+    fn apply(&mut self, config: &HashMap<String, Value>) -> Result<()> {
+        let values = ConfigValues::new("processor", &self.name, config);
+        let mut respawn = false;
+        if let Some(command) = config.get("command") {
+            match command.as_str() {
+                Some(command) if !command.is_empty() => self.command = command.to_string(),
+                _ => bail!("processor '{}': config.command must be a string", self.name),
+            }
+            respawn = true;
+        }
+        if let Some(args) = config.get("args") {
+            let Some(args) = args.as_array() else {
+                bail!("processor '{}': config.args must be a list", self.name);
+            };
+            self.args = args
+                .iter()
+                .map(|arg| match arg {
+                    Value::String(arg) => Ok(arg.clone()),
+                    Value::Number(n) => Ok(n.to_string()),
+                    _ => bail!("processor '{}': config.args must be strings", self.name),
+                })
+                .collect::<Result<_>>()?;
+            respawn = true;
+        }
+        if let Some(timeout) = values.duration("timeout")? {
+            if timeout < Duration::from_millis(100) {
+                bail!("processor '{}': config.timeout must be >= 100ms", self.name);
+            }
+            self.timeout = timeout;
+        }
+        if let Some(delay) = values.duration("restart_delay")? {
+            self.restart_delay = delay;
+        }
+        if let Some(on_failure) = config.get("on_failure") {
+            self.on_failure = match on_failure.as_str() {
+                Some("passthrough") => PipeFailure::Passthrough,
+                Some("silence") => PipeFailure::Silence,
+                _ => bail!(
+                    "processor '{}': config.on_failure must be 'passthrough' or 'silence'",
+                    self.name
+                ),
+            };
+        }
+        if let Some(enabled) = config.get("enabled").and_then(|v| v.as_bool()) {
+            self.enabled = enabled;
+            respawn |= !enabled;
+        }
+        if respawn && self.child.is_some() {
+            self.shutdown();
+            self.retry_at = None;
+        }
+        Ok(())
+    }
+
+    /// Beendet den Prozess; ausstehende Frames bekommen das Fehlerverhalten
+    fn shutdown(&mut self) {
+        if let Some(child) = self.child.take() {
+            child.stop();
+        }
+        self.output.clear();
+    }
+
+    fn fail(&mut self, reason: &str) {
+        self.shutdown();
+        self.restarts += 1;
+        self.retry_at = Some(Instant::now() + self.restart_delay);
+        self.warn(&format!(
+            "'{}' {}, restarting in {} ms",
+            self.command,
+            reason,
+            self.restart_delay.as_millis()
+        ));
+        self.emit_event(
+            "pipe_restarted",
+            EventPriority::Warning,
+            serde_json::json!({
+                "processor": self.name,
+                "command": self.command,
+                "reason": reason,
+                "restarts": self.restarts,
+            }),
+        );
+    }
+
+    fn fallback(&self, mut frame: PcmFrame) -> PcmFrame {
+        if self.on_failure == PipeFailure::Silence {
+            frame.samples.iter_mut().for_each(|sample| *sample = 0);
+        }
+        frame
+    }
+
+    /// Nimmt einen Frame an; fertige Frames landen in `ready`.
+    fn feed(&mut self, frame: PcmFrame, ready: &mut Vec<PcmFrame>) {
+        if !self.enabled {
+            ready.push(frame);
+            return;
+        }
+        let format = (frame.sample_rate, frame.channels);
+        if self
+            .child
+            .as_ref()
+            .is_some_and(|child| child.format != format)
+        {
+            self.info(&format!(
+                "Format changed to {} Hz / {} ch, restarting '{}'",
+                format.0, format.1, self.command
+            ));
+            self.shutdown();
+            self.flush_pending(ready);
+            self.retry_at = None;
+        }
+        if self.child.is_none() && self.retry_at.is_none_or(|at| Instant::now() >= at) {
+            match PipeChild::spawn(&self.name, &self.command, &self.args, format) {
+                Ok(child) => {
+                    self.starts += 1;
+                    self.retry_at = None;
+                    self.info(&format!(
+                        "Started '{}' (pid {})",
+                        self.command,
+                        child.child.id()
+                    ));
+                    self.child = Some(child);
+                }
+                Err(e) => {
+                    self.errors += 1;
+                    self.retry_at = Some(Instant::now() + self.restart_delay);
+                    self.warn(&format!("{:#}", e));
+                }
+            }
+        }
+        let Some(child) = &self.child else {
+            ready.push(self.fallback(frame));
+            return;
+        };
+        let bytes: Vec<u8> = frame
+            .samples
+            .iter()
+            .flat_map(|sample| sample.to_le_bytes())
+            .collect();
+        if child.stdin.send(bytes).is_err() {
+            self.pending.push_back((frame, Instant::now()));
+            self.fail("closed its input");
+            self.flush_pending(ready);
+            return;
+        }
+        self.pending.push_back((frame, Instant::now()));
+    }
+
+    /// Ordnet gelesene Bytes den ausstehenden Frames zu und prüft den Prozess.
+    fn collect(&mut self, ready: &mut Vec<PcmFrame>) {
+        let Some(child) = &self.child else {
+            self.flush_pending(ready);
+            return;
+        };
+        // Erst das Flag, dann lesen: was vor dem Schließen kam, ist im Kanal
+        let closed = child.closed.load(Ordering::Relaxed);
+        while let Ok(chunk) = child.stdout.try_recv() {
+            self.output.extend_from_slice(&chunk);
+        }
+        while let Some((frame, _)) = self.pending.front() {
+            let len = frame.samples.len() * 2;
+            if self.output.len() < len {
+                break;
+            }
+            let (mut frame, _) = self.pending.pop_front().expect("front checked");
+            for (sample, bytes) in frame.samples.iter_mut().zip(self.output.chunks_exact(2)) {
+                *sample = i16::from_le_bytes([bytes[0], bytes[1]]);
+            }
+            self.output.drain(..len);
+            ready.push(frame);
+        }
+
+        if closed {
+            self.fail("exited");
+            self.flush_pending(ready);
+        } else if let Some((_, sent)) = self.pending.front() {
+            if sent.elapsed() > self.timeout {
+                let reason = format!("produced no output for {} ms", self.timeout.as_millis());
+                self.fail(&reason);
+                self.flush_pending(ready);
+            }
+        }
+    }
+
+    fn flush_pending(&mut self, ready: &mut Vec<PcmFrame>) {
+        while let Some((frame, _)) = self.pending.pop_front() {
+            ready.push(self.fallback(frame));
+        }
+    }
+
+    /// Audio beim Prozess, in ms
+    fn pending_ms(&self) -> f32 {
+        self.pending
+            .iter()
+            .map(|(frame, _)| {
+                let frames = frame.samples.len() / frame.channels.max(1) as usize;
+                frames as f32 * 1000.0 / frame.sample_rate.max(1) as f32
+            })
+            .sum()
+    }
+}
+
+impl Drop for PipeProcessor {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+impl Processor for PipeProcessor {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn process(
+        &mut self,
+        input_buffer: &AudioRingBuffer,
+        output_buffer: &AudioRingBuffer,
+    ) -> Result<()> {
+        let mut ready = Vec::new();
+        while let Some(frame) = input_buffer.pop() {
+            self.feed(frame, &mut ready);
+        }
+        self.collect(&mut ready);
+        for frame in ready {
+            output_buffer.push(frame);
+        }
+        Ok(())
+    }
+
+    fn supports_batch(&self) -> bool {
+        true
+    }
+
+    /// Eingangs-Frames gehen an den Prozess, zurück kommen die fertigen,
+    /// ggf. aus früheren Stapeln.
+    fn process_batch(&mut self, frames: &mut Vec<PcmFrame>) -> Result<()> {
+        let mut ready = Vec::with_capacity(frames.len());
+        for frame in frames.drain(..) {
+            self.feed(frame, &mut ready);
+        }
+        self.collect(&mut ready);
+        *frames = ready;
+        Ok(())
+    }
+
+    fn status(&self) -> ProcessorStatus {
+        ProcessorStatus {
+            running: self.enabled && self.child.is_some(),
+            processing_rate_hz: 0.0,
+            latency_ms: self.pending_ms(),
+            errors: self.errors + self.restarts,
+            metrics: Some(serde_json::json!({
+                "pid": self.child.as_ref().map(|child| child.child.id()),
+                "starts": self.starts,
+                "restarts": self.restarts,
+            })),
+        }
+    }
+
+    /// Teil-Updates: `{"args": [...]}` startet den Prozess neu,
+    /// `{"enabled": false}` beendet ihn
+    fn update_config(&mut self, config: Value) -> Result<()> {
+        let Value::Object(map) = config else {
+            bail!("pipe config must be an object");
+        };
+        let map: HashMap<String, Value> = map.into_iter().collect();
+        self.apply(&map)
+    }
+
+    fn attach_event_emitter(&mut self, emitter: EventEmitter) {
+        self.emitter = Some(emitter);
+    }
+
+    fn event_emitter(&self) -> Option<&EventEmitter> {
+        self.emitter.as_ref()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl ComponentLogger for PipeProcessor {
+    fn log_context(&self) -> LogContext {
+        LogContext::new("Pipe", &self.name)
+    }
+}
+
+impl_connectable_processor!(PipeProcessor);
@@ -0,0 +1,230 @@
+// src/processors/resampler.rs
+//
+// Wandelt Frames auf die Ziel-Samplerate des Flows (`sample_rate`, Standard
+// 48 kHz), z. B. für 44,1-kHz-Producer. Frames beliebiger Größe; jeder
+// Input-Frame ergibt höchstens einen Ausgangs-Frame mit den dadurch fertig
+// gewordenen Samples. Der Zeitstempel eines Ausgangs-Frames ist die
+// Audio-Zeit seines ersten Samples, abgeleitet vom zuletzt gelesenen
+// Input-Frame; die Filterverzögerung ist darin schon enthalten. Frames mit
+// Zielrate laufen unverändert durch, ein Wechsel von Rate oder Kanalzahl
+// baut den Filter neu auf.
+use std::collections::HashMap;
+
+use anyhow::{bail, Result};
+use serde_json::Value;
+
+use crate::audio::resample::{ResampleQuality, Resampler};
+use crate::config::ConfigValues;
+use crate::core::logging::{ComponentLogger, LogContext};
+use crate::core::processor::{Processor, ProcessorStatus};
+use crate::core::ringbuffer::{AudioRingBuffer, PcmFrame};
+use crate::impl_connectable_processor;
+
+const DEFAULT_OUTPUT_RATE: u32 = 48_000;
+
+/// Input-Frames, die stärker als das vom erwarteten Zeitstempel abweichen,
+/// gelten als Lücke; der Filter beginnt dann neu.
+const GAP_TOLERANCE_NS: u64 = 50_000_000;
+
+pub struct ResamplerProcessor {
+    name: String,
+    output_rate: u32,
+    quality: ResampleQuality,
+    resampler: Option<Resampler>,
+    /// Input-Samples pro Kanal seit dem Filterstart
+    consumed: u64,
+    /// Ausgangs-Samples pro Kanal seit dem Filterstart
+    produced: u64,
+    /// Erwarteter Zeitstempel des nächsten Input-Frames
+    next_input_ns: Option<u64>,
+    errors: u64,
+}
+
+/// Gelesene `config`-Schlüssel (Strict-Modus, siehe `config::options`)
+pub const CONFIG_KEYS: &[&str] = &["sample_rate", "quality"];
+
+impl ResamplerProcessor {
+    pub fn new(name: &str, output_rate: u32, quality: ResampleQuality) -> Self {
+        Self {
+            name: name.to_string(),
+            output_rate,
+            quality,
+            resampler: None,
+            consumed: 0,
+            produced: 0,
+            next_input_ns: None,
+            errors: 0,
+        }
+    }
+
+    /// Optional `sample_rate` (Standard 48000) und `quality`
+    /// (`low` | `medium` | `high`, Standard `high`).
+    pub fn from_config(name: &str, config: &HashMap<String, Value>) -> Result<Self> {
+        let mut processor = Self::new(name, DEFAULT_OUTPUT_RATE, ResampleQuality::High);
+        processor.apply(config)?;
+        Ok(processor)
+    }
+
+    pub fn output_rate(&self) -> u32 {
+        self.output_rate
+    }
+
+    fn apply(&mut self, config: &HashMap<String, Value>) -> Result<()> {
+        let values = ConfigValues::new("processor", &self.name, config);
+        if let Some(rate) = values.f64("sample_rate")? {
+            self.output_rate = values.check_range("sample_rate", rate, 8_000.0, 192_000.0)? as u32;
+            self.resampler = None;
+        }
+        if let Some(quality) = config.get("quality") {
+            self.quality = quality
+                .as_str()
+                .and_then(ResampleQuality::parse)
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "processor '{}': config.quality must be low, medium or high",
+                        self.name
+                    )
+                })?;
+            self.resampler = None;
+        }
+        Ok(())
+    }
+
+    /// Wandelt einen Frame; `None`, solange der Filter noch keine fertigen
+    /// Samples liefert.
+    fn convert(&mut self, frame: PcmFrame) -> Option<PcmFrame> {
+        if frame.sample_rate == self.output_rate {
+            self.resampler = None;
+            return Some(frame);
+        }
+        if frame.channels == 0 || frame.sample_rate == 0 {
+            self.errors += 1;
+            return None;
+        }
+
+        let restart = match &self.resampler {
+            Some(resampler) => {
+                resampler.input_rate() != frame.sample_rate
+                    || resampler.channels() != frame.channels
+                    || self
+                        .next_input_ns
+                        .is_some_and(|expected| expected.abs_diff(frame.utc_ns) > GAP_TOLERANCE_NS)
+            }
+            None => true,
+        };
+        if restart {
+            if self.resampler.is_some() {
+                self.debug(&format!(
+                    "Restarting filter ({} Hz, {} channels)",
+                    frame.sample_rate, frame.channels
+                ));
+            }
+            self.resampler = Some(Resampler::new(
+                frame.sample_rate,
+                self.output_rate,
+                frame.channels,
+                self.quality,
+            ));
+            self.consumed = 0;
+            self.produced = 0;
+        }
+        let resampler = self.resampler.as_mut()?;
+
+        let channels = frame.channels as usize;
+        let input_frames = (frame.samples.len() / channels) as u64;
+        let samples = resampler.process(&frame.samples);
+        let first_output = self.produced;
+        let frame_start = self.consumed;
+        self.consumed += input_frames;
+        self.produced += (samples.len() / channels) as u64;
+        self.next_input_ns =
+            Some(frame.utc_ns + input_frames * 1_000_000_000 / frame.sample_rate as u64);
+
+        if samples.is_empty() {
+            return None;
+        }
+        // Input-Position des ersten Ausgangs-Samples relativ zum Frame-Anfang
+        let offset_frames = first_output as f64 * resampler.step() - frame_start as f64;
+        let offset_ns = offset_frames * 1e9 / frame.sample_rate as f64;
+        let utc_ns = (frame.utc_ns as f64 + offset_ns).max(0.0).round() as u64;
+
+        Some(PcmFrame {
+            utc_ns,
+            samples,
+            sample_rate: self.output_rate,
+            channels: frame.channels,
+            metadata: frame.metadata,
+        })
+    }
+}
+
+impl Processor for ResamplerProcessor {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn process(
+        &mut self,
+        input_buffer: &AudioRingBuffer,
+        output_buffer: &AudioRingBuffer,
+    ) -> Result<()> {
+        while let Some(frame) = input_buffer.pop() {
+            if let Some(frame) = self.convert(frame) {
+                output_buffer.push(frame);
+            }
+        }
+        Ok(())
+    }
+
+    fn supports_batch(&self) -> bool {
+        true
+    }
+
+    fn process_batch(&mut self, frames: &mut Vec<PcmFrame>) -> Result<()> {
+        let input = std::mem::take(frames);
+        frames.extend(input.into_iter().filter_map(|frame| self.convert(frame)));
+        Ok(())
+    }
+
+    fn status(&self) -> ProcessorStatus {
+        let latency_ms = self
+            .resampler
+            .as_ref()
+            .map(|resampler| {
+                resampler.latency_frames() as f32 * 1000.0 / resampler.input_rate() as f32
+            })
+            .unwrap_or(0.0);
+        ProcessorStatus {
+            running: true,
+            processing_rate_hz: 0.0,
+            latency_ms,
+            errors: self.errors,
+            metrics: None,
+        }
+    }
+
+    /// Teil-Updates: `{"sample_rate": 44100}`, `{"quality": "medium"}`
+    fn update_config(&mut self, config: Value) -> Result<()> {
+        let Value::Object(map) = config else {
+            bail!("resampler config must be an object");
+        };
+        let map: HashMap<String, Value> = map.into_iter().collect();
+        self.apply(&map)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl ComponentLogger for ResamplerProcessor {
+    fn log_context(&self) -> LogContext {
+        LogContext::new("Resampler", &self.name)
+    }
+}
+
+impl_connectable_processor!(ResamplerProcessor);
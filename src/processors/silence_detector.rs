@@ -0,0 +1,281 @@
+// src/processors/silence_detector.rs
+//
+// Dead-Air-Alarm: misst den Spitzenpegel jedes Frames und meldet, sobald der
+// Flow `duration` lang unter `threshold_db` liegt, `silence_detected` auf dem
+// EventBus (optional zusätzlich als JSON-POST an `webhook`). Kommt Audio
+// zurück und hält `recovery` lang an, folgt `silence_recovered`. Gemessen
+// wird in Audio-Zeit (`PcmFrame::utc_ns`), das Signal läuft unverändert
+// durch.
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use serde_json::Value;
+
+use crate::config::units::db_to_linear;
+use crate::config::ConfigValues;
+use crate::core::event_bus::EventEmitter;
+use crate::core::events::EventPriority;
+use crate::core::http_client::{self, HttpRequest};
+use crate::core::logging::{ComponentLogger, LogContext};
+use crate::core::processor::{Processor, ProcessorStatus};
+use crate::core::ringbuffer::{AudioRingBuffer, PcmFrame};
+use crate::impl_connectable_processor;
+
+const DEFAULT_THRESHOLD_DB: f32 = -50.0;
+const DEFAULT_DURATION: Duration = Duration::from_secs(10);
+const DEFAULT_RECOVERY: Duration = Duration::from_secs(2);
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub struct SilenceDetector {
+    name: String,
+    threshold_db: f32,
+    /// Schwelle als Sample-Betrag
+    threshold: f32,
+    duration: Duration,
+    recovery: Duration,
+    webhook: Option<String>,
+    enabled: bool,
+    /// Audio-Zeitstempel, an dem die aktuelle Stille begann
+    silent_since: Option<u64>,
+    /// Beginn des Audios nach einem Alarm (Recovery läuft)
+    audio_since: Option<u64>,
+    alarm: bool,
+    alarms: u64,
+    /// Fehlgeschlagene Webhooks (aus dem Sende-Thread gezählt)
+    webhook_errors: Arc<AtomicU64>,
+    emitter: Option<EventEmitter>,
+}
+
+/// Gelesene `config`-Schlüssel (Strict-Modus, siehe `config::options`)
+pub const CONFIG_KEYS: &[&str] = &["enabled", "threshold_db", "duration", "recovery", "webhook"];
+
+impl SilenceDetector {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            threshold_db: DEFAULT_THRESHOLD_DB,
+            threshold: db_to_linear(DEFAULT_THRESHOLD_DB) * 32768.0,
+            duration: DEFAULT_DURATION,
+            recovery: DEFAULT_RECOVERY,
+            webhook: None,
+            enabled: true,
+            silent_since: None,
+            audio_since: None,
+            alarm: false,
+            alarms: 0,
+            webhook_errors: Arc::new(AtomicU64::new(0)),
+            emitter: None,
+        }
+    }
+
+    /// Optional `threshold_db` (Standard -50 dBFS), `duration` ("10s"),
+    /// `recovery` ("2s"), `webhook` (http://…) und `enabled`.
+    pub fn from_config(name: &str, config: &HashMap<String, Value>) -> Result<Self> {
+        let mut detector = Self::new(name);
+        detector.apply(config)?;
+        Ok(detector)
+    }
+
+    /// `true`, solange ein Alarm aussteht (ausgelöst, noch nicht erholt).
+    pub fn is_alarm(&self) -> bool {
+        self.alarm
+    }
+
+    pub fn alarms(&self) -> u64 {
+        self.alarms
+    }
+
+    fn apply(&mut self, config: &HashMap<String, Value>) -> Result<()> {
+        let values = ConfigValues::new("processor", &self.name, config);
+
+        if let Some(db) = values.db("threshold_db")? {
+            self.threshold_db = values.check_range("threshold_db", db, -90.0, 0.0)?;
+            self.threshold = db_to_linear(self.threshold_db) * 32768.0;
+        }
+        if let Some(duration) = values.duration("duration")? {
+            if duration.is_zero() {
+                bail!("processor '{}': config.duration must be > 0", self.name);
+            }
+            self.duration = duration;
+        }
+        if let Some(recovery) = values.duration("recovery")? {
+            self.recovery = recovery;
+        }
+        if let Some(webhook) = config.get("webhook") {
+            self.webhook = match webhook {
+                Value::Null => None,
+                Value::String(url) if url.is_empty() => None,
+                Value::String(url) => {
+                    http_client::parse_http_url(url).with_context(|| {
+                        format!("processor '{}': config.webhook invalid", self.name)
+                    })?;
+                    Some(url.clone())
+                }
+                _ => bail!("processor '{}': config.webhook must be a URL", self.name),
+            };
+        }
+        if let Some(enabled) = config.get("enabled").and_then(|v| v.as_bool()) {
+            self.enabled = enabled;
+            if !enabled {
+                self.silent_since = None;
+                self.audio_since = None;
+            }
+        }
+        Ok(())
+    }
+
+    fn detect(&mut self, frame: &PcmFrame) {
+        if !self.enabled {
+            return;
+        }
+        let frames = frame.samples.len() / frame.channels.max(1) as usize;
+        let frame_end =
+            frame.utc_ns + frames as u64 * 1_000_000_000 / frame.sample_rate.max(1) as u64;
+        let peak = frame
+            .samples
+            .iter()
+            .map(|sample| (*sample as i32).unsigned_abs())
+            .max()
+            .unwrap_or(0);
+        let silent = peak as f32 <= self.threshold;
+
+        if silent {
+            self.audio_since = None;
+            let since = *self.silent_since.get_or_insert(frame.utc_ns);
+            if !self.alarm && frame_end.saturating_sub(since) >= self.duration.as_nanos() as u64 {
+                self.alarm = true;
+                self.alarms += 1;
+                let silent_ns = frame_end.saturating_sub(since);
+                self.warn(&format!(
+                    "Silence below {} dBFS for {} ms",
+                    self.threshold_db,
+                    silent_ns / 1_000_000
+                ));
+                self.notify("silence_detected", EventPriority::Warning, since, silent_ns);
+            }
+            return;
+        }
+
+        if !self.alarm {
+            self.silent_since = None;
+            return;
+        }
+        let audio_since = *self.audio_since.get_or_insert(frame.utc_ns);
+        if frame_end.saturating_sub(audio_since) >= self.recovery.as_nanos() as u64 {
+            let since = self.silent_since.take().unwrap_or(audio_since);
+            self.alarm = false;
+            self.audio_since = None;
+            let silent_ns = audio_since.saturating_sub(since);
+            self.info(&format!(
+                "Audio back after {} ms of silence",
+                silent_ns / 1_000_000
+            ));
+            self.notify("silence_recovered", EventPriority::Info, since, silent_ns);
+        }
+    }
+
+    fn notify(&self, event: &str, priority: EventPriority, since: u64, silent_ns: u64) {
+        let payload = serde_json::json!({
+            "processor": self.name,
+            "threshold_db": self.threshold_db,
+            "silent_since_utc_ns": since,
+            "silent_ms": silent_ns / 1_000_000,
+        });
+        self.emit_event(event, priority, payload.clone());
+
+        if let Some(url) = self.webhook.clone() {
+            let mut body = payload;
+            body["event"] = Value::from(event);
+            let name = self.name.clone();
+            let errors = self.webhook_errors.clone();
+            // Nicht im Audio-Thread auf die Gegenstelle warten
+            std::thread::spawn(move || {
+                let request =
+                    HttpRequest::post(&url, "application/json", body.to_string().into_bytes())
+                        .timeout(WEBHOOK_TIMEOUT);
+                let result =
+                    http_client::send(&request).and_then(|response| response.error_for_status());
+                if let Err(e) = result {
+                    errors.fetch_add(1, Ordering::Relaxed);
+                    log::warn!("[{}] silence webhook failed: {:#}", name, e);
+                }
+            });
+        }
+    }
+}
+
+impl Processor for SilenceDetector {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn process(
+        &mut self,
+        input_buffer: &AudioRingBuffer,
+        output_buffer: &AudioRingBuffer,
+    ) -> Result<()> {
+        while let Some(frame) = input_buffer.pop() {
+            self.detect(&frame);
+            output_buffer.push(frame);
+        }
+        Ok(())
+    }
+
+    fn supports_batch(&self) -> bool {
+        true
+    }
+
+    fn process_batch(&mut self, frames: &mut Vec<PcmFrame>) -> Result<()> {
+        for frame in frames.iter() {
+            self.detect(frame);
+        }
+        Ok(())
+    }
+
+    fn status(&self) -> ProcessorStatus {
+        ProcessorStatus {
+            running: self.enabled,
+            processing_rate_hz: 0.0,
+            latency_ms: 0.0,
+            errors: self.webhook_errors.load(Ordering::Relaxed),
+            metrics: None,
+        }
+    }
+
+    /// Teil-Updates: `{"threshold_db": -45}`, `{"duration": "30s"}`,
+    /// `{"webhook": null}` …
+    fn update_config(&mut self, config: Value) -> Result<()> {
+        let Value::Object(map) = config else {
+            bail!("silence_detector config must be an object");
+        };
+        let map: HashMap<String, Value> = map.into_iter().collect();
+        self.apply(&map)
+    }
+
+    fn attach_event_emitter(&mut self, emitter: EventEmitter) {
+        self.emitter = Some(emitter);
+    }
+
+    fn event_emitter(&self) -> Option<&EventEmitter> {
+        self.emitter.as_ref()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl ComponentLogger for SilenceDetector {
+    fn log_context(&self) -> LogContext {
+        LogContext::new("SilenceDetector", &self.name)
+    }
+}
+
+impl_connectable_processor!(SilenceDetector);
@@ -0,0 +1,385 @@
+// src/processors/silence_fallback.rs
+//
+// Letzte Rückfallebene gegen Dead Air im Flow: liegt das Programm `duration`
+// lang unter `threshold_db`, ersetzt der Processor es durch einen Testton
+// (`tone_hz`, `level_db`) oder eine in Schleife gespielte WAV-Datei (`file`).
+// Kommt das Programm zurück und hält `recovery` lang an, wird wieder
+// umgeschaltet. Beide Wechsel werden über `ramp` überblendet und als
+// `fallback_started` / `fallback_stopped` auf dem EventBus gemeldet. Gemessen
+// wird das Eingangssignal in Audio-Zeit (`PcmFrame::utc_ns`).
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{bail, Result};
+use serde_json::Value;
+
+use crate::config::units::db_to_linear;
+use crate::config::ConfigValues;
+use crate::core::event_bus::EventEmitter;
+use crate::core::events::EventPriority;
+use crate::core::logging::{ComponentLogger, LogContext};
+use crate::core::processor::{Processor, ProcessorStatus};
+use crate::core::ringbuffer::{AudioRingBuffer, PcmFrame};
+use crate::impl_connectable_processor;
+use crate::processors::ident::IdentClip;
+
+const DEFAULT_THRESHOLD_DB: f32 = -50.0;
+const DEFAULT_DURATION: Duration = Duration::from_secs(10);
+const DEFAULT_RECOVERY: Duration = Duration::from_secs(2);
+const DEFAULT_RAMP: Duration = Duration::from_millis(100);
+const DEFAULT_TONE_HZ: f64 = 1000.0;
+const DEFAULT_LEVEL_DB: f32 = -18.0;
+
+pub struct SilenceFallback {
+    name: String,
+    threshold_db: f32,
+    /// Schwelle als Sample-Betrag
+    threshold: f32,
+    duration: Duration,
+    recovery: Duration,
+    ramp: Duration,
+    tone_hz: f64,
+    /// Spitzenwert des Testtons (linear)
+    level: f32,
+    /// Ersatzdatei statt Testton
+    clip: Option<IdentClip>,
+    /// Verstärkung der Ersatzdatei (linear)
+    gain: f32,
+    enabled: bool,
+    /// Audio-Zeitstempel, an dem die aktuelle Stille begann
+    silent_since: Option<u64>,
+    /// Beginn des Programms während der Ersatz läuft (Recovery)
+    audio_since: Option<u64>,
+    active: bool,
+    /// Anteil des Ersatzsignals, läuft über `ramp` zwischen 0 und 1
+    mix: f32,
+    tone_phase: f64,
+    clip_position: usize,
+    activations: u64,
+    errors: u64,
+    rate_warned: bool,
+    emitter: Option<EventEmitter>,
+}
+
+/// Gelesene `config`-Schlüssel (Strict-Modus, siehe `config::options`)
+pub const CONFIG_KEYS: &[&str] = &[
+    "enabled",
+    "threshold_db",
+    "duration",
+    "recovery",
+    "file",
+    "tone_hz",
+    "level_db",
+    "gain_db",
+    "ramp",
+];
+
+impl SilenceFallback {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            threshold_db: DEFAULT_THRESHOLD_DB,
+            threshold: db_to_linear(DEFAULT_THRESHOLD_DB) * 32768.0,
+            duration: DEFAULT_DURATION,
+            recovery: DEFAULT_RECOVERY,
+            ramp: DEFAULT_RAMP,
+            tone_hz: DEFAULT_TONE_HZ,
+            level: db_to_linear(DEFAULT_LEVEL_DB),
+            clip: None,
+            gain: 1.0,
+            enabled: true,
+            silent_since: None,
+            audio_since: None,
+            active: false,
+            mix: 0.0,
+            tone_phase: 0.0,
+            clip_position: 0,
+            activations: 0,
+            errors: 0,
+            rate_warned: false,
+            emitter: None,
+        }
+    }
+
+    /// Optional `threshold_db` (Standard -50 dBFS), `duration` ("10s"),
+    /// `recovery` ("2s"), `ramp` ("100ms"), `tone_hz` (1000), `level_db`
+    /// (Testton, -18 dBFS), `file` (WAV statt Testton), `gain_db` (Datei,
+    /// 0 dB) und `enabled`.
+    pub fn from_config(name: &str, config: &HashMap<String, Value>) -> Result<Self> {
+        let mut fallback = Self::new(name);
+        fallback.apply(config)?;
+        Ok(fallback)
+    }
+
+    /// `true`, solange das Ersatzsignal läuft
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    pub fn activations(&self) -> u64 {
+        self.activations
+    }
+
+    fn source(&self) -> &'static str {
+        if self.clip.is_some() {
+            "file"
+        } else {
+            "tone"
+        }
+    }
+
+    fn apply(&mut self, config: &HashMap<String, Value>) -> Result<()> {
+        let values = ConfigValues::new("processor", &self.name, config);
+
+        if let Some(db) = values.db("threshold_db")? {
+            self.threshold_db = values.check_range("threshold_db", db, -90.0, 0.0)?;
+            self.threshold = db_to_linear(self.threshold_db) * 32768.0;
+        }
+        if let Some(duration) = values.duration("duration")? {
+            if duration.is_zero() {
+                bail!("processor '{}': config.duration must be > 0", self.name);
+            }
+            self.duration = duration;
+        }
+        if let Some(recovery) = values.duration("recovery")? {
+            self.recovery = recovery;
+        }
+        if let Some(ramp) = values.duration("ramp")? {
+            self.ramp = ramp;
+        }
+        if let Some(tone_hz) = values.f64("tone_hz")? {
+            self.tone_hz = values.check_range("tone_hz", tone_hz, 20.0, 20_000.0)?;
+        }
+        if let Some(db) = values.db("level_db")? {
+            self.level = db_to_linear(values.check_range("level_db", db, -60.0, 0.0)?);
+        }
+        if let Some(db) = values.db("gain_db")? {
+            self.gain = db_to_linear(values.check_range("gain_db", db, -60.0, 12.0)?);
+        }
+        match config.get("file") {
+            None => {}
+            Some(Value::Null) => self.clip = None,
+            Some(Value::String(file)) if file.is_empty() => self.clip = None,
+            Some(Value::String(file)) => {
+                self.clip = Some(IdentClip::load(Path::new(file))?);
+                self.clip_position = 0;
+                self.rate_warned = false;
+            }
+            Some(_) => bail!("processor '{}': config.file must be a path", self.name),
+        }
+        if let Some(enabled) = config.get("enabled").and_then(|v| v.as_bool()) {
+            self.enabled = enabled;
+            if !enabled {
+                self.silent_since = None;
+                self.audio_since = None;
+                self.mix = 0.0;
+                if self.active {
+                    self.active = false;
+                    self.info("Fallback disabled, back to program");
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn detect(&mut self, frame: &PcmFrame) {
+        let frames = frame.samples.len() / frame.channels.max(1) as usize;
+        let frame_end =
+            frame.utc_ns + frames as u64 * 1_000_000_000 / frame.sample_rate.max(1) as u64;
+        let peak = frame
+            .samples
+            .iter()
+            .map(|sample| (*sample as i32).unsigned_abs())
+            .max()
+            .unwrap_or(0);
+        let silent = peak as f32 <= self.threshold;
+
+        if !self.active {
+            if !silent {
+                self.silent_since = None;
+                return;
+            }
+            let since = *self.silent_since.get_or_insert(frame.utc_ns);
+            let silent_ns = frame_end.saturating_sub(since);
+            if silent_ns >= self.duration.as_nanos() as u64 {
+                self.active = true;
+                self.activations += 1;
+                self.clip_position = 0;
+                self.warn(&format!(
+                    "Silence for {} ms, switching to {}",
+                    silent_ns / 1_000_000,
+                    self.source()
+                ));
+                self.notify("fallback_started", EventPriority::Warning, since, silent_ns);
+            }
+            return;
+        }
+
+        if silent {
+            self.audio_since = None;
+            return;
+        }
+        let audio_since = *self.audio_since.get_or_insert(frame.utc_ns);
+        if frame_end.saturating_sub(audio_since) >= self.recovery.as_nanos() as u64 {
+            let since = self.silent_since.take().unwrap_or(audio_since);
+            self.active = false;
+            self.audio_since = None;
+            let silent_ns = audio_since.saturating_sub(since);
+            self.info(&format!(
+                "Program back after {} ms, leaving {}",
+                silent_ns / 1_000_000,
+                self.source()
+            ));
+            self.notify("fallback_stopped", EventPriority::Info, since, silent_ns);
+        }
+    }
+
+    fn notify(&self, event: &str, priority: EventPriority, since: u64, silent_ns: u64) {
+        self.emit_event(
+            event,
+            priority,
+            serde_json::json!({
+                "processor": self.name,
+                "source": self.source(),
+                "threshold_db": self.threshold_db,
+                "silent_since_utc_ns": since,
+                "silent_ms": silent_ns / 1_000_000,
+            }),
+        );
+    }
+
+    fn replace(&mut self, frame: &mut PcmFrame) {
+        if !self.enabled || frame.sample_rate == 0 {
+            return;
+        }
+        self.detect(frame);
+        let target = if self.active { 1.0 } else { 0.0 };
+        if self.mix == 0.0 && target == 0.0 {
+            return;
+        }
+
+        // Datei mit anderer Samplerate: Testton statt Stille
+        if let Some(clip) = &self.clip {
+            if clip.sample_rate != frame.sample_rate && !self.rate_warned {
+                self.rate_warned = true;
+                self.errors += 1;
+                self.warn(&format!(
+                    "Fallback file sample rate {} Hz does not match flow ({} Hz), using tone",
+                    clip.sample_rate, frame.sample_rate
+                ));
+            }
+        }
+        let clip = self
+            .clip
+            .as_ref()
+            .filter(|clip| clip.sample_rate == frame.sample_rate && clip.frames() > 0);
+
+        let channels = frame.channels.max(1) as usize;
+        let ramp_frames = (self.ramp.as_secs_f32() * frame.sample_rate as f32).max(1.0);
+        let step = 1.0 / ramp_frames;
+        let phase_step = 2.0 * std::f64::consts::PI * self.tone_hz / frame.sample_rate as f64;
+        for samples in frame.samples.chunks_exact_mut(channels) {
+            if self.mix < target {
+                self.mix = (self.mix + step).min(target);
+            } else if self.mix > target {
+                self.mix = (self.mix - step).max(target);
+            }
+            let tone = (self.tone_phase.sin() as f32) * self.level * 32767.0;
+            for (channel, sample) in samples.iter_mut().enumerate() {
+                let fallback = match clip {
+                    Some(clip) => clip.sample(self.clip_position, channel) * self.gain,
+                    None => tone,
+                };
+                let mixed = *sample as f32 * (1.0 - self.mix) + fallback * self.mix;
+                *sample = mixed.round().clamp(-32768.0, 32767.0) as i16;
+            }
+            match clip {
+                Some(clip) => self.clip_position = (self.clip_position + 1) % clip.frames(),
+                None => {
+                    self.tone_phase = (self.tone_phase + phase_step) % std::f64::consts::TAU;
+                }
+            }
+        }
+        // Marker für Consumer/Recorder: dieser Frame enthält Ersatzsignal
+        frame
+            .metadata
+            .insert("fallback", serde_json::json!(self.name));
+    }
+}
+
+impl Processor for SilenceFallback {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn process(
+        &mut self,
+        input_buffer: &AudioRingBuffer,
+        output_buffer: &AudioRingBuffer,
+    ) -> Result<()> {
+        while let Some(mut frame) = input_buffer.pop() {
+            self.replace(&mut frame);
+            output_buffer.push(frame);
+        }
+        Ok(())
+    }
+
+    fn supports_batch(&self) -> bool {
+        true
+    }
+
+    fn process_batch(&mut self, frames: &mut Vec<PcmFrame>) -> Result<()> {
+        for frame in frames.iter_mut() {
+            self.replace(frame);
+        }
+        Ok(())
+    }
+
+    fn status(&self) -> ProcessorStatus {
+        ProcessorStatus {
+            running: self.enabled,
+            processing_rate_hz: 0.0,
+            latency_ms: 0.0,
+            errors: self.errors,
+            metrics: Some(serde_json::json!({
+                "active": self.active,
+                "source": self.source(),
+                "activations": self.activations,
+            })),
+        }
+    }
+
+    /// Teil-Updates: `{"duration": "5s"}`, `{"file": null}`, `{"enabled": false}`
+    fn update_config(&mut self, config: Value) -> Result<()> {
+        let Value::Object(map) = config else {
+            bail!("silence_fallback config must be an object");
+        };
+        let map: HashMap<String, Value> = map.into_iter().collect();
+        self.apply(&map)
+    }
+
+    fn attach_event_emitter(&mut self, emitter: EventEmitter) {
+        self.emitter = Some(emitter);
+    }
+
+    fn event_emitter(&self) -> Option<&EventEmitter> {
+        self.emitter.as_ref()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl ComponentLogger for SilenceFallback {
+    fn log_context(&self) -> LogContext {
+        LogContext::new("SilenceFallback", &self.name)
+    }
+}
+
+impl_connectable_processor!(SilenceFallback);
@@ -0,0 +1,374 @@
+// src/processors/switcher.rs
+//
+// Quellenumschalter: liest wie der Mixer direkt aus Registry-Buffern
+// (`sources`) und gibt genau eine davon aus. Beim Umschalten (`active`, zur
+// Laufzeit über `processor.configure`) wird über `crossfade` überblendet,
+// damit der Wechsel weder knackt noch hart schneidet. Die Frames des
+// Flow-Eingangs dienen nur als Takt und werden verworfen; nicht aktive
+// Quellen werden laufend geleert, damit ein Wechsel ohne Rückstand beginnt.
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{bail, Result};
+use serde_json::Value;
+
+use crate::config::ConfigValues;
+use crate::core::event_bus::EventEmitter;
+use crate::core::events::EventPriority;
+use crate::core::logging::{ComponentLogger, LogContext};
+use crate::core::processor::{Processor, ProcessorStatus};
+use crate::core::ringbuffer::{AudioRingBuffer, PcmFrame};
+use crate::core::BufferRegistry;
+use crate::impl_connectable_processor;
+
+const DEFAULT_CROSSFADE: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrossfadeCurve {
+    /// Gleichbleibende Leistung (cos/sin), kein Loch bei unkorrelierten Quellen
+    EqualPower,
+    Linear,
+}
+
+impl CrossfadeCurve {
+    /// Pegel (ausgehend, eingehend) bei Fortschritt `t` in 0..=1
+    fn gains(self, t: f32) -> (f32, f32) {
+        match self {
+            Self::EqualPower => {
+                let angle = t * std::f32::consts::FRAC_PI_2;
+                (angle.cos(), angle.sin())
+            }
+            Self::Linear => (1.0 - t, t),
+        }
+    }
+}
+
+struct Source {
+    name: String,
+    /// Buffer-Name in der Registry (`producer:<name>` für einfache Namen)
+    buffer_name: String,
+    reader_id: String,
+    buffer: Option<Arc<AudioRingBuffer>>,
+}
+
+struct Fade {
+    from: usize,
+    /// Fortschritt in Sample-Frames der eingehenden Quelle
+    position: u64,
+}
+
+pub struct Switcher {
+    name: String,
+    sources: Vec<Source>,
+    active: usize,
+    crossfade: Duration,
+    curve: CrossfadeCurve,
+    fade: Option<Fade>,
+    registry: Option<Arc<BufferRegistry>>,
+    switches: u64,
+    /// Durchläufe, in denen die aktive Quelle nicht in der Registry stand
+    errors: u64,
+    emitter: Option<EventEmitter>,
+}
+
+/// Gelesene `config`-Schlüssel (Strict-Modus, siehe `config::options`)
+pub const CONFIG_KEYS: &[&str] = &["sources", "active", "crossfade", "curve"];
+
+impl Switcher {
+    /// `sources` = Registry-Buffer (einfache Namen meinen `producer:<name>`),
+    /// aktiv ist zunächst die erste Quelle.
+    pub fn new(name: &str, sources: &[&str]) -> Self {
+        Self {
+            name: name.to_string(),
+            sources: sources
+                .iter()
+                .map(|source| Source {
+                    name: source.to_string(),
+                    buffer_name: if source.contains(':') {
+                        source.to_string()
+                    } else {
+                        format!("producer:{}", source)
+                    },
+                    reader_id: format!("switcher:{}:{}", name, source),
+                    buffer: None,
+                })
+                .collect(),
+            active: 0,
+            crossfade: DEFAULT_CROSSFADE,
+            curve: CrossfadeCurve::EqualPower,
+            fade: None,
+            registry: None,
+            switches: 0,
+            errors: 0,
+            emitter: None,
+        }
+    }
+
+    /// `sources` (Pflicht), optional `active`, `crossfade` (Standard
+    /// "500ms") und `curve` (`equal_power` | `linear`).
+    pub fn from_config(name: &str, config: &HashMap<String, Value>) -> Result<Self> {
+        let sources: Vec<&str> = config
+            .get("sources")
+            .and_then(|v| v.as_array())
+            .map(|sources| sources.iter().filter_map(|v| v.as_str()).collect())
+            .unwrap_or_default();
+        if sources.is_empty() {
+            bail!(
+                "processor '{}': config.sources must list at least one buffer",
+                name
+            );
+        }
+        let mut switcher = Self::new(name, &sources);
+        let mut initial = config.clone();
+        // Beim Start ohne Überblendung auf die gewünschte Quelle
+        if let Some(active) = initial.remove("active") {
+            switcher.active = switcher.source_index(&active)?;
+        }
+        switcher.apply(&initial)?;
+        Ok(switcher)
+    }
+
+    pub fn active(&self) -> &str {
+        &self.sources[self.active].name
+    }
+
+    pub fn is_fading(&self) -> bool {
+        self.fade.is_some()
+    }
+
+    pub fn switches(&self) -> u64 {
+        self.switches
+    }
+
+    /// Schaltet auf `source` um (mit Überblendung, falls konfiguriert).
+    pub fn select(&mut self, source: &str) -> Result<()> {
+        let index = self.source_index(&Value::String(source.to_string()))?;
+        if index == self.active {
+            return Ok(());
+        }
+        let from = self.active;
+        self.active = index;
+        self.switches += 1;
+        // Laufende Überblendung wird abgebrochen, ausgeblendet wird die
+        // bisher aktive Quelle
+        self.fade = (!self.crossfade.is_zero()).then_some(Fade { from, position: 0 });
+        self.info(&format!(
+            "Switching '{}' -> '{}' ({} ms crossfade)",
+            self.sources[from].name,
+            self.sources[index].name,
+            self.crossfade.as_millis()
+        ));
+        self.emit_event(
+            "source_switched",
+            EventPriority::Info,
+            serde_json::json!({
+                "processor": self.name,
+                "from": self.sources[from].name,
+                "to": self.sources[index].name,
+                "crossfade_ms": self.crossfade.as_millis() as u64,
+            }),
+        );
+        Ok(())
+    }
+
+    fn source_index(&self, value: &Value) -> Result<usize> {
+        let name = value.as_str().unwrap_or_default();
+        self.sources
+            .iter()
+            .position(|source| source.name == name || source.buffer_name == name)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "processor '{}': unknown source '{}' (configured: {})",
+                    self.name,
+                    name,
+                    self.sources
+                        .iter()
+                        .map(|source| source.name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            })
+    }
+
+    fn apply(&mut self, config: &HashMap<String, Value>) -> Result<()> {
+        let values = ConfigValues::new("processor", &self.name, config);
+        if let Some(crossfade) = values.duration("crossfade")? {
+            self.crossfade = crossfade;
+        }
+        if let Some(curve) = config.get("curve") {
+            self.curve = match curve.as_str() {
+                Some("equal_power") => CrossfadeCurve::EqualPower,
+                Some("linear") => CrossfadeCurve::Linear,
+                _ => bail!(
+                    "processor '{}': config.curve must be equal_power or linear",
+                    self.name
+                ),
+            };
+        }
+        if let Some(active) = config.get("active") {
+            let index = self.source_index(active)?;
+            let name = self.sources[index].name.clone();
+            self.select(&name)?;
+        }
+        Ok(())
+    }
+
+    fn buffer(&mut self, index: usize) -> Option<Arc<AudioRingBuffer>> {
+        let source = &mut self.sources[index];
+        if source.buffer.is_none() {
+            source.buffer = self
+                .registry
+                .as_ref()
+                .and_then(|registry| registry.get(&source.buffer_name));
+        }
+        source.buffer.clone()
+    }
+
+    fn switch_frames(&mut self) -> Vec<PcmFrame> {
+        let Some(active) = self.buffer(self.active) else {
+            if self.errors == 0 {
+                self.warn(&format!(
+                    "Source buffer '{}' not found in registry",
+                    self.sources[self.active].buffer_name
+                ));
+            }
+            self.errors += 1;
+            return Vec::new();
+        };
+        let reader_id = self.sources[self.active].reader_id.clone();
+        let mut frames =
+            active.pop_batch_for_reader(&reader_id, active.available_for_reader(&reader_id));
+
+        if let Some(fade) = self.fade.take() {
+            self.fade = self.crossfade_frames(fade, &mut frames);
+        }
+
+        // Übrige Quellen leeren, damit ein späterer Wechsel aktuell einsetzt
+        let fading_from = self.fade.as_ref().map(|fade| fade.from);
+        for index in 0..self.sources.len() {
+            if index == self.active || Some(index) == fading_from {
+                continue;
+            }
+            if let Some(buffer) = self.buffer(index) {
+                let reader_id = &self.sources[index].reader_id;
+                buffer.pop_batch_for_reader(reader_id, buffer.available_for_reader(reader_id));
+            }
+        }
+        frames
+    }
+
+    /// Mischt die ausgehende Quelle Frame für Frame unter; `None`, sobald
+    /// die Überblendung fertig ist.
+    fn crossfade_frames(&mut self, mut fade: Fade, frames: &mut [PcmFrame]) -> Option<Fade> {
+        let Some(rate) = frames.first().map(|frame| frame.sample_rate) else {
+            return Some(fade);
+        };
+        let total = (self.crossfade.as_secs_f64() * rate as f64).max(1.0) as u64;
+        let outgoing = self.buffer(fade.from);
+        let reader_id = self.sources[fade.from].reader_id.clone();
+        for frame in frames.iter_mut() {
+            if fade.position >= total {
+                break;
+            }
+            let previous = outgoing
+                .as_ref()
+                .and_then(|buffer| buffer.pop_for_reader(&reader_id))
+                .filter(|previous| {
+                    previous.channels == frame.channels && previous.sample_rate == frame.sample_rate
+                });
+            let channels = frame.channels.max(1) as usize;
+            for (n, samples) in frame.samples.chunks_exact_mut(channels).enumerate() {
+                let t = ((fade.position + n as u64) as f32 / total as f32).min(1.0);
+                let (gain_out, gain_in) = self.curve.gains(t);
+                for (c, sample) in samples.iter_mut().enumerate() {
+                    let old = previous
+                        .as_ref()
+                        .and_then(|previous| previous.samples.get(n * channels + c))
+                        .copied()
+                        .unwrap_or(0) as f32;
+                    let value = *sample as f32 * gain_in + old * gain_out;
+                    *sample = value.round().clamp(-32768.0, 32767.0) as i16;
+                }
+            }
+            fade.position += (frame.samples.len() / channels) as u64;
+        }
+        if fade.position >= total {
+            self.debug("Crossfade finished");
+            None
+        } else {
+            Some(fade)
+        }
+    }
+}
+
+impl Processor for Switcher {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn process(
+        &mut self,
+        input_buffer: &AudioRingBuffer,
+        output_buffer: &AudioRingBuffer,
+    ) -> Result<()> {
+        // Flow-Eingang nur als Takt
+        while input_buffer.pop().is_some() {}
+        let frames = self.switch_frames();
+        if !frames.is_empty() {
+            output_buffer.push_batch(frames);
+        }
+        Ok(())
+    }
+
+    fn status(&self) -> ProcessorStatus {
+        ProcessorStatus {
+            running: self.registry.is_some(),
+            processing_rate_hz: 0.0,
+            latency_ms: 0.0,
+            errors: self.errors,
+            metrics: None,
+        }
+    }
+
+    /// Teil-Updates: `{"active": "backup"}`, `{"crossfade": "2s"}`; beides
+    /// zusammen blendet schon mit der neuen Zeit über.
+    fn update_config(&mut self, config: Value) -> Result<()> {
+        let Value::Object(map) = config else {
+            bail!("switcher config must be an object");
+        };
+        let map: HashMap<String, Value> = map.into_iter().collect();
+        self.apply(&map)
+    }
+
+    fn attach_buffer_registry(&mut self, registry: Arc<BufferRegistry>) {
+        for source in &mut self.sources {
+            source.buffer = None;
+        }
+        self.registry = Some(registry);
+    }
+
+    fn attach_event_emitter(&mut self, emitter: EventEmitter) {
+        self.emitter = Some(emitter);
+    }
+
+    fn event_emitter(&self) -> Option<&EventEmitter> {
+        self.emitter.as_ref()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl ComponentLogger for Switcher {
+    fn log_context(&self) -> LogContext {
+        LogContext::new("Switcher", &self.name)
+    }
+}
+
+impl_connectable_processor!(Switcher);
@@ -0,0 +1,385 @@
+// src/processors/wasm.rs
+//
+// WebAssembly-Processor: lädt ein Modul (`.wasm` oder Textformat `.wat`)
+// und ruft es für jeden Frame auf, eigene DSP- oder Analyse-Logik ohne
+// Neubau von airlift-node. Ausgeführt wird im Interpreter (wasmi), ohne
+// WASI und ohne Zugriff auf Dateien oder Netz.
+//
+// ABI (alle Zahlen i32, sofern nicht anders angegeben):
+// - Export `memory`
+// - Export `alloc(bytes) -> ptr`: Puffer für die Samples; wird erneut
+//   aufgerufen, wenn ein Frame mehr Platz braucht
+// - Export `process(ptr, samples, channels, sample_rate) -> samples`:
+//   bearbeitet interleaved i16 (little endian) an Ort und Stelle; Rückgabe
+//   ist die neue Sample-Zahl (höchstens `samples`, 0 verwirft den Frame,
+//   negativ = Fehler)
+// - optionaler Export `set_param(name_ptr, name_len, value: f64)` für
+//   `params` aus der Config und Automation
+// - optionaler Import `env.log(ptr, len)` schreibt UTF-8 ins Log
+//
+// `fuel` begrenzt die Instruktionen je Frame; Traps und Fehler zählen unter
+// `errors`, der Frame geht dann unverändert weiter.
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde_json::Value;
+use wasmi::{Caller, Config, Engine, Linker, Memory, Module, Store, TypedFunc};
+
+use crate::config::ConfigValues;
+use crate::core::logging::{ComponentLogger, LogContext};
+use crate::core::processor::{Processor, ProcessorStatus};
+use crate::core::ringbuffer::{AudioRingBuffer, PcmFrame};
+use crate::impl_connectable_processor;
+
+const DEFAULT_FUEL: u64 = 50_000_000;
+
+/// Instanziiertes Modul samt Exporten
+struct WasmInstance {
+    store: Store<String>,
+    memory: Memory,
+    alloc: TypedFunc<i32, i32>,
+    process: TypedFunc<(i32, i32, i32, i32), i32>,
+    set_param: Option<TypedFunc<(i32, i32, f64), ()>>,
+    /// Vom Modul geholter Puffer: Zeiger und Größe in Bytes
+    buffer: Option<(i32, usize)>,
+}
+
+impl WasmInstance {
+    fn load(name: &str, path: &Path) -> Result<Self> {
+        let bytes = std::fs::read(path)
+            .with_context(|| format!("failed to read wasm module {:?}", path))?;
+        // `.wat` wird übersetzt, Binärmodule gehen unverändert durch
+        let wasm = wat::parse_bytes(&bytes)
+            .map_err(|e| anyhow!("invalid wasm module {:?}: {}", path, e))?;
+
+        let mut config = Config::default();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config);
+        let module = Module::new(&engine, &wasm[..])
+            .map_err(|e| anyhow!("invalid wasm module {:?}: {}", path, e))?;
+        let mut store = Store::new(&engine, name.to_string());
+        store.set_fuel(DEFAULT_FUEL).map_err(|e| anyhow!("{}", e))?;
+
+        let mut linker = <Linker<String>>::new(&engine);
+        linker
+            .func_wrap(
+                "env",
+                "log",
+                |caller: Caller<'_, String>, ptr: i32, len: i32| {
+                    let Some(memory) = caller.get_export("memory").and_then(|e| e.into_memory())
+                    else {
+                        return;
+                    };
+                    let data = memory.data(&caller);
+                    let start = ptr as usize;
+                    if let Some(message) = data.get(start..start.saturating_add(len as usize)) {
+                        log::info!("[{}] {}", caller.data(), String::from_utf8_lossy(message));
+                    }
+                },
+            )
+            .map_err(|e| anyhow!("{}", e))?;
+        let instance = linker
+            .instantiate(&mut store, &module)
+            .and_then(|pre| pre.start(&mut store))
+            .map_err(|e| anyhow!("failed to instantiate wasm module {:?}: {}", path, e))?;
+
+        let memory = instance
+            .get_memory(&store, "memory")
+            .with_context(|| format!("wasm module {:?} must export 'memory'", path))?;
+        let alloc = instance
+            .get_typed_func::<i32, i32>(&store, "alloc")
+            .map_err(|e| anyhow!("wasm module {:?}: export 'alloc': {}", path, e))?;
+        let process = instance
+            .get_typed_func::<(i32, i32, i32, i32), i32>(&store, "process")
+            .map_err(|e| anyhow!("wasm module {:?}: export 'process': {}", path, e))?;
+        let set_param = instance
+            .get_typed_func::<(i32, i32, f64), ()>(&store, "set_param")
+            .ok();
+        Ok(Self {
+            store,
+            memory,
+            alloc,
+            process,
+            set_param,
+            buffer: None,
+        })
+    }
+
+    /// Puffer mit mindestens `bytes` Bytes im Modul-Speicher
+    fn buffer(&mut self, bytes: usize) -> Result<i32> {
+        match self.buffer {
+            Some((ptr, capacity)) if capacity >= bytes => Ok(ptr),
+            _ => {
+                let ptr = self
+                    .alloc
+                    .call(&mut self.store, bytes as i32)
+                    .map_err(|e| anyhow!("alloc failed: {}", e))?;
+                if ptr <= 0 {
+                    bail!("alloc returned {}", ptr);
+                }
+                self.buffer = Some((ptr, bytes));
+                Ok(ptr)
+            }
+        }
+    }
+
+    fn set_param(&mut self, name: &str, value: f64, fuel: u64) -> Result<bool> {
+        let Some(set_param) = self.set_param else {
+            return Ok(false);
+        };
+        self.store.set_fuel(fuel).map_err(|e| anyhow!("{}", e))?;
+        let ptr = self.buffer(name.len().max(1))?;
+        self.memory
+            .write(&mut self.store, ptr as usize, name.as_bytes())
+            .map_err(|e| anyhow!("memory write failed: {}", e))?;
+        set_param
+            .call(&mut self.store, (ptr, name.len() as i32, value))
+            .map_err(|e| anyhow!("set_param failed: {}", e))?;
+        Ok(true)
+    }
+
+    fn process(&mut self, frame: &mut PcmFrame, fuel: u64) -> Result<bool> {
+        self.store.set_fuel(fuel).map_err(|e| anyhow!("{}", e))?;
+        let bytes: Vec<u8> = frame
+            .samples
+            .iter()
+            .flat_map(|sample| sample.to_le_bytes())
+            .collect();
+        let ptr = self.buffer(bytes.len().max(2))?;
+        self.memory
+            .write(&mut self.store, ptr as usize, &bytes)
+            .map_err(|e| anyhow!("memory write failed: {}", e))?;
+        let samples = self
+            .process
+            .call(
+                &mut self.store,
+                (
+                    ptr,
+                    frame.samples.len() as i32,
+                    frame.channels as i32,
+                    frame.sample_rate as i32,
+                ),
+            )
+            .map_err(|e| anyhow!("process failed: {}", e))?;
+        if samples < 0 || samples as usize > frame.samples.len() {
+            bail!("process returned {}", samples);
+        }
+        let mut out = vec![0u8; samples as usize * 2];
+        self.memory
+            .read(&self.store, ptr as usize, &mut out)
+            .map_err(|e| anyhow!("memory read failed: {}", e))?;
+        frame.samples = out
+            .chunks_exact(2)
+            .map(|bytes| i16::from_le_bytes([bytes[0], bytes[1]]))
+            .collect();
+        Ok(samples > 0)
+    }
+}
+
+pub struct WasmProcessor {
+    name: String,
+    module: PathBuf,
+    instance: WasmInstance,
+    fuel: u64,
+    params: HashMap<String, f64>,
+    enabled: bool,
+    errors: u64,
+    last_error: Option<String>,
+}
+
+/// Gelesene `config`-Schlüssel (Strict-Modus, siehe `config::options`)
+pub const CONFIG_KEYS: &[&str] = &["enabled", "module", "params", "fuel"];
+
+impl WasmProcessor {
+    pub fn load(name: &str, module: &Path) -> Result<Self> {
+        Ok(Self {
+            name: name.to_string(),
+            module: module.to_path_buf(),
+            instance: WasmInstance::load(name, module)?,
+            fuel: DEFAULT_FUEL,
+            params: HashMap::new(),
+            enabled: true,
+            errors: 0,
+            last_error: None,
+        })
+    }
+
+    /// Erwartet `module` (Pfad zu `.wasm`/`.wat`); optional `params`
+    /// (Name → Zahl, über `set_param`), `fuel` (Instruktionen je Frame) und
+    /// `enabled`.
+    pub fn from_config(name: &str, config: &HashMap<String, Value>) -> Result<Self> {
+        let module = config
+            .get("module")
+            .and_then(|v| v.as_str())
+            .with_context(|| format!("processor '{}': config.module is required", name))?;
+        let mut processor = Self::load(name, Path::new(module))?;
+        let mut config = config.clone();
+        config.remove("module");
+        processor.apply(&config)?;
+        Ok(processor)
+    }
+
+    pub fn errors(&self) -> u64 {
+        self.errors
+    }
+
+    fn apply(&mut self, config: &HashMap<String, Value>) -> Result<()> {
+        let values = ConfigValues::new("processor", &self.name, config);
+        if let Some(fuel) = values.f64("fuel")? {
+            self.fuel = values.check_range("fuel", fuel, 1_000.0, 1e12)? as u64;
+        }
+        let mut params = self.params.clone();
+        if let Some(value) = config.get("params") {
+            let Some(table) = value.as_object() else {
+                bail!("processor '{}': config.params must be a table", self.name);
+            };
+            for (name, value) in table {
+                let Some(value) = value.as_f64() else {
+                    bail!(
+                        "processor '{}': config.params.{} must be a number",
+                        self.name,
+                        name
+                    );
+                };
+                params.insert(name.clone(), value);
+            }
+        }
+        let module = match config.get("module") {
+            Some(Value::String(module)) => Some(PathBuf::from(module)),
+            Some(_) => bail!("processor '{}': config.module must be a path", self.name),
+            None => None,
+        };
+        if module.is_some() || config.contains_key("params") {
+            // Erst laden und parametrieren, dann tauschen: ein kaputtes Modul
+            // ersetzt kein laufendes
+            let mut fresh = match &module {
+                Some(path) => Some(WasmInstance::load(&self.name, path)?),
+                None => None,
+            };
+            let instance = fresh.as_mut().unwrap_or(&mut self.instance);
+            for (name, value) in &params {
+                if !instance.set_param(name, *value, self.fuel)? {
+                    bail!(
+                        "processor '{}': module does not export 'set_param'",
+                        self.name
+                    );
+                }
+            }
+            if let (Some(fresh), Some(module)) = (fresh, module) {
+                self.instance = fresh;
+                self.module = module;
+            }
+            self.params = params;
+        }
+        if let Some(enabled) = config.get("enabled").and_then(|v| v.as_bool()) {
+            self.enabled = enabled;
+        }
+        Ok(())
+    }
+
+    fn record_error(&mut self, error: anyhow::Error) {
+        self.errors += 1;
+        let message = format!("{:#}", error);
+        // Gleicher Fehler in jedem Frame: nur beim ersten Mal loggen
+        if self.last_error.as_deref() != Some(message.as_str()) {
+            self.warn(&format!("{}, passing frames through", message));
+        }
+        self.last_error = Some(message);
+    }
+
+    /// `false`, wenn das Modul den Frame verworfen hat
+    fn run(&mut self, frame: &mut PcmFrame) -> bool {
+        if !self.enabled || frame.samples.is_empty() {
+            return true;
+        }
+        let original = frame.samples.clone();
+        match self.instance.process(frame, self.fuel) {
+            Ok(keep) => keep,
+            Err(e) => {
+                frame.samples = original;
+                self.record_error(e);
+                true
+            }
+        }
+    }
+}
+
+impl Processor for WasmProcessor {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn process(
+        &mut self,
+        input_buffer: &AudioRingBuffer,
+        output_buffer: &AudioRingBuffer,
+    ) -> Result<()> {
+        while let Some(mut frame) = input_buffer.pop() {
+            if self.run(&mut frame) {
+                output_buffer.push(frame);
+            }
+        }
+        Ok(())
+    }
+
+    fn supports_batch(&self) -> bool {
+        true
+    }
+
+    fn process_batch(&mut self, frames: &mut Vec<PcmFrame>) -> Result<()> {
+        frames.retain_mut(|frame| self.run(frame));
+        Ok(())
+    }
+
+    fn status(&self) -> ProcessorStatus {
+        ProcessorStatus {
+            running: self.enabled,
+            processing_rate_hz: 0.0,
+            latency_ms: 0.0,
+            errors: self.errors,
+            metrics: Some(serde_json::json!({
+                "module": self.module,
+                "params": self.params,
+                "last_error": self.last_error,
+            })),
+        }
+    }
+
+    /// Teil-Updates: `{"params": {"gain": 0.5}}`, `{"module": "neu.wasm"}`
+    fn update_config(&mut self, config: Value) -> Result<()> {
+        let Value::Object(map) = config else {
+            bail!("wasm config must be an object");
+        };
+        let map: HashMap<String, Value> = map.into_iter().collect();
+        self.apply(&map)
+    }
+
+    /// Automation geht direkt an `set_param`, ohne Config-Umweg
+    fn set_parameter(&mut self, name: &str, value: f64) -> Result<()> {
+        if !self.instance.set_param(name, value, self.fuel)? {
+            bail!(
+                "processor '{}': module does not export 'set_param'",
+                self.name
+            );
+        }
+        self.params.insert(name.to_string(), value);
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl ComponentLogger for WasmProcessor {
+    fn log_context(&self) -> LogContext {
+        LogContext::new("Wasm", &self.name)
+    }
+}
+
+impl_connectable_processor!(WasmProcessor);
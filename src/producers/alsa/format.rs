@@ -0,0 +1,146 @@
+// src/producers/alsa/format.rs
+//
+// Sampleformate, die der ALSA-Producer aushandeln kann. Intern läuft die
+// Pipeline mit s16; 24-Bit-, 32-Bit- und Float-Geräte werden beim Lesen
+// über `RawFormat::decode` gewandelt.
+use anyhow::bail;
+
+use crate::core::device_scanner::{AudioFormat, SampleType};
+use crate::producers::pipe::RawFormat;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlsaSampleFormat {
+    S16,
+    /// 24 Bit in 3 Bytes (S24_3LE), typisch für USB-Interfaces
+    S24_3,
+    S32,
+    Float,
+}
+
+impl AlsaSampleFormat {
+    /// Reihenfolge bei `format = "auto"`: s16 zuerst, da ohne Wandlung.
+    pub const AUTO_ORDER: [Self; 4] = [Self::S16, Self::S32, Self::S24_3, Self::Float];
+
+    pub fn parse(name: &str) -> anyhow::Result<Option<Self>> {
+        match name.to_ascii_lowercase().as_str() {
+            "auto" => Ok(None),
+            "s16" | "s16_le" | "s16le" => Ok(Some(Self::S16)),
+            "s24_3le" | "s24_3" | "s24" => Ok(Some(Self::S24_3)),
+            "s32" | "s32_le" | "s32le" => Ok(Some(Self::S32)),
+            "float" | "float_le" | "f32" | "f32le" => Ok(Some(Self::Float)),
+            other => bail!("unknown ALSA format '{}' (auto, s16, s24_3le, s32, float)", other),
+        }
+    }
+
+    /// Kandidaten in Verhandlungsreihenfolge; ein konfiguriertes Format
+    /// wird bevorzugt, die übrigen bleiben als Fallback.
+    pub fn candidates(preferred: Option<Self>) -> Vec<Self> {
+        let mut order = Vec::with_capacity(Self::AUTO_ORDER.len());
+        order.extend(preferred);
+        order.extend(Self::AUTO_ORDER.iter().copied().filter(|f| Some(*f) != preferred));
+        order
+    }
+
+    pub fn alsa_format(self) -> alsa::pcm::Format {
+        match self {
+            Self::S16 => alsa::pcm::Format::S16LE,
+            Self::S24_3 => alsa::pcm::Format::S243LE,
+            Self::S32 => alsa::pcm::Format::S32LE,
+            Self::Float => alsa::pcm::Format::FloatLE,
+        }
+    }
+
+    pub fn raw_format(self) -> RawFormat {
+        match self {
+            Self::S16 => RawFormat::S16Le,
+            Self::S24_3 => RawFormat::S24Le,
+            Self::S32 => RawFormat::S32Le,
+            Self::Float => RawFormat::F32Le,
+        }
+    }
+
+    pub fn bytes_per_sample(self) -> usize {
+        self.raw_format().bytes_per_sample()
+    }
+
+    pub fn bit_depth(self) -> u8 {
+        match self {
+            Self::S16 => 16,
+            Self::S24_3 => 24,
+            Self::S32 | Self::Float => 32,
+        }
+    }
+
+    pub fn sample_type(self) -> SampleType {
+        match self {
+            Self::Float => SampleType::Float,
+            _ => SampleType::SignedInteger,
+        }
+    }
+
+    /// Gegenstück zu den Formaten, die `AlsaDeviceScanner` meldet.
+    pub fn from_audio_format(format: &AudioFormat) -> Option<Self> {
+        match (&format.sample_type, format.bit_depth) {
+            (SampleType::SignedInteger, 16) => Some(Self::S16),
+            (SampleType::SignedInteger, 24) => Some(Self::S24_3),
+            (SampleType::SignedInteger, 32) => Some(Self::S32),
+            (SampleType::Float, 32) => Some(Self::Float),
+            _ => None,
+        }
+    }
+
+    pub fn audio_format(self, sample_rate: u32, channels: u8) -> AudioFormat {
+        AudioFormat {
+            sample_rate,
+            channels,
+            sample_type: self.sample_type(),
+            bit_depth: self.bit_depth(),
+        }
+    }
+
+    /// s16-Samples aus einem interleaved Lesepuffer.
+    pub fn decode(self, bytes: &[u8]) -> Vec<i16> {
+        self.raw_format().decode(bytes)
+    }
+}
+
+impl std::fmt::Display for AlsaSampleFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::S16 => "S16_LE",
+            Self::S24_3 => "S24_3LE",
+            Self::S32 => "S32_LE",
+            Self::Float => "FLOAT_LE",
+        })
+    }
+}
+
+/// Alle Formate, die das Gerät laut Hardware-Parametern annimmt.
+pub fn supported_sample_formats(hwp: &alsa::pcm::HwParams) -> Vec<AlsaSampleFormat> {
+    AlsaSampleFormat::AUTO_ORDER
+        .iter()
+        .copied()
+        .filter(|format| hwp.test_format(format.alsa_format()).is_ok())
+        .collect()
+}
+
+/// Setzt das erste vom Gerät unterstützte Format aus `candidates(preferred)`.
+pub fn negotiate_format(
+    hwp: &alsa::pcm::HwParams,
+    preferred: Option<AlsaSampleFormat>,
+) -> anyhow::Result<AlsaSampleFormat> {
+    let supported = supported_sample_formats(hwp);
+    for format in AlsaSampleFormat::candidates(preferred) {
+        if supported.contains(&format) && hwp.set_format(format.alsa_format()).is_ok() {
+            if let Some(wanted) = preferred.filter(|p| *p != format) {
+                log::warn!(
+                    "ALSA format {} not supported by device, falling back to {}",
+                    wanted,
+                    format
+                );
+            }
+            return Ok(format);
+        }
+    }
+    bail!("device supports none of S16_LE, S24_3LE, S32_LE, FLOAT_LE")
+}
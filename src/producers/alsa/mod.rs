@@ -1,7 +1,9 @@
+pub mod format;
 mod output_capture;
 pub mod producer;
 mod scanner;
 
+pub use format::AlsaSampleFormat;
 pub use output_capture::AlsaOutputCapture;
 pub use producer::AlsaProducer;
 pub use scanner::AlsaDeviceScanner;
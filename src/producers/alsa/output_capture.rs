@@ -109,10 +109,12 @@ impl crate::core::Producer for AlsaOutputCapture {
     fn status(&self) -> crate::core::ProducerStatus {
         crate::core::ProducerStatus {
             running: self.running.load(Ordering::Relaxed),
+            paused: false,
             connected: true,
             samples_processed: self.samples_processed.load(Ordering::Relaxed),
             errors: 0,
             buffer_stats: self.ring_buffer.as_ref().map(|b| b.stats()),
+            decoder: None,
         }
     }
 
@@ -233,6 +235,7 @@ impl AlsaOutputCapture {
                                 samples: chunk_samples.clone(),
                                 sample_rate,
                                 channels: channels as u8,
+                                metadata: Default::default(),
                             };
                             rb.push(frame);
                         }
@@ -277,6 +280,7 @@ impl AlsaOutputCapture {
                         samples: chunk_samples,
                         sample_rate,
                         channels: channels as u8,
+                        metadata: Default::default(),
                     };
                     rb.push(frame);
                 }
@@ -1,16 +1,33 @@
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+use crate::core::lock::lock_mutex;
+use crate::producers::alsa::format::{negotiate_format, AlsaSampleFormat};
 use crate::producers::wait::StopWait;
 
 // Logging/idle timing constants to avoid magic numbers in capture loops.
 const BUFFER_LOG_EVERY_NS: u64 = 5_000_000_000;
 const STOP_WAIT_IDLE_MS: u64 = 1;
 const STOP_WAIT_ERROR_MS: u64 = 10;
-const DEMO_TICK_INTERVAL_MS: u64 = 100;
-const DEMO_LOG_EVERY_TICKS: u64 = 10;
+
+/// Gewünschtes Format aus der Config; `sample_format: None` = automatisch.
+#[derive(Debug, Clone, Copy)]
+struct CaptureRequest {
+    sample_rate: u32,
+    channels: u32,
+    sample_format: Option<AlsaSampleFormat>,
+}
+
+/// Mit dem Gerät ausgehandeltes Format, nach dem `capture_frames` liest.
+#[derive(Debug, Clone, Copy)]
+struct NegotiatedFormat {
+    sample_format: AlsaSampleFormat,
+    sample_rate: u32,
+    channels: usize,
+    period_frames: usize,
+}
 
 pub struct AlsaProducer {
     name: String,
@@ -22,12 +39,25 @@ pub struct AlsaProducer {
     stop_wait: Arc<StopWait>,
     sample_rate: u32,
     channels: u8,
+    /// `config.format`; `None` = automatisch
+    format: Option<AlsaSampleFormat>,
+    negotiated_format: Arc<Mutex<Option<AlsaSampleFormat>>>,
 }
 
+/// Gelesene `config`-Schlüssel (Strict-Modus, siehe `config::options`)
+pub const CONFIG_KEYS: &[&str] = &["format"];
+
 impl AlsaProducer {
     pub fn new(name: &str, config: &crate::config::ProducerConfig) -> Result<Self> {
         let sample_rate = config.sample_rate.unwrap_or(44100);
         let channels = config.channels.unwrap_or(2);
+        let format = match config.config.get("format") {
+            None => None,
+            Some(value) => AlsaSampleFormat::parse(value.as_str().ok_or_else(|| {
+                anyhow!("producer '{}': config.format must be a string", name)
+            })?)
+            .map_err(|e| anyhow!("producer '{}': {}", name, e))?,
+        };
 
         Ok(Self {
             name: name.to_string(),
@@ -39,9 +69,16 @@ impl AlsaProducer {
             stop_wait: Arc::new(StopWait::new()),
             sample_rate,
             channels,
+            format,
+            negotiated_format: Arc::new(Mutex::new(None)),
         })
     }
 
+    /// Mit dem Gerät ausgehandeltes Format (nach dem Start).
+    pub fn negotiated_format(&self) -> Option<AlsaSampleFormat> {
+        *lock_mutex(&self.negotiated_format, "alsa.negotiated_format")
+    }
+
     pub fn utc_ns_now() -> u64 {
         let d = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
         d.as_secs() * 1_000_000_000 + d.subsec_nanos() as u64
@@ -67,10 +104,11 @@ impl crate::core::Producer for AlsaProducer {
             .unwrap_or_else(|| "default".to_string());
 
         log::info!(
-            "ALSA config: device={}, rate={}, channels={}",
+            "ALSA config: device={}, rate={}, channels={}, format={}",
             device,
             self.sample_rate,
-            self.channels
+            self.channels,
+            self.format.map_or("auto".to_string(), |f| f.to_string())
         );
 
         self.running.store(true, Ordering::SeqCst);
@@ -80,15 +118,19 @@ impl crate::core::Producer for AlsaProducer {
         let samples_processed = self.samples_processed.clone();
         let name = self.name.clone();
         let ring_buffer = self.ring_buffer.clone();
-        let sample_rate = self.sample_rate;
-        let channels = self.channels;
+        let request = CaptureRequest {
+            sample_rate: self.sample_rate,
+            channels: self.channels as u32,
+            sample_format: self.format,
+        };
+        let negotiated = self.negotiated_format.clone();
         let stop_wait = self.stop_wait.clone();
 
         let handle = std::thread::spawn(move || {
             if let Err(e) = Self::run_alsa_capture(
                 &device,
-                sample_rate,
-                channels as u32,
+                request,
+                negotiated,
                 running.clone(),
                 samples_processed.clone(),
                 ring_buffer,
@@ -120,10 +162,12 @@ impl crate::core::Producer for AlsaProducer {
     fn status(&self) -> crate::core::ProducerStatus {
         crate::core::ProducerStatus {
             running: self.running.load(Ordering::Relaxed),
+            paused: false,
             connected: true,
             samples_processed: self.samples_processed.load(Ordering::Relaxed),
             errors: 0,
             buffer_stats: self.ring_buffer.as_ref().map(|b| b.stats()),
+            decoder: None,
         }
     }
 
@@ -135,15 +179,15 @@ impl crate::core::Producer for AlsaProducer {
 impl AlsaProducer {
     fn run_alsa_capture(
         device: &str,
-        sample_rate: u32,
-        channels: u32,
+        request: CaptureRequest,
+        negotiated: Arc<Mutex<Option<AlsaSampleFormat>>>,
         running: Arc<AtomicBool>,
         samples_processed: Arc<AtomicU64>,
         ring_buffer: Option<Arc<crate::core::AudioRingBuffer>>,
         stop_wait: Arc<StopWait>,
     ) -> Result<()> {
         use alsa::{
-            pcm::{Access, Format, HwParams, PCM},
+            pcm::{Access, HwParams, PCM},
             Direction, ValueOr,
         };
 
@@ -154,85 +198,75 @@ impl AlsaProducer {
         let hwp = HwParams::any(&pcm)?;
         hwp.set_access(Access::RWInterleaved)?;
 
-        // Format versuchen
-        let format_result = hwp
-            .set_format(Format::s16())
-            .or_else(|_| hwp.set_format(Format::s32()))
-            .or_else(|_| hwp.set_format(Format::float()));
-
-        if let Err(e) = format_result {
-            log::error!("No supported format found: {}", e);
-            anyhow::bail!("Unsupported format for device: {}", device);
-        }
+        let sample_format = negotiate_format(&hwp, request.sample_format)
+            .with_context(|| format!("Unsupported format for device: {}", device))?;
 
-        hwp.set_channels(channels)?;
-        hwp.set_rate(sample_rate, ValueOr::Nearest)?;
+        hwp.set_channels(request.channels)?;
+        hwp.set_rate(request.sample_rate, ValueOr::Nearest)?;
 
         let period_frames = hwp.set_period_size_near(480, ValueOr::Nearest)?;
         let _buffer_size = hwp.set_buffer_size_near(period_frames * 4)?;
 
         pcm.hw_params(&hwp)?;
         pcm.prepare()?;
+        *lock_mutex(&negotiated, "alsa.negotiated_format") = Some(sample_format);
 
         log::info!(
-            "ALSA capture started: {}Hz, {}ch, period={} frames",
-            sample_rate,
-            channels,
+            "ALSA capture started: {}Hz, {}ch, {}, period={} frames",
+            request.sample_rate,
+            request.channels,
+            sample_format,
             period_frames
         );
 
-        if let Ok(io) = pcm.io_i16() {
-            Self::capture_i16(
-                io,
-                period_frames as usize,
-                channels as usize,
-                sample_rate,
-                running,
-                samples_processed,
-                ring_buffer,
-                stop_wait.clone(),
-            )?;
-        } else {
-            log::warn!("i16 capture failed, using demo mode");
-            Self::capture_demo(
-                sample_rate,
-                channels,
-                running,
-                samples_processed,
-                ring_buffer,
-                stop_wait,
-            )?;
-        }
+        let format = NegotiatedFormat {
+            sample_format,
+            sample_rate: request.sample_rate,
+            channels: request.channels as usize,
+            period_frames: period_frames as usize,
+        };
+        Self::capture_frames(
+            pcm.io_bytes(),
+            format,
+            running,
+            samples_processed,
+            ring_buffer,
+            stop_wait,
+        )?;
 
         log::info!("ALSA capture stopped");
         Ok(())
     }
 
-    fn capture_i16(
-        io: alsa::pcm::IO<i16>,
-        period_frames: usize,
-        channels: usize,
-        sample_rate: u32,
+    /// Liest interleaved im ausgehandelten Format und wandelt nach s16.
+    fn capture_frames(
+        io: alsa::pcm::IO<u8>,
+        format: NegotiatedFormat,
         running: Arc<AtomicBool>,
         samples_processed: Arc<AtomicU64>,
         ring_buffer: Option<Arc<crate::core::AudioRingBuffer>>,
         stop_wait: Arc<StopWait>,
     ) -> Result<()> {
+        let NegotiatedFormat {
+            sample_format,
+            sample_rate,
+            channels,
+            period_frames,
+        } = format;
         let target_frames = sample_rate as usize / 10; // 100ms
         let target_samples = target_frames * channels;
 
-        let period_samples = period_frames * channels;
-        let mut buffer = vec![0i16; period_samples];
+        let frame_bytes = channels * sample_format.bytes_per_sample();
+        let mut buffer = vec![0u8; period_frames * frame_bytes];
         let mut fifo: Vec<i16> = Vec::with_capacity(target_samples * 2);
+        let mut last_log = 0u64;
 
         while running.load(Ordering::Relaxed) {
             match io.readi(&mut buffer) {
                 Ok(frames) if frames > 0 => {
-                    let samples_read = frames as usize * channels;
-                    let slice = &buffer[..samples_read];
-
-                    fifo.extend_from_slice(slice);
-                    samples_processed.fetch_add(samples_read as u64, Ordering::Relaxed);
+                    let samples = sample_format.decode(&buffer[..frames * frame_bytes]);
+                    samples_processed.fetch_add(samples.len() as u64, Ordering::Relaxed);
+                    fifo.extend_from_slice(&samples);
 
                     // 100ms-Chunks verarbeiten
                     while fifo.len() >= target_samples {
@@ -242,22 +276,17 @@ impl AlsaProducer {
                         if let Some(rb) = &ring_buffer {
                             let frame = crate::core::PcmFrame {
                                 utc_ns: crate::core::timestamp::utc_ns_now(),
-                                samples: chunk_samples.clone(),
+                                samples: chunk_samples,
                                 sample_rate,
                                 channels: channels as u8,
+                                metadata: Default::default(),
                             };
                             let buffer_len = rb.push(frame);
 
-                            static mut LAST_LOG: u64 = 0;
-                            unsafe {
-                                let now = crate::core::utc_ns_now();
-                                if now - LAST_LOG >= BUFFER_LOG_EVERY_NS {
-                                    log::debug!(
-                                        "Pushed frame to buffer. Buffer size: {}",
-                                        buffer_len
-                                    );
-                                    LAST_LOG = now;
-                                }
+                            let now = crate::core::utc_ns_now();
+                            if now - last_log >= BUFFER_LOG_EVERY_NS {
+                                log::debug!("Pushed frame to buffer. Buffer size: {}", buffer_len);
+                                last_log = now;
                             }
                         }
                     }
@@ -273,45 +302,4 @@ impl AlsaProducer {
         }
         Ok(())
     }
-
-    fn capture_demo(
-        sample_rate: u32,
-        channels: u32,
-        running: Arc<AtomicBool>,
-        samples_processed: Arc<AtomicU64>,
-        ring_buffer: Option<Arc<crate::core::AudioRingBuffer>>,
-        stop_wait: Arc<StopWait>,
-    ) -> Result<()> {
-        log::warn!("Using demo mode - simulating audio");
-
-        let target_frames = sample_rate as usize / 10; // 100ms
-        let target_samples = target_frames * channels as usize;
-
-        let mut tick = 0;
-        while running.load(Ordering::Relaxed) {
-            stop_wait.wait_timeout(Duration::from_millis(DEMO_TICK_INTERVAL_MS));
-            tick += 1;
-
-            if tick % DEMO_LOG_EVERY_TICKS == 0 {
-                // Alle Sekunde
-                let chunk_samples = vec![0i16; target_samples];
-                samples_processed.fetch_add(target_samples as u64, Ordering::Relaxed);
-
-                // In RingBuffer speichern
-                if let Some(rb) = &ring_buffer {
-                    let frame = crate::core::PcmFrame {
-                        utc_ns: crate::core::timestamp::utc_ns_now(),
-                        samples: chunk_samples,
-                        sample_rate,
-                        channels: channels as u8,
-                    };
-                    rb.push(frame);
-                }
-
-                log::debug!("Demo: simulated {} samples", target_samples);
-            }
-        }
-
-        Ok(())
-    }
 }
@@ -1,4 +1,5 @@
 use crate::core::device_scanner::*;
+use crate::producers::alsa::format::supported_sample_formats;
 use anyhow::{Context, Result};
 use std::ffi::CStr;
 
@@ -114,25 +115,26 @@ impl DeviceScanner for AlsaDeviceScanner {
 
 impl AlsaDeviceScanner {
     fn get_supported_formats(&self, device_id: &str) -> Result<Vec<AudioFormat>> {
-        use alsa::{pcm::PCM, Direction};
+        use alsa::{
+            pcm::{HwParams, PCM},
+            Direction,
+        };
 
         let mut formats = Vec::new();
 
         // Try to open device to query formats
         let pcm = PCM::new(device_id, Direction::Capture, false)?;
-        let hw_params = pcm.hw_params_current()?;
+        let hw_params = HwParams::any(&pcm)?;
+        let channels = hw_params.get_channels_max().unwrap_or(2).min(2) as u8;
 
         // Common sample rates
         let sample_rates = [44100, 48000, 96000, 192000];
 
-        for &rate in &sample_rates {
-            if hw_params.test_rate(rate).is_ok() {
-                formats.push(AudioFormat {
-                    sample_rate: rate,
-                    channels: 2,
-                    sample_type: SampleType::SignedInteger,
-                    bit_depth: 16,
-                });
+        for sample_format in supported_sample_formats(&hw_params) {
+            for &rate in &sample_rates {
+                if hw_params.test_rate(rate).is_ok() {
+                    formats.push(sample_format.audio_format(rate, channels));
+                }
             }
         }
 
@@ -0,0 +1,449 @@
+// src/producers/cpal.rs
+//
+// Audio-Eingang über cpal: CoreAudio (macOS), WASAPI (Windows), unter Linux
+// ebenfalls ALSA. Config wie `alsa_input` – `device` ist der Gerätename, den
+// das Betriebssystem anzeigt (fehlt oder "default" = Standard-Eingang),
+// dazu `sample_rate` und `channels`. Der cpal-Stream ist nicht `Send` und lebt
+// deshalb in einem eigenen Thread; der Callback sammelt 100-ms-Frames.
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::Duration;
+
+use ::cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use anyhow::Result;
+
+use crate::config::{ConfigValues, ProducerConfig};
+use crate::core::{
+    AudioError, AudioRingBuffer, ErrorInfo, LastError, PcmFrame, Producer, ProducerStatus,
+};
+use crate::impl_connectable_producer;
+use crate::producers::wait::StopWait;
+
+/// So lange darf das Öffnen des Geräts dauern
+const OPEN_TIMEOUT: Duration = Duration::from_secs(5);
+const STOP_POLL: Duration = Duration::from_millis(100);
+
+/// Sampleformate, die wir vom Gerät übernehmen; in dieser Reihenfolge bevorzugt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CpalSampleFormat {
+    I16,
+    F32,
+    I32,
+    U16,
+}
+
+impl CpalSampleFormat {
+    fn from_cpal(format: ::cpal::SampleFormat) -> Option<Self> {
+        match format {
+            ::cpal::SampleFormat::I16 => Some(Self::I16),
+            ::cpal::SampleFormat::F32 => Some(Self::F32),
+            ::cpal::SampleFormat::I32 => Some(Self::I32),
+            ::cpal::SampleFormat::U16 => Some(Self::U16),
+            _ => None,
+        }
+    }
+
+    fn preference(self) -> u8 {
+        match self {
+            Self::I16 => 0,
+            Self::F32 => 1,
+            Self::I32 => 2,
+            Self::U16 => 3,
+        }
+    }
+}
+
+/// Vom Gerät gemeldeter Eingabe-Modus (Auszug aus `SupportedStreamConfigRange`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputMode {
+    pub channels: u16,
+    pub min_rate: u32,
+    pub max_rate: u32,
+    pub format: CpalSampleFormat,
+}
+
+/// Passender Modus für Kanäle und Rate, bevorzugt s16.
+pub fn pick_input_mode(modes: &[InputMode], channels: u8, sample_rate: u32) -> Option<InputMode> {
+    modes
+        .iter()
+        .filter(|mode| {
+            mode.channels == channels as u16
+                && mode.min_rate <= sample_rate
+                && sample_rate <= mode.max_rate
+        })
+        .min_by_key(|mode| mode.format.preference())
+        .copied()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CpalSettings {
+    /// `None` = Standard-Eingang des Systems
+    pub device: Option<String>,
+    pub sample_rate: u32,
+    pub channels: u8,
+}
+
+impl CpalSettings {
+    pub fn from_producer_config(name: &str, cfg: &ProducerConfig) -> Result<Self> {
+        let values = ConfigValues::new("producer", name, &cfg.config);
+        let device = cfg
+            .device
+            .as_deref()
+            .map(str::trim)
+            .filter(|device| !device.is_empty() && *device != "default")
+            .map(str::to_string);
+        let sample_rate = cfg.sample_rate.unwrap_or(44100);
+        values.check_range("sample_rate", sample_rate, 8_000, 192_000)?;
+        let channels = cfg.channels.unwrap_or(2);
+        values.check_range("channels", channels, 1, 8)?;
+        Ok(Self {
+            device,
+            sample_rate,
+            channels,
+        })
+    }
+}
+
+/// Namen aller Eingabegeräte des Standard-Hosts.
+pub fn input_device_names() -> Vec<String> {
+    ::cpal::default_host()
+        .input_devices()
+        .map(|devices| devices.filter_map(|device| device.name().ok()).collect())
+        .unwrap_or_default()
+}
+
+/// Sammelt Samples aus dem Callback zu Frames fester Länge.
+struct FrameAssembler {
+    fifo: Vec<i16>,
+    frame_samples: usize,
+    sample_rate: u32,
+    channels: u8,
+    ring: Option<Arc<AudioRingBuffer>>,
+    samples_processed: Arc<AtomicU64>,
+}
+
+impl FrameAssembler {
+    fn push(&mut self, samples: impl Iterator<Item = i16>) {
+        let before = self.fifo.len();
+        self.fifo.extend(samples);
+        self.samples_processed
+            .fetch_add((self.fifo.len() - before) as u64, Ordering::Relaxed);
+
+        while self.fifo.len() >= self.frame_samples {
+            let samples: Vec<i16> = self.fifo.drain(..self.frame_samples).collect();
+            if let Some(ring) = &self.ring {
+                ring.push(PcmFrame {
+                    utc_ns: crate::core::timestamp::utc_ns_now(),
+                    samples,
+                    sample_rate: self.sample_rate,
+                    channels: self.channels,
+                    metadata: Default::default(),
+                });
+            }
+        }
+    }
+}
+
+fn f32_to_i16(sample: f32) -> i16 {
+    (sample * i16::MAX as f32)
+        .round()
+        .clamp(i16::MIN as f32, i16::MAX as f32) as i16
+}
+
+pub struct CpalProducer {
+    name: String,
+    settings: CpalSettings,
+    running: Arc<AtomicBool>,
+    connected: Arc<AtomicBool>,
+    samples_processed: Arc<AtomicU64>,
+    errors: Arc<AtomicU64>,
+    last_error: Arc<LastError>,
+    ring: Option<Arc<AudioRingBuffer>>,
+    stop_wait: Arc<StopWait>,
+    thread_handle: Option<thread::JoinHandle<()>>,
+}
+
+impl CpalProducer {
+    pub fn new(name: &str, cfg: &ProducerConfig) -> Result<Self> {
+        Ok(Self::with_settings(name, CpalSettings::from_producer_config(name, cfg)?))
+    }
+
+    pub fn with_settings(name: &str, settings: CpalSettings) -> Self {
+        Self {
+            name: name.to_string(),
+            settings,
+            running: Arc::new(AtomicBool::new(false)),
+            connected: Arc::new(AtomicBool::new(false)),
+            samples_processed: Arc::new(AtomicU64::new(0)),
+            errors: Arc::new(AtomicU64::new(0)),
+            last_error: Arc::new(LastError::default()),
+            ring: None,
+            stop_wait: Arc::new(StopWait::new()),
+            thread_handle: None,
+        }
+    }
+
+    fn find_device(settings: &CpalSettings) -> Result<::cpal::Device, AudioError> {
+        let host = ::cpal::default_host();
+        let Some(wanted) = settings.device.as_deref() else {
+            return host
+                .default_input_device()
+                .ok_or_else(|| AudioError::device("no default input device"));
+        };
+        let devices = host
+            .input_devices()
+            .map_err(|e| AudioError::device(format!("enumerate input devices: {}", e)))?;
+        let mut available = Vec::new();
+        for device in devices {
+            match device.name() {
+                Ok(name) if name == wanted => return Ok(device),
+                Ok(name) => available.push(name),
+                Err(_) => {}
+            }
+        }
+        // Gerät kann gerade abgesteckt sein: wiederholbar
+        Err(AudioError::device(format!(
+            "input device '{}' not found (available: {})",
+            wanted,
+            available.join(", ")
+        )))
+    }
+
+    /// Öffnet Gerät und Stream; läuft im Capture-Thread.
+    fn open_stream(
+        name: &str,
+        settings: &CpalSettings,
+        mut assembler: FrameAssembler,
+        running: Arc<AtomicBool>,
+        errors: Arc<AtomicU64>,
+        last_error: Arc<LastError>,
+        stop_wait: Arc<StopWait>,
+    ) -> Result<(::cpal::Stream, String), AudioError> {
+        let device = Self::find_device(settings)?;
+        let device_name = device.name().unwrap_or_else(|_| "unknown".to_string());
+
+        let modes: Vec<InputMode> = device
+            .supported_input_configs()
+            .map_err(|e| AudioError::device(format!("query '{}': {}", device_name, e)))?
+            .filter_map(|range| {
+                Some(InputMode {
+                    channels: range.channels(),
+                    min_rate: range.min_sample_rate().0,
+                    max_rate: range.max_sample_rate().0,
+                    format: CpalSampleFormat::from_cpal(range.sample_format())?,
+                })
+            })
+            .collect();
+        let mode = pick_input_mode(&modes, settings.channels, settings.sample_rate)
+            .ok_or_else(|| {
+                let supported: Vec<String> = modes
+                    .iter()
+                    .map(|m| {
+                        format!("{}ch {}-{} Hz {:?}", m.channels, m.min_rate, m.max_rate, m.format)
+                    })
+                    .collect();
+                AudioError::config(format!(
+                    "device '{}' does not support {} ch @ {} Hz (supported: {})",
+                    device_name,
+                    settings.channels,
+                    settings.sample_rate,
+                    supported.join(", ")
+                ))
+            })?;
+
+        let stream_config = ::cpal::StreamConfig {
+            channels: settings.channels as u16,
+            sample_rate: ::cpal::SampleRate(settings.sample_rate),
+            buffer_size: ::cpal::BufferSize::Default,
+        };
+
+        // Abgestecktes Gerät beendet den Producer; der Watchdog startet neu
+        let producer = name.to_string();
+        let on_error = move |error: ::cpal::StreamError| {
+            errors.fetch_add(1, Ordering::Relaxed);
+            log::warn!("CpalProducer '{}': stream error: {}", producer, error);
+            if matches!(error, ::cpal::StreamError::DeviceNotAvailable) {
+                last_error.record_audio(&AudioError::device(format!(
+                    "input device lost: {}",
+                    error
+                )));
+                running.store(false, Ordering::SeqCst);
+                stop_wait.notify_all();
+            }
+        };
+
+        let stream = match mode.format {
+            CpalSampleFormat::I16 => device.build_input_stream(
+                &stream_config,
+                move |data: &[i16], _: &::cpal::InputCallbackInfo| {
+                    assembler.push(data.iter().copied())
+                },
+                on_error,
+                None,
+            ),
+            CpalSampleFormat::F32 => device.build_input_stream(
+                &stream_config,
+                move |data: &[f32], _: &::cpal::InputCallbackInfo| {
+                    assembler.push(data.iter().map(|&s| f32_to_i16(s)))
+                },
+                on_error,
+                None,
+            ),
+            CpalSampleFormat::I32 => device.build_input_stream(
+                &stream_config,
+                move |data: &[i32], _: &::cpal::InputCallbackInfo| {
+                    assembler.push(data.iter().map(|&s| (s >> 16) as i16))
+                },
+                on_error,
+                None,
+            ),
+            CpalSampleFormat::U16 => device.build_input_stream(
+                &stream_config,
+                move |data: &[u16], _: &::cpal::InputCallbackInfo| {
+                    assembler.push(data.iter().map(|&s| (s as i32 - 32768) as i16))
+                },
+                on_error,
+                None,
+            ),
+        }
+        .map_err(|e| AudioError::device(format!("open '{}': {}", device_name, e)))?;
+
+        stream
+            .play()
+            .map_err(|e| AudioError::device(format!("start '{}': {}", device_name, e)))?;
+        Ok((stream, format!("{} ({:?})", device_name, mode.format)))
+    }
+}
+
+impl Producer for CpalProducer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn start(&mut self) -> Result<()> {
+        if self.running.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        // Alter Thread (Gerät verloren) ist bereits auf dem Weg nach draußen
+        if let Some(handle) = self.thread_handle.take() {
+            let _ = handle.join();
+        }
+
+        self.running.store(true, Ordering::SeqCst);
+        self.last_error.clear();
+
+        let name = self.name.clone();
+        let settings = self.settings.clone();
+        let assembler = FrameAssembler {
+            fifo: Vec::new(),
+            frame_samples: (settings.sample_rate as usize / 10) * settings.channels as usize,
+            sample_rate: settings.sample_rate,
+            channels: settings.channels,
+            ring: self.ring.clone(),
+            samples_processed: self.samples_processed.clone(),
+        };
+        let running = self.running.clone();
+        let connected = self.connected.clone();
+        let errors = self.errors.clone();
+        let last_error = self.last_error.clone();
+        let stop_wait = self.stop_wait.clone();
+        let (ready_tx, ready_rx) = mpsc::channel();
+
+        self.thread_handle = Some(thread::spawn(move || {
+            let opened = Self::open_stream(
+                &name,
+                &settings,
+                assembler,
+                running.clone(),
+                errors,
+                last_error,
+                stop_wait.clone(),
+            );
+            let stream = match opened {
+                Ok((stream, device)) => {
+                    let _ = ready_tx.send(Ok(device));
+                    stream
+                }
+                Err(e) => {
+                    running.store(false, Ordering::SeqCst);
+                    let _ = ready_tx.send(Err(e));
+                    return;
+                }
+            };
+
+            connected.store(true, Ordering::Relaxed);
+            while running.load(Ordering::Relaxed) {
+                stop_wait.wait_timeout(STOP_POLL);
+            }
+            drop(stream);
+            connected.store(false, Ordering::Relaxed);
+            log::info!("CpalProducer '{}': capture stopped", name);
+        }));
+
+        match ready_rx.recv_timeout(OPEN_TIMEOUT) {
+            Ok(Ok(device)) => {
+                log::info!(
+                    "CpalProducer '{}': capturing from {}, {} Hz, {} ch",
+                    self.name,
+                    device,
+                    self.settings.sample_rate,
+                    self.settings.channels
+                );
+                Ok(())
+            }
+            Ok(Err(e)) => {
+                if let Some(handle) = self.thread_handle.take() {
+                    let _ = handle.join();
+                }
+                self.errors.fetch_add(1, Ordering::Relaxed);
+                self.last_error.record_audio(&e);
+                Err(AudioError::with_context(format!("producer '{}'", self.name), e).into())
+            }
+            Err(_) => {
+                // Hängt im Treiber: Thread zurücklassen, er beendet sich nach dem Öffnen
+                self.running.store(false, Ordering::SeqCst);
+                self.thread_handle = None;
+                let e = AudioError::device(format!(
+                    "opening input device timed out after {:?}",
+                    OPEN_TIMEOUT
+                ));
+                self.last_error.record_audio(&e);
+                Err(e.into())
+            }
+        }
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        self.running.store(false, Ordering::SeqCst);
+        self.stop_wait.notify_all();
+        if let Some(handle) = self.thread_handle.take() {
+            if handle.join().is_err() {
+                log::error!("CpalProducer '{}': capture thread panicked", self.name);
+            }
+        }
+        Ok(())
+    }
+
+    fn status(&self) -> ProducerStatus {
+        ProducerStatus {
+            running: self.running.load(Ordering::Relaxed),
+            paused: false,
+            connected: self.connected.load(Ordering::Relaxed),
+            samples_processed: self.samples_processed.load(Ordering::Relaxed),
+            errors: self.errors.load(Ordering::Relaxed),
+            buffer_stats: self.ring.as_ref().map(|r| r.stats()),
+            decoder: None,
+        }
+    }
+
+    fn attach_ring_buffer(&mut self, buffer: Arc<AudioRingBuffer>) {
+        self.ring = Some(buffer);
+    }
+
+    fn last_error(&self) -> Option<ErrorInfo> {
+        self.last_error.get()
+    }
+}
+
+impl_connectable_producer!(CpalProducer);
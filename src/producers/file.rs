@@ -1,22 +1,256 @@
+// src/producers/file.rs
+//
+// Spielt eine Audiodatei als Producer ab. WAV liest hound direkt, komprimierte
+// Formate (FLAC, MP3, OGG/Vorbis, AAC/M4A) dekodiert symphonia (Cargo-Feature
+// `symphonia`). Ausgabe in 20-ms-Frames nach Wanduhr, mit Rate und Kanälen der
+// Datei; `loop_audio` spielt lückenlos von vorn. `pause()` hält an der
+// aktuellen Position an, `resume()` setzt dort fort.
 use crate::impl_connectable_producer;
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, bail, Result};
 use std::fs::File;
-use std::io::Read;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use crate::audio::sanitize_audio_path;
 use crate::core::{AudioRingBuffer, Producer, ProducerStatus};
 use crate::producers::wait::StopWait;
 
-// Timing constants for file playback loop.
-const FRAME_INTERVAL_MS: u64 = 100; // 10 FPS
+const FRAME_MS: u64 = 20;
+/// Samples pro Kanal, die der WAV-Reader je Block liest
+const WAV_BLOCK_FRAMES: usize = 4096;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AudioFileInfo {
+    pub sample_rate: u32,
+    pub channels: u8,
+    /// Spieldauer, falls der Container sie angibt
+    pub duration: Option<Duration>,
+    /// z. B. "wav", "flac", "mp3"
+    pub codec: String,
+}
+
+/// Dekodiert eine Datei blockweise nach interleaved s16.
+pub trait AudioFileReader: Send {
+    fn info(&self) -> &AudioFileInfo;
+    /// Nächster Block, `None` am Dateiende
+    fn read_block(&mut self) -> Result<Option<Vec<i16>>>;
+}
+
+fn extension(path: &Path) -> String {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase()
+}
+
+/// Öffnet `path` mit dem passenden Decoder (anhand der Dateiendung).
+pub fn open_audio_file(path: &Path) -> Result<Box<dyn AudioFileReader>> {
+    match extension(path).as_str() {
+        "wav" | "wave" => Ok(Box::new(WavFileReader::open(path)?)),
+        #[cfg(feature = "symphonia")]
+        _ => Ok(Box::new(symphonia_reader::SymphoniaFileReader::open(path)?)),
+        #[cfg(not(feature = "symphonia"))]
+        other => bail!(
+            "{}: format '{}' needs the 'symphonia' feature (only WAV is built in)",
+            path.display(),
+            other
+        ),
+    }
+}
+
+/// Format, Rate, Kanäle und Dauer einer Datei, ohne abzuspielen.
+pub fn probe_audio_file(path: &Path) -> Result<AudioFileInfo> {
+    Ok(open_audio_file(path)?.info().clone())
+}
+
+struct WavFileReader {
+    reader: hound::WavReader<BufReader<File>>,
+    spec: hound::WavSpec,
+    info: AudioFileInfo,
+}
+
+impl WavFileReader {
+    fn open(path: &Path) -> Result<Self> {
+        let reader = hound::WavReader::open(path)
+            .map_err(|e| anyhow!("{}: {}", path.display(), e))?;
+        let spec = reader.spec();
+        match (spec.sample_format, spec.bits_per_sample) {
+            (hound::SampleFormat::Int, 8 | 16 | 24 | 32) | (hound::SampleFormat::Float, 32) => {}
+            (format, bits) => bail!(
+                "{}: unsupported WAV format {:?} {} bit",
+                path.display(),
+                format,
+                bits
+            ),
+        }
+        let channels = u8::try_from(spec.channels)
+            .map_err(|_| anyhow!("{}: too many channels ({})", path.display(), spec.channels))?;
+        let info = AudioFileInfo {
+            sample_rate: spec.sample_rate,
+            channels,
+            duration: Some(Duration::from_secs_f64(
+                reader.duration() as f64 / spec.sample_rate as f64,
+            )),
+            codec: "wav".to_string(),
+        };
+        Ok(Self { reader, spec, info })
+    }
+}
+
+impl AudioFileReader for WavFileReader {
+    fn info(&self) -> &AudioFileInfo {
+        &self.info
+    }
+
+    fn read_block(&mut self) -> Result<Option<Vec<i16>>> {
+        let limit = WAV_BLOCK_FRAMES * self.spec.channels as usize;
+        let block: Vec<i16> = match (self.spec.sample_format, self.spec.bits_per_sample) {
+            (hound::SampleFormat::Float, _) => self
+                .reader
+                .samples::<f32>()
+                .take(limit)
+                .map(|s| s.map(|v| (v.clamp(-1.0, 1.0) * i16::MAX as f32) as i16))
+                .collect::<Result<_, _>>()?,
+            (_, bits) if bits <= 16 => self
+                .reader
+                .samples::<i16>()
+                .take(limit)
+                .map(|s| s.map(|v| if bits == 8 { v << 8 } else { v }))
+                .collect::<Result<_, _>>()?,
+            (_, bits) => self
+                .reader
+                .samples::<i32>()
+                .take(limit)
+                .map(|s| s.map(|v| (v >> (bits - 16)) as i16))
+                .collect::<Result<_, _>>()?,
+        };
+        Ok((!block.is_empty()).then_some(block))
+    }
+}
+
+#[cfg(feature = "symphonia")]
+mod symphonia_reader {
+    use super::*;
+    use symphonia::core::audio::SampleBuffer;
+    use symphonia::core::codecs::{Decoder, DecoderOptions, CODEC_TYPE_NULL};
+    use symphonia::core::errors::Error as SymphoniaError;
+    use symphonia::core::formats::{FormatOptions, FormatReader};
+    use symphonia::core::io::MediaSourceStream;
+    use symphonia::core::meta::MetadataOptions;
+    use symphonia::core::probe::Hint;
+
+    pub(super) struct SymphoniaFileReader {
+        format: Box<dyn FormatReader>,
+        decoder: Box<dyn Decoder>,
+        track_id: u32,
+        info: AudioFileInfo,
+    }
+
+    impl SymphoniaFileReader {
+        pub(super) fn open(path: &Path) -> Result<Self> {
+            let file = File::open(path).map_err(|e| anyhow!("{}: {}", path.display(), e))?;
+            let stream = MediaSourceStream::new(Box::new(file), Default::default());
+            let mut hint = Hint::new();
+            let ext = extension(path);
+            if !ext.is_empty() {
+                hint.with_extension(&ext);
+            }
+            let probed = symphonia::default::get_probe()
+                .format(&hint, stream, &FormatOptions::default(), &MetadataOptions::default())
+                .map_err(|e| anyhow!("{}: unsupported or damaged file: {}", path.display(), e))?;
+            let format = probed.format;
+            let track = format
+                .tracks()
+                .iter()
+                .find(|track| track.codec_params.codec != CODEC_TYPE_NULL)
+                .ok_or_else(|| anyhow!("{}: no audio track", path.display()))?;
+            let params = track.codec_params.clone();
+            let track_id = track.id;
+            let decoder = symphonia::default::get_codecs()
+                .make(&params, &DecoderOptions::default())
+                .map_err(|e| anyhow!("{}: no decoder: {}", path.display(), e))?;
+
+            let sample_rate = params
+                .sample_rate
+                .ok_or_else(|| anyhow!("{}: unknown sample rate", path.display()))?;
+            let channels = params
+                .channels
+                .map(|channels| channels.count())
+                .ok_or_else(|| anyhow!("{}: unknown channel layout", path.display()))?;
+            let duration = params
+                .n_frames
+                .map(|frames| Duration::from_secs_f64(frames as f64 / sample_rate as f64));
+            let codec = symphonia::default::get_codecs()
+                .get_codec(params.codec)
+                .map(|descriptor| descriptor.short_name.to_string())
+                .unwrap_or_else(|| ext.clone());
+
+            Ok(Self {
+                format,
+                decoder,
+                track_id,
+                info: AudioFileInfo {
+                    sample_rate,
+                    channels: u8::try_from(channels)
+                        .map_err(|_| anyhow!("{}: too many channels", path.display()))?,
+                    duration,
+                    codec,
+                },
+            })
+        }
+    }
+
+    impl AudioFileReader for SymphoniaFileReader {
+        fn info(&self) -> &AudioFileInfo {
+            &self.info
+        }
+
+        fn read_block(&mut self) -> Result<Option<Vec<i16>>> {
+            loop {
+                let packet = match self.format.next_packet() {
+                    Ok(packet) => packet,
+                    Err(SymphoniaError::IoError(e))
+                        if e.kind() == std::io::ErrorKind::UnexpectedEof =>
+                    {
+                        return Ok(None);
+                    }
+                    Err(e) => return Err(anyhow!("read packet: {}", e)),
+                };
+                if packet.track_id() != self.track_id {
+                    continue;
+                }
+                match self.decoder.decode(&packet) {
+                    Ok(decoded) => {
+                        if decoded.frames() == 0 {
+                            continue;
+                        }
+                        let mut buffer =
+                            SampleBuffer::<i16>::new(decoded.capacity() as u64, *decoded.spec());
+                        buffer.copy_interleaved_ref(decoded);
+                        return Ok(Some(buffer.samples().to_vec()));
+                    }
+                    // Einzelne kaputte Pakete überspringen
+                    Err(SymphoniaError::DecodeError(e)) => {
+                        log::warn!("skipping undecodable packet: {}", e);
+                    }
+                    Err(e) => return Err(anyhow!("decode: {}", e)),
+                }
+            }
+        }
+    }
+}
 
 pub struct FileProducer {
     name: String,
     running: Arc<AtomicBool>,
+    paused: Arc<AtomicBool>,
     samples_processed: Arc<AtomicU64>,
+    errors: Arc<AtomicU64>,
     config: crate::config::ProducerConfig,
+    info: Option<AudioFileInfo>,
     thread_handle: Option<std::thread::JoinHandle<()>>,
     ring_buffer: Option<Arc<AudioRingBuffer>>,
     stop_wait: Arc<StopWait>,
@@ -27,20 +261,29 @@ impl FileProducer {
         Self {
             name: name.to_string(),
             running: Arc::new(AtomicBool::new(false)),
+            paused: Arc::new(AtomicBool::new(false)),
             samples_processed: Arc::new(AtomicU64::new(0)),
+            errors: Arc::new(AtomicU64::new(0)),
             config: config.clone(),
+            info: None,
             thread_handle: None,
             ring_buffer: None,
             stop_wait: Arc::new(StopWait::new()),
         }
     }
 
-    fn utc_ns_now() -> u64 {
-        use std::time::{SystemTime, UNIX_EPOCH};
-        SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_nanos() as u64
+    /// Format der Datei, verfügbar ab dem ersten `start()`.
+    pub fn file_info(&self) -> Option<&AudioFileInfo> {
+        self.info.as_ref()
+    }
+
+    fn path(&self) -> Result<PathBuf> {
+        sanitize_audio_path(
+            self.config
+                .path
+                .as_deref()
+                .ok_or_else(|| anyhow!("No file path specified"))?,
+        )
     }
 }
 
@@ -54,91 +297,129 @@ impl Producer for FileProducer {
             return Ok(());
         }
 
-        let path = sanitize_audio_path(
-            self.config
-                .path
-                .as_deref()
-                .ok_or_else(|| anyhow!("No file path specified"))?,
-        )?;
+        let path = self.path()?;
+        let ring_buffer = self
+            .ring_buffer
+            .clone()
+            .ok_or_else(|| anyhow!("FileProducer '{}' has no ring buffer", self.name))?;
+        // Öffnen schon hier, damit Format-Fehler beim Start gemeldet werden
+        let mut reader = open_audio_file(&path)?;
+        let info = reader.info().clone();
+        let loop_audio = self.config.loop_audio.unwrap_or(false);
 
         log::info!(
-            "FileProducer '{}': Starting (path: {}, loop: {})",
+            "FileProducer '{}': Starting (path: {}, {}, {} Hz, {} ch, duration: {}, loop: {})",
             self.name,
             path.display(),
-            self.config.loop_audio.unwrap_or(false)
+            info.codec,
+            info.sample_rate,
+            info.channels,
+            info.duration
+                .map(|d| format!("{:.1}s", d.as_secs_f64()))
+                .unwrap_or_else(|| "unknown".to_string()),
+            loop_audio
         );
+        for (key, configured, actual) in [
+            ("sample_rate", self.config.sample_rate, info.sample_rate),
+            ("channels", self.config.channels.map(u32::from), info.channels as u32),
+        ] {
+            if configured.is_some_and(|value| value != actual) {
+                log::warn!(
+                    "FileProducer '{}': {} {} configured, file has {}; using the file's value",
+                    self.name,
+                    key,
+                    configured.unwrap_or_default(),
+                    actual
+                );
+            }
+        }
+        self.info = Some(info.clone());
 
-        let sample_rate = self.config.sample_rate.unwrap_or(48000);
-        let channels = self.config.channels.unwrap_or(2);
-        let loop_audio = self.config.loop_audio.unwrap_or(false);
-
+        self.paused.store(false, Ordering::SeqCst);
         self.running.store(true, Ordering::SeqCst);
 
         let running = self.running.clone();
+        let paused = self.paused.clone();
         let name = self.name.clone();
         let samples_processed = self.samples_processed.clone();
-        let ring_buffer = self.ring_buffer.clone();
+        let errors = self.errors.clone();
         let stop_wait = self.stop_wait.clone();
 
         let handle = std::thread::spawn(move || {
-            log::info!("FileProducer '{}': Playing {}", name, path.display());
+            let frame_samples =
+                (info.sample_rate as u64 * FRAME_MS / 1000) as usize * info.channels as usize;
+            let mut pending: Vec<i16> = Vec::new();
+            let mut end_of_input = false;
+            // Schutz gegen Endlosschleife bei leeren Dateien im Loop-Modus
+            let mut read_since_open = false;
+            let mut started = Instant::now();
+            let mut frames_sent: u32 = 0;
+            let mut was_paused = false;
 
-            let mut iteration = 0;
             while running.load(Ordering::Relaxed) {
-                iteration += 1;
-
-                match File::open(&path) {
-                    Ok(mut file) => {
-                        // Einfache Simulation: Erzeuge Test-Daten
-                        let samples_per_frame = (sample_rate as usize / 10) * channels as usize; // 100ms
-                        let mut chunk = vec![0i16; samples_per_frame];
-
-                        // Fülle mit Test-Daten (Sinus-ähnlich)
-                        for (i, sample) in chunk.iter_mut().enumerate() {
-                            let t = i as f32 / sample_rate as f32;
-                            *sample = (t.sin() * 10000.0) as i16;
-                        }
+                if paused.load(Ordering::Relaxed) {
+                    was_paused = true;
+                    stop_wait.wait_timeout(Duration::from_millis(FRAME_MS));
+                    continue;
+                }
+                if was_paused {
+                    // Takt neu beginnen, sonst würde die Pause nachgeholt
+                    started = Instant::now();
+                    frames_sent = 0;
+                    was_paused = false;
+                }
 
-                        // In RingBuffer speichern
-                        if let Some(rb) = &ring_buffer {
-                            let frame = crate::core::ringbuffer::PcmFrame {
-                                utc_ns: Self::utc_ns_now(),
-                                samples: chunk.to_vec(),
-                                sample_rate,
-                                channels,
-                            };
-                            log::debug!(
-                                "FileProducer '{}': Schreibe Frame {} ({} samples) in Buffer",
-                                name,
-                                iteration,
-                                chunk.len()
-                            );
-                            rb.push(frame);
-                            samples_processed.fetch_add(chunk.len() as u64, Ordering::Relaxed);
-                        } else {
-                            log::warn!("FileProducer '{}': KEIN Buffer attached!", name);
+                while !end_of_input && pending.len() < frame_samples {
+                    match reader.read_block() {
+                        Ok(Some(block)) => {
+                            read_since_open = true;
+                            pending.extend(block);
+                        }
+                        Ok(None) if loop_audio && read_since_open => match open_audio_file(&path) {
+                            Ok(reopened) => {
+                                log::debug!("FileProducer '{}': looping", name);
+                                reader = reopened;
+                                read_since_open = false;
+                            }
+                            Err(e) => {
+                                log::error!("FileProducer '{}': reopen failed: {}", name, e);
+                                errors.fetch_add(1, Ordering::Relaxed);
+                                end_of_input = true;
+                            }
+                        },
+                        Ok(None) => end_of_input = true,
+                        Err(e) => {
+                            log::error!("FileProducer '{}': {}", name, e);
+                            errors.fetch_add(1, Ordering::Relaxed);
+                            end_of_input = true;
                         }
-
-                        log::debug!("FileProducer '{}': Generated frame", name);
-                    }
-                    Err(e) => {
-                        log::error!(
-                            "FileProducer '{}': Failed to open {}: {}",
-                            name,
-                            path.display(),
-                            e
-                        );
-                        break;
                     }
                 }
+                if pending.is_empty() {
+                    break;
+                }
 
-                stop_wait.wait_timeout(std::time::Duration::from_millis(FRAME_INTERVAL_MS));
+                let take = pending.len().min(frame_samples);
+                let samples: Vec<i16> = pending.drain(..take).collect();
+                samples_processed.fetch_add(samples.len() as u64, Ordering::Relaxed);
+                ring_buffer.push(crate::core::ringbuffer::PcmFrame {
+                    utc_ns: crate::core::timestamp::utc_ns_now(),
+                    samples,
+                    sample_rate: info.sample_rate,
+                    channels: info.channels,
+                    metadata: Default::default(),
+                });
+                frames_sent = frames_sent.saturating_add(1);
 
-                if !loop_audio {
-                    break;
+                // Nach Wanduhr takten
+                let due = started + Duration::from_millis(FRAME_MS) * frames_sent;
+                let now = Instant::now();
+                if due > now {
+                    stop_wait.wait_timeout(due - now);
                 }
             }
 
+            running.store(false, Ordering::SeqCst);
             log::info!("FileProducer '{}': Thread stopped", name);
         });
 
@@ -160,13 +441,37 @@ impl Producer for FileProducer {
         Ok(())
     }
 
+    fn pause(&mut self) -> Result<()> {
+        if !self.running.load(Ordering::Relaxed) {
+            bail!("FileProducer '{}' is not running", self.name);
+        }
+        if !self.paused.swap(true, Ordering::SeqCst) {
+            log::info!("FileProducer '{}': Paused", self.name);
+        }
+        Ok(())
+    }
+
+    fn resume(&mut self) -> Result<()> {
+        if !self.running.load(Ordering::Relaxed) {
+            bail!("FileProducer '{}' is not running", self.name);
+        }
+        if self.paused.swap(false, Ordering::SeqCst) {
+            log::info!("FileProducer '{}': Resumed", self.name);
+            self.stop_wait.notify_all();
+        }
+        Ok(())
+    }
+
     fn status(&self) -> ProducerStatus {
+        let running = self.running.load(Ordering::Relaxed);
         ProducerStatus {
-            running: self.running.load(Ordering::Relaxed),
+            running,
+            paused: running && self.paused.load(Ordering::Relaxed),
             connected: self.ring_buffer.is_some(),
             samples_processed: self.samples_processed.load(Ordering::Relaxed),
-            errors: 0,
+            errors: self.errors.load(Ordering::Relaxed),
             buffer_stats: self.ring_buffer.as_ref().map(|b| b.stats()),
+            decoder: None,
         }
     }
 
@@ -0,0 +1,382 @@
+// src/producers/generator.rs
+//
+// Messsignal-Generator für Einmessen und automatisierte Tests: Sinus,
+// logarithmischer Sweep, weißes/rosa Rauschen, GLITS, EBU-Stereo-Ident und
+// Multiton. Pegel in dBFS (Spitze), Ausgabe in 10-ms-Frames nach Wanduhr.
+use std::f64::consts::PI;
+use std::sync::{
+    atomic::{AtomicBool, AtomicU64, Ordering},
+    Arc,
+};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail};
+
+use crate::config::{ConfigValues, ProducerConfig};
+use crate::core::{AudioRingBuffer, PcmFrame, Producer, ProducerStatus};
+use crate::impl_connectable_producer;
+use crate::producers::wait::StopWait;
+
+const FRAME_MS: u64 = 10;
+const DEFAULT_LEVEL_DB: f32 = -18.0;
+const DEFAULT_MULTITONE: [f64; 3] = [100.0, 1000.0, 10_000.0];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Signal {
+    Sine { frequency: f64 },
+    /// Logarithmischer Sweep, wiederholt sich nach `duration`
+    Sweep { start_hz: f64, end_hz: f64, duration: Duration },
+    WhiteNoise,
+    PinkNoise,
+    /// BBC GLITS: 1 kHz, links eine, rechts zwei 250-ms-Pausen je 4 s
+    Glits,
+    /// EBU Tech 3304: 1 kHz, links alle 3 s für 250 ms unterbrochen
+    EbuIdent,
+    /// Summe mehrerer Sinustöne, Pegel gilt für die Summe
+    Multitone { frequencies: Vec<f64> },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratorConfig {
+    pub signal: Signal,
+    pub level_db: f32,
+    pub sample_rate: u32,
+    pub channels: u8,
+}
+
+/// Gelesene `config`-Schlüssel (Strict-Modus, siehe `config::options`)
+pub const CONFIG_KEYS: &[&str] = &[
+    "signal",
+    "frequency",
+    "frequencies",
+    "start_hz",
+    "end_hz",
+    "sweep_time",
+    "level_db",
+];
+
+impl GeneratorConfig {
+    pub fn from_producer_config(name: &str, cfg: &ProducerConfig) -> anyhow::Result<Self> {
+        let values = ConfigValues::new("producer", name, &cfg.config);
+        let sample_rate = cfg.sample_rate.unwrap_or(48_000);
+        let nyquist = sample_rate as f64 / 2.0;
+        let frequency = |key: &str, default: f64| -> anyhow::Result<f64> {
+            let hz = values.f64(key)?.unwrap_or(default);
+            values.check_range(key, hz, 1.0, nyquist)
+        };
+
+        let kind = cfg
+            .config
+            .get("signal")
+            .map(|v| v.as_str().ok_or_else(|| anyhow!("producer '{}': config.signal must be a string", name)))
+            .transpose()?
+            .unwrap_or("sine");
+        let signal = match kind {
+            "sine" => Signal::Sine {
+                frequency: frequency("frequency", 1000.0)?,
+            },
+            "sweep" => Signal::Sweep {
+                start_hz: frequency("start_hz", 20.0)?,
+                end_hz: frequency("end_hz", 20_000.0_f64.min(nyquist))?,
+                duration: values
+                    .duration("sweep_time")?
+                    .unwrap_or(Duration::from_secs(10))
+                    .max(Duration::from_millis(100)),
+            },
+            "white" | "white_noise" => Signal::WhiteNoise,
+            "pink" | "pink_noise" => Signal::PinkNoise,
+            "glits" => Signal::Glits,
+            "ebu" | "ebu_ident" => Signal::EbuIdent,
+            "multitone" => {
+                let frequencies = match cfg.config.get("frequencies") {
+                    None => DEFAULT_MULTITONE.to_vec(),
+                    Some(serde_json::Value::Array(items)) => items
+                        .iter()
+                        .map(|item| {
+                            item.as_f64()
+                                .filter(|hz| *hz >= 1.0 && *hz <= nyquist)
+                                .ok_or_else(|| {
+                                    anyhow!(
+                                        "producer '{}': config.frequencies entry {} invalid (1..={} Hz)",
+                                        name,
+                                        item,
+                                        nyquist
+                                    )
+                                })
+                        })
+                        .collect::<anyhow::Result<Vec<_>>>()?,
+                    Some(other) => bail!(
+                        "producer '{}': config.frequencies must be an array, got {}",
+                        name,
+                        other
+                    ),
+                };
+                if frequencies.is_empty() {
+                    bail!("producer '{}': config.frequencies must not be empty", name);
+                }
+                Signal::Multitone { frequencies }
+            }
+            other => bail!(
+                "producer '{}': unknown signal '{}' (sine, sweep, white, pink, glits, ebu, multitone)",
+                name,
+                other
+            ),
+        };
+
+        let level_db = values.db("level_db")?.unwrap_or(DEFAULT_LEVEL_DB);
+        values.check_range("level_db", level_db, -120.0, 0.0)?;
+        let channels = cfg.channels.unwrap_or(2);
+        values.check_range("channels", channels, 1, 2)?;
+        if matches!(signal, Signal::Glits | Signal::EbuIdent) && channels != 2 {
+            bail!("producer '{}': signal '{}' needs 2 channels", name, kind);
+        }
+
+        Ok(Self {
+            signal,
+            level_db,
+            sample_rate,
+            channels,
+        })
+    }
+}
+
+/// Erzeugt die Samples; ohne Threads, damit Pegel und Timing testbar sind.
+pub struct SignalGenerator {
+    config: GeneratorConfig,
+    amplitude: f64,
+    /// Position in Samples seit Start
+    position: u64,
+    /// Phasen je Ton (Sinus, Sweep, Multiton)
+    phases: Vec<f64>,
+    rng: u64,
+    pink: [f64; 7],
+}
+
+impl SignalGenerator {
+    pub fn new(config: GeneratorConfig) -> Self {
+        let tones = match &config.signal {
+            Signal::Multitone { frequencies } => frequencies.len(),
+            _ => 1,
+        };
+        Self {
+            amplitude: crate::config::units::db_to_linear(config.level_db) as f64,
+            config,
+            position: 0,
+            phases: vec![0.0; tones],
+            rng: 0x9E37_79B9_7F4A_7C15,
+            pink: [0.0; 7],
+        }
+    }
+
+    pub fn config(&self) -> &GeneratorConfig {
+        &self.config
+    }
+
+    /// Nächste `samples_per_channel` Samples, interleaved.
+    pub fn next_samples(&mut self, samples_per_channel: usize) -> Vec<i16> {
+        let channels = self.config.channels as usize;
+        let mut samples = Vec::with_capacity(samples_per_channel * channels);
+        for _ in 0..samples_per_channel {
+            let (left, right) = self.next_pair();
+            samples.push(to_i16(left));
+            if channels == 2 {
+                samples.push(to_i16(right));
+            }
+            self.position += 1;
+        }
+        samples
+    }
+
+    fn next_pair(&mut self) -> (f64, f64) {
+        let rate = self.config.sample_rate as f64;
+        let amplitude = self.amplitude;
+        let t = self.position as f64 / rate;
+        let phases = &mut self.phases;
+        match &self.config.signal {
+            Signal::Sine { frequency } => {
+                let v = tone(&mut phases[0], *frequency, rate, amplitude);
+                (v, v)
+            }
+            Signal::Sweep {
+                start_hz,
+                end_hz,
+                duration,
+            } => {
+                let progress = (t % duration.as_secs_f64()) / duration.as_secs_f64();
+                let frequency = start_hz * (end_hz / start_hz).powf(progress);
+                let v = tone(&mut phases[0], frequency, rate, amplitude);
+                (v, v)
+            }
+            Signal::WhiteNoise => {
+                let v = white(&mut self.rng) * amplitude;
+                (v, v)
+            }
+            Signal::PinkNoise => {
+                let v = pink(&mut self.rng, &mut self.pink) * amplitude;
+                (v, v)
+            }
+            Signal::Glits => {
+                let v = tone(&mut phases[0], 1000.0, rate, amplitude);
+                let cycle = t % 4.0;
+                let left_gap = cycle < 0.25;
+                let right_gap = (0.5..0.75).contains(&cycle) || (1.0..1.25).contains(&cycle);
+                (
+                    if left_gap { 0.0 } else { v },
+                    if right_gap { 0.0 } else { v },
+                )
+            }
+            Signal::EbuIdent => {
+                let v = tone(&mut phases[0], 1000.0, rate, amplitude);
+                (if t % 3.0 < 0.25 { 0.0 } else { v }, v)
+            }
+            Signal::Multitone { frequencies } => {
+                let scale = 1.0 / frequencies.len() as f64;
+                let v = frequencies
+                    .iter()
+                    .zip(phases.iter_mut())
+                    .map(|(frequency, phase)| tone(phase, *frequency, rate, amplitude))
+                    .sum::<f64>()
+                    * scale;
+                (v, v)
+            }
+        }
+    }
+}
+
+/// Phasenkontinuierlicher Sinus (auch bei wechselnder Frequenz im Sweep).
+fn tone(phase: &mut f64, frequency: f64, rate: f64, amplitude: f64) -> f64 {
+    let value = phase.sin() * amplitude;
+    *phase = (*phase + 2.0 * PI * frequency / rate) % (2.0 * PI);
+    value
+}
+
+/// xorshift64*, gleichverteilt in [-1, 1)
+fn white(rng: &mut u64) -> f64 {
+    *rng ^= *rng >> 12;
+    *rng ^= *rng << 25;
+    *rng ^= *rng >> 27;
+    let bits = rng.wrapping_mul(0x2545_F491_4F6C_DD1D) >> 11;
+    bits as f64 / (1u64 << 52) as f64 - 1.0
+}
+
+/// Rosa Rauschen nach Paul Kellet (-3 dB/Oktave)
+fn pink(rng: &mut u64, b: &mut [f64; 7]) -> f64 {
+    let white = white(rng);
+    b[0] = 0.99886 * b[0] + white * 0.0555179;
+    b[1] = 0.99332 * b[1] + white * 0.0750759;
+    b[2] = 0.96900 * b[2] + white * 0.1538520;
+    b[3] = 0.86650 * b[3] + white * 0.3104856;
+    b[4] = 0.55000 * b[4] + white * 0.5329522;
+    b[5] = -0.7616 * b[5] - white * 0.0168980;
+    let value = b[0] + b[1] + b[2] + b[3] + b[4] + b[5] + b[6] + white * 0.5362;
+    b[6] = white * 0.115926;
+    (value * 0.11).clamp(-1.0, 1.0)
+}
+
+fn to_i16(value: f64) -> i16 {
+    (value * i16::MAX as f64).round().clamp(i16::MIN as f64, i16::MAX as f64) as i16
+}
+
+pub struct GeneratorProducer {
+    name: String,
+    config: GeneratorConfig,
+    running: Arc<AtomicBool>,
+    samples_processed: Arc<AtomicU64>,
+    ring: Option<Arc<AudioRingBuffer>>,
+    stop_wait: Arc<StopWait>,
+    thread_handle: Option<thread::JoinHandle<()>>,
+}
+
+impl GeneratorProducer {
+    pub fn new(name: &str, cfg: &ProducerConfig) -> anyhow::Result<Self> {
+        Ok(Self::with_config(name, GeneratorConfig::from_producer_config(name, cfg)?))
+    }
+
+    pub fn with_config(name: &str, config: GeneratorConfig) -> Self {
+        Self {
+            name: name.to_string(),
+            config,
+            running: Arc::new(AtomicBool::new(false)),
+            samples_processed: Arc::new(AtomicU64::new(0)),
+            ring: None,
+            stop_wait: Arc::new(StopWait::new()),
+            thread_handle: None,
+        }
+    }
+}
+
+impl Producer for GeneratorProducer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn start(&mut self) -> anyhow::Result<()> {
+        if self.running.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        let ring = self
+            .ring
+            .clone()
+            .ok_or_else(|| anyhow!("generator '{}' has no ring buffer", self.name))?;
+        let running = self.running.clone();
+        let samples_processed = self.samples_processed.clone();
+        let stop_wait = self.stop_wait.clone();
+        let mut generator = SignalGenerator::new(self.config.clone());
+
+        self.running.store(true, Ordering::SeqCst);
+        self.thread_handle = Some(thread::spawn(move || {
+            let rate = generator.config().sample_rate;
+            let channels = generator.config().channels;
+            let frame_samples = (rate as u64 * FRAME_MS / 1000) as usize;
+            let started = Instant::now();
+            let mut frames_sent: u64 = 0;
+
+            while running.load(Ordering::Relaxed) {
+                // Nach Wanduhr nachliefern, was seit dem Start fällig ist
+                let due = started.elapsed().as_millis() as u64 / FRAME_MS;
+                while frames_sent <= due {
+                    let samples = generator.next_samples(frame_samples);
+                    samples_processed.fetch_add(samples.len() as u64, Ordering::Relaxed);
+                    ring.push(PcmFrame {
+                        utc_ns: crate::core::timestamp::utc_ns_now(),
+                        samples,
+                        sample_rate: rate,
+                        channels,
+                        metadata: Default::default(),
+                    });
+                    frames_sent += 1;
+                }
+                stop_wait.wait_timeout(Duration::from_millis(FRAME_MS));
+            }
+        }));
+        Ok(())
+    }
+
+    fn stop(&mut self) -> anyhow::Result<()> {
+        self.running.store(false, Ordering::SeqCst);
+        self.stop_wait.notify_all();
+        if let Some(handle) = self.thread_handle.take() {
+            let _ = handle.join();
+        }
+        Ok(())
+    }
+
+    fn status(&self) -> ProducerStatus {
+        ProducerStatus {
+            running: self.running.load(Ordering::Relaxed),
+            paused: false,
+            connected: true,
+            samples_processed: self.samples_processed.load(Ordering::Relaxed),
+            errors: 0,
+            buffer_stats: self.ring.as_ref().map(|r| r.stats()),
+            decoder: None,
+        }
+    }
+
+    fn attach_ring_buffer(&mut self, buffer: Arc<AudioRingBuffer>) {
+        self.ring = Some(buffer);
+    }
+}
+
+impl_connectable_producer!(GeneratorProducer);
@@ -0,0 +1,356 @@
+// src/producers/link.rs
+//
+// Empfangsseite von `airlift_link`: nimmt Verbindungen eines `airlift_link`-
+// Consumers an und schreibt die Frames mit ihrem Original-`utc_ns` in den
+// Ring. Es ist immer nur ein Sender aktiv; eine neue Verbindung löst die alte
+// ab (z. B. nach Neustart des sendenden Nodes). Den Codec wählt der Producer
+// aus dem Angebot des Senders nach seiner eigenen Policy.
+use std::io::{self, BufReader, BufWriter};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context};
+
+use crate::aoip::link::{
+    read_hello, read_packet, write_answer, LinkChoice, LinkDecoder, LinkOffer, LinkPolicy, DEFAULT_LINK_PORT,
+};
+use crate::config::ProducerConfig;
+use crate::core::lock::lock_mutex;
+use crate::core::{AudioError, AudioRingBuffer, ErrorInfo, LastError, Producer, ProducerStatus};
+use crate::impl_connectable_producer;
+
+const ACCEPT_POLL: Duration = Duration::from_millis(100);
+/// Ohne Daten so lange gilt der Sender als getrennt
+const IDLE_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinkProducerConfig {
+    pub listen: SocketAddr,
+    /// Nur Sender mit diesem Stream-Namen annehmen
+    pub stream: Option<String>,
+    /// Codec-Policy (`network`, `codecs`, `bitrate`)
+    pub policy: LinkPolicy,
+}
+
+/// Gelesene `config`-Schlüssel (Strict-Modus, siehe `config::options`)
+pub const CONFIG_KEYS: &[&str] = &["transport", "listen", "stream"];
+
+impl LinkProducerConfig {
+    pub fn from_producer_config(name: &str, cfg: &ProducerConfig) -> anyhow::Result<Self> {
+        match cfg.config.get("transport").and_then(|v| v.as_str()) {
+            None | Some("tcp") => {}
+            Some("quic") => bail!("producer '{}': QUIC transport is not available in this build, use tcp", name),
+            Some(other) => bail!("producer '{}': unknown transport '{}' (tcp)", name, other),
+        }
+        let listen = match cfg.config.get("listen") {
+            None => SocketAddr::from(([0, 0, 0, 0], DEFAULT_LINK_PORT)),
+            Some(value) => value
+                .as_str()
+                .and_then(|text| text.parse().ok())
+                .ok_or_else(|| anyhow!("producer '{}': config.listen must be \"ip:port\", got {}", name, value))?,
+        };
+        Ok(Self {
+            listen,
+            stream: cfg
+                .config
+                .get("stream")
+                .and_then(|v| v.as_str())
+                .map(str::to_string),
+            policy: LinkPolicy::from_config("producer", name, &cfg.config)?,
+        })
+    }
+}
+
+pub struct LinkProducer {
+    name: String,
+    config: LinkProducerConfig,
+    running: Arc<AtomicBool>,
+    connected: Arc<AtomicBool>,
+    samples_processed: Arc<AtomicU64>,
+    errors: Arc<AtomicU64>,
+    last_error: Arc<LastError>,
+    /// Tatsächlich gebundene Adresse (bei Port 0 vom System gewählt)
+    local_addr: Arc<Mutex<Option<SocketAddr>>>,
+    /// Adresse des aktuellen Senders
+    peer: Arc<Mutex<Option<SocketAddr>>>,
+    /// Ausgehandelter Codec des aktuellen Senders
+    negotiated: Arc<Mutex<Option<LinkChoice>>>,
+    /// Verbindung des aktuellen Senders; Shutdown beendet dessen Session
+    current: Arc<Mutex<Option<TcpStream>>>,
+    ring: Option<Arc<AudioRingBuffer>>,
+    thread_handle: Option<thread::JoinHandle<()>>,
+}
+
+impl LinkProducer {
+    pub fn new(name: &str, cfg: &ProducerConfig) -> anyhow::Result<Self> {
+        Ok(Self::with_config(name, LinkProducerConfig::from_producer_config(name, cfg)?))
+    }
+
+    pub fn with_config(name: &str, config: LinkProducerConfig) -> Self {
+        Self {
+            name: name.to_string(),
+            config,
+            running: Arc::new(AtomicBool::new(false)),
+            connected: Arc::new(AtomicBool::new(false)),
+            samples_processed: Arc::new(AtomicU64::new(0)),
+            errors: Arc::new(AtomicU64::new(0)),
+            last_error: Arc::new(LastError::default()),
+            local_addr: Arc::new(Mutex::new(None)),
+            peer: Arc::new(Mutex::new(None)),
+            negotiated: Arc::new(Mutex::new(None)),
+            current: Arc::new(Mutex::new(None)),
+            ring: None,
+            thread_handle: None,
+        }
+    }
+
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        *lock_mutex(&self.local_addr, "link_producer.local_addr")
+    }
+
+    pub fn peer(&self) -> Option<SocketAddr> {
+        *lock_mutex(&self.peer, "link_producer.peer")
+    }
+
+    pub fn negotiated(&self) -> Option<LinkChoice> {
+        lock_mutex(&self.negotiated, "link_producer.negotiated").clone()
+    }
+}
+
+/// Eine Sender-Verbindung; endet bei EOF, Fehler, Leerlauf oder Shutdown
+/// durch einen neuen Sender.
+struct Session<'a> {
+    name: &'a str,
+    expected_stream: Option<&'a str>,
+    policy: &'a LinkPolicy,
+    negotiated: &'a Mutex<Option<LinkChoice>>,
+    ring: &'a AudioRingBuffer,
+    running: &'a AtomicBool,
+    samples_processed: &'a AtomicU64,
+    errors: &'a AtomicU64,
+    last_error: &'a LastError,
+}
+
+impl Session<'_> {
+    fn run(&self, stream: TcpStream) -> anyhow::Result<()> {
+        stream.set_read_timeout(Some(IDLE_TIMEOUT))?;
+        stream.set_nodelay(true)?;
+        let mut writer = BufWriter::new(stream.try_clone()?);
+        let mut reader = BufReader::new(stream);
+        let hello = read_hello(&mut reader).context("hello")?;
+        let choice = match self.accept(&hello.stream, &hello.offer) {
+            Ok(choice) => choice,
+            Err(message) => {
+                if hello.expects_answer() {
+                    let _ = write_answer(&mut writer, Err(&message));
+                }
+                return Err(AudioError::config(message).into());
+            }
+        };
+        if hello.expects_answer() {
+            write_answer(&mut writer, Ok(&choice)).context("answer")?;
+        }
+        log::info!(
+            "LinkProducer '{}': receiving stream '{}' ({})",
+            self.name,
+            hello.stream,
+            choice
+        );
+        *lock_mutex(self.negotiated, "link_producer.session") = Some(choice);
+
+        let mut decoder = LinkDecoder::new();
+        while self.running.load(Ordering::Relaxed) {
+            let Some(packet) = read_packet(&mut reader)? else {
+                return Ok(());
+            };
+            match decoder.decode(&packet) {
+                Ok(None) => {}
+                Ok(Some(frame)) => {
+                    self.samples_processed
+                        .fetch_add(frame.samples.len() as u64, Ordering::Relaxed);
+                    self.ring.push(frame);
+                }
+                Err(e) => {
+                    self.last_error.record_audio(&AudioError::codec(format!("{:#}", e)));
+                    if self.errors.fetch_add(1, Ordering::Relaxed) == 0 {
+                        log::warn!("LinkProducer '{}': {}", self.name, e);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Stream-Namen prüfen und den Codec wählen; Fehler gehen als Grund an
+    /// den Sender.
+    fn accept(&self, stream: &str, offer: &LinkOffer) -> Result<LinkChoice, String> {
+        if let Some(expected) = self.expected_stream {
+            if stream != expected {
+                return Err(format!("stream '{}' rejected (expected '{}')", stream, expected));
+            }
+        }
+        self.policy
+            .negotiate(offer)
+            .map_err(|e| format!("stream '{}' rejected: {}", stream, e))
+    }
+}
+
+fn shutdown(current: &Mutex<Option<TcpStream>>) {
+    if let Some(stream) = lock_mutex(current, "link_producer.shutdown").take() {
+        let _ = stream.shutdown(Shutdown::Both);
+    }
+}
+
+impl Producer for LinkProducer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn start(&mut self) -> anyhow::Result<()> {
+        if self.running.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        let ring = self
+            .ring
+            .clone()
+            .ok_or_else(|| anyhow!("link producer '{}' has no ring buffer", self.name))?;
+        let listener = TcpListener::bind(self.config.listen)
+            .with_context(|| format!("LinkProducer '{}': bind {} failed", self.name, self.config.listen))?;
+        listener.set_nonblocking(true)?;
+        let local = listener.local_addr()?;
+        *lock_mutex(&self.local_addr, "link_producer.start") = Some(local);
+        log::info!("LinkProducer '{}': listening on {}", self.name, local);
+
+        self.last_error.clear();
+        self.running.store(true, Ordering::SeqCst);
+        let running = self.running.clone();
+        let connected = self.connected.clone();
+        let samples_processed = self.samples_processed.clone();
+        let errors = self.errors.clone();
+        let last_error = self.last_error.clone();
+        let peer = self.peer.clone();
+        let current = self.current.clone();
+        let name = self.name.clone();
+        let expected = self.config.stream.clone();
+        let policy = self.config.policy.clone();
+        let negotiated = self.negotiated.clone();
+
+        self.thread_handle = Some(thread::spawn(move || {
+            // Generation je Verbindung: nur die aktuelle Session setzt den Status zurück
+            let generation = Arc::new(AtomicU64::new(0));
+            let mut sessions: Vec<thread::JoinHandle<()>> = Vec::new();
+
+            while running.load(Ordering::Relaxed) {
+                let (stream, addr) = match listener.accept() {
+                    Ok(accepted) => accepted,
+                    Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                        sessions.retain(|session| !session.is_finished());
+                        thread::sleep(ACCEPT_POLL);
+                        continue;
+                    }
+                    Err(e) => {
+                        log::warn!("LinkProducer '{}': accept failed: {}", name, e);
+                        errors.fetch_add(1, Ordering::Relaxed);
+                        last_error.record_audio(&AudioError::network(format!("accept: {}", e)));
+                        thread::sleep(ACCEPT_POLL);
+                        continue;
+                    }
+                };
+                let handle = match stream.set_nonblocking(false).and_then(|_| stream.try_clone()) {
+                    Ok(handle) => handle,
+                    Err(e) => {
+                        log::warn!("LinkProducer '{}': {}", name, e);
+                        continue;
+                    }
+                };
+                // Vorherigen Sender trennen
+                shutdown(&current);
+                *lock_mutex(&current, "link_producer.current") = Some(handle);
+                let mine = generation.fetch_add(1, Ordering::SeqCst) + 1;
+                log::info!("LinkProducer '{}': sender connected from {}", name, addr);
+                *lock_mutex(&peer, "link_producer.peer") = Some(addr);
+                connected.store(true, Ordering::SeqCst);
+
+                let generation = generation.clone();
+                let running = running.clone();
+                let connected = connected.clone();
+                let samples_processed = samples_processed.clone();
+                let errors = errors.clone();
+                let last_error = last_error.clone();
+                let peer = peer.clone();
+                let ring = ring.clone();
+                let name = name.clone();
+                let expected = expected.clone();
+                let policy = policy.clone();
+                let negotiated = negotiated.clone();
+                sessions.push(thread::spawn(move || {
+                    let session = Session {
+                        name: &name,
+                        expected_stream: expected.as_deref(),
+                        policy: &policy,
+                        negotiated: &negotiated,
+                        ring: &ring,
+                        running: &running,
+                        samples_processed: &samples_processed,
+                        errors: &errors,
+                        last_error: &last_error,
+                    };
+                    if let Err(e) = session.run(stream) {
+                        log::warn!("LinkProducer '{}': sender {}: {:#}", name, addr, e);
+                        errors.fetch_add(1, Ordering::Relaxed);
+                        last_error.record(&e);
+                    }
+                    // Nur der aktuelle Sender setzt den Status zurück
+                    if generation.load(Ordering::SeqCst) == mine {
+                        connected.store(false, Ordering::SeqCst);
+                        *lock_mutex(&peer, "link_producer.peer") = None;
+                        *lock_mutex(&negotiated, "link_producer.negotiated") = None;
+                        log::info!("LinkProducer '{}': sender {} disconnected", name, addr);
+                    }
+                }));
+            }
+            shutdown(&current);
+            for session in sessions {
+                let _ = session.join();
+            }
+            connected.store(false, Ordering::SeqCst);
+        }));
+        Ok(())
+    }
+
+    fn stop(&mut self) -> anyhow::Result<()> {
+        self.running.store(false, Ordering::SeqCst);
+        shutdown(&self.current);
+        if let Some(handle) = self.thread_handle.take() {
+            let _ = handle.join();
+        }
+        *lock_mutex(&self.peer, "link_producer.stop") = None;
+        *lock_mutex(&self.negotiated, "link_producer.stop") = None;
+        Ok(())
+    }
+
+    fn status(&self) -> ProducerStatus {
+        ProducerStatus {
+            running: self.running.load(Ordering::Relaxed),
+            paused: false,
+            connected: self.connected.load(Ordering::Relaxed),
+            samples_processed: self.samples_processed.load(Ordering::Relaxed),
+            errors: self.errors.load(Ordering::Relaxed),
+            buffer_stats: self.ring.as_ref().map(|r| r.stats()),
+            decoder: None,
+        }
+    }
+
+    fn attach_ring_buffer(&mut self, buffer: Arc<AudioRingBuffer>) {
+        self.ring = Some(buffer);
+    }
+
+    fn last_error(&self) -> Option<ErrorInfo> {
+        self.last_error.get()
+    }
+}
+
+impl_connectable_producer!(LinkProducer);
@@ -1,6 +1,17 @@
 #[cfg(feature = "alsa")]
 pub mod alsa;
+#[cfg(feature = "cpal")]
+pub mod cpal;
 pub mod file;
+pub mod generator;
+pub mod link;
+#[cfg(feature = "symphonia")]
+pub mod mpegts;
+pub mod pipe;
 pub mod sine;
+#[cfg(feature = "srt")]
+pub mod srt;
 pub mod wait;
+#[cfg(feature = "whip")]
+pub mod whip;
 pub mod ws;
@@ -0,0 +1,312 @@
+// src/producers/mpegts.rs
+//
+// MPEG-TS über UDP (Multicast oder Unicast), das übliche Übergabeformat von
+// Playout-Systemen. Der Demuxer wählt die erste Audio-PID aus der PMT
+// (MPEG-1/2 Audio oder AAC/ADTS), dekodiert mit symphonia und schreibt PCM in
+// den Slot-Buffer. Die Frame-Zeit folgt den PTS, verankert auf die Wanduhr.
+use crate::impl_connectable_producer;
+use std::net::{Ipv4Addr, SocketAddr, UdpSocket};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail, Context, Result};
+
+use crate::aoip::mpegts::{ts_packets, PtsClock, TsAudioCodec, TsDemuxer};
+use crate::config::{ConfigValues, ProducerConfig};
+use crate::core::lock::lock_mutex;
+use crate::core::timestamp::utc_ns_now;
+use crate::core::{AudioError, AudioRingBuffer, ErrorInfo, LastError, Producer, ProducerStatus};
+use crate::decoders::mpeg_audio::TsAudioDecoder;
+use crate::decoders::AudioDecoder;
+
+/// Wie oft die Empfangsschleife das Stop-Flag prüft.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+const INACTIVITY_TIMEOUT: Duration = Duration::from_secs(5);
+/// Größtes UDP-Datagramm (üblich sind 7 × 188 Byte)
+const MAX_DATAGRAM: usize = 65_536;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MpegTsConfig {
+    /// Multicast-Gruppe oder lokale Adresse mit Port
+    pub address: SocketAddr,
+    /// Lokale Adresse für den Multicast-Join
+    pub interface: Option<Ipv4Addr>,
+    /// Feste Audio-PID statt der ersten aus der PMT
+    pub pid: Option<u16>,
+}
+
+/// Gelesene `config`-Schlüssel (Strict-Modus, siehe `config::options`)
+pub const CONFIG_KEYS: &[&str] = &["address", "interface", "pid"];
+
+impl MpegTsConfig {
+    /// Erwartet `address` ("239.1.1.1:1234" oder "0.0.0.0:1234"); optional
+    /// `interface` und `pid`.
+    pub fn from_config(name: &str, config: &ProducerConfig) -> Result<Self> {
+        let values = ConfigValues::new("producer", name, &config.config);
+
+        let address = config
+            .config
+            .get("address")
+            .and_then(|v| v.as_str())
+            .or(config.device.as_deref())
+            .with_context(|| format!("producer '{}': config.address is required", name))?;
+        let address: SocketAddr = address
+            .parse()
+            .with_context(|| format!("producer '{}': invalid address '{}'", name, address))?;
+        if address.ip().is_multicast() && !address.is_ipv4() {
+            bail!("producer '{}': only IPv4 multicast is supported", name);
+        }
+        let interface = config
+            .config
+            .get("interface")
+            .and_then(|v| v.as_str())
+            .map(|ip| {
+                ip.parse::<Ipv4Addr>()
+                    .with_context(|| format!("producer '{}': invalid interface '{}'", name, ip))
+            })
+            .transpose()?;
+        let pid = match values.f64("pid")? {
+            Some(pid) => Some(values.check_range("pid", pid as u16, 0x0010, 0x1ffe)?),
+            None => None,
+        };
+
+        Ok(Self {
+            address,
+            interface,
+            pid,
+        })
+    }
+}
+
+struct Shared {
+    running: AtomicBool,
+    connected: AtomicBool,
+    samples_processed: AtomicU64,
+    errors: AtomicU64,
+    stream: Mutex<Option<(u16, TsAudioCodec)>>,
+    last_error: LastError,
+}
+
+pub struct MpegTsProducer {
+    name: String,
+    config: MpegTsConfig,
+    shared: Arc<Shared>,
+    ring_buffer: Option<Arc<AudioRingBuffer>>,
+    thread_handle: Option<std::thread::JoinHandle<()>>,
+}
+
+impl MpegTsProducer {
+    pub fn new(name: &str, config: &ProducerConfig) -> Result<Self> {
+        Ok(Self::with_config(name, MpegTsConfig::from_config(name, config)?))
+    }
+
+    pub fn with_config(name: &str, config: MpegTsConfig) -> Self {
+        Self {
+            name: name.to_string(),
+            config,
+            shared: Arc::new(Shared {
+                running: AtomicBool::new(false),
+                connected: AtomicBool::new(false),
+                samples_processed: AtomicU64::new(0),
+                errors: AtomicU64::new(0),
+                stream: Mutex::new(None),
+                last_error: LastError::default(),
+            }),
+            ring_buffer: None,
+            thread_handle: None,
+        }
+    }
+
+    /// Gewählte Audio-PID und Codec, sobald die PMT empfangen wurde.
+    pub fn audio_stream(&self) -> Option<(u16, TsAudioCodec)> {
+        *lock_mutex(&self.shared.stream, "mpegts_producer.stream")
+    }
+
+    fn open_socket(&self) -> Result<UdpSocket> {
+        let socket = match self.config.address {
+            SocketAddr::V4(group) if group.ip().is_multicast() => {
+                let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, group.port()))?;
+                socket.join_multicast_v4(
+                    group.ip(),
+                    &self.config.interface.unwrap_or(Ipv4Addr::UNSPECIFIED),
+                )?;
+                socket
+            }
+            address => UdpSocket::bind(address)?,
+        };
+        socket.set_read_timeout(Some(POLL_INTERVAL))?;
+        Ok(socket)
+    }
+}
+
+/// Demux- und Decoder-Zustand der Empfangsschleife.
+struct Receiver {
+    demuxer: TsDemuxer,
+    /// Decoder für die aktuell gewählte PID
+    decoder: Option<((u16, TsAudioCodec), TsAudioDecoder)>,
+    clock: PtsClock,
+}
+
+impl Receiver {
+    fn handle(&mut self, name: &str, shared: &Shared, ring: &AudioRingBuffer, datagram: &[u8]) {
+        for packet in ts_packets(datagram) {
+            let errors_before = self.demuxer.errors();
+            let pes = self.demuxer.push(packet);
+            let lost = self.demuxer.errors() - errors_before;
+            if lost > 0 {
+                shared.errors.fetch_add(lost, Ordering::Relaxed);
+                if let Some((_, decoder)) = self.decoder.as_mut() {
+                    decoder.reset();
+                }
+            }
+            let Some(pes) = pes else {
+                continue;
+            };
+
+            let stream = (pes.pid, pes.codec);
+            if self.decoder.as_ref().map(|(current, _)| *current) != Some(stream) {
+                *lock_mutex(&shared.stream, "mpegts_producer.stream") = Some(stream);
+                self.decoder = Some((stream, TsAudioDecoder::new(pes.codec)));
+            }
+            let Some((_, decoder)) = self.decoder.as_mut() else {
+                continue;
+            };
+            match decoder.decode(&pes.payload) {
+                Ok(Some(mut frame)) => {
+                    frame.utc_ns = self.clock.utc_ns(pes.pts, utc_ns_now());
+                    shared
+                        .samples_processed
+                        .fetch_add(frame.samples.len() as u64, Ordering::Relaxed);
+                    ring.push(frame);
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    shared.last_error.record_audio(&AudioError::codec(format!("{:#}", e)));
+                    if shared.errors.fetch_add(1, Ordering::Relaxed) == 0 {
+                        log::warn!("MpegTsProducer '{}': {}", name, e);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Producer for MpegTsProducer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn start(&mut self) -> Result<()> {
+        if self.shared.running.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+
+        let ring = self
+            .ring_buffer
+            .clone()
+            .ok_or_else(|| anyhow!("MpegTsProducer '{}' missing ring buffer", self.name))?;
+        let socket = self
+            .open_socket()
+            .map_err(|e| {
+                AudioError::network(format!("cannot open {}: {:#}", self.config.address, e))
+            })
+            .with_context(|| format!("MpegTsProducer '{}'", self.name))?;
+
+        log::info!(
+            "MpegTsProducer '{}': Starting ({}{})",
+            self.name,
+            self.config.address,
+            self.config
+                .pid
+                .map(|pid| format!(", PID {}", pid))
+                .unwrap_or_default()
+        );
+
+        self.shared.last_error.clear();
+        self.shared.running.store(true, Ordering::SeqCst);
+        let name = self.name.clone();
+        let shared = self.shared.clone();
+        let mut receiver = Receiver {
+            demuxer: TsDemuxer::new(self.config.pid),
+            decoder: None,
+            clock: PtsClock::default(),
+        };
+
+        self.thread_handle = Some(std::thread::spawn(move || {
+            let mut buf = vec![0u8; MAX_DATAGRAM];
+            let mut last_data: Option<Instant> = None;
+
+            while shared.running.load(Ordering::Relaxed) {
+                match socket.recv(&mut buf) {
+                    Ok(len) => {
+                        if last_data.is_none() {
+                            log::info!("MpegTsProducer '{}': receiving", name);
+                        }
+                        last_data = Some(Instant::now());
+                        shared.connected.store(true, Ordering::SeqCst);
+                        receiver.handle(&name, &shared, &ring, &buf[..len]);
+                    }
+                    Err(e)
+                        if matches!(
+                            e.kind(),
+                            std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+                        ) => {}
+                    Err(e) => {
+                        shared.errors.fetch_add(1, Ordering::Relaxed);
+                        shared
+                            .last_error
+                            .record_audio(&AudioError::network(format!("receive: {}", e)));
+                        log::warn!("MpegTsProducer '{}': receive error: {}", name, e);
+                        std::thread::sleep(POLL_INTERVAL);
+                    }
+                }
+                if last_data.is_some_and(|at| at.elapsed() >= INACTIVITY_TIMEOUT) {
+                    shared.last_error.record_audio(&AudioError::network(format!(
+                        "no datagrams for {} s",
+                        INACTIVITY_TIMEOUT.as_secs()
+                    )));
+                    log::warn!("MpegTsProducer '{}': inactivity timeout", name);
+                    last_data = None;
+                    shared.connected.store(false, Ordering::SeqCst);
+                }
+            }
+
+            shared.connected.store(false, Ordering::SeqCst);
+            log::info!("MpegTsProducer '{}': stopped", name);
+        }));
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        self.shared.running.store(false, Ordering::SeqCst);
+        if let Some(handle) = self.thread_handle.take() {
+            if handle.join().is_err() {
+                self.shared.errors.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        Ok(())
+    }
+
+    fn status(&self) -> ProducerStatus {
+        ProducerStatus {
+            running: self.shared.running.load(Ordering::Relaxed),
+            paused: false,
+            connected: self.shared.connected.load(Ordering::Relaxed),
+            samples_processed: self.shared.samples_processed.load(Ordering::Relaxed),
+            errors: self.shared.errors.load(Ordering::Relaxed),
+            buffer_stats: self.ring_buffer.as_ref().map(|buffer| buffer.stats()),
+            decoder: None,
+        }
+    }
+
+    fn attach_ring_buffer(&mut self, buffer: Arc<AudioRingBuffer>) {
+        self.ring_buffer = Some(buffer);
+    }
+
+    fn last_error(&self) -> Option<ErrorInfo> {
+        self.shared.last_error.get()
+    }
+}
+
+impl_connectable_producer!(MpegTsProducer);
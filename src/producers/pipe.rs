@@ -0,0 +1,369 @@
+// src/producers/pipe.rs
+//
+// Roh-PCM von stdin oder einer Named Pipe (FIFO), z. B. aus ffmpeg, arecord
+// oder eigener DSP. Format, Rate und Kanäle kommen aus der Config; die Daten
+// werden in Frames von `frame_ms` zerlegt und nach s16 gewandelt.
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::sync::{
+    atomic::{AtomicBool, AtomicU64, Ordering},
+    Arc,
+};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail};
+
+use crate::audio::sanitize_audio_path;
+use crate::config::{ConfigValues, ProducerConfig};
+use crate::core::{
+    AudioError, AudioRingBuffer, ErrorInfo, LastError, PcmFrame, Producer, ProducerStatus,
+};
+use crate::impl_connectable_producer;
+
+const DEFAULT_FRAME_MS: u64 = 20;
+/// Wartezeit, bevor eine FIFO nach EOF/Fehler neu geöffnet wird
+const REOPEN_DELAY: Duration = Duration::from_millis(500);
+
+/// Sampleformat der Rohdaten (Namen wie bei ffmpeg `-f`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RawFormat {
+    S16Le,
+    S16Be,
+    S24Le,
+    S32Le,
+    F32Le,
+}
+
+impl RawFormat {
+    pub fn parse(name: &str) -> anyhow::Result<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "s16le" | "s16" => Ok(Self::S16Le),
+            "s16be" => Ok(Self::S16Be),
+            "s24le" | "s24" => Ok(Self::S24Le),
+            "s32le" | "s32" => Ok(Self::S32Le),
+            "f32le" | "f32" => Ok(Self::F32Le),
+            other => bail!("unknown raw format '{}' (s16le, s16be, s24le, s32le, f32le)", other),
+        }
+    }
+
+    pub fn bytes_per_sample(self) -> usize {
+        match self {
+            Self::S16Le | Self::S16Be => 2,
+            Self::S24Le => 3,
+            Self::S32Le | Self::F32Le => 4,
+        }
+    }
+
+    /// Wandelt vollständige Samples nach s16; ein unvollständiger Rest wird ignoriert.
+    pub fn decode(self, bytes: &[u8]) -> Vec<i16> {
+        bytes
+            .chunks_exact(self.bytes_per_sample())
+            .map(|b| match self {
+                Self::S16Le => i16::from_le_bytes([b[0], b[1]]),
+                Self::S16Be => i16::from_be_bytes([b[0], b[1]]),
+                Self::S24Le => i16::from_le_bytes([b[1], b[2]]),
+                Self::S32Le => i16::from_le_bytes([b[2], b[3]]),
+                Self::F32Le => {
+                    let value = f32::from_le_bytes([b[0], b[1], b[2], b[3]]);
+                    (value * i16::MAX as f32).round().clamp(i16::MIN as f32, i16::MAX as f32) as i16
+                }
+            })
+            .collect()
+    }
+
+    /// Gegenstück zu `decode` für den `pipe`-Consumer.
+    pub fn encode(self, samples: &[i16]) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(samples.len() * self.bytes_per_sample());
+        for sample in samples {
+            match self {
+                Self::S16Le => bytes.extend_from_slice(&sample.to_le_bytes()),
+                Self::S16Be => bytes.extend_from_slice(&sample.to_be_bytes()),
+                Self::S24Le => bytes.extend_from_slice(&((*sample as i32) << 8).to_le_bytes()[..3]),
+                Self::S32Le => bytes.extend_from_slice(&((*sample as i32) << 16).to_le_bytes()),
+                Self::F32Le => bytes.extend_from_slice(&(*sample as f32 / i16::MAX as f32).to_le_bytes()),
+            }
+        }
+        bytes
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PipeSource {
+    Stdin,
+    /// FIFO oder Datei; wird nach EOF neu geöffnet, wenn `reopen` gesetzt ist
+    Path(PathBuf),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PipeConfig {
+    pub source: PipeSource,
+    pub format: RawFormat,
+    pub sample_rate: u32,
+    pub channels: u8,
+    pub frame_ms: u64,
+    /// Nach Wanduhr takten (Quelle liefert schneller als Echtzeit, z. B. ffmpeg ohne `-re`)
+    pub pace: bool,
+    pub reopen: bool,
+}
+
+/// Gelesene `config`-Schlüssel (Strict-Modus, siehe `config::options`)
+pub const CONFIG_KEYS: &[&str] = &["format", "frame_ms", "pace", "reopen"];
+
+impl PipeConfig {
+    pub fn from_producer_config(name: &str, cfg: &ProducerConfig) -> anyhow::Result<Self> {
+        let values = ConfigValues::new("producer", name, &cfg.config);
+        let source = match cfg.path.as_deref().map(str::trim) {
+            None | Some("") | Some("-") => PipeSource::Stdin,
+            Some(path) => PipeSource::Path(sanitize_audio_path(path)?),
+        };
+        let format = match cfg.config.get("format") {
+            None => RawFormat::S16Le,
+            Some(value) => RawFormat::parse(value.as_str().ok_or_else(|| {
+                anyhow!("producer '{}': config.format must be a string", name)
+            })?)
+            .map_err(|e| anyhow!("producer '{}': {}", name, e))?,
+        };
+        let sample_rate = cfg.sample_rate.unwrap_or(48_000);
+        values.check_range("sample_rate", sample_rate, 8_000, 192_000)?;
+        let channels = cfg.channels.unwrap_or(2);
+        values.check_range("channels", channels, 1, 8)?;
+        let frame_ms = values
+            .duration("frame_ms")?
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(DEFAULT_FRAME_MS);
+        values.check_range("frame_ms", frame_ms, 1, 1000)?;
+        let flag = |key: &str, default: bool| -> anyhow::Result<bool> {
+            match cfg.config.get(key) {
+                None => Ok(default),
+                Some(value) => value
+                    .as_bool()
+                    .ok_or_else(|| anyhow!("producer '{}': config.{} must be a boolean", name, key)),
+            }
+        };
+        let is_path = matches!(source, PipeSource::Path(_));
+
+        Ok(Self {
+            pace: flag("pace", false)?,
+            reopen: flag("reopen", is_path)? && is_path,
+            source,
+            format,
+            sample_rate,
+            channels,
+            frame_ms,
+        })
+    }
+
+    /// Bytes pro Frame (`frame_ms` über alle Kanäle)
+    pub fn frame_bytes(&self) -> usize {
+        let samples = (self.sample_rate as u64 * self.frame_ms / 1000).max(1) as usize;
+        samples * self.channels as usize * self.format.bytes_per_sample()
+    }
+}
+
+/// Liest bis `buf` voll ist oder EOF; liefert die Anzahl gelesener Bytes.
+fn read_full(reader: &mut dyn Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+pub struct PipeProducer {
+    name: String,
+    config: PipeConfig,
+    /// Pro Start ein eigenes Flag: ein blockierender Read kann nicht
+    /// abgebrochen werden, der alte Thread beendet sich beim nächsten Read.
+    running: Arc<AtomicBool>,
+    connected: Arc<AtomicBool>,
+    samples_processed: Arc<AtomicU64>,
+    errors: Arc<AtomicU64>,
+    last_error: Arc<LastError>,
+    ring: Option<Arc<AudioRingBuffer>>,
+    thread_handle: Option<thread::JoinHandle<()>>,
+}
+
+impl PipeProducer {
+    pub fn new(name: &str, cfg: &ProducerConfig) -> anyhow::Result<Self> {
+        Ok(Self::with_config(name, PipeConfig::from_producer_config(name, cfg)?))
+    }
+
+    pub fn with_config(name: &str, config: PipeConfig) -> Self {
+        Self {
+            name: name.to_string(),
+            config,
+            running: Arc::new(AtomicBool::new(false)),
+            connected: Arc::new(AtomicBool::new(false)),
+            samples_processed: Arc::new(AtomicU64::new(0)),
+            errors: Arc::new(AtomicU64::new(0)),
+            last_error: Arc::new(LastError::default()),
+            ring: None,
+            thread_handle: None,
+        }
+    }
+
+    fn open(source: &PipeSource) -> io::Result<Box<dyn Read + Send>> {
+        match source {
+            PipeSource::Stdin => Ok(Box::new(io::stdin())),
+            // Öffnen einer FIFO blockiert, bis ein Schreiber da ist
+            PipeSource::Path(path) => Ok(Box::new(File::open(path)?)),
+        }
+    }
+}
+
+impl Producer for PipeProducer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn start(&mut self) -> anyhow::Result<()> {
+        if self.running.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        let ring = self
+            .ring
+            .clone()
+            .ok_or_else(|| anyhow!("pipe producer '{}' has no ring buffer", self.name))?;
+
+        self.running = Arc::new(AtomicBool::new(true));
+        let running = self.running.clone();
+        let connected = self.connected.clone();
+        let samples_processed = self.samples_processed.clone();
+        let errors = self.errors.clone();
+        let last_error = self.last_error.clone();
+        last_error.clear();
+        let config = self.config.clone();
+        let name = self.name.clone();
+
+        log::info!(
+            "PipeProducer '{}': reading {:?} ({:?}, {} Hz, {} ch)",
+            name,
+            config.source,
+            config.format,
+            config.sample_rate,
+            config.channels
+        );
+
+        self.thread_handle = Some(thread::spawn(move || {
+            let mut buf = vec![0u8; config.frame_bytes()];
+            let frame_duration = Duration::from_millis(config.frame_ms);
+            let started = Instant::now();
+            let mut frames_sent: u32 = 0;
+
+            'outer: while running.load(Ordering::Relaxed) {
+                let mut reader = match Self::open(&config.source) {
+                    Ok(reader) => reader,
+                    Err(e) => {
+                        log::error!("PipeProducer '{}': open failed: {}", name, e);
+                        errors.fetch_add(1, Ordering::Relaxed);
+                        last_error.record_audio(&AudioError::device(format!(
+                            "open {:?}: {}",
+                            config.source, e
+                        )));
+                        if !config.reopen {
+                            break;
+                        }
+                        thread::sleep(REOPEN_DELAY);
+                        continue;
+                    }
+                };
+                connected.store(true, Ordering::Relaxed);
+
+                loop {
+                    let filled = match read_full(reader.as_mut(), &mut buf) {
+                        Ok(filled) => filled,
+                        Err(e) => {
+                            log::warn!("PipeProducer '{}': read failed: {}", name, e);
+                            errors.fetch_add(1, Ordering::Relaxed);
+                            last_error
+                                .record_audio(&AudioError::device(format!("read: {}", e)));
+                            0
+                        }
+                    };
+                    if !running.load(Ordering::Relaxed) {
+                        break 'outer;
+                    }
+
+                    let frame_len = config.format.bytes_per_sample() * config.channels as usize;
+                    let usable = filled - filled % frame_len;
+                    if usable > 0 {
+                        let samples = config.format.decode(&buf[..usable]);
+                        samples_processed.fetch_add(samples.len() as u64, Ordering::Relaxed);
+                        ring.push(PcmFrame {
+                            utc_ns: crate::core::timestamp::utc_ns_now(),
+                            samples,
+                            sample_rate: config.sample_rate,
+                            channels: config.channels,
+                            metadata: Default::default(),
+                        });
+                        frames_sent = frames_sent.saturating_add(1);
+                        if config.pace {
+                            let due = started + frame_duration * frames_sent;
+                            thread::sleep(due.saturating_duration_since(Instant::now()));
+                        }
+                    }
+
+                    // Kurzer Read = EOF (Schreiber weg) oder Fehler
+                    if filled < buf.len() {
+                        break;
+                    }
+                }
+
+                connected.store(false, Ordering::Relaxed);
+                if !config.reopen {
+                    log::info!("PipeProducer '{}': end of input", name);
+                    break;
+                }
+                log::info!("PipeProducer '{}': writer closed, reopening", name);
+                thread::sleep(REOPEN_DELAY);
+            }
+            running.store(false, Ordering::SeqCst);
+        }));
+        Ok(())
+    }
+
+    fn stop(&mut self) -> anyhow::Result<()> {
+        self.running.store(false, Ordering::SeqCst);
+        if let Some(handle) = self.thread_handle.take() {
+            // Nur joinen, wenn der Thread nicht in einem Read/Open hängt
+            if handle.is_finished() {
+                let _ = handle.join();
+            } else {
+                log::debug!(
+                    "PipeProducer '{}': reader still blocked, detaching",
+                    self.name
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn status(&self) -> ProducerStatus {
+        ProducerStatus {
+            running: self.running.load(Ordering::Relaxed),
+            paused: false,
+            connected: self.connected.load(Ordering::Relaxed),
+            samples_processed: self.samples_processed.load(Ordering::Relaxed),
+            errors: self.errors.load(Ordering::Relaxed),
+            buffer_stats: self.ring.as_ref().map(|r| r.stats()),
+            decoder: None,
+        }
+    }
+
+    fn attach_ring_buffer(&mut self, buffer: Arc<AudioRingBuffer>) {
+        self.ring = Some(buffer);
+    }
+
+    fn last_error(&self) -> Option<ErrorInfo> {
+        self.last_error.get()
+    }
+}
+
+impl_connectable_producer!(PipeProducer);
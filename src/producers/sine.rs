@@ -75,6 +75,7 @@ impl Producer for SineProducer {
                         samples,
                         sample_rate: rate,
                         channels: 2,
+                        metadata: Default::default(),
                     });
                 }
 
@@ -95,10 +96,12 @@ impl Producer for SineProducer {
     fn status(&self) -> ProducerStatus {
         ProducerStatus {
             running: self.running.load(Ordering::Relaxed),
+            paused: false,
             connected: true,
             samples_processed: self.samples_processed.load(Ordering::Relaxed),
             errors: 0,
             buffer_stats: self.ring.as_ref().map(|r| r.stats()),
+            decoder: None,
         }
     }
 
@@ -0,0 +1,441 @@
+// src/producers/srt.rs
+//
+// SRT-Input als Producer: empfängt RFMA-Pakete (Magic "RFMA", seq, utc_ns,
+// Payload-Länge, Payload) im Listener- oder Caller-Modus und schreibt die
+// dekodierten Frames in den Slot-Buffer (`producer:<name>`). Ohne Decoder wird
+// die Payload als s16le-PCM interpretiert (wie im alten `srt_in`).
+use crate::impl_connectable_producer;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context, Result};
+use futures_util::{StreamExt, TryStreamExt};
+use srt_tokio::{SrtListener, SrtSocket};
+
+use crate::config::{ConfigValues, ProducerConfig};
+use crate::core::lock::lock_mutex;
+use crate::core::{
+    AudioError, AudioRingBuffer, ErrorInfo, LastError, PcmFrame, Producer, ProducerStatus,
+};
+use crate::decoders::AudioDecoder;
+
+const DEFAULT_LATENCY: Duration = Duration::from_millis(120);
+/// Wie oft die Empfangsschleife das Stop-Flag prüft.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+const INACTIVITY_TIMEOUT: Duration = Duration::from_secs(5);
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+const RFMA_MAGIC: &[u8; 4] = b"RFMA";
+const RFMA_HEADER_LEN: usize = 4 + 8 + 8 + 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SrtMode {
+    Listener,
+    Caller,
+}
+
+#[derive(Debug, Clone)]
+pub struct SrtConfig {
+    pub mode: SrtMode,
+    /// Listener: lokale Bind-Adresse, Caller: Gegenstelle
+    pub address: SocketAddr,
+    pub latency: Duration,
+    pub streamid: Option<String>,
+    pub sample_rate: u32,
+    pub channels: u8,
+}
+
+/// Gelesene `config`-Schlüssel (Strict-Modus, siehe `config::options`)
+pub const CONFIG_KEYS: &[&str] = &["mode", "address", "latency_ms", "streamid"];
+
+impl SrtConfig {
+    /// Erwartet `address`; optional `mode` ("listener"/"caller"), `latency_ms`
+    /// und `streamid`.
+    pub fn from_config(name: &str, config: &ProducerConfig) -> Result<Self> {
+        let values = ConfigValues::new("producer", name, &config.config);
+
+        let mode = match config.config.get("mode").and_then(|v| v.as_str()) {
+            None | Some("listener") => SrtMode::Listener,
+            Some("caller") => SrtMode::Caller,
+            Some(other) => bail!(
+                "producer '{}': config.mode must be \"listener\" or \"caller\", got \"{}\"",
+                name,
+                other
+            ),
+        };
+
+        let address = config
+            .config
+            .get("address")
+            .and_then(|v| v.as_str())
+            .or(config.device.as_deref())
+            .with_context(|| format!("producer '{}': config.address is required", name))?;
+        let address: SocketAddr = address
+            .parse()
+            .with_context(|| format!("producer '{}': invalid address '{}'", name, address))?;
+
+        let latency = values.duration("latency_ms")?.unwrap_or(DEFAULT_LATENCY);
+        values.check_range("latency_ms", latency.as_millis() as u64, 20, 8_000)?;
+
+        Ok(Self {
+            mode,
+            address,
+            latency,
+            streamid: config
+                .config
+                .get("streamid")
+                .and_then(|v| v.as_str())
+                .map(str::to_string),
+            sample_rate: config.sample_rate.unwrap_or(48_000),
+            channels: config.channels.unwrap_or(2),
+        })
+    }
+}
+
+/// Ein RFMA-Paket aus dem SRT-Stream.
+pub struct RfmaPacket<'a> {
+    pub seq: u64,
+    pub utc_ns: u64,
+    pub payload: &'a [u8],
+}
+
+pub fn parse_rfma(buf: &[u8]) -> Result<RfmaPacket<'_>> {
+    if buf.len() < RFMA_HEADER_LEN {
+        let message = format!("RFMA packet too short ({} bytes)", buf.len());
+        return Err(AudioError::codec(message).into());
+    }
+    if &buf[..4] != RFMA_MAGIC {
+        return Err(AudioError::codec("invalid RFMA magic").into());
+    }
+    let seq = u64::from_be_bytes(buf[4..12].try_into()?);
+    let utc_ns = u64::from_be_bytes(buf[12..20].try_into()?);
+    let len = u32::from_be_bytes(buf[20..24].try_into()?) as usize;
+    let payload = buf
+        .get(RFMA_HEADER_LEN..RFMA_HEADER_LEN + len)
+        .ok_or_else(|| {
+            AudioError::codec(format!("RFMA payload truncated (expected {} bytes)", len))
+        })?;
+    Ok(RfmaPacket { seq, utc_ns, payload })
+}
+
+struct Shared {
+    running: AtomicBool,
+    connected: AtomicBool,
+    samples_processed: AtomicU64,
+    errors: AtomicU64,
+    last_error: LastError,
+}
+
+pub struct SrtProducer {
+    name: String,
+    config: SrtConfig,
+    shared: Arc<Shared>,
+    ring_buffer: Option<Arc<AudioRingBuffer>>,
+    decoder: Arc<Mutex<Option<Box<dyn AudioDecoder>>>>,
+    thread_handle: Option<std::thread::JoinHandle<()>>,
+}
+
+impl SrtProducer {
+    pub fn new(name: &str, config: &ProducerConfig) -> Result<Self> {
+        Ok(Self::with_config(name, SrtConfig::from_config(name, config)?))
+    }
+
+    pub fn with_config(name: &str, config: SrtConfig) -> Self {
+        Self {
+            name: name.to_string(),
+            config,
+            shared: Arc::new(Shared {
+                running: AtomicBool::new(false),
+                connected: AtomicBool::new(false),
+                samples_processed: AtomicU64::new(0),
+                errors: AtomicU64::new(0),
+                last_error: LastError::default(),
+            }),
+            ring_buffer: None,
+            decoder: Arc::new(Mutex::new(None)),
+            thread_handle: None,
+        }
+    }
+}
+
+impl Producer for SrtProducer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn start(&mut self) -> Result<()> {
+        if self.shared.running.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+
+        let ring = self
+            .ring_buffer
+            .clone()
+            .ok_or_else(|| anyhow!("SrtProducer '{}' missing ring buffer", self.name))?;
+
+        log::info!(
+            "SrtProducer '{}': Starting ({:?} {}, latency {} ms)",
+            self.name,
+            self.config.mode,
+            self.config.address,
+            self.config.latency.as_millis()
+        );
+
+        self.shared.last_error.clear();
+        self.shared.running.store(true, Ordering::SeqCst);
+
+        let name = self.name.clone();
+        let config = self.config.clone();
+        let shared = self.shared.clone();
+        let decoder = self.decoder.clone();
+
+        let handle = std::thread::spawn(move || {
+            let runtime = match tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+            {
+                Ok(runtime) => runtime,
+                Err(e) => {
+                    log::error!("SrtProducer '{}': failed to create runtime: {}", name, e);
+                    shared
+                        .last_error
+                        .record_audio(&AudioError::internal(format!("tokio runtime: {}", e)));
+                    shared.errors.fetch_add(1, Ordering::Relaxed);
+                    shared.running.store(false, Ordering::SeqCst);
+                    return;
+                }
+            };
+
+            runtime.block_on(async {
+                while shared.running.load(Ordering::Relaxed) {
+                    let result = match config.mode {
+                        SrtMode::Listener => accept(&config, &shared).await,
+                        SrtMode::Caller => call(&config).await,
+                    };
+                    match result {
+                        Ok(Some(socket)) => {
+                            receive(&name, &config, &shared, &ring, &decoder, socket).await
+                        }
+                        Ok(None) => {}
+                        Err(e) => {
+                            shared.errors.fetch_add(1, Ordering::Relaxed);
+                            shared.last_error.record(&e);
+                            log::warn!("SrtProducer '{}': connection failed: {}", name, e);
+                            tokio::time::sleep(RECONNECT_DELAY).await;
+                        }
+                    }
+                }
+            });
+
+            shared.connected.store(false, Ordering::SeqCst);
+            log::info!("SrtProducer '{}': stopped", name);
+        });
+
+        self.thread_handle = Some(handle);
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        self.shared.running.store(false, Ordering::SeqCst);
+        if let Some(handle) = self.thread_handle.take() {
+            if handle.join().is_err() {
+                self.shared.errors.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        Ok(())
+    }
+
+    fn status(&self) -> ProducerStatus {
+        ProducerStatus {
+            running: self.shared.running.load(Ordering::Relaxed),
+            paused: false,
+            connected: self.shared.connected.load(Ordering::Relaxed),
+            samples_processed: self.shared.samples_processed.load(Ordering::Relaxed),
+            errors: self.shared.errors.load(Ordering::Relaxed),
+            buffer_stats: self.ring_buffer.as_ref().map(|buffer| buffer.stats()),
+            decoder: None,
+        }
+    }
+
+    fn attach_ring_buffer(&mut self, buffer: Arc<AudioRingBuffer>) {
+        self.ring_buffer = Some(buffer);
+    }
+
+    fn attach_decoder(&mut self, decoder: Box<dyn AudioDecoder>) {
+        *lock_mutex(&self.decoder, "srt_producer.attach_decoder") = Some(decoder);
+    }
+
+    fn last_error(&self) -> Option<ErrorInfo> {
+        self.shared.last_error.get()
+    }
+}
+
+/// Wartet auf einen Caller; mit `streamid` werden andere Stream-IDs abgewiesen.
+async fn accept(config: &SrtConfig, shared: &Shared) -> Result<Option<SrtSocket>> {
+    let (_listener, mut incoming) = SrtListener::builder()
+        .latency(config.latency)
+        .bind(config.address)
+        .await
+        .map_err(|e| AudioError::network(format!("bind {}: {}", config.address, e)))?;
+    let mut requests = incoming.incoming();
+
+    while shared.running.load(Ordering::Relaxed) {
+        let request = match tokio::time::timeout(POLL_INTERVAL, requests.next()).await {
+            Ok(Some(request)) => request,
+            Ok(None) => return Ok(None),
+            Err(_) => continue,
+        };
+
+        let requested = request.stream_id().map(|id| id.to_string());
+        if let Some(expected) = &config.streamid {
+            if requested.as_deref() != Some(expected.as_str()) {
+                log::warn!(
+                    "SRT listener {}: rejecting stream id {:?} from {}",
+                    config.address,
+                    requested,
+                    request.remote()
+                );
+                let _ = request
+                    .reject(srt_tokio::options::RejectReason::Server(
+                        srt_tokio::options::ServerRejectReason::BadRequest,
+                    ))
+                    .await;
+                continue;
+            }
+        }
+        let socket = request
+            .accept(None)
+            .await
+            .map_err(|e| AudioError::network(format!("accept: {}", e)))?;
+        return Ok(Some(socket));
+    }
+    Ok(None)
+}
+
+async fn call(config: &SrtConfig) -> Result<Option<SrtSocket>> {
+    let socket = SrtSocket::builder()
+        .latency(config.latency)
+        .call(config.address, config.streamid.as_deref())
+        .await
+        .map_err(|e| AudioError::network(format!("call {}: {}", config.address, e)))?;
+    Ok(Some(socket))
+}
+
+async fn receive(
+    name: &str,
+    config: &SrtConfig,
+    shared: &Shared,
+    ring: &AudioRingBuffer,
+    decoder: &Mutex<Option<Box<dyn AudioDecoder>>>,
+    mut socket: SrtSocket,
+) {
+    shared.connected.store(true, Ordering::SeqCst);
+    log::info!("SrtProducer '{}': connected", name);
+    let mut idle = Duration::ZERO;
+
+    while shared.running.load(Ordering::Relaxed) {
+        match tokio::time::timeout(POLL_INTERVAL, socket.try_next()).await {
+            Ok(Ok(Some((_instant, message)))) => {
+                idle = Duration::ZERO;
+                match decode_packet(config, decoder, &message) {
+                    Ok(Some(frame)) => {
+                        shared
+                            .samples_processed
+                            .fetch_add(frame.samples.len() as u64, Ordering::Relaxed);
+                        ring.push(frame);
+                    }
+                    Ok(None) => {}
+                    Err(e) => {
+                        shared.errors.fetch_add(1, Ordering::Relaxed);
+                        shared.last_error.record(&e);
+                        log::debug!("SrtProducer '{}': dropping packet: {}", name, e);
+                    }
+                }
+            }
+            Ok(Ok(None)) => {
+                log::info!("SrtProducer '{}': peer disconnected", name);
+                break;
+            }
+            Ok(Err(e)) => {
+                shared.errors.fetch_add(1, Ordering::Relaxed);
+                shared
+                    .last_error
+                    .record_audio(&AudioError::network(format!("receive: {}", e)));
+                log::warn!("SrtProducer '{}': receive error: {}", name, e);
+                break;
+            }
+            Err(_) => {
+                idle += POLL_INTERVAL;
+                if idle >= INACTIVITY_TIMEOUT {
+                    shared.last_error.record_audio(&AudioError::network(format!(
+                        "no packets for {} s",
+                        INACTIVITY_TIMEOUT.as_secs()
+                    )));
+                    log::warn!("SrtProducer '{}': inactivity timeout", name);
+                    break;
+                }
+            }
+        }
+    }
+
+    shared.connected.store(false, Ordering::SeqCst);
+}
+
+fn decode_packet(
+    config: &SrtConfig,
+    decoder: &Mutex<Option<Box<dyn AudioDecoder>>>,
+    message: &[u8],
+) -> Result<Option<PcmFrame>> {
+    let packet = parse_rfma(message)?;
+
+    if let Some(decoder) = lock_mutex(decoder, "srt_producer.decode").as_mut() {
+        return Ok(decoder.decode(packet.payload)?.map(|mut frame| {
+            frame.utc_ns = packet.utc_ns;
+            frame
+        }));
+    }
+
+    if packet.payload.len() % 2 != 0 {
+        let message = format!("invalid PCM payload length {}", packet.payload.len());
+        return Err(AudioError::codec(message).into());
+    }
+    let samples = packet
+        .payload
+        .chunks_exact(2)
+        .map(|b| i16::from_le_bytes([b[0], b[1]]))
+        .collect();
+    Ok(Some(PcmFrame {
+        utc_ns: packet.utc_ns,
+        samples,
+        sample_rate: config.sample_rate,
+        channels: config.channels,
+        metadata: Default::default(),
+    }))
+}
+
+impl_connectable_producer!(SrtProducer);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_rfma_packet() {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(b"RFMA");
+        buf.extend_from_slice(&7u64.to_be_bytes());
+        buf.extend_from_slice(&42u64.to_be_bytes());
+        buf.extend_from_slice(&4u32.to_be_bytes());
+        buf.extend_from_slice(&[1, 0, 2, 0]);
+
+        let packet = parse_rfma(&buf).unwrap();
+        assert_eq!(packet.seq, 7);
+        assert_eq!(packet.utc_ns, 42);
+        assert_eq!(packet.payload, &[1, 0, 2, 0]);
+
+        assert!(parse_rfma(&buf[..buf.len() - 1]).is_err());
+        assert!(parse_rfma(b"XXXX").is_err());
+    }
+}
@@ -1,6 +1,7 @@
 use std::sync::{Condvar, Mutex};
 use std::time::Duration;
 
+#[derive(Debug)]
 pub struct StopWait {
     lock: Mutex<()>,
     condvar: Condvar,
@@ -0,0 +1,589 @@
+// src/producers/whip.rs
+//
+// WHIP-Ingest (WebRTC-HTTP Ingestion Protocol): Browser und Hardware-Encoder
+// schicken per `POST /whip/<producer>` ein SDP-Offer und erhalten die Antwort
+// (ohne Trickle-ICE, Kandidaten stecken im Answer). Die Opus-RTP-Pakete
+// laufen durch den angehängten `AudioDecoder` (Default: `OpusDecoder`) in den
+// Slot-Buffer (`producer:<name>`).
+use crate::impl_connectable_producer;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, Weak};
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context, Result};
+use webrtc::api::interceptor_registry::register_default_interceptors;
+use webrtc::api::media_engine::{MediaEngine, MIME_TYPE_OPUS};
+use webrtc::api::{APIBuilder, API};
+use webrtc::ice_transport::ice_server::RTCIceServer;
+use webrtc::interceptor::registry::Registry;
+use webrtc::peer_connection::configuration::RTCConfiguration;
+use webrtc::peer_connection::peer_connection_state::RTCPeerConnectionState;
+use webrtc::peer_connection::sdp::session_description::RTCSessionDescription;
+use webrtc::peer_connection::RTCPeerConnection;
+use webrtc::rtp_transceiver::rtp_codec::{
+    RTCRtpCodecCapability, RTCRtpCodecParameters, RTPCodecType,
+};
+use webrtc::track::track_remote::TrackRemote;
+
+use crate::config::{ConfigValues, ProducerConfig};
+use crate::core::lock::lock_mutex;
+use crate::core::timestamp::utc_ns_now;
+use crate::core::{AudioRingBuffer, Producer, ProducerStatus};
+use crate::decoders::opus::{OpusDecoder, OpusDecoderOptions};
+use crate::decoders::AudioDecoder;
+
+const OPUS_PAYLOAD_TYPE: u8 = 111;
+const ICE_GATHER_TIMEOUT: Duration = Duration::from_secs(5);
+/// Größere Lücken werden nicht per PLC aufgefüllt, sondern übersprungen.
+const MAX_CONCEALED_PACKETS: u16 = 5;
+
+#[derive(Debug, Clone)]
+pub struct WhipConfig {
+    /// Erwarteter Bearer-Token (`Authorization: Bearer <token>`)
+    pub token: Option<String>,
+    pub ice_servers: Vec<String>,
+    pub max_sessions: usize,
+    pub channels: u8,
+    /// Optionen für den Standard-Decoder (`config.opus`)
+    pub opus: OpusDecoderOptions,
+}
+
+/// Gelesene `config`-Schlüssel (Strict-Modus, siehe `config::options`)
+pub const CONFIG_KEYS: &[&str] = &["ice_servers", "max_sessions", "token", "opus"];
+
+impl WhipConfig {
+    /// Optional `token`, `ice_servers` (Liste von URLs), `max_sessions` und
+    /// `opus` (Decoder-Optionen, siehe `OpusDecoderOptions::from_config`).
+    pub fn from_config(name: &str, config: &ProducerConfig) -> Result<Self> {
+        let values = ConfigValues::new("producer", name, &config.config);
+
+        let ice_servers = match config.config.get("ice_servers") {
+            None => Vec::new(),
+            Some(value) => value
+                .as_array()
+                .with_context(|| format!("producer '{}': config.ice_servers must be a list", name))?
+                .iter()
+                .map(|v| {
+                    v.as_str().map(str::to_string).with_context(|| {
+                        format!("producer '{}': config.ice_servers must contain URLs", name)
+                    })
+                })
+                .collect::<Result<_>>()?,
+        };
+
+        let max_sessions = config
+            .config
+            .get("max_sessions")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(1);
+        values.check_range("max_sessions", max_sessions, 1, 16)?;
+
+        let channels = config.channels.unwrap_or(2);
+        values.check_range("channels", channels, 1, 2)?;
+
+        Ok(Self {
+            token: config
+                .config
+                .get("token")
+                .and_then(|v| v.as_str())
+                .map(str::to_string),
+            ice_servers,
+            max_sessions: max_sessions as usize,
+            channels,
+            opus: OpusDecoderOptions::from_config(name, config.config.get("opus"))?,
+        })
+    }
+}
+
+/// Fehler beim Anlegen einer Session, mit passendem HTTP-Status.
+#[derive(Debug)]
+pub enum WhipError {
+    Unauthorized,
+    TooManySessions,
+    BadOffer(anyhow::Error),
+    Internal(anyhow::Error),
+}
+
+impl WhipError {
+    pub fn status(&self) -> u16 {
+        match self {
+            WhipError::Unauthorized => 401,
+            WhipError::TooManySessions => 503,
+            WhipError::BadOffer(_) => 400,
+            WhipError::Internal(_) => 500,
+        }
+    }
+}
+
+impl std::fmt::Display for WhipError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WhipError::Unauthorized => write!(f, "invalid or missing bearer token"),
+            WhipError::TooManySessions => write!(f, "maximum number of WHIP sessions reached"),
+            WhipError::BadOffer(e) => write!(f, "invalid SDP offer: {:#}", e),
+            WhipError::Internal(e) => write!(f, "{:#}", e),
+        }
+    }
+}
+
+struct Shared {
+    running: AtomicBool,
+    active_tracks: AtomicUsize,
+    samples_processed: AtomicU64,
+    errors: AtomicU64,
+}
+
+/// Laufender Endpunkt eines WHIP-Producers; lebt von `start` bis `stop`.
+pub struct WhipEndpoint {
+    name: String,
+    config: WhipConfig,
+    handle: tokio::runtime::Handle,
+    api: API,
+    shared: Arc<Shared>,
+    ring: Arc<AudioRingBuffer>,
+    decoder: Arc<Mutex<Option<Box<dyn AudioDecoder>>>>,
+    sessions: Mutex<HashMap<String, Arc<RTCPeerConnection>>>,
+    next_session: AtomicU64,
+}
+
+impl WhipEndpoint {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn session_count(&self) -> usize {
+        lock_mutex(&self.sessions, "whip.session_count").len()
+    }
+
+    fn authorize(&self, authorization: Option<&str>) -> Result<(), WhipError> {
+        match &self.config.token {
+            None => Ok(()),
+            Some(token) => match authorization.and_then(|h| h.strip_prefix("Bearer ")) {
+                Some(given) if given.trim() == token => Ok(()),
+                _ => Err(WhipError::Unauthorized),
+            },
+        }
+    }
+
+    /// Beantwortet ein SDP-Offer; liefert Session-ID und SDP-Answer.
+    pub fn offer(
+        self: &Arc<Self>,
+        sdp: String,
+        authorization: Option<&str>,
+    ) -> Result<(String, String), WhipError> {
+        self.authorize(authorization)?;
+        if !self.shared.running.load(Ordering::Relaxed) {
+            return Err(WhipError::Internal(anyhow!("producer '{}' is stopped", self.name)));
+        }
+        if self.session_count() >= self.config.max_sessions {
+            return Err(WhipError::TooManySessions);
+        }
+
+        let session_id = format!(
+            "{:x}-{}",
+            utc_ns_now() / 1_000_000,
+            self.next_session.fetch_add(1, Ordering::Relaxed)
+        );
+        let endpoint = self.clone();
+        let id = session_id.clone();
+        let answer = self
+            .handle
+            .block_on(async move { endpoint.negotiate(id, sdp).await })?;
+        log::info!("WhipProducer '{}': session {} created", self.name, session_id);
+        Ok((session_id, answer))
+    }
+
+    async fn negotiate(self: Arc<Self>, session_id: String, sdp: String) -> Result<String, WhipError> {
+        let offer = RTCSessionDescription::offer(sdp).map_err(|e| WhipError::BadOffer(e.into()))?;
+
+        let config = RTCConfiguration {
+            ice_servers: if self.config.ice_servers.is_empty() {
+                Vec::new()
+            } else {
+                vec![RTCIceServer {
+                    urls: self.config.ice_servers.clone(),
+                    ..Default::default()
+                }]
+            },
+            ..Default::default()
+        };
+        let pc = Arc::new(
+            self.api
+                .new_peer_connection(config)
+                .await
+                .map_err(|e| WhipError::Internal(e.into()))?,
+        );
+
+        let weak: Weak<WhipEndpoint> = Arc::downgrade(&self);
+        pc.on_track(Box::new(move |track, _receiver, _transceiver| {
+            let weak = weak.clone();
+            Box::pin(async move {
+                if let Some(endpoint) = weak.upgrade() {
+                    endpoint.receive(track).await;
+                }
+            })
+        }));
+
+        let weak: Weak<WhipEndpoint> = Arc::downgrade(&self);
+        let id = session_id.clone();
+        pc.on_peer_connection_state_change(Box::new(move |state| {
+            let weak = weak.clone();
+            let id = id.clone();
+            Box::pin(async move {
+                let Some(endpoint) = weak.upgrade() else {
+                    return;
+                };
+                log::info!("WhipProducer '{}': session {} {}", endpoint.name, id, state);
+                if matches!(
+                    state,
+                    RTCPeerConnectionState::Failed | RTCPeerConnectionState::Closed
+                ) {
+                    // Nicht im Callback der Verbindung selbst schließen
+                    tokio::spawn(async move {
+                        endpoint.remove_session(&id).await;
+                    });
+                }
+            })
+        }));
+
+        let result = async {
+            pc.set_remote_description(offer)
+                .await
+                .map_err(|e| WhipError::BadOffer(e.into()))?;
+            let answer = pc
+                .create_answer(None)
+                .await
+                .map_err(|e| WhipError::BadOffer(e.into()))?;
+            let mut gathered = pc.gathering_complete_promise().await;
+            pc.set_local_description(answer)
+                .await
+                .map_err(|e| WhipError::Internal(e.into()))?;
+            // Kein Trickle-ICE: Answer erst mit allen Kandidaten zurückgeben
+            let _ = tokio::time::timeout(ICE_GATHER_TIMEOUT, gathered.recv()).await;
+            pc.local_description()
+                .await
+                .map(|desc| desc.sdp)
+                .ok_or_else(|| WhipError::Internal(anyhow!("no local description")))
+        }
+        .await;
+
+        match result {
+            Ok(answer) => {
+                lock_mutex(&self.sessions, "whip.negotiate").insert(session_id, pc);
+                Ok(answer)
+            }
+            Err(e) => {
+                let _ = pc.close().await;
+                Err(e)
+            }
+        }
+    }
+
+    async fn receive(&self, track: Arc<TrackRemote>) {
+        let codec = track.codec();
+        if !codec.capability.mime_type.eq_ignore_ascii_case(MIME_TYPE_OPUS) {
+            log::warn!(
+                "WhipProducer '{}': ignoring {} track",
+                self.name,
+                codec.capability.mime_type
+            );
+            return;
+        }
+
+        self.shared.active_tracks.fetch_add(1, Ordering::SeqCst);
+        let mut last_seq: Option<u16> = None;
+
+        while self.shared.running.load(Ordering::Relaxed) {
+            let packet = match track.read_rtp().await {
+                Ok((packet, _)) => packet,
+                Err(e) => {
+                    log::debug!("WhipProducer '{}': track ended: {}", self.name, e);
+                    break;
+                }
+            };
+
+            let seq = packet.header.sequence_number;
+            let lost = last_seq.map_or(0, |last| seq.wrapping_sub(last).wrapping_sub(1));
+            if lost >= u16::MAX / 2 {
+                // Verspätetes/dupliziertes Paket
+                continue;
+            }
+            last_seq = Some(seq);
+
+            let mut decoder = lock_mutex(&self.decoder, "whip.receive");
+            if decoder.is_none() {
+                match OpusDecoder::with_options(self.config.channels, self.config.opus.clone()) {
+                    Ok(opus) => *decoder = Some(Box::new(opus)),
+                    Err(e) => {
+                        self.shared.errors.fetch_add(1, Ordering::Relaxed);
+                        log::error!("WhipProducer '{}': {}", self.name, e);
+                        break;
+                    }
+                }
+            }
+            let decoder = decoder.as_mut().expect("decoder initialised");
+
+            // Kleine Lücken per FEC bzw. Packet-Loss-Concealment füllen
+            let mut frames = Vec::new();
+            if lost > 0 && lost <= MAX_CONCEALED_PACKETS {
+                match decoder.conceal(lost, &packet.payload) {
+                    Ok(concealed) => frames = concealed,
+                    Err(e) => {
+                        self.shared.errors.fetch_add(1, Ordering::Relaxed);
+                        log::debug!("WhipProducer '{}': concealment failed: {}", self.name, e);
+                    }
+                }
+            }
+            match decoder.decode(&packet.payload) {
+                Ok(frame) => frames.extend(frame),
+                Err(e) => {
+                    self.shared.errors.fetch_add(1, Ordering::Relaxed);
+                    log::debug!("WhipProducer '{}': dropping packet: {}", self.name, e);
+                }
+            }
+            for frame in frames {
+                self.shared
+                    .samples_processed
+                    .fetch_add(frame.samples.len() as u64, Ordering::Relaxed);
+                self.ring.push(frame);
+            }
+        }
+
+        self.shared.active_tracks.fetch_sub(1, Ordering::SeqCst);
+    }
+
+    async fn remove_session(&self, session_id: &str) -> bool {
+        let pc = lock_mutex(&self.sessions, "whip.remove_session").remove(session_id);
+        match pc {
+            Some(pc) => {
+                let _ = pc.close().await;
+                log::info!("WhipProducer '{}': session {} closed", self.name, session_id);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Beendet eine Session (`DELETE` auf die Resource-URL).
+    pub fn delete(&self, session_id: &str, authorization: Option<&str>) -> Result<bool, WhipError> {
+        self.authorize(authorization)?;
+        Ok(self.handle.block_on(self.remove_session(session_id)))
+    }
+
+    fn close_all(&self) {
+        let sessions: Vec<String> = lock_mutex(&self.sessions, "whip.close_all")
+            .keys()
+            .cloned()
+            .collect();
+        for id in sessions {
+            self.handle.block_on(self.remove_session(&id));
+        }
+    }
+}
+
+static WHIP_ENDPOINTS: OnceLock<Mutex<HashMap<String, Arc<WhipEndpoint>>>> = OnceLock::new();
+
+fn whip_endpoints() -> &'static Mutex<HashMap<String, Arc<WhipEndpoint>>> {
+    WHIP_ENDPOINTS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Endpunkt eines laufenden WHIP-Producers.
+pub fn whip_endpoint(name: &str) -> Option<Arc<WhipEndpoint>> {
+    lock_mutex(whip_endpoints(), "whip.lookup").get(name).cloned()
+}
+
+fn build_api() -> Result<API> {
+    let mut media = MediaEngine::default();
+    media.register_codec(
+        RTCRtpCodecParameters {
+            capability: RTCRtpCodecCapability {
+                mime_type: MIME_TYPE_OPUS.to_owned(),
+                clock_rate: 48_000,
+                channels: 2,
+                sdp_fmtp_line: "minptime=10;useinbandfec=1;stereo=1".to_owned(),
+                rtcp_feedback: vec![],
+            },
+            payload_type: OPUS_PAYLOAD_TYPE,
+            ..Default::default()
+        },
+        RTPCodecType::Audio,
+    )?;
+    let registry = register_default_interceptors(Registry::new(), &mut media)?;
+    Ok(APIBuilder::new()
+        .with_media_engine(media)
+        .with_interceptor_registry(registry)
+        .build())
+}
+
+pub struct WhipProducer {
+    name: String,
+    config: WhipConfig,
+    shared: Arc<Shared>,
+    ring_buffer: Option<Arc<AudioRingBuffer>>,
+    decoder: Arc<Mutex<Option<Box<dyn AudioDecoder>>>>,
+    runtime: Option<tokio::runtime::Runtime>,
+    endpoint: Option<Arc<WhipEndpoint>>,
+}
+
+impl WhipProducer {
+    pub fn new(name: &str, config: &ProducerConfig) -> Result<Self> {
+        Ok(Self::with_config(name, WhipConfig::from_config(name, config)?))
+    }
+
+    pub fn with_config(name: &str, config: WhipConfig) -> Self {
+        Self {
+            name: name.to_string(),
+            config,
+            shared: Arc::new(Shared {
+                running: AtomicBool::new(false),
+                active_tracks: AtomicUsize::new(0),
+                samples_processed: AtomicU64::new(0),
+                errors: AtomicU64::new(0),
+            }),
+            ring_buffer: None,
+            decoder: Arc::new(Mutex::new(None)),
+            runtime: None,
+            endpoint: None,
+        }
+    }
+}
+
+impl Producer for WhipProducer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn start(&mut self) -> Result<()> {
+        if self.shared.running.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+
+        let ring = self
+            .ring_buffer
+            .clone()
+            .ok_or_else(|| anyhow!("WhipProducer '{}' missing ring buffer", self.name))?;
+
+        let mut endpoints = lock_mutex(whip_endpoints(), "whip.start");
+        if endpoints.contains_key(&self.name) {
+            bail!("WHIP endpoint '{}' is already registered", self.name);
+        }
+
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(2)
+            .thread_name(format!("whip-{}", self.name))
+            .enable_all()
+            .build()
+            .context("failed to create WHIP runtime")?;
+
+        let endpoint = Arc::new(WhipEndpoint {
+            name: self.name.clone(),
+            config: self.config.clone(),
+            handle: runtime.handle().clone(),
+            api: build_api()?,
+            shared: self.shared.clone(),
+            ring,
+            decoder: self.decoder.clone(),
+            sessions: Mutex::new(HashMap::new()),
+            next_session: AtomicU64::new(0),
+        });
+
+        self.shared.running.store(true, Ordering::SeqCst);
+        endpoints.insert(self.name.clone(), endpoint.clone());
+        self.endpoint = Some(endpoint);
+        self.runtime = Some(runtime);
+
+        log::info!(
+            "WhipProducer '{}': accepting offers on /whip/{}",
+            self.name,
+            self.name
+        );
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        self.shared.running.store(false, Ordering::SeqCst);
+        lock_mutex(whip_endpoints(), "whip.stop").remove(&self.name);
+
+        if let Some(endpoint) = self.endpoint.take() {
+            endpoint.close_all();
+        }
+        // Runtime nie aus einem ihrer eigenen Tasks heraus droppen
+        if let Some(runtime) = self.runtime.take() {
+            runtime.shutdown_timeout(Duration::from_secs(1));
+        }
+        Ok(())
+    }
+
+    fn status(&self) -> ProducerStatus {
+        ProducerStatus {
+            running: self.shared.running.load(Ordering::Relaxed),
+            paused: false,
+            connected: self.shared.active_tracks.load(Ordering::Relaxed) > 0,
+            samples_processed: self.shared.samples_processed.load(Ordering::Relaxed),
+            errors: self.shared.errors.load(Ordering::Relaxed),
+            buffer_stats: self.ring_buffer.as_ref().map(|buffer| buffer.stats()),
+            decoder: lock_mutex(&self.decoder, "whip_producer.status")
+                .as_ref()
+                .and_then(|decoder| decoder.stats()),
+        }
+    }
+
+    fn attach_ring_buffer(&mut self, buffer: Arc<AudioRingBuffer>) {
+        self.ring_buffer = Some(buffer);
+    }
+
+    fn attach_decoder(&mut self, decoder: Box<dyn AudioDecoder>) {
+        *lock_mutex(&self.decoder, "whip_producer.attach_decoder") = Some(decoder);
+    }
+}
+
+impl Drop for WhipProducer {
+    fn drop(&mut self) {
+        let _ = self.stop();
+    }
+}
+
+impl_connectable_producer!(WhipProducer);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn producer_config(config: serde_json::Value) -> ProducerConfig {
+        ProducerConfig {
+            producer_type: "whip".to_string(),
+            enabled: true,
+            device: None,
+            path: None,
+            channels: None,
+            sample_rate: None,
+            loop_audio: None,
+            config: serde_json::from_value(config).unwrap(),
+        }
+    }
+
+    #[test]
+    fn parses_whip_config() {
+        let config = WhipConfig::from_config(
+            "contrib",
+            &producer_config(serde_json::json!({
+                "token": "secret",
+                "ice_servers": ["stun:stun.example.org:3478"],
+                "max_sessions": 2
+            })),
+        )
+        .unwrap();
+        assert_eq!(config.token.as_deref(), Some("secret"));
+        assert_eq!(config.ice_servers, vec!["stun:stun.example.org:3478"]);
+        assert_eq!(config.max_sessions, 2);
+        assert_eq!(config.channels, 2);
+        assert_eq!(config.opus, OpusDecoderOptions::default());
+
+        let err = WhipConfig::from_config(
+            "contrib",
+            &producer_config(serde_json::json!({ "max_sessions": 0 })),
+        );
+        assert!(err.is_err());
+    }
+}
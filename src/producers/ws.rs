@@ -41,7 +41,7 @@ impl WsHandle {
                 log::info!(
                     "WsProducer '{}' stats: buffer_frames={}, dropped_frames={}, samples_processed={}, errors={}",
                     self.state.name,
-                    stats.current_frames,
+                    stats.current_frames.get(),
                     stats.dropped_frames,
                     self.state.samples_processed.load(Ordering::Relaxed),
                     self.state.errors.load(Ordering::Relaxed)
@@ -99,10 +99,12 @@ impl Producer for WsProducer {
         let ring = lock_mutex(&self.state.ring, "ws.producer.status");
         ProducerStatus {
             running: self.state.running.load(Ordering::Relaxed),
+            paused: false,
             connected: ring.is_some(),
             samples_processed: self.state.samples_processed.load(Ordering::Relaxed),
             errors: self.state.errors.load(Ordering::Relaxed),
             buffer_stats: ring.as_ref().map(|r| r.stats()),
+            decoder: None,
         }
     }
 
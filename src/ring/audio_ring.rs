@@ -2,6 +2,7 @@ use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 
 use crate::ring::{PcmFrame, PcmSink};
+use crate::types::{Samples, Slots};
 
 #[derive(Clone)]
 pub struct AudioSlot {
@@ -38,7 +39,8 @@ pub enum RingRead {
 }
 
 impl AudioRing {
-    pub fn new(cap: usize, prealloc_samples: usize, sample_rate: u32, channels: u8) -> Self {
+    pub fn new(slots: Slots, prealloc_samples: Samples, sample_rate: u32, channels: u8) -> Self {
+        let cap = slots.get();
         let mut slots = Vec::with_capacity(cap);
         for _ in 0..cap {
             slots.push(AudioSlot {
@@ -46,7 +48,7 @@ impl AudioRing {
                 utc_ns: 0,
                 sample_rate,
                 channels,
-                samples: Arc::new(vec![0i16; prealloc_samples]),
+                samples: Arc::new(vec![0i16; prealloc_samples.get()]),
             });
         }
 
@@ -1,6 +1,7 @@
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::sync::{Arc, Condvar, Mutex};
+use std::sync::{Arc, Condvar, Mutex, Weak};
 
+use crate::core::watermark::{ReaderLag, WatermarkMonitor};
 use crate::ring::{EncodedFramePacket, EncodedSink, EncodedSource, RingStats};
 use crate::types::EncodedFrame;
 
@@ -17,17 +18,26 @@ struct Inner {
     head_seq: u64,
 }
 
+/// Leseposition eines Readers, damit der Writer Watermarks prüfen kann.
+struct ReaderPosition {
+    id: u64,
+    last_seq: Weak<AtomicU64>,
+}
+
 #[derive(Clone)]
 pub struct EncodedRing {
     inner: Arc<Mutex<Inner>>,
     next_seq: Arc<AtomicU64>,
     notify: Arc<Condvar>,
+    readers: Arc<Mutex<Vec<ReaderPosition>>>,
+    next_reader_id: Arc<AtomicU64>,
+    watermark: Arc<Mutex<Option<Arc<WatermarkMonitor>>>>,
 }
 
-#[derive(Clone)]
 pub struct EncodedRingReader {
     ring: EncodedRing,
     last_seq: u64,
+    position: Arc<AtomicU64>,
 }
 
 pub enum EncodedRingRead {
@@ -57,9 +67,17 @@ impl EncodedRing {
             inner: Arc::new(Mutex::new(inner)),
             next_seq: Arc::new(AtomicU64::new(1)),
             notify: Arc::new(Condvar::new()),
+            readers: Arc::new(Mutex::new(Vec::new())),
+            next_reader_id: Arc::new(AtomicU64::new(1)),
+            watermark: Arc::new(Mutex::new(None)),
         }
     }
 
+    /// High/Low-Watermarks pro Reader aktivieren (`None` deaktiviert).
+    pub fn set_watermark(&self, monitor: Option<Arc<WatermarkMonitor>>) {
+        *self.watermark.lock().unwrap() = monitor;
+    }
+
     pub fn writer_push(&self, utc_ns: u64, frame: EncodedFrame) -> u64 {
         let seq = self.next_seq.fetch_add(1, Ordering::Relaxed);
 
@@ -71,18 +89,114 @@ impl EncodedRing {
             frame: Arc::new(frame),
         };
         g.head_seq = seq;
+        let cap = g.cap;
+        drop(g);
         self.notify.notify_all();
+        self.check_watermark(seq, cap);
         seq
     }
 
     pub fn subscribe(&self) -> EncodedRingReader {
-        let head = self.head_seq();
+        self.reader_at(self.head_seq())
+    }
+
+    /// Reader, der nach `seq` weiterliest (z. B. gespeicherter Cursor); liegt
+    /// `seq` nicht mehr im Ring, meldet der erste Read eine Lücke.
+    pub fn subscribe_after(&self, seq: u64) -> EncodedRingReader {
+        self.reader_at(seq.min(self.head_seq()))
+    }
+
+    /// Reader ab dem ersten Frame mit `utc_ns >= from_utc_ns` (Timeshift
+    /// innerhalb des Rings); älter als der Ring = ab dem ältesten Frame.
+    pub fn subscribe_at_utc(&self, from_utc_ns: u64) -> EncodedRingReader {
+        let slots = self.snapshot();
+        let seq = slots
+            .iter()
+            .find(|slot| slot.utc_ns >= from_utc_ns)
+            .map(|slot| slot.seq - 1)
+            .unwrap_or_else(|| self.head_seq());
+        self.reader_at(seq)
+    }
+
+    /// Alle gültigen Slots, älteste zuerst.
+    pub fn snapshot(&self) -> Vec<EncodedSlot> {
+        let g = self.inner.lock().unwrap();
+        let oldest = g.head_seq.saturating_sub(g.cap as u64 - 1).max(1);
+        (oldest..=g.head_seq)
+            .filter_map(|seq| {
+                let slot = &g.slots[(seq as usize) % g.cap];
+                (slot.seq == seq).then(|| slot.clone())
+            })
+            .collect()
+    }
+
+    /// Füllt einen leeren Ring aus einem Snapshot; die Sequenznummern
+    /// bleiben erhalten, damit gespeicherte Cursor weiter passen. Liefert
+    /// die Zahl übernommener Slots (0, wenn der Ring schon Daten hat).
+    pub fn restore(&self, slots: &[EncodedSlot]) -> usize {
+        let mut g = self.inner.lock().unwrap();
+        if g.head_seq != 0 {
+            return 0;
+        }
+        // Nur das lückenlose Ende übernehmen, sonst fänden Reader fehlende
+        // Sequenznummern nie
+        let cap = g.cap;
+        let mut start = slots.len();
+        while start > 0 && slots.len() - start < cap {
+            let slot = &slots[start - 1];
+            let contiguous = start == slots.len() || slots[start].seq == slot.seq + 1;
+            if slot.seq == 0 || !contiguous {
+                break;
+            }
+            start -= 1;
+        }
+        for slot in &slots[start..] {
+            g.slots[(slot.seq as usize) % cap] = slot.clone();
+            g.head_seq = slot.seq;
+        }
+        self.next_seq.store(g.head_seq + 1, Ordering::Relaxed);
+        let restored = slots.len() - start;
+        drop(g);
+        self.notify.notify_all();
+        restored
+    }
+
+    fn reader_at(&self, last_seq: u64) -> EncodedRingReader {
+        let position = Arc::new(AtomicU64::new(last_seq));
+        let id = self.next_reader_id.fetch_add(1, Ordering::Relaxed);
+        self.readers.lock().unwrap().push(ReaderPosition {
+            id,
+            last_seq: Arc::downgrade(&position),
+        });
         EncodedRingReader {
             ring: self.clone(),
-            last_seq: if head > 0 { head } else { 0 },
+            last_seq,
+            position,
         }
     }
 
+    fn check_watermark(&self, head: u64, cap: usize) {
+        let Some(monitor) = self.watermark.lock().unwrap().clone() else {
+            return;
+        };
+        let lags = {
+            let mut readers = self.readers.lock().unwrap();
+            readers.retain(|reader| reader.last_seq.strong_count() > 0);
+            readers
+                .iter()
+                .filter_map(|reader| {
+                    let last_seq = reader.last_seq.upgrade()?.load(Ordering::Relaxed);
+                    Some(ReaderLag {
+                        reader: format!("reader#{}", reader.id),
+                        position: last_seq,
+                        lag: head.saturating_sub(last_seq) as usize,
+                    })
+                })
+                .collect::<Vec<_>>()
+        };
+        monitor.observe(cap, lags);
+    }
+
     pub fn stats(&self) -> RingStats {
         let g = self.inner.lock().unwrap();
         RingStats {
@@ -141,7 +255,18 @@ impl EncodedSink for EncodedRing {
     }
 }
 
+impl Clone for EncodedRingReader {
+    fn clone(&self) -> Self {
+        self.ring.reader_at(self.last_seq)
+    }
+}
+
 impl EncodedRingReader {
+    fn set_last_seq(&mut self, seq: u64) {
+        self.last_seq = seq;
+        self.position.store(seq, Ordering::Relaxed);
+    }
+
     pub fn poll(&mut self) -> EncodedRingRead {
         let head = self.ring.head_seq();
         if head == 0 || head <= self.last_seq {
@@ -152,13 +277,13 @@ impl EncodedRingReader {
         let cap = self.ring.cap() as u64;
         if head.saturating_sub(next) >= cap {
             let missed = head.saturating_sub(next) + 1 - cap;
-            self.last_seq = head.saturating_sub(cap - 1);
+            self.set_last_seq(head.saturating_sub(cap - 1));
             return EncodedRingRead::Gap { missed };
         }
 
         match self.ring.get_by_seq(next) {
             Some(slot) => {
-                self.last_seq = next;
+                self.set_last_seq(next);
                 EncodedRingRead::Frame {
                     frame: (*slot.frame).clone(),
                     utc_ns: slot.utc_ns,
@@ -193,6 +318,11 @@ impl EncodedRingReader {
         }
     }
 
+    /// Zuletzt gelesene Sequenznummer (Cursor).
+    pub fn last_seq(&self) -> u64 {
+        self.last_seq
+    }
+
     pub fn fill(&self) -> u64 {
         let head = self.ring.head_seq();
         head.saturating_sub(self.last_seq)
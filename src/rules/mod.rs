@@ -0,0 +1,484 @@
+// src/rules/mod.rs
+//
+// Regel-Engine (Lua 5.4 via mlua) für standortspezifische Automation ohne
+// Fork. Skripte registrieren beim Laden Handler für Events (`airlift.on`) und
+// Timer (`airlift.every`) und bekommen eine kleine API: Status lesen,
+// Mixer-Gain setzen, Flows starten/stoppen, Webhooks senden.
+//
+// Sandbox: nur base/table/string/math/utf8 (ohne dofile/loadfile/load), kein
+// io/os/require; Speicher- und Laufzeitlimit pro Aufruf. Lua lebt komplett im
+// Regel-Thread, Events kommen über eine begrenzte Queue vom Event-Bus.
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Context, Result};
+use crossbeam_channel::{bounded, Receiver, RecvTimeoutError, Sender, TrySendError};
+use mlua::{Function, HookTriggers, Lua, LuaOptions, LuaSerdeExt, RegistryKey, StdLib};
+
+use crate::api::status::build_status;
+use crate::config::{Config, RulesConfig};
+use crate::core::lock::lock_mutex;
+use crate::core::{AirliftNode, Event, EventBus, EventHandler, EventType};
+
+pub mod webhook;
+
+const DEFAULT_MAX_RUNTIME: Duration = Duration::from_millis(100);
+const MEMORY_LIMIT: usize = 16 * 1024 * 1024;
+const EVENT_QUEUE: usize = 256;
+const MIN_TIMER_INTERVAL: Duration = Duration::from_millis(100);
+/// Wie oft der Regel-Thread das Stop-Flag prüft.
+const STOP_POLL: Duration = Duration::from_millis(200);
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(5);
+const HANDLER_NAME: &str = "lua_rules";
+
+struct RulesEventHandler {
+    tx: Sender<Event>,
+    filter: Option<Vec<EventType>>,
+    dropped: AtomicU64,
+}
+
+impl EventHandler for RulesEventHandler {
+    fn handle_event(&self, event: &Event) -> Result<()> {
+        match self.tx.try_send(event.clone()) {
+            Ok(()) | Err(TrySendError::Disconnected(_)) => Ok(()),
+            Err(TrySendError::Full(_)) => {
+                // Regeln hängen hinterher: lieber verwerfen als den Bus blockieren
+                if self.dropped.fetch_add(1, Ordering::Relaxed).is_multiple_of(100) {
+                    log::warn!("Rules: event queue full, dropping events");
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn name(&self) -> &str {
+        HANDLER_NAME
+    }
+
+    fn event_type_filter(&self) -> Option<Vec<EventType>> {
+        self.filter.clone()
+    }
+}
+
+struct Timer {
+    interval: Duration,
+    next: Instant,
+    func: RegistryKey,
+}
+
+/// Lua-Zustand im Regel-Thread.
+struct RuleRuntime {
+    lua: Lua,
+    handlers: Rc<RefCell<Vec<(String, RegistryKey)>>>,
+    timers: Rc<RefCell<Vec<Timer>>>,
+    deadline: Rc<Cell<Option<Instant>>>,
+    max_runtime: Duration,
+}
+
+impl RuleRuntime {
+    fn new(
+        rules: &RulesConfig,
+        node: Arc<Mutex<AirliftNode>>,
+        config: Arc<Mutex<Config>>,
+    ) -> Result<Self> {
+        let lua = Lua::new_with(
+            StdLib::TABLE | StdLib::STRING | StdLib::MATH | StdLib::UTF8,
+            LuaOptions::new(),
+        )?;
+        lua.set_memory_limit(MEMORY_LIMIT)?;
+
+        let runtime = Self {
+            lua,
+            handlers: Rc::new(RefCell::new(Vec::new())),
+            timers: Rc::new(RefCell::new(Vec::new())),
+            deadline: Rc::new(Cell::new(None)),
+            max_runtime: rules
+                .max_runtime_ms
+                .map(Duration::from_millis)
+                .unwrap_or(DEFAULT_MAX_RUNTIME),
+        };
+
+        let deadline = runtime.deadline.clone();
+        runtime.lua.set_hook(
+            HookTriggers::new().every_nth_instruction(10_000),
+            move |_lua, _debug| match deadline.get() {
+                Some(deadline) if Instant::now() > deadline => Err(mlua::Error::RuntimeError(
+                    "rule exceeded its time limit".to_string(),
+                )),
+                _ => Ok(()),
+            },
+        );
+
+        runtime.install_api(node, config, rules.webhook_allow.clone())?;
+
+        let loading = Rc::new(Cell::new(true));
+        runtime.install_registration(loading.clone())?;
+        for script in &rules.scripts {
+            let source = std::fs::read_to_string(script)
+                .with_context(|| format!("cannot read rules script '{}'", script))?;
+            runtime.deadline.set(Some(Instant::now() + runtime.max_runtime));
+            let result = runtime.lua.load(source.as_str()).set_name(script.as_str()).exec();
+            runtime.deadline.set(None);
+            result.map_err(|e| anyhow!("rules script '{}': {}", script, e))?;
+            log::info!("Rules: loaded '{}'", script);
+        }
+        loading.set(false);
+
+        Ok(runtime)
+    }
+
+    fn install_api(
+        &self,
+        node: Arc<Mutex<AirliftNode>>,
+        config: Arc<Mutex<Config>>,
+        webhook_allow: Vec<String>,
+    ) -> Result<()> {
+        let lua = &self.lua;
+        let globals = lua.globals();
+        for unsafe_fn in ["dofile", "loadfile", "load", "collectgarbage"] {
+            globals.set(unsafe_fn, mlua::Value::Nil)?;
+        }
+
+        let api = lua.create_table()?;
+
+        let log_fn = lua.create_function(|_, message: String| {
+            log::info!("[rules] {}", message);
+            Ok(())
+        })?;
+        globals.set("print", log_fn.clone())?;
+        api.set("log", log_fn)?;
+
+        let (status_node, status_config) = (node.clone(), config);
+        api.set(
+            "status",
+            lua.create_function(move |lua, ()| {
+                // Config vor dem Node locken (wie /api/status)
+                let config = lock_mutex(&status_config, "rules.status.config").clone();
+                let status = {
+                    let node = lock_mutex(&status_node, "rules.status.node");
+                    build_status(&node, &config)
+                };
+                lua.to_value(&status)
+            })?,
+        )?;
+
+        let start_node = node.clone();
+        api.set(
+            "flow_start",
+            lua.create_function(move |_, flow: String| {
+                lock_mutex(&start_node, "rules.flow_start")
+                    .start_flow_by_name(&flow)
+                    .map_err(mlua::Error::external)
+            })?,
+        )?;
+
+        let stop_node = node.clone();
+        api.set(
+            "flow_stop",
+            lua.create_function(move |_, flow: String| {
+                lock_mutex(&stop_node, "rules.flow_stop")
+                    .stop_flow_by_name(&flow)
+                    .map_err(mlua::Error::external)
+            })?,
+        )?;
+
+        let gain_node = node.clone();
+        api.set(
+            "set_gain",
+            lua.create_function(move |_, (flow, mixer, gain_db): (String, String, f64)| {
+                let gain = 10f64.powf(gain_db / 20.0);
+                let mut node = lock_mutex(&gain_node, "rules.set_gain");
+                node.flow_mut(&flow)
+                    .and_then(|flow| {
+                        flow.update_processor_config(
+                            &mixer,
+                            serde_json::json!({ "master_gain": gain }),
+                        )
+                    })
+                    .map_err(mlua::Error::external)
+            })?,
+        )?;
+
+        // Rampe statt Sprung; ohne `input` (nil) gilt sie dem Master-Gain
+        let fade_node = node;
+        api.set(
+            "fade",
+            lua.create_function(
+                move |_,
+                      (flow, mixer, input, gain_db, seconds): (
+                    String,
+                    String,
+                    Option<String>,
+                    f64,
+                    f64,
+                )| {
+                    if !(0.0..=3600.0).contains(&seconds) {
+                        return Err(mlua::Error::RuntimeError(format!(
+                            "fade duration {} s out of range (0..3600)",
+                            seconds
+                        )));
+                    }
+                    let gain = 10f64.powf(gain_db / 20.0);
+                    let ramp_ms = seconds * 1000.0;
+                    let update = match input {
+                        Some(input) => {
+                            serde_json::json!({ "input": input, "gain": gain, "ramp": ramp_ms })
+                        }
+                        None => serde_json::json!({ "master_gain": gain, "ramp": ramp_ms }),
+                    };
+                    let mut node = lock_mutex(&fade_node, "rules.fade");
+                    node.flow_mut(&flow)
+                        .and_then(|flow| flow.update_processor_config(&mixer, update))
+                        .map_err(mlua::Error::external)
+                },
+            )?,
+        )?;
+
+        api.set(
+            "webhook",
+            lua.create_function(move |lua, (url, body): (String, mlua::Value)| {
+                if !webhook_allow.iter().any(|prefix| url.starts_with(prefix)) {
+                    return Err(mlua::Error::RuntimeError(format!(
+                        "webhook URL '{}' is not in rules.webhook_allow",
+                        url
+                    )));
+                }
+                let body: serde_json::Value = lua.from_value(body)?;
+                // Nicht im Regel-Thread auf die Gegenstelle warten
+                std::thread::spawn(move || {
+                    if let Err(e) = webhook::post_json(&url, &body, WEBHOOK_TIMEOUT) {
+                        log::warn!("[rules] webhook failed: {:#}", e);
+                    }
+                });
+                Ok(())
+            })?,
+        )?;
+
+        globals.set("airlift", api)?;
+        Ok(())
+    }
+
+    /// `airlift.on` / `airlift.every` – nur während die Skripte laden.
+    fn install_registration(&self, loading: Rc<Cell<bool>>) -> Result<()> {
+        let api: mlua::Table = self.lua.globals().get("airlift")?;
+
+        let handlers = self.handlers.clone();
+        let on_loading = loading.clone();
+        api.set(
+            "on",
+            self.lua
+                .create_function(move |lua, (event, func): (String, Function)| {
+                    if !on_loading.get() {
+                        return Err(mlua::Error::RuntimeError(
+                            "airlift.on is only allowed while loading scripts".to_string(),
+                        ));
+                    }
+                    handlers
+                        .borrow_mut()
+                        .push((event, lua.create_registry_value(func)?));
+                    Ok(())
+                })?,
+        )?;
+
+        let timers = self.timers.clone();
+        api.set(
+            "every",
+            self.lua
+                .create_function(move |lua, (seconds, func): (f64, Function)| {
+                    if !loading.get() {
+                        return Err(mlua::Error::RuntimeError(
+                            "airlift.every is only allowed while loading scripts".to_string(),
+                        ));
+                    }
+                    let interval = Duration::try_from_secs_f64(seconds)
+                        .ok()
+                        .filter(|interval| *interval >= MIN_TIMER_INTERVAL)
+                        .ok_or_else(|| {
+                            mlua::Error::RuntimeError(format!(
+                                "airlift.every: interval must be >= {} s",
+                                MIN_TIMER_INTERVAL.as_secs_f64()
+                            ))
+                        })?;
+                    timers.borrow_mut().push(Timer {
+                        interval,
+                        next: Instant::now() + interval,
+                        func: lua.create_registry_value(func)?,
+                    });
+                    Ok(())
+                })?,
+        )?;
+        Ok(())
+    }
+
+    /// Filter für den Event-Bus-Handler; `None` wenn ein Skript "*" abonniert.
+    fn event_filter(&self) -> Option<Vec<EventType>> {
+        let handlers = self.handlers.borrow();
+        if handlers.iter().any(|(name, _)| name == "*") {
+            return None;
+        }
+        Some(
+            handlers
+                .iter()
+                .map(|(name, _)| EventType::from_name(name))
+                .collect(),
+        )
+    }
+
+    fn call(&self, what: &str, func: Function, arg: mlua::Value) {
+        self.deadline.set(Some(Instant::now() + self.max_runtime));
+        let result = func.call::<_, ()>(arg);
+        self.deadline.set(None);
+        if let Err(e) = result {
+            log::warn!("[rules] {} failed: {}", what, e);
+        }
+    }
+
+    fn dispatch(&self, event: &Event) {
+        let name = event.event_type_str();
+        let funcs: Vec<Function> = self
+            .handlers
+            .borrow()
+            .iter()
+            .filter(|(filter, _)| filter == "*" || filter == name)
+            .filter_map(|(_, key)| self.lua.registry_value(key).ok())
+            .collect();
+        if funcs.is_empty() {
+            return;
+        }
+
+        let payload = serde_json::json!({
+            "type": name,
+            "priority": event.priority,
+            "source": event.source,
+            "instance": event.source_instance,
+            "timestamp_ns": event.timestamp,
+            "payload": event.payload,
+        });
+        let arg = match self.lua.to_value(&payload) {
+            Ok(arg) => arg,
+            Err(e) => {
+                log::warn!("[rules] cannot convert event: {}", e);
+                return;
+            }
+        };
+        for func in funcs {
+            self.call(&format!("handler for '{}'", name), func, arg.clone());
+        }
+    }
+
+    /// Führt fällige Timer aus und liefert den nächsten Fälligkeitszeitpunkt.
+    fn run_timers(&self, now: Instant) -> Option<Instant> {
+        let due: Vec<Function> = self
+            .timers
+            .borrow_mut()
+            .iter_mut()
+            .filter(|timer| timer.next <= now)
+            .filter_map(|timer| {
+                // Verpasste Ticks nicht nachholen
+                timer.next = now + timer.interval;
+                self.lua.registry_value(&timer.func).ok()
+            })
+            .collect();
+        for func in due {
+            self.call("timer", func, mlua::Value::Nil);
+        }
+        self.timers.borrow().iter().map(|timer| timer.next).min()
+    }
+
+    fn run(&self, events: &Receiver<Event>, stop: &AtomicBool) {
+        while !stop.load(Ordering::Relaxed) {
+            let now = Instant::now();
+            let wait = self
+                .run_timers(now)
+                .map(|next| next.saturating_duration_since(now).min(STOP_POLL))
+                .unwrap_or(STOP_POLL);
+            match events.recv_timeout(wait) {
+                Ok(event) => self.dispatch(&event),
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        }
+    }
+}
+
+/// Laufende Regel-Engine; `stop` (oder Drop) beendet Thread und Bus-Handler.
+pub struct RulesEngine {
+    stop: Arc<AtomicBool>,
+    event_bus: Arc<Mutex<EventBus>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl RulesEngine {
+    /// Lädt alle Skripte; Syntax- oder Laufzeitfehler beim Laden sind fatal.
+    pub fn start(
+        rules: &RulesConfig,
+        node: Arc<Mutex<AirliftNode>>,
+        config: Arc<Mutex<Config>>,
+    ) -> Result<Self> {
+        let event_bus = lock_mutex(&node, "rules.event_bus").event_bus();
+        let (event_tx, event_rx) = bounded::<Event>(EVENT_QUEUE);
+        let (ready_tx, ready_rx) = mpsc::channel::<Result<Option<Vec<EventType>>>>();
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let thread_stop = stop.clone();
+        let rules = rules.clone();
+        let thread = std::thread::Builder::new()
+            .name("lua-rules".to_string())
+            .spawn(move || {
+                let runtime = match RuleRuntime::new(&rules, node, config) {
+                    Ok(runtime) => runtime,
+                    Err(e) => {
+                        let _ = ready_tx.send(Err(e));
+                        return;
+                    }
+                };
+                let _ = ready_tx.send(Ok(runtime.event_filter()));
+                runtime.run(&event_rx, &thread_stop);
+            })?;
+
+        let filter = match ready_rx.recv() {
+            Ok(Ok(filter)) => filter,
+            Ok(Err(e)) => {
+                let _ = thread.join();
+                return Err(e);
+            }
+            Err(_) => {
+                let _ = thread.join();
+                return Err(anyhow!("rules thread terminated during startup"));
+            }
+        };
+
+        lock_mutex(&event_bus, "rules.register_handler").register_handler(Arc::new(
+            RulesEventHandler {
+                tx: event_tx,
+                filter,
+                dropped: AtomicU64::new(0),
+            },
+        ))?;
+
+        Ok(Self {
+            stop,
+            event_bus,
+            thread: Some(thread),
+        })
+    }
+
+    pub fn stop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        let _ = lock_mutex(&self.event_bus, "rules.unregister_handler")
+            .unregister_handler(HANDLER_NAME);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for RulesEngine {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
@@ -0,0 +1,18 @@
+// src/rules/webhook.rs
+//
+// HTTP-POST für `airlift.webhook` (nur http://, JSON-Body) über den
+// gemeinsamen HTTP-Client.
+use std::time::Duration;
+
+use anyhow::{bail, Result};
+
+use crate::core::http_client::{self, HttpRequest};
+
+pub fn post_json(url: &str, body: &serde_json::Value, timeout: Duration) -> Result<u16> {
+    let request = HttpRequest::post(url, "application/json", body.to_string().into_bytes()).timeout(timeout);
+    let response = http_client::send(&request)?;
+    if response.status >= 400 {
+        bail!("webhook {} answered {}", url, response.status);
+    }
+    Ok(response.status)
+}
@@ -0,0 +1,393 @@
+// src/storage/influx.rs
+//
+// InfluxDB-1.x-Backend über den gemeinsamen HTTP-Client. Peaks und Events
+// landen in einer Warteschlange, die ein Hintergrund-Thread sekündlich als
+// Line-Protocol schreibt – der Event-Thread wartet nie auf Influx. Lesen
+// geht per InfluxQL (`/query`, `epoch=ms`). Aufbewahrung regelt die
+// Retention-Policy der Datenbank.
+//
+// Measurements: `airlift_peak` (Tag `flow`), `airlift_event` (Tags
+// `priority`, `source`; Feld `event` = JSON), `airlift_recording` (Tag
+// `path`; Feld `info` = JSON).
+use std::io::Read;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde_json::Value;
+
+use crate::audio::archive::RecordingInfo;
+use crate::config::StorageConfig;
+use crate::core::http_client::{self, HttpRequest};
+use crate::core::labels::{self, Labels};
+use crate::core::lock::lock_mutex;
+use crate::core::Event;
+
+use super::{PeakPoint, StorageBackend};
+
+/// Abstand der gebündelten Schreibvorgänge
+pub const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+/// Bei nicht erreichbarer Datenbank bleiben höchstens so viele Zeilen liegen
+pub const MAX_PENDING_LINES: usize = 10_000;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct InfluxOptions {
+    /// z. B. `http://influx:8086`
+    pub url: String,
+    pub database: String,
+    pub username: Option<String>,
+    pub password: Option<String>,
+}
+
+impl InfluxOptions {
+    pub fn from_config(config: &StorageConfig) -> Result<Self> {
+        let url = config
+            .url
+            .as_deref()
+            .ok_or_else(|| anyhow!("storage.url is required for the influx backend"))?
+            .trim_end_matches('/')
+            .to_string();
+        http_client::parse_http_url(&url).context("storage.url invalid")?;
+        let database = config
+            .database
+            .clone()
+            .filter(|database| !database.trim().is_empty())
+            .ok_or_else(|| anyhow!("storage.database is required for the influx backend"))?;
+        Ok(Self {
+            url,
+            database,
+            username: config.username.clone(),
+            password: config.password.clone(),
+        })
+    }
+
+    fn endpoint(&self, path: &str, extra: &str) -> String {
+        let mut url = format!("{}/{}?db={}", self.url, path, url_encode(&self.database));
+        if let Some(username) = &self.username {
+            url.push_str(&format!("&u={}", url_encode(username)));
+        }
+        if let Some(password) = &self.password {
+            url.push_str(&format!("&p={}", url_encode(password)));
+        }
+        url.push_str(extra);
+        url
+    }
+}
+
+pub struct InfluxBackend {
+    options: InfluxOptions,
+    pending: Arc<Mutex<Vec<String>>>,
+}
+
+impl InfluxBackend {
+    pub fn new(options: InfluxOptions) -> Self {
+        let pending = Arc::new(Mutex::new(Vec::new()));
+        let writer = Arc::downgrade(&pending);
+        let flush_options = options.clone();
+        std::thread::Builder::new()
+            .name("storage-influx".to_string())
+            .spawn(move || {
+                loop {
+                    std::thread::sleep(FLUSH_INTERVAL);
+                    // Endet mit dem Backend
+                    let Some(pending) = writer.upgrade() else {
+                        break;
+                    };
+                    if let Err(e) = flush_pending(&flush_options, &pending) {
+                        log::warn!("[storage] influx write failed: {:#}", e);
+                    }
+                }
+            })
+            .ok();
+        Self { options, pending }
+    }
+
+    /// Wartende Zeilen sofort schreiben (sonst spätestens nach `FLUSH_INTERVAL`).
+    pub fn flush(&self) -> Result<()> {
+        flush_pending(&self.options, &self.pending)
+    }
+
+    fn enqueue(&self, line: String) {
+        let mut pending = lock_mutex(&self.pending, "storage.influx.enqueue");
+        pending.push(line);
+        if pending.len() > MAX_PENDING_LINES {
+            let excess = pending.len() - MAX_PENDING_LINES;
+            pending.drain(..excess);
+        }
+    }
+
+    fn write(&self, body: String) -> Result<()> {
+        write_lines(&self.options, body)
+    }
+
+    /// Zeilen des ersten Ergebnisses als Spalte → Wert.
+    fn query(&self, statement: &str) -> Result<Vec<serde_json::Map<String, Value>>> {
+        let request = HttpRequest::post(
+            &self.options.endpoint("query", "&epoch=ms"),
+            "application/x-www-form-urlencoded",
+            format!("q={}", url_encode(statement)).into_bytes(),
+        );
+        let mut response = http_client::send(&request)?;
+        let mut body = String::new();
+        response.read_to_string(&mut body)?;
+        if response.status >= 400 {
+            bail!("influx query failed: {} {}", response.status_line, body.trim());
+        }
+        parse_query_response(&body)
+    }
+}
+
+fn flush_pending(options: &InfluxOptions, pending: &Mutex<Vec<String>>) -> Result<()> {
+    let lines = std::mem::take(&mut *lock_mutex(pending, "storage.influx.flush"));
+    if lines.is_empty() {
+        return Ok(());
+    }
+    let result = write_lines(options, lines.join("\n"));
+    if result.is_err() {
+        // Beim nächsten Mal erneut, vor allem was inzwischen dazukam
+        let mut pending = lock_mutex(pending, "storage.influx.requeue");
+        let newer = std::mem::replace(&mut *pending, lines);
+        pending.extend(newer);
+        if pending.len() > MAX_PENDING_LINES {
+            let excess = pending.len() - MAX_PENDING_LINES;
+            pending.drain(..excess);
+        }
+    }
+    result
+}
+
+fn write_lines(options: &InfluxOptions, body: String) -> Result<()> {
+    let request = HttpRequest::post(
+        &options.endpoint("write", "&precision=ns"),
+        "text/plain; charset=utf-8",
+        body.into_bytes(),
+    );
+    let mut response = http_client::send(&request)?;
+    if response.status >= 400 {
+        let mut text = String::new();
+        let _ = response.read_to_string(&mut text);
+        bail!("influx write failed: {} {}", response.status_line, text.trim());
+    }
+    Ok(())
+}
+
+/// `results[0].series[*]` als Zeilen; ein `error` im Ergebnis wird zum Fehler.
+pub fn parse_query_response(body: &str) -> Result<Vec<serde_json::Map<String, Value>>> {
+    let json: Value = serde_json::from_str(body).context("influx returned invalid JSON")?;
+    if let Some(error) = json.get("error").and_then(Value::as_str) {
+        bail!("influx: {}", error);
+    }
+    let result = json
+        .get("results")
+        .and_then(|results| results.get(0))
+        .ok_or_else(|| anyhow!("influx response without results"))?;
+    if let Some(error) = result.get("error").and_then(Value::as_str) {
+        bail!("influx: {}", error);
+    }
+    let mut rows = Vec::new();
+    for series in result.get("series").and_then(Value::as_array).into_iter().flatten() {
+        let columns: Vec<&str> = series
+            .get("columns")
+            .and_then(Value::as_array)
+            .map(|columns| columns.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+        let tags = series.get("tags").and_then(Value::as_object);
+        for values in series.get("values").and_then(Value::as_array).into_iter().flatten() {
+            let mut row: serde_json::Map<String, Value> = tags.cloned().unwrap_or_default();
+            for (column, value) in columns.iter().zip(values.as_array().into_iter().flatten()) {
+                row.insert(column.to_string(), value.clone());
+            }
+            rows.push(row);
+        }
+    }
+    Ok(rows)
+}
+
+/// Tag-Werte: Komma, Gleichheitszeichen, Leerzeichen und Backslash maskieren.
+pub fn escape_tag(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        // Zeilenumbrüche würden die Zeile beenden
+        let c = if c == '\n' { ' ' } else { c };
+        if matches!(c, ',' | '=' | ' ' | '\\') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// String-Felder in Anführungszeichen.
+pub fn escape_field(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// String-Literal für InfluxQL.
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+fn url_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+/// `config.labels` als zusätzliche Tags, z. B. `,site=studio-b`.
+fn label_tags(labels: &Labels) -> String {
+    labels
+        .iter()
+        .map(|(key, value)| format!(",{}={}", escape_tag(key), escape_tag(value)))
+        .collect()
+}
+
+pub fn peak_line(point: &PeakPoint) -> String {
+    format!(
+        "airlift_peak,flow={}{} peak_l={},peak_r={},silence={} {}",
+        escape_tag(&point.flow),
+        label_tags(&labels::flow_labels(&point.flow)),
+        point.peak_l,
+        point.peak_r,
+        point.silence,
+        point.ts * 1_000_000
+    )
+}
+
+pub fn event_line(event: &Event) -> Result<String> {
+    Ok(format!(
+        "airlift_event,priority={:?},source={}{} event={} {}",
+        event.priority,
+        escape_tag(if event.source.is_empty() { "unknown" } else { &event.source }),
+        label_tags(&event.labels),
+        escape_field(&serde_json::to_string(event)?),
+        event.timestamp
+    ))
+}
+
+fn time_filter(from: u64, to: u64) -> String {
+    format!("time >= {}ms AND time <= {}ms", from, to.min(i64::MAX as u64 / 1_000_000))
+}
+
+fn flow_filter(flow: Option<&str>) -> String {
+    flow.map(|flow| format!(" AND flow = {}", quote(flow))).unwrap_or_default()
+}
+
+fn field_u64(row: &serde_json::Map<String, Value>, key: &str) -> u64 {
+    row.get(key).and_then(Value::as_u64).unwrap_or(0)
+}
+
+impl StorageBackend for InfluxBackend {
+    fn kind(&self) -> &'static str {
+        "influx"
+    }
+
+    fn push_peak(&self, point: PeakPoint) -> Result<()> {
+        self.enqueue(peak_line(&point));
+        Ok(())
+    }
+
+    fn peaks(&self, from: u64, to: u64, flow: Option<&str>) -> Result<Vec<PeakPoint>> {
+        let rows = self.query(&format!(
+            "SELECT peak_l, peak_r, silence, flow FROM airlift_peak WHERE {}{}",
+            time_filter(from, to),
+            flow_filter(flow)
+        ))?;
+        let mut points: Vec<PeakPoint> = rows
+            .iter()
+            .map(|row| PeakPoint {
+                ts: field_u64(row, "time"),
+                peak_l: row.get("peak_l").and_then(Value::as_f64).unwrap_or(0.0) as f32,
+                peak_r: row.get("peak_r").and_then(Value::as_f64).unwrap_or(0.0) as f32,
+                silence: row.get("silence").and_then(Value::as_bool).unwrap_or(false),
+                flow: row.get("flow").and_then(Value::as_str).unwrap_or("unknown").to_string(),
+            })
+            .collect();
+        points.sort_by_key(|point| point.ts);
+        Ok(points)
+    }
+
+    fn peak_count(&self, from: u64, to: u64, flow: Option<&str>) -> Result<usize> {
+        let rows = self.query(&format!(
+            "SELECT COUNT(peak_l) FROM airlift_peak WHERE {}{}",
+            time_filter(from, to),
+            flow_filter(flow)
+        ))?;
+        Ok(rows.first().map(|row| field_u64(row, "count")).unwrap_or(0) as usize)
+    }
+
+    fn peak_range(&self, flow: Option<&str>) -> Result<Option<(u64, u64)>> {
+        let filter = flow.map(|flow| format!(" WHERE flow = {}", quote(flow))).unwrap_or_default();
+        let first = self.query(&format!("SELECT FIRST(peak_l) FROM airlift_peak{}", filter))?;
+        let last = self.query(&format!("SELECT LAST(peak_l) FROM airlift_peak{}", filter))?;
+        Ok(match (first.first(), last.first()) {
+            (Some(first), Some(last)) => Some((field_u64(first, "time"), field_u64(last, "time"))),
+            _ => None,
+        })
+    }
+
+    fn push_event(&self, event: &Event) -> Result<()> {
+        self.enqueue(event_line(event)?);
+        Ok(())
+    }
+
+    fn events(&self, from: u64, to: u64, limit: usize) -> Result<Vec<Event>> {
+        let rows = self.query(&format!(
+            "SELECT event FROM airlift_event WHERE {} ORDER BY time DESC LIMIT {}",
+            time_filter(from, to),
+            limit
+        ))?;
+        let mut events = rows
+            .iter()
+            .filter_map(|row| row.get("event").and_then(Value::as_str))
+            .map(|json| Ok(serde_json::from_str(json)?))
+            .collect::<Result<Vec<Event>>>()?;
+        events.reverse();
+        Ok(events)
+    }
+
+    /// Sofort geschrieben: der Index soll direkt nach der Aufnahme stimmen.
+    fn index_recording(&self, recording: &RecordingInfo) -> Result<()> {
+        self.write(format!(
+            "airlift_recording,path={} id={},info={} {}",
+            escape_tag(&recording.path),
+            escape_field(&recording.id),
+            escape_field(&serde_json::to_string(recording)?),
+            recording.entry.finished_at_ms * 1_000_000
+        ))
+    }
+
+    fn forget_recording(&self, path: &Path) -> Result<()> {
+        self.query(&format!(
+            "DELETE FROM airlift_recording WHERE path = {}",
+            quote(&path.display().to_string())
+        ))?;
+        Ok(())
+    }
+
+    fn recordings(&self) -> Result<Vec<RecordingInfo>> {
+        let rows = self.query("SELECT info, path FROM airlift_recording")?;
+        // Gleicher Pfad erneut aufgenommen: der jüngste Eintrag gilt
+        let mut by_path: std::collections::BTreeMap<String, (u64, RecordingInfo)> =
+            std::collections::BTreeMap::new();
+        for row in &rows {
+            let Some(json) = row.get("info").and_then(Value::as_str) else {
+                continue;
+            };
+            let recording: RecordingInfo = serde_json::from_str(json)?;
+            let time = field_u64(row, "time");
+            if by_path.get(&recording.path).is_none_or(|(seen, _)| *seen <= time) {
+                by_path.insert(recording.path.clone(), (time, recording));
+            }
+        }
+        let mut recordings: Vec<(u64, RecordingInfo)> = by_path.into_values().collect();
+        recordings.sort_by_key(|(time, _)| *time);
+        Ok(recordings.into_iter().map(|(_, recording)| recording).collect())
+    }
+}
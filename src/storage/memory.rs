@@ -0,0 +1,214 @@
+// src/storage/memory.rs
+//
+// Standard-Backend: Peaks und Events als Ringpuffer im Prozess (weg nach
+// einem Neustart), Aufnahmen direkt aus den Manifesten der Archive.
+use std::collections::VecDeque;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Result;
+
+use crate::audio::archive::{archive_registry, RecordingInfo};
+use crate::core::lock::lock_mutex;
+use crate::core::Event;
+
+use super::{aggregate_points, event_ms, PeakBucket, PeakPoint, StorageBackend, StorageMemory};
+
+/// Obergrenze des Event-Logs unabhängig von der Aufbewahrung
+pub const MAX_EVENTS: usize = 10_000;
+
+#[derive(Debug)]
+pub struct PeakHistory {
+    points: VecDeque<PeakPoint>,
+    retention_ms: u64,
+}
+
+impl Default for PeakHistory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PeakHistory {
+    pub fn new() -> Self {
+        Self::with_retention(super::DEFAULT_RETENTION)
+    }
+
+    pub fn with_retention(retention: Duration) -> Self {
+        Self {
+            points: VecDeque::new(),
+            retention_ms: retention.as_millis() as u64,
+        }
+    }
+
+    pub fn push(&mut self, point: PeakPoint) {
+        self.points.push_back(point);
+        self.trim_to_retention();
+    }
+
+    fn matching<'a>(
+        &'a self,
+        from: u64,
+        to: u64,
+        flow: Option<&'a str>,
+    ) -> impl Iterator<Item = &'a PeakPoint> {
+        self.points
+            .iter()
+            .filter(move |point| point.ts >= from && point.ts <= to)
+            .filter(move |point| flow.map(|filter| point.flow == filter).unwrap_or(true))
+    }
+
+    pub fn range(&self, from: u64, to: u64, flow: Option<&str>) -> Vec<PeakPoint> {
+        self.matching(from, to, flow).cloned().collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Geschätzter Speicher: reservierte Punkte plus Flow-Namen.
+    pub fn memory_bytes(&self) -> usize {
+        self.points.capacity() * std::mem::size_of::<PeakPoint>()
+            + self.points.iter().map(|point| point.flow.capacity()).sum::<usize>()
+    }
+
+    pub fn count(&self, from: u64, to: u64, flow: Option<&str>) -> usize {
+        self.matching(from, to, flow).count()
+    }
+
+    /// Min/Max/Mittel je Flow und Bucket, siehe `aggregate_points`.
+    pub fn aggregate(&self, from: u64, to: u64, flow: Option<&str>, bucket_ms: u64) -> Vec<PeakBucket> {
+        aggregate_points(self.matching(from, to, flow), bucket_ms)
+    }
+
+    pub fn buffer_range(&self, flow: Option<&str>) -> Option<(u64, u64)> {
+        let mut iter = self
+            .points
+            .iter()
+            .filter(|point| flow.map(|filter| point.flow == filter).unwrap_or(true));
+        let start = iter.next()?.ts;
+        let end = iter.next_back().map(|point| point.ts).unwrap_or(start);
+        Some((start, end))
+    }
+
+    fn trim_to_retention(&mut self) {
+        if let Some(latest) = self.points.back().map(|point| point.ts) {
+            let min_ts = latest.saturating_sub(self.retention_ms);
+            while let Some(front) = self.points.front() {
+                if front.ts < min_ts {
+                    self.points.pop_front();
+                } else {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+pub struct MemoryBackend {
+    peaks: Mutex<PeakHistory>,
+    events: Mutex<VecDeque<Event>>,
+    retention_ms: u64,
+}
+
+impl Default for MemoryBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MemoryBackend {
+    pub fn new() -> Self {
+        Self::with_retention(super::DEFAULT_RETENTION)
+    }
+
+    pub fn with_retention(retention: Duration) -> Self {
+        Self {
+            peaks: Mutex::new(PeakHistory::with_retention(retention)),
+            events: Mutex::new(VecDeque::new()),
+            retention_ms: retention.as_millis() as u64,
+        }
+    }
+}
+
+impl StorageBackend for MemoryBackend {
+    fn kind(&self) -> &'static str {
+        "memory"
+    }
+
+    fn push_peak(&self, point: PeakPoint) -> Result<()> {
+        lock_mutex(&self.peaks, "storage.memory.push_peak").push(point);
+        Ok(())
+    }
+
+    fn peaks(&self, from: u64, to: u64, flow: Option<&str>) -> Result<Vec<PeakPoint>> {
+        Ok(lock_mutex(&self.peaks, "storage.memory.peaks").range(from, to, flow))
+    }
+
+    fn peak_count(&self, from: u64, to: u64, flow: Option<&str>) -> Result<usize> {
+        Ok(lock_mutex(&self.peaks, "storage.memory.peak_count").count(from, to, flow))
+    }
+
+    fn aggregate_peaks(
+        &self,
+        from: u64,
+        to: u64,
+        flow: Option<&str>,
+        bucket_ms: u64,
+    ) -> Result<Vec<PeakBucket>> {
+        Ok(lock_mutex(&self.peaks, "storage.memory.aggregate").aggregate(from, to, flow, bucket_ms))
+    }
+
+    fn peak_range(&self, flow: Option<&str>) -> Result<Option<(u64, u64)>> {
+        Ok(lock_mutex(&self.peaks, "storage.memory.peak_range").buffer_range(flow))
+    }
+
+    fn push_event(&self, event: &Event) -> Result<()> {
+        let mut events = lock_mutex(&self.events, "storage.memory.push_event");
+        events.push_back(event.clone());
+        let min_ms = event_ms(event).saturating_sub(self.retention_ms);
+        while events.len() > MAX_EVENTS || events.front().is_some_and(|old| event_ms(old) < min_ms) {
+            events.pop_front();
+        }
+        Ok(())
+    }
+
+    fn events(&self, from: u64, to: u64, limit: usize) -> Result<Vec<Event>> {
+        let events = lock_mutex(&self.events, "storage.memory.events");
+        let mut matching: Vec<Event> = events
+            .iter()
+            .rev()
+            .filter(|event| (from..=to).contains(&event_ms(event)))
+            .take(limit)
+            .cloned()
+            .collect();
+        matching.reverse();
+        Ok(matching)
+    }
+
+    /// Die Manifeste sind bereits der Index.
+    fn index_recording(&self, _recording: &RecordingInfo) -> Result<()> {
+        Ok(())
+    }
+
+    fn forget_recording(&self, _path: &Path) -> Result<()> {
+        Ok(())
+    }
+
+    fn recordings(&self) -> Result<Vec<RecordingInfo>> {
+        Ok(archive_registry().recordings())
+    }
+
+    fn memory_usage(&self) -> StorageMemory {
+        let peaks = lock_mutex(&self.peaks, "storage.memory.usage");
+        StorageMemory {
+            peak_points: peaks.len(),
+            bytes: peaks.memory_bytes(),
+        }
+    }
+}
@@ -0,0 +1,201 @@
+// src/storage/mod.rs
+//
+// Ablage für Peak-Historie, Event-Log und Aufnahme-Index. Die API-Handler
+// sprechen nur mit `StorageBackend`; welches Backend dahinter steht, legt
+// `[storage]` fest:
+//
+// - `memory` (Standard): Ringpuffer im Prozess, Aufnahmen aus den Manifesten
+// - `sqlite`: eine Datei, übersteht Neustarts (Cargo-Feature `sqlite`)
+// - `influx`: InfluxDB 1.x über HTTP, Schreiben gebündelt im Hintergrund
+//
+// Ein weiteres Backend (Postgres, ClickHouse …) implementiert den Trait und
+// bekommt einen Zweig in `open`.
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+use anyhow::{bail, Result};
+use serde::Serialize;
+
+use crate::audio::archive::RecordingInfo;
+use crate::config::StorageConfig;
+use crate::core::lock::lock_mutex;
+use crate::core::Event;
+
+pub mod influx;
+pub mod memory;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+
+pub use influx::InfluxBackend;
+pub use memory::{MemoryBackend, PeakHistory};
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteBackend;
+
+/// Standard-Aufbewahrung für Peaks und Events
+pub const DEFAULT_RETENTION: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PeakPoint {
+    pub ts: u64,
+    pub peak_l: f32,
+    pub peak_r: f32,
+    pub silence: bool,
+    pub flow: String,
+}
+
+/// Aggregierter Zeitraum `[ts, ts + bucket_ms)` eines Flows. `peak_l`/`peak_r`
+/// sind die Maxima, damit Clients ohne Aggregations-Support weiter zeichnen.
+#[derive(Debug, Clone, Serialize)]
+pub struct PeakBucket {
+    pub ts: u64,
+    pub bucket_ms: u64,
+    pub peak_l: f32,
+    pub peak_r: f32,
+    pub min_l: f32,
+    pub min_r: f32,
+    pub avg_l: f32,
+    pub avg_r: f32,
+    /// Nur wenn alle Punkte im Bucket still waren
+    pub silence: bool,
+    pub count: usize,
+    pub flow: String,
+}
+
+/// Speicher des Backends im Prozess (für `/api/memory`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StorageMemory {
+    pub peak_points: usize,
+    pub bytes: usize,
+}
+
+/// Persistenz für Historie und Aufnahme-Metadaten. Zeiten in ms (UTC),
+/// Bereiche inklusive beider Grenzen.
+pub trait StorageBackend: Send + Sync {
+    /// `memory`, `sqlite`, `influx`
+    fn kind(&self) -> &'static str;
+
+    fn push_peak(&self, point: PeakPoint) -> Result<()>;
+
+    /// Rohpunkte im Bereich, älteste zuerst.
+    fn peaks(&self, from: u64, to: u64, flow: Option<&str>) -> Result<Vec<PeakPoint>>;
+
+    fn peak_count(&self, from: u64, to: u64, flow: Option<&str>) -> Result<usize> {
+        Ok(self.peaks(from, to, flow)?.len())
+    }
+
+    fn aggregate_peaks(
+        &self,
+        from: u64,
+        to: u64,
+        flow: Option<&str>,
+        bucket_ms: u64,
+    ) -> Result<Vec<PeakBucket>> {
+        Ok(aggregate_points(&self.peaks(from, to, flow)?, bucket_ms))
+    }
+
+    /// Ältester und jüngster gespeicherter Punkt.
+    fn peak_range(&self, flow: Option<&str>) -> Result<Option<(u64, u64)>>;
+
+    fn push_event(&self, event: &Event) -> Result<()>;
+
+    /// Die jüngsten `limit` Events im Bereich, älteste zuerst.
+    fn events(&self, from: u64, to: u64, limit: usize) -> Result<Vec<Event>>;
+
+    /// Fertige Aufnahme eintragen (gleicher Pfad ersetzt den Eintrag).
+    fn index_recording(&self, recording: &RecordingInfo) -> Result<()>;
+
+    fn forget_recording(&self, path: &Path) -> Result<()>;
+
+    fn recordings(&self) -> Result<Vec<RecordingInfo>>;
+
+    fn find_recording(&self, id: &str) -> Result<Option<RecordingInfo>> {
+        Ok(self.recordings()?.into_iter().find(|recording| recording.id == id))
+    }
+
+    fn memory_usage(&self) -> StorageMemory {
+        StorageMemory::default()
+    }
+}
+
+/// Min/Max/Mittel je Flow und Bucket; Buckets sind an Vielfachen von
+/// `bucket_ms` ausgerichtet, leere Buckets entfallen.
+pub fn aggregate_points<'a>(
+    points: impl IntoIterator<Item = &'a PeakPoint>,
+    bucket_ms: u64,
+) -> Vec<PeakBucket> {
+    let bucket_ms = bucket_ms.max(1);
+    let mut buckets: BTreeMap<(u64, &str), PeakBucket> = BTreeMap::new();
+    for point in points {
+        let start = point.ts - point.ts % bucket_ms;
+        let bucket = buckets
+            .entry((start, point.flow.as_str()))
+            .or_insert_with(|| PeakBucket {
+                ts: start,
+                bucket_ms,
+                peak_l: point.peak_l,
+                peak_r: point.peak_r,
+                min_l: point.peak_l,
+                min_r: point.peak_r,
+                avg_l: 0.0,
+                avg_r: 0.0,
+                silence: true,
+                count: 0,
+                flow: point.flow.clone(),
+            });
+        bucket.peak_l = bucket.peak_l.max(point.peak_l);
+        bucket.peak_r = bucket.peak_r.max(point.peak_r);
+        bucket.min_l = bucket.min_l.min(point.peak_l);
+        bucket.min_r = bucket.min_r.min(point.peak_r);
+        // Zunächst Summen, unten durch `count` geteilt
+        bucket.avg_l += point.peak_l;
+        bucket.avg_r += point.peak_r;
+        bucket.silence &= point.silence;
+        bucket.count += 1;
+    }
+    buckets
+        .into_values()
+        .map(|mut bucket| {
+            bucket.avg_l /= bucket.count as f32;
+            bucket.avg_r /= bucket.count as f32;
+            bucket
+        })
+        .collect()
+}
+
+/// Zeitstempel eines Events in ms (Events tragen ns).
+pub fn event_ms(event: &Event) -> u64 {
+    event.timestamp / 1_000_000
+}
+
+/// Backend laut `[storage]`.
+pub fn open(config: &StorageConfig) -> Result<Arc<dyn StorageBackend>> {
+    config.validate()?;
+    let retention = config.retention()?;
+    match config.backend() {
+        "memory" => Ok(Arc::new(MemoryBackend::with_retention(retention))),
+        #[cfg(feature = "sqlite")]
+        "sqlite" => Ok(Arc::new(SqliteBackend::open(
+            config.path.as_deref().unwrap_or_default(),
+            retention,
+        )?)),
+        "influx" => Ok(Arc::new(InfluxBackend::new(influx::InfluxOptions::from_config(config)?))),
+        other => bail!("storage backend '{}' is not available in this build", other),
+    }
+}
+
+static BACKEND: OnceLock<Mutex<Arc<dyn StorageBackend>>> = OnceLock::new();
+
+fn slot() -> &'static Mutex<Arc<dyn StorageBackend>> {
+    BACKEND.get_or_init(|| Mutex::new(Arc::new(MemoryBackend::new())))
+}
+
+/// Backend des Prozesses; ohne `install` der Speicher-Ringpuffer.
+pub fn install(backend: Arc<dyn StorageBackend>) {
+    *lock_mutex(slot(), "storage.install") = backend;
+}
+
+pub fn backend() -> Arc<dyn StorageBackend> {
+    lock_mutex(slot(), "storage.backend").clone()
+}
@@ -0,0 +1,216 @@
+// src/storage/sqlite.rs
+//
+// SQLite-Backend (Cargo-Feature `sqlite`): Peaks, Events und Aufnahme-Index
+// in einer Datei, damit die Historie einen Neustart übersteht. Alte Peaks
+// und Events werden beim Schreiben höchstens einmal pro Minute gelöscht.
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::audio::archive::RecordingInfo;
+use crate::core::lock::lock_mutex;
+use crate::core::Event;
+
+use super::{event_ms, PeakPoint, StorageBackend};
+
+/// Abstand zwischen zwei Aufräumläufen
+const PRUNE_INTERVAL_MS: u64 = 60_000;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS peaks (
+        ts INTEGER NOT NULL,
+        flow TEXT NOT NULL,
+        peak_l REAL NOT NULL,
+        peak_r REAL NOT NULL,
+        silence INTEGER NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS peaks_ts ON peaks (ts);
+    CREATE INDEX IF NOT EXISTS peaks_flow_ts ON peaks (flow, ts);
+    CREATE TABLE IF NOT EXISTS events (
+        ts INTEGER NOT NULL,
+        event TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS events_ts ON events (ts);
+    CREATE TABLE IF NOT EXISTS recordings (
+        path TEXT PRIMARY KEY,
+        id TEXT NOT NULL,
+        finished_at_ms INTEGER NOT NULL,
+        info TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS recordings_id ON recordings (id);
+";
+
+struct Inner {
+    connection: Connection,
+    last_prune_ms: u64,
+}
+
+pub struct SqliteBackend {
+    inner: Mutex<Inner>,
+    retention_ms: u64,
+}
+
+impl SqliteBackend {
+    pub fn open(path: impl AsRef<Path>, retention: Duration) -> Result<Self> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("cannot create {}", parent.display()))?;
+        }
+        let connection = Connection::open(path)
+            .with_context(|| format!("cannot open SQLite database {}", path.display()))?;
+        // WAL: API-Abfragen blockieren das Schreiben aus dem Event-Thread nicht
+        connection.pragma_update(None, "journal_mode", "WAL")?;
+        connection.pragma_update(None, "synchronous", "NORMAL")?;
+        connection.execute_batch(SCHEMA)?;
+        Ok(Self {
+            inner: Mutex::new(Inner {
+                connection,
+                last_prune_ms: 0,
+            }),
+            retention_ms: retention.as_millis() as u64,
+        })
+    }
+
+    fn prune(&self, inner: &mut Inner, now_ms: u64) -> Result<()> {
+        if now_ms < inner.last_prune_ms + PRUNE_INTERVAL_MS {
+            return Ok(());
+        }
+        inner.last_prune_ms = now_ms;
+        let min_ts = now_ms.saturating_sub(self.retention_ms) as i64;
+        inner.connection.execute("DELETE FROM peaks WHERE ts < ?1", [min_ts])?;
+        inner.connection.execute("DELETE FROM events WHERE ts < ?1", [min_ts])?;
+        Ok(())
+    }
+}
+
+fn peak_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<PeakPoint> {
+    Ok(PeakPoint {
+        ts: row.get::<_, i64>(0)? as u64,
+        flow: row.get(1)?,
+        peak_l: row.get::<_, f64>(2)? as f32,
+        peak_r: row.get::<_, f64>(3)? as f32,
+        silence: row.get(4)?,
+    })
+}
+
+impl StorageBackend for SqliteBackend {
+    fn kind(&self) -> &'static str {
+        "sqlite"
+    }
+
+    fn push_peak(&self, point: PeakPoint) -> Result<()> {
+        let mut inner = lock_mutex(&self.inner, "storage.sqlite.push_peak");
+        inner.connection.execute(
+            "INSERT INTO peaks (ts, flow, peak_l, peak_r, silence) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![point.ts as i64, point.flow, point.peak_l as f64, point.peak_r as f64, point.silence],
+        )?;
+        self.prune(&mut inner, point.ts)
+    }
+
+    fn peaks(&self, from: u64, to: u64, flow: Option<&str>) -> Result<Vec<PeakPoint>> {
+        let inner = lock_mutex(&self.inner, "storage.sqlite.peaks");
+        let mut statement = inner.connection.prepare_cached(
+            "SELECT ts, flow, peak_l, peak_r, silence FROM peaks
+             WHERE ts >= ?1 AND ts <= ?2 AND (?3 IS NULL OR flow = ?3)
+             ORDER BY ts, rowid",
+        )?;
+        let points = statement
+            .query_map(params![from as i64, to.min(i64::MAX as u64) as i64, flow], peak_from_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(points)
+    }
+
+    fn peak_count(&self, from: u64, to: u64, flow: Option<&str>) -> Result<usize> {
+        let inner = lock_mutex(&self.inner, "storage.sqlite.peak_count");
+        let count: i64 = inner.connection.query_row(
+            "SELECT COUNT(*) FROM peaks WHERE ts >= ?1 AND ts <= ?2 AND (?3 IS NULL OR flow = ?3)",
+            params![from as i64, to.min(i64::MAX as u64) as i64, flow],
+            |row| row.get(0),
+        )?;
+        Ok(count as usize)
+    }
+
+    fn peak_range(&self, flow: Option<&str>) -> Result<Option<(u64, u64)>> {
+        let inner = lock_mutex(&self.inner, "storage.sqlite.peak_range");
+        let range: (Option<i64>, Option<i64>) = inner.connection.query_row(
+            "SELECT MIN(ts), MAX(ts) FROM peaks WHERE ?1 IS NULL OR flow = ?1",
+            params![flow],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        Ok(match range {
+            (Some(start), Some(end)) => Some((start as u64, end as u64)),
+            _ => None,
+        })
+    }
+
+    fn push_event(&self, event: &Event) -> Result<()> {
+        let mut inner = lock_mutex(&self.inner, "storage.sqlite.push_event");
+        inner.connection.execute(
+            "INSERT INTO events (ts, event) VALUES (?1, ?2)",
+            params![event_ms(event) as i64, serde_json::to_string(event)?],
+        )?;
+        self.prune(&mut inner, event_ms(event))
+    }
+
+    fn events(&self, from: u64, to: u64, limit: usize) -> Result<Vec<Event>> {
+        let inner = lock_mutex(&self.inner, "storage.sqlite.events");
+        let mut statement = inner.connection.prepare_cached(
+            "SELECT event FROM events WHERE ts >= ?1 AND ts <= ?2 ORDER BY ts DESC, rowid DESC LIMIT ?3",
+        )?;
+        let mut events = statement
+            .query_map(
+                params![from as i64, to.min(i64::MAX as u64) as i64, limit as i64],
+                |row| row.get::<_, String>(0),
+            )?
+            .map(|json| Ok(serde_json::from_str(&json?)?))
+            .collect::<Result<Vec<Event>>>()?;
+        events.reverse();
+        Ok(events)
+    }
+
+    fn index_recording(&self, recording: &RecordingInfo) -> Result<()> {
+        let inner = lock_mutex(&self.inner, "storage.sqlite.index_recording");
+        inner.connection.execute(
+            "INSERT OR REPLACE INTO recordings (path, id, finished_at_ms, info) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                recording.path,
+                recording.id,
+                recording.entry.finished_at_ms as i64,
+                serde_json::to_string(recording)?
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn forget_recording(&self, path: &Path) -> Result<()> {
+        let inner = lock_mutex(&self.inner, "storage.sqlite.forget_recording");
+        inner
+            .connection
+            .execute("DELETE FROM recordings WHERE path = ?1", [path.display().to_string()])?;
+        Ok(())
+    }
+
+    fn recordings(&self) -> Result<Vec<RecordingInfo>> {
+        let inner = lock_mutex(&self.inner, "storage.sqlite.recordings");
+        let mut statement =
+            inner.connection.prepare_cached("SELECT info FROM recordings ORDER BY finished_at_ms, path")?;
+        let recordings = statement
+            .query_map([], |row| row.get::<_, String>(0))?
+            .map(|json| Ok(serde_json::from_str(&json?)?))
+            .collect::<Result<Vec<RecordingInfo>>>()?;
+        Ok(recordings)
+    }
+
+    fn find_recording(&self, id: &str) -> Result<Option<RecordingInfo>> {
+        let inner = lock_mutex(&self.inner, "storage.sqlite.find_recording");
+        let json: Option<String> = inner
+            .connection
+            .query_row("SELECT info FROM recordings WHERE id = ?1 LIMIT 1", [id], |row| row.get(0))
+            .optional()?;
+        json.map(|json| Ok(serde_json::from_str(&json)?)).transpose()
+    }
+}
@@ -6,8 +6,10 @@ use std::sync::{Arc, Mutex};
 use anyhow::Result;
 
 use crate::core::consumer::{Consumer, ConsumerStatus};
+use crate::core::encoded_flow::EncodedProducer;
 use crate::core::ringbuffer::{AudioRingBuffer, PcmFrame};
 use crate::core::{Producer, ProducerStatus};
+use crate::ring::{EncodedFramePacket, EncodedSink};
 
 pub struct MockProducer {
     name: String,
@@ -90,10 +92,12 @@ impl Producer for MockProducer {
     fn status(&self) -> ProducerStatus {
         ProducerStatus {
             running: self.running.load(Ordering::Relaxed),
+            paused: false,
             connected: self.ring_buffer.is_some(),
             samples_processed: self.samples_processed.load(Ordering::Relaxed),
             errors: self.errors.load(Ordering::Relaxed),
             buffer_stats: self.ring_buffer.as_ref().map(|buffer| buffer.stats()),
+            decoder: None,
         }
     }
 
@@ -205,6 +209,7 @@ impl Consumer for MockConsumer {
             frames_processed: self.frames_processed.load(Ordering::Relaxed),
             bytes_written: self.bytes_written.load(Ordering::Relaxed),
             errors: self.errors.load(Ordering::Relaxed),
+            connection: None,
         }
     }
 
@@ -213,6 +218,121 @@ impl Consumer for MockConsumer {
     }
 }
 
+/// Liefert eine feste Liste kodierter Frames (Passthrough-Tests).
+pub struct MockEncodedProducer {
+    name: String,
+    running: Arc<AtomicBool>,
+    sink: Option<Arc<dyn EncodedSink>>,
+    frames: Vec<EncodedFramePacket>,
+    thread_handle: Option<std::thread::JoinHandle<()>>,
+    frames_sent: Arc<AtomicU64>,
+}
+
+impl MockEncodedProducer {
+    pub fn new(name: &str, frames: Vec<EncodedFramePacket>) -> Self {
+        Self {
+            name: name.to_string(),
+            running: Arc::new(AtomicBool::new(false)),
+            sink: None,
+            frames,
+            thread_handle: None,
+            frames_sent: Arc::new(AtomicU64::new(0)),
+        }
+    }
+}
+
+impl EncodedProducer for MockEncodedProducer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn start(&mut self) -> Result<()> {
+        if self.running.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+
+        let sink = self
+            .sink
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("MockEncodedProducer '{}' missing sink", self.name))?;
+
+        self.running.store(true, Ordering::SeqCst);
+
+        let running = self.running.clone();
+        let frames = std::mem::take(&mut self.frames);
+        let frames_sent = self.frames_sent.clone();
+
+        let handle = std::thread::spawn(move || {
+            for frame in frames {
+                if !running.load(Ordering::Relaxed) {
+                    break;
+                }
+                if sink.push(frame).is_ok() {
+                    frames_sent.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            running.store(false, Ordering::SeqCst);
+        });
+
+        self.thread_handle = Some(handle);
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        self.running.store(false, Ordering::SeqCst);
+        if let Some(handle) = self.thread_handle.take() {
+            let _ = handle.join();
+        }
+        Ok(())
+    }
+
+    fn status(&self) -> ProducerStatus {
+        ProducerStatus {
+            running: self.running.load(Ordering::Relaxed),
+            paused: false,
+            connected: self.sink.is_some(),
+            samples_processed: self.frames_sent.load(Ordering::Relaxed),
+            errors: 0,
+            buffer_stats: None,
+            decoder: None,
+        }
+    }
+
+    fn attach_encoded_sink(&mut self, sink: Arc<dyn EncodedSink>) {
+        self.sink = Some(sink);
+    }
+}
+
+/// Sammelt alle empfangenen kodierten Frames.
+#[derive(Default)]
+pub struct MockEncodedSink {
+    received: Mutex<Vec<EncodedFramePacket>>,
+}
+
+impl MockEncodedSink {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn received(&self) -> Vec<EncodedFramePacket> {
+        self.received.lock().expect("lock received").clone()
+    }
+
+    pub fn payloads(&self) -> Vec<Vec<u8>> {
+        self.received()
+            .into_iter()
+            .map(|packet| packet.frame.payload)
+            .collect()
+    }
+}
+
+impl EncodedSink for MockEncodedSink {
+    fn push(&self, frame: EncodedFramePacket) -> Result<()> {
+        self.received.lock().expect("lock received").push(frame);
+        Ok(())
+    }
+}
+
 impl_connectable_producer!(MockProducer);
 
 impl_connectable_consumer!(MockConsumer);
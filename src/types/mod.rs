@@ -1,4 +1,10 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+pub mod units;
+
+pub use units::{Frames, Millis, Samples, Slots};
 
 #[derive(Clone, Debug)]
 pub struct PcmFrame {
@@ -6,9 +12,65 @@ pub struct PcmFrame {
     pub samples: Vec<i16>,
     pub sample_rate: u32,
     pub channels: u8,
+    /// Begleitdaten, die Processors unverändert weiterreichen
+    pub metadata: FrameMetadata,
 }
 
-#[derive(Clone, Debug, Serialize)]
+/// Frei belegbare Begleitdaten eines Frames, z. B. Marker, Messwerte oder
+/// Quellkennungen. Leer ohne Allokation; Klone teilen sich die Einträge,
+/// erst Schreiben kopiert.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FrameMetadata(Option<Arc<BTreeMap<String, serde_json::Value>>>);
+
+impl FrameMetadata {
+    pub fn is_empty(&self) -> bool {
+        self.0.as_ref().is_none_or(|entries| entries.is_empty())
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.as_ref().map_or(0, |entries| entries.len())
+    }
+
+    pub fn get(&self, key: &str) -> Option<&serde_json::Value> {
+        self.0.as_ref().and_then(|entries| entries.get(key))
+    }
+
+    pub fn insert(&mut self, key: impl Into<String>, value: serde_json::Value) -> Option<serde_json::Value> {
+        Arc::make_mut(self.0.get_or_insert_with(Default::default)).insert(key.into(), value)
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<serde_json::Value> {
+        self.0.as_mut().and_then(|entries| Arc::make_mut(entries).remove(key))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &serde_json::Value)> {
+        self.0.iter().flat_map(|entries| entries.iter())
+    }
+
+    /// Übernimmt Einträge aus `other`, die hier noch fehlen.
+    pub fn merge(&mut self, other: &FrameMetadata) {
+        if other.is_empty() {
+            return;
+        }
+        if self.is_empty() {
+            *self = other.clone();
+            return;
+        }
+        for (key, value) in other.iter() {
+            if self.get(key).is_none() {
+                self.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+impl Serialize for FrameMetadata {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.iter())
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct CodecInfo {
     pub kind: CodecKind,
     pub sample_rate: u32,
@@ -22,7 +84,7 @@ pub struct EncodedFrame {
     pub info: CodecInfo,
 }
 
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum CodecKind {
     Pcm,
     OpusOgg,
@@ -33,7 +95,7 @@ pub enum CodecKind {
     Flac,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ContainerKind {
     Raw,
     Ogg,
@@ -0,0 +1,156 @@
+// src/types/units.rs
+//
+// Zähl-Einheiten für Audio-Mengen. `PcmFrame::samples` ist interleaved, ein
+// Slot im Ringbuffer hält einen ganzen `PcmFrame` – wer Samples, Frames
+// (Samples pro Kanal) und Slots als nacktes `usize` mischt, rechnet schnell
+// um den Faktor Kanäle oder Frame-Länge daneben. Umrechnungen gibt es nur
+// explizit mit Kanalzahl bzw. Samplerate. Serialisiert wird die nackte Zahl.
+use std::fmt;
+use std::ops::{Add, AddAssign, Sub};
+
+use serde::{Deserialize, Serialize};
+
+macro_rules! count_unit {
+    ($(#[$meta:meta])* $name:ident, $suffix:literal) => {
+        $(#[$meta])*
+        #[derive(
+            Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+        )]
+        #[serde(transparent)]
+        pub struct $name(pub usize);
+
+        impl $name {
+            pub const ZERO: Self = Self(0);
+
+            pub fn get(self) -> usize {
+                self.0
+            }
+
+            pub fn is_zero(self) -> bool {
+                self.0 == 0
+            }
+
+            pub fn saturating_sub(self, other: Self) -> Self {
+                Self(self.0.saturating_sub(other.0))
+            }
+        }
+
+        impl Add for $name {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                Self(self.0 + other.0)
+            }
+        }
+
+        impl AddAssign for $name {
+            fn add_assign(&mut self, other: Self) {
+                self.0 += other.0;
+            }
+        }
+
+        impl Sub for $name {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                Self(self.0 - other.0)
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{} {}", self.0, $suffix)
+            }
+        }
+    };
+}
+
+count_unit!(
+    /// Interleaved Samples (`PcmFrame::samples.len()`)
+    Samples,
+    "samples"
+);
+count_unit!(
+    /// Samples pro Kanal, also Zeitpunkte
+    Frames,
+    "frames"
+);
+count_unit!(
+    /// Plätze in einem Ringbuffer, je einer pro `PcmFrame`
+    Slots,
+    "slots"
+);
+
+impl Samples {
+    pub fn of(frame: &crate::types::PcmFrame) -> Self {
+        Self(frame.samples.len())
+    }
+
+    pub fn to_frames(self, channels: u8) -> Frames {
+        Frames(self.0 / channels.max(1) as usize)
+    }
+
+    /// Speicher als `i16`
+    pub fn bytes(self) -> u64 {
+        (self.0 * std::mem::size_of::<i16>()) as u64
+    }
+}
+
+impl Frames {
+    pub fn to_samples(self, channels: u8) -> Samples {
+        Samples(self.0 * channels.max(1) as usize)
+    }
+
+    pub fn to_millis(self, sample_rate: u32) -> Millis {
+        Millis(self.0 as f64 * 1000.0 / sample_rate.max(1) as f64)
+    }
+}
+
+impl Slots {
+    /// Gesamtdauer, wenn jeder Slot `per_slot` lang ist
+    pub fn span(self, per_slot: Millis) -> Millis {
+        Millis(self.0 as f64 * per_slot.0)
+    }
+}
+
+/// Dauer in Millisekunden (Latenzen, Frame-Längen)
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Millis(pub f64);
+
+impl Millis {
+    pub const ZERO: Self = Self(0.0);
+
+    pub fn get(self) -> f64 {
+        self.0
+    }
+
+    pub fn max(self, other: Self) -> Self {
+        Self(self.0.max(other.0))
+    }
+
+    /// Abgerundet auf ganze Frames
+    pub fn to_frames(self, sample_rate: u32) -> Frames {
+        Frames((self.0.max(0.0) * sample_rate as f64 / 1000.0) as usize)
+    }
+}
+
+impl Add for Millis {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self(self.0 + other.0)
+    }
+}
+
+impl AddAssign for Millis {
+    fn add_assign(&mut self, other: Self) {
+        self.0 += other.0;
+    }
+}
+
+impl fmt::Display for Millis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.0} ms", self.0)
+    }
+}
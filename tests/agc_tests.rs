@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+use airlift_node::core::processor::Processor;
+use airlift_node::processors::Agc;
+use airlift_node::PcmFrame;
+
+fn agc(config: serde_json::Value) -> anyhow::Result<Agc> {
+    let config: HashMap<String, serde_json::Value> = serde_json::from_value(config)?;
+    Agc::from_config("leveler", &config)
+}
+
+/// `seconds` × 100-ms-Frames Mono-Sinus mit Spitzenpegel `level_db`
+fn tone(level_db: f64, seconds: usize) -> Vec<PcmFrame> {
+    let amplitude = 32768.0 * 10f64.powf(level_db / 20.0);
+    (0..seconds * 10)
+        .map(|i| PcmFrame {
+            utc_ns: i as u64 * 100_000_000,
+            samples: (0..4800)
+                .map(|n| (amplitude * (2.0 * PI * 500.0 * n as f64 / 48_000.0).sin()) as i16)
+                .collect(),
+            sample_rate: 48_000,
+            channels: 1,
+            metadata: Default::default(),
+        })
+        .collect()
+}
+
+fn peak_db(frame: &PcmFrame) -> f64 {
+    let peak = frame
+        .samples
+        .iter()
+        .map(|s| s.unsigned_abs())
+        .max()
+        .unwrap_or(0);
+    20.0 * (peak as f64 / 32768.0).log10()
+}
+
+#[test]
+fn gain_drifts_slowly_to_target_and_freezes_on_silence() -> anyhow::Result<()> {
+    let mut leveler = agc(serde_json::json!({ "target": "-20dB", "rate": 1.0, "window": "1s" }))?;
+
+    // Zuspielung 10 dB zu leise: nach 5 s erst +5 dB, nach 15 s am Ziel
+    let mut frames = tone(-30.0, 5);
+    leveler.process_batch(&mut frames)?;
+    assert!(
+        (leveler.gain_db() - 5.0).abs() < 0.2,
+        "gain {}",
+        leveler.gain_db()
+    );
+    let mut frames = tone(-30.0, 10);
+    leveler.process_batch(&mut frames)?;
+    assert!(
+        (leveler.gain_db() - 10.0).abs() < 0.2,
+        "gain {}",
+        leveler.gain_db()
+    );
+    assert!((peak_db(frames.last().unwrap()) + 20.0).abs() < 0.3);
+
+    // Stille hält die Verstärkung fest
+    let mut silence = tone(-80.0, 10);
+    leveler.process_batch(&mut silence)?;
+    assert!(leveler.is_frozen());
+    assert!(
+        (leveler.gain_db() - 10.0).abs() < 0.2,
+        "gain {}",
+        leveler.gain_db()
+    );
+
+    // Zu laut: höchstens 1 dB/s nach unten
+    let mut loud = tone(-10.0, 2);
+    leveler.process_batch(&mut loud)?;
+    assert!(!leveler.is_frozen());
+    assert!(
+        (leveler.gain_db() - 8.0).abs() < 0.2,
+        "gain {}",
+        leveler.gain_db()
+    );
+    Ok(())
+}
+
+#[test]
+fn gain_is_limited_and_config_validated() -> anyhow::Result<()> {
+    let mut leveler = agc(serde_json::json!({ "max_gain": "6dB", "rate": 10 }))?;
+    let mut frames = tone(-40.0, 5);
+    leveler.process_batch(&mut frames)?;
+    assert!(
+        (leveler.gain_db() - 6.0).abs() < 0.01,
+        "gain {}",
+        leveler.gain_db()
+    );
+
+    leveler.update_config(serde_json::json!({ "enabled": false }))?;
+    let mut frames = tone(-40.0, 1);
+    let original = frames[0].samples.clone();
+    leveler.process_batch(&mut frames)?;
+    assert_eq!(frames[0].samples, original);
+
+    assert!(agc(serde_json::json!({ "target": "3dB" })).is_err());
+    assert!(agc(serde_json::json!({ "max_gain": -1 })).is_err());
+    assert!(agc(serde_json::json!({ "window": "10ms" })).is_err());
+    Ok(())
+}
@@ -0,0 +1,66 @@
+#![cfg(feature = "alsa")]
+
+use airlift_node::config::ProducerConfig;
+use airlift_node::core::device_scanner::{AudioFormat, SampleType};
+use airlift_node::producers::alsa::{AlsaProducer, AlsaSampleFormat};
+
+#[test]
+fn parses_format_names() {
+    assert_eq!(AlsaSampleFormat::parse("auto").unwrap(), None);
+    assert_eq!(AlsaSampleFormat::parse("S24_3LE").unwrap(), Some(AlsaSampleFormat::S24_3));
+    assert_eq!(AlsaSampleFormat::parse("float").unwrap(), Some(AlsaSampleFormat::Float));
+    assert!(AlsaSampleFormat::parse("u8").is_err());
+}
+
+#[test]
+fn preferred_format_is_tried_first() {
+    assert_eq!(
+        AlsaSampleFormat::candidates(Some(AlsaSampleFormat::Float)),
+        vec![
+            AlsaSampleFormat::Float,
+            AlsaSampleFormat::S16,
+            AlsaSampleFormat::S32,
+            AlsaSampleFormat::S24_3,
+        ]
+    );
+    assert_eq!(AlsaSampleFormat::candidates(None), AlsaSampleFormat::AUTO_ORDER.to_vec());
+}
+
+#[test]
+fn converts_device_formats_to_s16() {
+    // Halbe Vollaussteuerung in jedem Format
+    let s24 = AlsaSampleFormat::S24_3.decode(&[0x00, 0x00, 0x40, 0x00, 0x00, 0xC0]);
+    assert_eq!(s24, vec![0x4000, -0x4000]);
+    let s32 = AlsaSampleFormat::S32.decode(&0x4000_0000i32.to_le_bytes());
+    assert_eq!(s32, vec![0x4000]);
+    let float: Vec<u8> = [0.5f32, -1.5].iter().flat_map(|v| v.to_le_bytes()).collect();
+    assert_eq!(AlsaSampleFormat::Float.decode(&float), vec![16384, i16::MIN]);
+}
+
+#[test]
+fn maps_scanner_formats() {
+    for format in AlsaSampleFormat::AUTO_ORDER {
+        let scanned = format.audio_format(48_000, 2);
+        assert_eq!(AlsaSampleFormat::from_audio_format(&scanned), Some(format));
+    }
+    let unknown = AudioFormat {
+        sample_rate: 48_000,
+        channels: 2,
+        sample_type: SampleType::Float,
+        bit_depth: 64,
+    };
+    assert_eq!(AlsaSampleFormat::from_audio_format(&unknown), None);
+}
+
+#[test]
+fn rejects_unknown_configured_format() {
+    let mut cfg = ProducerConfig {
+        producer_type: "alsa".to_string(),
+        enabled: true,
+        ..ProducerConfig::default()
+    };
+    cfg.config.insert("format".to_string(), serde_json::json!("s24_3le"));
+    assert!(AlsaProducer::new("mic", &cfg).is_ok());
+    cfg.config.insert("format".to_string(), serde_json::json!("mulaw"));
+    assert!(AlsaProducer::new("mic", &cfg).is_err());
+}
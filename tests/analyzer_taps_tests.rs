@@ -0,0 +1,289 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use airlift_node::core::analyzer_taps::{AnalyzerTap, DEFAULT_ANALYZER_INTERVAL};
+use airlift_node::core::processor::basic::Gain;
+use airlift_node::core::{
+    AnalyzerKind, AnalyzerTapConfig, AnalyzerValues, AudioRingBuffer, BufferRegistry, Flow,
+    TapPoint,
+};
+use airlift_node::PcmFrame;
+use serde_json::json;
+
+const MS: u64 = 1_000_000;
+
+/// 10 ms Stereo bei 48 kHz mit konstantem Sample-Wert
+fn frame(utc_ns: u64, value: i16) -> PcmFrame {
+    PcmFrame {
+        utc_ns,
+        samples: vec![value; 960],
+        sample_rate: 48_000,
+        channels: 2,
+        metadata: Default::default(),
+    }
+}
+
+/// 10 ms Sinus, phasenrichtig ab Frame `index`
+fn sine_frame(index: u64, hz: f64) -> PcmFrame {
+    let samples = (0..480u64)
+        .flat_map(|offset| {
+            let t = (index * 480 + offset) as f64 / 48_000.0;
+            let value = ((2.0 * std::f64::consts::PI * hz * t).sin() * 16_384.0) as i16;
+            [value, value]
+        })
+        .collect();
+    PcmFrame {
+        utc_ns: index * 10 * MS,
+        samples,
+        sample_rate: 48_000,
+        channels: 2,
+        metadata: Default::default(),
+    }
+}
+
+fn tap(kind: AnalyzerKind, interval_ms: u64) -> AnalyzerTap {
+    AnalyzerTap::new(AnalyzerTapConfig {
+        name: "probe".to_string(),
+        point: TapPoint::Output,
+        kind,
+        interval: Duration::from_millis(interval_ms),
+    })
+}
+
+#[test]
+fn tap_points_parse_and_print() -> anyhow::Result<()> {
+    for text in ["input:mic", "merge", "processor:eq", "output"] {
+        assert_eq!(TapPoint::parse(text)?.to_string(), text);
+    }
+    assert_eq!(
+        TapPoint::parse("processor:eq")?,
+        TapPoint::Processor("eq".to_string())
+    );
+    for bad in ["", "input:", "post", "consumer:icecast"] {
+        assert!(TapPoint::parse(bad).is_err(), "{}", bad);
+    }
+    Ok(())
+}
+
+#[test]
+fn analyzer_config_is_validated_against_flow() -> anyhow::Result<()> {
+    let inputs = vec!["mic".to_string()];
+    let processors = vec!["eq".to_string(), "limiter".to_string()];
+
+    let taps = AnalyzerTapConfig::from_config(
+        "main",
+        &json!({
+            "mic_raw": { "point": "input:mic" },
+            "post_eq": { "point": "processor:eq", "kind": "lufs", "interval": "1s" },
+            "out": { "point": "output", "kind": "spectrum", "interval": 250 },
+        }),
+        &inputs,
+        &processors,
+    )?;
+    assert_eq!(taps.len(), 3);
+    let mic = taps.iter().find(|tap| tap.name == "mic_raw").unwrap();
+    assert_eq!(
+        (mic.kind, mic.interval),
+        (AnalyzerKind::Peak, DEFAULT_ANALYZER_INTERVAL)
+    );
+    let post_eq = taps.iter().find(|tap| tap.name == "post_eq").unwrap();
+    assert_eq!(post_eq.point, TapPoint::Processor("eq".to_string()));
+    assert_eq!(post_eq.interval, Duration::from_secs(1));
+
+    for config in [
+        json!(true),
+        json!({ "a": "output" }),
+        json!({ "a": { "kind": "peak" } }),
+        json!({ "a": { "point": "input:line" } }),
+        json!({ "a": { "point": "processor:gate" } }),
+        json!({ "a": { "point": "output", "kind": "phase" } }),
+        json!({ "a": { "point": "output", "interval": "5ms" } }),
+        json!({ "a": { "point": "output", "kind": "lufs", "interval": "150ms" } }),
+    ] {
+        assert!(
+            AnalyzerTapConfig::from_config("main", &config, &inputs, &processors).is_err(),
+            "{}",
+            config
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn peak_tap_reports_each_interval() {
+    let mut tap = tap(AnalyzerKind::Peak, 50);
+    let readings: Vec<_> = (0..10u64)
+        .flat_map(|index| tap.push(&frame(index * 10 * MS, 16_384)))
+        .collect();
+
+    assert_eq!(readings.len(), 2);
+    assert_eq!((readings[0].utc_ns, readings[0].window_ms), (50 * MS, 50));
+    assert_eq!(readings[0].point, "output");
+    match &readings[1].values {
+        AnalyzerValues::Peak { peaks, clipped, .. } => {
+            assert_eq!((*peaks, *clipped), ([0.5, 0.5], 0))
+        }
+        other => panic!("unexpected values {:?}", other),
+    }
+}
+
+#[test]
+fn lufs_and_spectrum_taps_close_at_frame_boundaries() {
+    let mut lufs = tap(AnalyzerKind::Lufs, 200);
+    let mut spectrum = tap(AnalyzerKind::Spectrum, 100);
+    let mut lufs_readings = Vec::new();
+    let mut spectrum_readings = Vec::new();
+    for index in 0..40u64 {
+        let frame = sine_frame(index, 1_000.0);
+        lufs_readings.extend(lufs.push(&frame));
+        spectrum_readings.extend(spectrum.push(&frame));
+    }
+
+    assert_eq!(lufs_readings.len(), 2);
+    assert_eq!(lufs_readings[0].utc_ns, 200 * MS);
+    match &lufs_readings[1].values {
+        // Halbe Aussteuerung, 1 kHz, beide Kanäle: rund −6 LUFS
+        AnalyzerValues::Lufs { short_term, block } => {
+            let short_term = short_term.unwrap();
+            assert!((short_term + 6.0).abs() < 0.5, "{}", short_term);
+            assert!(block.is_some());
+        }
+        other => panic!("unexpected values {:?}", other),
+    }
+
+    assert_eq!(spectrum_readings.len(), 4);
+    match &spectrum_readings[3].values {
+        AnalyzerValues::Spectrum {
+            bands_hz,
+            levels_db,
+        } => {
+            let loudest = levels_db
+                .iter()
+                .enumerate()
+                .max_by(|a, b| a.1.partial_cmp(b.1).unwrap())
+                .map(|(index, _)| bands_hz[index])
+                .unwrap();
+            assert_eq!(loudest, 1_000.0);
+            // −6 dBFS Sinus im eigenen Band
+            assert!((levels_db[5].unwrap() + 6.0).abs() < 0.5);
+        }
+        other => panic!("unexpected values {:?}", other),
+    }
+}
+
+#[test]
+fn flow_measures_input_and_post_processor_points() -> anyhow::Result<()> {
+    let registry = BufferRegistry::new();
+    let input = Arc::new(AudioRingBuffer::new(64));
+    registry.register("producer:mic", input.clone())?;
+
+    let mut flow = Flow::new("main");
+    flow.add_input_from_registry(&registry, "producer:mic")?;
+    flow.add_processor(Box::new(Gain::new("boost", 2.0)));
+    flow.add_processor(Box::new(Gain::new("trim", 1.0)));
+    flow.set_analyzer_taps(AnalyzerTapConfig::from_config(
+        "main",
+        &json!({
+            "raw": { "point": "input:mic", "interval": "20ms" },
+            "boosted": { "point": "processor:boost", "interval": "20ms" },
+        }),
+        &["mic".to_string()],
+        &["boost".to_string(), "trim".to_string()],
+    )?);
+    flow.start()?;
+
+    let deadline = Instant::now() + Duration::from_secs(2);
+    let mut index = 0u64;
+    while Instant::now() < deadline && flow.analyzer_readings().len() < 2 {
+        input.push(frame(index * 10 * MS, 4_096));
+        index += 1;
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    let readings = flow.status().analyzers;
+    flow.stop()?;
+
+    let peak = |name: &str| match &readings.get(name).expect(name).values {
+        AnalyzerValues::Peak { peaks, .. } => peaks[0],
+        other => panic!("unexpected values {:?}", other),
+    };
+    assert_eq!(peak("raw"), 0.125);
+    assert_eq!(peak("boosted"), 0.25);
+    assert_eq!(readings["boosted"].point, "processor:boost");
+    Ok(())
+}
+
+#[test]
+fn silence_tap_counts_silent_time_until_audio_returns() {
+    let mut tap = tap(AnalyzerKind::Silence, 50);
+    let readings: Vec<_> = (0..20u64)
+        .flat_map(|index| tap.push(&frame(index * 10 * MS, if index < 15 { 0 } else { 8_192 })))
+        .collect();
+
+    let silence = |index: usize| match &readings[index].values {
+        AnalyzerValues::Silence { silent, silent_ms, .. } => (*silent, *silent_ms),
+        other => panic!("unexpected values {:?}", other),
+    };
+    assert_eq!(readings.len(), 4);
+    assert_eq!(silence(0), (true, 50));
+    assert_eq!(silence(2), (true, 150));
+    assert_eq!(silence(3), (false, 0));
+}
+
+#[test]
+fn default_analyzers_follow_the_flow() -> anyhow::Result<()> {
+    let config: airlift_node::config::Config = toml::from_str(
+        r#"
+        node_name = "test"
+        [producers]
+        [processors]
+        [consumers]
+        [flows]
+        "#,
+    )?;
+    let defaults = config.default_analyzer_taps()?;
+    assert_eq!(
+        defaults.iter().map(|tap| (tap.name.as_str(), tap.kind)).collect::<Vec<_>>(),
+        [("peak", AnalyzerKind::Peak), ("silence", AnalyzerKind::Silence)]
+    );
+    assert!(defaults.iter().all(|tap| tap.point == TapPoint::Output));
+
+    let mut node = airlift_node::core::AirliftNode::new();
+    node.set_default_analyzer_taps(defaults);
+
+    // Eigener Abgriff gleichen Namens hat Vorrang
+    let mut own = Flow::new("own");
+    own.set_analyzer_taps(AnalyzerTapConfig::from_config(
+        "own",
+        &json!({ "peak": { "point": "merge", "interval": "20ms" } }),
+        &[],
+        &[],
+    )?);
+    node.add_flow(own);
+    let mut quiet = Flow::new("quiet");
+    quiet.set_default_analyzers(false);
+    node.add_flow(quiet);
+
+    let taps = |name: &str| {
+        let flow = &node.flows[node.flow_index_by_name(name).unwrap()];
+        flow.analyzer_taps().iter().map(|tap| tap.point.to_string()).collect::<Vec<_>>()
+    };
+    assert_eq!(taps("own"), ["merge", "output"]);
+    assert!(taps("quiet").is_empty());
+
+    node.remove_flow("own")?;
+    assert!(node.flow_index_by_name("own").is_none());
+
+    for bad in [
+        r#"kinds = ["peak", "phase"]"#,
+        r#"kinds = ["peak", "peak"]"#,
+        r#"interval = "5ms""#,
+        r#"kinds = ["lufs"]
+           interval = "150ms""#,
+    ] {
+        let section: airlift_node::config::AnalyzerDefaultsConfig = toml::from_str(bad)?;
+        assert!(section.taps().is_err(), "{}", bad);
+    }
+    let off: airlift_node::config::AnalyzerDefaultsConfig = toml::from_str("enabled = false")?;
+    assert!(off.taps()?.is_empty());
+    Ok(())
+}
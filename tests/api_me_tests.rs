@@ -0,0 +1,30 @@
+use airlift_node::api::me::describe;
+use airlift_node::config::BindConfig;
+
+#[test]
+fn open_listener_allows_every_action() {
+    let me = describe(&BindConfig::open("127.0.0.1:8087"));
+    assert_eq!(me.listener, "127.0.0.1:8087");
+    assert!(!me.authenticated);
+    assert!(me.capabilities.values().all(|capability| capability.allowed));
+    assert_eq!(me.capabilities["start_stop"].endpoints, ["POST /api/control"]);
+}
+
+#[test]
+fn read_only_listener_only_allows_reading() {
+    let bind = BindConfig {
+        address: "10.20.0.5:8087".to_string(),
+        token: Some("geheim".to_string()),
+        read_only: true,
+    };
+    let me = describe(&bind);
+    assert!(me.authenticated);
+    for name in ["start_stop", "recorder", "config_write", "debug_capture", "probe"] {
+        assert!(!me.capabilities[name].allowed, "{}", name);
+    }
+    assert!(me.capabilities["clip_export"].allowed);
+
+    let json = serde_json::to_value(&me).unwrap();
+    assert_eq!(json["capabilities"]["config_write"]["allowed"], false);
+    assert!(json.get("token").is_none());
+}
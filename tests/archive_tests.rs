@@ -0,0 +1,41 @@
+use std::fs;
+
+use airlift_node::audio::archive::{
+    load_manifest, manifest_path, record_file_at, utc_date, verify_dir, FileState,
+};
+
+#[test]
+fn manifest_verification_reports_missing_and_corrupted_files() {
+    let dir = std::env::temp_dir().join(format!("airlift-archive-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+
+    let now_ms = 1_700_000_000_000;
+    let names = ["a.wav", "b.wav", "c.wav"];
+    for name in names {
+        fs::write(dir.join(name), name.repeat(1000)).unwrap();
+        record_file_at(&dir.join(name), 60_000, now_ms).unwrap();
+    }
+
+    let manifest = load_manifest(&manifest_path(&dir, &utc_date(now_ms))).unwrap();
+    assert_eq!(manifest.date, "2023-11-14");
+    assert_eq!(manifest.entries.len(), 3);
+    assert_eq!(manifest.entries[0].bytes, 5000);
+    assert_eq!(manifest.entries[0].duration_ms, 60_000);
+
+    let report = verify_dir(&dir);
+    assert!(report.is_clean());
+    assert_eq!(report.ok, 3);
+
+    fs::write(dir.join("b.wav"), "tampered").unwrap();
+    fs::remove_file(dir.join("c.wav")).unwrap();
+
+    let report = verify_dir(&dir);
+    assert_eq!(report.checked, 3);
+    assert_eq!(report.ok, 1);
+    let states: Vec<_> = report.problems.iter().map(|p| p.state).collect();
+    assert_eq!(states, vec![FileState::Corrupted, FileState::Missing]);
+    assert!(report.problems[0].path.ends_with("b.wav"));
+
+    let _ = fs::remove_dir_all(&dir);
+}
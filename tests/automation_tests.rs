@@ -0,0 +1,83 @@
+use std::time::{Duration, Instant};
+
+use airlift_node::core::processor::basic::Gain;
+use airlift_node::core::{AirliftNode, AutomationLane, AutomationShape, Flow};
+use airlift_node::testing::mocks::{MockConsumer, MockProducer};
+use airlift_node::PcmFrame;
+
+fn frame(utc_ns: u64) -> PcmFrame {
+    PcmFrame {
+        utc_ns,
+        samples: vec![100, 100],
+        sample_rate: 48_000,
+        channels: 1,
+        metadata: Default::default(),
+    }
+}
+
+fn lane(shape: AutomationShape) -> AutomationLane {
+    AutomationLane {
+        id: 0,
+        processor: "gain".to_string(),
+        parameter: "gain".to_string(),
+        shape,
+        from: 1.0,
+        to: 3.0,
+        start_ns: 100,
+        duration_ns: 10,
+    }
+}
+
+#[test]
+fn automation_is_applied_per_frame_timestamp() -> anyhow::Result<()> {
+    let (consumer, received) = MockConsumer::new_with_shared("out");
+    let mut flow = Flow::new("flow");
+    flow.add_processor(Box::new(Gain::new("gain", 1.0)));
+    flow.add_consumer(Box::new(consumer));
+    let id = flow.schedule_automation(lane(AutomationShape::Linear))?;
+    assert_eq!(flow.automation().lanes()[0].id, id);
+
+    let mut node = AirliftNode::new();
+    node.add_flow(flow);
+    let frames = vec![frame(50), frame(100), frame(105), frame(110), frame(200)];
+    node.add_producer(Box::new(MockProducer::new("src", frames)))?;
+    node.connect_flow_input(0, "producer:src")?;
+
+    node.start()?;
+    let deadline = Instant::now() + Duration::from_secs(1);
+    while Instant::now() < deadline && received.lock().unwrap().len() < 5 {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    node.stop()?;
+
+    let levels: Vec<i16> = received
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|f| f.samples[0])
+        .collect();
+    // Vor dem Start unverändert, danach Rampe 1.0 → 3.0, Endwert bleibt stehen
+    assert_eq!(levels, vec![100, 100, 200, 300, 300]);
+    assert!(node.flows()[0].automation().is_empty());
+    Ok(())
+}
+
+#[test]
+fn automation_requires_known_processor() {
+    let mut flow = Flow::new("flow");
+    flow.add_processor(Box::new(Gain::new("gain", 1.0)));
+
+    let mut unknown = lane(AutomationShape::Linear);
+    unknown.processor = "missing".to_string();
+    assert!(flow.schedule_automation(unknown).is_err());
+
+    let mut no_duration = lane(AutomationShape::Smooth);
+    no_duration.duration_ns = 0;
+    assert!(flow.schedule_automation(no_duration).is_err());
+
+    let id = flow
+        .schedule_automation(lane(AutomationShape::Step))
+        .expect("step lane");
+    assert!(flow.automation().cancel(id));
+    assert!(!flow.automation().cancel(id));
+}
@@ -0,0 +1,113 @@
+use airlift_node::core::processor::basic::Gain;
+use airlift_node::core::processor::{process_batched, Processor, ProcessorStatus, MAX_BATCH_SIZE};
+use airlift_node::core::{AudioRingBuffer, Flow, PcmFrame};
+
+fn frame(index: u64) -> PcmFrame {
+    PcmFrame {
+        utc_ns: index,
+        samples: vec![index as i16 * 10; 4],
+        sample_rate: 48_000,
+        channels: 2,
+        metadata: Default::default(),
+    }
+}
+
+/// Processor ohne Batch-Unterstützung, zählt `process`-Aufrufe.
+struct Counting {
+    calls: usize,
+}
+
+impl Processor for Counting {
+    fn name(&self) -> &str {
+        "counting"
+    }
+
+    fn process(&mut self, input: &AudioRingBuffer, output: &AudioRingBuffer) -> anyhow::Result<()> {
+        self.calls += 1;
+        while let Some(frame) = input.pop() {
+            output.push(frame);
+        }
+        Ok(())
+    }
+
+    fn status(&self) -> ProcessorStatus {
+        ProcessorStatus {
+            running: true,
+            processing_rate_hz: 0.0,
+            latency_ms: 0.0,
+            errors: 0,
+            metrics: None,
+        }
+    }
+
+    fn update_config(&mut self, _config: serde_json::Value) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn batch_pop_respects_max_and_reader_positions() {
+    let buffer = AudioRingBuffer::new(8);
+    buffer.push_batch((1..=5).map(frame));
+    assert_eq!(buffer.len(), 5);
+
+    let first = buffer.pop_batch_for_reader("a", 3);
+    assert_eq!(
+        first.iter().map(|f| f.utc_ns).collect::<Vec<_>>(),
+        [1, 2, 3]
+    );
+    let rest = buffer.pop_batch_for_reader("a", 10);
+    assert_eq!(rest.iter().map(|f| f.utc_ns).collect::<Vec<_>>(), [4, 5]);
+    assert!(buffer.pop_batch_for_reader("a", 10).is_empty());
+    assert!(buffer.pop_batch_for_reader("b", 0).is_empty());
+
+    // Andere Leser sind unabhängig, Einzel- und Batch-Pop teilen die Position
+    assert_eq!(buffer.pop_for_reader("b").map(|f| f.utc_ns), Some(1));
+    assert_eq!(buffer.pop_batch_for_reader("b", 2).len(), 2);
+    assert_eq!(buffer.pop_for_reader("b").map(|f| f.utc_ns), Some(4));
+
+    // Überholte Leser beginnen beim ältesten vorhandenen Frame
+    buffer.push_batch((6..=15).map(frame));
+    let lagging = buffer.pop_batch_for_reader("a", 4);
+    assert_eq!(lagging.first().map(|f| f.utc_ns), Some(8));
+}
+
+#[test]
+fn batched_processing_matches_per_frame_processing() -> anyhow::Result<()> {
+    let run = |max_batch: usize| -> anyhow::Result<Vec<PcmFrame>> {
+        let input = AudioRingBuffer::new(64);
+        let output = AudioRingBuffer::new(64);
+        input.push_batch((1..=20).map(frame));
+        let mut gain = Gain::new("gain", 2.0);
+        process_batched(&mut gain, &input, &output, max_batch)?;
+        Ok(output.pop_batch(64))
+    };
+    let per_frame = run(1)?;
+    let batched = run(7)?;
+    assert_eq!(per_frame.len(), 20);
+    assert_eq!(batched.len(), 20);
+    for (a, b) in per_frame.iter().zip(&batched) {
+        assert_eq!(a.utc_ns, b.utc_ns);
+        assert_eq!(a.samples, b.samples);
+    }
+    assert_eq!(batched[2].samples, vec![60; 4]);
+
+    // Ohne Batch-Unterstützung bleibt es bei einem `process`-Aufruf
+    let input = AudioRingBuffer::new(64);
+    let output = AudioRingBuffer::new(64);
+    input.push_batch((1..=20).map(frame));
+    let mut counting = Counting { calls: 0 };
+    process_batched(&mut counting, &input, &output, 8)?;
+    assert_eq!(counting.calls, 1);
+    assert_eq!(output.len(), 20);
+    Ok(())
+}
+
+#[test]
+fn flow_batch_size_is_clamped() {
+    let mut flow = Flow::new("batch");
+    flow.set_batch_size(0);
+    assert_eq!(flow.batch_size(), 1);
+    flow.set_batch_size(100_000);
+    assert_eq!(flow.batch_size(), MAX_BATCH_SIZE);
+}
@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+
+use airlift_node::config::{Config, ConsumerConfig, FlowConfig};
+use airlift_node::core::buffer_sizing::{buffer_report, flow_buffer_sizing};
+use airlift_node::core::{AirliftNode, AudioRingBuffer, BufferSizing, Flow};
+use airlift_node::testing::mocks::MockProducer;
+use airlift_node::{PcmFrame, Samples, Slots};
+use serde_json::json;
+
+fn values(value: serde_json::Value) -> HashMap<String, serde_json::Value> {
+    HashMap::from([("buffer".to_string(), value)])
+}
+
+fn frame(samples: usize) -> PcmFrame {
+    PcmFrame {
+        utc_ns: 1,
+        samples: vec![7; samples],
+        sample_rate: 48_000,
+        channels: 2,
+        metadata: Default::default(),
+    }
+}
+
+#[test]
+fn buffer_config_accepts_slots_and_tables() -> anyhow::Result<()> {
+    assert_eq!(BufferSizing::from_config("producer", "mic", &HashMap::new())?, None);
+    assert_eq!(
+        BufferSizing::from_config("producer", "mic", &values(json!(250)))?,
+        Some(BufferSizing { slots: Slots(250), prealloc_samples: Samples(0) })
+    );
+    assert_eq!(
+        BufferSizing::from_config("flow", "main", &values(json!({ "slots": 64, "prealloc": 1920 })))?,
+        Some(BufferSizing { slots: Slots(64), prealloc_samples: Samples(1920) })
+    );
+
+    assert!(BufferSizing::from_config("producer", "mic", &values(json!(0))).is_err());
+    assert!(BufferSizing::from_config("producer", "mic", &values(json!(1_000_000))).is_err());
+    let err = BufferSizing::from_config("consumer", "rec", &values(json!("big"))).unwrap_err();
+    assert!(err.to_string().contains("consumer 'rec'"), "{}", err);
+    Ok(())
+}
+
+#[test]
+fn preallocated_ring_reuses_slot_storage() {
+    let ring = AudioRingBuffer::with_prealloc(Slots(4), Samples(1920));
+    assert_eq!(ring.capacity(), 4);
+    assert_eq!(ring.prealloc_samples(), Samples(1920));
+    assert_eq!(ring.last_frame_shape(), None);
+
+    for _ in 0..6 {
+        ring.push(frame(960));
+    }
+    // Größer als die Vorbelegung: Frame wird übernommen
+    ring.push(frame(4000));
+
+    assert_eq!(ring.len(), 4);
+    assert_eq!(ring.last_frame_shape(), Some((Samples(4000), 48_000, 2)));
+    let first = ring.pop().unwrap();
+    assert_eq!(first.samples.len(), 960);
+    assert!(first.samples.iter().all(|sample| *sample == 7));
+}
+
+#[test]
+fn flow_output_grows_to_largest_consumer() -> anyhow::Result<()> {
+    let mut config = Config::default();
+    config.consumers.insert(
+        "archive".to_string(),
+        ConsumerConfig {
+            consumer_type: "file".to_string(),
+            enabled: true,
+            path: Some("/tmp/archive.wav".to_string()),
+            url: None,
+            config: values(json!(5000)),
+        },
+    );
+    config.flows.insert(
+        "main".to_string(),
+        FlowConfig {
+            enabled: true,
+            inputs: Vec::new(),
+            processors: Vec::new(),
+            outputs: vec!["archive".to_string()],
+            config: values(json!({ "slots": 100 })),
+        },
+    );
+
+    let (internal, output) = flow_buffer_sizing(&config, "main")?;
+    assert_eq!(internal.slots, Slots(100));
+    assert_eq!(output.slots, Slots(5000));
+    Ok(())
+}
+
+#[test]
+fn report_translates_sizes_into_latency_and_memory() -> anyhow::Result<()> {
+    let mut node = AirliftNode::new();
+    node.add_producer_with_buffer(
+        Box::new(MockProducer::new("mic", Vec::new())),
+        BufferSizing { slots: Slots(50), prealloc_samples: Samples(0) },
+    )?;
+    node.add_flow(Flow::with_buffer_sizing(
+        "main",
+        BufferSizing { slots: Slots(10), prealloc_samples: Samples(0) },
+        BufferSizing { slots: Slots(20), prealloc_samples: Samples(0) },
+    ));
+    node.connect_flow_input(0, "producer:mic")?;
+
+    // 960 Samples Stereo @ 48 kHz = 10 ms pro Frame
+    node.buffer_registry().get("producer:mic").unwrap().push(frame(960));
+
+    let report = buffer_report(&node);
+    let mic = report.buffers.iter().find(|b| b.name == "producer:mic").unwrap();
+    assert!(mic.measured);
+    assert_eq!(mic.slots, Slots(50));
+    assert!((mic.worst_case_latency_ms.get() - 500.0).abs() < 1e-6);
+
+    let output = report.buffers.iter().find(|b| b.name == "flow:main:output").unwrap();
+    assert!(!output.measured);
+    assert!((output.worst_case_latency_ms.get() - 400.0).abs() < 1e-6);
+
+    // Input 500 ms + Merge 10 × 20 ms + Output 20 × 20 ms
+    assert_eq!(report.flows.len(), 1);
+    assert!((report.flows[0].worst_case_latency_ms.get() - 1100.0).abs() < 1e-6);
+    assert_eq!(
+        report.total_memory_bytes,
+        report.buffers.iter().map(|b| b.memory_bytes).sum::<u64>()
+    );
+    assert!(mic.memory_bytes >= 50 * 960 * 2);
+    Ok(())
+}
+
+#[test]
+fn unit_conversions_keep_channels_and_rates_apart() {
+    use airlift_node::{Frames, Millis};
+
+    // 1920 Samples Stereo = 960 Frames = 20 ms @ 48 kHz
+    let samples = Samples(1920);
+    assert_eq!(samples.to_frames(2), Frames(960));
+    assert_eq!(Frames(960).to_samples(2), samples);
+    assert_eq!(Frames(960).to_millis(48_000), Millis(20.0));
+    assert_eq!(Millis(20.0).to_frames(44_100), Frames(882));
+    assert_eq!(Slots(50).span(Millis(20.0)), Millis(1000.0));
+    assert_eq!(samples.bytes(), 3840);
+
+    // Serialisiert wird die nackte Zahl, `/api/status` bleibt unverändert
+    let sizing = BufferSizing { slots: Slots(64), prealloc_samples: Samples(1920) };
+    assert_eq!(serde_json::to_value(sizing).unwrap(), json!({ "slots": 64, "prealloc_samples": 1920 }));
+    assert_eq!(Slots(64).to_string(), "64 slots");
+}
@@ -0,0 +1,60 @@
+use std::time::{Duration, Instant};
+
+use airlift_node::core::processor::basic::Gain;
+use airlift_node::core::{AirliftNode, Flow};
+use airlift_node::testing::mocks::{MockConsumer, MockProducer};
+use airlift_node::PcmFrame;
+
+fn frame() -> PcmFrame {
+    PcmFrame {
+        utc_ns: 1,
+        samples: vec![100, 200, 300, 400],
+        sample_rate: 48_000,
+        channels: 2,
+        metadata: Default::default(),
+    }
+}
+
+fn run_flow(flow_bypass: bool, node_bypass: bool) -> anyhow::Result<Vec<i16>> {
+    let (consumer, received) = MockConsumer::new_with_shared("out");
+    let mut flow = Flow::new("flow");
+    flow.add_processor(Box::new(Gain::new("gain", 2.0)));
+    flow.add_consumer(Box::new(consumer));
+    flow.set_bypass(flow_bypass);
+
+    let mut node = AirliftNode::new();
+    node.set_bypass(node_bypass);
+    node.add_flow(flow);
+    node.add_producer(Box::new(MockProducer::new("src", vec![frame()])))?;
+    node.connect_flow_input(0, "producer:src")?;
+
+    assert_eq!(node.flows()[0].bypass_active(), flow_bypass || node_bypass);
+
+    node.start()?;
+    let deadline = Instant::now() + Duration::from_secs(1);
+    while Instant::now() < deadline && received.lock().unwrap().is_empty() {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    node.stop()?;
+
+    let frames = received.lock().unwrap();
+    Ok(frames.first().map(|f| f.samples.clone()).unwrap_or_default())
+}
+
+#[test]
+fn processors_run_without_bypass() -> anyhow::Result<()> {
+    assert_eq!(run_flow(false, false)?, vec![200, 400, 600, 800]);
+    Ok(())
+}
+
+#[test]
+fn flow_bypass_skips_processors() -> anyhow::Result<()> {
+    assert_eq!(run_flow(true, false)?, vec![100, 200, 300, 400]);
+    Ok(())
+}
+
+#[test]
+fn global_bypass_applies_to_all_flows() -> anyhow::Result<()> {
+    assert_eq!(run_flow(false, true)?, vec![100, 200, 300, 400]);
+    Ok(())
+}
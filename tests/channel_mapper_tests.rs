@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+
+use airlift_node::core::processor::Processor;
+use airlift_node::processors::ChannelMapper;
+use airlift_node::PcmFrame;
+
+fn frame(samples: Vec<i16>, channels: u8) -> PcmFrame {
+    PcmFrame {
+        utc_ns: 0,
+        samples,
+        sample_rate: 48_000,
+        channels,
+        metadata: Default::default(),
+    }
+}
+
+fn mapper(config: serde_json::Value) -> anyhow::Result<ChannelMapper> {
+    let config: HashMap<String, serde_json::Value> = serde_json::from_value(config)?;
+    ChannelMapper::from_config("layout", &config)
+}
+
+fn run(mapper: &mut ChannelMapper, input: PcmFrame) -> anyhow::Result<PcmFrame> {
+    let mut frames = vec![input];
+    mapper.process_batch(&mut frames)?;
+    Ok(frames.remove(0))
+}
+
+#[test]
+fn downmix_laws_duplicate_and_swap() -> anyhow::Result<()> {
+    let stereo = || frame(vec![1000, 3000, -2000, -2000], 2);
+
+    let mono = run(
+        &mut mapper(serde_json::json!({ "mode": "downmix" }))?,
+        stereo(),
+    )?;
+    assert_eq!(mono.channels, 1);
+    assert_eq!(mono.samples, [2000, -2000]);
+
+    let mono = run(
+        &mut mapper(serde_json::json!({ "mode": "downmix", "law": "-3dB" }))?,
+        stereo(),
+    )?;
+    assert_eq!(mono.samples, [2828, -2828]);
+
+    // Summe ohne Absenkung übersteuert und wird begrenzt
+    let mono = run(
+        &mut mapper(serde_json::json!({ "mode": "downmix", "law": "0dB" }))?,
+        frame(vec![30000, 30000], 2),
+    )?;
+    assert_eq!(mono.samples, [32767]);
+
+    // Mono-Frames laufen beim Downmix unverändert durch
+    let untouched = run(
+        &mut mapper(serde_json::json!({ "mode": "downmix" }))?,
+        frame(vec![5, 6], 1),
+    )?;
+    assert_eq!(untouched.samples, [5, 6]);
+
+    let duplicated = run(
+        &mut mapper(serde_json::json!({ "mode": "duplicate" }))?,
+        frame(vec![5, 6], 1),
+    )?;
+    assert_eq!(duplicated.channels, 2);
+    assert_eq!(duplicated.samples, [5, 5, 6, 6]);
+
+    let mut swap = mapper(serde_json::json!({ "mode": "swap" }))?;
+    let swapped = run(&mut swap, stereo())?;
+    assert_eq!(swapped.samples, [3000, 1000, -2000, -2000]);
+
+    swap.update_config(serde_json::json!({ "enabled": false }))?;
+    assert_eq!(run(&mut swap, stereo())?.samples, stereo().samples);
+
+    assert!(mapper(serde_json::json!({})).is_err());
+    assert!(mapper(serde_json::json!({ "mode": "downmix", "law": "-4.5dB" })).is_err());
+    Ok(())
+}
+
+#[test]
+fn matrix_maps_channels_and_counts_mismatches() -> anyhow::Result<()> {
+    // Stereo auf L, R und Mitte
+    let mut matrix = mapper(serde_json::json!({
+        "mode": "matrix",
+        "matrix": [[1, 0], [0, 1], [0.5, 0.5]],
+    }))?;
+    let out = run(&mut matrix, frame(vec![100, 300], 2))?;
+    assert_eq!(out.channels, 3);
+    assert_eq!(out.samples, [100, 300, 200]);
+
+    let mono = run(&mut matrix, frame(vec![7], 1))?;
+    assert_eq!(mono.samples, [7]);
+    assert_eq!(matrix.status().errors, 1);
+
+    assert!(mapper(serde_json::json!({ "mode": "matrix", "matrix": [[1, 0], [1]] })).is_err());
+    assert!(mapper(serde_json::json!({ "mode": "matrix" })).is_err());
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use airlift_node::audio::loudness::LoudnessMeter;
+use airlift_node::core::compare::{
+    compare_sessions, ComparePair, CompareSession, MAX_COMPARE_SESSIONS, PAIR_HEADER_LEN,
+};
+use airlift_node::core::Flow;
+use airlift_node::PcmFrame;
+
+/// 10 ms Sinus (997 Hz) bei 48 kHz, Stereo, Amplitude in dBFS
+fn sine(utc_ns: u64, index: usize, level_db: f32) -> PcmFrame {
+    let amplitude = 32767.0 * 10f32.powf(level_db / 20.0);
+    let mut samples = Vec::with_capacity(960);
+    for n in 0..480 {
+        let t = (index * 480 + n) as f32 / 48_000.0;
+        let value = (amplitude * (2.0 * std::f32::consts::PI * 997.0 * t).sin()) as i16;
+        samples.push(value);
+        samples.push(value);
+    }
+    PcmFrame {
+        utc_ns,
+        samples,
+        sample_rate: 48_000,
+        channels: 2,
+        metadata: Default::default(),
+    }
+}
+
+#[test]
+fn loudness_meter_reports_short_term_lufs() {
+    let mut meter = LoudnessMeter::new();
+    assert_eq!(meter.short_term(), None);
+
+    let mut blocks = 0;
+    for index in 0..300 {
+        blocks += meter.push(&sine(index as u64, index, -20.0));
+    }
+    assert_eq!(blocks, 30);
+    // Stereo-Sinus bei 997 Hz: LUFS ≈ dBFS
+    let lufs = meter.short_term().unwrap();
+    assert!((lufs + 20.0).abs() < 0.2, "{}", lufs);
+    assert!((meter.last_block().unwrap() + 20.0).abs() < 0.2);
+}
+
+#[test]
+fn pair_encoding_has_header_and_both_taps() {
+    let pair = ComparePair {
+        utc_ns: 42,
+        pre: PcmFrame {
+            utc_ns: 42,
+            samples: vec![1, -2],
+            sample_rate: 48_000,
+            channels: 2,
+            metadata: Default::default(),
+        },
+        post: PcmFrame {
+            utc_ns: 42,
+            samples: vec![3],
+            sample_rate: 48_000,
+            channels: 1,
+            metadata: Default::default(),
+        },
+    };
+    let bytes = pair.encode();
+    assert_eq!(bytes.len(), PAIR_HEADER_LEN + 6);
+    assert_eq!(u64::from_le_bytes(bytes[0..8].try_into().unwrap()), 42);
+    assert_eq!(u32::from_le_bytes(bytes[8..12].try_into().unwrap()), 48_000);
+    assert_eq!((bytes[12], bytes[13]), (2, 1));
+    assert_eq!(u32::from_le_bytes(bytes[16..20].try_into().unwrap()), 2);
+    assert_eq!(i16::from_le_bytes([bytes[22], bytes[23]]), -2);
+    assert_eq!(i16::from_le_bytes([bytes[24], bytes[25]]), 3);
+}
+
+// Sessions sind global begrenzt, daher alles in einem Test
+#[test]
+fn sessions_pair_by_timestamp_and_attenuate_louder_tap() -> anyhow::Result<()> {
+    let flow = Flow::new("program");
+    // Vor dem Öffnen geschriebene Frames gehören nicht zum Vergleich
+    flow.input_merge_buffer.push(sine(1, 0, -20.0));
+    flow.output_buffer.push(sine(1, 0, -10.0));
+
+    let mut session = CompareSession::open(&flow)?;
+    assert!(session.poll().is_empty());
+
+    // Post hat ein Frame ohne Gegenstück (z. B. verworfen im Merge-Buffer)
+    flow.output_buffer.push(sine(5, 0, -10.0));
+    let mut pairs = Vec::new();
+    for index in 1..=300 {
+        let utc_ns = 10 + index as u64;
+        flow.input_merge_buffer.push(sine(utc_ns, index, -20.0));
+        flow.output_buffer.push(sine(utc_ns, index, -10.0));
+        pairs.extend(session.poll());
+    }
+    assert_eq!(pairs.len(), 300);
+    assert!(pairs.iter().all(|pair| pair.pre.utc_ns == pair.post.utc_ns));
+
+    let levels = session.levels();
+    assert!(levels.aligned);
+    assert_eq!(levels.dropped, 1);
+    assert_eq!(levels.pairs, 300);
+    assert!((levels.post_lufs.unwrap() - levels.pre_lufs.unwrap() - 10.0).abs() < 0.5);
+    // Nur die lautere Nachher-Seite wird abgesenkt, schrittweise
+    assert_eq!(levels.pre_gain_db, 0.0);
+    assert!(levels.post_gain_db < -5.0 && levels.post_gain_db >= -10.5);
+    let last = pairs.last().unwrap();
+    let peak = |frame: &PcmFrame| {
+        frame
+            .samples
+            .iter()
+            .map(|s| s.unsigned_abs())
+            .max()
+            .unwrap()
+    };
+    assert!(peak(&last.post) < peak(&sine(0, 0, -10.0)) / 2 + 1);
+
+    assert_eq!(compare_sessions("program").len(), 1);
+    assert!(compare_sessions("other").is_empty());
+
+    let mut extra = Vec::new();
+    for _ in 1..MAX_COMPARE_SESSIONS {
+        extra.push(CompareSession::open(&flow)?);
+    }
+    assert!(CompareSession::open(&flow).is_err(), "session limit");
+    drop(extra);
+    drop(session);
+    assert!(compare_sessions("program").is_empty());
+    Ok(())
+}
@@ -0,0 +1,147 @@
+use std::sync::Mutex;
+
+use serde_json::json;
+
+use airlift_node::api::config::handle_config_message;
+use airlift_node::config::{Config, ConfigMode, ConfigPatch, ConfigRevisions};
+use airlift_node::core::AirliftNode;
+
+const BASE: &str = r#"
+node_name = "studio"
+
+[producers.mic]
+type = "sine"
+enabled = true
+
+[producers.backup]
+type = "sine"
+enabled = true
+
+[processors]
+
+[consumers.out]
+type = "file"
+enabled = true
+path = "/tmp/out.wav"
+
+[flows.main]
+enabled = true
+inputs = ["mic"]
+processors = []
+outputs = ["out"]
+"#;
+
+fn send(
+    config: &Mutex<Config>,
+    node: &Mutex<AirliftNode>,
+    revisions: &ConfigRevisions,
+    message: serde_json::Value,
+) -> serde_json::Value {
+    handle_config_message(&message.to_string(), config, node, revisions, false)
+}
+
+#[test]
+fn patches_list_touched_objects() {
+    let patch = ConfigPatch::from_json(
+        r#"{"node_name": "x", "producers": {"mic": {"enabled": false}}, "flows": {"main": {}}}"#,
+        ConfigMode::Strict,
+    )
+    .unwrap();
+    assert_eq!(patch.touched_objects(), ["flows.main", "node_name", "producers.mic"]);
+}
+
+#[test]
+fn stale_patches_conflict_only_on_the_same_objects() {
+    let config = Mutex::new(Config::from_toml(BASE).expect("config parses"));
+    let node = Mutex::new(AirliftNode::new());
+    let revisions = ConfigRevisions::new();
+
+    let state = send(&config, &node, &revisions, json!({"type": "config.get", "id": 1}));
+    assert_eq!(state["type"], "config.state");
+    assert_eq!(state["revision"], 0);
+
+    // Operator A schaltet das Mikro ab
+    let first = send(
+        &config,
+        &node,
+        &revisions,
+        json!({"type": "config.patch", "id": "a", "base_revision": 0,
+               "patch": {"producers": {"mic": {"enabled": false}}}}),
+    );
+    assert_eq!(first["type"], "config.applied", "{}", first);
+    assert_eq!(first["revision"], 1);
+    assert_eq!(first["objects"], json!(["producers.mic"]));
+
+    // Operator B arbeitet noch auf Revision 0: anderes Objekt geht durch
+    let other = send(
+        &config,
+        &node,
+        &revisions,
+        json!({"type": "config.patch", "id": "b1", "base_revision": 0,
+               "patch": {"producers": {"backup": {"enabled": false}}}}),
+    );
+    assert_eq!(other["type"], "config.applied", "{}", other);
+    assert_eq!(other["revision"], 2);
+
+    // ... dasselbe Objekt kollidiert und ändert nichts
+    let conflict = send(
+        &config,
+        &node,
+        &revisions,
+        json!({"type": "config.patch", "id": "b2", "base_revision": 0,
+               "patch": {"producers": {"mic": {"enabled": true}}}}),
+    );
+    assert_eq!(conflict["type"], "config.conflict");
+    assert_eq!(conflict["id"], "b2");
+    assert_eq!(conflict["revision"], 2);
+    assert_eq!(conflict["conflicts"], json!([{"object": "producers.mic", "revision": 1}]));
+    assert!(!config.lock().unwrap().producers["mic"].enabled);
+
+    // Nach erneutem Lesen klappt es
+    let retry = send(
+        &config,
+        &node,
+        &revisions,
+        json!({"type": "config.patch", "id": "b3", "base_revision": 2,
+               "patch": {"producers": {"mic": {"enabled": true}}}}),
+    );
+    assert_eq!(retry["type"], "config.applied", "{}", retry);
+    assert!(config.lock().unwrap().producers["mic"].enabled);
+}
+
+#[test]
+fn patches_need_a_known_base_revision_and_write_access() {
+    let config = Mutex::new(Config::from_toml(BASE).expect("config parses"));
+    let node = Mutex::new(AirliftNode::new());
+    let revisions = ConfigRevisions::new();
+    let patch = json!({"node_name": "other"});
+
+    let missing = send(&config, &node, &revisions, json!({"type": "config.patch", "patch": patch}));
+    assert_eq!(missing["type"], "error");
+
+    let future = send(
+        &config,
+        &node,
+        &revisions,
+        json!({"type": "config.patch", "base_revision": 5, "patch": patch}),
+    );
+    assert_eq!(future["type"], "error");
+
+    let message = json!({"type": "config.patch", "base_revision": 0, "patch": patch});
+    let read_only =
+        handle_config_message(&message.to_string(), &config, &node, &revisions, true);
+    assert_eq!(read_only["type"], "error");
+    assert_eq!(config.lock().unwrap().node_name, "studio");
+    assert_eq!(revisions.current(), 0);
+}
+
+#[test]
+fn replacing_the_config_invalidates_older_revisions() {
+    let revisions = ConfigRevisions::new();
+    let objects = vec!["flows.main".to_string()];
+    assert_eq!(revisions.commit(&objects), 1);
+    assert_eq!(revisions.replace_all(), 2);
+    assert_eq!(revisions.conflicts(1, &["producers.mic".to_string()]).len(), 1);
+    assert!(revisions.conflicts(2, &objects).is_empty());
+    assert_eq!(revisions.snapshot(), (2, Default::default()));
+}
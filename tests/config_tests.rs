@@ -0,0 +1,172 @@
+use airlift_node::config::{Config, ConfigMode, ConfigPatch};
+
+const BASE: &str = r#"
+node_name = "test"
+
+[producers.sine]
+type = "sine"
+enabled = true
+
+[processors]
+
+[consumers.out]
+type = "file"
+enabled = true
+path = "/tmp/out.wav"
+
+[flows.main]
+enabled = true
+inputs = ["sine"]
+processors = []
+outputs = ["out"]
+"#;
+
+#[test]
+fn strict_mode_rejects_unknown_fields() {
+    let toml = format!("config_mode = \"strict\"\n{}\nbitrte = 1\n", BASE);
+    let err = Config::from_toml(&toml).unwrap_err().to_string();
+    assert!(err.contains("flows.main.bitrte"), "{}", err);
+}
+
+#[test]
+fn compat_mode_ignores_unknown_fields() {
+    let toml = format!("{}\nbitrte = 1\n", BASE);
+    let config = Config::from_toml(&toml).expect("compat config parses");
+    assert_eq!(config.config_mode, ConfigMode::Compat);
+}
+
+#[test]
+fn known_flow_options_are_not_flagged() {
+    let toml = format!(
+        "config_mode = \"strict\"\n{}\n[flows.main.config]\non_air_gpio = \"/tmp/gpio\"\n",
+        BASE
+    );
+    assert!(Config::from_toml(&toml).is_ok());
+}
+
+#[test]
+fn empty_optional_sections_are_not_flagged() {
+    let toml = format!(
+        "config_mode = \"strict\"\n{}\n[monitoring]\nhttp_port = 8087\nbinds = []\n[http_client]\nno_proxy = []\n[failover]\n[schedules]\n",
+        BASE
+    );
+    Config::from_toml(&toml).expect("empty lists and tables are known fields");
+}
+
+#[test]
+fn unknown_component_options_are_flagged() {
+    let toml = format!(
+        "config_mode = \"strict\"\n{}\n[consumers.out.config]\nformat = \"wav\"\nrotate_evry = \"1h\"\n",
+        BASE
+    );
+    let err = Config::from_toml(&toml).unwrap_err().to_string();
+    assert!(err.contains("consumers.out.config.rotate_evry"), "{}", err);
+    assert!(!err.contains("config.format"), "{}", err);
+
+    let toml = format!("{}\n[producers.sine.config]\nfrequncy = 1000\n", BASE);
+    let mut config = Config::from_toml(&toml).expect("compat only warns");
+    config.config_mode = ConfigMode::Strict;
+    let patch = ConfigPatch::from_json(
+        r#"{"consumers": {"out": {"config": {"bitrte": "128k"}}}}"#,
+        ConfigMode::Strict,
+    )
+    .expect("config maps are free-form in the patch itself");
+    let err = config.apply_patch(&patch).unwrap_err().to_string();
+    assert!(err.contains("consumers.out.config.bitrte"), "{}", err);
+    assert!(!err.contains("frequncy"), "existing keys are not reported again: {}", err);
+}
+
+#[test]
+fn module_keys_are_accepted_in_strict_mode() {
+    let toml = format!(
+        "config_mode = \"strict\"\n{}\n[consumers.rtmp]\ntype = \"rtmp_out\"\nenabled = true\n[consumers.rtmp.config]\nurl = \"rtmp://a.rtmp.youtube.com/live2\"\nstream_key = \"abcd\"\n",
+        BASE
+    );
+    Config::from_toml(&toml).expect("rtmp_out reads config.url");
+
+    // Generisch geprüfte Schlüssel erreichen die Bereichsprüfung
+    let toml = format!(
+        "config_mode = \"strict\"\n{}\n[consumers.out.config]\ncodec = \"opusogg\"\nbitrate = \"600k\"\n",
+        BASE
+    );
+    let config = Config::from_toml(&toml).expect("bitrate is known on every module");
+    let err = airlift_node::app::configurator::validate_config_capabilities(&config)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("config.bitrate"), "{}", err);
+}
+
+#[test]
+fn new_configs_default_to_strict() {
+    assert_eq!(Config::default().config_mode, ConfigMode::Strict);
+}
+
+#[test]
+fn strict_patch_rejects_unknown_fields() {
+    let err = ConfigPatch::from_json(r#"{"node_nme": "x"}"#, ConfigMode::Strict).unwrap_err();
+    assert!(err.to_string().contains("node_nme"));
+    assert!(ConfigPatch::from_json(r#"{"node_nme": "x"}"#, ConfigMode::Compat).is_ok());
+}
+
+#[test]
+fn out_of_range_bitrate_is_rejected_with_context() {
+    let toml = format!(
+        "{}\n[consumers.out.config]\ncodec = \"opusogg\"\nbitrate = \"600k\"\n",
+        BASE
+    );
+    let config = Config::from_toml(&toml).expect("config parses");
+    let err = airlift_node::app::configurator::validate_config_capabilities(&config)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("consumer 'out'"), "{}", err);
+    assert!(err.contains("config.bitrate"), "{}", err);
+}
+
+#[test]
+fn unit_values_are_parsed() {
+    let toml = format!(
+        "{}\n[consumers.out.config]\ncodec = \"opusogg\"\nbitrate = \"128k\"\n",
+        BASE
+    );
+    let config = Config::from_toml(&toml).expect("config parses");
+    assert!(airlift_node::app::configurator::validate_config_capabilities(&config).is_ok());
+}
+
+#[test]
+fn oversized_durations_are_config_errors() {
+    use airlift_node::config::units::parse_duration;
+    use airlift_node::config::ConfigValues;
+    use std::collections::HashMap;
+
+    assert!(parse_duration("99999999999999999999999d").is_err());
+    let values = HashMap::from([
+        ("reconnect".to_string(), serde_json::json!(1e300)),
+        ("max_reconnect".to_string(), serde_json::json!("99999999999999999999999d")),
+    ]);
+    let values = ConfigValues::new("consumer", "out", &values);
+    let err = values.duration("reconnect").unwrap_err().to_string();
+    assert!(err.contains("consumer 'out': config.reconnect"), "{}", err);
+    let err = values.duration("max_reconnect").unwrap_err().to_string();
+    assert!(err.contains("config.max_reconnect"), "{}", err);
+}
+
+#[test]
+fn monitoring_binds_default_to_all_interfaces() {
+    let mut config = Config::from_toml(BASE).expect("config parses");
+    let binds = config.monitoring.effective_binds();
+    assert_eq!(binds.len(), 1);
+    assert_eq!(binds[0].address, "0.0.0.0:8087");
+
+    let toml = format!(
+        "{}\n[monitoring]\nhttp_port = 8087\n[[monitoring.binds]]\naddress = \"127.0.0.1:8087\"\n[[monitoring.binds]]\naddress = \"10.0.0.5:8087\"\ntoken = \"secret\"\nread_only = true\n",
+        BASE
+    );
+    config = Config::from_toml(&toml).expect("binds parse");
+    config.validate().expect("binds are valid");
+    let binds = config.monitoring.effective_binds();
+    assert_eq!(binds.len(), 2);
+    assert!(binds[1].read_only);
+
+    config.monitoring.binds[1].address = "10.0.0.5".to_string();
+    assert!(config.validate().is_err());
+}
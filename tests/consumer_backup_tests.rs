@@ -0,0 +1,246 @@
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use airlift_node::config::{Config, ConsumerConfig};
+use airlift_node::consumers::backup::{BackupConfig, BackupConsumer, BackupRole};
+use airlift_node::core::{
+    AudioRingBuffer, ConnectionPhase, ConnectionState, Consumer, ConsumerStatus, Event, EventBus,
+    EventEmitter, EventHandler, EventType,
+};
+use serde_json::json;
+
+fn consumer_config(config: serde_json::Value) -> ConsumerConfig {
+    ConsumerConfig {
+        consumer_type: "icecast".to_string(),
+        enabled: true,
+        path: None,
+        url: None,
+        config: serde_json::from_value(config).unwrap(),
+    }
+}
+
+/// Netzwerk-Ziel mit Reconnect-Zählung; `up` steuert, ob der Server erreichbar ist.
+struct Server {
+    name: &'static str,
+    up: Arc<AtomicBool>,
+    running: AtomicBool,
+    failed_attempts: AtomicU32,
+}
+
+impl Server {
+    fn new(name: &'static str, up: Arc<AtomicBool>) -> Self {
+        Self {
+            name,
+            up,
+            running: AtomicBool::new(false),
+            failed_attempts: AtomicU32::new(0),
+        }
+    }
+}
+
+impl Consumer for Server {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn start(&mut self) -> anyhow::Result<()> {
+        self.running.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn stop(&mut self) -> anyhow::Result<()> {
+        self.running.store(false, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn status(&self) -> ConsumerStatus {
+        let running = self.running.load(Ordering::SeqCst);
+        let connected = running && self.up.load(Ordering::SeqCst);
+        // Jede Abfrage ohne Server zählt als weiterer Fehlversuch
+        let failed_attempts = if connected {
+            self.failed_attempts.swap(0, Ordering::SeqCst)
+        } else {
+            self.failed_attempts.fetch_add(1, Ordering::SeqCst) + 1
+        };
+        ConsumerStatus {
+            running,
+            connected,
+            frames_processed: 0,
+            bytes_written: 0,
+            errors: 0,
+            connection: Some(ConnectionState {
+                phase: if connected {
+                    ConnectionPhase::Connected
+                } else {
+                    ConnectionPhase::Backoff
+                },
+                endpoint: format!("{}:8000/live", self.name),
+                failed_attempts: if connected { 0 } else { failed_attempts },
+                retry_in_ms: None,
+                last_error: None,
+            }),
+        }
+    }
+
+    fn attach_input_buffer(&mut self, _buffer: Arc<AudioRingBuffer>) {}
+}
+
+struct Collector {
+    events: Mutex<Vec<Event>>,
+}
+
+impl EventHandler for Collector {
+    fn handle_event(&self, event: &Event) -> anyhow::Result<()> {
+        self.events.lock().unwrap().push(event.clone());
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "collector"
+    }
+
+    fn event_type_filter(&self) -> Option<Vec<EventType>> {
+        Some(vec![EventType::ConsumerFailover])
+    }
+}
+
+fn wait_until(condition: impl Fn() -> bool) {
+    let deadline = Instant::now() + Duration::from_secs(3);
+    while Instant::now() < deadline && !condition() {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
+
+#[test]
+fn backup_config_is_validated() -> anyhow::Result<()> {
+    let config = BackupConfig::from_config(
+        "ice_a",
+        &consumer_config(json!({ "backup": "ice_b", "failback_after": "1m" })),
+    )?;
+    assert_eq!(config.backup, "ice_b");
+    assert_eq!(config.failover_after, 3);
+    assert_eq!(config.failback_after, Duration::from_secs(60));
+
+    for config in [
+        json!({}),
+        json!({ "backup": "" }),
+        json!({ "backup": "ice_a" }),
+        json!({ "backup": "ice_b", "failover_after": 0 }),
+        json!({ "backup": "ice_b", "failover_after": 1.5 }),
+        json!({ "backup": "ice_b", "failback_after": "2h" }),
+    ] {
+        assert!(
+            BackupConfig::from_config("ice_a", &consumer_config(config.clone())).is_err(),
+            "{}",
+            config
+        );
+    }
+    Ok(())
+}
+
+const CONFIG: &str = r#"
+node_name = "studio"
+
+[producers.mic]
+type = "sine"
+enabled = true
+
+[processors]
+
+[consumers.ice_a]
+type = "file"
+enabled = true
+path = "/tmp/a.wav"
+config = { backup = "ice_b" }
+
+[consumers.ice_b]
+type = "file"
+enabled = true
+path = "/tmp/b.wav"
+
+[flows.main]
+enabled = true
+inputs = ["mic"]
+processors = []
+outputs = ["ice_a"]
+"#;
+
+#[test]
+fn config_validation_keeps_backups_private() -> anyhow::Result<()> {
+    Config::from_toml(CONFIG)?.validate()?;
+
+    for broken in [
+        CONFIG.replace(r#"backup = "ice_b""#, r#"backup = "ice_c""#),
+        CONFIG.replace(r#"outputs = ["ice_a"]"#, r#"outputs = ["ice_a", "ice_b"]"#),
+        CONFIG.replace("path = \"/tmp/b.wav\"", "path = \"/tmp/b.wav\"\nconfig = { backup = \"ice_a\" }"),
+    ] {
+        assert!(Config::from_toml(&broken)?.validate().is_err(), "{}", broken);
+    }
+    Ok(())
+}
+
+#[test]
+fn backup_takes_over_and_fails_back_with_events() -> anyhow::Result<()> {
+    let mut bus = EventBus::new("test");
+    let collector = Arc::new(Collector {
+        events: Mutex::new(Vec::new()),
+    });
+    bus.register_handler(collector.clone())?;
+    bus.start()?;
+    let bus = Arc::new(Mutex::new(bus));
+
+    let primary_up = Arc::new(AtomicBool::new(true));
+    let mut consumer = BackupConsumer::with_config(
+        Box::new(Server::new("ice_a", primary_up.clone())),
+        Box::new(Server::new("ice_b", Arc::new(AtomicBool::new(true)))),
+        BackupConfig {
+            backup: "ice_b".to_string(),
+            failover_after: 3,
+            failback_after: Duration::from_millis(300),
+        },
+    );
+    consumer.attach_event_emitter(EventEmitter::new(bus.clone(), "consumer", "ice_a"));
+    consumer.attach_input_buffer(Arc::new(AudioRingBuffer::new(16)));
+    consumer.start()?;
+
+    std::thread::sleep(Duration::from_millis(300));
+    assert_eq!(consumer.active(), BackupRole::Primary);
+    assert!(!consumer.targets()[1].running, "backup idles while the primary is up");
+
+    // Primärer Server fällt aus: nach drei Fehlversuchen sendet das Ersatzziel
+    primary_up.store(false, Ordering::SeqCst);
+    wait_until(|| consumer.active() == BackupRole::Backup);
+    assert_eq!(consumer.active(), BackupRole::Backup);
+    let targets = consumer.targets();
+    assert_eq!(targets[1].active, Some(true));
+    assert!(targets[1].running && consumer.status().connected);
+
+    // Zurück, sobald der primäre Server lange genug stabil ist
+    primary_up.store(true, Ordering::SeqCst);
+    wait_until(|| consumer.active() == BackupRole::Primary);
+    assert_eq!(consumer.active(), BackupRole::Primary);
+    assert!(!consumer.targets()[1].running);
+
+    wait_until(|| collector.events.lock().unwrap().len() >= 2);
+    let events = collector.events.lock().unwrap().clone();
+    let route = |event: &Event| {
+        (
+            event.payload["from"].as_str().unwrap_or_default().to_string(),
+            event.payload["to"].as_str().unwrap_or_default().to_string(),
+            event.payload["reason"].as_str().unwrap_or_default().to_string(),
+        )
+    };
+    assert_eq!(
+        events.iter().map(route).collect::<Vec<_>>(),
+        [
+            ("ice_a".to_string(), "ice_b".to_string(), "failed".to_string()),
+            ("ice_b".to_string(), "ice_a".to_string(), "recovered".to_string()),
+        ]
+    );
+    assert!(events[0].payload["failures"].as_u64().unwrap_or(0) >= 3);
+
+    consumer.stop()?;
+    assert!(!consumer.targets().iter().any(|target| target.running));
+    Ok(())
+}
@@ -0,0 +1,189 @@
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use airlift_node::config::{Config, ConsumerConfig};
+use airlift_node::consumers::mirror::{MirrorConfig, MirrorConsumer};
+use airlift_node::core::{AudioRingBuffer, Consumer, ConsumerStatus};
+use serde_json::json;
+
+fn consumer_config(config: serde_json::Value) -> ConsumerConfig {
+    ConsumerConfig {
+        consumer_type: "icecast".to_string(),
+        enabled: true,
+        path: None,
+        url: None,
+        config: serde_json::from_value(config).unwrap(),
+    }
+}
+
+/// Ziel, dessen Start `failing_starts`-mal scheitert
+struct Target {
+    name: &'static str,
+    running: AtomicBool,
+    failing_starts: AtomicU32,
+}
+
+impl Target {
+    fn new(name: &'static str, failing_starts: u32) -> Self {
+        Self {
+            name,
+            running: AtomicBool::new(false),
+            failing_starts: AtomicU32::new(failing_starts),
+        }
+    }
+}
+
+impl Consumer for Target {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn start(&mut self) -> anyhow::Result<()> {
+        if self.failing_starts.load(Ordering::SeqCst) > 0 {
+            self.failing_starts.fetch_sub(1, Ordering::SeqCst);
+            anyhow::bail!("{} unreachable", self.name);
+        }
+        self.running.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn stop(&mut self) -> anyhow::Result<()> {
+        self.running.store(false, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn status(&self) -> ConsumerStatus {
+        let running = self.running.load(Ordering::SeqCst);
+        ConsumerStatus {
+            running,
+            connected: running,
+            frames_processed: 0,
+            bytes_written: 0,
+            errors: 0,
+            connection: None,
+        }
+    }
+
+    fn attach_input_buffer(&mut self, _buffer: Arc<AudioRingBuffer>) {}
+}
+
+#[test]
+fn mirror_config_is_validated() -> anyhow::Result<()> {
+    let config =
+        MirrorConfig::from_config("ice", &consumer_config(json!({ "mirror": "staging" })))?;
+    assert_eq!(config.mirror, "staging");
+
+    for config in [
+        json!({}),
+        json!({ "mirror": "" }),
+        json!({ "mirror": "ice" }),
+    ] {
+        assert!(
+            MirrorConfig::from_config("ice", &consumer_config(config.clone())).is_err(),
+            "{}",
+            config
+        );
+    }
+    Ok(())
+}
+
+const CONFIG: &str = r#"
+node_name = "studio"
+
+[producers.mic]
+type = "sine"
+enabled = true
+
+[processors]
+
+[consumers.ice]
+type = "file"
+enabled = true
+path = "/tmp/live.wav"
+config = { mirror = "staging" }
+
+[consumers.staging]
+type = "file"
+enabled = true
+path = "/tmp/staging.wav"
+
+[flows.main]
+enabled = true
+inputs = ["mic"]
+processors = []
+outputs = ["ice"]
+"#;
+
+#[test]
+fn config_validation_keeps_mirrors_private() -> anyhow::Result<()> {
+    Config::from_toml(CONFIG)?.validate()?;
+
+    for broken in [
+        CONFIG.replace(r#"mirror = "staging""#, r#"mirror = "nope""#),
+        CONFIG.replace(r#"outputs = ["ice"]"#, r#"outputs = ["ice", "staging"]"#),
+        CONFIG.replace(
+            "path = \"/tmp/staging.wav\"",
+            "path = \"/tmp/staging.wav\"\nconfig = { mirror = \"ice\" }",
+        ),
+        CONFIG.replace(
+            r#"config = { mirror = "staging" }"#,
+            r#"config = { mirror = "staging", backup = "staging" }"#,
+        ),
+    ] {
+        assert!(
+            Config::from_toml(&broken)?.validate().is_err(),
+            "{}",
+            broken
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn failing_mirror_is_retried_without_touching_the_primary() -> anyhow::Result<()> {
+    let mut consumer = MirrorConsumer::with_config(
+        Box::new(Target::new("ice", 0)),
+        Box::new(Target::new("staging", 1)),
+        MirrorConfig {
+            mirror: "staging".to_string(),
+        },
+    );
+    consumer.attach_input_buffer(Arc::new(AudioRingBuffer::new(16)));
+    consumer.start()?;
+
+    let status = consumer.status();
+    assert!(status.running && status.connected);
+    assert_eq!(
+        status.errors, 0,
+        "mirror failures stay out of the primary status"
+    );
+
+    let targets = consumer.targets();
+    assert_eq!(targets.len(), 2);
+    assert_eq!(
+        (targets[0].name.as_str(), targets[0].mirror),
+        ("ice", Some(false))
+    );
+    assert_eq!(
+        (targets[1].name.as_str(), targets[1].mirror),
+        ("staging", Some(true))
+    );
+    assert!(!targets[1].running);
+    assert_eq!(targets[1].errors, 1);
+    assert!(targets[1].retry_in_ms.is_some());
+
+    // Nach dem Backoff (1 s) startet der Spiegel neu
+    let deadline = Instant::now() + Duration::from_secs(3);
+    while !consumer.targets()[1].running && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(20));
+    }
+    let mirror = &consumer.targets()[1];
+    assert!(mirror.running);
+    assert_eq!(mirror.restarts, 1);
+
+    consumer.stop()?;
+    assert!(!consumer.status().running);
+    assert!(!consumer.targets().iter().any(|target| target.running));
+    Ok(())
+}
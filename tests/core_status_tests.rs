@@ -4,10 +4,12 @@ use airlift_node::core::ProducerStatus;
 fn test_producer_status() {
     let status = ProducerStatus {
         running: true,
+        paused: false,
         connected: true,
         samples_processed: 1000,
         errors: 0,
         buffer_stats: None,
+        decoder: None,
     };
 
     assert!(status.running);
@@ -0,0 +1,49 @@
+#![cfg(feature = "cpal")]
+
+use airlift_node::config::ProducerConfig;
+use airlift_node::producers::cpal::{pick_input_mode, CpalSampleFormat, CpalSettings, InputMode};
+
+fn mode(channels: u16, min_rate: u32, max_rate: u32, format: CpalSampleFormat) -> InputMode {
+    InputMode {
+        channels,
+        min_rate,
+        max_rate,
+        format,
+    }
+}
+
+#[test]
+fn settings_follow_the_alsa_input_shape() {
+    let mut cfg = ProducerConfig {
+        producer_type: "cpal".to_string(),
+        enabled: true,
+        device: Some("default".to_string()),
+        ..ProducerConfig::default()
+    };
+    let settings = CpalSettings::from_producer_config("mic", &cfg).unwrap();
+    assert_eq!(settings.device, None);
+    assert_eq!((settings.sample_rate, settings.channels), (44100, 2));
+
+    cfg.device = Some("USB Audio CODEC ".to_string());
+    cfg.sample_rate = Some(48000);
+    cfg.channels = Some(1);
+    let settings = CpalSettings::from_producer_config("mic", &cfg).unwrap();
+    assert_eq!(settings.device.as_deref(), Some("USB Audio CODEC"));
+    assert_eq!((settings.sample_rate, settings.channels), (48000, 1));
+
+    cfg.channels = Some(0);
+    assert!(CpalSettings::from_producer_config("mic", &cfg).is_err());
+}
+
+#[test]
+fn input_mode_prefers_s16_within_range() {
+    let modes = [
+        mode(2, 44100, 48000, CpalSampleFormat::F32),
+        mode(2, 8000, 96000, CpalSampleFormat::I16),
+        mode(1, 8000, 96000, CpalSampleFormat::I16),
+    ];
+    assert_eq!(pick_input_mode(&modes, 2, 48000), Some(modes[1]));
+    assert_eq!(pick_input_mode(&modes[..1], 2, 48000), Some(modes[0]));
+    assert_eq!(pick_input_mode(&modes, 2, 192000), None);
+    assert_eq!(pick_input_mode(&modes, 4, 48000), None);
+}
@@ -0,0 +1,79 @@
+use std::time::{Duration, Instant};
+
+use airlift_node::core::debug_capture::{debug_capture, DebugCaptureRequest};
+use airlift_node::core::logging::verbose_logging;
+use airlift_node::core::{AirliftNode, Flow};
+use airlift_node::testing::mocks::{MockConsumer, MockProducer};
+use airlift_node::PcmFrame;
+
+fn frame(utc_ns: u64) -> PcmFrame {
+    PcmFrame {
+        utc_ns,
+        samples: vec![1000, -1000],
+        sample_rate: 48_000,
+        channels: 1,
+        metadata: Default::default(),
+    }
+}
+
+#[test]
+fn capture_traces_frames_and_reverts_after_duration() -> anyhow::Result<()> {
+    let (consumer, _) = MockConsumer::new_with_shared("out");
+    let mut flow = Flow::new("program");
+    flow.add_consumer(Box::new(consumer));
+    let mut node = AirliftNode::new();
+    node.add_flow(flow);
+    node.add_producer(Box::new(MockProducer::new("src", Vec::new())))?;
+    node.connect_flow_input(0, "producer:src")?;
+    node.set_readiness_timeout(Duration::ZERO);
+    node.start()?;
+
+    let path = std::env::temp_dir().join(format!("airlift-capture-{}.jsonl", std::process::id()));
+    let missing = DebugCaptureRequest {
+        flow: "nope".to_string(),
+        duration: Duration::from_millis(300),
+        path: Some(path.clone()),
+        verbose: true,
+    };
+    assert!(debug_capture().start(&node, missing).is_err());
+
+    let request = DebugCaptureRequest {
+        flow: "program".to_string(),
+        duration: Duration::from_millis(300),
+        path: Some(path.clone()),
+        verbose: true,
+    };
+    let status = debug_capture().start(&node, request.clone())?;
+    assert!(status.active);
+    assert!(verbose_logging());
+    assert!(debug_capture().start(&node, request).is_err(), "only one capture at a time");
+
+    // Frames erst nach Capture-Start, damit sie im Trace landen
+    node.flows()[0].input_buffers[0].push(frame(1));
+    node.flows()[0].input_buffers[0].push(frame(2));
+
+    let deadline = Instant::now() + Duration::from_secs(3);
+    while Instant::now() < deadline && debug_capture().status().is_some_and(|s| s.active) {
+        std::thread::sleep(Duration::from_millis(20));
+    }
+    node.stop()?;
+
+    let status = debug_capture().status().expect("status kept");
+    assert!(!status.active);
+    assert!(!verbose_logging(), "verbosity reverted");
+    assert!(status.stats_samples >= 1);
+
+    let content = std::fs::read_to_string(&path)?;
+    std::fs::remove_file(&path)?;
+    let lines: Vec<serde_json::Value> = content
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    let inputs = lines
+        .iter()
+        .filter(|line| line["frame"]["kind"] == "input")
+        .count();
+    assert_eq!(inputs, 2);
+    assert!(lines.iter().any(|line| line["ring_stats"]["buffers"].is_array()));
+    Ok(())
+}
@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use airlift_node::codecs::PCM_I16_SAMPLES;
+use airlift_node::config::ConsumerConfig;
+use airlift_node::consumers::debug_dump::{hex_dump, DebugDumpConfig, FrameStats};
+use airlift_node::consumers::DebugDumpConsumer;
+use airlift_node::core::{AudioRingBuffer, Consumer, Flow};
+use airlift_node::{Frames, PcmFrame, Samples};
+
+fn config(values: serde_json::Value) -> ConsumerConfig {
+    ConsumerConfig {
+        consumer_type: "debug_dump".to_string(),
+        enabled: true,
+        path: None,
+        url: None,
+        config: serde_json::from_value::<HashMap<String, serde_json::Value>>(values).unwrap(),
+    }
+}
+
+/// 960 Samples Stereo @ 48 kHz = 10 ms
+fn frame(utc_ms: u64, value: i16) -> PcmFrame {
+    sized_frame(utc_ms, value, 960)
+}
+
+fn sized_frame(utc_ms: u64, value: i16, samples: usize) -> PcmFrame {
+    PcmFrame {
+        utc_ns: utc_ms * 1_000_000,
+        samples: vec![value; samples],
+        sample_rate: 48_000,
+        channels: 2,
+        metadata: Default::default(),
+    }
+}
+
+#[test]
+fn config_updates_only_given_keys() -> anyhow::Result<()> {
+    let parsed =
+        DebugDumpConfig::from_config("dump", &config(serde_json::json!({ "hex_bytes": 16 })))?;
+    assert_eq!(
+        parsed,
+        DebugDumpConfig {
+            hex_bytes: 16,
+            ..Default::default()
+        }
+    );
+
+    let updated = parsed.updated(
+        "dump",
+        &serde_json::json!({ "enabled": false, "every": 50 }),
+    )?;
+    assert!(!updated.enabled);
+    assert_eq!((updated.every, updated.hex_bytes), (50, 16));
+
+    for bad in [
+        serde_json::json!({ "every": 0 }),
+        serde_json::json!({ "hex_bytes": 100_000 }),
+        serde_json::json!({ "codec": "nope" }),
+        serde_json::json!({ "enabled": "yes" }),
+    ] {
+        assert!(parsed.updated("dump", &bad).is_err(), "{}", bad);
+    }
+    Ok(())
+}
+
+#[test]
+fn frame_stats_measure_levels_and_timestamp_jumps() {
+    let first = FrameStats::measure(1, &frame(0, i16::MAX), None);
+    assert_eq!((first.samples, first.frames), (Samples(960), Frames(480)));
+    assert_eq!(first.duration_ns, 10_000_000);
+    assert!(first.peak_dbfs.abs() < 0.01);
+    assert_eq!(first.clipped, 960);
+    assert_eq!(first.delta_ns, None);
+
+    let next = FrameStats::measure(2, &frame(10, 0), Some(&first));
+    assert_eq!(next.jitter_ns(&first), Some(0));
+    assert_eq!(next.peak_dbfs, -120.0);
+    assert!(!next.line(Some(&first)).contains("GAP"));
+
+    let late = FrameStats::measure(3, &frame(35, 1000), Some(&next));
+    assert_eq!(late.jitter_ns(&next), Some(15_000_000));
+    assert!(
+        late.line(Some(&next)).contains("GAP +15.000ms"),
+        "{}",
+        late.line(Some(&next))
+    );
+
+    assert_eq!(hex_dump(&[0x00, 0x1f, 0xa3], 8), "00 1f a3");
+    assert_eq!(hex_dump(&[1, 2, 3, 4], 2), "01 02 … (+2 bytes)");
+}
+
+#[test]
+fn consumer_counts_gaps_and_switches_at_runtime() -> anyhow::Result<()> {
+    let buffer = Arc::new(AudioRingBuffer::new(64));
+    let mut consumer =
+        DebugDumpConsumer::new("dump", &config(serde_json::json!({ "hex_bytes": 8 })))?;
+    consumer.attach_input_buffer(buffer.clone());
+    consumer.start()?;
+
+    // Frames in der Größe des PCM-Codecs (100 ms), der letzte 50 ms zu spät
+    for (utc_ms, value) in [(0, 100), (100, 200), (250, 300)] {
+        buffer.push(sized_frame(utc_ms, value, PCM_I16_SAMPLES));
+    }
+    let deadline = Instant::now() + Duration::from_secs(2);
+    while consumer.status().frames_processed < 3 && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    let status = consumer.status();
+    assert_eq!(status.frames_processed, 3);
+    assert_eq!(
+        status.bytes_written,
+        3 * PCM_I16_SAMPLES as u64 * 2,
+        "pcm hex dump encodes every frame"
+    );
+    assert_eq!(consumer.gaps(), 1);
+    assert_eq!(consumer.last_stats().unwrap().utc_ns, 250_000_000);
+
+    consumer.update_config(serde_json::json!({ "enabled": false }))?;
+    assert!(!consumer.config().enabled);
+    assert!(consumer
+        .update_config(serde_json::json!({ "every": 0 }))
+        .is_err());
+    assert!(
+        !consumer.config().enabled,
+        "invalid updates leave the settings alone"
+    );
+    consumer.stop()?;
+    assert!(!consumer.status().running);
+
+    // Über den Flow, wie `consumer.configure`
+    let mut flow = Flow::new("main");
+    flow.add_consumer(Box::new(consumer));
+    flow.update_consumer_config("dump", serde_json::json!({ "enabled": true }))?;
+    assert!(flow
+        .update_consumer_config("missing", serde_json::json!({}))
+        .is_err());
+    Ok(())
+}
@@ -0,0 +1,226 @@
+use std::collections::HashMap;
+use std::net::TcpStream;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use airlift_node::codecs::PCM_I16_SAMPLES;
+use airlift_node::config::{Config, ConsumerConfig};
+use airlift_node::consumers::zmq::{greeting, ready, ZmqPubConsumer};
+use airlift_node::core::{AirliftNode, AudioRingBuffer, Consumer, ConsumerStatus, Flow};
+use airlift_node::PcmFrame;
+
+/// Ausgang mit steuerbarer Zuhörerzahl
+struct Listened {
+    name: &'static str,
+    listeners: Arc<AtomicUsize>,
+    running: Arc<AtomicBool>,
+    drains: bool,
+}
+
+impl Listened {
+    fn new(
+        name: &'static str,
+        listeners: usize,
+        drains: bool,
+    ) -> (Self, Arc<AtomicUsize>, Arc<AtomicBool>) {
+        let count = Arc::new(AtomicUsize::new(listeners));
+        let running = Arc::new(AtomicBool::new(false));
+        (
+            Self {
+                name,
+                listeners: count.clone(),
+                running: running.clone(),
+                drains,
+            },
+            count,
+            running,
+        )
+    }
+}
+
+impl Consumer for Listened {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn start(&mut self) -> anyhow::Result<()> {
+        self.running.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn stop(&mut self) -> anyhow::Result<()> {
+        self.running.store(false, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn status(&self) -> ConsumerStatus {
+        ConsumerStatus {
+            running: self.running.load(Ordering::SeqCst),
+            connected: self.listeners() > 0,
+            frames_processed: 0,
+            bytes_written: 0,
+            errors: 0,
+            connection: None,
+        }
+    }
+
+    fn attach_input_buffer(&mut self, _buffer: Arc<AudioRingBuffer>) {}
+
+    fn listeners(&self) -> usize {
+        self.listeners.load(Ordering::SeqCst)
+    }
+
+    fn begin_drain(&mut self) -> bool {
+        self.drains
+    }
+}
+
+fn frame(utc_ns: u64) -> PcmFrame {
+    PcmFrame {
+        utc_ns,
+        samples: vec![3; PCM_I16_SAMPLES],
+        sample_rate: 48_000,
+        channels: 2,
+        metadata: Default::default(),
+    }
+}
+
+fn wait_until(condition: impl Fn() -> bool) {
+    let deadline = Instant::now() + Duration::from_secs(3);
+    while Instant::now() < deadline && !condition() {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
+
+const CONFIG: &str = r#"
+node_name = "studio"
+
+[producers]
+
+[processors]
+
+[consumers]
+
+[flows]
+
+[reload]
+drain_grace = "2m"
+"#;
+
+#[test]
+fn reload_setting_is_validated() -> anyhow::Result<()> {
+    let config = Config::from_toml(CONFIG)?;
+    config.validate()?;
+    assert_eq!(
+        config.reload.unwrap().drain_grace()?,
+        Duration::from_secs(120)
+    );
+    let broken = Config::from_toml(&CONFIG.replace("\"2m\"", "\"soon\""))?;
+    assert!(broken.validate().is_err());
+    Ok(())
+}
+
+#[test]
+fn listened_outputs_are_drained_until_their_listeners_leave() -> anyhow::Result<()> {
+    let mut node = AirliftNode::new();
+    let mut flow = Flow::new("main");
+    let (busy, listeners, busy_running) = Listened::new("busy", 2, true);
+    let (idle, _, _) = Listened::new("idle", 0, true);
+    let (legacy, _, _) = Listened::new("legacy", 1, false);
+    flow.add_consumer(Box::new(busy));
+    flow.add_consumer(Box::new(idle));
+    flow.add_consumer(Box::new(legacy));
+    let old_output = flow.output_buffer.clone();
+    node.add_flow(flow);
+    busy_running.store(true, Ordering::SeqCst);
+
+    // Nur Ausgänge mit Zuhörern, die Draining können, verlassen den Flow
+    assert_eq!(node.begin_draining(Duration::from_secs(10)), 1);
+    assert_eq!(node.flows[0].consumer_names(), ["idle", "legacy"]);
+    let status = node.draining_consumers();
+    assert_eq!(status.len(), 1);
+    assert_eq!(
+        (status[0].consumer.as_str(), status[0].flow.as_str()),
+        ("busy", "main")
+    );
+    assert!(!status[0].forwarding);
+
+    // Neuer Flow gleichen Namens speist den alten Buffer
+    node.reset_modules();
+    node.add_flow(Flow::new("main"));
+    node.connect_draining();
+    assert!(node.draining_consumers()[0].forwarding);
+    old_output.skip_to_latest("probe");
+    node.flows[0].output_buffer.push(frame(42));
+    wait_until(|| old_output.available_for_reader("probe") > 0);
+    assert_eq!(
+        old_output.pop_for_reader("probe").map(|f| f.utc_ns),
+        Some(42)
+    );
+
+    // Letzter Zuhörer weg: Consumer wird gestoppt
+    listeners.store(0, Ordering::SeqCst);
+    wait_until(|| !busy_running.load(Ordering::SeqCst));
+    assert!(!busy_running.load(Ordering::SeqCst));
+    wait_until(|| node.draining_consumers().is_empty());
+    assert!(node.draining_consumers().is_empty());
+    Ok(())
+}
+
+#[test]
+fn drains_end_after_the_grace_period() {
+    let mut node = AirliftNode::new();
+    let mut flow = Flow::new("main");
+    let (busy, _, running) = Listened::new("busy", 5, true);
+    flow.add_consumer(Box::new(busy));
+    running.store(true, Ordering::SeqCst);
+    node.add_flow(flow);
+
+    node.begin_draining(Duration::from_millis(200));
+    assert!(running.load(Ordering::SeqCst));
+    wait_until(|| !running.load(Ordering::SeqCst));
+    assert!(
+        !running.load(Ordering::SeqCst),
+        "listeners are cut after the grace period"
+    );
+}
+
+#[test]
+fn draining_zmq_publisher_frees_its_port() -> anyhow::Result<()> {
+    let config = |bind: String| ConsumerConfig {
+        consumer_type: "zmq_pub".to_string(),
+        enabled: true,
+        path: None,
+        url: None,
+        config: serde_json::from_value::<HashMap<String, serde_json::Value>>(
+            serde_json::json!({ "bind": format!("tcp://{}", bind) }),
+        )
+        .unwrap(),
+    };
+    let mut old = ZmqPubConsumer::new("out", "studio", &config("127.0.0.1:0".to_string()))?;
+    old.attach_input_buffer(Arc::new(AudioRingBuffer::new(16)));
+    old.start()?;
+    let addr = old.local_addr().unwrap();
+
+    let mut subscriber = TcpStream::connect(addr)?;
+    std::io::Write::write_all(&mut subscriber, &greeting(false))?;
+    std::io::Write::write_all(&mut subscriber, &ready("SUB"))?;
+    wait_until(|| old.listeners() == 1);
+    assert_eq!(old.listeners(), 1);
+
+    assert!(old.begin_drain());
+    assert!(old.local_addr().is_none());
+    assert!(!old.begin_drain(), "draining twice is a no-op");
+
+    // Neue Config bindet denselben Port, der alte Subscriber bleibt verbunden
+    let mut new = ZmqPubConsumer::new("out", "studio", &config(addr.to_string()))?;
+    new.attach_input_buffer(Arc::new(AudioRingBuffer::new(16)));
+    new.start()?;
+    assert_eq!(new.local_addr(), Some(addr));
+    assert_eq!(old.listeners(), 1);
+
+    new.stop()?;
+    old.stop()?;
+    Ok(())
+}
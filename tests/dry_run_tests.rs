@@ -0,0 +1,159 @@
+use std::path::PathBuf;
+
+use airlift_node::app::dry_run::{default_output_path, dry_run};
+use airlift_node::app::init::build_plugin_registry;
+use airlift_node::audio::loudness::IntegratedLoudness;
+use airlift_node::audio::true_peak::TruePeakMeter;
+use airlift_node::config::Config;
+use airlift_node::PcmFrame;
+
+const CONFIG: &str = r#"
+node_name = "test"
+
+[producers.sine]
+type = "sine"
+enabled = true
+
+[processors.half]
+type = "gain"
+enabled = true
+[processors.half.config]
+gain = 0.5
+
+[processors.off]
+type = "gain"
+enabled = false
+[processors.off.config]
+gain = 0.1
+
+[consumers.out]
+type = "file"
+enabled = true
+path = "/tmp/out.wav"
+
+[flows.main]
+enabled = true
+inputs = ["sine"]
+processors = ["half", "off"]
+outputs = ["out"]
+"#;
+
+/// Stereo-Sinus 997 Hz bei 48 kHz mit Amplitude in dBFS
+fn sine_samples(seconds: usize, level_db: f32) -> Vec<i16> {
+    let amplitude = 32767.0 * 10f32.powf(level_db / 20.0);
+    let mut samples = Vec::with_capacity(seconds * 96_000);
+    for n in 0..seconds * 48_000 {
+        let t = n as f32 / 48_000.0;
+        let value = (amplitude * (2.0 * std::f32::consts::PI * 997.0 * t).sin()) as i16;
+        samples.push(value);
+        samples.push(value);
+    }
+    samples
+}
+
+fn write_wav(name: &str, samples: &[i16]) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("airlift-{}-{}.wav", name, std::process::id()));
+    let spec = hound::WavSpec {
+        channels: 2,
+        sample_rate: 48_000,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut writer = hound::WavWriter::create(&path, spec).unwrap();
+    for sample in samples {
+        writer.write_sample(*sample).unwrap();
+    }
+    writer.finalize().unwrap();
+    path
+}
+
+#[test]
+fn integrated_loudness_gates_silence() {
+    let mut meter = IntegratedLoudness::new();
+    assert_eq!(meter.integrated(), None);
+
+    let tone = sine_samples(4, -20.0);
+    for (index, chunk) in tone.chunks(960).enumerate() {
+        meter.push(&PcmFrame {
+            utc_ns: index as u64,
+            samples: chunk.to_vec(),
+            sample_rate: 48_000,
+            channels: 2,
+            metadata: Default::default(),
+        });
+    }
+    // Stille danach darf die integrierte Lautheit nicht absenken
+    for index in 0..400 {
+        meter.push(&PcmFrame {
+            utc_ns: 1_000 + index,
+            samples: vec![0; 960],
+            sample_rate: 48_000,
+            channels: 2,
+            metadata: Default::default(),
+        });
+    }
+    let integrated = meter.integrated().unwrap();
+    assert!((integrated + 20.0).abs() < 0.3, "{}", integrated);
+    assert!((meter.max_short_term().unwrap() + 20.0).abs() < 0.3);
+    assert_eq!(meter.block_loudness().count(), 80);
+}
+
+#[test]
+fn true_peak_of_full_scale_sine() {
+    let mut meter = TruePeakMeter::new();
+    assert_eq!(meter.dbtp(), None);
+    meter.push(&PcmFrame {
+        utc_ns: 0,
+        samples: sine_samples(1, -6.0),
+        sample_rate: 48_000,
+        channels: 2,
+        metadata: Default::default(),
+    });
+    let dbtp = meter.dbtp().unwrap();
+    assert!((dbtp + 6.0).abs() < 0.3, "{}", dbtp);
+}
+
+#[test]
+fn dry_run_reports_levels_and_writes_output() {
+    let config = Config::from_toml(CONFIG).unwrap();
+    let input = write_wav("dryrun-in", &sine_samples(5, -20.0));
+    let output = default_output_path(&input);
+    assert!(output.to_string_lossy().ends_with(".dryrun.wav"));
+
+    let report = dry_run(&config, "main", &build_plugin_registry(), &input, &output).unwrap();
+    assert_eq!(report.processors, vec!["half".to_string()]);
+    assert_eq!(report.sample_rate, 48_000);
+    assert!((report.input_seconds - 5.0).abs() < 0.01);
+    assert!((report.output_seconds - 5.0).abs() < 0.01);
+
+    let before = report.before.integrated_lufs.unwrap();
+    let after = report.after.integrated_lufs.unwrap();
+    assert!((before - after - 6.0).abs() < 0.3, "{} {}", before, after);
+    let peak_drop = report.before.true_peak_dbtp.unwrap() - report.after.true_peak_dbtp.unwrap();
+    assert!((peak_drop - 6.0).abs() < 0.3, "{}", peak_drop);
+
+    // Gleichmäßige Absenkung um 6 dB: alles in der Klasse 6–7 dB
+    let reduction = &report.gain_reduction;
+    assert_eq!(reduction.blocks, 50);
+    let bin = reduction.histogram.iter().find(|bin| bin.blocks > 0).unwrap();
+    assert_eq!(bin.from_db, 6.0);
+    assert_eq!(bin.blocks, 50);
+    assert_eq!(reduction.histogram.last().unwrap().to_db, None);
+
+    let written = hound::WavReader::open(&output).unwrap();
+    assert_eq!(written.spec().channels, 2);
+    assert_eq!(written.len(), 5 * 96_000);
+
+    let _ = std::fs::remove_file(&input);
+    let _ = std::fs::remove_file(&output);
+}
+
+#[test]
+fn dry_run_rejects_unknown_flow() {
+    let config = Config::from_toml(CONFIG).unwrap();
+    let input = PathBuf::from("/nonexistent.wav");
+    let err = dry_run(&config, "missing", &build_plugin_registry(), &input, &input)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("flow 'missing'"), "{}", err);
+}
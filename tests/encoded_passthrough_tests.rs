@@ -0,0 +1,115 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use airlift_node::codecs::{CodecInfo, CodecKind, ContainerKind, EncodedFrame};
+use airlift_node::core::{AirliftNode, EncodedFlow, SpliceMode};
+use airlift_node::ring::EncodedFramePacket;
+use airlift_node::testing::mocks::{MockEncodedProducer, MockEncodedSink};
+
+fn packet(index: u8) -> EncodedFramePacket {
+    EncodedFramePacket {
+        utc_ns: 1_000 + index as u64,
+        frame: EncodedFrame {
+            // Beliebige Bytes inkl. 0x00/0xFF, müssen unverändert ankommen
+            payload: (0..=index).map(|b| b.wrapping_mul(37) ^ 0xA5).collect(),
+            info: CodecInfo {
+                kind: CodecKind::Mp3,
+                sample_rate: 44_100,
+                channels: 2,
+                container: ContainerKind::Mpeg,
+            },
+        },
+    }
+}
+
+fn wait_for(sink: &MockEncodedSink, count: usize) {
+    let deadline = Instant::now() + Duration::from_secs(2);
+    while Instant::now() < deadline && sink.received().len() < count {
+        std::thread::sleep(Duration::from_millis(5));
+    }
+}
+
+#[test]
+fn passthrough_delivers_bit_exact_frames_to_all_outputs() -> anyhow::Result<()> {
+    let input: Vec<EncodedFramePacket> = (0..50).map(packet).collect();
+    let first = Arc::new(MockEncodedSink::new());
+    let second = Arc::new(MockEncodedSink::new());
+
+    let mut flow = EncodedFlow::new("relay");
+    flow.set_producer(Box::new(MockEncodedProducer::new("ts_in", input.clone())));
+    flow.add_output("icecast", first.clone());
+    flow.add_output("archive", second.clone());
+    flow.enable_inspection();
+
+    let mut node = AirliftNode::new();
+    node.add_encoded_flow(flow);
+    node.start()?;
+    wait_for(&first, input.len());
+    wait_for(&second, input.len());
+    let status = node.encoded_flows()[0].status();
+    node.stop()?;
+
+    let expected: Vec<Vec<u8>> = input.iter().map(|p| p.frame.payload.clone()).collect();
+    assert_eq!(first.payloads(), expected);
+    assert_eq!(second.payloads(), expected);
+    assert_eq!(first.received()[3].utc_ns, input[3].utc_ns);
+
+    assert_eq!(status.producer.as_deref(), Some("ts_in"));
+    assert_eq!(status.outputs.len(), 2);
+    assert_eq!(status.outputs[0].frames, 50);
+    assert_eq!(status.outputs[0].gaps, 0);
+    let inspection = status.inspection.expect("inspection enabled");
+    assert_eq!(inspection.frames, 50);
+    assert_eq!(
+        inspection.bytes,
+        expected.iter().map(|p| p.len() as u64).sum::<u64>()
+    );
+    assert_eq!(inspection.codec.map(|c| c.sample_rate), Some(44_100));
+    Ok(())
+}
+
+#[test]
+fn stopped_encoded_flow_reports_not_running() -> anyhow::Result<()> {
+    let mut flow = EncodedFlow::new("idle");
+    flow.add_output("out", Arc::new(MockEncodedSink::new()));
+    flow.start()?;
+    assert!(flow.is_running());
+    flow.stop()?;
+    assert!(!flow.is_running());
+    assert!(flow.status().inspection.is_none());
+    Ok(())
+}
+
+#[test]
+fn ogg_streams_are_never_spliced() -> anyhow::Result<()> {
+    let ogg = |index: u8| {
+        let mut packet = packet(index);
+        packet.frame.info = CodecInfo {
+            kind: CodecKind::OpusOgg,
+            sample_rate: 48_000,
+            channels: 2,
+            container: ContainerKind::Ogg,
+        };
+        packet
+    };
+    let sink = Arc::new(MockEncodedSink::new());
+    let mut flow = EncodedFlow::new("ogg_relay");
+    flow.set_producer(Box::new(MockEncodedProducer::new(
+        "ogg_in",
+        (0..5).map(ogg).collect(),
+    )));
+    flow.add_output("out", sink.clone());
+    flow.start()?;
+    wait_for(&sink, 5);
+
+    // Gleicher Codec, aber eigene Serial ohne Header: kein Wechsel
+    flow.set_mode(SpliceMode::Processed);
+    flow.processed_input().push(ogg(10))?;
+    let splice = flow.status().splice;
+    flow.stop()?;
+    assert_eq!(splice.mode, SpliceMode::Passthrough);
+    assert_eq!(splice.requested_mode, SpliceMode::Processed);
+    assert_eq!(splice.rejected, 1);
+    assert_eq!(sink.received().len(), 5);
+    Ok(())
+}
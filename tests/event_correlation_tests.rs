@@ -0,0 +1,93 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use airlift_node::core::{
+    AirliftNode, CorrelationScope, Event, EventHandler, EventType, Flow,
+};
+
+struct Collector {
+    events: Mutex<Vec<Event>>,
+}
+
+impl EventHandler for Collector {
+    fn handle_event(&self, event: &Event) -> anyhow::Result<()> {
+        self.events.lock().unwrap().push(event.clone());
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "collector"
+    }
+
+    fn event_type_filter(&self) -> Option<Vec<EventType>> {
+        Some(vec![EventType::FlowStateChanged])
+    }
+}
+
+#[test]
+fn flow_restart_events_share_correlation_id() -> anyhow::Result<()> {
+    let mut node = AirliftNode::new();
+    let collector = Arc::new(Collector {
+        events: Mutex::new(Vec::new()),
+    });
+    node.event_bus()
+        .lock()
+        .unwrap()
+        .register_handler(collector.clone())?;
+    node.add_flow(Flow::new("main"));
+
+    let correlation_id = {
+        let scope = CorrelationScope::begin("control");
+        node.restart_flow_by_name("main")?;
+        scope.id().to_string()
+    };
+    node.stop_flow_by_name("main")?;
+
+    let deadline = Instant::now() + Duration::from_secs(1);
+    while Instant::now() < deadline && collector.events.lock().unwrap().len() < 3 {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+
+    let events = collector.events.lock().unwrap();
+    assert_eq!(events.len(), 3);
+    assert_eq!(events[0].correlation_id.as_deref(), Some(correlation_id.as_str()));
+    assert_eq!(events[1].correlation_id.as_deref(), Some(correlation_id.as_str()));
+    assert!(events[2].correlation_id.is_none());
+    Ok(())
+}
+
+#[test]
+fn parallel_flow_starts_keep_the_correlation_id() -> anyhow::Result<()> {
+    let mut node = AirliftNode::new();
+    let collector = Arc::new(Collector {
+        events: Mutex::new(Vec::new()),
+    });
+    node.event_bus()
+        .lock()
+        .unwrap()
+        .register_handler(collector.clone())?;
+    node.set_startup_workers(4);
+    for name in ["a", "b", "c", "d"] {
+        node.add_flow(Flow::new(name));
+    }
+
+    let correlation_id = {
+        let scope = CorrelationScope::begin("control");
+        node.start()?;
+        scope.id().to_string()
+    };
+
+    let deadline = Instant::now() + Duration::from_secs(1);
+    while Instant::now() < deadline && collector.events.lock().unwrap().len() < 4 {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    node.stop()?;
+
+    let events = collector.events.lock().unwrap();
+    let started: Vec<&Event> = events.iter().take(4).collect();
+    assert_eq!(started.len(), 4);
+    for event in started {
+        assert_eq!(event.correlation_id.as_deref(), Some(correlation_id.as_str()));
+    }
+    Ok(())
+}
@@ -0,0 +1,146 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use airlift_node::core::{
+    DedupKey, Event, EventBus, EventDeduplicator, EventHandler, EventPriority, EventType,
+};
+
+fn failure(instance: &str, error_type: &str, priority: EventPriority) -> Event {
+    Event::new(
+        EventType::Error,
+        priority,
+        "consumer",
+        instance,
+        serde_json::json!({ "error_type": error_type, "message": "connection refused" }),
+    )
+}
+
+#[test]
+fn repeats_are_counted_and_summarized_per_component_and_kind() {
+    let start = Instant::now();
+    let mut dedup = EventDeduplicator::new(Duration::from_secs(10));
+
+    assert_eq!(
+        DedupKey::of(&failure(
+            "icecast",
+            "connect_failed",
+            EventPriority::Warning
+        ))
+        .unwrap()
+        .kind,
+        "Error/connect_failed"
+    );
+    assert!(DedupKey::of(&failure("icecast", "connect_failed", EventPriority::Info)).is_none());
+
+    let first = dedup.filter(
+        failure("icecast", "connect_failed", EventPriority::Warning),
+        start,
+    );
+    assert_eq!(first.len(), 1);
+    for second in 1..=4 {
+        let out = dedup.filter(
+            failure("icecast", "connect_failed", EventPriority::Warning),
+            start + Duration::from_secs(second),
+        );
+        assert!(out.is_empty(), "repeat {} suppressed", second);
+    }
+
+    // Andere Fehlerart, andere Instanz und Info-Events laufen durch
+    let at = start + Duration::from_secs(2);
+    for event in [
+        failure("icecast", "connection_lost", EventPriority::Warning),
+        failure("srt_out", "connect_failed", EventPriority::Warning),
+        failure("icecast", "connect_failed", EventPriority::Info),
+        failure("icecast", "connect_failed", EventPriority::Info),
+    ] {
+        assert_eq!(dedup.filter(event, at).len(), 1);
+    }
+
+    assert!(dedup.flush(start + Duration::from_secs(9)).is_empty());
+    let summaries = dedup.flush(start + Duration::from_secs(10));
+    assert_eq!(summaries.len(), 1, "only the window with repeats reports");
+    let summary = &summaries[0];
+    assert_eq!(summary.source_instance, "icecast");
+    assert_eq!(summary.priority, EventPriority::Warning);
+    assert_eq!(summary.payload["error_type"], "connect_failed");
+    assert_eq!(summary.payload["repeated"], 4);
+    assert_eq!(summary.payload["summary"], "repeated 4 times in last 10 s");
+
+    let stats = dedup.stats();
+    assert_eq!((stats.suppressed, stats.summaries), (4, 1));
+    assert_eq!(stats.tracked, 2, "windows opened at +2 s are still running");
+
+    // Nach Ablauf beginnt ein neues Fenster; die Zusammenfassung kommt
+    // spätestens mit dem nächsten Event derselben Art
+    let later = start + Duration::from_secs(20);
+    dedup.filter(
+        failure("srt_out", "connect_failed", EventPriority::Error),
+        later,
+    );
+    dedup.filter(
+        failure("srt_out", "connect_failed", EventPriority::Error),
+        later,
+    );
+    let out = dedup.filter(
+        failure("srt_out", "connect_failed", EventPriority::Error),
+        later + Duration::from_secs(15),
+    );
+    assert_eq!(out.len(), 2);
+    assert_eq!(out[0].payload["repeated"], 1);
+    assert!(out[1].payload.get("repeated").is_none());
+
+    dedup.set_window(Duration::ZERO);
+    let at = later + Duration::from_secs(60);
+    dedup.flush(at);
+    for _ in 0..3 {
+        let out = dedup.filter(
+            failure("srt_out", "connect_failed", EventPriority::Error),
+            at,
+        );
+        assert_eq!(out.len(), 1, "window 0 disables de-duplication");
+    }
+}
+
+struct Collector {
+    events: Mutex<Vec<Event>>,
+}
+
+impl EventHandler for Collector {
+    fn handle_event(&self, event: &Event) -> anyhow::Result<()> {
+        self.events.lock().unwrap().push(event.clone());
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "collector"
+    }
+}
+
+#[test]
+fn bus_dispatches_first_event_and_a_later_summary() -> anyhow::Result<()> {
+    let mut bus = EventBus::new("dedup_test");
+    let collector = Arc::new(Collector {
+        events: Mutex::new(Vec::new()),
+    });
+    bus.register_handler(collector.clone())?;
+    bus.set_dedup_window(Duration::from_millis(200));
+    bus.start()?;
+
+    for _ in 0..5 {
+        bus.publish(failure("icecast", "connect_failed", EventPriority::Warning))?;
+    }
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while collector.events.lock().unwrap().len() < 2 && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(20));
+    }
+    bus.stop()?;
+
+    let events = collector.events.lock().unwrap();
+    assert_eq!(events.len(), 2, "{:?}", events);
+    assert!(events[0].payload.get("repeated").is_none());
+    assert_eq!(events[1].payload["repeated"], 4);
+    assert_eq!(bus.event_count(), 5);
+    assert_eq!(bus.dedup_stats().suppressed, 4);
+    Ok(())
+}
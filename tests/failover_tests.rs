@@ -0,0 +1,230 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use airlift_node::config::{Config, FailoverConfig};
+use airlift_node::core::event_bus::EventEmitter;
+use airlift_node::core::failover::FailoverReason;
+use airlift_node::core::ringbuffer::AudioRingBuffer;
+use airlift_node::core::{
+    AirliftNode, Event, EventHandler, EventType, FailoverGroup, FailoverSelector,
+    FailoverSettings, Flow,
+};
+use airlift_node::testing::mocks::{MockConsumer, MockProducer};
+use airlift_node::PcmFrame;
+
+const S: u64 = 1_000_000_000;
+
+fn frame(level: i16) -> PcmFrame {
+    PcmFrame {
+        utc_ns: 1,
+        samples: vec![level, level],
+        sample_rate: 48_000,
+        channels: 2,
+        metadata: Default::default(),
+    }
+}
+
+fn program_selector() -> FailoverSelector {
+    FailoverSelector::new(
+        "program",
+        vec!["srt".to_string(), "file".to_string()],
+        FailoverSettings {
+            timeout: Duration::from_secs(2),
+            recover_after: Duration::from_secs(5),
+            silence_threshold: 0.001,
+        },
+        0,
+    )
+}
+
+#[test]
+fn switches_on_silence_and_back_after_recovery() {
+    let mut selector = program_selector();
+    selector.record(1, &frame(1000), 0);
+    selector.record(0, &frame(0), S);
+    selector.record(1, &frame(1000), S);
+    assert_eq!(selector.evaluate(S), None);
+
+    selector.record(0, &frame(0), 2 * S);
+    selector.record(1, &frame(1000), 2 * S);
+    let switch = selector.evaluate(2 * S).expect("failover");
+    assert_eq!((switch.from.as_str(), switch.to.as_str()), ("srt", "file"));
+    assert_eq!(switch.reason, FailoverReason::Silence);
+
+    // Primär wieder hörbar, zurück erst nach `recover_after`
+    for second in 3..=7 {
+        selector.record(0, &frame(1000), second * S);
+        selector.record(1, &frame(1000), second * S);
+        assert_eq!(selector.evaluate(second * S), None, "t = {}s", second);
+    }
+    selector.record(0, &frame(1000), 8 * S);
+    let back = selector.evaluate(8 * S).expect("recovery");
+    assert_eq!(back.to, "srt");
+    assert_eq!(back.reason, FailoverReason::Recovered);
+    assert_eq!(selector.status(8 * S).switches, 2);
+}
+
+#[test]
+fn missing_frames_count_as_disconnect() {
+    let mut selector = program_selector();
+    selector.record(0, &frame(1000), 0);
+    selector.record(1, &frame(1000), 2 * S);
+    let switch = selector.evaluate(2 * S).expect("failover");
+    assert_eq!(switch.reason, FailoverReason::Disconnected);
+
+    // Kein gesundes Mitglied: aktiver Producer bleibt
+    let mut stuck = program_selector();
+    assert_eq!(stuck.evaluate(3 * S), None);
+    assert_eq!(stuck.active(), "srt");
+}
+
+#[test]
+fn group_buffer_feeds_flow_and_reports_status() -> anyhow::Result<()> {
+    let (consumer, received) = MockConsumer::new_with_shared("out");
+    let mut flow = Flow::new("flow");
+    flow.add_consumer(Box::new(consumer));
+
+    let mut node = AirliftNode::new();
+    node.add_flow(flow);
+    node.add_producer(Box::new(MockProducer::new("primary", vec![frame(1000)])))?;
+    node.add_producer(Box::new(MockProducer::new("backup", vec![frame(2000)])))?;
+    assert!(node
+        .add_failover_group(
+            "program",
+            &["primary".to_string(), "missing".to_string()],
+            FailoverSettings::default(),
+        )
+        .is_err());
+    node.add_failover_group(
+        "program",
+        &["primary".to_string(), "backup".to_string()],
+        FailoverSettings::default(),
+    )?;
+    node.connect_flow_input(0, "failover:program")?;
+
+    node.start()?;
+    let deadline = Instant::now() + Duration::from_secs(1);
+    while Instant::now() < deadline && received.lock().unwrap().is_empty() {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    let status = node.failover_status();
+    node.stop()?;
+
+    let levels: Vec<i16> = received.lock().unwrap().iter().map(|f| f.samples[0]).collect();
+    assert_eq!(levels, vec![1000], "only the primary is forwarded");
+    assert_eq!(status[0].active, "primary");
+    assert_eq!(status[0].members.len(), 2);
+    Ok(())
+}
+
+struct FailoverCollector {
+    events: Mutex<Vec<Event>>,
+}
+
+impl EventHandler for FailoverCollector {
+    fn handle_event(&self, event: &Event) -> anyhow::Result<()> {
+        self.events.lock().unwrap().push(event.clone());
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "failover_collector"
+    }
+
+    fn event_type_filter(&self) -> Option<Vec<EventType>> {
+        Some(vec![EventType::ProducerFailover])
+    }
+}
+
+#[test]
+fn failover_sequence_shares_correlation_id() -> anyhow::Result<()> {
+    let node = AirliftNode::new();
+    let collector = Arc::new(FailoverCollector {
+        events: Mutex::new(Vec::new()),
+    });
+    node.event_bus()
+        .lock()
+        .unwrap()
+        .register_handler(collector.clone())?;
+
+    let primary = Arc::new(AudioRingBuffer::new(64));
+    let backup = Arc::new(AudioRingBuffer::new(64));
+    let mut group = FailoverGroup::new(
+        "program",
+        vec![
+            ("primary".to_string(), primary.clone()),
+            ("backup".to_string(), backup.clone()),
+        ],
+        Arc::new(AudioRingBuffer::new(64)),
+        FailoverSettings {
+            timeout: Duration::from_millis(50),
+            recover_after: Duration::from_millis(50),
+            silence_threshold: 0.001,
+        },
+    )
+    .with_emitter(EventEmitter::new(node.event_bus(), "failover", "program"));
+    group.start();
+
+    // Erst Stille auf dem primären Producer, dann wieder Audio
+    let feed = |level: i16, count: usize| {
+        for _ in 0..count {
+            primary.push(frame(level));
+            backup.push(frame(1000));
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    };
+    feed(0, 20);
+    feed(1000, 20);
+    group.stop();
+
+    let deadline = Instant::now() + Duration::from_secs(1);
+    while Instant::now() < deadline && collector.events.lock().unwrap().len() < 2 {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    let events = collector.events.lock().unwrap();
+    assert_eq!(events.len(), 2, "switch away and back");
+    let id = events[0].correlation_id.as_deref().expect("correlation id");
+    assert!(id.starts_with("failover-"), "{}", id);
+    assert_eq!(events[1].correlation_id.as_deref(), Some(id));
+    Ok(())
+}
+
+#[test]
+fn config_validates_failover_groups() {
+    let mut config = Config::from_toml(
+        r#"
+node_name = "test"
+[monitoring]
+http_port = 8080
+[producers.srt]
+type = "sine"
+enabled = true
+[producers.file]
+type = "sine"
+enabled = true
+[processors]
+[consumers]
+[flows.program]
+enabled = true
+inputs = ["program"]
+processors = []
+outputs = []
+[failover.program]
+producers = ["srt", "file"]
+timeout = "3s"
+"#,
+    )
+    .expect("config parses");
+    config.validate().expect("valid failover config");
+
+    config.failover.insert(
+        "broken".to_string(),
+        FailoverConfig {
+            producers: vec!["srt".to_string(), "nope".to_string()],
+            timeout: None,
+            recover_after: None,
+            silence_threshold_db: None,
+        },
+    );
+    assert!(config.validate().is_err());
+}
@@ -0,0 +1,209 @@
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use airlift_node::config::{Config, ConsumerConfig};
+use airlift_node::consumers::fanout::{FanoutConfig, FanoutConsumer};
+use airlift_node::core::{AudioRingBuffer, Consumer, ConsumerStatus};
+use airlift_node::testing::mocks::MockConsumer;
+use airlift_node::PcmFrame;
+use serde_json::json;
+
+fn consumer_config(config: serde_json::Value) -> ConsumerConfig {
+    ConsumerConfig {
+        consumer_type: "fanout".to_string(),
+        enabled: true,
+        path: None,
+        url: None,
+        config: serde_json::from_value(config).unwrap(),
+    }
+}
+
+fn frame(index: u64) -> PcmFrame {
+    PcmFrame {
+        utc_ns: index * 10_000_000,
+        samples: vec![0; 960],
+        sample_rate: 48_000,
+        channels: 2,
+        metadata: Default::default(),
+    }
+}
+
+/// Scheitert beim ersten Start; `running` zurücksetzen simuliert einen Ausfall.
+struct FlakyConsumer {
+    running: Arc<AtomicBool>,
+    starts: Arc<AtomicU32>,
+}
+
+impl Consumer for FlakyConsumer {
+    fn name(&self) -> &str {
+        "flaky"
+    }
+
+    fn start(&mut self) -> anyhow::Result<()> {
+        if self.starts.fetch_add(1, Ordering::SeqCst) == 0 {
+            anyhow::bail!("server refused");
+        }
+        self.running.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn stop(&mut self) -> anyhow::Result<()> {
+        self.running.store(false, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn status(&self) -> ConsumerStatus {
+        ConsumerStatus {
+            running: self.running.load(Ordering::SeqCst),
+            connected: self.running.load(Ordering::SeqCst),
+            frames_processed: 0,
+            bytes_written: 0,
+            errors: 0,
+            connection: None,
+        }
+    }
+
+    fn attach_input_buffer(&mut self, _buffer: Arc<AudioRingBuffer>) {}
+}
+
+#[test]
+fn fanout_config_rejects_invalid_targets() -> anyhow::Result<()> {
+    let config = FanoutConfig::from_config(
+        "out",
+        &consumer_config(json!({ "targets": ["ice_a", "file"], "max_restart": "10s" })),
+    )?;
+    assert_eq!(config.targets, ["ice_a", "file"]);
+    assert_eq!(config.restart, Duration::from_secs(1));
+    assert_eq!(config.max_restart, Duration::from_secs(10));
+
+    for config in [
+        json!({}),
+        json!({ "targets": [] }),
+        json!({ "targets": "ice_a" }),
+        json!({ "targets": ["ice_a", ""] }),
+        json!({ "targets": ["ice_a", "ice_a"] }),
+        json!({ "targets": ["out"] }),
+        json!({ "targets": ["ice_a"], "restart": 10 }),
+    ] {
+        assert!(
+            FanoutConfig::from_config("out", &consumer_config(config.clone())).is_err(),
+            "{}",
+            config
+        );
+    }
+    Ok(())
+}
+
+const CONFIG: &str = r#"
+node_name = "studio"
+
+[producers.mic]
+type = "sine"
+enabled = true
+
+[processors]
+
+[consumers.out]
+type = "fanout"
+enabled = true
+config = { targets = ["a", "b"] }
+
+[consumers.a]
+type = "file"
+enabled = true
+path = "/tmp/a.wav"
+
+[consumers.b]
+type = "file"
+enabled = true
+path = "/tmp/b.wav"
+
+[flows.main]
+enabled = true
+inputs = ["mic"]
+processors = []
+outputs = ["out"]
+"#;
+
+#[test]
+fn config_validation_keeps_fanout_targets_exclusive() -> anyhow::Result<()> {
+    Config::from_toml(CONFIG)?.validate()?;
+
+    for broken in [
+        CONFIG.replace(r#"["a", "b"]"#, r#"["a", "c"]"#),
+        CONFIG.replace(r#"outputs = ["out"]"#, r#"outputs = ["out", "b"]"#),
+        CONFIG.replace(
+            "type = \"file\"\nenabled = true\npath = \"/tmp/b.wav\"",
+            "type = \"fanout\"\nenabled = true\nconfig = { targets = [\"c\"] }",
+        ),
+    ] {
+        assert!(
+            Config::from_toml(&broken)?.validate().is_err(),
+            "{}",
+            broken
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn failing_target_is_restarted_without_stalling_others() -> anyhow::Result<()> {
+    let buffer = Arc::new(AudioRingBuffer::new(64));
+    let (mock, received) = MockConsumer::new_with_shared("archive");
+    let flaky_running = Arc::new(AtomicBool::new(false));
+    let starts = Arc::new(AtomicU32::new(0));
+
+    let mut fanout = FanoutConsumer::with_config(
+        "out",
+        FanoutConfig {
+            targets: vec!["archive".to_string(), "flaky".to_string()],
+            restart: Duration::from_millis(100),
+            max_restart: Duration::from_millis(400),
+        },
+    );
+    fanout.add_target(Box::new(mock));
+    fanout.add_target(Box::new(FlakyConsumer {
+        running: flaky_running.clone(),
+        starts: starts.clone(),
+    }));
+    fanout.attach_input_buffer(buffer.clone());
+    fanout.start()?;
+
+    for index in 0..10 {
+        buffer.push(frame(index));
+    }
+    let deadline = Instant::now() + Duration::from_secs(2);
+    while Instant::now() < deadline
+        && (received.lock().unwrap().len() < 10 || !flaky_running.load(Ordering::SeqCst))
+    {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    assert_eq!(received.lock().unwrap().len(), 10);
+
+    let targets = fanout.targets();
+    assert_eq!(targets.len(), 2);
+    assert_eq!(
+        (targets[0].name.as_str(), targets[0].restarts),
+        ("archive", 0)
+    );
+    assert_eq!(targets[0].frames_processed, 10);
+    assert_eq!((targets[1].running, targets[1].restarts), (true, 1));
+    assert_eq!(targets[1].errors, 1);
+    assert_eq!(targets[1].last_error.as_deref(), Some("server refused"));
+
+    // Ausfall nach dem Start: erneuter Neustart, das Archiv läuft weiter
+    flaky_running.store(false, Ordering::SeqCst);
+    let deadline = Instant::now() + Duration::from_secs(2);
+    while Instant::now() < deadline && fanout.targets()[1].restarts < 2 {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    let status = fanout.status();
+    assert!(status.running && status.connected);
+    assert_eq!(fanout.targets()[1].restarts, 2);
+    assert!(fanout.targets()[0].running);
+
+    fanout.stop()?;
+    assert!(!fanout.targets().iter().any(|target| target.running));
+    Ok(())
+}
@@ -0,0 +1,281 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use airlift_node::audio::wav::{self, BwfOptions, WavFormat};
+use airlift_node::codecs::{AudioCodec, CodecInfo, CodecKind, ContainerKind, EncodedFrame};
+use airlift_node::core::consumer::file_writer::{FileConsumer, FileFormat, RecorderState};
+use airlift_node::core::timestamp::utc_ns_now;
+use airlift_node::core::{AudioRingBuffer, Consumer, FileRotation, StateStore, TimeZone};
+use airlift_node::PcmFrame;
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("airlift-format-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn config(pairs: &[(&str, serde_json::Value)]) -> HashMap<String, serde_json::Value> {
+    pairs
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.clone()))
+        .collect()
+}
+
+/// Schreibt einen Stream-Header, 1 Byte pro Frame und einen Abschluss.
+struct TaggingEncoder {
+    info: CodecInfo,
+    started: bool,
+}
+
+impl TaggingEncoder {
+    fn boxed() -> anyhow::Result<Box<dyn AudioCodec>> {
+        Ok(Box::new(Self {
+            info: CodecInfo {
+                kind: CodecKind::Mp3,
+                sample_rate: 48_000,
+                channels: 2,
+                container: ContainerKind::Mpeg,
+            },
+            started: false,
+        }))
+    }
+
+    fn frame(&self, payload: &[u8]) -> EncodedFrame {
+        EncodedFrame {
+            payload: payload.to_vec(),
+            info: self.info.clone(),
+        }
+    }
+}
+
+impl AudioCodec for TaggingEncoder {
+    fn info(&self) -> &CodecInfo {
+        &self.info
+    }
+
+    fn encode(&mut self, _pcm: &[i16]) -> anyhow::Result<Vec<EncodedFrame>> {
+        let mut frames = Vec::new();
+        if !self.started {
+            self.started = true;
+            frames.push(self.frame(b"HDR"));
+        }
+        frames.push(self.frame(b"F"));
+        Ok(frames)
+    }
+
+    fn flush(&mut self) -> anyhow::Result<Vec<EncodedFrame>> {
+        Ok(vec![self.frame(b"END")])
+    }
+}
+
+#[test]
+fn format_comes_from_config_or_extension() {
+    let from = |path: &str, pairs: &[(&str, serde_json::Value)]| {
+        FileConsumer::from_config("rec", path, &config(pairs), TimeZone::utc())
+    };
+    assert_eq!(from("/tmp/rec.wav", &[]).unwrap().format(), FileFormat::Wav);
+    assert_eq!(from("/tmp/rec.raw", &[]).unwrap().format(), FileFormat::Wav);
+    assert_eq!(FileFormat::from_path("/archive/%H%M.opus"), Some(FileFormat::OggOpus));
+    assert_eq!(FileFormat::parse("FLAC"), Some(FileFormat::Flac));
+
+    let err = from("/tmp/rec.bin", &[("format", "aiff".into())]).err().unwrap().to_string();
+    assert!(err.contains("config.format 'aiff'"), "{}", err);
+    let err = from("/tmp/rec.wav", &[("format", "mp3".into())]).err().unwrap().to_string();
+    assert!(err.contains("does not match"), "{}", err);
+    let err = from("/tmp/rec.wav", &[("bitrate", "128k".into())]).err().unwrap().to_string();
+    assert!(err.contains("config.bitrate needs format 'mp3'"), "{}", err);
+
+    // Kodierte Formate brauchen einen Encoder im Build
+    for (path, format) in [("/tmp/rec.mp3", "mp3"), ("/tmp/rec.ogg", "ogg"), ("/tmp/rec.flac", "flac")] {
+        let consumer = from(path, &[("format", format.into())]);
+        let codec = FileFormat::parse(format).unwrap().codec_id().unwrap();
+        let available = airlift_node::codecs::create_encoder(codec).is_ok();
+        assert_eq!(consumer.is_ok(), available, "{}", format);
+    }
+}
+
+#[test]
+fn encoded_segments_get_their_own_stream_headers() {
+    let dir = temp_dir("segments");
+    let template = format!("{}/rec-%H%M%S.mp3", dir.display());
+    let rotation = FileRotation::from_config("rec", &template, &config(&[("rotate_every", "10s".into())]));
+    let mut consumer = FileConsumer::new("rec", &template)
+        .with_encoder(FileFormat::Mp3, Arc::new(TaggingEncoder::boxed))
+        .with_rotation(rotation.unwrap());
+    let buffer = Arc::new(AudioRingBuffer::new(4096));
+    consumer.attach_input_buffer(buffer.clone());
+    consumer.start().unwrap();
+
+    // 15 s ab einer vollen 10-s-Grenze: genau zwei Segmente
+    let start_ns = (utc_ns_now() / 10_000_000_000 + 1) * 10_000_000_000;
+    for index in 0..150u64 {
+        buffer.push(PcmFrame {
+            utc_ns: start_ns + index * 100_000_000,
+            samples: vec![1; 9600],
+            sample_rate: 48_000,
+            channels: 2,
+            metadata: Default::default(),
+        });
+    }
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while consumer.status().frames_processed < 150 && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(20));
+    }
+    consumer.stop().unwrap();
+
+    let mut files: Vec<PathBuf> = fs::read_dir(&dir)
+        .unwrap()
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "mp3"))
+        .collect();
+    files.sort();
+    let contents: Vec<Vec<u8>> = files.iter().map(|path| fs::read(path).unwrap()).collect();
+    // Das beim Start geöffnete Segment endet leer an der ersten Grenze,
+    // danach 10 s und 5 s – jedes mit eigenem Header und Abschluss
+    assert_eq!(contents.len(), 3, "{:?}", files);
+    assert_eq!(contents[0], b"END");
+    assert_eq!(contents[1], [b"HDR".as_slice(), &[b'F'; 100], b"END"].concat());
+    assert_eq!(contents[2], [b"HDR".as_slice(), &[b'F'; 50], b"END"].concat());
+    assert_eq!(consumer.status().bytes_written, 3 + 100 + 3 + 50);
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn interrupted_encoded_recording_is_renamed_as_is() {
+    let dir = temp_dir("recover");
+    let store = StateStore::open(dir.join("state"), Duration::from_secs(60), Duration::from_secs(1)).unwrap();
+    let path = dir.join("program.mp3");
+    let part_path = dir.join("program.mp3.part");
+    fs::write(&part_path, b"HDRFFFF").unwrap();
+    let started_at_ms = utc_ns_now() / 1_000_000 - 4_000;
+    store
+        .save_json(
+            "recorder-archive",
+            &RecorderState {
+                path: path.clone(),
+                part_path: part_path.clone(),
+                started_at_ms,
+                data_bytes: 7,
+            },
+        )
+        .unwrap();
+
+    let consumer = FileConsumer::new("archive", path.to_str().unwrap())
+        .with_encoder(FileFormat::Mp3, Arc::new(TaggingEncoder::boxed));
+    assert_eq!(consumer.recover_interrupted(&store), Some(path.clone()));
+    assert!(!part_path.exists());
+    assert_eq!(fs::read(&path).unwrap(), b"HDRFFFF");
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn bwf_header_carries_local_origination_time() {
+    let dir = temp_dir("bwf");
+    let path = dir.join("program.wav");
+    let mut consumer = FileConsumer::from_config(
+        "program",
+        path.to_str().unwrap(),
+        &config(&[("bwf", true.into()), ("bwf_description", "Studio A".into())]),
+        TimeZone::parse("+01:00").unwrap(),
+    )
+    .unwrap();
+    let buffer = Arc::new(AudioRingBuffer::new(64));
+    consumer.attach_input_buffer(buffer.clone());
+    consumer.start().unwrap();
+    for index in 0..10u64 {
+        buffer.push(PcmFrame {
+            utc_ns: utc_ns_now() + index * 100_000_000,
+            samples: vec![3; 9600],
+            sample_rate: 48_000,
+            channels: 2,
+            metadata: Default::default(),
+        });
+    }
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while consumer.status().frames_processed < 10 && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(20));
+    }
+    consumer.stop().unwrap();
+
+    let reader = hound::WavReader::open(&path).unwrap();
+    assert_eq!(reader.duration(), 48_000);
+    drop(reader);
+    let data = fs::read(&path).unwrap();
+    assert_eq!(&data[12..16], b"JUNK");
+    assert_eq!(&data[48..52], b"bext");
+    let bext = &data[56..];
+    assert_eq!(&bext[..8], b"Studio A");
+    assert_eq!(&bext[256..268], b"airlift-node");
+    let date = std::str::from_utf8(&bext[320..330]).unwrap();
+    let time = std::str::from_utf8(&bext[330..338]).unwrap();
+    assert!(date.len() == 10 && date.as_bytes()[4] == b'-', "{}", date);
+    // TimeReference passt zur Uhrzeit im Header
+    let reference = u64::from_le_bytes(bext[338..346].try_into().unwrap());
+    let seconds: Vec<u64> = time.split(':').map(|part| part.parse().unwrap()).collect();
+    assert_eq!(reference / 48_000, seconds[0] * 3600 + seconds[1] * 60 + seconds[2]);
+
+    let err = FileConsumer::from_config(
+        "program",
+        "/tmp/program.mp3",
+        &config(&[("bwf", true.into())]),
+        TimeZone::utc(),
+    )
+    .err()
+    .map(|e| e.to_string())
+    .unwrap_or_default();
+    assert!(err.contains("config.bwf needs format 'wav'"), "{}", err);
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn files_beyond_four_gib_become_rf64() {
+    let dir = temp_dir("rf64");
+    let path = dir.join("long.wav");
+    let format = WavFormat {
+        sample_rate: 48_000,
+        channels: 2,
+        bits_per_sample: 16,
+    };
+    let bext = BwfOptions::default().bext(0, &TimeZone::utc(), &format);
+    let header = wav::header(&format, Some(&bext));
+    fs::write(&path, &header).unwrap();
+
+    // Dünn besetzte Datei: 5 GiB Audiodaten ohne echten Plattenplatz
+    let data_bytes = 5u64 << 30;
+    let mut file = fs::OpenOptions::new().read(true).write(true).open(&path).unwrap();
+    file.set_len(header.len() as u64 + data_bytes).unwrap();
+    assert_eq!(wav::data_offset(&mut file).unwrap(), header.len() as u64);
+    assert!(wav::finalize(&mut file, header.len() as u64, data_bytes, &format).unwrap());
+    drop(file);
+
+    let mut head = vec![0u8; 48];
+    std::io::Read::read_exact(&mut fs::File::open(&path).unwrap(), &mut head).unwrap();
+    assert_eq!(&head[..4], b"RF64");
+    assert_eq!(&head[4..8], &u32::MAX.to_le_bytes());
+    assert_eq!(&head[12..16], b"ds64");
+    let field = |offset: usize| u64::from_le_bytes(head[offset..offset + 8].try_into().unwrap());
+    assert_eq!(field(20), header.len() as u64 - 8 + data_bytes);
+    assert_eq!(field(28), data_bytes);
+    assert_eq!(field(36), data_bytes / 4);
+    // Der Daten-Offset bleibt auch als RF64 auffindbar
+    let mut file = fs::File::open(&path).unwrap();
+    assert_eq!(wav::data_offset(&mut file).unwrap(), header.len() as u64);
+
+    // Unter 4 GiB bleibt es ein normales RIFF mit JUNK
+    let small = dir.join("short.wav");
+    fs::write(&small, &header).unwrap();
+    let mut file = fs::OpenOptions::new().read(true).write(true).open(&small).unwrap();
+    file.set_len(header.len() as u64 + 4_000).unwrap();
+    assert!(!wav::finalize(&mut file, header.len() as u64, 4_000, &format).unwrap());
+    drop(file);
+    assert_eq!(hound::WavReader::open(&small).unwrap().duration(), 1_000);
+
+    let _ = fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,134 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use airlift_node::config::ProducerConfig;
+use airlift_node::core::{AudioRingBuffer, Producer};
+use airlift_node::producers::file::{open_audio_file, probe_audio_file, FileProducer};
+
+/// 50 ms Stereo, 16 Bit, 48 kHz: Sample-Wert = Index
+fn write_wav(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("airlift-{}-{}.wav", name, std::process::id()));
+    let spec = hound::WavSpec {
+        channels: 2,
+        sample_rate: 48_000,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut writer = hound::WavWriter::create(&path, spec).unwrap();
+    for i in 0..4800 {
+        writer.write_sample(i as i16).unwrap();
+    }
+    writer.finalize().unwrap();
+    path
+}
+
+fn producer(path: &Path, loop_audio: bool) -> (FileProducer, Arc<AudioRingBuffer>) {
+    let cfg = ProducerConfig {
+        producer_type: "file".to_string(),
+        enabled: true,
+        path: Some(path.to_string_lossy().into_owned()),
+        loop_audio: Some(loop_audio),
+        ..ProducerConfig::default()
+    };
+    let mut producer = FileProducer::new("file", &cfg);
+    let ring = Arc::new(AudioRingBuffer::new(64));
+    producer.attach_ring_buffer(ring.clone());
+    (producer, ring)
+}
+
+fn drain(ring: &AudioRingBuffer) -> Vec<i16> {
+    std::iter::from_fn(|| ring.pop()).flat_map(|frame| frame.samples).collect()
+}
+
+#[test]
+fn probe_reports_format_and_duration() {
+    let path = write_wav("probe");
+    let info = probe_audio_file(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!((info.sample_rate, info.channels), (48_000, 2));
+    assert_eq!(info.codec, "wav");
+    assert_eq!(info.duration, Some(Duration::from_millis(50)));
+    assert!(open_audio_file(Path::new("/nonexistent/file.wav")).is_err());
+}
+
+#[test]
+fn plays_file_once_in_order() -> anyhow::Result<()> {
+    let path = write_wav("once");
+    let (mut producer, ring) = producer(&path, false);
+    producer.start()?;
+    assert_eq!(producer.file_info().map(|info| info.channels), Some(2));
+
+    let deadline = Instant::now() + Duration::from_secs(2);
+    while Instant::now() < deadline && producer.status().running {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    producer.stop()?;
+    std::fs::remove_file(&path)?;
+
+    let samples = drain(&ring);
+    assert_eq!(samples.len(), 4800);
+    assert!(samples.iter().enumerate().all(|(i, s)| *s == i as i16));
+    Ok(())
+}
+
+#[test]
+fn looping_continues_seamlessly() -> anyhow::Result<()> {
+    let path = write_wav("loop");
+    let (mut producer, ring) = producer(&path, true);
+    producer.start()?;
+
+    let deadline = Instant::now() + Duration::from_secs(2);
+    while Instant::now() < deadline && producer.status().samples_processed < 9600 {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    assert!(producer.status().running, "looping file keeps running");
+    producer.stop()?;
+    std::fs::remove_file(&path)?;
+
+    let samples = drain(&ring);
+    assert!(samples.len() > 4800);
+    assert_eq!(samples[4799], 4799);
+    assert_eq!(samples[4800], 0, "second pass starts at the beginning");
+    Ok(())
+}
+
+#[test]
+fn pause_holds_position_and_resume_continues() -> anyhow::Result<()> {
+    let path = write_wav("pause");
+    let (mut producer, ring) = producer(&path, true);
+    assert!(producer.pause().is_err(), "stopped producer cannot pause");
+    producer.start()?;
+
+    let deadline = Instant::now() + Duration::from_secs(2);
+    while Instant::now() < deadline && producer.status().samples_processed == 0 {
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    producer.pause()?;
+    let status = producer.status();
+    assert!(status.running && status.paused);
+
+    // Ein gerade laufender Frame darf noch durchgehen
+    std::thread::sleep(Duration::from_millis(30));
+    let held = producer.status().samples_processed;
+    std::thread::sleep(Duration::from_millis(100));
+    assert_eq!(producer.status().samples_processed, held, "no frames while paused");
+
+    producer.resume()?;
+    assert!(!producer.status().paused);
+    let deadline = Instant::now() + Duration::from_secs(2);
+    while Instant::now() < deadline && producer.status().samples_processed < held + 9600 {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    producer.stop()?;
+    std::fs::remove_file(&path)?;
+
+    let samples = drain(&ring);
+    assert!(samples.len() >= held as usize + 9600);
+    assert!(
+        samples.iter().enumerate().all(|(i, s)| *s == (i % 4800) as i16),
+        "playback continues where it was paused"
+    );
+    Ok(())
+}
@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+
+use airlift_node::audio::archive::{list_recordings, record_file_at};
+use airlift_node::core::consumer::file_writer::FileConsumer;
+use airlift_node::core::file_rotation::component_matches;
+use airlift_node::core::timestamp::utc_ns_now;
+use airlift_node::core::{AudioRingBuffer, Consumer, FileRotation, Retention, TimeZone};
+use airlift_node::PcmFrame;
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("airlift-rotation-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn config(pairs: &[(&str, serde_json::Value)]) -> HashMap<String, serde_json::Value> {
+    pairs
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.clone()))
+        .collect()
+}
+
+fn files_in(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(dir)
+        .unwrap()
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn templates_expand_in_local_time() {
+    let tz = TimeZone::parse("+01:00").unwrap();
+    // 2023-11-14T22:13:20Z
+    let ms = 1_700_000_000_000;
+    assert_eq!(
+        tz.format_template("/archive/%Y/%m/%d/%H%M%S.wav", ms),
+        "/archive/2023/11/14/231320.wav"
+    );
+    assert_eq!(tz.format_template("%j %z %s 100%%", ms), "318 +0100 1700000000 100%");
+    assert_eq!(tz.format_template("%q", ms), "%q");
+}
+
+#[test]
+fn template_components_match_generated_names() {
+    assert!(component_matches("%H%M.wav", "2313.wav"));
+    assert!(!component_matches("%H%M.wav", "2313.wav.part"));
+    assert!(!component_matches("%H%M.wav", "231.wav"));
+    assert!(component_matches("%Y", "2023"));
+    assert!(component_matches("rec-{time}.wav", "rec-20231114T231320+0100_20231114T221320Z.wav"));
+    assert!(!component_matches("rec-{time}.wav", "other.wav"));
+}
+
+#[test]
+fn rotation_deadlines_follow_the_local_clock() {
+    let rotation = FileRotation {
+        every: Some(Duration::from_secs(900)),
+        max_bytes: None,
+    };
+    let tz = TimeZone::parse("+05:30").unwrap();
+    // 22:13:20Z = 03:43:20 local, nächste Viertelstunde 03:45 = 22:15Z
+    assert_eq!(rotation.deadline_ms(1_700_000_000_000, &tz), Some(1_700_000_100_000));
+    assert!(rotation.due(Some(1_700_000_100_000), 1_700_000_100_000, 0));
+    assert!(!rotation.due(Some(1_700_000_100_000), 1_700_000_099_999, 0));
+
+    let by_size = FileRotation {
+        every: None,
+        max_bytes: Some(2 * 1024 * 1024),
+    };
+    assert_eq!(by_size.deadline_ms(0, &tz), None);
+    assert!(by_size.due(None, 0, 2 * 1024 * 1024));
+}
+
+#[test]
+fn rotation_config_requires_time_code_and_sane_values() {
+    let every = config(&[("rotate_every", "1h".into())]);
+    let err = FileRotation::from_config("rec", "/tmp/rec.wav", &every)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("time code"), "{}", err);
+    let rotation = FileRotation::from_config("rec", "/tmp/%H%M.wav", &every)
+        .unwrap()
+        .unwrap();
+    assert_eq!(rotation.every, Some(Duration::from_secs(3600)));
+
+    let tiny = config(&[("rotate_size", "10KB".into())]);
+    let err = FileRotation::from_config("rec", "/tmp/%H%M.wav", &tiny)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("config.rotate_size"), "{}", err);
+    assert!(FileRotation::from_config("rec", "/tmp/rec.wav", &HashMap::new())
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn retention_removes_oldest_segments_and_manifest_entries() {
+    let dir = temp_dir("retention");
+    let template = format!("{}/%Y%m%d/%H%M.wav", dir.display());
+    let day = dir.join("20231114");
+    fs::create_dir_all(&day).unwrap();
+    for (index, name) in ["2200.wav", "2215.wav", "2230.wav"].iter().enumerate() {
+        let path = day.join(name);
+        fs::write(&path, vec![index as u8; 100]).unwrap();
+        record_file_at(&path, 900_000, 1_700_000_000_000).unwrap();
+        // Änderungszeiten auseinanderziehen
+        std::thread::sleep(Duration::from_millis(20));
+    }
+    fs::write(day.join("2245.wav.part"), b"open").unwrap();
+    fs::write(day.join("notes.txt"), b"keep").unwrap();
+
+    let retention = Retention::new(&template, Some(2), None);
+    assert_eq!(retention.segments().len(), 3);
+    let removed = retention.apply(SystemTime::now());
+    assert_eq!(removed, vec![day.join("2200.wav")]);
+    assert_eq!(
+        files_in(&day),
+        vec!["2215.wav", "2230.wav", "2245.wav.part", "manifest-2023-11-14.json", "notes.txt"]
+    );
+    let recordings: Vec<String> = list_recordings(&day)
+        .into_iter()
+        .map(|recording| recording.entry.file)
+        .collect();
+    assert_eq!(recordings, vec!["2215.wav", "2230.wav"]);
+
+    // Alles älter als 0 s fällt weg; das leere Tagesverzeichnis bleibt wegen notes.txt
+    let all = Retention::new(&template, None, Some(Duration::ZERO));
+    assert_eq!(all.apply(SystemTime::now() + Duration::from_secs(1)).len(), 2);
+    assert!(list_recordings(&day).is_empty());
+    assert_eq!(files_in(&day), vec!["2245.wav.part", "notes.txt"]);
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn file_consumer_rotates_on_frame_time_and_finalizes_atomically() {
+    let dir = temp_dir("consumer");
+    let template = format!("{}/rec-%H%M%S.wav", dir.display());
+    let cfg = config(&[("rotate_every", "10s".into())]);
+    let mut consumer =
+        FileConsumer::from_config("rec", &template, &cfg, TimeZone::utc()).unwrap();
+    let buffer = Arc::new(AudioRingBuffer::new(4096));
+    consumer.attach_input_buffer(buffer.clone());
+    consumer.start().unwrap();
+
+    // 25 s Audio mit Zeitstempeln ab jetzt: zwei 10-s-Grenzen
+    let start_ns = utc_ns_now();
+    for index in 0..250u64 {
+        buffer.push(PcmFrame {
+            utc_ns: start_ns + index * 100_000_000,
+            samples: vec![1; 9600],
+            sample_rate: 48_000,
+            channels: 2,
+            metadata: Default::default(),
+        });
+    }
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while consumer.status().frames_processed < 250 && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(20));
+    }
+    consumer.stop().unwrap();
+
+    let wavs: Vec<String> = files_in(&dir)
+        .into_iter()
+        .filter(|name| name.ends_with(".wav"))
+        .collect();
+    assert!(wavs.len() == 3 || wavs.len() == 4, "{:?}", wavs);
+    assert!(files_in(&dir).iter().all(|name| !name.ends_with(".part")));
+    let total: u32 = wavs
+        .iter()
+        .map(|name| hound::WavReader::open(dir.join(name)).unwrap().duration())
+        .sum();
+    assert_eq!(total, 250 * 4800);
+
+    let _ = fs::remove_dir_all(&dir);
+}
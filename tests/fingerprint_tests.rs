@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+use std::f64::consts::PI;
+use std::time::{Duration, Instant};
+
+use airlift_node::audio::fingerprint::{similarity, Fingerprinter};
+use airlift_node::core::processor::Processor;
+use airlift_node::processors::FingerprintProcessor;
+use airlift_node::PcmFrame;
+
+const RATE: u32 = 48_000;
+
+/// `seconds` Mono-Melodie (Töne à 300 ms, Grundton plus Oktave) in
+/// 100-ms-Frames; `seed` wählt die Tonfolge
+fn melody(seed: u64, gain: f64, seconds: usize) -> Vec<PcmFrame> {
+    let mut state = seed;
+    let notes: Vec<f64> = (0..seconds * 4)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            220.0 * 2f64.powf((state >> 59) as f64 / 12.0)
+        })
+        .collect();
+    let samples: Vec<i16> = (0..seconds * RATE as usize)
+        .map(|n| {
+            let t = n as f64 / RATE as f64;
+            let hz = notes[(t / 0.3) as usize];
+            (gain * (8000.0 * (2.0 * PI * hz * t).sin() + 3000.0 * (4.0 * PI * hz * t).sin()))
+                as i16
+        })
+        .collect();
+    samples
+        .chunks(4800)
+        .enumerate()
+        .map(|(i, chunk)| PcmFrame {
+            utc_ns: i as u64 * 100_000_000,
+            samples: chunk.to_vec(),
+            sample_rate: RATE,
+            channels: 1,
+            metadata: Default::default(),
+        })
+        .collect()
+}
+
+fn hashes(frames: &[PcmFrame]) -> Vec<u32> {
+    let mut fingerprinter = Fingerprinter::new();
+    frames
+        .iter()
+        .flat_map(|frame| fingerprinter.push(frame))
+        .map(|(_, hash)| hash)
+        .collect()
+}
+
+fn fingerprint(config: serde_json::Value) -> anyhow::Result<FingerprintProcessor> {
+    let config: HashMap<String, serde_json::Value> = serde_json::from_value(config)?;
+    FingerprintProcessor::from_config("verify", &config)
+}
+
+#[test]
+fn fingerprints_survive_gain_changes_and_separate_programs() {
+    let reference = hashes(&melody(7, 1.0, 10));
+    assert_eq!(reference.len(), 100);
+
+    let quieter = hashes(&melody(7, 0.25, 10));
+    let same = similarity(&reference, &quieter);
+    assert!(same > 0.95, "same program {}", same);
+
+    let other = hashes(&melody(42, 1.0, 10));
+    let different = similarity(&reference, &other);
+    assert!(different < 0.8, "other program {}", different);
+
+    assert!(hashes(&melody(7, 0.0, 1)).iter().all(|hash| *hash == 0));
+}
+
+#[test]
+fn windows_are_written_as_json_lines() -> anyhow::Result<()> {
+    let path =
+        std::env::temp_dir().join(format!("airlift-fingerprint-{}.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let mut processor = fingerprint(serde_json::json!({
+        "window": "2s",
+        "interval": "1s",
+        "file": path.to_string_lossy(),
+    }))?;
+
+    // Fenster bei 2, 3, 4 und 5 s; das Audio bleibt unverändert
+    let mut frames = melody(7, 1.0, 5);
+    let original = frames[0].samples.clone();
+    processor.process_batch(&mut frames)?;
+    assert_eq!(frames[0].samples, original);
+    assert_eq!(processor.windows(), 4);
+
+    let deadline = Instant::now() + Duration::from_secs(2);
+    let mut lines = Vec::new();
+    while Instant::now() < deadline {
+        lines = std::fs::read_to_string(&path)
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+            .collect();
+        if lines.len() == 4 {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    std::fs::remove_file(&path)?;
+    assert_eq!(lines.len(), 4);
+    assert_eq!(lines[0]["processor"], "verify");
+    assert_eq!(lines[0]["hashes"], 20);
+    assert_eq!(lines[0]["start_utc_ns"], 0);
+    assert_eq!(lines[0]["end_utc_ns"], 2_000_000_000u64);
+    assert_eq!(lines[3]["start_utc_ns"], 3_000_000_000u64);
+    // 20 Hashes à 4 Byte, Base64
+    assert_eq!(lines[0]["fingerprint"].as_str().unwrap().len(), 108);
+
+    assert!(fingerprint(serde_json::json!({ "window": "5s" })).is_err());
+    assert!(fingerprint(serde_json::json!({ "url": "ftp://example" })).is_err());
+    assert!(fingerprint(serde_json::json!({
+        "window": "5s",
+        "interval": "10s",
+        "url": "http://127.0.0.1/fp",
+    }))
+    .is_err());
+    Ok(())
+}
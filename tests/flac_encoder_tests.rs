@@ -0,0 +1,209 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use airlift_node::codecs::flac::FlacEncoder;
+use airlift_node::codecs::{create_encoder, AudioCodec, FlacEncoderOptions};
+use airlift_node::core::consumer::file_writer::{FileConsumer, FileFormat};
+use airlift_node::core::timestamp::utc_ns_now;
+use airlift_node::core::{AudioRingBuffer, Consumer, TimeZone};
+use airlift_node::PcmFrame;
+use md5::{Digest, Md5};
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("airlift-flac-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Stereo-Mischung aus Tönen und leisem Rauschen, in 100-ms-Blöcken
+fn program(blocks: usize) -> Vec<Vec<i16>> {
+    let mut seed = 0x2545_f491u32;
+    let mut noise = move || {
+        seed ^= seed << 13;
+        seed ^= seed >> 17;
+        seed ^= seed << 5;
+        (seed % 129) as f64 - 64.0
+    };
+    (0..blocks)
+        .map(|block| {
+            (0..4800)
+                .flat_map(|i| {
+                    let t = (block * 4800 + i) as f64 / 48_000.0;
+                    let tone = (t * 220.0 * std::f64::consts::TAU).sin() * 6000.0
+                        + (t * 331.0 * std::f64::consts::TAU).sin() * 3000.0;
+                    let left = tone + noise();
+                    let right =
+                        tone * 0.8 + (t * 1250.0 * std::f64::consts::TAU).sin() * 1500.0 + noise();
+                    [left as i16, right as i16]
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// STREAMINFO-Felder: (min/max Blockgröße, Rate, Kanäle, Bits, Samples, MD5)
+fn streaminfo(data: &[u8]) -> (u16, u16, u32, u8, u8, u64, [u8; 16]) {
+    assert_eq!(&data[..4], b"fLaC");
+    assert_eq!(data[4] & 0x7F, 0, "STREAMINFO first");
+    let info = &data[8..42];
+    let packed = u64::from_be_bytes(info[10..18].try_into().unwrap());
+    (
+        u16::from_be_bytes([info[0], info[1]]),
+        u16::from_be_bytes([info[2], info[3]]),
+        (packed >> 44) as u32,
+        ((packed >> 41) & 0x7) as u8 + 1,
+        ((packed >> 36) & 0x1F) as u8 + 1,
+        packed & 0xF_FFFF_FFFF,
+        info[18..34].try_into().unwrap(),
+    )
+}
+
+fn encode(options: &FlacEncoderOptions, blocks: &[Vec<i16>]) -> anyhow::Result<Vec<u8>> {
+    let mut encoder = FlacEncoder::with_options(2, options)?;
+    let mut data = Vec::new();
+    for block in blocks {
+        for frame in encoder.encode(block)? {
+            data.extend_from_slice(&frame.payload);
+        }
+    }
+    for frame in encoder.flush()? {
+        data.extend_from_slice(&frame.payload);
+    }
+    let header = encoder.final_header().expect("final header after flush");
+    data[..header.len()].copy_from_slice(&header);
+    Ok(data)
+}
+
+#[cfg(feature = "symphonia")]
+#[test]
+fn every_level_decodes_back_to_the_input() -> anyhow::Result<()> {
+    let dir = temp_dir("roundtrip");
+    // 1,25 s: der letzte Block ist kürzer als die Blockgröße
+    let mut blocks = program(12);
+    blocks.push(blocks[0][..4800].to_vec());
+    let input: Vec<i16> = blocks.concat();
+    let md5: [u8; 16] = Md5::digest(
+        input
+            .iter()
+            .flat_map(|sample| sample.to_le_bytes())
+            .collect::<Vec<u8>>(),
+    )
+    .into();
+
+    for level in [0, 3, 5, 8] {
+        let options = FlacEncoderOptions {
+            compression_level: level,
+        };
+        let data = encode(&options, &blocks)?;
+        let (min_block, max_block, rate, channels, bits, samples, digest) = streaminfo(&data);
+        assert_eq!(
+            (min_block as usize, max_block as usize),
+            (options.block_size(), options.block_size())
+        );
+        assert_eq!((rate, channels, bits), (48_000, 2, 16));
+        assert_eq!(samples, input.len() as u64 / 2, "level {}", level);
+        assert_eq!(digest, md5, "level {}", level);
+
+        let path = dir.join(format!("level{}.flac", level));
+        fs::write(&path, &data)?;
+        let mut reader = airlift_node::producers::file::open_audio_file(&path)?;
+        assert_eq!(reader.info().codec, "flac");
+        let mut decoded = Vec::new();
+        while let Some(block) = reader.read_block()? {
+            decoded.extend(block);
+        }
+        assert!(decoded == input, "level {} is not lossless", level);
+    }
+
+    let _ = fs::remove_dir_all(&dir);
+    Ok(())
+}
+
+#[test]
+fn file_consumer_archives_at_about_half_the_pcm_size() -> anyhow::Result<()> {
+    let dir = temp_dir("archive");
+    let path = dir.join("program.flac");
+    let config: HashMap<String, serde_json::Value> =
+        serde_json::from_value(serde_json::json!({ "flac": { "compression_level": 8 } }))?;
+    let mut consumer =
+        FileConsumer::from_config("archive", path.to_str().unwrap(), &config, TimeZone::utc())?;
+    assert_eq!(consumer.format(), FileFormat::Flac);
+    let buffer = Arc::new(AudioRingBuffer::new(64));
+    consumer.attach_input_buffer(buffer.clone());
+    consumer.start()?;
+    let blocks = program(20);
+    for (index, samples) in blocks.iter().enumerate() {
+        buffer.push(PcmFrame {
+            utc_ns: utc_ns_now() + index as u64 * 100_000_000,
+            samples: samples.clone(),
+            sample_rate: 48_000,
+            channels: 2,
+            metadata: Default::default(),
+        });
+    }
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while consumer.status().frames_processed < 20 && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(20));
+    }
+    consumer.stop()?;
+
+    let data = fs::read(&path)?;
+    let (.., samples, digest) = streaminfo(&data);
+    assert_eq!(samples, 96_000);
+    assert_ne!(digest, [0; 16]);
+    let pcm_bytes = 96_000 * 2 * 2;
+    assert!(
+        data.len() * 10 < pcm_bytes * 6,
+        "{} bytes FLAC for {} bytes PCM",
+        data.len(),
+        pcm_bytes
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+    Ok(())
+}
+
+#[test]
+fn compression_level_comes_from_config() -> anyhow::Result<()> {
+    let parse = |value: serde_json::Value| {
+        FlacEncoderOptions::from_config("consumer", "archive", Some(&value))
+    };
+    assert_eq!(
+        FlacEncoderOptions::from_config("consumer", "archive", None)?.compression_level,
+        5
+    );
+    assert_eq!(
+        parse(serde_json::json!({ "compression_level": 0 }))?.block_size(),
+        1152
+    );
+    assert_eq!(
+        parse(serde_json::json!({ "compression_level": "8" }))?.block_size(),
+        4096
+    );
+    for broken in [
+        serde_json::json!({ "compression_level": 9 }),
+        serde_json::json!({ "compression_level": 2.5 }),
+        serde_json::json!({ "compression_level": "best" }),
+        serde_json::json!(8),
+    ] {
+        assert!(parse(broken.clone()).is_err(), "{}", broken);
+    }
+
+    // Die Registry kennt `flac`, auch für Surround
+    assert!(create_encoder("flac").is_ok());
+    assert!(FlacEncoder::new(6).is_ok());
+    assert!(FlacEncoder::new(9).is_err());
+
+    let config: HashMap<String, serde_json::Value> =
+        serde_json::from_value(serde_json::json!({ "flac": { "compression_level": 8 } }))?;
+    let err = FileConsumer::from_config("archive", "/tmp/archive.wav", &config, TimeZone::utc())
+        .err()
+        .map(|e| e.to_string())
+        .unwrap_or_default();
+    assert!(err.contains("config.flac needs format 'flac'"), "{}", err);
+    Ok(())
+}
@@ -13,12 +13,14 @@ fn e2e_producer_processor_consumer_flow() -> anyhow::Result<()> {
             samples: vec![1, 2, 3, 4],
             sample_rate: 48_000,
             channels: 2,
+            metadata: Default::default(),
         },
         PcmFrame {
             utc_ns: 2,
             samples: vec![5, 6, 7, 8],
             sample_rate: 48_000,
             channels: 2,
+            metadata: Default::default(),
         },
     ];
 
@@ -13,6 +13,7 @@ fn flow_with_processor_and_consumer_runs() -> anyhow::Result<()> {
             samples: vec![1, 2, 3, 4],
             sample_rate: 48_000,
             channels: 2,
+            metadata: Default::default(),
         },
     ];
 
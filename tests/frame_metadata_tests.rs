@@ -0,0 +1,113 @@
+use std::time::{Duration, Instant};
+
+use airlift_node::core::processor::basic::Gain;
+use airlift_node::core::{AirliftNode, AudioRingBuffer, Flow};
+use airlift_node::testing::mocks::{MockConsumer, MockProducer};
+use airlift_node::types::Samples;
+use airlift_node::{FrameMetadata, PcmFrame, Slots};
+use serde_json::json;
+
+fn frame(utc_ns: u64, metadata: FrameMetadata) -> PcmFrame {
+    PcmFrame {
+        utc_ns,
+        samples: vec![100; 8],
+        sample_rate: 48_000,
+        channels: 2,
+        metadata,
+    }
+}
+
+fn tagged(pairs: &[(&str, serde_json::Value)]) -> FrameMetadata {
+    let mut metadata = FrameMetadata::default();
+    for (key, value) in pairs {
+        metadata.insert(*key, value.clone());
+    }
+    metadata
+}
+
+#[test]
+fn metadata_is_copy_on_write_and_merges_without_overwriting() {
+    let empty = FrameMetadata::default();
+    assert!(empty.is_empty());
+    assert_eq!(serde_json::to_value(&empty).unwrap(), json!({}));
+
+    let mut upstream = tagged(&[("source", json!("studio1")), ("marker", json!("news"))]);
+    let shared = upstream.clone();
+    upstream.insert("lufs", json!(-23.1));
+    assert_eq!(shared.len(), 2, "clones keep their own entries");
+    assert_eq!(upstream.len(), 3);
+    assert_eq!(upstream.remove("marker"), Some(json!("news")));
+
+    let mut mixed = tagged(&[("source", json!("mic"))]);
+    mixed.merge(&shared);
+    assert_eq!(
+        serde_json::to_value(&mixed).unwrap(),
+        json!({ "source": "mic", "marker": "news" })
+    );
+}
+
+#[test]
+fn ring_buffers_keep_metadata_also_in_preallocated_slots() {
+    for buffer in [
+        AudioRingBuffer::new(4),
+        AudioRingBuffer::with_prealloc(Slots(4), Samples(64)),
+    ] {
+        // Zweiter Durchlauf überschreibt vorhandene Slots
+        for round in 0..2u64 {
+            for index in 0..4u64 {
+                let utc_ns = round * 4 + index;
+                let metadata = if index % 2 == 0 {
+                    tagged(&[("seq", json!(utc_ns))])
+                } else {
+                    FrameMetadata::default()
+                };
+                buffer.push(frame(utc_ns, metadata));
+            }
+            for index in 0..4u64 {
+                let utc_ns = round * 4 + index;
+                let popped = buffer.pop_for_reader("reader").unwrap();
+                assert_eq!(popped.utc_ns, utc_ns);
+                assert_eq!(
+                    popped.metadata.get("seq").cloned(),
+                    (index % 2 == 0).then(|| json!(utc_ns))
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn metadata_survives_a_flow_with_processors() -> anyhow::Result<()> {
+    let frames = vec![
+        frame(
+            1,
+            tagged(&[("source", json!("sat")), ("marker", json!("top_of_hour"))]),
+        ),
+        frame(2, FrameMetadata::default()),
+    ];
+    let producer = MockProducer::new("sat", frames.clone());
+    let (consumer, received) = MockConsumer::new_with_shared("recorder");
+
+    let mut flow = Flow::new("program");
+    flow.add_processor(Box::new(Gain::new("gain", 0.5)));
+    flow.add_consumer(Box::new(consumer));
+
+    let mut node = AirliftNode::new();
+    node.add_flow(flow);
+    node.add_producer(Box::new(producer))?;
+    node.connect_flow_input(0, "producer:sat")?;
+    node.start()?;
+
+    let deadline = Instant::now() + Duration::from_secs(2);
+    while received.lock().unwrap().len() < frames.len() && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    node.stop()?;
+
+    let received = received.lock().unwrap();
+    assert!(received.len() >= 2, "got {} frames", received.len());
+    assert_eq!(received[0].samples, vec![50; 8]);
+    assert_eq!(received[0].metadata, frames[0].metadata);
+    assert!(received[1].metadata.is_empty());
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use std::collections::HashMap;
+
+use airlift_node::config::ProducerConfig;
+use airlift_node::producers::generator::{GeneratorConfig, Signal, SignalGenerator};
+
+fn config(values: serde_json::Value) -> ProducerConfig {
+    ProducerConfig {
+        producer_type: "generator".to_string(),
+        config: serde_json::from_value::<HashMap<String, serde_json::Value>>(values).unwrap(),
+        ..ProducerConfig::default()
+    }
+}
+
+fn generator(values: serde_json::Value) -> SignalGenerator {
+    SignalGenerator::new(GeneratorConfig::from_producer_config("gen", &config(values)).unwrap())
+}
+
+fn peak(samples: &[i16]) -> i16 {
+    samples.iter().map(|s| s.saturating_abs()).max().unwrap_or(0)
+}
+
+#[test]
+fn sine_peak_matches_level() {
+    let mut sine = generator(serde_json::json!({ "signal": "sine", "level_db": "-6dB" }));
+    let samples = sine.next_samples(4800);
+    // -6 dBFS ≈ 0.501 · 32767
+    assert!((peak(&samples) as i32 - 16_422).abs() < 40, "peak {}", peak(&samples));
+}
+
+#[test]
+fn glits_interrupts_left_once_and_right_twice() {
+    let mut glits = generator(serde_json::json!({ "signal": "glits" }));
+    // 10-ms-Blöcke über einen 4-s-Zyklus: stille Blöcke je Kanal zählen
+    let (mut left_silent, mut right_silent) = (0, 0);
+    for _ in 0..400 {
+        let block = glits.next_samples(480);
+        let left: Vec<i16> = block.iter().step_by(2).copied().collect();
+        let right: Vec<i16> = block.iter().skip(1).step_by(2).copied().collect();
+        left_silent += usize::from(peak(&left) == 0);
+        right_silent += usize::from(peak(&right) == 0);
+    }
+    assert_eq!(left_silent, 25);
+    assert_eq!(right_silent, 50);
+}
+
+#[test]
+fn noise_stays_within_level_and_is_not_silent() {
+    for signal in ["white", "pink"] {
+        let mut noise = generator(serde_json::json!({ "signal": signal, "level_db": -12 }));
+        let samples = noise.next_samples(48_000);
+        let limit = (0.2512 * 32767.0) as i16 + 1;
+        assert!(peak(&samples) <= limit, "{} peak {}", signal, peak(&samples));
+        assert!(peak(&samples) > limit / 4, "{} too quiet", signal);
+    }
+}
+
+#[test]
+fn invalid_generator_config_is_rejected() {
+    let parse = |values| GeneratorConfig::from_producer_config("gen", &config(values));
+    assert!(parse(serde_json::json!({ "signal": "chirp" })).is_err());
+    assert!(parse(serde_json::json!({ "signal": "sine", "frequency": 30_000 })).is_err());
+    assert!(parse(serde_json::json!({ "level_db": 3 })).is_err());
+    assert_eq!(
+        parse(serde_json::json!({ "signal": "multitone", "frequencies": [440, 880] }))
+            .unwrap()
+            .signal,
+        Signal::Multitone {
+            frequencies: vec![440.0, 880.0]
+        }
+    );
+}
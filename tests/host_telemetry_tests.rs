@@ -0,0 +1,123 @@
+use std::path::Path;
+
+use airlift_node::core::host_telemetry::{
+    mount_for, parse_loadavg, parse_mountinfo, parse_proc_stat, parse_smartctl, read_hwmon,
+};
+
+#[test]
+fn load_and_cpu_usage_are_parsed_from_proc() {
+    assert_eq!(
+        parse_loadavg("0.52 0.58 0.59 1/467 12345\n"),
+        Some([0.52, 0.58, 0.59])
+    );
+    assert_eq!(parse_loadavg("garbage"), None);
+
+    let before = parse_proc_stat(
+        "cpu  100 0 100 700 100 0 0 0 0 0\n\
+         cpu0 50 0 50 350 50 0 0 0 0 0\n\
+         cpu1 50 0 50 350 50 0 0 0 0 0\n\
+         intr 12345 0 0\n",
+    );
+    let after = parse_proc_stat(
+        "cpu  200 0 200 800 100 0 0 0 0 0\n\
+         cpu0 150 0 100 350 50 0 0 0 0 0\n\
+         cpu1 50 0 50 450 50 0 0 0 0 0\n",
+    );
+    assert_eq!(before.len(), 3);
+    assert_eq!(before[0].0, None);
+    assert_eq!(before[2].0, Some(1));
+
+    // Gesamt: 200 Jiffies Arbeit von 300; cpu0 voll ausgelastet, cpu1 idle
+    assert_eq!(after[0].1.usage_since(&before[0].1), Some(2.0 / 3.0));
+    assert_eq!(after[1].1.usage_since(&before[1].1), Some(1.0));
+    assert_eq!(after[2].1.usage_since(&before[2].1), Some(0.0));
+    assert_eq!(
+        before[0].1.usage_since(&after[0].1),
+        None,
+        "counter went backwards"
+    );
+}
+
+#[test]
+fn hwmon_temperatures_use_labels_when_present() -> anyhow::Result<()> {
+    let root = std::env::temp_dir().join(format!("airlift-hwmon-{}", std::process::id()));
+    let coretemp = root.join("hwmon0");
+    std::fs::create_dir_all(&coretemp)?;
+    std::fs::write(coretemp.join("name"), "coretemp\n")?;
+    std::fs::write(coretemp.join("temp1_input"), "52000\n")?;
+    std::fs::write(coretemp.join("temp1_label"), "Package id 0\n")?;
+    std::fs::write(coretemp.join("temp2_input"), "48500\n")?;
+    std::fs::write(coretemp.join("fan1_input"), "1200\n")?;
+
+    let temperatures = read_hwmon(&root);
+    assert_eq!(temperatures.len(), 2);
+    assert_eq!(temperatures[0].chip, "coretemp");
+    assert_eq!(temperatures[0].sensor, "Package id 0");
+    assert_eq!(temperatures[0].celsius, 52.0);
+    assert_eq!(temperatures[1].sensor, "temp2");
+    assert_eq!(temperatures[1].celsius, 48.5);
+
+    assert!(read_hwmon(&root.join("missing")).is_empty());
+    std::fs::remove_dir_all(&root)?;
+    Ok(())
+}
+
+#[test]
+fn recording_paths_resolve_to_their_mount() {
+    let mounts = parse_mountinfo(
+        "22 1 8:2 / / rw,relatime shared:1 - ext4 /dev/sda2 rw\n\
+         30 22 8:17 / /srv/audio\\040archive rw,noatime shared:2 - xfs /dev/sdb1 rw\n\
+         31 22 0:25 / /proc rw - proc proc rw\n",
+    );
+    assert_eq!(mounts.len(), 3);
+
+    let archive = mount_for(Path::new("/srv/audio archive/studio/2026"), &mounts).unwrap();
+    assert_eq!(archive.device, "/dev/sdb1");
+    assert_eq!(archive.mount_point, Path::new("/srv/audio archive"));
+    assert_eq!(
+        mount_for(Path::new("/var/lib/airlift"), &mounts)
+            .unwrap()
+            .device,
+        "/dev/sda2"
+    );
+    // Nur ganze Pfad-Komponenten zählen
+    assert_eq!(
+        mount_for(Path::new("/srv/audio"), &mounts).unwrap().device,
+        "/dev/sda2"
+    );
+}
+
+#[test]
+fn smartctl_json_reports_health_for_ata_and_nvme() {
+    let ata = parse_smartctl(
+        r#"{
+            "smart_status": {"passed": true},
+            "temperature": {"current": 38},
+            "power_on_time": {"hours": 21034},
+            "ata_smart_attributes": {"table": [
+                {"id": 9, "raw": {"value": 21034}},
+                {"id": 5, "raw": {"value": 8}}
+            ]}
+        }"#,
+        1_000,
+    )
+    .unwrap();
+    assert!(ata.passed);
+    assert_eq!(ata.temperature_celsius, Some(38.0));
+    assert_eq!(ata.power_on_hours, Some(21034));
+    assert_eq!(ata.reallocated_sectors, Some(8));
+    assert_eq!(ata.checked_at_ms, 1_000);
+
+    let nvme = parse_smartctl(
+        r#"{"smart_status": {"passed": false}, "nvme_smart_health_information_log": {"media_errors": 3}}"#,
+        2_000,
+    )
+    .unwrap();
+    assert!(!nvme.passed);
+    assert_eq!(nvme.reallocated_sectors, Some(3));
+    assert_eq!(nvme.temperature_celsius, None);
+
+    // Ohne Health-Status (z. B. fehlende Rechte) kein Ergebnis
+    assert!(parse_smartctl(r#"{"smartctl": {"exit_status": 2}}"#, 0).is_none());
+    assert!(parse_smartctl("not json", 0).is_none());
+}
@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+use airlift_node::core::processor::Processor;
+use airlift_node::processors::HumFilter;
+use airlift_node::PcmFrame;
+
+const RATE: u32 = 48_000;
+
+fn hum_filter(config: serde_json::Value) -> anyhow::Result<HumFilter> {
+    let config: HashMap<String, serde_json::Value> = serde_json::from_value(config)?;
+    HumFilter::from_config("clean", &config)
+}
+
+/// Amplitude der Komponente bei `frequency` (Korrelation über ganze Perioden)
+fn amplitude(samples: &[i16], frequency: f64) -> f64 {
+    let (mut sin, mut cos) = (0.0, 0.0);
+    for (n, sample) in samples.iter().enumerate() {
+        let phase = 2.0 * PI * frequency * n as f64 / RATE as f64;
+        sin += *sample as f64 * phase.sin();
+        cos += *sample as f64 * phase.cos();
+    }
+    2.0 * (sin * sin + cos * cos).sqrt() / samples.len() as f64
+}
+
+/// 2 s Mono: 1-kHz-Programm, 50-Hz-Brumm mit Oberwelle und DC-Versatz,
+/// in 100-ms-Frames
+fn dirty_signal() -> Vec<PcmFrame> {
+    let samples: Vec<i16> = (0..2 * RATE as usize)
+        .map(|n| {
+            let t = n as f64 / RATE as f64;
+            (3000.0
+                + 8000.0 * (2.0 * PI * 1000.0 * t).sin()
+                + 4000.0 * (2.0 * PI * 50.0 * t).sin()
+                + 2000.0 * (2.0 * PI * 150.0 * t).sin())
+            .round() as i16
+        })
+        .collect();
+    samples
+        .chunks(4800)
+        .enumerate()
+        .map(|(i, chunk)| PcmFrame {
+            utc_ns: i as u64 * 100_000_000,
+            samples: chunk.to_vec(),
+            sample_rate: RATE,
+            channels: 1,
+            metadata: Default::default(),
+        })
+        .collect()
+}
+
+#[test]
+fn removes_dc_and_mains_hum_but_keeps_program() -> anyhow::Result<()> {
+    let mut filter = hum_filter(serde_json::json!({ "mains": 50 }))?;
+    let mut frames = dirty_signal();
+    filter.process_batch(&mut frames)?;
+
+    // Zweite Sekunde, nach dem Einschwingen
+    let settled: Vec<i16> = frames[10..]
+        .iter()
+        .flat_map(|frame| frame.samples.iter().copied())
+        .collect();
+    let mean = settled.iter().map(|s| *s as f64).sum::<f64>() / settled.len() as f64;
+    assert!(mean.abs() < 20.0, "dc {}", mean);
+    assert!(
+        amplitude(&settled, 50.0) < 40.0,
+        "50 Hz {}",
+        amplitude(&settled, 50.0)
+    );
+    assert!(
+        amplitude(&settled, 150.0) < 40.0,
+        "150 Hz {}",
+        amplitude(&settled, 150.0)
+    );
+    let program = amplitude(&settled, 1000.0);
+    assert!((program - 8000.0).abs() < 80.0, "1 kHz {}", program);
+    Ok(())
+}
+
+#[test]
+fn dc_only_by_default_and_config_validation() -> anyhow::Result<()> {
+    let mut filter = hum_filter(serde_json::json!({}))?;
+    let mut frames = dirty_signal();
+    filter.process_batch(&mut frames)?;
+    let settled: Vec<i16> = frames[10..]
+        .iter()
+        .flat_map(|frame| frame.samples.iter().copied())
+        .collect();
+    let mean = settled.iter().map(|s| *s as f64).sum::<f64>() / settled.len() as f64;
+    assert!(mean.abs() < 20.0, "dc {}", mean);
+    // Ohne `mains` bleibt der Brumm stehen
+    assert!(amplitude(&settled, 50.0) > 3500.0);
+
+    filter.update_config(serde_json::json!({ "enabled": false }))?;
+    let mut untouched = dirty_signal();
+    let original = untouched[0].samples.clone();
+    filter.process_batch(&mut untouched)?;
+    assert_eq!(untouched[0].samples, original);
+
+    assert!(hum_filter(serde_json::json!({ "mains": 55 })).is_err());
+    assert!(hum_filter(serde_json::json!({ "mains": 60, "q": 500 })).is_err());
+    assert!(hum_filter(serde_json::json!({ "dc_cutoff": 0 })).is_err());
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use std::collections::HashMap;
+
+use airlift_node::app::init::build_plugin_registry;
+use airlift_node::config::ProcessorConfig;
+use airlift_node::core::Flow;
+
+fn write_ident(path: &std::path::Path) -> anyhow::Result<()> {
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: 48_000,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut writer = hound::WavWriter::create(path, spec)?;
+    for _ in 0..4800 {
+        writer.write_sample(1000i16)?;
+    }
+    writer.finalize()?;
+    Ok(())
+}
+
+#[test]
+fn ident_processor_is_configurable_at_runtime() -> anyhow::Result<()> {
+    let path = std::env::temp_dir().join(format!("airlift-ident-{}.wav", std::process::id()));
+    write_ident(&path)?;
+
+    let mut config = HashMap::new();
+    config.insert("file".to_string(), serde_json::json!(path.to_string_lossy()));
+    config.insert("interval".to_string(), serde_json::json!("10m"));
+    let processor_cfg = ProcessorConfig {
+        processor_type: "ident".to_string(),
+        enabled: true,
+        config,
+    };
+
+    let processor = build_plugin_registry().create_processor("ident", &processor_cfg)?;
+    let mut flow = Flow::new("flow");
+    flow.add_processor(processor);
+
+    flow.update_processor_config("ident", serde_json::json!({ "enabled": false }))?;
+    assert!(flow
+        .update_processor_config("ident", serde_json::json!({ "duck_db": 3 }))
+        .is_err());
+    assert!(flow
+        .update_processor_config("missing", serde_json::json!({}))
+        .is_err());
+
+    std::fs::remove_file(&path)?;
+    Ok(())
+}
+
+#[test]
+fn ident_processor_requires_file() {
+    let processor_cfg = ProcessorConfig {
+        processor_type: "ident".to_string(),
+        enabled: true,
+        config: HashMap::new(),
+    };
+    assert!(build_plugin_registry()
+        .create_processor("ident", &processor_cfg)
+        .is_err());
+}
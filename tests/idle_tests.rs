@@ -0,0 +1,64 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use airlift_node::core::idle::{IdleBackoff, MAX_IDLE};
+use airlift_node::core::{AudioRingBuffer, PcmFrame};
+use airlift_node::producers::wait::StopWait;
+
+fn frame() -> PcmFrame {
+    PcmFrame {
+        utc_ns: 0,
+        samples: vec![0; 960],
+        sample_rate: 48_000,
+        channels: 2,
+        metadata: Default::default(),
+    }
+}
+
+#[test]
+fn backoff_grows_after_grace_and_snaps_back() {
+    let min = Duration::from_millis(2);
+    let mut idle = IdleBackoff::new(min).with_grace(Duration::from_millis(30));
+
+    assert_eq!(idle.next_wait(), min);
+    std::thread::sleep(Duration::from_millis(40));
+    let waits: Vec<Duration> = (0..10).map(|_| idle.next_wait()).collect();
+    assert_eq!(&waits[..4], [min, min * 2, min * 4, min * 8]);
+    assert_eq!(waits[9], MAX_IDLE);
+    assert!(idle.is_backed_off());
+
+    idle.reset();
+    assert!(!idle.is_backed_off());
+    assert_eq!(idle.next_wait(), min);
+
+    let mut capped = IdleBackoff::new(min)
+        .with_grace(Duration::ZERO)
+        .with_max(Duration::from_millis(5));
+    assert_eq!((0..4).map(|_| capped.next_wait()).last(), Some(Duration::from_millis(5)));
+}
+
+#[test]
+fn push_wakes_a_backed_off_reader() {
+    let buffer = Arc::new(AudioRingBuffer::new(16));
+    let wait = Arc::new(StopWait::new());
+    let mut idle = IdleBackoff::new(Duration::from_millis(2))
+        .with_grace(Duration::ZERO)
+        .with_max(Duration::from_secs(5));
+    // Lange Wartezeit erzwingen
+    for _ in 0..12 {
+        idle.next_wait();
+    }
+
+    let producer = buffer.clone();
+    let pusher = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(100));
+        producer.push(frame());
+    });
+    let started = Instant::now();
+    idle.wait_for(&buffer, &wait);
+    let waited = started.elapsed();
+    pusher.join().unwrap();
+
+    assert!(waited >= Duration::from_millis(50), "{:?}", waited);
+    assert!(waited < Duration::from_secs(2), "{:?}", waited);
+}
@@ -56,6 +56,7 @@ fn test_buffer_tracking() {
             samples: vec![i as i16; 480 * 2], // 480 Frames Stereo
             sample_rate: 48000,
             channels: 2,
+            metadata: Default::default(),
         };
         buffer.push(frame);
         
@@ -14,6 +14,7 @@ fn test_ringbuffer_basic() {
         samples: vec![1, 2, 3, 4],
         sample_rate: 48000,
         channels: 2,
+        metadata: Default::default(),
     };
 
     let new_len = buffer.push(frame);
@@ -34,6 +35,7 @@ fn test_ringbuffer_multi_reader() {
             samples: vec![i as i16; 48],
             sample_rate: 48000,
             channels: 2,
+            metadata: Default::default(),
         };
         buffer.push(frame);
     }
@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+
+use airlift_node::api::peaks::PeakPoint;
+use airlift_node::config::Config;
+use airlift_node::core::labels::{self, parse_labels};
+use airlift_node::core::{Event, EventPriority, EventType};
+use airlift_node::storage::influx::{event_line, peak_line};
+use serde_json::json;
+
+const CONFIG: &str = r#"
+node_name = "studio"
+
+[producers.mic]
+type = "sine"
+enabled = true
+config = { labels = { site = "studio-b", rack = 3 } }
+
+[processors]
+
+[consumers.rec]
+type = "file"
+enabled = true
+path = "/tmp/rec.wav"
+
+[flows.main]
+enabled = true
+inputs = ["mic"]
+processors = []
+outputs = ["rec"]
+config = { labels = { site = "studio b", transmitter = "north" } }
+"#;
+
+#[test]
+fn labels_are_validated() -> anyhow::Result<()> {
+    let config: HashMap<String, serde_json::Value> =
+        serde_json::from_value(json!({ "labels": { "site": " studio-b ", "live": true } }))?;
+    let labels = parse_labels("flow", "main", &config)?;
+    assert_eq!(labels.get("site").map(String::as_str), Some("studio-b"));
+    assert_eq!(labels.get("live").map(String::as_str), Some("true"));
+    assert!(parse_labels("flow", "main", &HashMap::new())?.is_empty());
+
+    for (bad, message) in [
+        (json!({ "labels": "site=studio-b" }), "table"),
+        (json!({ "labels": { "1site": "a" } }), "must match"),
+        (json!({ "labels": { "__name__": "a" } }), "must match"),
+        (json!({ "labels": { "site-name": "a" } }), "must match"),
+        (json!({ "labels": { "flow": "a" } }), "reserved"),
+        (json!({ "labels": { "site": "" } }), "characters"),
+        (json!({ "labels": { "site": ["a"] } }), "string"),
+    ] {
+        let config: HashMap<String, serde_json::Value> = serde_json::from_value(bad.clone())?;
+        let err = parse_labels("flow", "main", &config)
+            .err()
+            .map(|e| e.to_string())
+            .unwrap_or_default();
+        assert!(err.contains(message), "{}: {}", bad, err);
+    }
+
+    let broken = CONFIG.replace("transmitter = \"north\"", "buffer = \"north\"");
+    assert!(Config::from_toml(&broken)?.validate().is_err());
+    Ok(())
+}
+
+#[test]
+fn labels_reach_events_and_influx_tags() -> anyhow::Result<()> {
+    let config = Config::from_toml(CONFIG)?;
+    config.validate()?;
+    labels::install(config.labels()?);
+
+    assert_eq!(labels::flow_labels("main").get("transmitter").map(String::as_str), Some("north"));
+    assert_eq!(labels::buffer_labels("producer:mic").get("rack").map(String::as_str), Some("3"));
+    assert_eq!(labels::buffer_labels("flow:main:output").len(), 2);
+    assert!(labels::buffer_labels("failover:main").is_empty());
+
+    let event = Event::new(EventType::FlowStateChanged, EventPriority::Info, "flow", "main", json!({}));
+    assert_eq!(event.labels.get("site").map(String::as_str), Some("studio b"));
+    let serialized = serde_json::to_value(&event)?;
+    assert_eq!(serialized["labels"]["transmitter"], "north");
+    let unlabeled = Event::new(EventType::Error, EventPriority::Info, "consumer", "rec", json!({}));
+    assert!(serde_json::to_value(&unlabeled)?.get("labels").is_none());
+
+    let line = event_line(&event)?;
+    assert!(
+        line.starts_with("airlift_event,priority=Info,source=flow,site=studio\\ b,transmitter=north event="),
+        "{}",
+        line
+    );
+    let point = PeakPoint {
+        ts: 1_000,
+        peak_l: 0.5,
+        peak_r: 0.25,
+        silence: false,
+        flow: "main".to_string(),
+    };
+    assert_eq!(
+        peak_line(&point),
+        "airlift_peak,flow=main,site=studio\\ b,transmitter=north peak_l=0.5,peak_r=0.25,silence=false 1000000000"
+    );
+    Ok(())
+}
@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use airlift_node::api::peaks::PeakHistoryHandler;
+use airlift_node::core::processor::basic::Gain;
+use airlift_node::core::processor::Processor;
+use airlift_node::core::{AirliftNode, EventBus, EventEmitter, Flow, PeakRates};
+use airlift_node::processors::LevelMeter;
+use airlift_node::storage::{MemoryBackend, StorageBackend};
+use airlift_node::PcmFrame;
+
+/// 100 ms Stereo bei 48 kHz mit konstantem Pegel
+fn block(index: u64, level: i16) -> PcmFrame {
+    PcmFrame {
+        utc_ns: 1_700_000_000_000_000_000 + index * 100_000_000,
+        samples: vec![level; 9600],
+        sample_rate: 48_000,
+        channels: 2,
+        metadata: Default::default(),
+    }
+}
+
+fn level_meter(config: serde_json::Value) -> anyhow::Result<LevelMeter> {
+    let config: HashMap<String, serde_json::Value> = serde_json::from_value(config)?;
+    LevelMeter::from_config("meter", &config)
+}
+
+#[test]
+fn meter_writes_its_own_series_into_the_peak_history() -> anyhow::Result<()> {
+    let storage: Arc<dyn StorageBackend> = Arc::new(MemoryBackend::new());
+    let mut bus = EventBus::new("test");
+    bus.start()?;
+    bus.register_handler(Arc::new(PeakHistoryHandler::new(
+        "history",
+        storage.clone(),
+    )))?;
+    let bus = Arc::new(Mutex::new(bus));
+
+    let mut meter = level_meter(serde_json::json!({}))?;
+    meter.attach_event_emitter(
+        EventEmitter::new(bus.clone(), "processor", "meter")
+            .with_context(serde_json::json!({ "flow": "news" })),
+    );
+    assert_eq!(meter.series(), "news:meter");
+
+    // 2 s halbe Aussteuerung, das Signal bleibt unverändert
+    let mut frames: Vec<PcmFrame> = (0..20).map(|i| block(i, 16384)).collect();
+    meter.process_batch(&mut frames)?;
+    assert_eq!(frames.len(), 20);
+    assert!(frames
+        .iter()
+        .all(|frame| frame.samples.iter().all(|s| *s == 16384)));
+
+    let stats = meter.levels().stats.clone().expect("stats window");
+    assert_eq!(stats.window_ms, 1000);
+    assert_eq!(stats.peaks, [0.5, 0.5]);
+    assert!(!stats.silence);
+    let metrics = meter.status().metrics.unwrap();
+    assert_eq!(metrics["series"], "news:meter");
+    assert_eq!(metrics["peak_rates"]["stats_ms"], 1000);
+
+    let deadline = Instant::now() + Duration::from_secs(1);
+    let mut points = Vec::new();
+    while Instant::now() < deadline && points.len() < 2 {
+        points = storage.peaks(0, u64::MAX, Some("news:meter"))?;
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    bus.lock().unwrap().stop()?;
+    assert_eq!(points.len(), 2);
+    assert!(points
+        .iter()
+        .all(|point| point.peak_l == 0.5 && point.flow == "news:meter"));
+    // Der Eingangspegel des Flows bleibt eine eigene Reihe
+    assert!(storage.peaks(0, u64::MAX, Some("news"))?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn config_sets_series_and_rates() -> anyhow::Result<()> {
+    let mut meter = level_meter(serde_json::json!({
+        "series": "studio",
+        "peak_rates": { "meter": "100ms", "stats": "500ms" },
+    }))?;
+    assert_eq!(meter.series(), "studio");
+    let mut frames: Vec<PcmFrame> = (0..5).map(|i| block(i, 0)).collect();
+    meter.process_batch(&mut frames)?;
+    let stats = meter.levels().stats.clone().expect("stats window");
+    assert_eq!(stats.window_ms, 500);
+    assert!(stats.silence);
+
+    meter.update_config(serde_json::json!({ "series": null }))?;
+    assert_eq!(meter.series(), "meter");
+    for bad in [
+        serde_json::json!({ "series": "" }),
+        serde_json::json!({ "series": 5 }),
+        serde_json::json!({ "peak_rates": { "stats": "75ms" } }),
+        serde_json::json!({ "peak_rates": "fast" }),
+    ] {
+        assert!(meter.update_config(bad.clone()).is_err(), "{}", bad);
+    }
+    assert!(meter.update_config(serde_json::json!([])).is_err());
+    Ok(())
+}
+
+#[test]
+fn node_appends_an_output_meter_to_every_flow() -> anyhow::Result<()> {
+    let config: airlift_node::config::Config = toml::from_str(
+        r#"
+        node_name = "test"
+        [producers]
+        [processors]
+        [consumers]
+        [flows]
+        [analyzers]
+        output_meter = true
+        "#,
+    )?;
+    assert!(config.output_meter());
+
+    let mut node = AirliftNode::new();
+    assert!(!node.output_meter());
+    node.set_output_meter(config.output_meter());
+
+    let mut news = Flow::new("news");
+    news.set_peak_rates(PeakRates {
+        stats: Duration::from_secs(2),
+        ..PeakRates::default()
+    });
+    news.add_processor(Box::new(Gain::new("gain", 1.0)));
+    node.add_flow(news);
+    let mut quiet = Flow::new("quiet");
+    quiet.set_default_analyzers(false);
+    node.add_flow(quiet);
+
+    let news = &node.flows[node.flow_index_by_name("news").unwrap()];
+    assert_eq!(news.processor_names(), ["gain", "output_meter"]);
+    let status = news.status();
+    let metrics = status.processor_status[1].metrics.as_ref().unwrap();
+    assert_eq!(metrics["series"], "news:output");
+    assert_eq!(metrics["peak_rates"]["stats_ms"], 2000);
+    let quiet = &node.flows[node.flow_index_by_name("quiet").unwrap()];
+    assert!(quiet.processor_names().is_empty());
+    Ok(())
+}
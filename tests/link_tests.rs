@@ -0,0 +1,298 @@
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use airlift_node::aoip::link::{
+    decode_pcm, encode_pcm, local_codecs, read_answer, read_hello, read_packet, write_answer,
+    write_hello, write_packet, LinkChoice, LinkCodec, LinkNetwork, LinkOffer, LinkPolicy,
+    DEFAULT_LINK_OPUS_BITRATE,
+};
+use airlift_node::config::{ConsumerConfig, ProducerConfig};
+use airlift_node::consumers::link::{LinkConsumer, LinkConsumerConfig};
+use airlift_node::core::{AudioRingBuffer, Consumer, Producer};
+use airlift_node::producers::link::{LinkProducer, LinkProducerConfig};
+use airlift_node::PcmFrame;
+use serde_json::json;
+
+fn frame(utc_ns: u64) -> PcmFrame {
+    PcmFrame {
+        utc_ns,
+        samples: vec![-3, 1000, i16::MIN, i16::MAX],
+        sample_rate: 48_000,
+        channels: 2,
+        metadata: Default::default(),
+    }
+}
+
+fn wait_until(timeout: Duration, mut done: impl FnMut() -> bool) -> bool {
+    let deadline = Instant::now() + timeout;
+    while Instant::now() < deadline {
+        if done() {
+            return true;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    done()
+}
+
+#[test]
+fn wire_format_round_trips_timestamps() -> anyhow::Result<()> {
+    let mut wire = Vec::new();
+    let offer = LinkOffer {
+        network: LinkNetwork::Wan,
+        bitrate: Some(96_000),
+        codecs: vec![LinkCodec::Opus, LinkCodec::Pcm],
+    };
+    write_hello(&mut wire, "studio_a", &offer)?;
+    write_packet(&mut wire, &encode_pcm(&frame(1_700_000_000_123_456_789)))?;
+    write_packet(&mut wire, &encode_pcm(&frame(1_700_000_000_223_456_789)))?;
+
+    let mut reader = Cursor::new(wire);
+    let hello = read_hello(&mut reader)?;
+    assert_eq!(hello.stream, "studio_a");
+    assert_eq!(hello.offer, offer);
+    assert!(hello.expects_answer());
+    let first = decode_pcm(&read_packet(&mut reader)?.unwrap())?;
+    assert_eq!(first.utc_ns, 1_700_000_000_123_456_789);
+    assert_eq!(first.samples, frame(0).samples);
+    assert_eq!((first.sample_rate, first.channels), (48_000, 2));
+    let second = decode_pcm(&read_packet(&mut reader)?.unwrap())?;
+    assert_eq!(second.utc_ns, 1_700_000_000_223_456_789);
+    assert!(read_packet(&mut reader)?.is_none());
+
+    assert!(read_hello(&mut Cursor::new(b"HTTP/1.1".to_vec())).is_err());
+    // Version 1 ohne Angebot: PCM, keine Antwort
+    let legacy = read_hello(&mut Cursor::new(b"ALNK\x01\x00\x02ab".to_vec()))?;
+    assert_eq!(legacy.stream, "ab");
+    assert_eq!(legacy.offer, LinkOffer::legacy());
+    assert!(!legacy.expects_answer());
+    // Längenfeld kleiner als der Header
+    assert!(read_packet(&mut Cursor::new(vec![0, 0, 0, 3, 1, 2, 3])).is_err());
+    Ok(())
+}
+
+#[test]
+fn link_configs_are_validated() -> anyhow::Result<()> {
+    let consumer = |config: serde_json::Value| ConsumerConfig {
+        consumer_type: "airlift_link".to_string(),
+        enabled: true,
+        path: None,
+        url: None,
+        config: serde_json::from_value(config).unwrap(),
+    };
+    let parsed =
+        LinkConsumerConfig::from_config("uplink", &consumer(json!({ "address": "10.0.0.2" })))?;
+    assert_eq!(parsed.address, "10.0.0.2:7700");
+    assert_eq!(parsed.stream, "uplink");
+    assert!(LinkConsumerConfig::from_config("uplink", &consumer(json!({}))).is_err());
+    assert!(LinkConsumerConfig::from_config(
+        "uplink",
+        &consumer(json!({ "address": "10.0.0.2:7700", "transport": "quic" }))
+    )
+    .is_err());
+
+    let producer = ProducerConfig {
+        producer_type: "airlift_link".to_string(),
+        enabled: true,
+        device: None,
+        path: None,
+        channels: None,
+        sample_rate: None,
+        loop_audio: None,
+        config: HashMap::from([("listen".to_string(), json!("not an address"))]),
+    };
+    assert!(LinkProducerConfig::from_producer_config("downlink", &producer).is_err());
+
+    let policy = |config: serde_json::Value| {
+        LinkPolicy::from_config(
+            "consumer",
+            "uplink",
+            &serde_json::from_value(config).unwrap(),
+        )
+    };
+    let parsed = policy(json!({ "network": "wan", "codecs": ["pcm"], "bitrate": "64k" }))?;
+    assert_eq!(parsed.network, LinkNetwork::Wan);
+    assert_eq!(parsed.codecs, [LinkCodec::Pcm]);
+    assert_eq!(parsed.bitrate, Some(64_000));
+    for broken in [
+        json!({ "network": "satellite" }),
+        json!({ "codecs": "pcm" }),
+        json!({ "codecs": ["mp3"] }),
+        json!({ "bitrate": "2M" }),
+        // FLAC kann dieser Build auf dem Link nicht
+        json!({ "codecs": ["flac"] }),
+    ] {
+        assert!(policy(broken.clone()).is_err(), "{}", broken);
+    }
+    Ok(())
+}
+
+#[test]
+fn codecs_are_negotiated_by_network_and_policy() -> anyhow::Result<()> {
+    let lossy = local_codecs().contains(&LinkCodec::Opus);
+
+    // LAN: verlustfrei zuerst, WAN: Opus zuerst (soweit im Build)
+    let sender = LinkPolicy::default();
+    let lan = sender.offer(Some("192.168.1.20".parse()?));
+    assert_eq!(lan.network, LinkNetwork::Lan);
+    assert_eq!(lan.codecs[0], LinkCodec::Pcm);
+    let wan = sender.offer(Some("203.0.113.7".parse()?));
+    assert_eq!(wan.network, LinkNetwork::Wan);
+    assert_eq!(wan.codecs.last(), Some(&LinkCodec::Pcm));
+    assert_eq!(
+        sender.offer(Some("fd00::1".parse()?)).network,
+        LinkNetwork::Lan
+    );
+
+    let receiver = LinkPolicy::default();
+    let expected = if lossy {
+        LinkChoice {
+            codec: LinkCodec::Opus,
+            bitrate: Some(DEFAULT_LINK_OPUS_BITRATE),
+        }
+    } else {
+        LinkChoice {
+            codec: LinkCodec::Pcm,
+            bitrate: None,
+        }
+    };
+    assert_eq!(receiver.negotiate(&wan)?, expected);
+    assert_eq!(receiver.negotiate(&lan)?.codec, LinkCodec::Pcm);
+
+    // Fest eingestelltes LAN beim Empfänger überstimmt die Reihenfolge
+    let lan_receiver = LinkPolicy {
+        network: LinkNetwork::Lan,
+        ..LinkPolicy::default()
+    };
+    assert_eq!(lan_receiver.negotiate(&wan)?.codec, LinkCodec::Pcm);
+
+    // Kleinere Bitrate-Obergrenze gewinnt
+    let offer = LinkOffer {
+        network: LinkNetwork::Wan,
+        bitrate: Some(96_000),
+        codecs: vec![LinkCodec::Opus, LinkCodec::Pcm],
+    };
+    let capped = LinkPolicy {
+        bitrate: Some(64_000),
+        ..LinkPolicy::default()
+    };
+    if lossy {
+        assert_eq!(capped.negotiate(&offer)?.bitrate, Some(64_000));
+    }
+
+    // Nichts Gemeinsames: Ablehnung mit Grund
+    let flac_only = LinkOffer {
+        codecs: vec![LinkCodec::Flac],
+        ..offer
+    };
+    let error = receiver.negotiate(&flac_only).unwrap_err().to_string();
+    assert!(error.contains("no common codec"), "{}", error);
+
+    let mut wire = Vec::new();
+    write_answer(&mut wire, Ok(&expected))?;
+    write_answer(&mut wire, Err("stream 'x' rejected"))?;
+    let mut reader = Cursor::new(wire);
+    assert_eq!(read_answer(&mut reader)?, expected);
+    let rejected = read_answer(&mut reader).unwrap_err().to_string();
+    assert!(rejected.contains("stream 'x' rejected"), "{}", rejected);
+    Ok(())
+}
+
+#[test]
+fn frames_keep_utc_ns_across_nodes() -> anyhow::Result<()> {
+    let received = Arc::new(AudioRingBuffer::new(64));
+    let mut producer = LinkProducer::with_config(
+        "downlink",
+        LinkProducerConfig {
+            listen: "127.0.0.1:0".parse()?,
+            stream: Some("program".to_string()),
+            policy: LinkPolicy::default(),
+        },
+    );
+    producer.attach_ring_buffer(received.clone());
+    producer.start()?;
+    let address = producer.local_addr().unwrap().to_string();
+
+    let source = Arc::new(AudioRingBuffer::new(64));
+    let mut consumer = LinkConsumer::with_config(
+        "uplink",
+        LinkConsumerConfig {
+            address,
+            stream: "program".to_string(),
+            reconnect: Duration::from_millis(100),
+            policy: LinkPolicy::default(),
+        },
+    );
+    consumer.attach_input_buffer(source.clone());
+    consumer.start()?;
+
+    assert!(wait_until(Duration::from_secs(3), || {
+        consumer.status().connected && producer.status().connected
+    }));
+    for i in 0..5 {
+        source.push(frame(1_000_000_000 + i * 20_000_000));
+    }
+
+    assert!(wait_until(Duration::from_secs(3), || received.len() >= 5));
+    let timestamps: Vec<u64> = received.iter().map(|frame| frame.utc_ns).collect();
+    assert_eq!(
+        timestamps[timestamps.len() - 5..],
+        [
+            1_000_000_000,
+            1_020_000_000,
+            1_040_000_000,
+            1_060_000_000,
+            1_080_000_000
+        ]
+    );
+    assert!(consumer.status().bytes_written > 0);
+    // Loopback gilt als LAN: verlustfrei
+    let pcm = Some(LinkChoice {
+        codec: LinkCodec::Pcm,
+        bitrate: None,
+    });
+    assert_eq!(consumer.negotiated(), pcm);
+    assert_eq!(producer.negotiated(), pcm);
+
+    consumer.stop()?;
+    producer.stop()?;
+    assert!(!producer.status().connected);
+    Ok(())
+}
+
+#[test]
+fn rejected_senders_do_not_connect() -> anyhow::Result<()> {
+    let mut producer = LinkProducer::with_config(
+        "downlink",
+        LinkProducerConfig {
+            listen: "127.0.0.1:0".parse()?,
+            stream: Some("program".to_string()),
+            policy: LinkPolicy::default(),
+        },
+    );
+    producer.attach_ring_buffer(Arc::new(AudioRingBuffer::new(16)));
+    producer.start()?;
+
+    let mut consumer = LinkConsumer::with_config(
+        "uplink",
+        LinkConsumerConfig {
+            address: producer.local_addr().unwrap().to_string(),
+            stream: "backup".to_string(),
+            reconnect: Duration::from_millis(100),
+            policy: LinkPolicy::default(),
+        },
+    );
+    consumer.attach_input_buffer(Arc::new(AudioRingBuffer::new(16)));
+    consumer.start()?;
+
+    assert!(wait_until(Duration::from_secs(3), || {
+        consumer.status().errors > 0
+    }));
+    assert!(!consumer.status().connected);
+    assert!(consumer.negotiated().is_none());
+
+    consumer.stop()?;
+    producer.stop()?;
+    Ok(())
+}
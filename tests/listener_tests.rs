@@ -0,0 +1,53 @@
+use std::net::TcpListener;
+use std::time::Duration;
+
+use airlift_node::api::listeners::{self, BindRetry};
+
+#[test]
+fn port_zero_binds_ephemeral_port_and_reports_it() {
+    let server = listeners::bind_http("test-ephemeral", "127.0.0.1:0", BindRetry::once())
+        .expect("ephemeral bind");
+    let port = server.server_addr().to_ip().expect("ip listener").port();
+    assert_ne!(port, 0);
+
+    let listener = listeners::listeners()
+        .into_iter()
+        .find(|listener| listener.component == "test-ephemeral")
+        .expect("listener registered");
+    assert_eq!(listener.configured, "127.0.0.1:0");
+    assert_eq!(listener.port, port);
+    assert_eq!(listener.address, format!("127.0.0.1:{}", port));
+}
+
+#[test]
+fn busy_ports_are_retried_and_reported_together() {
+    let busy = TcpListener::bind("127.0.0.1:0").unwrap();
+    let busy_addr = busy.local_addr().unwrap().to_string();
+    let retry = BindRetry {
+        attempts: 2,
+        initial_backoff: Duration::from_millis(10),
+    };
+
+    let targets = vec![
+        ("test-ok".to_string(), "127.0.0.1:0".to_string()),
+        ("test-busy".to_string(), busy_addr.clone()),
+        ("test-invalid".to_string(), "not-an-address".to_string()),
+    ];
+    let Err(report) = listeners::bind_all(&targets, retry) else {
+        panic!("expected bind failure");
+    };
+
+    assert_eq!(report.failures.len(), 2);
+    assert_eq!(report.failures[0].component, "test-busy");
+    assert_eq!(report.failures[0].attempts, 2, "address in use is retried");
+    assert_eq!(report.failures[1].component, "test-invalid");
+    assert_eq!(report.failures[1].attempts, 1, "other errors fail at once");
+    let message = report.to_string();
+    assert!(message.contains(&busy_addr), "{}", message);
+    assert!(message.contains("not-an-address"), "{}", message);
+
+    // Der erfolgreich gebundene Listener wird wieder freigegeben
+    assert!(!listeners::listeners()
+        .iter()
+        .any(|listener| listener.component == "test-ok"));
+}
@@ -0,0 +1,77 @@
+use airlift_node::api::memory::{memory_report, parse_vm_rss};
+use airlift_node::api::peaks::PeakPoint;
+use airlift_node::core::{AirliftNode, BufferSizing, Flow};
+use airlift_node::storage::{MemoryBackend, StorageBackend};
+use airlift_node::testing::mocks::MockProducer;
+use airlift_node::{Samples, Slots};
+
+fn node() -> anyhow::Result<AirliftNode> {
+    let mut node = AirliftNode::new();
+    node.add_producer_with_buffer(
+        Box::new(MockProducer::new("mic", Vec::new())),
+        BufferSizing { slots: Slots(500), prealloc_samples: Samples(0) },
+    )?;
+    node.add_flow(Flow::with_buffer_sizing(
+        "main",
+        BufferSizing { slots: Slots(10), prealloc_samples: Samples(0) },
+        BufferSizing { slots: Slots(20), prealloc_samples: Samples(0) },
+    ));
+    node.connect_flow_input(0, "producer:mic")?;
+    Ok(node)
+}
+
+#[test]
+fn report_lists_subsystems_and_largest_rings_first() -> anyhow::Result<()> {
+    let node = node()?;
+    let storage = MemoryBackend::new();
+    for i in 0..100 {
+        storage.push_peak(PeakPoint {
+            ts: 1_000 + i * 100,
+            peak_l: 0.5,
+            peak_r: 0.25,
+            silence: false,
+            flow: "main".to_string(),
+        })?;
+    }
+
+    let report = memory_report(&node, &storage);
+    let names: Vec<&str> = report.subsystems.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, ["ring_buffers", "timeshift", "peak_history", "event_queues"]);
+
+    let rings = &report.subsystems[0];
+    assert_eq!(rings.items, report.ring_buffers.len());
+    assert_eq!(
+        rings.estimated_bytes,
+        report.ring_buffers.iter().map(|b| b.memory_bytes).sum::<u64>()
+    );
+    assert_eq!(report.ring_buffers[0].name, "producer:mic");
+    assert!(report
+        .ring_buffers
+        .windows(2)
+        .all(|pair| pair[0].memory_bytes >= pair[1].memory_bytes));
+
+    let peaks = &report.subsystems[2];
+    assert_eq!(peaks.items, 100);
+    assert!(peaks.estimated_bytes >= 100 * std::mem::size_of::<PeakPoint>() as u64);
+
+    assert_eq!(report.subsystems[1].items, 0);
+    assert_eq!(
+        report.estimated_total_bytes,
+        report.subsystems.iter().map(|s| s.estimated_bytes).sum::<u64>()
+    );
+    if cfg!(target_os = "linux") {
+        let rss = report.process_rss_bytes.expect("VmRSS on Linux");
+        assert!(rss > 0);
+        let unaccounted = rss.saturating_sub(report.estimated_total_bytes);
+        assert_eq!(report.unaccounted_bytes, Some(unaccounted));
+    }
+    Ok(())
+}
+
+#[test]
+fn vm_rss_is_parsed_from_proc_status() {
+    let status = "Name:\tairlift-node\nVmPeak:\t  300000 kB\nVmRSS:\t   12345 kB\nThreads:\t12\n";
+    assert_eq!(parse_vm_rss(status), Some(12_345 * 1024));
+    assert_eq!(parse_vm_rss("Name:\tairlift-node\n"), None);
+    assert_eq!(parse_vm_rss("VmRSS:\tlots kB\n"), None);
+}
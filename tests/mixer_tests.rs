@@ -0,0 +1,255 @@
+use std::sync::Arc;
+
+use airlift_node::core::processor::Processor;
+use airlift_node::core::{AudioRingBuffer, BufferRegistry};
+use airlift_node::processors::{Mixer, MixerConfig, MixerInputConfig};
+use airlift_node::PcmFrame;
+
+/// 100 ms Stereo bei 48 kHz mit konstantem Pegel
+fn block(level: i16) -> PcmFrame {
+    PcmFrame {
+        utc_ns: 0,
+        samples: vec![level; 9600],
+        sample_rate: 48_000,
+        channels: 2,
+        metadata: Default::default(),
+    }
+}
+
+fn input(name: &str) -> MixerInputConfig {
+    MixerInputConfig {
+        name: name.to_string(),
+        source: format!("producer:{}", name),
+        gain: 1.0,
+        enabled: Some(true),
+        mute: None,
+        solo: None,
+    }
+}
+
+/// Mixer über `mic` und `music` samt deren Buffern
+fn studio_mixer() -> anyhow::Result<(Mixer, Arc<AudioRingBuffer>, Arc<AudioRingBuffer>)> {
+    let registry = Arc::new(BufferRegistry::new());
+    let mic = Arc::new(AudioRingBuffer::new(16));
+    let music = Arc::new(AudioRingBuffer::new(16));
+    registry.register("producer:mic", mic.clone())?;
+    registry.register("producer:music", music.clone())?;
+    let mut mixer = Mixer::from_config(
+        "desk",
+        MixerConfig {
+            inputs: vec![input("mic"), input("music")],
+            output_sample_rate: Some(48_000),
+            output_channels: Some(2),
+            master_gain: None,
+            auto_connect: Some(true),
+        },
+    );
+    mixer.attach_buffer_registry(registry);
+    mixer.connect_from_registry()?;
+    Ok((mixer, mic, music))
+}
+
+/// Pegel des nächsten gemischten Frames (mic 1000, music 3000)
+fn mix(mixer: &mut Mixer, mic: &AudioRingBuffer, music: &AudioRingBuffer) -> anyhow::Result<i16> {
+    let output = AudioRingBuffer::new(16);
+    mic.push(block(1000));
+    music.push(block(3000));
+    mixer.process(&AudioRingBuffer::new(1), &output)?;
+    let frame = output.pop().expect("mixed frame");
+    Ok(frame.samples[0])
+}
+
+#[test]
+fn input_gain_mute_and_solo_apply_live() -> anyhow::Result<()> {
+    let (mut mixer, mic, music) = studio_mixer()?;
+    assert_eq!(mix(&mut mixer, &mic, &music)?, 4000);
+
+    Processor::update_config(
+        &mut mixer,
+        serde_json::json!({ "input": "music", "gain": 0.5 }),
+    )?;
+    assert_eq!(mix(&mut mixer, &mic, &music)?, 2500);
+
+    Processor::update_config(
+        &mut mixer,
+        serde_json::json!({ "input": "mic", "mute": true }),
+    )?;
+    assert_eq!(mix(&mut mixer, &mic, &music)?, 1500);
+
+    // Solo übersteuert die übrigen Inputs, Mute bleibt Mute
+    Processor::update_config(
+        &mut mixer,
+        serde_json::json!({ "input": "mic", "mute": false, "solo": true }),
+    )?;
+    assert_eq!(mix(&mut mixer, &mic, &music)?, 1000);
+    mixer.set_input_solo("mic", false)?;
+    Processor::update_config(
+        &mut mixer,
+        serde_json::json!({ "input": "music", "gain_db": 0.0 }),
+    )?;
+    assert_eq!(mix(&mut mixer, &mic, &music)?, 4000);
+
+    // Werte stehen in der Config und überstehen erneutes Verbinden
+    mixer.set_input_mute("music", true)?;
+    mixer.connect_from_registry()?;
+    assert_eq!(mix(&mut mixer, &mic, &music)?, 1000);
+    Ok(())
+}
+
+#[test]
+fn status_lists_inputs_and_rejects_bad_updates() -> anyhow::Result<()> {
+    let (mut mixer, _mic, _music) = studio_mixer()?;
+    Processor::update_config(
+        &mut mixer,
+        serde_json::json!({ "input": "music", "gain": 0.5, "solo": true }),
+    )?;
+
+    let metrics = mixer.status().metrics.unwrap();
+    assert_eq!(metrics["master_gain"], 1.0);
+    let inputs = metrics["inputs"].as_array().unwrap();
+    assert_eq!(inputs.len(), 2);
+    assert_eq!(inputs[0]["name"], "mic");
+    assert_eq!(inputs[0]["source"], "producer:mic");
+    assert_eq!(inputs[0]["audible"], false);
+    assert_eq!(inputs[1]["gain"], 0.5);
+    assert!((inputs[1]["gain_db"].as_f64().unwrap() + 6.02).abs() < 0.01);
+    assert_eq!(inputs[1]["solo"], true);
+    assert_eq!(inputs[1]["audible"], true);
+
+    for update in [
+        serde_json::json!({ "input": "drums", "mute": true }),
+        serde_json::json!({ "input": "mic" }),
+        serde_json::json!({ "input": "mic", "gain": 0.5, "gain_db": -6 }),
+        serde_json::json!({ "input": "mic", "gain": 20 }),
+        serde_json::json!({ "input": "mic", "mute": "yes" }),
+    ] {
+        assert!(Processor::update_config(&mut mixer, update).is_err());
+    }
+    // Fehlerhafte Updates ändern nichts
+    assert_eq!(mixer.status().metrics.unwrap()["inputs"][0]["mute"], false);
+    Ok(())
+}
+
+#[test]
+fn mismatched_inputs_are_converted_to_the_output_format() -> anyhow::Result<()> {
+    let (mut mixer, mic, music) = studio_mixer()?;
+    let output = AudioRingBuffer::new(64);
+    // mic: 44,1 kHz Mono, music: 48 kHz Stereo in 200-ms-Frames
+    for _ in 0..5 {
+        mic.push(PcmFrame {
+            utc_ns: 0,
+            samples: vec![1000; 4410],
+            sample_rate: 44_100,
+            channels: 1,
+            metadata: Default::default(),
+        });
+        mic.push(PcmFrame {
+            utc_ns: 0,
+            samples: vec![1000; 4410],
+            sample_rate: 44_100,
+            channels: 1,
+            metadata: Default::default(),
+        });
+        music.push(PcmFrame {
+            samples: vec![3000; 19_200],
+            ..block(0)
+        });
+        mixer.process(&AudioRingBuffer::new(1), &output)?;
+    }
+    // Reste großer Frames gehen nicht verloren und stauen sich nicht
+    mixer.process(&AudioRingBuffer::new(1), &output)?;
+    let frames: Vec<PcmFrame> = std::iter::from_fn(|| output.pop()).collect();
+    assert_eq!(frames.len(), 10);
+    for frame in &frames[1..] {
+        assert_eq!(frame.sample_rate, 48_000);
+        assert_eq!(frame.channels, 2);
+        assert_eq!(frame.samples.len(), 9600);
+    }
+    // Nach dem Einschwingen des Filters summieren sich beide Pegel
+    assert!(frames[5].samples.iter().all(|s| (*s - 4000).abs() <= 2));
+
+    let metrics = mixer.status().metrics.unwrap();
+    assert_eq!(metrics["inputs"][0]["sample_rate"], 44_100);
+    assert_eq!(metrics["inputs"][0]["channels"], 1);
+    assert_eq!(metrics["inputs"][0]["resampling"], true);
+    assert_eq!(metrics["inputs"][1]["resampling"], false);
+
+    // Mono-Ausgang: Stereo-Inputs werden gemittelt
+    let (mut mono, mic, _music) = studio_mixer()?;
+    Processor::update_config(
+        &mut mono,
+        serde_json::json!({
+            "inputs": [{ "name": "mic", "source": "producer:mic", "gain": 1.0 }],
+            "output_channels": 1,
+        }),
+    )?;
+    mic.push(PcmFrame {
+        samples: [1000, 3000].repeat(4800),
+        ..block(0)
+    });
+    let output = AudioRingBuffer::new(4);
+    mono.process(&AudioRingBuffer::new(1), &output)?;
+    let frame = output.pop().expect("mixed frame");
+    assert_eq!(frame.channels, 1);
+    assert_eq!(frame.samples, vec![2000; 4800]);
+    Ok(())
+}
+
+#[test]
+fn gain_ramps_run_sample_by_sample_in_audio_time() -> anyhow::Result<()> {
+    let (mut mixer, mic, music) = studio_mixer()?;
+    mixer.set_input_mute("mic", true)?;
+    Processor::update_config(
+        &mut mixer,
+        serde_json::json!({ "input": "music", "gain": 0.0, "ramp": "200ms" }),
+    )?;
+    let metrics = mixer.status().metrics.unwrap();
+    assert_eq!(metrics["inputs"][1]["ramp"]["to"], 0.0);
+    assert_eq!(metrics["inputs"][1]["ramp"]["remaining_ms"], 200);
+
+    let output = AudioRingBuffer::new(8);
+    for _ in 0..3 {
+        mic.push(block(1000));
+        music.push(block(3000));
+        mixer.process(&AudioRingBuffer::new(1), &output)?;
+    }
+    let frames: Vec<PcmFrame> = std::iter::from_fn(|| output.pop()).collect();
+    assert_eq!(frames.len(), 3);
+    // 3000 → 1500 im ersten, → 0 im zweiten Frame, ohne Stufen
+    assert_eq!(frames[0].samples[0], 3000);
+    assert!((frames[0].samples[9598] - 1500).abs() <= 1);
+    assert!(frames[1].samples[9598].abs() <= 1);
+    let falling = frames[0]
+        .samples
+        .chunks(2)
+        .zip(frames[0].samples.chunks(2).skip(1));
+    assert!(falling
+        .clone()
+        .all(|(a, b)| a[0] >= b[0] && a[0] - b[0] <= 1));
+    assert!(frames[2].samples.iter().all(|s| *s == 0));
+    let metrics = mixer.status().metrics.unwrap();
+    assert_eq!(metrics["inputs"][1]["gain"], 0.0);
+    assert!(metrics["inputs"][1]["ramp"].is_null());
+
+    // Master-Rampe in Millisekunden; ein direkter Wert bricht sie ab
+    mixer.set_input_gain("music", 1.0)?;
+    Processor::update_config(
+        &mut mixer,
+        serde_json::json!({ "master_gain": 0.5, "ramp": 100 }),
+    )?;
+    assert_eq!(mix(&mut mixer, &mic, &music)?, 3000);
+    assert_eq!(mix(&mut mixer, &mic, &music)?, 1500);
+    assert!(mixer.status().metrics.unwrap()["master_ramp"].is_null());
+
+    assert!(Processor::update_config(
+        &mut mixer,
+        serde_json::json!({ "input": "music", "mute": true, "ramp": "1s" }),
+    )
+    .is_err());
+    assert!(Processor::update_config(
+        &mut mixer,
+        serde_json::json!({ "input": "music", "gain": 0.5, "ramp": "soon" }),
+    )
+    .is_err());
+    Ok(())
+}
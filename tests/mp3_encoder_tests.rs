@@ -0,0 +1,219 @@
+#![cfg(feature = "mp3")]
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use airlift_node::codecs::mp3::{Mp3Encoder, MP3_FRAME_SAMPLES};
+use airlift_node::codecs::{create_encoder, AudioCodec, CodecKind};
+use airlift_node::core::consumer::file_writer::{FileConsumer, FileFormat};
+use airlift_node::core::timestamp::utc_ns_now;
+use airlift_node::core::{AudioRingBuffer, Consumer, TimeZone};
+use airlift_node::PcmFrame;
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("airlift-mp3-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Sinus mit `level` (0..1) links und halber Lautstärke rechts
+fn tone(samples: usize, freq: f64, level: f64) -> Vec<i16> {
+    (0..samples)
+        .flat_map(|i| {
+            let value =
+                (i as f64 / 48_000.0 * freq * std::f64::consts::TAU).sin() * level * 32767.0;
+            [value as i16, (value * 0.5) as i16]
+        })
+        .collect()
+}
+
+fn encode(encoder: &mut Mp3Encoder, pcm: &[i16]) -> anyhow::Result<Vec<u8>> {
+    let mut data = Vec::new();
+    for chunk in pcm.chunks(4800 * encoder.info().channels as usize) {
+        for frame in encoder.encode(chunk)? {
+            data.extend(frame.payload);
+        }
+    }
+    for frame in encoder.flush()? {
+        data.extend(frame.payload);
+    }
+    Ok(data)
+}
+
+#[test]
+fn rejects_unsupported_channels_and_bitrates() {
+    assert!(Mp3Encoder::new(3, 128_000).is_err());
+    assert!(Mp3Encoder::new(0, 128_000).is_err());
+    let err = Mp3Encoder::new(2, 100_000).err().unwrap().to_string();
+    assert!(err.contains("32, 40, 48"), "{}", err);
+
+    let encoder = create_encoder("mp3").unwrap();
+    assert!(matches!(encoder.info().kind, CodecKind::Mp3));
+    assert_eq!(encoder.info().sample_rate, 48_000);
+}
+
+#[test]
+fn frames_have_the_size_of_the_bitrate() -> anyhow::Result<()> {
+    for (channels, bitrate, bytes) in [
+        (2u8, 128_000u32, 384usize),
+        (1, 32_000, 96),
+        (2, 320_000, 960),
+    ] {
+        let mut encoder = Mp3Encoder::new(channels, bitrate)?;
+        let pcm: Vec<i16> = tone(MP3_FRAME_SAMPLES * 3 + 100, 1000.0, 0.5)
+            .chunks(2)
+            .flat_map(|pair| pair[..channels as usize].to_vec())
+            .collect();
+        let frames = encoder.encode(&pcm)?;
+        assert_eq!(frames.len(), 3);
+        for frame in &frames {
+            assert_eq!(frame.payload.len(), bytes, "{} bit/s", bitrate);
+            // Sync, MPEG-1 Layer III ohne CRC
+            assert_eq!(&frame.payload[..2], &[0xFF, 0xFB]);
+            let mono = frame.payload[3] >> 6 == 0b11;
+            assert_eq!(mono, channels == 1);
+        }
+        // Rest plus ein Frame Ausklang
+        assert_eq!(encoder.flush()?.len(), 2);
+        assert!(encoder.encode(&pcm).is_err(), "no input after flush");
+    }
+
+    let mut silent = Mp3Encoder::new(2, 128_000)?;
+    assert!(silent.flush()?.is_empty());
+    Ok(())
+}
+
+/// Amplitude und Signal-Rausch-Abstand (dB) eines Tons bekannter Frequenz,
+/// per kleinster Quadrate, unabhängig von der Verzögerung des Codecs.
+#[cfg(feature = "symphonia")]
+fn fit_tone(samples: &[f64], freq: f64) -> (f64, f64) {
+    let basis = |i: usize| {
+        let phase = i as f64 / 48_000.0 * freq * std::f64::consts::TAU;
+        (phase.sin(), phase.cos())
+    };
+    let (mut ss, mut sc, mut cc, mut ys, mut yc) = (0.0, 0.0, 0.0, 0.0, 0.0);
+    for (i, y) in samples.iter().enumerate() {
+        let (s, c) = basis(i);
+        ss += s * s;
+        sc += s * c;
+        cc += c * c;
+        ys += y * s;
+        yc += y * c;
+    }
+    let det = ss * cc - sc * sc;
+    let (a, b) = ((ys * cc - yc * sc) / det, (yc * ss - ys * sc) / det);
+    let (mut signal, mut noise) = (0.0, 0.0);
+    for (i, y) in samples.iter().enumerate() {
+        let (s, c) = basis(i);
+        let model = a * s + b * c;
+        signal += model * model;
+        noise += (y - model) * (y - model);
+    }
+    ((a * a + b * b).sqrt(), 10.0 * (signal / noise).log10())
+}
+
+#[cfg(feature = "symphonia")]
+#[test]
+fn decodes_back_to_the_input_tone() -> anyhow::Result<()> {
+    let dir = temp_dir("roundtrip");
+    for (channels, bitrate, freq) in [
+        (2u8, 128_000, 1000.0),
+        (2, 64_000, 440.0),
+        (1, 96_000, 5000.0),
+    ] {
+        let pcm: Vec<i16> = tone(96_000, freq, 0.5)
+            .chunks(2)
+            .flat_map(|pair| pair[..channels as usize].to_vec())
+            .collect();
+        let mut encoder = Mp3Encoder::new(channels, bitrate)?;
+        let path = dir.join(format!("tone-{}.mp3", bitrate));
+        fs::write(&path, encode(&mut encoder, &pcm)?)?;
+
+        let mut reader = airlift_node::producers::file::open_audio_file(&path)?;
+        assert_eq!(reader.info().codec, "mp3");
+        assert_eq!(
+            (reader.info().sample_rate, reader.info().channels),
+            (48_000, channels)
+        );
+        let mut decoded = Vec::new();
+        while let Some(block) = reader.read_block()? {
+            decoded.extend(block);
+        }
+        assert!(
+            decoded.len() >= pcm.len(),
+            "flush emits the filter bank delay"
+        );
+
+        for channel in 0..channels as usize {
+            let samples: Vec<f64> = decoded
+                .iter()
+                .skip(channel)
+                .step_by(channels as usize)
+                .skip(24_000)
+                .take(48_000)
+                .map(|sample| *sample as f64 / 32767.0)
+                .collect();
+            let (level, snr) = fit_tone(&samples, freq);
+            let expected = if channel == 0 { 0.5 } else { 0.25 };
+            assert!(
+                (level - expected).abs() < 0.005,
+                "{} bit/s: level {}",
+                bitrate,
+                level
+            );
+            assert!(snr > 50.0, "{} bit/s: {:.1} dB", bitrate, snr);
+        }
+    }
+    let _ = fs::remove_dir_all(&dir);
+    Ok(())
+}
+
+#[test]
+fn file_consumer_records_mp3_at_the_configured_bitrate() -> anyhow::Result<()> {
+    let dir = temp_dir("archive");
+    let path = dir.join("program.mp3");
+    let config: HashMap<String, serde_json::Value> =
+        serde_json::from_value(serde_json::json!({ "bitrate": "64k" }))?;
+    let mut consumer =
+        FileConsumer::from_config("archive", path.to_str().unwrap(), &config, TimeZone::utc())?;
+    assert_eq!(consumer.format(), FileFormat::Mp3);
+    let wrong: HashMap<String, serde_json::Value> =
+        serde_json::from_value(serde_json::json!({ "bitrate": "100k" }))?;
+    assert!(
+        FileConsumer::from_config("archive", path.to_str().unwrap(), &wrong, TimeZone::utc())
+            .is_err()
+    );
+
+    let buffer = Arc::new(AudioRingBuffer::new(64));
+    consumer.attach_input_buffer(buffer.clone());
+    consumer.start()?;
+    let pcm = tone(96_000, 440.0, 0.3);
+    for (index, block) in pcm.chunks(9600).enumerate() {
+        buffer.push(PcmFrame {
+            utc_ns: utc_ns_now() + index as u64 * 100_000_000,
+            samples: block.to_vec(),
+            sample_rate: 48_000,
+            channels: 2,
+            metadata: Default::default(),
+        });
+    }
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while consumer.status().frames_processed < 20 && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(20));
+    }
+    consumer.stop()?;
+
+    // 2 s plus Ausklang bei 64 kbit/s: 192 Byte pro Frame
+    let data = fs::read(&path)?;
+    assert_eq!(data.len() % 192, 0);
+    assert_eq!(
+        data.len() / 192,
+        96_000usize.div_ceil(MP3_FRAME_SAMPLES) + 1
+    );
+    let _ = fs::remove_dir_all(&dir);
+    Ok(())
+}
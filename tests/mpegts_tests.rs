@@ -0,0 +1,270 @@
+use airlift_node::aoip::mpegts::{ts_packets, PtsClock, TsAudioCodec, TsDemuxer, TS_PACKET_LEN};
+use airlift_node::decoders::mpeg_audio::{parse_adts_header, parse_mpa_header, EsCodec, EsFramer};
+
+const PMT_PID: u16 = 0x1000;
+const AUDIO_PID: u16 = 0x0101;
+
+fn crc32_mpeg2(data: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+    for &byte in data {
+        crc ^= (byte as u32) << 24;
+        for _ in 0..8 {
+            crc = if crc & 0x8000_0000 != 0 {
+                (crc << 1) ^ 0x04c1_1db7
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+/// TS-Paket; kurze Payloads werden per Adaptation-Field aufgefüllt.
+fn ts_packet(pid: u16, unit_start: bool, continuity: u8, payload: &[u8]) -> Vec<u8> {
+    assert!(payload.len() <= 184);
+    let mut packet = vec![
+        0x47,
+        (if unit_start { 0x40 } else { 0 }) | (pid >> 8) as u8,
+        pid as u8,
+        0x10 | (continuity & 0x0f),
+    ];
+    if payload.len() < 184 {
+        packet[3] |= 0x20;
+        let adaptation_len = 183 - payload.len();
+        packet.push(adaptation_len as u8);
+        if adaptation_len > 0 {
+            packet.push(0x00);
+            packet.resize(5 + adaptation_len, 0xff);
+        }
+    }
+    packet.extend_from_slice(payload);
+    assert_eq!(packet.len(), TS_PACKET_LEN);
+    packet
+}
+
+fn psi(table_id: u8, body: &[u8]) -> Vec<u8> {
+    let len = body.len() + 4;
+    let mut section = vec![table_id, 0xb0 | (len >> 8) as u8, len as u8];
+    section.extend_from_slice(body);
+    let crc = crc32_mpeg2(&section);
+    section.extend_from_slice(&crc.to_be_bytes());
+    let mut payload = vec![0x00];
+    payload.extend_from_slice(&section);
+    payload
+}
+
+fn pat() -> Vec<u8> {
+    let pmt_pid = [0xe0 | (PMT_PID >> 8) as u8, PMT_PID as u8];
+    let body = [0x00, 0x01, 0xc1, 0x00, 0x00, 0x00, 0x01, pmt_pid[0], pmt_pid[1]];
+    ts_packet(0, true, 0, &psi(0x00, &body))
+}
+
+fn pmt(streams: &[(u8, u16)]) -> Vec<u8> {
+    let mut body = vec![0x00, 0x01, 0xc1, 0x00, 0x00, 0xe1, 0x00, 0xf0, 0x00];
+    for &(stream_type, pid) in streams {
+        body.extend_from_slice(&[stream_type, 0xe0 | (pid >> 8) as u8, pid as u8, 0xf0, 0x00]);
+    }
+    ts_packet(PMT_PID, true, 0, &psi(0x02, &body))
+}
+
+fn pes(pts: u64, data: &[u8], bounded: bool) -> Vec<u8> {
+    let len = if bounded { 3 + 5 + data.len() } else { 0 };
+    let mut pes = vec![0, 0, 1, 0xc0, (len >> 8) as u8, len as u8, 0x80, 0x80, 5];
+    pes.extend_from_slice(&[
+        0x21 | ((pts >> 29) & 0x0e) as u8,
+        (pts >> 22) as u8,
+        ((pts >> 14) & 0xfe) as u8 | 1,
+        (pts >> 7) as u8,
+        ((pts << 1) & 0xfe) as u8 | 1,
+    ]);
+    pes.extend_from_slice(data);
+    pes
+}
+
+fn split(pid: u16, pes: &[u8], first_continuity: u8) -> Vec<Vec<u8>> {
+    pes.chunks(184)
+        .enumerate()
+        .map(|(i, chunk)| ts_packet(pid, i == 0, first_continuity.wrapping_add(i as u8), chunk))
+        .collect()
+}
+
+fn adts_frame(len: usize) -> Vec<u8> {
+    // AAC-LC, 48 kHz, Stereo, ohne CRC
+    let mut frame = vec![
+        0xff,
+        0xf1,
+        0x4c,
+        0x80 | ((len >> 11) & 0x03) as u8,
+        (len >> 3) as u8,
+        (((len & 0x07) << 5) as u8) | 0x1f,
+        0xfc,
+    ];
+    frame.resize(len, 0x55);
+    frame
+}
+
+fn demuxer_with_tables() -> TsDemuxer {
+    let mut demuxer = TsDemuxer::new(None);
+    assert!(demuxer.push(&pat()).is_none());
+    assert!(demuxer.push(&pmt(&[(0x1b, 0x0100), (0x0f, AUDIO_PID)])).is_none());
+    demuxer
+}
+
+#[test]
+fn demuxer_picks_first_audio_pid_and_reassembles_pes() {
+    let mut demuxer = demuxer_with_tables();
+    assert_eq!(demuxer.audio_stream(), Some((AUDIO_PID, TsAudioCodec::AacAdts)));
+
+    // Mit Längenangabe: fertig, sobald das letzte Paket da ist
+    let data: Vec<u8> = (0..400).map(|i| i as u8).collect();
+    let packets = split(AUDIO_PID, &pes(900_000, &data, true), 0);
+    assert_eq!(packets.len(), 3);
+    assert!(demuxer.push(&packets[0]).is_none());
+    assert!(demuxer.push(&packets[1]).is_none());
+    let packet = demuxer.push(&packets[2]).expect("complete PES");
+    assert_eq!(packet.pid, AUDIO_PID);
+    assert_eq!(packet.pts, Some(900_000));
+    assert_eq!(packet.payload, data);
+
+    // Ohne Längenangabe: fertig mit dem nächsten PES-Start
+    let first = split(AUDIO_PID, &pes(901_920, &[1, 2, 3], false), 3);
+    let second = split(AUDIO_PID, &pes(903_840, &[4, 5], false), 4);
+    assert!(demuxer.push(&first[0]).is_none());
+    let packet = demuxer.push(&second[0]).expect("previous PES");
+    assert_eq!((packet.pts, packet.payload), (Some(901_920), vec![1, 2, 3]));
+
+    // Andere PIDs werden ignoriert
+    assert!(demuxer.push(&ts_packet(0x0100, true, 0, &pes(0, &[9], true))).is_none());
+    assert_eq!(demuxer.errors(), 0);
+}
+
+#[test]
+fn continuity_gap_drops_partial_pes() {
+    let mut demuxer = demuxer_with_tables();
+    let packets = split(AUDIO_PID, &pes(0, &[7; 300], true), 0);
+    assert!(demuxer.push(&packets[0]).is_none());
+    // Paket mit Continuity 1 fehlt, stattdessen 2
+    let late = ts_packet(AUDIO_PID, false, 2, &[7; 100]);
+    assert!(demuxer.push(&late).is_none());
+    assert_eq!(demuxer.errors(), 1);
+
+    let next = split(AUDIO_PID, &pes(1_920, &[8; 10], true), 3);
+    let packet = demuxer.push(&next[0]).expect("fresh PES after gap");
+    assert_eq!(packet.payload, vec![8; 10]);
+
+    assert!(demuxer.push(&[0u8; TS_PACKET_LEN]).is_none());
+    assert_eq!(demuxer.errors(), 2);
+}
+
+#[test]
+fn forced_pid_selects_matching_stream() {
+    let mut demuxer = TsDemuxer::new(Some(0x0102));
+    demuxer.push(&pat());
+    demuxer.push(&pmt(&[(0x0f, AUDIO_PID), (0x04, 0x0102)]));
+    assert_eq!(demuxer.audio_stream(), Some((0x0102, TsAudioCodec::MpegAudio)));
+}
+
+#[test]
+fn datagrams_with_and_without_rtp_header() {
+    let packets: Vec<u8> = (0..7).flat_map(|i| ts_packet(AUDIO_PID, false, i, &[0; 184])).collect();
+    assert_eq!(ts_packets(&packets).count(), 7);
+
+    let mut rtp = vec![0x80, 33, 0, 1, 0, 0, 0, 0, 0, 0, 0, 1];
+    rtp.extend_from_slice(&packets);
+    assert_eq!(ts_packets(&rtp).count(), 7);
+    assert!(ts_packets(&rtp).all(|packet| packet[0] == 0x47));
+
+    assert_eq!(ts_packets(b"not a transport stream").count(), 0);
+}
+
+#[test]
+fn es_framer_splits_frames_across_pes_boundaries() {
+    let header = parse_adts_header(&adts_frame(100)).unwrap();
+    assert_eq!(header.frame_len, 100);
+    assert_eq!(header.payload_offset, 7);
+    assert_eq!((header.sample_rate, header.channels), (48_000, 2));
+    assert_eq!(
+        header.codec,
+        EsCodec::Aac {
+            audio_specific_config: [0x11, 0x90]
+        }
+    );
+
+    let mut stream = vec![0x00, 0x12];
+    stream.extend(adts_frame(100));
+    stream.extend(adts_frame(120));
+    let mut framer = EsFramer::new(TsAudioCodec::AacAdts);
+    framer.push(&stream[..150]);
+    let (first, data) = framer.next_frame().expect("first frame");
+    assert_eq!((first.frame_len, data.len()), (100, 100));
+    assert!(framer.next_frame().is_none());
+    framer.push(&stream[150..]);
+    assert_eq!(framer.next_frame().map(|(header, _)| header.frame_len), Some(120));
+    assert!(framer.next_frame().is_none());
+}
+
+#[test]
+fn mpeg_audio_header_lengths() {
+    // MPEG-1 Layer II, 128 kbit/s, 48 kHz, Stereo
+    let header = parse_mpa_header(&[0xff, 0xfd, 0x84, 0x00]).unwrap();
+    assert_eq!(header.codec, EsCodec::Mp2);
+    assert_eq!((header.frame_len, header.sample_rate, header.channels), (384, 48_000, 2));
+
+    // MPEG-2 Layer III, 64 kbit/s, 24 kHz, Mono, Padding
+    let header = parse_mpa_header(&[0xff, 0xf3, 0x86, 0xc0]).unwrap();
+    assert_eq!(header.codec, EsCodec::Mp3);
+    assert_eq!((header.frame_len, header.sample_rate, header.channels), (193, 24_000, 1));
+
+    // Free-Format und reservierte Werte
+    assert!(parse_mpa_header(&[0xff, 0xfd, 0x04, 0x00]).is_none());
+    assert!(parse_mpa_header(&[0xff, 0xfd, 0x8c, 0x00]).is_none());
+}
+
+#[test]
+fn pts_clock_follows_pts_and_resyncs_on_jumps() {
+    let mut clock = PtsClock::default();
+    let start = 1_700_000_000_000_000_000;
+    assert_eq!(clock.utc_ns(Some(90_000), start), start);
+    // 1920 Ticks = 21,33 ms, unabhängig von der Ankunftszeit
+    assert_eq!(clock.utc_ns(Some(91_920), start + 5_000_000), start + 21_333_333);
+    // Umbruch der 33-Bit-PTS
+    let mut wrapping = PtsClock::default();
+    wrapping.utc_ns(Some((1 << 33) - 900), start);
+    assert_eq!(wrapping.utc_ns(Some(900), start), start + 20_000_000);
+    // Sprung (neuer Encoder): neu verankern
+    assert_eq!(clock.utc_ns(Some(5), start + 40_000_000), start + 40_000_000);
+    assert_eq!(clock.utc_ns(None, start + 60_000_000), start + 60_000_000);
+}
+
+#[cfg(feature = "symphonia")]
+#[test]
+fn mpegts_config_is_validated() {
+    use airlift_node::config::ProducerConfig;
+    use airlift_node::producers::mpegts::MpegTsConfig;
+    use serde_json::json;
+
+    let producer = |config: serde_json::Value| ProducerConfig {
+        producer_type: "mpegts".to_string(),
+        enabled: true,
+        device: None,
+        path: None,
+        channels: None,
+        sample_rate: None,
+        loop_audio: None,
+        config: serde_json::from_value(config).unwrap(),
+    };
+    let parsed = MpegTsConfig::from_config(
+        "playout",
+        &producer(json!({ "address": "239.1.1.1:1234", "interface": "10.0.0.5", "pid": 257 })),
+    )
+    .unwrap();
+    assert!(parsed.address.ip().is_multicast());
+    assert_eq!(parsed.pid, Some(257));
+
+    assert!(MpegTsConfig::from_config("playout", &producer(json!({}))).is_err());
+    assert!(MpegTsConfig::from_config(
+        "playout",
+        &producer(json!({ "address": "239.1.1.1:1234", "pid": 9000 }))
+    )
+    .is_err());
+}
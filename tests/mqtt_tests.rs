@@ -0,0 +1,264 @@
+use std::io::Write;
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use airlift_node::config::MqttConfig;
+use airlift_node::core::{Event, EventPriority, EventType};
+use airlift_node::mqtt::{read_packet, MqttOptions, MqttPublisher, MqttRouter};
+use airlift_node::AirliftNode;
+
+fn options(broker: &str) -> MqttOptions {
+    MqttOptions::from_config(
+        &MqttConfig {
+            broker: broker.to_string(),
+            ..Default::default()
+        },
+        "studio/a",
+    )
+    .unwrap()
+}
+
+fn reading(payload: serde_json::Value) -> Event {
+    Event::new(
+        EventType::AnalyzerReading,
+        EventPriority::Info,
+        "analyzer",
+        "main",
+        payload,
+    )
+}
+
+#[test]
+fn options_apply_defaults_and_reject_invalid_values() {
+    let parsed = options("mqtt://broker.local");
+    assert_eq!((parsed.host.as_str(), parsed.port), ("broker.local", 1883));
+    assert_eq!(parsed.client_id, "airlift-studio/a");
+    assert_eq!(parsed.prefix, "airlift/studio_a");
+    assert_eq!(parsed.qos, 0);
+    assert_eq!(parsed.events, ["silence", "loudness", "peak"]);
+    assert_eq!(parsed.health_interval, Some(Duration::from_secs(10)));
+    assert_eq!(options("10.0.0.5:11883").port, 11883);
+
+    let invalid = [
+        MqttConfig {
+            broker: "mqtts://broker".into(),
+            ..Default::default()
+        },
+        MqttConfig {
+            broker: "broker:99999".into(),
+            ..Default::default()
+        },
+        MqttConfig {
+            broker: "broker".into(),
+            qos: Some(3),
+            ..Default::default()
+        },
+        MqttConfig {
+            broker: "broker".into(),
+            events: vec!["a/b".into()],
+            ..Default::default()
+        },
+        MqttConfig {
+            broker: "broker".into(),
+            topic_prefix: Some("x/#".into()),
+            ..Default::default()
+        },
+        MqttConfig {
+            broker: "broker".into(),
+            health_interval: Some("500ms".into()),
+            ..Default::default()
+        },
+        MqttConfig {
+            broker: "broker".into(),
+            password: Some("secret".into()),
+            ..Default::default()
+        },
+    ];
+    for config in invalid {
+        assert!(
+            MqttOptions::from_config(&config, "node").is_err(),
+            "{:?}",
+            config
+        );
+    }
+
+    let disabled = MqttConfig {
+        broker: "broker".into(),
+        health_interval: Some("0s".into()),
+        ..Default::default()
+    };
+    assert_eq!(
+        MqttOptions::from_config(&disabled, "node")
+            .unwrap()
+            .health_interval,
+        None
+    );
+}
+
+#[test]
+fn router_maps_events_to_flow_topics() {
+    let mut options = options("broker");
+    options.events.push("ConsumerFailover".to_string());
+    let mut router = MqttRouter::new(&options);
+
+    let silence = |silent: bool| {
+        reading(
+            serde_json::json!({ "kind": "silence", "silent": silent, "flow": "main", "tap": "out" }),
+        )
+    };
+    let first = router
+        .route(&silence(true))
+        .expect("first state is published");
+    assert_eq!(first.topic, "airlift/studio_a/flows/main/silence");
+    assert!(first.retain);
+    assert!(router.route(&silence(true)).is_none(), "unchanged state");
+    assert!(router.route(&silence(false)).is_some());
+
+    let loudness = router
+        .route(&reading(
+            serde_json::json!({ "kind": "lufs", "momentary": -23.0, "flow": "main" }),
+        ))
+        .unwrap();
+    assert_eq!(loudness.topic, "airlift/studio_a/flows/main/loudness");
+    assert!(!loudness.retain);
+    let payload: serde_json::Value = serde_json::from_slice(&loudness.payload).unwrap();
+    assert_eq!(payload["momentary"], -23.0);
+    assert!(router
+        .route(&reading(
+            serde_json::json!({ "kind": "spectrum", "flow": "main" })
+        ))
+        .is_none());
+
+    let stats = |rate: &str| {
+        Event::new(
+            EventType::AudioLevelStats,
+            EventPriority::Debug,
+            "flow",
+            "main",
+            serde_json::json!({ "rate": rate, "peaks": [0.5, 0.4] }),
+        )
+    };
+    assert_eq!(
+        router.route(&stats("stats")).unwrap().topic,
+        "airlift/studio_a/flows/main/peak"
+    );
+    assert!(router.route(&stats("aggregate")).is_none());
+
+    let error = Event::new(
+        EventType::ConsumerFailover,
+        EventPriority::Error,
+        "consumer",
+        "icecast",
+        serde_json::json!({ "error": "connection refused" }),
+    );
+    let message = router.route(&error).unwrap();
+    assert_eq!(message.topic, "airlift/studio_a/events/ConsumerFailover");
+    let payload: serde_json::Value = serde_json::from_slice(&message.payload).unwrap();
+    assert_eq!(payload["source_instance"], "icecast");
+
+    let ignored = Event::new(
+        EventType::ProducerFailover,
+        EventPriority::Error,
+        "producer",
+        "alsa",
+        serde_json::json!({}),
+    );
+    assert!(router.route(&ignored).is_none());
+}
+
+/// Liest das nächste PUBLISH (QoS 1) und bestätigt es: `(Topic, Payload, retain)`.
+fn expect_publish(stream: &mut TcpStream) -> anyhow::Result<(String, Vec<u8>, bool)> {
+    loop {
+        let (header, body) = read_packet(stream)?;
+        if header == 0xc0 {
+            stream.write_all(&[0xd0, 0])?;
+            continue;
+        }
+        anyhow::ensure!(
+            header & 0xf0 == 0x30,
+            "expected PUBLISH, got 0x{:02x}",
+            header
+        );
+        assert_eq!((header >> 1) & 0x03, 1, "QoS 1");
+        let topic_len = u16::from_be_bytes([body[0], body[1]]) as usize;
+        let topic = String::from_utf8(body[2..2 + topic_len].to_vec())?;
+        let id = &body[2 + topic_len..4 + topic_len];
+        stream.write_all(&[0x40, 2, id[0], id[1]])?;
+        return Ok((topic, body[4 + topic_len..].to_vec(), header & 0x01 == 1));
+    }
+}
+
+#[test]
+fn publisher_sends_status_health_and_events_to_broker() -> anyhow::Result<()> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let config = MqttConfig {
+        broker: format!("mqtt://{}", listener.local_addr()?),
+        client_id: Some("test-node".into()),
+        username: Some("user".into()),
+        password: Some("pass".into()),
+        qos: Some(1),
+        ..Default::default()
+    };
+    let options = MqttOptions::from_config(&config, "node")?;
+    let node = Arc::new(Mutex::new(AirliftNode::new()));
+    let event_bus = node.lock().unwrap().event_bus();
+    let mut publisher = MqttPublisher::start(options, node)?;
+
+    let (mut stream, _) = listener.accept()?;
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    let (header, connect) = read_packet(&mut stream)?;
+    assert_eq!(header, 0x10);
+    assert_eq!(&connect[2..6], b"MQTT");
+    let flags = connect[7];
+    assert_eq!(flags & 0xc0, 0xc0, "username and password");
+    assert_eq!(flags & 0x24, 0x24, "retained last will");
+    let text = String::from_utf8_lossy(&connect);
+    assert!(text.contains("test-node") && text.contains("airlift/node/status"));
+    assert!(text.contains("offline"));
+    stream.write_all(&[0x20, 2, 0, 0])?;
+
+    let (topic, payload, retain) = expect_publish(&mut stream)?;
+    assert_eq!(
+        (topic.as_str(), payload.as_slice(), retain),
+        ("airlift/node/status", &b"online"[..], true)
+    );
+    let (topic, payload, retain) = expect_publish(&mut stream)?;
+    assert_eq!(topic, "airlift/node/health");
+    assert!(retain);
+    let health: serde_json::Value = serde_json::from_slice(&payload)?;
+    assert!(health["flows"].is_array());
+    assert!(publisher.is_connected());
+
+    event_bus
+        .lock()
+        .unwrap()
+        .publish(reading(serde_json::json!({
+            "kind": "silence", "silent": true, "silent_ms": 5000, "flow": "main", "tap": "out"
+        })))?;
+    let (topic, payload, _) = expect_publish(&mut stream)?;
+    assert_eq!(topic, "airlift/node/flows/main/silence");
+    let payload: serde_json::Value = serde_json::from_slice(&payload)?;
+    assert_eq!(payload["silent_ms"], 5000);
+    // Gezählt wird erst nach dem PUBACK
+    let deadline = std::time::Instant::now() + Duration::from_secs(2);
+    while publisher.published() < 2 && std::time::Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    assert_eq!(publisher.published(), 2);
+
+    let stopper = std::thread::spawn(move || {
+        publisher.stop();
+        publisher
+    });
+    let (topic, payload, retain) = expect_publish(&mut stream)?;
+    assert_eq!(
+        (topic.as_str(), payload.as_slice(), retain),
+        ("airlift/node/status", &b"offline"[..], true)
+    );
+    let (header, _) = read_packet(&mut stream)?;
+    assert_eq!(header, 0xe0, "DISCONNECT");
+    let publisher = stopper.join().unwrap();
+    assert!(!publisher.is_connected());
+    Ok(())
+}
@@ -0,0 +1,181 @@
+use std::collections::BTreeMap;
+
+use airlift_node::audio::ogg::{paginate, OggChainer, OggPage, NO_GRANULE};
+use airlift_node::core::stream_metadata::{self, StreamMetadata};
+
+const SERIAL: u32 = 0x1234;
+
+fn opus_head(pre_skip: u16) -> Vec<u8> {
+    let mut head = b"OpusHead".to_vec();
+    head.push(1);
+    head.push(2);
+    head.extend_from_slice(&pre_skip.to_le_bytes());
+    head.extend_from_slice(&48_000u32.to_le_bytes());
+    head.extend_from_slice(&[0, 0, 0]);
+    head
+}
+
+fn opus_tags() -> Vec<u8> {
+    let mut tags = b"OpusTags".to_vec();
+    tags.extend_from_slice(&(11u32).to_le_bytes());
+    tags.extend_from_slice(b"test-vendor");
+    tags.extend_from_slice(&0u32.to_le_bytes());
+    tags
+}
+
+/// Opus-Strom wie von einem Encoder: Header, dann eine Seite je 20-ms-Paket.
+fn encoder_stream(audio_pages: u64) -> Vec<Vec<u8>> {
+    let mut sequence = 0;
+    let mut pages: Vec<Vec<u8>> = paginate(&[opus_head(312)], SERIAL, &mut sequence, true)
+        .iter()
+        .chain(paginate(&[opus_tags()], SERIAL, &mut sequence, false).iter())
+        .map(OggPage::to_bytes)
+        .collect();
+    for index in 0..audio_pages {
+        let mut page = paginate(&[vec![index as u8; 300]], SERIAL, &mut sequence, false).remove(0);
+        page.granule = 312 + (index + 1) * 960;
+        pages.push(page.to_bytes());
+    }
+    pages
+}
+
+fn parse_all(mut data: &[u8]) -> Vec<OggPage> {
+    let mut pages = Vec::new();
+    while let Some((page, len)) = OggPage::parse(data).unwrap() {
+        pages.push(page);
+        data = &data[len..];
+    }
+    assert!(data.is_empty());
+    pages
+}
+
+fn comments(page: &OggPage) -> Vec<String> {
+    let body = &page.body;
+    assert!(body.starts_with(b"OpusTags"));
+    let vendor = u32::from_le_bytes(body[8..12].try_into().unwrap()) as usize;
+    assert_eq!(&body[12..12 + vendor], b"test-vendor");
+    let mut pos = 12 + vendor;
+    let count = u32::from_le_bytes(body[pos..pos + 4].try_into().unwrap());
+    pos += 4;
+    (0..count)
+        .map(|_| {
+            let len = u32::from_le_bytes(body[pos..pos + 4].try_into().unwrap()) as usize;
+            pos += 4;
+            pos += len;
+            String::from_utf8(body[pos - len..pos].to_vec()).unwrap()
+        })
+        .collect()
+}
+
+#[test]
+fn chainer_starts_a_new_link_with_updated_tags() -> anyhow::Result<()> {
+    let stream = encoder_stream(6);
+    let mut chainer = OggChainer::new();
+    chainer.set_comments(vec!["TITLE=Morning Show".to_string()]);
+    let mut out = Vec::new();
+    // Seitengrenzen und Encoder-Pakete müssen nicht übereinstimmen
+    let joined: Vec<u8> = stream[..5].concat();
+    for chunk in joined.chunks(97) {
+        out.extend(chainer.push(chunk)?);
+    }
+    chainer.set_comments(vec!["TITLE=News".to_string(), "ARTIST=Desk".to_string()]);
+    for page in &stream[5..] {
+        out.extend(chainer.push(page)?);
+    }
+    assert_eq!(chainer.links(), 1);
+
+    let pages = parse_all(&out);
+    let first: Vec<&OggPage> = pages.iter().filter(|page| page.serial == SERIAL).collect();
+    let second: Vec<&OggPage> = pages
+        .iter()
+        .filter(|page| page.serial == SERIAL + 1)
+        .collect();
+    assert_eq!(first.len() + second.len(), pages.len());
+
+    // Erster Link: Header des Encoders, Kommentar schon ersetzt, sauber beendet
+    assert!(first[0].is_bos() && first[0].body == opus_head(312));
+    assert_eq!(comments(first[1]), ["TITLE=Morning Show"]);
+    assert_eq!(first[4].granule, 312 + 3 * 960);
+    let eos = first.last().unwrap();
+    assert!(eos.is_eos() && eos.body.is_empty());
+    assert_eq!(eos.granule, first[4].granule);
+    assert!(first
+        .iter()
+        .enumerate()
+        .all(|(index, page)| page.sequence == index as u32));
+
+    // Zweiter Link: neuer BOS ohne Pre-Skip, neue Tags, Granule ab 0
+    assert!(second[0].is_bos() && second[0].body == opus_head(0));
+    assert_eq!(comments(second[1]), ["TITLE=News", "ARTIST=Desk"]);
+    assert_eq!(second[1].sequence, 1);
+    assert_eq!(second[2].granule, 960);
+    assert_eq!(second[2].body, vec![3u8; 300]);
+    assert_eq!(second.last().unwrap().granule, 3 * 960);
+    assert!(second.iter().skip(1).all(|page| !page.is_bos()));
+    Ok(())
+}
+
+#[test]
+fn chainer_rejects_other_ogg_codecs() {
+    let mut sequence = 0;
+    let page = paginate(&[b"\x7fFLAC".to_vec()], SERIAL, &mut sequence, true).remove(0);
+    assert!(OggChainer::new().push(&page.to_bytes()).is_err());
+
+    let mut corrupt = encoder_stream(0).concat();
+    corrupt[30] ^= 0xff;
+    assert!(OggChainer::new().push(&corrupt).is_err());
+
+    // Große Pakete laufen über mehrere Seiten
+    let pages = paginate(&[vec![7u8; 255 * 300]], SERIAL, &mut sequence, false);
+    assert_eq!(pages.len(), 2);
+    assert_eq!(pages[0].granule, NO_GRANULE);
+    assert!(pages[1].is_continued() && pages[1].granule == 0);
+}
+
+#[test]
+fn metadata_is_validated_and_latest_entry_wins() -> anyhow::Result<()> {
+    let metadata = |title: &str, tags: &[(&str, &str)]| StreamMetadata {
+        title: Some(title.to_string()),
+        artist: None,
+        tags: tags
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect::<BTreeMap<_, _>>(),
+    };
+    let normalized = metadata(" Song ", &[("album", "Live"), ("genre", " ")]).normalized()?;
+    assert_eq!(normalized.comments(), ["TITLE=Song", "ALBUM=Live"]);
+    for broken in [
+        metadata(" ", &[]),
+        metadata("Song", &[("a=b", "x")]),
+        metadata("Song", &[("title", "x")]),
+        metadata(&"x".repeat(2000), &[]),
+    ] {
+        assert!(broken.clone().normalized().is_err(), "{:?}", broken);
+    }
+
+    let before = stream_metadata::version();
+    stream_metadata::set(Some("ice_meta"), metadata("Only here", &[]))?;
+    stream_metadata::set(None, metadata("Everywhere", &[]))?;
+    assert!(stream_metadata::version() >= before + 2);
+    let current = stream_metadata::current("ice_meta").unwrap();
+    assert_eq!(current.metadata.title.as_deref(), Some("Everywhere"));
+    stream_metadata::set(Some("ice_meta"), metadata("Override", &[]))?;
+    assert_eq!(
+        stream_metadata::current("ice_meta")
+            .unwrap()
+            .metadata
+            .title
+            .as_deref(),
+        Some("Override")
+    );
+    stream_metadata::clear(Some("ice_meta"));
+    assert_eq!(
+        stream_metadata::current("ice_meta")
+            .unwrap()
+            .metadata
+            .title
+            .as_deref(),
+        Some("Everywhere")
+    );
+    Ok(())
+}
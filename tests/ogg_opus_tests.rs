@@ -0,0 +1,89 @@
+use airlift_node::audio::ogg::OggPage;
+use airlift_node::codecs::ogg_opus::{opus_head, ChannelMapping, OggOpusMuxer};
+
+fn parse_all(mut data: &[u8]) -> Vec<OggPage> {
+    let mut pages = Vec::new();
+    while let Some((page, len)) = OggPage::parse(data).unwrap() {
+        pages.push(page);
+        data = &data[len..];
+    }
+    assert!(data.is_empty());
+    pages
+}
+
+#[test]
+fn surround_layouts_follow_mapping_family_1() -> anyhow::Result<()> {
+    let stereo = ChannelMapping::for_channels(2)?;
+    assert_eq!((stereo.family, stereo.streams, stereo.coupled), (0, 1, 1));
+    assert!(!stereo.is_multistream());
+
+    // 5.1 in Vorbis-Reihenfolge: FL/FR und RL/RR gekoppelt, C und LFE mono
+    let surround = ChannelMapping::for_channels(6)?;
+    assert_eq!(
+        (surround.family, surround.streams, surround.coupled),
+        (1, 4, 2)
+    );
+    assert_eq!(surround.mapping, [0, 4, 1, 2, 3, 5]);
+    assert!(surround.is_multistream());
+
+    for channels in 1..=8 {
+        let mapping = ChannelMapping::for_channels(channels)?;
+        let decoded = mapping.coupled * 2 + (mapping.streams - mapping.coupled);
+        assert_eq!(mapping.mapping.len(), channels as usize);
+        assert!(mapping.mapping.iter().all(|index| *index < decoded));
+    }
+    assert!(ChannelMapping::for_channels(0).is_err());
+    assert!(ChannelMapping::for_channels(9).is_err());
+
+    // Familie 0 ohne Tabelle, Familie 1 mit Stream-Anzahl und Mapping
+    assert_eq!(opus_head(&stereo, 312, 48_000).len(), 19);
+    let head = opus_head(&surround, 312, 48_000);
+    assert_eq!(&head[..8], b"OpusHead");
+    assert_eq!(head[9], 6);
+    assert_eq!(u16::from_le_bytes([head[10], head[11]]), 312);
+    assert_eq!(&head[18..], &[1, 4, 2, 0, 4, 1, 2, 3, 5]);
+    Ok(())
+}
+
+#[test]
+fn muxer_writes_headers_granules_and_eos() -> anyhow::Result<()> {
+    let mapping = ChannelMapping::for_channels(8)?;
+    let mut muxer = OggOpusMuxer::new(7, &mapping, 312, 48_000);
+
+    let mut bytes = Vec::new();
+    for page in muxer.pages(&[vec![1; 200], vec![2; 200]], 960) {
+        bytes.extend(page.to_bytes());
+    }
+    // Paket über mehrere Seiten (> 255 Segmente)
+    for page in muxer.pages(&[vec![3; 70_000]], 960) {
+        bytes.extend(page.to_bytes());
+    }
+    for page in muxer.finish(&[], 960) {
+        bytes.extend(page.to_bytes());
+    }
+
+    let pages = parse_all(&bytes);
+    assert!(pages.iter().all(|page| page.serial == 7));
+    assert!(pages
+        .iter()
+        .enumerate()
+        .all(|(index, page)| page.sequence == index as u32));
+
+    assert!(pages[0].is_bos());
+    assert!(pages[0].body.starts_with(b"OpusHead"));
+    assert_eq!(pages[0].body[18], 1, "mapping family 1");
+    assert!(pages[1].body.starts_with(b"OpusTags"));
+    assert_eq!((pages[0].granule, pages[1].granule), (0, 0));
+
+    assert_eq!(pages[2].granule, 312 + 2 * 960);
+    assert_eq!(pages[3].granule, u64::MAX, "no packet ends on this page");
+    assert!(pages[4].is_continued());
+    assert_eq!(pages[4].granule, 312 + 3 * 960);
+
+    let last = pages.last().unwrap();
+    assert!(last.is_eos());
+    assert_eq!(last.granule, 312 + 3 * 960);
+    assert!(pages[..pages.len() - 1].iter().all(|page| !page.is_eos()));
+    assert_eq!(muxer.granule(), 312 + 3 * 960);
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use std::time::{Duration, Instant};
+
+use airlift_node::core::{AirliftNode, AudioError, Flow, OnAirInterlock, OnAirState};
+use airlift_node::testing::mocks::{MockConsumer, MockProducer};
+use airlift_node::PcmFrame;
+
+#[test]
+fn idle_flow_cannot_go_on_air() {
+    let mut flow = Flow::new("idle");
+
+    assert_eq!(flow.on_air_state(), OnAirState::OffAir);
+    assert_eq!(flow.on_air_interlocks(), vec![OnAirInterlock::Silence]);
+
+    let result = flow.go_on_air();
+    assert!(matches!(result, Err(AudioError::InterlockActive { .. })));
+    assert_eq!(flow.on_air_state(), OnAirState::OffAir);
+}
+
+#[test]
+fn off_air_requires_confirmation_token() -> anyhow::Result<()> {
+    let frames = vec![PcmFrame {
+        utc_ns: 1,
+        samples: vec![16_000, -16_000, 16_000, -16_000],
+        sample_rate: 48_000,
+        channels: 2,
+        metadata: Default::default(),
+    }];
+
+    let mut flow = Flow::new("program");
+    flow.add_consumer(Box::new(MockConsumer::new("out")));
+
+    let mut node = AirliftNode::new();
+    node.add_flow(flow);
+    node.add_producer(Box::new(MockProducer::new("test", frames)))?;
+    node.connect_flow_input(0, "producer:test")?;
+    node.start()?;
+
+    let deadline = Instant::now() + Duration::from_secs(1);
+    while Instant::now() < deadline && !node.flow_mut("program")?.on_air_interlocks().is_empty() {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+
+    let flow = node.flow_mut("program")?;
+    flow.go_on_air()?;
+    assert_eq!(flow.on_air_state(), OnAirState::OnAir);
+
+    let token = flow.request_off_air();
+    assert!(flow.confirm_off_air("wrong").is_err());
+    assert_eq!(flow.on_air_state(), OnAirState::OnAir);
+
+    flow.confirm_off_air(&token)?;
+    assert_eq!(flow.on_air_state(), OnAirState::OffAir);
+
+    node.stop()?;
+    Ok(())
+}
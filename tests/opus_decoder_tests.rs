@@ -0,0 +1,89 @@
+#![cfg(feature = "opus")]
+
+use airlift_node::decoders::opus::{OpusDecoder, OpusDecoderOptions};
+use airlift_node::decoders::AudioDecoder;
+
+/// TOC-Byte ohne Nutzdaten: CELT-only, Fullband, 20 ms.
+const CELT_PACKET: [u8; 1] = [0xF8];
+/// TOC-Byte ohne Nutzdaten: SILK-only, Narrowband, 20 ms (kann FEC tragen).
+const SILK_PACKET: [u8; 1] = [0x08];
+
+#[test]
+fn options_from_config() -> anyhow::Result<()> {
+    assert_eq!(OpusDecoderOptions::from_config("contrib", None)?, OpusDecoderOptions::default());
+
+    let options = OpusDecoderOptions::from_config(
+        "contrib",
+        Some(&serde_json::json!({ "gain": "-3dB", "fec": false, "phase_inversion": false })),
+    )?;
+    assert_eq!(options.gain_db, -3.0);
+    assert_eq!(options.gain_q8(), -768);
+    assert!(!options.fec);
+    assert!(options.disable_phase_inversion);
+
+    for invalid in [
+        serde_json::json!({ "gain": 200 }),
+        serde_json::json!({ "fec": "yes" }),
+        serde_json::json!("loud"),
+    ] {
+        assert!(OpusDecoderOptions::from_config("contrib", Some(&invalid)).is_err());
+    }
+    Ok(())
+}
+
+#[test]
+fn decoder_applies_options() -> anyhow::Result<()> {
+    let options = OpusDecoderOptions {
+        gain_db: -6.0,
+        fec: true,
+        disable_phase_inversion: true,
+    };
+    let decoder = OpusDecoder::with_options(2, options.clone())?;
+    assert_eq!(decoder.options(), &options);
+    assert!(OpusDecoder::new(3).is_err());
+    Ok(())
+}
+
+#[test]
+fn counts_packets_and_concealment() -> anyhow::Result<()> {
+    let mut decoder = OpusDecoder::new(2)?;
+
+    let frame = decoder.decode(&CELT_PACKET)?.expect("frame");
+    assert_eq!(frame.samples.len(), 960 * 2);
+    let stats = decoder.stats().expect("stats");
+    assert_eq!(stats.packets, 1);
+    assert_eq!(stats.bandwidth.as_deref(), Some("fullband"));
+
+    // CELT-only-Folgepaket: keine FEC, beide Lücken per PLC
+    let frames = decoder.conceal(2, &CELT_PACKET)?;
+    assert_eq!(frames.len(), 2);
+    let stats = decoder.stats().expect("stats");
+    assert_eq!(stats.plc_events, 2);
+    assert_eq!(stats.fec_recoveries, 0);
+    Ok(())
+}
+
+#[test]
+fn fec_recovers_packet_before_silk_packet() -> anyhow::Result<()> {
+    let mut decoder = OpusDecoder::new(1)?;
+    decoder.decode(&SILK_PACKET)?;
+
+    let frames = decoder.conceal(2, &SILK_PACKET)?;
+    assert_eq!(frames.len(), 2);
+    let stats = decoder.stats().expect("stats");
+    assert_eq!(stats.plc_events, 1);
+    assert_eq!(stats.fec_recoveries, 1);
+
+    let mut without_fec = OpusDecoder::with_options(
+        1,
+        OpusDecoderOptions {
+            fec: false,
+            ..OpusDecoderOptions::default()
+        },
+    )?;
+    without_fec.conceal(2, &SILK_PACKET)?;
+    let stats = without_fec.stats().expect("stats");
+    assert_eq!(stats.plc_events, 2);
+    assert_eq!(stats.fec_recoveries, 0);
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use std::time::Duration;
+
+use airlift_node::codecs::ogg_opus::opus_packet_samples;
+use airlift_node::codecs::{OpusBitrateMode, OpusEncoderOptions};
+
+#[test]
+fn options_from_config() -> anyhow::Result<()> {
+    let defaults = OpusEncoderOptions::from_config("consumer", "out", None)?;
+    assert_eq!(defaults, OpusEncoderOptions::default());
+    assert_eq!(defaults.frame_samples(), 960);
+
+    let options = OpusEncoderOptions::from_config(
+        "consumer",
+        "out",
+        Some(&serde_json::json!({
+            "bitrate": "96k",
+            "complexity": 5,
+            "bitrate_mode": "CBR",
+            "frame_size": 2.5,
+        })),
+    )?;
+    assert_eq!(options.bitrate, Some(96_000));
+    assert_eq!(options.complexity, Some(5));
+    assert_eq!(options.bitrate_mode, Some(OpusBitrateMode::Cbr));
+    assert_eq!(options.frame_size, Duration::from_micros(2_500));
+    assert_eq!(options.frame_samples(), 120);
+
+    for invalid in [
+        serde_json::json!({ "bitrate": "1k" }),
+        serde_json::json!({ "bitrate": "600k" }),
+        serde_json::json!({ "complexity": 11 }),
+        serde_json::json!({ "bitrate_mode": "abr" }),
+        serde_json::json!({ "bitrate_mode": true }),
+        serde_json::json!({ "frame_size": "30ms" }),
+        serde_json::json!("fast"),
+    ] {
+        assert!(
+            OpusEncoderOptions::from_config("consumer", "out", Some(&invalid)).is_err(),
+            "{}",
+            invalid
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn packet_samples_follow_the_toc_byte() {
+    // CELT 20 ms, CELT 2,5 ms, SILK 60 ms, zwei Hybrid-Frames à 10 ms
+    assert_eq!(opus_packet_samples(&[0xF8]), Some(960));
+    assert_eq!(opus_packet_samples(&[0xE0]), Some(120));
+    assert_eq!(opus_packet_samples(&[0x18]), Some(2_880));
+    assert_eq!(opus_packet_samples(&[0x61]), Some(960));
+    assert_eq!(opus_packet_samples(&[]), None);
+    assert_eq!(opus_packet_samples(&[0xFB]), None);
+}
+
+#[cfg(feature = "opus")]
+#[test]
+fn rtp_encoder_emits_one_frame_per_packet() -> anyhow::Result<()> {
+    use airlift_node::codecs::{create_encoder_with_options, CodecKind, ContainerKind};
+
+    let options = OpusEncoderOptions {
+        bitrate: Some(64_000),
+        complexity: Some(10),
+        bitrate_mode: Some(OpusBitrateMode::Cbr),
+        frame_size: Duration::from_millis(10),
+    };
+    let mut encoder = create_encoder_with_options("opuswebrtc", 2, &options)?;
+    assert!(matches!(encoder.info().kind, CodecKind::OpusWebRtc));
+    assert_eq!(encoder.info().container, ContainerKind::Rtp);
+
+    // 100 ms Stereo: zehn 10-ms-Pakete, bei CBR alle gleich groß
+    let pcm: Vec<i16> = (0..9600).map(|n| ((n % 200) as i16 - 100) * 100).collect();
+    let frames = encoder.encode(&pcm)?;
+    assert_eq!(frames.len(), 10);
+    for frame in &frames {
+        assert_eq!(opus_packet_samples(&frame.payload), Some(480));
+        assert_eq!(frame.payload.len(), 64_000 / 8 / 100);
+    }
+    // Angefangenes Paket kommt erst mit `flush`
+    assert!(encoder.encode(&pcm[..100])?.is_empty());
+    assert_eq!(encoder.flush()?.len(), 1);
+
+    assert!(create_encoder_with_options("opuswebrtc", 6, &options).is_err());
+    let mut ogg = create_encoder_with_options("opusogg", 6, &options)?;
+    assert!(ogg.encode(&vec![0; 2880 * 6])?[0]
+        .payload
+        .starts_with(b"OggS"));
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use airlift_node::core::parallel::for_each_bounded;
+use airlift_node::core::{AirliftNode, AudioRingBuffer, Producer, ProducerStatus};
+
+#[test]
+fn results_keep_input_order_and_worker_limit() {
+    let active = AtomicUsize::new(0);
+    let peak = AtomicUsize::new(0);
+    let mut items: Vec<u64> = (0..12).collect();
+
+    let results = for_each_bounded(&mut items, 3, |item| {
+        let now = active.fetch_add(1, Ordering::SeqCst) + 1;
+        peak.fetch_max(now, Ordering::SeqCst);
+        // Spätere Elemente sind schneller fertig
+        std::thread::sleep(Duration::from_millis(24 - *item * 2));
+        active.fetch_sub(1, Ordering::SeqCst);
+        *item *= 10;
+        *item
+    });
+
+    assert_eq!(results, (0..12).map(|i| i * 10).collect::<Vec<_>>());
+    assert_eq!(items[11], 110);
+    assert!(peak.load(Ordering::SeqCst) <= 3);
+    assert!(peak.load(Ordering::SeqCst) > 1);
+}
+
+struct SlowProducer {
+    name: String,
+    fail: bool,
+}
+
+impl Producer for SlowProducer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn start(&mut self) -> anyhow::Result<()> {
+        std::thread::sleep(Duration::from_millis(200));
+        if self.fail {
+            anyhow::bail!("connection refused");
+        }
+        Ok(())
+    }
+
+    fn stop(&mut self) -> anyhow::Result<()> {
+        std::thread::sleep(Duration::from_millis(200));
+        Ok(())
+    }
+
+    fn status(&self) -> ProducerStatus {
+        ProducerStatus {
+            running: false,
+            paused: false,
+            connected: false,
+            samples_processed: 0,
+            errors: 0,
+            buffer_stats: None,
+            decoder: None,
+        }
+    }
+
+    fn attach_ring_buffer(&mut self, _buffer: Arc<AudioRingBuffer>) {}
+}
+
+#[test]
+fn node_starts_and_stops_producers_concurrently() -> anyhow::Result<()> {
+    let mut node = AirliftNode::new();
+    for i in 0..4 {
+        node.add_producer(Box::new(SlowProducer {
+            name: format!("srt{}", i),
+            fail: i == 2,
+        }))?;
+    }
+    node.set_startup_workers(4);
+    node.set_readiness_timeout(Duration::ZERO);
+
+    let started = Instant::now();
+    node.start()?;
+    assert!(started.elapsed() < Duration::from_millis(700), "start took {:?}", started.elapsed());
+
+    let stopped = Instant::now();
+    node.stop()?;
+    assert!(stopped.elapsed() < Duration::from_millis(700), "stop took {:?}", stopped.elapsed());
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use airlift_node::api::peaks::{auto_bucket_ms, PeakHistory, PeakPoint};
+
+fn point(ts: u64, peak: f32, flow: &str) -> PeakPoint {
+    PeakPoint {
+        ts,
+        peak_l: peak,
+        peak_r: peak / 2.0,
+        silence: peak < 0.01,
+        flow: flow.to_string(),
+    }
+}
+
+#[test]
+fn aggregates_min_max_avg_per_bucket_and_flow() {
+    let mut history = PeakHistory::new();
+    for (ts, peak) in [(1_000, 0.2), (1_400, 0.6), (1_900, 0.4), (2_100, 0.005)] {
+        history.push(point(ts, peak, "main"));
+    }
+    history.push(point(1_500, 0.9, "backup"));
+
+    let buckets = history.aggregate(0, 3_000, None, 1_000);
+    assert_eq!(buckets.len(), 3);
+
+    let main = &buckets[1];
+    assert_eq!((main.ts, main.flow.as_str(), main.count), (1_000, "main", 3));
+    assert_eq!((main.min_l, main.peak_l), (0.2, 0.6));
+    assert!((main.avg_l - 0.4).abs() < 1e-6);
+    assert_eq!(main.peak_r, 0.3);
+    assert!(!main.silence);
+
+    assert_eq!(buckets[0].flow, "backup");
+    assert_eq!((buckets[2].ts, buckets[2].count), (2_000, 1));
+    assert!(buckets[2].silence);
+
+    let filtered = history.aggregate(0, 3_000, Some("backup"), 1_000);
+    assert_eq!(filtered.len(), 1);
+}
+
+#[test]
+fn auto_bucket_fits_range_into_max_points() {
+    let day = 24 * 60 * 60 * 1000;
+    assert_eq!(auto_bucket_ms(0, day, 2000), 60_000);
+    assert_eq!(auto_bucket_ms(0, 10_000, 2000), 100);
+    assert_eq!(auto_bucket_ms(0, 60_000, 100), 1_000);
+    assert!(day / auto_bucket_ms(0, day, 10) <= 10);
+}
@@ -0,0 +1,191 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use airlift_node::api::peaks::PeakHistoryHandler;
+use airlift_node::core::peak_rates::{MultiRatePeaks, PeakTap};
+use airlift_node::core::{
+    Event, EventHandler, EventPriority, EventType, FlowLevels, PeakRates, PeakTier,
+};
+use airlift_node::storage::{MemoryBackend, StorageBackend};
+use airlift_node::PcmFrame;
+use serde_json::json;
+
+const MS: u64 = 1_000_000;
+
+/// 10 ms Stereo bei 48 kHz mit konstantem Sample-Wert
+fn frame(utc_ns: u64, value: i16) -> PcmFrame {
+    PcmFrame {
+        utc_ns,
+        samples: vec![value; 960],
+        sample_rate: 48_000,
+        channels: 2,
+        metadata: Default::default(),
+    }
+}
+
+#[test]
+fn slower_rates_are_rolled_up_from_meter_windows() {
+    let mut peaks = MultiRatePeaks::new(PeakRates::default());
+    let mut windows = Vec::new();
+    for index in 0..1000u64 {
+        // Eine übersteuerte 10-ms-Strecke in Sekunde 5
+        let value = if index == 500 { i16::MAX } else { 16384 };
+        windows.extend(peaks.push(&frame(index * 10 * MS, value)));
+    }
+
+    let count = |tier| windows.iter().filter(|w| w.tier == tier).count();
+    assert_eq!(
+        (
+            count(PeakTier::Meter),
+            count(PeakTier::Stats),
+            count(PeakTier::Aggregate)
+        ),
+        (200, 10, 1)
+    );
+
+    let meter = &windows[0];
+    assert_eq!(
+        (meter.window_ms, meter.frames, meter.utc_ns),
+        (50, 2400, 50 * MS)
+    );
+    assert_eq!(meter.peaks, [0.5, 0.5]);
+    assert!((meter.rms[0] - 0.5).abs() < 1e-6);
+
+    // Statistik-Fenster folgt direkt auf das Meter-Fenster, das es schließt
+    assert_eq!(windows[20].tier, PeakTier::Stats);
+    assert_eq!(windows[20].utc_ns, windows[19].utc_ns);
+
+    let aggregate = windows.last().unwrap();
+    assert_eq!(aggregate.tier, PeakTier::Aggregate);
+    assert_eq!((aggregate.frames, aggregate.utc_ns), (480_000, 10_000 * MS));
+    assert_eq!(aggregate.clipped, 960);
+    assert!(aggregate.peaks[0] > 0.99);
+    assert!(!aggregate.silence);
+}
+
+#[test]
+fn mono_is_mirrored_and_rate_changes_restart_windows() {
+    let mut peaks = MultiRatePeaks::new(PeakRates::default());
+    let mono = PcmFrame {
+        utc_ns: 0,
+        samples: vec![-3277; 2400],
+        sample_rate: 48_000,
+        channels: 1,
+        metadata: Default::default(),
+    };
+    let windows = peaks.push(&mono);
+    assert_eq!(windows.len(), 1);
+    assert_eq!(windows[0].peaks[0], windows[0].peaks[1]);
+
+    // Halbes Fenster bei 48 kHz, danach 44.1 kHz: neues Fenster ab dort
+    peaks.push(&PcmFrame {
+        samples: vec![0; 1200],
+        ..mono.clone()
+    });
+    let windows = peaks.push(&PcmFrame {
+        samples: vec![0; 2205],
+        sample_rate: 44_100,
+        ..mono
+    });
+    assert_eq!(windows.len(), 1);
+    assert_eq!(windows[0].frames, 2205);
+    assert!(windows[0].silence);
+}
+
+#[test]
+fn peak_rates_are_parsed_and_validated() -> anyhow::Result<()> {
+    assert_eq!(
+        PeakRates::from_config("main", &json!(true))?,
+        PeakRates::default()
+    );
+    let rates = PeakRates::from_config(
+        "main",
+        &json!({ "meter": "100ms", "stats": "2s", "aggregate": "1min" }),
+    )?;
+    assert_eq!(
+        (rates.meter, rates.stats, rates.aggregate),
+        (
+            Duration::from_millis(100),
+            Duration::from_secs(2),
+            Duration::from_secs(60)
+        )
+    );
+
+    for config in [
+        json!("fast"),
+        json!({ "meter": "5ms" }),
+        json!({ "meter": "100ms", "stats": "50ms" }),
+        json!({ "meter": "30ms" }),
+        json!({ "stats": "1500ms" }),
+    ] {
+        assert!(
+            PeakRates::from_config("main", &config).is_err(),
+            "{}",
+            config
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn tap_updates_levels_and_silence_flag() {
+    let silence = Arc::new(AtomicBool::new(true));
+    let levels = Arc::new(Mutex::new(FlowLevels::default()));
+    let mut tap = PeakTap::new(
+        "main",
+        PeakRates::default(),
+        silence.clone(),
+        levels.clone(),
+    );
+
+    for index in 0..100u64 {
+        tap.push(&frame(index * 10 * MS, 8192));
+    }
+    tap.publish();
+    assert!(!silence.load(Ordering::Relaxed));
+    let snapshot = levels.lock().unwrap().clone();
+    assert_eq!(snapshot.meter.unwrap().peaks, [0.25, 0.25]);
+    assert_eq!(snapshot.stats.unwrap().window_ms, 1000);
+    assert!(snapshot.aggregate.is_none());
+
+    for index in 100..105u64 {
+        tap.push(&frame(index * 10 * MS, 0));
+    }
+    assert!(silence.load(Ordering::Relaxed));
+}
+
+#[test]
+fn peak_history_keeps_only_stats_windows() -> anyhow::Result<()> {
+    let storage = Arc::new(MemoryBackend::new());
+    let handler = PeakHistoryHandler::new("history", storage.clone());
+    assert!(handler
+        .event_type_filter()
+        .unwrap()
+        .iter()
+        .any(|filter| filter.matches(&EventType::AudioLevelStats)));
+
+    for (rate, ms) in [
+        ("stats", 1_700_000_000_000),
+        ("aggregate", 1_700_000_010_000),
+    ] {
+        handler.handle_event(&Event::new(
+            EventType::AudioLevelStats,
+            EventPriority::Debug,
+            "flow",
+            "main",
+            json!({
+                "timestamp": ms * MS,
+                "rate": rate,
+                "peaks": [0.5, 0.25],
+                "silence": false,
+                "flow": "main",
+            }),
+        ))?;
+    }
+
+    let points = storage.peaks(0, u64::MAX, Some("main"))?;
+    assert_eq!(points.len(), 1);
+    assert_eq!((points[0].ts, points[0].peak_r), (1_700_000_000_000, 0.25));
+    Ok(())
+}
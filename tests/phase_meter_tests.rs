@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+use std::f64::consts::PI;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use airlift_node::core::processor::Processor;
+use airlift_node::core::{Event, EventBus, EventEmitter, EventHandler, EventType};
+use airlift_node::processors::PhaseMeter;
+use airlift_node::PcmFrame;
+
+/// 100 ms Stereo-Sinus bei 48 kHz mit Amplituden je Kanal; negative
+/// Amplitude = verpolt
+fn block(index: u64, left: f64, right: f64) -> PcmFrame {
+    let samples = (0..4800)
+        .flat_map(|n| {
+            let value = (2.0 * PI * 440.0 * n as f64 / 48_000.0).sin();
+            [(left * value) as i16, (right * value) as i16]
+        })
+        .collect();
+    PcmFrame {
+        utc_ns: index * 100_000_000,
+        samples,
+        sample_rate: 48_000,
+        channels: 2,
+        metadata: Default::default(),
+    }
+}
+
+fn phase_meter(config: serde_json::Value) -> anyhow::Result<PhaseMeter> {
+    let config: HashMap<String, serde_json::Value> = serde_json::from_value(config)?;
+    PhaseMeter::from_config("phase", &config)
+}
+
+struct Collector {
+    events: Mutex<Vec<Event>>,
+}
+
+impl EventHandler for Collector {
+    fn handle_event(&self, event: &Event) -> anyhow::Result<()> {
+        self.events.lock().unwrap().push(event.clone());
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "collector"
+    }
+
+    fn event_type_filter(&self) -> Option<Vec<EventType>> {
+        Some(vec![
+            EventType::custom("phase_problem"),
+            EventType::custom("phase_recovered"),
+        ])
+    }
+}
+
+#[test]
+fn alarm_after_negative_correlation_and_recovery() -> anyhow::Result<()> {
+    let mut bus = EventBus::new("test");
+    bus.start()?;
+    let collector = Arc::new(Collector {
+        events: Mutex::new(Vec::new()),
+    });
+    bus.register_handler(collector.clone())?;
+    let bus = Arc::new(Mutex::new(bus));
+
+    let mut meter = phase_meter(serde_json::json!({ "window": "100ms", "duration": "1s" }))?;
+    meter.attach_event_emitter(EventEmitter::new(bus.clone(), "processor", "phase"));
+
+    // 0,5 s gleichphasig, 1,5 s mit verpoltem rechten Kanal, dann wieder richtig
+    let mut frames: Vec<PcmFrame> = (0..5).map(|i| block(i, 8000.0, 8000.0)).collect();
+    meter.process_batch(&mut frames)?;
+    let reading = meter.latest().unwrap();
+    assert!(reading.correlation > 0.99, "{:?}", reading);
+    assert!(reading.balance.abs() < 0.01, "{:?}", reading);
+
+    let mut frames: Vec<PcmFrame> = (5..20).map(|i| block(i, 8000.0, -8000.0)).collect();
+    meter.process_batch(&mut frames)?;
+    assert!(meter.latest().unwrap().correlation < -0.99);
+    assert!(meter.is_alarm());
+    assert_eq!(meter.alarms(), 1);
+    assert_eq!(meter.status().metrics.unwrap()["phase_alarm"], true);
+
+    // Stille zählt weder für noch gegen den Alarm
+    meter.process_batch(&mut vec![block(20, 0.0, 0.0)])?;
+    assert!(meter.is_alarm());
+    meter.process_batch(&mut vec![block(21, 8000.0, 8000.0)])?;
+    assert!(!meter.is_alarm());
+
+    let deadline = Instant::now() + Duration::from_secs(1);
+    while Instant::now() < deadline && collector.events.lock().unwrap().len() < 2 {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    bus.lock().unwrap().stop()?;
+
+    let events = collector.events.lock().unwrap();
+    assert_eq!(events.len(), 2);
+    assert!(events[0]
+        .event_type
+        .matches(&EventType::custom("phase_problem")));
+    assert_eq!(events[0].payload["negative_since_utc_ns"], 500_000_000u64);
+    assert_eq!(events[0].payload["negative_ms"], 1000);
+    assert!(events[1]
+        .event_type
+        .matches(&EventType::custom("phase_recovered")));
+    assert_eq!(events[1].payload["negative_ms"], 1600);
+    Ok(())
+}
+
+#[test]
+fn balance_in_status_metrics_and_config_validation() -> anyhow::Result<()> {
+    let mut meter = phase_meter(serde_json::json!({}))?;
+    assert!(meter.status().metrics.unwrap()["correlation"].is_null());
+
+    // Nur links: Balance -1, Signal bleibt unverändert
+    let mut frames: Vec<PcmFrame> = (0..3).map(|i| block(i, 8000.0, 0.0)).collect();
+    let original = frames[0].samples.clone();
+    meter.process_batch(&mut frames)?;
+    assert_eq!(frames[0].samples, original);
+    let metrics = meter.status().metrics.unwrap();
+    assert!((metrics["balance"].as_f64().unwrap() + 1.0).abs() < 1e-9);
+    assert_eq!(metrics["utc_ns"], 300_000_000u64);
+
+    // Rechts 6 dB lauter
+    let mut frames: Vec<PcmFrame> = (3..6).map(|i| block(i, 4000.0, 8000.0)).collect();
+    meter.process_batch(&mut frames)?;
+    let balance = meter.latest().unwrap().balance;
+    assert!((balance - 1.0 / 3.0).abs() < 0.01, "balance {}", balance);
+
+    assert!(phase_meter(serde_json::json!({ "alarm_below": 2 })).is_err());
+    assert!(phase_meter(serde_json::json!({ "window": "1ms" })).is_err());
+    assert!(phase_meter(serde_json::json!({ "duration": "0s" })).is_err());
+    Ok(())
+}
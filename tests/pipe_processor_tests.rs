@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use airlift_node::core::processor::Processor;
+use airlift_node::processors::PipeProcessor;
+use airlift_node::PcmFrame;
+
+/// 10 ms Stereo bei 48 kHz, Rampe ab `index`
+fn block(index: u64) -> PcmFrame {
+    PcmFrame {
+        utc_ns: index * 10_000_000,
+        samples: (0..960).map(|n| (index as i16) * 100 + n as i16).collect(),
+        sample_rate: 48_000,
+        channels: 2,
+        metadata: Default::default(),
+    }
+}
+
+fn pipe(config: serde_json::Value) -> anyhow::Result<PipeProcessor> {
+    let config: HashMap<String, serde_json::Value> = serde_json::from_value(config)?;
+    PipeProcessor::from_config("dsp", &config)
+}
+
+/// Ruft den Processor auf, bis `count` Frames zurück sind
+fn run(
+    processor: &mut PipeProcessor,
+    input: Vec<PcmFrame>,
+    count: usize,
+) -> anyhow::Result<Vec<PcmFrame>> {
+    let mut output = Vec::new();
+    let mut batch = input;
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while output.len() < count && Instant::now() < deadline {
+        processor.process_batch(&mut batch)?;
+        output.append(&mut batch);
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    Ok(output)
+}
+
+#[test]
+fn streams_pcm_through_external_command() -> anyhow::Result<()> {
+    let mut processor = pipe(serde_json::json!({ "command": "cat" }))?;
+    let input: Vec<PcmFrame> = (0..20).map(block).collect();
+    let output = run(&mut processor, input.clone(), 20)?;
+    assert!(processor.is_running());
+    assert_eq!(output.len(), 20);
+    for (got, expected) in output.iter().zip(&input) {
+        assert_eq!(got.utc_ns, expected.utc_ns);
+        assert_eq!(got.samples, expected.samples);
+    }
+    assert_eq!(processor.status().metrics.unwrap()["starts"], 1);
+
+    // `{rate}` und `{channels}` kommen aus dem Format des Flows
+    let mut processor = pipe(serde_json::json!({
+        "command": "sh",
+        "args": ["-c", "test {rate} = 48000 && test {channels} = 2 && exec cat"],
+    }))?;
+    let output = run(&mut processor, vec![block(0)], 1)?;
+    assert_eq!(output[0].samples, block(0).samples);
+    assert_eq!(processor.restarts(), 0);
+    Ok(())
+}
+
+#[test]
+fn watchdog_restarts_and_falls_back() -> anyhow::Result<()> {
+    // Liest nichts und antwortet nie: nach `timeout` Neustart, Frames stumm
+    let mut processor = pipe(serde_json::json!({
+        "command": "sleep",
+        "args": ["30"],
+        "timeout": "200ms",
+        "restart_delay": "10s",
+        "on_failure": "silence",
+    }))?;
+    let output = run(&mut processor, vec![block(1), block(2)], 2)?;
+    assert_eq!(output.len(), 2);
+    assert!(output
+        .iter()
+        .all(|frame| frame.samples.iter().all(|s| *s == 0)));
+    assert_eq!(processor.restarts(), 1);
+    assert!(!processor.is_running());
+
+    // Während `restart_delay` laufen Frames direkt durch die Rückfallebene
+    let mut frames = vec![block(3)];
+    processor.process_batch(&mut frames)?;
+    assert_eq!(frames.len(), 1);
+    assert!(frames[0].samples.iter().all(|s| *s == 0));
+
+    // Beendet sich der Prozess, geht das Programm unverändert weiter
+    let mut processor = pipe(serde_json::json!({ "command": "true", "restart_delay": "10s" }))?;
+    let output = run(&mut processor, vec![block(4)], 1)?;
+    assert_eq!(output[0].samples, block(4).samples);
+    assert_eq!(processor.restarts(), 1);
+
+    assert!(pipe(serde_json::json!({})).is_err());
+    assert!(pipe(serde_json::json!({ "command": "cat", "on_failure": "retry" })).is_err());
+    assert!(pipe(serde_json::json!({ "command": "cat", "args": "-u" })).is_err());
+    Ok(())
+}
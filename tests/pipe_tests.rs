@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use airlift_node::config::{ConsumerConfig, ProducerConfig};
+use airlift_node::consumers::pipe::{PipeConsumer, PipeOutput, PipeOutputConfig, PipeTarget};
+use airlift_node::core::{AudioRingBuffer, Consumer, Producer};
+use airlift_node::producers::pipe::{PipeConfig, PipeProducer, PipeSource, RawFormat};
+use airlift_node::PcmFrame;
+
+fn config(path: Option<&str>, values: serde_json::Value) -> ProducerConfig {
+    ProducerConfig {
+        producer_type: "pipe".to_string(),
+        path: path.map(str::to_string),
+        channels: Some(2),
+        sample_rate: Some(48_000),
+        config: serde_json::from_value::<HashMap<String, serde_json::Value>>(values).unwrap(),
+        ..ProducerConfig::default()
+    }
+}
+
+#[test]
+fn raw_formats_decode_to_s16() {
+    assert_eq!(RawFormat::S16Le.decode(&[0x34, 0x12, 0xff]), vec![0x1234]);
+    assert_eq!(RawFormat::S16Be.decode(&[0x12, 0x34]), vec![0x1234]);
+    assert_eq!(RawFormat::S24Le.decode(&[0xaa, 0x34, 0x12]), vec![0x1234]);
+    assert_eq!(RawFormat::S32Le.decode(&[0, 0, 0x34, 0x12]), vec![0x1234]);
+    let half = 0.5f32.to_le_bytes();
+    assert_eq!(RawFormat::F32Le.decode(&half), vec![16_384]);
+    assert!(RawFormat::parse("mp3").is_err());
+}
+
+#[test]
+fn config_defaults_to_stdin_s16le() {
+    let stdin = PipeConfig::from_producer_config("in", &config(Some("-"), serde_json::json!({})))
+        .unwrap();
+    assert_eq!(stdin.source, PipeSource::Stdin);
+    assert_eq!(stdin.format, RawFormat::S16Le);
+    assert!(!stdin.reopen, "stdin cannot be reopened");
+    // 20 ms · 48 kHz · 2 Kanäle · 2 Bytes
+    assert_eq!(stdin.frame_bytes(), 3840);
+
+    let fifo = PipeConfig::from_producer_config(
+        "in",
+        &config(Some("/run/airlift/in.fifo"), serde_json::json!({ "format": "f32le", "frame_ms": 10 })),
+    )
+    .unwrap();
+    assert!(fifo.reopen);
+    assert_eq!(fifo.frame_bytes(), 480 * 2 * 4);
+
+    let bad = config(None, serde_json::json!({ "format": "s8" }));
+    assert!(PipeConfig::from_producer_config("in", &bad).is_err());
+}
+
+#[test]
+fn reads_file_into_frames_until_eof() -> anyhow::Result<()> {
+    let path = std::env::temp_dir().join(format!("airlift-pipe-{}.raw", std::process::id()));
+    // 30 ms Stereo-s16le: ein volles 20-ms-Frame und ein 10-ms-Rest
+    let bytes: Vec<u8> = (0..2880u32)
+        .flat_map(|i| (i as i16).to_le_bytes())
+        .collect();
+    std::fs::write(&path, &bytes)?;
+
+    let cfg = config(Some(&path.to_string_lossy()), serde_json::json!({ "reopen": false }));
+    let mut producer = PipeProducer::new("pipe", &cfg)?;
+    let ring = Arc::new(AudioRingBuffer::new(16));
+    producer.attach_ring_buffer(ring.clone());
+    producer.start()?;
+
+    let deadline = Instant::now() + Duration::from_secs(2);
+    while Instant::now() < deadline && producer.status().running {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    producer.stop()?;
+    std::fs::remove_file(&path)?;
+
+    let first = ring.pop().expect("first frame");
+    let second = ring.pop().expect("remainder frame");
+    assert_eq!(first.samples.len(), 1920);
+    assert_eq!(second.samples.len(), 960);
+    assert_eq!(second.samples[959], 2879);
+    assert_eq!(producer.status().samples_processed, 2880);
+    Ok(())
+}
+
+fn consumer_config(path: Option<&str>, values: serde_json::Value) -> ConsumerConfig {
+    ConsumerConfig {
+        consumer_type: "pipe".to_string(),
+        enabled: true,
+        path: path.map(str::to_string),
+        url: None,
+        config: serde_json::from_value(values).unwrap(),
+    }
+}
+
+#[test]
+fn raw_formats_round_trip_through_encode() {
+    let samples = [0i16, 1, -1, 0x1234, i16::MIN, i16::MAX];
+    for format in ["s16le", "s16be", "s24le", "s32le", "f32le"] {
+        let format = RawFormat::parse(format).unwrap();
+        let bytes = format.encode(&samples);
+        assert_eq!(bytes.len(), samples.len() * format.bytes_per_sample());
+        assert_eq!(format.decode(&bytes), samples, "{:?}", format);
+    }
+    assert_eq!(RawFormat::S24Le.encode(&[0x1234]), [0x00, 0x34, 0x12]);
+}
+
+#[test]
+fn pipe_output_config_defaults_to_stdout_s16le() -> anyhow::Result<()> {
+    let stdout = PipeOutputConfig::from_config("out", &consumer_config(None, serde_json::json!({})))?;
+    assert_eq!(stdout.target, PipeTarget::Stdout);
+    assert_eq!(stdout.output, PipeOutput::Raw(RawFormat::S16Le));
+    assert!(!stdout.reopen, "stdout cannot be reopened");
+
+    let fifo = PipeOutputConfig::from_config(
+        "out",
+        &consumer_config(Some("/run/airlift/out.fifo"), serde_json::json!({ "codec": "PCM" })),
+    )?;
+    assert!(fifo.reopen);
+    assert_eq!(fifo.output, PipeOutput::Encoded("pcm".to_string()));
+
+    for (path, values) in [
+        (None, serde_json::json!({ "format": "s8" })),
+        (None, serde_json::json!({ "codec": "wma" })),
+        (None, serde_json::json!({ "format": "s16le", "codec": "pcm" })),
+        (Some("/tmp/../etc/out"), serde_json::json!({})),
+        (Some("/tmp/out"), serde_json::json!({ "reopen": "yes" })),
+    ] {
+        assert!(PipeOutputConfig::from_config("out", &consumer_config(path, values.clone())).is_err(), "{}", values);
+    }
+    Ok(())
+}
+
+#[test]
+fn pipe_consumer_writes_raw_pcm_to_path() -> anyhow::Result<()> {
+    let path = std::env::temp_dir().join(format!("airlift-pipe-out-{}.raw", std::process::id()));
+    let cfg = consumer_config(
+        Some(&path.to_string_lossy()),
+        serde_json::json!({ "format": "s16be", "reopen": false }),
+    );
+    let mut consumer = PipeConsumer::new("out", &cfg)?;
+    let ring = Arc::new(AudioRingBuffer::new(16));
+    consumer.attach_input_buffer(ring.clone());
+    consumer.start()?;
+
+    let deadline = Instant::now() + Duration::from_secs(2);
+    while Instant::now() < deadline && !consumer.status().connected {
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    for index in 0..3u64 {
+        ring.push(PcmFrame {
+            utc_ns: index * 10_000_000,
+            samples: vec![0x0102; 960],
+            sample_rate: 48_000,
+            channels: 2,
+            metadata: Default::default(),
+        });
+    }
+    while Instant::now() < deadline && consumer.status().frames_processed < 3 {
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    consumer.stop()?;
+
+    let bytes = std::fs::read(&path)?;
+    std::fs::remove_file(&path)?;
+    assert_eq!(bytes.len(), 3 * 960 * 2);
+    assert_eq!(&bytes[..4], &[0x01, 0x02, 0x01, 0x02]);
+    assert_eq!(consumer.status().bytes_written, bytes.len() as u64);
+    Ok(())
+}
@@ -0,0 +1,210 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use airlift_node::core::consumer::file_writer::FileConsumer;
+use airlift_node::core::timestamp::utc_ns_now;
+use airlift_node::core::{
+    AudioRingBuffer, Consumer, EventBus, EventEmitter, EventPriority, EventType, PreRoll,
+    PreRollBuffer, TimeZone,
+};
+use airlift_node::PcmFrame;
+use serde_json::json;
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir =
+        std::env::temp_dir().join(format!("airlift-pre-roll-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn config(value: serde_json::Value) -> HashMap<String, serde_json::Value> {
+    serde_json::from_value(value).unwrap()
+}
+
+/// 100 ms Stereo bei 48 kHz
+fn frame(utc_ns: u64) -> PcmFrame {
+    PcmFrame {
+        utc_ns,
+        samples: vec![1; 9600],
+        sample_rate: 48_000,
+        channels: 2,
+        metadata: Default::default(),
+    }
+}
+
+fn wait_until(condition: impl Fn() -> bool) {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while !condition() && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
+
+fn wavs_in(dir: &PathBuf) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(dir)
+        .unwrap()
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|name| name.ends_with(".wav"))
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn pre_roll_config_and_buffer() -> anyhow::Result<()> {
+    let pre_roll = PreRoll::from_config(
+        "rec",
+        &config(json!({ "pre_roll": "10s", "record_on": ["ProducerFailover", "silence_end"] })),
+    )?
+    .unwrap();
+    assert_eq!(pre_roll.duration, Duration::from_secs(10));
+    assert_eq!(pre_roll.record_for, Duration::from_secs(60));
+    assert!(pre_roll.record_on[0].matches(&EventType::ProducerFailover));
+    assert!(pre_roll.record_on[1].matches(&EventType::custom("silence_end")));
+
+    assert!(PreRoll::from_config("rec", &HashMap::new())?.is_none());
+    for broken in [
+        json!({ "record_on": "Error" }),
+        json!({ "pre_roll": "1h" }),
+        json!({ "pre_roll": "5s", "record_on": 3 }),
+        json!({ "pre_roll": "5s", "record_for": "0s" }),
+    ] {
+        assert!(
+            PreRoll::from_config("rec", &config(broken.clone())).is_err(),
+            "{}",
+            broken
+        );
+    }
+
+    // Hält die jüngsten Frames, zusammen mindestens 250 ms
+    let mut held = PreRollBuffer::new(Duration::from_millis(250));
+    for index in 0..10 {
+        held.push(frame(index));
+    }
+    assert_eq!(held.held(), Duration::from_millis(300));
+    assert_eq!(held.first_utc_ns(), Some(7));
+    assert_eq!(held.take().len(), 3);
+    assert_eq!(held.held(), Duration::ZERO);
+    Ok(())
+}
+
+#[test]
+fn triggered_recording_starts_with_the_pre_roll() -> anyhow::Result<()> {
+    let dir = temp_dir("manual");
+    let template = format!("{}/rec-%H%M%S.wav", dir.display());
+    let mut consumer = FileConsumer::from_config(
+        "rec",
+        &template,
+        &config(json!({ "pre_roll": "300ms" })),
+        TimeZone::utc(),
+    )?;
+    let buffer = Arc::new(AudioRingBuffer::new(4096));
+    consumer.attach_input_buffer(buffer.clone());
+    consumer.start()?;
+
+    // Scharf, aber ohne Auslöser: nur der Vorlauf läuft mit
+    let start_ns = utc_ns_now();
+    for index in 0..10 {
+        buffer.push(frame(start_ns + index * 100_000_000));
+    }
+    wait_until(|| buffer.available_for_reader("consumer:rec") == 0);
+    assert!(!consumer.recording());
+    assert!(wavs_in(&dir).is_empty());
+
+    consumer.update_config(json!({ "record": true }))?;
+    for index in 10..15 {
+        buffer.push(frame(start_ns + index * 100_000_000));
+    }
+    wait_until(|| consumer.status().frames_processed >= 8);
+    assert!(consumer.recording());
+
+    // Beenden greift mit dem nächsten Frame
+    consumer.update_config(json!({ "record": false }))?;
+    buffer.push(frame(start_ns + 15 * 100_000_000));
+    wait_until(|| !consumer.recording());
+    consumer.stop()?;
+
+    let wavs = wavs_in(&dir);
+    let expected =
+        TimeZone::utc().format_template("rec-%H%M%S.wav", (start_ns + 700_000_000) / 1_000_000);
+    assert_eq!(
+        wavs,
+        vec![expected],
+        "file is named after the first pre-roll frame"
+    );
+    let duration = hound::WavReader::open(dir.join(&wavs[0]))?.duration();
+    assert_eq!(duration, 8 * 4800, "3 pre-roll frames + 5 live frames");
+
+    let mut continuous =
+        FileConsumer::from_config("plain", &template, &HashMap::new(), TimeZone::utc())?;
+    assert!(continuous.update_config(json!({ "record": true })).is_err());
+
+    let _ = fs::remove_dir_all(&dir);
+    Ok(())
+}
+
+#[test]
+fn events_trigger_a_recording_for_record_for() -> anyhow::Result<()> {
+    let dir = temp_dir("event");
+    let template = format!("{}/incident-{{time}}.wav", dir.display());
+    let mut consumer = FileConsumer::from_config(
+        "incident",
+        &template,
+        &config(json!({ "pre_roll": "200ms", "record_on": "silence_end", "record_for": "300ms" })),
+        TimeZone::utc(),
+    )?;
+    let mut bus = EventBus::new("pre_roll_test");
+    bus.start()?;
+    let bus = Arc::new(Mutex::new(bus));
+    consumer.attach_event_emitter(EventEmitter::new(bus.clone(), "consumer", "incident"));
+    let buffer = Arc::new(AudioRingBuffer::new(4096));
+    consumer.attach_input_buffer(buffer.clone());
+    consumer.start()?;
+
+    let start_ns = utc_ns_now();
+    for index in 0..5 {
+        buffer.push(frame(start_ns + index * 100_000_000));
+    }
+    wait_until(|| buffer.available_for_reader("consumer:incident") == 0);
+    assert!(!consumer.recording());
+
+    // Nur `silence_end` löst aus
+    EventEmitter::new(bus.clone(), "processor", "silence").emit(
+        EventType::custom("silence_start"),
+        EventPriority::Info,
+        json!({}),
+    );
+    EventEmitter::new(bus.clone(), "processor", "silence").emit(
+        EventType::custom("silence_end"),
+        EventPriority::Info,
+        json!({}),
+    );
+    std::thread::sleep(Duration::from_millis(100));
+    buffer.push(frame(start_ns + 5 * 100_000_000));
+    wait_until(|| consumer.recording());
+    assert!(consumer.recording());
+
+    // Nach `record_for` endet die Aufnahme
+    std::thread::sleep(Duration::from_millis(400));
+    buffer.push(frame(start_ns + 6 * 100_000_000));
+    wait_until(|| !consumer.recording());
+    assert!(!consumer.recording());
+    consumer.stop()?;
+    bus.lock().unwrap().stop()?;
+
+    let wavs = wavs_in(&dir);
+    assert_eq!(wavs.len(), 1, "{:?}", wavs);
+    let duration = hound::WavReader::open(dir.join(&wavs[0]))?.duration();
+    assert_eq!(
+        duration,
+        3 * 4800,
+        "2 pre-roll frames + the triggering frame"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+    Ok(())
+}
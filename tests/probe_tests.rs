@@ -0,0 +1,48 @@
+use airlift_node::decoders::probe::{probe_path, sniff};
+
+#[test]
+fn probes_wav_file() -> anyhow::Result<()> {
+    let path = std::env::temp_dir().join(format!("airlift-probe-{}.wav", std::process::id()));
+    let spec = hound::WavSpec {
+        channels: 2,
+        sample_rate: 44_100,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut writer = hound::WavWriter::create(&path, spec)?;
+    for _ in 0..100 {
+        writer.write_sample(0i16)?;
+    }
+    writer.finalize()?;
+
+    let result = probe_path(&path)?;
+    std::fs::remove_file(&path)?;
+
+    let format = result.format.expect("wav detected");
+    assert_eq!(format.codec, "pcm");
+    assert_eq!(format.container, "wav");
+    assert_eq!(format.sample_rate, Some(44_100));
+    assert_eq!(format.channels, Some(2));
+    assert_eq!(format.bitrate, Some(44_100 * 2 * 16));
+    Ok(())
+}
+
+#[test]
+fn sniffs_ogg_opus_header() {
+    let mut page = b"OggS".to_vec();
+    page.extend_from_slice(&[0; 22]);
+    page.push(1); // ein Segment
+    page.push(19);
+    page.extend_from_slice(b"OpusHead\x01\x02\x38\x01\x80\xbb\x00\x00\x00\x00\x00");
+
+    let format = sniff(&page).expect("opus detected");
+    assert_eq!(format.codec, "opus");
+    assert_eq!(format.channels, Some(2));
+    assert_eq!(format.sample_rate, Some(48_000));
+}
+
+#[test]
+fn unknown_data_is_not_detected() {
+    assert!(sniff(b"hello world, this is not audio").is_none());
+    assert!(probe_path(std::path::Path::new("/nonexistent/airlift.wav")).is_err());
+}
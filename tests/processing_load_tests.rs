@@ -0,0 +1,159 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use airlift_node::core::processing_load::{frame_duration, LoadTransition};
+use airlift_node::core::{LoadMonitor, OverloadAction, ProcessingBudget, ProcessingLoad};
+use airlift_node::PcmFrame;
+
+const S: u64 = 1_000_000_000;
+
+fn budget(action: OverloadAction) -> ProcessingBudget {
+    ProcessingBudget {
+        limit: 0.8,
+        recover: 0.5,
+        hold: Duration::from_secs(1),
+        action,
+        optional: vec!["meter".to_string()],
+    }
+}
+
+fn monitor(budget: Option<ProcessingBudget>) -> (LoadMonitor, Arc<Mutex<ProcessingLoad>>) {
+    let load = Arc::new(Mutex::new(ProcessingLoad::default()));
+    (LoadMonitor::new("main", budget, load.clone()), load)
+}
+
+/// Ein volles Messfenster mit dem gegebenen Echtzeitfaktor.
+fn window(monitor: &mut LoadMonitor, now_ns: u64, factor: f64) -> Option<LoadTransition> {
+    let audio = Duration::from_millis(500);
+    monitor.record_at(now_ns, audio.mul_f64(factor), audio)
+}
+
+#[test]
+fn realtime_factor_is_measured_per_window() {
+    let (mut monitor, load) = monitor(None);
+    let audio = Duration::from_millis(100);
+
+    for _ in 0..4 {
+        assert_eq!(monitor.record_at(0, Duration::from_millis(30), audio), None);
+    }
+    assert_eq!(load.lock().unwrap().realtime_factor, 0.0);
+    assert_eq!(load.lock().unwrap().last_iteration_us, 30_000);
+
+    monitor.record_at(0, Duration::from_millis(30), audio);
+    let load = monitor.load();
+    assert!((load.realtime_factor - 0.3).abs() < 1e-4);
+    assert_eq!(load.limit, None);
+    assert!(!load.overloaded);
+}
+
+#[test]
+fn overload_needs_hold_and_recovery_uses_hysteresis() {
+    let (mut monitor, _) = monitor(Some(budget(OverloadAction::Passthrough)));
+
+    assert_eq!(window(&mut monitor, 0, 1.2), None);
+    assert!(!monitor.passthrough());
+    assert_eq!(window(&mut monitor, S, 1.2), Some(LoadTransition::Overloaded));
+    assert!(monitor.passthrough());
+    assert_eq!(monitor.load().action, Some(OverloadAction::Passthrough));
+
+    // Zwischen `recover` und `limit`: bleibt in Überlast
+    assert_eq!(window(&mut monitor, 2 * S, 0.6), None);
+    assert_eq!(window(&mut monitor, 4 * S, 0.6), None);
+    assert!(monitor.passthrough());
+
+    assert_eq!(window(&mut monitor, 5 * S, 0.0), None);
+    assert_eq!(window(&mut monitor, 6 * S, 0.0), Some(LoadTransition::Recovered));
+    assert!(!monitor.passthrough());
+
+    let load = monitor.load();
+    assert_eq!(load.overloads, 1);
+    assert!(!load.overloaded);
+    assert!((load.peak_realtime_factor - 1.2).abs() < 1e-4);
+}
+
+#[test]
+fn short_spikes_do_not_trigger_overload() {
+    let (mut monitor, _) = monitor(Some(budget(OverloadAction::Alert)));
+
+    assert_eq!(window(&mut monitor, 0, 1.5), None);
+    assert_eq!(window(&mut monitor, S / 2, 0.2), None);
+    assert_eq!(window(&mut monitor, S, 1.5), None);
+    assert_eq!(window(&mut monitor, 3 * S / 2, 1.5), None);
+    assert_eq!(window(&mut monitor, 2 * S, 1.5), Some(LoadTransition::Overloaded));
+    // Alert ändert die Verarbeitung nicht
+    assert!(!monitor.passthrough());
+    assert!(!monitor.skips("meter"));
+}
+
+#[test]
+fn skip_optional_only_skips_listed_processors() {
+    let (mut monitor, _) = monitor(Some(budget(OverloadAction::SkipOptional)));
+    assert!(!monitor.skips("meter"));
+
+    window(&mut monitor, 0, 2.0);
+    window(&mut monitor, S, 2.0);
+    assert!(monitor.skips("meter"));
+    assert!(!monitor.skips("limiter"));
+    assert!(!monitor.passthrough());
+}
+
+#[test]
+fn budget_from_config() -> anyhow::Result<()> {
+    let processors = vec!["limiter".to_string(), "meter".to_string()];
+    let budget = ProcessingBudget::from_config(
+        "main",
+        &serde_json::json!({
+            "limit": 0.9,
+            "hold": "5s",
+            "action": "skip_optional",
+            "optional": ["meter"],
+        }),
+        &processors,
+    )?;
+    assert_eq!(budget.limit, 0.9);
+    assert_eq!(budget.recover, 0.5);
+    assert_eq!(budget.hold, Duration::from_secs(5));
+    assert_eq!(budget.action, OverloadAction::SkipOptional);
+    assert_eq!(budget.optional, vec!["meter".to_string()]);
+
+    let defaults = ProcessingBudget::from_config("main", &serde_json::json!(true), &processors)?;
+    assert_eq!(defaults, ProcessingBudget::default());
+
+    let low = ProcessingBudget::from_config("main", &serde_json::json!({ "limit": 0.3 }), &processors)?;
+    assert_eq!(low.recover, 0.3);
+
+    for invalid in [
+        serde_json::json!({ "limit": 1.5 }),
+        serde_json::json!({ "limit": 0.5, "recover": 0.6 }),
+        serde_json::json!({ "action": "panic" }),
+        serde_json::json!({ "action": "skip_optional" }),
+        serde_json::json!({ "optional": ["compressor"] }),
+        serde_json::json!("fast"),
+    ] {
+        assert!(
+            ProcessingBudget::from_config("main", &invalid, &processors).is_err(),
+            "{} accepted",
+            invalid
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn frame_duration_uses_rate_and_channels() {
+    let frame = PcmFrame {
+        utc_ns: 0,
+        samples: vec![0; 1920],
+        sample_rate: 48_000,
+        channels: 2,
+        metadata: Default::default(),
+    };
+    assert_eq!(frame_duration(&frame), Duration::from_millis(20));
+    assert_eq!(
+        frame_duration(&PcmFrame {
+            sample_rate: 0,
+            ..frame
+        }),
+        Duration::ZERO
+    );
+}
@@ -0,0 +1,124 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use airlift_node::core::processor::{Processor, ProcessorStatus};
+use airlift_node::core::{
+    AirliftNode, AudioRingBuffer, Event, EventEmitter, EventHandler, EventPriority, EventType,
+    Flow,
+};
+use airlift_node::testing::mocks::MockProducer;
+use airlift_node::PcmFrame;
+
+struct ClipDetector {
+    emitter: Option<EventEmitter>,
+}
+
+impl Processor for ClipDetector {
+    fn name(&self) -> &str {
+        "clip"
+    }
+
+    fn process(&mut self, input: &AudioRingBuffer, output: &AudioRingBuffer) -> anyhow::Result<()> {
+        while let Some(frame) = input.pop() {
+            if frame.samples.contains(&i16::MAX) {
+                self.emit_event(
+                    "clip_detected",
+                    EventPriority::Warning,
+                    serde_json::json!({ "utc_ns": frame.utc_ns }),
+                );
+            }
+            output.push(frame);
+        }
+        Ok(())
+    }
+
+    fn status(&self) -> ProcessorStatus {
+        ProcessorStatus {
+            running: true,
+            processing_rate_hz: 0.0,
+            latency_ms: 0.0,
+            errors: 0,
+            metrics: None,
+        }
+    }
+
+    fn update_config(&mut self, _config: serde_json::Value) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    fn attach_event_emitter(&mut self, emitter: EventEmitter) {
+        self.emitter = Some(emitter);
+    }
+
+    fn event_emitter(&self) -> Option<&EventEmitter> {
+        self.emitter.as_ref()
+    }
+}
+
+struct Collector {
+    events: Mutex<Vec<Event>>,
+}
+
+impl EventHandler for Collector {
+    fn handle_event(&self, event: &Event) -> anyhow::Result<()> {
+        self.events.lock().unwrap().push(event.clone());
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "collector"
+    }
+
+    fn event_type_filter(&self) -> Option<Vec<EventType>> {
+        Some(vec![EventType::custom("clip_detected")])
+    }
+}
+
+#[test]
+fn custom_event_filter_matches_by_name() {
+    let filter = EventType::custom("clip_detected");
+    assert!(filter.matches(&EventType::custom("clip_detected")));
+    assert!(!filter.matches(&EventType::custom("agc_gain_reduction_high")));
+    assert!(EventType::custom("*").matches(&EventType::custom("anything")));
+    assert!(!filter.matches(&EventType::AudioPeak));
+}
+
+#[test]
+fn processor_publishes_custom_event() -> anyhow::Result<()> {
+    let frames = vec![PcmFrame {
+        utc_ns: 42,
+        samples: vec![i16::MAX, 0, 0, 0],
+        sample_rate: 48_000,
+        channels: 2,
+        metadata: Default::default(),
+    }];
+
+    let mut flow = Flow::new("flow");
+    flow.add_processor(Box::new(ClipDetector { emitter: None }));
+
+    let mut node = AirliftNode::new();
+    let collector = Arc::new(Collector {
+        events: Mutex::new(Vec::new()),
+    });
+    node.event_bus()
+        .lock()
+        .unwrap()
+        .register_handler(collector.clone())?;
+
+    node.add_flow(flow);
+    node.add_producer(Box::new(MockProducer::new("test", frames)))?;
+    node.connect_flow_input(0, "producer:test")?;
+    node.start()?;
+
+    let deadline = Instant::now() + Duration::from_secs(1);
+    while Instant::now() < deadline && collector.events.lock().unwrap().is_empty() {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    node.stop()?;
+
+    let events = collector.events.lock().unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].source_instance, "clip");
+    assert_eq!(events[0].payload["utc_ns"], 42);
+    Ok(())
+}
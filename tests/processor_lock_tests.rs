@@ -0,0 +1,122 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use airlift_node::core::processor::basic::Gain;
+use airlift_node::core::processor::{Processor, ProcessorStatus};
+use airlift_node::core::{AirliftNode, AudioRingBuffer, Flow};
+use airlift_node::testing::mocks::{MockConsumer, MockProducer};
+use airlift_node::PcmFrame;
+
+const SLOW_PASS: Duration = Duration::from_millis(300);
+
+/// Langsamer Processor am Anfang der Kette: jeder Aufruf dauert `SLOW_PASS`.
+struct Slow {
+    entered: Arc<AtomicBool>,
+}
+
+impl Processor for Slow {
+    fn name(&self) -> &str {
+        "slow"
+    }
+
+    fn process(&mut self, input: &AudioRingBuffer, output: &AudioRingBuffer) -> anyhow::Result<()> {
+        self.entered.store(true, Ordering::SeqCst);
+        std::thread::sleep(SLOW_PASS);
+        while let Some(frame) = input.pop() {
+            output.push(frame);
+        }
+        Ok(())
+    }
+
+    fn status(&self) -> ProcessorStatus {
+        ProcessorStatus {
+            running: true,
+            processing_rate_hz: 0.0,
+            latency_ms: 0.0,
+            errors: 0,
+            metrics: None,
+        }
+    }
+
+    fn update_config(&mut self, _config: serde_json::Value) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+fn frame() -> PcmFrame {
+    PcmFrame {
+        utc_ns: 1,
+        samples: vec![100, 200, 300, 400],
+        sample_rate: 48_000,
+        channels: 2,
+        metadata: Default::default(),
+    }
+}
+
+fn wait_until(timeout: Duration, mut done: impl FnMut() -> bool) -> bool {
+    let deadline = Instant::now() + timeout;
+    while Instant::now() < deadline {
+        if done() {
+            return true;
+        }
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    done()
+}
+
+/// Startet `slow -> gain` und wartet, bis der erste Durchlauf in `slow` hängt.
+fn start_slow_chain() -> anyhow::Result<(AirliftNode, Arc<Mutex<Vec<PcmFrame>>>)> {
+    let entered = Arc::new(AtomicBool::new(false));
+    let (consumer, received) = MockConsumer::new_with_shared("out");
+    let mut flow = Flow::new("flow");
+    flow.add_processor(Box::new(Slow {
+        entered: entered.clone(),
+    }));
+    flow.add_processor(Box::new(Gain::new("gain", 1.0)));
+    flow.add_consumer(Box::new(consumer));
+
+    let mut node = AirliftNode::new();
+    node.add_flow(flow);
+    node.add_producer(Box::new(MockProducer::new("src", vec![frame()])))?;
+    node.connect_flow_input(0, "producer:src")?;
+    node.start()?;
+
+    assert!(wait_until(Duration::from_secs(2), || entered.load(Ordering::SeqCst)));
+    Ok((node, received))
+}
+
+#[test]
+fn config_update_does_not_wait_for_the_whole_pass() -> anyhow::Result<()> {
+    let (mut node, _) = start_slow_chain()?;
+
+    let started = Instant::now();
+    node.flows()[0].update_processor_config("gain", serde_json::json!({ "gain": 2.0 }))?;
+    let waited = started.elapsed();
+    node.stop()?;
+
+    assert!(
+        waited < SLOW_PASS / 2,
+        "update waited {:?} behind the slow processor",
+        waited
+    );
+    Ok(())
+}
+
+#[test]
+fn config_update_reaches_the_running_processor() -> anyhow::Result<()> {
+    let (mut node, received) = start_slow_chain()?;
+
+    node.flows()[0].update_processor_config("gain", serde_json::json!({ "gain": 2.0 }))?;
+    let arrived = wait_until(Duration::from_secs(3), || {
+        !received.lock().unwrap().is_empty()
+    });
+    node.stop()?;
+
+    assert!(arrived);
+    assert_eq!(
+        received.lock().unwrap()[0].samples,
+        vec![200, 400, 600, 800]
+    );
+    Ok(())
+}
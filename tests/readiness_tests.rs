@@ -0,0 +1,275 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use airlift_node::core::{
+    AirliftNode, AudioRingBuffer, Consumer, ConsumerStatus, Flow, Producer, ProducerStatus,
+};
+use airlift_node::PcmFrame;
+
+type Stamp = Arc<Mutex<Option<Instant>>>;
+
+/// Liefert das erste Frame erst nach `delay` (z. B. Encoder/Netzwerk-Input).
+struct DelayedProducer {
+    name: &'static str,
+    delay: Option<Duration>,
+    first_push: Stamp,
+    buffer: Option<Arc<AudioRingBuffer>>,
+    running: Arc<AtomicBool>,
+}
+
+impl Producer for DelayedProducer {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn start(&mut self) -> anyhow::Result<()> {
+        self.running.store(true, Ordering::SeqCst);
+        let Some(delay) = self.delay else {
+            return Ok(());
+        };
+        let buffer = self.buffer.clone().expect("buffer attached");
+        let first_push = self.first_push.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(delay);
+            *first_push.lock().unwrap() = Some(Instant::now());
+            buffer.push(PcmFrame {
+                utc_ns: 1,
+                samples: vec![0; 4],
+                sample_rate: 48_000,
+                channels: 2,
+                metadata: Default::default(),
+            });
+        });
+        Ok(())
+    }
+
+    fn stop(&mut self) -> anyhow::Result<()> {
+        self.running.store(false, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn status(&self) -> ProducerStatus {
+        ProducerStatus {
+            running: self.running.load(Ordering::Relaxed),
+            paused: false,
+            connected: true,
+            samples_processed: 0,
+            errors: 0,
+            buffer_stats: None,
+            decoder: None,
+        }
+    }
+
+    fn attach_ring_buffer(&mut self, buffer: Arc<AudioRingBuffer>) {
+        self.buffer = Some(buffer);
+    }
+}
+
+/// Merkt sich nur den Startzeitpunkt.
+struct StartRecorder {
+    started: Stamp,
+}
+
+impl Consumer for StartRecorder {
+    fn name(&self) -> &str {
+        "recorder"
+    }
+
+    fn start(&mut self) -> anyhow::Result<()> {
+        *self.started.lock().unwrap() = Some(Instant::now());
+        Ok(())
+    }
+
+    fn stop(&mut self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    fn status(&self) -> ConsumerStatus {
+        ConsumerStatus {
+            running: self.started.lock().unwrap().is_some(),
+            connected: true,
+            frames_processed: 0,
+            bytes_written: 0,
+            errors: 0,
+            connection: None,
+        }
+    }
+
+    fn attach_input_buffer(&mut self, _buffer: Arc<AudioRingBuffer>) {}
+}
+
+fn node_with(delay: Option<Duration>) -> (AirliftNode, Stamp, Stamp) {
+    let first_push = Arc::new(Mutex::new(None));
+    let started = Arc::new(Mutex::new(None));
+
+    let mut flow = Flow::new("flow");
+    flow.add_consumer(Box::new(StartRecorder {
+        started: started.clone(),
+    }));
+    let mut node = AirliftNode::new();
+    node.add_flow(flow);
+    node.add_producer(Box::new(DelayedProducer {
+        name: "delayed",
+        delay,
+        first_push: first_push.clone(),
+        buffer: None,
+        running: Arc::new(AtomicBool::new(false)),
+    }))
+    .unwrap();
+    node.connect_flow_input(0, "producer:delayed").unwrap();
+    (node, first_push, started)
+}
+
+#[test]
+fn consumers_start_after_inputs_produce() -> anyhow::Result<()> {
+    let (mut node, first_push, started) = node_with(Some(Duration::from_millis(100)));
+    node.start()?;
+    node.stop()?;
+
+    let first_push = first_push.lock().unwrap().expect("producer pushed");
+    let started = started.lock().unwrap().expect("consumer started");
+    assert!(started >= first_push, "consumer started before its input produced");
+    Ok(())
+}
+
+#[test]
+fn readiness_timeout_starts_consumers_anyway() -> anyhow::Result<()> {
+    let (mut node, _, started) = node_with(None);
+    node.set_readiness_timeout(Duration::from_millis(50));
+
+    let begin = Instant::now();
+    node.start()?;
+    let waited = begin.elapsed();
+    node.stop()?;
+
+    assert!(started.lock().unwrap().is_some());
+    assert!(waited >= Duration::from_millis(50));
+    assert!(waited < Duration::from_secs(2));
+    Ok(())
+}
+
+fn delayed(name: &'static str, delay: Duration) -> (Box<DelayedProducer>, Stamp) {
+    let first_push = Arc::new(Mutex::new(None));
+    let producer = Box::new(DelayedProducer {
+        name,
+        delay: Some(delay),
+        first_push: first_push.clone(),
+        buffer: None,
+        running: Arc::new(AtomicBool::new(false)),
+    });
+    (producer, first_push)
+}
+
+fn recorded_flow(name: &str) -> (Flow, Stamp) {
+    let started = Arc::new(Mutex::new(None));
+    let mut flow = Flow::new(name);
+    flow.add_consumer(Box::new(StartRecorder {
+        started: started.clone(),
+    }));
+    (flow, started)
+}
+
+#[test]
+fn each_flow_waits_only_for_its_own_inputs() -> anyhow::Result<()> {
+    let (fast_flow, fast_started) = recorded_flow("fast");
+    let (slow_flow, slow_started) = recorded_flow("slow");
+    let (fast, fast_push) = delayed("fast", Duration::from_millis(10));
+    let (slow, slow_push) = delayed("slow", Duration::from_millis(400));
+
+    let mut node = AirliftNode::new();
+    node.add_flow(fast_flow);
+    node.add_flow(slow_flow);
+    node.add_producer(fast)?;
+    node.add_producer(slow)?;
+    node.connect_flow_input(0, "producer:fast")?;
+    node.connect_flow_input(1, "producer:slow")?;
+    node.start()?;
+    node.stop()?;
+
+    let fast_started = fast_started.lock().unwrap().expect("fast consumer started");
+    let slow_push = slow_push.lock().unwrap().expect("slow producer pushed");
+    assert!(fast_started >= fast_push.lock().unwrap().expect("fast producer pushed"));
+    assert!(fast_started < slow_push, "fast flow waited for the slow input");
+    assert!(slow_started.lock().unwrap().expect("slow consumer started") >= slow_push);
+    Ok(())
+}
+
+#[test]
+fn downstream_flow_is_gated_on_the_upstream_output() -> anyhow::Result<()> {
+    // Downstream absichtlich zuerst hinzugefügt: die Startreihenfolge folgt den Abhängigkeiten
+    let (downstream, downstream_started) = recorded_flow("downstream");
+    let (upstream, _) = recorded_flow("upstream");
+    let (producer, first_push) = delayed("source", Duration::from_millis(100));
+
+    let mut node = AirliftNode::new();
+    node.set_readiness_timeout(Duration::from_secs(5));
+    node.add_flow(downstream);
+    node.add_flow(upstream);
+    node.add_producer(producer)?;
+    node.connect_flow_input(1, "producer:source")?;
+    let upstream_output = node.flows()[1].output_buffer.clone();
+    node.buffer_registry().register("flow:upstream:output", upstream_output)?;
+    node.connect_flow_input(0, "flow:upstream:output")?;
+
+    let begin = Instant::now();
+    node.start()?;
+    let waited = begin.elapsed();
+    node.stop()?;
+
+    let first_push = first_push.lock().unwrap().expect("producer pushed");
+    let started = downstream_started.lock().unwrap().expect("downstream consumer started");
+    assert!(started >= first_push, "downstream consumer started before audio reached it");
+    assert!(waited < Duration::from_secs(5), "downstream flow ran into the timeout");
+    Ok(())
+}
+
+#[test]
+fn runtime_flow_start_uses_the_readiness_gate() -> anyhow::Result<()> {
+    let (flow, started) = recorded_flow("late");
+    let (producer, first_push) = delayed("source", Duration::from_millis(100));
+
+    let mut node = AirliftNode::new();
+    node.add_flow(flow);
+    node.add_producer(producer)?;
+    node.connect_flow_input(0, "producer:source")?;
+    node.start_producer_by_name("source")?;
+    node.start_flow_by_name("late")?;
+    node.stop_flow_by_name("late")?;
+    node.stop()?;
+
+    let first_push = first_push.lock().unwrap().expect("producer pushed");
+    let started = started.lock().unwrap().expect("consumer started");
+    assert!(started >= first_push, "consumer started before its input produced");
+    Ok(())
+}
+
+#[test]
+fn runtime_flow_start_does_not_hold_the_shared_node() -> anyhow::Result<()> {
+    let (flow, started) = recorded_flow("late");
+    let (producer, first_push) = delayed("source", Duration::from_millis(300));
+
+    let mut node = AirliftNode::new();
+    node.set_readiness_timeout(Duration::from_secs(5));
+    node.add_flow(flow);
+    node.add_producer(producer)?;
+    node.connect_flow_input(0, "producer:source")?;
+    let node = node.into_shared();
+
+    node.lock().unwrap().start_producer_by_name("source")?;
+    let begin = Instant::now();
+    node.lock().unwrap().start_flow_by_name("late")?;
+    assert!(begin.elapsed() < Duration::from_millis(200), "start_flow_by_name waited for readiness");
+    assert!(started.lock().unwrap().is_none(), "consumer started before its input produced");
+
+    let deadline = Instant::now() + Duration::from_secs(2);
+    while started.lock().unwrap().is_none() && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    node.lock().unwrap().stop()?;
+
+    let first_push = first_push.lock().unwrap().expect("producer pushed");
+    let started = started.lock().unwrap().expect("consumer started in the background");
+    assert!(started >= first_push, "consumer started before its input produced");
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+
+use airlift_node::audio::resample::{ResampleQuality, Resampler};
+use airlift_node::core::processor::Processor;
+use airlift_node::processors::ResamplerProcessor;
+use airlift_node::PcmFrame;
+
+fn sine(rate: u32, frequency: f64, start: usize, len: usize) -> Vec<i16> {
+    (start..start + len)
+        .map(|n| {
+            let t = n as f64 / rate as f64;
+            (16000.0 * (2.0 * std::f64::consts::PI * frequency * t).sin()).round() as i16
+        })
+        .collect()
+}
+
+fn resampler(config: serde_json::Value) -> anyhow::Result<ResamplerProcessor> {
+    let config: HashMap<String, serde_json::Value> = serde_json::from_value(config)?;
+    ResamplerProcessor::from_config("to48k", &config)
+}
+
+#[test]
+fn converts_sine_in_irregular_chunks_without_drift() {
+    let input = sine(44_100, 1000.0, 0, 44_100);
+    let mut resampler = Resampler::new(44_100, 48_000, 1, ResampleQuality::High);
+
+    let mut output = Vec::new();
+    let mut position = 0;
+    for size in [1, 7, 441, 1024, 3, 4410].iter().cycle() {
+        if position >= input.len() {
+            break;
+        }
+        let end = (position + size).min(input.len());
+        output.extend(resampler.process(&input[position..end]));
+        position = end;
+    }
+
+    // Ausgang n liegt auf Input-Position n * 147 / 160, minus Filterverzögerung
+    let expected_len = input.len() * 160 / 147 - resampler.latency_frames() * 160 / 147;
+    assert!(
+        output.len().abs_diff(expected_len) <= 2,
+        "{} vs {}",
+        output.len(),
+        expected_len
+    );
+    let ideal = sine(48_000, 1000.0, 0, output.len());
+    let warmup = 200;
+    let max_error = output[warmup..]
+        .iter()
+        .zip(&ideal[warmup..])
+        .map(|(a, b)| (*a as i32 - *b as i32).abs())
+        .max()
+        .unwrap();
+    assert!(max_error <= 4, "max error {}", max_error);
+}
+
+#[test]
+fn processor_sets_rate_and_continuous_timestamps() -> anyhow::Result<()> {
+    let mut processor = resampler(serde_json::json!({ "sample_rate": 48000 }))?;
+    let mut frames: Vec<PcmFrame> = (0..10)
+        .map(|i| PcmFrame {
+            utc_ns: 1_000_000_000 + i as u64 * 10_000_000,
+            samples: sine(44_100, 440.0, i * 441, 441)
+                .into_iter()
+                .flat_map(|s| [s, s])
+                .collect(),
+            sample_rate: 44_100,
+            channels: 2,
+            metadata: Default::default(),
+        })
+        .collect();
+    processor.process_batch(&mut frames)?;
+
+    assert!(!frames.is_empty());
+    let mut expected_ns = None;
+    for frame in &frames {
+        assert_eq!(frame.sample_rate, 48_000);
+        assert_eq!(frame.channels, 2);
+        assert_eq!(frame.samples.len() % 2, 0);
+        if let Some(expected) = expected_ns {
+            let diff = (frame.utc_ns as i64 - expected as i64).abs();
+            assert!(diff < 25_000, "timestamp gap {} ns", diff);
+        }
+        expected_ns =
+            Some(frame.utc_ns + (frame.samples.len() / 2) as u64 * 1_000_000_000 / 48_000);
+    }
+    assert_eq!(frames[0].utc_ns, 1_000_000_000);
+    assert!(processor.status().latency_ms > 0.0);
+    Ok(())
+}
+
+#[test]
+fn frames_at_target_rate_pass_unchanged() -> anyhow::Result<()> {
+    let mut processor = resampler(serde_json::json!({ "quality": "low" }))?;
+    let frame = PcmFrame {
+        utc_ns: 42,
+        samples: vec![1, 2, 3, 4],
+        sample_rate: 48_000,
+        channels: 2,
+        metadata: Default::default(),
+    };
+    let mut frames = vec![frame];
+    processor.process_batch(&mut frames)?;
+    assert_eq!(frames[0].samples, [1, 2, 3, 4]);
+    assert_eq!(frames[0].utc_ns, 42);
+
+    assert!(resampler(serde_json::json!({ "quality": "ultra" })).is_err());
+    assert!(resampler(serde_json::json!({ "sample_rate": 1000 })).is_err());
+    Ok(())
+}
@@ -11,6 +11,7 @@ fn test_multi_reader_lockfree() {
             samples: vec![i as i16; 32],
             sample_rate: 48000,
             channels: 2,
+            metadata: Default::default(),
         });
     }
 
@@ -34,6 +35,7 @@ fn test_wrap_around_lockfree() {
             samples: vec![i as i16; 16],
             sample_rate: 48000,
             channels: 2,
+            metadata: Default::default(),
         });
     }
 
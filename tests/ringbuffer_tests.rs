@@ -11,6 +11,7 @@ fn test_basic_buffer_operations_debug() {
         samples: vec![1, 2, 3, 4, 5, 6],
         sample_rate: 48000,
         channels: 2,
+        metadata: Default::default(),
     };
 
     let new_len = buffer.push(frame);
@@ -39,6 +40,7 @@ fn test_basic_buffer_operations() {
         samples: vec![1, 2, 3, 4, 5, 6],
         sample_rate: 48000,
         channels: 2,
+        metadata: Default::default(),
     };
 
     let new_len = buffer.push(frame);
@@ -75,6 +77,7 @@ fn test_multi_reader() {
             samples: vec![i as i16; 96],
             sample_rate: 48000,
             channels: 2,
+            metadata: Default::default(),
         };
         buffer.push(frame);
     }
@@ -111,6 +114,7 @@ fn test_buffer_wrap_around() {
             samples: vec![i as i16; 48],
             sample_rate: 48000,
             channels: 2,
+            metadata: Default::default(),
         };
         buffer.push(frame);
     }
@@ -142,6 +146,7 @@ fn test_buffer_logging_integration() {
             samples: vec![i as i16; 48],
             sample_rate: 48000,
             channels: 2,
+            metadata: Default::default(),
         };
         buffer.push(frame);
     }
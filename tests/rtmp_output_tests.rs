@@ -0,0 +1,353 @@
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::time::Duration;
+
+use airlift_node::codecs::{CodecInfo, CodecKind, ContainerKind};
+use airlift_node::config::ConsumerConfig;
+use airlift_node::consumers::rtmp::{
+    aac_audio_specific_config, encode_command, flv_audio_header, parse_rtmp_url, split_adts,
+    write_message, Amf0, ChunkReader, RtmpMessage, RtmpOutputConfig, RtmpSession, DEFAULT_PORT,
+    MSG_AUDIO, MSG_COMMAND_AMF0, MSG_DATA_AMF0,
+};
+use serde_json::json;
+
+fn consumer_config(config: serde_json::Value) -> ConsumerConfig {
+    ConsumerConfig {
+        consumer_type: "rtmp_out".to_string(),
+        enabled: true,
+        path: None,
+        url: None,
+        config: serde_json::from_value(config).unwrap(),
+    }
+}
+
+fn aac_info() -> CodecInfo {
+    CodecInfo {
+        kind: CodecKind::AacLc,
+        sample_rate: 48_000,
+        channels: 2,
+        container: ContainerKind::Raw,
+    }
+}
+
+#[test]
+fn rtmp_urls_split_application_and_key() -> anyhow::Result<()> {
+    let (url, key) = parse_rtmp_url("rtmp://a.rtmp.youtube.com/live2", true)?;
+    assert_eq!(
+        (url.host.as_str(), url.port, url.app.as_str()),
+        ("a.rtmp.youtube.com", DEFAULT_PORT, "live2")
+    );
+    assert_eq!(key, None);
+    assert_eq!(url.tc_url(), "rtmp://a.rtmp.youtube.com:1935/live2");
+
+    let (url, key) = parse_rtmp_url("rtmp://live.twitch.tv:1936/app/live_123_abc", false)?;
+    assert_eq!((url.port, url.app.as_str()), (1936, "app"));
+    assert_eq!(key.as_deref(), Some("live_123_abc"));
+
+    let (url, _) = parse_rtmp_url("rtmps://ingest.example.org/live", true)?;
+    assert!(url.tls);
+    assert_eq!(url.port, 443);
+
+    for bad in [
+        "http://example.org/live",
+        "rtmp:///live",
+        "rtmp://host",
+        "rtmp://host:0/live",
+    ] {
+        assert!(parse_rtmp_url(bad, true).is_err(), "{}", bad);
+    }
+    Ok(())
+}
+
+#[test]
+fn rtmp_configs_are_validated() {
+    for config in [
+        json!({ "stream_key": "abc" }),
+        json!({ "url": "rtmp://host/live" }),
+        json!({ "url": "rtmp://host/live", "stream_key": "a b" }),
+        json!({ "url": "rtmp://host/live", "stream_key": "abc", "tls_server_name": "host" }),
+        json!({ "url": "rtmp://host/live", "stream_key": "abc", "codec": "pcm" }),
+        json!({ "url": "rtmp://host/live", "stream_key": "abc", "codec": "opuswebrtc" }),
+    ] {
+        assert!(
+            RtmpOutputConfig::from_config("live", &consumer_config(config.clone())).is_err(),
+            "{}",
+            config
+        );
+    }
+
+    // rtmps:// braucht das Feature `tls`
+    let rtmps = RtmpOutputConfig::from_config(
+        "live",
+        &consumer_config(json!({ "url": "rtmps://host/live", "stream_key": "abc" })),
+    );
+    let tls_error = rtmps.err().map(|e| format!("{:#}", e)).unwrap_or_default();
+    assert_eq!(tls_error.contains("'tls' feature"), !cfg!(feature = "tls"), "{}", tls_error);
+}
+
+#[test]
+fn flv_audio_headers_and_aac_config() -> anyhow::Result<()> {
+    assert_eq!(flv_audio_header(&aac_info()), Some(0xAF));
+    let mp3 = CodecInfo {
+        kind: CodecKind::Mp3,
+        container: ContainerKind::Mpeg,
+        ..aac_info()
+    };
+    assert_eq!(flv_audio_header(&mp3), Some(0x2F));
+    let pcm = CodecInfo {
+        kind: CodecKind::Pcm,
+        ..aac_info()
+    };
+    assert_eq!(flv_audio_header(&pcm), None);
+
+    assert_eq!(aac_audio_specific_config(48_000, 2)?, [0x11, 0x90]);
+    assert_eq!(aac_audio_specific_config(44_100, 1)?, [0x12, 0x08]);
+    assert!(aac_audio_specific_config(47_000, 2).is_err());
+
+    // Zwei ADTS-Frames (ohne CRC) mit 3 bzw. 1 Byte Nutzdaten
+    let adts = |payload: &[u8]| {
+        let len = 7 + payload.len();
+        let mut frame = vec![
+            0xFF,
+            0xF1,
+            0x4C,
+            0x80,
+            (len >> 3) as u8,
+            ((len & 7) << 5) as u8 | 0x1F,
+            0xFC,
+        ];
+        frame.extend_from_slice(payload);
+        frame
+    };
+    let mut data = adts(&[1, 2, 3]);
+    data.extend(adts(&[4]));
+    assert_eq!(split_adts(&data), vec![&[1u8, 2, 3][..], &[4u8][..]]);
+    assert_eq!(split_adts(&[0x21, 0x10]), vec![&[0x21u8, 0x10][..]]);
+    Ok(())
+}
+
+#[test]
+fn amf_and_chunks_round_trip() -> anyhow::Result<()> {
+    let command = encode_command(
+        "onStatus",
+        0.0,
+        &[
+            Amf0::Null,
+            Amf0::Object(vec![
+                ("level".into(), Amf0::String("status".into())),
+                (
+                    "code".into(),
+                    Amf0::String("NetStream.Publish.Start".into()),
+                ),
+            ]),
+        ],
+    );
+    let values = Amf0::decode_all(&command)?;
+    assert_eq!(values[0].as_str(), Some("onStatus"));
+    assert_eq!(
+        values[3].get("code").and_then(Amf0::as_str),
+        Some("NetStream.Publish.Start")
+    );
+
+    let long = RtmpMessage {
+        csid: 4,
+        timestamp: 0x0100_0000,
+        type_id: MSG_AUDIO,
+        stream_id: 1,
+        payload: (0..300u32).map(|i| i as u8).collect(),
+    };
+    let short = RtmpMessage {
+        csid: 3,
+        timestamp: 40,
+        type_id: MSG_COMMAND_AMF0,
+        stream_id: 0,
+        payload: command,
+    };
+    let mut wire = Vec::new();
+    write_message(&mut wire, 128, &long);
+    write_message(&mut wire, 128, &short);
+
+    // Byteweise zuführen: unvollständige Chunks dürfen nichts verbrauchen
+    let mut reader = ChunkReader::new();
+    let mut messages = Vec::new();
+    for byte in wire {
+        reader.feed(&[byte]);
+        while let Some(message) = reader.next_message()? {
+            messages.push(message);
+        }
+    }
+    assert_eq!(messages, vec![long, short]);
+    Ok(())
+}
+
+/// Minimaler Ingest: Handshake, `_result` auf connect/createStream und
+/// `onStatus` auf publish; liefert alle danach empfangenen Nachrichten.
+fn serve_ingest(listener: TcpListener, publish_code: &'static str) -> Vec<RtmpMessage> {
+    let (mut stream, _) = listener.accept().unwrap();
+    stream
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .unwrap();
+    let mut c0c1 = vec![0u8; 1537];
+    stream.read_exact(&mut c0c1).unwrap();
+    assert_eq!(c0c1[0], 3);
+    let mut s0s1s2 = vec![3u8];
+    s0s1s2.extend_from_slice(&[7u8; 1536]);
+    s0s1s2.extend_from_slice(&c0c1[1..]);
+    stream.write_all(&s0s1s2).unwrap();
+    let mut c2 = vec![0u8; 1536];
+    stream.read_exact(&mut c2).unwrap();
+    assert!(c2.iter().all(|b| *b == 7), "C2 echoes S1");
+
+    let reply = |stream: &mut TcpStream, stream_id: u32, payload: Vec<u8>| {
+        let mut out = Vec::new();
+        let message = RtmpMessage {
+            csid: 3,
+            timestamp: 0,
+            type_id: MSG_COMMAND_AMF0,
+            stream_id,
+            payload,
+        };
+        write_message(&mut out, 128, &message);
+        stream.write_all(&out).unwrap();
+    };
+
+    let mut reader = ChunkReader::new();
+    let mut received = Vec::new();
+    let mut buf = [0u8; 4096];
+    loop {
+        let read = match stream.read(&mut buf) {
+            Ok(0) | Err(_) => return received,
+            Ok(read) => read,
+        };
+        reader.feed(&buf[..read]);
+        while let Some(message) = reader.next_message().unwrap() {
+            if message.type_id == MSG_COMMAND_AMF0 {
+                let values = Amf0::decode_all(&message.payload).unwrap();
+                let transaction = values[1].as_f64().unwrap();
+                match values[0].as_str().unwrap() {
+                    "connect" => {
+                        assert_eq!(values[2].get("app").and_then(Amf0::as_str), Some("live2"));
+                        reply(
+                            &mut stream,
+                            0,
+                            encode_command("_result", transaction, &[Amf0::Null, Amf0::Null]),
+                        );
+                    }
+                    "createStream" => {
+                        reply(
+                            &mut stream,
+                            0,
+                            encode_command(
+                                "_result",
+                                transaction,
+                                &[Amf0::Null, Amf0::Number(1.0)],
+                            ),
+                        );
+                    }
+                    "publish" => {
+                        assert_eq!(values[3].as_str(), Some("secret-key"));
+                        let level = if publish_code.ends_with("Start") {
+                            "status"
+                        } else {
+                            "error"
+                        };
+                        let status = Amf0::Object(vec![
+                            ("level".into(), Amf0::String(level.into())),
+                            ("code".into(), Amf0::String(publish_code.into())),
+                        ]);
+                        reply(
+                            &mut stream,
+                            1,
+                            encode_command("onStatus", 0.0, &[Amf0::Null, status]),
+                        );
+                    }
+                    _ => {}
+                }
+            }
+            received.push(message);
+        }
+    }
+}
+
+fn session_config(port: u16) -> RtmpOutputConfig {
+    let (url, _) = parse_rtmp_url(&format!("rtmp://127.0.0.1:{}/live2", port), true).unwrap();
+    RtmpOutputConfig {
+        url,
+        stream_key: "secret-key".to_string(),
+        codec: "aaclc".to_string(),
+        reconnect: Duration::from_secs(1),
+        max_reconnect: Duration::from_secs(30),
+        tls: None,
+    }
+}
+
+#[test]
+fn session_publishes_metadata_sequence_header_and_audio() -> anyhow::Result<()> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let port = listener.local_addr()?.port();
+    let server = std::thread::spawn(move || serve_ingest(listener, "NetStream.Publish.Start"));
+
+    let mut session = RtmpSession::connect(&session_config(port), &aac_info())?;
+    session.send_audio(21, &[1, 2, 3])?;
+    session.poll()?;
+    drop(session);
+
+    let received = server.join().unwrap();
+    let commands: Vec<String> = received
+        .iter()
+        .filter(|m| m.type_id == MSG_COMMAND_AMF0)
+        .map(|m| {
+            Amf0::decode_all(&m.payload).unwrap()[0]
+                .as_str()
+                .unwrap()
+                .to_string()
+        })
+        .collect();
+    assert_eq!(
+        commands,
+        [
+            "connect",
+            "releaseStream",
+            "FCPublish",
+            "createStream",
+            "publish"
+        ]
+    );
+
+    let metadata = received
+        .iter()
+        .find(|m| m.type_id == MSG_DATA_AMF0)
+        .expect("metadata");
+    let values = Amf0::decode_all(&metadata.payload)?;
+    assert_eq!(values[1].as_str(), Some("onMetaData"));
+    assert_eq!(
+        values[2].get("audiocodecid").and_then(Amf0::as_f64),
+        Some(10.0)
+    );
+
+    let audio: Vec<&RtmpMessage> = received.iter().filter(|m| m.type_id == MSG_AUDIO).collect();
+    assert_eq!(audio.len(), 2);
+    assert_eq!(audio[0].payload, [0xAF, 0, 0x11, 0x90]);
+    assert_eq!(audio[1].payload, [0xAF, 1, 1, 2, 3]);
+    assert_eq!((audio[1].timestamp, audio[1].stream_id), (21, 1));
+    Ok(())
+}
+
+#[test]
+fn rejected_stream_key_fails_the_session() -> anyhow::Result<()> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let port = listener.local_addr()?.port();
+    let server = std::thread::spawn(move || serve_ingest(listener, "NetStream.Publish.BadName"));
+
+    let error = match RtmpSession::connect(&session_config(port), &aac_info()) {
+        Ok(_) => panic!("publish must fail"),
+        Err(error) => error,
+    };
+    assert!(
+        format!("{:#}", error).contains("NetStream.Publish.BadName"),
+        "{:#}",
+        error
+    );
+    server.join().unwrap();
+    Ok(())
+}
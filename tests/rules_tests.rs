@@ -0,0 +1,112 @@
+#![cfg(feature = "lua")]
+
+use std::sync::{Arc, Mutex};
+
+use airlift_node::config::{Config, RulesConfig};
+use airlift_node::core::Flow;
+use airlift_node::processors::{MixerConfig, MixerInputConfig};
+use airlift_node::rules::RulesEngine;
+use airlift_node::AirliftNode;
+
+fn start_with_script(name: &str, script: &str) -> anyhow::Result<RulesEngine> {
+    start_on_node(name, script, Arc::new(Mutex::new(AirliftNode::new())))
+}
+
+fn start_on_node(
+    name: &str,
+    script: &str,
+    node: Arc<Mutex<AirliftNode>>,
+) -> anyhow::Result<RulesEngine> {
+    let path = std::env::temp_dir().join(format!("airlift-rules-{}-{}.lua", name, std::process::id()));
+    std::fs::write(&path, script)?;
+
+    let rules = RulesConfig {
+        scripts: vec![path.display().to_string()],
+        max_runtime_ms: Some(50),
+        webhook_allow: Vec::new(),
+    };
+    let config = Arc::new(Mutex::new(Config::default()));
+    let result = RulesEngine::start(&rules, node, config);
+    let _ = std::fs::remove_file(&path);
+    result
+}
+
+#[test]
+fn loads_script_with_handlers_and_timers() {
+    let script = r#"
+        local status = airlift.status()
+        airlift.log("running: " .. tostring(status.running))
+        airlift.on("BufferWatermark", function(ev) airlift.log(ev.instance) end)
+        airlift.every(60, function() airlift.log("tick") end)
+    "#;
+    let mut engine = start_with_script("ok", script).expect("script loads");
+    engine.stop();
+}
+
+#[test]
+fn sandbox_blocks_io_and_os() {
+    assert!(start_with_script("io", "io.open('/etc/passwd')").is_err());
+    assert!(start_with_script("os", "os.execute('true')").is_err());
+    assert!(start_with_script("dofile", "dofile('/etc/passwd')").is_err());
+    assert!(start_with_script("require", "require('socket')").is_err());
+}
+
+#[test]
+fn runaway_scripts_are_aborted() {
+    let err = start_with_script("loop", "while true do end")
+        .err()
+        .expect("endless loop must fail");
+    assert!(format!("{:#}", err).contains("time limit"));
+}
+
+#[test]
+fn webhooks_require_allowlist() {
+    let script = r#"airlift.webhook("http://127.0.0.1:9/hook", { text = "hi" })"#;
+    assert!(start_with_script("webhook", script).is_err());
+}
+
+#[test]
+fn fade_ramps_mixer_gains() -> anyhow::Result<()> {
+    let mut node = AirliftNode::new();
+    node.add_flow(Flow::new("main"));
+    node.create_and_add_mixer(
+        0,
+        "desk",
+        MixerConfig {
+            inputs: vec![MixerInputConfig {
+                name: "music".to_string(),
+                source: "producer:music".to_string(),
+                gain: 1.0,
+                enabled: Some(true),
+                mute: None,
+                solo: None,
+            }],
+            output_sample_rate: None,
+            output_channels: None,
+            master_gain: None,
+            auto_connect: Some(true),
+        },
+    )?;
+    let node = Arc::new(Mutex::new(node));
+
+    let script = r#"
+        airlift.fade("main", "desk", "music", -20, 2)
+        airlift.fade("main", "desk", nil, -6, 0.5)
+    "#;
+    let mut engine = start_on_node("fade", script, node.clone())?;
+    engine.stop();
+    let status = node.lock().unwrap().flows()[0].status();
+    let metrics = status.processor_status[0].metrics.clone().unwrap();
+    assert_eq!(metrics["master_gain"], 1.0);
+    assert!((metrics["master_ramp"]["to"].as_f64().unwrap() - 0.501).abs() < 0.001);
+    assert_eq!(metrics["master_ramp"]["remaining_ms"], 500);
+
+    for script in [
+        r#"airlift.fade("main", "desk", "drums", -20, 2)"#,
+        r#"airlift.fade("main", "desk", "music", -20, -1)"#,
+        r#"airlift.fade("other", "desk", nil, -20, 2)"#,
+    ] {
+        assert!(start_on_node("fade-error", script, node.clone()).is_err());
+    }
+    Ok(())
+}
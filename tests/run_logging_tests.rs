@@ -36,6 +36,7 @@ fn test_buffer_logging() {
             samples: vec![i as i16; 96], // Kleine Frames
             sample_rate: 48000,
             channels: 2,
+            metadata: Default::default(),
         };
         buffer.push(frame);
     }
@@ -113,6 +114,7 @@ fn test_multi_reader_logging() {
                 samples: vec![i as i16; 192],
                 sample_rate: 48000,
                 channels: 2,
+                metadata: Default::default(),
             };
             producer_buffer.push(frame);
             thread::sleep(Duration::from_millis(10));
@@ -0,0 +1,120 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use airlift_node::app::runtime::{NodeRuntime, ShutdownPhase};
+use airlift_node::config::Config;
+
+const CONFIG: &str = r#"
+node_name = "embedded"
+
+[producers.tone]
+type = "sine"
+enabled = true
+
+[processors.thru]
+type = "passthrough"
+enabled = true
+
+[consumers]
+
+[flows.main]
+enabled = true
+inputs = ["tone"]
+processors = ["thru"]
+outputs = []
+"#;
+
+#[test]
+fn shutdown_hooks_run_in_phase_and_registration_order() -> anyhow::Result<()> {
+    let log = Arc::new(Mutex::new(Vec::<String>::new()));
+    let record = |entry: &'static str| {
+        let log = log.clone();
+        move || {
+            log.lock().unwrap().push(entry.to_string());
+            Ok(())
+        }
+    };
+
+    let mut runtime = NodeRuntime::builder(Config::from_toml(CONFIG)?)
+        .api(false)
+        .process_globals(false)
+        .on_shutdown(ShutdownPhase::AfterStop, "upload", record("after:upload"))
+        .on_shutdown(
+            ShutdownPhase::BeforeStop,
+            "announce",
+            record("before:announce"),
+        )
+        .on_shutdown(ShutdownPhase::BeforeStop, "failing", || {
+            anyhow::bail!("controller unreachable")
+        })
+        .build()?;
+    assert!(!runtime.is_started());
+    assert!(!runtime.node().lock().unwrap().is_running());
+
+    // Während der Hooks vor dem Stopp läuft Audio noch, danach nicht mehr
+    let node = runtime.node();
+    let running = log.clone();
+    runtime.on_shutdown(ShutdownPhase::BeforeStop, "check", move || {
+        let state = node.lock().unwrap().is_running();
+        running
+            .lock()
+            .unwrap()
+            .push(format!("before:running={}", state));
+        Ok(())
+    });
+    let node = runtime.node();
+    let stopped = log.clone();
+    runtime.on_shutdown(ShutdownPhase::AfterStop, "check", move || {
+        let state = node.lock().unwrap().is_running();
+        stopped
+            .lock()
+            .unwrap()
+            .push(format!("after:running={}", state));
+        Ok(())
+    });
+
+    runtime.start()?;
+    assert!(runtime.is_started());
+    assert!(runtime.start().is_err(), "second start must be rejected");
+    assert!(runtime.node().lock().unwrap().is_running());
+
+    let handle = runtime.shutdown_handle();
+    std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(100));
+        handle.request();
+    });
+    let result = runtime.wait();
+
+    // Der fehlgeschlagene Hook wird gemeldet, hält die übrigen aber nicht auf
+    let error = format!("{:#}", result.unwrap_err());
+    assert!(
+        error.contains("shutdown hook 'failing' failed"),
+        "{}",
+        error
+    );
+    assert_eq!(
+        *log.lock().unwrap(),
+        [
+            "before:announce",
+            "before:running=true",
+            "after:upload",
+            "after:running=false"
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn dropping_a_started_runtime_stops_the_node() -> anyhow::Result<()> {
+    let mut runtime = NodeRuntime::builder(Config::from_toml(CONFIG)?)
+        .api(false)
+        .process_globals(false)
+        .build()?;
+    runtime.start()?;
+    let node = runtime.node();
+    assert!(node.lock().unwrap().is_running());
+
+    drop(runtime);
+    assert!(!node.lock().unwrap().is_running());
+    Ok(())
+}
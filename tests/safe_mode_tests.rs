@@ -0,0 +1,71 @@
+use std::path::PathBuf;
+
+use airlift_node::core::safe_mode::{should_enter, UNRECORDED_EXIT};
+use airlift_node::core::CrashCounter;
+
+fn crash_file(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("airlift-crash-{}-{}.json", name, std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+#[test]
+fn unstable_starts_accumulate_until_safe_mode() {
+    let path = crash_file("loop");
+    for start in 0..3 {
+        let counter = CrashCounter::open(&path);
+        let previous = counter.previous();
+        assert_eq!(previous.unstable_starts, start);
+        assert!(!should_enter(&previous, 3));
+        counter.begin_start().unwrap();
+        if start == 1 {
+            counter.record_failure("flow 'main': processor 'x' failed");
+        }
+    }
+
+    let counter = CrashCounter::open(&path);
+    let previous = counter.previous();
+    assert_eq!(previous.unstable_starts, 3);
+    assert!(should_enter(&previous, 3));
+    assert!(!should_enter(&previous, 0), "0 disables safe mode");
+    // Der dritte Start endete ohne erfassten Grund; der alte bleibt stehen
+    assert_eq!(
+        previous.last_reason.as_deref(),
+        Some("flow 'main': processor 'x' failed")
+    );
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn silent_exit_gets_placeholder_reason() {
+    let path = crash_file("silent");
+    CrashCounter::open(&path).begin_start().unwrap();
+    let previous = CrashCounter::open(&path).previous();
+    assert_eq!(previous.unstable_starts, 1);
+    assert_eq!(previous.last_reason.as_deref(), Some(UNRECORDED_EXIT));
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn stable_start_resets_counter_and_ignores_later_failures() {
+    let path = crash_file("stable");
+    let counter = CrashCounter::open(&path);
+    counter.begin_start().unwrap();
+    counter.record_failure("early");
+    counter.mark_stable();
+    assert!(counter.is_stable());
+    counter.record_failure("late panic in a worker thread");
+
+    let previous = CrashCounter::open(&path).previous();
+    assert_eq!(previous.unstable_starts, 0);
+    assert_eq!(previous.last_reason, None);
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn unreadable_crash_file_starts_from_zero() {
+    let path = crash_file("corrupt");
+    std::fs::write(&path, "not json").unwrap();
+    assert_eq!(CrashCounter::open(&path).previous().unstable_starts, 0);
+    let _ = std::fs::remove_file(&path);
+}
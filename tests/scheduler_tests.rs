@@ -0,0 +1,126 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use airlift_node::config::ScheduleConfig;
+use airlift_node::core::scheduler::{CronSchedule, ScheduleEntry, ScheduleTarget};
+use airlift_node::core::{AirliftNode, Event, EventHandler, EventType, Flow};
+use airlift_node::testing::mocks::MockProducer;
+
+const MINUTE: u64 = 60_000;
+const HOUR: u64 = 60 * MINUTE;
+const DAY: u64 = 24 * HOUR;
+/// Freitag, 2026-10-16 00:00 UTC
+const FRIDAY: u64 = 20_742 * DAY;
+
+fn schedule(cron: &str, action: &str, producer: Option<&str>, flow: Option<&str>) -> ScheduleConfig {
+    ScheduleConfig {
+        cron: cron.to_string(),
+        action: action.to_string(),
+        producer: producer.map(str::to_string),
+        flow: flow.map(str::to_string),
+        consumer: None,
+        enabled: true,
+    }
+}
+
+#[test]
+fn cron_next_and_previous_runs() -> anyhow::Result<()> {
+    let weekdays = CronSchedule::parse("0 18 * * mon-fri")?;
+    assert_eq!(weekdays.next_after(FRIDAY + 12 * HOUR), Some(FRIDAY + 18 * HOUR));
+    // Nach Freitag 18:00 kommt Montag
+    assert_eq!(weekdays.next_after(FRIDAY + 18 * HOUR), Some(FRIDAY + 3 * DAY + 18 * HOUR));
+    assert_eq!(
+        weekdays.previous_at_or_before(FRIDAY + DAY + 12 * HOUR),
+        Some(FRIDAY + 18 * HOUR)
+    );
+    assert_eq!(weekdays.previous_at_or_before(FRIDAY + 18 * HOUR), Some(FRIDAY + 18 * HOUR));
+
+    let daily = CronSchedule::parse("@daily")?;
+    assert_eq!(daily.next_after(FRIDAY), Some(FRIDAY + DAY));
+
+    let quarter = CronSchedule::parse("*/15 * * * *")?;
+    assert!(quarter.matches_minute((FRIDAY + 45 * MINUTE) / MINUTE));
+    assert!(!quarter.matches_minute((FRIDAY + 50 * MINUTE) / MINUTE));
+
+    assert_eq!(CronSchedule::parse("0 0 * * 7")?, CronSchedule::parse("0 0 * * sun")?);
+
+    // Tag und Wochentag eingeschränkt: einer genügt
+    let either = CronSchedule::parse("0 0 13 * fri")?;
+    assert!(either.matches_minute(FRIDAY / MINUTE));
+    assert!(either.matches_minute((FRIDAY - 3 * DAY) / MINUTE));
+    assert!(!either.matches_minute((FRIDAY + DAY) / MINUTE));
+    Ok(())
+}
+
+#[test]
+fn invalid_cron_and_schedule_configs_are_rejected() {
+    assert!(CronSchedule::parse("61 * * * *").is_err());
+    assert!(CronSchedule::parse("* * *").is_err());
+    assert!(CronSchedule::parse("*/0 * * * *").is_err());
+    assert!(CronSchedule::parse("0 0 * foo *").is_err());
+
+    assert!(ScheduleEntry::from_config("both", &schedule("@daily", "enable", Some("a"), Some("b"))).is_err());
+    assert!(ScheduleEntry::from_config("none", &schedule("@daily", "enable", None, None)).is_err());
+    assert!(ScheduleEntry::from_config("action", &schedule("@daily", "toggle", Some("a"), None)).is_err());
+
+    let entry = ScheduleEntry::from_config("sat", &schedule("0 18 * * *", "enable", Some("sat"), None)).unwrap();
+    assert_eq!(entry.target, ScheduleTarget::Producer("sat".to_string()));
+    assert_eq!(entry.target.to_string(), "producer:sat");
+}
+
+struct Collector {
+    events: Mutex<Vec<Event>>,
+}
+
+impl EventHandler for Collector {
+    fn handle_event(&self, event: &Event) -> anyhow::Result<()> {
+        self.events.lock().unwrap().push(event.clone());
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "collector"
+    }
+
+    fn event_type_filter(&self) -> Option<Vec<EventType>> {
+        Some(vec![EventType::ScheduleFired])
+    }
+}
+
+#[test]
+fn schedule_apply_switches_targets_and_publishes_events() -> anyhow::Result<()> {
+    let mut node = AirliftNode::new();
+    let collector = Arc::new(Collector {
+        events: Mutex::new(Vec::new()),
+    });
+    node.event_bus()
+        .lock()
+        .unwrap()
+        .register_handler(collector.clone())?;
+    node.add_flow(Flow::new("main"));
+    node.add_producer(Box::new(MockProducer::new("sat", Vec::new())))?;
+
+    let flow_off = ScheduleEntry::from_config("night", &schedule("0 1 * * *", "disable", None, Some("main")))?;
+    let flow_on = ScheduleEntry::from_config("morning", &schedule("0 6 * * *", "enable", None, Some("main")))?;
+    let sat_on = ScheduleEntry::from_config("sat", &schedule("0 18 * * *", "enable", Some("sat"), None))?;
+    let missing = ScheduleEntry::from_config("missing", &schedule("@hourly", "enable", Some("nope"), None))?;
+
+    flow_on.apply(&mut node, "test")?;
+    assert!(node.flows()[0].status().running);
+    flow_off.apply(&mut node, "test")?;
+    assert!(!node.flows()[0].status().running);
+    sat_on.apply(&mut node, "test")?;
+    assert!(missing.apply(&mut node, "test").is_err());
+
+    let deadline = Instant::now() + Duration::from_secs(1);
+    while Instant::now() < deadline && collector.events.lock().unwrap().len() < 4 {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+
+    let events = collector.events.lock().unwrap();
+    assert_eq!(events.len(), 4);
+    assert_eq!(events[2].payload["target"], "producer:sat");
+    assert_eq!(events[2].payload["ok"], true);
+    assert_eq!(events[3].payload["ok"], false);
+    Ok(())
+}
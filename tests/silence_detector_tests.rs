@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use airlift_node::core::processor::Processor;
+use airlift_node::core::{Event, EventBus, EventEmitter, EventHandler, EventType};
+use airlift_node::processors::SilenceDetector;
+use airlift_node::PcmFrame;
+
+/// 100 ms Stereo bei 48 kHz
+fn block(index: u64, level: i16) -> PcmFrame {
+    PcmFrame {
+        utc_ns: index * 100_000_000,
+        samples: vec![level; 9600],
+        sample_rate: 48_000,
+        channels: 2,
+        metadata: Default::default(),
+    }
+}
+
+fn silence_detector(config: serde_json::Value) -> anyhow::Result<SilenceDetector> {
+    let config: HashMap<String, serde_json::Value> = serde_json::from_value(config)?;
+    SilenceDetector::from_config("dead_air", &config)
+}
+
+struct Collector {
+    events: Mutex<Vec<Event>>,
+}
+
+impl EventHandler for Collector {
+    fn handle_event(&self, event: &Event) -> anyhow::Result<()> {
+        self.events.lock().unwrap().push(event.clone());
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "collector"
+    }
+
+    fn event_type_filter(&self) -> Option<Vec<EventType>> {
+        Some(vec![
+            EventType::custom("silence_detected"),
+            EventType::custom("silence_recovered"),
+        ])
+    }
+}
+
+#[test]
+fn alarm_after_duration_and_recovery_when_audio_returns() -> anyhow::Result<()> {
+    let mut bus = EventBus::new("test");
+    bus.start()?;
+    let collector = Arc::new(Collector {
+        events: Mutex::new(Vec::new()),
+    });
+    bus.register_handler(collector.clone())?;
+    let bus = Arc::new(Mutex::new(bus));
+
+    let mut detector = silence_detector(serde_json::json!({
+        "threshold_db": -40,
+        "duration": "1s",
+        "recovery": "300ms",
+    }))?;
+    detector.attach_event_emitter(EventEmitter::new(bus.clone(), "processor", "dead_air"));
+
+    // 0,5 s Programm, 1,5 s Stille (-50 dBFS ≈ 100), dann Programm
+    let mut frames: Vec<PcmFrame> = (0..5).map(|i| block(i, 8000)).collect();
+    frames.extend((5..20).map(|i| block(i, 100)));
+    detector.process_batch(&mut frames)?;
+    assert!(detector.is_alarm());
+    assert_eq!(detector.alarms(), 1);
+
+    // Kurzer Ausschlag unterbricht die Erholung
+    let mut frames = vec![block(20, 8000), block(21, 0)];
+    frames.extend((22..24).map(|i| block(i, 8000)));
+    detector.process_batch(&mut frames)?;
+    assert!(detector.is_alarm());
+    detector.process_batch(&mut vec![block(24, 8000)])?;
+    assert!(!detector.is_alarm());
+
+    let deadline = Instant::now() + Duration::from_secs(1);
+    while Instant::now() < deadline && collector.events.lock().unwrap().len() < 2 {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    bus.lock().unwrap().stop()?;
+
+    let events = collector.events.lock().unwrap();
+    assert_eq!(events.len(), 2);
+    assert!(events[0]
+        .event_type
+        .matches(&EventType::custom("silence_detected")));
+    assert_eq!(events[0].payload["silent_since_utc_ns"], 500_000_000u64);
+    assert_eq!(events[0].payload["silent_ms"], 1000);
+    assert!(events[1]
+        .event_type
+        .matches(&EventType::custom("silence_recovered")));
+    // Stille von 0,5 s bis zum Beginn des anhaltenden Programms bei 2,2 s
+    assert_eq!(events[1].payload["silent_ms"], 1700);
+    Ok(())
+}
+
+#[test]
+fn webhook_receives_alarm_as_json() -> anyhow::Result<()> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let url = format!("http://{}/hooks/dead-air", listener.local_addr()?);
+    let server = std::thread::spawn(move || -> anyhow::Result<String> {
+        let (stream, _) = listener.accept()?;
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let mut length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line)?;
+            if line.trim().is_empty() {
+                break;
+            }
+            if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                length = value.trim().parse()?;
+            }
+        }
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body)?;
+        (&stream).write_all(b"HTTP/1.1 204 No Content\r\nContent-Length: 0\r\n\r\n")?;
+        Ok(String::from_utf8(body)?)
+    });
+
+    let mut detector =
+        silence_detector(serde_json::json!({ "duration": "200ms", "webhook": url }))?;
+    let mut frames: Vec<PcmFrame> = (0..3).map(|i| block(i, 0)).collect();
+    detector.process_batch(&mut frames)?;
+    assert!(detector.is_alarm());
+
+    let body: serde_json::Value = serde_json::from_str(&server.join().unwrap()?)?;
+    assert_eq!(body["event"], "silence_detected");
+    assert_eq!(body["processor"], "dead_air");
+    assert_eq!(body["silent_ms"], 200);
+
+    assert!(silence_detector(serde_json::json!({ "webhook": "ftp://example" })).is_err());
+    assert!(silence_detector(serde_json::json!({ "threshold_db": 3 })).is_err());
+    assert!(silence_detector(serde_json::json!({ "duration": "0s" })).is_err());
+    Ok(())
+}
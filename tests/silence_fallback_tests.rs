@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use airlift_node::core::processor::Processor;
+use airlift_node::core::{Event, EventBus, EventEmitter, EventHandler, EventType};
+use airlift_node::processors::SilenceFallback;
+use airlift_node::PcmFrame;
+
+/// 100 ms Stereo bei 48 kHz
+fn block(index: u64, level: i16) -> PcmFrame {
+    PcmFrame {
+        utc_ns: index * 100_000_000,
+        samples: vec![level; 9600],
+        sample_rate: 48_000,
+        channels: 2,
+        metadata: Default::default(),
+    }
+}
+
+fn silence_fallback(config: serde_json::Value) -> anyhow::Result<SilenceFallback> {
+    let config: HashMap<String, serde_json::Value> = serde_json::from_value(config)?;
+    SilenceFallback::from_config("dead_air", &config)
+}
+
+fn peak(frame: &PcmFrame) -> u16 {
+    frame
+        .samples
+        .iter()
+        .map(|s| s.unsigned_abs())
+        .max()
+        .unwrap_or(0)
+}
+
+struct Collector {
+    events: Mutex<Vec<Event>>,
+}
+
+impl EventHandler for Collector {
+    fn handle_event(&self, event: &Event) -> anyhow::Result<()> {
+        self.events.lock().unwrap().push(event.clone());
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "collector"
+    }
+
+    fn event_type_filter(&self) -> Option<Vec<EventType>> {
+        Some(vec![
+            EventType::custom("fallback_started"),
+            EventType::custom("fallback_stopped"),
+        ])
+    }
+}
+
+#[test]
+fn tone_replaces_silence_until_program_returns() -> anyhow::Result<()> {
+    let mut bus = EventBus::new("test");
+    bus.start()?;
+    let collector = Arc::new(Collector {
+        events: Mutex::new(Vec::new()),
+    });
+    bus.register_handler(collector.clone())?;
+    let bus = Arc::new(Mutex::new(bus));
+
+    let mut fallback = silence_fallback(serde_json::json!({
+        "duration": "1s",
+        "recovery": "300ms",
+        "ramp": 0,
+        "level_db": "-6dB",
+    }))?;
+    fallback.attach_event_emitter(EventEmitter::new(bus.clone(), "processor", "dead_air"));
+
+    // 0,5 s Programm, dann Stille: ab 1,5 s läuft der Testton
+    let mut frames: Vec<PcmFrame> = (0..5).map(|i| block(i, 8000)).collect();
+    frames.extend((5..20).map(|i| block(i, 0)));
+    fallback.process_batch(&mut frames)?;
+    assert!(fallback.is_active());
+    assert_eq!(peak(&frames[13]), 0);
+    assert!(frames[13].metadata.get("fallback").is_none());
+    let tone = peak(&frames[15]);
+    assert!((16_300..=16_500).contains(&tone), "tone peak {}", tone);
+    assert_eq!(
+        frames[15].metadata.get("fallback"),
+        Some(&serde_json::json!("dead_air"))
+    );
+
+    // Programm kommt zurück, der Ton läuft noch `recovery` lang
+    let mut frames: Vec<PcmFrame> = (20..25).map(|i| block(i, 8000)).collect();
+    fallback.process_batch(&mut frames)?;
+    assert!(!fallback.is_active());
+    assert_ne!(frames[1].samples, vec![8000; 9600]);
+    assert_eq!(frames[4].samples, vec![8000; 9600]);
+    assert_eq!(fallback.status().metrics.unwrap()["activations"], 1);
+
+    let deadline = Instant::now() + Duration::from_secs(1);
+    while Instant::now() < deadline && collector.events.lock().unwrap().len() < 2 {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    bus.lock().unwrap().stop()?;
+
+    let events = collector.events.lock().unwrap();
+    assert_eq!(events.len(), 2);
+    assert!(events[0]
+        .event_type
+        .matches(&EventType::custom("fallback_started")));
+    assert_eq!(events[0].payload["source"], "tone");
+    assert_eq!(events[0].payload["silent_since_utc_ns"], 500_000_000u64);
+    assert_eq!(events[0].payload["silent_ms"], 1000);
+    assert!(events[1]
+        .event_type
+        .matches(&EventType::custom("fallback_stopped")));
+    assert_eq!(events[1].payload["silent_ms"], 1500);
+    Ok(())
+}
+
+#[test]
+fn backup_file_loops_and_config_is_validated() -> anyhow::Result<()> {
+    let path = std::env::temp_dir().join(format!("airlift-fallback-{}.wav", std::process::id()));
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: 48_000,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut writer = hound::WavWriter::create(&path, spec)?;
+    for n in 0..3000 {
+        writer.write_sample(if n < 1500 { 1000i16 } else { -1000 })?;
+    }
+    writer.finalize()?;
+
+    let mut fallback = silence_fallback(serde_json::json!({
+        "duration": "200ms",
+        "ramp": 0,
+        "file": path.to_string_lossy(),
+    }))?;
+    let mut frames: Vec<PcmFrame> = (0..3).map(|i| block(i, 0)).collect();
+    fallback.process_batch(&mut frames)?;
+    std::fs::remove_file(&path)?;
+    assert!(fallback.is_active());
+
+    // Mono-Datei auf beiden Kanälen, 3000 Frames in Schleife
+    let samples = &frames[1].samples;
+    assert_eq!(&samples[..4], &[1000, 1000, 1000, 1000]);
+    assert_eq!(&samples[3000..3002], &[-1000, -1000]);
+    assert_eq!(&samples[6000..6002], &[1000, 1000]);
+    assert_eq!(fallback.status().metrics.unwrap()["source"], "file");
+
+    fallback.update_config(serde_json::json!({ "enabled": false }))?;
+    let mut frames = vec![block(3, 0)];
+    fallback.process_batch(&mut frames)?;
+    assert_eq!(peak(&frames[0]), 0);
+
+    assert!(silence_fallback(serde_json::json!({ "duration": "0s" })).is_err());
+    assert!(silence_fallback(serde_json::json!({ "tone_hz": 5 })).is_err());
+    assert!(silence_fallback(serde_json::json!({ "file": "/nonexistent.wav" })).is_err());
+    Ok(())
+}
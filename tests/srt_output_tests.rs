@@ -0,0 +1,66 @@
+#![cfg(feature = "srt")]
+
+use std::time::Duration;
+
+use airlift_node::config::ConsumerConfig;
+use airlift_node::consumers::srt::{encode_rfma, SrtOutputConfig};
+use airlift_node::producers::srt::{parse_rfma, SrtMode};
+use serde_json::json;
+
+fn consumer_config(config: serde_json::Value) -> ConsumerConfig {
+    ConsumerConfig {
+        consumer_type: "srt_out".to_string(),
+        enabled: true,
+        path: None,
+        url: None,
+        config: serde_json::from_value(config).unwrap(),
+    }
+}
+
+#[test]
+fn srt_output_configs_are_validated() -> anyhow::Result<()> {
+    let parsed = SrtOutputConfig::from_config(
+        "contribution",
+        &consumer_config(json!({ "address": "127.0.0.1:9000", "streamid": "studio-a" })),
+    )?;
+    assert_eq!(parsed.mode, SrtMode::Caller);
+    assert_eq!(parsed.latency, Duration::from_millis(120));
+    assert_eq!((parsed.passphrase.as_deref(), parsed.key_length), (None, 16));
+    assert_eq!(parsed.codec, "pcm");
+    assert_eq!(parsed.endpoint(), "srt://127.0.0.1:9000 (caller, streamid studio-a)");
+
+    let listener = SrtOutputConfig::from_config(
+        "contribution",
+        &consumer_config(json!({
+            "address": "0.0.0.0:9000", "mode": "listener", "latency_ms": 400,
+            "passphrase": "sehr-geheim-123", "key_length": 32
+        })),
+    )?;
+    assert_eq!(listener.mode, SrtMode::Listener);
+    assert_eq!(listener.latency.as_millis(), 400);
+    assert_eq!(listener.key_length, 32);
+
+    for config in [
+        json!({}),
+        json!({ "address": "not-an-address" }),
+        json!({ "address": "127.0.0.1:9000", "mode": "rendezvous" }),
+        json!({ "address": "127.0.0.1:9000", "latency_ms": 5 }),
+        json!({ "address": "127.0.0.1:9000", "passphrase": "kurz" }),
+        json!({ "address": "127.0.0.1:9000", "passphrase": "sehr-geheim-123", "key_length": 20 }),
+        json!({ "address": "127.0.0.1:9000", "key_length": 32 }),
+        json!({ "address": "127.0.0.1:9000", "codec": "wma" }),
+    ] {
+        let result = SrtOutputConfig::from_config("contribution", &consumer_config(config.clone()));
+        assert!(result.is_err(), "{}", config);
+    }
+    Ok(())
+}
+
+#[test]
+fn rfma_packets_round_trip_to_the_srt_producer() {
+    let packet = encode_rfma(7, 1_700_000_000_000_000_000, &[1, 0, 2, 0]);
+    let parsed = parse_rfma(&packet).unwrap();
+    assert_eq!(parsed.seq, 7);
+    assert_eq!(parsed.utc_ns, 1_700_000_000_000_000_000);
+    assert_eq!(parsed.payload, &[1, 0, 2, 0]);
+}
@@ -29,6 +29,7 @@ fn test_core_components() {
         samples: vec![42, 43, 44],
         sample_rate: 48000,
         channels: 1,
+        metadata: Default::default(),
     };
 
     buffer.push(frame);
@@ -46,6 +47,7 @@ fn test_core_components() {
             samples: vec![i as i16; 96],
             sample_rate: 48000,
             channels: 2,
+            metadata: Default::default(),
         };
         buffer.push(frame);
     }
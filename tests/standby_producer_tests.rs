@@ -0,0 +1,63 @@
+use std::time::{Duration, Instant};
+
+use airlift_node::core::{AirliftNode, Flow};
+use airlift_node::testing::mocks::{MockConsumer, MockProducer};
+use airlift_node::PcmFrame;
+
+fn frame(utc_ns: u64) -> PcmFrame {
+    PcmFrame {
+        utc_ns,
+        samples: vec![1, 2, 3, 4],
+        sample_rate: 48_000,
+        channels: 2,
+        metadata: Default::default(),
+    }
+}
+
+#[test]
+fn standby_producer_feeds_same_buffer_after_switch() -> anyhow::Result<()> {
+    let (consumer, received) = MockConsumer::new_with_shared("out");
+    let mut flow = Flow::new("flow");
+    flow.add_consumer(Box::new(consumer));
+
+    let mut node = AirliftNode::new();
+    node.add_flow(flow);
+    node.add_producer(Box::new(MockProducer::new("card_a", vec![frame(1)])))?;
+    node.add_standby_producer("card_a", Box::new(MockProducer::new("card_b", vec![frame(2)])))?;
+    node.connect_flow_input(0, "producer:card_a")?;
+
+    assert_eq!(node.producer_names(), vec!["card_a".to_string()]);
+    assert_eq!(
+        node.standby_producers(),
+        vec![("card_b".to_string(), "card_a".to_string())]
+    );
+
+    node.start()?;
+    node.activate_standby_producer("card_b")?;
+
+    assert_eq!(node.producer_names(), vec!["card_b".to_string()]);
+    assert_eq!(node.producer_slots(), &["card_a".to_string()]);
+    assert_eq!(
+        node.standby_producers(),
+        vec![("card_a".to_string(), "card_a".to_string())]
+    );
+
+    let deadline = Instant::now() + Duration::from_secs(1);
+    while Instant::now() < deadline && received.lock().unwrap().len() < 2 {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    node.stop()?;
+
+    let utc: Vec<u64> = received.lock().unwrap().iter().map(|f| f.utc_ns).collect();
+    assert!(utc.contains(&2), "standby frames missing: {:?}", utc);
+    Ok(())
+}
+
+#[test]
+fn unknown_standby_is_rejected() {
+    let mut node = AirliftNode::new();
+    assert!(node.activate_standby_producer("missing").is_err());
+    assert!(node
+        .add_standby_producer("missing", Box::new(MockProducer::new("x", Vec::new())))
+        .is_err());
+}
@@ -0,0 +1,198 @@
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use airlift_node::audio::archive::list_recordings;
+use airlift_node::codecs::{CodecInfo, CodecKind, ContainerKind, EncodedFrame};
+use airlift_node::core::consumer::file_writer::{FileConsumer, RecorderState};
+use airlift_node::core::state_store::{self, decode_slots, encode_slots};
+use airlift_node::core::{EncodedFlow, SpliceMode, StateStore};
+use airlift_node::ring::{EncodedFramePacket, EncodedRing, EncodedRingRead};
+use airlift_node::testing::mocks::{MockEncodedProducer, MockEncodedSink};
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("airlift-state-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn frame(tag: u8) -> EncodedFrame {
+    EncodedFrame {
+        payload: vec![tag, 0x00, 0xFF, tag],
+        info: CodecInfo {
+            kind: CodecKind::Mp3,
+            sample_rate: 44_100,
+            channels: 2,
+            container: ContainerKind::Mpeg,
+        },
+    }
+}
+
+#[test]
+fn store_round_trips_and_discards_stale_or_foreign_files() {
+    let dir = temp_dir("store");
+    let store = StateStore::open(&dir, Duration::from_secs(60), Duration::from_secs(1)).unwrap();
+    store.save_json("flow/main", &vec![1u32, 2, 3]).unwrap();
+    let stored = store.load_fresh_json::<Vec<u32>>("flow/main").unwrap();
+    assert_eq!(stored.value, vec![1, 2, 3]);
+    assert!(stored.age < Duration::from_secs(5));
+    // Schlüssel werden zu sicheren Dateinamen
+    assert!(dir.join("flow_main.state").exists());
+
+    let strict = StateStore::open(&dir, Duration::ZERO, Duration::from_secs(1)).unwrap();
+    std::thread::sleep(Duration::from_millis(5));
+    assert!(strict.load_fresh_json::<Vec<u32>>("flow/main").is_none());
+    assert!(strict.load_json::<Vec<u32>>("flow/main").is_some());
+
+    fs::write(dir.join("garbage.state"), b"not a state file").unwrap();
+    assert!(store.load_bytes("garbage").is_none());
+    store.remove("flow/main");
+    assert!(store.load_bytes("flow/main").is_none());
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn ring_snapshot_restores_sequence_numbers_and_time_index() {
+    let ring = EncodedRing::new(4, frame(0));
+    for tag in 1..=6u8 {
+        ring.writer_push(1_000 * tag as u64, frame(tag));
+    }
+    let slots = ring.snapshot();
+    assert_eq!(slots.iter().map(|slot| slot.seq).collect::<Vec<_>>(), vec![3, 4, 5, 6]);
+
+    let decoded = decode_slots(&encode_slots(&slots)).unwrap();
+    assert_eq!(decoded.len(), 4);
+    assert_eq!(decoded[1].frame.payload, frame(4).payload);
+    assert_eq!(decoded[1].utc_ns, 4_000);
+    assert!(decode_slots(&encode_slots(&slots)[..20]).is_err());
+
+    let restored = EncodedRing::new(4, frame(0));
+    assert_eq!(restored.restore(&decoded), 4);
+    // Ein Ring mit Daten bleibt unangetastet
+    assert_eq!(restored.restore(&decoded), 0);
+
+    // Cursor nach seq 4 liest 5 und 6
+    let mut reader = restored.subscribe_after(4);
+    match reader.poll() {
+        EncodedRingRead::Frame { utc_ns, .. } => assert_eq!(utc_ns, 5_000),
+        _ => panic!("expected frame 5"),
+    }
+    let mut timeshift = restored.subscribe_at_utc(4_500);
+    match timeshift.poll() {
+        EncodedRingRead::Frame { frame, .. } => assert_eq!(frame.payload[0], 5),
+        _ => panic!("expected frame 5"),
+    }
+    // Neue Frames laufen hinter dem wiederhergestellten Ende weiter
+    assert_eq!(restored.writer_push(7_000, frame(7)), 7);
+
+    // Lücken: nur das lückenlose Ende wird übernommen
+    let mut gappy = decoded.clone();
+    gappy.remove(1);
+    let partial = EncodedRing::new(4, frame(0));
+    assert_eq!(partial.restore(&gappy), 2);
+    assert_eq!(partial.snapshot().first().map(|slot| slot.seq), Some(5));
+}
+
+#[test]
+fn encoded_flow_keeps_ring_cursor_and_splice_mode_across_restart() -> anyhow::Result<()> {
+    let dir = temp_dir("flow");
+    state_store::install(Some(StateStore::open(&dir, Duration::from_secs(60), Duration::from_secs(1))?));
+
+    let input: Vec<EncodedFramePacket> = (1..=20u8)
+        .map(|tag| EncodedFramePacket {
+            utc_ns: 1_000 * tag as u64,
+            frame: frame(tag),
+        })
+        .collect();
+    let sink = Arc::new(MockEncodedSink::new());
+    let mut flow = EncodedFlow::new("relay");
+    flow.set_producer(Box::new(MockEncodedProducer::new("ts_in", input)));
+    flow.add_output("icecast", sink.clone());
+    flow.start()?;
+    let deadline = Instant::now() + Duration::from_secs(2);
+    while sink.received().len() < 20 && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    flow.set_mode(SpliceMode::Processed);
+    flow.stop()?;
+    drop(flow);
+
+    // "Neuer Prozess": gleicher Flow-Name, leerer Ring
+    let resumed = Arc::new(MockEncodedSink::new());
+    let mut flow = EncodedFlow::new("relay");
+    flow.add_output("icecast", resumed.clone());
+    flow.start()?;
+    assert_eq!(flow.ring().snapshot().len(), 20);
+    let status = flow.status();
+    assert_eq!(status.splice.requested_mode, SpliceMode::Processed);
+
+    // Cursor stand am Ende: nichts doppelt, neue Frames laufen weiter
+    flow.processed_input().push(EncodedFramePacket {
+        utc_ns: 50_000,
+        frame: frame(50),
+    })?;
+    let deadline = Instant::now() + Duration::from_secs(2);
+    while resumed.received().is_empty() && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    flow.stop()?;
+    assert_eq!(resumed.payloads(), vec![frame(50).payload]);
+
+    state_store::install(None);
+    let _ = fs::remove_dir_all(&dir);
+    Ok(())
+}
+
+#[test]
+fn interrupted_recording_is_finalized_on_next_start() {
+    let dir = temp_dir("recorder");
+    let store = StateStore::open(dir.join("state"), Duration::from_secs(60), Duration::from_secs(1)).unwrap();
+    let path = dir.join("program.wav");
+    let part_path = dir.join("program.wav.part");
+
+    // Header mit Platzhalter-Größen, 1 s Stereo-PCM und ein halbes Sample
+    let mut file = fs::File::create(&part_path).unwrap();
+    let mut header = Vec::new();
+    header.extend_from_slice(b"RIFF\0\0\0\0WAVEfmt ");
+    header.extend_from_slice(&16u32.to_le_bytes());
+    header.extend_from_slice(&1u16.to_le_bytes());
+    header.extend_from_slice(&2u16.to_le_bytes());
+    header.extend_from_slice(&48_000u32.to_le_bytes());
+    header.extend_from_slice(&192_000u32.to_le_bytes());
+    header.extend_from_slice(&4u16.to_le_bytes());
+    header.extend_from_slice(&16u16.to_le_bytes());
+    header.extend_from_slice(b"data\0\0\0\0");
+    file.write_all(&header).unwrap();
+    file.write_all(&vec![1u8; 192_000]).unwrap();
+    file.write_all(&[7u8]).unwrap();
+    drop(file);
+    store
+        .save_json(
+            "recorder-archive",
+            &RecorderState {
+                path: path.clone(),
+                part_path: part_path.clone(),
+                started_at_ms: 1_700_000_000_000,
+                data_bytes: 96_000,
+            },
+        )
+        .unwrap();
+
+    let consumer = FileConsumer::new("archive", path.to_str().unwrap());
+    assert_eq!(consumer.recover_interrupted(&store), Some(path.clone()));
+    assert!(!part_path.exists());
+    let reader = hound::WavReader::open(&path).unwrap();
+    assert_eq!(reader.duration(), 48_000);
+    let recordings = list_recordings(&dir);
+    assert_eq!(recordings.len(), 1);
+    assert_eq!(recordings[0].entry.duration_ms, 1_000);
+
+    // Zustand ist verbraucht
+    assert_eq!(consumer.recover_interrupted(&store), None);
+
+    let _ = fs::remove_dir_all(&dir);
+}
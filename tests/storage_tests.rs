@@ -0,0 +1,227 @@
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::mpsc;
+use std::thread;
+
+use airlift_node::api::events::EventHistoryHandler;
+use airlift_node::api::peaks::PeakPoint;
+use airlift_node::config::StorageConfig;
+use airlift_node::core::{Event, EventHandler, EventPriority, EventType};
+use airlift_node::storage::influx::{InfluxBackend, InfluxOptions};
+use airlift_node::storage::{self, MemoryBackend, StorageBackend};
+use serde_json::json;
+
+fn point(ts: u64, peak: f32, flow: &str) -> PeakPoint {
+    PeakPoint {
+        ts,
+        peak_l: peak,
+        peak_r: peak / 2.0,
+        silence: false,
+        flow: flow.to_string(),
+    }
+}
+
+fn event_at(ms: u64, event_type: EventType, name: &str) -> Event {
+    let mut event = Event::new(event_type, EventPriority::Info, "flow", name, json!({ "name": name }));
+    event.timestamp = ms * 1_000_000;
+    event
+}
+
+/// Beantwortet nacheinander die Verbindungen mit `responses` und meldet
+/// jede Anfrage samt Body.
+fn serve(responses: Vec<&'static str>) -> (u16, mpsc::Receiver<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        for response in responses {
+            let Ok((mut stream, _)) = listener.accept() else {
+                return;
+            };
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            loop {
+                let head_end = request.windows(4).position(|w| w == b"\r\n\r\n");
+                if let Some(end) = head_end {
+                    let head = String::from_utf8_lossy(&request[..end]).to_lowercase();
+                    let length = head
+                        .lines()
+                        .find_map(|line| line.strip_prefix("content-length:"))
+                        .and_then(|value| value.trim().parse::<usize>().ok())
+                        .unwrap_or(0);
+                    if request.len() >= end + 4 + length {
+                        break;
+                    }
+                }
+                match stream.read(&mut buf) {
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => request.extend_from_slice(&buf[..n]),
+                }
+            }
+            let _ = tx.send(String::from_utf8_lossy(&request).to_string());
+            let _ = stream.write_all(response.as_bytes());
+        }
+    });
+    (port, rx)
+}
+
+#[test]
+fn memory_backend_keeps_recent_events_newest_last() -> anyhow::Result<()> {
+    let storage = MemoryBackend::new();
+    for (ms, name) in [(1_000, "a"), (2_000, "b"), (3_000, "c"), (4_000, "d")] {
+        storage.push_event(&event_at(ms, EventType::FlowStateChanged, name))?;
+    }
+    let names = |events: Vec<Event>| -> Vec<String> {
+        events.into_iter().map(|event| event.source_instance).collect()
+    };
+    assert_eq!(names(storage.events(0, u64::MAX, 2)?), ["c", "d"]);
+    assert_eq!(names(storage.events(1_500, 3_000, 10)?), ["b", "c"]);
+
+    // Außerhalb der Aufbewahrung fällt das Älteste heraus
+    storage.push_event(&event_at(1_001 + 24 * 3_600_000, EventType::FlowStateChanged, "e"))?;
+    assert_eq!(names(storage.events(0, u64::MAX, 10)?), ["b", "c", "d", "e"]);
+
+    // Peaks laufen über dieselbe Schnittstelle
+    storage.push_peak(point(1_000, 0.2, "main"))?;
+    storage.push_peak(point(1_500, 0.6, "main"))?;
+    assert_eq!(storage.peak_range(Some("main"))?, Some((1_000, 1_500)));
+    let buckets = storage.aggregate_peaks(0, 2_000, None, 1_000)?;
+    assert_eq!((buckets.len(), buckets[0].peak_l), (1, 0.6));
+    Ok(())
+}
+
+#[test]
+fn event_history_skips_measurements() -> anyhow::Result<()> {
+    let storage = std::sync::Arc::new(MemoryBackend::new());
+    let handler = EventHistoryHandler::new("events", storage.clone());
+    assert_eq!(handler.priority_filter(), Some(EventPriority::Info));
+    handler.handle_event(&event_at(1_000, EventType::AudioLevelStats, "stats"))?;
+    handler.handle_event(&event_at(1_001, EventType::OnAirChanged, "on_air"))?;
+    let events = storage.events(0, u64::MAX, 10)?;
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].source_instance, "on_air");
+    Ok(())
+}
+
+#[test]
+fn storage_config_is_validated() {
+    let parse = |toml_text: &str| toml::from_str::<StorageConfig>(toml_text).unwrap().validate();
+    assert!(parse("").is_ok());
+    assert!(parse("backend = \"influx\"\nurl = \"http://influx:8086\"\ndatabase = \"airlift\"").is_ok());
+    assert_eq!(parse("backend = \"sqlite\"\npath = \"/tmp/history.db\"").is_ok(), cfg!(feature = "sqlite"));
+
+    for (bad, message) in [
+        ("backend = \"postgres\"", "unknown"),
+        ("backend = \"influx\"\nurl = \"http://influx:8086\"", "storage.database"),
+        ("backend = \"influx\"\ndatabase = \"airlift\"", "storage.url"),
+        ("backend = \"influx\"\nurl = \"https://influx\"\ndatabase = \"x\"", "storage.url"),
+        ("retention = \"10s\"", "storage.retention"),
+    ] {
+        let err = parse(bad).err().map(|e| format!("{:#}", e)).unwrap_or_default();
+        assert!(err.contains(message), "{}: {}", bad, err);
+    }
+
+    let config = toml::from_str::<StorageConfig>("retention = \"7d\"").unwrap();
+    assert_eq!(storage::open(&config).unwrap().kind(), "memory");
+}
+
+#[test]
+fn influx_backend_writes_line_protocol_and_reads_influxql() -> anyhow::Result<()> {
+    let (port, requests) = serve(vec![
+        "HTTP/1.0 204 No Content\r\n\r\n",
+        concat!(
+            "HTTP/1.0 200 OK\r\nContent-Type: application/json\r\n\r\n",
+            r#"{"results":[{"statement_id":0,"series":[{"name":"airlift_peak","#,
+            r#""columns":["time","peak_l","peak_r","silence","flow"],"#,
+            r#""values":[[2000,0.5,0.25,false,"main"],[1000,0.1,0.05,true,"main"]]}]}]}"#
+        ),
+        "HTTP/1.0 200 OK\r\n\r\n{\"results\":[{\"statement_id\":0,\"error\":\"database not found: airlift\"}]}",
+    ]);
+    let backend = InfluxBackend::new(InfluxOptions {
+        url: format!("http://127.0.0.1:{}", port),
+        database: "airlift".to_string(),
+        username: Some("node".to_string()),
+        password: Some("s&cret".to_string()),
+    });
+    backend.push_peak(point(1_000, 0.5, "main program"))?;
+    backend.push_event(&event_at(1_001, EventType::OnAirChanged, "on_air"))?;
+    backend.flush()?;
+
+    let write = requests.recv()?;
+    assert!(
+        write.starts_with("POST /write?db=airlift&u=node&p=s%26cret&precision=ns HTTP/1.0\r\n"),
+        "{}",
+        write
+    );
+    let body = write.split("\r\n\r\n").nth(1).unwrap();
+    let lines: Vec<&str> = body.lines().collect();
+    assert_eq!(lines[0], "airlift_peak,flow=main\\ program peak_l=0.5,peak_r=0.25,silence=false 1000000000");
+    assert!(lines[1].starts_with("airlift_event,priority=Info,source=flow event=\"{\\\"id\\\":"), "{}", lines[1]);
+    assert!(lines[1].ends_with(" 1001000000"), "{}", lines[1]);
+
+    let points = backend.peaks(500, 2_500, Some("main"))?;
+    let query = requests.recv()?;
+    assert!(query.starts_with("POST /query?db=airlift&u=node&p=s%26cret&epoch=ms "), "{}", query);
+    assert!(query.contains("q=SELECT%20peak_l"), "{}", query);
+    assert!(query.contains("time%20%3E%3D%20500ms"), "{}", query);
+    assert!(query.contains("flow%20%3D%20%27main%27"), "{}", query);
+    assert_eq!(points.iter().map(|point| point.ts).collect::<Vec<_>>(), [1_000, 2_000]);
+    assert!(points[0].silence && !points[1].silence);
+
+    let err = backend.peak_count(0, 1, None).unwrap_err();
+    assert!(format!("{:#}", err).contains("database not found"), "{:#}", err);
+    Ok(())
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn sqlite_backend_survives_reopen() -> anyhow::Result<()> {
+    use std::time::Duration;
+
+    use airlift_node::audio::archive::{recording_info, ManifestEntry};
+    use airlift_node::storage::SqliteBackend;
+
+    let dir = std::env::temp_dir().join(format!("airlift-storage-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let path = dir.join("history.db");
+    let entry = |file: &str, sha: &str, at: u64| ManifestEntry {
+        file: file.to_string(),
+        duration_ms: 60_000,
+        bytes: 1_000,
+        sha256: sha.repeat(64),
+        finished_at_ms: at,
+        finished_at_local: None,
+        timezone: None,
+    };
+    {
+        let storage = SqliteBackend::open(&path, Duration::from_secs(3600))?;
+        for (ts, peak, flow) in [(1_000, 0.2, "main"), (1_400, 0.6, "main"), (1_500, 0.9, "backup")] {
+            storage.push_peak(point(ts, peak, flow))?;
+        }
+        storage.push_event(&event_at(1_200, EventType::OnAirChanged, "on_air"))?;
+        storage.index_recording(&recording_info(&dir, entry("a.wav", "a", 5_000)))?;
+        storage.index_recording(&recording_info(&dir, entry("b.wav", "b", 6_000)))?;
+        storage.forget_recording(&dir.join("a.wav"))?;
+    }
+
+    let storage = SqliteBackend::open(&path, Duration::from_secs(3600))?;
+    assert_eq!(storage.peak_count(0, 2_000, Some("main"))?, 2);
+    assert_eq!(storage.peak_range(None)?, Some((1_000, 1_500)));
+    let buckets = storage.aggregate_peaks(0, 2_000, Some("main"), 1_000)?;
+    assert_eq!((buckets[0].count, buckets[0].peak_l, buckets[0].min_l), (2, 0.6, 0.2));
+    let events = storage.events(0, u64::MAX, 10)?;
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].source_instance, "on_air");
+
+    let recordings = storage.recordings()?;
+    assert_eq!(recordings.len(), 1);
+    assert_eq!(recordings[0].entry.file, "b.wav");
+    assert_eq!(storage.find_recording(&"b".repeat(16))?.map(|r| r.path), Some(dir.join("b.wav").display().to_string()));
+
+    // Aufräumen beim Schreiben: alles älter als die Aufbewahrung verschwindet
+    storage.push_peak(point(1_000 + 2 * 3_600_000, 0.1, "main"))?;
+    assert_eq!(storage.peak_count(0, u64::MAX, None)?, 1);
+
+    let _ = std::fs::remove_dir_all(&dir);
+    Ok(())
+}
@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use airlift_node::core::processor::Processor;
+use airlift_node::core::{AudioRingBuffer, BufferRegistry};
+use airlift_node::processors::Switcher;
+use airlift_node::PcmFrame;
+
+/// 100 ms Stereo bei 48 kHz mit konstantem Pegel
+fn block(level: i16) -> PcmFrame {
+    PcmFrame {
+        utc_ns: 0,
+        samples: vec![level; 9600],
+        sample_rate: 48_000,
+        channels: 2,
+        metadata: Default::default(),
+    }
+}
+
+fn source_switcher(config: serde_json::Value) -> anyhow::Result<Switcher> {
+    let config: HashMap<String, serde_json::Value> = serde_json::from_value(config)?;
+    Switcher::from_config("sources", &config)
+}
+
+fn run(switcher: &mut Switcher) -> anyhow::Result<Vec<PcmFrame>> {
+    let input = AudioRingBuffer::new(8);
+    let output = AudioRingBuffer::new(16);
+    input.push(block(0));
+    switcher.process(&input, &output)?;
+    Ok(std::iter::from_fn(|| output.pop()).collect())
+}
+
+#[test]
+fn crossfades_between_registry_sources() -> anyhow::Result<()> {
+    let registry = Arc::new(BufferRegistry::new());
+    let studio = Arc::new(AudioRingBuffer::new(16));
+    let backup = Arc::new(AudioRingBuffer::new(16));
+    registry.register("producer:studio", studio.clone())?;
+    registry.register("producer:backup", backup.clone())?;
+
+    let mut switcher = source_switcher(serde_json::json!({
+        "sources": ["studio", "backup"],
+        "crossfade": "200ms",
+        "curve": "linear",
+    }))?;
+    switcher.attach_buffer_registry(registry);
+    assert_eq!(switcher.active(), "studio");
+
+    studio.push(block(1000));
+    backup.push(block(3000));
+    let frames = run(&mut switcher)?;
+    assert_eq!(frames.len(), 1);
+    assert!(frames[0].samples.iter().all(|s| *s == 1000));
+
+    switcher.update_config(serde_json::json!({ "active": "backup" }))?;
+    assert_eq!(switcher.active(), "backup");
+    assert!(switcher.is_fading());
+
+    for _ in 0..3 {
+        studio.push(block(1000));
+        backup.push(block(3000));
+    }
+    let frames = run(&mut switcher)?;
+    assert_eq!(frames.len(), 3);
+    // Linear über 200 ms: 1000 → 2000 im ersten, → 3000 im zweiten Frame
+    assert_eq!(frames[0].samples[0], 1000);
+    assert!((frames[0].samples[9598] - 2000).abs() <= 1);
+    assert!((frames[1].samples[9598] - 3000).abs() <= 1);
+    let mut rising = frames[0]
+        .samples
+        .chunks(2)
+        .zip(frames[0].samples.chunks(2).skip(1));
+    assert!(rising.all(|(a, b)| a[0] <= b[0]));
+    assert!(frames[2].samples.iter().all(|s| *s == 3000));
+    assert!(!switcher.is_fading());
+    assert_eq!(switcher.switches(), 1);
+
+    assert!(switcher
+        .update_config(serde_json::json!({ "active": "nope" }))
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn hard_cut_without_crossfade_and_config_errors() -> anyhow::Result<()> {
+    let registry = Arc::new(BufferRegistry::new());
+    let a = Arc::new(AudioRingBuffer::new(16));
+    let b = Arc::new(AudioRingBuffer::new(16));
+    registry.register("flow:a", a.clone())?;
+    registry.register("producer:b", b.clone())?;
+
+    let mut switcher = source_switcher(serde_json::json!({
+        "sources": ["flow:a", "b"],
+        "active": "b",
+        "crossfade": 0,
+    }))?;
+    switcher.attach_buffer_registry(registry);
+    assert_eq!(switcher.active(), "b");
+    assert_eq!(switcher.switches(), 0);
+
+    a.push(block(500));
+    b.push(block(-500));
+    assert!(run(&mut switcher)?[0].samples.iter().all(|s| *s == -500));
+
+    switcher.update_config(serde_json::json!({ "active": "flow:a" }))?;
+    assert!(!switcher.is_fading());
+    a.push(block(500));
+    b.push(block(-500));
+    assert!(run(&mut switcher)?[0].samples.iter().all(|s| *s == 500));
+
+    assert!(source_switcher(serde_json::json!({ "sources": [] })).is_err());
+    assert!(source_switcher(serde_json::json!({ "sources": ["a"], "active": "b" })).is_err());
+    assert!(source_switcher(serde_json::json!({ "sources": ["a"], "curve": "s" })).is_err());
+    Ok(())
+}
@@ -24,6 +24,7 @@ fn test_ringbuffer_threading_contention() {
                     samples: vec![producer_id as i16; 32],
                     sample_rate: 48_000,
                     channels: 2,
+                    metadata: Default::default(),
                 };
                 buffer.push(frame);
                 pushed.fetch_add(1, Ordering::Relaxed);
@@ -0,0 +1,163 @@
+use airlift_node::audio::archive::record_file_in;
+use airlift_node::config::Config;
+use airlift_node::core::scheduler::{CronSchedule, ScheduleEntry};
+use airlift_node::core::TimeZone;
+
+const MINUTE: u64 = 60_000;
+const HOUR: u64 = 60 * MINUTE;
+const DAY: u64 = 24 * HOUR;
+/// 2026-03-29 00:00 UTC, Umstellung auf Sommerzeit um 01:00 UTC
+const SPRING: u64 = 20_541 * DAY;
+/// 2026-10-25 00:00 UTC, Rückstellung um 01:00 UTC
+const AUTUMN: u64 = 20_751 * DAY;
+
+fn berlin() -> TimeZone {
+    TimeZone::parse("CET-1CEST,M3.5.0,M10.5.0/3").unwrap()
+}
+
+#[test]
+fn posix_rules_switch_offsets_at_the_right_instant() {
+    let tz = berlin();
+    let spring_s = (SPRING / 1000) as i64;
+    assert_eq!(tz.offset_at(spring_s + 3599), 3600);
+    assert_eq!(tz.offset_at(spring_s + 3600), 7200);
+
+    assert_eq!(tz.format_local(AUTUMN + 30 * MINUTE), "2026-10-25T02:30:00+02:00");
+    assert_eq!(tz.format_local(AUTUMN + 90 * MINUTE), "2026-10-25T02:30:00+01:00");
+    assert_eq!(
+        tz.file_stamp(AUTUMN + 90 * MINUTE),
+        "20261025T023000+0100_20261025T013000Z"
+    );
+
+    let sydney = TimeZone::parse("AEST-10AEDT,M10.1.0,M4.1.0/3").unwrap();
+    assert_eq!(sydney.offset_at((AUTUMN / 1000) as i64), 11 * 3600);
+    assert_eq!(sydney.offset_at((SPRING / 1000) as i64 + 180 * 86_400), 10 * 3600);
+
+    assert!(TimeZone::parse("UTC").unwrap().is_utc());
+    assert_eq!(TimeZone::parse("-03:30").unwrap().offset_at(0), -12_600);
+    for invalid in ["", "+25:00", "X1", "CET-1CEST,M13.5.0,M10.5.0", "../etc/passwd"] {
+        assert!(TimeZone::parse(invalid).is_err(), "{}", invalid);
+    }
+}
+
+#[test]
+fn local_boundaries_follow_dst() {
+    let tz = berlin();
+    // Der 29. März hat 23 Stunden
+    let midnight = tz.next_local_midnight(SPRING - 12 * HOUR);
+    assert_eq!(midnight, SPRING - HOUR);
+    assert_eq!(tz.next_local_midnight(midnight), SPRING + 22 * HOUR);
+
+    // 01:30 CET -> 03:00 CEST, die Stunde 02 fällt aus
+    assert_eq!(tz.next_top_of_hour(SPRING + 30 * MINUTE), SPRING + HOUR);
+    // 02:30 CEST -> 02:00 CET (doppelte Stunde) -> 03:00 CET
+    assert_eq!(tz.next_top_of_hour(AUTUMN + 30 * MINUTE), AUTUMN + HOUR);
+    assert_eq!(tz.next_top_of_hour(AUTUMN + HOUR), AUTUMN + 2 * HOUR);
+
+    // Halbstündige Offsets: volle Ortsstunde ist nicht die volle UTC-Stunde
+    let india = TimeZone::parse("+05:30").unwrap();
+    assert_eq!(india.next_top_of_hour(AUTUMN), AUTUMN + 30 * MINUTE);
+}
+
+#[test]
+fn cron_runs_in_local_time_across_dst() -> anyhow::Result<()> {
+    let tz = berlin();
+    let night = CronSchedule::parse("30 2 * * *")?;
+    // Übersprungenes 02:30 läuft direkt nach der Umstellung (03:00 CEST)
+    assert_eq!(night.next_after_in(SPRING, &tz), Some(SPRING + HOUR));
+    assert_eq!(night.next_after_in(SPRING + HOUR, &tz), Some(SPRING + DAY + 30 * MINUTE));
+
+    // Doppeltes 02:30 nur einmal
+    assert_eq!(night.next_after_in(AUTUMN, &tz), Some(AUTUMN + 30 * MINUTE));
+    assert_eq!(
+        night.next_after_in(AUTUMN + 30 * MINUTE, &tz),
+        Some(AUTUMN + DAY + 90 * MINUTE)
+    );
+    assert!(night.fires_at_minute((AUTUMN + 30 * MINUTE) / MINUTE, &tz));
+    assert!(!night.fires_at_minute((AUTUMN + 90 * MINUTE) / MINUTE, &tz));
+    assert_eq!(
+        night.previous_at_or_before_in(AUTUMN + 12 * HOUR, &tz),
+        Some(AUTUMN + 30 * MINUTE)
+    );
+
+    // UTC verhält sich wie bisher
+    let utc = TimeZone::utc();
+    assert_eq!(night.next_after_in(AUTUMN, &utc), night.next_after(AUTUMN));
+    Ok(())
+}
+
+const CONFIG: &str = r#"
+node_name = "studio"
+timezone = "+02:00"
+
+[producers.sat]
+type = "sine"
+enabled = true
+
+[processors]
+
+[consumers]
+
+[flows.main]
+enabled = true
+inputs = ["sat"]
+processors = []
+outputs = []
+config = { timezone = "CET-1CEST,M3.5.0,M10.5.0/3" }
+
+[schedules.sat_on]
+cron = "0 18 * * *"
+action = "enable"
+producer = "sat"
+
+[schedules.main_off]
+cron = "0 1 * * *"
+action = "disable"
+flow = "main"
+"#;
+
+#[test]
+fn schedules_use_the_flow_or_node_time_zone() -> anyhow::Result<()> {
+    let config = Config::from_toml(CONFIG)?;
+    config.validate()?;
+    let entries = ScheduleEntry::from_configs(&config)?;
+    assert_eq!(entries[0].name, "main_off");
+    assert_eq!(entries[0].timezone.name(), "CET-1CEST,M3.5.0,M10.5.0/3");
+    assert_eq!(entries[1].timezone.name(), "+02:00");
+    // 18:00 +02:00 = 16:00 UTC
+    assert_eq!(entries[1].next_after(AUTUMN), Some(AUTUMN + 16 * HOUR));
+
+    let broken = CONFIG.replace("\"+02:00\"", "\"Mars/Olympus\"");
+    assert!(Config::from_toml(&broken)?.validate().is_err());
+    Ok(())
+}
+
+#[test]
+fn zones_load_from_tzif_footer_and_recordings_keep_local_time() -> anyhow::Result<()> {
+    let dir = std::env::temp_dir().join(format!("airlift-tz-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("Europe"))?;
+
+    // TZif v2 ohne Übergänge, nur mit POSIX-Regel am Ende
+    let mut header = b"TZif2".to_vec();
+    header.resize(44, 0);
+    let mut tzif = header.clone();
+    tzif.extend_from_slice(&header);
+    tzif.extend_from_slice(b"\nCET-1CEST,M3.5.0,M10.5.0/3\n");
+    std::fs::write(dir.join("Europe/Berlin"), &tzif)?;
+
+    let tz = TimeZone::load("Europe/Berlin", &dir)?;
+    assert_eq!(tz.name(), "Europe/Berlin");
+    assert_eq!(tz.offset_at((AUTUMN / 1000) as i64), 7200);
+    assert!(TimeZone::load("Europe/Paris", &dir).is_err());
+    assert!(TimeZone::load("/etc/localtime", &dir).is_err());
+
+    let recording = dir.join("take.wav");
+    std::fs::write(&recording, b"RIFF")?;
+    let entry = record_file_in(&recording, 1_000, AUTUMN + 90 * MINUTE, &tz)?;
+    assert_eq!(entry.finished_at_ms, AUTUMN + 90 * MINUTE);
+    assert_eq!(entry.finished_at_local.as_deref(), Some("2026-10-25T02:30:00+01:00"));
+    assert_eq!(entry.timezone.as_deref(), Some("Europe/Berlin"));
+
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
@@ -0,0 +1,135 @@
+use std::net::UdpSocket;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use airlift_node::codecs::{CodecInfo, CodecKind, ContainerKind, PCM_I16_SAMPLES};
+use airlift_node::config::ConsumerConfig;
+use airlift_node::consumers::rtmp::split_adts;
+use airlift_node::consumers::udp::{to_adts, UdpOutputConfig, UdpOutputConsumer, UdpPacketizer};
+use airlift_node::core::{AudioRingBuffer, Consumer};
+use airlift_node::PcmFrame;
+use serde_json::json;
+
+fn consumer_config(config: serde_json::Value) -> ConsumerConfig {
+    ConsumerConfig {
+        consumer_type: "udp_out".to_string(),
+        enabled: true,
+        path: None,
+        url: None,
+        config: serde_json::from_value(config).unwrap(),
+    }
+}
+
+#[test]
+fn udp_output_configs_are_validated() -> anyhow::Result<()> {
+    let parsed = UdpOutputConfig::from_config("udp", &consumer_config(json!({ "target": "239.1.1.1:5000" })))?;
+    assert_eq!(parsed.packet_size, 1316);
+    assert_eq!((parsed.codec.as_str(), parsed.adts, parsed.rtp.is_none()), ("pcm", false, true));
+
+    let rtp = UdpOutputConfig::from_config(
+        "udp",
+        &consumer_config(json!({ "target": "udp://127.0.0.1:5004", "packet_size": 200, "rtp": true, "ssrc": 42 })),
+    )?;
+    let options = rtp.rtp.clone().unwrap();
+    assert_eq!((options.payload_type, options.ssrc), (96, Some(42)));
+    assert_eq!(rtp.payload_size(), 188);
+
+    for config in [
+        json!({}),
+        json!({ "target": "no-port" }),
+        json!({ "target": "127.0.0.1:0" }),
+        json!({ "target": "127.0.0.1:5000", "packet_size": 32 }),
+        json!({ "target": "127.0.0.1:5000", "packet_size": 70_000 }),
+        json!({ "target": "127.0.0.1:5000", "codec": "wma" }),
+        json!({ "target": "127.0.0.1:5000", "adts": true }),
+        json!({ "target": "127.0.0.1:5000", "payload_type": 97 }),
+        json!({ "target": "127.0.0.1:5000", "rtp": true, "payload_type": 128 }),
+        json!({ "target": "127.0.0.1:5000", "rtp": "yes" }),
+        json!({ "target": "127.0.0.1:5000", "opus": { "complexity": 11 } }),
+    ] {
+        let result = UdpOutputConfig::from_config("udp", &consumer_config(config.clone()));
+        assert!(result.is_err(), "{}", config);
+    }
+    Ok(())
+}
+
+#[test]
+fn packetizer_splits_payloads_and_writes_rtp_headers() -> anyhow::Result<()> {
+    let raw = UdpOutputConfig::from_config(
+        "udp",
+        &consumer_config(json!({ "target": "127.0.0.1:5000", "packet_size": 100 })),
+    )?;
+    let packets = UdpPacketizer::new(&raw, 1).packetize(&[7; 250], 0);
+    assert_eq!(packets.iter().map(Vec::len).collect::<Vec<_>>(), [100, 100, 50]);
+
+    let rtp = UdpOutputConfig::from_config(
+        "udp",
+        &consumer_config(json!({ "target": "127.0.0.1:5000", "packet_size": 100, "rtp": true, "payload_type": 11 })),
+    )?;
+    let mut packetizer = UdpPacketizer::new(&rtp, 0xDEAD_BEEF);
+    let packets = packetizer.packetize(&[7; 100], 4_800);
+    assert_eq!(packets.iter().map(Vec::len).collect::<Vec<_>>(), [100, 24]);
+    assert_eq!(&packets[0][..2], &[0x80, 11]);
+    assert_eq!(packets[1][1], 0x80 | 11, "marker on the last fragment");
+    assert_eq!(&packets[1][2..4], &[0, 1]);
+    assert_eq!(&packets[1][4..8], &4_800u32.to_be_bytes());
+    assert_eq!(&packets[1][8..12], &0xDEAD_BEEFu32.to_be_bytes());
+    let next = packetizer.packetize(&[7; 10], 9_600);
+    assert_eq!(&next[0][2..4], &[0, 2]);
+    Ok(())
+}
+
+#[test]
+fn raw_aac_frames_get_adts_headers() -> anyhow::Result<()> {
+    let info = CodecInfo {
+        kind: CodecKind::AacLc,
+        sample_rate: 48_000,
+        channels: 2,
+        container: ContainerKind::Raw,
+    };
+    let framed = to_adts(&[1, 2, 3, 4], &info)?;
+    assert_eq!(&framed[..7], &[0xFF, 0xF1, 0x4C, 0x80, 0x01, 0x7F, 0xFC]);
+    assert_eq!(split_adts(&framed), vec![&[1u8, 2, 3, 4][..]]);
+    // Bereits gerahmte Daten bleiben, wie sie sind
+    assert_eq!(to_adts(&framed, &info)?, framed);
+    Ok(())
+}
+
+#[test]
+fn udp_output_sends_pcm_to_the_target() -> anyhow::Result<()> {
+    let receiver = UdpSocket::bind("127.0.0.1:0")?;
+    receiver.set_read_timeout(Some(Duration::from_secs(2)))?;
+    let target = receiver.local_addr()?;
+
+    let buffer = Arc::new(AudioRingBuffer::new(16));
+    let mut consumer = UdpOutputConsumer::new(
+        "udp",
+        &consumer_config(json!({ "target": target.to_string(), "packet_size": 1000 })),
+    )?;
+    consumer.attach_input_buffer(buffer.clone());
+    consumer.start()?;
+    buffer.push(PcmFrame {
+        utc_ns: 0,
+        samples: vec![0x0102; PCM_I16_SAMPLES],
+        sample_rate: 48_000,
+        channels: 2,
+        metadata: Default::default(),
+    });
+
+    let mut received = Vec::new();
+    let mut datagram = [0u8; 2048];
+    let deadline = Instant::now() + Duration::from_secs(2);
+    while received.len() < PCM_I16_SAMPLES * 2 && Instant::now() < deadline {
+        let len = receiver.recv(&mut datagram)?;
+        assert!(len <= 1000);
+        received.extend_from_slice(&datagram[..len]);
+    }
+    consumer.stop()?;
+
+    assert_eq!(received.len(), PCM_I16_SAMPLES * 2);
+    assert_eq!(&received[..2], &0x0102i16.to_le_bytes());
+    let status = consumer.status();
+    assert_eq!((status.frames_processed, status.bytes_written), (1, PCM_I16_SAMPLES as u64 * 2));
+    assert!(!status.running);
+    Ok(())
+}
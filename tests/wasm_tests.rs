@@ -0,0 +1,139 @@
+#![cfg(feature = "wasm")]
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use airlift_node::core::processor::Processor;
+use airlift_node::processors::WasmProcessor;
+use airlift_node::PcmFrame;
+
+/// Gain über `set_param`, Puffer fest ab Offset 1024
+const GAIN: &str = r#"
+(module
+  (import "env" "log" (func $log (param i32 i32)))
+  (memory (export "memory") 2)
+  (data (i32.const 16) "loaded")
+  (global $gain (mut f64) (f64.const 1))
+  (func (export "alloc") (param $bytes i32) (result i32) (i32.const 1024))
+  (func (export "set_param") (param $name i32) (param $len i32) (param $value f64)
+    (global.set $gain (local.get $value)))
+  (func (export "process") (param $ptr i32) (param $samples i32) (param $channels i32)
+                           (param $rate i32) (result i32)
+    (local $i i32) (local $at i32)
+    (block $done
+      (loop $next
+        (br_if $done (i32.ge_u (local.get $i) (local.get $samples)))
+        (local.set $at (i32.add (local.get $ptr) (i32.shl (local.get $i) (i32.const 1))))
+        (i32.store16 (local.get $at)
+          (i32.trunc_sat_f64_s
+            (f64.mul (f64.convert_i32_s (i32.load16_s (local.get $at))) (global.get $gain))))
+        (local.set $i (i32.add (local.get $i) (i32.const 1)))
+        (br $next)))
+    (local.get $samples))
+  (func $start (call $log (i32.const 16) (i32.const 6)))
+  (start $start))
+"#;
+
+/// `mode` 0: Frame verwerfen, 1: Trap, 2: Endlosschleife
+const FAULTY: &str = r#"
+(module
+  (memory (export "memory") 1)
+  (global $mode (mut f64) (f64.const 0))
+  (func (export "alloc") (param i32) (result i32) (i32.const 64))
+  (func (export "set_param") (param i32 i32) (param $value f64)
+    (global.set $mode (local.get $value)))
+  (func (export "process") (param i32 i32 i32 i32) (result i32)
+    (if (f64.eq (global.get $mode) (f64.const 1)) (then unreachable))
+    (if (f64.eq (global.get $mode) (f64.const 2)) (then (loop $forever (br $forever))))
+    (i32.const 0)))
+"#;
+
+fn module(name: &str, source: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("airlift-{}-{}.wat", name, std::process::id()));
+    std::fs::write(&path, source).unwrap();
+    path
+}
+
+fn frame() -> PcmFrame {
+    PcmFrame {
+        utc_ns: 0,
+        samples: vec![1000, -2000, 30000, -30000],
+        sample_rate: 48_000,
+        channels: 2,
+        metadata: Default::default(),
+    }
+}
+
+fn wasm(config: serde_json::Value) -> anyhow::Result<WasmProcessor> {
+    let config: HashMap<String, serde_json::Value> = serde_json::from_value(config)?;
+    WasmProcessor::from_config("dsp", &config)
+}
+
+#[test]
+fn module_processes_frames_in_place() -> anyhow::Result<()> {
+    let path = module("gain", GAIN);
+    let mut processor = wasm(serde_json::json!({
+        "module": path.to_string_lossy(),
+        "params": { "gain": 0.5 },
+    }))?;
+    let mut frames = vec![frame()];
+    processor.process_batch(&mut frames)?;
+    assert_eq!(frames[0].samples, vec![500, -1000, 15000, -15000]);
+
+    // Automation geht direkt an `set_param`
+    processor.set_parameter("gain", 2.0)?;
+    let mut frames = vec![frame()];
+    processor.process_batch(&mut frames)?;
+    assert_eq!(frames[0].samples[..2], [2000, -4000]);
+    assert_eq!(processor.status().metrics.unwrap()["params"]["gain"], 2.0);
+
+    processor.update_config(serde_json::json!({ "enabled": false }))?;
+    let mut frames = vec![frame()];
+    processor.process_batch(&mut frames)?;
+    assert_eq!(frames[0].samples, frame().samples);
+    assert_eq!(processor.errors(), 0);
+    std::fs::remove_file(&path)?;
+    Ok(())
+}
+
+#[test]
+fn faults_pass_frames_through() -> anyhow::Result<()> {
+    let path = module("faulty", FAULTY);
+    let mut processor = wasm(serde_json::json!({
+        "module": path.to_string_lossy(),
+        "fuel": 100_000,
+    }))?;
+    // Rückgabe 0 verwirft den Frame
+    let mut frames = vec![frame()];
+    processor.process_batch(&mut frames)?;
+    assert!(frames.is_empty());
+
+    for (mode, count) in [(1.0, 1), (2.0, 2)] {
+        processor.set_parameter("mode", mode)?;
+        let mut frames = vec![frame()];
+        processor.process_batch(&mut frames)?;
+        assert_eq!(frames[0].samples, frame().samples);
+        assert_eq!(processor.errors(), count);
+    }
+    let metrics = processor.status().metrics.unwrap();
+    assert!(metrics["last_error"]
+        .as_str()
+        .unwrap()
+        .contains("process failed"));
+
+    assert!(wasm(serde_json::json!({})).is_err());
+    assert!(wasm(serde_json::json!({ "module": "/nonexistent.wasm" })).is_err());
+    let broken = module("broken", "(module (func (export \"process\")))");
+    assert!(wasm(serde_json::json!({ "module": broken.to_string_lossy() })).is_err());
+    // Ein kaputtes Update lässt das laufende Modul in Betrieb
+    assert!(processor
+        .update_config(serde_json::json!({ "module": broken.to_string_lossy() }))
+        .is_err());
+    processor.set_parameter("mode", 0.0)?;
+    let mut frames = vec![frame()];
+    processor.process_batch(&mut frames)?;
+    assert!(frames.is_empty());
+    std::fs::remove_file(&path)?;
+    std::fs::remove_file(&broken)?;
+    Ok(())
+}
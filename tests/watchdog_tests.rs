@@ -0,0 +1,195 @@
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::Context;
+
+use airlift_node::core::{
+    classify, AirliftNode, AudioError, AudioRingBuffer, BufferSizing, ErrorCategory, ErrorInfo,
+    LastError, Producer, ProducerStatus, Watchdog, WatchdogAction, WatchdogSettings,
+};
+use airlift_node::{Samples, Slots};
+
+#[test]
+fn errors_are_classified_through_context_chains() {
+    let network = anyhow::Error::from(AudioError::network("connection refused"))
+        .context("producer 'remote'");
+    assert_eq!(classify(&network), (ErrorCategory::Network, true));
+
+    let io = Err::<(), _>(std::io::Error::from(std::io::ErrorKind::ConnectionReset))
+        .context("receive")
+        .unwrap_err();
+    assert_eq!(classify(&io), (ErrorCategory::Network, true));
+
+    let plain = anyhow::anyhow!("something odd");
+    assert_eq!(classify(&plain), (ErrorCategory::Internal, false));
+
+    let wrapped = AudioError::with_context("producer 'fifo'", AudioError::device("no such FIFO"));
+    assert_eq!(wrapped.category(), ErrorCategory::Device);
+    assert!(wrapped.is_retryable());
+    assert!(!AudioError::network("tls handshake").with_retryable(false).is_retryable());
+    assert!(!AudioError::config("missing address").is_retryable());
+    assert_eq!(
+        AudioError::ProducerNotFound { name: "x".into() }.category(),
+        ErrorCategory::Config
+    );
+
+    let info = ErrorInfo::from_audio(&AudioError::codec("bad ADTS header"));
+    let json = serde_json::to_value(&info).unwrap();
+    assert_eq!(json["category"], "codec");
+    assert_eq!(json["retryable"], false);
+}
+
+fn settings() -> WatchdogSettings {
+    WatchdogSettings {
+        initial_backoff: Duration::from_millis(100),
+        max_backoff: Duration::from_millis(400),
+        stable_after: Duration::from_secs(1),
+    }
+}
+
+fn network_error() -> ErrorInfo {
+    ErrorInfo::from_audio(&AudioError::network("connection refused"))
+}
+
+#[test]
+fn backoff_doubles_up_to_the_limit() {
+    let mut watchdog = Watchdog::new(settings());
+    let now = Instant::now();
+
+    let mut delays = Vec::new();
+    for _ in 0..4 {
+        match watchdog.report_failure("remote", network_error(), now) {
+            WatchdogAction::RetryIn(delay) => delays.push(delay.as_millis()),
+            WatchdogAction::GiveUp => panic!("network errors are retried"),
+        }
+        assert!(watchdog.take_due(now).is_empty());
+        assert_eq!(watchdog.take_due(now + Duration::from_secs(1)), ["remote"]);
+    }
+    assert_eq!(delays, [100, 200, 400, 400]);
+    assert_eq!(watchdog.status(now)[0].attempts, 4);
+
+    // Nach stabilem Lauf ausgetragen
+    watchdog.restarted("remote", now);
+    assert!(watchdog.take_recovered(now + Duration::from_millis(500)).is_empty());
+    assert_eq!(watchdog.take_recovered(now + Duration::from_secs(1)), [("remote".to_string(), 4)]);
+    assert!(watchdog.status(now).is_empty());
+}
+
+#[test]
+fn non_retryable_and_held_producers_are_not_restarted() {
+    let mut watchdog = Watchdog::new(settings());
+    let now = Instant::now();
+    let config = ErrorInfo::from_audio(&AudioError::config("invalid address"));
+    assert_eq!(watchdog.report_failure("bad", config, now), WatchdogAction::GiveUp);
+
+    watchdog.hold("stopped");
+    assert_eq!(watchdog.report_failure("stopped", network_error(), now), WatchdogAction::GiveUp);
+    assert!(watchdog.take_due(now + Duration::from_secs(60)).is_empty());
+    assert!(watchdog.is_failed("bad"));
+    assert!(watchdog.status(now).iter().all(|entry| entry.retry_in_ms.is_none()));
+}
+
+/// Schlägt die ersten `failures` Starts mit einem Netzwerkfehler fehl.
+struct FlakyProducer {
+    name: String,
+    failures: usize,
+    starts: Arc<AtomicUsize>,
+    running: Arc<AtomicBool>,
+    last_error: Arc<LastError>,
+}
+
+impl Producer for FlakyProducer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn start(&mut self) -> anyhow::Result<()> {
+        let attempt = self.starts.fetch_add(1, Ordering::SeqCst);
+        if attempt < self.failures {
+            return Err(AudioError::network(format!("connect failed ({})", attempt)).into());
+        }
+        self.last_error.clear();
+        self.running.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn stop(&mut self) -> anyhow::Result<()> {
+        self.running.store(false, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn status(&self) -> ProducerStatus {
+        ProducerStatus {
+            running: self.running.load(Ordering::SeqCst),
+            paused: false,
+            connected: true,
+            samples_processed: 0,
+            errors: 0,
+            buffer_stats: None,
+            decoder: None,
+        }
+    }
+
+    fn attach_ring_buffer(&mut self, _buffer: Arc<AudioRingBuffer>) {}
+
+    fn last_error(&self) -> Option<ErrorInfo> {
+        self.last_error.get()
+    }
+}
+
+#[test]
+fn node_restarts_failed_producers_with_backoff() -> anyhow::Result<()> {
+    let starts = Arc::new(AtomicUsize::new(0));
+    let running = Arc::new(AtomicBool::new(false));
+    let last_error = Arc::new(LastError::default());
+    let mut node = AirliftNode::new();
+    node.set_watchdog_settings(settings());
+    node.add_producer_with_buffer(
+        Box::new(FlakyProducer {
+            name: "remote".to_string(),
+            failures: 2,
+            starts: starts.clone(),
+            running: running.clone(),
+            last_error: last_error.clone(),
+        }),
+        BufferSizing { slots: Slots(10), prealloc_samples: Samples(0) },
+    )?;
+
+    node.start()?;
+    let status = node.watchdog_status();
+    assert_eq!(status.len(), 1);
+    assert_eq!(status[0].error.category, ErrorCategory::Network);
+    assert!(status[0].retry_in_ms.is_some());
+    assert_eq!(node.producer_error("remote").map(|error| error.retryable), Some(true));
+
+    let base = Instant::now();
+    node.run_watchdog_at(base + Duration::from_secs(1));
+    assert_eq!(starts.load(Ordering::SeqCst), 2, "second start fails again");
+    node.run_watchdog_at(base + Duration::from_secs(2));
+    assert_eq!(starts.load(Ordering::SeqCst), 3);
+    assert!(running.load(Ordering::SeqCst));
+    assert!(node.watchdog_status()[0].restarted);
+
+    node.run_watchdog_at(base + Duration::from_secs(4));
+    assert!(node.watchdog_status().is_empty(), "recovered after stable run");
+    assert!(node.producer_error("remote").is_none());
+
+    // Ausfall zur Laufzeit: steht mit Fehler -> Neustart
+    running.store(false, Ordering::SeqCst);
+    last_error.record_audio(&AudioError::network("peer reset"));
+    node.run_watchdog_at(base + Duration::from_secs(5));
+    assert_eq!(node.watchdog_status().len(), 1);
+    node.run_watchdog_at(base + Duration::from_secs(6));
+    assert_eq!(starts.load(Ordering::SeqCst), 4);
+    assert!(running.load(Ordering::SeqCst));
+
+    // Bewusst gestoppt: kein Neustart
+    node.stop_producer_by_name("remote")?;
+    last_error.record_audio(&AudioError::network("peer reset"));
+    node.run_watchdog_at(base + Duration::from_secs(60));
+    assert_eq!(starts.load(Ordering::SeqCst), 4);
+
+    node.stop()?;
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use airlift_node::core::{
+    AudioRingBuffer, Event, EventBus, EventEmitter, EventHandler, EventType, WatermarkConfig,
+    WatermarkMonitor,
+};
+use airlift_node::PcmFrame;
+
+struct Collector {
+    events: Mutex<Vec<Event>>,
+}
+
+impl EventHandler for Collector {
+    fn handle_event(&self, event: &Event) -> anyhow::Result<()> {
+        self.events.lock().unwrap().push(event.clone());
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "collector"
+    }
+
+    fn event_type_filter(&self) -> Option<Vec<EventType>> {
+        Some(vec![EventType::BufferWatermark])
+    }
+}
+
+fn frame(utc_ns: u64) -> PcmFrame {
+    PcmFrame {
+        utc_ns,
+        samples: vec![0; 4],
+        sample_rate: 48_000,
+        channels: 2,
+        metadata: Default::default(),
+    }
+}
+
+fn wait_for(collector: &Collector, count: usize) {
+    let deadline = Instant::now() + Duration::from_secs(1);
+    while Instant::now() < deadline && collector.events.lock().unwrap().len() < count {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
+
+#[test]
+fn slow_reader_triggers_high_and_low_watermark() -> anyhow::Result<()> {
+    let mut bus = EventBus::new("test");
+    let collector = Arc::new(Collector {
+        events: Mutex::new(Vec::new()),
+    });
+    bus.register_handler(collector.clone())?;
+    bus.start()?;
+    let bus = Arc::new(Mutex::new(bus));
+
+    let config = WatermarkConfig {
+        hold: Duration::ZERO,
+        ..WatermarkConfig::default()
+    };
+    let buffer = AudioRingBuffer::new(10);
+    buffer.set_watermark(Some(Arc::new(
+        WatermarkMonitor::new("producer:test", config)
+            .with_emitter(EventEmitter::new(bus.clone(), "buffer", "producer:test")),
+    )));
+
+    buffer.push(frame(1));
+    assert!(buffer.pop_for_reader("consumer:slow").is_some());
+    for utc in 2..=10 {
+        buffer.push(frame(utc));
+    }
+    wait_for(&collector, 1);
+
+    for _ in 0..6 {
+        buffer.pop_for_reader("consumer:slow");
+    }
+    buffer.push(frame(11));
+    wait_for(&collector, 2);
+    bus.lock().unwrap().stop()?;
+
+    let events = collector.events.lock().unwrap();
+    let levels: Vec<&str> = events
+        .iter()
+        .map(|e| e.payload["level"].as_str().unwrap_or_default())
+        .collect();
+    assert_eq!(levels, vec!["high", "low"]);
+    assert_eq!(events[0].payload["buffer"], "producer:test");
+    Ok(())
+}
+
+#[test]
+fn watermark_config_parses_units() -> anyhow::Result<()> {
+    let value = serde_json::json!({ "high": 0.9, "low": 0.4, "hold": "2s" });
+    let config = WatermarkConfig::from_config("flow", "main", &value)?;
+    assert_eq!(config.hold, Duration::from_secs(2));
+    assert!((config.high - 0.9).abs() < f32::EPSILON);
+
+    let invalid = serde_json::json!({ "high": 0.5, "low": 0.7 });
+    assert!(WatermarkConfig::from_config("flow", "main", &invalid).is_err());
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use std::fs;
+
+use airlift_node::audio::archive::{archive_registry, record_file_at, recording_id};
+use airlift_node::audio::waveform::{generate_tiles, load_tile, tile_path, Waveform, MAX_ZOOM};
+
+#[test]
+fn tiles_cover_all_zoom_levels_and_round_trip() {
+    let dir = std::env::temp_dir().join(format!("airlift-waveform-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("show.wav");
+
+    // Stereo, 1024 Frames: erste Hälfte leise, zweite laut
+    let spec = hound::WavSpec {
+        channels: 2,
+        sample_rate: 48_000,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut writer = hound::WavWriter::create(&path, spec).unwrap();
+    for frame in 0..1024 {
+        let level: i16 = if frame < 512 { 256 } else { 16_384 };
+        writer.write_sample(level).unwrap();
+        writer.write_sample(-level).unwrap();
+    }
+    writer.finalize().unwrap();
+
+    let base = generate_tiles(&path).unwrap();
+    assert_eq!(base.samples_per_pixel, 256);
+    assert_eq!(base.data, vec![(-1, 1), (-1, 1), (-64, 64), (-64, 64)]);
+    assert!(tile_path(&path, MAX_ZOOM).exists());
+
+    let zoom1 = load_tile(&path, 1).unwrap();
+    assert_eq!(zoom1.samples_per_pixel, 512);
+    assert_eq!(zoom1.data, vec![(-1, 1), (-64, 64)]);
+    assert_eq!(load_tile(&path, MAX_ZOOM).unwrap().data, vec![(-64, 64)]);
+    assert!(load_tile(&path, MAX_ZOOM + 1).is_err());
+
+    let dat = zoom1.to_dat();
+    assert_eq!(dat.len(), 20 + 4);
+    assert_eq!(Waveform::from_dat(&dat).unwrap(), zoom1);
+    assert_eq!(zoom1.to_json()["data"], serde_json::json!([-1, 1, -64, 64]));
+
+    let entry = record_file_at(&path, 21, 1_700_000_000_000).unwrap();
+    let found = archive_registry().find_recording(&recording_id(&entry)).unwrap();
+    assert!(found.path.ends_with("show.wav"));
+
+    let _ = fs::remove_dir_all(&dir);
+}
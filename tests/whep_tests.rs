@@ -0,0 +1,55 @@
+#![cfg(feature = "whep")]
+
+use std::time::Duration;
+
+use airlift_node::config::ConsumerConfig;
+use airlift_node::consumers::whep::{opus_packet_duration, WhepConfig};
+use serde_json::json;
+
+fn consumer_config(config: serde_json::Value) -> ConsumerConfig {
+    ConsumerConfig {
+        consumer_type: "whep".to_string(),
+        enabled: true,
+        path: None,
+        url: None,
+        config: serde_json::from_value(config).unwrap(),
+    }
+}
+
+#[test]
+fn opus_packet_duration_follows_toc_byte() {
+    let ms = |value: u64| Some(Duration::from_millis(value));
+    // CELT FB 20 ms, Hybrid FB 20 ms, SILK NB 20 ms × 2
+    assert_eq!(opus_packet_duration(&[0xFC, 0x01]), ms(20));
+    assert_eq!(opus_packet_duration(&[0x78]), ms(20));
+    assert_eq!(opus_packet_duration(&[0x09, 0x00, 0x00]), ms(40));
+    assert_eq!(
+        opus_packet_duration(&[0xE0]),
+        Some(Duration::from_micros(2500))
+    );
+    // Code 3: Frame-Anzahl im zweiten Byte
+    assert_eq!(opus_packet_duration(&[0x1B, 0x02]), ms(120));
+    assert_eq!(opus_packet_duration(&[0xE3, 48]), ms(120));
+
+    // Über 120 ms, fehlende oder leere Frame-Anzahl, leeres Paket
+    for packet in [&[0x1B, 0x03][..], &[0xE3], &[0xE3, 0x00], &[]] {
+        assert_eq!(opus_packet_duration(packet), None, "{:?}", packet);
+    }
+}
+
+#[test]
+fn whep_config_rejects_invalid_settings() {
+    for config in [
+        json!({ "codec": "pcm" }),
+        json!({ "max_sessions": 0 }),
+        json!({ "max_sessions": 500 }),
+        json!({ "ice_servers": "stun:stun.example.org" }),
+        json!({ "ice_servers": [3478] }),
+    ] {
+        assert!(
+            WhepConfig::from_config("web", &consumer_config(config.clone())).is_err(),
+            "{}",
+            config
+        );
+    }
+}
@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+use std::io::{ErrorKind, Read, Write};
+use std::net::TcpStream;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use airlift_node::codecs::PCM_I16_SAMPLES;
+use airlift_node::config::ConsumerConfig;
+use airlift_node::consumers::zmq::{
+    greeting, parse_endpoint, parse_frame, ready, write_frame, ZmqPubConfig, ZmqPubConsumer,
+};
+use airlift_node::core::{AudioRingBuffer, Consumer};
+use airlift_node::PcmFrame;
+
+fn config(values: serde_json::Value) -> ConsumerConfig {
+    ConsumerConfig {
+        consumer_type: "zmq_pub".to_string(),
+        enabled: true,
+        path: None,
+        url: None,
+        config: serde_json::from_value::<HashMap<String, serde_json::Value>>(values).unwrap(),
+    }
+}
+
+/// Minimaler SUB-Socket: Greeting, READY, Abo als 3.0-Nachricht.
+fn subscribe(addr: std::net::SocketAddr, prefix: &[u8]) -> anyhow::Result<TcpStream> {
+    let mut stream = TcpStream::connect(addr)?;
+    stream.set_read_timeout(Some(Duration::from_secs(2)))?;
+    stream.write_all(&greeting(false))?;
+    let mut peer = [0u8; 64];
+    stream.read_exact(&mut peer)?;
+    assert_eq!((peer[0], peer[9], peer[10]), (0xff, 0x7f, 3));
+    assert_eq!(&peer[12..16], b"NULL");
+    stream.write_all(&ready("SUB"))?;
+
+    let mut pending = Vec::new();
+    let (flags, body) = read_frame(&mut stream, &mut pending)?;
+    assert_eq!(flags, 0x04, "READY is a command");
+    assert!(body.starts_with(b"\x05READY"));
+    assert!(body.windows(3).any(|w| w == b"PUB"));
+    assert!(pending.is_empty());
+
+    let mut subscription = vec![1u8];
+    subscription.extend_from_slice(prefix);
+    let mut out = Vec::new();
+    write_frame(&mut out, 0, &subscription);
+    stream.write_all(&out)?;
+    Ok(stream)
+}
+
+fn read_frame(stream: &mut TcpStream, pending: &mut Vec<u8>) -> anyhow::Result<(u8, Vec<u8>)> {
+    let mut chunk = [0u8; 8192];
+    loop {
+        if let Some((flags, body, len)) = parse_frame(pending, usize::MAX)? {
+            pending.drain(..len);
+            return Ok((flags, body));
+        }
+        let read = stream.read(&mut chunk)?;
+        anyhow::ensure!(read > 0, "connection closed");
+        pending.extend_from_slice(&chunk[..read]);
+    }
+}
+
+fn frame(utc_ns: u64) -> PcmFrame {
+    PcmFrame {
+        utc_ns,
+        samples: vec![7; PCM_I16_SAMPLES],
+        sample_rate: 48_000,
+        channels: 2,
+        metadata: Default::default(),
+    }
+}
+
+#[test]
+fn config_parses_endpoint_topic_and_limits() {
+    let parsed = ZmqPubConfig::from_config(
+        "out",
+        "studio",
+        &config(serde_json::json!({ "bind": "tcp://*:5556", "hwm": 10 })),
+    )
+    .unwrap();
+    assert_eq!(parsed.bind, "0.0.0.0:5556".parse().unwrap());
+    assert_eq!(parsed.topic, "studio");
+    assert_eq!(parsed.codec, "pcm");
+    assert_eq!(parsed.hwm, 10);
+
+    assert!(parse_endpoint("udp://127.0.0.1:5556").is_err());
+    assert!(parse_endpoint("tcp://127.0.0.1").is_err());
+    for bad in [
+        serde_json::json!({}),
+        serde_json::json!({ "bind": "tcp://*:5556", "hwm": 0 }),
+        serde_json::json!({ "bind": "tcp://*:5556", "codec": "nope" }),
+        serde_json::json!({ "bind": "tcp://*:5556", "topic": "x".repeat(300) }),
+    ] {
+        assert!(ZmqPubConfig::from_config("out", "studio", &config(bad)).is_err());
+    }
+}
+
+#[test]
+fn subscribers_receive_matching_topics_only() -> anyhow::Result<()> {
+    let buffer = Arc::new(AudioRingBuffer::new(64));
+    let mut consumer = ZmqPubConsumer::new(
+        "out",
+        "studio",
+        &config(serde_json::json!({ "bind": "tcp://127.0.0.1:0", "topic": "studio1" })),
+    )?;
+    consumer.attach_input_buffer(buffer.clone());
+    consumer.start()?;
+    let addr = consumer.local_addr().unwrap();
+
+    let mut matching = subscribe(addr, b"studio")?;
+    let mut other = subscribe(addr, b"news")?;
+    let deadline = Instant::now() + Duration::from_secs(2);
+    while consumer.subscribers() < 2 && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    assert_eq!(consumer.subscribers(), 2);
+    assert!(consumer.status().connected);
+
+    // Abos laufen asynchron ein: so lange senden, bis die erste Nachricht da ist
+    let pusher_buffer = buffer.clone();
+    let pusher = std::thread::spawn(move || {
+        for index in 1..=40 {
+            pusher_buffer.push(frame(index * 100_000_000));
+            std::thread::sleep(Duration::from_millis(25));
+        }
+    });
+    let mut pending = Vec::new();
+    let (flags, topic) = read_frame(&mut matching, &mut pending)?;
+    assert_eq!((flags, topic.as_slice()), (0x01, &b"studio1"[..]));
+    let (flags, header) = read_frame(&mut matching, &mut pending)?;
+    assert_eq!(flags, 0x01);
+    let header: serde_json::Value = serde_json::from_slice(&header)?;
+    assert_eq!(header["codec"], "pcm");
+    assert_eq!(header["sample_rate"], 48_000);
+    assert_eq!(header["utc_ns"].as_u64().unwrap() % 100_000_000, 0);
+    let (flags, payload) = read_frame(&mut matching, &mut pending)?;
+    assert_eq!(flags & 0x01, 0, "last part");
+    assert_eq!(payload.len(), PCM_I16_SAMPLES * 2);
+    assert_eq!(&payload[..2], &7i16.to_le_bytes());
+    pusher.join().unwrap();
+
+    other.set_nonblocking(true)?;
+    let mut probe = [0u8; 16];
+    assert!(matches!(other.read(&mut probe), Err(e) if e.kind() == ErrorKind::WouldBlock));
+
+    consumer.stop()?;
+    assert!(!consumer.status().running);
+    Ok(())
+}